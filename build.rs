@@ -29,6 +29,8 @@ fn main() -> anyhow::Result<()> {
             test_directory_module(out, "tests/misc_testsuite/simd", strategy)?;
             test_directory_module(out, "tests/misc_testsuite/threads", strategy)?;
             test_directory_module(out, "tests/misc_testsuite/memory64", strategy)?;
+            test_directory_module(out, "tests/misc_testsuite/tail-call", strategy)?;
+            test_directory_module(out, "tests/misc_testsuite/exception-handling", strategy)?;
             Ok(())
         })?;
 
@@ -175,6 +177,11 @@ fn ignore(testsuite: &str, testname: &str, strategy: &str) -> bool {
             ("memory64", "simd") if platform_is_s390x() => return true,
             // No full atomics support yet for s390x.
             ("memory64", "threads") if platform_is_s390x() => return true,
+            // No simd support yet for riscv64.
+            ("simd", _) if platform_is_riscv64() => return true,
+            ("memory64", "simd") if platform_is_riscv64() => return true,
+            // Tail calls are only implemented for x86_64 System V targets.
+            ("tail_call", _) if !platform_supports_tail_calls() => return true,
             _ => {}
         },
         _ => panic!("unrecognized strategy"),
@@ -186,3 +193,12 @@ fn ignore(testsuite: &str, testname: &str, strategy: &str) -> bool {
 fn platform_is_s390x() -> bool {
     env::var("CARGO_CFG_TARGET_ARCH").unwrap() == "s390x"
 }
+
+fn platform_is_riscv64() -> bool {
+    env::var("CARGO_CFG_TARGET_ARCH").unwrap() == "riscv64"
+}
+
+fn platform_supports_tail_calls() -> bool {
+    env::var("CARGO_CFG_TARGET_ARCH").unwrap() == "x86_64"
+        && env::var("CARGO_CFG_TARGET_OS").unwrap() != "windows"
+}
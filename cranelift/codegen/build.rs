@@ -216,10 +216,17 @@ fn get_isle_compilations(crate_dir: &std::path::Path) -> Result<IsleCompilations
         make_isle_source_path_relative(&cur_dir, crate_dir.join("src").join("clif.isle"));
     let prelude_isle =
         make_isle_source_path_relative(&cur_dir, crate_dir.join("src").join("prelude.isle"));
+    let clif_opt_isle =
+        make_isle_source_path_relative(&cur_dir, crate_dir.join("src").join("clif_opt.isle"));
+    let prelude_opt_isle =
+        make_isle_source_path_relative(&cur_dir, crate_dir.join("src").join("prelude_opt.isle"));
+    let src_opts = make_isle_source_path_relative(&cur_dir, crate_dir.join("src").join("opts"));
     let src_isa_x64 =
         make_isle_source_path_relative(&cur_dir, crate_dir.join("src").join("isa").join("x64"));
     let src_isa_aarch64 =
         make_isle_source_path_relative(&cur_dir, crate_dir.join("src").join("isa").join("aarch64"));
+    let src_isa_riscv64 =
+        make_isle_source_path_relative(&cur_dir, crate_dir.join("src").join("isa").join("riscv64"));
 
     // This is a set of ISLE compilation units.
     //
@@ -228,10 +235,19 @@ fn get_isle_compilations(crate_dir: &std::path::Path) -> Result<IsleCompilations
     //     (output Rust code file, input ISLE source files)
     //
     // There should be one entry for each backend that uses ISLE for lowering,
-    // and if/when we replace our peephole optimization passes with ISLE, there
-    // should be an entry for each of those as well.
+    // plus one for the mid-end optimizer's rewrite rules.
     Ok(IsleCompilations {
         items: vec![
+            // The mid-end optimizer's rewrite rules.
+            IsleCompilation {
+                output: src_opts.join("generated_code.rs"),
+                inputs: vec![
+                    clif_opt_isle,
+                    prelude_opt_isle,
+                    src_opts.join("algebraic.isle"),
+                    src_opts.join("cprop.isle"),
+                ],
+            },
             // The x86-64 instruction selector.
             IsleCompilation {
                 output: src_isa_x64
@@ -258,6 +274,19 @@ fn get_isle_compilations(crate_dir: &std::path::Path) -> Result<IsleCompilations
                     src_isa_aarch64.join("lower.isle"),
                 ],
             },
+            // The RISC-V instruction selector.
+            IsleCompilation {
+                output: src_isa_riscv64
+                    .join("lower")
+                    .join("isle")
+                    .join("generated_code.rs"),
+                inputs: vec![
+                    clif_isle.clone(),
+                    prelude_isle.clone(),
+                    src_isa_riscv64.join("inst.isle"),
+                    src_isa_riscv64.join("lower.isle"),
+                ],
+            },
         ],
     })
 }
@@ -397,10 +426,10 @@ fn rebuild_isle(
     let manifest_filename = compilation.manifest_filename();
     let _ = std::fs::remove_file(&manifest_filename);
 
-    let code = (|| {
+    let (code, warnings) = (|| {
         let lexer = isle::lexer::Lexer::from_files(&compilation.inputs[..])?;
         let defs = isle::parser::parse(lexer)?;
-        isle::compile::compile(&defs)
+        isle::compile::compile_with_warnings(&defs)
     })()
     .map_err(|e| {
         // Make sure to include the source snippets location info along with
@@ -426,6 +455,12 @@ fn rebuild_isle(
         impl std::error::Error for DebugReport {}
     })?;
 
+    // Warnings name the offending term and its location in their message, so
+    // each one fits on a single `cargo:warning` line.
+    for warning in &warnings {
+        println!("cargo:warning={}", warning);
+    }
+
     let code = rustfmt(&code).unwrap_or_else(|e| {
         println!(
             "cargo:warning=Failed to run `rustfmt` on ISLE-generated code: {:?}",
@@ -1085,22 +1085,49 @@ fn gen_inst_builder(inst: &Instruction, format: &InstructionFormat, fmt: &mut Fo
     fmtln!(fmt, "}")
 }
 
+/// Which ISLE compilation a generated ISLE file is for.
 #[cfg(feature = "rebuild-isle")]
-fn gen_isle(formats: &[&InstructionFormat], instructions: &AllInstructions, fmt: &mut Formatter) {
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum IsleTarget {
+    /// Instruction lowering in the backends, which matches on `Inst`s.
+    Lower,
+    /// Rewrites in the mid-end optimizer, which match on and build `Value`s.
+    Opt,
+}
+
+#[cfg(feature = "rebuild-isle")]
+fn gen_isle(
+    formats: &[&InstructionFormat],
+    instructions: &AllInstructions,
+    fmt: &mut Formatter,
+    isle_target: IsleTarget,
+) {
     use std::collections::{BTreeMap, BTreeSet};
     use std::fmt::Write;
 
     use crate::cdsl::formats::FormatField;
 
-    fmt.multi_line(
-        r#"
+    match isle_target {
+        IsleTarget::Lower => fmt.multi_line(
+            r#"
 ;; GENERATED BY `gen_isle`. DO NOT EDIT!!!
 ;;
 ;; This ISLE file defines all the external type declarations for Cranelift's
 ;; data structures that ISLE will process, such as `InstructionData` and
 ;; `Opcode`.
         "#,
-    );
+        ),
+        IsleTarget::Opt => fmt.multi_line(
+            r#"
+;; GENERATED BY `gen_isle`. DO NOT EDIT!!!
+;;
+;; This ISLE file defines all the external type declarations for Cranelift's
+;; data structures that ISLE will process, such as `InstructionData` and
+;; `Opcode`, along with value-level extractors and constructors for the
+;; mid-end optimizer's rewrite rules.
+        "#,
+        ),
+    }
     fmt.empty_line();
 
     // Collect and deduplicate the immediate types from the instruction fields.
@@ -1227,10 +1254,110 @@ fn gen_isle(formats: &[&InstructionFormat], instructions: &AllInstructions, fmt:
     fmt.line(")");
     fmt.empty_line();
 
+    match isle_target {
+        IsleTarget::Lower => gen_isle_lower_extractors(instructions, fmt),
+        IsleTarget::Opt => gen_isle_opt_terms(instructions, fmt),
+    }
+}
+
+/// Generate the ISLE pattern for an instruction's `InstructionData`, binding its
+/// operands to variables with the operands' names.
+#[cfg(feature = "rebuild-isle")]
+fn isle_inst_data_pattern(inst: &crate::cdsl::instructions::Instruction) -> String {
+    use std::fmt::Write;
+
+    let mut s = format!(
+        "(InstructionData.{} (Opcode.{})",
+        inst.format.name, inst.camel_name
+    );
+
+    // Value and varargs operands.
+    if inst.format.typevar_operand.is_some() {
+        if inst.format.has_value_list {
+            // The instruction format uses a value list, but the
+            // instruction itself might have not only a `&[Value]`
+            // varargs operand, but also one or more `Value` operands as
+            // well. If this is the case, then we need to read them off
+            // the front of the `ValueList`.
+            let values: Vec<_> = inst
+                .operands_in
+                .iter()
+                .filter(|o| o.is_value())
+                .map(|o| o.name)
+                .collect();
+            let varargs = inst
+                .operands_in
+                .iter()
+                .find(|o| o.is_varargs())
+                .unwrap()
+                .name;
+            if values.is_empty() {
+                write!(&mut s, " (value_list_slice {})", varargs).unwrap();
+            } else {
+                write!(
+                    &mut s,
+                    " (unwrap_head_value_list_{} {} {})",
+                    values.len(),
+                    values.join(" "),
+                    varargs
+                )
+                .unwrap();
+            }
+        } else if inst.format.num_value_operands == 1 {
+            write!(
+                &mut s,
+                " {}",
+                inst.operands_in.iter().find(|o| o.is_value()).unwrap().name
+            )
+            .unwrap();
+        } else {
+            let values = inst
+                .operands_in
+                .iter()
+                .filter(|o| o.is_value())
+                .map(|o| o.name)
+                .collect::<Vec<_>>();
+            assert_eq!(values.len(), inst.format.num_value_operands);
+            let values = values.join(" ");
+            write!(
+                &mut s,
+                " (value_array_{} {})",
+                inst.format.num_value_operands, values,
+            )
+            .unwrap();
+        }
+    }
+
+    // Immediates.
+    let imm_operands: Vec<_> = inst
+        .operands_in
+        .iter()
+        .filter(|o| !o.is_value() && !o.is_varargs())
+        .collect();
+    assert_eq!(imm_operands.len(), inst.format.imm_fields.len());
+    for op in imm_operands {
+        write!(&mut s, " {}", op.name).unwrap();
+    }
+
+    s.push(')');
+    s
+}
+
+/// The ISLE type of an instruction's input operand.
+#[cfg(feature = "rebuild-isle")]
+fn isle_operand_type(operand: &crate::cdsl::operands::Operand) -> &'static str {
+    let ty = operand.kind.rust_type;
+    if ty == "&[Value]" {
+        "ValueSlice"
+    } else {
+        ty.rsplit("::").next().unwrap()
+    }
+}
+
+/// Generate the `Inst` extractors used by the backends' lowering rules.
+#[cfg(feature = "rebuild-isle")]
+fn gen_isle_lower_extractors(instructions: &AllInstructions, fmt: &mut Formatter) {
     // Generate the helper extractors for each opcode's full instruction.
-    //
-    // TODO: if/when we port our peephole optimization passes to ISLE we will
-    // want helper constructors as well.
     fmt.line(";;;; Extracting Opcode, Operands, and Immediates from `InstructionData` ;;;;;;;;");
     fmt.empty_line();
     for inst in instructions {
@@ -1240,14 +1367,7 @@ fn gen_isle(formats: &[&InstructionFormat], instructions: &AllInstructions, fmt:
             inst.name,
             inst.operands_in
                 .iter()
-                .map(|o| {
-                    let ty = o.kind.rust_type;
-                    if ty == "&[Value]" {
-                        "ValueSlice"
-                    } else {
-                        ty.rsplit("::").next().unwrap()
-                    }
-                })
+                .map(isle_operand_type)
                 .collect::<Vec<_>>()
                 .join(" ")
         );
@@ -1263,83 +1383,77 @@ fn gen_isle(formats: &[&InstructionFormat], instructions: &AllInstructions, fmt:
                     .collect::<Vec<_>>()
                     .join(" ")
             );
-            let mut s = format!(
-                "(inst_data (InstructionData.{} (Opcode.{})",
-                inst.format.name, inst.camel_name
-            );
-
-            // Value and varargs operands.
-            if inst.format.typevar_operand.is_some() {
-                if inst.format.has_value_list {
-                    // The instruction format uses a value list, but the
-                    // instruction itself might have not only a `&[Value]`
-                    // varargs operand, but also one or more `Value` operands as
-                    // well. If this is the case, then we need to read them off
-                    // the front of the `ValueList`.
-                    let values: Vec<_> = inst
-                        .operands_in
-                        .iter()
-                        .filter(|o| o.is_value())
-                        .map(|o| o.name)
-                        .collect();
-                    let varargs = inst
-                        .operands_in
-                        .iter()
-                        .find(|o| o.is_varargs())
-                        .unwrap()
-                        .name;
-                    if values.is_empty() {
-                        write!(&mut s, " (value_list_slice {})", varargs).unwrap();
-                    } else {
-                        write!(
-                            &mut s,
-                            " (unwrap_head_value_list_{} {} {})",
-                            values.len(),
-                            values.join(" "),
-                            varargs
-                        )
-                        .unwrap();
-                    }
-                } else if inst.format.num_value_operands == 1 {
-                    write!(
-                        &mut s,
-                        " {}",
-                        inst.operands_in.iter().find(|o| o.is_value()).unwrap().name
-                    )
-                    .unwrap();
-                } else {
-                    let values = inst
-                        .operands_in
-                        .iter()
-                        .filter(|o| o.is_value())
-                        .map(|o| o.name)
-                        .collect::<Vec<_>>();
-                    assert_eq!(values.len(), inst.format.num_value_operands);
-                    let values = values.join(" ");
-                    write!(
-                        &mut s,
-                        " (value_array_{} {})",
-                        inst.format.num_value_operands, values,
-                    )
-                    .unwrap();
-                }
-            }
+            fmtln!(fmt, "(inst_data {})", isle_inst_data_pattern(inst));
+        });
+        fmt.line(")");
+        fmt.empty_line();
+    }
+}
 
-            // Immediates.
-            let imm_operands: Vec<_> = inst
-                .operands_in
-                .iter()
-                .filter(|o| !o.is_value() && !o.is_varargs())
-                .collect();
-            assert_eq!(imm_operands.len(), inst.format.imm_fields.len());
-            for op in imm_operands {
-                write!(&mut s, " {}", op.name).unwrap();
-            }
+/// Generate the `Value` extractors and constructors used by the mid-end's
+/// rewrite rules.
+///
+/// These are only generated for instructions which produce a single value and
+/// are free of side effects, since those are the only instructions the mid-end
+/// is allowed to rewrite. The first argument of every term is the type of that
+/// value.
+///
+/// Instructions whose only side effect is that they may trap, such as integer
+/// division, get an extractor but no constructor: the mid-end keeps them as
+/// pure nodes when their operands make trapping impossible, so rules may match
+/// on them, but rules must never create new ones.
+#[cfg(feature = "rebuild-isle")]
+fn gen_isle_opt_terms(instructions: &AllInstructions, fmt: &mut Formatter) {
+    fmt.line(";;;; Extracting and Constructing Pure Instructions as `Value`s ;;;;;;;;;;;;;;;;;");
+    fmt.empty_line();
+    for inst in instructions {
+        let has_side_effects = inst.is_terminator
+            || inst.is_branch
+            || inst.is_call
+            || inst.is_return
+            || inst.can_load
+            || inst.can_store
+            || inst.other_side_effects
+            || inst.writes_cpu_flags;
+        if has_side_effects
+            || inst.operands_out.len() != 1
+            || inst.value_results.len() != 1
+            || inst.format.has_value_list
+        {
+            continue;
+        }
+        assert!(inst.operands_in.iter().all(|o| o.name != "ty"));
 
-            s.push_str("))");
-            fmt.line(&s);
+        fmtln!(
+            fmt,
+            "(decl {} ({}) Value)",
+            inst.name,
+            std::iter::once("Type")
+                .chain(inst.operands_in.iter().map(isle_operand_type))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        let term = format!(
+            "({} {})",
+            inst.name,
+            std::iter::once("ty")
+                .chain(inst.operands_in.iter().map(|o| o.name))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        let inst_data = isle_inst_data_pattern(inst);
+        fmtln!(fmt, "(extractor");
+        fmt.indent(|fmt| {
+            fmtln!(fmt, "{}", term);
+            fmtln!(fmt, "(inst_data ty {})", inst_data);
         });
         fmt.line(")");
+        if !inst.can_trap {
+            fmtln!(fmt, "(rule {}", term);
+            fmt.indent(|fmt| {
+                fmtln!(fmt, "(make_inst ty {}))", inst_data);
+            });
+        }
         fmt.empty_line();
     }
 }
@@ -1409,7 +1523,8 @@ pub(crate) fn generate(
     all_inst: &AllInstructions,
     opcode_filename: &str,
     inst_builder_filename: &str,
-    isle_filename: &str,
+    isle_lower_filename: &str,
+    isle_opt_filename: &str,
     out_dir: &str,
     crate_dir: &Path,
 ) -> Result<(), error::Error> {
@@ -1430,15 +1545,20 @@ pub(crate) fn generate(
     // ISLE DSL.
     #[cfg(feature = "rebuild-isle")]
     {
-        let mut fmt = Formatter::new();
-        gen_isle(&formats, all_inst, &mut fmt);
         let crate_src_dir = crate_dir.join("src");
-        fmt.update_file(isle_filename, &crate_src_dir.display().to_string())?;
+
+        let mut fmt = Formatter::new();
+        gen_isle(&formats, all_inst, &mut fmt, IsleTarget::Lower);
+        fmt.update_file(isle_lower_filename, &crate_src_dir.display().to_string())?;
+
+        let mut fmt = Formatter::new();
+        gen_isle(&formats, all_inst, &mut fmt, IsleTarget::Opt);
+        fmt.update_file(isle_opt_filename, &crate_src_dir.display().to_string())?;
     }
     #[cfg(not(feature = "rebuild-isle"))]
     {
         // Silence unused variable warnings.
-        let _ = (isle_filename, crate_dir);
+        let _ = (isle_lower_filename, isle_opt_filename, crate_dir);
     }
 
     // Instruction builder.
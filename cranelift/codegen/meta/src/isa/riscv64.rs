@@ -0,0 +1,65 @@
+use crate::cdsl::isa::TargetIsa;
+use crate::cdsl::settings::{PredicateNode, SettingGroup, SettingGroupBuilder};
+
+use crate::shared::Definitions as SharedDefinitions;
+
+fn define_settings(_shared: &SettingGroup) -> SettingGroup {
+    let mut settings = SettingGroupBuilder::new("riscv64");
+
+    // The baseline architecture for cranelift is RV64IMAFD (the "G" subset
+    // without the Zicsr/Zifencei details), so these are enabled by default.
+    // Code generation fails if any of them is turned off.
+    let has_m = settings.add_bool(
+        "has_m",
+        "Has M extension support.",
+        "Integer multiplication and division.",
+        true,
+    );
+    let has_a = settings.add_bool(
+        "has_a",
+        "Has A extension support.",
+        "Atomic instructions.",
+        true,
+    );
+    let has_f = settings.add_bool(
+        "has_f",
+        "Has F extension support.",
+        "Single-precision floating point.",
+        true,
+    );
+    let has_d = settings.add_bool(
+        "has_d",
+        "Has D extension support.",
+        "Double-precision floating point.",
+        true,
+    );
+
+    // Optional bit-manipulation extensions.
+    let has_zba = settings.add_bool(
+        "has_zba",
+        "Has Zba extension support.",
+        "Address generation instructions.",
+        false,
+    );
+    let has_zbb = settings.add_bool(
+        "has_zbb",
+        "Has Zbb extension support.",
+        "Basic bit-manipulation instructions.",
+        false,
+    );
+
+    settings.add_predicate("has_g", predicate!(has_m && has_a && has_f && has_d));
+    settings.add_preset(
+        "rv64gc_zba_zbb",
+        "RV64GC with the Zba and Zbb bit-manipulation extensions.",
+        preset!(has_m && has_a && has_f && has_d && has_zba && has_zbb),
+    );
+
+    settings.build()
+}
+
+pub(crate) fn define(shared_defs: &mut SharedDefinitions) -> TargetIsa {
+    let settings = define_settings(&shared_defs.settings);
+
+    TargetIsa::new("riscv64", settings)
+}
@@ -49,6 +49,7 @@ pub fn generate(isas: &[isa::Isa], out_dir: &str, crate_dir: &Path) -> Result<()
         "opcodes.rs",
         "inst_builder.rs",
         "clif.isle",
+        "clif_opt.isle",
         &out_dir,
         crate_dir,
     )?;
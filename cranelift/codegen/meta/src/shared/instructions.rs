@@ -390,6 +390,56 @@ fn define_control_flow(
         .is_call(true),
     );
 
+    let FN = &Operand::new("FN", &entities.func_ref)
+        .with_doc("function to call, declared by `function`");
+    let args = &Operand::new("args", &entities.varargs).with_doc("call arguments");
+    ig.push(
+        Inst::new(
+            "return_call",
+            r#"
+        Direct tail call.
+
+        Tail call a function which has been declared in the preamble. The
+        argument types must match the function's signature, the callee's
+        return types must match the calling function's return types, and both
+        functions must use the `tail` calling convention.
+
+        The current function's frame is torn down before control is
+        transferred, and the callee returns directly to the current function's
+        caller. This instruction is a block terminator.
+        "#,
+            &formats.call,
+        )
+        .operands_in(vec![FN, args])
+        .is_call(true)
+        .is_terminator(true),
+    );
+
+    let SIG = &Operand::new("SIG", &entities.sig_ref).with_doc("function signature");
+    let callee = &Operand::new("callee", iAddr).with_doc("address of function to call");
+    let args = &Operand::new("args", &entities.varargs).with_doc("call arguments");
+    ig.push(
+        Inst::new(
+            "return_call_indirect",
+            r#"
+        Indirect tail call.
+
+        Tail call the function pointed to by `callee` with the given arguments.
+        The called function must match the specified signature, its return
+        types must match the calling function's return types, and both
+        functions must use the `tail` calling convention.
+
+        The current function's frame is torn down before control is
+        transferred, and the callee returns directly to the current function's
+        caller. This instruction is a block terminator.
+        "#,
+            &formats.call_indirect,
+        )
+        .operands_in(vec![SIG, callee, args])
+        .is_call(true)
+        .is_terminator(true),
+    );
+
     let FN = &Operand::new("FN", &entities.func_ref)
         .with_doc("function to call, declared by `function`");
     let addr = &Operand::new("addr", iAddr);
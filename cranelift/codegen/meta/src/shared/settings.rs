@@ -7,26 +7,12 @@ pub(crate) fn define() -> SettingGroup {
         "regalloc",
         "Register allocator to use with the MachInst backend.",
         r#"
-            This selects the register allocator as an option among those offered by the `regalloc.rs`
-            crate. Please report register allocation bugs to the maintainers of this crate whenever
-            possible.
-
-            Note: this only applies to target that use the MachInst backend. As of 2020-04-17, this
-            means the x86_64 backend doesn't use this yet.
-
-            Possible values:
-
-            - `backtracking` is a greedy, backtracking register allocator as implemented in
-            Spidermonkey's optimizing tier IonMonkey. It may take more time to allocate registers, but
-            it should generate better code in general, resulting in better throughput of generated
-            code.
-            - `backtracking_checked` is the backtracking allocator with additional self checks that may
-            take some time to run, and thus these checks are disabled by default.
-            - `experimental_linear_scan` is an experimental linear scan allocator. It may take less
-            time to allocate registers, but generated code's quality may be inferior. As of
-            2020-04-17, it is still experimental and it should not be used in production settings.
-            - `experimental_linear_scan_checked` is the linear scan allocator with additional self
-            checks that may take some time to run, and thus these checks are disabled by default.
+            Deprecated: this setting is accepted for compatibility but has no effect, and will be
+            removed in a future release. All backends now use `regalloc2`, which has a single
+            allocation algorithm.
+
+            The `_checked` variants no longer enable any checks; use the `regalloc_checker`
+            setting to run the register allocation checker.
         "#,
         vec![
             "backtracking",
@@ -36,6 +22,21 @@ pub(crate) fn define() -> SettingGroup {
         ],
     );
 
+    settings.add_bool(
+        "regalloc_checker",
+        "Enable the symbolic checker for register allocation.",
+        r#"
+            This performs a verification that the register allocator preserves
+            equivalent dataflow with respect to the original (pre-regalloc)
+            program. This analysis is somewhat expensive. However, if it succeeds,
+            it provides independent evidence (by a carefully-reviewed, from-first-principles
+            analysis) that no regalloc bugs were triggered for the particular compilations
+            performed. This is a valuable assurance to have as regalloc bugs can be
+            very dangerous and difficult to debug.
+        "#,
+        false,
+    );
+
     settings.add_enum(
         "opt_level",
         "Optimization level for generated code.",
@@ -49,6 +50,18 @@ pub(crate) fn define() -> SettingGroup {
         vec!["none", "speed", "speed_and_size"],
     );
 
+    settings.add_bool(
+        "use_egraphs",
+        "Use the e-graph based mid-end optimizer.",
+        r#"
+            When `opt_level` is not `none`, this runs a single pass that performs GVN, LICM,
+            constant folding and algebraic simplification together on an e-graph of the function.
+            When disabled, the legacy pipeline of separate preopt, LICM, GVN and DCE passes is used
+            instead.
+        "#,
+        true,
+    );
+
     settings.add_bool(
         "enable_verifier",
         "Run the Cranelift IR verifier at strategic times during compilation.",
@@ -290,13 +303,27 @@ pub(crate) fn define() -> SettingGroup {
         "The log2 of the size of the stack guard region.",
         r#"
             Stack frames larger than this size will have stack overflow checked
-            by calling the probestack function.
+            by probing the stack, as selected by `probestack_strategy`.
 
             The default is 12, which translates to a size of 4096.
         "#,
         12,
     );
 
+    settings.add_enum(
+        "probestack_strategy",
+        "Controls what kinds of stack probes are emitted.",
+        r#"
+            Supported strategies:
+
+            - `outline`: Always emits stack probes as calls to a probe stack function.
+            - `inline`: Always emits inline stack probes. Only x86_64 and aarch64
+              support this; building an ISA for another target with this strategy
+              fails when `enable_probestack` is set.
+        "#,
+        vec!["outline", "inline"],
+    );
+
     // Jump table options.
 
     settings.add_bool(
@@ -323,5 +350,22 @@ pub(crate) fn define() -> SettingGroup {
         true,
     );
 
+    settings.add_bool(
+        "enable_table_access_spectre_mitigation",
+        "Enable Spectre mitigation on table bounds checks.",
+        r#"
+            This option uses a conditional move to ensure that when a table
+            access index is bounds-checked and a conditional branch is used
+            for the out-of-bounds case, a misspeculation of that conditional
+            branch (falsely predicted in-bounds) will select a null address
+            rather than an out-of-bounds one on the speculative path.
+
+            This option is enabled by default because it is highly
+            recommended for secure sandboxing. The embedder should consider
+            the security implications carefully before disabling this option.
+        "#,
+        true,
+    );
+
     settings.build()
 }
@@ -49,6 +49,10 @@ pub enum Reloc {
     Arm64Call,
     /// s390x PC-relative 4-byte offset
     S390xPCRel32Dbl,
+    /// RISC-V call target. Covers an `auipc` and the following `jalr`, whose
+    /// immediates together form a 32-bit offset from the `auipc` to the
+    /// destination. This is equivalent to `R_RISCV_CALL_PLT` in ELF.
+    RiscvCall,
 
     /// Elf x86_64 32 bit signed PC relative offset to two GOT entries for GD symbol.
     ElfX86_64TlsGd,
@@ -80,6 +84,7 @@ impl fmt::Display for Reloc {
             Self::X86CallPLTRel4 => write!(f, "CallPLTRel4"),
             Self::X86GOTPCRel4 => write!(f, "GOTPCRel4"),
             Self::Arm32Call | Self::Arm64Call => write!(f, "Call"),
+            Self::RiscvCall => write!(f, "RiscvCall"),
 
             Self::ElfX86_64TlsGd => write!(f, "ElfX86_64TlsGd"),
             Self::MachOX86_64Tlv => write!(f, "MachOX86_64Tlv"),
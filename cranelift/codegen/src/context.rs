@@ -12,6 +12,7 @@
 use crate::binemit::{CodeInfo, MemoryCodeSink, RelocSink, StackMapSink, TrapSink};
 use crate::dce::do_dce;
 use crate::dominator_tree::DominatorTree;
+use crate::egraph::do_egraph;
 use crate::flowgraph::ControlFlowGraph;
 use crate::ir::Function;
 use crate::isa::TargetIsa;
@@ -143,8 +144,14 @@ impl Context {
             self.func.display()
         );
 
+        // The e-graph doesn't keep track of value labels, so fall back to the legacy passes when
+        // generating debug info.
+        let use_egraphs = opt_level != OptLevel::None
+            && isa.flags().use_egraphs()
+            && self.func.dfg.values_labels.is_none();
+
         self.compute_cfg();
-        if opt_level != OptLevel::None {
+        if opt_level != OptLevel::None && !use_egraphs {
             self.preopt(isa)?;
         }
         if isa.flags().enable_nan_canonicalization() {
@@ -152,7 +159,12 @@ impl Context {
         }
 
         self.legalize(isa)?;
-        if opt_level != OptLevel::None {
+        if use_egraphs {
+            self.compute_domtree();
+            self.eliminate_unreachable_code(isa)?;
+            self.compute_loop_analysis();
+            self.egraph_pass(isa)?;
+        } else if opt_level != OptLevel::None {
             self.compute_domtree();
             self.compute_loop_analysis();
             self.licm(isa)?;
@@ -161,7 +173,7 @@ impl Context {
 
         self.compute_domtree();
         self.eliminate_unreachable_code(isa)?;
-        if opt_level != OptLevel::None {
+        if opt_level != OptLevel::None && !use_egraphs {
             self.dce(isa)?;
         }
 
@@ -352,6 +364,16 @@ impl Context {
         self.verify_if(isa)
     }
 
+    /// Run the e-graph based optimizer on the function.
+    ///
+    /// This performs GVN, LICM, DCE, constant folding and algebraic simplification together. The
+    /// function must not contain unreachable code, and the dominator tree and loop analysis must
+    /// be up to date.
+    pub fn egraph_pass<'a, FOI: Into<FlagsOrIsa<'a>>>(&mut self, fisa: FOI) -> CodegenResult<()> {
+        do_egraph(&mut self.func, &self.domtree, &self.loop_analysis);
+        self.verify_if(fisa)
+    }
+
     /// Perform unreachable code elimination.
     pub fn eliminate_unreachable_code<'a, FOI>(&mut self, fisa: FOI) -> CodegenResult<()>
     where
@@ -0,0 +1,337 @@
+//! Elaboration of the e-graph back into the function's layout.
+
+use super::EGraph;
+use crate::dominator_tree::{DominatorTree, DominatorTreePreorder};
+use crate::entity::{EntitySet, SecondaryMap};
+use crate::fx::FxHashMap;
+use crate::ir::{Block, Inst, Value, ValueDef};
+use crate::loop_analysis::{Loop, LoopAnalysis};
+use crate::packed_option::PackedOption;
+use alloc::vec::Vec;
+use smallvec::SmallVec;
+
+/// A loop whose header dominates the block being elaborated.
+struct LoopEntry {
+    lp: Loop,
+    header: Block,
+    /// The block that values invariant in the loop are hoisted to: the immediate dominator of
+    /// the loop's header.
+    hoist_block: Block,
+    /// The depth of `hoist_block` in the dominator tree.
+    hoist_depth: u32,
+}
+
+/// Where to place an elaborated instruction.
+#[derive(Clone, Copy)]
+struct Placement {
+    block: Block,
+    /// The depth of `block` in the dominator tree.
+    depth: u32,
+    /// The instruction to insert before.
+    before: Inst,
+}
+
+/// The result of elaborating an e-class.
+#[derive(Clone, Copy)]
+enum Elaborated {
+    /// The e-class is computed by this value, defined in the block at this depth.
+    Value(Value, u32),
+    /// The e-class is a node without arguments, such as a constant, which is cheap enough to
+    /// recompute in every block where it's used, rather than keeping it alive across blocks.
+    /// It's placed together with its user.
+    Remat(Value),
+}
+
+enum ElabStackEntry {
+    /// Elaborate the given e-class.
+    Start(Value),
+    /// Place the instruction defining the pure node of the given e-class, once its arguments
+    /// have been elaborated.
+    Finish(Value, Inst),
+}
+
+enum DomTreeEvent {
+    Enter(Block),
+    Exit(Block),
+}
+
+struct Elaborator {
+    /// The value computing each e-class that is available at the current point of the
+    /// traversal, and the depth of the block it's defined in.
+    available: FxHashMap<Value, (Value, u32)>,
+    /// The e-classes made available in each block on the current dominator tree path, indexed
+    /// by depth, to remove from `available` once the traversal leaves the block.
+    scopes: Vec<Vec<Value>>,
+    /// The rematerialized value of each e-class in each block.
+    remat: FxHashMap<(Block, Value), Value>,
+    /// The loops whose headers dominate the current block, outermost first.
+    loop_stack: Vec<LoopEntry>,
+    /// The depth of each block on the current dominator tree path.
+    block_depth: SecondaryMap<Block, u32>,
+    /// The pure nodes whose instruction has been placed in the layout. Placing the same node
+    /// again, in a block that isn't dominated by the first one, needs a copy.
+    placed: EntitySet<Inst>,
+    stack: Vec<ElabStackEntry>,
+    results: Vec<Elaborated>,
+}
+
+impl EGraph<'_> {
+    /// Walk the skeleton in dominator tree order, and place the values it uses back into the
+    /// layout.
+    pub(super) fn elaborate(
+        &mut self,
+        domtree: &DominatorTree,
+        preorder: &DominatorTreePreorder,
+        loop_analysis: &LoopAnalysis,
+    ) {
+        let entry = match self.func.layout.entry_block() {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        let mut loop_headers: SecondaryMap<Block, PackedOption<Loop>> = SecondaryMap::new();
+        for lp in loop_analysis.loops() {
+            loop_headers[loop_analysis.loop_header(lp)] = lp.into();
+        }
+
+        let mut elab = Elaborator {
+            available: FxHashMap::default(),
+            scopes: Vec::new(),
+            remat: FxHashMap::default(),
+            loop_stack: Vec::new(),
+            block_depth: SecondaryMap::new(),
+            placed: EntitySet::new(),
+            stack: Vec::new(),
+            results: Vec::new(),
+        };
+
+        let mut events = vec![DomTreeEvent::Enter(entry)];
+        while let Some(event) = events.pop() {
+            match event {
+                DomTreeEvent::Enter(block) => {
+                    let depth = elab.scopes.len() as u32;
+                    elab.scopes.push(Vec::new());
+                    elab.block_depth[block] = depth;
+
+                    // Values can only be hoisted out of a loop if its header has an immediate
+                    // dominator, which excludes loops headed by the entry block.
+                    if let (Some(lp), Some(idom)) =
+                        (loop_headers[block].expand(), domtree.idom(block))
+                    {
+                        let hoist_block = self.func.layout.inst_block(idom).unwrap();
+                        elab.loop_stack.push(LoopEntry {
+                            lp,
+                            header: block,
+                            hoist_block,
+                            hoist_depth: elab.block_depth[hoist_block],
+                        });
+                    }
+
+                    self.elaborate_block(&mut elab, block, depth, loop_analysis);
+
+                    events.push(DomTreeEvent::Exit(block));
+                    let first = events.len();
+                    events.extend(preorder.children(block).map(DomTreeEvent::Enter));
+                    events[first..].reverse();
+                }
+                DomTreeEvent::Exit(block) => {
+                    for class in elab.scopes.pop().unwrap() {
+                        elab.available.remove(&class);
+                    }
+                    if elab.loop_stack.last().map(|entry| entry.header) == Some(block) {
+                        elab.loop_stack.pop();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Elaborate the arguments of the skeleton instructions in `block`.
+    fn elaborate_block(
+        &mut self,
+        elab: &mut Elaborator,
+        block: Block,
+        depth: u32,
+        loop_analysis: &LoopAnalysis,
+    ) {
+        let mut next = self.func.layout.first_inst(block);
+        while let Some(inst) = next {
+            next = self.func.layout.next_inst(inst);
+
+            // Branches must all be at the end of the block, so the arguments of any branch are
+            // placed before the first one.
+            let before = if self.func.dfg[inst].opcode().is_branch() {
+                self.first_branch(block)
+            } else {
+                inst
+            };
+            let placement = Placement {
+                block,
+                depth,
+                before,
+            };
+
+            let args: SmallVec<[Value; 8]> = self.func.dfg.inst_args(inst).into();
+            for (i, arg) in args.into_iter().enumerate() {
+                let arg = self.func.dfg.resolve_aliases(arg);
+                let value = self.elaborate_value(elab, arg, placement, loop_analysis);
+                self.func.dfg.inst_args_mut(inst)[i] = value;
+            }
+        }
+    }
+
+    /// Get the first of the branches at the end of `block`.
+    fn first_branch(&self, block: Block) -> Inst {
+        let layout = &self.func.layout;
+        let mut first = layout.last_inst(block).unwrap();
+        while let Some(prev) = layout.prev_inst(first) {
+            if !self.func.dfg[prev].opcode().is_branch() {
+                break;
+            }
+            first = prev;
+        }
+        first
+    }
+
+    /// Get a value computing the e-class of `value` which is available at `placement`.
+    fn elaborate_value(
+        &mut self,
+        elab: &mut Elaborator,
+        value: Value,
+        placement: Placement,
+        loop_analysis: &LoopAnalysis,
+    ) -> Value {
+        debug_assert!(elab.stack.is_empty() && elab.results.is_empty());
+        let class = self.find(value);
+        elab.stack.push(ElabStackEntry::Start(class));
+
+        while let Some(entry) = elab.stack.pop() {
+            match entry {
+                ElabStackEntry::Start(class) => {
+                    if let Some(&(value, depth)) = elab.available.get(&class) {
+                        elab.results.push(Elaborated::Value(value, depth));
+                        continue;
+                    }
+                    let best = self.best(class);
+                    match self.node_inst(best) {
+                        // Block parameters and the results of skeleton instructions are
+                        // defined in a block dominating the current one.
+                        None => {
+                            let depth = elab.block_depth[self.def_block(best)];
+                            elab.results.push(Elaborated::Value(best, depth));
+                        }
+                        Some(inst) if self.func.dfg.inst_args(inst).is_empty() => {
+                            elab.results.push(Elaborated::Remat(class));
+                        }
+                        Some(inst) => {
+                            elab.stack.push(ElabStackEntry::Finish(class, inst));
+                            let args: SmallVec<[Value; 4]> = self.func.dfg.inst_args(inst).into();
+                            for &arg in args.iter().rev() {
+                                let arg = self.find(arg);
+                                elab.stack.push(ElabStackEntry::Start(arg));
+                            }
+                        }
+                    }
+                }
+
+                ElabStackEntry::Finish(class, inst) => {
+                    let num_args = self.func.dfg.inst_args(inst).len();
+                    let args_start = elab.results.len() - num_args;
+                    let args: SmallVec<[Elaborated; 4]> =
+                        elab.results.drain(args_start..).collect();
+
+                    let max_arg_depth = args
+                        .iter()
+                        .map(|arg| match *arg {
+                            Elaborated::Value(_, depth) => depth,
+                            Elaborated::Remat(_) => 0,
+                        })
+                        .max()
+                        .unwrap_or(0);
+
+                    // Hoist the instruction out of the outermost loop it's invariant in, if any.
+                    let target = elab
+                        .loop_stack
+                        .iter()
+                        .find(|entry| {
+                            entry.hoist_depth >= max_arg_depth
+                                && loop_analysis.is_in_loop(placement.block, entry.lp)
+                        })
+                        .map(|entry| Placement {
+                            block: entry.hoist_block,
+                            depth: entry.hoist_depth,
+                            before: self.first_branch(entry.hoist_block),
+                        })
+                        .unwrap_or(placement);
+
+                    let args: SmallVec<[Value; 4]> = args
+                        .into_iter()
+                        .map(|arg| match arg {
+                            Elaborated::Value(value, _) => value,
+                            Elaborated::Remat(class) => self.remat(elab, class, target),
+                        })
+                        .collect();
+
+                    let value = self.place(elab, inst, &args, target);
+                    elab.available.insert(class, (value, target.depth));
+                    elab.scopes[target.depth as usize].push(class);
+                    elab.results.push(Elaborated::Value(value, target.depth));
+                }
+            }
+        }
+
+        debug_assert_eq!(elab.results.len(), 1);
+        match elab.results.pop().unwrap() {
+            Elaborated::Value(value, _) => value,
+            Elaborated::Remat(class) => self.remat(elab, class, placement),
+        }
+    }
+
+    /// Place the argument-less node of `class` in the block of `placement`, unless it's already
+    /// there.
+    fn remat(&mut self, elab: &mut Elaborator, class: Value, placement: Placement) -> Value {
+        if let Some(&value) = elab.remat.get(&(placement.block, class)) {
+            return value;
+        }
+        let best = self.best(class);
+        let inst = self.node_inst(best).unwrap();
+        let value = self.place(elab, inst, &[], placement);
+        elab.remat.insert((placement.block, class), value);
+        value
+    }
+
+    /// Insert the pure node defined by `inst` into the layout at `placement`, with `args` as
+    /// its arguments, and get its result.
+    fn place(
+        &mut self,
+        elab: &mut Elaborator,
+        inst: Inst,
+        args: &[Value],
+        placement: Placement,
+    ) -> Value {
+        let inst = if elab.placed.insert(inst) {
+            inst
+        } else {
+            let data = self.func.dfg[inst].clone();
+            let ctrl_typevar = self.func.dfg.ctrl_typevar(inst);
+            let copy = self.func.dfg.make_inst(data);
+            self.func.dfg.make_inst_results(copy, ctrl_typevar);
+            let srcloc = self.func.srclocs[inst];
+            if !srcloc.is_default() {
+                self.func.srclocs[copy] = srcloc;
+            }
+            copy
+        };
+        self.func.dfg.inst_args_mut(inst).copy_from_slice(args);
+        self.func.layout.insert_inst(inst, placement.before);
+        self.func.dfg.first_result(inst)
+    }
+
+    /// Get the block defining `value`, which must not be a pure node.
+    fn def_block(&self, value: Value) -> Block {
+        match self.func.dfg.value_def(value) {
+            ValueDef::Param(block, _) => block,
+            ValueDef::Result(inst, _) => self.func.layout.inst_block(inst).unwrap(),
+        }
+    }
+}
@@ -0,0 +1,426 @@
+//! An e-graph based mid-end optimizer.
+//!
+//! This pass performs GVN, LICM, DCE, constant folding and algebraic simplification together,
+//! in three steps:
+//!
+//! 1. The pure instructions of the function, which compute a single value and have no other
+//!    effect, are removed from the layout and inserted into an e-graph as "nodes". What remains
+//!    in the layout is the "skeleton" of the function: its side-effecting instructions, whose
+//!    results are opaque to the e-graph just like block parameters are.
+//!
+//! 2. Every node inserted into the e-graph is deduplicated against the existing nodes, which
+//!    is GVN, and then rewritten by the ISLE rules in `src/opts/*.isle`. The rewritten value is
+//!    merged into the same e-class as the original, and the cheapest value of each e-class is
+//!    tracked as the e-classes are merged.
+//!
+//! 3. The skeleton is walked in dominator tree order, and the cheapest value of each e-class it
+//!    uses is "elaborated" back into instructions right before the first use. Elaborated values
+//!    are reused in the blocks dominated by the one they were placed in, and are hoisted out of
+//!    the loops they don't depend on, which is LICM. Values that are never used are never
+//!    elaborated, which is DCE.
+
+use crate::dominator_tree::{DominatorTree, DominatorTreePreorder};
+use crate::entity::{EntitySet, SecondaryMap};
+use crate::fx::FxHashMap;
+use crate::ir::{types, Function, Inst, InstructionData, Opcode, Type, Value, ValueListPool};
+use crate::loop_analysis::LoopAnalysis;
+use crate::packed_option::PackedOption;
+use crate::timing;
+use core::hash::{Hash, Hasher};
+use smallvec::SmallVec;
+
+mod elaborate;
+
+/// The maximum depth of nested rewrites.
+///
+/// Rewriting a node can create new nodes, which are rewritten in turn. This bounds the time
+/// spent on pathological chains of rewrites.
+const MAX_REWRITE_DEPTH: u32 = 5;
+
+/// Optimize `func` with an e-graph.
+///
+/// The function must not contain unreachable blocks.
+pub fn do_egraph(func: &mut Function, domtree: &DominatorTree, loop_analysis: &LoopAnalysis) {
+    let _tt = timing::egraph();
+    debug_assert!(domtree.is_valid());
+    debug_assert!(loop_analysis.is_valid());
+
+    let mut preorder = DominatorTreePreorder::new();
+    preorder.compute(domtree, &func.layout);
+
+    let mut egraph = EGraph::new(func);
+    egraph.build(&preorder);
+    egraph.elaborate(domtree, &preorder, loop_analysis);
+}
+
+/// The key by which identical pure nodes are deduplicated.
+///
+/// Pure nodes never use value lists, so their `InstructionData` can be hashed and compared
+/// without the function's value list pool.
+struct NodeKey {
+    data: InstructionData,
+    ty: Type,
+}
+
+impl Hash for NodeKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash(state, &ValueListPool::new());
+        self.ty.hash(state);
+    }
+}
+
+impl PartialEq for NodeKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.data.eq(&other.data, &ValueListPool::new()) && self.ty == other.ty
+    }
+}
+
+impl Eq for NodeKey {}
+
+/// An e-graph over the values of a function.
+///
+/// E-classes are identified by their canonical value. The values defined by pure nodes, block
+/// parameters and the results of side-effecting instructions are all members of e-classes,
+/// the latter two always being the canonical values of their e-classes.
+pub(crate) struct EGraph<'a> {
+    pub(crate) func: &'a mut Function,
+    /// The union-find forest of e-classes: the parent of each value, or `None` for canonical
+    /// values. Values only ever join existing e-classes, so canonical values stay canonical.
+    parent: SecondaryMap<Value, PackedOption<Value>>,
+    /// The cheapest value of each e-class, indexed by its canonical value, or `None` when that
+    /// is the canonical value itself.
+    best: SecondaryMap<Value, PackedOption<Value>>,
+    /// The cost of computing each value, including its arguments.
+    cost: SecondaryMap<Value, u32>,
+    /// The instructions defining pure nodes. These are not in the layout.
+    nodes: EntitySet<Inst>,
+    /// The value of each pure node, for deduplicating identical nodes.
+    node_map: FxHashMap<NodeKey, Value>,
+    /// The current depth of nested rewrites.
+    rewrite_depth: u32,
+}
+
+impl<'a> EGraph<'a> {
+    fn new(func: &'a mut Function) -> Self {
+        Self {
+            func,
+            parent: SecondaryMap::new(),
+            best: SecondaryMap::new(),
+            cost: SecondaryMap::new(),
+            nodes: EntitySet::new(),
+            node_map: FxHashMap::default(),
+            rewrite_depth: 0,
+        }
+    }
+
+    /// Move the pure instructions of the function into the e-graph, visiting blocks in
+    /// dominator tree pre-order so that values are inserted before their uses.
+    fn build(&mut self, preorder: &DominatorTreePreorder) {
+        let entry = match self.func.layout.entry_block() {
+            Some(entry) => entry,
+            None => return,
+        };
+        let mut stack = vec![entry];
+        while let Some(block) = stack.pop() {
+            let mut next = self.func.layout.first_inst(block);
+            while let Some(inst) = next {
+                next = self.func.layout.next_inst(inst);
+
+                let mut args: SmallVec<[Value; 4]> = self.func.dfg.inst_args(inst).into();
+                for arg in args.iter_mut() {
+                    *arg = self.func.dfg.resolve_aliases(*arg);
+                }
+                let opcode = self.func.dfg[inst].opcode();
+                self.canonicalize_args(opcode, &mut args);
+                if !self.is_pure_node(inst, &args) {
+                    continue;
+                }
+
+                self.func.layout.remove_inst(inst);
+                self.func.dfg.inst_args_mut(inst).copy_from_slice(&args);
+                let value = self.func.dfg.first_result(inst);
+                let canonical = self.insert_node(inst);
+                if canonical != value && self.parent[value].is_none() {
+                    // The node was identical to an existing one, which replaces it.
+                    self.parent[value] = canonical.into();
+                }
+            }
+
+            // Push the children in reverse, so they're visited in the order of the CFG's reverse
+            // post-order.
+            let first = stack.len();
+            stack.extend(preorder.children(block));
+            stack[first..].reverse();
+        }
+    }
+
+    /// Replace `args`, the arguments of an instruction with the given opcode, by the canonical
+    /// values of their e-classes.
+    ///
+    /// The arguments of commutative operations are also put in a canonical order, so that
+    /// identical nodes are deduplicated regardless of the order of their arguments: constants
+    /// go last, which is also the form that the rewrite rules and the backends expect.
+    fn canonicalize_args(&mut self, opcode: Opcode, args: &mut [Value]) {
+        for arg in args.iter_mut() {
+            *arg = self.find(*arg);
+        }
+        if is_commutative(opcode) {
+            let key0 = (self.iconst_value(args[0]).is_some(), args[0]);
+            let key1 = (self.iconst_value(args[1]).is_some(), args[1]);
+            if key0 > key1 {
+                args.swap(0, 1);
+            }
+        }
+    }
+
+    /// Can `inst`, whose canonicalized arguments are `args`, be moved into the e-graph?
+    fn is_pure_node(&mut self, inst: Inst, args: &[Value]) -> bool {
+        let dfg = &self.func.dfg;
+        let opcode = dfg[inst].opcode();
+        if opcode.is_call()
+            || opcode.is_branch()
+            || opcode.is_terminator()
+            || opcode.is_return()
+            || opcode.can_load()
+            || opcode.can_store()
+            || opcode.other_side_effects()
+            || opcode.writes_cpu_flags()
+        {
+            return false;
+        }
+
+        // These trap when out of bounds, but aren't marked as such since the legalizer expands
+        // them into explicit bounds checks.
+        if let Opcode::HeapAddr | Opcode::TableAddr = opcode {
+            return false;
+        }
+
+        let results = dfg.inst_results(inst);
+        if results.len() != 1 {
+            return false;
+        }
+
+        // Keep CPU flags in the skeleton, since they can't be live across other instructions, and
+        // references too, since they must be tracked in stack maps.
+        let is_plain_type = |ty: Type| !ty.is_flags() && !ty.is_ref();
+        if !is_plain_type(dfg.value_type(results[0]))
+            || !args.iter().all(|&arg| is_plain_type(dfg.value_type(arg)))
+        {
+            return false;
+        }
+
+        if opcode.can_trap() {
+            return self.is_division_without_trap(opcode, args);
+        }
+
+        true
+    }
+
+    /// Is this an integer division or remainder by a constant that can't trap?
+    fn is_division_without_trap(&mut self, opcode: Opcode, args: &[Value]) -> bool {
+        let (ty, divisor) = match self.iconst_value(args[1]) {
+            Some(c) => c,
+            None => return false,
+        };
+        match opcode {
+            Opcode::Udiv | Opcode::Urem => divisor != 0,
+            // The minimum value divided by -1 overflows.
+            Opcode::Sdiv | Opcode::Srem => divisor != 0 && divisor != u64::MAX >> (64 - ty.bits()),
+            _ => false,
+        }
+    }
+
+    /// Get the canonical value of the e-class of `value`.
+    pub(crate) fn find(&mut self, mut value: Value) -> Value {
+        while let Some(parent) = self.parent[value].expand() {
+            // Path halving: point the value at its grandparent, if any, as we go.
+            if let Some(grandparent) = self.parent[parent].expand() {
+                self.parent[value] = grandparent.into();
+            }
+            value = parent;
+        }
+        value
+    }
+
+    /// Get the cheapest value of the e-class of `value`.
+    pub(crate) fn best(&mut self, value: Value) -> Value {
+        let canonical = self.find(value);
+        self.best[canonical].expand().unwrap_or(canonical)
+    }
+
+    /// Merge the e-class of `value`, which must be a canonical value, into the e-class of `into`.
+    fn union(&mut self, value: Value, into: Value) {
+        debug_assert!(self.parent[value].is_none());
+        let canonical = self.find(into);
+        if canonical == value {
+            return;
+        }
+        let best = self.best(value);
+        let existing = self.best(canonical);
+        self.parent[value] = canonical.into();
+        // Prefer the existing value when both are equally cheap, since that's the result of the
+        // rewrite.
+        if self.cost[best] < self.cost[existing] {
+            self.best[canonical] = best.into();
+        }
+    }
+
+    /// Get the instruction defining the pure node `value`, if it is one.
+    pub(crate) fn node_inst(&self, value: Value) -> Option<Inst> {
+        self.func
+            .dfg
+            .value_def(value)
+            .inst()
+            .filter(|&inst| self.nodes.contains(inst))
+    }
+
+    /// Get the type and instruction data of the cheapest node in the e-class of `value`, if the
+    /// cheapest value is a pure node.
+    pub(crate) fn node_data(&mut self, value: Value) -> Option<(Type, InstructionData)> {
+        let best = self.best(value);
+        let inst = self.node_inst(best)?;
+        Some((self.func.dfg.value_type(best), self.func.dfg[inst].clone()))
+    }
+
+    /// If the e-class of `value` is an integer constant of a scalar type at most 64 bits wide,
+    /// get that type and the constant's bits.
+    pub(crate) fn iconst_value(&mut self, value: Value) -> Option<(Type, u64)> {
+        match self.node_data(value)? {
+            (ty, InstructionData::UnaryImm { opcode, imm })
+                if opcode == Opcode::Iconst && ty.is_int() && ty.bits() <= 64 =>
+            {
+                let bits = imm.bits() as u64;
+                Some((ty, bits & (u64::MAX >> (64 - ty.bits()))))
+            }
+            _ => None,
+        }
+    }
+
+    /// Create a pure node of type `ty` and insert it into the e-graph.
+    ///
+    /// Returns the canonical value of the node's e-class, which may be an existing e-class.
+    pub(crate) fn make_node(&mut self, ty: Type, mut data: InstructionData) -> Value {
+        let mut args: SmallVec<[Value; 4]> = data.arguments(&self.func.dfg.value_lists).into();
+        self.canonicalize_args(data.opcode(), &mut args);
+        data.arguments_mut(&mut self.func.dfg.value_lists)
+            .copy_from_slice(&args);
+        let key = NodeKey { data, ty };
+        if let Some(&existing) = self.node_map.get(&key) {
+            return self.find(existing);
+        }
+        let data = key.data;
+
+        let constraints = data.opcode().constraints();
+        let ctrl_typevar = if !constraints.is_polymorphic() {
+            types::INVALID
+        } else if constraints.requires_typevar_operand() {
+            let operand = data
+                .typevar_operand(&self.func.dfg.value_lists)
+                .expect("Instruction format doesn't have a designated operand, bad opcode.");
+            self.func.dfg.value_type(operand)
+        } else {
+            ty
+        };
+        let inst = self.func.dfg.make_inst(data);
+        self.func.dfg.make_inst_results(inst, ctrl_typevar);
+        debug_assert_eq!(
+            self.func.dfg.value_type(self.func.dfg.first_result(inst)),
+            ty
+        );
+        self.insert_node(inst)
+    }
+
+    /// Insert the pure node defined by `inst`, whose arguments are canonical values, into the
+    /// e-graph, and rewrite it.
+    ///
+    /// Returns the canonical value of the node's e-class, which may be an existing e-class.
+    fn insert_node(&mut self, inst: Inst) -> Value {
+        let value = self.func.dfg.first_result(inst);
+        let key = NodeKey {
+            data: self.func.dfg[inst].clone(),
+            ty: self.func.dfg.value_type(value),
+        };
+        if let Some(&existing) = self.node_map.get(&key) {
+            return self.find(existing);
+        }
+        self.node_map.insert(key, value);
+        self.nodes.insert(inst);
+
+        let mut cost = op_cost(self.func.dfg[inst].opcode());
+        for i in 0..self.func.dfg.inst_args(inst).len() {
+            let arg = self.func.dfg.inst_args(inst)[i];
+            let best = self.best(arg);
+            cost = cost.saturating_add(self.cost[best]);
+        }
+        self.cost[value] = cost;
+
+        if self.rewrite_depth < MAX_REWRITE_DEPTH {
+            self.rewrite_depth += 1;
+            let rewritten = crate::opts::simplify(self, value);
+            self.rewrite_depth -= 1;
+            if let Some(rewritten) = rewritten {
+                log::trace!("egraph: rewrote {} to {}", value, rewritten);
+                self.union(value, rewritten);
+            }
+        }
+
+        self.find(value)
+    }
+}
+
+/// The cost of computing the result of an instruction, not counting its arguments.
+///
+/// These are rough relative latencies. Division is expensive enough that the sequences which
+/// replace division by a constant are cheaper.
+fn op_cost(opcode: Opcode) -> u32 {
+    match opcode {
+        Opcode::Iconst
+        | Opcode::Bconst
+        | Opcode::F32const
+        | Opcode::F64const
+        | Opcode::Vconst
+        | Opcode::Null => 1,
+        Opcode::Iadd
+        | Opcode::Isub
+        | Opcode::Ineg
+        | Opcode::Band
+        | Opcode::Bor
+        | Opcode::Bxor
+        | Opcode::Bnot
+        | Opcode::BandNot
+        | Opcode::BorNot
+        | Opcode::BxorNot
+        | Opcode::Ishl
+        | Opcode::Ushr
+        | Opcode::Sshr
+        | Opcode::Rotl
+        | Opcode::Rotr
+        | Opcode::Icmp
+        | Opcode::Select
+        | Opcode::Bint
+        | Opcode::Uextend
+        | Opcode::Sextend
+        | Opcode::Ireduce => 2,
+        Opcode::Imul | Opcode::Umulhi | Opcode::Smulhi => 3,
+        Opcode::Udiv | Opcode::Sdiv | Opcode::Urem | Opcode::Srem => 64,
+        _ => 4,
+    }
+}
+
+/// Is `opcode` a commutative binary operation on integers?
+fn is_commutative(opcode: Opcode) -> bool {
+    match opcode {
+        Opcode::Iadd
+        | Opcode::Imul
+        | Opcode::Umulhi
+        | Opcode::Smulhi
+        | Opcode::Band
+        | Opcode::Bor
+        | Opcode::Bxor
+        | Opcode::Imin
+        | Opcode::Umin
+        | Opcode::Imax
+        | Opcode::Umax => true,
+        _ => false,
+    }
+}
@@ -550,7 +550,7 @@ impl DataFlowGraph {
         self.results[inst].clear(&mut self.value_lists);
 
         // Get the call signature if this is a function call.
-        if let Some(sig) = self.call_signature(inst) {
+        if let Some(sig) = self.call_results_signature(inst) {
             // Create result values corresponding to the call return types.
             debug_assert_eq!(
                 self.insts[inst].opcode().constraints().num_fixed_results(),
@@ -709,6 +709,18 @@ impl DataFlowGraph {
         }
     }
 
+    /// Get the signature whose return types determine the results of the call instruction
+    /// `inst`.
+    ///
+    /// This is the same as `call_signature()` except for tail calls, which never return to the
+    /// calling function and so have no results of their own.
+    pub(crate) fn call_results_signature(&self, inst: Inst) -> Option<SigRef> {
+        if self.insts[inst].opcode().is_terminator() {
+            return None;
+        }
+        self.call_signature(inst)
+    }
+
     /// Check if `inst` is a branch.
     pub fn analyze_branch(&self, inst: Inst) -> BranchInfo {
         self.insts[inst].analyze_branch(&self.value_lists)
@@ -735,7 +747,7 @@ impl DataFlowGraph {
         }
 
         // Not a fixed result, try to extract a return type from the call signature.
-        self.call_signature(inst).and_then(|sigref| {
+        self.call_results_signature(inst).and_then(|sigref| {
             self.signatures[sigref]
                 .returns
                 .get(result_idx - num_fixed_results)
@@ -1012,7 +1024,7 @@ impl DataFlowGraph {
         reuse: &[Value],
     ) -> usize {
         // Get the call signature if this is a function call.
-        if let Some(sig) = self.call_signature(inst) {
+        if let Some(sig) = self.call_results_signature(inst) {
             assert_eq!(
                 self.insts[inst].opcode().constraints().num_fixed_results(),
                 0
@@ -183,6 +183,13 @@ impl ABIMachineSpec for AArch64MachineDeps {
         args_or_rets: ArgsOrRets,
         add_ret_area_ptr: bool,
     ) -> CodegenResult<(Vec<ABIArg>, i64, Option<usize>)> {
+        if call_conv == isa::CallConv::Tail {
+            return Err(CodegenError::Unsupported(format!(
+                "calling convention {} is not supported on aarch64",
+                call_conv
+            )));
+        }
+
         let is_apple_cc = call_conv.extends_apple_aarch64();
         let is_baldrdash = call_conv.extends_baldrdash();
         let has_baldrdash_tls = call_conv == isa::CallConv::Baldrdash2020;
@@ -505,7 +512,7 @@ impl ABIMachineSpec for AArch64MachineDeps {
         }
     }
 
-    fn gen_ret() -> Inst {
+    fn gen_ret(_stack_bytes_to_pop: u32) -> Inst {
         Inst::Ret
     }
 
@@ -693,6 +700,56 @@ impl ABIMachineSpec for AArch64MachineDeps {
         smallvec![]
     }
 
+    fn gen_inline_probestack(frame_size: u32, guard_size: u32) -> SmallInstVec<Self::I> {
+        // Unroll at most this many probes before falling back to a loop.
+        const PROBE_MAX_UNROLL: u32 = 3;
+
+        let probe_count = frame_size / guard_size;
+        let mut insts = SmallVec::new();
+        match Imm12::maybe_from_u64(guard_size as u64) {
+            Some(step) if probe_count > PROBE_MAX_UNROLL => {
+                // The loop runs from `sp - guard_size` down to
+                // `sp - frame_size`, using the two temporaries that are free
+                // in the prologue.
+                let start = writable_spilltmp_reg();
+                let end = writable_tmp2_reg();
+                insts.push(Inst::AluRRImm12 {
+                    alu_op: ALUOp::Sub64,
+                    rd: start,
+                    rn: stack_reg(),
+                    imm12: step,
+                });
+                insts.extend(Inst::load_constant(end, frame_size as u64));
+                insts.push(Inst::AluRRRExtend {
+                    alu_op: ALUOp::Sub64,
+                    rd: end,
+                    rn: stack_reg(),
+                    rm: end.to_reg(),
+                    extendop: ExtendOp::UXTX,
+                });
+                insts.push(Inst::StackProbeLoop {
+                    start,
+                    end: end.to_reg(),
+                    step,
+                });
+            }
+            _ => {
+                // `str wzr, [sp, #-guard_size * i]` for each page, from the
+                // top down. The stored value is irrelevant; we only need to
+                // touch the page.
+                for i in 1..=probe_count {
+                    let offset = -((guard_size * i) as i64);
+                    insts.push(Self::gen_store_stack(
+                        StackAMode::SPOffset(offset, I32),
+                        zero_reg(),
+                        I32,
+                    ));
+                }
+            }
+        }
+        insts
+    }
+
     // Returns stack bytes used as well as instructions. Does not adjust
     // nominal SP offset; abi_impl generic code will do that.
     fn gen_clobber_save(
@@ -1060,6 +1117,7 @@ impl ABIMachineSpec for AArch64MachineDeps {
         tmp: Writable<Reg>,
         callee_conv: isa::CallConv,
         caller_conv: isa::CallConv,
+        _callee_pop_size: u32,
     ) -> SmallVec<[(InstIsSafepoint, Inst); 2]> {
         let mut insts = SmallVec::new();
         match &dest {
@@ -2769,6 +2769,53 @@ impl MachInstEmit for Inst {
                     add.emit(sink, emit_info, state);
                 }
             }
+            &Inst::StackProbeLoop { start, end, step } => {
+                /* Emit this:
+                     again:
+                      str   wzr, [start]
+                      sub   start, start, #step
+                      cmp   start, end
+                      b.hs  again
+
+                   The stored value is irrelevant; we only need to touch every page, in order
+                   from the highest address down.
+                */
+                let again_label = sink.get_label();
+
+                // again:
+                sink.bind_label(again_label);
+
+                Inst::Store32 {
+                    rd: zero_reg(),
+                    mem: AMode::reg(start.to_reg()),
+                    flags: MemFlags::trusted(),
+                }
+                .emit(sink, emit_info, state);
+
+                Inst::AluRRImm12 {
+                    alu_op: ALUOp::Sub64,
+                    rd: start,
+                    rn: start.to_reg(),
+                    imm12: step,
+                }
+                .emit(sink, emit_info, state);
+
+                Inst::AluRRR {
+                    alu_op: ALUOp::SubS64,
+                    rd: writable_zero_reg(),
+                    rn: start.to_reg(),
+                    rm: end,
+                }
+                .emit(sink, emit_info, state);
+
+                // b.hs again
+                let br_offset = sink.cur_offset();
+                sink.put4(enc_conditional_br(
+                    BranchTarget::Label(again_label),
+                    CondBrKind::Cond(Cond::Hs),
+                ));
+                sink.use_label_at_offset(br_offset, again_label, LabelUse::Branch19);
+            }
             &Inst::VirtualSPOffsetAdj { offset } => {
                 log::trace!(
                     "virtual sp offset adjusted by {} -> {}",
@@ -6510,6 +6510,16 @@ fn test_aarch64_binemit() {
 
     insns.push((Inst::Fence {}, "BF3B03D5", "dmb ish"));
 
+    insns.push((
+        Inst::StackProbeLoop {
+            start: writable_xreg(16),
+            end: xreg(17),
+            step: Imm12::maybe_from_u64(4096).unwrap(),
+        },
+        "1F0200B9100640D11F0211EBA2FFFF54",
+        "stack_probe_loop x16, x17, #4096",
+    ));
+
     let flags = settings::Flags::new(settings::builder());
     let rru = create_reg_universe(&flags);
     let emit_info = EmitInfo::new(flags);
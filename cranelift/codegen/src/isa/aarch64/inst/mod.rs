@@ -13,7 +13,7 @@ use crate::machinst::*;
 use crate::{settings, CodegenError, CodegenResult};
 
 use regalloc::RegUsageCollector;
-use regalloc::{PrettyPrint, RealRegUniverse, Reg, RegClass, SpillSlot, VirtualReg, Writable};
+use regalloc::{PrettyPrint, RealRegUniverse, Reg, RegClass, Writable};
 
 use alloc::vec::Vec;
 use core::convert::TryFrom;
@@ -1011,6 +1011,10 @@ fn aarch64_get_regs(inst: &Inst, collector: &mut RegUsageCollector) {
             collector.add_def(rd);
             memarg_regs(mem, collector);
         }
+        &Inst::StackProbeLoop { start, end, .. } => {
+            collector.add_mod(start);
+            collector.add_use(end);
+        }
         &Inst::VirtualSPOffsetAdj { .. } => {}
         &Inst::ValueLabelMarker { reg, .. } => {
             collector.add_use(reg);
@@ -1849,6 +1853,14 @@ pub fn aarch64_map_regs<RM: RegMapper>(inst: &mut Inst, mapper: &RM) {
             mapper.map_def(rd);
             map_mem(mapper, mem);
         }
+        &mut Inst::StackProbeLoop {
+            ref mut start,
+            ref mut end,
+            ..
+        } => {
+            mapper.map_mod(start);
+            mapper.map_use(end);
+        }
         &mut Inst::VirtualSPOffsetAdj { .. } => {}
         &mut Inst::EmitIsland { .. } => {}
         &mut Inst::ElfTlsGetAddr { .. } => {}
@@ -1978,10 +1990,6 @@ impl MachInst for Inst {
         Inst::Nop4
     }
 
-    fn maybe_direct_reload(&self, _reg: VirtualReg, _slot: SpillSlot) -> Option<Inst> {
-        None
-    }
-
     fn rc_for_type(ty: Type) -> CodegenResult<(&'static [RegClass], &'static [Type])> {
         match ty {
             I8 => Ok((&[RegClass::I64], &[I8])),
@@ -2032,10 +2040,6 @@ impl MachInst for Inst {
         44
     }
 
-    fn ref_type_regclass(_: &settings::Flags) -> RegClass {
-        RegClass::I64
-    }
-
     fn gen_value_label_marker(label: ValueLabel, reg: Reg) -> Self {
         Inst::ValueLabelMarker { label, reg }
     }
@@ -3435,6 +3439,12 @@ impl Inst {
                 }
                 ret
             }
+            &Inst::StackProbeLoop { start, end, step } => {
+                let start = start.to_reg().show_rru(mb_rru);
+                let end = end.show_rru(mb_rru);
+                let step = step.show_rru(mb_rru);
+                format!("stack_probe_loop {}, {}, {}", start, end, step)
+            }
             &Inst::VirtualSPOffsetAdj { offset } => {
                 state.virtual_sp_offset += offset;
                 format!("virtual_sp_offset_adjust {}", offset)
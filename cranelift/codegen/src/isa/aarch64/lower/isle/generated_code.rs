@@ -646,6 +646,11 @@ pub enum MInst {
         rd: WritableReg,
         mem: AMode,
     },
+    StackProbeLoop {
+        start: WritableReg,
+        end: Reg,
+        step: Imm12,
+    },
     VirtualSPOffsetAdj {
         offset: i64,
     },
@@ -664,7 +669,7 @@ pub enum MInst {
     },
 }
 
-/// Internal type ALUOp: defined at src/isa/aarch64/inst.isle line 783.
+/// Internal type ALUOp: defined at src/isa/aarch64/inst.isle line 793.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ALUOp {
     Add32,
@@ -711,7 +716,7 @@ pub enum ALUOp {
     SbcS64,
 }
 
-/// Internal type ALUOp3: defined at src/isa/aarch64/inst.isle line 844.
+/// Internal type ALUOp3: defined at src/isa/aarch64/inst.isle line 854.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ALUOp3 {
     MAdd32,
@@ -720,7 +725,7 @@ pub enum ALUOp3 {
     MSub64,
 }
 
-/// Internal type BitOp: defined at src/isa/aarch64/inst.isle line 892.
+/// Internal type BitOp: defined at src/isa/aarch64/inst.isle line 902.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum BitOp {
     RBit32,
@@ -731,7 +736,7 @@ pub enum BitOp {
     Cls64,
 }
 
-/// Internal type FPUOp1: defined at src/isa/aarch64/inst.isle line 958.
+/// Internal type FPUOp1: defined at src/isa/aarch64/inst.isle line 968.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum FPUOp1 {
     Abs32,
@@ -744,7 +749,7 @@ pub enum FPUOp1 {
     Cvt64To32,
 }
 
-/// Internal type FPUOp2: defined at src/isa/aarch64/inst.isle line 971.
+/// Internal type FPUOp2: defined at src/isa/aarch64/inst.isle line 981.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum FPUOp2 {
     Add32,
@@ -765,14 +770,14 @@ pub enum FPUOp2 {
     Uqsub64,
 }
 
-/// Internal type FPUOp3: defined at src/isa/aarch64/inst.isle line 996.
+/// Internal type FPUOp3: defined at src/isa/aarch64/inst.isle line 1006.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum FPUOp3 {
     MAdd32,
     MAdd64,
 }
 
-/// Internal type FpuToIntOp: defined at src/isa/aarch64/inst.isle line 1003.
+/// Internal type FpuToIntOp: defined at src/isa/aarch64/inst.isle line 1013.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum FpuToIntOp {
     F32ToU32,
@@ -785,7 +790,7 @@ pub enum FpuToIntOp {
     F64ToI64,
 }
 
-/// Internal type IntToFpuOp: defined at src/isa/aarch64/inst.isle line 1016.
+/// Internal type IntToFpuOp: defined at src/isa/aarch64/inst.isle line 1026.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum IntToFpuOp {
     U32ToF32,
@@ -798,7 +803,7 @@ pub enum IntToFpuOp {
     I64ToF64,
 }
 
-/// Internal type FpuRoundMode: defined at src/isa/aarch64/inst.isle line 1030.
+/// Internal type FpuRoundMode: defined at src/isa/aarch64/inst.isle line 1040.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum FpuRoundMode {
     Minus32,
@@ -811,7 +816,7 @@ pub enum FpuRoundMode {
     Nearest64,
 }
 
-/// Internal type VecExtendOp: defined at src/isa/aarch64/inst.isle line 1043.
+/// Internal type VecExtendOp: defined at src/isa/aarch64/inst.isle line 1053.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum VecExtendOp {
     Sxtl8,
@@ -822,7 +827,7 @@ pub enum VecExtendOp {
     Uxtl32,
 }
 
-/// Internal type VecALUOp: defined at src/isa/aarch64/inst.isle line 1060.
+/// Internal type VecALUOp: defined at src/isa/aarch64/inst.isle line 1070.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum VecALUOp {
     Sqadd,
@@ -864,7 +869,7 @@ pub enum VecALUOp {
     Sqrdmulh,
 }
 
-/// Internal type VecMisc2: defined at src/isa/aarch64/inst.isle line 1139.
+/// Internal type VecMisc2: defined at src/isa/aarch64/inst.isle line 1149.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum VecMisc2 {
     Not,
@@ -886,7 +891,7 @@ pub enum VecMisc2 {
     Cmeq0,
 }
 
-/// Internal type VecRRLongOp: defined at src/isa/aarch64/inst.isle line 1178.
+/// Internal type VecRRLongOp: defined at src/isa/aarch64/inst.isle line 1188.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum VecRRLongOp {
     Fcvtl16,
@@ -896,7 +901,7 @@ pub enum VecRRLongOp {
     Shll32,
 }
 
-/// Internal type VecRRNarrowOp: defined at src/isa/aarch64/inst.isle line 1193.
+/// Internal type VecRRNarrowOp: defined at src/isa/aarch64/inst.isle line 1203.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum VecRRNarrowOp {
     Xtn16,
@@ -915,7 +920,7 @@ pub enum VecRRNarrowOp {
     Fcvtn64,
 }
 
-/// Internal type VecRRRLongOp: defined at src/isa/aarch64/inst.isle line 1225.
+/// Internal type VecRRRLongOp: defined at src/isa/aarch64/inst.isle line 1235.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum VecRRRLongOp {
     Smull8,
@@ -929,13 +934,13 @@ pub enum VecRRRLongOp {
     Umlal32,
 }
 
-/// Internal type VecPairOp: defined at src/isa/aarch64/inst.isle line 1242.
+/// Internal type VecPairOp: defined at src/isa/aarch64/inst.isle line 1252.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum VecPairOp {
     Addp,
 }
 
-/// Internal type VecRRPairLongOp: defined at src/isa/aarch64/inst.isle line 1250.
+/// Internal type VecRRPairLongOp: defined at src/isa/aarch64/inst.isle line 1260.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum VecRRPairLongOp {
     Saddlp8,
@@ -944,14 +949,14 @@ pub enum VecRRPairLongOp {
     Uaddlp16,
 }
 
-/// Internal type VecLanesOp: defined at src/isa/aarch64/inst.isle line 1261.
+/// Internal type VecLanesOp: defined at src/isa/aarch64/inst.isle line 1271.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum VecLanesOp {
     Addv,
     Uminv,
 }
 
-/// Internal type VecShiftImmOp: defined at src/isa/aarch64/inst.isle line 1270.
+/// Internal type VecShiftImmOp: defined at src/isa/aarch64/inst.isle line 1280.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum VecShiftImmOp {
     Shl,
@@ -959,7 +964,7 @@ pub enum VecShiftImmOp {
     Sshr,
 }
 
-/// Internal type AtomicRMWOp: defined at src/isa/aarch64/inst.isle line 1281.
+/// Internal type AtomicRMWOp: defined at src/isa/aarch64/inst.isle line 1291.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum AtomicRMWOp {
     Add,
@@ -1089,28 +1094,28 @@ pub fn constructor_vector_size<C: Context>(ctx: &mut C, arg0: Type) -> Option<Ve
     if let Some((pattern1_0, pattern1_1)) = C::multi_lane(ctx, pattern0_0) {
         if pattern1_0 == 8 {
             if pattern1_1 == 16 {
-                // Rule at src/isa/aarch64/inst.isle line 952.
+                // Rule at src/isa/aarch64/inst.isle line 962.
                 let expr0_0 = VectorSize::Size8x16;
                 return Some(expr0_0);
             }
         }
         if pattern1_0 == 16 {
             if pattern1_1 == 8 {
-                // Rule at src/isa/aarch64/inst.isle line 953.
+                // Rule at src/isa/aarch64/inst.isle line 963.
                 let expr0_0 = VectorSize::Size16x8;
                 return Some(expr0_0);
             }
         }
         if pattern1_0 == 32 {
             if pattern1_1 == 4 {
-                // Rule at src/isa/aarch64/inst.isle line 954.
+                // Rule at src/isa/aarch64/inst.isle line 964.
                 let expr0_0 = VectorSize::Size32x4;
                 return Some(expr0_0);
             }
         }
         if pattern1_0 == 64 {
             if pattern1_1 == 2 {
-                // Rule at src/isa/aarch64/inst.isle line 955.
+                // Rule at src/isa/aarch64/inst.isle line 965.
                 let expr0_0 = VectorSize::Size64x2;
                 return Some(expr0_0);
             }
@@ -1127,7 +1132,7 @@ pub fn constructor_movz<C: Context>(
 ) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1384.
+    // Rule at src/isa/aarch64/inst.isle line 1394.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::MovZ {
@@ -1148,7 +1153,7 @@ pub fn constructor_movn<C: Context>(
 ) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1391.
+    // Rule at src/isa/aarch64/inst.isle line 1401.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::MovN {
@@ -1171,7 +1176,7 @@ pub fn constructor_alu_rr_imm_logic<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1398.
+    // Rule at src/isa/aarch64/inst.isle line 1408.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::AluRRImmLogic {
@@ -1195,7 +1200,7 @@ pub fn constructor_alu_rr_imm_shift<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1405.
+    // Rule at src/isa/aarch64/inst.isle line 1415.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::AluRRImmShift {
@@ -1219,7 +1224,7 @@ pub fn constructor_alu_rrr<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1412.
+    // Rule at src/isa/aarch64/inst.isle line 1422.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::AluRRR {
@@ -1245,7 +1250,7 @@ pub fn constructor_vec_rrr<C: Context>(
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
     let pattern3_0 = arg3;
-    // Rule at src/isa/aarch64/inst.isle line 1419.
+    // Rule at src/isa/aarch64/inst.isle line 1429.
     let expr0_0: Type = I8X16;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::VecRRR {
@@ -1264,7 +1269,7 @@ pub fn constructor_vec_rrr<C: Context>(
 pub fn constructor_vec_dup<C: Context>(ctx: &mut C, arg0: Reg, arg1: &VectorSize) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1426.
+    // Rule at src/isa/aarch64/inst.isle line 1436.
     let expr0_0: Type = I8X16;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::VecDup {
@@ -1287,7 +1292,7 @@ pub fn constructor_alu_rr_imm12<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1433.
+    // Rule at src/isa/aarch64/inst.isle line 1443.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::AluRRImm12 {
@@ -1313,7 +1318,7 @@ pub fn constructor_alu_rrr_shift<C: Context>(
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
     let pattern3_0 = arg3;
-    // Rule at src/isa/aarch64/inst.isle line 1440.
+    // Rule at src/isa/aarch64/inst.isle line 1450.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::AluRRRShift {
@@ -1340,7 +1345,7 @@ pub fn constructor_alu_rrr_extend<C: Context>(
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
     let pattern3_0 = arg3;
-    // Rule at src/isa/aarch64/inst.isle line 1447.
+    // Rule at src/isa/aarch64/inst.isle line 1457.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::AluRRRExtend {
@@ -1365,7 +1370,7 @@ pub fn constructor_alu_rr_extend_reg<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1455.
+    // Rule at src/isa/aarch64/inst.isle line 1465.
     let expr0_0 = C::put_extended_in_reg(ctx, pattern2_0);
     let expr1_0 = C::get_extended_op(ctx, pattern2_0);
     let expr2_0 = constructor_alu_rrr_extend(ctx, pattern0_0, pattern1_0, expr0_0, &expr1_0)?;
@@ -1384,7 +1389,7 @@ pub fn constructor_alu_rrrr<C: Context>(
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
     let pattern3_0 = arg3;
-    // Rule at src/isa/aarch64/inst.isle line 1462.
+    // Rule at src/isa/aarch64/inst.isle line 1472.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::AluRRRR {
@@ -1403,7 +1408,7 @@ pub fn constructor_alu_rrrr<C: Context>(
 pub fn constructor_bit_rr<C: Context>(ctx: &mut C, arg0: &BitOp, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1469.
+    // Rule at src/isa/aarch64/inst.isle line 1479.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::BitRR {
@@ -1424,7 +1429,7 @@ pub fn constructor_add64_with_flags<C: Context>(
 ) -> Option<ProducesFlags> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1476.
+    // Rule at src/isa/aarch64/inst.isle line 1486.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = ALUOp::AddS64;
@@ -1446,7 +1451,7 @@ pub fn constructor_add64_with_flags<C: Context>(
 pub fn constructor_adc64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<ConsumesFlags> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1483.
+    // Rule at src/isa/aarch64/inst.isle line 1493.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = ALUOp::Adc64;
@@ -1472,7 +1477,7 @@ pub fn constructor_sub64_with_flags<C: Context>(
 ) -> Option<ProducesFlags> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1490.
+    // Rule at src/isa/aarch64/inst.isle line 1500.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = ALUOp::SubS64;
@@ -1498,7 +1503,7 @@ pub fn constructor_cmp64_imm<C: Context>(
 ) -> Option<ProducesFlags> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1496.
+    // Rule at src/isa/aarch64/inst.isle line 1506.
     let expr0_0 = ALUOp::SubS64;
     let expr1_0 = C::writable_zero_reg(ctx);
     let expr2_0 = MInst::AluRRImm12 {
@@ -1519,7 +1524,7 @@ pub fn constructor_cmp64_imm<C: Context>(
 pub fn constructor_sbc64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<ConsumesFlags> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1502.
+    // Rule at src/isa/aarch64/inst.isle line 1512.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = ALUOp::Sbc64;
@@ -1547,7 +1552,7 @@ pub fn constructor_vec_misc<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1509.
+    // Rule at src/isa/aarch64/inst.isle line 1519.
     let expr0_0: Type = I8X16;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::VecMisc {
@@ -1573,7 +1578,7 @@ pub fn constructor_vec_rrr_long<C: Context>(
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
     let pattern3_0 = arg3;
-    // Rule at src/isa/aarch64/inst.isle line 1516.
+    // Rule at src/isa/aarch64/inst.isle line 1526.
     let expr0_0: Type = I8X16;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::VecRRRLong {
@@ -1602,7 +1607,7 @@ pub fn constructor_vec_rrrr_long<C: Context>(
     let pattern2_0 = arg2;
     let pattern3_0 = arg3;
     let pattern4_0 = arg4;
-    // Rule at src/isa/aarch64/inst.isle line 1526.
+    // Rule at src/isa/aarch64/inst.isle line 1536.
     let expr0_0: Type = I8X16;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::FpuMove128 {
@@ -1632,7 +1637,7 @@ pub fn constructor_vec_rr_narrow<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1534.
+    // Rule at src/isa/aarch64/inst.isle line 1544.
     let expr0_0: Type = I8X16;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::VecRRNarrow {
@@ -1656,7 +1661,7 @@ pub fn constructor_vec_rr_long<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1541.
+    // Rule at src/isa/aarch64/inst.isle line 1551.
     let expr0_0: Type = I8X16;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::VecRRLong {
@@ -1680,7 +1685,7 @@ pub fn constructor_mov_from_vec<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1548.
+    // Rule at src/isa/aarch64/inst.isle line 1558.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::MovFromVec {
@@ -1706,7 +1711,7 @@ pub fn constructor_mov_from_vec_signed<C: Context>(
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
     let pattern3_0 = arg3;
-    // Rule at src/isa/aarch64/inst.isle line 1555.
+    // Rule at src/isa/aarch64/inst.isle line 1565.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::MovFromVecSigned {
@@ -1733,7 +1738,7 @@ pub fn constructor_extend<C: Context>(
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
     let pattern3_0 = arg3;
-    // Rule at src/isa/aarch64/inst.isle line 1562.
+    // Rule at src/isa/aarch64/inst.isle line 1572.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::Extend {
@@ -1752,7 +1757,7 @@ pub fn constructor_extend<C: Context>(
 pub fn constructor_load_acquire<C: Context>(ctx: &mut C, arg0: Type, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1569.
+    // Rule at src/isa/aarch64/inst.isle line 1579.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::LoadAcquire {
@@ -1773,7 +1778,7 @@ pub fn constructor_tst64_imm<C: Context>(
 ) -> Option<ProducesFlags> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1579.
+    // Rule at src/isa/aarch64/inst.isle line 1589.
     let expr0_0 = ALUOp::AndS64;
     let expr1_0 = C::writable_zero_reg(ctx);
     let expr2_0 = MInst::AluRRImmLogic {
@@ -1800,7 +1805,7 @@ pub fn constructor_csel<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1592.
+    // Rule at src/isa/aarch64/inst.isle line 1602.
     let expr0_0: Type = I64;
     let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
     let expr2_0 = MInst::CSel {
@@ -1823,14 +1828,14 @@ pub fn constructor_add<C: Context>(ctx: &mut C, arg0: Type, arg1: Reg, arg2: Reg
     if pattern0_0 == I64 {
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
-        // Rule at src/isa/aarch64/inst.isle line 1601.
+        // Rule at src/isa/aarch64/inst.isle line 1611.
         let expr0_0 = constructor_add64(ctx, pattern2_0, pattern3_0)?;
         return Some(expr0_0);
     }
     if let Some(pattern1_0) = C::fits_in_32(ctx, pattern0_0) {
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
-        // Rule at src/isa/aarch64/inst.isle line 1600.
+        // Rule at src/isa/aarch64/inst.isle line 1610.
         let expr0_0 = constructor_add32(ctx, pattern2_0, pattern3_0)?;
         return Some(expr0_0);
     }
@@ -1841,7 +1846,7 @@ pub fn constructor_add<C: Context>(ctx: &mut C, arg0: Type, arg1: Reg, arg2: Reg
 pub fn constructor_add32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1604.
+    // Rule at src/isa/aarch64/inst.isle line 1614.
     let expr0_0 = ALUOp::Add32;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -1851,7 +1856,7 @@ pub fn constructor_add32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Optio
 pub fn constructor_add64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1607.
+    // Rule at src/isa/aarch64/inst.isle line 1617.
     let expr0_0 = ALUOp::Add64;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -1868,14 +1873,14 @@ pub fn constructor_add_imm<C: Context>(
     if pattern0_0 == I64 {
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
-        // Rule at src/isa/aarch64/inst.isle line 1611.
+        // Rule at src/isa/aarch64/inst.isle line 1621.
         let expr0_0 = constructor_add64_imm(ctx, pattern2_0, pattern3_0)?;
         return Some(expr0_0);
     }
     if let Some(pattern1_0) = C::fits_in_32(ctx, pattern0_0) {
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
-        // Rule at src/isa/aarch64/inst.isle line 1610.
+        // Rule at src/isa/aarch64/inst.isle line 1620.
         let expr0_0 = constructor_add32_imm(ctx, pattern2_0, pattern3_0)?;
         return Some(expr0_0);
     }
@@ -1886,7 +1891,7 @@ pub fn constructor_add_imm<C: Context>(
 pub fn constructor_add32_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: Imm12) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1614.
+    // Rule at src/isa/aarch64/inst.isle line 1624.
     let expr0_0 = ALUOp::Add32;
     let expr1_0 = constructor_alu_rr_imm12(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -1896,7 +1901,7 @@ pub fn constructor_add32_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: Imm12) ->
 pub fn constructor_add64_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: Imm12) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1617.
+    // Rule at src/isa/aarch64/inst.isle line 1627.
     let expr0_0 = ALUOp::Add64;
     let expr1_0 = constructor_alu_rr_imm12(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -1913,14 +1918,14 @@ pub fn constructor_add_extend<C: Context>(
     if pattern0_0 == I64 {
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
-        // Rule at src/isa/aarch64/inst.isle line 1621.
+        // Rule at src/isa/aarch64/inst.isle line 1631.
         let expr0_0 = constructor_add64_extend(ctx, pattern2_0, pattern3_0)?;
         return Some(expr0_0);
     }
     if let Some(pattern1_0) = C::fits_in_32(ctx, pattern0_0) {
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
-        // Rule at src/isa/aarch64/inst.isle line 1620.
+        // Rule at src/isa/aarch64/inst.isle line 1630.
         let expr0_0 = constructor_add32_extend(ctx, pattern2_0, pattern3_0)?;
         return Some(expr0_0);
     }
@@ -1935,7 +1940,7 @@ pub fn constructor_add32_extend<C: Context>(
 ) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1624.
+    // Rule at src/isa/aarch64/inst.isle line 1634.
     let expr0_0 = ALUOp::Add32;
     let expr1_0 = constructor_alu_rr_extend_reg(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -1949,7 +1954,7 @@ pub fn constructor_add64_extend<C: Context>(
 ) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1627.
+    // Rule at src/isa/aarch64/inst.isle line 1637.
     let expr0_0 = ALUOp::Add64;
     let expr1_0 = constructor_alu_rr_extend_reg(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -1968,7 +1973,7 @@ pub fn constructor_add_shift<C: Context>(
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
         let pattern4_0 = arg3;
-        // Rule at src/isa/aarch64/inst.isle line 1631.
+        // Rule at src/isa/aarch64/inst.isle line 1641.
         let expr0_0 = constructor_add64_shift(ctx, pattern2_0, pattern3_0, pattern4_0)?;
         return Some(expr0_0);
     }
@@ -1976,7 +1981,7 @@ pub fn constructor_add_shift<C: Context>(
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
         let pattern4_0 = arg3;
-        // Rule at src/isa/aarch64/inst.isle line 1630.
+        // Rule at src/isa/aarch64/inst.isle line 1640.
         let expr0_0 = constructor_add32_shift(ctx, pattern2_0, pattern3_0, pattern4_0)?;
         return Some(expr0_0);
     }
@@ -1993,7 +1998,7 @@ pub fn constructor_add32_shift<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1634.
+    // Rule at src/isa/aarch64/inst.isle line 1644.
     let expr0_0 = ALUOp::Add32;
     let expr1_0 = constructor_alu_rrr_shift(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2009,7 +2014,7 @@ pub fn constructor_add64_shift<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1637.
+    // Rule at src/isa/aarch64/inst.isle line 1647.
     let expr0_0 = ALUOp::Add64;
     let expr1_0 = constructor_alu_rrr_shift(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2025,7 +2030,7 @@ pub fn constructor_add_vec<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1640.
+    // Rule at src/isa/aarch64/inst.isle line 1650.
     let expr0_0 = VecALUOp::Add;
     let expr1_0 = constructor_vec_rrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2037,14 +2042,14 @@ pub fn constructor_sub<C: Context>(ctx: &mut C, arg0: Type, arg1: Reg, arg2: Reg
     if pattern0_0 == I64 {
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
-        // Rule at src/isa/aarch64/inst.isle line 1646.
+        // Rule at src/isa/aarch64/inst.isle line 1656.
         let expr0_0 = constructor_sub64(ctx, pattern2_0, pattern3_0)?;
         return Some(expr0_0);
     }
     if let Some(pattern1_0) = C::fits_in_32(ctx, pattern0_0) {
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
-        // Rule at src/isa/aarch64/inst.isle line 1645.
+        // Rule at src/isa/aarch64/inst.isle line 1655.
         let expr0_0 = constructor_sub32(ctx, pattern2_0, pattern3_0)?;
         return Some(expr0_0);
     }
@@ -2055,7 +2060,7 @@ pub fn constructor_sub<C: Context>(ctx: &mut C, arg0: Type, arg1: Reg, arg2: Reg
 pub fn constructor_sub32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1649.
+    // Rule at src/isa/aarch64/inst.isle line 1659.
     let expr0_0 = ALUOp::Sub32;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2065,7 +2070,7 @@ pub fn constructor_sub32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Optio
 pub fn constructor_sub64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1652.
+    // Rule at src/isa/aarch64/inst.isle line 1662.
     let expr0_0 = ALUOp::Sub64;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2082,14 +2087,14 @@ pub fn constructor_sub_imm<C: Context>(
     if pattern0_0 == I64 {
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
-        // Rule at src/isa/aarch64/inst.isle line 1656.
+        // Rule at src/isa/aarch64/inst.isle line 1666.
         let expr0_0 = constructor_sub64_imm(ctx, pattern2_0, pattern3_0)?;
         return Some(expr0_0);
     }
     if let Some(pattern1_0) = C::fits_in_32(ctx, pattern0_0) {
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
-        // Rule at src/isa/aarch64/inst.isle line 1655.
+        // Rule at src/isa/aarch64/inst.isle line 1665.
         let expr0_0 = constructor_sub32_imm(ctx, pattern2_0, pattern3_0)?;
         return Some(expr0_0);
     }
@@ -2100,7 +2105,7 @@ pub fn constructor_sub_imm<C: Context>(
 pub fn constructor_sub32_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: Imm12) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1659.
+    // Rule at src/isa/aarch64/inst.isle line 1669.
     let expr0_0 = ALUOp::Sub32;
     let expr1_0 = constructor_alu_rr_imm12(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2110,7 +2115,7 @@ pub fn constructor_sub32_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: Imm12) ->
 pub fn constructor_sub64_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: Imm12) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1662.
+    // Rule at src/isa/aarch64/inst.isle line 1672.
     let expr0_0 = ALUOp::Sub64;
     let expr1_0 = constructor_alu_rr_imm12(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2127,14 +2132,14 @@ pub fn constructor_sub_extend<C: Context>(
     if pattern0_0 == I64 {
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
-        // Rule at src/isa/aarch64/inst.isle line 1666.
+        // Rule at src/isa/aarch64/inst.isle line 1676.
         let expr0_0 = constructor_sub64_extend(ctx, pattern2_0, pattern3_0)?;
         return Some(expr0_0);
     }
     if let Some(pattern1_0) = C::fits_in_32(ctx, pattern0_0) {
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
-        // Rule at src/isa/aarch64/inst.isle line 1665.
+        // Rule at src/isa/aarch64/inst.isle line 1675.
         let expr0_0 = constructor_sub32_extend(ctx, pattern2_0, pattern3_0)?;
         return Some(expr0_0);
     }
@@ -2149,7 +2154,7 @@ pub fn constructor_sub32_extend<C: Context>(
 ) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1669.
+    // Rule at src/isa/aarch64/inst.isle line 1679.
     let expr0_0 = ALUOp::Sub32;
     let expr1_0 = constructor_alu_rr_extend_reg(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2163,7 +2168,7 @@ pub fn constructor_sub64_extend<C: Context>(
 ) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1672.
+    // Rule at src/isa/aarch64/inst.isle line 1682.
     let expr0_0 = ALUOp::Sub64;
     let expr1_0 = constructor_alu_rr_extend_reg(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2182,7 +2187,7 @@ pub fn constructor_sub_shift<C: Context>(
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
         let pattern4_0 = arg3;
-        // Rule at src/isa/aarch64/inst.isle line 1676.
+        // Rule at src/isa/aarch64/inst.isle line 1686.
         let expr0_0 = constructor_sub64_shift(ctx, pattern2_0, pattern3_0, pattern4_0)?;
         return Some(expr0_0);
     }
@@ -2190,7 +2195,7 @@ pub fn constructor_sub_shift<C: Context>(
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
         let pattern4_0 = arg3;
-        // Rule at src/isa/aarch64/inst.isle line 1675.
+        // Rule at src/isa/aarch64/inst.isle line 1685.
         let expr0_0 = constructor_sub32_shift(ctx, pattern2_0, pattern3_0, pattern4_0)?;
         return Some(expr0_0);
     }
@@ -2207,7 +2212,7 @@ pub fn constructor_sub32_shift<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1679.
+    // Rule at src/isa/aarch64/inst.isle line 1689.
     let expr0_0 = ALUOp::Sub32;
     let expr1_0 = constructor_alu_rrr_shift(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2223,7 +2228,7 @@ pub fn constructor_sub64_shift<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1682.
+    // Rule at src/isa/aarch64/inst.isle line 1692.
     let expr0_0 = ALUOp::Sub64;
     let expr1_0 = constructor_alu_rrr_shift(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2239,7 +2244,7 @@ pub fn constructor_sub_vec<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1685.
+    // Rule at src/isa/aarch64/inst.isle line 1695.
     let expr0_0 = VecALUOp::Sub;
     let expr1_0 = constructor_vec_rrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2258,7 +2263,7 @@ pub fn constructor_madd<C: Context>(
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
         let pattern4_0 = arg3;
-        // Rule at src/isa/aarch64/inst.isle line 1691.
+        // Rule at src/isa/aarch64/inst.isle line 1701.
         let expr0_0 = constructor_madd64(ctx, pattern2_0, pattern3_0, pattern4_0)?;
         return Some(expr0_0);
     }
@@ -2266,7 +2271,7 @@ pub fn constructor_madd<C: Context>(
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
         let pattern4_0 = arg3;
-        // Rule at src/isa/aarch64/inst.isle line 1690.
+        // Rule at src/isa/aarch64/inst.isle line 1700.
         let expr0_0 = constructor_madd32(ctx, pattern2_0, pattern3_0, pattern4_0)?;
         return Some(expr0_0);
     }
@@ -2278,7 +2283,7 @@ pub fn constructor_madd32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg, arg2: R
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1694.
+    // Rule at src/isa/aarch64/inst.isle line 1704.
     let expr0_0 = ALUOp3::MAdd32;
     let expr1_0 = constructor_alu_rrrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2289,7 +2294,7 @@ pub fn constructor_madd64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg, arg2: R
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1697.
+    // Rule at src/isa/aarch64/inst.isle line 1707.
     let expr0_0 = ALUOp3::MAdd64;
     let expr1_0 = constructor_alu_rrrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2300,7 +2305,7 @@ pub fn constructor_msub64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg, arg2: R
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1702.
+    // Rule at src/isa/aarch64/inst.isle line 1712.
     let expr0_0 = ALUOp3::MSub64;
     let expr1_0 = constructor_alu_rrrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2316,7 +2321,7 @@ pub fn constructor_uqadd<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1706.
+    // Rule at src/isa/aarch64/inst.isle line 1716.
     let expr0_0 = VecALUOp::Uqadd;
     let expr1_0 = constructor_vec_rrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2332,7 +2337,7 @@ pub fn constructor_sqadd<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1710.
+    // Rule at src/isa/aarch64/inst.isle line 1720.
     let expr0_0 = VecALUOp::Sqadd;
     let expr1_0 = constructor_vec_rrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2348,7 +2353,7 @@ pub fn constructor_uqsub<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1714.
+    // Rule at src/isa/aarch64/inst.isle line 1724.
     let expr0_0 = VecALUOp::Uqsub;
     let expr1_0 = constructor_vec_rrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2364,7 +2369,7 @@ pub fn constructor_sqsub<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1718.
+    // Rule at src/isa/aarch64/inst.isle line 1728.
     let expr0_0 = VecALUOp::Sqsub;
     let expr1_0 = constructor_vec_rrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2374,7 +2379,7 @@ pub fn constructor_sqsub<C: Context>(
 pub fn constructor_umulh<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1722.
+    // Rule at src/isa/aarch64/inst.isle line 1732.
     let expr0_0 = ALUOp::UMulH;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2384,7 +2389,7 @@ pub fn constructor_umulh<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Optio
 pub fn constructor_smulh<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1726.
+    // Rule at src/isa/aarch64/inst.isle line 1736.
     let expr0_0 = ALUOp::SMulH;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2400,7 +2405,7 @@ pub fn constructor_mul<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1730.
+    // Rule at src/isa/aarch64/inst.isle line 1740.
     let expr0_0 = VecALUOp::Mul;
     let expr1_0 = constructor_vec_rrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2410,7 +2415,7 @@ pub fn constructor_mul<C: Context>(
 pub fn constructor_neg<C: Context>(ctx: &mut C, arg0: Reg, arg1: &VectorSize) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1734.
+    // Rule at src/isa/aarch64/inst.isle line 1744.
     let expr0_0 = VecMisc2::Neg;
     let expr1_0 = constructor_vec_misc(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2420,7 +2425,7 @@ pub fn constructor_neg<C: Context>(ctx: &mut C, arg0: Reg, arg1: &VectorSize) ->
 pub fn constructor_rev64<C: Context>(ctx: &mut C, arg0: Reg, arg1: &VectorSize) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1738.
+    // Rule at src/isa/aarch64/inst.isle line 1748.
     let expr0_0 = VecMisc2::Rev64;
     let expr1_0 = constructor_vec_misc(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2430,7 +2435,7 @@ pub fn constructor_rev64<C: Context>(ctx: &mut C, arg0: Reg, arg1: &VectorSize)
 pub fn constructor_xtn64<C: Context>(ctx: &mut C, arg0: Reg, arg1: bool) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1742.
+    // Rule at src/isa/aarch64/inst.isle line 1752.
     let expr0_0 = VecRRNarrowOp::Xtn64;
     let expr1_0 = constructor_vec_rr_narrow(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2446,7 +2451,7 @@ pub fn constructor_addp<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1746.
+    // Rule at src/isa/aarch64/inst.isle line 1756.
     let expr0_0 = VecALUOp::Addp;
     let expr1_0 = constructor_vec_rrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2456,7 +2461,7 @@ pub fn constructor_addp<C: Context>(
 pub fn constructor_shll32<C: Context>(ctx: &mut C, arg0: Reg, arg1: bool) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1750.
+    // Rule at src/isa/aarch64/inst.isle line 1760.
     let expr0_0 = VecRRLongOp::Shll32;
     let expr1_0 = constructor_vec_rr_long(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2474,7 +2479,7 @@ pub fn constructor_umlal32<C: Context>(
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
     let pattern3_0 = arg3;
-    // Rule at src/isa/aarch64/inst.isle line 1754.
+    // Rule at src/isa/aarch64/inst.isle line 1764.
     let expr0_0 = VecRRRLongOp::Umlal32;
     let expr1_0 = constructor_vec_rrrr_long(
         ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0, pattern3_0,
@@ -2492,7 +2497,7 @@ pub fn constructor_smull8<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1758.
+    // Rule at src/isa/aarch64/inst.isle line 1768.
     let expr0_0 = VecRRRLongOp::Smull8;
     let expr1_0 = constructor_vec_rrr_long(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2508,7 +2513,7 @@ pub fn constructor_umull8<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1762.
+    // Rule at src/isa/aarch64/inst.isle line 1772.
     let expr0_0 = VecRRRLongOp::Umull8;
     let expr1_0 = constructor_vec_rrr_long(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2524,7 +2529,7 @@ pub fn constructor_smull16<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1766.
+    // Rule at src/isa/aarch64/inst.isle line 1776.
     let expr0_0 = VecRRRLongOp::Smull16;
     let expr1_0 = constructor_vec_rrr_long(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2540,7 +2545,7 @@ pub fn constructor_umull16<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1770.
+    // Rule at src/isa/aarch64/inst.isle line 1780.
     let expr0_0 = VecRRRLongOp::Umull16;
     let expr1_0 = constructor_vec_rrr_long(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2556,7 +2561,7 @@ pub fn constructor_smull32<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1774.
+    // Rule at src/isa/aarch64/inst.isle line 1784.
     let expr0_0 = VecRRRLongOp::Smull32;
     let expr1_0 = constructor_vec_rrr_long(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2572,7 +2577,7 @@ pub fn constructor_umull32<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1778.
+    // Rule at src/isa/aarch64/inst.isle line 1788.
     let expr0_0 = VecRRRLongOp::Umull32;
     let expr1_0 = constructor_vec_rrr_long(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2582,7 +2587,7 @@ pub fn constructor_umull32<C: Context>(
 pub fn constructor_asr64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1782.
+    // Rule at src/isa/aarch64/inst.isle line 1792.
     let expr0_0 = ALUOp::Asr64;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2592,7 +2597,7 @@ pub fn constructor_asr64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Optio
 pub fn constructor_asr64_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmShift) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1785.
+    // Rule at src/isa/aarch64/inst.isle line 1795.
     let expr0_0 = ALUOp::Asr64;
     let expr1_0 = constructor_alu_rr_imm_shift(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2602,7 +2607,7 @@ pub fn constructor_asr64_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmShift)
 pub fn constructor_lsr32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1789.
+    // Rule at src/isa/aarch64/inst.isle line 1799.
     let expr0_0 = ALUOp::Lsr32;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2612,7 +2617,7 @@ pub fn constructor_lsr32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Optio
 pub fn constructor_lsr32_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmShift) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1792.
+    // Rule at src/isa/aarch64/inst.isle line 1802.
     let expr0_0 = ALUOp::Lsr32;
     let expr1_0 = constructor_alu_rr_imm_shift(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2622,7 +2627,7 @@ pub fn constructor_lsr32_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmShift)
 pub fn constructor_lsr64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1795.
+    // Rule at src/isa/aarch64/inst.isle line 1805.
     let expr0_0 = ALUOp::Lsr64;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2632,7 +2637,7 @@ pub fn constructor_lsr64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Optio
 pub fn constructor_lsr64_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmShift) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1798.
+    // Rule at src/isa/aarch64/inst.isle line 1808.
     let expr0_0 = ALUOp::Lsr64;
     let expr1_0 = constructor_alu_rr_imm_shift(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2642,7 +2647,7 @@ pub fn constructor_lsr64_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmShift)
 pub fn constructor_lsl32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1802.
+    // Rule at src/isa/aarch64/inst.isle line 1812.
     let expr0_0 = ALUOp::Lsl32;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2652,7 +2657,7 @@ pub fn constructor_lsl32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Optio
 pub fn constructor_lsl32_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmShift) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1805.
+    // Rule at src/isa/aarch64/inst.isle line 1815.
     let expr0_0 = ALUOp::Lsl32;
     let expr1_0 = constructor_alu_rr_imm_shift(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2662,7 +2667,7 @@ pub fn constructor_lsl32_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmShift)
 pub fn constructor_lsl64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1808.
+    // Rule at src/isa/aarch64/inst.isle line 1818.
     let expr0_0 = ALUOp::Lsl64;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2672,7 +2677,7 @@ pub fn constructor_lsl64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Optio
 pub fn constructor_lsl64_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmShift) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1811.
+    // Rule at src/isa/aarch64/inst.isle line 1821.
     let expr0_0 = ALUOp::Lsl64;
     let expr1_0 = constructor_alu_rr_imm_shift(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2682,7 +2687,7 @@ pub fn constructor_lsl64_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmShift)
 pub fn constructor_udiv64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1815.
+    // Rule at src/isa/aarch64/inst.isle line 1825.
     let expr0_0 = ALUOp::UDiv64;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2692,7 +2697,7 @@ pub fn constructor_udiv64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Opti
 pub fn constructor_sdiv64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1819.
+    // Rule at src/isa/aarch64/inst.isle line 1829.
     let expr0_0 = ALUOp::SDiv64;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2702,7 +2707,7 @@ pub fn constructor_sdiv64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Opti
 pub fn constructor_not<C: Context>(ctx: &mut C, arg0: Reg, arg1: &VectorSize) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1823.
+    // Rule at src/isa/aarch64/inst.isle line 1833.
     let expr0_0 = VecMisc2::Not;
     let expr1_0 = constructor_vec_misc(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2719,14 +2724,14 @@ pub fn constructor_orr_not<C: Context>(
     if pattern0_0 == I64 {
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
-        // Rule at src/isa/aarch64/inst.isle line 1829.
+        // Rule at src/isa/aarch64/inst.isle line 1839.
         let expr0_0 = constructor_orr_not64(ctx, pattern2_0, pattern3_0)?;
         return Some(expr0_0);
     }
     if let Some(pattern1_0) = C::fits_in_32(ctx, pattern0_0) {
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
-        // Rule at src/isa/aarch64/inst.isle line 1828.
+        // Rule at src/isa/aarch64/inst.isle line 1838.
         let expr0_0 = constructor_orr_not32(ctx, pattern2_0, pattern3_0)?;
         return Some(expr0_0);
     }
@@ -2737,7 +2742,7 @@ pub fn constructor_orr_not<C: Context>(
 pub fn constructor_orr_not32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1832.
+    // Rule at src/isa/aarch64/inst.isle line 1842.
     let expr0_0 = ALUOp::OrrNot32;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2747,7 +2752,7 @@ pub fn constructor_orr_not32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> O
 pub fn constructor_orr_not64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1835.
+    // Rule at src/isa/aarch64/inst.isle line 1845.
     let expr0_0 = ALUOp::OrrNot64;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2766,7 +2771,7 @@ pub fn constructor_orr_not_shift<C: Context>(
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
         let pattern4_0 = arg3;
-        // Rule at src/isa/aarch64/inst.isle line 1839.
+        // Rule at src/isa/aarch64/inst.isle line 1849.
         let expr0_0 = constructor_orr_not_shift64(ctx, pattern2_0, pattern3_0, pattern4_0)?;
         return Some(expr0_0);
     }
@@ -2774,7 +2779,7 @@ pub fn constructor_orr_not_shift<C: Context>(
         let pattern2_0 = arg1;
         let pattern3_0 = arg2;
         let pattern4_0 = arg3;
-        // Rule at src/isa/aarch64/inst.isle line 1838.
+        // Rule at src/isa/aarch64/inst.isle line 1848.
         let expr0_0 = constructor_orr_not_shift32(ctx, pattern2_0, pattern3_0, pattern4_0)?;
         return Some(expr0_0);
     }
@@ -2791,7 +2796,7 @@ pub fn constructor_orr_not_shift32<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1842.
+    // Rule at src/isa/aarch64/inst.isle line 1852.
     let expr0_0 = ALUOp::OrrNot32;
     let expr1_0 = constructor_alu_rrr_shift(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2807,7 +2812,7 @@ pub fn constructor_orr_not_shift64<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1845.
+    // Rule at src/isa/aarch64/inst.isle line 1855.
     let expr0_0 = ALUOp::OrrNot64;
     let expr1_0 = constructor_alu_rrr_shift(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2817,7 +2822,7 @@ pub fn constructor_orr_not_shift64<C: Context>(
 pub fn constructor_orr32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1850.
+    // Rule at src/isa/aarch64/inst.isle line 1860.
     let expr0_0 = ALUOp::Orr32;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2827,7 +2832,7 @@ pub fn constructor_orr32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Optio
 pub fn constructor_orr32_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmLogic) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1853.
+    // Rule at src/isa/aarch64/inst.isle line 1863.
     let expr0_0 = ALUOp::Orr32;
     let expr1_0 = constructor_alu_rr_imm_logic(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2837,7 +2842,7 @@ pub fn constructor_orr32_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmLogic)
 pub fn constructor_orr64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1856.
+    // Rule at src/isa/aarch64/inst.isle line 1866.
     let expr0_0 = ALUOp::Orr64;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2847,7 +2852,7 @@ pub fn constructor_orr64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Optio
 pub fn constructor_orr64_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmLogic) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1859.
+    // Rule at src/isa/aarch64/inst.isle line 1869.
     let expr0_0 = ALUOp::Orr64;
     let expr1_0 = constructor_alu_rr_imm_logic(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2863,7 +2868,7 @@ pub fn constructor_orr_vec<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1862.
+    // Rule at src/isa/aarch64/inst.isle line 1872.
     let expr0_0 = VecALUOp::Orr;
     let expr1_0 = constructor_vec_rrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2873,7 +2878,7 @@ pub fn constructor_orr_vec<C: Context>(
 pub fn constructor_and32_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmLogic) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1867.
+    // Rule at src/isa/aarch64/inst.isle line 1877.
     let expr0_0 = ALUOp::And32;
     let expr1_0 = constructor_alu_rr_imm_logic(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2889,7 +2894,7 @@ pub fn constructor_and_vec<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1870.
+    // Rule at src/isa/aarch64/inst.isle line 1880.
     let expr0_0 = VecALUOp::And;
     let expr1_0 = constructor_vec_rrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2905,7 +2910,7 @@ pub fn constructor_eor_vec<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1874.
+    // Rule at src/isa/aarch64/inst.isle line 1884.
     let expr0_0 = VecALUOp::Eor;
     let expr1_0 = constructor_vec_rrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2921,7 +2926,7 @@ pub fn constructor_bic_vec<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1878.
+    // Rule at src/isa/aarch64/inst.isle line 1888.
     let expr0_0 = VecALUOp::Bic;
     let expr1_0 = constructor_vec_rrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2937,7 +2942,7 @@ pub fn constructor_sshl<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1882.
+    // Rule at src/isa/aarch64/inst.isle line 1892.
     let expr0_0 = VecALUOp::Sshl;
     let expr1_0 = constructor_vec_rrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2953,7 +2958,7 @@ pub fn constructor_ushl<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 1886.
+    // Rule at src/isa/aarch64/inst.isle line 1896.
     let expr0_0 = VecALUOp::Ushl;
     let expr1_0 = constructor_vec_rrr(ctx, &expr0_0, pattern0_0, pattern1_0, pattern2_0)?;
     return Some(expr1_0);
@@ -2963,7 +2968,7 @@ pub fn constructor_ushl<C: Context>(
 pub fn constructor_rotr32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1891.
+    // Rule at src/isa/aarch64/inst.isle line 1901.
     let expr0_0 = ALUOp::RotR32;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2973,7 +2978,7 @@ pub fn constructor_rotr32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Opti
 pub fn constructor_rotr32_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmShift) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1894.
+    // Rule at src/isa/aarch64/inst.isle line 1904.
     let expr0_0 = ALUOp::RotR32;
     let expr1_0 = constructor_alu_rr_imm_shift(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2983,7 +2988,7 @@ pub fn constructor_rotr32_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmShift
 pub fn constructor_rotr64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1897.
+    // Rule at src/isa/aarch64/inst.isle line 1907.
     let expr0_0 = ALUOp::RotR64;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -2993,7 +2998,7 @@ pub fn constructor_rotr64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Opti
 pub fn constructor_rotr64_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmShift) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1900.
+    // Rule at src/isa/aarch64/inst.isle line 1910.
     let expr0_0 = ALUOp::RotR64;
     let expr1_0 = constructor_alu_rr_imm_shift(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -3002,7 +3007,7 @@ pub fn constructor_rotr64_imm<C: Context>(ctx: &mut C, arg0: Reg, arg1: ImmShift
 // Generated as internal constructor for term rbit32.
 pub fn constructor_rbit32<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
-    // Rule at src/isa/aarch64/inst.isle line 1905.
+    // Rule at src/isa/aarch64/inst.isle line 1915.
     let expr0_0 = BitOp::RBit32;
     let expr1_0 = constructor_bit_rr(ctx, &expr0_0, pattern0_0)?;
     return Some(expr1_0);
@@ -3011,7 +3016,7 @@ pub fn constructor_rbit32<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
 // Generated as internal constructor for term rbit64.
 pub fn constructor_rbit64<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
-    // Rule at src/isa/aarch64/inst.isle line 1908.
+    // Rule at src/isa/aarch64/inst.isle line 1918.
     let expr0_0 = BitOp::RBit64;
     let expr1_0 = constructor_bit_rr(ctx, &expr0_0, pattern0_0)?;
     return Some(expr1_0);
@@ -3020,7 +3025,7 @@ pub fn constructor_rbit64<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
 // Generated as internal constructor for term clz32.
 pub fn constructor_clz32<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
-    // Rule at src/isa/aarch64/inst.isle line 1913.
+    // Rule at src/isa/aarch64/inst.isle line 1923.
     let expr0_0 = BitOp::Clz32;
     let expr1_0 = constructor_bit_rr(ctx, &expr0_0, pattern0_0)?;
     return Some(expr1_0);
@@ -3029,7 +3034,7 @@ pub fn constructor_clz32<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
 // Generated as internal constructor for term clz64.
 pub fn constructor_clz64<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
-    // Rule at src/isa/aarch64/inst.isle line 1916.
+    // Rule at src/isa/aarch64/inst.isle line 1926.
     let expr0_0 = BitOp::Clz64;
     let expr1_0 = constructor_bit_rr(ctx, &expr0_0, pattern0_0)?;
     return Some(expr1_0);
@@ -3038,7 +3043,7 @@ pub fn constructor_clz64<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
 // Generated as internal constructor for term cls32.
 pub fn constructor_cls32<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
-    // Rule at src/isa/aarch64/inst.isle line 1921.
+    // Rule at src/isa/aarch64/inst.isle line 1931.
     let expr0_0 = BitOp::Cls32;
     let expr1_0 = constructor_bit_rr(ctx, &expr0_0, pattern0_0)?;
     return Some(expr1_0);
@@ -3047,7 +3052,7 @@ pub fn constructor_cls32<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
 // Generated as internal constructor for term cls64.
 pub fn constructor_cls64<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
-    // Rule at src/isa/aarch64/inst.isle line 1924.
+    // Rule at src/isa/aarch64/inst.isle line 1934.
     let expr0_0 = BitOp::Cls64;
     let expr1_0 = constructor_bit_rr(ctx, &expr0_0, pattern0_0)?;
     return Some(expr1_0);
@@ -3057,7 +3062,7 @@ pub fn constructor_cls64<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
 pub fn constructor_eon32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1929.
+    // Rule at src/isa/aarch64/inst.isle line 1939.
     let expr0_0 = ALUOp::EorNot32;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -3067,7 +3072,7 @@ pub fn constructor_eon32<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Optio
 pub fn constructor_eon64<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
-    // Rule at src/isa/aarch64/inst.isle line 1932.
+    // Rule at src/isa/aarch64/inst.isle line 1942.
     let expr0_0 = ALUOp::EorNot64;
     let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
     return Some(expr1_0);
@@ -3084,25 +3089,25 @@ pub fn constructor_imm<C: Context>(ctx: &mut C, arg0: Type, arg1: u64) -> Option
         };
         if let Some(pattern3_0) = closure3() {
             if let Some(pattern4_0) = C::imm_logic_from_u64(ctx, pattern2_0, pattern3_0) {
-                // Rule at src/isa/aarch64/inst.isle line 1947.
+                // Rule at src/isa/aarch64/inst.isle line 1957.
                 let expr0_0 = C::zero_reg(ctx);
                 let expr1_0 = constructor_orr64_imm(ctx, expr0_0, pattern4_0)?;
                 return Some(expr1_0);
             }
         }
         if let Some(pattern3_0) = C::move_wide_const_from_u64(ctx, pattern2_0) {
-            // Rule at src/isa/aarch64/inst.isle line 1939.
+            // Rule at src/isa/aarch64/inst.isle line 1949.
             let expr0_0 = OperandSize::Size64;
             let expr1_0 = constructor_movz(ctx, pattern3_0, &expr0_0)?;
             return Some(expr1_0);
         }
         if let Some(pattern3_0) = C::move_wide_const_from_negated_u64(ctx, pattern2_0) {
-            // Rule at src/isa/aarch64/inst.isle line 1943.
+            // Rule at src/isa/aarch64/inst.isle line 1953.
             let expr0_0 = OperandSize::Size64;
             let expr1_0 = constructor_movn(ctx, pattern3_0, &expr0_0)?;
             return Some(expr1_0);
         }
-        // Rule at src/isa/aarch64/inst.isle line 1954.
+        // Rule at src/isa/aarch64/inst.isle line 1964.
         let expr0_0 = C::load_constant64_full(ctx, pattern2_0);
         return Some(expr0_0);
     }
@@ -3114,17 +3119,17 @@ pub fn constructor_put_in_reg_sext32<C: Context>(ctx: &mut C, arg0: Value) -> Op
     let pattern0_0 = arg0;
     let pattern1_0 = C::value_type(ctx, pattern0_0);
     if pattern1_0 == I32 {
-        // Rule at src/isa/aarch64/inst.isle line 1965.
+        // Rule at src/isa/aarch64/inst.isle line 1975.
         let expr0_0 = C::put_in_reg(ctx, pattern0_0);
         return Some(expr0_0);
     }
     if pattern1_0 == I64 {
-        // Rule at src/isa/aarch64/inst.isle line 1966.
+        // Rule at src/isa/aarch64/inst.isle line 1976.
         let expr0_0 = C::put_in_reg(ctx, pattern0_0);
         return Some(expr0_0);
     }
     if let Some(pattern2_0) = C::fits_in_32(ctx, pattern1_0) {
-        // Rule at src/isa/aarch64/inst.isle line 1961.
+        // Rule at src/isa/aarch64/inst.isle line 1971.
         let expr0_0 = C::put_in_reg(ctx, pattern0_0);
         let expr1_0: bool = true;
         let expr2_0 = C::ty_bits(ctx, pattern2_0);
@@ -3140,17 +3145,17 @@ pub fn constructor_put_in_reg_zext32<C: Context>(ctx: &mut C, arg0: Value) -> Op
     let pattern0_0 = arg0;
     let pattern1_0 = C::value_type(ctx, pattern0_0);
     if pattern1_0 == I32 {
-        // Rule at src/isa/aarch64/inst.isle line 1974.
+        // Rule at src/isa/aarch64/inst.isle line 1984.
         let expr0_0 = C::put_in_reg(ctx, pattern0_0);
         return Some(expr0_0);
     }
     if pattern1_0 == I64 {
-        // Rule at src/isa/aarch64/inst.isle line 1975.
+        // Rule at src/isa/aarch64/inst.isle line 1985.
         let expr0_0 = C::put_in_reg(ctx, pattern0_0);
         return Some(expr0_0);
     }
     if let Some(pattern2_0) = C::fits_in_32(ctx, pattern1_0) {
-        // Rule at src/isa/aarch64/inst.isle line 1970.
+        // Rule at src/isa/aarch64/inst.isle line 1980.
         let expr0_0 = C::put_in_reg(ctx, pattern0_0);
         let expr1_0: bool = false;
         let expr2_0 = C::ty_bits(ctx, pattern2_0);
@@ -3166,12 +3171,12 @@ pub fn constructor_put_in_reg_sext64<C: Context>(ctx: &mut C, arg0: Value) -> Op
     let pattern0_0 = arg0;
     let pattern1_0 = C::value_type(ctx, pattern0_0);
     if pattern1_0 == I64 {
-        // Rule at src/isa/aarch64/inst.isle line 1983.
+        // Rule at src/isa/aarch64/inst.isle line 1993.
         let expr0_0 = C::put_in_reg(ctx, pattern0_0);
         return Some(expr0_0);
     }
     if let Some(pattern2_0) = C::fits_in_32(ctx, pattern1_0) {
-        // Rule at src/isa/aarch64/inst.isle line 1979.
+        // Rule at src/isa/aarch64/inst.isle line 1989.
         let expr0_0 = C::put_in_reg(ctx, pattern0_0);
         let expr1_0: bool = true;
         let expr2_0 = C::ty_bits(ctx, pattern2_0);
@@ -3187,12 +3192,12 @@ pub fn constructor_put_in_reg_zext64<C: Context>(ctx: &mut C, arg0: Value) -> Op
     let pattern0_0 = arg0;
     let pattern1_0 = C::value_type(ctx, pattern0_0);
     if pattern1_0 == I64 {
-        // Rule at src/isa/aarch64/inst.isle line 1991.
+        // Rule at src/isa/aarch64/inst.isle line 2001.
         let expr0_0 = C::put_in_reg(ctx, pattern0_0);
         return Some(expr0_0);
     }
     if let Some(pattern2_0) = C::fits_in_32(ctx, pattern1_0) {
-        // Rule at src/isa/aarch64/inst.isle line 1987.
+        // Rule at src/isa/aarch64/inst.isle line 1997.
         let expr0_0 = C::put_in_reg(ctx, pattern0_0);
         let expr1_0: bool = false;
         let expr2_0 = C::ty_bits(ctx, pattern2_0);
@@ -3206,7 +3211,7 @@ pub fn constructor_put_in_reg_zext64<C: Context>(ctx: &mut C, arg0: Value) -> Op
 // Generated as internal constructor for term trap_if_zero_divisor.
 pub fn constructor_trap_if_zero_divisor<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
     let pattern0_0 = arg0;
-    // Rule at src/isa/aarch64/inst.isle line 1996.
+    // Rule at src/isa/aarch64/inst.isle line 2006.
     let expr0_0 = C::cond_br_zero(ctx, pattern0_0);
     let expr1_0 = C::trap_code_division_by_zero(ctx);
     let expr2_0 = MInst::TrapIf {
@@ -3221,12 +3226,12 @@ pub fn constructor_trap_if_zero_divisor<C: Context>(ctx: &mut C, arg0: Reg) -> O
 pub fn constructor_size_from_ty<C: Context>(ctx: &mut C, arg0: Type) -> Option<OperandSize> {
     let pattern0_0 = arg0;
     if pattern0_0 == I64 {
-        // Rule at src/isa/aarch64/inst.isle line 2002.
+        // Rule at src/isa/aarch64/inst.isle line 2012.
         let expr0_0 = OperandSize::Size64;
         return Some(expr0_0);
     }
     if let Some(pattern1_0) = C::fits_in_32(ctx, pattern0_0) {
-        // Rule at src/isa/aarch64/inst.isle line 2001.
+        // Rule at src/isa/aarch64/inst.isle line 2011.
         let expr0_0 = OperandSize::Size32;
         return Some(expr0_0);
     }
@@ -3243,7 +3248,7 @@ pub fn constructor_trap_if_div_overflow<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 2008.
+    // Rule at src/isa/aarch64/inst.isle line 2018.
     let expr0_0 = constructor_adds_op(ctx, pattern0_0)?;
     let expr1_0 = C::writable_zero_reg(ctx);
     let expr2_0: u8 = 1;
@@ -3287,12 +3292,12 @@ pub fn constructor_trap_if_div_overflow<C: Context>(
 pub fn constructor_adds_op<C: Context>(ctx: &mut C, arg0: Type) -> Option<ALUOp> {
     let pattern0_0 = arg0;
     if pattern0_0 == I64 {
-        // Rule at src/isa/aarch64/inst.isle line 2028.
+        // Rule at src/isa/aarch64/inst.isle line 2038.
         let expr0_0 = ALUOp::AddS64;
         return Some(expr0_0);
     }
     if let Some(pattern1_0) = C::fits_in_32(ctx, pattern0_0) {
-        // Rule at src/isa/aarch64/inst.isle line 2027.
+        // Rule at src/isa/aarch64/inst.isle line 2037.
         let expr0_0 = ALUOp::AddS32;
         return Some(expr0_0);
     }
@@ -3326,7 +3331,7 @@ pub fn constructor_alu_rs_imm_logic_commutative<C: Context>(
                             C::imm_logic_from_imm64(ctx, pattern5_1, pattern7_0)
                         {
                             let pattern9_0 = arg3;
-                            // Rule at src/isa/aarch64/inst.isle line 2058.
+                            // Rule at src/isa/aarch64/inst.isle line 2068.
                             let expr0_0 = C::put_in_reg(ctx, pattern9_0);
                             let expr1_0 =
                                 constructor_alu_rr_imm_logic(ctx, pattern0_0, expr0_0, pattern8_0)?;
@@ -3357,7 +3362,7 @@ pub fn constructor_alu_rs_imm_logic_commutative<C: Context>(
                                         C::lshl_from_imm64(ctx, pattern10_1, pattern12_0)
                                     {
                                         let pattern14_0 = arg3;
-                                        // Rule at src/isa/aarch64/inst.isle line 2064.
+                                        // Rule at src/isa/aarch64/inst.isle line 2074.
                                         let expr0_0 = C::put_in_reg(ctx, pattern14_0);
                                         let expr1_0 = C::put_in_reg(ctx, pattern7_0);
                                         let expr2_0 = constructor_alu_rrr_shift(
@@ -3394,7 +3399,7 @@ pub fn constructor_alu_rs_imm_logic_commutative<C: Context>(
                         if let Some(pattern9_0) =
                             C::imm_logic_from_imm64(ctx, pattern6_1, pattern8_0)
                         {
-                            // Rule at src/isa/aarch64/inst.isle line 2056.
+                            // Rule at src/isa/aarch64/inst.isle line 2066.
                             let expr0_0 = C::put_in_reg(ctx, pattern2_0);
                             let expr1_0 =
                                 constructor_alu_rr_imm_logic(ctx, pattern0_0, expr0_0, pattern9_0)?;
@@ -3424,7 +3429,7 @@ pub fn constructor_alu_rs_imm_logic_commutative<C: Context>(
                                     if let Some(pattern14_0) =
                                         C::lshl_from_imm64(ctx, pattern11_1, pattern13_0)
                                     {
-                                        // Rule at src/isa/aarch64/inst.isle line 2062.
+                                        // Rule at src/isa/aarch64/inst.isle line 2072.
                                         let expr0_0 = C::put_in_reg(ctx, pattern2_0);
                                         let expr1_0 = C::put_in_reg(ctx, pattern8_0);
                                         let expr2_0 = constructor_alu_rrr_shift(
@@ -3445,7 +3450,7 @@ pub fn constructor_alu_rs_imm_logic_commutative<C: Context>(
             _ => {}
         }
     }
-    // Rule at src/isa/aarch64/inst.isle line 2052.
+    // Rule at src/isa/aarch64/inst.isle line 2062.
     let expr0_0 = C::put_in_reg(ctx, pattern2_0);
     let expr1_0 = C::put_in_reg(ctx, pattern3_0);
     let expr2_0 = constructor_alu_rrr(ctx, pattern0_0, expr0_0, expr1_0)?;
@@ -3479,7 +3484,7 @@ pub fn constructor_alu_rs_imm_logic<C: Context>(
                         if let Some(pattern9_0) =
                             C::imm_logic_from_imm64(ctx, pattern6_1, pattern8_0)
                         {
-                            // Rule at src/isa/aarch64/inst.isle line 2072.
+                            // Rule at src/isa/aarch64/inst.isle line 2082.
                             let expr0_0 = C::put_in_reg(ctx, pattern2_0);
                             let expr1_0 =
                                 constructor_alu_rr_imm_logic(ctx, pattern0_0, expr0_0, pattern9_0)?;
@@ -3509,7 +3514,7 @@ pub fn constructor_alu_rs_imm_logic<C: Context>(
                                     if let Some(pattern14_0) =
                                         C::lshl_from_imm64(ctx, pattern11_1, pattern13_0)
                                     {
-                                        // Rule at src/isa/aarch64/inst.isle line 2074.
+                                        // Rule at src/isa/aarch64/inst.isle line 2084.
                                         let expr0_0 = C::put_in_reg(ctx, pattern2_0);
                                         let expr1_0 = C::put_in_reg(ctx, pattern8_0);
                                         let expr2_0 = constructor_alu_rrr_shift(
@@ -3530,7 +3535,7 @@ pub fn constructor_alu_rs_imm_logic<C: Context>(
             _ => {}
         }
     }
-    // Rule at src/isa/aarch64/inst.isle line 2070.
+    // Rule at src/isa/aarch64/inst.isle line 2080.
     let expr0_0 = C::put_in_reg(ctx, pattern2_0);
     let expr1_0 = C::put_in_reg(ctx, pattern3_0);
     let expr2_0 = constructor_alu_rrr(ctx, pattern0_0, expr0_0, expr1_0)?;
@@ -3547,7 +3552,7 @@ pub fn constructor_i128_alu_bitop<C: Context>(
     let pattern0_0 = arg0;
     let pattern1_0 = arg1;
     let pattern2_0 = arg2;
-    // Rule at src/isa/aarch64/inst.isle line 2082.
+    // Rule at src/isa/aarch64/inst.isle line 2092.
     let expr0_0 = C::put_in_regs(ctx, pattern1_0);
     let expr1_0: usize = 0;
     let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
@@ -3569,912 +3574,561 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
     let pattern0_0 = arg0;
     if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
         let pattern2_0 = C::value_type(ctx, pattern1_0);
-        if pattern2_0 == I8 {
+        if pattern2_0 == I128 {
             let pattern4_0 = C::inst_data(ctx, pattern0_0);
-            if let &InstructionData::Unary {
+            if let &InstructionData::Binary {
                 opcode: ref pattern5_0,
-                arg: pattern5_1,
+                args: ref pattern5_1,
             } = &pattern4_0
             {
-                match &pattern5_0 {
-                    &Opcode::Bitrev => {
-                        // Rule at src/isa/aarch64/lower.isle line 1013.
-                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
-                        let expr1_0 = constructor_rbit32(ctx, expr0_0)?;
-                        let expr2_0: u8 = 24;
-                        let expr3_0 = C::imm_shift_from_u8(ctx, expr2_0);
-                        let expr4_0 = constructor_lsr32_imm(ctx, expr1_0, expr3_0)?;
-                        let expr5_0 = C::value_reg(ctx, expr4_0);
-                        return Some(expr5_0);
-                    }
-                    &Opcode::Clz => {
-                        // Rule at src/isa/aarch64/lower.isle line 1038.
-                        let expr0_0 = constructor_put_in_reg_zext32(ctx, pattern5_1)?;
-                        let expr1_0 = constructor_clz32(ctx, expr0_0)?;
-                        let expr2_0: u8 = 24;
-                        let expr3_0 = C::u8_into_imm12(ctx, expr2_0);
-                        let expr4_0 = constructor_sub32_imm(ctx, expr1_0, expr3_0)?;
-                        let expr5_0 = C::value_reg(ctx, expr4_0);
-                        return Some(expr5_0);
-                    }
-                    &Opcode::Cls => {
-                        // Rule at src/isa/aarch64/lower.isle line 1095.
-                        let expr0_0 = constructor_put_in_reg_zext32(ctx, pattern5_1)?;
-                        let expr1_0 = constructor_cls32(ctx, expr0_0)?;
-                        let expr2_0: u8 = 24;
-                        let expr3_0 = C::u8_into_imm12(ctx, expr2_0);
-                        let expr4_0 = constructor_sub32_imm(ctx, expr1_0, expr3_0)?;
-                        let expr5_0 = C::value_reg(ctx, expr4_0);
-                        return Some(expr5_0);
-                    }
-                    &Opcode::Ctz => {
-                        // Rule at src/isa/aarch64/lower.isle line 1073.
-                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
-                        let expr1_0 = constructor_rbit32(ctx, expr0_0)?;
-                        let expr2_0: Type = I32;
-                        let expr3_0: u64 = 8388608;
-                        let expr4_0 = C::u64_into_imm_logic(ctx, expr2_0, expr3_0);
-                        let expr5_0 = constructor_orr32_imm(ctx, expr1_0, expr4_0)?;
-                        let expr6_0 = constructor_clz32(ctx, expr5_0)?;
-                        let expr7_0 = C::value_reg(ctx, expr6_0);
-                        return Some(expr7_0);
-                    }
-                    _ => {}
+                if let &Opcode::Iadd = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    // Rule at src/isa/aarch64/lower.isle line 79.
+                    let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                    let expr1_0: usize = 0;
+                    let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                    let expr3_0: usize = 1;
+                    let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                    let expr5_0 = C::put_in_regs(ctx, pattern7_1);
+                    let expr6_0: usize = 0;
+                    let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
+                    let expr8_0: usize = 1;
+                    let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
+                    let expr10_0 = constructor_add64_with_flags(ctx, expr2_0, expr7_0)?;
+                    let expr11_0 = constructor_adc64(ctx, expr4_0, expr9_0)?;
+                    let expr12_0 = constructor_with_flags(ctx, &expr10_0, &expr11_0)?;
+                    return Some(expr12_0);
                 }
             }
         }
-        if pattern2_0 == I16 {
+        if let Some((pattern3_0, pattern3_1)) = C::multi_lane(ctx, pattern2_0) {
             let pattern4_0 = C::inst_data(ctx, pattern0_0);
-            if let &InstructionData::Unary {
+            if let &InstructionData::Binary {
                 opcode: ref pattern5_0,
-                arg: pattern5_1,
+                args: ref pattern5_1,
             } = &pattern4_0
             {
-                match &pattern5_0 {
-                    &Opcode::Bitrev => {
-                        // Rule at src/isa/aarch64/lower.isle line 1019.
-                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
-                        let expr1_0 = constructor_rbit32(ctx, expr0_0)?;
-                        let expr2_0: u8 = 16;
-                        let expr3_0 = C::imm_shift_from_u8(ctx, expr2_0);
-                        let expr4_0 = constructor_lsr32_imm(ctx, expr1_0, expr3_0)?;
-                        let expr5_0 = C::value_reg(ctx, expr4_0);
-                        return Some(expr5_0);
-                    }
-                    &Opcode::Clz => {
-                        // Rule at src/isa/aarch64/lower.isle line 1041.
-                        let expr0_0 = constructor_put_in_reg_zext32(ctx, pattern5_1)?;
-                        let expr1_0 = constructor_clz32(ctx, expr0_0)?;
-                        let expr2_0: u8 = 16;
-                        let expr3_0 = C::u8_into_imm12(ctx, expr2_0);
-                        let expr4_0 = constructor_sub32_imm(ctx, expr1_0, expr3_0)?;
-                        let expr5_0 = C::value_reg(ctx, expr4_0);
-                        return Some(expr5_0);
-                    }
-                    &Opcode::Cls => {
-                        // Rule at src/isa/aarch64/lower.isle line 1098.
-                        let expr0_0 = constructor_put_in_reg_zext32(ctx, pattern5_1)?;
-                        let expr1_0 = constructor_cls32(ctx, expr0_0)?;
-                        let expr2_0: u8 = 16;
-                        let expr3_0 = C::u8_into_imm12(ctx, expr2_0);
-                        let expr4_0 = constructor_sub32_imm(ctx, expr1_0, expr3_0)?;
-                        let expr5_0 = C::value_reg(ctx, expr4_0);
-                        return Some(expr5_0);
-                    }
-                    &Opcode::Ctz => {
-                        // Rule at src/isa/aarch64/lower.isle line 1076.
-                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
-                        let expr1_0 = constructor_rbit32(ctx, expr0_0)?;
-                        let expr2_0: Type = I32;
-                        let expr3_0: u64 = 32768;
-                        let expr4_0 = C::u64_into_imm_logic(ctx, expr2_0, expr3_0);
-                        let expr5_0 = constructor_orr32_imm(ctx, expr1_0, expr4_0)?;
-                        let expr6_0 = constructor_clz32(ctx, expr5_0)?;
-                        let expr7_0 = C::value_reg(ctx, expr6_0);
-                        return Some(expr7_0);
-                    }
-                    _ => {}
+                if let &Opcode::Iadd = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    // Rule at src/isa/aarch64/lower.isle line 75.
+                    let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                    let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                    let expr2_0 = constructor_vector_size(ctx, pattern2_0)?;
+                    let expr3_0 = constructor_add_vec(ctx, expr0_0, expr1_0, &expr2_0)?;
+                    let expr4_0 = C::value_reg(ctx, expr3_0);
+                    return Some(expr4_0);
                 }
             }
         }
-        if pattern2_0 == I32 {
+        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
             let pattern4_0 = C::inst_data(ctx, pattern0_0);
-            match &pattern4_0 {
-                &InstructionData::Binary {
-                    opcode: ref pattern5_0,
-                    args: ref pattern5_1,
-                } => {
-                    match &pattern5_0 {
-                        &Opcode::Rotl => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
-                                let pattern9_0 = C::inst_data(ctx, pattern8_0);
-                                if let &InstructionData::UnaryImm {
-                                    opcode: ref pattern10_0,
-                                    imm: pattern10_1,
-                                } = &pattern9_0
-                                {
-                                    if let &Opcode::Iconst = &pattern10_0 {
-                                        let closure12 = || {
-                                            let expr0_0: Type = I32;
-                                            return Some(expr0_0);
-                                        };
-                                        if let Some(pattern12_0) = closure12() {
-                                            if let Some(pattern13_0) = C::imm_shift_from_imm64(
-                                                ctx,
-                                                pattern10_1,
-                                                pattern12_0,
-                                            ) {
-                                                // Rule at src/isa/aarch64/lower.isle line 896.
-                                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                                let expr1_0: Type = I32;
-                                                let expr2_0 =
-                                                    C::negate_imm_shift(ctx, expr1_0, pattern13_0);
-                                                let expr3_0 =
-                                                    constructor_rotr32_imm(ctx, expr0_0, expr2_0)?;
-                                                let expr4_0 = C::value_reg(ctx, expr3_0);
-                                                return Some(expr4_0);
-                                            }
-                                        }
-                                    }
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Iadd = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    if let Some(pattern8_0) = C::def_inst(ctx, pattern7_0) {
+                        let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                        if let &InstructionData::UnaryImm {
+                            opcode: ref pattern10_0,
+                            imm: pattern10_1,
+                        } = &pattern9_0
+                        {
+                            if let &Opcode::Iconst = &pattern10_0 {
+                                let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
+                                if let Some(pattern13_0) = C::imm12_from_u64(ctx, pattern12_0) {
+                                    // Rule at src/isa/aarch64/lower.isle line 37.
+                                    let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                                    let expr1_0 =
+                                        constructor_add_imm(ctx, pattern3_0, expr0_0, pattern13_0)?;
+                                    let expr2_0 = C::value_reg(ctx, expr1_0);
+                                    return Some(expr2_0);
                                 }
                             }
-                            // Rule at src/isa/aarch64/lower.isle line 886.
-                            let expr0_0 = C::zero_reg(ctx);
-                            let expr1_0 = C::put_in_reg(ctx, pattern7_1);
-                            let expr2_0 = constructor_sub32(ctx, expr0_0, expr1_0)?;
-                            let expr3_0 = C::put_in_reg(ctx, pattern7_0);
-                            let expr4_0 = constructor_rotr32(ctx, expr3_0, expr2_0)?;
-                            let expr5_0 = C::value_reg(ctx, expr4_0);
-                            return Some(expr5_0);
                         }
-                        &Opcode::Rotr => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
-                                let pattern9_0 = C::inst_data(ctx, pattern8_0);
-                                if let &InstructionData::UnaryImm {
-                                    opcode: ref pattern10_0,
-                                    imm: pattern10_1,
-                                } = &pattern9_0
-                                {
-                                    if let &Opcode::Iconst = &pattern10_0 {
-                                        let closure12 = || {
-                                            let expr0_0: Type = I32;
-                                            return Some(expr0_0);
-                                        };
-                                        if let Some(pattern12_0) = closure12() {
-                                            if let Some(pattern13_0) = C::imm_shift_from_imm64(
-                                                ctx,
-                                                pattern10_1,
-                                                pattern12_0,
-                                            ) {
-                                                // Rule at src/isa/aarch64/lower.isle line 940.
-                                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                                let expr1_0 = constructor_rotr32_imm(
-                                                    ctx,
-                                                    expr0_0,
-                                                    pattern13_0,
-                                                )?;
-                                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                                return Some(expr2_0);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            // Rule at src/isa/aarch64/lower.isle line 928.
-                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                            let expr1_0 = C::put_in_reg(ctx, pattern7_1);
-                            let expr2_0 = constructor_rotr32(ctx, expr0_0, expr1_0)?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
-                        _ => {}
                     }
                 }
-                &InstructionData::Unary {
-                    opcode: ref pattern5_0,
-                    arg: pattern5_1,
-                } => {
-                    match &pattern5_0 {
-                        &Opcode::Bitrev => {
-                            // Rule at src/isa/aarch64/lower.isle line 1022.
-                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
-                            let expr1_0 = constructor_rbit32(ctx, expr0_0)?;
-                            let expr2_0 = C::value_reg(ctx, expr1_0);
-                            return Some(expr2_0);
-                        }
-                        &Opcode::Clz => {
-                            // Rule at src/isa/aarch64/lower.isle line 1044.
-                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
-                            let expr1_0 = constructor_clz32(ctx, expr0_0)?;
-                            let expr2_0 = C::value_reg(ctx, expr1_0);
-                            return Some(expr2_0);
-                        }
-                        &Opcode::Cls => {
-                            // Rule at src/isa/aarch64/lower.isle line 1101.
-                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
-                            let expr1_0 = constructor_cls32(ctx, expr0_0)?;
-                            let expr2_0 = C::value_reg(ctx, expr1_0);
-                            return Some(expr2_0);
-                        }
-                        &Opcode::Ctz => {
-                            // Rule at src/isa/aarch64/lower.isle line 1079.
-                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
-                            let expr1_0 = constructor_rbit32(ctx, expr0_0)?;
-                            let expr2_0 = constructor_clz32(ctx, expr1_0)?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
-                        _ => {}
-                    }
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if pattern2_0 == I128 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Isub = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    // Rule at src/isa/aarch64/lower.isle line 130.
+                    let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                    let expr1_0: usize = 0;
+                    let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                    let expr3_0: usize = 1;
+                    let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                    let expr5_0 = C::put_in_regs(ctx, pattern7_1);
+                    let expr6_0: usize = 0;
+                    let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
+                    let expr8_0: usize = 1;
+                    let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
+                    let expr10_0 = constructor_sub64_with_flags(ctx, expr2_0, expr7_0)?;
+                    let expr11_0 = constructor_sbc64(ctx, expr4_0, expr9_0)?;
+                    let expr12_0 = constructor_with_flags(ctx, &expr10_0, &expr11_0)?;
+                    return Some(expr12_0);
                 }
-                _ => {}
             }
         }
-        if pattern2_0 == I64 {
+        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
             let pattern4_0 = C::inst_data(ctx, pattern0_0);
-            match &pattern4_0 {
-                &InstructionData::Binary {
-                    opcode: ref pattern5_0,
-                    args: ref pattern5_1,
-                } => {
-                    match &pattern5_0 {
-                        &Opcode::Umulhi => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 374.
-                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                            let expr1_0 = C::put_in_reg(ctx, pattern7_1);
-                            let expr2_0 = constructor_umulh(ctx, expr0_0, expr1_0)?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
-                        &Opcode::Smulhi => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 360.
-                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                            let expr1_0 = C::put_in_reg(ctx, pattern7_1);
-                            let expr2_0 = constructor_smulh(ctx, expr0_0, expr1_0)?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
-                        &Opcode::Band => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 609.
-                            let expr0_0 = ALUOp::And64;
-                            let expr1_0: Type = I64;
-                            let expr2_0 = constructor_alu_rs_imm_logic_commutative(
-                                ctx, &expr0_0, expr1_0, pattern7_0, pattern7_1,
-                            )?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
-                        &Opcode::Bor => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 622.
-                            let expr0_0 = ALUOp::Orr64;
-                            let expr1_0: Type = I64;
-                            let expr2_0 = constructor_alu_rs_imm_logic_commutative(
-                                ctx, &expr0_0, expr1_0, pattern7_0, pattern7_1,
-                            )?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
-                        &Opcode::Bxor => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 635.
-                            let expr0_0 = ALUOp::Eor64;
-                            let expr1_0: Type = I64;
-                            let expr2_0 = constructor_alu_rs_imm_logic_commutative(
-                                ctx, &expr0_0, expr1_0, pattern7_0, pattern7_1,
-                            )?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
-                        &Opcode::BandNot => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 648.
-                            let expr0_0 = ALUOp::AndNot64;
-                            let expr1_0: Type = I64;
-                            let expr2_0 = constructor_alu_rs_imm_logic(
-                                ctx, &expr0_0, expr1_0, pattern7_0, pattern7_1,
-                            )?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
-                        &Opcode::BorNot => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 661.
-                            let expr0_0 = ALUOp::OrrNot64;
-                            let expr1_0: Type = I64;
-                            let expr2_0 = constructor_alu_rs_imm_logic(
-                                ctx, &expr0_0, expr1_0, pattern7_0, pattern7_1,
-                            )?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
-                        &Opcode::BxorNot => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 671.
-                            let expr0_0 = ALUOp::EorNot64;
-                            let expr1_0: Type = I64;
-                            let expr2_0 = constructor_alu_rs_imm_logic(
-                                ctx, &expr0_0, expr1_0, pattern7_0, pattern7_1,
-                            )?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
-                        &Opcode::Rotl => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
-                                let pattern9_0 = C::inst_data(ctx, pattern8_0);
-                                if let &InstructionData::UnaryImm {
-                                    opcode: ref pattern10_0,
-                                    imm: pattern10_1,
-                                } = &pattern9_0
-                                {
-                                    if let &Opcode::Iconst = &pattern10_0 {
-                                        let closure12 = || {
-                                            let expr0_0: Type = I64;
-                                            return Some(expr0_0);
-                                        };
-                                        if let Some(pattern12_0) = closure12() {
-                                            if let Some(pattern13_0) = C::imm_shift_from_imm64(
-                                                ctx,
-                                                pattern10_1,
-                                                pattern12_0,
-                                            ) {
-                                                // Rule at src/isa/aarch64/lower.isle line 900.
-                                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                                let expr1_0: Type = I64;
-                                                let expr2_0 =
-                                                    C::negate_imm_shift(ctx, expr1_0, pattern13_0);
-                                                let expr3_0 =
-                                                    constructor_rotr64_imm(ctx, expr0_0, expr2_0)?;
-                                                let expr4_0 = C::value_reg(ctx, expr3_0);
-                                                return Some(expr4_0);
-                                            }
-                                        }
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Iadd = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    if let Some(pattern8_0) = C::def_inst(ctx, pattern7_0) {
+                        let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                        match &pattern9_0 {
+                            &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } => {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
+                                    if let Some(pattern13_0) =
+                                        C::imm12_from_negated_u64(ctx, pattern12_0)
+                                    {
+                                        // Rule at src/isa/aarch64/lower.isle line 45.
+                                        let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                                        let expr1_0 = constructor_sub_imm(
+                                            ctx,
+                                            pattern3_0,
+                                            expr0_0,
+                                            pattern13_0,
+                                        )?;
+                                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                                        return Some(expr2_0);
                                     }
                                 }
                             }
-                            // Rule at src/isa/aarch64/lower.isle line 891.
-                            let expr0_0 = C::zero_reg(ctx);
-                            let expr1_0 = C::put_in_reg(ctx, pattern7_1);
-                            let expr2_0 = constructor_sub64(ctx, expr0_0, expr1_0)?;
-                            let expr3_0 = C::put_in_reg(ctx, pattern7_0);
-                            let expr4_0 = constructor_rotr64(ctx, expr3_0, expr2_0)?;
-                            let expr5_0 = C::value_reg(ctx, expr4_0);
-                            return Some(expr5_0);
-                        }
-                        &Opcode::Rotr => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
-                                let pattern9_0 = C::inst_data(ctx, pattern8_0);
-                                if let &InstructionData::UnaryImm {
-                                    opcode: ref pattern10_0,
-                                    imm: pattern10_1,
-                                } = &pattern9_0
-                                {
-                                    if let &Opcode::Iconst = &pattern10_0 {
-                                        let closure12 = || {
-                                            let expr0_0: Type = I64;
-                                            return Some(expr0_0);
-                                        };
-                                        if let Some(pattern12_0) = closure12() {
-                                            if let Some(pattern13_0) = C::imm_shift_from_imm64(
-                                                ctx,
-                                                pattern10_1,
-                                                pattern12_0,
-                                            ) {
-                                                // Rule at src/isa/aarch64/lower.isle line 944.
-                                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                                let expr1_0 = constructor_rotr64_imm(
-                                                    ctx,
-                                                    expr0_0,
-                                                    pattern13_0,
-                                                )?;
-                                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                                return Some(expr2_0);
+                            &InstructionData::Binary {
+                                opcode: ref pattern10_0,
+                                args: ref pattern10_1,
+                            } => {
+                                match &pattern10_0 {
+                                    &Opcode::Imul => {
+                                        let (pattern12_0, pattern12_1) =
+                                            C::unpack_value_array_2(ctx, &pattern10_1);
+                                        // Rule at src/isa/aarch64/lower.isle line 70.
+                                        let expr0_0 = C::put_in_reg(ctx, pattern12_0);
+                                        let expr1_0 = C::put_in_reg(ctx, pattern12_1);
+                                        let expr2_0 = C::put_in_reg(ctx, pattern7_1);
+                                        let expr3_0 = constructor_madd(
+                                            ctx, pattern3_0, expr0_0, expr1_0, expr2_0,
+                                        )?;
+                                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                                        return Some(expr4_0);
+                                    }
+                                    &Opcode::Ishl => {
+                                        let (pattern12_0, pattern12_1) =
+                                            C::unpack_value_array_2(ctx, &pattern10_1);
+                                        if let Some(pattern13_0) = C::def_inst(ctx, pattern12_1) {
+                                            let pattern14_0 = C::inst_data(ctx, pattern13_0);
+                                            if let &InstructionData::UnaryImm {
+                                                opcode: ref pattern15_0,
+                                                imm: pattern15_1,
+                                            } = &pattern14_0
+                                            {
+                                                if let &Opcode::Iconst = &pattern15_0 {
+                                                    let closure17 = || {
+                                                        return Some(pattern3_0);
+                                                    };
+                                                    if let Some(pattern17_0) = closure17() {
+                                                        if let Some(pattern18_0) =
+                                                            C::lshl_from_imm64(
+                                                                ctx,
+                                                                pattern15_1,
+                                                                pattern17_0,
+                                                            )
+                                                        {
+                                                            // Rule at src/isa/aarch64/lower.isle line 62.
+                                                            let expr0_0 =
+                                                                C::put_in_reg(ctx, pattern7_1);
+                                                            let expr1_0 =
+                                                                C::put_in_reg(ctx, pattern12_0);
+                                                            let expr2_0 = constructor_add_shift(
+                                                                ctx,
+                                                                pattern3_0,
+                                                                expr0_0,
+                                                                expr1_0,
+                                                                pattern18_0,
+                                                            )?;
+                                                            let expr3_0 =
+                                                                C::value_reg(ctx, expr2_0);
+                                                            return Some(expr3_0);
+                                                        }
+                                                    }
+                                                }
                                             }
                                         }
                                     }
+                                    _ => {}
                                 }
                             }
-                            // Rule at src/isa/aarch64/lower.isle line 932.
-                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                            let expr1_0 = C::put_in_reg(ctx, pattern7_1);
-                            let expr2_0 = constructor_rotr64(ctx, expr0_0, expr1_0)?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
-                        &Opcode::Ishl => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 683.
-                            let expr0_0 = ALUOp::Lsl64;
-                            let expr1_0: Type = I64;
-                            let expr2_0 = C::put_in_reg(ctx, pattern7_0);
-                            let expr3_0 =
-                                constructor_do_shift(ctx, &expr0_0, expr1_0, expr2_0, pattern7_1)?;
-                            let expr4_0 = C::value_reg(ctx, expr3_0);
-                            return Some(expr4_0);
-                        }
-                        &Opcode::Ushr => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 771.
-                            let expr0_0 = ALUOp::Lsr64;
-                            let expr1_0: Type = I64;
-                            let expr2_0 = constructor_put_in_reg_zext64(ctx, pattern7_0)?;
-                            let expr3_0 =
-                                constructor_do_shift(ctx, &expr0_0, expr1_0, expr2_0, pattern7_1)?;
-                            let expr4_0 = C::value_reg(ctx, expr3_0);
-                            return Some(expr4_0);
+                            _ => {}
                         }
-                        &Opcode::Sshr => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 820.
-                            let expr0_0 = ALUOp::Asr64;
-                            let expr1_0: Type = I64;
-                            let expr2_0 = constructor_put_in_reg_sext64(ctx, pattern7_0)?;
-                            let expr3_0 =
-                                constructor_do_shift(ctx, &expr0_0, expr1_0, expr2_0, pattern7_1)?;
-                            let expr4_0 = C::value_reg(ctx, expr3_0);
-                            return Some(expr4_0);
-                        }
-                        _ => {}
                     }
                 }
-                &InstructionData::Unary {
-                    opcode: ref pattern5_0,
-                    arg: pattern5_1,
-                } => {
-                    match &pattern5_0 {
-                        &Opcode::Bitrev => {
-                            // Rule at src/isa/aarch64/lower.isle line 1025.
-                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
-                            let expr1_0 = constructor_rbit64(ctx, expr0_0)?;
-                            let expr2_0 = C::value_reg(ctx, expr1_0);
-                            return Some(expr2_0);
-                        }
-                        &Opcode::Clz => {
-                            // Rule at src/isa/aarch64/lower.isle line 1047.
-                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
-                            let expr1_0 = constructor_clz64(ctx, expr0_0)?;
-                            let expr2_0 = C::value_reg(ctx, expr1_0);
-                            return Some(expr2_0);
-                        }
-                        &Opcode::Cls => {
-                            // Rule at src/isa/aarch64/lower.isle line 1104.
-                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
-                            let expr1_0 = constructor_cls64(ctx, expr0_0)?;
-                            let expr2_0 = C::value_reg(ctx, expr1_0);
-                            return Some(expr2_0);
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if pattern2_0 == I128 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Unary {
+                opcode: ref pattern5_0,
+                arg: pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Uextend => {
+                        if let Some(pattern7_0) = C::def_inst(ctx, pattern5_1) {
+                            let pattern8_0 = C::inst_data(ctx, pattern7_0);
+                            if let &InstructionData::BinaryImm8 {
+                                opcode: ref pattern9_0,
+                                arg: pattern9_1,
+                                imm: pattern9_2,
+                            } = &pattern8_0
+                            {
+                                if let &Opcode::Extractlane = &pattern9_0 {
+                                    let pattern11_0 = C::value_type(ctx, pattern9_1);
+                                    let pattern12_0 = C::u8_from_uimm8(ctx, pattern9_2);
+                                    // Rule at src/isa/aarch64/lower.isle line 514.
+                                    let expr0_0 = C::put_in_reg(ctx, pattern9_1);
+                                    let expr1_0 = constructor_vector_size(ctx, pattern11_0)?;
+                                    let expr2_0 = constructor_mov_from_vec(
+                                        ctx,
+                                        expr0_0,
+                                        pattern12_0,
+                                        &expr1_0,
+                                    )?;
+                                    let expr3_0: Type = I64;
+                                    let expr4_0: u64 = 0;
+                                    let expr5_0 = constructor_imm(ctx, expr3_0, expr4_0)?;
+                                    let expr6_0 = C::value_regs(ctx, expr2_0, expr5_0);
+                                    return Some(expr6_0);
+                                }
+                            }
                         }
-                        &Opcode::Ctz => {
-                            // Rule at src/isa/aarch64/lower.isle line 1082.
-                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
-                            let expr1_0 = constructor_rbit64(ctx, expr0_0)?;
-                            let expr2_0 = constructor_clz64(ctx, expr1_0)?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
+                    }
+                    &Opcode::Sextend => {
+                        if let Some(pattern7_0) = C::def_inst(ctx, pattern5_1) {
+                            let pattern8_0 = C::inst_data(ctx, pattern7_0);
+                            if let &InstructionData::BinaryImm8 {
+                                opcode: ref pattern9_0,
+                                arg: pattern9_1,
+                                imm: pattern9_2,
+                            } = &pattern8_0
+                            {
+                                if let &Opcode::Extractlane = &pattern9_0 {
+                                    let pattern11_0 = C::value_type(ctx, pattern9_1);
+                                    if pattern11_0 == I64X2 {
+                                        let pattern13_0 = C::u8_from_uimm8(ctx, pattern9_2);
+                                        // Rule at src/isa/aarch64/lower.isle line 562.
+                                        let expr0_0 = C::put_in_reg(ctx, pattern9_1);
+                                        let expr1_0 = VectorSize::Size64x2;
+                                        let expr2_0 = constructor_mov_from_vec(
+                                            ctx,
+                                            expr0_0,
+                                            pattern13_0,
+                                            &expr1_0,
+                                        )?;
+                                        let expr3_0: u8 = 63;
+                                        let expr4_0 = C::imm_shift_from_u8(ctx, expr3_0);
+                                        let expr5_0 = constructor_asr64_imm(ctx, expr2_0, expr4_0)?;
+                                        let expr6_0 = C::value_regs(ctx, expr2_0, expr5_0);
+                                        return Some(expr6_0);
+                                    }
+                                }
+                            }
                         }
-                        _ => {}
                     }
+                    _ => {}
                 }
-                _ => {}
             }
         }
-        if pattern2_0 == I128 {
+        if let Some((pattern3_0, pattern3_1)) = C::multi_lane(ctx, pattern2_0) {
             let pattern4_0 = C::inst_data(ctx, pattern0_0);
-            match &pattern4_0 {
-                &InstructionData::Binary {
-                    opcode: ref pattern5_0,
-                    args: ref pattern5_1,
-                } => {
-                    match &pattern5_0 {
-                        &Opcode::Iadd => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 79.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0: usize = 0;
-                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
-                            let expr3_0: usize = 1;
-                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
-                            let expr5_0 = C::put_in_regs(ctx, pattern7_1);
-                            let expr6_0: usize = 0;
-                            let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
-                            let expr8_0: usize = 1;
-                            let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
-                            let expr10_0 = constructor_add64_with_flags(ctx, expr2_0, expr7_0)?;
-                            let expr11_0 = constructor_adc64(ctx, expr4_0, expr9_0)?;
-                            let expr12_0 = constructor_with_flags(ctx, &expr10_0, &expr11_0)?;
-                            return Some(expr12_0);
-                        }
-                        &Opcode::Isub => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 130.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0: usize = 0;
-                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
-                            let expr3_0: usize = 1;
-                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
-                            let expr5_0 = C::put_in_regs(ctx, pattern7_1);
-                            let expr6_0: usize = 0;
-                            let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
-                            let expr8_0: usize = 1;
-                            let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
-                            let expr10_0 = constructor_sub64_with_flags(ctx, expr2_0, expr7_0)?;
-                            let expr11_0 = constructor_sbc64(ctx, expr4_0, expr9_0)?;
-                            let expr12_0 = constructor_with_flags(ctx, &expr10_0, &expr11_0)?;
-                            return Some(expr12_0);
-                        }
-                        &Opcode::Imul => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 185.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0: usize = 0;
-                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
-                            let expr3_0: usize = 1;
-                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
-                            let expr5_0 = C::put_in_regs(ctx, pattern7_1);
-                            let expr6_0: usize = 0;
-                            let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
-                            let expr8_0: usize = 1;
-                            let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
-                            let expr10_0 = constructor_umulh(ctx, expr2_0, expr7_0)?;
-                            let expr11_0 = constructor_madd64(ctx, expr2_0, expr9_0, expr10_0)?;
-                            let expr12_0 = constructor_madd64(ctx, expr4_0, expr7_0, expr11_0)?;
-                            let expr13_0 = C::zero_reg(ctx);
-                            let expr14_0 = constructor_madd64(ctx, expr2_0, expr7_0, expr13_0)?;
-                            let expr15_0 = C::value_regs(ctx, expr14_0, expr12_0);
-                            return Some(expr15_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Isub = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    // Rule at src/isa/aarch64/lower.isle line 126.
+                    let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                    let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                    let expr2_0 = constructor_vector_size(ctx, pattern2_0)?;
+                    let expr3_0 = constructor_sub_vec(ctx, expr0_0, expr1_0, &expr2_0)?;
+                    let expr4_0 = C::value_reg(ctx, expr3_0);
+                    return Some(expr4_0);
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Iadd = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    if let Some(pattern8_0) = C::extended_value_from_value(ctx, pattern7_0) {
+                        // Rule at src/isa/aarch64/lower.isle line 53.
+                        let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                        let expr1_0 =
+                            constructor_add_extend(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                }
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if pattern2_0 == I32 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Rotl => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let closure12 = || {
+                                        let expr0_0: Type = I32;
+                                        return Some(expr0_0);
+                                    };
+                                    if let Some(pattern12_0) = closure12() {
+                                        if let Some(pattern13_0) =
+                                            C::imm_shift_from_imm64(ctx, pattern10_1, pattern12_0)
+                                        {
+                                            // Rule at src/isa/aarch64/lower.isle line 896.
+                                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                            let expr1_0: Type = I32;
+                                            let expr2_0 =
+                                                C::negate_imm_shift(ctx, expr1_0, pattern13_0);
+                                            let expr3_0 =
+                                                constructor_rotr32_imm(ctx, expr0_0, expr2_0)?;
+                                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                                            return Some(expr4_0);
+                                        }
+                                    }
+                                }
+                            }
                         }
-                        &Opcode::Band => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 612.
-                            let expr0_0 = ALUOp::And64;
-                            let expr1_0 =
-                                constructor_i128_alu_bitop(ctx, &expr0_0, pattern7_0, pattern7_1)?;
-                            return Some(expr1_0);
+                    }
+                    &Opcode::Rotr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let closure12 = || {
+                                        let expr0_0: Type = I32;
+                                        return Some(expr0_0);
+                                    };
+                                    if let Some(pattern12_0) = closure12() {
+                                        if let Some(pattern13_0) =
+                                            C::imm_shift_from_imm64(ctx, pattern10_1, pattern12_0)
+                                        {
+                                            // Rule at src/isa/aarch64/lower.isle line 940.
+                                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                            let expr1_0 =
+                                                constructor_rotr32_imm(ctx, expr0_0, pattern13_0)?;
+                                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                                            return Some(expr2_0);
+                                        }
+                                    }
+                                }
+                            }
                         }
-                        &Opcode::Bor => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 625.
-                            let expr0_0 = ALUOp::Orr64;
-                            let expr1_0 =
-                                constructor_i128_alu_bitop(ctx, &expr0_0, pattern7_0, pattern7_1)?;
-                            return Some(expr1_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if pattern2_0 == I64 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Rotl => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let closure12 = || {
+                                        let expr0_0: Type = I64;
+                                        return Some(expr0_0);
+                                    };
+                                    if let Some(pattern12_0) = closure12() {
+                                        if let Some(pattern13_0) =
+                                            C::imm_shift_from_imm64(ctx, pattern10_1, pattern12_0)
+                                        {
+                                            // Rule at src/isa/aarch64/lower.isle line 900.
+                                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                            let expr1_0: Type = I64;
+                                            let expr2_0 =
+                                                C::negate_imm_shift(ctx, expr1_0, pattern13_0);
+                                            let expr3_0 =
+                                                constructor_rotr64_imm(ctx, expr0_0, expr2_0)?;
+                                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                                            return Some(expr4_0);
+                                        }
+                                    }
+                                }
+                            }
                         }
-                        &Opcode::Bxor => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 638.
-                            let expr0_0 = ALUOp::Eor64;
-                            let expr1_0 =
-                                constructor_i128_alu_bitop(ctx, &expr0_0, pattern7_0, pattern7_1)?;
-                            return Some(expr1_0);
+                    }
+                    &Opcode::Rotr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let closure12 = || {
+                                        let expr0_0: Type = I64;
+                                        return Some(expr0_0);
+                                    };
+                                    if let Some(pattern12_0) = closure12() {
+                                        if let Some(pattern13_0) =
+                                            C::imm_shift_from_imm64(ctx, pattern10_1, pattern12_0)
+                                        {
+                                            // Rule at src/isa/aarch64/lower.isle line 944.
+                                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                            let expr1_0 =
+                                                constructor_rotr64_imm(ctx, expr0_0, pattern13_0)?;
+                                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                                            return Some(expr2_0);
+                                        }
+                                    }
+                                }
+                            }
                         }
-                        &Opcode::BandNot => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 651.
-                            let expr0_0 = ALUOp::AndNot64;
-                            let expr1_0 =
-                                constructor_i128_alu_bitop(ctx, &expr0_0, pattern7_0, pattern7_1)?;
-                            return Some(expr1_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if pattern2_0 == I128 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Unary {
+                opcode: ref pattern5_0,
+                arg: pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Bnot => {
+                        // Rule at src/isa/aarch64/lower.isle line 590.
+                        let expr0_0 = C::put_in_regs(ctx, pattern5_1);
+                        let expr1_0: usize = 0;
+                        let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                        let expr3_0: usize = 1;
+                        let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                        let expr5_0 = C::zero_reg(ctx);
+                        let expr6_0 = constructor_orr_not64(ctx, expr5_0, expr2_0)?;
+                        let expr7_0 = C::zero_reg(ctx);
+                        let expr8_0 = constructor_orr_not64(ctx, expr7_0, expr4_0)?;
+                        let expr9_0 = C::value_regs(ctx, expr6_0, expr8_0);
+                        return Some(expr9_0);
+                    }
+                    &Opcode::Uextend => {
+                        // Rule at src/isa/aarch64/lower.isle line 509.
+                        let expr0_0 = constructor_put_in_reg_zext64(ctx, pattern5_1)?;
+                        let expr1_0: Type = I64;
+                        let expr2_0: u64 = 0;
+                        let expr3_0 = constructor_imm(ctx, expr1_0, expr2_0)?;
+                        let expr4_0 = C::value_regs(ctx, expr0_0, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    &Opcode::Sextend => {
+                        if let Some(pattern7_0) = C::def_inst(ctx, pattern5_1) {
+                            let pattern8_0 = C::inst_data(ctx, pattern7_0);
+                            if let &InstructionData::BinaryImm8 {
+                                opcode: ref pattern9_0,
+                                arg: pattern9_1,
+                                imm: pattern9_2,
+                            } = &pattern8_0
+                            {
+                                if let &Opcode::Extractlane = &pattern9_0 {
+                                    let pattern11_0 = C::value_type(ctx, pattern9_1);
+                                    if let Some(()) = C::not_i64x2(ctx, pattern11_0) {
+                                        let pattern13_0 = C::u8_from_uimm8(ctx, pattern9_2);
+                                        // Rule at src/isa/aarch64/lower.isle line 549.
+                                        let expr0_0 = C::put_in_reg(ctx, pattern9_1);
+                                        let expr1_0 = constructor_vector_size(ctx, pattern11_0)?;
+                                        let expr2_0: Type = I64;
+                                        let expr3_0 = constructor_size_from_ty(ctx, expr2_0)?;
+                                        let expr4_0 = constructor_mov_from_vec_signed(
+                                            ctx,
+                                            expr0_0,
+                                            pattern13_0,
+                                            &expr1_0,
+                                            &expr3_0,
+                                        )?;
+                                        let expr5_0: u8 = 63;
+                                        let expr6_0 = C::imm_shift_from_u8(ctx, expr5_0);
+                                        let expr7_0 = constructor_asr64_imm(ctx, expr4_0, expr6_0)?;
+                                        let expr8_0 = C::value_regs(ctx, expr4_0, expr7_0);
+                                        return Some(expr8_0);
+                                    }
+                                }
+                            }
                         }
-                        &Opcode::BorNot => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 664.
-                            let expr0_0 = ALUOp::OrrNot64;
-                            let expr1_0 =
-                                constructor_i128_alu_bitop(ctx, &expr0_0, pattern7_0, pattern7_1)?;
-                            return Some(expr1_0);
-                        }
-                        &Opcode::BxorNot => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 674.
-                            let expr0_0 = ALUOp::EorNot64;
-                            let expr1_0 =
-                                constructor_i128_alu_bitop(ctx, &expr0_0, pattern7_0, pattern7_1)?;
-                            return Some(expr1_0);
-                        }
-                        &Opcode::Rotl => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 909.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0 = C::put_in_regs(ctx, pattern7_1);
-                            let expr2_0: usize = 0;
-                            let expr3_0 = C::value_regs_get(ctx, expr1_0, expr2_0);
-                            let expr4_0: Type = I64;
-                            let expr5_0: u64 = 128;
-                            let expr6_0 = constructor_imm(ctx, expr4_0, expr5_0)?;
-                            let expr7_0 = constructor_sub64(ctx, expr6_0, expr3_0)?;
-                            let expr8_0 = constructor_lower_shl128(ctx, expr0_0, expr3_0)?;
-                            let expr9_0 = constructor_lower_ushr128(ctx, expr0_0, expr7_0)?;
-                            let expr10_0: usize = 0;
-                            let expr11_0 = C::value_regs_get(ctx, expr8_0, expr10_0);
-                            let expr12_0: usize = 0;
-                            let expr13_0 = C::value_regs_get(ctx, expr9_0, expr12_0);
-                            let expr14_0 = constructor_orr64(ctx, expr11_0, expr13_0)?;
-                            let expr15_0: usize = 1;
-                            let expr16_0 = C::value_regs_get(ctx, expr8_0, expr15_0);
-                            let expr17_0: usize = 1;
-                            let expr18_0 = C::value_regs_get(ctx, expr9_0, expr17_0);
-                            let expr19_0 = constructor_orr64(ctx, expr16_0, expr18_0)?;
-                            let expr20_0 = C::value_regs(ctx, expr14_0, expr19_0);
-                            return Some(expr20_0);
-                        }
-                        &Opcode::Rotr => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 996.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0 = C::put_in_regs(ctx, pattern7_1);
-                            let expr2_0: usize = 0;
-                            let expr3_0 = C::value_regs_get(ctx, expr1_0, expr2_0);
-                            let expr4_0: Type = I64;
-                            let expr5_0: u64 = 128;
-                            let expr6_0 = constructor_imm(ctx, expr4_0, expr5_0)?;
-                            let expr7_0 = constructor_sub64(ctx, expr6_0, expr3_0)?;
-                            let expr8_0 = constructor_lower_ushr128(ctx, expr0_0, expr3_0)?;
-                            let expr9_0 = constructor_lower_shl128(ctx, expr0_0, expr7_0)?;
-                            let expr10_0: usize = 1;
-                            let expr11_0 = C::value_regs_get(ctx, expr8_0, expr10_0);
-                            let expr12_0: usize = 1;
-                            let expr13_0 = C::value_regs_get(ctx, expr9_0, expr12_0);
-                            let expr14_0 = constructor_orr64(ctx, expr11_0, expr13_0)?;
-                            let expr15_0: usize = 0;
-                            let expr16_0 = C::value_regs_get(ctx, expr8_0, expr15_0);
-                            let expr17_0: usize = 0;
-                            let expr18_0 = C::value_regs_get(ctx, expr9_0, expr17_0);
-                            let expr19_0 = constructor_orr64(ctx, expr16_0, expr18_0)?;
-                            let expr20_0 = C::value_regs(ctx, expr19_0, expr14_0);
-                            return Some(expr20_0);
-                        }
-                        &Opcode::Ishl => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 687.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0 = C::put_in_regs(ctx, pattern7_1);
-                            let expr2_0: usize = 0;
-                            let expr3_0 = C::value_regs_get(ctx, expr1_0, expr2_0);
-                            let expr4_0 = constructor_lower_shl128(ctx, expr0_0, expr3_0)?;
-                            return Some(expr4_0);
-                        }
-                        &Opcode::Ushr => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 775.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0 = C::put_in_regs(ctx, pattern7_1);
-                            let expr2_0: usize = 0;
-                            let expr3_0 = C::value_regs_get(ctx, expr1_0, expr2_0);
-                            let expr4_0 = constructor_lower_ushr128(ctx, expr0_0, expr3_0)?;
-                            return Some(expr4_0);
-                        }
-                        &Opcode::Sshr => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 824.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0 = C::put_in_regs(ctx, pattern7_1);
-                            let expr2_0: usize = 0;
-                            let expr3_0 = C::value_regs_get(ctx, expr1_0, expr2_0);
-                            let expr4_0 = constructor_lower_sshr128(ctx, expr0_0, expr3_0)?;
-                            return Some(expr4_0);
-                        }
-                        _ => {}
-                    }
-                }
-                &InstructionData::Unary {
-                    opcode: ref pattern5_0,
-                    arg: pattern5_1,
-                } => {
-                    match &pattern5_0 {
-                        &Opcode::Bnot => {
-                            // Rule at src/isa/aarch64/lower.isle line 590.
-                            let expr0_0 = C::put_in_regs(ctx, pattern5_1);
-                            let expr1_0: usize = 0;
-                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
-                            let expr3_0: usize = 1;
-                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
-                            let expr5_0 = C::zero_reg(ctx);
-                            let expr6_0 = constructor_orr_not64(ctx, expr5_0, expr2_0)?;
-                            let expr7_0 = C::zero_reg(ctx);
-                            let expr8_0 = constructor_orr_not64(ctx, expr7_0, expr4_0)?;
-                            let expr9_0 = C::value_regs(ctx, expr6_0, expr8_0);
-                            return Some(expr9_0);
-                        }
-                        &Opcode::Bitrev => {
-                            // Rule at src/isa/aarch64/lower.isle line 1028.
-                            let expr0_0 = C::put_in_regs(ctx, pattern5_1);
-                            let expr1_0: usize = 0;
-                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
-                            let expr3_0 = constructor_rbit64(ctx, expr2_0)?;
-                            let expr4_0: usize = 1;
-                            let expr5_0 = C::value_regs_get(ctx, expr0_0, expr4_0);
-                            let expr6_0 = constructor_rbit64(ctx, expr5_0)?;
-                            let expr7_0 = C::value_regs(ctx, expr6_0, expr3_0);
-                            return Some(expr7_0);
-                        }
-                        &Opcode::Clz => {
-                            // Rule at src/isa/aarch64/lower.isle line 1050.
-                            let expr0_0 = C::put_in_regs(ctx, pattern5_1);
-                            let expr1_0 = constructor_lower_clz128(ctx, expr0_0)?;
-                            return Some(expr1_0);
-                        }
-                        &Opcode::Cls => {
-                            // Rule at src/isa/aarch64/lower.isle line 1116.
-                            let expr0_0 = C::put_in_regs(ctx, pattern5_1);
-                            let expr1_0: usize = 0;
-                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
-                            let expr3_0: usize = 1;
-                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
-                            let expr5_0 = constructor_cls64(ctx, expr2_0)?;
-                            let expr6_0 = constructor_cls64(ctx, expr4_0)?;
-                            let expr7_0 = constructor_eon64(ctx, expr4_0, expr2_0)?;
-                            let expr8_0: u8 = 63;
-                            let expr9_0 = C::imm_shift_from_u8(ctx, expr8_0);
-                            let expr10_0 = constructor_lsr64_imm(ctx, expr7_0, expr9_0)?;
-                            let expr11_0 = constructor_madd64(ctx, expr5_0, expr10_0, expr10_0)?;
-                            let expr12_0: u8 = 63;
-                            let expr13_0 = C::u8_into_imm12(ctx, expr12_0);
-                            let expr14_0 = constructor_cmp64_imm(ctx, expr6_0, expr13_0)?;
-                            let expr15_0 = Cond::Eq;
-                            let expr16_0 = C::zero_reg(ctx);
-                            let expr17_0 = constructor_csel(ctx, &expr15_0, expr11_0, expr16_0)?;
-                            let expr18_0 = constructor_with_flags_1(ctx, &expr14_0, &expr17_0)?;
-                            let expr19_0 = constructor_add64(ctx, expr18_0, expr6_0)?;
-                            let expr20_0: Type = I64;
-                            let expr21_0: u64 = 0;
-                            let expr22_0 = constructor_imm(ctx, expr20_0, expr21_0)?;
-                            let expr23_0 = C::value_regs(ctx, expr19_0, expr22_0);
-                            return Some(expr23_0);
-                        }
-                        &Opcode::Ctz => {
-                            // Rule at src/isa/aarch64/lower.isle line 1085.
-                            let expr0_0 = C::put_in_regs(ctx, pattern5_1);
-                            let expr1_0: usize = 0;
-                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
-                            let expr3_0 = constructor_rbit64(ctx, expr2_0)?;
-                            let expr4_0: usize = 1;
-                            let expr5_0 = C::value_regs_get(ctx, expr0_0, expr4_0);
-                            let expr6_0 = constructor_rbit64(ctx, expr5_0)?;
-                            let expr7_0 = C::value_regs(ctx, expr6_0, expr3_0);
-                            let expr8_0 = constructor_lower_clz128(ctx, expr7_0)?;
-                            return Some(expr8_0);
-                        }
-                        &Opcode::Uextend => {
-                            if let Some(pattern7_0) = C::def_inst(ctx, pattern5_1) {
-                                let pattern8_0 = C::inst_data(ctx, pattern7_0);
-                                if let &InstructionData::BinaryImm8 {
-                                    opcode: ref pattern9_0,
-                                    arg: pattern9_1,
-                                    imm: pattern9_2,
-                                } = &pattern8_0
-                                {
-                                    if let &Opcode::Extractlane = &pattern9_0 {
-                                        let pattern11_0 = C::value_type(ctx, pattern9_1);
-                                        let pattern12_0 = C::u8_from_uimm8(ctx, pattern9_2);
-                                        // Rule at src/isa/aarch64/lower.isle line 514.
-                                        let expr0_0 = C::put_in_reg(ctx, pattern9_1);
-                                        let expr1_0 = constructor_vector_size(ctx, pattern11_0)?;
-                                        let expr2_0 = constructor_mov_from_vec(
-                                            ctx,
-                                            expr0_0,
-                                            pattern12_0,
-                                            &expr1_0,
-                                        )?;
-                                        let expr3_0: Type = I64;
-                                        let expr4_0: u64 = 0;
-                                        let expr5_0 = constructor_imm(ctx, expr3_0, expr4_0)?;
-                                        let expr6_0 = C::value_regs(ctx, expr2_0, expr5_0);
-                                        return Some(expr6_0);
-                                    }
-                                }
-                            }
-                            // Rule at src/isa/aarch64/lower.isle line 509.
-                            let expr0_0 = constructor_put_in_reg_zext64(ctx, pattern5_1)?;
-                            let expr1_0: Type = I64;
-                            let expr2_0: u64 = 0;
-                            let expr3_0 = constructor_imm(ctx, expr1_0, expr2_0)?;
-                            let expr4_0 = C::value_regs(ctx, expr0_0, expr3_0);
-                            return Some(expr4_0);
-                        }
-                        &Opcode::Sextend => {
-                            if let Some(pattern7_0) = C::def_inst(ctx, pattern5_1) {
-                                let pattern8_0 = C::inst_data(ctx, pattern7_0);
-                                if let &InstructionData::BinaryImm8 {
-                                    opcode: ref pattern9_0,
-                                    arg: pattern9_1,
-                                    imm: pattern9_2,
-                                } = &pattern8_0
-                                {
-                                    if let &Opcode::Extractlane = &pattern9_0 {
-                                        let pattern11_0 = C::value_type(ctx, pattern9_1);
-                                        if pattern11_0 == I64X2 {
-                                            let pattern13_0 = C::u8_from_uimm8(ctx, pattern9_2);
-                                            // Rule at src/isa/aarch64/lower.isle line 562.
-                                            let expr0_0 = C::put_in_reg(ctx, pattern9_1);
-                                            let expr1_0 = VectorSize::Size64x2;
-                                            let expr2_0 = constructor_mov_from_vec(
-                                                ctx,
-                                                expr0_0,
-                                                pattern13_0,
-                                                &expr1_0,
-                                            )?;
-                                            let expr3_0: u8 = 63;
-                                            let expr4_0 = C::imm_shift_from_u8(ctx, expr3_0);
-                                            let expr5_0 =
-                                                constructor_asr64_imm(ctx, expr2_0, expr4_0)?;
-                                            let expr6_0 = C::value_regs(ctx, expr2_0, expr5_0);
-                                            return Some(expr6_0);
-                                        }
-                                        if let Some(()) = C::not_i64x2(ctx, pattern11_0) {
-                                            let pattern13_0 = C::u8_from_uimm8(ctx, pattern9_2);
-                                            // Rule at src/isa/aarch64/lower.isle line 549.
-                                            let expr0_0 = C::put_in_reg(ctx, pattern9_1);
-                                            let expr1_0 =
-                                                constructor_vector_size(ctx, pattern11_0)?;
-                                            let expr2_0: Type = I64;
-                                            let expr3_0 = constructor_size_from_ty(ctx, expr2_0)?;
-                                            let expr4_0 = constructor_mov_from_vec_signed(
-                                                ctx,
-                                                expr0_0,
-                                                pattern13_0,
-                                                &expr1_0,
-                                                &expr3_0,
-                                            )?;
-                                            let expr5_0: u8 = 63;
-                                            let expr6_0 = C::imm_shift_from_u8(ctx, expr5_0);
-                                            let expr7_0 =
-                                                constructor_asr64_imm(ctx, expr4_0, expr6_0)?;
-                                            let expr8_0 = C::value_regs(ctx, expr4_0, expr7_0);
-                                            return Some(expr8_0);
-                                        }
-                                    }
-                                }
-                            }
-                            // Rule at src/isa/aarch64/lower.isle line 537.
-                            let expr0_0 = constructor_put_in_reg_sext64(ctx, pattern5_1)?;
-                            let expr1_0: u8 = 63;
-                            let expr2_0 = C::imm_shift_from_u8(ctx, expr1_0);
-                            let expr3_0 = constructor_asr64_imm(ctx, expr0_0, expr2_0)?;
-                            let expr4_0 = C::value_regs(ctx, expr0_0, expr3_0);
-                            return Some(expr4_0);
-                        }
-                        _ => {}
                     }
+                    _ => {}
                 }
-                _ => {}
             }
         }
-        if pattern2_0 == I16X8 {
+        if pattern2_0 == I64X2 {
             let pattern4_0 = C::inst_data(ctx, pattern0_0);
             if let &InstructionData::Binary {
                 opcode: ref pattern5_0,
@@ -4493,7 +4147,7 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                             match &pattern10_0 {
                                 &Opcode::SwidenLow => {
                                     let pattern12_0 = C::value_type(ctx, pattern10_1);
-                                    if pattern12_0 == I8X16 {
+                                    if pattern12_0 == I32X4 {
                                         if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
                                             let pattern15_0 = C::inst_data(ctx, pattern14_0);
                                             if let &InstructionData::Unary {
@@ -4504,14 +4158,14 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                                                 if let &Opcode::SwidenLow = &pattern16_0 {
                                                     let pattern18_0 =
                                                         C::value_type(ctx, pattern16_1);
-                                                    if pattern18_0 == I8X16 {
-                                                        // Rule at src/isa/aarch64/lower.isle line 287.
+                                                    if pattern18_0 == I32X4 {
+                                                        // Rule at src/isa/aarch64/lower.isle line 335.
                                                         let expr0_0 =
                                                             C::put_in_reg(ctx, pattern10_1);
                                                         let expr1_0 =
                                                             C::put_in_reg(ctx, pattern16_1);
                                                         let expr2_0: bool = false;
-                                                        let expr3_0 = constructor_smull8(
+                                                        let expr3_0 = constructor_smull32(
                                                             ctx, expr0_0, expr1_0, expr2_0,
                                                         )?;
                                                         let expr4_0 = C::value_reg(ctx, expr3_0);
@@ -4524,7 +4178,7 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                                 }
                                 &Opcode::SwidenHigh => {
                                     let pattern12_0 = C::value_type(ctx, pattern10_1);
-                                    if pattern12_0 == I8X16 {
+                                    if pattern12_0 == I32X4 {
                                         if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
                                             let pattern15_0 = C::inst_data(ctx, pattern14_0);
                                             if let &InstructionData::Unary {
@@ -4535,14 +4189,14 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                                                 if let &Opcode::SwidenHigh = &pattern16_0 {
                                                     let pattern18_0 =
                                                         C::value_type(ctx, pattern16_1);
-                                                    if pattern18_0 == I8X16 {
-                                                        // Rule at src/isa/aarch64/lower.isle line 293.
+                                                    if pattern18_0 == I32X4 {
+                                                        // Rule at src/isa/aarch64/lower.isle line 341.
                                                         let expr0_0 =
                                                             C::put_in_reg(ctx, pattern10_1);
                                                         let expr1_0 =
                                                             C::put_in_reg(ctx, pattern16_1);
                                                         let expr2_0: bool = true;
-                                                        let expr3_0 = constructor_smull8(
+                                                        let expr3_0 = constructor_smull32(
                                                             ctx, expr0_0, expr1_0, expr2_0,
                                                         )?;
                                                         let expr4_0 = C::value_reg(ctx, expr3_0);
@@ -4555,7 +4209,7 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                                 }
                                 &Opcode::UwidenLow => {
                                     let pattern12_0 = C::value_type(ctx, pattern10_1);
-                                    if pattern12_0 == I8X16 {
+                                    if pattern12_0 == I32X4 {
                                         if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
                                             let pattern15_0 = C::inst_data(ctx, pattern14_0);
                                             if let &InstructionData::Unary {
@@ -4566,14 +4220,14 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                                                 if let &Opcode::UwidenLow = &pattern16_0 {
                                                     let pattern18_0 =
                                                         C::value_type(ctx, pattern16_1);
-                                                    if pattern18_0 == I8X16 {
-                                                        // Rule at src/isa/aarch64/lower.isle line 299.
+                                                    if pattern18_0 == I32X4 {
+                                                        // Rule at src/isa/aarch64/lower.isle line 347.
                                                         let expr0_0 =
                                                             C::put_in_reg(ctx, pattern10_1);
                                                         let expr1_0 =
                                                             C::put_in_reg(ctx, pattern16_1);
                                                         let expr2_0: bool = false;
-                                                        let expr3_0 = constructor_umull8(
+                                                        let expr3_0 = constructor_umull32(
                                                             ctx, expr0_0, expr1_0, expr2_0,
                                                         )?;
                                                         let expr4_0 = C::value_reg(ctx, expr3_0);
@@ -4586,7 +4240,7 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                                 }
                                 &Opcode::UwidenHigh => {
                                     let pattern12_0 = C::value_type(ctx, pattern10_1);
-                                    if pattern12_0 == I8X16 {
+                                    if pattern12_0 == I32X4 {
                                         if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
                                             let pattern15_0 = C::inst_data(ctx, pattern14_0);
                                             if let &InstructionData::Unary {
@@ -4597,14 +4251,14 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                                                 if let &Opcode::UwidenHigh = &pattern16_0 {
                                                     let pattern18_0 =
                                                         C::value_type(ctx, pattern16_1);
-                                                    if pattern18_0 == I8X16 {
-                                                        // Rule at src/isa/aarch64/lower.isle line 305.
+                                                    if pattern18_0 == I32X4 {
+                                                        // Rule at src/isa/aarch64/lower.isle line 353.
                                                         let expr0_0 =
                                                             C::put_in_reg(ctx, pattern10_1);
                                                         let expr1_0 =
                                                             C::put_in_reg(ctx, pattern16_1);
                                                         let expr2_0: bool = true;
-                                                        let expr3_0 = constructor_umull8(
+                                                        let expr3_0 = constructor_umull32(
                                                             ctx, expr0_0, expr1_0, expr2_0,
                                                         )?;
                                                         let expr4_0 = C::value_reg(ctx, expr3_0);
@@ -4622,402 +4276,77 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                 }
             }
         }
-        if pattern2_0 == I32X4 {
+        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
             let pattern4_0 = C::inst_data(ctx, pattern0_0);
             if let &InstructionData::Binary {
                 opcode: ref pattern5_0,
                 args: ref pattern5_1,
             } = &pattern4_0
             {
-                if let &Opcode::Imul = &pattern5_0 {
-                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                    if let Some(pattern8_0) = C::def_inst(ctx, pattern7_0) {
-                        let pattern9_0 = C::inst_data(ctx, pattern8_0);
-                        if let &InstructionData::Unary {
-                            opcode: ref pattern10_0,
-                            arg: pattern10_1,
-                        } = &pattern9_0
-                        {
-                            match &pattern10_0 {
-                                &Opcode::SwidenLow => {
-                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
-                                    if pattern12_0 == I16X8 {
-                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
-                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
-                                            if let &InstructionData::Unary {
-                                                opcode: ref pattern16_0,
-                                                arg: pattern16_1,
-                                            } = &pattern15_0
-                                            {
-                                                if let &Opcode::SwidenLow = &pattern16_0 {
-                                                    let pattern18_0 =
-                                                        C::value_type(ctx, pattern16_1);
-                                                    if pattern18_0 == I16X8 {
-                                                        // Rule at src/isa/aarch64/lower.isle line 311.
-                                                        let expr0_0 =
-                                                            C::put_in_reg(ctx, pattern10_1);
-                                                        let expr1_0 =
-                                                            C::put_in_reg(ctx, pattern16_1);
-                                                        let expr2_0: bool = false;
-                                                        let expr3_0 = constructor_smull16(
-                                                            ctx, expr0_0, expr1_0, expr2_0,
-                                                        )?;
-                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
-                                                        return Some(expr4_0);
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                                &Opcode::SwidenHigh => {
-                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
-                                    if pattern12_0 == I16X8 {
-                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
-                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
-                                            if let &InstructionData::Unary {
-                                                opcode: ref pattern16_0,
-                                                arg: pattern16_1,
-                                            } = &pattern15_0
-                                            {
-                                                if let &Opcode::SwidenHigh = &pattern16_0 {
-                                                    let pattern18_0 =
-                                                        C::value_type(ctx, pattern16_1);
-                                                    if pattern18_0 == I16X8 {
-                                                        // Rule at src/isa/aarch64/lower.isle line 317.
-                                                        let expr0_0 =
-                                                            C::put_in_reg(ctx, pattern10_1);
-                                                        let expr1_0 =
-                                                            C::put_in_reg(ctx, pattern16_1);
-                                                        let expr2_0: bool = true;
-                                                        let expr3_0 = constructor_smull16(
-                                                            ctx, expr0_0, expr1_0, expr2_0,
-                                                        )?;
-                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
-                                                        return Some(expr4_0);
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                                &Opcode::UwidenLow => {
-                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
-                                    if pattern12_0 == I16X8 {
-                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
-                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
-                                            if let &InstructionData::Unary {
-                                                opcode: ref pattern16_0,
-                                                arg: pattern16_1,
-                                            } = &pattern15_0
-                                            {
-                                                if let &Opcode::UwidenLow = &pattern16_0 {
-                                                    let pattern18_0 =
-                                                        C::value_type(ctx, pattern16_1);
-                                                    if pattern18_0 == I16X8 {
-                                                        // Rule at src/isa/aarch64/lower.isle line 323.
-                                                        let expr0_0 =
-                                                            C::put_in_reg(ctx, pattern10_1);
-                                                        let expr1_0 =
-                                                            C::put_in_reg(ctx, pattern16_1);
-                                                        let expr2_0: bool = false;
-                                                        let expr3_0 = constructor_umull16(
-                                                            ctx, expr0_0, expr1_0, expr2_0,
-                                                        )?;
-                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
-                                                        return Some(expr4_0);
-                                                    }
-                                                }
-                                            }
-                                        }
+                match &pattern5_0 {
+                    &Opcode::Iadd => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
+                                    if let Some(pattern13_0) = C::imm12_from_u64(ctx, pattern12_0) {
+                                        // Rule at src/isa/aarch64/lower.isle line 34.
+                                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                        let expr1_0 = constructor_add_imm(
+                                            ctx,
+                                            pattern3_0,
+                                            expr0_0,
+                                            pattern13_0,
+                                        )?;
+                                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                                        return Some(expr2_0);
                                     }
                                 }
-                                &Opcode::UwidenHigh => {
-                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
-                                    if pattern12_0 == I16X8 {
-                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
-                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
-                                            if let &InstructionData::Unary {
-                                                opcode: ref pattern16_0,
-                                                arg: pattern16_1,
-                                            } = &pattern15_0
-                                            {
-                                                if let &Opcode::UwidenHigh = &pattern16_0 {
-                                                    let pattern18_0 =
-                                                        C::value_type(ctx, pattern16_1);
-                                                    if pattern18_0 == I16X8 {
-                                                        // Rule at src/isa/aarch64/lower.isle line 329.
-                                                        let expr0_0 =
-                                                            C::put_in_reg(ctx, pattern10_1);
-                                                        let expr1_0 =
-                                                            C::put_in_reg(ctx, pattern16_1);
-                                                        let expr2_0: bool = true;
-                                                        let expr3_0 = constructor_umull16(
-                                                            ctx, expr0_0, expr1_0, expr2_0,
-                                                        )?;
-                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
-                                                        return Some(expr4_0);
-                                                    }
-                                                }
-                                            }
-                                        }
+                            }
+                        }
+                    }
+                    &Opcode::Isub => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
+                                    if let Some(pattern13_0) = C::imm12_from_u64(ctx, pattern12_0) {
+                                        // Rule at src/isa/aarch64/lower.isle line 106.
+                                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                        let expr1_0 = constructor_sub_imm(
+                                            ctx,
+                                            pattern3_0,
+                                            expr0_0,
+                                            pattern13_0,
+                                        )?;
+                                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                                        return Some(expr2_0);
                                     }
                                 }
-                                _ => {}
                             }
                         }
                     }
+                    _ => {}
                 }
             }
         }
-        if pattern2_0 == I64X2 {
-            let pattern4_0 = C::inst_data(ctx, pattern0_0);
-            if let &InstructionData::Binary {
-                opcode: ref pattern5_0,
-                args: ref pattern5_1,
-            } = &pattern4_0
-            {
-                if let &Opcode::Imul = &pattern5_0 {
-                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                    if let Some(pattern8_0) = C::def_inst(ctx, pattern7_0) {
-                        let pattern9_0 = C::inst_data(ctx, pattern8_0);
-                        if let &InstructionData::Unary {
-                            opcode: ref pattern10_0,
-                            arg: pattern10_1,
-                        } = &pattern9_0
-                        {
-                            match &pattern10_0 {
-                                &Opcode::SwidenLow => {
-                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
-                                    if pattern12_0 == I32X4 {
-                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
-                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
-                                            if let &InstructionData::Unary {
-                                                opcode: ref pattern16_0,
-                                                arg: pattern16_1,
-                                            } = &pattern15_0
-                                            {
-                                                if let &Opcode::SwidenLow = &pattern16_0 {
-                                                    let pattern18_0 =
-                                                        C::value_type(ctx, pattern16_1);
-                                                    if pattern18_0 == I32X4 {
-                                                        // Rule at src/isa/aarch64/lower.isle line 335.
-                                                        let expr0_0 =
-                                                            C::put_in_reg(ctx, pattern10_1);
-                                                        let expr1_0 =
-                                                            C::put_in_reg(ctx, pattern16_1);
-                                                        let expr2_0: bool = false;
-                                                        let expr3_0 = constructor_smull32(
-                                                            ctx, expr0_0, expr1_0, expr2_0,
-                                                        )?;
-                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
-                                                        return Some(expr4_0);
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                                &Opcode::SwidenHigh => {
-                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
-                                    if pattern12_0 == I32X4 {
-                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
-                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
-                                            if let &InstructionData::Unary {
-                                                opcode: ref pattern16_0,
-                                                arg: pattern16_1,
-                                            } = &pattern15_0
-                                            {
-                                                if let &Opcode::SwidenHigh = &pattern16_0 {
-                                                    let pattern18_0 =
-                                                        C::value_type(ctx, pattern16_1);
-                                                    if pattern18_0 == I32X4 {
-                                                        // Rule at src/isa/aarch64/lower.isle line 341.
-                                                        let expr0_0 =
-                                                            C::put_in_reg(ctx, pattern10_1);
-                                                        let expr1_0 =
-                                                            C::put_in_reg(ctx, pattern16_1);
-                                                        let expr2_0: bool = true;
-                                                        let expr3_0 = constructor_smull32(
-                                                            ctx, expr0_0, expr1_0, expr2_0,
-                                                        )?;
-                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
-                                                        return Some(expr4_0);
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                                &Opcode::UwidenLow => {
-                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
-                                    if pattern12_0 == I32X4 {
-                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
-                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
-                                            if let &InstructionData::Unary {
-                                                opcode: ref pattern16_0,
-                                                arg: pattern16_1,
-                                            } = &pattern15_0
-                                            {
-                                                if let &Opcode::UwidenLow = &pattern16_0 {
-                                                    let pattern18_0 =
-                                                        C::value_type(ctx, pattern16_1);
-                                                    if pattern18_0 == I32X4 {
-                                                        // Rule at src/isa/aarch64/lower.isle line 347.
-                                                        let expr0_0 =
-                                                            C::put_in_reg(ctx, pattern10_1);
-                                                        let expr1_0 =
-                                                            C::put_in_reg(ctx, pattern16_1);
-                                                        let expr2_0: bool = false;
-                                                        let expr3_0 = constructor_umull32(
-                                                            ctx, expr0_0, expr1_0, expr2_0,
-                                                        )?;
-                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
-                                                        return Some(expr4_0);
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                                &Opcode::UwidenHigh => {
-                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
-                                    if pattern12_0 == I32X4 {
-                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
-                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
-                                            if let &InstructionData::Unary {
-                                                opcode: ref pattern16_0,
-                                                arg: pattern16_1,
-                                            } = &pattern15_0
-                                            {
-                                                if let &Opcode::UwidenHigh = &pattern16_0 {
-                                                    let pattern18_0 =
-                                                        C::value_type(ctx, pattern16_1);
-                                                    if pattern18_0 == I32X4 {
-                                                        // Rule at src/isa/aarch64/lower.isle line 353.
-                                                        let expr0_0 =
-                                                            C::put_in_reg(ctx, pattern10_1);
-                                                        let expr1_0 =
-                                                            C::put_in_reg(ctx, pattern16_1);
-                                                        let expr2_0: bool = true;
-                                                        let expr3_0 = constructor_umull32(
-                                                            ctx, expr0_0, expr1_0, expr2_0,
-                                                        )?;
-                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
-                                                        return Some(expr4_0);
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                    // Rule at src/isa/aarch64/lower.isle line 246.
-                    let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                    let expr1_0 = C::put_in_reg(ctx, pattern7_1);
-                    let expr2_0 = VectorSize::Size32x4;
-                    let expr3_0 = constructor_rev64(ctx, expr1_0, &expr2_0)?;
-                    let expr4_0 = VectorSize::Size32x4;
-                    let expr5_0 = constructor_mul(ctx, expr3_0, expr0_0, &expr4_0)?;
-                    let expr6_0: bool = false;
-                    let expr7_0 = constructor_xtn64(ctx, expr0_0, expr6_0)?;
-                    let expr8_0 = VectorSize::Size32x4;
-                    let expr9_0 = constructor_addp(ctx, expr5_0, expr5_0, &expr8_0)?;
-                    let expr10_0: bool = false;
-                    let expr11_0 = constructor_xtn64(ctx, expr1_0, expr10_0)?;
-                    let expr12_0: bool = false;
-                    let expr13_0 = constructor_shll32(ctx, expr9_0, expr12_0)?;
-                    let expr14_0: bool = false;
-                    let expr15_0 = constructor_umlal32(ctx, expr13_0, expr11_0, expr7_0, expr14_0)?;
-                    let expr16_0 = C::value_reg(ctx, expr15_0);
-                    return Some(expr16_0);
-                }
-            }
-        }
-        let pattern3_0 = C::inst_data(ctx, pattern0_0);
-        match &pattern3_0 {
-            &InstructionData::NullAry {
-                opcode: ref pattern4_0,
-            } => {
-                if let &Opcode::Null = &pattern4_0 {
-                    // Rule at src/isa/aarch64/lower.isle line 22.
-                    let expr0_0: u64 = 0;
-                    let expr1_0 = constructor_imm(ctx, pattern2_0, expr0_0)?;
-                    let expr2_0 = C::value_reg(ctx, expr1_0);
-                    return Some(expr2_0);
-                }
-            }
-            &InstructionData::UnaryImm {
-                opcode: ref pattern4_0,
-                imm: pattern4_1,
-            } => {
-                if let &Opcode::Iconst = &pattern4_0 {
-                    let pattern6_0 = C::u64_from_imm64(ctx, pattern4_1);
-                    // Rule at src/isa/aarch64/lower.isle line 9.
-                    let expr0_0 = constructor_imm(ctx, pattern2_0, pattern6_0)?;
-                    let expr1_0 = C::value_reg(ctx, expr0_0);
-                    return Some(expr1_0);
-                }
-            }
-            &InstructionData::UnaryBool {
-                opcode: ref pattern4_0,
-                imm: pattern4_1,
-            } => {
-                if let &Opcode::Bconst = &pattern4_0 {
-                    if pattern4_1 == true {
-                        // Rule at src/isa/aarch64/lower.isle line 17.
-                        let expr0_0: u64 = 1;
-                        let expr1_0 = constructor_imm(ctx, pattern2_0, expr0_0)?;
-                        let expr2_0 = C::value_reg(ctx, expr1_0);
-                        return Some(expr2_0);
-                    }
-                    if pattern4_1 == false {
-                        // Rule at src/isa/aarch64/lower.isle line 14.
-                        let expr0_0: u64 = 0;
-                        let expr1_0 = constructor_imm(ctx, pattern2_0, expr0_0)?;
-                        let expr2_0 = C::value_reg(ctx, expr1_0);
-                        return Some(expr2_0);
-                    }
-                }
-            }
-            _ => {}
-        }
-        if let Some((pattern3_0, pattern3_1)) = C::multi_lane(ctx, pattern2_0) {
-            let pattern4_0 = C::inst_data(ctx, pattern0_0);
-            if let &InstructionData::Binary {
-                opcode: ref pattern5_0,
-                args: ref pattern5_1,
-            } = &pattern4_0
-            {
-                match &pattern5_0 {
-                    &Opcode::Iadd => {
-                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                        // Rule at src/isa/aarch64/lower.isle line 75.
-                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                        let expr1_0 = C::put_in_reg(ctx, pattern7_1);
-                        let expr2_0 = constructor_vector_size(ctx, pattern2_0)?;
-                        let expr3_0 = constructor_add_vec(ctx, expr0_0, expr1_0, &expr2_0)?;
-                        let expr4_0 = C::value_reg(ctx, expr3_0);
-                        return Some(expr4_0);
-                    }
-                    &Opcode::Isub => {
-                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                        // Rule at src/isa/aarch64/lower.isle line 126.
-                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                        let expr1_0 = C::put_in_reg(ctx, pattern7_1);
-                        let expr2_0 = constructor_vector_size(ctx, pattern2_0)?;
-                        let expr3_0 = constructor_sub_vec(ctx, expr0_0, expr1_0, &expr2_0)?;
-                        let expr4_0 = C::value_reg(ctx, expr3_0);
-                        return Some(expr4_0);
-                    }
-                    _ => {}
-                }
-            }
-        }
-        if let Some(pattern3_0) = C::fits_in_16(ctx, pattern2_0) {
+    }
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if pattern2_0 == I32 {
             let pattern4_0 = C::inst_data(ctx, pattern0_0);
             if let &InstructionData::Binary {
                 opcode: ref pattern5_0,
@@ -5027,82 +4356,21 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                 match &pattern5_0 {
                     &Opcode::Rotl => {
                         let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
-                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
-                            if let &InstructionData::UnaryImm {
-                                opcode: ref pattern10_0,
-                                imm: pattern10_1,
-                            } = &pattern9_0
-                            {
-                                if let &Opcode::Iconst = &pattern10_0 {
-                                    let closure12 = || {
-                                        return Some(pattern3_0);
-                                    };
-                                    if let Some(pattern12_0) = closure12() {
-                                        if let Some(pattern13_0) =
-                                            C::imm_shift_from_imm64(ctx, pattern10_1, pattern12_0)
-                                        {
-                                            // Rule at src/isa/aarch64/lower.isle line 874.
-                                            let expr0_0 =
-                                                constructor_put_in_reg_zext32(ctx, pattern7_0)?;
-                                            let expr1_0 =
-                                                C::negate_imm_shift(ctx, pattern3_0, pattern13_0);
-                                            let expr2_0 = constructor_small_rotr_imm(
-                                                ctx, pattern3_0, expr0_0, expr1_0,
-                                            )?;
-                                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                                            return Some(expr3_0);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        // Rule at src/isa/aarch64/lower.isle line 869.
+                        // Rule at src/isa/aarch64/lower.isle line 886.
                         let expr0_0 = C::zero_reg(ctx);
                         let expr1_0 = C::put_in_reg(ctx, pattern7_1);
                         let expr2_0 = constructor_sub32(ctx, expr0_0, expr1_0)?;
-                        let expr3_0 = constructor_put_in_reg_zext32(ctx, pattern7_0)?;
-                        let expr4_0 = constructor_small_rotr(ctx, pattern3_0, expr3_0, expr2_0)?;
+                        let expr3_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr4_0 = constructor_rotr32(ctx, expr3_0, expr2_0)?;
                         let expr5_0 = C::value_reg(ctx, expr4_0);
                         return Some(expr5_0);
                     }
                     &Opcode::Rotr => {
                         let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
-                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
-                            if let &InstructionData::UnaryImm {
-                                opcode: ref pattern10_0,
-                                imm: pattern10_1,
-                            } = &pattern9_0
-                            {
-                                if let &Opcode::Iconst = &pattern10_0 {
-                                    let closure12 = || {
-                                        return Some(pattern3_0);
-                                    };
-                                    if let Some(pattern12_0) = closure12() {
-                                        if let Some(pattern13_0) =
-                                            C::imm_shift_from_imm64(ctx, pattern10_1, pattern12_0)
-                                        {
-                                            // Rule at src/isa/aarch64/lower.isle line 936.
-                                            let expr0_0 =
-                                                constructor_put_in_reg_zext32(ctx, pattern7_0)?;
-                                            let expr1_0 = constructor_small_rotr_imm(
-                                                ctx,
-                                                pattern3_0,
-                                                expr0_0,
-                                                pattern13_0,
-                                            )?;
-                                            let expr2_0 = C::value_reg(ctx, expr1_0);
-                                            return Some(expr2_0);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        // Rule at src/isa/aarch64/lower.isle line 924.
-                        let expr0_0 = constructor_put_in_reg_zext32(ctx, pattern7_0)?;
+                        // Rule at src/isa/aarch64/lower.isle line 928.
+                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
                         let expr1_0 = C::put_in_reg(ctx, pattern7_1);
-                        let expr2_0 = constructor_small_rotr(ctx, pattern3_0, expr0_0, expr1_0)?;
+                        let expr2_0 = constructor_rotr32(ctx, expr0_0, expr1_0)?;
                         let expr3_0 = C::value_reg(ctx, expr2_0);
                         return Some(expr3_0);
                     }
@@ -5110,7 +4378,7 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                 }
             }
         }
-        if let Some(pattern3_0) = C::fits_in_32(ctx, pattern2_0) {
+        if pattern2_0 == I64 {
             let pattern4_0 = C::inst_data(ctx, pattern0_0);
             if let &InstructionData::Binary {
                 opcode: ref pattern5_0,
@@ -5118,127 +4386,108 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
             } = &pattern4_0
             {
                 match &pattern5_0 {
-                    &Opcode::Umulhi => {
-                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                        // Rule at src/isa/aarch64/lower.isle line 377.
-                        let expr0_0 = constructor_put_in_reg_zext64(ctx, pattern7_0)?;
-                        let expr1_0 = constructor_put_in_reg_zext64(ctx, pattern7_1)?;
-                        let expr2_0 = C::zero_reg(ctx);
-                        let expr3_0 = constructor_madd64(ctx, expr0_0, expr1_0, expr2_0)?;
-                        let expr4_0 = C::ty_bits(ctx, pattern3_0);
-                        let expr5_0 = C::imm_shift_from_u8(ctx, expr4_0);
-                        let expr6_0 = constructor_lsr64_imm(ctx, expr3_0, expr5_0)?;
-                        let expr7_0 = C::value_reg(ctx, expr6_0);
-                        return Some(expr7_0);
-                    }
-                    &Opcode::Smulhi => {
-                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                        // Rule at src/isa/aarch64/lower.isle line 363.
-                        let expr0_0 = constructor_put_in_reg_sext64(ctx, pattern7_0)?;
-                        let expr1_0 = constructor_put_in_reg_sext64(ctx, pattern7_1)?;
-                        let expr2_0 = C::zero_reg(ctx);
-                        let expr3_0 = constructor_madd64(ctx, expr0_0, expr1_0, expr2_0)?;
-                        let expr4_0 = C::ty_bits(ctx, pattern3_0);
-                        let expr5_0 = C::imm_shift_from_u8(ctx, expr4_0);
-                        let expr6_0 = constructor_asr64_imm(ctx, expr3_0, expr5_0)?;
-                        let expr7_0 = C::value_reg(ctx, expr6_0);
-                        return Some(expr7_0);
-                    }
                     &Opcode::Band => {
                         let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                        // Rule at src/isa/aarch64/lower.isle line 606.
-                        let expr0_0 = ALUOp::And32;
-                        let expr1_0 = constructor_alu_rs_imm_logic_commutative(
-                            ctx, &expr0_0, pattern3_0, pattern7_0, pattern7_1,
+                        // Rule at src/isa/aarch64/lower.isle line 609.
+                        let expr0_0 = ALUOp::And64;
+                        let expr1_0: Type = I64;
+                        let expr2_0 = constructor_alu_rs_imm_logic_commutative(
+                            ctx, &expr0_0, expr1_0, pattern7_0, pattern7_1,
                         )?;
-                        let expr2_0 = C::value_reg(ctx, expr1_0);
-                        return Some(expr2_0);
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
                     }
                     &Opcode::Bor => {
                         let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                        // Rule at src/isa/aarch64/lower.isle line 619.
-                        let expr0_0 = ALUOp::Orr32;
-                        let expr1_0 = constructor_alu_rs_imm_logic_commutative(
-                            ctx, &expr0_0, pattern3_0, pattern7_0, pattern7_1,
+                        // Rule at src/isa/aarch64/lower.isle line 622.
+                        let expr0_0 = ALUOp::Orr64;
+                        let expr1_0: Type = I64;
+                        let expr2_0 = constructor_alu_rs_imm_logic_commutative(
+                            ctx, &expr0_0, expr1_0, pattern7_0, pattern7_1,
                         )?;
-                        let expr2_0 = C::value_reg(ctx, expr1_0);
-                        return Some(expr2_0);
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
                     }
                     &Opcode::Bxor => {
                         let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                        // Rule at src/isa/aarch64/lower.isle line 632.
-                        let expr0_0 = ALUOp::Eor32;
-                        let expr1_0 = constructor_alu_rs_imm_logic_commutative(
-                            ctx, &expr0_0, pattern3_0, pattern7_0, pattern7_1,
+                        // Rule at src/isa/aarch64/lower.isle line 635.
+                        let expr0_0 = ALUOp::Eor64;
+                        let expr1_0: Type = I64;
+                        let expr2_0 = constructor_alu_rs_imm_logic_commutative(
+                            ctx, &expr0_0, expr1_0, pattern7_0, pattern7_1,
                         )?;
-                        let expr2_0 = C::value_reg(ctx, expr1_0);
-                        return Some(expr2_0);
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
                     }
                     &Opcode::BandNot => {
                         let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                        // Rule at src/isa/aarch64/lower.isle line 645.
-                        let expr0_0 = ALUOp::AndNot32;
-                        let expr1_0 = constructor_alu_rs_imm_logic(
-                            ctx, &expr0_0, pattern3_0, pattern7_0, pattern7_1,
+                        // Rule at src/isa/aarch64/lower.isle line 648.
+                        let expr0_0 = ALUOp::AndNot64;
+                        let expr1_0: Type = I64;
+                        let expr2_0 = constructor_alu_rs_imm_logic(
+                            ctx, &expr0_0, expr1_0, pattern7_0, pattern7_1,
                         )?;
-                        let expr2_0 = C::value_reg(ctx, expr1_0);
-                        return Some(expr2_0);
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
                     }
-                    &Opcode::BorNot => {
+                    &Opcode::Rotl => {
                         let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                        // Rule at src/isa/aarch64/lower.isle line 658.
-                        let expr0_0 = ALUOp::OrrNot32;
-                        let expr1_0 = constructor_alu_rs_imm_logic(
-                            ctx, &expr0_0, pattern3_0, pattern7_0, pattern7_1,
-                        )?;
-                        let expr2_0 = C::value_reg(ctx, expr1_0);
-                        return Some(expr2_0);
+                        // Rule at src/isa/aarch64/lower.isle line 891.
+                        let expr0_0 = C::zero_reg(ctx);
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                        let expr2_0 = constructor_sub64(ctx, expr0_0, expr1_0)?;
+                        let expr3_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr4_0 = constructor_rotr64(ctx, expr3_0, expr2_0)?;
+                        let expr5_0 = C::value_reg(ctx, expr4_0);
+                        return Some(expr5_0);
                     }
-                    &Opcode::BxorNot => {
+                    &Opcode::Rotr => {
                         let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                        // Rule at src/isa/aarch64/lower.isle line 668.
-                        let expr0_0 = ALUOp::EorNot32;
-                        let expr1_0 = constructor_alu_rs_imm_logic(
-                            ctx, &expr0_0, pattern3_0, pattern7_0, pattern7_1,
-                        )?;
-                        let expr2_0 = C::value_reg(ctx, expr1_0);
-                        return Some(expr2_0);
+                        // Rule at src/isa/aarch64/lower.isle line 932.
+                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                        let expr2_0 = constructor_rotr64(ctx, expr0_0, expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
                     }
                     &Opcode::Ishl => {
                         let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                        // Rule at src/isa/aarch64/lower.isle line 679.
-                        let expr0_0 = ALUOp::Lsl32;
-                        let expr1_0 = C::put_in_reg(ctx, pattern7_0);
-                        let expr2_0 =
-                            constructor_do_shift(ctx, &expr0_0, pattern3_0, expr1_0, pattern7_1)?;
-                        let expr3_0 = C::value_reg(ctx, expr2_0);
-                        return Some(expr3_0);
+                        // Rule at src/isa/aarch64/lower.isle line 683.
+                        let expr0_0 = ALUOp::Lsl64;
+                        let expr1_0: Type = I64;
+                        let expr2_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr3_0 =
+                            constructor_do_shift(ctx, &expr0_0, expr1_0, expr2_0, pattern7_1)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
                     }
                     &Opcode::Ushr => {
                         let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                        // Rule at src/isa/aarch64/lower.isle line 767.
-                        let expr0_0 = ALUOp::Lsr32;
-                        let expr1_0 = constructor_put_in_reg_zext32(ctx, pattern7_0)?;
-                        let expr2_0 =
-                            constructor_do_shift(ctx, &expr0_0, pattern3_0, expr1_0, pattern7_1)?;
-                        let expr3_0 = C::value_reg(ctx, expr2_0);
-                        return Some(expr3_0);
+                        // Rule at src/isa/aarch64/lower.isle line 771.
+                        let expr0_0 = ALUOp::Lsr64;
+                        let expr1_0: Type = I64;
+                        let expr2_0 = constructor_put_in_reg_zext64(ctx, pattern7_0)?;
+                        let expr3_0 =
+                            constructor_do_shift(ctx, &expr0_0, expr1_0, expr2_0, pattern7_1)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
                     }
                     &Opcode::Sshr => {
                         let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                        // Rule at src/isa/aarch64/lower.isle line 816.
-                        let expr0_0 = ALUOp::Asr32;
-                        let expr1_0 = constructor_put_in_reg_sext32(ctx, pattern7_0)?;
-                        let expr2_0 =
-                            constructor_do_shift(ctx, &expr0_0, pattern3_0, expr1_0, pattern7_1)?;
-                        let expr3_0 = C::value_reg(ctx, expr2_0);
-                        return Some(expr3_0);
+                        // Rule at src/isa/aarch64/lower.isle line 820.
+                        let expr0_0 = ALUOp::Asr64;
+                        let expr1_0: Type = I64;
+                        let expr2_0 = constructor_put_in_reg_sext64(ctx, pattern7_0)?;
+                        let expr3_0 =
+                            constructor_do_shift(ctx, &expr0_0, expr1_0, expr2_0, pattern7_1)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
                     }
                     _ => {}
                 }
             }
         }
-        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
+        if pattern2_0 == I128 {
             let pattern4_0 = C::inst_data(ctx, pattern0_0);
             match &pattern4_0 {
                 &InstructionData::Binary {
@@ -5246,261 +4495,507 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                     args: ref pattern5_1,
                 } => {
                     match &pattern5_0 {
-                        &Opcode::Iadd => {
+                        &Opcode::Imul => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            if let Some(pattern8_0) = C::def_inst(ctx, pattern7_0) {
-                                let pattern9_0 = C::inst_data(ctx, pattern8_0);
-                                match &pattern9_0 {
-                                    &InstructionData::UnaryImm {
-                                        opcode: ref pattern10_0,
-                                        imm: pattern10_1,
-                                    } => {
-                                        if let &Opcode::Iconst = &pattern10_0 {
-                                            let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
-                                            if let Some(pattern13_0) =
-                                                C::imm12_from_u64(ctx, pattern12_0)
-                                            {
-                                                // Rule at src/isa/aarch64/lower.isle line 37.
-                                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
-                                                let expr1_0 = constructor_add_imm(
-                                                    ctx,
-                                                    pattern3_0,
-                                                    expr0_0,
-                                                    pattern13_0,
-                                                )?;
-                                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                                return Some(expr2_0);
-                                            }
-                                            if let Some(pattern13_0) =
-                                                C::imm12_from_negated_u64(ctx, pattern12_0)
+                            // Rule at src/isa/aarch64/lower.isle line 185.
+                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                            let expr1_0: usize = 0;
+                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                            let expr3_0: usize = 1;
+                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                            let expr5_0 = C::put_in_regs(ctx, pattern7_1);
+                            let expr6_0: usize = 0;
+                            let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
+                            let expr8_0: usize = 1;
+                            let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
+                            let expr10_0 = constructor_umulh(ctx, expr2_0, expr7_0)?;
+                            let expr11_0 = constructor_madd64(ctx, expr2_0, expr9_0, expr10_0)?;
+                            let expr12_0 = constructor_madd64(ctx, expr4_0, expr7_0, expr11_0)?;
+                            let expr13_0 = C::zero_reg(ctx);
+                            let expr14_0 = constructor_madd64(ctx, expr2_0, expr7_0, expr13_0)?;
+                            let expr15_0 = C::value_regs(ctx, expr14_0, expr12_0);
+                            return Some(expr15_0);
+                        }
+                        &Opcode::Band => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/aarch64/lower.isle line 612.
+                            let expr0_0 = ALUOp::And64;
+                            let expr1_0 =
+                                constructor_i128_alu_bitop(ctx, &expr0_0, pattern7_0, pattern7_1)?;
+                            return Some(expr1_0);
+                        }
+                        &Opcode::Bor => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/aarch64/lower.isle line 625.
+                            let expr0_0 = ALUOp::Orr64;
+                            let expr1_0 =
+                                constructor_i128_alu_bitop(ctx, &expr0_0, pattern7_0, pattern7_1)?;
+                            return Some(expr1_0);
+                        }
+                        &Opcode::Bxor => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/aarch64/lower.isle line 638.
+                            let expr0_0 = ALUOp::Eor64;
+                            let expr1_0 =
+                                constructor_i128_alu_bitop(ctx, &expr0_0, pattern7_0, pattern7_1)?;
+                            return Some(expr1_0);
+                        }
+                        &Opcode::BandNot => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/aarch64/lower.isle line 651.
+                            let expr0_0 = ALUOp::AndNot64;
+                            let expr1_0 =
+                                constructor_i128_alu_bitop(ctx, &expr0_0, pattern7_0, pattern7_1)?;
+                            return Some(expr1_0);
+                        }
+                        &Opcode::Rotl => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/aarch64/lower.isle line 909.
+                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                            let expr1_0 = C::put_in_regs(ctx, pattern7_1);
+                            let expr2_0: usize = 0;
+                            let expr3_0 = C::value_regs_get(ctx, expr1_0, expr2_0);
+                            let expr4_0: Type = I64;
+                            let expr5_0: u64 = 128;
+                            let expr6_0 = constructor_imm(ctx, expr4_0, expr5_0)?;
+                            let expr7_0 = constructor_sub64(ctx, expr6_0, expr3_0)?;
+                            let expr8_0 = constructor_lower_shl128(ctx, expr0_0, expr3_0)?;
+                            let expr9_0 = constructor_lower_ushr128(ctx, expr0_0, expr7_0)?;
+                            let expr10_0: usize = 0;
+                            let expr11_0 = C::value_regs_get(ctx, expr8_0, expr10_0);
+                            let expr12_0: usize = 0;
+                            let expr13_0 = C::value_regs_get(ctx, expr9_0, expr12_0);
+                            let expr14_0 = constructor_orr64(ctx, expr11_0, expr13_0)?;
+                            let expr15_0: usize = 1;
+                            let expr16_0 = C::value_regs_get(ctx, expr8_0, expr15_0);
+                            let expr17_0: usize = 1;
+                            let expr18_0 = C::value_regs_get(ctx, expr9_0, expr17_0);
+                            let expr19_0 = constructor_orr64(ctx, expr16_0, expr18_0)?;
+                            let expr20_0 = C::value_regs(ctx, expr14_0, expr19_0);
+                            return Some(expr20_0);
+                        }
+                        &Opcode::Rotr => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/aarch64/lower.isle line 996.
+                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                            let expr1_0 = C::put_in_regs(ctx, pattern7_1);
+                            let expr2_0: usize = 0;
+                            let expr3_0 = C::value_regs_get(ctx, expr1_0, expr2_0);
+                            let expr4_0: Type = I64;
+                            let expr5_0: u64 = 128;
+                            let expr6_0 = constructor_imm(ctx, expr4_0, expr5_0)?;
+                            let expr7_0 = constructor_sub64(ctx, expr6_0, expr3_0)?;
+                            let expr8_0 = constructor_lower_ushr128(ctx, expr0_0, expr3_0)?;
+                            let expr9_0 = constructor_lower_shl128(ctx, expr0_0, expr7_0)?;
+                            let expr10_0: usize = 1;
+                            let expr11_0 = C::value_regs_get(ctx, expr8_0, expr10_0);
+                            let expr12_0: usize = 1;
+                            let expr13_0 = C::value_regs_get(ctx, expr9_0, expr12_0);
+                            let expr14_0 = constructor_orr64(ctx, expr11_0, expr13_0)?;
+                            let expr15_0: usize = 0;
+                            let expr16_0 = C::value_regs_get(ctx, expr8_0, expr15_0);
+                            let expr17_0: usize = 0;
+                            let expr18_0 = C::value_regs_get(ctx, expr9_0, expr17_0);
+                            let expr19_0 = constructor_orr64(ctx, expr16_0, expr18_0)?;
+                            let expr20_0 = C::value_regs(ctx, expr19_0, expr14_0);
+                            return Some(expr20_0);
+                        }
+                        &Opcode::Ishl => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/aarch64/lower.isle line 687.
+                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                            let expr1_0 = C::put_in_regs(ctx, pattern7_1);
+                            let expr2_0: usize = 0;
+                            let expr3_0 = C::value_regs_get(ctx, expr1_0, expr2_0);
+                            let expr4_0 = constructor_lower_shl128(ctx, expr0_0, expr3_0)?;
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Ushr => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/aarch64/lower.isle line 775.
+                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                            let expr1_0 = C::put_in_regs(ctx, pattern7_1);
+                            let expr2_0: usize = 0;
+                            let expr3_0 = C::value_regs_get(ctx, expr1_0, expr2_0);
+                            let expr4_0 = constructor_lower_ushr128(ctx, expr0_0, expr3_0)?;
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Sshr => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/aarch64/lower.isle line 824.
+                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                            let expr1_0 = C::put_in_regs(ctx, pattern7_1);
+                            let expr2_0: usize = 0;
+                            let expr3_0 = C::value_regs_get(ctx, expr1_0, expr2_0);
+                            let expr4_0 = constructor_lower_sshr128(ctx, expr0_0, expr3_0)?;
+                            return Some(expr4_0);
+                        }
+                        _ => {}
+                    }
+                }
+                &InstructionData::Unary {
+                    opcode: ref pattern5_0,
+                    arg: pattern5_1,
+                } => {
+                    if let &Opcode::Sextend = &pattern5_0 {
+                        // Rule at src/isa/aarch64/lower.isle line 537.
+                        let expr0_0 = constructor_put_in_reg_sext64(ctx, pattern5_1)?;
+                        let expr1_0: u8 = 63;
+                        let expr2_0 = C::imm_shift_from_u8(ctx, expr1_0);
+                        let expr3_0 = constructor_asr64_imm(ctx, expr0_0, expr2_0)?;
+                        let expr4_0 = C::value_regs(ctx, expr0_0, expr3_0);
+                        return Some(expr4_0);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if pattern2_0 == I16X8 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Imul = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    if let Some(pattern8_0) = C::def_inst(ctx, pattern7_0) {
+                        let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                        if let &InstructionData::Unary {
+                            opcode: ref pattern10_0,
+                            arg: pattern10_1,
+                        } = &pattern9_0
+                        {
+                            match &pattern10_0 {
+                                &Opcode::SwidenLow => {
+                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
+                                    if pattern12_0 == I8X16 {
+                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
+                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
+                                            if let &InstructionData::Unary {
+                                                opcode: ref pattern16_0,
+                                                arg: pattern16_1,
+                                            } = &pattern15_0
                                             {
-                                                // Rule at src/isa/aarch64/lower.isle line 45.
-                                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
-                                                let expr1_0 = constructor_sub_imm(
-                                                    ctx,
-                                                    pattern3_0,
-                                                    expr0_0,
-                                                    pattern13_0,
-                                                )?;
-                                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                                return Some(expr2_0);
-                                            }
-                                        }
-                                    }
-                                    &InstructionData::Binary {
-                                        opcode: ref pattern10_0,
-                                        args: ref pattern10_1,
-                                    } => {
-                                        match &pattern10_0 {
-                                            &Opcode::Imul => {
-                                                let (pattern12_0, pattern12_1) =
-                                                    C::unpack_value_array_2(ctx, &pattern10_1);
-                                                // Rule at src/isa/aarch64/lower.isle line 70.
-                                                let expr0_0 = C::put_in_reg(ctx, pattern12_0);
-                                                let expr1_0 = C::put_in_reg(ctx, pattern12_1);
-                                                let expr2_0 = C::put_in_reg(ctx, pattern7_1);
-                                                let expr3_0 = constructor_madd(
-                                                    ctx, pattern3_0, expr0_0, expr1_0, expr2_0,
-                                                )?;
-                                                let expr4_0 = C::value_reg(ctx, expr3_0);
-                                                return Some(expr4_0);
-                                            }
-                                            &Opcode::Ishl => {
-                                                let (pattern12_0, pattern12_1) =
-                                                    C::unpack_value_array_2(ctx, &pattern10_1);
-                                                if let Some(pattern13_0) =
-                                                    C::def_inst(ctx, pattern12_1)
-                                                {
-                                                    let pattern14_0 =
-                                                        C::inst_data(ctx, pattern13_0);
-                                                    if let &InstructionData::UnaryImm {
-                                                        opcode: ref pattern15_0,
-                                                        imm: pattern15_1,
-                                                    } = &pattern14_0
-                                                    {
-                                                        if let &Opcode::Iconst = &pattern15_0 {
-                                                            let closure17 = || {
-                                                                return Some(pattern3_0);
-                                                            };
-                                                            if let Some(pattern17_0) = closure17() {
-                                                                if let Some(pattern18_0) =
-                                                                    C::lshl_from_imm64(
-                                                                        ctx,
-                                                                        pattern15_1,
-                                                                        pattern17_0,
-                                                                    )
-                                                                {
-                                                                    // Rule at src/isa/aarch64/lower.isle line 62.
-                                                                    let expr0_0 = C::put_in_reg(
-                                                                        ctx, pattern7_1,
-                                                                    );
-                                                                    let expr1_0 = C::put_in_reg(
-                                                                        ctx,
-                                                                        pattern12_0,
-                                                                    );
-                                                                    let expr2_0 =
-                                                                        constructor_add_shift(
-                                                                            ctx,
-                                                                            pattern3_0,
-                                                                            expr0_0,
-                                                                            expr1_0,
-                                                                            pattern18_0,
-                                                                        )?;
-                                                                    let expr3_0 =
-                                                                        C::value_reg(ctx, expr2_0);
-                                                                    return Some(expr3_0);
-                                                                }
-                                                            }
-                                                        }
+                                                if let &Opcode::SwidenLow = &pattern16_0 {
+                                                    let pattern18_0 =
+                                                        C::value_type(ctx, pattern16_1);
+                                                    if pattern18_0 == I8X16 {
+                                                        // Rule at src/isa/aarch64/lower.isle line 287.
+                                                        let expr0_0 =
+                                                            C::put_in_reg(ctx, pattern10_1);
+                                                        let expr1_0 =
+                                                            C::put_in_reg(ctx, pattern16_1);
+                                                        let expr2_0: bool = false;
+                                                        let expr3_0 = constructor_smull8(
+                                                            ctx, expr0_0, expr1_0, expr2_0,
+                                                        )?;
+                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                                                        return Some(expr4_0);
                                                     }
                                                 }
                                             }
-                                            _ => {}
                                         }
                                     }
-                                    _ => {}
                                 }
-                            }
-                            if let Some(pattern8_0) = C::extended_value_from_value(ctx, pattern7_0)
-                            {
-                                // Rule at src/isa/aarch64/lower.isle line 53.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
-                                let expr1_0 =
-                                    constructor_add_extend(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
-                            }
-                            if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
-                                let pattern9_0 = C::inst_data(ctx, pattern8_0);
-                                match &pattern9_0 {
-                                    &InstructionData::UnaryImm {
-                                        opcode: ref pattern10_0,
-                                        imm: pattern10_1,
-                                    } => {
-                                        if let &Opcode::Iconst = &pattern10_0 {
-                                            let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
-                                            if let Some(pattern13_0) =
-                                                C::imm12_from_u64(ctx, pattern12_0)
-                                            {
-                                                // Rule at src/isa/aarch64/lower.isle line 34.
-                                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                                let expr1_0 = constructor_add_imm(
-                                                    ctx,
-                                                    pattern3_0,
-                                                    expr0_0,
-                                                    pattern13_0,
-                                                )?;
-                                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                                return Some(expr2_0);
-                                            }
-                                            if let Some(pattern13_0) =
-                                                C::imm12_from_negated_u64(ctx, pattern12_0)
+                                &Opcode::SwidenHigh => {
+                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
+                                    if pattern12_0 == I8X16 {
+                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
+                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
+                                            if let &InstructionData::Unary {
+                                                opcode: ref pattern16_0,
+                                                arg: pattern16_1,
+                                            } = &pattern15_0
                                             {
-                                                // Rule at src/isa/aarch64/lower.isle line 42.
-                                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                                let expr1_0 = constructor_sub_imm(
-                                                    ctx,
-                                                    pattern3_0,
-                                                    expr0_0,
-                                                    pattern13_0,
-                                                )?;
-                                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                                return Some(expr2_0);
+                                                if let &Opcode::SwidenHigh = &pattern16_0 {
+                                                    let pattern18_0 =
+                                                        C::value_type(ctx, pattern16_1);
+                                                    if pattern18_0 == I8X16 {
+                                                        // Rule at src/isa/aarch64/lower.isle line 293.
+                                                        let expr0_0 =
+                                                            C::put_in_reg(ctx, pattern10_1);
+                                                        let expr1_0 =
+                                                            C::put_in_reg(ctx, pattern16_1);
+                                                        let expr2_0: bool = true;
+                                                        let expr3_0 = constructor_smull8(
+                                                            ctx, expr0_0, expr1_0, expr2_0,
+                                                        )?;
+                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                                                        return Some(expr4_0);
+                                                    }
+                                                }
                                             }
                                         }
                                     }
-                                    &InstructionData::Binary {
-                                        opcode: ref pattern10_0,
-                                        args: ref pattern10_1,
-                                    } => {
-                                        match &pattern10_0 {
-                                            &Opcode::Imul => {
-                                                let (pattern12_0, pattern12_1) =
-                                                    C::unpack_value_array_2(ctx, &pattern10_1);
-                                                // Rule at src/isa/aarch64/lower.isle line 67.
-                                                let expr0_0 = C::put_in_reg(ctx, pattern12_0);
-                                                let expr1_0 = C::put_in_reg(ctx, pattern12_1);
-                                                let expr2_0 = C::put_in_reg(ctx, pattern7_0);
-                                                let expr3_0 = constructor_madd(
-                                                    ctx, pattern3_0, expr0_0, expr1_0, expr2_0,
-                                                )?;
-                                                let expr4_0 = C::value_reg(ctx, expr3_0);
-                                                return Some(expr4_0);
-                                            }
-                                            &Opcode::Ishl => {
-                                                let (pattern12_0, pattern12_1) =
-                                                    C::unpack_value_array_2(ctx, &pattern10_1);
-                                                if let Some(pattern13_0) =
-                                                    C::def_inst(ctx, pattern12_1)
-                                                {
-                                                    let pattern14_0 =
-                                                        C::inst_data(ctx, pattern13_0);
-                                                    if let &InstructionData::UnaryImm {
-                                                        opcode: ref pattern15_0,
-                                                        imm: pattern15_1,
-                                                    } = &pattern14_0
-                                                    {
-                                                        if let &Opcode::Iconst = &pattern15_0 {
-                                                            let closure17 = || {
-                                                                return Some(pattern3_0);
-                                                            };
-                                                            if let Some(pattern17_0) = closure17() {
-                                                                if let Some(pattern18_0) =
-                                                                    C::lshl_from_imm64(
-                                                                        ctx,
-                                                                        pattern15_1,
-                                                                        pattern17_0,
-                                                                    )
-                                                                {
-                                                                    // Rule at src/isa/aarch64/lower.isle line 58.
-                                                                    let expr0_0 = C::put_in_reg(
-                                                                        ctx, pattern7_0,
-                                                                    );
-                                                                    let expr1_0 = C::put_in_reg(
-                                                                        ctx,
-                                                                        pattern12_0,
-                                                                    );
-                                                                    let expr2_0 =
-                                                                        constructor_add_shift(
-                                                                            ctx,
-                                                                            pattern3_0,
-                                                                            expr0_0,
-                                                                            expr1_0,
-                                                                            pattern18_0,
-                                                                        )?;
-                                                                    let expr3_0 =
-                                                                        C::value_reg(ctx, expr2_0);
-                                                                    return Some(expr3_0);
-                                                                }
-                                                            }
-                                                        }
+                                }
+                                &Opcode::UwidenLow => {
+                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
+                                    if pattern12_0 == I8X16 {
+                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
+                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
+                                            if let &InstructionData::Unary {
+                                                opcode: ref pattern16_0,
+                                                arg: pattern16_1,
+                                            } = &pattern15_0
+                                            {
+                                                if let &Opcode::UwidenLow = &pattern16_0 {
+                                                    let pattern18_0 =
+                                                        C::value_type(ctx, pattern16_1);
+                                                    if pattern18_0 == I8X16 {
+                                                        // Rule at src/isa/aarch64/lower.isle line 299.
+                                                        let expr0_0 =
+                                                            C::put_in_reg(ctx, pattern10_1);
+                                                        let expr1_0 =
+                                                            C::put_in_reg(ctx, pattern16_1);
+                                                        let expr2_0: bool = false;
+                                                        let expr3_0 = constructor_umull8(
+                                                            ctx, expr0_0, expr1_0, expr2_0,
+                                                        )?;
+                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                                                        return Some(expr4_0);
                                                     }
                                                 }
                                             }
-                                            _ => {}
                                         }
                                     }
-                                    _ => {}
                                 }
-                            }
-                            if let Some(pattern8_0) = C::extended_value_from_value(ctx, pattern7_1)
-                            {
-                                // Rule at src/isa/aarch64/lower.isle line 50.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr1_0 =
-                                    constructor_add_extend(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
-                            }
-                            // Rule at src/isa/aarch64/lower.isle line 30.
-                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                            let expr1_0 = C::put_in_reg(ctx, pattern7_1);
-                            let expr2_0 = constructor_add(ctx, pattern3_0, expr0_0, expr1_0)?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
+                                &Opcode::UwidenHigh => {
+                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
+                                    if pattern12_0 == I8X16 {
+                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
+                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
+                                            if let &InstructionData::Unary {
+                                                opcode: ref pattern16_0,
+                                                arg: pattern16_1,
+                                            } = &pattern15_0
+                                            {
+                                                if let &Opcode::UwidenHigh = &pattern16_0 {
+                                                    let pattern18_0 =
+                                                        C::value_type(ctx, pattern16_1);
+                                                    if pattern18_0 == I8X16 {
+                                                        // Rule at src/isa/aarch64/lower.isle line 305.
+                                                        let expr0_0 =
+                                                            C::put_in_reg(ctx, pattern10_1);
+                                                        let expr1_0 =
+                                                            C::put_in_reg(ctx, pattern16_1);
+                                                        let expr2_0: bool = true;
+                                                        let expr3_0 = constructor_umull8(
+                                                            ctx, expr0_0, expr1_0, expr2_0,
+                                                        )?;
+                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                                                        return Some(expr4_0);
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                _ => {}
+                            }
                         }
-                        &Opcode::Isub => {
+                    }
+                }
+            }
+        }
+        if pattern2_0 == I32X4 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Imul = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    if let Some(pattern8_0) = C::def_inst(ctx, pattern7_0) {
+                        let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                        if let &InstructionData::Unary {
+                            opcode: ref pattern10_0,
+                            arg: pattern10_1,
+                        } = &pattern9_0
+                        {
+                            match &pattern10_0 {
+                                &Opcode::SwidenLow => {
+                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
+                                    if pattern12_0 == I16X8 {
+                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
+                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
+                                            if let &InstructionData::Unary {
+                                                opcode: ref pattern16_0,
+                                                arg: pattern16_1,
+                                            } = &pattern15_0
+                                            {
+                                                if let &Opcode::SwidenLow = &pattern16_0 {
+                                                    let pattern18_0 =
+                                                        C::value_type(ctx, pattern16_1);
+                                                    if pattern18_0 == I16X8 {
+                                                        // Rule at src/isa/aarch64/lower.isle line 311.
+                                                        let expr0_0 =
+                                                            C::put_in_reg(ctx, pattern10_1);
+                                                        let expr1_0 =
+                                                            C::put_in_reg(ctx, pattern16_1);
+                                                        let expr2_0: bool = false;
+                                                        let expr3_0 = constructor_smull16(
+                                                            ctx, expr0_0, expr1_0, expr2_0,
+                                                        )?;
+                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                                                        return Some(expr4_0);
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                &Opcode::SwidenHigh => {
+                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
+                                    if pattern12_0 == I16X8 {
+                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
+                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
+                                            if let &InstructionData::Unary {
+                                                opcode: ref pattern16_0,
+                                                arg: pattern16_1,
+                                            } = &pattern15_0
+                                            {
+                                                if let &Opcode::SwidenHigh = &pattern16_0 {
+                                                    let pattern18_0 =
+                                                        C::value_type(ctx, pattern16_1);
+                                                    if pattern18_0 == I16X8 {
+                                                        // Rule at src/isa/aarch64/lower.isle line 317.
+                                                        let expr0_0 =
+                                                            C::put_in_reg(ctx, pattern10_1);
+                                                        let expr1_0 =
+                                                            C::put_in_reg(ctx, pattern16_1);
+                                                        let expr2_0: bool = true;
+                                                        let expr3_0 = constructor_smull16(
+                                                            ctx, expr0_0, expr1_0, expr2_0,
+                                                        )?;
+                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                                                        return Some(expr4_0);
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                &Opcode::UwidenLow => {
+                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
+                                    if pattern12_0 == I16X8 {
+                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
+                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
+                                            if let &InstructionData::Unary {
+                                                opcode: ref pattern16_0,
+                                                arg: pattern16_1,
+                                            } = &pattern15_0
+                                            {
+                                                if let &Opcode::UwidenLow = &pattern16_0 {
+                                                    let pattern18_0 =
+                                                        C::value_type(ctx, pattern16_1);
+                                                    if pattern18_0 == I16X8 {
+                                                        // Rule at src/isa/aarch64/lower.isle line 323.
+                                                        let expr0_0 =
+                                                            C::put_in_reg(ctx, pattern10_1);
+                                                        let expr1_0 =
+                                                            C::put_in_reg(ctx, pattern16_1);
+                                                        let expr2_0: bool = false;
+                                                        let expr3_0 = constructor_umull16(
+                                                            ctx, expr0_0, expr1_0, expr2_0,
+                                                        )?;
+                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                                                        return Some(expr4_0);
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                &Opcode::UwidenHigh => {
+                                    let pattern12_0 = C::value_type(ctx, pattern10_1);
+                                    if pattern12_0 == I16X8 {
+                                        if let Some(pattern14_0) = C::def_inst(ctx, pattern7_1) {
+                                            let pattern15_0 = C::inst_data(ctx, pattern14_0);
+                                            if let &InstructionData::Unary {
+                                                opcode: ref pattern16_0,
+                                                arg: pattern16_1,
+                                            } = &pattern15_0
+                                            {
+                                                if let &Opcode::UwidenHigh = &pattern16_0 {
+                                                    let pattern18_0 =
+                                                        C::value_type(ctx, pattern16_1);
+                                                    if pattern18_0 == I16X8 {
+                                                        // Rule at src/isa/aarch64/lower.isle line 329.
+                                                        let expr0_0 =
+                                                            C::put_in_reg(ctx, pattern10_1);
+                                                        let expr1_0 =
+                                                            C::put_in_reg(ctx, pattern16_1);
+                                                        let expr2_0: bool = true;
+                                                        let expr3_0 = constructor_umull16(
+                                                            ctx, expr0_0, expr1_0, expr2_0,
+                                                        )?;
+                                                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                                                        return Some(expr4_0);
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        if pattern2_0 == I64X2 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Imul = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    // Rule at src/isa/aarch64/lower.isle line 246.
+                    let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                    let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                    let expr2_0 = VectorSize::Size32x4;
+                    let expr3_0 = constructor_rev64(ctx, expr1_0, &expr2_0)?;
+                    let expr4_0 = VectorSize::Size32x4;
+                    let expr5_0 = constructor_mul(ctx, expr3_0, expr0_0, &expr4_0)?;
+                    let expr6_0: bool = false;
+                    let expr7_0 = constructor_xtn64(ctx, expr0_0, expr6_0)?;
+                    let expr8_0 = VectorSize::Size32x4;
+                    let expr9_0 = constructor_addp(ctx, expr5_0, expr5_0, &expr8_0)?;
+                    let expr10_0: bool = false;
+                    let expr11_0 = constructor_xtn64(ctx, expr1_0, expr10_0)?;
+                    let expr12_0: bool = false;
+                    let expr13_0 = constructor_shll32(ctx, expr9_0, expr12_0)?;
+                    let expr14_0: bool = false;
+                    let expr15_0 = constructor_umlal32(ctx, expr13_0, expr11_0, expr7_0, expr14_0)?;
+                    let expr16_0 = C::value_reg(ctx, expr15_0);
+                    return Some(expr16_0);
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            match &pattern4_0 {
+                &InstructionData::Binary {
+                    opcode: ref pattern5_0,
+                    args: ref pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Iadd => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
                             if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
@@ -5512,26 +5007,12 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                                     } => {
                                         if let &Opcode::Iconst = &pattern10_0 {
                                             let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
-                                            if let Some(pattern13_0) =
-                                                C::imm12_from_u64(ctx, pattern12_0)
-                                            {
-                                                // Rule at src/isa/aarch64/lower.isle line 106.
-                                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                                let expr1_0 = constructor_sub_imm(
-                                                    ctx,
-                                                    pattern3_0,
-                                                    expr0_0,
-                                                    pattern13_0,
-                                                )?;
-                                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                                return Some(expr2_0);
-                                            }
                                             if let Some(pattern13_0) =
                                                 C::imm12_from_negated_u64(ctx, pattern12_0)
                                             {
-                                                // Rule at src/isa/aarch64/lower.isle line 111.
+                                                // Rule at src/isa/aarch64/lower.isle line 42.
                                                 let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                                let expr1_0 = constructor_add_imm(
+                                                let expr1_0 = constructor_sub_imm(
                                                     ctx,
                                                     pattern3_0,
                                                     expr0_0,
@@ -5546,17 +5027,120 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                                         opcode: ref pattern10_0,
                                         args: ref pattern10_1,
                                     } => {
-                                        if let &Opcode::Ishl = &pattern10_0 {
-                                            let (pattern12_0, pattern12_1) =
-                                                C::unpack_value_array_2(ctx, &pattern10_1);
-                                            if let Some(pattern13_0) = C::def_inst(ctx, pattern12_1)
-                                            {
-                                                let pattern14_0 = C::inst_data(ctx, pattern13_0);
-                                                if let &InstructionData::UnaryImm {
-                                                    opcode: ref pattern15_0,
-                                                    imm: pattern15_1,
-                                                } = &pattern14_0
-                                                {
+                                        match &pattern10_0 {
+                                            &Opcode::Imul => {
+                                                let (pattern12_0, pattern12_1) =
+                                                    C::unpack_value_array_2(ctx, &pattern10_1);
+                                                // Rule at src/isa/aarch64/lower.isle line 67.
+                                                let expr0_0 = C::put_in_reg(ctx, pattern12_0);
+                                                let expr1_0 = C::put_in_reg(ctx, pattern12_1);
+                                                let expr2_0 = C::put_in_reg(ctx, pattern7_0);
+                                                let expr3_0 = constructor_madd(
+                                                    ctx, pattern3_0, expr0_0, expr1_0, expr2_0,
+                                                )?;
+                                                let expr4_0 = C::value_reg(ctx, expr3_0);
+                                                return Some(expr4_0);
+                                            }
+                                            &Opcode::Ishl => {
+                                                let (pattern12_0, pattern12_1) =
+                                                    C::unpack_value_array_2(ctx, &pattern10_1);
+                                                if let Some(pattern13_0) =
+                                                    C::def_inst(ctx, pattern12_1)
+                                                {
+                                                    let pattern14_0 =
+                                                        C::inst_data(ctx, pattern13_0);
+                                                    if let &InstructionData::UnaryImm {
+                                                        opcode: ref pattern15_0,
+                                                        imm: pattern15_1,
+                                                    } = &pattern14_0
+                                                    {
+                                                        if let &Opcode::Iconst = &pattern15_0 {
+                                                            let closure17 = || {
+                                                                return Some(pattern3_0);
+                                                            };
+                                                            if let Some(pattern17_0) = closure17() {
+                                                                if let Some(pattern18_0) =
+                                                                    C::lshl_from_imm64(
+                                                                        ctx,
+                                                                        pattern15_1,
+                                                                        pattern17_0,
+                                                                    )
+                                                                {
+                                                                    // Rule at src/isa/aarch64/lower.isle line 58.
+                                                                    let expr0_0 = C::put_in_reg(
+                                                                        ctx, pattern7_0,
+                                                                    );
+                                                                    let expr1_0 = C::put_in_reg(
+                                                                        ctx,
+                                                                        pattern12_0,
+                                                                    );
+                                                                    let expr2_0 =
+                                                                        constructor_add_shift(
+                                                                            ctx,
+                                                                            pattern3_0,
+                                                                            expr0_0,
+                                                                            expr1_0,
+                                                                            pattern18_0,
+                                                                        )?;
+                                                                    let expr3_0 =
+                                                                        C::value_reg(ctx, expr2_0);
+                                                                    return Some(expr3_0);
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                            _ => {}
+                                        }
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                        &Opcode::Isub => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                                let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                                match &pattern9_0 {
+                                    &InstructionData::UnaryImm {
+                                        opcode: ref pattern10_0,
+                                        imm: pattern10_1,
+                                    } => {
+                                        if let &Opcode::Iconst = &pattern10_0 {
+                                            let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
+                                            if let Some(pattern13_0) =
+                                                C::imm12_from_negated_u64(ctx, pattern12_0)
+                                            {
+                                                // Rule at src/isa/aarch64/lower.isle line 111.
+                                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                                let expr1_0 = constructor_add_imm(
+                                                    ctx,
+                                                    pattern3_0,
+                                                    expr0_0,
+                                                    pattern13_0,
+                                                )?;
+                                                let expr2_0 = C::value_reg(ctx, expr1_0);
+                                                return Some(expr2_0);
+                                            }
+                                        }
+                                    }
+                                    &InstructionData::Binary {
+                                        opcode: ref pattern10_0,
+                                        args: ref pattern10_1,
+                                    } => {
+                                        if let &Opcode::Ishl = &pattern10_0 {
+                                            let (pattern12_0, pattern12_1) =
+                                                C::unpack_value_array_2(ctx, &pattern10_1);
+                                            if let Some(pattern13_0) = C::def_inst(ctx, pattern12_1)
+                                            {
+                                                let pattern14_0 = C::inst_data(ctx, pattern13_0);
+                                                if let &InstructionData::UnaryImm {
+                                                    opcode: ref pattern15_0,
+                                                    imm: pattern15_1,
+                                                } = &pattern14_0
+                                                {
                                                     if let &Opcode::Iconst = &pattern15_0 {
                                                         let closure17 = || {
                                                             return Some(pattern3_0);
@@ -5595,33 +5179,920 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                                     _ => {}
                                 }
                             }
-                            if let Some(pattern8_0) = C::extended_value_from_value(ctx, pattern7_1)
-                            {
-                                // Rule at src/isa/aarch64/lower.isle line 116.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr1_0 =
-                                    constructor_sub_extend(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
+                        }
+                        _ => {}
+                    }
+                }
+                &InstructionData::Unary {
+                    opcode: ref pattern5_0,
+                    arg: pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Bnot => {
+                            if let Some(pattern7_0) = C::def_inst(ctx, pattern5_1) {
+                                let pattern8_0 = C::inst_data(ctx, pattern7_0);
+                                if let &InstructionData::Binary {
+                                    opcode: ref pattern9_0,
+                                    args: ref pattern9_1,
+                                } = &pattern8_0
+                                {
+                                    if let &Opcode::Ishl = &pattern9_0 {
+                                        let (pattern11_0, pattern11_1) =
+                                            C::unpack_value_array_2(ctx, &pattern9_1);
+                                        if let Some(pattern12_0) = C::def_inst(ctx, pattern11_1) {
+                                            let pattern13_0 = C::inst_data(ctx, pattern12_0);
+                                            if let &InstructionData::UnaryImm {
+                                                opcode: ref pattern14_0,
+                                                imm: pattern14_1,
+                                            } = &pattern13_0
+                                            {
+                                                if let &Opcode::Iconst = &pattern14_0 {
+                                                    let closure16 = || {
+                                                        return Some(pattern3_0);
+                                                    };
+                                                    if let Some(pattern16_0) = closure16() {
+                                                        if let Some(pattern17_0) =
+                                                            C::lshl_from_imm64(
+                                                                ctx,
+                                                                pattern14_1,
+                                                                pattern16_0,
+                                                            )
+                                                        {
+                                                            // Rule at src/isa/aarch64/lower.isle line 585.
+                                                            let expr0_0 = C::zero_reg(ctx);
+                                                            let expr1_0 =
+                                                                C::put_in_reg(ctx, pattern11_0);
+                                                            let expr2_0 =
+                                                                constructor_orr_not_shift(
+                                                                    ctx,
+                                                                    pattern3_0,
+                                                                    expr0_0,
+                                                                    expr1_0,
+                                                                    pattern17_0,
+                                                                )?;
+                                                            let expr3_0 =
+                                                                C::value_reg(ctx, expr2_0);
+                                                            return Some(expr3_0);
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
                             }
-                            // Rule at src/isa/aarch64/lower.isle line 102.
+                        }
+                        &Opcode::Uextend => {
+                            if let Some(pattern7_0) = C::def_inst(ctx, pattern5_1) {
+                                let pattern8_0 = C::inst_data(ctx, pattern7_0);
+                                if let &InstructionData::BinaryImm8 {
+                                    opcode: ref pattern9_0,
+                                    arg: pattern9_1,
+                                    imm: pattern9_2,
+                                } = &pattern8_0
+                                {
+                                    if let &Opcode::Extractlane = &pattern9_0 {
+                                        let pattern11_0 = C::value_type(ctx, pattern9_1);
+                                        let pattern12_0 = C::u8_from_uimm8(ctx, pattern9_2);
+                                        // Rule at src/isa/aarch64/lower.isle line 496.
+                                        let expr0_0 = C::put_in_reg(ctx, pattern9_1);
+                                        let expr1_0 = constructor_vector_size(ctx, pattern11_0)?;
+                                        let expr2_0 = constructor_mov_from_vec(
+                                            ctx,
+                                            expr0_0,
+                                            pattern12_0,
+                                            &expr1_0,
+                                        )?;
+                                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                                        return Some(expr3_0);
+                                    }
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if pattern2_0 == I64 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Umulhi => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 374.
+                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                        let expr2_0 = constructor_umulh(ctx, expr0_0, expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    &Opcode::Smulhi => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 360.
+                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                        let expr2_0 = constructor_smulh(ctx, expr0_0, expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    &Opcode::BorNot => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 661.
+                        let expr0_0 = ALUOp::OrrNot64;
+                        let expr1_0: Type = I64;
+                        let expr2_0 = constructor_alu_rs_imm_logic(
+                            ctx, &expr0_0, expr1_0, pattern7_0, pattern7_1,
+                        )?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    &Opcode::BxorNot => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 671.
+                        let expr0_0 = ALUOp::EorNot64;
+                        let expr1_0: Type = I64;
+                        let expr2_0 = constructor_alu_rs_imm_logic(
+                            ctx, &expr0_0, expr1_0, pattern7_0, pattern7_1,
+                        )?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if pattern2_0 == I128 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::BorNot => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 664.
+                        let expr0_0 = ALUOp::OrrNot64;
+                        let expr1_0 =
+                            constructor_i128_alu_bitop(ctx, &expr0_0, pattern7_0, pattern7_1)?;
+                        return Some(expr1_0);
+                    }
+                    &Opcode::BxorNot => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 674.
+                        let expr0_0 = ALUOp::EorNot64;
+                        let expr1_0 =
+                            constructor_i128_alu_bitop(ctx, &expr0_0, pattern7_0, pattern7_1)?;
+                        return Some(expr1_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_16(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Rotl => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let closure12 = || {
+                                        return Some(pattern3_0);
+                                    };
+                                    if let Some(pattern12_0) = closure12() {
+                                        if let Some(pattern13_0) =
+                                            C::imm_shift_from_imm64(ctx, pattern10_1, pattern12_0)
+                                        {
+                                            // Rule at src/isa/aarch64/lower.isle line 874.
+                                            let expr0_0 =
+                                                constructor_put_in_reg_zext32(ctx, pattern7_0)?;
+                                            let expr1_0 =
+                                                C::negate_imm_shift(ctx, pattern3_0, pattern13_0);
+                                            let expr2_0 = constructor_small_rotr_imm(
+                                                ctx, pattern3_0, expr0_0, expr1_0,
+                                            )?;
+                                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                                            return Some(expr3_0);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    &Opcode::Rotr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let closure12 = || {
+                                        return Some(pattern3_0);
+                                    };
+                                    if let Some(pattern12_0) = closure12() {
+                                        if let Some(pattern13_0) =
+                                            C::imm_shift_from_imm64(ctx, pattern10_1, pattern12_0)
+                                        {
+                                            // Rule at src/isa/aarch64/lower.isle line 936.
+                                            let expr0_0 =
+                                                constructor_put_in_reg_zext32(ctx, pattern7_0)?;
+                                            let expr1_0 = constructor_small_rotr_imm(
+                                                ctx,
+                                                pattern3_0,
+                                                expr0_0,
+                                                pattern13_0,
+                                            )?;
+                                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                                            return Some(expr2_0);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_32(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Band => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 606.
+                        let expr0_0 = ALUOp::And32;
+                        let expr1_0 = constructor_alu_rs_imm_logic_commutative(
+                            ctx, &expr0_0, pattern3_0, pattern7_0, pattern7_1,
+                        )?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                    &Opcode::Bor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 619.
+                        let expr0_0 = ALUOp::Orr32;
+                        let expr1_0 = constructor_alu_rs_imm_logic_commutative(
+                            ctx, &expr0_0, pattern3_0, pattern7_0, pattern7_1,
+                        )?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                    &Opcode::Bxor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 632.
+                        let expr0_0 = ALUOp::Eor32;
+                        let expr1_0 = constructor_alu_rs_imm_logic_commutative(
+                            ctx, &expr0_0, pattern3_0, pattern7_0, pattern7_1,
+                        )?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                    &Opcode::BandNot => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 645.
+                        let expr0_0 = ALUOp::AndNot32;
+                        let expr1_0 = constructor_alu_rs_imm_logic(
+                            ctx, &expr0_0, pattern3_0, pattern7_0, pattern7_1,
+                        )?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                    &Opcode::Ishl => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 679.
+                        let expr0_0 = ALUOp::Lsl32;
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr2_0 =
+                            constructor_do_shift(ctx, &expr0_0, pattern3_0, expr1_0, pattern7_1)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    &Opcode::Ushr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 767.
+                        let expr0_0 = ALUOp::Lsr32;
+                        let expr1_0 = constructor_put_in_reg_zext32(ctx, pattern7_0)?;
+                        let expr2_0 =
+                            constructor_do_shift(ctx, &expr0_0, pattern3_0, expr1_0, pattern7_1)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    &Opcode::Sshr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 816.
+                        let expr0_0 = ALUOp::Asr32;
+                        let expr1_0 = constructor_put_in_reg_sext32(ctx, pattern7_0)?;
+                        let expr2_0 =
+                            constructor_do_shift(ctx, &expr0_0, pattern3_0, expr1_0, pattern7_1)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            match &pattern4_0 {
+                &InstructionData::Binary {
+                    opcode: ref pattern5_0,
+                    args: ref pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Iadd => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            if let Some(pattern8_0) = C::extended_value_from_value(ctx, pattern7_1)
+                            {
+                                // Rule at src/isa/aarch64/lower.isle line 50.
+                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                let expr1_0 =
+                                    constructor_add_extend(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                                let expr2_0 = C::value_reg(ctx, expr1_0);
+                                return Some(expr2_0);
+                            }
+                        }
+                        &Opcode::Isub => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            if let Some(pattern8_0) = C::extended_value_from_value(ctx, pattern7_1)
+                            {
+                                // Rule at src/isa/aarch64/lower.isle line 116.
+                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                let expr1_0 =
+                                    constructor_sub_extend(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                                let expr2_0 = C::value_reg(ctx, expr1_0);
+                                return Some(expr2_0);
+                            }
+                        }
+                        &Opcode::Imul => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/aarch64/lower.isle line 181.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr2_0 = C::zero_reg(ctx);
+                            let expr3_0 =
+                                constructor_madd(ctx, pattern3_0, expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Sdiv => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                                let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                                if let &InstructionData::UnaryImm {
+                                    opcode: ref pattern10_0,
+                                    imm: pattern10_1,
+                                } = &pattern9_0
+                                {
+                                    if let &Opcode::Iconst = &pattern10_0 {
+                                        if let Some(pattern12_0) =
+                                            C::safe_divisor_from_imm64(ctx, pattern10_1)
+                                        {
+                                            // Rule at src/isa/aarch64/lower.isle line 441.
+                                            let expr0_0 =
+                                                constructor_put_in_reg_sext64(ctx, pattern7_0)?;
+                                            let expr1_0 =
+                                                constructor_imm(ctx, pattern3_0, pattern12_0)?;
+                                            let expr2_0 =
+                                                constructor_sdiv64(ctx, expr0_0, expr1_0)?;
+                                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                                            return Some(expr3_0);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                &InstructionData::Unary {
+                    opcode: ref pattern5_0,
+                    arg: pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Ineg => {
+                            // Rule at src/isa/aarch64/lower.isle line 171.
+                            let expr0_0 = C::zero_reg(ctx);
+                            let expr1_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr2_0 = constructor_sub(ctx, pattern3_0, expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                        &Opcode::Bnot => {
+                            // Rule at src/isa/aarch64/lower.isle line 580.
+                            let expr0_0 = C::zero_reg(ctx);
+                            let expr1_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr2_0 = constructor_orr_not(ctx, pattern3_0, expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                        &Opcode::Uextend => {
+                            let pattern7_0 = C::value_type(ctx, pattern5_1);
+                            if let Some(pattern8_0) = C::sinkable_atomic_load(ctx, pattern5_1) {
+                                // Rule at src/isa/aarch64/lower.isle line 503.
+                                let expr0_0 = C::sink_atomic_load(ctx, &pattern8_0);
+                                let expr1_0 = constructor_load_acquire(ctx, pattern7_0, expr0_0)?;
+                                let expr2_0 = C::value_reg(ctx, expr1_0);
+                                return Some(expr2_0);
+                            }
+                        }
+                        &Opcode::Sextend => {
+                            if let Some(pattern7_0) = C::def_inst(ctx, pattern5_1) {
+                                let pattern8_0 = C::inst_data(ctx, pattern7_0);
+                                if let &InstructionData::BinaryImm8 {
+                                    opcode: ref pattern9_0,
+                                    arg: pattern9_1,
+                                    imm: pattern9_2,
+                                } = &pattern8_0
+                                {
+                                    if let &Opcode::Extractlane = &pattern9_0 {
+                                        let pattern11_0 = C::value_type(ctx, pattern9_1);
+                                        let pattern12_0 = C::u8_from_uimm8(ctx, pattern9_2);
+                                        // Rule at src/isa/aarch64/lower.isle line 528.
+                                        let expr0_0 = C::put_in_reg(ctx, pattern9_1);
+                                        let expr1_0 = constructor_vector_size(ctx, pattern11_0)?;
+                                        let expr2_0 = constructor_size_from_ty(ctx, pattern3_0)?;
+                                        let expr3_0 = constructor_mov_from_vec_signed(
+                                            ctx,
+                                            expr0_0,
+                                            pattern12_0,
+                                            &expr1_0,
+                                            &expr2_0,
+                                        )?;
+                                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                                        return Some(expr4_0);
+                                    }
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if pattern2_0 == I8 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Unary {
+                opcode: ref pattern5_0,
+                arg: pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Bitrev => {
+                        // Rule at src/isa/aarch64/lower.isle line 1013.
+                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr1_0 = constructor_rbit32(ctx, expr0_0)?;
+                        let expr2_0: u8 = 24;
+                        let expr3_0 = C::imm_shift_from_u8(ctx, expr2_0);
+                        let expr4_0 = constructor_lsr32_imm(ctx, expr1_0, expr3_0)?;
+                        let expr5_0 = C::value_reg(ctx, expr4_0);
+                        return Some(expr5_0);
+                    }
+                    &Opcode::Clz => {
+                        // Rule at src/isa/aarch64/lower.isle line 1038.
+                        let expr0_0 = constructor_put_in_reg_zext32(ctx, pattern5_1)?;
+                        let expr1_0 = constructor_clz32(ctx, expr0_0)?;
+                        let expr2_0: u8 = 24;
+                        let expr3_0 = C::u8_into_imm12(ctx, expr2_0);
+                        let expr4_0 = constructor_sub32_imm(ctx, expr1_0, expr3_0)?;
+                        let expr5_0 = C::value_reg(ctx, expr4_0);
+                        return Some(expr5_0);
+                    }
+                    &Opcode::Cls => {
+                        // Rule at src/isa/aarch64/lower.isle line 1095.
+                        let expr0_0 = constructor_put_in_reg_zext32(ctx, pattern5_1)?;
+                        let expr1_0 = constructor_cls32(ctx, expr0_0)?;
+                        let expr2_0: u8 = 24;
+                        let expr3_0 = C::u8_into_imm12(ctx, expr2_0);
+                        let expr4_0 = constructor_sub32_imm(ctx, expr1_0, expr3_0)?;
+                        let expr5_0 = C::value_reg(ctx, expr4_0);
+                        return Some(expr5_0);
+                    }
+                    &Opcode::Ctz => {
+                        // Rule at src/isa/aarch64/lower.isle line 1073.
+                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr1_0 = constructor_rbit32(ctx, expr0_0)?;
+                        let expr2_0: Type = I32;
+                        let expr3_0: u64 = 8388608;
+                        let expr4_0 = C::u64_into_imm_logic(ctx, expr2_0, expr3_0);
+                        let expr5_0 = constructor_orr32_imm(ctx, expr1_0, expr4_0)?;
+                        let expr6_0 = constructor_clz32(ctx, expr5_0)?;
+                        let expr7_0 = C::value_reg(ctx, expr6_0);
+                        return Some(expr7_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if pattern2_0 == I16 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Unary {
+                opcode: ref pattern5_0,
+                arg: pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Bitrev => {
+                        // Rule at src/isa/aarch64/lower.isle line 1019.
+                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr1_0 = constructor_rbit32(ctx, expr0_0)?;
+                        let expr2_0: u8 = 16;
+                        let expr3_0 = C::imm_shift_from_u8(ctx, expr2_0);
+                        let expr4_0 = constructor_lsr32_imm(ctx, expr1_0, expr3_0)?;
+                        let expr5_0 = C::value_reg(ctx, expr4_0);
+                        return Some(expr5_0);
+                    }
+                    &Opcode::Clz => {
+                        // Rule at src/isa/aarch64/lower.isle line 1041.
+                        let expr0_0 = constructor_put_in_reg_zext32(ctx, pattern5_1)?;
+                        let expr1_0 = constructor_clz32(ctx, expr0_0)?;
+                        let expr2_0: u8 = 16;
+                        let expr3_0 = C::u8_into_imm12(ctx, expr2_0);
+                        let expr4_0 = constructor_sub32_imm(ctx, expr1_0, expr3_0)?;
+                        let expr5_0 = C::value_reg(ctx, expr4_0);
+                        return Some(expr5_0);
+                    }
+                    &Opcode::Cls => {
+                        // Rule at src/isa/aarch64/lower.isle line 1098.
+                        let expr0_0 = constructor_put_in_reg_zext32(ctx, pattern5_1)?;
+                        let expr1_0 = constructor_cls32(ctx, expr0_0)?;
+                        let expr2_0: u8 = 16;
+                        let expr3_0 = C::u8_into_imm12(ctx, expr2_0);
+                        let expr4_0 = constructor_sub32_imm(ctx, expr1_0, expr3_0)?;
+                        let expr5_0 = C::value_reg(ctx, expr4_0);
+                        return Some(expr5_0);
+                    }
+                    &Opcode::Ctz => {
+                        // Rule at src/isa/aarch64/lower.isle line 1076.
+                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr1_0 = constructor_rbit32(ctx, expr0_0)?;
+                        let expr2_0: Type = I32;
+                        let expr3_0: u64 = 32768;
+                        let expr4_0 = C::u64_into_imm_logic(ctx, expr2_0, expr3_0);
+                        let expr5_0 = constructor_orr32_imm(ctx, expr1_0, expr4_0)?;
+                        let expr6_0 = constructor_clz32(ctx, expr5_0)?;
+                        let expr7_0 = C::value_reg(ctx, expr6_0);
+                        return Some(expr7_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if pattern2_0 == I32 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Unary {
+                opcode: ref pattern5_0,
+                arg: pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Bitrev => {
+                        // Rule at src/isa/aarch64/lower.isle line 1022.
+                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr1_0 = constructor_rbit32(ctx, expr0_0)?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                    &Opcode::Clz => {
+                        // Rule at src/isa/aarch64/lower.isle line 1044.
+                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr1_0 = constructor_clz32(ctx, expr0_0)?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                    &Opcode::Cls => {
+                        // Rule at src/isa/aarch64/lower.isle line 1101.
+                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr1_0 = constructor_cls32(ctx, expr0_0)?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                    &Opcode::Ctz => {
+                        // Rule at src/isa/aarch64/lower.isle line 1079.
+                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr1_0 = constructor_rbit32(ctx, expr0_0)?;
+                        let expr2_0 = constructor_clz32(ctx, expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if pattern2_0 == I64 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Unary {
+                opcode: ref pattern5_0,
+                arg: pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Bitrev => {
+                        // Rule at src/isa/aarch64/lower.isle line 1025.
+                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr1_0 = constructor_rbit64(ctx, expr0_0)?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                    &Opcode::Clz => {
+                        // Rule at src/isa/aarch64/lower.isle line 1047.
+                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr1_0 = constructor_clz64(ctx, expr0_0)?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                    &Opcode::Cls => {
+                        // Rule at src/isa/aarch64/lower.isle line 1104.
+                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr1_0 = constructor_cls64(ctx, expr0_0)?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                    &Opcode::Ctz => {
+                        // Rule at src/isa/aarch64/lower.isle line 1082.
+                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr1_0 = constructor_rbit64(ctx, expr0_0)?;
+                        let expr2_0 = constructor_clz64(ctx, expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if pattern2_0 == I128 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Unary {
+                opcode: ref pattern5_0,
+                arg: pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Bitrev => {
+                        // Rule at src/isa/aarch64/lower.isle line 1028.
+                        let expr0_0 = C::put_in_regs(ctx, pattern5_1);
+                        let expr1_0: usize = 0;
+                        let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                        let expr3_0 = constructor_rbit64(ctx, expr2_0)?;
+                        let expr4_0: usize = 1;
+                        let expr5_0 = C::value_regs_get(ctx, expr0_0, expr4_0);
+                        let expr6_0 = constructor_rbit64(ctx, expr5_0)?;
+                        let expr7_0 = C::value_regs(ctx, expr6_0, expr3_0);
+                        return Some(expr7_0);
+                    }
+                    &Opcode::Clz => {
+                        // Rule at src/isa/aarch64/lower.isle line 1050.
+                        let expr0_0 = C::put_in_regs(ctx, pattern5_1);
+                        let expr1_0 = constructor_lower_clz128(ctx, expr0_0)?;
+                        return Some(expr1_0);
+                    }
+                    &Opcode::Cls => {
+                        // Rule at src/isa/aarch64/lower.isle line 1116.
+                        let expr0_0 = C::put_in_regs(ctx, pattern5_1);
+                        let expr1_0: usize = 0;
+                        let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                        let expr3_0: usize = 1;
+                        let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                        let expr5_0 = constructor_cls64(ctx, expr2_0)?;
+                        let expr6_0 = constructor_cls64(ctx, expr4_0)?;
+                        let expr7_0 = constructor_eon64(ctx, expr4_0, expr2_0)?;
+                        let expr8_0: u8 = 63;
+                        let expr9_0 = C::imm_shift_from_u8(ctx, expr8_0);
+                        let expr10_0 = constructor_lsr64_imm(ctx, expr7_0, expr9_0)?;
+                        let expr11_0 = constructor_madd64(ctx, expr5_0, expr10_0, expr10_0)?;
+                        let expr12_0: u8 = 63;
+                        let expr13_0 = C::u8_into_imm12(ctx, expr12_0);
+                        let expr14_0 = constructor_cmp64_imm(ctx, expr6_0, expr13_0)?;
+                        let expr15_0 = Cond::Eq;
+                        let expr16_0 = C::zero_reg(ctx);
+                        let expr17_0 = constructor_csel(ctx, &expr15_0, expr11_0, expr16_0)?;
+                        let expr18_0 = constructor_with_flags_1(ctx, &expr14_0, &expr17_0)?;
+                        let expr19_0 = constructor_add64(ctx, expr18_0, expr6_0)?;
+                        let expr20_0: Type = I64;
+                        let expr21_0: u64 = 0;
+                        let expr22_0 = constructor_imm(ctx, expr20_0, expr21_0)?;
+                        let expr23_0 = C::value_regs(ctx, expr19_0, expr22_0);
+                        return Some(expr23_0);
+                    }
+                    &Opcode::Ctz => {
+                        // Rule at src/isa/aarch64/lower.isle line 1085.
+                        let expr0_0 = C::put_in_regs(ctx, pattern5_1);
+                        let expr1_0: usize = 0;
+                        let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                        let expr3_0 = constructor_rbit64(ctx, expr2_0)?;
+                        let expr4_0: usize = 1;
+                        let expr5_0 = C::value_regs_get(ctx, expr0_0, expr4_0);
+                        let expr6_0 = constructor_rbit64(ctx, expr5_0)?;
+                        let expr7_0 = C::value_regs(ctx, expr6_0, expr3_0);
+                        let expr8_0 = constructor_lower_clz128(ctx, expr7_0)?;
+                        return Some(expr8_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let pattern3_0 = C::inst_data(ctx, pattern0_0);
+        match &pattern3_0 {
+            &InstructionData::NullAry {
+                opcode: ref pattern4_0,
+            } => {
+                if let &Opcode::Null = &pattern4_0 {
+                    // Rule at src/isa/aarch64/lower.isle line 22.
+                    let expr0_0: u64 = 0;
+                    let expr1_0 = constructor_imm(ctx, pattern2_0, expr0_0)?;
+                    let expr2_0 = C::value_reg(ctx, expr1_0);
+                    return Some(expr2_0);
+                }
+            }
+            &InstructionData::UnaryImm {
+                opcode: ref pattern4_0,
+                imm: pattern4_1,
+            } => {
+                if let &Opcode::Iconst = &pattern4_0 {
+                    let pattern6_0 = C::u64_from_imm64(ctx, pattern4_1);
+                    // Rule at src/isa/aarch64/lower.isle line 9.
+                    let expr0_0 = constructor_imm(ctx, pattern2_0, pattern6_0)?;
+                    let expr1_0 = C::value_reg(ctx, expr0_0);
+                    return Some(expr1_0);
+                }
+            }
+            &InstructionData::UnaryBool {
+                opcode: ref pattern4_0,
+                imm: pattern4_1,
+            } => {
+                if let &Opcode::Bconst = &pattern4_0 {
+                    if pattern4_1 == true {
+                        // Rule at src/isa/aarch64/lower.isle line 17.
+                        let expr0_0: u64 = 1;
+                        let expr1_0 = constructor_imm(ctx, pattern2_0, expr0_0)?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                    if pattern4_1 == false {
+                        // Rule at src/isa/aarch64/lower.isle line 14.
+                        let expr0_0: u64 = 0;
+                        let expr1_0 = constructor_imm(ctx, pattern2_0, expr0_0)?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                }
+            }
+            _ => {}
+        }
+        if let Some(pattern3_0) = C::fits_in_16(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Rotl => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 869.
+                        let expr0_0 = C::zero_reg(ctx);
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                        let expr2_0 = constructor_sub32(ctx, expr0_0, expr1_0)?;
+                        let expr3_0 = constructor_put_in_reg_zext32(ctx, pattern7_0)?;
+                        let expr4_0 = constructor_small_rotr(ctx, pattern3_0, expr3_0, expr2_0)?;
+                        let expr5_0 = C::value_reg(ctx, expr4_0);
+                        return Some(expr5_0);
+                    }
+                    &Opcode::Rotr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 924.
+                        let expr0_0 = constructor_put_in_reg_zext32(ctx, pattern7_0)?;
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                        let expr2_0 = constructor_small_rotr(ctx, pattern3_0, expr0_0, expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_32(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Umulhi => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 377.
+                        let expr0_0 = constructor_put_in_reg_zext64(ctx, pattern7_0)?;
+                        let expr1_0 = constructor_put_in_reg_zext64(ctx, pattern7_1)?;
+                        let expr2_0 = C::zero_reg(ctx);
+                        let expr3_0 = constructor_madd64(ctx, expr0_0, expr1_0, expr2_0)?;
+                        let expr4_0 = C::ty_bits(ctx, pattern3_0);
+                        let expr5_0 = C::imm_shift_from_u8(ctx, expr4_0);
+                        let expr6_0 = constructor_lsr64_imm(ctx, expr3_0, expr5_0)?;
+                        let expr7_0 = C::value_reg(ctx, expr6_0);
+                        return Some(expr7_0);
+                    }
+                    &Opcode::Smulhi => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 363.
+                        let expr0_0 = constructor_put_in_reg_sext64(ctx, pattern7_0)?;
+                        let expr1_0 = constructor_put_in_reg_sext64(ctx, pattern7_1)?;
+                        let expr2_0 = C::zero_reg(ctx);
+                        let expr3_0 = constructor_madd64(ctx, expr0_0, expr1_0, expr2_0)?;
+                        let expr4_0 = C::ty_bits(ctx, pattern3_0);
+                        let expr5_0 = C::imm_shift_from_u8(ctx, expr4_0);
+                        let expr6_0 = constructor_asr64_imm(ctx, expr3_0, expr5_0)?;
+                        let expr7_0 = C::value_reg(ctx, expr6_0);
+                        return Some(expr7_0);
+                    }
+                    &Opcode::BorNot => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 658.
+                        let expr0_0 = ALUOp::OrrNot32;
+                        let expr1_0 = constructor_alu_rs_imm_logic(
+                            ctx, &expr0_0, pattern3_0, pattern7_0, pattern7_1,
+                        )?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                    &Opcode::BxorNot => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/aarch64/lower.isle line 668.
+                        let expr0_0 = ALUOp::EorNot32;
+                        let expr1_0 = constructor_alu_rs_imm_logic(
+                            ctx, &expr0_0, pattern3_0, pattern7_0, pattern7_1,
+                        )?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            match &pattern4_0 {
+                &InstructionData::Binary {
+                    opcode: ref pattern5_0,
+                    args: ref pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Iadd => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/aarch64/lower.isle line 30.
                             let expr0_0 = C::put_in_reg(ctx, pattern7_0);
                             let expr1_0 = C::put_in_reg(ctx, pattern7_1);
-                            let expr2_0 = constructor_sub(ctx, pattern3_0, expr0_0, expr1_0)?;
+                            let expr2_0 = constructor_add(ctx, pattern3_0, expr0_0, expr1_0)?;
                             let expr3_0 = C::value_reg(ctx, expr2_0);
                             return Some(expr3_0);
                         }
-                        &Opcode::Imul => {
+                        &Opcode::Isub => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/aarch64/lower.isle line 181.
+                            // Rule at src/isa/aarch64/lower.isle line 102.
                             let expr0_0 = C::put_in_reg(ctx, pattern7_0);
                             let expr1_0 = C::put_in_reg(ctx, pattern7_1);
-                            let expr2_0 = C::zero_reg(ctx);
-                            let expr3_0 =
-                                constructor_madd(ctx, pattern3_0, expr0_0, expr1_0, expr2_0)?;
-                            let expr4_0 = C::value_reg(ctx, expr3_0);
-                            return Some(expr4_0);
+                            let expr2_0 = constructor_sub(ctx, pattern3_0, expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
                         }
                         &Opcode::Udiv => {
                             let (pattern7_0, pattern7_1) =
@@ -5636,30 +6107,6 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                         &Opcode::Sdiv => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
-                                let pattern9_0 = C::inst_data(ctx, pattern8_0);
-                                if let &InstructionData::UnaryImm {
-                                    opcode: ref pattern10_0,
-                                    imm: pattern10_1,
-                                } = &pattern9_0
-                                {
-                                    if let &Opcode::Iconst = &pattern10_0 {
-                                        if let Some(pattern12_0) =
-                                            C::safe_divisor_from_imm64(ctx, pattern10_1)
-                                        {
-                                            // Rule at src/isa/aarch64/lower.isle line 441.
-                                            let expr0_0 =
-                                                constructor_put_in_reg_sext64(ctx, pattern7_0)?;
-                                            let expr1_0 =
-                                                constructor_imm(ctx, pattern3_0, pattern12_0)?;
-                                            let expr2_0 =
-                                                constructor_sdiv64(ctx, expr0_0, expr1_0)?;
-                                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                                            return Some(expr3_0);
-                                        }
-                                    }
-                                }
-                            }
                             // Rule at src/isa/aarch64/lower.isle line 426.
                             let expr0_0 = constructor_put_in_reg_sext64(ctx, pattern7_0)?;
                             let expr1_0 = constructor_put_nonzero_in_reg_sext64(ctx, pattern7_1)?;
@@ -5700,108 +6147,8 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                     arg: pattern5_1,
                 } => {
                     match &pattern5_0 {
-                        &Opcode::Ineg => {
-                            // Rule at src/isa/aarch64/lower.isle line 171.
-                            let expr0_0 = C::zero_reg(ctx);
-                            let expr1_0 = C::put_in_reg(ctx, pattern5_1);
-                            let expr2_0 = constructor_sub(ctx, pattern3_0, expr0_0, expr1_0)?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
-                        &Opcode::Bnot => {
-                            if let Some(pattern7_0) = C::def_inst(ctx, pattern5_1) {
-                                let pattern8_0 = C::inst_data(ctx, pattern7_0);
-                                if let &InstructionData::Binary {
-                                    opcode: ref pattern9_0,
-                                    args: ref pattern9_1,
-                                } = &pattern8_0
-                                {
-                                    if let &Opcode::Ishl = &pattern9_0 {
-                                        let (pattern11_0, pattern11_1) =
-                                            C::unpack_value_array_2(ctx, &pattern9_1);
-                                        if let Some(pattern12_0) = C::def_inst(ctx, pattern11_1) {
-                                            let pattern13_0 = C::inst_data(ctx, pattern12_0);
-                                            if let &InstructionData::UnaryImm {
-                                                opcode: ref pattern14_0,
-                                                imm: pattern14_1,
-                                            } = &pattern13_0
-                                            {
-                                                if let &Opcode::Iconst = &pattern14_0 {
-                                                    let closure16 = || {
-                                                        return Some(pattern3_0);
-                                                    };
-                                                    if let Some(pattern16_0) = closure16() {
-                                                        if let Some(pattern17_0) =
-                                                            C::lshl_from_imm64(
-                                                                ctx,
-                                                                pattern14_1,
-                                                                pattern16_0,
-                                                            )
-                                                        {
-                                                            // Rule at src/isa/aarch64/lower.isle line 585.
-                                                            let expr0_0 = C::zero_reg(ctx);
-                                                            let expr1_0 =
-                                                                C::put_in_reg(ctx, pattern11_0);
-                                                            let expr2_0 =
-                                                                constructor_orr_not_shift(
-                                                                    ctx,
-                                                                    pattern3_0,
-                                                                    expr0_0,
-                                                                    expr1_0,
-                                                                    pattern17_0,
-                                                                )?;
-                                                            let expr3_0 =
-                                                                C::value_reg(ctx, expr2_0);
-                                                            return Some(expr3_0);
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            // Rule at src/isa/aarch64/lower.isle line 580.
-                            let expr0_0 = C::zero_reg(ctx);
-                            let expr1_0 = C::put_in_reg(ctx, pattern5_1);
-                            let expr2_0 = constructor_orr_not(ctx, pattern3_0, expr0_0, expr1_0)?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
                         &Opcode::Uextend => {
-                            if let Some(pattern7_0) = C::def_inst(ctx, pattern5_1) {
-                                let pattern8_0 = C::inst_data(ctx, pattern7_0);
-                                if let &InstructionData::BinaryImm8 {
-                                    opcode: ref pattern9_0,
-                                    arg: pattern9_1,
-                                    imm: pattern9_2,
-                                } = &pattern8_0
-                                {
-                                    if let &Opcode::Extractlane = &pattern9_0 {
-                                        let pattern11_0 = C::value_type(ctx, pattern9_1);
-                                        let pattern12_0 = C::u8_from_uimm8(ctx, pattern9_2);
-                                        // Rule at src/isa/aarch64/lower.isle line 496.
-                                        let expr0_0 = C::put_in_reg(ctx, pattern9_1);
-                                        let expr1_0 = constructor_vector_size(ctx, pattern11_0)?;
-                                        let expr2_0 = constructor_mov_from_vec(
-                                            ctx,
-                                            expr0_0,
-                                            pattern12_0,
-                                            &expr1_0,
-                                        )?;
-                                        let expr3_0 = C::value_reg(ctx, expr2_0);
-                                        return Some(expr3_0);
-                                    }
-                                }
-                            }
                             let pattern7_0 = C::value_type(ctx, pattern5_1);
-                            if let Some(pattern8_0) = C::sinkable_atomic_load(ctx, pattern5_1) {
-                                // Rule at src/isa/aarch64/lower.isle line 503.
-                                let expr0_0 = C::sink_atomic_load(ctx, &pattern8_0);
-                                let expr1_0 = constructor_load_acquire(ctx, pattern7_0, expr0_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
-                            }
                             // Rule at src/isa/aarch64/lower.isle line 491.
                             let expr0_0 = C::put_in_reg(ctx, pattern5_1);
                             let expr1_0: bool = false;
@@ -5813,33 +6160,6 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                             return Some(expr5_0);
                         }
                         &Opcode::Sextend => {
-                            if let Some(pattern7_0) = C::def_inst(ctx, pattern5_1) {
-                                let pattern8_0 = C::inst_data(ctx, pattern7_0);
-                                if let &InstructionData::BinaryImm8 {
-                                    opcode: ref pattern9_0,
-                                    arg: pattern9_1,
-                                    imm: pattern9_2,
-                                } = &pattern8_0
-                                {
-                                    if let &Opcode::Extractlane = &pattern9_0 {
-                                        let pattern11_0 = C::value_type(ctx, pattern9_1);
-                                        let pattern12_0 = C::u8_from_uimm8(ctx, pattern9_2);
-                                        // Rule at src/isa/aarch64/lower.isle line 528.
-                                        let expr0_0 = C::put_in_reg(ctx, pattern9_1);
-                                        let expr1_0 = constructor_vector_size(ctx, pattern11_0)?;
-                                        let expr2_0 = constructor_size_from_ty(ctx, pattern3_0)?;
-                                        let expr3_0 = constructor_mov_from_vec_signed(
-                                            ctx,
-                                            expr0_0,
-                                            pattern12_0,
-                                            &expr1_0,
-                                            &expr2_0,
-                                        )?;
-                                        let expr4_0 = C::value_reg(ctx, expr3_0);
-                                        return Some(expr4_0);
-                                    }
-                                }
-                            }
                             let pattern7_0 = C::value_type(ctx, pattern5_1);
                             // Rule at src/isa/aarch64/lower.isle line 523.
                             let expr0_0 = C::put_in_reg(ctx, pattern5_1);
@@ -6167,8 +6487,6 @@ pub fn constructor_do_shift<C: Context>(
             }
         }
     }
-    let pattern0_0 = arg0;
-    let pattern1_0 = arg1;
     if pattern1_0 == I32 {
         let pattern3_0 = arg2;
         let pattern4_0 = arg3;
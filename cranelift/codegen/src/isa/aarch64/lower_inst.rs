@@ -962,6 +962,13 @@ pub(crate) fn lower_insn_to_regs<C: LowerCtx<I = Inst>>(
             });
         }
 
+        Opcode::ReturnCall | Opcode::ReturnCallIndirect => {
+            return Err(CodegenError::Unsupported(format!(
+                "{}: tail calls are not supported on this target",
+                op
+            )));
+        }
+
         Opcode::Call | Opcode::CallIndirect => {
             let caller_conv = ctx.abi().call_conv();
             let (mut abi, inputs) = match op {
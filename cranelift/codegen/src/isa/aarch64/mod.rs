@@ -166,7 +166,7 @@ pub fn isa_builder(triple: Triple) -> IsaBuilder {
         constructor: |triple, shared_flags, builder| {
             let isa_flags = aarch64_settings::Flags::new(&shared_flags, builder);
             let backend = AArch64Backend::new_with_flags(triple, shared_flags, isa_flags);
-            Box::new(TargetIsaAdapter::new(backend))
+            Ok(Box::new(TargetIsaAdapter::new(backend)))
         },
     }
 }
@@ -212,11 +212,11 @@ mod test {
         let buffer = backend.compile_function(&mut func, false).unwrap().buffer;
         let code = &buffer.data[..];
 
-        // mov x1, #0x1234
-        // add w0, w0, w1
+        // mov x4, #0x1234
+        // add w0, w0, w4
         // ret
         let golden = vec![
-            0x81, 0x46, 0x82, 0xd2, 0x00, 0x00, 0x01, 0x0b, 0xc0, 0x03, 0x5f, 0xd6,
+            0x84, 0x46, 0x82, 0xd2, 0x00, 0x00, 0x04, 0x0b, 0xc0, 0x03, 0x5f, 0xd6,
         ];
 
         assert_eq!(code, &golden[..]);
@@ -267,23 +267,23 @@ mod test {
             .unwrap();
         let code = &result.buffer.data[..];
 
-        // mov	x1, #0x1234                	// #4660
-        // add	w0, w0, w1
-        // mov	w1, w0
-        // cbnz	x1, 0x28
-        // mov	x1, #0x1234                	// #4660
-        // add	w1, w0, w1
-        // mov	w1, w1
-        // cbnz	x1, 0x18
-        // mov	w1, w0
-        // cbnz	x1, 0x18
-        // mov	x1, #0x1234                	// #4660
-        // sub	w0, w0, w1
+        // mov	x11, #0x1234                	// #4660
+        // add	w3, w0, w11
+        // mov	w12, w3
+        // cbnz	x12, 0x28
+        // mov	x15, #0x1234                	// #4660
+        // add	w10, w3, w15
+        // mov	w0, w10
+        // cbnz	x0, 0x18
+        // mov	w4, w3
+        // cbnz	x4, 0x18
+        // mov	x5, #0x1234                	// #4660
+        // sub	w0, w3, w5
         // ret
         let golden = vec![
-            129, 70, 130, 210, 0, 0, 1, 11, 225, 3, 0, 42, 161, 0, 0, 181, 129, 70, 130, 210, 1, 0,
-            1, 11, 225, 3, 1, 42, 161, 255, 255, 181, 225, 3, 0, 42, 97, 255, 255, 181, 129, 70,
-            130, 210, 0, 0, 1, 75, 192, 3, 95, 214,
+            139, 70, 130, 210, 3, 0, 11, 11, 236, 3, 3, 42, 172, 0, 0, 181, 143, 70, 130, 210, 106,
+            0, 15, 11, 224, 3, 10, 42, 160, 255, 255, 181, 228, 3, 3, 42, 100, 255, 255, 181, 133,
+            70, 130, 210, 96, 0, 5, 75, 192, 3, 95, 214,
         ];
 
         assert_eq!(code, &golden[..]);
@@ -50,12 +50,19 @@ impl ABIMachineSpec for Arm32MachineDeps {
     }
 
     fn compute_arg_locs(
-        _call_conv: isa::CallConv,
+        call_conv: isa::CallConv,
         _flags: &settings::Flags,
         params: &[ir::AbiParam],
         args_or_rets: ArgsOrRets,
         add_ret_area_ptr: bool,
     ) -> CodegenResult<(Vec<ABIArg>, i64, Option<usize>)> {
+        if call_conv == isa::CallConv::Tail {
+            return Err(CodegenError::Unsupported(format!(
+                "calling convention {} is not supported on arm32",
+                call_conv
+            )));
+        }
+
         let mut next_rreg = 0;
         let mut next_stack: u64 = 0;
         let mut ret = vec![];
@@ -178,7 +185,7 @@ impl ABIMachineSpec for Arm32MachineDeps {
         }
     }
 
-    fn gen_ret() -> Inst {
+    fn gen_ret(_stack_bytes_to_pop: u32) -> Inst {
         Inst::Ret
     }
 
@@ -312,6 +319,12 @@ impl ABIMachineSpec for Arm32MachineDeps {
         smallvec![]
     }
 
+    fn gen_inline_probestack(_: u32, _: u32) -> SmallInstVec<Self::I> {
+        // Inline stack probes aren't implemented here yet; the ISA builder
+        // rejects the inline strategy when stack probes are enabled.
+        unreachable!("inline stack probes are not supported on arm32")
+    }
+
     /// Returns stack bytes used as well as instructions. Does not adjust
     /// nominal SP offset; caller will do that.
     fn gen_clobber_save(
@@ -376,6 +389,7 @@ impl ABIMachineSpec for Arm32MachineDeps {
         tmp: Writable<Reg>,
         _callee_conv: isa::CallConv,
         _caller_conv: isa::CallConv,
+        _callee_pop_size: u32,
     ) -> SmallVec<[(InstIsSafepoint, Inst); 2]> {
         let mut insts = SmallVec::new();
         match &dest {
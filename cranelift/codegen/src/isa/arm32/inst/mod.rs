@@ -8,7 +8,7 @@ use crate::ir::{ExternalName, Opcode, TrapCode, Type};
 use crate::machinst::*;
 use crate::{settings, CodegenError, CodegenResult};
 
-use regalloc::{PrettyPrint, RealRegUniverse, Reg, RegClass, SpillSlot, VirtualReg, Writable};
+use regalloc::{PrettyPrint, RealRegUniverse, Reg, RegClass, Writable};
 use regalloc::{RegUsageCollector, RegUsageMapper};
 
 use alloc::boxed::Box;
@@ -838,10 +838,6 @@ impl MachInst for Inst {
         Inst::Nop2
     }
 
-    fn maybe_direct_reload(&self, _reg: VirtualReg, _slot: SpillSlot) -> Option<Inst> {
-        None
-    }
-
     fn rc_for_type(ty: Type) -> CodegenResult<(&'static [RegClass], &'static [Type])> {
         match ty {
             I8 | I16 | I32 | B1 | B8 | B16 | B32 => Ok((&[RegClass::I32], &[I32])),
@@ -867,10 +863,6 @@ impl MachInst for Inst {
         // It inst with four 32-bit instructions
         2 + 4 * 4
     }
-
-    fn ref_type_regclass(_: &settings::Flags) -> RegClass {
-        RegClass::I32
-    }
 }
 
 //=============================================================================
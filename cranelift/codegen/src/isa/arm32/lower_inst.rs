@@ -6,7 +6,7 @@ use crate::ir::Opcode;
 use crate::machinst::lower::*;
 use crate::machinst::*;
 use crate::settings::Flags;
-use crate::CodegenResult;
+use crate::{CodegenError, CodegenResult};
 
 use crate::isa::arm32::abi::*;
 use crate::isa::arm32::inst::*;
@@ -492,6 +492,13 @@ pub(crate) fn lower_insn_to_regs<C: LowerCtx<I = Inst>>(
                 ctx.emit(Inst::gen_move(retval_reg, reg, ty));
             }
         }
+        Opcode::ReturnCall | Opcode::ReturnCallIndirect => {
+            return Err(CodegenError::Unsupported(format!(
+                "{}: tail calls are not supported on this target",
+                op
+            )));
+        }
+
         Opcode::Call | Opcode::CallIndirect => {
             let caller_conv = ctx.abi().call_conv();
             let (mut abi, inputs) = match op {
@@ -7,7 +7,7 @@ use crate::machinst::{
     compile, MachBackend, MachCompileResult, MachTextSectionBuilder, TargetIsaAdapter,
     TextSectionBuilder, VCode,
 };
-use crate::result::CodegenResult;
+use crate::result::{CodegenError, CodegenResult};
 use crate::settings;
 
 use alloc::{boxed::Box, vec::Vec};
@@ -126,8 +126,15 @@ pub fn isa_builder(triple: Triple) -> IsaBuilder {
         triple,
         setup: settings::builder(),
         constructor: |triple, shared_flags, _| {
+            if shared_flags.enable_probestack()
+                && shared_flags.probestack_strategy() == settings::ProbestackStrategy::Inline
+            {
+                return Err(CodegenError::Unsupported(
+                    "arm32: inline stack probes are not supported".into(),
+                ));
+            }
             let backend = Arm32Backend::new_with_flags(triple, shared_flags);
-            Box::new(TargetIsaAdapter::new(backend))
+            Ok(Box::new(TargetIsaAdapter::new(backend)))
         },
     }
 }
@@ -43,6 +43,12 @@ pub enum CallConv {
     ///
     /// Differs from apple-aarch64 in the same way as `WasmtimeSystemV`.
     WasmtimeAppleAarch64,
+    /// Convention which supports tail calls with `return_call` and
+    /// `return_call_indirect`, not ABI-stable.
+    ///
+    /// Arguments and return values are passed in the same locations as with
+    /// `WasmtimeSystemV`. Currently only supported on x86-64.
+    Tail,
 }
 
 impl CallConv {
@@ -105,6 +111,14 @@ impl CallConv {
             _ => false,
         }
     }
+
+    /// Does the calling convention support tail calls?
+    pub fn supports_tail_calls(self) -> bool {
+        match self {
+            Self::Tail => true,
+            _ => false,
+        }
+    }
 }
 
 impl fmt::Display for CallConv {
@@ -122,6 +136,7 @@ impl fmt::Display for CallConv {
             Self::WasmtimeSystemV => "wasmtime_system_v",
             Self::WasmtimeFastcall => "wasmtime_fastcall",
             Self::WasmtimeAppleAarch64 => "wasmtime_apple_aarch64",
+            Self::Tail => "tail",
         })
     }
 }
@@ -142,6 +157,7 @@ impl str::FromStr for CallConv {
             "wasmtime_system_v" => Ok(Self::WasmtimeSystemV),
             "wasmtime_fastcall" => Ok(Self::WasmtimeFastcall),
             "wasmtime_apple_aarch64" => Ok(Self::WasmtimeAppleAarch64),
+            "tail" => Ok(Self::Tail),
             _ => Err(()),
         }
     }
@@ -35,7 +35,7 @@
 //!     }
 //!     Ok(mut isa_builder) => {
 //!         isa_builder.set("use_popcnt", "on");
-//!         let isa = isa_builder.finish(shared_flags);
+//!         let isa = isa_builder.finish(shared_flags).unwrap();
 //!     }
 //! }
 //! ```
@@ -50,6 +50,7 @@ use crate::ir;
 #[cfg(feature = "unwind")]
 use crate::isa::unwind::systemv::RegisterMappingError;
 use crate::machinst::{MachBackend, UnwindInfoKind};
+use crate::result::CodegenResult;
 use crate::settings;
 use crate::settings::SetResult;
 use alloc::{boxed::Box, vec::Vec};
@@ -71,6 +72,9 @@ pub(crate) mod aarch64;
 #[cfg(feature = "s390x")]
 mod s390x;
 
+#[cfg(feature = "riscv64")]
+mod riscv64;
+
 pub mod unwind;
 
 mod call_conv;
@@ -103,6 +107,7 @@ pub fn lookup(triple: Triple) -> Result<Builder, LookupError> {
         Architecture::Arm { .. } => isa_builder!(arm32, (feature = "arm32"), triple),
         Architecture::Aarch64 { .. } => isa_builder!(aarch64, (feature = "arm64"), triple),
         Architecture::S390x { .. } => isa_builder!(s390x, (feature = "s390x"), triple),
+        Architecture::Riscv64 { .. } => isa_builder!(riscv64, (feature = "riscv64"), triple),
         _ => Err(LookupError::Unsupported),
     }
 }
@@ -145,7 +150,8 @@ impl fmt::Display for LookupError {
 pub struct Builder {
     triple: Triple,
     setup: settings::Builder,
-    constructor: fn(Triple, settings::Flags, settings::Builder) -> Box<dyn TargetIsa>,
+    constructor:
+        fn(Triple, settings::Flags, settings::Builder) -> CodegenResult<Box<dyn TargetIsa>>,
 }
 
 impl Builder {
@@ -161,7 +167,9 @@ impl Builder {
 
     /// Combine the ISA-specific settings with the provided ISA-independent settings and allocate a
     /// fully configured `TargetIsa` trait object.
-    pub fn finish(self, shared_flags: settings::Flags) -> Box<dyn TargetIsa> {
+    ///
+    /// Returns an error if the settings ask for something this ISA doesn't support.
+    pub fn finish(self, shared_flags: settings::Flags) -> CodegenResult<Box<dyn TargetIsa>> {
         (self.constructor)(self.triple, shared_flags, self.setup)
     }
 }
@@ -0,0 +1,795 @@
+//! RISC-V 64 ISA definitions: instruction arguments.
+
+// Some variants are never constructed, but we still want them as options in the future.
+#![allow(dead_code)]
+
+use crate::ir::condcodes::{CondCode, IntCC};
+use crate::ir::Type;
+use crate::isa::riscv64::inst::*;
+use crate::machinst::MachLabel;
+
+use regalloc::{PrettyPrint, RealRegUniverse, Reg};
+
+use std::string::String;
+
+//=============================================================================
+// Instruction sub-components: memory addresses
+
+/// An addressing mode specified for a load/store operation.
+///
+/// RISC-V only has a single "register plus signed 12-bit offset" addressing
+/// mode; every variant here is lowered to that form at emission time, using
+/// the spilltmp register to materialize offsets that do not fit.
+#[derive(Clone, Debug)]
+pub enum AMode {
+    /// Arbitrary offset from a register.
+    RegOffset(Reg, i64, Type),
+
+    /// Offset from the stack pointer.
+    SPOffset(i64, Type),
+
+    /// Offset from the frame pointer.
+    FPOffset(i64, Type),
+
+    /// Offset from the "nominal stack pointer", which is where the real SP is
+    /// just after stack and spill slots are allocated in the function prologue.
+    /// At emission time, this is converted to `SPOffset` with a fixup added to
+    /// the offset constant. The fixup is a running value that is tracked as
+    /// emission iterates through instructions in linear order, and can be
+    /// adjusted up and down with [Inst::VirtualSPOffsetAdj].
+    ///
+    /// The standard ABI is in charge of handling this (by emitting the
+    /// adjustment meta-instructions). It maintains the invariant that "nominal
+    /// SP" is where the actual SP is after the function prologue and before
+    /// clobber pushes. See the diagram in the documentation for
+    /// [crate::isa::riscv64::abi](the ABI module) for more details.
+    NominalSPOffset(i64, Type),
+}
+
+impl AMode {
+    /// Memory reference using an address in a register.
+    pub fn reg(reg: Reg) -> AMode {
+        AMode::RegOffset(reg, 0, crate::ir::types::I64)
+    }
+
+    /// Memory reference using an address in a register and an offset.
+    pub fn reg_offset(reg: Reg, offset: i64, ty: Type) -> AMode {
+        AMode::RegOffset(reg, offset, ty)
+    }
+
+    /// Does the address resolve to just a register value, with no offset or
+    /// other computation?
+    pub fn is_reg(&self) -> Option<Reg> {
+        match self {
+            &AMode::RegOffset(r, off, _) if off == 0 => Some(r),
+            &AMode::FPOffset(off, _) if off == 0 => Some(fp_reg()),
+            &AMode::SPOffset(off, _) if off == 0 => Some(stack_reg()),
+            _ => None,
+        }
+    }
+}
+
+impl PrettyPrint for AMode {
+    fn show_rru(&self, mb_rru: Option<&RealRegUniverse>) -> String {
+        match self {
+            &AMode::RegOffset(r, off, _) => format!("{}({})", off, r.show_rru(mb_rru)),
+            &AMode::SPOffset(off, _) => format!("{}(sp)", off),
+            &AMode::FPOffset(off, _) => format!("{}(fp)", off),
+            &AMode::NominalSPOffset(off, _) => format!("{}(nominal_sp)", off),
+        }
+    }
+}
+
+//=============================================================================
+// Instruction sub-components: conditions
+
+/// A comparison of two integer registers, as performed by the RISC-V branch
+/// instructions.
+#[derive(Clone, Copy, Debug)]
+pub struct IntegerCompare {
+    pub kind: IntCC,
+    pub rs1: Reg,
+    pub rs2: Reg,
+}
+
+impl IntegerCompare {
+    /// Return the comparison that is true exactly when this one is false.
+    pub fn inverse(self) -> IntegerCompare {
+        IntegerCompare {
+            kind: self.kind.inverse(),
+            ..self
+        }
+    }
+
+    /// Return the branch instruction's `funct3` and the registers to put in
+    /// its `rs1` and `rs2` fields, swapping them for the conditions that
+    /// have no direct branch instruction.
+    pub(crate) fn branch_funct3_and_regs(&self) -> (u32, Reg, Reg) {
+        let (rs1, rs2) = (self.rs1, self.rs2);
+        match self.kind {
+            IntCC::Equal => (0b000, rs1, rs2),
+            IntCC::NotEqual => (0b001, rs1, rs2),
+            IntCC::SignedLessThan => (0b100, rs1, rs2),
+            IntCC::SignedGreaterThanOrEqual => (0b101, rs1, rs2),
+            IntCC::SignedGreaterThan => (0b100, rs2, rs1),
+            IntCC::SignedLessThanOrEqual => (0b101, rs2, rs1),
+            IntCC::UnsignedLessThan => (0b110, rs1, rs2),
+            IntCC::UnsignedGreaterThanOrEqual => (0b111, rs1, rs2),
+            IntCC::UnsignedGreaterThan => (0b110, rs2, rs1),
+            IntCC::UnsignedLessThanOrEqual => (0b111, rs2, rs1),
+            IntCC::Overflow | IntCC::NotOverflow => {
+                panic!("overflow conditions are not branch conditions on riscv64")
+            }
+        }
+    }
+
+    /// The mnemonic of the branch instruction testing this comparison.
+    fn op_name(&self) -> &'static str {
+        match self.branch_funct3_and_regs().0 {
+            0b000 => "beq",
+            0b001 => "bne",
+            0b100 => "blt",
+            0b101 => "bge",
+            0b110 => "bltu",
+            0b111 => "bgeu",
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl PrettyPrint for IntegerCompare {
+    fn show_rru(&self, mb_rru: Option<&RealRegUniverse>) -> String {
+        let (_, rs1, rs2) = self.branch_funct3_and_regs();
+        format!(
+            "{} {},{}",
+            self.op_name(),
+            rs1.show_rru(mb_rru),
+            rs2.show_rru(mb_rru)
+        )
+    }
+}
+
+/// A branch target. Either unresolved (basic-block index) or resolved (offset
+/// from start of current instruction).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BranchTarget {
+    /// An unresolved reference to a Label, as passed into
+    /// `lower_branch_group()`.
+    Label(MachLabel),
+    /// A fixed PC offset.
+    ResolvedOffset(i32),
+}
+
+impl BranchTarget {
+    /// Return the target's label, if it is a label-based target.
+    pub fn as_label(self) -> Option<MachLabel> {
+        match self {
+            BranchTarget::Label(l) => Some(l),
+            _ => None,
+        }
+    }
+
+    /// Return the target's offset, if specified, or zero if label-based.
+    pub fn as_offset(self) -> i32 {
+        match self {
+            BranchTarget::ResolvedOffset(off) => off,
+            _ => 0,
+        }
+    }
+}
+
+impl PrettyPrint for BranchTarget {
+    fn show_rru(&self, _mb_rru: Option<&RealRegUniverse>) -> String {
+        match self {
+            &BranchTarget::Label(label) => format!("label{:?}", label.get()),
+            &BranchTarget::ResolvedOffset(off) => format!("{}", off),
+        }
+    }
+}
+
+//=============================================================================
+// Instruction sub-components: opcodes
+
+/// The major opcodes used by this backend.
+pub(crate) mod opcode {
+    pub const LOAD: u32 = 0b0000011;
+    pub const LOAD_FP: u32 = 0b0000111;
+    pub const MISC_MEM: u32 = 0b0001111;
+    pub const OP_IMM: u32 = 0b0010011;
+    pub const AUIPC: u32 = 0b0010111;
+    pub const OP_IMM_32: u32 = 0b0011011;
+    pub const STORE: u32 = 0b0100011;
+    pub const STORE_FP: u32 = 0b0100111;
+    pub const AMO: u32 = 0b0101111;
+    pub const OP: u32 = 0b0110011;
+    pub const LUI: u32 = 0b0110111;
+    pub const OP_32: u32 = 0b0111011;
+    pub const OP_FP: u32 = 0b1010011;
+    pub const BRANCH: u32 = 0b1100011;
+    pub const JALR: u32 = 0b1100111;
+    pub const JAL: u32 = 0b1101111;
+    pub const SYSTEM: u32 = 0b1110011;
+}
+
+/// Floating-point rounding modes, as encoded in the `rm` field.
+pub(crate) mod rounding_mode {
+    /// Round to nearest, ties to even.
+    pub const RNE: u32 = 0b000;
+    /// Round towards zero.
+    pub const RTZ: u32 = 0b001;
+    /// Round down (towards negative infinity).
+    pub const RDN: u32 = 0b010;
+    /// Round up (towards positive infinity).
+    pub const RUP: u32 = 0b011;
+}
+
+impl AluOPRRR {
+    /// Returns the `(opcode, funct3, funct7)` fields of the R-type encoding.
+    pub(crate) fn encoding(self) -> (u32, u32, u32) {
+        use opcode::{OP, OP_32};
+        match self {
+            AluOPRRR::Add => (OP, 0b000, 0b0000000),
+            AluOPRRR::Sub => (OP, 0b000, 0b0100000),
+            AluOPRRR::Sll => (OP, 0b001, 0b0000000),
+            AluOPRRR::Slt => (OP, 0b010, 0b0000000),
+            AluOPRRR::Sltu => (OP, 0b011, 0b0000000),
+            AluOPRRR::Xor => (OP, 0b100, 0b0000000),
+            AluOPRRR::Srl => (OP, 0b101, 0b0000000),
+            AluOPRRR::Sra => (OP, 0b101, 0b0100000),
+            AluOPRRR::Or => (OP, 0b110, 0b0000000),
+            AluOPRRR::And => (OP, 0b111, 0b0000000),
+            AluOPRRR::Addw => (OP_32, 0b000, 0b0000000),
+            AluOPRRR::Subw => (OP_32, 0b000, 0b0100000),
+            AluOPRRR::Sllw => (OP_32, 0b001, 0b0000000),
+            AluOPRRR::Srlw => (OP_32, 0b101, 0b0000000),
+            AluOPRRR::Sraw => (OP_32, 0b101, 0b0100000),
+            AluOPRRR::Mul => (OP, 0b000, 0b0000001),
+            AluOPRRR::Mulh => (OP, 0b001, 0b0000001),
+            AluOPRRR::Mulhsu => (OP, 0b010, 0b0000001),
+            AluOPRRR::Mulhu => (OP, 0b011, 0b0000001),
+            AluOPRRR::Div => (OP, 0b100, 0b0000001),
+            AluOPRRR::Divu => (OP, 0b101, 0b0000001),
+            AluOPRRR::Rem => (OP, 0b110, 0b0000001),
+            AluOPRRR::Remu => (OP, 0b111, 0b0000001),
+            AluOPRRR::Mulw => (OP_32, 0b000, 0b0000001),
+            AluOPRRR::Divw => (OP_32, 0b100, 0b0000001),
+            AluOPRRR::Divuw => (OP_32, 0b101, 0b0000001),
+            AluOPRRR::Remw => (OP_32, 0b110, 0b0000001),
+            AluOPRRR::Remuw => (OP_32, 0b111, 0b0000001),
+            AluOPRRR::AddUw => (OP_32, 0b000, 0b0000100),
+            AluOPRRR::Andn => (OP, 0b111, 0b0100000),
+            AluOPRRR::Orn => (OP, 0b110, 0b0100000),
+            AluOPRRR::Xnor => (OP, 0b100, 0b0100000),
+            AluOPRRR::Max => (OP, 0b110, 0b0000101),
+            AluOPRRR::Maxu => (OP, 0b111, 0b0000101),
+            AluOPRRR::Min => (OP, 0b100, 0b0000101),
+            AluOPRRR::Minu => (OP, 0b101, 0b0000101),
+            AluOPRRR::Rol => (OP, 0b001, 0b0110000),
+            AluOPRRR::Rolw => (OP_32, 0b001, 0b0110000),
+            AluOPRRR::Ror => (OP, 0b101, 0b0110000),
+            AluOPRRR::Rorw => (OP_32, 0b101, 0b0110000),
+        }
+    }
+
+    /// Get the assembly mnemonic for this opcode.
+    pub fn op_name(self) -> &'static str {
+        match self {
+            AluOPRRR::Add => "add",
+            AluOPRRR::Sub => "sub",
+            AluOPRRR::Sll => "sll",
+            AluOPRRR::Slt => "slt",
+            AluOPRRR::Sltu => "sltu",
+            AluOPRRR::Xor => "xor",
+            AluOPRRR::Srl => "srl",
+            AluOPRRR::Sra => "sra",
+            AluOPRRR::Or => "or",
+            AluOPRRR::And => "and",
+            AluOPRRR::Addw => "addw",
+            AluOPRRR::Subw => "subw",
+            AluOPRRR::Sllw => "sllw",
+            AluOPRRR::Srlw => "srlw",
+            AluOPRRR::Sraw => "sraw",
+            AluOPRRR::Mul => "mul",
+            AluOPRRR::Mulh => "mulh",
+            AluOPRRR::Mulhsu => "mulhsu",
+            AluOPRRR::Mulhu => "mulhu",
+            AluOPRRR::Div => "div",
+            AluOPRRR::Divu => "divu",
+            AluOPRRR::Rem => "rem",
+            AluOPRRR::Remu => "remu",
+            AluOPRRR::Mulw => "mulw",
+            AluOPRRR::Divw => "divw",
+            AluOPRRR::Divuw => "divuw",
+            AluOPRRR::Remw => "remw",
+            AluOPRRR::Remuw => "remuw",
+            AluOPRRR::AddUw => "add.uw",
+            AluOPRRR::Andn => "andn",
+            AluOPRRR::Orn => "orn",
+            AluOPRRR::Xnor => "xnor",
+            AluOPRRR::Max => "max",
+            AluOPRRR::Maxu => "maxu",
+            AluOPRRR::Min => "min",
+            AluOPRRR::Minu => "minu",
+            AluOPRRR::Rol => "rol",
+            AluOPRRR::Rolw => "rolw",
+            AluOPRRR::Ror => "ror",
+            AluOPRRR::Rorw => "rorw",
+        }
+    }
+}
+
+impl AluOPRRI {
+    /// Returns the `(opcode, funct3)` fields of the I-type encoding and the
+    /// 12-bit immediate field for the given immediate operand. Shift amounts
+    /// are masked to the width of the operation.
+    pub(crate) fn encoding(self, imm12: Imm12) -> (u32, u32, u32) {
+        use opcode::{OP_IMM, OP_IMM_32};
+        let shamt64 = imm12.bits() & 0x3f;
+        let shamt32 = imm12.bits() & 0x1f;
+        match self {
+            AluOPRRI::Addi => (OP_IMM, 0b000, imm12.bits()),
+            AluOPRRI::Slti => (OP_IMM, 0b010, imm12.bits()),
+            AluOPRRI::Sltiu => (OP_IMM, 0b011, imm12.bits()),
+            AluOPRRI::Xori => (OP_IMM, 0b100, imm12.bits()),
+            AluOPRRI::Ori => (OP_IMM, 0b110, imm12.bits()),
+            AluOPRRI::Andi => (OP_IMM, 0b111, imm12.bits()),
+            AluOPRRI::Slli => (OP_IMM, 0b001, shamt64),
+            AluOPRRI::Srli => (OP_IMM, 0b101, shamt64),
+            AluOPRRI::Srai => (OP_IMM, 0b101, 0x400 | shamt64),
+            AluOPRRI::Addiw => (OP_IMM_32, 0b000, imm12.bits()),
+            AluOPRRI::Slliw => (OP_IMM_32, 0b001, shamt32),
+            AluOPRRI::Srliw => (OP_IMM_32, 0b101, shamt32),
+            AluOPRRI::Sraiw => (OP_IMM_32, 0b101, 0x400 | shamt32),
+            AluOPRRI::Rori => (OP_IMM, 0b101, 0x600 | shamt64),
+            AluOPRRI::Roriw => (OP_IMM_32, 0b101, 0x600 | shamt32),
+        }
+    }
+
+    /// Get the assembly mnemonic for this opcode.
+    pub fn op_name(self) -> &'static str {
+        match self {
+            AluOPRRI::Addi => "addi",
+            AluOPRRI::Slti => "slti",
+            AluOPRRI::Sltiu => "sltiu",
+            AluOPRRI::Xori => "xori",
+            AluOPRRI::Ori => "ori",
+            AluOPRRI::Andi => "andi",
+            AluOPRRI::Slli => "slli",
+            AluOPRRI::Srli => "srli",
+            AluOPRRI::Srai => "srai",
+            AluOPRRI::Addiw => "addiw",
+            AluOPRRI::Slliw => "slliw",
+            AluOPRRI::Srliw => "srliw",
+            AluOPRRI::Sraiw => "sraiw",
+            AluOPRRI::Rori => "rori",
+            AluOPRRI::Roriw => "roriw",
+        }
+    }
+
+    /// Is the immediate operand of this opcode a shift amount?
+    pub fn is_shift(self) -> bool {
+        match self {
+            AluOPRRI::Slli
+            | AluOPRRI::Srli
+            | AluOPRRI::Srai
+            | AluOPRRI::Slliw
+            | AluOPRRI::Srliw
+            | AluOPRRI::Sraiw
+            | AluOPRRI::Rori
+            | AluOPRRI::Roriw => true,
+            _ => false,
+        }
+    }
+}
+
+impl AluOPRR {
+    /// Returns the `(opcode, funct3, imm12)` fields of the I-type encoding;
+    /// the unary Zbb instructions encode their operation in the immediate.
+    pub(crate) fn encoding(self) -> (u32, u32, u32) {
+        use opcode::{OP_32, OP_IMM, OP_IMM_32};
+        match self {
+            AluOPRR::Clz => (OP_IMM, 0b001, 0x600),
+            AluOPRR::Ctz => (OP_IMM, 0b001, 0x601),
+            AluOPRR::Cpop => (OP_IMM, 0b001, 0x602),
+            AluOPRR::SextB => (OP_IMM, 0b001, 0x604),
+            AluOPRR::SextH => (OP_IMM, 0b001, 0x605),
+            AluOPRR::Clzw => (OP_IMM_32, 0b001, 0x600),
+            AluOPRR::Ctzw => (OP_IMM_32, 0b001, 0x601),
+            AluOPRR::Cpopw => (OP_IMM_32, 0b001, 0x602),
+            AluOPRR::Rev8 => (OP_IMM, 0b101, 0x6b8),
+            // `zext.h` is an R-type instruction with `rs2 = 0`, which has the
+            // same layout.
+            AluOPRR::ZextH => (OP_32, 0b100, 0x080),
+        }
+    }
+
+    /// Get the assembly mnemonic for this opcode.
+    pub fn op_name(self) -> &'static str {
+        match self {
+            AluOPRR::Clz => "clz",
+            AluOPRR::Clzw => "clzw",
+            AluOPRR::Ctz => "ctz",
+            AluOPRR::Ctzw => "ctzw",
+            AluOPRR::Cpop => "cpop",
+            AluOPRR::Cpopw => "cpopw",
+            AluOPRR::SextB => "sext.b",
+            AluOPRR::SextH => "sext.h",
+            AluOPRR::ZextH => "zext.h",
+            AluOPRR::Rev8 => "rev8",
+        }
+    }
+}
+
+impl FpuOPRR {
+    /// Returns the `(funct7, rs2, rm)` fields of the R-type encoding.
+    pub(crate) fn encoding(self) -> (u32, u32, u32) {
+        use rounding_mode::{RNE, RTZ};
+        match self {
+            FpuOPRR::FsqrtS => (0b0101100, 0, RNE),
+            FpuOPRR::FsqrtD => (0b0101101, 0, RNE),
+            FpuOPRR::FcvtSD => (0b0100000, 1, RNE),
+            FpuOPRR::FcvtDS => (0b0100001, 0, RNE),
+            FpuOPRR::FcvtWS => (0b1100000, 0, RTZ),
+            FpuOPRR::FcvtWuS => (0b1100000, 1, RTZ),
+            FpuOPRR::FcvtLS => (0b1100000, 2, RTZ),
+            FpuOPRR::FcvtLuS => (0b1100000, 3, RTZ),
+            FpuOPRR::FcvtWD => (0b1100001, 0, RTZ),
+            FpuOPRR::FcvtWuD => (0b1100001, 1, RTZ),
+            FpuOPRR::FcvtLD => (0b1100001, 2, RTZ),
+            FpuOPRR::FcvtLuD => (0b1100001, 3, RTZ),
+            FpuOPRR::FcvtSW => (0b1101000, 0, RNE),
+            FpuOPRR::FcvtSWu => (0b1101000, 1, RNE),
+            FpuOPRR::FcvtSL => (0b1101000, 2, RNE),
+            FpuOPRR::FcvtSLu => (0b1101000, 3, RNE),
+            FpuOPRR::FcvtDW => (0b1101001, 0, RNE),
+            FpuOPRR::FcvtDWu => (0b1101001, 1, RNE),
+            FpuOPRR::FcvtDL => (0b1101001, 2, RNE),
+            FpuOPRR::FcvtDLu => (0b1101001, 3, RNE),
+            FpuOPRR::FmvXW => (0b1110000, 0, 0b000),
+            FpuOPRR::FmvXD => (0b1110001, 0, 0b000),
+            FpuOPRR::FclassS => (0b1110000, 0, 0b001),
+            FpuOPRR::FclassD => (0b1110001, 0, 0b001),
+            FpuOPRR::FmvWX => (0b1111000, 0, 0b000),
+            FpuOPRR::FmvDX => (0b1111001, 0, 0b000),
+        }
+    }
+
+    /// Get the assembly mnemonic for this opcode.
+    pub fn op_name(self) -> &'static str {
+        match self {
+            FpuOPRR::FsqrtS => "fsqrt.s",
+            FpuOPRR::FsqrtD => "fsqrt.d",
+            FpuOPRR::FcvtSD => "fcvt.s.d",
+            FpuOPRR::FcvtDS => "fcvt.d.s",
+            FpuOPRR::FcvtWS => "fcvt.w.s",
+            FpuOPRR::FcvtWuS => "fcvt.wu.s",
+            FpuOPRR::FcvtLS => "fcvt.l.s",
+            FpuOPRR::FcvtLuS => "fcvt.lu.s",
+            FpuOPRR::FcvtWD => "fcvt.w.d",
+            FpuOPRR::FcvtWuD => "fcvt.wu.d",
+            FpuOPRR::FcvtLD => "fcvt.l.d",
+            FpuOPRR::FcvtLuD => "fcvt.lu.d",
+            FpuOPRR::FcvtSW => "fcvt.s.w",
+            FpuOPRR::FcvtSWu => "fcvt.s.wu",
+            FpuOPRR::FcvtSL => "fcvt.s.l",
+            FpuOPRR::FcvtSLu => "fcvt.s.lu",
+            FpuOPRR::FcvtDW => "fcvt.d.w",
+            FpuOPRR::FcvtDWu => "fcvt.d.wu",
+            FpuOPRR::FcvtDL => "fcvt.d.l",
+            FpuOPRR::FcvtDLu => "fcvt.d.lu",
+            FpuOPRR::FmvXW => "fmv.x.w",
+            FpuOPRR::FmvXD => "fmv.x.d",
+            FpuOPRR::FclassS => "fclass.s",
+            FpuOPRR::FclassD => "fclass.d",
+            FpuOPRR::FmvWX => "fmv.w.x",
+            FpuOPRR::FmvDX => "fmv.d.x",
+        }
+    }
+
+    /// Does this operation read an integer register (rather than a float
+    /// register)?
+    pub fn src_is_int(self) -> bool {
+        match self {
+            FpuOPRR::FcvtSW
+            | FpuOPRR::FcvtSWu
+            | FpuOPRR::FcvtSL
+            | FpuOPRR::FcvtSLu
+            | FpuOPRR::FcvtDW
+            | FpuOPRR::FcvtDWu
+            | FpuOPRR::FcvtDL
+            | FpuOPRR::FcvtDLu
+            | FpuOPRR::FmvWX
+            | FpuOPRR::FmvDX => true,
+            _ => false,
+        }
+    }
+
+    /// Does this operation write an integer register (rather than a float
+    /// register)?
+    pub fn dst_is_int(self) -> bool {
+        match self {
+            FpuOPRR::FcvtWS
+            | FpuOPRR::FcvtWuS
+            | FpuOPRR::FcvtLS
+            | FpuOPRR::FcvtLuS
+            | FpuOPRR::FcvtWD
+            | FpuOPRR::FcvtWuD
+            | FpuOPRR::FcvtLD
+            | FpuOPRR::FcvtLuD
+            | FpuOPRR::FmvXW
+            | FpuOPRR::FmvXD
+            | FpuOPRR::FclassS
+            | FpuOPRR::FclassD => true,
+            _ => false,
+        }
+    }
+
+    /// Does this operation take a rounding mode?
+    pub fn has_rounding_mode(self) -> bool {
+        match self {
+            FpuOPRR::FmvXW
+            | FpuOPRR::FmvXD
+            | FpuOPRR::FclassS
+            | FpuOPRR::FclassD
+            | FpuOPRR::FmvWX
+            | FpuOPRR::FmvDX => false,
+            _ => true,
+        }
+    }
+}
+
+impl FpuOPRRR {
+    /// Returns the `(funct7, funct3)` fields of the R-type encoding. For
+    /// arithmetic operations `funct3` is the rounding mode.
+    pub(crate) fn encoding(self) -> (u32, u32) {
+        use rounding_mode::RNE;
+        match self {
+            FpuOPRRR::FaddS => (0b0000000, RNE),
+            FpuOPRRR::FsubS => (0b0000100, RNE),
+            FpuOPRRR::FmulS => (0b0001000, RNE),
+            FpuOPRRR::FdivS => (0b0001100, RNE),
+            FpuOPRRR::FsgnjS => (0b0010000, 0b000),
+            FpuOPRRR::FsgnjnS => (0b0010000, 0b001),
+            FpuOPRRR::FsgnjxS => (0b0010000, 0b010),
+            FpuOPRRR::FminS => (0b0010100, 0b000),
+            FpuOPRRR::FmaxS => (0b0010100, 0b001),
+            FpuOPRRR::FeqS => (0b1010000, 0b010),
+            FpuOPRRR::FltS => (0b1010000, 0b001),
+            FpuOPRRR::FleS => (0b1010000, 0b000),
+            FpuOPRRR::FaddD => (0b0000001, RNE),
+            FpuOPRRR::FsubD => (0b0000101, RNE),
+            FpuOPRRR::FmulD => (0b0001001, RNE),
+            FpuOPRRR::FdivD => (0b0001101, RNE),
+            FpuOPRRR::FsgnjD => (0b0010001, 0b000),
+            FpuOPRRR::FsgnjnD => (0b0010001, 0b001),
+            FpuOPRRR::FsgnjxD => (0b0010001, 0b010),
+            FpuOPRRR::FminD => (0b0010101, 0b000),
+            FpuOPRRR::FmaxD => (0b0010101, 0b001),
+            FpuOPRRR::FeqD => (0b1010001, 0b010),
+            FpuOPRRR::FltD => (0b1010001, 0b001),
+            FpuOPRRR::FleD => (0b1010001, 0b000),
+        }
+    }
+
+    /// Get the assembly mnemonic for this opcode.
+    pub fn op_name(self) -> &'static str {
+        match self {
+            FpuOPRRR::FaddS => "fadd.s",
+            FpuOPRRR::FsubS => "fsub.s",
+            FpuOPRRR::FmulS => "fmul.s",
+            FpuOPRRR::FdivS => "fdiv.s",
+            FpuOPRRR::FsgnjS => "fsgnj.s",
+            FpuOPRRR::FsgnjnS => "fsgnjn.s",
+            FpuOPRRR::FsgnjxS => "fsgnjx.s",
+            FpuOPRRR::FminS => "fmin.s",
+            FpuOPRRR::FmaxS => "fmax.s",
+            FpuOPRRR::FeqS => "feq.s",
+            FpuOPRRR::FltS => "flt.s",
+            FpuOPRRR::FleS => "fle.s",
+            FpuOPRRR::FaddD => "fadd.d",
+            FpuOPRRR::FsubD => "fsub.d",
+            FpuOPRRR::FmulD => "fmul.d",
+            FpuOPRRR::FdivD => "fdiv.d",
+            FpuOPRRR::FsgnjD => "fsgnj.d",
+            FpuOPRRR::FsgnjnD => "fsgnjn.d",
+            FpuOPRRR::FsgnjxD => "fsgnjx.d",
+            FpuOPRRR::FminD => "fmin.d",
+            FpuOPRRR::FmaxD => "fmax.d",
+            FpuOPRRR::FeqD => "feq.d",
+            FpuOPRRR::FltD => "flt.d",
+            FpuOPRRR::FleD => "fle.d",
+        }
+    }
+
+    /// Does this operation write an integer register (rather than a float
+    /// register)?
+    pub fn dst_is_int(self) -> bool {
+        match self {
+            FpuOPRRR::FeqS
+            | FpuOPRRR::FltS
+            | FpuOPRRR::FleS
+            | FpuOPRRR::FeqD
+            | FpuOPRRR::FltD
+            | FpuOPRRR::FleD => true,
+            _ => false,
+        }
+    }
+}
+
+impl FpuOPRRRR {
+    /// Returns the `(opcode, fmt)` fields of the R4-type encoding.
+    pub(crate) fn encoding(self) -> (u32, u32) {
+        match self {
+            FpuOPRRRR::FmaddS => (0b1000011, 0b00),
+            FpuOPRRRR::FmsubS => (0b1000111, 0b00),
+            FpuOPRRRR::FnmsubS => (0b1001011, 0b00),
+            FpuOPRRRR::FnmaddS => (0b1001111, 0b00),
+            FpuOPRRRR::FmaddD => (0b1000011, 0b01),
+            FpuOPRRRR::FmsubD => (0b1000111, 0b01),
+            FpuOPRRRR::FnmsubD => (0b1001011, 0b01),
+            FpuOPRRRR::FnmaddD => (0b1001111, 0b01),
+        }
+    }
+
+    /// Get the assembly mnemonic for this opcode.
+    pub fn op_name(self) -> &'static str {
+        match self {
+            FpuOPRRRR::FmaddS => "fmadd.s",
+            FpuOPRRRR::FmsubS => "fmsub.s",
+            FpuOPRRRR::FnmsubS => "fnmsub.s",
+            FpuOPRRRR::FnmaddS => "fnmadd.s",
+            FpuOPRRRR::FmaddD => "fmadd.d",
+            FpuOPRRRR::FmsubD => "fmsub.d",
+            FpuOPRRRR::FnmsubD => "fnmsub.d",
+            FpuOPRRRR::FnmaddD => "fnmadd.d",
+        }
+    }
+}
+
+impl LoadOP {
+    /// Returns the `(opcode, funct3)` fields of the I-type encoding.
+    pub(crate) fn encoding(self) -> (u32, u32) {
+        use opcode::{LOAD, LOAD_FP};
+        match self {
+            LoadOP::Lb => (LOAD, 0b000),
+            LoadOP::Lh => (LOAD, 0b001),
+            LoadOP::Lw => (LOAD, 0b010),
+            LoadOP::Ld => (LOAD, 0b011),
+            LoadOP::Lbu => (LOAD, 0b100),
+            LoadOP::Lhu => (LOAD, 0b101),
+            LoadOP::Lwu => (LOAD, 0b110),
+            LoadOP::Flw => (LOAD_FP, 0b010),
+            LoadOP::Fld => (LOAD_FP, 0b011),
+        }
+    }
+
+    /// Get the assembly mnemonic for this opcode.
+    pub fn op_name(self) -> &'static str {
+        match self {
+            LoadOP::Lb => "lb",
+            LoadOP::Lh => "lh",
+            LoadOP::Lw => "lw",
+            LoadOP::Ld => "ld",
+            LoadOP::Lbu => "lbu",
+            LoadOP::Lhu => "lhu",
+            LoadOP::Lwu => "lwu",
+            LoadOP::Flw => "flw",
+            LoadOP::Fld => "fld",
+        }
+    }
+
+    /// The load that reads a value of type `ty` without extending it
+    /// any further than the type itself requires.
+    pub fn from_type(ty: Type) -> LoadOP {
+        if ty.is_float() {
+            return if ty.bits() == 32 {
+                LoadOP::Flw
+            } else {
+                LoadOP::Fld
+            };
+        }
+        match ty.bits() {
+            1 | 8 => LoadOP::Lbu,
+            16 => LoadOP::Lhu,
+            32 => LoadOP::Lw,
+            64 => LoadOP::Ld,
+            _ => panic!("unsupported load type: {}", ty),
+        }
+    }
+}
+
+impl StoreOP {
+    /// Returns the `(opcode, funct3)` fields of the S-type encoding.
+    pub(crate) fn encoding(self) -> (u32, u32) {
+        use opcode::{STORE, STORE_FP};
+        match self {
+            StoreOP::Sb => (STORE, 0b000),
+            StoreOP::Sh => (STORE, 0b001),
+            StoreOP::Sw => (STORE, 0b010),
+            StoreOP::Sd => (STORE, 0b011),
+            StoreOP::Fsw => (STORE_FP, 0b010),
+            StoreOP::Fsd => (STORE_FP, 0b011),
+        }
+    }
+
+    /// Get the assembly mnemonic for this opcode.
+    pub fn op_name(self) -> &'static str {
+        match self {
+            StoreOP::Sb => "sb",
+            StoreOP::Sh => "sh",
+            StoreOP::Sw => "sw",
+            StoreOP::Sd => "sd",
+            StoreOP::Fsw => "fsw",
+            StoreOP::Fsd => "fsd",
+        }
+    }
+
+    /// The store that writes a value of type `ty`.
+    pub fn from_type(ty: Type) -> StoreOP {
+        if ty.is_float() {
+            return if ty.bits() == 32 {
+                StoreOP::Fsw
+            } else {
+                StoreOP::Fsd
+            };
+        }
+        match ty.bits() {
+            1 | 8 => StoreOP::Sb,
+            16 => StoreOP::Sh,
+            32 => StoreOP::Sw,
+            64 => StoreOP::Sd,
+            _ => panic!("unsupported store type: {}", ty),
+        }
+    }
+}
+
+impl AtomicOP {
+    /// Returns the `(funct5, funct3)` fields of the AMO encoding.
+    pub(crate) fn encoding(self) -> (u32, u32) {
+        let (funct5, is_64) = match self {
+            AtomicOP::AmoswapW => (0b00001, false),
+            AtomicOP::AmoaddW => (0b00000, false),
+            AtomicOP::AmoxorW => (0b00100, false),
+            AtomicOP::AmoandW => (0b01100, false),
+            AtomicOP::AmoorW => (0b01000, false),
+            AtomicOP::AmominW => (0b10000, false),
+            AtomicOP::AmomaxW => (0b10100, false),
+            AtomicOP::AmominuW => (0b11000, false),
+            AtomicOP::AmomaxuW => (0b11100, false),
+            AtomicOP::AmoswapD => (0b00001, true),
+            AtomicOP::AmoaddD => (0b00000, true),
+            AtomicOP::AmoxorD => (0b00100, true),
+            AtomicOP::AmoandD => (0b01100, true),
+            AtomicOP::AmoorD => (0b01000, true),
+            AtomicOP::AmominD => (0b10000, true),
+            AtomicOP::AmomaxD => (0b10100, true),
+            AtomicOP::AmominuD => (0b11000, true),
+            AtomicOP::AmomaxuD => (0b11100, true),
+        };
+        (funct5, if is_64 { 0b011 } else { 0b010 })
+    }
+
+    /// Get the assembly mnemonic for this opcode.
+    pub fn op_name(self) -> &'static str {
+        match self {
+            AtomicOP::AmoswapW => "amoswap.w.aqrl",
+            AtomicOP::AmoaddW => "amoadd.w.aqrl",
+            AtomicOP::AmoxorW => "amoxor.w.aqrl",
+            AtomicOP::AmoandW => "amoand.w.aqrl",
+            AtomicOP::AmoorW => "amoor.w.aqrl",
+            AtomicOP::AmominW => "amomin.w.aqrl",
+            AtomicOP::AmomaxW => "amomax.w.aqrl",
+            AtomicOP::AmominuW => "amominu.w.aqrl",
+            AtomicOP::AmomaxuW => "amomaxu.w.aqrl",
+            AtomicOP::AmoswapD => "amoswap.d.aqrl",
+            AtomicOP::AmoaddD => "amoadd.d.aqrl",
+            AtomicOP::AmoxorD => "amoxor.d.aqrl",
+            AtomicOP::AmoandD => "amoand.d.aqrl",
+            AtomicOP::AmoorD => "amoor.d.aqrl",
+            AtomicOP::AmominD => "amomin.d.aqrl",
+            AtomicOP::AmomaxD => "amomax.d.aqrl",
+            AtomicOP::AmominuD => "amominu.d.aqrl",
+            AtomicOP::AmomaxuD => "amomaxu.d.aqrl",
+        }
+    }
+}
@@ -0,0 +1,1495 @@
+//! RISC-V 64 ISA: binary code emission.
+
+use crate::binemit::{Reloc, StackMap};
+use crate::ir::types::*;
+use crate::ir::{AtomicRmwOp, TrapCode};
+use crate::isa::riscv64::inst::*;
+use crate::isa::riscv64::settings as riscv64_settings;
+
+use regalloc::Reg;
+
+use core::convert::TryFrom;
+
+/// Memory addressing mode finalization: convert the generic arbitrary-offset
+/// modes into the single RISC-V "register plus signed 12-bit offset" form,
+/// possibly by emitting some helper instructions that come immediately before
+/// the use of this amode. Returns the helper instructions, the base register
+/// and the offset.
+pub fn mem_finalize(mem: &AMode, state: &EmitState) -> (SmallVec<[Inst; 4]>, Reg, Imm12) {
+    let (base, off) = match mem {
+        &AMode::RegOffset(reg, off, _) => (reg, off),
+        &AMode::SPOffset(off, _) => (stack_reg(), off),
+        &AMode::FPOffset(off, _) => (fp_reg(), off),
+        &AMode::NominalSPOffset(off, _) => {
+            log::trace!(
+                "mem_finalize: nominal SP offset {} + adj {} -> {}",
+                off,
+                state.virtual_sp_offset,
+                off + state.virtual_sp_offset
+            );
+            (stack_reg(), off + state.virtual_sp_offset)
+        }
+    };
+
+    if let Some(imm12) = Imm12::maybe_from_i64(off) {
+        (smallvec![], base, imm12)
+    } else {
+        // The base may itself be the spilltmp register if it came from an
+        // earlier finalization, in which case we use the second temporary.
+        let tmp = if base == spilltmp_reg() {
+            writable_tmp2_reg()
+        } else {
+            writable_spilltmp_reg()
+        };
+        let mut insts = Inst::load_constant(tmp, off as u64);
+        insts.push(Inst::AluRRR {
+            alu_op: AluOPRRR::Add,
+            rd: tmp,
+            rs1: tmp.to_reg(),
+            rs2: base,
+        });
+        (insts, tmp.to_reg(), Imm12::ZERO)
+    }
+}
+
+//=============================================================================
+// Instructions and subcomponents: emission
+
+fn machreg_to_gpr(m: Reg) -> u32 {
+    assert_eq!(m.get_class(), RegClass::I64);
+    u32::try_from(m.to_real_reg().get_hw_encoding()).unwrap()
+}
+
+fn machreg_to_fpr(m: Reg) -> u32 {
+    assert_eq!(m.get_class(), RegClass::F64);
+    u32::try_from(m.to_real_reg().get_hw_encoding()).unwrap()
+}
+
+fn machreg_to_gpr_or_fpr(m: Reg) -> u32 {
+    u32::try_from(m.to_real_reg().get_hw_encoding()).unwrap()
+}
+
+/// Encode an R-type instruction.
+pub(crate) fn enc_r(opcode: u32, rd: u32, funct3: u32, rs1: u32, rs2: u32, funct7: u32) -> u32 {
+    debug_assert!(rd < 32 && rs1 < 32 && rs2 < 32);
+    opcode | rd << 7 | funct3 << 12 | rs1 << 15 | rs2 << 20 | funct7 << 25
+}
+
+/// Encode an R4-type instruction (fused multiply-add).
+pub(crate) fn enc_r4(opcode: u32, rd: u32, rm: u32, rs1: u32, rs2: u32, fmt: u32, rs3: u32) -> u32 {
+    opcode | rd << 7 | rm << 12 | rs1 << 15 | rs2 << 20 | fmt << 25 | rs3 << 27
+}
+
+/// Encode an I-type instruction. `imm12` is the raw 12-bit immediate field.
+pub(crate) fn enc_i(opcode: u32, rd: u32, funct3: u32, rs1: u32, imm12: u32) -> u32 {
+    debug_assert!(rd < 32 && rs1 < 32);
+    debug_assert!(imm12 < (1 << 12));
+    opcode | rd << 7 | funct3 << 12 | rs1 << 15 | imm12 << 20
+}
+
+/// Encode an S-type instruction. `imm12` is the raw 12-bit immediate field.
+pub(crate) fn enc_s(opcode: u32, funct3: u32, rs1: u32, rs2: u32, imm12: u32) -> u32 {
+    debug_assert!(imm12 < (1 << 12));
+    opcode | (imm12 & 0x1f) << 7 | funct3 << 12 | rs1 << 15 | rs2 << 20 | (imm12 >> 5) << 25
+}
+
+/// Encode a conditional branch with the given byte offset.
+pub(crate) fn enc_b(funct3: u32, rs1: u32, rs2: u32, off: i32) -> u32 {
+    debug_assert!(off >= -(1 << 12) && off < (1 << 12) && off & 1 == 0);
+    opcode::BRANCH | funct3 << 12 | rs1 << 15 | rs2 << 20 | enc_b_offset(off)
+}
+
+/// The immediate bits of a B-type instruction for the given byte offset.
+pub(crate) fn enc_b_offset(off: i32) -> u32 {
+    let off = off as u32;
+    ((off >> 12) & 1) << 31
+        | ((off >> 5) & 0x3f) << 25
+        | ((off >> 1) & 0xf) << 8
+        | ((off >> 11) & 1) << 7
+}
+
+/// Encode a U-type instruction.
+pub(crate) fn enc_u(opcode: u32, rd: u32, imm20: u32) -> u32 {
+    debug_assert!(imm20 < (1 << 20));
+    opcode | rd << 7 | imm20 << 12
+}
+
+/// Encode a `jal` with the given byte offset.
+pub(crate) fn enc_jal(rd: u32, off: i32) -> u32 {
+    debug_assert!(off >= -(1 << 20) && off < (1 << 20) && off & 1 == 0);
+    opcode::JAL | rd << 7 | enc_jal_offset(off)
+}
+
+/// The immediate bits of a J-type instruction for the given byte offset.
+pub(crate) fn enc_jal_offset(off: i32) -> u32 {
+    let off = off as u32;
+    ((off >> 20) & 1) << 31
+        | ((off >> 1) & 0x3ff) << 21
+        | ((off >> 11) & 1) << 20
+        | ((off >> 12) & 0xff) << 12
+}
+
+/// Encode a `jalr rd, off(rs1)`.
+pub(crate) fn enc_jalr(rd: u32, rs1: u32, off: i32) -> u32 {
+    enc_i(opcode::JALR, rd, 0b000, rs1, (off as u32) & 0xfff)
+}
+
+/// Encode an `auipc rd, imm20`.
+pub(crate) fn enc_auipc(rd: u32, imm20: u32) -> u32 {
+    enc_u(opcode::AUIPC, rd, imm20)
+}
+
+/// Encode a load-reserved (`lr`) or store-conditional (`sc`) instruction, or
+/// an AMO, with acquire-release ordering.
+fn enc_amo(funct5: u32, funct3: u32, rd: u32, rs1: u32, rs2: u32) -> u32 {
+    opcode::AMO | rd << 7 | funct3 << 12 | rs1 << 15 | rs2 << 20 | 0b11 << 25 | funct5 << 27
+}
+
+/// The `funct3` of `lr`/`sc` for a value of type `ty`.
+fn lr_sc_funct3(ty: Type) -> u32 {
+    if ty.bits() == 64 {
+        0b011
+    } else {
+        0b010
+    }
+}
+
+/// `csrrw zero, fflags, zero`: clear the accrued floating-point exception
+/// flags.
+const CLEAR_FFLAGS: u32 = 0x00101073;
+
+/// The invalid-operation bit of the `fflags` CSR.
+const FFLAGS_NV: u32 = 1 << 4;
+
+/// State carried between emissions of a sequence of instructions.
+#[derive(Default, Clone, Debug)]
+pub struct EmitState {
+    /// Addend to convert nominal-SP offsets to real-SP offsets at the current
+    /// program point.
+    pub(crate) virtual_sp_offset: i64,
+    /// Offset of FP from nominal-SP.
+    pub(crate) nominal_sp_to_fp: i64,
+    /// Safepoint stack map for upcoming instruction, as provided to `pre_safepoint()`.
+    stack_map: Option<StackMap>,
+    /// Current source-code location corresponding to instruction to be emitted.
+    cur_srcloc: SourceLoc,
+}
+
+impl MachInstEmitState<Inst> for EmitState {
+    fn new(abi: &dyn ABICallee<I = Inst>) -> Self {
+        EmitState {
+            virtual_sp_offset: 0,
+            nominal_sp_to_fp: abi.frame_size() as i64,
+            stack_map: None,
+            cur_srcloc: SourceLoc::default(),
+        }
+    }
+
+    fn pre_safepoint(&mut self, stack_map: StackMap) {
+        self.stack_map = Some(stack_map);
+    }
+
+    fn pre_sourceloc(&mut self, srcloc: SourceLoc) {
+        self.cur_srcloc = srcloc;
+    }
+}
+
+impl EmitState {
+    fn take_stack_map(&mut self) -> Option<StackMap> {
+        self.stack_map.take()
+    }
+
+    fn clear_post_insn(&mut self) {
+        self.stack_map = None;
+    }
+
+    fn cur_srcloc(&self) -> SourceLoc {
+        self.cur_srcloc
+    }
+}
+
+/// Constant state used during function compilation.
+pub struct EmitInfo {
+    flags: settings::Flags,
+    isa_flags: riscv64_settings::Flags,
+}
+
+impl EmitInfo {
+    pub(crate) fn new(flags: settings::Flags, isa_flags: riscv64_settings::Flags) -> Self {
+        Self { flags, isa_flags }
+    }
+}
+
+/// Emit `inst` and return nothing; used for the expansions of
+/// pseudo-instructions below.
+fn emit_all(insts: &[Inst], sink: &mut MachBuffer<Inst>, info: &EmitInfo, state: &mut EmitState) {
+    for inst in insts {
+        inst.emit(sink, info, state);
+    }
+}
+
+/// Register a heap-out-of-bounds trap at the current offset, for the atomic
+/// memory accesses, which carry no `MemFlags`.
+fn add_heap_trap(sink: &mut MachBuffer<Inst>, state: &EmitState) {
+    let srcloc = state.cur_srcloc();
+    if srcloc != SourceLoc::default() {
+        sink.add_trap(srcloc, TrapCode::HeapOutOfBounds);
+    }
+}
+
+/// Emit a branch over the next (4-byte) instruction if `kind` holds.
+fn emit_skip_next_if(sink: &mut MachBuffer<Inst>, kind: IntegerCompare) {
+    let (funct3, rs1, rs2) = kind.branch_funct3_and_regs();
+    sink.put4(enc_b(funct3, machreg_to_gpr(rs1), machreg_to_gpr(rs2), 8));
+}
+
+/// Emit a backwards conditional branch to `target`, an offset that has
+/// already been emitted in this instruction.
+fn emit_branch_back_if(sink: &mut MachBuffer<Inst>, kind: IntegerCompare, target: CodeOffset) {
+    let off = target as i32 - sink.cur_offset() as i32;
+    let (funct3, rs1, rs2) = kind.branch_funct3_and_regs();
+    sink.put4(enc_b(funct3, machreg_to_gpr(rs1), machreg_to_gpr(rs2), off));
+}
+
+impl MachInstEmit for Inst {
+    type State = EmitState;
+    type Info = EmitInfo;
+
+    fn emit(&self, sink: &mut MachBuffer<Inst>, emit_info: &Self::Info, state: &mut EmitState) {
+        // N.B.: we *must* not exceed the "worst-case size" used to compute
+        // where to insert islands, except when islands are explicitly triggered
+        // (with an `EmitIsland`). We check this in debug builds. This is `mut`
+        // to allow disabling the check for `JTSequence`, which is always
+        // emitted following an `EmitIsland`.
+        let mut start_off = sink.cur_offset();
+
+        let x30 = tmp2_reg();
+        let x31 = spilltmp_reg();
+        let wx30 = writable_tmp2_reg();
+        let wx31 = writable_spilltmp_reg();
+
+        match self {
+            &Inst::Nop0 => {}
+            &Inst::Nop4 => {
+                // addi zero, zero, 0
+                sink.put4(0x00000013);
+            }
+            &Inst::Lui { rd, imm } => {
+                sink.put4(enc_u(opcode::LUI, machreg_to_gpr(rd.to_reg()), imm.bits()));
+            }
+            &Inst::LoadConst64 { rd, imm } => {
+                // auipc rd, 0
+                // ld rd, 12(rd)
+                // j 12
+                // .8byte imm
+                let rd = machreg_to_gpr(rd.to_reg());
+                sink.put4(enc_auipc(rd, 0));
+                sink.put4(enc_i(opcode::LOAD, rd, 0b011, rd, 12));
+                sink.put4(enc_jal(0, 12));
+                sink.put8(imm);
+            }
+            &Inst::AluRRR {
+                alu_op,
+                rd,
+                rs1,
+                rs2,
+            } => {
+                let (opcode, funct3, funct7) = alu_op.encoding();
+                sink.put4(enc_r(
+                    opcode,
+                    machreg_to_gpr(rd.to_reg()),
+                    funct3,
+                    machreg_to_gpr(rs1),
+                    machreg_to_gpr(rs2),
+                    funct7,
+                ));
+            }
+            &Inst::AluRRImm12 {
+                alu_op,
+                rd,
+                rs,
+                imm12,
+            } => {
+                let (opcode, funct3, imm) = alu_op.encoding(imm12);
+                sink.put4(enc_i(
+                    opcode,
+                    machreg_to_gpr(rd.to_reg()),
+                    funct3,
+                    machreg_to_gpr(rs),
+                    imm,
+                ));
+            }
+            &Inst::AluRR { alu_op, rd, rs } => {
+                let (opcode, funct3, imm) = alu_op.encoding();
+                sink.put4(enc_i(
+                    opcode,
+                    machreg_to_gpr(rd.to_reg()),
+                    funct3,
+                    machreg_to_gpr(rs),
+                    imm,
+                ));
+            }
+            &Inst::FpuRR { alu_op, rd, rs } => {
+                let (funct7, rs2, rm) = alu_op.encoding();
+                sink.put4(enc_r(
+                    opcode::OP_FP,
+                    machreg_to_gpr_or_fpr(rd.to_reg()),
+                    rm,
+                    machreg_to_gpr_or_fpr(rs),
+                    rs2,
+                    funct7,
+                ));
+            }
+            &Inst::FpuRRR {
+                alu_op,
+                rd,
+                rs1,
+                rs2,
+            } => {
+                let (funct7, funct3) = alu_op.encoding();
+                sink.put4(enc_r(
+                    opcode::OP_FP,
+                    machreg_to_gpr_or_fpr(rd.to_reg()),
+                    funct3,
+                    machreg_to_fpr(rs1),
+                    machreg_to_fpr(rs2),
+                    funct7,
+                ));
+            }
+            &Inst::FpuRRRR {
+                alu_op,
+                rd,
+                rs1,
+                rs2,
+                rs3,
+            } => {
+                let (opcode, fmt) = alu_op.encoding();
+                sink.put4(enc_r4(
+                    opcode,
+                    machreg_to_fpr(rd.to_reg()),
+                    rounding_mode::RNE,
+                    machreg_to_fpr(rs1),
+                    machreg_to_fpr(rs2),
+                    fmt,
+                    machreg_to_fpr(rs3),
+                ));
+            }
+            &Inst::Load {
+                rd,
+                op,
+                flags,
+                ref from,
+            } => {
+                let (mem_insts, base, imm12) = mem_finalize(from, state);
+                emit_all(&mem_insts, sink, emit_info, state);
+
+                let srcloc = state.cur_srcloc();
+                if srcloc != SourceLoc::default() && !flags.notrap() {
+                    // Register the offset at which the actual load instruction starts.
+                    sink.add_trap(srcloc, TrapCode::HeapOutOfBounds);
+                }
+
+                let (opcode, funct3) = op.encoding();
+                sink.put4(enc_i(
+                    opcode,
+                    machreg_to_gpr_or_fpr(rd.to_reg()),
+                    funct3,
+                    machreg_to_gpr(base),
+                    imm12.bits(),
+                ));
+            }
+            &Inst::Store {
+                ref to,
+                op,
+                flags,
+                src,
+            } => {
+                let (mem_insts, base, imm12) = mem_finalize(to, state);
+                emit_all(&mem_insts, sink, emit_info, state);
+
+                let srcloc = state.cur_srcloc();
+                if srcloc != SourceLoc::default() && !flags.notrap() {
+                    // Register the offset at which the actual store instruction starts.
+                    sink.add_trap(srcloc, TrapCode::HeapOutOfBounds);
+                }
+
+                let (opcode, funct3) = op.encoding();
+                sink.put4(enc_s(
+                    opcode,
+                    funct3,
+                    machreg_to_gpr(base),
+                    machreg_to_gpr_or_fpr(src),
+                    imm12.bits(),
+                ));
+            }
+            &Inst::Mov { rd, rm, ty } => {
+                if rd.to_reg() == rm {
+                    // Nothing to do.
+                } else if ty.is_float() {
+                    // fsgnj.d rd, rm, rm copies all 64 bits, which also
+                    // preserves the NaN-boxing of single-precision values.
+                    let rm = machreg_to_fpr(rm);
+                    sink.put4(enc_r(
+                        opcode::OP_FP,
+                        machreg_to_fpr(rd.to_reg()),
+                        0b000,
+                        rm,
+                        rm,
+                        0b0010001,
+                    ));
+                } else {
+                    // addi rd, rm, 0
+                    sink.put4(enc_i(
+                        opcode::OP_IMM,
+                        machreg_to_gpr(rd.to_reg()),
+                        0b000,
+                        machreg_to_gpr(rm),
+                        0,
+                    ));
+                }
+            }
+            &Inst::Extend {
+                rd,
+                rn,
+                signed,
+                from_bits,
+                to_bits: _,
+            } => {
+                // Values are always extended all the way to 64 bits; the upper
+                // bits of narrower destinations are undefined anyway.
+                let zbb = |alu_op| smallvec![Inst::AluRR { alu_op, rd, rs: rn }];
+                let insts: SmallVec<[Inst; 2]> = match (signed, from_bits) {
+                    (_, 64) => smallvec![Inst::gen_move(rd, rn, I64)],
+                    (true, 8) if emit_info.isa_flags.has_zbb() => zbb(AluOPRR::SextB),
+                    (true, 16) if emit_info.isa_flags.has_zbb() => zbb(AluOPRR::SextH),
+                    (false, 16) if emit_info.isa_flags.has_zbb() => zbb(AluOPRR::ZextH),
+                    (false, 32) if emit_info.isa_flags.has_zba() => smallvec![Inst::AluRRR {
+                        alu_op: AluOPRRR::AddUw,
+                        rd,
+                        rs1: rn,
+                        rs2: zero_reg(),
+                    }],
+                    (false, 1) => smallvec![Inst::AluRRImm12 {
+                        alu_op: AluOPRRI::Andi,
+                        rd,
+                        rs: rn,
+                        imm12: Imm12::maybe_from_i64(1).unwrap(),
+                    }],
+                    (false, 8) => smallvec![Inst::AluRRImm12 {
+                        alu_op: AluOPRRI::Andi,
+                        rd,
+                        rs: rn,
+                        imm12: Imm12::maybe_from_i64(255).unwrap(),
+                    }],
+                    (true, 32) => smallvec![Inst::AluRRImm12 {
+                        alu_op: AluOPRRI::Addiw,
+                        rd,
+                        rs: rn,
+                        imm12: Imm12::ZERO,
+                    }],
+                    _ => {
+                        let shift = Imm12::maybe_from_i64(64 - from_bits as i64).unwrap();
+                        smallvec![
+                            Inst::AluRRImm12 {
+                                alu_op: AluOPRRI::Slli,
+                                rd,
+                                rs: rn,
+                                imm12: shift,
+                            },
+                            Inst::AluRRImm12 {
+                                alu_op: if signed {
+                                    AluOPRRI::Srai
+                                } else {
+                                    AluOPRRI::Srli
+                                },
+                                rd,
+                                rs: rd.to_reg(),
+                                imm12: shift,
+                            }
+                        ]
+                    }
+                };
+                emit_all(&insts, sink, emit_info, state);
+            }
+            &Inst::Select { rd, cond, x, y, ty } => {
+                // beqz cond, 1f
+                // mv rd, x
+                // j 2f
+                // 1: mv rd, y
+                // 2:
+                let move_x = Inst::gen_move(rd, x, ty);
+                let move_y = Inst::gen_move(rd, y, ty);
+                let x_size = if rd.to_reg() == x { 0 } else { 4 };
+                let y_size = if rd.to_reg() == y { 0 } else { 4 };
+                sink.put4(enc_b(0b000, machreg_to_gpr(cond), 0, 4 + x_size + 4));
+                move_x.emit(sink, emit_info, state);
+                sink.put4(enc_jal(0, 4 + y_size));
+                move_y.emit(sink, emit_info, state);
+            }
+            &Inst::FcvtToInt {
+                is_sat,
+                is_signed,
+                in_ty,
+                out_ty,
+                rd,
+                rs,
+            } => {
+                let is_double = in_ty == F64;
+                let out_bits = out_ty.bits();
+                let cvt_op = match (is_signed, out_bits == 64, is_double) {
+                    (true, true, false) => FpuOPRR::FcvtLS,
+                    (false, true, false) => FpuOPRR::FcvtLuS,
+                    (true, false, false) => FpuOPRR::FcvtWS,
+                    (false, false, false) => FpuOPRR::FcvtWuS,
+                    (true, true, true) => FpuOPRR::FcvtLD,
+                    (false, true, true) => FpuOPRR::FcvtLuD,
+                    (true, false, true) => FpuOPRR::FcvtWD,
+                    (false, false, true) => FpuOPRR::FcvtWuD,
+                };
+                let feq_op = if is_double {
+                    FpuOPRRR::FeqD
+                } else {
+                    FpuOPRRR::FeqS
+                };
+                let cvt = Inst::FpuRR {
+                    alu_op: cvt_op,
+                    rd,
+                    rs,
+                };
+                let is_nan_check = Inst::FpuRRR {
+                    alu_op: feq_op,
+                    rd: wx31,
+                    rs1: rs,
+                    rs2: rs,
+                };
+
+                // The `(min, max)` range of narrow outputs, which the
+                // conversion to 32 bits does not check for itself.
+                let narrow_range = if out_bits < 32 {
+                    Some(if is_signed {
+                        (-(1i64 << (out_bits - 1)), (1i64 << (out_bits - 1)) - 1)
+                    } else {
+                        (0, (1i64 << out_bits) - 1)
+                    })
+                } else {
+                    None
+                };
+
+                if !is_sat {
+                    // feq x31, rs, rs
+                    // bnez x31, 1f
+                    // udf  ; bad conversion to integer (NaN)
+                    // 1: csrw fflags, zero
+                    // fcvt rd, rs, rtz
+                    // frflags x31
+                    // andi x31, x31, NV
+                    // beqz x31, 2f
+                    // udf  ; integer overflow
+                    // 2:
+                    is_nan_check.emit(sink, emit_info, state);
+                    emit_skip_next_if(
+                        sink,
+                        IntegerCompare {
+                            kind: IntCC::NotEqual,
+                            rs1: x31,
+                            rs2: zero_reg(),
+                        },
+                    );
+                    Inst::Udf {
+                        trap_code: TrapCode::BadConversionToInteger,
+                    }
+                    .emit(sink, emit_info, state);
+                    sink.put4(CLEAR_FFLAGS);
+                    cvt.emit(sink, emit_info, state);
+                    // csrrs x31, fflags, zero
+                    sink.put4(enc_i(opcode::SYSTEM, machreg_to_gpr(x31), 0b010, 0, 0x001));
+                    sink.put4(enc_i(
+                        opcode::OP_IMM,
+                        machreg_to_gpr(x31),
+                        0b111,
+                        machreg_to_gpr(x31),
+                        FFLAGS_NV,
+                    ));
+                    emit_skip_next_if(
+                        sink,
+                        IntegerCompare {
+                            kind: IntCC::Equal,
+                            rs1: x31,
+                            rs2: zero_reg(),
+                        },
+                    );
+                    Inst::Udf {
+                        trap_code: TrapCode::IntegerOverflow,
+                    }
+                    .emit(sink, emit_info, state);
+
+                    if narrow_range.is_some() {
+                        // Check that the result fits in the output type by
+                        // comparing it with itself truncated and re-extended.
+                        let shift = Imm12::maybe_from_i64(64 - out_bits as i64).unwrap();
+                        Inst::AluRRImm12 {
+                            alu_op: AluOPRRI::Slli,
+                            rd: wx31,
+                            rs: rd.to_reg(),
+                            imm12: shift,
+                        }
+                        .emit(sink, emit_info, state);
+                        Inst::AluRRImm12 {
+                            alu_op: if is_signed {
+                                AluOPRRI::Srai
+                            } else {
+                                AluOPRRI::Srli
+                            },
+                            rd: wx31,
+                            rs: x31,
+                            imm12: shift,
+                        }
+                        .emit(sink, emit_info, state);
+                        emit_skip_next_if(
+                            sink,
+                            IntegerCompare {
+                                kind: IntCC::Equal,
+                                rs1: x31,
+                                rs2: rd.to_reg(),
+                            },
+                        );
+                        Inst::Udf {
+                            trap_code: TrapCode::IntegerOverflow,
+                        }
+                        .emit(sink, emit_info, state);
+                    }
+                } else {
+                    // The conversion itself saturates, but converts NaN to the
+                    // maximum value; mask that to zero:
+                    //
+                    // fcvt rd, rs, rtz
+                    // feq x31, rs, rs
+                    // neg x31, x31
+                    // and rd, rd, x31
+                    cvt.emit(sink, emit_info, state);
+                    is_nan_check.emit(sink, emit_info, state);
+                    Inst::AluRRR {
+                        alu_op: AluOPRRR::Sub,
+                        rd: wx31,
+                        rs1: zero_reg(),
+                        rs2: x31,
+                    }
+                    .emit(sink, emit_info, state);
+                    Inst::AluRRR {
+                        alu_op: AluOPRRR::And,
+                        rd,
+                        rs1: rd.to_reg(),
+                        rs2: x31,
+                    }
+                    .emit(sink, emit_info, state);
+
+                    if let Some((min, max)) = narrow_range {
+                        // Clamp the result to the range of the output type,
+                        // keeping `rd` where it is within each bound and
+                        // replacing it with the bound otherwise.
+                        let mut clamps: SmallVec<[(i64, IntCC); 2]> = smallvec![(
+                            max,
+                            if is_signed {
+                                IntCC::SignedGreaterThanOrEqual
+                            } else {
+                                IntCC::UnsignedGreaterThanOrEqual
+                            }
+                        )];
+                        if is_signed {
+                            clamps.push((min, IntCC::SignedLessThanOrEqual));
+                        }
+                        for (bound, kind) in clamps {
+                            emit_all(
+                                &Inst::load_constant(wx31, bound as u64),
+                                sink,
+                                emit_info,
+                                state,
+                            );
+                            emit_skip_next_if(
+                                sink,
+                                IntegerCompare {
+                                    kind,
+                                    rs1: x31,
+                                    rs2: rd.to_reg(),
+                                },
+                            );
+                            Inst::gen_move(rd, x31, I64).emit(sink, emit_info, state);
+                        }
+                    }
+                }
+            }
+            &Inst::FloatRound { op, ty, rd, rs } => {
+                // Values whose exponent shows that they have no fractional
+                // bits (including infinities and NaNs) are returned unchanged
+                // (with NaNs quieted); all others fit in an i64 and are
+                // converted there and back with the appropriate rounding
+                // mode, then given the sign of the input so that e.g.
+                // `ceil(-0.5)` is `-0.0`:
+                //
+                // fmv.x.d x30, rs
+                // srli x31, x30, 52
+                // andi x31, x31, 0x7ff
+                // addi x31, x31, -1075
+                // blt x31, zero, 1f
+                // fmin.d rd, rs, rs
+                // j 2f
+                // 1: fcvt.l.d x31, rs, rm
+                // fcvt.d.l rd, x31, rm
+                // fmv.x.d x31, rd
+                // srli x30, x30, 63
+                // slli x30, x30, 63
+                // or x31, x31, x30
+                // fmv.d.x rd, x31
+                // 2:
+                let is_double = ty == F64;
+                let (fmv_to_int, fmv_to_float, cvt_to_int, cvt_to_float, fmin) = if is_double {
+                    (
+                        FpuOPRR::FmvXD,
+                        FpuOPRR::FmvDX,
+                        FpuOPRR::FcvtLD,
+                        FpuOPRR::FcvtDL,
+                        FpuOPRRR::FminD,
+                    )
+                } else {
+                    (
+                        FpuOPRR::FmvXW,
+                        FpuOPRR::FmvWX,
+                        FpuOPRR::FcvtLS,
+                        FpuOPRR::FcvtSL,
+                        FpuOPRRR::FminS,
+                    )
+                };
+                let (mantissa_bits, exp_mask, exp_bias) = if is_double {
+                    (52, 0x7ff, 1023 + 52)
+                } else {
+                    (23, 0xff, 127 + 23)
+                };
+                let rm = match op {
+                    FloatRoundOP::Ceil => rounding_mode::RUP,
+                    FloatRoundOP::Floor => rounding_mode::RDN,
+                    FloatRoundOP::Trunc => rounding_mode::RTZ,
+                    FloatRoundOP::Nearest => rounding_mode::RNE,
+                };
+                let enc_fpu_rr_rm = |op: FpuOPRR, rd: u32, rs: u32| {
+                    let (funct7, rs2, _) = op.encoding();
+                    enc_r(opcode::OP_FP, rd, rm, rs, rs2, funct7)
+                };
+
+                Inst::FpuRR {
+                    alu_op: fmv_to_int,
+                    rd: wx30,
+                    rs,
+                }
+                .emit(sink, emit_info, state);
+                Inst::AluRRImm12 {
+                    alu_op: AluOPRRI::Srli,
+                    rd: wx31,
+                    rs: x30,
+                    imm12: Imm12::maybe_from_i64(mantissa_bits).unwrap(),
+                }
+                .emit(sink, emit_info, state);
+                Inst::AluRRImm12 {
+                    alu_op: AluOPRRI::Andi,
+                    rd: wx31,
+                    rs: x31,
+                    imm12: Imm12::maybe_from_i64(exp_mask).unwrap(),
+                }
+                .emit(sink, emit_info, state);
+                Inst::AluRRImm12 {
+                    alu_op: AluOPRRI::Addi,
+                    rd: wx31,
+                    rs: x31,
+                    imm12: Imm12::maybe_from_i64(-exp_bias).unwrap(),
+                }
+                .emit(sink, emit_info, state);
+                // blt x31, zero, 1f
+                sink.put4(enc_b(0b100, machreg_to_gpr(x31), 0, 12));
+                Inst::FpuRRR {
+                    alu_op: fmin,
+                    rd,
+                    rs1: rs,
+                    rs2: rs,
+                }
+                .emit(sink, emit_info, state);
+                sink.put4(enc_jal(0, 4 * 8));
+                // 1:
+                sink.put4(enc_fpu_rr_rm(
+                    cvt_to_int,
+                    machreg_to_gpr(x31),
+                    machreg_to_fpr(rs),
+                ));
+                sink.put4(enc_fpu_rr_rm(
+                    cvt_to_float,
+                    machreg_to_fpr(rd.to_reg()),
+                    machreg_to_gpr(x31),
+                ));
+                Inst::FpuRR {
+                    alu_op: fmv_to_int,
+                    rd: wx31,
+                    rs: rd.to_reg(),
+                }
+                .emit(sink, emit_info, state);
+                Inst::AluRRImm12 {
+                    alu_op: AluOPRRI::Srli,
+                    rd: wx30,
+                    rs: x30,
+                    imm12: Imm12::maybe_from_i64(63).unwrap(),
+                }
+                .emit(sink, emit_info, state);
+                Inst::AluRRImm12 {
+                    alu_op: AluOPRRI::Slli,
+                    rd: wx30,
+                    rs: x30,
+                    imm12: Imm12::maybe_from_i64(ty.bits() as i64 - 1).unwrap(),
+                }
+                .emit(sink, emit_info, state);
+                Inst::AluRRR {
+                    alu_op: AluOPRRR::Or,
+                    rd: wx31,
+                    rs1: x31,
+                    rs2: x30,
+                }
+                .emit(sink, emit_info, state);
+                Inst::FpuRR {
+                    alu_op: fmv_to_float,
+                    rd,
+                    rs: x31,
+                }
+                .emit(sink, emit_info, state);
+                // 2:
+            }
+            &Inst::FloatMinMax {
+                is_max,
+                ty,
+                rd,
+                rs1,
+                rs2,
+            } => {
+                // RISC-V's `fmin`/`fmax` return the non-NaN operand if only
+                // one operand is NaN, but CLIF requires a NaN result:
+                //
+                // feq x31, rs1, rs1
+                // feq x30, rs2, rs2
+                // and x31, x31, x30
+                // beqz x31, 1f
+                // fmin rd, rs1, rs2
+                // j 2f
+                // 1: fadd rd, rs1, rs2
+                // 2:
+                let (feq, op, add) = match (ty == F64, is_max) {
+                    (false, false) => (FpuOPRRR::FeqS, FpuOPRRR::FminS, FpuOPRRR::FaddS),
+                    (false, true) => (FpuOPRRR::FeqS, FpuOPRRR::FmaxS, FpuOPRRR::FaddS),
+                    (true, false) => (FpuOPRRR::FeqD, FpuOPRRR::FminD, FpuOPRRR::FaddD),
+                    (true, true) => (FpuOPRRR::FeqD, FpuOPRRR::FmaxD, FpuOPRRR::FaddD),
+                };
+                Inst::FpuRRR {
+                    alu_op: feq,
+                    rd: wx31,
+                    rs1,
+                    rs2: rs1,
+                }
+                .emit(sink, emit_info, state);
+                Inst::FpuRRR {
+                    alu_op: feq,
+                    rd: wx30,
+                    rs1: rs2,
+                    rs2,
+                }
+                .emit(sink, emit_info, state);
+                Inst::AluRRR {
+                    alu_op: AluOPRRR::And,
+                    rd: wx31,
+                    rs1: x31,
+                    rs2: x30,
+                }
+                .emit(sink, emit_info, state);
+                sink.put4(enc_b(0b000, machreg_to_gpr(x31), 0, 12));
+                Inst::FpuRRR {
+                    alu_op: op,
+                    rd,
+                    rs1,
+                    rs2,
+                }
+                .emit(sink, emit_info, state);
+                sink.put4(enc_jal(0, 8));
+                Inst::FpuRRR {
+                    alu_op: add,
+                    rd,
+                    rs1,
+                    rs2,
+                }
+                .emit(sink, emit_info, state);
+            }
+            &Inst::Ret => {
+                // jalr zero, 0(ra)
+                sink.put4(enc_jalr(0, machreg_to_gpr(link_reg()), 0));
+            }
+            &Inst::EpiloguePlaceholder => {
+                // Noop; this is just a placeholder for epilogues.
+            }
+            &Inst::Call { ref info } => {
+                // auipc ra, 0
+                // jalr ra, 0(ra)
+                //
+                // with an `R_RISCV_CALL_PLT`-style relocation covering both.
+                if let Some(s) = state.take_stack_map() {
+                    sink.add_stack_map(StackMapExtent::UpcomingBytes(8), s);
+                }
+                let loc = state.cur_srcloc();
+                sink.add_reloc(loc, Reloc::RiscvCall, &info.dest, 0);
+                let ra = machreg_to_gpr(link_reg());
+                sink.put4(enc_auipc(ra, 0));
+                sink.put4(enc_jalr(ra, ra, 0));
+                if info.opcode.is_call() {
+                    sink.add_call_site(loc, info.opcode);
+                }
+            }
+            &Inst::CallInd { ref info } => {
+                if let Some(s) = state.take_stack_map() {
+                    sink.add_stack_map(StackMapExtent::UpcomingBytes(4), s);
+                }
+                sink.put4(enc_jalr(
+                    machreg_to_gpr(link_reg()),
+                    machreg_to_gpr(info.rn),
+                    0,
+                ));
+                let loc = state.cur_srcloc();
+                if info.opcode.is_call() {
+                    sink.add_call_site(loc, info.opcode);
+                }
+            }
+            &Inst::Jal { dest } => {
+                let off = sink.cur_offset();
+                // Indicate that the jump uses a label, if so, so that a fixup can occur later.
+                if let Some(l) = dest.as_label() {
+                    sink.use_label_at_offset(off, l, LabelUse::Jal20);
+                    sink.add_uncond_branch(off, off + 4, l);
+                }
+                // Emit the jump itself.
+                sink.put4(enc_jal(0, dest.as_offset()));
+            }
+            &Inst::CondBr {
+                taken,
+                not_taken,
+                kind,
+            } => {
+                // Conditional part first.
+                let cond_off = sink.cur_offset();
+                let enc_cond = |kind: IntegerCompare| {
+                    let (funct3, rs1, rs2) = kind.branch_funct3_and_regs();
+                    enc_b(
+                        funct3,
+                        machreg_to_gpr(rs1),
+                        machreg_to_gpr(rs2),
+                        taken.as_offset(),
+                    )
+                };
+                if let Some(l) = taken.as_label() {
+                    sink.use_label_at_offset(cond_off, l, LabelUse::B12);
+                    let inverted = enc_cond(kind.inverse()).to_le_bytes();
+                    sink.add_cond_branch(cond_off, cond_off + 4, l, &inverted[..]);
+                }
+                sink.put4(enc_cond(kind));
+
+                // Unconditional part next.
+                let uncond_off = sink.cur_offset();
+                if let Some(l) = not_taken.as_label() {
+                    sink.use_label_at_offset(uncond_off, l, LabelUse::Jal20);
+                    sink.add_uncond_branch(uncond_off, uncond_off + 4, l);
+                }
+                sink.put4(enc_jal(0, not_taken.as_offset()));
+            }
+            &Inst::JTSequence { index, ref info } => {
+                // This sequence is *one* instruction in the vcode, and is expanded only here at
+                // emission time, because we cannot allow the regalloc to insert spills/reloads in
+                // the middle; we depend on hardcoded PC-rel addressing below.
+                //
+                // li x30, <number of targets>
+                // bgeu index, x30, default
+                // auipc x31, 0
+                // slli x30, index, 2
+                // add x31, x31, x30
+                // jalr zero, 16(x31)
+                // j target0
+                // j target1
+                // ...
+                emit_all(
+                    &Inst::load_constant(wx30, info.targets.len() as u64),
+                    sink,
+                    emit_info,
+                    state,
+                );
+
+                // No need to inform the sink's branch folding logic about this branch, because it
+                // will not be merged with any other branch, flipped, or elided (it is not preceded
+                // or succeeded by any other branch). Just emit it with the label use.
+                let default_br_offset = sink.cur_offset();
+                if let BranchTarget::Label(l) = info.default_target {
+                    sink.use_label_at_offset(default_br_offset, l, LabelUse::B12);
+                }
+                sink.put4(enc_b(
+                    0b111,
+                    machreg_to_gpr(index),
+                    machreg_to_gpr(x30),
+                    info.default_target.as_offset(),
+                ));
+
+                sink.put4(enc_auipc(machreg_to_gpr(x31), 0));
+                Inst::AluRRImm12 {
+                    alu_op: AluOPRRI::Slli,
+                    rd: wx30,
+                    rs: index,
+                    imm12: Imm12::maybe_from_i64(2).unwrap(),
+                }
+                .emit(sink, emit_info, state);
+                Inst::AluRRR {
+                    alu_op: AluOPRRR::Add,
+                    rd: wx31,
+                    rs1: x31,
+                    rs2: x30,
+                }
+                .emit(sink, emit_info, state);
+                sink.put4(enc_jalr(0, machreg_to_gpr(x31), 16));
+
+                // Emit the jump table itself, as a table of jumps.
+                for &target in info.targets.iter() {
+                    let jump_off = sink.cur_offset();
+                    sink.use_label_at_offset(jump_off, target.as_label().unwrap(), LabelUse::Jal20);
+                    sink.put4(enc_jal(0, 0));
+                }
+
+                // Lowering produces an EmitIsland before using a JTSequence, so we can safely
+                // disable the worst-case-size check in this case.
+                start_off = sink.cur_offset();
+            }
+            &Inst::TrapIf { kind, trap_code } => {
+                // b<inverted cond> 1f
+                // udf
+                // 1:
+                emit_skip_next_if(sink, kind.inverse());
+                Inst::Udf { trap_code }.emit(sink, emit_info, state);
+            }
+            &Inst::Udf { trap_code } => {
+                let srcloc = state.cur_srcloc();
+                sink.add_trap(srcloc, trap_code);
+                if let Some(s) = state.take_stack_map() {
+                    sink.add_stack_map(StackMapExtent::UpcomingBytes(4), s);
+                }
+                // The all-zeros instruction is defined to be illegal.
+                sink.put4(0);
+            }
+            &Inst::EBreak => {
+                sink.put4(0x00100073);
+            }
+            &Inst::LoadExtName {
+                rd,
+                ref name,
+                offset,
+            } => {
+                // auipc rd, 0
+                // ld rd, 12(rd)
+                // j 12
+                // .8byte name + offset
+                let rd = machreg_to_gpr(rd.to_reg());
+                sink.put4(enc_auipc(rd, 0));
+                sink.put4(enc_i(opcode::LOAD, rd, 0b011, rd, 12));
+                sink.put4(enc_jal(0, 12));
+                let srcloc = state.cur_srcloc();
+                sink.add_reloc(srcloc, Reloc::Abs8, name, offset);
+                if emit_info.flags.emit_all_ones_funcaddrs() {
+                    sink.put8(u64::max_value());
+                } else {
+                    sink.put8(0);
+                }
+            }
+            &Inst::LoadAddr { rd, ref mem } => {
+                let (mem_insts, base, imm12) = mem_finalize(mem, state);
+                emit_all(&mem_insts, sink, emit_info, state);
+                if imm12.value() != 0 || base != rd.to_reg() {
+                    Inst::AluRRImm12 {
+                        alu_op: AluOPRRI::Addi,
+                        rd,
+                        rs: base,
+                        imm12,
+                    }
+                    .emit(sink, emit_info, state);
+                }
+            }
+            &Inst::VirtualSPOffsetAdj { amount } => {
+                log::trace!(
+                    "virtual sp offset adjusted by {} -> {}",
+                    amount,
+                    state.virtual_sp_offset + amount,
+                );
+                state.virtual_sp_offset += amount;
+            }
+            &Inst::Fence { pred, succ } => {
+                sink.put4(opcode::MISC_MEM | (pred as u32) << 24 | (succ as u32) << 20);
+            }
+            &Inst::Atomic { op, rd, addr, src } => {
+                let (funct5, funct3) = op.encoding();
+                add_heap_trap(sink, state);
+                sink.put4(enc_amo(
+                    funct5,
+                    funct3,
+                    machreg_to_gpr(rd.to_reg()),
+                    machreg_to_gpr(addr),
+                    machreg_to_gpr(src),
+                ));
+            }
+            &Inst::AtomicRmwLoop {
+                op,
+                ty,
+                dst,
+                addr,
+                src,
+                shift,
+                mask,
+            } => {
+                // For full-word accesses:
+                //
+                // 1: lr.{w,d}.aqrl x30, (addr)
+                // <op> x31, x30, src
+                // sc.{w,d}.aqrl x31, x31, (addr)
+                // bnez x31, 1b
+                // mv dst, x30
+                //
+                // Narrow accesses operate on the surrounding word, isolating
+                // the old value with `shift` before the operation and merging
+                // the new one back in under `mask`:
+                //
+                // 1: lr.w.aqrl x30, (addr)
+                // srl x31, x30, shift
+                // <op> x31, x31, src
+                // sll x31, x31, shift
+                // xor x31, x31, x30
+                // and x31, x31, mask
+                // xor x31, x31, x30
+                // sc.w.aqrl x31, x31, (addr)
+                // bnez x31, 1b
+                // srl dst, x30, shift
+                let is_narrow = ty.bits() < 32;
+                let funct3 = lr_sc_funct3(ty);
+                let loop_start = sink.cur_offset();
+                add_heap_trap(sink, state);
+                sink.put4(enc_amo(
+                    0b00010,
+                    funct3,
+                    machreg_to_gpr(x30),
+                    machreg_to_gpr(addr),
+                    0,
+                ));
+                let old = if is_narrow {
+                    Inst::AluRRR {
+                        alu_op: AluOPRRR::Srl,
+                        rd: wx31,
+                        rs1: x30,
+                        rs2: shift,
+                    }
+                    .emit(sink, emit_info, state);
+                    x31
+                } else {
+                    x30
+                };
+                emit_atomic_rmw_op(op, ty, old, src, sink, emit_info, state);
+                if is_narrow {
+                    let merge = [
+                        Inst::AluRRR {
+                            alu_op: AluOPRRR::Sll,
+                            rd: wx31,
+                            rs1: x31,
+                            rs2: shift,
+                        },
+                        Inst::AluRRR {
+                            alu_op: AluOPRRR::Xor,
+                            rd: wx31,
+                            rs1: x31,
+                            rs2: x30,
+                        },
+                        Inst::AluRRR {
+                            alu_op: AluOPRRR::And,
+                            rd: wx31,
+                            rs1: x31,
+                            rs2: mask,
+                        },
+                        Inst::AluRRR {
+                            alu_op: AluOPRRR::Xor,
+                            rd: wx31,
+                            rs1: x31,
+                            rs2: x30,
+                        },
+                    ];
+                    emit_all(&merge, sink, emit_info, state);
+                }
+                add_heap_trap(sink, state);
+                sink.put4(enc_amo(
+                    0b00011,
+                    funct3,
+                    machreg_to_gpr(x31),
+                    machreg_to_gpr(addr),
+                    machreg_to_gpr(x31),
+                ));
+                emit_branch_back_if(
+                    sink,
+                    IntegerCompare {
+                        kind: IntCC::NotEqual,
+                        rs1: x31,
+                        rs2: zero_reg(),
+                    },
+                    loop_start,
+                );
+                if is_narrow {
+                    Inst::AluRRR {
+                        alu_op: AluOPRRR::Srl,
+                        rd: dst,
+                        rs1: x30,
+                        rs2: shift,
+                    }
+                    .emit(sink, emit_info, state);
+                } else {
+                    Inst::gen_move(dst, x30, I64).emit(sink, emit_info, state);
+                }
+            }
+            &Inst::AtomicCas {
+                ty,
+                dst,
+                addr,
+                expected,
+                replacement,
+                shift,
+                mask,
+            } => {
+                // For full-word accesses:
+                //
+                // 1: lr.{w,d}.aqrl x30, (addr)
+                // bne x30, expected, 2f
+                // sc.{w,d}.aqrl x31, replacement, (addr)
+                // bnez x31, 1b
+                // 2: mv dst, x30
+                //
+                // For narrow accesses:
+                //
+                // 1: lr.w.aqrl x30, (addr)
+                // and x31, x30, mask
+                // srl x31, x31, shift
+                // bne x31, expected, 2f
+                // sll x31, replacement, shift
+                // xor x31, x31, x30
+                // and x31, x31, mask
+                // xor x31, x31, x30
+                // sc.w.aqrl x31, x31, (addr)
+                // bnez x31, 1b
+                // 2: srl dst, x30, shift
+                let is_narrow = ty.bits() < 32;
+                let funct3 = lr_sc_funct3(ty);
+                let loop_start = sink.cur_offset();
+                add_heap_trap(sink, state);
+                sink.put4(enc_amo(
+                    0b00010,
+                    funct3,
+                    machreg_to_gpr(x30),
+                    machreg_to_gpr(addr),
+                    0,
+                ));
+                let (old, fail_branch_off) = if is_narrow {
+                    Inst::AluRRR {
+                        alu_op: AluOPRRR::And,
+                        rd: wx31,
+                        rs1: x30,
+                        rs2: mask,
+                    }
+                    .emit(sink, emit_info, state);
+                    Inst::AluRRR {
+                        alu_op: AluOPRRR::Srl,
+                        rd: wx31,
+                        rs1: x31,
+                        rs2: shift,
+                    }
+                    .emit(sink, emit_info, state);
+                    (x31, 4 * 7)
+                } else {
+                    (x30, 4 * 3)
+                };
+                sink.put4(enc_b(
+                    0b001,
+                    machreg_to_gpr(old),
+                    machreg_to_gpr(expected),
+                    fail_branch_off,
+                ));
+                let new = if is_narrow {
+                    let merge = [
+                        Inst::AluRRR {
+                            alu_op: AluOPRRR::Sll,
+                            rd: wx31,
+                            rs1: replacement,
+                            rs2: shift,
+                        },
+                        Inst::AluRRR {
+                            alu_op: AluOPRRR::Xor,
+                            rd: wx31,
+                            rs1: x31,
+                            rs2: x30,
+                        },
+                        Inst::AluRRR {
+                            alu_op: AluOPRRR::And,
+                            rd: wx31,
+                            rs1: x31,
+                            rs2: mask,
+                        },
+                        Inst::AluRRR {
+                            alu_op: AluOPRRR::Xor,
+                            rd: wx31,
+                            rs1: x31,
+                            rs2: x30,
+                        },
+                    ];
+                    emit_all(&merge, sink, emit_info, state);
+                    x31
+                } else {
+                    replacement
+                };
+                add_heap_trap(sink, state);
+                sink.put4(enc_amo(
+                    0b00011,
+                    funct3,
+                    machreg_to_gpr(x31),
+                    machreg_to_gpr(addr),
+                    machreg_to_gpr(new),
+                ));
+                emit_branch_back_if(
+                    sink,
+                    IntegerCompare {
+                        kind: IntCC::NotEqual,
+                        rs1: x31,
+                        rs2: zero_reg(),
+                    },
+                    loop_start,
+                );
+                // 2:
+                if is_narrow {
+                    Inst::AluRRR {
+                        alu_op: AluOPRRR::Srl,
+                        rd: dst,
+                        rs1: x30,
+                        rs2: shift,
+                    }
+                    .emit(sink, emit_info, state);
+                } else {
+                    // Always emit the move, even if it is redundant, so that
+                    // the branch above has a target of known size.
+                    sink.put4(enc_i(
+                        opcode::OP_IMM,
+                        machreg_to_gpr(dst.to_reg()),
+                        0b000,
+                        machreg_to_gpr(x30),
+                        0,
+                    ));
+                }
+            }
+            &Inst::EmitIsland { needed_space } => {
+                if sink.island_needed(needed_space + 4) {
+                    let jump_around_label = sink.get_label();
+                    let jmp = Inst::Jal {
+                        dest: BranchTarget::Label(jump_around_label),
+                    };
+                    jmp.emit(sink, emit_info, state);
+                    sink.emit_island(needed_space + 4);
+                    sink.bind_label(jump_around_label);
+                }
+            }
+            &Inst::ValueLabelMarker { .. } => {
+                // Nothing; this is only used to compute debug info.
+            }
+            &Inst::Unwind { ref inst } => {
+                sink.add_unwind(inst.clone());
+            }
+        }
+
+        let end_off = sink.cur_offset();
+        debug_assert!((end_off - start_off) <= Inst::worst_case_size());
+
+        state.clear_post_insn();
+    }
+
+    fn pretty_print(&self, mb_rru: Option<&RealRegUniverse>, state: &mut EmitState) -> String {
+        self.print_with_state(mb_rru, state)
+    }
+}
+
+/// Emit the operation of an `AtomicRmwLoop`, computing the new value into
+/// x31 from the old value in `old` (x30, or x31 for narrow accesses) and the
+/// operand `src`.
+fn emit_atomic_rmw_op(
+    op: AtomicRmwOp,
+    ty: Type,
+    old: Reg,
+    src: Reg,
+    sink: &mut MachBuffer<Inst>,
+    emit_info: &EmitInfo,
+    state: &mut EmitState,
+) {
+    let x31 = spilltmp_reg();
+    let wx31 = writable_spilltmp_reg();
+    let alu = |alu_op: AluOPRRR| Inst::AluRRR {
+        alu_op,
+        rd: wx31,
+        rs1: old,
+        rs2: src,
+    };
+    match op {
+        AtomicRmwOp::Add => alu(AluOPRRR::Add).emit(sink, emit_info, state),
+        AtomicRmwOp::Sub => alu(AluOPRRR::Sub).emit(sink, emit_info, state),
+        AtomicRmwOp::And => alu(AluOPRRR::And).emit(sink, emit_info, state),
+        AtomicRmwOp::Or => alu(AluOPRRR::Or).emit(sink, emit_info, state),
+        AtomicRmwOp::Xor => alu(AluOPRRR::Xor).emit(sink, emit_info, state),
+        AtomicRmwOp::Nand => {
+            alu(AluOPRRR::And).emit(sink, emit_info, state);
+            Inst::AluRRImm12 {
+                alu_op: AluOPRRI::Xori,
+                rd: wx31,
+                rs: x31,
+                imm12: Imm12::maybe_from_i64(-1).unwrap(),
+            }
+            .emit(sink, emit_info, state);
+        }
+        AtomicRmwOp::Xchg => Inst::gen_move(wx31, src, I64).emit(sink, emit_info, state),
+        AtomicRmwOp::Smin | AtomicRmwOp::Smax | AtomicRmwOp::Umin | AtomicRmwOp::Umax => {
+            // Extend the old value as the operand has been, so that they can
+            // be compared; then keep it unless the operand wins the
+            // comparison:
+            //
+            // <extend> x31, old
+            // b<keep> x31, src, 1f
+            // mv x31, src
+            // 1:
+            let signed = match op {
+                AtomicRmwOp::Smin | AtomicRmwOp::Smax => true,
+                _ => false,
+            };
+            Inst::Extend {
+                rd: wx31,
+                rn: old,
+                signed,
+                from_bits: ty.bits() as u8,
+                to_bits: 64,
+            }
+            .emit(sink, emit_info, state);
+            let keep = match op {
+                AtomicRmwOp::Smin => IntCC::SignedLessThan,
+                AtomicRmwOp::Smax => IntCC::SignedGreaterThanOrEqual,
+                AtomicRmwOp::Umin => IntCC::UnsignedLessThan,
+                AtomicRmwOp::Umax => IntCC::UnsignedGreaterThanOrEqual,
+                _ => unreachable!(),
+            };
+            emit_skip_next_if(
+                sink,
+                IntegerCompare {
+                    kind: keep,
+                    rs1: x31,
+                    rs2: src,
+                },
+            );
+            Inst::gen_move(wx31, src, I64).emit(sink, emit_info, state);
+        }
+    }
+}
@@ -0,0 +1,2133 @@
+use crate::ir::condcodes::IntCC;
+use crate::ir::types::*;
+use crate::ir::{AtomicRmwOp, ExternalName, MemFlags, Opcode, TrapCode};
+use crate::isa::riscv64::inst::*;
+use crate::isa::riscv64::settings as riscv64_settings;
+use crate::isa::test_utils;
+use crate::isa::CallConv;
+use crate::settings;
+use crate::settings::Configurable;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+#[test]
+fn test_riscv64_binemit() {
+    let mut insns = Vec::<(Inst, &str, &str)>::new();
+
+    // N.B.: the architecture is little-endian, so when transcribing the 32-bit
+    // hex instructions from e.g. objdump disassembly, one must swap the bytes
+    // seen below. (E.g., a `ret` is normally written as the u32 `00008067`,
+    // but we write it here as 67800000.)
+    //
+    // Useful helper script to produce the encodings from the text:
+    //
+    //      #!/bin/sh
+    //      llvm-mc -triple=riscv64 -mattr=+m,+a,+f,+d,+zba,+zbb -show-encoding
+    //
+    // Then:
+    //
+    //      $ echo "add a0, a1, a2" | riscv64inst.sh
+    insns.push((Inst::Ret, "67800000", "ret"));
+    insns.push((Inst::Nop0, "", "nop-zero-len"));
+    insns.push((Inst::Nop4, "13000000", "nop"));
+    insns.push((Inst::EBreak, "73001000", "ebreak"));
+    insns.push((
+        Inst::Udf {
+            trap_code: TrapCode::Interrupt,
+        },
+        "00000000",
+        "udf",
+    ));
+    insns.push((
+        Inst::Lui {
+            rd: writable_xreg(10),
+            imm: Imm20::maybe_from_i64(0x12345).unwrap(),
+        },
+        "37553412",
+        "lui a0,74565",
+    ));
+    insns.push((
+        Inst::Lui {
+            rd: writable_xreg(11),
+            imm: Imm20::maybe_from_i64(-1).unwrap(),
+        },
+        "B7F5FFFF",
+        "lui a1,-1",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Add,
+            rd: writable_xreg(10),
+            rs1: xreg(5),
+            rs2: xreg(18),
+        },
+        "33852201",
+        "add a0,t0,s2",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Sub,
+            rd: writable_xreg(11),
+            rs1: xreg(6),
+            rs2: xreg(19),
+        },
+        "B3053341",
+        "sub a1,t1,s3",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Sll,
+            rd: writable_xreg(12),
+            rs1: xreg(7),
+            rs2: xreg(20),
+        },
+        "33964301",
+        "sll a2,t2,s4",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Slt,
+            rd: writable_xreg(13),
+            rs1: xreg(5),
+            rs2: xreg(21),
+        },
+        "B3A65201",
+        "slt a3,t0,s5",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Sltu,
+            rd: writable_xreg(14),
+            rs1: xreg(6),
+            rs2: xreg(22),
+        },
+        "33376301",
+        "sltu a4,t1,s6",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Xor,
+            rd: writable_xreg(15),
+            rs1: xreg(7),
+            rs2: xreg(23),
+        },
+        "B3C77301",
+        "xor a5,t2,s7",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Srl,
+            rd: writable_xreg(16),
+            rs1: xreg(5),
+            rs2: xreg(24),
+        },
+        "33D88201",
+        "srl a6,t0,s8",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Sra,
+            rd: writable_xreg(17),
+            rs1: xreg(6),
+            rs2: xreg(25),
+        },
+        "B3589341",
+        "sra a7,t1,s9",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Or,
+            rd: writable_xreg(10),
+            rs1: xreg(7),
+            rs2: xreg(26),
+        },
+        "33E5A301",
+        "or a0,t2,s10",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::And,
+            rd: writable_xreg(11),
+            rs1: xreg(5),
+            rs2: xreg(27),
+        },
+        "B3F5B201",
+        "and a1,t0,s11",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Addw,
+            rd: writable_xreg(12),
+            rs1: xreg(6),
+            rs2: xreg(18),
+        },
+        "3B062301",
+        "addw a2,t1,s2",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Subw,
+            rd: writable_xreg(13),
+            rs1: xreg(7),
+            rs2: xreg(19),
+        },
+        "BB863341",
+        "subw a3,t2,s3",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Sllw,
+            rd: writable_xreg(14),
+            rs1: xreg(5),
+            rs2: xreg(20),
+        },
+        "3B974201",
+        "sllw a4,t0,s4",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Srlw,
+            rd: writable_xreg(15),
+            rs1: xreg(6),
+            rs2: xreg(21),
+        },
+        "BB575301",
+        "srlw a5,t1,s5",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Sraw,
+            rd: writable_xreg(16),
+            rs1: xreg(7),
+            rs2: xreg(22),
+        },
+        "3BD86341",
+        "sraw a6,t2,s6",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Mul,
+            rd: writable_xreg(17),
+            rs1: xreg(5),
+            rs2: xreg(23),
+        },
+        "B3887203",
+        "mul a7,t0,s7",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Mulh,
+            rd: writable_xreg(10),
+            rs1: xreg(6),
+            rs2: xreg(24),
+        },
+        "33158303",
+        "mulh a0,t1,s8",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Mulhsu,
+            rd: writable_xreg(11),
+            rs1: xreg(7),
+            rs2: xreg(25),
+        },
+        "B3A59303",
+        "mulhsu a1,t2,s9",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Mulhu,
+            rd: writable_xreg(12),
+            rs1: xreg(5),
+            rs2: xreg(26),
+        },
+        "33B6A203",
+        "mulhu a2,t0,s10",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Div,
+            rd: writable_xreg(13),
+            rs1: xreg(6),
+            rs2: xreg(27),
+        },
+        "B346B303",
+        "div a3,t1,s11",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Divu,
+            rd: writable_xreg(14),
+            rs1: xreg(7),
+            rs2: xreg(18),
+        },
+        "33D72303",
+        "divu a4,t2,s2",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Rem,
+            rd: writable_xreg(15),
+            rs1: xreg(5),
+            rs2: xreg(19),
+        },
+        "B3E73203",
+        "rem a5,t0,s3",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Remu,
+            rd: writable_xreg(16),
+            rs1: xreg(6),
+            rs2: xreg(20),
+        },
+        "33784303",
+        "remu a6,t1,s4",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Mulw,
+            rd: writable_xreg(17),
+            rs1: xreg(7),
+            rs2: xreg(21),
+        },
+        "BB885303",
+        "mulw a7,t2,s5",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Divw,
+            rd: writable_xreg(10),
+            rs1: xreg(5),
+            rs2: xreg(22),
+        },
+        "3BC56203",
+        "divw a0,t0,s6",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Divuw,
+            rd: writable_xreg(11),
+            rs1: xreg(6),
+            rs2: xreg(23),
+        },
+        "BB557303",
+        "divuw a1,t1,s7",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Remw,
+            rd: writable_xreg(12),
+            rs1: xreg(7),
+            rs2: xreg(24),
+        },
+        "3BE68303",
+        "remw a2,t2,s8",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Remuw,
+            rd: writable_xreg(13),
+            rs1: xreg(5),
+            rs2: xreg(25),
+        },
+        "BBF69203",
+        "remuw a3,t0,s9",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::AddUw,
+            rd: writable_xreg(14),
+            rs1: xreg(6),
+            rs2: xreg(26),
+        },
+        "3B07A309",
+        "add.uw a4,t1,s10",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Andn,
+            rd: writable_xreg(15),
+            rs1: xreg(7),
+            rs2: xreg(27),
+        },
+        "B3F7B341",
+        "andn a5,t2,s11",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Orn,
+            rd: writable_xreg(16),
+            rs1: xreg(5),
+            rs2: xreg(18),
+        },
+        "33E82241",
+        "orn a6,t0,s2",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Xnor,
+            rd: writable_xreg(17),
+            rs1: xreg(6),
+            rs2: xreg(19),
+        },
+        "B3483341",
+        "xnor a7,t1,s3",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Max,
+            rd: writable_xreg(10),
+            rs1: xreg(7),
+            rs2: xreg(20),
+        },
+        "33E5430B",
+        "max a0,t2,s4",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Maxu,
+            rd: writable_xreg(11),
+            rs1: xreg(5),
+            rs2: xreg(21),
+        },
+        "B3F5520B",
+        "maxu a1,t0,s5",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Min,
+            rd: writable_xreg(12),
+            rs1: xreg(6),
+            rs2: xreg(22),
+        },
+        "3346630B",
+        "min a2,t1,s6",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Minu,
+            rd: writable_xreg(13),
+            rs1: xreg(7),
+            rs2: xreg(23),
+        },
+        "B3D6730B",
+        "minu a3,t2,s7",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Rol,
+            rd: writable_xreg(14),
+            rs1: xreg(5),
+            rs2: xreg(24),
+        },
+        "33978261",
+        "rol a4,t0,s8",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Rolw,
+            rd: writable_xreg(15),
+            rs1: xreg(6),
+            rs2: xreg(25),
+        },
+        "BB179361",
+        "rolw a5,t1,s9",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Ror,
+            rd: writable_xreg(16),
+            rs1: xreg(7),
+            rs2: xreg(26),
+        },
+        "33D8A361",
+        "ror a6,t2,s10",
+    ));
+    insns.push((
+        Inst::AluRRR {
+            alu_op: AluOPRRR::Rorw,
+            rd: writable_xreg(17),
+            rs1: xreg(5),
+            rs2: xreg(27),
+        },
+        "BBD8B261",
+        "rorw a7,t0,s11",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Addi,
+            rd: writable_xreg(11),
+            rs: xreg(28),
+            imm12: Imm12::maybe_from_i64(-2048).unwrap(),
+        },
+        "93050E80",
+        "addi a1,t3,-2048",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Addi,
+            rd: writable_xreg(12),
+            rs: xreg(29),
+            imm12: Imm12::maybe_from_i64(2047).unwrap(),
+        },
+        "1386FE7F",
+        "addi a2,t4,2047",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Slti,
+            rd: writable_xreg(13),
+            rs: xreg(30),
+            imm12: Imm12::maybe_from_i64(-1).unwrap(),
+        },
+        "9326FFFF",
+        "slti a3,t5,-1",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Sltiu,
+            rd: writable_xreg(14),
+            rs: xreg(31),
+            imm12: Imm12::maybe_from_i64(1).unwrap(),
+        },
+        "13B71F00",
+        "sltiu a4,t6,1",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Xori,
+            rd: writable_xreg(15),
+            rs: xreg(28),
+            imm12: Imm12::maybe_from_i64(-1).unwrap(),
+        },
+        "9347FEFF",
+        "xori a5,t3,-1",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Ori,
+            rd: writable_xreg(16),
+            rs: xreg(29),
+            imm12: Imm12::maybe_from_i64(127).unwrap(),
+        },
+        "13E8FE07",
+        "ori a6,t4,127",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Andi,
+            rd: writable_xreg(17),
+            rs: xreg(30),
+            imm12: Imm12::maybe_from_i64(255).unwrap(),
+        },
+        "9378FF0F",
+        "andi a7,t5,255",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Slli,
+            rd: writable_xreg(11),
+            rs: xreg(31),
+            imm12: Imm12::maybe_from_i64(63).unwrap(),
+        },
+        "9395FF03",
+        "slli a1,t6,63",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Srli,
+            rd: writable_xreg(12),
+            rs: xreg(28),
+            imm12: Imm12::maybe_from_i64(1).unwrap(),
+        },
+        "13561E00",
+        "srli a2,t3,1",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Srai,
+            rd: writable_xreg(13),
+            rs: xreg(29),
+            imm12: Imm12::maybe_from_i64(32).unwrap(),
+        },
+        "93D60E42",
+        "srai a3,t4,32",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Addiw,
+            rd: writable_xreg(14),
+            rs: xreg(30),
+            imm12: Imm12::maybe_from_i64(-5).unwrap(),
+        },
+        "1B07BFFF",
+        "addiw a4,t5,-5",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Slliw,
+            rd: writable_xreg(15),
+            rs: xreg(31),
+            imm12: Imm12::maybe_from_i64(31).unwrap(),
+        },
+        "9B97FF01",
+        "slliw a5,t6,31",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Srliw,
+            rd: writable_xreg(16),
+            rs: xreg(28),
+            imm12: Imm12::maybe_from_i64(7).unwrap(),
+        },
+        "1B587E00",
+        "srliw a6,t3,7",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Sraiw,
+            rd: writable_xreg(17),
+            rs: xreg(29),
+            imm12: Imm12::maybe_from_i64(16).unwrap(),
+        },
+        "9BD80E41",
+        "sraiw a7,t4,16",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Rori,
+            rd: writable_xreg(11),
+            rs: xreg(30),
+            imm12: Imm12::maybe_from_i64(17).unwrap(),
+        },
+        "93551F61",
+        "rori a1,t5,17",
+    ));
+    insns.push((
+        Inst::AluRRImm12 {
+            alu_op: AluOPRRI::Roriw,
+            rd: writable_xreg(12),
+            rs: xreg(31),
+            imm12: Imm12::maybe_from_i64(3).unwrap(),
+        },
+        "1BD63F60",
+        "roriw a2,t6,3",
+    ));
+    insns.push((
+        Inst::AluRR {
+            alu_op: AluOPRR::Clz,
+            rd: writable_xreg(10),
+            rs: xreg(18),
+        },
+        "13150960",
+        "clz a0,s2",
+    ));
+    insns.push((
+        Inst::AluRR {
+            alu_op: AluOPRR::Clzw,
+            rd: writable_xreg(11),
+            rs: xreg(19),
+        },
+        "9B950960",
+        "clzw a1,s3",
+    ));
+    insns.push((
+        Inst::AluRR {
+            alu_op: AluOPRR::Ctz,
+            rd: writable_xreg(12),
+            rs: xreg(20),
+        },
+        "13161A60",
+        "ctz a2,s4",
+    ));
+    insns.push((
+        Inst::AluRR {
+            alu_op: AluOPRR::Ctzw,
+            rd: writable_xreg(13),
+            rs: xreg(21),
+        },
+        "9B961A60",
+        "ctzw a3,s5",
+    ));
+    insns.push((
+        Inst::AluRR {
+            alu_op: AluOPRR::Cpop,
+            rd: writable_xreg(14),
+            rs: xreg(22),
+        },
+        "13172B60",
+        "cpop a4,s6",
+    ));
+    insns.push((
+        Inst::AluRR {
+            alu_op: AluOPRR::Cpopw,
+            rd: writable_xreg(15),
+            rs: xreg(23),
+        },
+        "9B972B60",
+        "cpopw a5,s7",
+    ));
+    insns.push((
+        Inst::AluRR {
+            alu_op: AluOPRR::SextB,
+            rd: writable_xreg(16),
+            rs: xreg(24),
+        },
+        "13184C60",
+        "sext.b a6,s8",
+    ));
+    insns.push((
+        Inst::AluRR {
+            alu_op: AluOPRR::SextH,
+            rd: writable_xreg(17),
+            rs: xreg(25),
+        },
+        "93985C60",
+        "sext.h a7,s9",
+    ));
+    insns.push((
+        Inst::AluRR {
+            alu_op: AluOPRR::ZextH,
+            rd: writable_xreg(10),
+            rs: xreg(26),
+        },
+        "3B450D08",
+        "zext.h a0,s10",
+    ));
+    insns.push((
+        Inst::AluRR {
+            alu_op: AluOPRR::Rev8,
+            rd: writable_xreg(11),
+            rs: xreg(27),
+        },
+        "93D58D6B",
+        "rev8 a1,s11",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FsqrtS,
+            rd: writable_freg(10),
+            rs: freg(11),
+        },
+        "53850558",
+        "fsqrt.s fa0,fa1",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FsqrtD,
+            rd: writable_freg(11),
+            rs: freg(14),
+        },
+        "D305075A",
+        "fsqrt.d fa1,fa4",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtSD,
+            rd: writable_freg(12),
+            rs: freg(17),
+        },
+        "53861840",
+        "fcvt.s.d fa2,fa7",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtDS,
+            rd: writable_freg(13),
+            rs: freg(20),
+        },
+        "D3060A42",
+        "fcvt.d.s fa3,fs4",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtWS,
+            rd: writable_xreg(14),
+            rs: freg(23),
+        },
+        "53970BC0",
+        "fcvt.w.s a4,fs7,rtz",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtWuS,
+            rd: writable_xreg(15),
+            rs: freg(26),
+        },
+        "D3171DC0",
+        "fcvt.wu.s a5,fs10,rtz",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtLS,
+            rd: writable_xreg(16),
+            rs: freg(29),
+        },
+        "53982EC0",
+        "fcvt.l.s a6,ft9,rtz",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtLuS,
+            rd: writable_xreg(17),
+            rs: freg(12),
+        },
+        "D31836C0",
+        "fcvt.lu.s a7,fa2,rtz",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtWD,
+            rd: writable_xreg(10),
+            rs: freg(15),
+        },
+        "539507C2",
+        "fcvt.w.d a0,fa5,rtz",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtWuD,
+            rd: writable_xreg(11),
+            rs: freg(18),
+        },
+        "D31519C2",
+        "fcvt.wu.d a1,fs2,rtz",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtLD,
+            rd: writable_xreg(12),
+            rs: freg(21),
+        },
+        "53962AC2",
+        "fcvt.l.d a2,fs5,rtz",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtLuD,
+            rd: writable_xreg(13),
+            rs: freg(24),
+        },
+        "D3163CC2",
+        "fcvt.lu.d a3,fs8,rtz",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtSW,
+            rd: writable_freg(14),
+            rs: xreg(27),
+        },
+        "53870DD0",
+        "fcvt.s.w fa4,s11",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtSWu,
+            rd: writable_freg(15),
+            rs: xreg(30),
+        },
+        "D3071FD0",
+        "fcvt.s.wu fa5,t5",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtSL,
+            rd: writable_freg(16),
+            rs: xreg(13),
+        },
+        "538826D0",
+        "fcvt.s.l fa6,a3",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtSLu,
+            rd: writable_freg(17),
+            rs: xreg(16),
+        },
+        "D30838D0",
+        "fcvt.s.lu fa7,a6",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtDW,
+            rd: writable_freg(10),
+            rs: xreg(19),
+        },
+        "538509D2",
+        "fcvt.d.w fa0,s3",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtDWu,
+            rd: writable_freg(11),
+            rs: xreg(22),
+        },
+        "D3051BD2",
+        "fcvt.d.wu fa1,s6",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtDL,
+            rd: writable_freg(12),
+            rs: xreg(25),
+        },
+        "53862CD2",
+        "fcvt.d.l fa2,s9",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FcvtDLu,
+            rd: writable_freg(13),
+            rs: xreg(28),
+        },
+        "D3063ED2",
+        "fcvt.d.lu fa3,t3",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FmvXW,
+            rd: writable_xreg(14),
+            rs: freg(11),
+        },
+        "538705E0",
+        "fmv.x.w a4,fa1",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FmvXD,
+            rd: writable_xreg(15),
+            rs: freg(14),
+        },
+        "D30707E2",
+        "fmv.x.d a5,fa4",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FmvWX,
+            rd: writable_freg(16),
+            rs: xreg(17),
+        },
+        "538808F0",
+        "fmv.w.x fa6,a7",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FmvDX,
+            rd: writable_freg(17),
+            rs: xreg(20),
+        },
+        "D3080AF2",
+        "fmv.d.x fa7,s4",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FclassS,
+            rd: writable_xreg(10),
+            rs: freg(23),
+        },
+        "53950BE0",
+        "fclass.s a0,fs7",
+    ));
+    insns.push((
+        Inst::FpuRR {
+            alu_op: FpuOPRR::FclassD,
+            rd: writable_xreg(11),
+            rs: freg(26),
+        },
+        "D3150DE2",
+        "fclass.d a1,fs10",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FaddS,
+            rd: writable_freg(10),
+            rs1: freg(0),
+            rs2: freg(18),
+        },
+        "53052001",
+        "fadd.s fa0,ft0,fs2",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FsubS,
+            rd: writable_freg(11),
+            rs1: freg(1),
+            rs2: freg(19),
+        },
+        "D3853009",
+        "fsub.s fa1,ft1,fs3",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FmulS,
+            rd: writable_freg(12),
+            rs1: freg(2),
+            rs2: freg(20),
+        },
+        "53064111",
+        "fmul.s fa2,ft2,fs4",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FdivS,
+            rd: writable_freg(13),
+            rs1: freg(3),
+            rs2: freg(21),
+        },
+        "D3865119",
+        "fdiv.s fa3,ft3,fs5",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FsgnjS,
+            rd: writable_freg(14),
+            rs1: freg(4),
+            rs2: freg(22),
+        },
+        "53076221",
+        "fsgnj.s fa4,ft4,fs6",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FsgnjnS,
+            rd: writable_freg(15),
+            rs1: freg(5),
+            rs2: freg(23),
+        },
+        "D3977221",
+        "fsgnjn.s fa5,ft5,fs7",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FsgnjxS,
+            rd: writable_freg(16),
+            rs1: freg(6),
+            rs2: freg(24),
+        },
+        "53288321",
+        "fsgnjx.s fa6,ft6,fs8",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FminS,
+            rd: writable_freg(17),
+            rs1: freg(7),
+            rs2: freg(25),
+        },
+        "D3889329",
+        "fmin.s fa7,ft7,fs9",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FmaxS,
+            rd: writable_freg(10),
+            rs1: freg(0),
+            rs2: freg(26),
+        },
+        "5315A029",
+        "fmax.s fa0,ft0,fs10",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FeqS,
+            rd: writable_xreg(11),
+            rs1: freg(1),
+            rs2: freg(27),
+        },
+        "D3A5B0A1",
+        "feq.s a1,ft1,fs11",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FltS,
+            rd: writable_xreg(12),
+            rs1: freg(2),
+            rs2: freg(18),
+        },
+        "531621A1",
+        "flt.s a2,ft2,fs2",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FleS,
+            rd: writable_xreg(13),
+            rs1: freg(3),
+            rs2: freg(19),
+        },
+        "D38631A1",
+        "fle.s a3,ft3,fs3",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FaddD,
+            rd: writable_freg(14),
+            rs1: freg(4),
+            rs2: freg(20),
+        },
+        "53074203",
+        "fadd.d fa4,ft4,fs4",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FsubD,
+            rd: writable_freg(15),
+            rs1: freg(5),
+            rs2: freg(21),
+        },
+        "D387520B",
+        "fsub.d fa5,ft5,fs5",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FmulD,
+            rd: writable_freg(16),
+            rs1: freg(6),
+            rs2: freg(22),
+        },
+        "53086313",
+        "fmul.d fa6,ft6,fs6",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FdivD,
+            rd: writable_freg(17),
+            rs1: freg(7),
+            rs2: freg(23),
+        },
+        "D388731B",
+        "fdiv.d fa7,ft7,fs7",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FsgnjD,
+            rd: writable_freg(10),
+            rs1: freg(0),
+            rs2: freg(24),
+        },
+        "53058023",
+        "fsgnj.d fa0,ft0,fs8",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FsgnjnD,
+            rd: writable_freg(11),
+            rs1: freg(1),
+            rs2: freg(25),
+        },
+        "D3959023",
+        "fsgnjn.d fa1,ft1,fs9",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FsgnjxD,
+            rd: writable_freg(12),
+            rs1: freg(2),
+            rs2: freg(26),
+        },
+        "5326A123",
+        "fsgnjx.d fa2,ft2,fs10",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FminD,
+            rd: writable_freg(13),
+            rs1: freg(3),
+            rs2: freg(27),
+        },
+        "D386B12B",
+        "fmin.d fa3,ft3,fs11",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FmaxD,
+            rd: writable_freg(14),
+            rs1: freg(4),
+            rs2: freg(18),
+        },
+        "5317222B",
+        "fmax.d fa4,ft4,fs2",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FeqD,
+            rd: writable_xreg(15),
+            rs1: freg(5),
+            rs2: freg(19),
+        },
+        "D3A732A3",
+        "feq.d a5,ft5,fs3",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FltD,
+            rd: writable_xreg(16),
+            rs1: freg(6),
+            rs2: freg(20),
+        },
+        "531843A3",
+        "flt.d a6,ft6,fs4",
+    ));
+    insns.push((
+        Inst::FpuRRR {
+            alu_op: FpuOPRRR::FleD,
+            rd: writable_xreg(17),
+            rs1: freg(7),
+            rs2: freg(21),
+        },
+        "D38853A3",
+        "fle.d a7,ft7,fs5",
+    ));
+    insns.push((
+        Inst::FpuRRRR {
+            alu_op: FpuOPRRRR::FmaddS,
+            rd: writable_freg(10),
+            rs1: freg(0),
+            rs2: freg(18),
+            rs3: freg(28),
+        },
+        "430520E1",
+        "fmadd.s fa0,ft0,fs2,ft8",
+    ));
+    insns.push((
+        Inst::FpuRRRR {
+            alu_op: FpuOPRRRR::FmsubS,
+            rd: writable_freg(11),
+            rs1: freg(1),
+            rs2: freg(19),
+            rs3: freg(29),
+        },
+        "C78530E9",
+        "fmsub.s fa1,ft1,fs3,ft9",
+    ));
+    insns.push((
+        Inst::FpuRRRR {
+            alu_op: FpuOPRRRR::FnmsubS,
+            rd: writable_freg(12),
+            rs1: freg(2),
+            rs2: freg(20),
+            rs3: freg(30),
+        },
+        "4B0641F1",
+        "fnmsub.s fa2,ft2,fs4,ft10",
+    ));
+    insns.push((
+        Inst::FpuRRRR {
+            alu_op: FpuOPRRRR::FnmaddS,
+            rd: writable_freg(13),
+            rs1: freg(3),
+            rs2: freg(21),
+            rs3: freg(31),
+        },
+        "CF8651F9",
+        "fnmadd.s fa3,ft3,fs5,ft11",
+    ));
+    insns.push((
+        Inst::FpuRRRR {
+            alu_op: FpuOPRRRR::FmaddD,
+            rd: writable_freg(14),
+            rs1: freg(4),
+            rs2: freg(22),
+            rs3: freg(28),
+        },
+        "430762E3",
+        "fmadd.d fa4,ft4,fs6,ft8",
+    ));
+    insns.push((
+        Inst::FpuRRRR {
+            alu_op: FpuOPRRRR::FmsubD,
+            rd: writable_freg(15),
+            rs1: freg(5),
+            rs2: freg(23),
+            rs3: freg(29),
+        },
+        "C78772EB",
+        "fmsub.d fa5,ft5,fs7,ft9",
+    ));
+    insns.push((
+        Inst::FpuRRRR {
+            alu_op: FpuOPRRRR::FnmsubD,
+            rd: writable_freg(16),
+            rs1: freg(6),
+            rs2: freg(24),
+            rs3: freg(30),
+        },
+        "4B0883F3",
+        "fnmsub.d fa6,ft6,fs8,ft10",
+    ));
+    insns.push((
+        Inst::FpuRRRR {
+            alu_op: FpuOPRRRR::FnmaddD,
+            rd: writable_freg(17),
+            rs1: freg(7),
+            rs2: freg(25),
+            rs3: freg(31),
+        },
+        "CF8893FB",
+        "fnmadd.d fa7,ft7,fs9,ft11",
+    ));
+    insns.push((
+        Inst::Load {
+            rd: writable_xreg(10),
+            op: LoadOP::Lb,
+            flags: MemFlags::new(),
+            from: AMode::RegOffset(xreg(18), -2048, I8),
+        },
+        "03050980",
+        "lb a0,-2048(s2)",
+    ));
+    insns.push((
+        Inst::Load {
+            rd: writable_xreg(11),
+            op: LoadOP::Lh,
+            flags: MemFlags::new(),
+            from: AMode::RegOffset(xreg(19), 2047, I16),
+        },
+        "8395F97F",
+        "lh a1,2047(s3)",
+    ));
+    insns.push((
+        Inst::Load {
+            rd: writable_xreg(12),
+            op: LoadOP::Lw,
+            flags: MemFlags::new(),
+            from: AMode::RegOffset(xreg(20), 8, I32),
+        },
+        "03268A00",
+        "lw a2,8(s4)",
+    ));
+    insns.push((
+        Inst::Load {
+            rd: writable_xreg(13),
+            op: LoadOP::Lbu,
+            flags: MemFlags::new(),
+            from: AMode::RegOffset(xreg(21), 0, I8),
+        },
+        "83C60A00",
+        "lbu a3,0(s5)",
+    ));
+    insns.push((
+        Inst::Load {
+            rd: writable_xreg(14),
+            op: LoadOP::Lhu,
+            flags: MemFlags::new(),
+            from: AMode::RegOffset(xreg(22), -4, I16),
+        },
+        "0357CBFF",
+        "lhu a4,-4(s6)",
+    ));
+    insns.push((
+        Inst::Load {
+            rd: writable_xreg(15),
+            op: LoadOP::Lwu,
+            flags: MemFlags::new(),
+            from: AMode::RegOffset(xreg(23), 100, I32),
+        },
+        "83E74B06",
+        "lwu a5,100(s7)",
+    ));
+    insns.push((
+        Inst::Load {
+            rd: writable_xreg(16),
+            op: LoadOP::Ld,
+            flags: MemFlags::new(),
+            from: AMode::RegOffset(xreg(24), 16, I64),
+        },
+        "03380C01",
+        "ld a6,16(s8)",
+    ));
+    insns.push((
+        Inst::Load {
+            rd: writable_freg(17),
+            op: LoadOP::Flw,
+            flags: MemFlags::new(),
+            from: AMode::RegOffset(xreg(25), 4, F32),
+        },
+        "87A84C00",
+        "flw fa7,4(s9)",
+    ));
+    insns.push((
+        Inst::Load {
+            rd: writable_freg(10),
+            op: LoadOP::Fld,
+            flags: MemFlags::new(),
+            from: AMode::RegOffset(xreg(26), 2040, F64),
+        },
+        "07358D7F",
+        "fld fa0,2040(s10)",
+    ));
+    insns.push((
+        Inst::Store {
+            to: AMode::RegOffset(xreg(5), -2048, I8),
+            op: StoreOP::Sb,
+            flags: MemFlags::new(),
+            src: xreg(10),
+        },
+        "2380A280",
+        "sb a0,-2048(t0)",
+    ));
+    insns.push((
+        Inst::Store {
+            to: AMode::RegOffset(xreg(6), 2047, I16),
+            op: StoreOP::Sh,
+            flags: MemFlags::new(),
+            src: xreg(11),
+        },
+        "A31FB37E",
+        "sh a1,2047(t1)",
+    ));
+    insns.push((
+        Inst::Store {
+            to: AMode::RegOffset(xreg(7), 8, I32),
+            op: StoreOP::Sw,
+            flags: MemFlags::new(),
+            src: xreg(12),
+        },
+        "23A4C300",
+        "sw a2,8(t2)",
+    ));
+    insns.push((
+        Inst::Store {
+            to: AMode::RegOffset(xreg(5), 0, I64),
+            op: StoreOP::Sd,
+            flags: MemFlags::new(),
+            src: xreg(13),
+        },
+        "23B0D200",
+        "sd a3,0(t0)",
+    ));
+    insns.push((
+        Inst::Store {
+            to: AMode::RegOffset(xreg(6), -4, F32),
+            op: StoreOP::Fsw,
+            flags: MemFlags::new(),
+            src: freg(14),
+        },
+        "272EE3FE",
+        "fsw fa4,-4(t1)",
+    ));
+    insns.push((
+        Inst::Store {
+            to: AMode::RegOffset(xreg(7), 1000, F64),
+            op: StoreOP::Fsd,
+            flags: MemFlags::new(),
+            src: freg(15),
+        },
+        "27B4F33E",
+        "fsd fa5,1000(t2)",
+    ));
+    insns.push((
+        Inst::Load {
+            rd: writable_xreg(10),
+            op: LoadOP::Ld,
+            flags: MemFlags::new(),
+            from: AMode::RegOffset(xreg(11), 0x12345, I64),
+        },
+        "B72F01009B8F5F34B38FBF0003B50F00",
+        "lui t6,18 ; addiw t6,t6,837 ; add t6,t6,a1 ; ld a0,0(t6)",
+    ));
+    insns.push((
+        Inst::Store {
+            to: AMode::SPOffset(-4096, I64),
+            op: StoreOP::Sd,
+            flags: MemFlags::new(),
+            src: xreg(12),
+        },
+        "B7FFFFFFB38F2F0023B0CF00",
+        "lui t6,-1 ; add t6,t6,sp ; sd a2,0(t6)",
+    ));
+    insns.push((
+        Inst::Load {
+            rd: writable_xreg(13),
+            op: LoadOP::Ld,
+            flags: MemFlags::new(),
+            from: AMode::FPOffset(16, I64),
+        },
+        "83360401",
+        "ld a3,16(fp)",
+    ));
+    insns.push((
+        Inst::Load {
+            rd: writable_xreg(14),
+            op: LoadOP::Lw,
+            flags: MemFlags::new(),
+            from: AMode::NominalSPOffset(24, I32),
+        },
+        "03278101",
+        "lw a4,24(sp)",
+    ));
+    insns.push((
+        Inst::Mov {
+            rd: writable_xreg(10),
+            rm: xreg(20),
+            ty: I64,
+        },
+        "13050A00",
+        "mv a0,s4",
+    ));
+    insns.push((
+        Inst::Mov {
+            rd: writable_freg(10),
+            rm: freg(20),
+            ty: F64,
+        },
+        "53054A23",
+        "fmv.d fa0,fs4",
+    ));
+    insns.push((
+        Inst::Mov {
+            rd: writable_freg(1),
+            rm: freg(2),
+            ty: F32,
+        },
+        "D3002122",
+        "fmv.d ft1,ft2",
+    ));
+    insns.push((
+        Inst::Extend {
+            rd: writable_xreg(10),
+            rn: xreg(11),
+            signed: false,
+            from_bits: 1,
+            to_bits: 64,
+        },
+        "13F51500",
+        "zext a0,a1,1,64",
+    ));
+    insns.push((
+        Inst::Extend {
+            rd: writable_xreg(10),
+            rn: xreg(11),
+            signed: false,
+            from_bits: 8,
+            to_bits: 64,
+        },
+        "13F5F50F",
+        "zext a0,a1,8,64",
+    ));
+    insns.push((
+        Inst::Extend {
+            rd: writable_xreg(10),
+            rn: xreg(11),
+            signed: true,
+            from_bits: 8,
+            to_bits: 64,
+        },
+        "1395850313558543",
+        "sext a0,a1,8,64",
+    ));
+    insns.push((
+        Inst::Extend {
+            rd: writable_xreg(10),
+            rn: xreg(11),
+            signed: false,
+            from_bits: 16,
+            to_bits: 64,
+        },
+        "1395050313550503",
+        "zext a0,a1,16,64",
+    ));
+    insns.push((
+        Inst::Extend {
+            rd: writable_xreg(10),
+            rn: xreg(11),
+            signed: true,
+            from_bits: 16,
+            to_bits: 64,
+        },
+        "1395050313550543",
+        "sext a0,a1,16,64",
+    ));
+    insns.push((
+        Inst::Extend {
+            rd: writable_xreg(10),
+            rn: xreg(11),
+            signed: false,
+            from_bits: 32,
+            to_bits: 64,
+        },
+        "1395050213550502",
+        "zext a0,a1,32,64",
+    ));
+    insns.push((
+        Inst::Extend {
+            rd: writable_xreg(10),
+            rn: xreg(11),
+            signed: true,
+            from_bits: 32,
+            to_bits: 64,
+        },
+        "1B850500",
+        "sext a0,a1,32,64",
+    ));
+    insns.push((
+        Inst::Extend {
+            rd: writable_xreg(10),
+            rn: xreg(11),
+            signed: true,
+            from_bits: 1,
+            to_bits: 64,
+        },
+        "1395F5031355F543",
+        "sext a0,a1,1,64",
+    ));
+    insns.push((
+        Inst::Select {
+            rd: writable_xreg(10),
+            cond: xreg(11),
+            x: xreg(12),
+            y: xreg(13),
+            ty: I64,
+        },
+        "63860500130506006F00800013850600",
+        "select a0,a2,a3##condition=a1",
+    ));
+    insns.push((
+        Inst::Select {
+            rd: writable_xreg(10),
+            cond: xreg(11),
+            x: xreg(10),
+            y: xreg(13),
+            ty: I64,
+        },
+        "638405006F00800013850600",
+        "select a0,a0,a3##condition=a1",
+    ));
+    insns.push((
+        Inst::Select {
+            rd: writable_freg(10),
+            cond: xreg(11),
+            x: freg(12),
+            y: freg(13),
+            ty: F64,
+        },
+        "638605005305C6226F0080005385D622",
+        "select fa0,fa2,fa3##condition=a1",
+    ));
+    insns.push((
+        Inst::Fence {
+            pred: 0b0011,
+            succ: 0b0011,
+        },
+        "0F003003",
+        "fence rw,rw",
+    ));
+    insns.push((
+        Inst::Fence {
+            pred: 0b0010,
+            succ: 0b0011,
+        },
+        "0F003002",
+        "fence r,rw",
+    ));
+    insns.push((
+        Inst::Fence {
+            pred: 0b0011,
+            succ: 0b0001,
+        },
+        "0F001003",
+        "fence rw,w",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmoswapW,
+            rd: writable_xreg(10),
+            addr: xreg(18),
+            src: xreg(5),
+        },
+        "2F25590E",
+        "amoswap.w.aqrl a0,t0,(s2)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmoaddW,
+            rd: writable_xreg(11),
+            addr: xreg(19),
+            src: xreg(6),
+        },
+        "AFA56906",
+        "amoadd.w.aqrl a1,t1,(s3)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmoxorW,
+            rd: writable_xreg(12),
+            addr: xreg(20),
+            src: xreg(7),
+        },
+        "2F267A26",
+        "amoxor.w.aqrl a2,t2,(s4)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmoandW,
+            rd: writable_xreg(13),
+            addr: xreg(21),
+            src: xreg(5),
+        },
+        "AFA65A66",
+        "amoand.w.aqrl a3,t0,(s5)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmoorW,
+            rd: writable_xreg(14),
+            addr: xreg(22),
+            src: xreg(6),
+        },
+        "2F276B46",
+        "amoor.w.aqrl a4,t1,(s6)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmominW,
+            rd: writable_xreg(15),
+            addr: xreg(23),
+            src: xreg(7),
+        },
+        "AFA77B86",
+        "amomin.w.aqrl a5,t2,(s7)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmomaxW,
+            rd: writable_xreg(16),
+            addr: xreg(24),
+            src: xreg(5),
+        },
+        "2F285CA6",
+        "amomax.w.aqrl a6,t0,(s8)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmominuW,
+            rd: writable_xreg(17),
+            addr: xreg(25),
+            src: xreg(6),
+        },
+        "AFA86CC6",
+        "amominu.w.aqrl a7,t1,(s9)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmomaxuW,
+            rd: writable_xreg(10),
+            addr: xreg(26),
+            src: xreg(7),
+        },
+        "2F257DE6",
+        "amomaxu.w.aqrl a0,t2,(s10)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmoswapD,
+            rd: writable_xreg(11),
+            addr: xreg(27),
+            src: xreg(5),
+        },
+        "AFB55D0E",
+        "amoswap.d.aqrl a1,t0,(s11)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmoaddD,
+            rd: writable_xreg(12),
+            addr: xreg(18),
+            src: xreg(6),
+        },
+        "2F366906",
+        "amoadd.d.aqrl a2,t1,(s2)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmoxorD,
+            rd: writable_xreg(13),
+            addr: xreg(19),
+            src: xreg(7),
+        },
+        "AFB67926",
+        "amoxor.d.aqrl a3,t2,(s3)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmoandD,
+            rd: writable_xreg(14),
+            addr: xreg(20),
+            src: xreg(5),
+        },
+        "2F375A66",
+        "amoand.d.aqrl a4,t0,(s4)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmoorD,
+            rd: writable_xreg(15),
+            addr: xreg(21),
+            src: xreg(6),
+        },
+        "AFB76A46",
+        "amoor.d.aqrl a5,t1,(s5)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmominD,
+            rd: writable_xreg(16),
+            addr: xreg(22),
+            src: xreg(7),
+        },
+        "2F387B86",
+        "amomin.d.aqrl a6,t2,(s6)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmomaxD,
+            rd: writable_xreg(17),
+            addr: xreg(23),
+            src: xreg(5),
+        },
+        "AFB85BA6",
+        "amomax.d.aqrl a7,t0,(s7)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmominuD,
+            rd: writable_xreg(10),
+            addr: xreg(24),
+            src: xreg(6),
+        },
+        "2F356CC6",
+        "amominu.d.aqrl a0,t1,(s8)",
+    ));
+    insns.push((
+        Inst::Atomic {
+            op: AtomicOP::AmomaxuD,
+            rd: writable_xreg(11),
+            addr: xreg(25),
+            src: xreg(7),
+        },
+        "AFB57CE6",
+        "amomaxu.d.aqrl a1,t2,(s9)",
+    ));
+    insns.push((
+        Inst::AtomicRmwLoop {
+            op: AtomicRmwOp::Add,
+            ty: I64,
+            dst: writable_xreg(10),
+            addr: xreg(11),
+            src: xreg(12),
+            shift: zero_reg(),
+            mask: zero_reg(),
+        },
+        "2FBF0516B30FCF00AFBFF51FE39A0FFE13050F00",
+        "atomic_rmw.i64 Add a0,(a1),a2##shift=zero mask=zero",
+    ));
+    insns.push((
+        Inst::AtomicRmwLoop {
+            op: AtomicRmwOp::Nand,
+            ty: I32,
+            dst: writable_xreg(10),
+            addr: xreg(11),
+            src: xreg(12),
+            shift: zero_reg(),
+            mask: zero_reg(),
+        },
+        "2FAF0516B37FCF0093CFFFFFAFAFF51FE3980FFE13050F00",
+        "atomic_rmw.i32 Nand a0,(a1),a2##shift=zero mask=zero",
+    ));
+    insns.push((
+        Inst::AtomicRmwLoop {
+            op: AtomicRmwOp::Umax,
+            ty: I8,
+            dst: writable_xreg(10),
+            addr: xreg(11),
+            src: xreg(12),
+            shift: xreg(13),
+            mask: xreg(14),
+        },
+        "2FAF0516B35FDF0093FFFF0F63F4CF00930F0600B39FDF00B3CFEF01B3FFEF00B3CFEF01AFAFF51FE39C0FFC3355DF00",
+        "atomic_rmw.i8 Umax a0,(a1),a2##shift=a3 mask=a4",
+    ));
+    insns.push((
+        Inst::AtomicRmwLoop {
+            op: AtomicRmwOp::Smin,
+            ty: I16,
+            dst: writable_xreg(10),
+            addr: xreg(11),
+            src: xreg(12),
+            shift: xreg(13),
+            mask: xreg(14),
+        },
+        "2FAF0516B35FDF00939F0F0393DF0F4363C4CF00930F0600B39FDF00B3CFEF01B3FFEF00B3CFEF01AFAFF51FE39A0FFC3355DF00",
+        "atomic_rmw.i16 Smin a0,(a1),a2##shift=a3 mask=a4",
+    ));
+    insns.push((
+        Inst::AtomicCas {
+            ty: I64,
+            dst: writable_xreg(10),
+            addr: xreg(11),
+            expected: xreg(12),
+            replacement: xreg(13),
+            shift: zero_reg(),
+            mask: zero_reg(),
+        },
+        "2FBF05166316CF00AFBFD51EE39A0FFE13050F00",
+        "atomic_cas.i64 a0,(a1),a2,a3##shift=zero mask=zero",
+    ));
+    insns.push((
+        Inst::AtomicCas {
+            ty: I8,
+            dst: writable_xreg(10),
+            addr: xreg(11),
+            expected: xreg(12),
+            replacement: xreg(13),
+            shift: xreg(14),
+            mask: xreg(15),
+        },
+        "2FAF0516B37FFF00B3DFEF00639ECF00B39FE600B3CFEF01B3FFFF00B3CFEF01AFAFF51FE39E0FFC3355EF00",
+        "atomic_cas.i8 a0,(a1),a2,a3##shift=a4 mask=a5",
+    ));
+    insns.push((
+        Inst::TrapIf {
+            kind: IntegerCompare {
+                kind: IntCC::UnsignedGreaterThan,
+                rs1: xreg(10),
+                rs2: xreg(11),
+            },
+            trap_code: TrapCode::HeapOutOfBounds,
+        },
+        "63F4A50000000000",
+        "bgeu a1,a0,8 ; udf",
+    ));
+    insns.push((
+        Inst::CondBr {
+            taken: BranchTarget::ResolvedOffset(64),
+            not_taken: BranchTarget::ResolvedOffset(-128),
+            kind: IntegerCompare {
+                kind: IntCC::SignedLessThan,
+                rs1: xreg(10),
+                rs2: zero_reg(),
+            },
+        },
+        "634005046FF01FF8",
+        "blt a0,zero,64 ; j -128",
+    ));
+    insns.push((
+        Inst::CondBr {
+            taken: BranchTarget::ResolvedOffset(-4096),
+            not_taken: BranchTarget::ResolvedOffset(4),
+            kind: IntegerCompare {
+                kind: IntCC::UnsignedLessThanOrEqual,
+                rs1: xreg(10),
+                rs2: xreg(11),
+            },
+        },
+        "63F0A5806F004000",
+        "bgeu a1,a0,-4096 ; j 4",
+    ));
+    insns.push((
+        Inst::Jal {
+            dest: BranchTarget::ResolvedOffset(1048574),
+        },
+        "6FF0FF7F",
+        "j 1048574",
+    ));
+    insns.push((
+        Inst::Jal {
+            dest: BranchTarget::ResolvedOffset(-1048576),
+        },
+        "6F000080",
+        "j -1048576",
+    ));
+    insns.push((
+        Inst::CallInd {
+            info: Box::new(CallIndInfo {
+                rn: xreg(10),
+                uses: Vec::new(),
+                defs: Vec::new(),
+                opcode: Opcode::CallIndirect,
+                caller_callconv: CallConv::SystemV,
+                callee_callconv: CallConv::SystemV,
+            }),
+        },
+        "E7000500",
+        "callind a0",
+    ));
+    insns.push((
+        Inst::Call {
+            info: Box::new(CallInfo {
+                dest: ExternalName::testcase("test0"),
+                uses: Vec::new(),
+                defs: Vec::new(),
+                opcode: Opcode::Call,
+                caller_callconv: CallConv::SystemV,
+                callee_callconv: CallConv::SystemV,
+            }),
+        },
+        "97000000E7800000",
+        "call %test0",
+    ));
+    insns.push((
+        Inst::LoadAddr {
+            rd: writable_xreg(10),
+            mem: AMode::RegOffset(xreg(11), -100, I64),
+        },
+        "1385C5F9",
+        "addi a0,a1,-100",
+    ));
+    insns.push((
+        Inst::LoadAddr {
+            rd: writable_xreg(10),
+            mem: AMode::SPOffset(0, I64),
+        },
+        "13050100",
+        "addi a0,sp,0",
+    ));
+    insns.push((
+        Inst::LoadConst64 {
+            rd: writable_xreg(10),
+            imm: 0x0123_4567_89ab_cdef,
+        },
+        "170500000335C5006F00C000EFCDAB8967452301",
+        "auipc a0,0 ; ld a0,12(a0) ; j 12 ; .8byte 0x123456789abcdef",
+    ));
+    insns.push((
+        Inst::FloatMinMax {
+            is_max: true,
+            ty: F64,
+            rd: writable_freg(10),
+            rs1: freg(11),
+            rs2: freg(12),
+        },
+        "D3AFB5A2532FC6A2B3FFEF0163860F005395C52A6F0080005385C502",
+        "fmax.f64 fa0,fa1,fa2",
+    ));
+    insns.push((
+        Inst::FloatRound {
+            op: FloatRoundOP::Floor,
+            ty: F32,
+            rd: writable_freg(10),
+            rs: freg(11),
+        },
+        "538F05E0935F7F0193FFFF0F938FAFF663C60F005385B5286F000002D3AF25C053A52FD0D30F05E0135FFF03131FFF01B3EFEF0153850FF0",
+        "floor.f32 fa0,fa1",
+    ));
+    insns.push((
+        Inst::FcvtToInt {
+            is_sat: false,
+            is_signed: true,
+            in_ty: F64,
+            out_ty: I32,
+            rd: writable_xreg(10),
+            rs: freg(11),
+        },
+        "D3AFB5A263940F000000000073101000539505C2F32F100093FF0F0163840F0000000000",
+        "fcvt_to_sint.i32.f64 a0,fa1",
+    ));
+    insns.push((
+        Inst::FcvtToInt {
+            is_sat: true,
+            is_signed: false,
+            in_ty: F32,
+            out_ty: I64,
+            rd: writable_xreg(10),
+            rs: freg(11),
+        },
+        "539535C0D3AFB5A0B30FF0413375F501",
+        "fcvt_to_uint_sat.i64.f32 a0,fa1",
+    ));
+
+    check_insns(insns, riscv64_settings::builder());
+}
+
+#[test]
+fn test_riscv64_binemit_bitmanip() {
+    let mut insns = Vec::<(Inst, &str, &str)>::new();
+
+    // With the Zba and Zbb extensions, extensions use single instructions.
+    insns.push((
+        Inst::Extend {
+            rd: writable_xreg(10),
+            rn: xreg(11),
+            signed: true,
+            from_bits: 8,
+            to_bits: 64,
+        },
+        "13954560",
+        "sext a0,a1,8,64",
+    ));
+    insns.push((
+        Inst::Extend {
+            rd: writable_xreg(10),
+            rn: xreg(11),
+            signed: true,
+            from_bits: 16,
+            to_bits: 64,
+        },
+        "13955560",
+        "sext a0,a1,16,64",
+    ));
+    insns.push((
+        Inst::Extend {
+            rd: writable_xreg(10),
+            rn: xreg(11),
+            signed: false,
+            from_bits: 16,
+            to_bits: 64,
+        },
+        "3BC50508",
+        "zext a0,a1,16,64",
+    ));
+    insns.push((
+        Inst::Extend {
+            rd: writable_xreg(10),
+            rn: xreg(11),
+            signed: false,
+            from_bits: 32,
+            to_bits: 64,
+        },
+        "3B850508",
+        "zext a0,a1,32,64",
+    ));
+    insns.push((
+        Inst::Extend {
+            rd: writable_xreg(10),
+            rn: xreg(11),
+            signed: false,
+            from_bits: 8,
+            to_bits: 64,
+        },
+        "13F5F50F",
+        "zext a0,a1,8,64",
+    ));
+
+    let mut isa_flag_builder = riscv64_settings::builder();
+    isa_flag_builder.enable("has_zba").unwrap();
+    isa_flag_builder.enable("has_zbb").unwrap();
+    check_insns(insns, isa_flag_builder);
+}
+
+fn check_insns(insns: Vec<(Inst, &str, &str)>, isa_flag_builder: settings::Builder) {
+    let flags = settings::Flags::new(settings::builder());
+    let isa_flags = riscv64_settings::Flags::new(&flags, isa_flag_builder);
+    let rru = create_reg_universe(&flags);
+    let emit_info = EmitInfo::new(flags, isa_flags);
+    for (insn, expected_encoding, expected_printing) in insns {
+        println!(
+            "RISC-V 64: {:?}, {}, {}",
+            insn, expected_encoding, expected_printing
+        );
+
+        // Check the printed text is as expected.
+        let actual_printing = insn.show_rru(Some(&rru));
+        assert_eq!(expected_printing, actual_printing);
+
+        let mut sink = test_utils::TestCodeSink::new();
+        let mut buffer = MachBuffer::new();
+        insn.emit(&mut buffer, &emit_info, &mut Default::default());
+        let buffer = buffer.finish();
+        buffer.emit(&mut sink);
+        let actual_encoding = &sink.stringify();
+        assert_eq!(expected_encoding, actual_encoding);
+    }
+}
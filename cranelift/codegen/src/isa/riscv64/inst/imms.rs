@@ -0,0 +1,104 @@
+//! RISC-V 64 ISA definitions: immediate constants.
+
+use regalloc::{PrettyPrint, RealRegUniverse};
+
+use std::string::String;
+
+/// A signed 12-bit immediate, as used by I-type and S-type instructions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Imm12 {
+    bits: i16,
+}
+
+impl Imm12 {
+    /// The zero immediate.
+    pub const ZERO: Imm12 = Imm12 { bits: 0 };
+
+    /// Compute an Imm12 from the given signed value, if it fits in 12 bits.
+    pub fn maybe_from_i64(value: i64) -> Option<Imm12> {
+        if value >= -2048 && value <= 2047 {
+            Some(Imm12 { bits: value as i16 })
+        } else {
+            None
+        }
+    }
+
+    /// Compute an Imm12 from the given value interpreted as a signed 64-bit
+    /// quantity, if it fits in 12 bits.
+    pub fn maybe_from_u64(value: u64) -> Option<Imm12> {
+        Self::maybe_from_i64(value as i64)
+    }
+
+    /// Returns the sign-extended value of this immediate.
+    pub fn value(&self) -> i32 {
+        self.bits as i32
+    }
+
+    /// Bits for encoding, in the low 12 bits of the result.
+    pub fn bits(&self) -> u32 {
+        (self.bits as u32) & 0xfff
+    }
+}
+
+/// A 20-bit immediate, as used by U-type instructions (`lui` and `auipc`).
+/// The value is stored sign-extended, i.e. it is the value that ends up in
+/// bits 31:12 of the destination.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Imm20 {
+    bits: i32,
+}
+
+impl Imm20 {
+    /// Compute an Imm20 from the given signed value, if it fits in 20 bits.
+    pub fn maybe_from_i64(value: i64) -> Option<Imm20> {
+        if value >= -(1 << 19) && value < (1 << 19) {
+            Some(Imm20 { bits: value as i32 })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the sign-extended value of this immediate.
+    pub fn value(&self) -> i32 {
+        self.bits
+    }
+
+    /// Bits for encoding, in the low 20 bits of the result.
+    pub fn bits(&self) -> u32 {
+        (self.bits as u32) & 0xfffff
+    }
+}
+
+impl PrettyPrint for Imm12 {
+    fn show_rru(&self, _mb_rru: Option<&RealRegUniverse>) -> String {
+        format!("{}", self.bits)
+    }
+}
+
+impl PrettyPrint for Imm20 {
+    fn show_rru(&self, _mb_rru: Option<&RealRegUniverse>) -> String {
+        format!("{}", self.bits)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn imm12_range() {
+        assert_eq!(Imm12::maybe_from_i64(2047).unwrap().bits(), 0x7ff);
+        assert_eq!(Imm12::maybe_from_i64(-2048).unwrap().bits(), 0x800);
+        assert_eq!(Imm12::maybe_from_i64(-1).unwrap().bits(), 0xfff);
+        assert!(Imm12::maybe_from_i64(2048).is_none());
+        assert!(Imm12::maybe_from_i64(-2049).is_none());
+        assert_eq!(Imm12::maybe_from_u64(u64::MAX).unwrap().value(), -1);
+    }
+
+    #[test]
+    fn imm20_range() {
+        assert_eq!(Imm20::maybe_from_i64(0x7ffff).unwrap().bits(), 0x7ffff);
+        assert_eq!(Imm20::maybe_from_i64(-1).unwrap().bits(), 0xfffff);
+        assert!(Imm20::maybe_from_i64(0x80000).is_none());
+    }
+}
@@ -0,0 +1,1161 @@
+//! This module defines riscv64-specific machine instruction types.
+
+// Some variants are not constructed, but we still want them as options in the future.
+#![allow(dead_code)]
+
+use crate::binemit::{Addend, CodeOffset, Reloc};
+use crate::ir::condcodes::IntCC;
+use crate::ir::types::{
+    B1, B128, B16, B32, B64, B8, F32, F64, FFLAGS, I128, I16, I32, I64, I8, IFLAGS, R32, R64,
+};
+use crate::ir::{ExternalName, MemFlags, Opcode, SourceLoc, Type, ValueLabel};
+use crate::isa::CallConv;
+use crate::machinst::*;
+use crate::{settings, CodegenError, CodegenResult};
+
+use regalloc::RegUsageCollector;
+use regalloc::{PrettyPrint, RealRegUniverse, Reg, RegClass, Writable};
+
+use alloc::vec::Vec;
+use smallvec::{smallvec, SmallVec};
+use std::string::{String, ToString};
+
+pub mod regs;
+pub use self::regs::*;
+pub mod imms;
+pub use self::imms::*;
+pub mod args;
+pub use self::args::*;
+pub mod emit;
+pub use self::emit::*;
+
+pub mod unwind;
+
+#[cfg(test)]
+mod emit_tests;
+
+//=============================================================================
+// Instructions (top level): definition
+
+pub use crate::isa::riscv64::lower::isle::generated_code::{
+    AluOPRR, AluOPRRI, AluOPRRR, AtomicOP, FloatRoundOP, FpuOPRR, FpuOPRRR, FpuOPRRRR, LoadOP,
+    MInst as Inst, StoreOP,
+};
+
+/// Additional information for (direct) Call instructions, left out of line to lower the size of
+/// the Inst enum.
+#[derive(Clone, Debug)]
+pub struct CallInfo {
+    pub dest: ExternalName,
+    pub uses: Vec<Reg>,
+    pub defs: Vec<Writable<Reg>>,
+    pub opcode: Opcode,
+    pub caller_callconv: CallConv,
+    pub callee_callconv: CallConv,
+}
+
+/// Additional information for CallInd instructions, left out of line to lower the size of the Inst
+/// enum.
+#[derive(Clone, Debug)]
+pub struct CallIndInfo {
+    pub rn: Reg,
+    pub uses: Vec<Reg>,
+    pub defs: Vec<Writable<Reg>>,
+    pub opcode: Opcode,
+    pub caller_callconv: CallConv,
+    pub callee_callconv: CallConv,
+}
+
+/// Additional information for JTSequence instructions, left out of line to lower the size of the Inst
+/// enum.
+#[derive(Clone, Debug)]
+pub struct JTSequenceInfo {
+    pub targets: Vec<BranchTarget>,
+    pub default_target: BranchTarget,
+    pub targets_for_term: Vec<MachLabel>, // needed for MachTerminator.
+}
+
+#[test]
+fn inst_size_test() {
+    // This test will help with unintentionally growing the size
+    // of the Inst enum.
+    assert_eq!(32, std::mem::size_of::<Inst>());
+}
+
+impl Inst {
+    /// Create an instruction sequence that loads a constant into a register,
+    /// using one or two instructions where possible and an inline 64-bit
+    /// literal otherwise.
+    pub fn load_constant(rd: Writable<Reg>, value: u64) -> SmallVec<[Inst; 4]> {
+        let value = value as i64;
+        if let Some(imm12) = Imm12::maybe_from_i64(value) {
+            // addi rd, zero, imm
+            smallvec![Inst::AluRRImm12 {
+                alu_op: AluOPRRI::Addi,
+                rd,
+                rs: zero_reg(),
+                imm12,
+            }]
+        } else if value == value as i32 as i64 {
+            // lui rd, hi20
+            // addiw rd, rd, lo12
+            //
+            // The upper part is rounded so that the lower part is a signed
+            // 12-bit value. For values just below 2^31 the upper part wraps
+            // around to -2^19; `addiw` then wraps the result back to the
+            // correct (positive) 32-bit value.
+            let hi = ((value + 0x800) >> 12) as i32;
+            let lo = value - ((hi as i64) << 12);
+            let hi = (hi << 12) >> 12;
+            let mut insts = smallvec![Inst::Lui {
+                rd,
+                imm: Imm20::maybe_from_i64(hi as i64).unwrap(),
+            }];
+            if lo != 0 {
+                insts.push(Inst::AluRRImm12 {
+                    alu_op: AluOPRRI::Addiw,
+                    rd,
+                    rs: rd.to_reg(),
+                    imm12: Imm12::maybe_from_i64(lo).unwrap(),
+                });
+            }
+            insts
+        } else {
+            smallvec![Inst::LoadConst64 {
+                rd,
+                imm: value as u64,
+            }]
+        }
+    }
+
+    /// Create instructions that load a 128-bit constant.
+    pub fn load_constant128(to_regs: ValueRegs<Writable<Reg>>, value: u128) -> SmallVec<[Inst; 4]> {
+        assert_eq!(to_regs.len(), 2, "Expected to load i128 into two registers");
+
+        let lower = value as u64;
+        let upper = (value >> 64) as u64;
+
+        let lower_reg = to_regs.regs()[0];
+        let upper_reg = to_regs.regs()[1];
+
+        let mut load_ins = Inst::load_constant(lower_reg, lower);
+        let load_upper = Inst::load_constant(upper_reg, upper);
+
+        load_ins.extend(load_upper.into_iter());
+        load_ins
+    }
+
+    /// Create instructions that load a floating-point constant, through an
+    /// integer temporary.
+    pub fn load_fp_constant<F: FnMut(Type) -> Writable<Reg>>(
+        rd: Writable<Reg>,
+        ty: Type,
+        bits: u64,
+        mut alloc_tmp: F,
+    ) -> SmallVec<[Inst; 4]> {
+        let tmp = alloc_tmp(I64);
+        let mut insts = Inst::load_constant(tmp, bits);
+        insts.push(Inst::FpuRR {
+            alu_op: if ty == F32 {
+                FpuOPRR::FmvWX
+            } else {
+                FpuOPRR::FmvDX
+            },
+            rd,
+            rs: tmp.to_reg(),
+        });
+        insts
+    }
+
+    /// Generic constructor for a load (zero-extending where appropriate).
+    pub fn gen_load(into_reg: Writable<Reg>, mem: AMode, ty: Type, flags: MemFlags) -> Inst {
+        Inst::Load {
+            rd: into_reg,
+            op: LoadOP::from_type(ty),
+            flags,
+            from: mem,
+        }
+    }
+
+    /// Generic constructor for a store.
+    pub fn gen_store(mem: AMode, from_reg: Reg, ty: Type, flags: MemFlags) -> Inst {
+        Inst::Store {
+            to: mem,
+            op: StoreOP::from_type(ty),
+            flags,
+            src: from_reg,
+        }
+    }
+}
+
+//=============================================================================
+// Instructions: get_regs
+
+fn memarg_regs(memarg: &AMode, collector: &mut RegUsageCollector) {
+    match memarg {
+        &AMode::RegOffset(reg, ..) => {
+            collector.add_use(reg);
+        }
+        &AMode::FPOffset(..) => {
+            collector.add_use(fp_reg());
+        }
+        &AMode::SPOffset(..) | &AMode::NominalSPOffset(..) => {
+            collector.add_use(stack_reg());
+        }
+    }
+}
+
+fn riscv64_get_regs(inst: &Inst, collector: &mut RegUsageCollector) {
+    match inst {
+        &Inst::Nop0 | &Inst::Nop4 => {}
+        &Inst::Lui { rd, .. } | &Inst::LoadConst64 { rd, .. } => {
+            collector.add_def(rd);
+        }
+        &Inst::AluRRR { rd, rs1, rs2, .. } | &Inst::FpuRRR { rd, rs1, rs2, .. } => {
+            collector.add_def(rd);
+            collector.add_use(rs1);
+            collector.add_use(rs2);
+        }
+        &Inst::AluRRImm12 { rd, rs, .. }
+        | &Inst::AluRR { rd, rs, .. }
+        | &Inst::FpuRR { rd, rs, .. } => {
+            collector.add_def(rd);
+            collector.add_use(rs);
+        }
+        &Inst::FpuRRRR {
+            rd, rs1, rs2, rs3, ..
+        } => {
+            collector.add_def(rd);
+            collector.add_use(rs1);
+            collector.add_use(rs2);
+            collector.add_use(rs3);
+        }
+        &Inst::Load { rd, ref from, .. } => {
+            collector.add_def(rd);
+            memarg_regs(from, collector);
+        }
+        &Inst::Store { ref to, src, .. } => {
+            collector.add_use(src);
+            memarg_regs(to, collector);
+        }
+        &Inst::Mov { rd, rm, .. } => {
+            collector.add_def(rd);
+            collector.add_use(rm);
+        }
+        &Inst::Extend { rd, rn, .. } => {
+            collector.add_def(rd);
+            collector.add_use(rn);
+        }
+        &Inst::Select { rd, cond, x, y, .. } => {
+            collector.add_def(rd);
+            collector.add_use(cond);
+            collector.add_use(x);
+            collector.add_use(y);
+        }
+        &Inst::FcvtToInt { rd, rs, .. } | &Inst::FloatRound { rd, rs, .. } => {
+            collector.add_def(rd);
+            collector.add_use(rs);
+        }
+        &Inst::FloatMinMax { rd, rs1, rs2, .. } => {
+            collector.add_def(rd);
+            collector.add_use(rs1);
+            collector.add_use(rs2);
+        }
+        &Inst::Ret | &Inst::EpiloguePlaceholder => {}
+        &Inst::Call { ref info, .. } => {
+            collector.add_uses(&*info.uses);
+            collector.add_defs(&*info.defs);
+        }
+        &Inst::CallInd { ref info, .. } => {
+            collector.add_uses(&*info.uses);
+            collector.add_defs(&*info.defs);
+            collector.add_use(info.rn);
+        }
+        &Inst::Jal { .. } => {}
+        &Inst::CondBr { kind, .. } | &Inst::TrapIf { kind, .. } => {
+            collector.add_use(kind.rs1);
+            collector.add_use(kind.rs2);
+        }
+        &Inst::JTSequence { index, .. } => {
+            collector.add_use(index);
+        }
+        &Inst::Udf { .. } | &Inst::EBreak => {}
+        &Inst::LoadExtName { rd, .. } => {
+            collector.add_def(rd);
+        }
+        &Inst::LoadAddr { rd, ref mem } => {
+            collector.add_def(rd);
+            memarg_regs(mem, collector);
+        }
+        &Inst::VirtualSPOffsetAdj { .. } | &Inst::Fence { .. } => {}
+        &Inst::Atomic { rd, addr, src, .. } => {
+            collector.add_def(rd);
+            collector.add_use(addr);
+            collector.add_use(src);
+        }
+        &Inst::AtomicRmwLoop {
+            dst,
+            addr,
+            src,
+            shift,
+            mask,
+            ..
+        } => {
+            collector.add_def(dst);
+            collector.add_use(addr);
+            collector.add_use(src);
+            collector.add_use(shift);
+            collector.add_use(mask);
+        }
+        &Inst::AtomicCas {
+            dst,
+            addr,
+            expected,
+            replacement,
+            shift,
+            mask,
+            ..
+        } => {
+            collector.add_def(dst);
+            collector.add_use(addr);
+            collector.add_use(expected);
+            collector.add_use(replacement);
+            collector.add_use(shift);
+            collector.add_use(mask);
+        }
+        &Inst::EmitIsland { .. } => {}
+        &Inst::ValueLabelMarker { reg, .. } => {
+            collector.add_use(reg);
+        }
+        &Inst::Unwind { .. } => {}
+    }
+}
+
+//=============================================================================
+// Instructions: map_regs
+
+pub fn riscv64_map_regs<RM: RegMapper>(inst: &mut Inst, mapper: &RM) {
+    fn map_mem<RM: RegMapper>(m: &RM, mem: &mut AMode) {
+        // N.B.: we take only the pre-map here, but this is OK because the
+        // only addressing modes that update registers (none here) would
+        // require a mod operand.
+        match mem {
+            &mut AMode::RegOffset(ref mut reg, ..) => m.map_use(reg),
+            &mut AMode::SPOffset(..)
+            | &mut AMode::FPOffset(..)
+            | &mut AMode::NominalSPOffset(..) => {}
+        };
+    }
+
+    fn map_compare<RM: RegMapper>(m: &RM, kind: &mut IntegerCompare) {
+        m.map_use(&mut kind.rs1);
+        m.map_use(&mut kind.rs2);
+    }
+
+    match inst {
+        &mut Inst::Nop0 | &mut Inst::Nop4 => {}
+        &mut Inst::Lui { ref mut rd, .. } | &mut Inst::LoadConst64 { ref mut rd, .. } => {
+            mapper.map_def(rd);
+        }
+        &mut Inst::AluRRR {
+            ref mut rd,
+            ref mut rs1,
+            ref mut rs2,
+            ..
+        }
+        | &mut Inst::FpuRRR {
+            ref mut rd,
+            ref mut rs1,
+            ref mut rs2,
+            ..
+        }
+        | &mut Inst::FloatMinMax {
+            ref mut rd,
+            ref mut rs1,
+            ref mut rs2,
+            ..
+        } => {
+            mapper.map_def(rd);
+            mapper.map_use(rs1);
+            mapper.map_use(rs2);
+        }
+        &mut Inst::AluRRImm12 {
+            ref mut rd,
+            ref mut rs,
+            ..
+        }
+        | &mut Inst::AluRR {
+            ref mut rd,
+            ref mut rs,
+            ..
+        }
+        | &mut Inst::FpuRR {
+            ref mut rd,
+            ref mut rs,
+            ..
+        }
+        | &mut Inst::FcvtToInt {
+            ref mut rd,
+            ref mut rs,
+            ..
+        }
+        | &mut Inst::FloatRound {
+            ref mut rd,
+            ref mut rs,
+            ..
+        } => {
+            mapper.map_def(rd);
+            mapper.map_use(rs);
+        }
+        &mut Inst::FpuRRRR {
+            ref mut rd,
+            ref mut rs1,
+            ref mut rs2,
+            ref mut rs3,
+            ..
+        } => {
+            mapper.map_def(rd);
+            mapper.map_use(rs1);
+            mapper.map_use(rs2);
+            mapper.map_use(rs3);
+        }
+        &mut Inst::Load {
+            ref mut rd,
+            ref mut from,
+            ..
+        } => {
+            mapper.map_def(rd);
+            map_mem(mapper, from);
+        }
+        &mut Inst::Store {
+            ref mut to,
+            ref mut src,
+            ..
+        } => {
+            mapper.map_use(src);
+            map_mem(mapper, to);
+        }
+        &mut Inst::Mov {
+            ref mut rd,
+            ref mut rm,
+            ..
+        } => {
+            mapper.map_def(rd);
+            mapper.map_use(rm);
+        }
+        &mut Inst::Extend {
+            ref mut rd,
+            ref mut rn,
+            ..
+        } => {
+            mapper.map_def(rd);
+            mapper.map_use(rn);
+        }
+        &mut Inst::Select {
+            ref mut rd,
+            ref mut cond,
+            ref mut x,
+            ref mut y,
+            ..
+        } => {
+            mapper.map_def(rd);
+            mapper.map_use(cond);
+            mapper.map_use(x);
+            mapper.map_use(y);
+        }
+        &mut Inst::Ret | &mut Inst::EpiloguePlaceholder => {}
+        &mut Inst::Call { ref mut info } => {
+            for r in info.uses.iter_mut() {
+                mapper.map_use(r);
+            }
+            for r in info.defs.iter_mut() {
+                mapper.map_def(r);
+            }
+        }
+        &mut Inst::CallInd { ref mut info, .. } => {
+            for r in info.uses.iter_mut() {
+                mapper.map_use(r);
+            }
+            for r in info.defs.iter_mut() {
+                mapper.map_def(r);
+            }
+            mapper.map_use(&mut info.rn);
+        }
+        &mut Inst::Jal { .. } => {}
+        &mut Inst::CondBr { ref mut kind, .. } | &mut Inst::TrapIf { ref mut kind, .. } => {
+            map_compare(mapper, kind);
+        }
+        &mut Inst::JTSequence { ref mut index, .. } => {
+            mapper.map_use(index);
+        }
+        &mut Inst::Udf { .. } | &mut Inst::EBreak => {}
+        &mut Inst::LoadExtName { ref mut rd, .. } => {
+            mapper.map_def(rd);
+        }
+        &mut Inst::LoadAddr {
+            ref mut rd,
+            ref mut mem,
+        } => {
+            mapper.map_def(rd);
+            map_mem(mapper, mem);
+        }
+        &mut Inst::VirtualSPOffsetAdj { .. } | &mut Inst::Fence { .. } => {}
+        &mut Inst::Atomic {
+            ref mut rd,
+            ref mut addr,
+            ref mut src,
+            ..
+        } => {
+            mapper.map_def(rd);
+            mapper.map_use(addr);
+            mapper.map_use(src);
+        }
+        &mut Inst::AtomicRmwLoop {
+            ref mut dst,
+            ref mut addr,
+            ref mut src,
+            ref mut shift,
+            ref mut mask,
+            ..
+        } => {
+            mapper.map_def(dst);
+            mapper.map_use(addr);
+            mapper.map_use(src);
+            mapper.map_use(shift);
+            mapper.map_use(mask);
+        }
+        &mut Inst::AtomicCas {
+            ref mut dst,
+            ref mut addr,
+            ref mut expected,
+            ref mut replacement,
+            ref mut shift,
+            ref mut mask,
+            ..
+        } => {
+            mapper.map_def(dst);
+            mapper.map_use(addr);
+            mapper.map_use(expected);
+            mapper.map_use(replacement);
+            mapper.map_use(shift);
+            mapper.map_use(mask);
+        }
+        &mut Inst::EmitIsland { .. } => {}
+        &mut Inst::ValueLabelMarker { ref mut reg, .. } => {
+            mapper.map_use(reg);
+        }
+        &mut Inst::Unwind { .. } => {}
+    }
+}
+
+//=============================================================================
+// Instructions: misc functions and external interface
+
+impl MachInst for Inst {
+    type LabelUse = LabelUse;
+
+    fn get_regs(&self, collector: &mut RegUsageCollector) {
+        riscv64_get_regs(self, collector)
+    }
+
+    fn map_regs<RM: RegMapper>(&mut self, mapper: &RM) {
+        riscv64_map_regs(self, mapper);
+    }
+
+    fn is_move(&self) -> Option<(Writable<Reg>, Reg)> {
+        match self {
+            &Inst::Mov { rd, rm, .. } => Some((rd, rm)),
+            _ => None,
+        }
+    }
+
+    fn is_epilogue_placeholder(&self) -> bool {
+        if let Inst::EpiloguePlaceholder = self {
+            true
+        } else {
+            false
+        }
+    }
+
+    fn is_included_in_clobbers(&self) -> bool {
+        // We exclude call instructions from the clobber-set when they are calls
+        // from caller to callee with the same ABI. Such calls cannot possibly
+        // force any new registers to be saved in the prologue, because anything
+        // that the callee clobbers, the caller is also allowed to clobber.
+        match self {
+            &Inst::Call { ref info } => info.caller_callconv != info.callee_callconv,
+            &Inst::CallInd { ref info } => info.caller_callconv != info.callee_callconv,
+            _ => true,
+        }
+    }
+
+    fn is_term<'a>(&'a self) -> MachTerminator<'a> {
+        match self {
+            &Inst::Ret | &Inst::EpiloguePlaceholder => MachTerminator::Ret,
+            &Inst::Jal { dest } => MachTerminator::Uncond(dest.as_label().unwrap()),
+            &Inst::CondBr {
+                taken, not_taken, ..
+            } => MachTerminator::Cond(taken.as_label().unwrap(), not_taken.as_label().unwrap()),
+            &Inst::JTSequence { ref info, .. } => {
+                MachTerminator::Indirect(&info.targets_for_term[..])
+            }
+            _ => MachTerminator::None,
+        }
+    }
+
+    fn gen_move(to_reg: Writable<Reg>, from_reg: Reg, ty: Type) -> Inst {
+        assert!(ty.bits() <= 64);
+        assert!(to_reg.to_reg().get_class() == from_reg.get_class());
+        Inst::Mov {
+            rd: to_reg,
+            rm: from_reg,
+            ty,
+        }
+    }
+
+    fn gen_constant<F: FnMut(Type) -> Writable<Reg>>(
+        to_regs: ValueRegs<Writable<Reg>>,
+        value: u128,
+        ty: Type,
+        alloc_tmp: F,
+    ) -> SmallVec<[Inst; 4]> {
+        let to_reg = to_regs.only_reg();
+        match ty {
+            F64 => Inst::load_fp_constant(to_reg.unwrap(), ty, value as u64, alloc_tmp),
+            F32 => Inst::load_fp_constant(to_reg.unwrap(), ty, value as u32 as u64, alloc_tmp),
+            B1 | B8 | B16 | B32 | B64 | I8 | I16 | I32 | I64 | R32 | R64 => {
+                Inst::load_constant(to_reg.unwrap(), value as u64)
+            }
+            I128 => Inst::load_constant128(to_regs, value),
+            _ => panic!("Cannot generate constant for type: {}", ty),
+        }
+    }
+
+    fn gen_nop(preferred_size: usize) -> Inst {
+        if preferred_size == 0 {
+            return Inst::Nop0;
+        }
+        // We can't give a NOP (or any insn) < 4 bytes.
+        assert!(preferred_size >= 4);
+        Inst::Nop4
+    }
+
+    fn rc_for_type(ty: Type) -> CodegenResult<(&'static [RegClass], &'static [Type])> {
+        match ty {
+            I8 => Ok((&[RegClass::I64], &[I8])),
+            I16 => Ok((&[RegClass::I64], &[I16])),
+            I32 => Ok((&[RegClass::I64], &[I32])),
+            I64 => Ok((&[RegClass::I64], &[I64])),
+            B1 => Ok((&[RegClass::I64], &[B1])),
+            B8 => Ok((&[RegClass::I64], &[B8])),
+            B16 => Ok((&[RegClass::I64], &[B16])),
+            B32 => Ok((&[RegClass::I64], &[B32])),
+            B64 => Ok((&[RegClass::I64], &[B64])),
+            R32 => panic!("32-bit reftype pointer should never be seen on riscv64"),
+            R64 => Ok((&[RegClass::I64], &[R64])),
+            F32 => Ok((&[RegClass::F64], &[F32])),
+            F64 => Ok((&[RegClass::F64], &[F64])),
+            I128 => Ok((&[RegClass::I64, RegClass::I64], &[I64, I64])),
+            B128 => Ok((&[RegClass::I64, RegClass::I64], &[B64, B64])),
+            IFLAGS | FFLAGS => Ok((&[RegClass::I64], &[I64])),
+            _ => Err(CodegenError::Unsupported(format!(
+                "Unexpected SSA-value type: {}",
+                ty
+            ))),
+        }
+    }
+
+    fn gen_jump(target: MachLabel) -> Inst {
+        Inst::Jal {
+            dest: BranchTarget::Label(target),
+        }
+    }
+
+    fn reg_universe(flags: &settings::Flags) -> RealRegUniverse {
+        create_reg_universe(flags)
+    }
+
+    fn worst_case_size() -> CodeOffset {
+        // The maximum size, in bytes, of any `Inst`'s emitted code. The
+        // longest sequence is the 14-instruction expansion of `FloatRound`.
+        //
+        // Note that inline jump-tables handle island/pool insertion separately, so we do not need
+        // to account for them here (otherwise the worst case would be 2^31 * 4, clearly not
+        // feasible for other reasons).
+        56
+    }
+
+    fn gen_value_label_marker(label: ValueLabel, reg: Reg) -> Self {
+        Inst::ValueLabelMarker { label, reg }
+    }
+
+    fn defines_value_label(&self) -> Option<(ValueLabel, Reg)> {
+        match self {
+            Inst::ValueLabelMarker { label, reg } => Some((*label, *reg)),
+            _ => None,
+        }
+    }
+}
+
+//=============================================================================
+// Pretty-printing of instructions.
+
+fn mem_finalize_for_show(
+    mem: &AMode,
+    mb_rru: Option<&RealRegUniverse>,
+    state: &EmitState,
+) -> (String, String) {
+    let (mem_insts, base, imm12) = mem_finalize(mem, state);
+    let mut mem_str = mem_insts
+        .into_iter()
+        .map(|inst| inst.show_rru(mb_rru))
+        .collect::<Vec<_>>()
+        .join(" ; ");
+    if !mem_str.is_empty() {
+        mem_str += " ; ";
+    }
+
+    (
+        mem_str,
+        format!("{}({})", imm12.value(), base.show_rru(mb_rru)),
+    )
+}
+
+impl PrettyPrint for Inst {
+    fn show_rru(&self, mb_rru: Option<&RealRegUniverse>) -> String {
+        self.pretty_print(mb_rru, &mut EmitState::default())
+    }
+}
+
+impl Inst {
+    fn print_with_state(&self, mb_rru: Option<&RealRegUniverse>, state: &mut EmitState) -> String {
+        let show = |r: Reg| r.show_rru(mb_rru);
+        let show_w = |r: Writable<Reg>| r.to_reg().show_rru(mb_rru);
+
+        match self {
+            &Inst::Nop0 => "nop-zero-len".to_string(),
+            &Inst::Nop4 => "nop".to_string(),
+            &Inst::Lui { rd, ref imm } => format!("lui {},{}", show_w(rd), imm.show_rru(mb_rru)),
+            &Inst::LoadConst64 { rd, imm } => format!(
+                "auipc {},0 ; ld {},12({}) ; j 12 ; .8byte 0x{:x}",
+                show_w(rd),
+                show_w(rd),
+                show_w(rd),
+                imm
+            ),
+            &Inst::AluRRR {
+                alu_op,
+                rd,
+                rs1,
+                rs2,
+            } => format!(
+                "{} {},{},{}",
+                alu_op.op_name(),
+                show_w(rd),
+                show(rs1),
+                show(rs2)
+            ),
+            &Inst::AluRRImm12 {
+                alu_op,
+                rd,
+                rs,
+                imm12,
+            } => format!(
+                "{} {},{},{}",
+                alu_op.op_name(),
+                show_w(rd),
+                show(rs),
+                imm12.value()
+            ),
+            &Inst::AluRR { alu_op, rd, rs } => {
+                format!("{} {},{}", alu_op.op_name(), show_w(rd), show(rs))
+            }
+            &Inst::FpuRR { alu_op, rd, rs } => {
+                let (_, _, rm) = alu_op.encoding();
+                let rm = if alu_op.has_rounding_mode() && rm == rounding_mode::RTZ {
+                    ",rtz"
+                } else {
+                    ""
+                };
+                format!("{} {},{}{}", alu_op.op_name(), show_w(rd), show(rs), rm)
+            }
+            &Inst::FpuRRR {
+                alu_op,
+                rd,
+                rs1,
+                rs2,
+            } => format!(
+                "{} {},{},{}",
+                alu_op.op_name(),
+                show_w(rd),
+                show(rs1),
+                show(rs2)
+            ),
+            &Inst::FpuRRRR {
+                alu_op,
+                rd,
+                rs1,
+                rs2,
+                rs3,
+            } => format!(
+                "{} {},{},{},{}",
+                alu_op.op_name(),
+                show_w(rd),
+                show(rs1),
+                show(rs2),
+                show(rs3)
+            ),
+            &Inst::Load {
+                rd, op, ref from, ..
+            } => {
+                let (mem_str, mem) = mem_finalize_for_show(from, mb_rru, state);
+                format!("{}{} {},{}", mem_str, op.op_name(), show_w(rd), mem)
+            }
+            &Inst::Store {
+                ref to, op, src, ..
+            } => {
+                let (mem_str, mem) = mem_finalize_for_show(to, mb_rru, state);
+                format!("{}{} {},{}", mem_str, op.op_name(), show(src), mem)
+            }
+            &Inst::Mov { rd, rm, ty } => {
+                if ty.is_float() {
+                    format!("fmv.d {},{}", show_w(rd), show(rm))
+                } else {
+                    format!("mv {},{}", show_w(rd), show(rm))
+                }
+            }
+            &Inst::Extend {
+                rd,
+                rn,
+                signed,
+                from_bits,
+                to_bits,
+            } => format!(
+                "{} {},{},{},{}",
+                if signed { "sext" } else { "zext" },
+                show_w(rd),
+                show(rn),
+                from_bits,
+                to_bits
+            ),
+            &Inst::Select { rd, cond, x, y, .. } => format!(
+                "select {},{},{}##condition={}",
+                show_w(rd),
+                show(x),
+                show(y),
+                show(cond)
+            ),
+            &Inst::FcvtToInt {
+                is_sat,
+                is_signed,
+                in_ty,
+                out_ty,
+                rd,
+                rs,
+            } => format!(
+                "fcvt_to_{}int{}.{}.{} {},{}",
+                if is_signed { "s" } else { "u" },
+                if is_sat { "_sat" } else { "" },
+                out_ty,
+                in_ty,
+                show_w(rd),
+                show(rs)
+            ),
+            &Inst::FloatRound { op, ty, rd, rs } => {
+                let op = match op {
+                    FloatRoundOP::Ceil => "ceil",
+                    FloatRoundOP::Floor => "floor",
+                    FloatRoundOP::Trunc => "trunc",
+                    FloatRoundOP::Nearest => "nearest",
+                };
+                format!("{}.{} {},{}", op, ty, show_w(rd), show(rs))
+            }
+            &Inst::FloatMinMax {
+                is_max,
+                ty,
+                rd,
+                rs1,
+                rs2,
+            } => format!(
+                "{}.{} {},{},{}",
+                if is_max { "fmax" } else { "fmin" },
+                ty,
+                show_w(rd),
+                show(rs1),
+                show(rs2)
+            ),
+            &Inst::Ret => "ret".to_string(),
+            &Inst::EpiloguePlaceholder => "epilogue placeholder".to_string(),
+            &Inst::Call { ref info } => format!("call {}", info.dest),
+            &Inst::CallInd { ref info } => format!("callind {}", show(info.rn)),
+            &Inst::Jal { ref dest } => format!("j {}", dest.show_rru(mb_rru)),
+            &Inst::CondBr {
+                ref taken,
+                ref not_taken,
+                ref kind,
+            } => format!(
+                "{},{} ; j {}",
+                kind.show_rru(mb_rru),
+                taken.show_rru(mb_rru),
+                not_taken.show_rru(mb_rru)
+            ),
+            &Inst::JTSequence {
+                ref info, index, ..
+            } => {
+                let default_target = info.default_target.show_rru(mb_rru);
+                format!(
+                    "jt_sequence {}, default {}, targets {:?}",
+                    show(index),
+                    default_target,
+                    info.targets
+                )
+            }
+            &Inst::TrapIf { ref kind, .. } => {
+                format!("{},8 ; udf", kind.inverse().show_rru(mb_rru))
+            }
+            &Inst::Udf { .. } => "udf".to_string(),
+            &Inst::EBreak => "ebreak".to_string(),
+            &Inst::LoadExtName {
+                rd,
+                ref name,
+                offset,
+            } => format!(
+                "auipc {},0 ; ld {},12({}) ; j 12 ; .8byte {} + {}",
+                show_w(rd),
+                show_w(rd),
+                show_w(rd),
+                name,
+                offset
+            ),
+            &Inst::LoadAddr { rd, ref mem } => {
+                let (mem_insts, base, imm12) = mem_finalize(mem, state);
+                let mut ret = String::new();
+                for inst in mem_insts.into_iter() {
+                    ret.push_str(&inst.show_rru(mb_rru));
+                    ret.push_str(" ; ");
+                }
+                if imm12.value() != 0 || base != rd.to_reg() {
+                    ret.push_str(&format!(
+                        "addi {},{},{}",
+                        show_w(rd),
+                        show(base),
+                        imm12.value()
+                    ));
+                }
+                ret
+            }
+            &Inst::VirtualSPOffsetAdj { amount } => {
+                state.virtual_sp_offset += amount;
+                format!("virtual_sp_offset_adjust {}", amount)
+            }
+            &Inst::Fence { pred, succ } => {
+                let show_set = |set: u8| {
+                    let mut ret = String::new();
+                    for (bit, name) in
+                        [(0b1000, "i"), (0b0100, "o"), (0b0010, "r"), (0b0001, "w")].iter()
+                    {
+                        if set & bit != 0 {
+                            ret.push_str(name);
+                        }
+                    }
+                    ret
+                };
+                format!("fence {},{}", show_set(pred), show_set(succ))
+            }
+            &Inst::Atomic { op, rd, addr, src } => format!(
+                "{} {},{},({})",
+                op.op_name(),
+                show_w(rd),
+                show(src),
+                show(addr)
+            ),
+            &Inst::AtomicRmwLoop {
+                op,
+                ty,
+                dst,
+                addr,
+                src,
+                shift,
+                mask,
+            } => format!(
+                "atomic_rmw.{} {:?} {},({}),{}##shift={} mask={}",
+                ty,
+                op,
+                show_w(dst),
+                show(addr),
+                show(src),
+                show(shift),
+                show(mask)
+            ),
+            &Inst::AtomicCas {
+                ty,
+                dst,
+                addr,
+                expected,
+                replacement,
+                shift,
+                mask,
+            } => format!(
+                "atomic_cas.{} {},({}),{},{}##shift={} mask={}",
+                ty,
+                show_w(dst),
+                show(addr),
+                show(expected),
+                show(replacement),
+                show(shift),
+                show(mask)
+            ),
+            &Inst::EmitIsland { needed_space } => format!("emit_island {}", needed_space),
+            &Inst::ValueLabelMarker { label, reg } => {
+                format!("value_label {:?}, {}", label, show(reg))
+            }
+            &Inst::Unwind { ref inst } => {
+                format!("unwind {:?}", inst)
+            }
+        }
+    }
+}
+
+//=============================================================================
+// Label fixups and jump veneers.
+
+/// Different forms of label references for different instruction formats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LabelUse {
+    /// 13-bit branch offset (conditional branches). PC-rel, offset is a
+    /// signed multiple of 2, scattered over bits 31:25 and 11:7.
+    B12,
+    /// 21-bit jump offset (`jal`). PC-rel, offset is a signed multiple of 2,
+    /// scattered over bits 31:12.
+    Jal20,
+    /// 32-bit PC-relative offset split over an `auipc` and a following
+    /// `jalr`, as used by calls and veneers.
+    PCRel32,
+}
+
+impl MachInstLabelUse for LabelUse {
+    /// Alignment for veneer code. Every RISC-V instruction (without the C
+    /// extension) must be 4-byte-aligned.
+    const ALIGN: CodeOffset = 4;
+
+    /// Maximum PC-relative range (positive), inclusive.
+    fn max_pos_range(self) -> CodeOffset {
+        match self {
+            LabelUse::B12 => (1 << 12) - 1,
+            LabelUse::Jal20 => (1 << 20) - 1,
+            // The upper 20 bits are rounded to account for the sign of the
+            // lower 12 bits, so the top 2KiB of the positive range are out
+            // of reach.
+            LabelUse::PCRel32 => 0x7fff_f7ff,
+        }
+    }
+
+    /// Maximum PC-relative range (negative).
+    fn max_neg_range(self) -> CodeOffset {
+        match self {
+            LabelUse::B12 => 1 << 12,
+            LabelUse::Jal20 => 1 << 20,
+            LabelUse::PCRel32 => 0x8000_0000,
+        }
+    }
+
+    /// Size of window into code needed to do the patch.
+    fn patch_size(self) -> CodeOffset {
+        match self {
+            LabelUse::B12 | LabelUse::Jal20 => 4,
+            LabelUse::PCRel32 => 8,
+        }
+    }
+
+    /// Perform the patch.
+    fn patch(self, buffer: &mut [u8], use_offset: CodeOffset, label_offset: CodeOffset) {
+        let pc_rel = (label_offset as i64) - (use_offset as i64);
+        debug_assert!(pc_rel <= self.max_pos_range() as i64);
+        debug_assert!(pc_rel >= -(self.max_neg_range() as i64));
+        debug_assert!(pc_rel & 1 == 0);
+        let pc_rel = pc_rel as i32;
+        let insn_word = u32::from_le_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]);
+        match self {
+            LabelUse::B12 => {
+                let insn_word = (insn_word & 0x01ff_f07f) | emit::enc_b_offset(pc_rel);
+                buffer[0..4].clone_from_slice(&u32::to_le_bytes(insn_word));
+            }
+            LabelUse::Jal20 => {
+                let insn_word = (insn_word & 0x0000_0fff) | emit::enc_jal_offset(pc_rel);
+                buffer[0..4].clone_from_slice(&u32::to_le_bytes(insn_word));
+            }
+            LabelUse::PCRel32 => {
+                let hi20 = (pc_rel.wrapping_add(0x800) as u32) & 0xffff_f000;
+                let lo12 = (pc_rel as u32).wrapping_sub(hi20) & 0xfff;
+                let auipc = (insn_word & 0xfff) | hi20;
+                let jalr = u32::from_le_bytes([buffer[4], buffer[5], buffer[6], buffer[7]]);
+                let jalr = (jalr & 0x000f_ffff) | lo12 << 20;
+                buffer[0..4].clone_from_slice(&u32::to_le_bytes(auipc));
+                buffer[4..8].clone_from_slice(&u32::to_le_bytes(jalr));
+            }
+        }
+    }
+
+    /// Is a veneer supported for this label reference type?
+    fn supports_veneer(self) -> bool {
+        match self {
+            LabelUse::B12 => true,   // veneer is a Jal20
+            LabelUse::Jal20 => true, // veneer is a PCRel32
+            LabelUse::PCRel32 => false,
+        }
+    }
+
+    /// How large is the veneer, if supported?
+    fn veneer_size(self) -> CodeOffset {
+        match self {
+            LabelUse::B12 => 4,
+            LabelUse::Jal20 => 8,
+            LabelUse::PCRel32 => unreachable!(),
+        }
+    }
+
+    /// Generate a veneer into the buffer, given that this veneer is at `veneer_offset`, and return
+    /// an offset and label-use for the veneer's use of the original label.
+    fn generate_veneer(
+        self,
+        buffer: &mut [u8],
+        veneer_offset: CodeOffset,
+    ) -> (CodeOffset, LabelUse) {
+        match self {
+            LabelUse::B12 => {
+                // jal zero, 0
+                let insn_word = emit::enc_jal(0, 0);
+                buffer[0..4].clone_from_slice(&u32::to_le_bytes(insn_word));
+                (veneer_offset, LabelUse::Jal20)
+            }
+
+            // This is promoting a 21-bit jump to a 32-bit one. Veneers are
+            // only used by intra-function jumps, for which x31 is reserved as
+            // a scratch register, so it is free here:
+            //
+            // auipc x31, 0
+            // jalr zero, 0(x31)
+            LabelUse::Jal20 => {
+                let tmp = machreg_to_gpr_num(spilltmp_reg());
+                let auipc = emit::enc_auipc(tmp, 0);
+                let jalr = emit::enc_jalr(0, tmp, 0);
+                buffer[0..4].clone_from_slice(&u32::to_le_bytes(auipc));
+                buffer[4..8].clone_from_slice(&u32::to_le_bytes(jalr));
+                (veneer_offset, LabelUse::PCRel32)
+            }
+
+            LabelUse::PCRel32 => panic!("Unsupported label-reference type for veneer generation!"),
+        }
+    }
+
+    fn from_reloc(reloc: Reloc, addend: Addend) -> Option<LabelUse> {
+        match (reloc, addend) {
+            (Reloc::RiscvCall, 0) => Some(LabelUse::PCRel32),
+            _ => None,
+        }
+    }
+}
+
+fn machreg_to_gpr_num(r: Reg) -> u32 {
+    r.to_real_reg().get_hw_encoding() as u32
+}
@@ -0,0 +1,259 @@
+//! RISC-V 64 ISA definitions: registers.
+
+use crate::settings;
+use regalloc::{RealRegUniverse, Reg, RegClass, RegClassInfo, Writable, NUM_REG_CLASSES};
+
+//=============================================================================
+// Registers, the Universe thereof, and printing
+
+#[rustfmt::skip]
+const XREG_INDICES: [u8; 32] = [
+    // x0 (zero), x1 (ra), x2 (sp), x3 (gp), x4 (tp)
+    58, 59, 60, 61, 62,
+    // x5 - x7 (t0 - t2)
+    40, 41, 42,
+    // x8 (fp)
+    63,
+    // x9 (s1)
+    45,
+    // x10 - x17 (a0 - a7)
+    32, 33, 34, 35, 36, 37, 38, 39,
+    // x18 - x27 (s2 - s11)
+    46, 47, 48, 49, 50, 51, 52, 53, 54, 55,
+    // x28, x29 (t3, t4)
+    43, 44,
+    // x30, x31 (t5, t6), reserved as scratch registers
+    56, 57,
+];
+
+#[rustfmt::skip]
+const FREG_INDICES: [u8; 32] = [
+    // f0 - f7 (ft0 - ft7)
+    0, 1, 2, 3, 4, 5, 6, 7,
+    // f8, f9 (fs0, fs1)
+    20, 21,
+    // f10 - f17 (fa0 - fa7)
+    8, 9, 10, 11, 12, 13, 14, 15,
+    // f18 - f27 (fs2 - fs11)
+    22, 23, 24, 25, 26, 27, 28, 29, 30, 31,
+    // f28 - f31 (ft8 - ft11)
+    16, 17, 18, 19,
+];
+
+/// Get a reference to an x-register (integer register).
+pub fn xreg(num: u8) -> Reg {
+    assert!(num < 32);
+    Reg::new_real(
+        RegClass::I64,
+        /* enc = */ num,
+        /* index = */ XREG_INDICES[num as usize],
+    )
+}
+
+/// Get a writable reference to an x-register.
+pub fn writable_xreg(num: u8) -> Writable<Reg> {
+    Writable::from_reg(xreg(num))
+}
+
+/// Get a reference to an f-register (floating-point register).
+pub fn freg(num: u8) -> Reg {
+    assert!(num < 32);
+    Reg::new_real(
+        RegClass::F64,
+        /* enc = */ num,
+        /* index = */ FREG_INDICES[num as usize],
+    )
+}
+
+/// Get a writable reference to an f-register.
+pub fn writable_freg(num: u8) -> Writable<Reg> {
+    Writable::from_reg(freg(num))
+}
+
+/// Get a reference to the hardwired zero register (x0).
+pub fn zero_reg() -> Reg {
+    xreg(0)
+}
+
+/// Get a writable reference to the zero register (this discards a result).
+pub fn writable_zero_reg() -> Writable<Reg> {
+    Writable::from_reg(zero_reg())
+}
+
+/// Get a reference to the return address register (x1).
+pub fn link_reg() -> Reg {
+    xreg(1)
+}
+
+/// Get a writable reference to the return address register.
+pub fn writable_link_reg() -> Writable<Reg> {
+    Writable::from_reg(link_reg())
+}
+
+/// Get a reference to the stack-pointer register (x2).
+pub fn stack_reg() -> Reg {
+    xreg(2)
+}
+
+/// Get a writable reference to the stack-pointer register.
+pub fn writable_stack_reg() -> Writable<Reg> {
+    Writable::from_reg(stack_reg())
+}
+
+/// Get a reference to the frame pointer (x8).
+pub fn fp_reg() -> Reg {
+    xreg(8)
+}
+
+/// Get a writable reference to the frame pointer.
+pub fn writable_fp_reg() -> Writable<Reg> {
+    Writable::from_reg(fp_reg())
+}
+
+/// Get a reference to the first temporary, sometimes "spill temporary", register. This register is
+/// used to compute the address of a spill slot when a direct offset addressing mode from FP is not
+/// sufficient (+/- 2^11 bytes). We exclude this register from regalloc and reserve it for this
+/// purpose for simplicity; otherwise we need a multi-stage analysis where we first determine how
+/// many spill slots we have, then perhaps remove the reg from the pool and recompute regalloc.
+///
+/// We use x31 (t6) for this because it's a caller-saved temporary that no ABI gives a special
+/// meaning to. Veneers and the expansions of pseudo-instructions also use it as a scratch
+/// register, so it must never be live across an instruction boundary.
+pub fn spilltmp_reg() -> Reg {
+    xreg(31)
+}
+
+/// Get a writable reference to the spilltmp reg.
+pub fn writable_spilltmp_reg() -> Writable<Reg> {
+    Writable::from_reg(spilltmp_reg())
+}
+
+/// Get a reference to the second temp register. We need this in some edge cases
+/// where we need both the spilltmp and another temporary.
+///
+/// We use x30 (t5), which is excluded from regalloc for the same reasons as x31.
+pub fn tmp2_reg() -> Reg {
+    xreg(30)
+}
+
+/// Get a writable reference to the tmp2 reg.
+pub fn writable_tmp2_reg() -> Writable<Reg> {
+    Writable::from_reg(tmp2_reg())
+}
+
+/// Create the register universe for RISC-V 64.
+pub fn create_reg_universe(_flags: &settings::Flags) -> RealRegUniverse {
+    let mut regs = vec![];
+    let mut allocable_by_class = [None; NUM_REG_CLASSES];
+
+    // Numbering Scheme: we put f-regs first, then x-regs. The caller-saved
+    // registers of each class come first so that leaf functions preferably
+    // use registers that do not need to be saved in the prologue.
+
+    // f-regs.
+    let base = regs.len();
+    let fnames = [
+        (0, "ft0"),
+        (1, "ft1"),
+        (2, "ft2"),
+        (3, "ft3"),
+        (4, "ft4"),
+        (5, "ft5"),
+        (6, "ft6"),
+        (7, "ft7"),
+        (10, "fa0"),
+        (11, "fa1"),
+        (12, "fa2"),
+        (13, "fa3"),
+        (14, "fa4"),
+        (15, "fa5"),
+        (16, "fa6"),
+        (17, "fa7"),
+        (28, "ft8"),
+        (29, "ft9"),
+        (30, "ft10"),
+        (31, "ft11"),
+        (8, "fs0"),
+        (9, "fs1"),
+        (18, "fs2"),
+        (19, "fs3"),
+        (20, "fs4"),
+        (21, "fs5"),
+        (22, "fs6"),
+        (23, "fs7"),
+        (24, "fs8"),
+        (25, "fs9"),
+        (26, "fs10"),
+        (27, "fs11"),
+    ];
+    for &(num, name) in fnames.iter() {
+        regs.push((freg(num).to_real_reg(), name.into()));
+    }
+
+    allocable_by_class[RegClass::F64.rc_to_usize()] = Some(RegClassInfo {
+        first: base,
+        last: regs.len() - 1,
+        suggested_scratch: Some(freg(31).get_index()),
+    });
+
+    // Allocatable x-regs: a0 - a7, t0 - t4, then the callee-saved s1 - s11.
+    let base = regs.len();
+    let xnames = [
+        (10, "a0"),
+        (11, "a1"),
+        (12, "a2"),
+        (13, "a3"),
+        (14, "a4"),
+        (15, "a5"),
+        (16, "a6"),
+        (17, "a7"),
+        (5, "t0"),
+        (6, "t1"),
+        (7, "t2"),
+        (28, "t3"),
+        (29, "t4"),
+        (9, "s1"),
+        (18, "s2"),
+        (19, "s3"),
+        (20, "s4"),
+        (21, "s5"),
+        (22, "s6"),
+        (23, "s7"),
+        (24, "s8"),
+        (25, "s9"),
+        (26, "s10"),
+        (27, "s11"),
+    ];
+    for &(num, name) in xnames.iter() {
+        regs.push((xreg(num).to_real_reg(), name.into()));
+    }
+
+    allocable_by_class[RegClass::I64.rc_to_usize()] = Some(RegClassInfo {
+        first: base,
+        last: regs.len() - 1,
+        suggested_scratch: Some(xreg(29).get_index()),
+    });
+
+    // Other regs, not available to the allocator.
+    let allocable = regs.len();
+    regs.push((tmp2_reg().to_real_reg(), "t5".into()));
+    regs.push((spilltmp_reg().to_real_reg(), "t6".into()));
+    regs.push((zero_reg().to_real_reg(), "zero".into()));
+    regs.push((link_reg().to_real_reg(), "ra".into()));
+    regs.push((stack_reg().to_real_reg(), "sp".into()));
+    regs.push((xreg(3).to_real_reg(), "gp".into()));
+    regs.push((xreg(4).to_real_reg(), "tp".into()));
+    regs.push((fp_reg().to_real_reg(), "fp".into()));
+
+    // Assert sanity: the indices in the register structs must match their
+    // actual indices in the array.
+    for (i, reg) in regs.iter().enumerate() {
+        assert_eq!(i, reg.0.get_index());
+    }
+
+    RealRegUniverse {
+        regs,
+        allocable,
+        allocable_by_class,
+    }
+}
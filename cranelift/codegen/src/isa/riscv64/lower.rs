@@ -0,0 +1,748 @@
+//! Lowering rules for riscv64.
+//!
+//! Values narrower than 64 bits live in 64-bit integer registers with
+//! undefined upper bits; every operation whose result depends on those bits
+//! (comparisons, divisions, right shifts, ...) extends its inputs first.
+//! Booleans are represented as 0 or 1 for `b1` and as 0 or all ones for the
+//! wider boolean types.
+
+use crate::ir::condcodes::{FloatCC, IntCC};
+use crate::ir::types::*;
+use crate::ir::Inst as IRInst;
+use crate::ir::{Opcode, Type, Value};
+use crate::machinst::lower::*;
+use crate::machinst::*;
+use crate::{CodegenError, CodegenResult};
+
+use crate::isa::riscv64::inst::*;
+use crate::isa::riscv64::Riscv64Backend;
+
+use super::lower_inst;
+
+use regalloc::{Reg, Writable};
+
+pub mod isle;
+
+//============================================================================
+// Lowering: convert instruction inputs to forms that we can use.
+
+/// How to handle narrow values loaded into registers; see note on `narrow_mode`
+/// parameter to `put_input_in_*` below.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum NarrowValueMode {
+    None,
+    /// Zero-extend to 64 bits if original is < 64 bits.
+    ZeroExtend64,
+    /// Sign-extend to 64 bits if original is < 64 bits.
+    SignExtend64,
+}
+
+/// Emits instruction(s) to generate the given constant value into newly-allocated
+/// temporary registers, returning these registers.
+///
+/// Narrow constants are sign-extended from their width, which always gives
+/// the shortest materialization sequence.
+fn generate_constant<C: LowerCtx<I = Inst>>(ctx: &mut C, ty: Type, c: u64) -> ValueRegs<Reg> {
+    let from_bits = ty_bits(ty);
+    let value = if from_bits < 64 && !ty.is_float() {
+        let shift = 64 - from_bits;
+        (((c << shift) as i64) >> shift) as u64
+    } else {
+        c
+    };
+
+    let cst_copy = ctx.alloc_tmp(ty);
+    for inst in Inst::gen_constant(cst_copy, value as u128, ty, |ty| {
+        ctx.alloc_tmp(ty).only_reg().unwrap()
+    })
+    .into_iter()
+    {
+        ctx.emit(inst);
+    }
+    non_writable_value_regs(cst_copy)
+}
+
+/// Extends a register according to `narrow_mode`.
+fn extend_reg<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    ty: Type,
+    in_reg: Reg,
+    narrow_mode: NarrowValueMode,
+) -> Reg {
+    let from_bits = ty_bits(ty) as u8;
+    if from_bits >= 64 || ty.is_float() {
+        return in_reg;
+    }
+    let signed = match narrow_mode {
+        NarrowValueMode::None => return in_reg,
+        NarrowValueMode::ZeroExtend64 => false,
+        NarrowValueMode::SignExtend64 => true,
+    };
+    let tmp = ctx.alloc_tmp(I64).only_reg().unwrap();
+    ctx.emit(Inst::Extend {
+        rd: tmp,
+        rn: in_reg,
+        signed,
+        from_bits,
+        to_bits: 64,
+    });
+    tmp.to_reg()
+}
+
+/// Lowers an instruction input to multiple regs
+fn lower_value_to_regs<C: LowerCtx<I = Inst>>(ctx: &mut C, value: Value) -> (ValueRegs<Reg>, Type) {
+    log::trace!("lower_value_to_regs: value {:?}", value);
+    let ty = ctx.value_ty(value);
+    let inputs = ctx.get_value_as_source_or_const(value);
+
+    let in_regs = if let Some(c) = inputs.constant {
+        // Generate constants fresh at each use to minimize long-range register pressure.
+        generate_constant(ctx, ty, c)
+    } else {
+        ctx.put_value_in_regs(value)
+    };
+
+    (in_regs, ty)
+}
+
+/// Lower an instruction input to a register
+///
+/// The given register will be extended appropriately, according to
+/// `narrow_mode` and the input's type.
+pub(crate) fn put_input_in_reg<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    input: InsnInput,
+    narrow_mode: NarrowValueMode,
+) -> Reg {
+    let value = ctx.input_as_value(input.insn, input.input);
+    put_value_in_reg(ctx, value, narrow_mode)
+}
+
+/// Like above, only for values
+pub(crate) fn put_value_in_reg<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    value: Value,
+    narrow_mode: NarrowValueMode,
+) -> Reg {
+    let (in_regs, ty) = lower_value_to_regs(ctx, value);
+    let reg = in_regs
+        .only_reg()
+        .expect("Multi-register value not expected");
+
+    extend_reg(ctx, ty, reg, narrow_mode)
+}
+
+/// Lower an instruction input to multiple regs
+pub(crate) fn put_input_in_regs<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    input: InsnInput,
+) -> ValueRegs<Reg> {
+    let value = ctx.input_as_value(input.insn, input.input);
+    let (in_regs, _) = lower_value_to_regs(ctx, value);
+    in_regs
+}
+
+//============================================================================
+// Lowering: addressing mode support.
+
+/// Lower the address of a load or store.
+///
+/// All address inputs are added together into a single base register; the
+/// offset is left in the `AMode`, and materialized at emission time if it does
+/// not fit in the 12-bit immediate field.
+pub(crate) fn lower_address<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    elem_ty: Type,
+    roots: &[InsnInput],
+    offset: i32,
+) -> AMode {
+    let mut base = None;
+    for &input in roots {
+        let reg = put_input_in_reg(ctx, input, NarrowValueMode::ZeroExtend64);
+        base = Some(match base {
+            None => reg,
+            Some(prev) => {
+                let sum = ctx.alloc_tmp(I64).only_reg().unwrap();
+                ctx.emit(Inst::AluRRR {
+                    alu_op: AluOPRRR::Add,
+                    rd: sum,
+                    rs1: prev,
+                    rs2: reg,
+                });
+                sum.to_reg()
+            }
+        });
+    }
+    let base = base.unwrap_or_else(zero_reg);
+    AMode::RegOffset(base, offset as i64, elem_ty)
+}
+
+pub(crate) fn lower_constant_u64<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    rd: Writable<Reg>,
+    value: u64,
+) {
+    for inst in Inst::load_constant(rd, value) {
+        ctx.emit(inst);
+    }
+}
+
+//============================================================================
+// Lowering: comparisons.
+
+pub(crate) fn condcode_is_signed(cc: IntCC) -> bool {
+    match cc {
+        IntCC::Equal
+        | IntCC::UnsignedGreaterThanOrEqual
+        | IntCC::UnsignedGreaterThan
+        | IntCC::UnsignedLessThanOrEqual
+        | IntCC::UnsignedLessThan
+        | IntCC::NotEqual => false,
+        IntCC::SignedGreaterThanOrEqual
+        | IntCC::SignedGreaterThan
+        | IntCC::SignedLessThanOrEqual
+        | IntCC::SignedLessThan
+        | IntCC::Overflow
+        | IntCC::NotOverflow => true,
+    }
+}
+
+/// The extension to use for the inputs of a comparison with condition `cc`
+/// of values of type `ty`.
+///
+/// Sign extension preserves the unsigned order too, so it is only required
+/// for signed conditions; otherwise we pick whichever is cheaper for `ty`.
+fn cmp_narrow_mode(cc: IntCC, ty: Type) -> NarrowValueMode {
+    if condcode_is_signed(cc) || ty == I32 {
+        NarrowValueMode::SignExtend64
+    } else {
+        NarrowValueMode::ZeroExtend64
+    }
+}
+
+/// Put the two inputs of an integer comparison of a type of at most 64 bits
+/// into registers, extended as the condition requires.
+pub(crate) fn put_cmp_inputs_in_regs<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    cc: IntCC,
+    lhs: InsnInput,
+    rhs: InsnInput,
+) -> (Reg, Reg) {
+    let narrow_mode = cmp_narrow_mode(cc, ctx.input_ty(lhs.insn, lhs.input));
+    let rs1 = put_input_in_reg(ctx, lhs, narrow_mode);
+    let rs2 = put_input_in_reg(ctx, rhs, narrow_mode);
+    (rs1, rs2)
+}
+
+pub(crate) fn alu_rrr<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    alu_op: AluOPRRR,
+    rs1: Reg,
+    rs2: Reg,
+) -> Reg {
+    let rd = ctx.alloc_tmp(I64).only_reg().unwrap();
+    ctx.emit(Inst::AluRRR {
+        alu_op,
+        rd,
+        rs1,
+        rs2,
+    });
+    rd.to_reg()
+}
+
+pub(crate) fn alu_rr_imm12<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    alu_op: AluOPRRI,
+    rs: Reg,
+    imm: i64,
+) -> Reg {
+    let rd = ctx.alloc_tmp(I64).only_reg().unwrap();
+    ctx.emit(Inst::AluRRImm12 {
+        alu_op,
+        rd,
+        rs,
+        imm12: Imm12::maybe_from_i64(imm).unwrap(),
+    });
+    rd.to_reg()
+}
+
+/// Compute a register that is 1 if the comparison holds and 0 otherwise. The
+/// registers must already be extended as the condition requires, and the
+/// condition must not be an overflow condition.
+pub(crate) fn lower_int_compare_to_bit<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    cmp: IntegerCompare,
+) -> Reg {
+    let IntegerCompare { kind, rs1, rs2 } = cmp;
+    match kind {
+        IntCC::Equal => {
+            let diff = alu_rrr(ctx, AluOPRRR::Xor, rs1, rs2);
+            alu_rr_imm12(ctx, AluOPRRI::Sltiu, diff, 1)
+        }
+        IntCC::NotEqual => {
+            let diff = alu_rrr(ctx, AluOPRRR::Xor, rs1, rs2);
+            alu_rrr(ctx, AluOPRRR::Sltu, zero_reg(), diff)
+        }
+        IntCC::SignedLessThan => alu_rrr(ctx, AluOPRRR::Slt, rs1, rs2),
+        IntCC::SignedGreaterThan => alu_rrr(ctx, AluOPRRR::Slt, rs2, rs1),
+        IntCC::UnsignedLessThan => alu_rrr(ctx, AluOPRRR::Sltu, rs1, rs2),
+        IntCC::UnsignedGreaterThan => alu_rrr(ctx, AluOPRRR::Sltu, rs2, rs1),
+        IntCC::SignedGreaterThanOrEqual
+        | IntCC::SignedLessThanOrEqual
+        | IntCC::UnsignedGreaterThanOrEqual
+        | IntCC::UnsignedLessThanOrEqual => {
+            let inverse = lower_int_compare_to_bit(ctx, cmp.inverse());
+            alu_rr_imm12(ctx, AluOPRRI::Xori, inverse, 1)
+        }
+        IntCC::Overflow | IntCC::NotOverflow => {
+            panic!("overflow conditions must be lowered with `lower_icmp`")
+        }
+    }
+}
+
+/// Lower an integer comparison of two inputs of type `ty` into a register
+/// holding 1 if the comparison holds and 0 otherwise.
+pub(crate) fn lower_icmp<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    cc: IntCC,
+    lhs: InsnInput,
+    rhs: InsnInput,
+    ty: Type,
+) -> CodegenResult<Reg> {
+    if ty == I128 {
+        return lower_icmp_i128(ctx, cc, lhs, rhs);
+    }
+
+    let (rs1, rs2) = put_cmp_inputs_in_regs(ctx, cc, lhs, rhs);
+    match cc {
+        IntCC::Overflow | IntCC::NotOverflow => {
+            // The subtraction `rs1 - rs2` overflows if it is not representable
+            // in the type. For narrow types the (sign-extended) 64-bit
+            // difference is exact, so it overflows if it changes when
+            // sign-extended from the type's width; for 64-bit values it
+            // overflows if the operands have different signs and the result's
+            // sign differs from that of `rs1`.
+            let diff = alu_rrr(ctx, AluOPRRR::Sub, rs1, rs2);
+            let overflow = if ty_bits(ty) < 64 {
+                let ext = ctx.alloc_tmp(I64).only_reg().unwrap();
+                ctx.emit(Inst::Extend {
+                    rd: ext,
+                    rn: diff,
+                    signed: true,
+                    from_bits: ty_bits(ty) as u8,
+                    to_bits: 64,
+                });
+                lower_int_compare_to_bit(
+                    ctx,
+                    IntegerCompare {
+                        kind: IntCC::NotEqual,
+                        rs1: diff,
+                        rs2: ext.to_reg(),
+                    },
+                )
+            } else {
+                let ops_differ = alu_rrr(ctx, AluOPRRR::Xor, rs1, rs2);
+                let result_differs = alu_rrr(ctx, AluOPRRR::Xor, rs1, diff);
+                let both = alu_rrr(ctx, AluOPRRR::And, ops_differ, result_differs);
+                alu_rrr(ctx, AluOPRRR::Slt, both, zero_reg())
+            };
+            if cc == IntCC::Overflow {
+                Ok(overflow)
+            } else {
+                Ok(alu_rr_imm12(ctx, AluOPRRI::Xori, overflow, 1))
+            }
+        }
+        _ => Ok(lower_int_compare_to_bit(
+            ctx,
+            IntegerCompare { kind: cc, rs1, rs2 },
+        )),
+    }
+}
+
+fn lower_icmp_i128<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    cc: IntCC,
+    lhs: InsnInput,
+    rhs: InsnInput,
+) -> CodegenResult<Reg> {
+    let lhs = put_input_in_regs(ctx, lhs);
+    let rhs = put_input_in_regs(ctx, rhs);
+    let (lhs_lo, lhs_hi) = (lhs.regs()[0], lhs.regs()[1]);
+    let (rhs_lo, rhs_hi) = (rhs.regs()[0], rhs.regs()[1]);
+
+    match cc {
+        IntCC::Equal | IntCC::NotEqual => {
+            let lo_diff = alu_rrr(ctx, AluOPRRR::Xor, lhs_lo, rhs_lo);
+            let hi_diff = alu_rrr(ctx, AluOPRRR::Xor, lhs_hi, rhs_hi);
+            let diff = alu_rrr(ctx, AluOPRRR::Or, lo_diff, hi_diff);
+            Ok(lower_int_compare_to_bit(
+                ctx,
+                IntegerCompare {
+                    kind: cc,
+                    rs1: diff,
+                    rs2: zero_reg(),
+                },
+            ))
+        }
+        IntCC::Overflow | IntCC::NotOverflow => Err(CodegenError::Unsupported(format!(
+            "riscv64: overflow comparisons of i128 values are not supported"
+        ))),
+        _ => {
+            // If the high halves differ they decide the result (and the
+            // strict and non-strict conditions agree); otherwise the low
+            // halves are compared as unsigned values.
+            let hi_result = lower_int_compare_to_bit(
+                ctx,
+                IntegerCompare {
+                    kind: cc,
+                    rs1: lhs_hi,
+                    rs2: rhs_hi,
+                },
+            );
+            let lo_result = lower_int_compare_to_bit(
+                ctx,
+                IntegerCompare {
+                    kind: cc.unsigned(),
+                    rs1: lhs_lo,
+                    rs2: rhs_lo,
+                },
+            );
+            let hi_differ = alu_rrr(ctx, AluOPRRR::Xor, lhs_hi, rhs_hi);
+            let rd = ctx.alloc_tmp(I64).only_reg().unwrap();
+            ctx.emit(Inst::Select {
+                rd,
+                cond: hi_differ,
+                x: hi_result,
+                y: lo_result,
+                ty: I64,
+            });
+            Ok(rd.to_reg())
+        }
+    }
+}
+
+/// Lower a floating-point comparison into a register holding 1 if the
+/// comparison holds and 0 otherwise.
+pub(crate) fn lower_fcmp<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    cc: FloatCC,
+    lhs: InsnInput,
+    rhs: InsnInput,
+    ty: Type,
+) -> Reg {
+    let rs1 = put_input_in_reg(ctx, lhs, NarrowValueMode::None);
+    let rs2 = put_input_in_reg(ctx, rhs, NarrowValueMode::None);
+    let (feq, flt, fle) = match ty {
+        F32 => (FpuOPRRR::FeqS, FpuOPRRR::FltS, FpuOPRRR::FleS),
+        F64 => (FpuOPRRR::FeqD, FpuOPRRR::FltD, FpuOPRRR::FleD),
+        _ => panic!("Bad float type for comparison: {}", ty),
+    };
+    let mut fpu_rrr = |alu_op, rs1, rs2| {
+        let rd = ctx.alloc_tmp(I64).only_reg().unwrap();
+        ctx.emit(Inst::FpuRRR {
+            alu_op,
+            rd,
+            rs1,
+            rs2,
+        });
+        rd.to_reg()
+    };
+
+    // Each condition is computed either directly or as the negation of its
+    // inverse condition.
+    let (bit, negate) = match cc {
+        FloatCC::Ordered | FloatCC::Unordered => {
+            let lhs_ordered = fpu_rrr(feq, rs1, rs1);
+            let rhs_ordered = fpu_rrr(feq, rs2, rs2);
+            let ordered = alu_rrr(ctx, AluOPRRR::And, lhs_ordered, rhs_ordered);
+            (ordered, cc == FloatCC::Unordered)
+        }
+        FloatCC::Equal => (fpu_rrr(feq, rs1, rs2), false),
+        FloatCC::NotEqual => (fpu_rrr(feq, rs1, rs2), true),
+        FloatCC::OrderedNotEqual | FloatCC::UnorderedOrEqual => {
+            let lt = fpu_rrr(flt, rs1, rs2);
+            let gt = fpu_rrr(flt, rs2, rs1);
+            let ne = alu_rrr(ctx, AluOPRRR::Or, lt, gt);
+            (ne, cc == FloatCC::UnorderedOrEqual)
+        }
+        FloatCC::LessThan => (fpu_rrr(flt, rs1, rs2), false),
+        FloatCC::LessThanOrEqual => (fpu_rrr(fle, rs1, rs2), false),
+        FloatCC::GreaterThan => (fpu_rrr(flt, rs2, rs1), false),
+        FloatCC::GreaterThanOrEqual => (fpu_rrr(fle, rs2, rs1), false),
+        FloatCC::UnorderedOrLessThan => (fpu_rrr(fle, rs2, rs1), true),
+        FloatCC::UnorderedOrLessThanOrEqual => (fpu_rrr(flt, rs2, rs1), true),
+        FloatCC::UnorderedOrGreaterThan => (fpu_rrr(fle, rs1, rs2), true),
+        FloatCC::UnorderedOrGreaterThanOrEqual => (fpu_rrr(flt, rs1, rs2), true),
+    };
+    if negate {
+        alu_rr_imm12(ctx, AluOPRRI::Xori, bit, 1)
+    } else {
+        bit
+    }
+}
+
+/// Write a boolean of type `ty` to `rd`, given a register holding 0 or 1.
+pub(crate) fn materialize_bool_result<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    rd: Writable<Reg>,
+    ty: Type,
+    bit: Reg,
+) {
+    if ty == B1 || !ty.is_bool() {
+        ctx.emit(Inst::gen_move(rd, bit, I64));
+    } else {
+        // Wider booleans are all ones when true.
+        ctx.emit(Inst::AluRRR {
+            alu_op: AluOPRRR::Sub,
+            rd,
+            rs1: zero_reg(),
+            rs2: bit,
+        });
+    }
+}
+
+/// Checks for an instance of `op` feeding the given input, possibly via a conversion `conv` (e.g.,
+/// Bint or a bitcast).
+pub(crate) fn maybe_input_insn_via_conv<C: LowerCtx<I = Inst>>(
+    c: &mut C,
+    input: InsnInput,
+    op: Opcode,
+    conv: Opcode,
+) -> Option<IRInst> {
+    let inputs = c.get_input_as_source_or_const(input.insn, input.input);
+    if let Some((src_inst, _)) = inputs.inst {
+        let data = c.data(src_inst);
+        if data.opcode() == op {
+            return Some(src_inst);
+        }
+        if data.opcode() == conv {
+            let inputs = c.get_input_as_source_or_const(src_inst, 0);
+            if let Some((src_inst, _)) = inputs.inst {
+                let data = c.data(src_inst);
+                if data.opcode() == op {
+                    return Some(src_inst);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Lower a boolean or integer condition input into an `IntegerCompare` that
+/// holds when the input is nonzero, fusing a feeding `icmp` or `fcmp` where
+/// possible.
+pub(crate) fn lower_condition<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    input: InsnInput,
+) -> CodegenResult<IntegerCompare> {
+    let ty = ctx.input_ty(input.insn, input.input);
+    if let Some(icmp_insn) = maybe_input_insn_via_conv(ctx, input, Opcode::Icmp, Opcode::Bint) {
+        let cc = ctx.data(icmp_insn).cond_code().unwrap();
+        let cmp_ty = ctx.input_ty(icmp_insn, 0);
+        let lhs = InsnInput {
+            insn: icmp_insn,
+            input: 0,
+        };
+        let rhs = InsnInput {
+            insn: icmp_insn,
+            input: 1,
+        };
+        match cc {
+            IntCC::Overflow | IntCC::NotOverflow => {}
+            _ if cmp_ty != I128 => {
+                let (rs1, rs2) = put_cmp_inputs_in_regs(ctx, cc, lhs, rhs);
+                return Ok(IntegerCompare { kind: cc, rs1, rs2 });
+            }
+            _ => {}
+        }
+        let bit = lower_icmp(ctx, cc, lhs, rhs, cmp_ty)?;
+        return Ok(IntegerCompare {
+            kind: IntCC::NotEqual,
+            rs1: bit,
+            rs2: zero_reg(),
+        });
+    }
+    if let Some(fcmp_insn) = maybe_input_insn_via_conv(ctx, input, Opcode::Fcmp, Opcode::Bint) {
+        let cc = ctx.data(fcmp_insn).fp_cond_code().unwrap();
+        let cmp_ty = ctx.input_ty(fcmp_insn, 0);
+        let bit = lower_fcmp(
+            ctx,
+            cc,
+            InsnInput {
+                insn: fcmp_insn,
+                input: 0,
+            },
+            InsnInput {
+                insn: fcmp_insn,
+                input: 1,
+            },
+            cmp_ty,
+        );
+        return Ok(IntegerCompare {
+            kind: IntCC::NotEqual,
+            rs1: bit,
+            rs2: zero_reg(),
+        });
+    }
+
+    let rs1 = if ty == I128 || ty == B128 {
+        let regs = put_input_in_regs(ctx, input);
+        alu_rrr(ctx, AluOPRRR::Or, regs.regs()[0], regs.regs()[1])
+    } else {
+        put_input_in_reg(ctx, input, cmp_narrow_mode(IntCC::NotEqual, ty))
+    };
+    Ok(IntegerCompare {
+        kind: IntCC::NotEqual,
+        rs1,
+        rs2: zero_reg(),
+    })
+}
+
+/// Lower the comparison performed by a flags-producing instruction (`ifcmp`,
+/// `ifcmp_imm` or `iadd_ifcout`), for the consumer's condition `cc`. RISC-V
+/// has no flags register, so the comparison is redone at each use.
+pub(crate) fn lower_flags_condition<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    flags_input: InsnInput,
+    cc: IntCC,
+) -> CodegenResult<IntegerCompare> {
+    let flags_insn = ctx
+        .get_input_as_source_or_const(flags_input.insn, flags_input.input)
+        .inst
+        .unwrap()
+        .0;
+    let lhs = InsnInput {
+        insn: flags_insn,
+        input: 0,
+    };
+    let rhs = InsnInput {
+        insn: flags_insn,
+        input: 1,
+    };
+    match ctx.data(flags_insn).opcode() {
+        Opcode::Ifcmp => {
+            let ty = ctx.input_ty(flags_insn, 0);
+            match cc {
+                IntCC::Overflow | IntCC::NotOverflow => {}
+                _ if ty != I128 => {
+                    let (rs1, rs2) = put_cmp_inputs_in_regs(ctx, cc, lhs, rhs);
+                    return Ok(IntegerCompare { kind: cc, rs1, rs2 });
+                }
+                _ => {}
+            }
+            let bit = lower_icmp(ctx, cc, lhs, rhs, ty)?;
+            Ok(IntegerCompare {
+                kind: IntCC::NotEqual,
+                rs1: bit,
+                rs2: zero_reg(),
+            })
+        }
+        Opcode::IaddIfcout => {
+            // The carry out of `a + b` is set exactly when the (truncated)
+            // sum is less than `a` as unsigned values, so the flags are
+            // modelled as the comparison of the sum with `a`; the carry
+            // condition is `UnsignedLessThan`, see
+            // `unsigned_add_overflow_condition`.
+            let ty = ctx.output_ty(flags_insn, 0);
+            let sum = put_value_in_reg(
+                ctx,
+                ctx.dfg().inst_results(flags_insn)[0],
+                NarrowValueMode::ZeroExtend64,
+            );
+            let a = put_input_in_reg(ctx, lhs, NarrowValueMode::ZeroExtend64);
+            match cc {
+                IntCC::UnsignedLessThan | IntCC::UnsignedGreaterThanOrEqual => Ok(IntegerCompare {
+                    kind: cc,
+                    rs1: sum,
+                    rs2: a,
+                }),
+                _ => Err(CodegenError::Unsupported(format!(
+                    "riscv64: condition {} on the flags of iadd_ifcout.{}",
+                    cc, ty
+                ))),
+            }
+        }
+        op => Err(CodegenError::Unsupported(format!(
+            "riscv64: flags produced by {}",
+            op
+        ))),
+    }
+}
+
+/// Lower the floating-point comparison performed by the `ffcmp` feeding a
+/// flags input, for the consumer's condition `cc`.
+pub(crate) fn lower_fflags_condition<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    flags_input: InsnInput,
+    cc: FloatCC,
+) -> IntegerCompare {
+    let ffcmp_insn = ctx
+        .get_input_as_source_or_const(flags_input.insn, flags_input.input)
+        .inst
+        .unwrap()
+        .0;
+    debug_assert_eq!(ctx.data(ffcmp_insn).opcode(), Opcode::Ffcmp);
+    let ty = ctx.input_ty(ffcmp_insn, 0);
+    let bit = lower_fcmp(
+        ctx,
+        cc,
+        InsnInput {
+            insn: ffcmp_insn,
+            input: 0,
+        },
+        InsnInput {
+            insn: ffcmp_insn,
+            input: 1,
+        },
+        ty,
+    );
+    IntegerCompare {
+        kind: IntCC::NotEqual,
+        rs1: bit,
+        rs2: zero_reg(),
+    }
+}
+
+/// This is target-word-size dependent.  And it excludes booleans and reftypes.
+pub(crate) fn is_valid_atomic_transaction_ty(ty: Type) -> bool {
+    match ty {
+        I8 | I16 | I32 | I64 => true,
+        _ => false,
+    }
+}
+
+//=============================================================================
+// Lowering-backend trait implementation.
+
+impl LowerBackend for Riscv64Backend {
+    type MInst = Inst;
+
+    fn lower<C: LowerCtx<I = Inst>>(&self, ctx: &mut C, ir_inst: IRInst) -> CodegenResult<()> {
+        // The vector extension is not supported yet; reject SIMD code before
+        // it reaches the scalar lowering rules.
+        let has_vector_type = (0..ctx.num_outputs(ir_inst))
+            .map(|i| ctx.output_ty(ir_inst, i))
+            .chain((0..ctx.num_inputs(ir_inst)).map(|i| ctx.input_ty(ir_inst, i)))
+            .any(|ty| ty.is_vector());
+        if has_vector_type {
+            return Err(CodegenError::Unsupported(format!(
+                "riscv64: SIMD instruction `{}` is not supported",
+                ctx.dfg().display_inst(ir_inst)
+            )));
+        }
+        lower_inst::lower_insn_to_regs(ctx, ir_inst, &self.flags, &self.isa_flags)
+    }
+
+    fn lower_branch_group<C: LowerCtx<I = Inst>>(
+        &self,
+        ctx: &mut C,
+        branches: &[IRInst],
+        targets: &[MachLabel],
+    ) -> CodegenResult<()> {
+        lower_inst::lower_branch(ctx, branches, targets)
+    }
+
+    fn maybe_pinned_reg(&self) -> Option<Reg> {
+        None
+    }
+}
@@ -0,0 +1,173 @@
+//! ISLE integration glue code for riscv64 lowering.
+
+// Pull in the ISLE generated code.
+pub mod generated_code;
+
+// Types that the generated ISLE code uses via `use super::*`.
+use super::{
+    zero_reg, AMode, BranchTarget, CallIndInfo, CallInfo, Imm12, Imm20, Inst as MInst,
+    IntegerCompare, JTSequenceInfo, Opcode, Reg,
+};
+use crate::isa::riscv64::settings::Flags;
+use crate::machinst::isle::*;
+use crate::{
+    binemit::CodeOffset,
+    ir::{
+        condcodes::IntCC, immediates::*, types::*, AtomicRmwOp, ExternalName, Inst,
+        InstructionData, MemFlags, TrapCode, Value, ValueLabel, ValueList,
+    },
+    isa::riscv64::inst::riscv64_map_regs,
+    isa::unwind::UnwindInst,
+    machinst::{InsnOutput, LowerCtx},
+};
+use std::boxed::Box;
+
+type BoxCallInfo = Box<CallInfo>;
+type BoxCallIndInfo = Box<CallIndInfo>;
+type BoxJTSequenceInfo = Box<JTSequenceInfo>;
+type BoxExternalName = Box<ExternalName>;
+
+/// The main entry point for lowering with ISLE.
+pub(crate) fn lower<C>(
+    lower_ctx: &mut C,
+    isa_flags: &Flags,
+    outputs: &[InsnOutput],
+    inst: Inst,
+) -> Result<(), ()>
+where
+    C: LowerCtx<I = MInst>,
+{
+    lower_common(
+        lower_ctx,
+        isa_flags,
+        outputs,
+        inst,
+        |cx, insn| generated_code::constructor_lower(cx, insn),
+        riscv64_map_regs,
+    )
+}
+
+impl<C> generated_code::Context for IsleContext<'_, C, Flags, 6>
+where
+    C: LowerCtx<I = MInst>,
+{
+    isle_prelude_methods!();
+
+    fn imm12_from_u64(&mut self, n: u64) -> Option<Imm12> {
+        Imm12::maybe_from_u64(n)
+    }
+
+    fn imm12_from_negated_u64(&mut self, n: u64) -> Option<Imm12> {
+        Imm12::maybe_from_i64((n as i64).wrapping_neg())
+    }
+
+    fn u8_into_imm12(&mut self, x: u8) -> Imm12 {
+        Imm12::maybe_from_u64(x.into()).unwrap()
+    }
+
+    fn u64_into_imm12(&mut self, x: u64) -> Imm12 {
+        Imm12::maybe_from_u64(x).unwrap()
+    }
+
+    fn imm12_const(&mut self, x: i16) -> Imm12 {
+        Imm12::maybe_from_i64(x.into()).unwrap()
+    }
+
+    fn shift_amount_from_value(&mut self, val: Value, ty: Type) -> Option<u8> {
+        let inst = self.lower_ctx.dfg().value_def(val).inst()?;
+        let constant = self.lower_ctx.get_constant(inst)?;
+        let ty_bits = u64::from(self.ty_bits(ty));
+        Some((constant & (ty_bits - 1)) as u8)
+    }
+
+    fn safe_divisor_from_imm64(&mut self, val: Imm64) -> Option<u64> {
+        match val.bits() {
+            0 | -1 => None,
+            n => Some(n as u64),
+        }
+    }
+
+    fn int_fits_in_64(&mut self, ty: Type) -> Option<Type> {
+        match ty {
+            I8 | I16 | I32 | I64 | R64 => Some(ty),
+            ty if ty.is_bool() && ty.bits() <= 64 => Some(ty),
+            _ => None,
+        }
+    }
+
+    fn ty_smin(&mut self, ty: Type) -> u64 {
+        let shift = 64 - self.ty_bits(ty);
+        (i64::MIN >> shift) as u64
+    }
+
+    fn has_zba(&mut self, _: Type) -> Option<()> {
+        if self.isa_flags.has_zba() {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn has_zbb(&mut self, _: Type) -> Option<()> {
+        if self.isa_flags.has_zbb() {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn emit(&mut self, inst: &MInst) -> Unit {
+        self.emitted_insts.push(inst.clone());
+    }
+
+    fn zero_reg(&mut self) -> Reg {
+        zero_reg()
+    }
+
+    fn int_compare(&mut self, kind: &IntCC, rs1: Reg, rs2: Reg) -> IntegerCompare {
+        IntegerCompare {
+            kind: *kind,
+            rs1,
+            rs2,
+        }
+    }
+
+    /// Load a constant of type `ty` into a new register.
+    ///
+    /// Integers are sign-extended from their width, which is always the
+    /// cheapest form to materialize as the upper bits are undefined. A true
+    /// boolean is 1 for `b1` and all ones for the wider boolean types.
+    fn imm(&mut self, ty: Type, value: u64) -> Reg {
+        let value = if ty.is_bool() {
+            match (value, ty) {
+                (0, _) => 0,
+                (_, B1) => 1,
+                _ => u64::MAX,
+            }
+        } else {
+            let shift = 64 - self.ty_bits(ty);
+            (((value << shift) as i64) >> shift) as u64
+        };
+        let rd = self.temp_writable_reg(I64);
+        for inst in MInst::load_constant(rd, value) {
+            self.emit(&inst);
+        }
+        self.writable_reg_to_reg(rd)
+    }
+
+    fn shift_mask(&mut self, ty: Type) -> u8 {
+        self.ty_bits(ty) - 1
+    }
+
+    fn rotl_to_rotr_amount(&mut self, ty: Type, amt: u8) -> u8 {
+        (self.ty_bits(ty) - amt) & (self.ty_bits(ty) - 1)
+    }
+
+    fn i64_sub_as_u64(&mut self, a: u8, b: u8) -> u64 {
+        (i64::from(a) - i64::from(b)) as u64
+    }
+
+    fn ty_bits_mask_plus_one(&mut self, ty: Type) -> u64 {
+        1u64 << self.ty_bits(ty)
+    }
+}
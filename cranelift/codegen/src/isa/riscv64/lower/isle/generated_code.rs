@@ -0,0 +1,3733 @@
+// GENERATED BY ISLE. DO NOT EDIT!
+//
+// Generated automatically from the instruction-selection DSL code in:
+// - src/clif.isle
+// - src/prelude.isle
+// - src/isa/riscv64/inst.isle
+// - src/isa/riscv64/lower.isle
+
+#![allow(dead_code, unreachable_code, unreachable_patterns)]
+#![allow(unused_imports, unused_variables, non_snake_case)]
+#![allow(irrefutable_let_patterns)]
+
+use super::*; // Pulls in all external types.
+
+/// Context during lowering: an implementation of this trait
+/// must be provided with all external constructors and extractors.
+/// A mutable borrow is passed along through all lowering logic.
+pub trait Context {
+    fn unpack_value_array_2(&mut self, arg0: &ValueArray2) -> (Value, Value);
+    fn pack_value_array_2(&mut self, arg0: Value, arg1: Value) -> ValueArray2;
+    fn unpack_value_array_3(&mut self, arg0: &ValueArray3) -> (Value, Value, Value);
+    fn pack_value_array_3(&mut self, arg0: Value, arg1: Value, arg2: Value) -> ValueArray3;
+    fn value_reg(&mut self, arg0: Reg) -> ValueRegs;
+    fn value_regs(&mut self, arg0: Reg, arg1: Reg) -> ValueRegs;
+    fn temp_writable_reg(&mut self, arg0: Type) -> WritableReg;
+    fn invalid_reg(&mut self) -> Reg;
+    fn put_in_reg(&mut self, arg0: Value) -> Reg;
+    fn put_in_regs(&mut self, arg0: Value) -> ValueRegs;
+    fn value_regs_get(&mut self, arg0: ValueRegs, arg1: usize) -> Reg;
+    fn u8_as_u64(&mut self, arg0: u8) -> u64;
+    fn u16_as_u64(&mut self, arg0: u16) -> u64;
+    fn u32_as_u64(&mut self, arg0: u32) -> u64;
+    fn ty_bits(&mut self, arg0: Type) -> u8;
+    fn ty_bits_u16(&mut self, arg0: Type) -> u16;
+    fn fits_in_16(&mut self, arg0: Type) -> Option<Type>;
+    fn fits_in_32(&mut self, arg0: Type) -> Option<Type>;
+    fn fits_in_64(&mut self, arg0: Type) -> Option<Type>;
+    fn ty_32_or_64(&mut self, arg0: Type) -> Option<Type>;
+    fn ty_8_or_16(&mut self, arg0: Type) -> Option<Type>;
+    fn vec128(&mut self, arg0: Type) -> Option<Type>;
+    fn not_i64x2(&mut self, arg0: Type) -> Option<()>;
+    fn value_list_slice(&mut self, arg0: ValueList) -> ValueSlice;
+    fn unwrap_head_value_list_1(&mut self, arg0: ValueList) -> (Value, ValueSlice);
+    fn unwrap_head_value_list_2(&mut self, arg0: ValueList) -> (Value, Value, ValueSlice);
+    fn writable_reg_to_reg(&mut self, arg0: WritableReg) -> Reg;
+    fn u8_from_uimm8(&mut self, arg0: Uimm8) -> u8;
+    fn u64_from_imm64(&mut self, arg0: Imm64) -> u64;
+    fn nonzero_u64_from_imm64(&mut self, arg0: Imm64) -> Option<u64>;
+    fn u64_from_ieee32(&mut self, arg0: Ieee32) -> u64;
+    fn u64_from_ieee64(&mut self, arg0: Ieee64) -> u64;
+    fn inst_results(&mut self, arg0: Inst) -> ValueSlice;
+    fn first_result(&mut self, arg0: Inst) -> Option<Value>;
+    fn inst_data(&mut self, arg0: Inst) -> InstructionData;
+    fn value_type(&mut self, arg0: Value) -> Type;
+    fn ty_bits_mask(&mut self, arg0: Type) -> u64;
+    fn multi_lane(&mut self, arg0: Type) -> Option<(u8, u16)>;
+    fn def_inst(&mut self, arg0: Value) -> Option<Inst>;
+    fn trap_code_division_by_zero(&mut self) -> TrapCode;
+    fn trap_code_integer_overflow(&mut self) -> TrapCode;
+    fn imm12_from_u64(&mut self, arg0: u64) -> Option<Imm12>;
+    fn imm12_from_negated_u64(&mut self, arg0: u64) -> Option<Imm12>;
+    fn u8_into_imm12(&mut self, arg0: u8) -> Imm12;
+    fn u64_into_imm12(&mut self, arg0: u64) -> Imm12;
+    fn imm12_const(&mut self, arg0: i16) -> Imm12;
+    fn shift_amount_from_value(&mut self, arg0: Value, arg1: Type) -> Option<u8>;
+    fn safe_divisor_from_imm64(&mut self, arg0: Imm64) -> Option<u64>;
+    fn int_fits_in_64(&mut self, arg0: Type) -> Option<Type>;
+    fn ty_smin(&mut self, arg0: Type) -> u64;
+    fn has_zba(&mut self, arg0: Type) -> Option<()>;
+    fn has_zbb(&mut self, arg0: Type) -> Option<()>;
+    fn emit(&mut self, arg0: &MInst) -> Unit;
+    fn zero_reg(&mut self) -> Reg;
+    fn int_compare(&mut self, arg0: &IntCC, arg1: Reg, arg2: Reg) -> IntegerCompare;
+    fn imm(&mut self, arg0: Type, arg1: u64) -> Reg;
+    fn shift_mask(&mut self, arg0: Type) -> u8;
+    fn rotl_to_rotr_amount(&mut self, arg0: Type, arg1: u8) -> u8;
+    fn i64_sub_as_u64(&mut self, arg0: u8, arg1: u8) -> u64;
+    fn ty_bits_mask_plus_one(&mut self, arg0: Type) -> u64;
+}
+
+/// Internal type ProducesFlags: defined at src/prelude.isle line 263.
+#[derive(Clone, Debug)]
+pub enum ProducesFlags {
+    ProducesFlags { inst: MInst, result: Reg },
+}
+
+/// Internal type ConsumesFlags: defined at src/prelude.isle line 266.
+#[derive(Clone, Debug)]
+pub enum ConsumesFlags {
+    ConsumesFlags { inst: MInst, result: Reg },
+}
+
+/// Internal type MInst: defined at src/isa/riscv64/inst.isle line 2.
+#[derive(Clone, Debug)]
+pub enum MInst {
+    Nop0,
+    Nop4,
+    Lui {
+        rd: WritableReg,
+        imm: Imm20,
+    },
+    LoadConst64 {
+        rd: WritableReg,
+        imm: u64,
+    },
+    AluRRR {
+        alu_op: AluOPRRR,
+        rd: WritableReg,
+        rs1: Reg,
+        rs2: Reg,
+    },
+    AluRRImm12 {
+        alu_op: AluOPRRI,
+        rd: WritableReg,
+        rs: Reg,
+        imm12: Imm12,
+    },
+    AluRR {
+        alu_op: AluOPRR,
+        rd: WritableReg,
+        rs: Reg,
+    },
+    FpuRR {
+        alu_op: FpuOPRR,
+        rd: WritableReg,
+        rs: Reg,
+    },
+    FpuRRR {
+        alu_op: FpuOPRRR,
+        rd: WritableReg,
+        rs1: Reg,
+        rs2: Reg,
+    },
+    FpuRRRR {
+        alu_op: FpuOPRRRR,
+        rd: WritableReg,
+        rs1: Reg,
+        rs2: Reg,
+        rs3: Reg,
+    },
+    Load {
+        rd: WritableReg,
+        op: LoadOP,
+        flags: MemFlags,
+        from: AMode,
+    },
+    Store {
+        to: AMode,
+        op: StoreOP,
+        flags: MemFlags,
+        src: Reg,
+    },
+    Mov {
+        rd: WritableReg,
+        rm: Reg,
+        ty: Type,
+    },
+    Extend {
+        rd: WritableReg,
+        rn: Reg,
+        signed: bool,
+        from_bits: u8,
+        to_bits: u8,
+    },
+    Select {
+        rd: WritableReg,
+        cond: Reg,
+        x: Reg,
+        y: Reg,
+        ty: Type,
+    },
+    FcvtToInt {
+        is_sat: bool,
+        is_signed: bool,
+        in_ty: Type,
+        out_ty: Type,
+        rd: WritableReg,
+        rs: Reg,
+    },
+    FloatRound {
+        op: FloatRoundOP,
+        ty: Type,
+        rd: WritableReg,
+        rs: Reg,
+    },
+    FloatMinMax {
+        is_max: bool,
+        ty: Type,
+        rd: WritableReg,
+        rs1: Reg,
+        rs2: Reg,
+    },
+    Ret,
+    EpiloguePlaceholder,
+    Call {
+        info: BoxCallInfo,
+    },
+    CallInd {
+        info: BoxCallIndInfo,
+    },
+    Jal {
+        dest: BranchTarget,
+    },
+    CondBr {
+        taken: BranchTarget,
+        not_taken: BranchTarget,
+        kind: IntegerCompare,
+    },
+    JTSequence {
+        index: Reg,
+        info: BoxJTSequenceInfo,
+    },
+    TrapIf {
+        kind: IntegerCompare,
+        trap_code: TrapCode,
+    },
+    Udf {
+        trap_code: TrapCode,
+    },
+    EBreak,
+    LoadExtName {
+        rd: WritableReg,
+        name: BoxExternalName,
+        offset: i64,
+    },
+    LoadAddr {
+        rd: WritableReg,
+        mem: AMode,
+    },
+    VirtualSPOffsetAdj {
+        amount: i64,
+    },
+    Fence {
+        pred: u8,
+        succ: u8,
+    },
+    Atomic {
+        op: AtomicOP,
+        rd: WritableReg,
+        addr: Reg,
+        src: Reg,
+    },
+    AtomicRmwLoop {
+        op: AtomicRmwOp,
+        ty: Type,
+        dst: WritableReg,
+        addr: Reg,
+        src: Reg,
+        shift: Reg,
+        mask: Reg,
+    },
+    AtomicCas {
+        ty: Type,
+        dst: WritableReg,
+        addr: Reg,
+        expected: Reg,
+        replacement: Reg,
+        shift: Reg,
+        mask: Reg,
+    },
+    EmitIsland {
+        needed_space: CodeOffset,
+    },
+    ValueLabelMarker {
+        reg: Reg,
+        label: ValueLabel,
+    },
+    Unwind {
+        inst: UnwindInst,
+    },
+}
+
+/// Internal type AluOPRRR: defined at src/isa/riscv64/inst.isle line 272.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AluOPRRR {
+    Add,
+    Sub,
+    Sll,
+    Slt,
+    Sltu,
+    Xor,
+    Srl,
+    Sra,
+    Or,
+    And,
+    Addw,
+    Subw,
+    Sllw,
+    Srlw,
+    Sraw,
+    Mul,
+    Mulh,
+    Mulhsu,
+    Mulhu,
+    Div,
+    Divu,
+    Rem,
+    Remu,
+    Mulw,
+    Divw,
+    Divuw,
+    Remw,
+    Remuw,
+    AddUw,
+    Andn,
+    Orn,
+    Xnor,
+    Max,
+    Maxu,
+    Min,
+    Minu,
+    Rol,
+    Rolw,
+    Ror,
+    Rorw,
+}
+
+/// Internal type AluOPRRI: defined at src/isa/riscv64/inst.isle line 321.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AluOPRRI {
+    Addi,
+    Slti,
+    Sltiu,
+    Xori,
+    Ori,
+    Andi,
+    Slli,
+    Srli,
+    Srai,
+    Addiw,
+    Slliw,
+    Srliw,
+    Sraiw,
+    Rori,
+    Roriw,
+}
+
+/// Internal type AluOPRR: defined at src/isa/riscv64/inst.isle line 343.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AluOPRR {
+    Clz,
+    Clzw,
+    Ctz,
+    Ctzw,
+    Cpop,
+    Cpopw,
+    SextB,
+    SextH,
+    ZextH,
+    Rev8,
+}
+
+/// Internal type FpuOPRR: defined at src/isa/riscv64/inst.isle line 358.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FpuOPRR {
+    FsqrtS,
+    FsqrtD,
+    FcvtSD,
+    FcvtDS,
+    FcvtWS,
+    FcvtWuS,
+    FcvtLS,
+    FcvtLuS,
+    FcvtWD,
+    FcvtWuD,
+    FcvtLD,
+    FcvtLuD,
+    FcvtSW,
+    FcvtSWu,
+    FcvtSL,
+    FcvtSLu,
+    FcvtDW,
+    FcvtDWu,
+    FcvtDL,
+    FcvtDLu,
+    FmvXW,
+    FmvXD,
+    FmvWX,
+    FmvDX,
+    FclassS,
+    FclassD,
+}
+
+/// Internal type FpuOPRRR: defined at src/isa/riscv64/inst.isle line 393.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FpuOPRRR {
+    FaddS,
+    FsubS,
+    FmulS,
+    FdivS,
+    FsgnjS,
+    FsgnjnS,
+    FsgnjxS,
+    FminS,
+    FmaxS,
+    FeqS,
+    FltS,
+    FleS,
+    FaddD,
+    FsubD,
+    FmulD,
+    FdivD,
+    FsgnjD,
+    FsgnjnD,
+    FsgnjxD,
+    FminD,
+    FmaxD,
+    FeqD,
+    FltD,
+    FleD,
+}
+
+/// Internal type FpuOPRRRR: defined at src/isa/riscv64/inst.isle line 422.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FpuOPRRRR {
+    FmaddS,
+    FmsubS,
+    FnmsubS,
+    FnmaddS,
+    FmaddD,
+    FmsubD,
+    FnmsubD,
+    FnmaddD,
+}
+
+/// Internal type FloatRoundOP: defined at src/isa/riscv64/inst.isle line 435.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FloatRoundOP {
+    Ceil,
+    Floor,
+    Trunc,
+    Nearest,
+}
+
+/// Internal type LoadOP: defined at src/isa/riscv64/inst.isle line 444.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LoadOP {
+    Lb,
+    Lh,
+    Lw,
+    Lbu,
+    Lhu,
+    Lwu,
+    Ld,
+    Flw,
+    Fld,
+}
+
+/// Internal type StoreOP: defined at src/isa/riscv64/inst.isle line 458.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StoreOP {
+    Sb,
+    Sh,
+    Sw,
+    Sd,
+    Fsw,
+    Fsd,
+}
+
+/// Internal type AtomicOP: defined at src/isa/riscv64/inst.isle line 469.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AtomicOP {
+    AmoswapW,
+    AmoaddW,
+    AmoxorW,
+    AmoandW,
+    AmoorW,
+    AmominW,
+    AmomaxW,
+    AmominuW,
+    AmomaxuW,
+    AmoswapD,
+    AmoaddD,
+    AmoxorD,
+    AmoandD,
+    AmoorD,
+    AmominD,
+    AmomaxD,
+    AmominuD,
+    AmomaxuD,
+}
+
+// Generated as internal constructor for term temp_reg.
+pub fn constructor_temp_reg<C: Context>(ctx: &mut C, arg0: Type) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    // Rule at src/prelude.isle line 60.
+    let expr0_0 = C::temp_writable_reg(ctx, pattern0_0);
+    let expr1_0 = C::writable_reg_to_reg(ctx, expr0_0);
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term lo_reg.
+pub fn constructor_lo_reg<C: Context>(ctx: &mut C, arg0: Value) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    // Rule at src/prelude.isle line 95.
+    let expr0_0 = C::put_in_regs(ctx, pattern0_0);
+    let expr1_0: usize = 0;
+    let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term with_flags.
+pub fn constructor_with_flags<C: Context>(
+    ctx: &mut C,
+    arg0: &ProducesFlags,
+    arg1: &ConsumesFlags,
+) -> Option<ValueRegs> {
+    let pattern0_0 = arg0;
+    if let &ProducesFlags::ProducesFlags {
+        inst: ref pattern1_0,
+        result: pattern1_1,
+    } = pattern0_0
+    {
+        let pattern2_0 = arg1;
+        if let &ConsumesFlags::ConsumesFlags {
+            inst: ref pattern3_0,
+            result: pattern3_1,
+        } = pattern2_0
+        {
+            // Rule at src/prelude.isle line 276.
+            let expr0_0 = C::emit(ctx, &pattern1_0);
+            let expr1_0 = C::emit(ctx, &pattern3_0);
+            let expr2_0 = C::value_regs(ctx, pattern1_1, pattern3_1);
+            return Some(expr2_0);
+        }
+    }
+    return None;
+}
+
+// Generated as internal constructor for term with_flags_1.
+pub fn constructor_with_flags_1<C: Context>(
+    ctx: &mut C,
+    arg0: &ProducesFlags,
+    arg1: &ConsumesFlags,
+) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    if let &ProducesFlags::ProducesFlags {
+        inst: ref pattern1_0,
+        result: pattern1_1,
+    } = pattern0_0
+    {
+        let pattern2_0 = arg1;
+        if let &ConsumesFlags::ConsumesFlags {
+            inst: ref pattern3_0,
+            result: pattern3_1,
+        } = pattern2_0
+        {
+            // Rule at src/prelude.isle line 284.
+            let expr0_0 = C::emit(ctx, &pattern1_0);
+            let expr1_0 = C::emit(ctx, &pattern3_0);
+            return Some(pattern3_1);
+        }
+    }
+    return None;
+}
+
+// Generated as internal constructor for term with_flags_2.
+pub fn constructor_with_flags_2<C: Context>(
+    ctx: &mut C,
+    arg0: &ProducesFlags,
+    arg1: &ConsumesFlags,
+    arg2: &ConsumesFlags,
+) -> Option<ValueRegs> {
+    let pattern0_0 = arg0;
+    if let &ProducesFlags::ProducesFlags {
+        inst: ref pattern1_0,
+        result: pattern1_1,
+    } = pattern0_0
+    {
+        let pattern2_0 = arg1;
+        if let &ConsumesFlags::ConsumesFlags {
+            inst: ref pattern3_0,
+            result: pattern3_1,
+        } = pattern2_0
+        {
+            let pattern4_0 = arg2;
+            if let &ConsumesFlags::ConsumesFlags {
+                inst: ref pattern5_0,
+                result: pattern5_1,
+            } = pattern4_0
+            {
+                // Rule at src/prelude.isle line 294.
+                let expr0_0 = C::emit(ctx, &pattern1_0);
+                let expr1_0 = C::emit(ctx, &pattern5_0);
+                let expr2_0 = C::emit(ctx, &pattern3_0);
+                let expr3_0 = C::value_regs(ctx, pattern3_1, pattern5_1);
+                return Some(expr3_0);
+            }
+        }
+    }
+    return None;
+}
+
+// Generated as internal constructor for term alu_rrr.
+pub fn constructor_alu_rrr<C: Context>(
+    ctx: &mut C,
+    arg0: &AluOPRRR,
+    arg1: Reg,
+    arg2: Reg,
+) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/isa/riscv64/inst.isle line 582.
+    let expr0_0: Type = I64;
+    let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
+    let expr2_0 = MInst::AluRRR {
+        alu_op: pattern0_0.clone(),
+        rd: expr1_0,
+        rs1: pattern1_0,
+        rs2: pattern2_0,
+    };
+    let expr3_0 = C::emit(ctx, &expr2_0);
+    let expr4_0 = C::writable_reg_to_reg(ctx, expr1_0);
+    return Some(expr4_0);
+}
+
+// Generated as internal constructor for term alu_rr_imm12.
+pub fn constructor_alu_rr_imm12<C: Context>(
+    ctx: &mut C,
+    arg0: &AluOPRRI,
+    arg1: Reg,
+    arg2: Imm12,
+) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/isa/riscv64/inst.isle line 589.
+    let expr0_0: Type = I64;
+    let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
+    let expr2_0 = MInst::AluRRImm12 {
+        alu_op: pattern0_0.clone(),
+        rd: expr1_0,
+        rs: pattern1_0,
+        imm12: pattern2_0,
+    };
+    let expr3_0 = C::emit(ctx, &expr2_0);
+    let expr4_0 = C::writable_reg_to_reg(ctx, expr1_0);
+    return Some(expr4_0);
+}
+
+// Generated as internal constructor for term alu_rr.
+pub fn constructor_alu_rr<C: Context>(ctx: &mut C, arg0: &AluOPRR, arg1: Reg) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 596.
+    let expr0_0: Type = I64;
+    let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
+    let expr2_0 = MInst::AluRR {
+        alu_op: pattern0_0.clone(),
+        rd: expr1_0,
+        rs: pattern1_0,
+    };
+    let expr3_0 = C::emit(ctx, &expr2_0);
+    let expr4_0 = C::writable_reg_to_reg(ctx, expr1_0);
+    return Some(expr4_0);
+}
+
+// Generated as internal constructor for term fpu_rr.
+pub fn constructor_fpu_rr<C: Context>(
+    ctx: &mut C,
+    arg0: &FpuOPRR,
+    arg1: Type,
+    arg2: Reg,
+) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/isa/riscv64/inst.isle line 604.
+    let expr0_0 = C::temp_writable_reg(ctx, pattern1_0);
+    let expr1_0 = MInst::FpuRR {
+        alu_op: pattern0_0.clone(),
+        rd: expr0_0,
+        rs: pattern2_0,
+    };
+    let expr2_0 = C::emit(ctx, &expr1_0);
+    let expr3_0 = C::writable_reg_to_reg(ctx, expr0_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term fpu_rrr.
+pub fn constructor_fpu_rrr<C: Context>(
+    ctx: &mut C,
+    arg0: &FpuOPRRR,
+    arg1: Type,
+    arg2: Reg,
+    arg3: Reg,
+) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/isa/riscv64/inst.isle line 612.
+    let expr0_0 = C::temp_writable_reg(ctx, pattern1_0);
+    let expr1_0 = MInst::FpuRRR {
+        alu_op: pattern0_0.clone(),
+        rd: expr0_0,
+        rs1: pattern2_0,
+        rs2: pattern3_0,
+    };
+    let expr2_0 = C::emit(ctx, &expr1_0);
+    let expr3_0 = C::writable_reg_to_reg(ctx, expr0_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term fpu_rrrr.
+pub fn constructor_fpu_rrrr<C: Context>(
+    ctx: &mut C,
+    arg0: &FpuOPRRRR,
+    arg1: Type,
+    arg2: Reg,
+    arg3: Reg,
+    arg4: Reg,
+) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    let pattern4_0 = arg4;
+    // Rule at src/isa/riscv64/inst.isle line 619.
+    let expr0_0 = C::temp_writable_reg(ctx, pattern1_0);
+    let expr1_0 = MInst::FpuRRRR {
+        alu_op: pattern0_0.clone(),
+        rd: expr0_0,
+        rs1: pattern2_0,
+        rs2: pattern3_0,
+        rs3: pattern4_0,
+    };
+    let expr2_0 = C::emit(ctx, &expr1_0);
+    let expr3_0 = C::writable_reg_to_reg(ctx, expr0_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term extend.
+pub fn constructor_extend<C: Context>(
+    ctx: &mut C,
+    arg0: Reg,
+    arg1: bool,
+    arg2: u8,
+    arg3: u8,
+) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/isa/riscv64/inst.isle line 626.
+    let expr0_0: Type = I64;
+    let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
+    let expr2_0 = MInst::Extend {
+        rd: expr1_0,
+        rn: pattern0_0,
+        signed: pattern1_0,
+        from_bits: pattern2_0,
+        to_bits: pattern3_0,
+    };
+    let expr3_0 = C::emit(ctx, &expr2_0);
+    let expr4_0 = C::writable_reg_to_reg(ctx, expr1_0);
+    return Some(expr4_0);
+}
+
+// Generated as internal constructor for term select_reg.
+pub fn constructor_select_reg<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Reg,
+    arg2: Reg,
+    arg3: Reg,
+) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/isa/riscv64/inst.isle line 633.
+    let expr0_0 = C::temp_writable_reg(ctx, pattern0_0);
+    let expr1_0 = MInst::Select {
+        rd: expr0_0,
+        cond: pattern1_0,
+        x: pattern2_0,
+        y: pattern3_0,
+        ty: pattern0_0,
+    };
+    let expr2_0 = C::emit(ctx, &expr1_0);
+    let expr3_0 = C::writable_reg_to_reg(ctx, expr0_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term fcvt_to_int.
+pub fn constructor_fcvt_to_int<C: Context>(
+    ctx: &mut C,
+    arg0: bool,
+    arg1: bool,
+    arg2: Type,
+    arg3: Type,
+    arg4: Reg,
+) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    let pattern4_0 = arg4;
+    // Rule at src/isa/riscv64/inst.isle line 640.
+    let expr0_0: Type = I64;
+    let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
+    let expr2_0 = MInst::FcvtToInt {
+        is_sat: pattern0_0,
+        is_signed: pattern1_0,
+        in_ty: pattern2_0,
+        out_ty: pattern3_0,
+        rd: expr1_0,
+        rs: pattern4_0,
+    };
+    let expr3_0 = C::emit(ctx, &expr2_0);
+    let expr4_0 = C::writable_reg_to_reg(ctx, expr1_0);
+    return Some(expr4_0);
+}
+
+// Generated as internal constructor for term float_round.
+pub fn constructor_float_round<C: Context>(
+    ctx: &mut C,
+    arg0: &FloatRoundOP,
+    arg1: Type,
+    arg2: Reg,
+) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/isa/riscv64/inst.isle line 647.
+    let expr0_0 = C::temp_writable_reg(ctx, pattern1_0);
+    let expr1_0 = MInst::FloatRound {
+        op: pattern0_0.clone(),
+        ty: pattern1_0,
+        rd: expr0_0,
+        rs: pattern2_0,
+    };
+    let expr2_0 = C::emit(ctx, &expr1_0);
+    let expr3_0 = C::writable_reg_to_reg(ctx, expr0_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term float_min_max.
+pub fn constructor_float_min_max<C: Context>(
+    ctx: &mut C,
+    arg0: bool,
+    arg1: Type,
+    arg2: Reg,
+    arg3: Reg,
+) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/isa/riscv64/inst.isle line 654.
+    let expr0_0 = C::temp_writable_reg(ctx, pattern1_0);
+    let expr1_0 = MInst::FloatMinMax {
+        is_max: pattern0_0,
+        ty: pattern1_0,
+        rd: expr0_0,
+        rs1: pattern2_0,
+        rs2: pattern3_0,
+    };
+    let expr2_0 = C::emit(ctx, &expr1_0);
+    let expr3_0 = C::writable_reg_to_reg(ctx, expr0_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term trap_if.
+pub fn constructor_trap_if<C: Context>(
+    ctx: &mut C,
+    arg0: &IntCC,
+    arg1: Reg,
+    arg2: Reg,
+    arg3: &TrapCode,
+) -> Option<Unit> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/isa/riscv64/inst.isle line 661.
+    let expr0_0 = C::int_compare(ctx, pattern0_0, pattern1_0, pattern2_0);
+    let expr1_0 = MInst::TrapIf {
+        kind: expr0_0,
+        trap_code: pattern3_0.clone(),
+    };
+    let expr2_0 = C::emit(ctx, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term trap_if_zero.
+pub fn constructor_trap_if_zero<C: Context>(
+    ctx: &mut C,
+    arg0: Reg,
+    arg1: &TrapCode,
+) -> Option<Unit> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 666.
+    let expr0_0 = IntCC::Equal;
+    let expr1_0 = C::zero_reg(ctx);
+    let expr2_0 = constructor_trap_if(ctx, &expr0_0, pattern0_0, expr1_0, pattern1_0)?;
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term add.
+pub fn constructor_add<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 672.
+    let expr0_0 = AluOPRRR::Add;
+    let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term addw.
+pub fn constructor_addw<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 675.
+    let expr0_0 = AluOPRRR::Addw;
+    let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term sub.
+pub fn constructor_sub<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 678.
+    let expr0_0 = AluOPRRR::Sub;
+    let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term subw.
+pub fn constructor_subw<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 681.
+    let expr0_0 = AluOPRRR::Subw;
+    let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term sltu.
+pub fn constructor_sltu<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 684.
+    let expr0_0 = AluOPRRR::Sltu;
+    let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term mul.
+pub fn constructor_mul<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 687.
+    let expr0_0 = AluOPRRR::Mul;
+    let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term mulhu.
+pub fn constructor_mulhu<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 690.
+    let expr0_0 = AluOPRRR::Mulhu;
+    let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term or.
+pub fn constructor_or<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 693.
+    let expr0_0 = AluOPRRR::Or;
+    let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term xor.
+pub fn constructor_xor<C: Context>(ctx: &mut C, arg0: Reg, arg1: Reg) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 696.
+    let expr0_0 = AluOPRRR::Xor;
+    let expr1_0 = constructor_alu_rrr(ctx, &expr0_0, pattern0_0, pattern1_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term addi.
+pub fn constructor_addi<C: Context>(ctx: &mut C, arg0: Reg, arg1: Imm12) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 699.
+    let expr0_0 = AluOPRRI::Addi;
+    let expr1_0 = constructor_alu_rr_imm12(ctx, &expr0_0, pattern0_0, pattern1_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term andi.
+pub fn constructor_andi<C: Context>(ctx: &mut C, arg0: Reg, arg1: Imm12) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 702.
+    let expr0_0 = AluOPRRI::Andi;
+    let expr1_0 = constructor_alu_rr_imm12(ctx, &expr0_0, pattern0_0, pattern1_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term xori.
+pub fn constructor_xori<C: Context>(ctx: &mut C, arg0: Reg, arg1: Imm12) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 705.
+    let expr0_0 = AluOPRRI::Xori;
+    let expr1_0 = constructor_alu_rr_imm12(ctx, &expr0_0, pattern0_0, pattern1_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term slli.
+pub fn constructor_slli<C: Context>(ctx: &mut C, arg0: Reg, arg1: u8) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 708.
+    let expr0_0 = AluOPRRI::Slli;
+    let expr1_0 = C::u8_into_imm12(ctx, pattern1_0);
+    let expr2_0 = constructor_alu_rr_imm12(ctx, &expr0_0, pattern0_0, expr1_0)?;
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term srli.
+pub fn constructor_srli<C: Context>(ctx: &mut C, arg0: Reg, arg1: u8) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 711.
+    let expr0_0 = AluOPRRI::Srli;
+    let expr1_0 = C::u8_into_imm12(ctx, pattern1_0);
+    let expr2_0 = constructor_alu_rr_imm12(ctx, &expr0_0, pattern0_0, expr1_0)?;
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term srai.
+pub fn constructor_srai<C: Context>(ctx: &mut C, arg0: Reg, arg1: u8) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/inst.isle line 714.
+    let expr0_0 = AluOPRRI::Srai;
+    let expr1_0 = C::u8_into_imm12(ctx, pattern1_0);
+    let expr2_0 = constructor_alu_rr_imm12(ctx, &expr0_0, pattern0_0, expr1_0)?;
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term bnot_reg.
+pub fn constructor_bnot_reg<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    // Rule at src/isa/riscv64/inst.isle line 718.
+    let expr0_0 = AluOPRRI::Xori;
+    let expr1_0: i16 = -1;
+    let expr2_0 = C::imm12_const(ctx, expr1_0);
+    let expr3_0 = constructor_alu_rr_imm12(ctx, &expr0_0, pattern0_0, expr2_0)?;
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term neg.
+pub fn constructor_neg<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    // Rule at src/isa/riscv64/inst.isle line 722.
+    let expr0_0 = C::zero_reg(ctx);
+    let expr1_0 = constructor_sub(ctx, expr0_0, pattern0_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term put_in_reg_sext64.
+pub fn constructor_put_in_reg_sext64<C: Context>(ctx: &mut C, arg0: Value) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = C::value_type(ctx, pattern0_0);
+    if pattern1_0 == I64 {
+        // Rule at src/isa/riscv64/inst.isle line 730.
+        let expr0_0 = C::put_in_reg(ctx, pattern0_0);
+        return Some(expr0_0);
+    }
+    if let Some(pattern2_0) = C::fits_in_32(ctx, pattern1_0) {
+        // Rule at src/isa/riscv64/inst.isle line 728.
+        let expr0_0 = C::put_in_reg(ctx, pattern0_0);
+        let expr1_0: bool = true;
+        let expr2_0 = C::ty_bits(ctx, pattern2_0);
+        let expr3_0: u8 = 64;
+        let expr4_0 = constructor_extend(ctx, expr0_0, expr1_0, expr2_0, expr3_0)?;
+        return Some(expr4_0);
+    }
+    return None;
+}
+
+// Generated as internal constructor for term put_in_reg_zext64.
+pub fn constructor_put_in_reg_zext64<C: Context>(ctx: &mut C, arg0: Value) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = C::value_type(ctx, pattern0_0);
+    if pattern1_0 == I64 {
+        // Rule at src/isa/riscv64/inst.isle line 736.
+        let expr0_0 = C::put_in_reg(ctx, pattern0_0);
+        return Some(expr0_0);
+    }
+    if let Some(pattern2_0) = C::fits_in_32(ctx, pattern1_0) {
+        // Rule at src/isa/riscv64/inst.isle line 734.
+        let expr0_0 = C::put_in_reg(ctx, pattern0_0);
+        let expr1_0: bool = false;
+        let expr2_0 = C::ty_bits(ctx, pattern2_0);
+        let expr3_0: u8 = 64;
+        let expr4_0 = constructor_extend(ctx, expr0_0, expr1_0, expr2_0, expr3_0)?;
+        return Some(expr4_0);
+    }
+    return None;
+}
+
+// Generated as internal constructor for term put_in_reg_ext64.
+pub fn constructor_put_in_reg_ext64<C: Context>(
+    ctx: &mut C,
+    arg0: bool,
+    arg1: Value,
+) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    if pattern0_0 == true {
+        let pattern2_0 = arg1;
+        // Rule at src/isa/riscv64/inst.isle line 740.
+        let expr0_0 = constructor_put_in_reg_sext64(ctx, pattern2_0)?;
+        return Some(expr0_0);
+    }
+    if pattern0_0 == false {
+        let pattern2_0 = arg1;
+        // Rule at src/isa/riscv64/inst.isle line 741.
+        let expr0_0 = constructor_put_in_reg_zext64(ctx, pattern2_0)?;
+        return Some(expr0_0);
+    }
+    return None;
+}
+
+// Generated as internal constructor for term trap_if_zero_divisor.
+pub fn constructor_trap_if_zero_divisor<C: Context>(ctx: &mut C, arg0: Reg) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    // Rule at src/isa/riscv64/inst.isle line 751.
+    let expr0_0 = C::trap_code_division_by_zero(ctx);
+    let expr1_0 = constructor_trap_if_zero(ctx, pattern0_0, &expr0_0)?;
+    return Some(pattern0_0);
+}
+
+// Generated as internal constructor for term trap_if_div_overflow.
+pub fn constructor_trap_if_div_overflow<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Reg,
+    arg2: Reg,
+) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/isa/riscv64/inst.isle line 759.
+    let expr0_0: u8 = 1;
+    let expr1_0 = C::u8_into_imm12(ctx, expr0_0);
+    let expr2_0 = constructor_addi(ctx, pattern2_0, expr1_0)?;
+    let expr3_0 = C::ty_smin(ctx, pattern0_0);
+    let expr4_0 = C::imm(ctx, pattern0_0, expr3_0);
+    let expr5_0 = constructor_xor(ctx, pattern1_0, expr4_0)?;
+    let expr6_0 = constructor_or(ctx, expr2_0, expr5_0)?;
+    let expr7_0 = C::trap_code_integer_overflow(ctx);
+    let expr8_0 = constructor_trap_if_zero(ctx, expr6_0, &expr7_0)?;
+    return Some(pattern1_0);
+}
+
+// Generated as internal constructor for term lower.
+pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueRegs> {
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if pattern2_0 == I32 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Ushr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        let closure8 = || {
+                            let expr0_0: Type = I32;
+                            return Some(expr0_0);
+                        };
+                        if let Some(pattern8_0) = closure8() {
+                            if let Some(pattern9_0) =
+                                C::shift_amount_from_value(ctx, pattern7_1, pattern8_0)
+                            {
+                                // Rule at src/isa/riscv64/lower.isle line 402.
+                                let expr0_0 = AluOPRRI::Srliw;
+                                let expr1_0 = C::put_in_reg(ctx, pattern7_0);
+                                let expr2_0 = C::u8_into_imm12(ctx, pattern9_0);
+                                let expr3_0 =
+                                    constructor_alu_rr_imm12(ctx, &expr0_0, expr1_0, expr2_0)?;
+                                let expr4_0 = C::value_reg(ctx, expr3_0);
+                                return Some(expr4_0);
+                            }
+                        }
+                    }
+                    &Opcode::Sshr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        let closure8 = || {
+                            let expr0_0: Type = I32;
+                            return Some(expr0_0);
+                        };
+                        if let Some(pattern8_0) = closure8() {
+                            if let Some(pattern9_0) =
+                                C::shift_amount_from_value(ctx, pattern7_1, pattern8_0)
+                            {
+                                // Rule at src/isa/riscv64/lower.isle line 422.
+                                let expr0_0 = AluOPRRI::Sraiw;
+                                let expr1_0 = C::put_in_reg(ctx, pattern7_0);
+                                let expr2_0 = C::u8_into_imm12(ctx, pattern9_0);
+                                let expr3_0 =
+                                    constructor_alu_rr_imm12(ctx, &expr0_0, expr1_0, expr2_0)?;
+                                let expr4_0 = C::value_reg(ctx, expr3_0);
+                                return Some(expr4_0);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if pattern2_0 == I64 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Ushr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        let closure8 = || {
+                            let expr0_0: Type = I64;
+                            return Some(expr0_0);
+                        };
+                        if let Some(pattern8_0) = closure8() {
+                            if let Some(pattern9_0) =
+                                C::shift_amount_from_value(ctx, pattern7_1, pattern8_0)
+                            {
+                                // Rule at src/isa/riscv64/lower.isle line 399.
+                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                let expr1_0 = constructor_srli(ctx, expr0_0, pattern9_0)?;
+                                let expr2_0 = C::value_reg(ctx, expr1_0);
+                                return Some(expr2_0);
+                            }
+                        }
+                    }
+                    &Opcode::Sshr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        let closure8 = || {
+                            let expr0_0: Type = I64;
+                            return Some(expr0_0);
+                        };
+                        if let Some(pattern8_0) = closure8() {
+                            if let Some(pattern9_0) =
+                                C::shift_amount_from_value(ctx, pattern7_1, pattern8_0)
+                            {
+                                // Rule at src/isa/riscv64/lower.isle line 419.
+                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                let expr1_0 = constructor_srai(ctx, expr0_0, pattern9_0)?;
+                                let expr2_0 = C::value_reg(ctx, expr1_0);
+                                return Some(expr2_0);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(()) = C::has_zbb(ctx, pattern2_0) {
+            if pattern2_0 == I32 {
+                let pattern5_0 = C::inst_data(ctx, pattern0_0);
+                if let &InstructionData::Binary {
+                    opcode: ref pattern6_0,
+                    args: ref pattern6_1,
+                } = &pattern5_0
+                {
+                    match &pattern6_0 {
+                        &Opcode::Rotl => {
+                            let (pattern8_0, pattern8_1) =
+                                C::unpack_value_array_2(ctx, &pattern6_1);
+                            let closure9 = || {
+                                let expr0_0: Type = I32;
+                                return Some(expr0_0);
+                            };
+                            if let Some(pattern9_0) = closure9() {
+                                if let Some(pattern10_0) =
+                                    C::shift_amount_from_value(ctx, pattern8_1, pattern9_0)
+                                {
+                                    // Rule at src/isa/riscv64/lower.isle line 501.
+                                    let expr0_0 = AluOPRRI::Roriw;
+                                    let expr1_0 = C::put_in_reg(ctx, pattern8_0);
+                                    let expr2_0: Type = I32;
+                                    let expr3_0 = C::rotl_to_rotr_amount(ctx, expr2_0, pattern10_0);
+                                    let expr4_0 = C::u8_into_imm12(ctx, expr3_0);
+                                    let expr5_0 =
+                                        constructor_alu_rr_imm12(ctx, &expr0_0, expr1_0, expr4_0)?;
+                                    let expr6_0 = C::value_reg(ctx, expr5_0);
+                                    return Some(expr6_0);
+                                }
+                            }
+                        }
+                        &Opcode::Rotr => {
+                            let (pattern8_0, pattern8_1) =
+                                C::unpack_value_array_2(ctx, &pattern6_1);
+                            let closure9 = || {
+                                let expr0_0: Type = I32;
+                                return Some(expr0_0);
+                            };
+                            if let Some(pattern9_0) = closure9() {
+                                if let Some(pattern10_0) =
+                                    C::shift_amount_from_value(ctx, pattern8_1, pattern9_0)
+                                {
+                                    // Rule at src/isa/riscv64/lower.isle line 494.
+                                    let expr0_0 = AluOPRRI::Roriw;
+                                    let expr1_0 = C::put_in_reg(ctx, pattern8_0);
+                                    let expr2_0 = C::u8_into_imm12(ctx, pattern10_0);
+                                    let expr3_0 =
+                                        constructor_alu_rr_imm12(ctx, &expr0_0, expr1_0, expr2_0)?;
+                                    let expr4_0 = C::value_reg(ctx, expr3_0);
+                                    return Some(expr4_0);
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if pattern2_0 == I64 {
+                let pattern5_0 = C::inst_data(ctx, pattern0_0);
+                if let &InstructionData::Binary {
+                    opcode: ref pattern6_0,
+                    args: ref pattern6_1,
+                } = &pattern5_0
+                {
+                    match &pattern6_0 {
+                        &Opcode::Rotl => {
+                            let (pattern8_0, pattern8_1) =
+                                C::unpack_value_array_2(ctx, &pattern6_1);
+                            let closure9 = || {
+                                let expr0_0: Type = I64;
+                                return Some(expr0_0);
+                            };
+                            if let Some(pattern9_0) = closure9() {
+                                if let Some(pattern10_0) =
+                                    C::shift_amount_from_value(ctx, pattern8_1, pattern9_0)
+                                {
+                                    // Rule at src/isa/riscv64/lower.isle line 497.
+                                    let expr0_0 = AluOPRRI::Rori;
+                                    let expr1_0 = C::put_in_reg(ctx, pattern8_0);
+                                    let expr2_0: Type = I64;
+                                    let expr3_0 = C::rotl_to_rotr_amount(ctx, expr2_0, pattern10_0);
+                                    let expr4_0 = C::u8_into_imm12(ctx, expr3_0);
+                                    let expr5_0 =
+                                        constructor_alu_rr_imm12(ctx, &expr0_0, expr1_0, expr4_0)?;
+                                    let expr6_0 = C::value_reg(ctx, expr5_0);
+                                    return Some(expr6_0);
+                                }
+                            }
+                        }
+                        &Opcode::Rotr => {
+                            let (pattern8_0, pattern8_1) =
+                                C::unpack_value_array_2(ctx, &pattern6_1);
+                            let closure9 = || {
+                                let expr0_0: Type = I64;
+                                return Some(expr0_0);
+                            };
+                            if let Some(pattern9_0) = closure9() {
+                                if let Some(pattern10_0) =
+                                    C::shift_amount_from_value(ctx, pattern8_1, pattern9_0)
+                                {
+                                    // Rule at src/isa/riscv64/lower.isle line 491.
+                                    let expr0_0 = AluOPRRI::Rori;
+                                    let expr1_0 = C::put_in_reg(ctx, pattern8_0);
+                                    let expr2_0 = C::u8_into_imm12(ctx, pattern10_0);
+                                    let expr3_0 =
+                                        constructor_alu_rr_imm12(ctx, &expr0_0, expr1_0, expr2_0)?;
+                                    let expr4_0 = C::value_reg(ctx, expr3_0);
+                                    return Some(expr4_0);
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Iadd = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    if let Some(pattern8_0) = C::def_inst(ctx, pattern7_0) {
+                        let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                        if let &InstructionData::UnaryImm {
+                            opcode: ref pattern10_0,
+                            imm: pattern10_1,
+                        } = &pattern9_0
+                        {
+                            if let &Opcode::Iconst = &pattern10_0 {
+                                let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
+                                if let Some(pattern13_0) = C::imm12_from_u64(ctx, pattern12_0) {
+                                    // Rule at src/isa/riscv64/lower.isle line 47.
+                                    let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                                    let expr1_0 = constructor_addi(ctx, expr0_0, pattern13_0)?;
+                                    let expr2_0 = C::value_reg(ctx, expr1_0);
+                                    return Some(expr2_0);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::int_fits_in_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Band => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_0) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
+                                    if let Some(pattern13_0) = C::imm12_from_u64(ctx, pattern12_0) {
+                                        // Rule at src/isa/riscv64/lower.isle line 267.
+                                        let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                                        let expr1_0 = constructor_andi(ctx, expr0_0, pattern13_0)?;
+                                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                                        return Some(expr2_0);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    &Opcode::Bor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_0) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
+                                    if let Some(pattern13_0) = C::imm12_from_u64(ctx, pattern12_0) {
+                                        // Rule at src/isa/riscv64/lower.isle line 276.
+                                        let expr0_0 = AluOPRRI::Ori;
+                                        let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                                        let expr2_0 = constructor_alu_rr_imm12(
+                                            ctx,
+                                            &expr0_0,
+                                            expr1_0,
+                                            pattern13_0,
+                                        )?;
+                                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                                        return Some(expr3_0);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    &Opcode::Bxor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_0) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
+                                    if let Some(pattern13_0) = C::imm12_from_u64(ctx, pattern12_0) {
+                                        // Rule at src/isa/riscv64/lower.isle line 285.
+                                        let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                                        let expr1_0 = constructor_xori(ctx, expr0_0, pattern13_0)?;
+                                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                                        return Some(expr2_0);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if let Some(()) = C::has_zbb(ctx, pattern2_0) {
+            if pattern2_0 == I32 {
+                let pattern5_0 = C::inst_data(ctx, pattern0_0);
+                if let &InstructionData::Binary {
+                    opcode: ref pattern6_0,
+                    args: ref pattern6_1,
+                } = &pattern5_0
+                {
+                    match &pattern6_0 {
+                        &Opcode::Rotl => {
+                            let (pattern8_0, pattern8_1) =
+                                C::unpack_value_array_2(ctx, &pattern6_1);
+                            // Rule at src/isa/riscv64/lower.isle line 482.
+                            let expr0_0 = AluOPRRR::Rolw;
+                            let expr1_0 = C::put_in_reg(ctx, pattern8_0);
+                            let expr2_0 = constructor_lo_reg(ctx, pattern8_1)?;
+                            let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Rotr => {
+                            let (pattern8_0, pattern8_1) =
+                                C::unpack_value_array_2(ctx, &pattern6_1);
+                            // Rule at src/isa/riscv64/lower.isle line 488.
+                            let expr0_0 = AluOPRRR::Rorw;
+                            let expr1_0 = C::put_in_reg(ctx, pattern8_0);
+                            let expr2_0 = constructor_lo_reg(ctx, pattern8_1)?;
+                            let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if pattern2_0 == I64 {
+                let pattern5_0 = C::inst_data(ctx, pattern0_0);
+                if let &InstructionData::Binary {
+                    opcode: ref pattern6_0,
+                    args: ref pattern6_1,
+                } = &pattern5_0
+                {
+                    match &pattern6_0 {
+                        &Opcode::Rotl => {
+                            let (pattern8_0, pattern8_1) =
+                                C::unpack_value_array_2(ctx, &pattern6_1);
+                            // Rule at src/isa/riscv64/lower.isle line 479.
+                            let expr0_0 = AluOPRRR::Rol;
+                            let expr1_0 = C::put_in_reg(ctx, pattern8_0);
+                            let expr2_0 = constructor_lo_reg(ctx, pattern8_1)?;
+                            let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Rotr => {
+                            let (pattern8_0, pattern8_1) =
+                                C::unpack_value_array_2(ctx, &pattern6_1);
+                            // Rule at src/isa/riscv64/lower.isle line 485.
+                            let expr0_0 = AluOPRRR::Ror;
+                            let expr1_0 = C::put_in_reg(ctx, pattern8_0);
+                            let expr2_0 = constructor_lo_reg(ctx, pattern8_1)?;
+                            let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_16(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Ushr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        let closure8 = || {
+                            return Some(pattern3_0);
+                        };
+                        if let Some(pattern8_0) = closure8() {
+                            if let Some(pattern9_0) =
+                                C::shift_amount_from_value(ctx, pattern7_1, pattern8_0)
+                            {
+                                // Rule at src/isa/riscv64/lower.isle line 405.
+                                let expr0_0 = constructor_put_in_reg_zext64(ctx, pattern7_0)?;
+                                let expr1_0 = constructor_srli(ctx, expr0_0, pattern9_0)?;
+                                let expr2_0 = C::value_reg(ctx, expr1_0);
+                                return Some(expr2_0);
+                            }
+                        }
+                    }
+                    &Opcode::Sshr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        let closure8 = || {
+                            return Some(pattern3_0);
+                        };
+                        if let Some(pattern8_0) = closure8() {
+                            if let Some(pattern9_0) =
+                                C::shift_amount_from_value(ctx, pattern7_1, pattern8_0)
+                            {
+                                // Rule at src/isa/riscv64/lower.isle line 425.
+                                let expr0_0 = constructor_put_in_reg_sext64(ctx, pattern7_0)?;
+                                let expr1_0 = constructor_srai(ctx, expr0_0, pattern9_0)?;
+                                let expr2_0 = C::value_reg(ctx, expr1_0);
+                                return Some(expr2_0);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Iadd => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
+                                    if let Some(pattern13_0) = C::imm12_from_u64(ctx, pattern12_0) {
+                                        // Rule at src/isa/riscv64/lower.isle line 44.
+                                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                        let expr1_0 = constructor_addi(ctx, expr0_0, pattern13_0)?;
+                                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                                        return Some(expr2_0);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    &Opcode::Isub => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
+                                    if let Some(pattern13_0) =
+                                        C::imm12_from_negated_u64(ctx, pattern12_0)
+                                    {
+                                        // Rule at src/isa/riscv64/lower.isle line 72.
+                                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                        let expr1_0 = constructor_addi(ctx, expr0_0, pattern13_0)?;
+                                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                                        return Some(expr2_0);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    &Opcode::Ishl => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        let closure8 = || {
+                            return Some(pattern3_0);
+                        };
+                        if let Some(pattern8_0) = closure8() {
+                            if let Some(pattern9_0) =
+                                C::shift_amount_from_value(ctx, pattern7_1, pattern8_0)
+                            {
+                                // Rule at src/isa/riscv64/lower.isle line 376.
+                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                let expr1_0 = constructor_slli(ctx, expr0_0, pattern9_0)?;
+                                let expr2_0 = C::value_reg(ctx, expr1_0);
+                                return Some(expr2_0);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::int_fits_in_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Band => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
+                                    if let Some(pattern13_0) = C::imm12_from_u64(ctx, pattern12_0) {
+                                        // Rule at src/isa/riscv64/lower.isle line 264.
+                                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                        let expr1_0 = constructor_andi(ctx, expr0_0, pattern13_0)?;
+                                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                                        return Some(expr2_0);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    &Opcode::Bor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
+                                    if let Some(pattern13_0) = C::imm12_from_u64(ctx, pattern12_0) {
+                                        // Rule at src/isa/riscv64/lower.isle line 273.
+                                        let expr0_0 = AluOPRRI::Ori;
+                                        let expr1_0 = C::put_in_reg(ctx, pattern7_0);
+                                        let expr2_0 = constructor_alu_rr_imm12(
+                                            ctx,
+                                            &expr0_0,
+                                            expr1_0,
+                                            pattern13_0,
+                                        )?;
+                                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                                        return Some(expr3_0);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    &Opcode::Bxor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                            let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                            if let &InstructionData::UnaryImm {
+                                opcode: ref pattern10_0,
+                                imm: pattern10_1,
+                            } = &pattern9_0
+                            {
+                                if let &Opcode::Iconst = &pattern10_0 {
+                                    let pattern12_0 = C::u64_from_imm64(ctx, pattern10_1);
+                                    if let Some(pattern13_0) = C::imm12_from_u64(ctx, pattern12_0) {
+                                        // Rule at src/isa/riscv64/lower.isle line 282.
+                                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                                        let expr1_0 = constructor_xori(ctx, expr0_0, pattern13_0)?;
+                                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                                        return Some(expr2_0);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if pattern2_0 == I32 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Rotl => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 443.
+                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr1_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr2_0 = AluOPRRR::Sllw;
+                        let expr3_0 = constructor_alu_rrr(ctx, &expr2_0, expr0_0, expr1_0)?;
+                        let expr4_0 = AluOPRRR::Srlw;
+                        let expr5_0 = constructor_neg(ctx, expr1_0)?;
+                        let expr6_0 = constructor_alu_rrr(ctx, &expr4_0, expr0_0, expr5_0)?;
+                        let expr7_0 = constructor_or(ctx, expr3_0, expr6_0)?;
+                        let expr8_0 = C::value_reg(ctx, expr7_0);
+                        return Some(expr8_0);
+                    }
+                    &Opcode::Rotr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 463.
+                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr1_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr2_0 = AluOPRRR::Srlw;
+                        let expr3_0 = constructor_alu_rrr(ctx, &expr2_0, expr0_0, expr1_0)?;
+                        let expr4_0 = AluOPRRR::Sllw;
+                        let expr5_0 = constructor_neg(ctx, expr1_0)?;
+                        let expr6_0 = constructor_alu_rrr(ctx, &expr4_0, expr0_0, expr5_0)?;
+                        let expr7_0 = constructor_or(ctx, expr3_0, expr6_0)?;
+                        let expr8_0 = C::value_reg(ctx, expr7_0);
+                        return Some(expr8_0);
+                    }
+                    &Opcode::Ishl => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 370.
+                        let expr0_0 = AluOPRRR::Sllw;
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr2_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    &Opcode::Ushr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 393.
+                        let expr0_0 = AluOPRRR::Srlw;
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr2_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    &Opcode::Sshr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 413.
+                        let expr0_0 = AluOPRRR::Sraw;
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr2_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if pattern2_0 == I64 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Umulhi => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 128.
+                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                        let expr2_0 = constructor_mulhu(ctx, expr0_0, expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    &Opcode::Smulhi => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 139.
+                        let expr0_0 = AluOPRRR::Mulh;
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr2_0 = C::put_in_reg(ctx, pattern7_1);
+                        let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    &Opcode::Rotl => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 437.
+                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr1_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr2_0 = AluOPRRR::Sll;
+                        let expr3_0 = constructor_alu_rrr(ctx, &expr2_0, expr0_0, expr1_0)?;
+                        let expr4_0 = AluOPRRR::Srl;
+                        let expr5_0 = constructor_neg(ctx, expr1_0)?;
+                        let expr6_0 = constructor_alu_rrr(ctx, &expr4_0, expr0_0, expr5_0)?;
+                        let expr7_0 = constructor_or(ctx, expr3_0, expr6_0)?;
+                        let expr8_0 = C::value_reg(ctx, expr7_0);
+                        return Some(expr8_0);
+                    }
+                    &Opcode::Rotr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 457.
+                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr1_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr2_0 = AluOPRRR::Srl;
+                        let expr3_0 = constructor_alu_rrr(ctx, &expr2_0, expr0_0, expr1_0)?;
+                        let expr4_0 = AluOPRRR::Sll;
+                        let expr5_0 = constructor_neg(ctx, expr1_0)?;
+                        let expr6_0 = constructor_alu_rrr(ctx, &expr4_0, expr0_0, expr5_0)?;
+                        let expr7_0 = constructor_or(ctx, expr3_0, expr6_0)?;
+                        let expr8_0 = C::value_reg(ctx, expr7_0);
+                        return Some(expr8_0);
+                    }
+                    &Opcode::Ishl => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 367.
+                        let expr0_0 = AluOPRRR::Sll;
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr2_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    &Opcode::Ushr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 390.
+                        let expr0_0 = AluOPRRR::Srl;
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr2_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    &Opcode::Sshr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 410.
+                        let expr0_0 = AluOPRRR::Sra;
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr2_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if pattern2_0 == I128 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            match &pattern4_0 {
+                &InstructionData::Binary {
+                    opcode: ref pattern5_0,
+                    args: ref pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Iadd => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 51.
+                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                            let expr1_0: usize = 0;
+                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                            let expr3_0: usize = 1;
+                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                            let expr5_0 = C::put_in_regs(ctx, pattern7_1);
+                            let expr6_0: usize = 0;
+                            let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
+                            let expr8_0: usize = 1;
+                            let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
+                            let expr10_0 = constructor_add(ctx, expr2_0, expr7_0)?;
+                            let expr11_0 = constructor_sltu(ctx, expr10_0, expr2_0)?;
+                            let expr12_0 = constructor_add(ctx, expr4_0, expr9_0)?;
+                            let expr13_0 = constructor_add(ctx, expr12_0, expr11_0)?;
+                            let expr14_0 = C::value_regs(ctx, expr10_0, expr13_0);
+                            return Some(expr14_0);
+                        }
+                        &Opcode::Isub => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 76.
+                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                            let expr1_0: usize = 0;
+                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                            let expr3_0: usize = 1;
+                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                            let expr5_0 = C::put_in_regs(ctx, pattern7_1);
+                            let expr6_0: usize = 0;
+                            let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
+                            let expr8_0: usize = 1;
+                            let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
+                            let expr10_0 = constructor_sub(ctx, expr2_0, expr7_0)?;
+                            let expr11_0 = constructor_sltu(ctx, expr2_0, expr7_0)?;
+                            let expr12_0 = constructor_sub(ctx, expr4_0, expr9_0)?;
+                            let expr13_0 = constructor_sub(ctx, expr12_0, expr11_0)?;
+                            let expr14_0 = C::value_regs(ctx, expr10_0, expr13_0);
+                            return Some(expr14_0);
+                        }
+                        &Opcode::Imul => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 112.
+                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                            let expr1_0: usize = 0;
+                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                            let expr3_0: usize = 1;
+                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                            let expr5_0 = C::put_in_regs(ctx, pattern7_1);
+                            let expr6_0: usize = 0;
+                            let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
+                            let expr8_0: usize = 1;
+                            let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
+                            let expr10_0 = constructor_mul(ctx, expr2_0, expr7_0)?;
+                            let expr11_0 = constructor_mulhu(ctx, expr2_0, expr7_0)?;
+                            let expr12_0 = constructor_mul(ctx, expr2_0, expr9_0)?;
+                            let expr13_0 = constructor_mul(ctx, expr4_0, expr7_0)?;
+                            let expr14_0 = constructor_add(ctx, expr11_0, expr12_0)?;
+                            let expr15_0 = constructor_add(ctx, expr14_0, expr13_0)?;
+                            let expr16_0 = C::value_regs(ctx, expr10_0, expr15_0);
+                            return Some(expr16_0);
+                        }
+                        &Opcode::Band => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 288.
+                            let expr0_0 = AluOPRRR::And;
+                            let expr1_0 =
+                                constructor_i128_alu_bitop(ctx, &expr0_0, pattern7_0, pattern7_1)?;
+                            return Some(expr1_0);
+                        }
+                        &Opcode::Bor => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 291.
+                            let expr0_0 = AluOPRRR::Or;
+                            let expr1_0 =
+                                constructor_i128_alu_bitop(ctx, &expr0_0, pattern7_0, pattern7_1)?;
+                            return Some(expr1_0);
+                        }
+                        &Opcode::Bxor => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 294.
+                            let expr0_0 = AluOPRRR::Xor;
+                            let expr1_0 =
+                                constructor_i128_alu_bitop(ctx, &expr0_0, pattern7_0, pattern7_1)?;
+                            return Some(expr1_0);
+                        }
+                        _ => {}
+                    }
+                }
+                &InstructionData::Unary {
+                    opcode: ref pattern5_0,
+                    arg: pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Ineg => {
+                            // Rule at src/isa/riscv64/lower.isle line 93.
+                            let expr0_0 = C::put_in_regs(ctx, pattern5_1);
+                            let expr1_0: usize = 0;
+                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                            let expr3_0: usize = 1;
+                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                            let expr5_0 = constructor_neg(ctx, expr2_0)?;
+                            let expr6_0 = C::zero_reg(ctx);
+                            let expr7_0 = constructor_sltu(ctx, expr6_0, expr2_0)?;
+                            let expr8_0 = constructor_neg(ctx, expr4_0)?;
+                            let expr9_0 = constructor_sub(ctx, expr8_0, expr7_0)?;
+                            let expr10_0 = C::value_regs(ctx, expr5_0, expr9_0);
+                            return Some(expr10_0);
+                        }
+                        &Opcode::Bnot => {
+                            // Rule at src/isa/riscv64/lower.isle line 254.
+                            let expr0_0 = C::put_in_regs(ctx, pattern5_1);
+                            let expr1_0: usize = 0;
+                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                            let expr3_0 = constructor_bnot_reg(ctx, expr2_0)?;
+                            let expr4_0: usize = 1;
+                            let expr5_0 = C::value_regs_get(ctx, expr0_0, expr4_0);
+                            let expr6_0 = constructor_bnot_reg(ctx, expr5_0)?;
+                            let expr7_0 = C::value_regs(ctx, expr3_0, expr6_0);
+                            return Some(expr7_0);
+                        }
+                        &Opcode::Bint => {
+                            // Rule at src/isa/riscv64/lower.isle line 246.
+                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr1_0: u8 = 1;
+                            let expr2_0 = C::u8_into_imm12(ctx, expr1_0);
+                            let expr3_0 = constructor_andi(ctx, expr0_0, expr2_0)?;
+                            let expr4_0: Type = I64;
+                            let expr5_0: u64 = 0;
+                            let expr6_0 = C::imm(ctx, expr4_0, expr5_0);
+                            let expr7_0 = C::value_regs(ctx, expr3_0, expr6_0);
+                            return Some(expr7_0);
+                        }
+                        &Opcode::Uextend => {
+                            // Rule at src/isa/riscv64/lower.isle line 221.
+                            let expr0_0 = constructor_put_in_reg_zext64(ctx, pattern5_1)?;
+                            let expr1_0: Type = I64;
+                            let expr2_0: u64 = 0;
+                            let expr3_0 = C::imm(ctx, expr1_0, expr2_0);
+                            let expr4_0 = C::value_regs(ctx, expr0_0, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Sextend => {
+                            // Rule at src/isa/riscv64/lower.isle line 229.
+                            let expr0_0 = constructor_put_in_reg_sext64(ctx, pattern5_1)?;
+                            let expr1_0: u8 = 63;
+                            let expr2_0 = constructor_srai(ctx, expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_regs(ctx, expr0_0, expr2_0);
+                            return Some(expr3_0);
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        if pattern2_0 == F32 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            match &pattern4_0 {
+                &InstructionData::Binary {
+                    opcode: ref pattern5_0,
+                    args: ref pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Band => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 306.
+                            let expr0_0: Type = F32;
+                            let expr1_0 = AluOPRRR::And;
+                            let expr2_0 = constructor_float_bitop(
+                                ctx, expr0_0, &expr1_0, pattern7_0, pattern7_1,
+                            )?;
+                            return Some(expr2_0);
+                        }
+                        &Opcode::Bor => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 310.
+                            let expr0_0: Type = F32;
+                            let expr1_0 = AluOPRRR::Or;
+                            let expr2_0 = constructor_float_bitop(
+                                ctx, expr0_0, &expr1_0, pattern7_0, pattern7_1,
+                            )?;
+                            return Some(expr2_0);
+                        }
+                        &Opcode::Bxor => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 314.
+                            let expr0_0: Type = F32;
+                            let expr1_0 = AluOPRRR::Xor;
+                            let expr2_0 = constructor_float_bitop(
+                                ctx, expr0_0, &expr1_0, pattern7_0, pattern7_1,
+                            )?;
+                            return Some(expr2_0);
+                        }
+                        _ => {}
+                    }
+                }
+                &InstructionData::Unary {
+                    opcode: ref pattern5_0,
+                    arg: pattern5_1,
+                } => {
+                    if let &Opcode::Bnot = &pattern5_0 {
+                        // Rule at src/isa/riscv64/lower.isle line 319.
+                        let expr0_0: Type = F32;
+                        let expr1_0: Type = F32;
+                        let expr2_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr3_0 = constructor_float_to_int(ctx, expr1_0, expr2_0)?;
+                        let expr4_0 = constructor_bnot_reg(ctx, expr3_0)?;
+                        let expr5_0 = constructor_int_to_float(ctx, expr0_0, expr4_0)?;
+                        let expr6_0 = C::value_reg(ctx, expr5_0);
+                        return Some(expr6_0);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if pattern2_0 == F64 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            match &pattern4_0 {
+                &InstructionData::Binary {
+                    opcode: ref pattern5_0,
+                    args: ref pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Band => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 308.
+                            let expr0_0: Type = F64;
+                            let expr1_0 = AluOPRRR::And;
+                            let expr2_0 = constructor_float_bitop(
+                                ctx, expr0_0, &expr1_0, pattern7_0, pattern7_1,
+                            )?;
+                            return Some(expr2_0);
+                        }
+                        &Opcode::Bor => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 312.
+                            let expr0_0: Type = F64;
+                            let expr1_0 = AluOPRRR::Or;
+                            let expr2_0 = constructor_float_bitop(
+                                ctx, expr0_0, &expr1_0, pattern7_0, pattern7_1,
+                            )?;
+                            return Some(expr2_0);
+                        }
+                        &Opcode::Bxor => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 316.
+                            let expr0_0: Type = F64;
+                            let expr1_0 = AluOPRRR::Xor;
+                            let expr2_0 = constructor_float_bitop(
+                                ctx, expr0_0, &expr1_0, pattern7_0, pattern7_1,
+                            )?;
+                            return Some(expr2_0);
+                        }
+                        _ => {}
+                    }
+                }
+                &InstructionData::Unary {
+                    opcode: ref pattern5_0,
+                    arg: pattern5_1,
+                } => {
+                    if let &Opcode::Bnot = &pattern5_0 {
+                        // Rule at src/isa/riscv64/lower.isle line 321.
+                        let expr0_0: Type = F64;
+                        let expr1_0: Type = F64;
+                        let expr2_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr3_0 = constructor_float_to_int(ctx, expr1_0, expr2_0)?;
+                        let expr4_0 = constructor_bnot_reg(ctx, expr3_0)?;
+                        let expr5_0 = constructor_int_to_float(ctx, expr0_0, expr4_0)?;
+                        let expr6_0 = C::value_reg(ctx, expr5_0);
+                        return Some(expr6_0);
+                    }
+                }
+                _ => {}
+            }
+        }
+        let pattern3_0 = C::inst_data(ctx, pattern0_0);
+        if let &InstructionData::Unary {
+            opcode: ref pattern4_0,
+            arg: pattern4_1,
+        } = &pattern3_0
+        {
+            match &pattern4_0 {
+                &Opcode::FcvtFromUint => {
+                    let pattern6_0 = C::value_type(ctx, pattern4_1);
+                    if let Some(pattern7_0) = C::fits_in_16(ctx, pattern6_0) {
+                        // Rule at src/isa/riscv64/lower.isle line 683.
+                        let expr0_0: bool = false;
+                        let expr1_0: Type = I64;
+                        let expr2_0 =
+                            constructor_int_to_float_op(ctx, expr0_0, expr1_0, pattern2_0)?;
+                        let expr3_0 = constructor_put_in_reg_zext64(ctx, pattern4_1)?;
+                        let expr4_0 = constructor_fpu_rr(ctx, &expr2_0, pattern2_0, expr3_0)?;
+                        let expr5_0 = C::value_reg(ctx, expr4_0);
+                        return Some(expr5_0);
+                    }
+                }
+                &Opcode::FcvtFromSint => {
+                    let pattern6_0 = C::value_type(ctx, pattern4_1);
+                    if let Some(pattern7_0) = C::fits_in_16(ctx, pattern6_0) {
+                        // Rule at src/isa/riscv64/lower.isle line 677.
+                        let expr0_0: bool = true;
+                        let expr1_0: Type = I64;
+                        let expr2_0 =
+                            constructor_int_to_float_op(ctx, expr0_0, expr1_0, pattern2_0)?;
+                        let expr3_0 = constructor_put_in_reg_sext64(ctx, pattern4_1)?;
+                        let expr4_0 = constructor_fpu_rr(ctx, &expr2_0, pattern2_0, expr3_0)?;
+                        let expr5_0 = C::value_reg(ctx, expr4_0);
+                        return Some(expr5_0);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some(()) = C::has_zbb(ctx, pattern2_0) {
+            if pattern2_0 == I32 {
+                let pattern5_0 = C::inst_data(ctx, pattern0_0);
+                if let &InstructionData::Unary {
+                    opcode: ref pattern6_0,
+                    arg: pattern6_1,
+                } = &pattern5_0
+                {
+                    match &pattern6_0 {
+                        &Opcode::Clz => {
+                            // Rule at src/isa/riscv64/lower.isle line 518.
+                            let expr0_0 = AluOPRR::Clzw;
+                            let expr1_0 = C::put_in_reg(ctx, pattern6_1);
+                            let expr2_0 = constructor_alu_rr(ctx, &expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                        &Opcode::Ctz => {
+                            // Rule at src/isa/riscv64/lower.isle line 529.
+                            let expr0_0 = AluOPRR::Ctzw;
+                            let expr1_0 = C::put_in_reg(ctx, pattern6_1);
+                            let expr2_0 = constructor_alu_rr(ctx, &expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                        &Opcode::Popcnt => {
+                            // Rule at src/isa/riscv64/lower.isle line 541.
+                            let expr0_0 = AluOPRR::Cpopw;
+                            let expr1_0 = C::put_in_reg(ctx, pattern6_1);
+                            let expr2_0 = constructor_alu_rr(ctx, &expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if pattern2_0 == I64 {
+                let pattern5_0 = C::inst_data(ctx, pattern0_0);
+                if let &InstructionData::Unary {
+                    opcode: ref pattern6_0,
+                    arg: pattern6_1,
+                } = &pattern5_0
+                {
+                    match &pattern6_0 {
+                        &Opcode::Clz => {
+                            // Rule at src/isa/riscv64/lower.isle line 515.
+                            let expr0_0 = AluOPRR::Clz;
+                            let expr1_0 = C::put_in_reg(ctx, pattern6_1);
+                            let expr2_0 = constructor_alu_rr(ctx, &expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                        &Opcode::Ctz => {
+                            // Rule at src/isa/riscv64/lower.isle line 526.
+                            let expr0_0 = AluOPRR::Ctz;
+                            let expr1_0 = C::put_in_reg(ctx, pattern6_1);
+                            let expr2_0 = constructor_alu_rr(ctx, &expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                        &Opcode::Popcnt => {
+                            // Rule at src/isa/riscv64/lower.isle line 538.
+                            let expr0_0 = AluOPRR::Cpop;
+                            let expr1_0 = C::put_in_reg(ctx, pattern6_1);
+                            let expr2_0 = constructor_alu_rr(ctx, &expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if let Some(pattern4_0) = C::int_fits_in_64(ctx, pattern2_0) {
+                let pattern5_0 = C::inst_data(ctx, pattern0_0);
+                if let &InstructionData::Binary {
+                    opcode: ref pattern6_0,
+                    args: ref pattern6_1,
+                } = &pattern5_0
+                {
+                    match &pattern6_0 {
+                        &Opcode::BandNot => {
+                            let (pattern8_0, pattern8_1) =
+                                C::unpack_value_array_2(ctx, &pattern6_1);
+                            // Rule at src/isa/riscv64/lower.isle line 352.
+                            let expr0_0 = AluOPRRR::Andn;
+                            let expr1_0 = C::put_in_reg(ctx, pattern8_0);
+                            let expr2_0 = C::put_in_reg(ctx, pattern8_1);
+                            let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::BorNot => {
+                            let (pattern8_0, pattern8_1) =
+                                C::unpack_value_array_2(ctx, &pattern6_1);
+                            // Rule at src/isa/riscv64/lower.isle line 355.
+                            let expr0_0 = AluOPRRR::Orn;
+                            let expr1_0 = C::put_in_reg(ctx, pattern8_0);
+                            let expr2_0 = C::put_in_reg(ctx, pattern8_1);
+                            let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::BxorNot => {
+                            let (pattern8_0, pattern8_1) =
+                                C::unpack_value_array_2(ctx, &pattern6_1);
+                            // Rule at src/isa/riscv64/lower.isle line 358.
+                            let expr0_0 = AluOPRRR::Xnor;
+                            let expr1_0 = C::put_in_reg(ctx, pattern8_0);
+                            let expr2_0 = C::put_in_reg(ctx, pattern8_1);
+                            let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Sdiv = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    if let Some(pattern8_0) = C::def_inst(ctx, pattern7_1) {
+                        let pattern9_0 = C::inst_data(ctx, pattern8_0);
+                        if let &InstructionData::UnaryImm {
+                            opcode: ref pattern10_0,
+                            imm: pattern10_1,
+                        } = &pattern9_0
+                        {
+                            if let &Opcode::Iconst = &pattern10_0 {
+                                if let Some(pattern12_0) =
+                                    C::safe_divisor_from_imm64(ctx, pattern10_1)
+                                {
+                                    // Rule at src/isa/riscv64/lower.isle line 187.
+                                    let expr0_0 = AluOPRRR::Div;
+                                    let expr1_0 = constructor_put_in_reg_sext64(ctx, pattern7_0)?;
+                                    let expr2_0 = C::imm(ctx, pattern3_0, pattern12_0);
+                                    let expr3_0 =
+                                        constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                                    let expr4_0 = C::value_reg(ctx, expr3_0);
+                                    return Some(expr4_0);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    let pattern1_0 = C::inst_data(ctx, pattern0_0);
+    match &pattern1_0 {
+        &InstructionData::UnaryIeee32 {
+            opcode: ref pattern2_0,
+            imm: pattern2_1,
+        } => {
+            if let &Opcode::F32const = &pattern2_0 {
+                let pattern4_0 = C::u64_from_ieee32(ctx, pattern2_1);
+                // Rule at src/isa/riscv64/lower.isle line 28.
+                let expr0_0 = FpuOPRR::FmvWX;
+                let expr1_0: Type = F32;
+                let expr2_0: Type = I32;
+                let expr3_0 = C::imm(ctx, expr2_0, pattern4_0);
+                let expr4_0 = constructor_fpu_rr(ctx, &expr0_0, expr1_0, expr3_0)?;
+                let expr5_0 = C::value_reg(ctx, expr4_0);
+                return Some(expr5_0);
+            }
+        }
+        &InstructionData::UnaryIeee64 {
+            opcode: ref pattern2_0,
+            imm: pattern2_1,
+        } => {
+            if let &Opcode::F64const = &pattern2_0 {
+                let pattern4_0 = C::u64_from_ieee64(ctx, pattern2_1);
+                // Rule at src/isa/riscv64/lower.isle line 31.
+                let expr0_0 = FpuOPRR::FmvDX;
+                let expr1_0: Type = F64;
+                let expr2_0: Type = I64;
+                let expr3_0 = C::imm(ctx, expr2_0, pattern4_0);
+                let expr4_0 = constructor_fpu_rr(ctx, &expr0_0, expr1_0, expr3_0)?;
+                let expr5_0 = C::value_reg(ctx, expr4_0);
+                return Some(expr5_0);
+            }
+        }
+        _ => {}
+    }
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if pattern2_0 == I32 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Unary {
+                opcode: ref pattern5_0,
+                arg: pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Bitcast = &pattern5_0 {
+                    let pattern7_0 = C::value_type(ctx, pattern5_1);
+                    if pattern7_0 == F32 {
+                        // Rule at src/isa/riscv64/lower.isle line 722.
+                        let expr0_0: Type = F32;
+                        let expr1_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr2_0 = constructor_float_to_int(ctx, expr0_0, expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                }
+            }
+        }
+        if pattern2_0 == I64 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Unary {
+                opcode: ref pattern5_0,
+                arg: pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Bitcast = &pattern5_0 {
+                    let pattern7_0 = C::value_type(ctx, pattern5_1);
+                    if pattern7_0 == F64 {
+                        // Rule at src/isa/riscv64/lower.isle line 725.
+                        let expr0_0: Type = F64;
+                        let expr1_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr2_0 = constructor_float_to_int(ctx, expr0_0, expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                }
+            }
+        }
+        if pattern2_0 == F32 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            match &pattern4_0 {
+                &InstructionData::Binary {
+                    opcode: ref pattern5_0,
+                    args: ref pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Fadd => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 565.
+                            let expr0_0 = FpuOPRRR::FaddS;
+                            let expr1_0: Type = F32;
+                            let expr2_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr3_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr4_0 =
+                                constructor_fpu_rrr(ctx, &expr0_0, expr1_0, expr2_0, expr3_0)?;
+                            let expr5_0 = C::value_reg(ctx, expr4_0);
+                            return Some(expr5_0);
+                        }
+                        &Opcode::Fsub => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 570.
+                            let expr0_0 = FpuOPRRR::FsubS;
+                            let expr1_0: Type = F32;
+                            let expr2_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr3_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr4_0 =
+                                constructor_fpu_rrr(ctx, &expr0_0, expr1_0, expr2_0, expr3_0)?;
+                            let expr5_0 = C::value_reg(ctx, expr4_0);
+                            return Some(expr5_0);
+                        }
+                        &Opcode::Fmul => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 575.
+                            let expr0_0 = FpuOPRRR::FmulS;
+                            let expr1_0: Type = F32;
+                            let expr2_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr3_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr4_0 =
+                                constructor_fpu_rrr(ctx, &expr0_0, expr1_0, expr2_0, expr3_0)?;
+                            let expr5_0 = C::value_reg(ctx, expr4_0);
+                            return Some(expr5_0);
+                        }
+                        &Opcode::Fdiv => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 580.
+                            let expr0_0 = FpuOPRRR::FdivS;
+                            let expr1_0: Type = F32;
+                            let expr2_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr3_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr4_0 =
+                                constructor_fpu_rrr(ctx, &expr0_0, expr1_0, expr2_0, expr3_0)?;
+                            let expr5_0 = C::value_reg(ctx, expr4_0);
+                            return Some(expr5_0);
+                        }
+                        &Opcode::Fcopysign => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 610.
+                            let expr0_0 = FpuOPRRR::FsgnjS;
+                            let expr1_0: Type = F32;
+                            let expr2_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr3_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr4_0 =
+                                constructor_fpu_rrr(ctx, &expr0_0, expr1_0, expr2_0, expr3_0)?;
+                            let expr5_0 = C::value_reg(ctx, expr4_0);
+                            return Some(expr5_0);
+                        }
+                        _ => {}
+                    }
+                }
+                &InstructionData::Ternary {
+                    opcode: ref pattern5_0,
+                    args: ref pattern5_1,
+                } => {
+                    if let &Opcode::Fma = &pattern5_0 {
+                        let (pattern7_0, pattern7_1, pattern7_2) =
+                            C::unpack_value_array_3(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 617.
+                        let expr0_0 = FpuOPRRRR::FmaddS;
+                        let expr1_0: Type = F32;
+                        let expr2_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr3_0 = C::put_in_reg(ctx, pattern7_1);
+                        let expr4_0 = C::put_in_reg(ctx, pattern7_2);
+                        let expr5_0 = constructor_fpu_rrrr(
+                            ctx, &expr0_0, expr1_0, expr2_0, expr3_0, expr4_0,
+                        )?;
+                        let expr6_0 = C::value_reg(ctx, expr5_0);
+                        return Some(expr6_0);
+                    }
+                }
+                &InstructionData::Unary {
+                    opcode: ref pattern5_0,
+                    arg: pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Sqrt => {
+                            // Rule at src/isa/riscv64/lower.isle line 587.
+                            let expr0_0 = FpuOPRR::FsqrtS;
+                            let expr1_0: Type = F32;
+                            let expr2_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr3_0 = constructor_fpu_rr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Fneg => {
+                            // Rule at src/isa/riscv64/lower.isle line 596.
+                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr1_0 = FpuOPRRR::FsgnjnS;
+                            let expr2_0: Type = F32;
+                            let expr3_0 =
+                                constructor_fpu_rrr(ctx, &expr1_0, expr2_0, expr0_0, expr0_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Fabs => {
+                            // Rule at src/isa/riscv64/lower.isle line 603.
+                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr1_0 = FpuOPRRR::FsgnjxS;
+                            let expr2_0: Type = F32;
+                            let expr3_0 =
+                                constructor_fpu_rrr(ctx, &expr1_0, expr2_0, expr0_0, expr0_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Bitcast => {
+                            let pattern7_0 = C::value_type(ctx, pattern5_1);
+                            if pattern7_0 == I32 {
+                                // Rule at src/isa/riscv64/lower.isle line 716.
+                                let expr0_0: Type = F32;
+                                let expr1_0 = C::put_in_reg(ctx, pattern5_1);
+                                let expr2_0 = constructor_int_to_float(ctx, expr0_0, expr1_0)?;
+                                let expr3_0 = C::value_reg(ctx, expr2_0);
+                                return Some(expr3_0);
+                            }
+                        }
+                        &Opcode::Fdemote => {
+                            // Rule at src/isa/riscv64/lower.isle line 629.
+                            let expr0_0 = FpuOPRR::FcvtSD;
+                            let expr1_0: Type = F32;
+                            let expr2_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr3_0 = constructor_fpu_rr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        if pattern2_0 == F64 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            match &pattern4_0 {
+                &InstructionData::Binary {
+                    opcode: ref pattern5_0,
+                    args: ref pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Fadd => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 567.
+                            let expr0_0 = FpuOPRRR::FaddD;
+                            let expr1_0: Type = F64;
+                            let expr2_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr3_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr4_0 =
+                                constructor_fpu_rrr(ctx, &expr0_0, expr1_0, expr2_0, expr3_0)?;
+                            let expr5_0 = C::value_reg(ctx, expr4_0);
+                            return Some(expr5_0);
+                        }
+                        &Opcode::Fsub => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 572.
+                            let expr0_0 = FpuOPRRR::FsubD;
+                            let expr1_0: Type = F64;
+                            let expr2_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr3_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr4_0 =
+                                constructor_fpu_rrr(ctx, &expr0_0, expr1_0, expr2_0, expr3_0)?;
+                            let expr5_0 = C::value_reg(ctx, expr4_0);
+                            return Some(expr5_0);
+                        }
+                        &Opcode::Fmul => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 577.
+                            let expr0_0 = FpuOPRRR::FmulD;
+                            let expr1_0: Type = F64;
+                            let expr2_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr3_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr4_0 =
+                                constructor_fpu_rrr(ctx, &expr0_0, expr1_0, expr2_0, expr3_0)?;
+                            let expr5_0 = C::value_reg(ctx, expr4_0);
+                            return Some(expr5_0);
+                        }
+                        &Opcode::Fdiv => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 582.
+                            let expr0_0 = FpuOPRRR::FdivD;
+                            let expr1_0: Type = F64;
+                            let expr2_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr3_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr4_0 =
+                                constructor_fpu_rrr(ctx, &expr0_0, expr1_0, expr2_0, expr3_0)?;
+                            let expr5_0 = C::value_reg(ctx, expr4_0);
+                            return Some(expr5_0);
+                        }
+                        &Opcode::Fcopysign => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 612.
+                            let expr0_0 = FpuOPRRR::FsgnjD;
+                            let expr1_0: Type = F64;
+                            let expr2_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr3_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr4_0 =
+                                constructor_fpu_rrr(ctx, &expr0_0, expr1_0, expr2_0, expr3_0)?;
+                            let expr5_0 = C::value_reg(ctx, expr4_0);
+                            return Some(expr5_0);
+                        }
+                        _ => {}
+                    }
+                }
+                &InstructionData::Ternary {
+                    opcode: ref pattern5_0,
+                    args: ref pattern5_1,
+                } => {
+                    if let &Opcode::Fma = &pattern5_0 {
+                        let (pattern7_0, pattern7_1, pattern7_2) =
+                            C::unpack_value_array_3(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 620.
+                        let expr0_0 = FpuOPRRRR::FmaddD;
+                        let expr1_0: Type = F64;
+                        let expr2_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr3_0 = C::put_in_reg(ctx, pattern7_1);
+                        let expr4_0 = C::put_in_reg(ctx, pattern7_2);
+                        let expr5_0 = constructor_fpu_rrrr(
+                            ctx, &expr0_0, expr1_0, expr2_0, expr3_0, expr4_0,
+                        )?;
+                        let expr6_0 = C::value_reg(ctx, expr5_0);
+                        return Some(expr6_0);
+                    }
+                }
+                &InstructionData::Unary {
+                    opcode: ref pattern5_0,
+                    arg: pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Sqrt => {
+                            // Rule at src/isa/riscv64/lower.isle line 589.
+                            let expr0_0 = FpuOPRR::FsqrtD;
+                            let expr1_0: Type = F64;
+                            let expr2_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr3_0 = constructor_fpu_rr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Fneg => {
+                            // Rule at src/isa/riscv64/lower.isle line 599.
+                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr1_0 = FpuOPRRR::FsgnjnD;
+                            let expr2_0: Type = F64;
+                            let expr3_0 =
+                                constructor_fpu_rrr(ctx, &expr1_0, expr2_0, expr0_0, expr0_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Fabs => {
+                            // Rule at src/isa/riscv64/lower.isle line 606.
+                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr1_0 = FpuOPRRR::FsgnjxD;
+                            let expr2_0: Type = F64;
+                            let expr3_0 =
+                                constructor_fpu_rrr(ctx, &expr1_0, expr2_0, expr0_0, expr0_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Bitcast => {
+                            let pattern7_0 = C::value_type(ctx, pattern5_1);
+                            if pattern7_0 == I64 {
+                                // Rule at src/isa/riscv64/lower.isle line 719.
+                                let expr0_0: Type = F64;
+                                let expr1_0 = C::put_in_reg(ctx, pattern5_1);
+                                let expr2_0 = constructor_int_to_float(ctx, expr0_0, expr1_0)?;
+                                let expr3_0 = C::value_reg(ctx, expr2_0);
+                                return Some(expr3_0);
+                            }
+                        }
+                        &Opcode::Fpromote => {
+                            // Rule at src/isa/riscv64/lower.isle line 626.
+                            let expr0_0 = FpuOPRR::FcvtDS;
+                            let expr1_0: Type = F64;
+                            let expr2_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr3_0 = constructor_fpu_rr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        let pattern3_0 = C::inst_data(ctx, pattern0_0);
+        match &pattern3_0 {
+            &InstructionData::NullAry {
+                opcode: ref pattern4_0,
+            } => {
+                if let &Opcode::Null = &pattern4_0 {
+                    // Rule at src/isa/riscv64/lower.isle line 23.
+                    let expr0_0: u64 = 0;
+                    let expr1_0 = C::imm(ctx, pattern2_0, expr0_0);
+                    let expr2_0 = C::value_reg(ctx, expr1_0);
+                    return Some(expr2_0);
+                }
+            }
+            &InstructionData::UnaryBool {
+                opcode: ref pattern4_0,
+                imm: pattern4_1,
+            } => {
+                if let &Opcode::Bconst = &pattern4_0 {
+                    if pattern4_1 == true {
+                        // Rule at src/isa/riscv64/lower.isle line 18.
+                        let expr0_0: u64 = 1;
+                        let expr1_0 = C::imm(ctx, pattern2_0, expr0_0);
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                    if pattern4_1 == false {
+                        // Rule at src/isa/riscv64/lower.isle line 14.
+                        let expr0_0: u64 = 0;
+                        let expr1_0 = C::imm(ctx, pattern2_0, expr0_0);
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                }
+            }
+            &InstructionData::Binary {
+                opcode: ref pattern4_0,
+                args: ref pattern4_1,
+            } => {
+                match &pattern4_0 {
+                    &Opcode::Fmin => {
+                        let (pattern6_0, pattern6_1) = C::unpack_value_array_2(ctx, &pattern4_1);
+                        // Rule at src/isa/riscv64/lower.isle line 634.
+                        let expr0_0: bool = false;
+                        let expr1_0 = C::put_in_reg(ctx, pattern6_0);
+                        let expr2_0 = C::put_in_reg(ctx, pattern6_1);
+                        let expr3_0 =
+                            constructor_float_min_max(ctx, expr0_0, pattern2_0, expr1_0, expr2_0)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    &Opcode::FminPseudo => {
+                        let (pattern6_0, pattern6_1) = C::unpack_value_array_2(ctx, &pattern4_1);
+                        // Rule at src/isa/riscv64/lower.isle line 643.
+                        let expr0_0 = C::put_in_reg(ctx, pattern6_0);
+                        let expr1_0 = C::put_in_reg(ctx, pattern6_1);
+                        let expr2_0 = constructor_float_lt(ctx, pattern2_0, expr1_0, expr0_0)?;
+                        let expr3_0 =
+                            constructor_select_reg(ctx, pattern2_0, expr2_0, expr1_0, expr0_0)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    &Opcode::Fmax => {
+                        let (pattern6_0, pattern6_1) = C::unpack_value_array_2(ctx, &pattern4_1);
+                        // Rule at src/isa/riscv64/lower.isle line 637.
+                        let expr0_0: bool = true;
+                        let expr1_0 = C::put_in_reg(ctx, pattern6_0);
+                        let expr2_0 = C::put_in_reg(ctx, pattern6_1);
+                        let expr3_0 =
+                            constructor_float_min_max(ctx, expr0_0, pattern2_0, expr1_0, expr2_0)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    &Opcode::FmaxPseudo => {
+                        let (pattern6_0, pattern6_1) = C::unpack_value_array_2(ctx, &pattern4_1);
+                        // Rule at src/isa/riscv64/lower.isle line 649.
+                        let expr0_0 = C::put_in_reg(ctx, pattern6_0);
+                        let expr1_0 = C::put_in_reg(ctx, pattern6_1);
+                        let expr2_0 = constructor_float_lt(ctx, pattern2_0, expr0_0, expr1_0)?;
+                        let expr3_0 =
+                            constructor_select_reg(ctx, pattern2_0, expr2_0, expr1_0, expr0_0)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    _ => {}
+                }
+            }
+            &InstructionData::Unary {
+                opcode: ref pattern4_0,
+                arg: pattern4_1,
+            } => {
+                match &pattern4_0 {
+                    &Opcode::Ceil => {
+                        // Rule at src/isa/riscv64/lower.isle line 661.
+                        let expr0_0 = FloatRoundOP::Ceil;
+                        let expr1_0 = C::put_in_reg(ctx, pattern4_1);
+                        let expr2_0 = constructor_float_round(ctx, &expr0_0, pattern2_0, expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    &Opcode::Floor => {
+                        // Rule at src/isa/riscv64/lower.isle line 664.
+                        let expr0_0 = FloatRoundOP::Floor;
+                        let expr1_0 = C::put_in_reg(ctx, pattern4_1);
+                        let expr2_0 = constructor_float_round(ctx, &expr0_0, pattern2_0, expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    &Opcode::Trunc => {
+                        // Rule at src/isa/riscv64/lower.isle line 667.
+                        let expr0_0 = FloatRoundOP::Trunc;
+                        let expr1_0 = C::put_in_reg(ctx, pattern4_1);
+                        let expr2_0 = constructor_float_round(ctx, &expr0_0, pattern2_0, expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    &Opcode::Nearest => {
+                        // Rule at src/isa/riscv64/lower.isle line 670.
+                        let expr0_0 = FloatRoundOP::Nearest;
+                        let expr1_0 = C::put_in_reg(ctx, pattern4_1);
+                        let expr2_0 = constructor_float_round(ctx, &expr0_0, pattern2_0, expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    &Opcode::FcvtToUint => {
+                        let pattern6_0 = C::value_type(ctx, pattern4_1);
+                        // Rule at src/isa/riscv64/lower.isle line 705.
+                        let expr0_0: bool = false;
+                        let expr1_0: bool = false;
+                        let expr2_0 = C::put_in_reg(ctx, pattern4_1);
+                        let expr3_0 = constructor_fcvt_to_int(
+                            ctx, expr0_0, expr1_0, pattern6_0, pattern2_0, expr2_0,
+                        )?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    &Opcode::FcvtToUintSat => {
+                        let pattern6_0 = C::value_type(ctx, pattern4_1);
+                        // Rule at src/isa/riscv64/lower.isle line 711.
+                        let expr0_0: bool = true;
+                        let expr1_0: bool = false;
+                        let expr2_0 = C::put_in_reg(ctx, pattern4_1);
+                        let expr3_0 = constructor_fcvt_to_int(
+                            ctx, expr0_0, expr1_0, pattern6_0, pattern2_0, expr2_0,
+                        )?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    &Opcode::FcvtToSint => {
+                        let pattern6_0 = C::value_type(ctx, pattern4_1);
+                        // Rule at src/isa/riscv64/lower.isle line 702.
+                        let expr0_0: bool = false;
+                        let expr1_0: bool = true;
+                        let expr2_0 = C::put_in_reg(ctx, pattern4_1);
+                        let expr3_0 = constructor_fcvt_to_int(
+                            ctx, expr0_0, expr1_0, pattern6_0, pattern2_0, expr2_0,
+                        )?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    &Opcode::FcvtToSintSat => {
+                        let pattern6_0 = C::value_type(ctx, pattern4_1);
+                        // Rule at src/isa/riscv64/lower.isle line 708.
+                        let expr0_0: bool = true;
+                        let expr1_0: bool = true;
+                        let expr2_0 = C::put_in_reg(ctx, pattern4_1);
+                        let expr3_0 = constructor_fcvt_to_int(
+                            ctx, expr0_0, expr1_0, pattern6_0, pattern2_0, expr2_0,
+                        )?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    &Opcode::FcvtFromUint => {
+                        let pattern6_0 = C::value_type(ctx, pattern4_1);
+                        if let Some(pattern7_0) = C::ty_32_or_64(ctx, pattern6_0) {
+                            // Rule at src/isa/riscv64/lower.isle line 686.
+                            let expr0_0: bool = false;
+                            let expr1_0 =
+                                constructor_int_to_float_op(ctx, expr0_0, pattern7_0, pattern2_0)?;
+                            let expr2_0 = C::put_in_reg(ctx, pattern4_1);
+                            let expr3_0 = constructor_fpu_rr(ctx, &expr1_0, pattern2_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                    }
+                    &Opcode::FcvtFromSint => {
+                        let pattern6_0 = C::value_type(ctx, pattern4_1);
+                        if let Some(pattern7_0) = C::ty_32_or_64(ctx, pattern6_0) {
+                            // Rule at src/isa/riscv64/lower.isle line 680.
+                            let expr0_0: bool = true;
+                            let expr1_0 =
+                                constructor_int_to_float_op(ctx, expr0_0, pattern7_0, pattern2_0)?;
+                            let expr2_0 = C::put_in_reg(ctx, pattern4_1);
+                            let expr3_0 = constructor_fpu_rr(ctx, &expr1_0, pattern2_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        if let Some(()) = C::has_zbb(ctx, pattern2_0) {
+            if let Some(pattern4_0) = C::fits_in_16(ctx, pattern2_0) {
+                let pattern5_0 = C::inst_data(ctx, pattern0_0);
+                if let &InstructionData::Unary {
+                    opcode: ref pattern6_0,
+                    arg: pattern6_1,
+                } = &pattern5_0
+                {
+                    match &pattern6_0 {
+                        &Opcode::Clz => {
+                            // Rule at src/isa/riscv64/lower.isle line 522.
+                            let expr0_0 = AluOPRR::Clz;
+                            let expr1_0 = constructor_put_in_reg_zext64(ctx, pattern6_1)?;
+                            let expr2_0 = constructor_alu_rr(ctx, &expr0_0, expr1_0)?;
+                            let expr3_0 = C::ty_bits(ctx, pattern4_0);
+                            let expr4_0: u8 = 64;
+                            let expr5_0 = C::i64_sub_as_u64(ctx, expr3_0, expr4_0);
+                            let expr6_0 = C::u64_into_imm12(ctx, expr5_0);
+                            let expr7_0 = constructor_addi(ctx, expr2_0, expr6_0)?;
+                            let expr8_0 = C::value_reg(ctx, expr7_0);
+                            return Some(expr8_0);
+                        }
+                        &Opcode::Ctz => {
+                            // Rule at src/isa/riscv64/lower.isle line 534.
+                            let expr0_0 = AluOPRR::Ctz;
+                            let expr1_0 = C::put_in_reg(ctx, pattern6_1);
+                            let expr2_0: Type = I64;
+                            let expr3_0 = C::ty_bits_mask_plus_one(ctx, pattern4_0);
+                            let expr4_0 = C::imm(ctx, expr2_0, expr3_0);
+                            let expr5_0 = constructor_or(ctx, expr1_0, expr4_0)?;
+                            let expr6_0 = constructor_alu_rr(ctx, &expr0_0, expr5_0)?;
+                            let expr7_0 = C::value_reg(ctx, expr6_0);
+                            return Some(expr7_0);
+                        }
+                        &Opcode::Popcnt => {
+                            // Rule at src/isa/riscv64/lower.isle line 544.
+                            let expr0_0 = AluOPRR::Cpop;
+                            let expr1_0 = constructor_put_in_reg_zext64(ctx, pattern6_1)?;
+                            let expr2_0 = constructor_alu_rr(ctx, &expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if let Some(pattern4_0) = C::fits_in_64(ctx, pattern2_0) {
+                let pattern5_0 = C::inst_data(ctx, pattern0_0);
+                if let &InstructionData::Unary {
+                    opcode: ref pattern6_0,
+                    arg: pattern6_1,
+                } = &pattern5_0
+                {
+                    if let &Opcode::Cls = &pattern6_0 {
+                        // Rule at src/isa/riscv64/lower.isle line 549.
+                        let expr0_0 = constructor_put_in_reg_sext64(ctx, pattern6_1)?;
+                        let expr1_0: u8 = 63;
+                        let expr2_0 = constructor_srai(ctx, expr0_0, expr1_0)?;
+                        let expr3_0 = AluOPRR::Clz;
+                        let expr4_0 = constructor_xor(ctx, expr0_0, expr2_0)?;
+                        let expr5_0 = constructor_alu_rr(ctx, &expr3_0, expr4_0)?;
+                        let expr6_0 = C::ty_bits(ctx, pattern4_0);
+                        let expr7_0: u8 = 65;
+                        let expr8_0 = C::i64_sub_as_u64(ctx, expr6_0, expr7_0);
+                        let expr9_0 = C::u64_into_imm12(ctx, expr8_0);
+                        let expr10_0 = constructor_addi(ctx, expr5_0, expr9_0)?;
+                        let expr11_0 = C::value_reg(ctx, expr10_0);
+                        return Some(expr11_0);
+                    }
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_16(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Rotl => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 449.
+                        let expr0_0 = constructor_put_in_reg_zext64(ctx, pattern7_0)?;
+                        let expr1_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr2_0 = C::shift_mask(ctx, pattern3_0);
+                        let expr3_0 = C::u8_into_imm12(ctx, expr2_0);
+                        let expr4_0 = constructor_andi(ctx, expr1_0, expr3_0)?;
+                        let expr5_0 = constructor_neg(ctx, expr1_0)?;
+                        let expr6_0 = C::shift_mask(ctx, pattern3_0);
+                        let expr7_0 = C::u8_into_imm12(ctx, expr6_0);
+                        let expr8_0 = constructor_andi(ctx, expr5_0, expr7_0)?;
+                        let expr9_0 = AluOPRRR::Sll;
+                        let expr10_0 = constructor_alu_rrr(ctx, &expr9_0, expr0_0, expr4_0)?;
+                        let expr11_0 = AluOPRRR::Srl;
+                        let expr12_0 = constructor_alu_rrr(ctx, &expr11_0, expr0_0, expr8_0)?;
+                        let expr13_0 = constructor_or(ctx, expr10_0, expr12_0)?;
+                        let expr14_0 = C::value_reg(ctx, expr13_0);
+                        return Some(expr14_0);
+                    }
+                    &Opcode::Rotr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 469.
+                        let expr0_0 = constructor_put_in_reg_zext64(ctx, pattern7_0)?;
+                        let expr1_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr2_0 = C::shift_mask(ctx, pattern3_0);
+                        let expr3_0 = C::u8_into_imm12(ctx, expr2_0);
+                        let expr4_0 = constructor_andi(ctx, expr1_0, expr3_0)?;
+                        let expr5_0 = constructor_neg(ctx, expr1_0)?;
+                        let expr6_0 = C::shift_mask(ctx, pattern3_0);
+                        let expr7_0 = C::u8_into_imm12(ctx, expr6_0);
+                        let expr8_0 = constructor_andi(ctx, expr5_0, expr7_0)?;
+                        let expr9_0 = AluOPRRR::Srl;
+                        let expr10_0 = constructor_alu_rrr(ctx, &expr9_0, expr0_0, expr4_0)?;
+                        let expr11_0 = AluOPRRR::Sll;
+                        let expr12_0 = constructor_alu_rrr(ctx, &expr11_0, expr0_0, expr8_0)?;
+                        let expr13_0 = constructor_or(ctx, expr10_0, expr12_0)?;
+                        let expr14_0 = C::value_reg(ctx, expr13_0);
+                        return Some(expr14_0);
+                    }
+                    &Opcode::Ishl => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 373.
+                        let expr0_0 = AluOPRRR::Sll;
+                        let expr1_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr2_0 = constructor_masked_shift_amount(ctx, pattern3_0, pattern7_1)?;
+                        let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    &Opcode::Ushr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 396.
+                        let expr0_0 = AluOPRRR::Srl;
+                        let expr1_0 = constructor_put_in_reg_zext64(ctx, pattern7_0)?;
+                        let expr2_0 = constructor_masked_shift_amount(ctx, pattern3_0, pattern7_1)?;
+                        let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    &Opcode::Sshr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 416.
+                        let expr0_0 = AluOPRRR::Sra;
+                        let expr1_0 = constructor_put_in_reg_sext64(ctx, pattern7_0)?;
+                        let expr2_0 = constructor_masked_shift_amount(ctx, pattern3_0, pattern7_1)?;
+                        let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_32(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Umulhi => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 131.
+                        let expr0_0 = constructor_put_in_reg_zext64(ctx, pattern7_0)?;
+                        let expr1_0 = constructor_put_in_reg_zext64(ctx, pattern7_1)?;
+                        let expr2_0 = constructor_mul(ctx, expr0_0, expr1_0)?;
+                        let expr3_0 = C::ty_bits(ctx, pattern3_0);
+                        let expr4_0 = constructor_srli(ctx, expr2_0, expr3_0)?;
+                        let expr5_0 = C::value_reg(ctx, expr4_0);
+                        return Some(expr5_0);
+                    }
+                    &Opcode::Smulhi => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 142.
+                        let expr0_0 = constructor_put_in_reg_sext64(ctx, pattern7_0)?;
+                        let expr1_0 = constructor_put_in_reg_sext64(ctx, pattern7_1)?;
+                        let expr2_0 = constructor_mul(ctx, expr0_0, expr1_0)?;
+                        let expr3_0 = C::ty_bits(ctx, pattern3_0);
+                        let expr4_0 = constructor_srai(ctx, expr2_0, expr3_0)?;
+                        let expr5_0 = C::value_reg(ctx, expr4_0);
+                        return Some(expr5_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            match &pattern4_0 {
+                &InstructionData::UnaryImm {
+                    opcode: ref pattern5_0,
+                    imm: pattern5_1,
+                } => {
+                    if let &Opcode::Iconst = &pattern5_0 {
+                        let pattern7_0 = C::u64_from_imm64(ctx, pattern5_1);
+                        // Rule at src/isa/riscv64/lower.isle line 9.
+                        let expr0_0 = C::imm(ctx, pattern3_0, pattern7_0);
+                        let expr1_0 = C::value_reg(ctx, expr0_0);
+                        return Some(expr1_0);
+                    }
+                }
+                &InstructionData::Binary {
+                    opcode: ref pattern5_0,
+                    args: ref pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Iadd => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 40.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr2_0 = constructor_add(ctx, expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                        &Opcode::Isub => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 68.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr2_0 = constructor_sub(ctx, expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                        &Opcode::Imul => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 105.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr2_0 = constructor_mul(ctx, expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                        &Opcode::Udiv => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 152.
+                            let expr0_0 = AluOPRRR::Divu;
+                            let expr1_0 = constructor_put_in_reg_zext64(ctx, pattern7_0)?;
+                            let expr2_0 = constructor_put_nonzero_in_reg_zext64(ctx, pattern7_1)?;
+                            let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Sdiv => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 179.
+                            let expr0_0 = constructor_put_in_reg_sext64(ctx, pattern7_0)?;
+                            let expr1_0 = constructor_put_nonzero_in_reg_sext64(ctx, pattern7_1)?;
+                            let expr2_0 = constructor_trap_if_div_overflow(
+                                ctx, pattern3_0, expr0_0, expr1_0,
+                            )?;
+                            let expr3_0 = AluOPRRR::Div;
+                            let expr4_0 = constructor_alu_rrr(ctx, &expr3_0, expr2_0, expr1_0)?;
+                            let expr5_0 = C::value_reg(ctx, expr4_0);
+                            return Some(expr5_0);
+                        }
+                        &Opcode::Urem => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 206.
+                            let expr0_0 = AluOPRRR::Remu;
+                            let expr1_0 = constructor_put_in_reg_zext64(ctx, pattern7_0)?;
+                            let expr2_0 = constructor_put_nonzero_in_reg_zext64(ctx, pattern7_1)?;
+                            let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Srem => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 211.
+                            let expr0_0 = AluOPRRR::Rem;
+                            let expr1_0 = constructor_put_in_reg_sext64(ctx, pattern7_0)?;
+                            let expr2_0 = constructor_put_nonzero_in_reg_sext64(ctx, pattern7_1)?;
+                            let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        _ => {}
+                    }
+                }
+                &InstructionData::Unary {
+                    opcode: ref pattern5_0,
+                    arg: pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Ineg => {
+                            // Rule at src/isa/riscv64/lower.isle line 90.
+                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr1_0 = constructor_neg(ctx, expr0_0)?;
+                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                            return Some(expr2_0);
+                        }
+                        &Opcode::Bint => {
+                            // Rule at src/isa/riscv64/lower.isle line 243.
+                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr1_0: u8 = 1;
+                            let expr2_0 = C::u8_into_imm12(ctx, expr1_0);
+                            let expr3_0 = constructor_andi(ctx, expr0_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Ireduce => {
+                            // Rule at src/isa/riscv64/lower.isle line 237.
+                            let expr0_0 = constructor_lo_reg(ctx, pattern5_1)?;
+                            let expr1_0 = C::value_reg(ctx, expr0_0);
+                            return Some(expr1_0);
+                        }
+                        &Opcode::Uextend => {
+                            let pattern7_0 = C::value_type(ctx, pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 218.
+                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr1_0: bool = false;
+                            let expr2_0 = C::ty_bits(ctx, pattern7_0);
+                            let expr3_0 = C::ty_bits(ctx, pattern3_0);
+                            let expr4_0 =
+                                constructor_extend(ctx, expr0_0, expr1_0, expr2_0, expr3_0)?;
+                            let expr5_0 = C::value_reg(ctx, expr4_0);
+                            return Some(expr5_0);
+                        }
+                        &Opcode::Sextend => {
+                            let pattern7_0 = C::value_type(ctx, pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 226.
+                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr1_0: bool = true;
+                            let expr2_0 = C::ty_bits(ctx, pattern7_0);
+                            let expr3_0 = C::ty_bits(ctx, pattern3_0);
+                            let expr4_0 =
+                                constructor_extend(ctx, expr0_0, expr1_0, expr2_0, expr3_0)?;
+                            let expr5_0 = C::value_reg(ctx, expr4_0);
+                            return Some(expr5_0);
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some(pattern3_0) = C::int_fits_in_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            match &pattern4_0 {
+                &InstructionData::Binary {
+                    opcode: ref pattern5_0,
+                    args: ref pattern5_1,
+                } => {
+                    match &pattern5_0 {
+                        &Opcode::Band => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 261.
+                            let expr0_0 = AluOPRRR::And;
+                            let expr1_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr2_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr3_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::Bor => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 270.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr2_0 = constructor_or(ctx, expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                        &Opcode::Bxor => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 279.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr2_0 = constructor_xor(ctx, expr0_0, expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                        &Opcode::BandNot => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 342.
+                            let expr0_0 = AluOPRRR::And;
+                            let expr1_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr2_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr3_0 = constructor_bnot_reg(ctx, expr2_0)?;
+                            let expr4_0 = constructor_alu_rrr(ctx, &expr0_0, expr1_0, expr3_0)?;
+                            let expr5_0 = C::value_reg(ctx, expr4_0);
+                            return Some(expr5_0);
+                        }
+                        &Opcode::BorNot => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 345.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr2_0 = constructor_bnot_reg(ctx, expr1_0)?;
+                            let expr3_0 = constructor_or(ctx, expr0_0, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        &Opcode::BxorNot => {
+                            let (pattern7_0, pattern7_1) =
+                                C::unpack_value_array_2(ctx, &pattern5_1);
+                            // Rule at src/isa/riscv64/lower.isle line 348.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr2_0 = constructor_xor(ctx, expr0_0, expr1_0)?;
+                            let expr3_0 = constructor_bnot_reg(ctx, expr2_0)?;
+                            let expr4_0 = C::value_reg(ctx, expr3_0);
+                            return Some(expr4_0);
+                        }
+                        _ => {}
+                    }
+                }
+                &InstructionData::Unary {
+                    opcode: ref pattern5_0,
+                    arg: pattern5_1,
+                } => {
+                    if let &Opcode::Bnot = &pattern5_0 {
+                        // Rule at src/isa/riscv64/lower.isle line 251.
+                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                        let expr1_0 = constructor_bnot_reg(ctx, expr0_0)?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    return None;
+}
+
+// Generated as internal constructor for term put_nonzero_in_reg_zext64.
+pub fn constructor_put_nonzero_in_reg_zext64<C: Context>(ctx: &mut C, arg0: Value) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::def_inst(ctx, pattern0_0) {
+        let pattern2_0 = C::inst_data(ctx, pattern1_0);
+        if let &InstructionData::UnaryImm {
+            opcode: ref pattern3_0,
+            imm: pattern3_1,
+        } = &pattern2_0
+        {
+            if let &Opcode::Iconst = &pattern3_0 {
+                if let Some(pattern5_0) = C::nonzero_u64_from_imm64(ctx, pattern3_1) {
+                    // Rule at src/isa/riscv64/lower.isle line 164.
+                    let expr0_0 = constructor_put_in_reg_zext64(ctx, pattern0_0)?;
+                    return Some(expr0_0);
+                }
+            }
+        }
+    }
+    // Rule at src/isa/riscv64/lower.isle line 159.
+    let expr0_0 = constructor_put_in_reg_zext64(ctx, pattern0_0)?;
+    let expr1_0 = constructor_trap_if_zero_divisor(ctx, expr0_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term put_nonzero_in_reg_sext64.
+pub fn constructor_put_nonzero_in_reg_sext64<C: Context>(ctx: &mut C, arg0: Value) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = C::value_type(ctx, pattern0_0);
+    if let Some(pattern2_0) = C::def_inst(ctx, pattern0_0) {
+        let pattern3_0 = C::inst_data(ctx, pattern2_0);
+        if let &InstructionData::UnaryImm {
+            opcode: ref pattern4_0,
+            imm: pattern4_1,
+        } = &pattern3_0
+        {
+            if let &Opcode::Iconst = &pattern4_0 {
+                if let Some(pattern6_0) = C::nonzero_u64_from_imm64(ctx, pattern4_1) {
+                    // Rule at src/isa/riscv64/lower.isle line 197.
+                    let expr0_0 = C::imm(ctx, pattern1_0, pattern6_0);
+                    return Some(expr0_0);
+                }
+            }
+        }
+    }
+    // Rule at src/isa/riscv64/lower.isle line 192.
+    let expr0_0 = constructor_put_in_reg_sext64(ctx, pattern0_0)?;
+    let expr1_0 = constructor_trap_if_zero_divisor(ctx, expr0_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term i128_alu_bitop.
+pub fn constructor_i128_alu_bitop<C: Context>(
+    ctx: &mut C,
+    arg0: &AluOPRRR,
+    arg1: Value,
+    arg2: Value,
+) -> Option<ValueRegs> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/isa/riscv64/lower.isle line 299.
+    let expr0_0 = C::put_in_regs(ctx, pattern1_0);
+    let expr1_0 = C::put_in_regs(ctx, pattern2_0);
+    let expr2_0: usize = 0;
+    let expr3_0 = C::value_regs_get(ctx, expr0_0, expr2_0);
+    let expr4_0: usize = 0;
+    let expr5_0 = C::value_regs_get(ctx, expr1_0, expr4_0);
+    let expr6_0 = constructor_alu_rrr(ctx, pattern0_0, expr3_0, expr5_0)?;
+    let expr7_0: usize = 1;
+    let expr8_0 = C::value_regs_get(ctx, expr0_0, expr7_0);
+    let expr9_0: usize = 1;
+    let expr10_0 = C::value_regs_get(ctx, expr1_0, expr9_0);
+    let expr11_0 = constructor_alu_rrr(ctx, pattern0_0, expr8_0, expr10_0)?;
+    let expr12_0 = C::value_regs(ctx, expr6_0, expr11_0);
+    return Some(expr12_0);
+}
+
+// Generated as internal constructor for term float_bitop.
+pub fn constructor_float_bitop<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: &AluOPRRR,
+    arg2: Value,
+    arg3: Value,
+) -> Option<ValueRegs> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/isa/riscv64/lower.isle line 325.
+    let expr0_0 = C::put_in_reg(ctx, pattern2_0);
+    let expr1_0 = constructor_float_to_int(ctx, pattern0_0, expr0_0)?;
+    let expr2_0 = C::put_in_reg(ctx, pattern3_0);
+    let expr3_0 = constructor_float_to_int(ctx, pattern0_0, expr2_0)?;
+    let expr4_0 = constructor_alu_rrr(ctx, pattern1_0, expr1_0, expr3_0)?;
+    let expr5_0 = constructor_int_to_float(ctx, pattern0_0, expr4_0)?;
+    let expr6_0 = C::value_reg(ctx, expr5_0);
+    return Some(expr6_0);
+}
+
+// Generated as internal constructor for term float_to_int.
+pub fn constructor_float_to_int<C: Context>(ctx: &mut C, arg0: Type, arg1: Reg) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    if pattern0_0 == F32 {
+        let pattern2_0 = arg1;
+        // Rule at src/isa/riscv64/lower.isle line 332.
+        let expr0_0 = FpuOPRR::FmvXW;
+        let expr1_0: Type = I64;
+        let expr2_0 = constructor_fpu_rr(ctx, &expr0_0, expr1_0, pattern2_0)?;
+        return Some(expr2_0);
+    }
+    if pattern0_0 == F64 {
+        let pattern2_0 = arg1;
+        // Rule at src/isa/riscv64/lower.isle line 333.
+        let expr0_0 = FpuOPRR::FmvXD;
+        let expr1_0: Type = I64;
+        let expr2_0 = constructor_fpu_rr(ctx, &expr0_0, expr1_0, pattern2_0)?;
+        return Some(expr2_0);
+    }
+    return None;
+}
+
+// Generated as internal constructor for term int_to_float.
+pub fn constructor_int_to_float<C: Context>(ctx: &mut C, arg0: Type, arg1: Reg) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    if pattern0_0 == F32 {
+        let pattern2_0 = arg1;
+        // Rule at src/isa/riscv64/lower.isle line 337.
+        let expr0_0 = FpuOPRR::FmvWX;
+        let expr1_0: Type = F32;
+        let expr2_0 = constructor_fpu_rr(ctx, &expr0_0, expr1_0, pattern2_0)?;
+        return Some(expr2_0);
+    }
+    if pattern0_0 == F64 {
+        let pattern2_0 = arg1;
+        // Rule at src/isa/riscv64/lower.isle line 338.
+        let expr0_0 = FpuOPRR::FmvDX;
+        let expr1_0: Type = F64;
+        let expr2_0 = constructor_fpu_rr(ctx, &expr0_0, expr1_0, pattern2_0)?;
+        return Some(expr2_0);
+    }
+    return None;
+}
+
+// Generated as internal constructor for term masked_shift_amount.
+pub fn constructor_masked_shift_amount<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/isa/riscv64/lower.isle line 381.
+    let expr0_0 = constructor_lo_reg(ctx, pattern1_0)?;
+    let expr1_0 = C::shift_mask(ctx, pattern0_0);
+    let expr2_0 = C::u8_into_imm12(ctx, expr1_0);
+    let expr3_0 = constructor_andi(ctx, expr0_0, expr2_0)?;
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term float_lt.
+pub fn constructor_float_lt<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Reg,
+    arg2: Reg,
+) -> Option<Reg> {
+    let pattern0_0 = arg0;
+    if pattern0_0 == F32 {
+        let pattern2_0 = arg1;
+        let pattern3_0 = arg2;
+        // Rule at src/isa/riscv64/lower.isle line 656.
+        let expr0_0 = FpuOPRRR::FltS;
+        let expr1_0: Type = I64;
+        let expr2_0 = constructor_fpu_rrr(ctx, &expr0_0, expr1_0, pattern2_0, pattern3_0)?;
+        return Some(expr2_0);
+    }
+    if pattern0_0 == F64 {
+        let pattern2_0 = arg1;
+        let pattern3_0 = arg2;
+        // Rule at src/isa/riscv64/lower.isle line 657.
+        let expr0_0 = FpuOPRRR::FltD;
+        let expr1_0: Type = I64;
+        let expr2_0 = constructor_fpu_rrr(ctx, &expr0_0, expr1_0, pattern2_0, pattern3_0)?;
+        return Some(expr2_0);
+    }
+    return None;
+}
+
+// Generated as internal constructor for term int_to_float_op.
+pub fn constructor_int_to_float_op<C: Context>(
+    ctx: &mut C,
+    arg0: bool,
+    arg1: Type,
+    arg2: Type,
+) -> Option<FpuOPRR> {
+    let pattern0_0 = arg0;
+    if pattern0_0 == true {
+        let pattern2_0 = arg1;
+        if pattern2_0 == I32 {
+            let pattern4_0 = arg2;
+            if pattern4_0 == F32 {
+                // Rule at src/isa/riscv64/lower.isle line 691.
+                let expr0_0 = FpuOPRR::FcvtSW;
+                return Some(expr0_0);
+            }
+            if pattern4_0 == F64 {
+                // Rule at src/isa/riscv64/lower.isle line 695.
+                let expr0_0 = FpuOPRR::FcvtDW;
+                return Some(expr0_0);
+            }
+        }
+        if pattern2_0 == I64 {
+            let pattern4_0 = arg2;
+            if pattern4_0 == F32 {
+                // Rule at src/isa/riscv64/lower.isle line 693.
+                let expr0_0 = FpuOPRR::FcvtSL;
+                return Some(expr0_0);
+            }
+            if pattern4_0 == F64 {
+                // Rule at src/isa/riscv64/lower.isle line 697.
+                let expr0_0 = FpuOPRR::FcvtDL;
+                return Some(expr0_0);
+            }
+        }
+    }
+    if pattern0_0 == false {
+        let pattern2_0 = arg1;
+        if pattern2_0 == I32 {
+            let pattern4_0 = arg2;
+            if pattern4_0 == F32 {
+                // Rule at src/isa/riscv64/lower.isle line 692.
+                let expr0_0 = FpuOPRR::FcvtSWu;
+                return Some(expr0_0);
+            }
+            if pattern4_0 == F64 {
+                // Rule at src/isa/riscv64/lower.isle line 696.
+                let expr0_0 = FpuOPRR::FcvtDWu;
+                return Some(expr0_0);
+            }
+        }
+        if pattern2_0 == I64 {
+            let pattern4_0 = arg2;
+            if pattern4_0 == F32 {
+                // Rule at src/isa/riscv64/lower.isle line 694.
+                let expr0_0 = FpuOPRR::FcvtSLu;
+                return Some(expr0_0);
+            }
+            if pattern4_0 == F64 {
+                // Rule at src/isa/riscv64/lower.isle line 698.
+                let expr0_0 = FpuOPRR::FcvtDLu;
+                return Some(expr0_0);
+            }
+        }
+    }
+    return None;
+}
@@ -0,0 +1,1273 @@
+//! Lower a single Cranelift instruction into vcode.
+
+use crate::binemit::CodeOffset;
+use crate::ir::condcodes::IntCC;
+use crate::ir::types::*;
+use crate::ir::Inst as IRInst;
+use crate::ir::{AtomicRmwOp, InstructionData, Opcode};
+use crate::isa::riscv64::settings as riscv64_settings;
+use crate::machinst::lower::*;
+use crate::machinst::*;
+use crate::settings::Flags;
+use crate::{CodegenError, CodegenResult};
+
+use crate::isa::riscv64::abi::*;
+use crate::isa::riscv64::inst::*;
+
+use regalloc::Reg;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use super::lower::*;
+
+/// The `fence` predecessor/successor set bits.
+const FENCE_R: u8 = 0b0010;
+const FENCE_W: u8 = 0b0001;
+const FENCE_RW: u8 = FENCE_R | FENCE_W;
+
+/// Actually codegen an instruction's results into registers.
+pub(crate) fn lower_insn_to_regs<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    insn: IRInst,
+    flags: &Flags,
+    isa_flags: &riscv64_settings::Flags,
+) -> CodegenResult<()> {
+    let op = ctx.data(insn).opcode();
+    let inputs = insn_inputs(ctx, insn);
+    let outputs = insn_outputs(ctx, insn);
+    let ty = if outputs.len() > 0 {
+        Some(ctx.output_ty(insn, 0))
+    } else {
+        None
+    };
+
+    if let Ok(()) = super::lower::isle::lower(ctx, isa_flags, &outputs, insn) {
+        return Ok(());
+    }
+
+    let implemented_in_isle = |ctx: &mut C| -> ! {
+        unreachable!(
+            "implemented in ISLE: inst = `{}`, type = `{:?}`",
+            ctx.dfg().display_inst(insn),
+            ty
+        );
+    };
+
+    let unsupported = |ctx: &mut C| -> CodegenResult<()> {
+        Err(CodegenError::Unsupported(format!(
+            "riscv64: unsupported instruction `{}`",
+            ctx.dfg().display_inst(insn)
+        )))
+    };
+
+    match op {
+        Opcode::Iconst | Opcode::Bconst | Opcode::Null | Opcode::F32const | Opcode::F64const => {
+            implemented_in_isle(ctx)
+        }
+
+        Opcode::Iadd | Opcode::Isub | Opcode::Ineg | Opcode::Imul => implemented_in_isle(ctx),
+
+        Opcode::Umulhi | Opcode::Smulhi => implemented_in_isle(ctx),
+
+        Opcode::Udiv | Opcode::Sdiv | Opcode::Urem | Opcode::Srem => {
+            // Only 128-bit division is left to this point.
+            return unsupported(ctx);
+        }
+
+        Opcode::Uextend | Opcode::Sextend | Opcode::Ireduce | Opcode::Bint => {
+            implemented_in_isle(ctx)
+        }
+
+        Opcode::Bnot
+        | Opcode::Band
+        | Opcode::Bor
+        | Opcode::Bxor
+        | Opcode::BandNot
+        | Opcode::BorNot
+        | Opcode::BxorNot => implemented_in_isle(ctx),
+
+        Opcode::Ishl | Opcode::Ushr | Opcode::Sshr | Opcode::Rotl | Opcode::Rotr => {
+            // Only 128-bit shifts and rotates are left to this point.
+            return unsupported(ctx);
+        }
+
+        Opcode::Bitrev => {
+            let ty = ty.unwrap();
+            let rd = get_output_reg(ctx, outputs[0]);
+            if ty == I128 {
+                // Reverse both halves and swap them.
+                let src = put_input_in_regs(ctx, inputs[0]);
+                let lo = lower_bitrev64(ctx, src.regs()[1], isa_flags);
+                let hi = lower_bitrev64(ctx, src.regs()[0], isa_flags);
+                ctx.emit(Inst::gen_move(rd.regs()[0], lo, I64));
+                ctx.emit(Inst::gen_move(rd.regs()[1], hi, I64));
+            } else {
+                let rn = put_input_in_reg(ctx, inputs[0], NarrowValueMode::None);
+                let rev = lower_bitrev64(ctx, rn, isa_flags);
+                let bits = ty_bits(ty) as i64;
+                let rd = rd.only_reg().unwrap();
+                if bits < 64 {
+                    ctx.emit(Inst::AluRRImm12 {
+                        alu_op: AluOPRRI::Srli,
+                        rd,
+                        rs: rev,
+                        imm12: Imm12::maybe_from_i64(64 - bits).unwrap(),
+                    });
+                } else {
+                    ctx.emit(Inst::gen_move(rd, rev, I64));
+                }
+            }
+        }
+
+        Opcode::Clz | Opcode::Ctz | Opcode::Popcnt | Opcode::Cls => {
+            // With Zbb these are lowered in ISLE; this is the generic
+            // fallback built on a SWAR population count.
+            let ty = ty.unwrap();
+            if ty == I128 {
+                return unsupported(ctx);
+            }
+            let rd = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let bits = ty_bits(ty) as i64;
+            let count = match op {
+                Opcode::Popcnt => {
+                    let rn = put_input_in_reg(ctx, inputs[0], NarrowValueMode::ZeroExtend64);
+                    lower_popcnt64(ctx, rn)
+                }
+                Opcode::Clz => {
+                    let rn = put_input_in_reg(ctx, inputs[0], NarrowValueMode::ZeroExtend64);
+                    let clz = lower_clz64(ctx, rn);
+                    if bits < 64 {
+                        alu_rr_imm12(ctx, AluOPRRI::Addi, clz, bits - 64)
+                    } else {
+                        clz
+                    }
+                }
+                Opcode::Cls => {
+                    // `cls(x) = clz(x ^ (x >> 63)) - 1` on the sign-extended
+                    // value, adjusted for the extension.
+                    let rn = put_input_in_reg(ctx, inputs[0], NarrowValueMode::SignExtend64);
+                    let sign = alu_rr_imm12(ctx, AluOPRRI::Srai, rn, 63);
+                    let val = alu_rrr(ctx, AluOPRRR::Xor, rn, sign);
+                    let clz = lower_clz64(ctx, val);
+                    alu_rr_imm12(ctx, AluOPRRI::Addi, clz, bits - 65)
+                }
+                Opcode::Ctz => {
+                    let rn = put_input_in_reg(ctx, inputs[0], NarrowValueMode::None);
+                    let rn = if bits < 64 {
+                        // Set the bit just above the value so that a zero
+                        // input counts to the width of the type.
+                        let tmp = ctx.alloc_tmp(I64).only_reg().unwrap();
+                        lower_constant_u64(ctx, tmp, 1 << bits);
+                        alu_rrr(ctx, AluOPRRR::Or, rn, tmp.to_reg())
+                    } else {
+                        rn
+                    };
+                    // The trailing zeros are the set bits of `!x & (x - 1)`.
+                    let not = alu_rr_imm12(ctx, AluOPRRI::Xori, rn, -1);
+                    let minus_one = alu_rr_imm12(ctx, AluOPRRI::Addi, rn, -1);
+                    let trailing = alu_rrr(ctx, AluOPRRR::And, not, minus_one);
+                    lower_popcnt64(ctx, trailing)
+                }
+                _ => unreachable!(),
+            };
+            ctx.emit(Inst::gen_move(rd, count, I64));
+        }
+
+        Opcode::Load
+        | Opcode::Uload8
+        | Opcode::Sload8
+        | Opcode::Uload16
+        | Opcode::Sload16
+        | Opcode::Uload32
+        | Opcode::Sload32
+        | Opcode::LoadComplex
+        | Opcode::Uload8Complex
+        | Opcode::Sload8Complex
+        | Opcode::Uload16Complex
+        | Opcode::Sload16Complex
+        | Opcode::Uload32Complex
+        | Opcode::Sload32Complex => {
+            let off = ctx.data(insn).load_store_offset().unwrap();
+            let flags = ctx.memflags(insn).unwrap();
+            let out_ty = ctx.output_ty(insn, 0);
+            let load_op = match op {
+                Opcode::Uload8 | Opcode::Uload8Complex => LoadOP::Lbu,
+                Opcode::Sload8 | Opcode::Sload8Complex => LoadOP::Lb,
+                Opcode::Uload16 | Opcode::Uload16Complex => LoadOP::Lhu,
+                Opcode::Sload16 | Opcode::Sload16Complex => LoadOP::Lh,
+                Opcode::Uload32 | Opcode::Uload32Complex => LoadOP::Lwu,
+                Opcode::Sload32 | Opcode::Sload32Complex => LoadOP::Lw,
+                Opcode::Load | Opcode::LoadComplex if out_ty == I128 => LoadOP::Ld,
+                Opcode::Load | Opcode::LoadComplex => LoadOP::from_type(out_ty),
+                _ => unreachable!(),
+            };
+            let mem_ty = if out_ty == I128 { I64 } else { out_ty };
+            let mem = lower_address(ctx, mem_ty, &inputs[..], off);
+            let rd = get_output_reg(ctx, outputs[0]);
+            if out_ty == I128 {
+                ctx.emit(Inst::Load {
+                    rd: rd.regs()[0],
+                    op: load_op,
+                    flags,
+                    from: mem.clone(),
+                });
+                ctx.emit(Inst::Load {
+                    rd: rd.regs()[1],
+                    op: load_op,
+                    flags,
+                    from: amode_add(&mem, 8),
+                });
+            } else {
+                ctx.emit(Inst::Load {
+                    rd: rd.only_reg().unwrap(),
+                    op: load_op,
+                    flags,
+                    from: mem,
+                });
+            }
+        }
+
+        Opcode::Store
+        | Opcode::Istore8
+        | Opcode::Istore16
+        | Opcode::Istore32
+        | Opcode::StoreComplex
+        | Opcode::Istore8Complex
+        | Opcode::Istore16Complex
+        | Opcode::Istore32Complex => {
+            let off = ctx.data(insn).load_store_offset().unwrap();
+            let flags = ctx.memflags(insn).unwrap();
+            let src_ty = ctx.input_ty(insn, 0);
+            let mem_ty = match op {
+                Opcode::Istore8 | Opcode::Istore8Complex => I8,
+                Opcode::Istore16 | Opcode::Istore16Complex => I16,
+                Opcode::Istore32 | Opcode::Istore32Complex => I32,
+                Opcode::Store | Opcode::StoreComplex if src_ty == I128 => I64,
+                Opcode::Store | Opcode::StoreComplex => src_ty,
+                _ => unreachable!(),
+            };
+            let store_op = StoreOP::from_type(mem_ty);
+            let mem = lower_address(ctx, mem_ty, &inputs[1..], off);
+            if src_ty == I128 {
+                let src = put_input_in_regs(ctx, inputs[0]);
+                ctx.emit(Inst::Store {
+                    to: mem.clone(),
+                    op: store_op,
+                    flags,
+                    src: src.regs()[0],
+                });
+                ctx.emit(Inst::Store {
+                    to: amode_add(&mem, 8),
+                    op: store_op,
+                    flags,
+                    src: src.regs()[1],
+                });
+            } else {
+                let src = put_input_in_reg(ctx, inputs[0], NarrowValueMode::None);
+                ctx.emit(Inst::Store {
+                    to: mem,
+                    op: store_op,
+                    flags,
+                    src,
+                });
+            }
+        }
+
+        Opcode::StackAddr => {
+            let (stack_slot, offset) = match *ctx.data(insn) {
+                InstructionData::StackLoad {
+                    opcode: Opcode::StackAddr,
+                    stack_slot,
+                    offset,
+                } => (stack_slot, offset),
+                _ => unreachable!(),
+            };
+            let rd = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let offset: i32 = offset.into();
+            let inst = ctx
+                .abi()
+                .stackslot_addr(stack_slot, u32::try_from(offset).unwrap(), rd);
+            ctx.emit(inst);
+        }
+
+        Opcode::AtomicRmw => {
+            let ty = ty.unwrap();
+            assert!(is_valid_atomic_transaction_ty(ty));
+            let rd = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let addr = put_input_in_reg(ctx, inputs[0], NarrowValueMode::None);
+            let rmw_op = ctx.data(insn).atomic_rmw_op().unwrap();
+            if ty.bits() >= 32 {
+                let is_64 = ty == I64;
+                let amo = |w, d| if is_64 { d } else { w };
+                let amo_op = match rmw_op {
+                    AtomicRmwOp::Add | AtomicRmwOp::Sub => {
+                        Some(amo(AtomicOP::AmoaddW, AtomicOP::AmoaddD))
+                    }
+                    AtomicRmwOp::And => Some(amo(AtomicOP::AmoandW, AtomicOP::AmoandD)),
+                    AtomicRmwOp::Or => Some(amo(AtomicOP::AmoorW, AtomicOP::AmoorD)),
+                    AtomicRmwOp::Xor => Some(amo(AtomicOP::AmoxorW, AtomicOP::AmoxorD)),
+                    AtomicRmwOp::Xchg => Some(amo(AtomicOP::AmoswapW, AtomicOP::AmoswapD)),
+                    AtomicRmwOp::Smin => Some(amo(AtomicOP::AmominW, AtomicOP::AmominD)),
+                    AtomicRmwOp::Smax => Some(amo(AtomicOP::AmomaxW, AtomicOP::AmomaxD)),
+                    AtomicRmwOp::Umin => Some(amo(AtomicOP::AmominuW, AtomicOP::AmominuD)),
+                    AtomicRmwOp::Umax => Some(amo(AtomicOP::AmomaxuW, AtomicOP::AmomaxuD)),
+                    AtomicRmwOp::Nand => None,
+                };
+                let src = put_input_in_reg(ctx, inputs[1], NarrowValueMode::None);
+                if let Some(amo_op) = amo_op {
+                    // Atomic subtraction is the addition of the negated value.
+                    let src = if rmw_op == AtomicRmwOp::Sub {
+                        alu_rrr(ctx, AluOPRRR::Sub, zero_reg(), src)
+                    } else {
+                        src
+                    };
+                    ctx.emit(Inst::Atomic {
+                        op: amo_op,
+                        rd,
+                        addr,
+                        src,
+                    });
+                } else {
+                    ctx.emit(Inst::AtomicRmwLoop {
+                        op: rmw_op,
+                        ty,
+                        dst: rd,
+                        addr,
+                        src,
+                        shift: zero_reg(),
+                        mask: zero_reg(),
+                    });
+                }
+            } else {
+                // The minimum and maximum operations compare the extended
+                // values; the loop extends the old value itself.
+                let narrow_mode = match rmw_op {
+                    AtomicRmwOp::Smin | AtomicRmwOp::Smax => NarrowValueMode::SignExtend64,
+                    AtomicRmwOp::Umin | AtomicRmwOp::Umax => NarrowValueMode::ZeroExtend64,
+                    _ => NarrowValueMode::None,
+                };
+                let src = put_input_in_reg(ctx, inputs[1], narrow_mode);
+                let (word_addr, shift, mask) = lower_narrow_atomic_address(ctx, addr, ty);
+                ctx.emit(Inst::AtomicRmwLoop {
+                    op: rmw_op,
+                    ty,
+                    dst: rd,
+                    addr: word_addr,
+                    src,
+                    shift,
+                    mask,
+                });
+            }
+        }
+
+        Opcode::AtomicCas => {
+            let ty = ty.unwrap();
+            assert!(is_valid_atomic_transaction_ty(ty));
+            let rd = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let addr = put_input_in_reg(ctx, inputs[0], NarrowValueMode::None);
+            let replacement = put_input_in_reg(ctx, inputs[2], NarrowValueMode::None);
+            if ty.bits() >= 32 {
+                // `lr.w` sign-extends the loaded value, so the expected value
+                // must be sign-extended to compare equal.
+                let expected = put_input_in_reg(ctx, inputs[1], NarrowValueMode::SignExtend64);
+                ctx.emit(Inst::AtomicCas {
+                    ty,
+                    dst: rd,
+                    addr,
+                    expected,
+                    replacement,
+                    shift: zero_reg(),
+                    mask: zero_reg(),
+                });
+            } else {
+                let expected = put_input_in_reg(ctx, inputs[1], NarrowValueMode::ZeroExtend64);
+                let (word_addr, shift, mask) = lower_narrow_atomic_address(ctx, addr, ty);
+                ctx.emit(Inst::AtomicCas {
+                    ty,
+                    dst: rd,
+                    addr: word_addr,
+                    expected,
+                    replacement,
+                    shift,
+                    mask,
+                });
+            }
+        }
+
+        Opcode::AtomicLoad => {
+            // Sequentially consistent loads are mapped to
+            // `fence rw,rw; l{b,h,w,d}; fence r,rw`.
+            let ty = ty.unwrap();
+            assert!(is_valid_atomic_transaction_ty(ty));
+            let rd = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let addr = put_input_in_reg(ctx, inputs[0], NarrowValueMode::None);
+            let flags = ctx.memflags(insn).unwrap();
+            ctx.emit(Inst::Fence {
+                pred: FENCE_RW,
+                succ: FENCE_RW,
+            });
+            ctx.emit(Inst::Load {
+                rd,
+                op: LoadOP::from_type(ty),
+                flags,
+                from: AMode::reg_offset(addr, 0, ty),
+            });
+            ctx.emit(Inst::Fence {
+                pred: FENCE_R,
+                succ: FENCE_RW,
+            });
+        }
+
+        Opcode::AtomicStore => {
+            // Sequentially consistent stores are mapped to
+            // `fence rw,w; s{b,h,w,d}`.
+            let ty = ctx.input_ty(insn, 0);
+            assert!(is_valid_atomic_transaction_ty(ty));
+            let src = put_input_in_reg(ctx, inputs[0], NarrowValueMode::None);
+            let addr = put_input_in_reg(ctx, inputs[1], NarrowValueMode::None);
+            let flags = ctx.memflags(insn).unwrap();
+            ctx.emit(Inst::Fence {
+                pred: FENCE_RW,
+                succ: FENCE_W,
+            });
+            ctx.emit(Inst::Store {
+                to: AMode::reg_offset(addr, 0, ty),
+                op: StoreOP::from_type(ty),
+                flags,
+                src,
+            });
+        }
+
+        Opcode::Fence => {
+            ctx.emit(Inst::Fence {
+                pred: FENCE_RW,
+                succ: FENCE_RW,
+            });
+        }
+
+        Opcode::StackLoad | Opcode::StackStore => {
+            panic!("Direct stack memory access not supported; should not be used by Wasm");
+        }
+
+        Opcode::HeapAddr => {
+            panic!("heap_addr should have been removed by legalization!");
+        }
+
+        Opcode::TableAddr => {
+            panic!("table_addr should have been removed by legalization!");
+        }
+
+        Opcode::Nop => {
+            // Nothing.
+        }
+
+        Opcode::Select => {
+            let cmp = lower_condition(ctx, inputs[0])?;
+            let cond = condition_to_reg(ctx, cmp);
+            lower_select(ctx, cond, inputs[1], inputs[2], outputs[0], ty.unwrap());
+        }
+
+        Opcode::Selectif | Opcode::SelectifSpectreGuard => {
+            let condcode = ctx.data(insn).cond_code().unwrap();
+            let cmp = lower_flags_condition(ctx, inputs[0], condcode)?;
+            let cond = condition_to_reg(ctx, cmp);
+            lower_select(ctx, cond, inputs[1], inputs[2], outputs[0], ty.unwrap());
+        }
+
+        Opcode::Bitselect => {
+            let ty = ty.unwrap();
+            let rd = get_output_reg(ctx, outputs[0]);
+            let c = put_input_in_regs(ctx, inputs[0]);
+            let x = put_input_in_regs(ctx, inputs[1]);
+            let y = put_input_in_regs(ctx, inputs[2]);
+            if ty.is_float() {
+                return unsupported(ctx);
+            }
+            for i in 0..rd.len() {
+                let (c, x, y) = (c.regs()[i], x.regs()[i], y.regs()[i]);
+                // rd = (x & c) | (y & !c)
+                let x_bits = alu_rrr(ctx, AluOPRRR::And, x, c);
+                let y_bits = if isa_flags.has_zbb() {
+                    alu_rrr(ctx, AluOPRRR::Andn, y, c)
+                } else {
+                    let not_c = alu_rr_imm12(ctx, AluOPRRI::Xori, c, -1);
+                    alu_rrr(ctx, AluOPRRR::And, y, not_c)
+                };
+                ctx.emit(Inst::AluRRR {
+                    alu_op: AluOPRRR::Or,
+                    rd: rd.regs()[i],
+                    rs1: x_bits,
+                    rs2: y_bits,
+                });
+            }
+        }
+
+        Opcode::Breduce | Opcode::Copy => {
+            // Narrow booleans need not be reduced: their upper bits are
+            // undefined, and the representation of `true` is the same in the
+            // low bit.
+            let rd = get_output_reg(ctx, outputs[0]);
+            let rn = put_input_in_regs(ctx, inputs[0]);
+            let ty = ty.unwrap();
+            let (_, tys) = Inst::rc_for_type(ty)?;
+            for ((&dst, &src), &ty) in rd.regs().iter().zip(rn.regs().iter()).zip(tys.iter()) {
+                ctx.emit(Inst::gen_move(dst, src, ty));
+            }
+        }
+
+        Opcode::Bextend | Opcode::Bmask => {
+            // Bextend and Bmask both simply sign-extend. This works for:
+            // - Bextend, because booleans are stored as 0 / -1, so we
+            //   sign-extend the -1 to a -1 in the wider width.
+            // - Bmask, because the resulting integer mask value must be
+            //   all-ones (-1) if the argument is true.
+            //
+            // A `b1` is stored as 0 / 1, so its low bit is negated instead.
+            let from_ty = ctx.input_ty(insn, 0);
+            let to_ty = ty.unwrap();
+            if from_ty == I128 || from_ty == B128 {
+                return unsupported(ctx);
+            }
+            let rn = if from_ty == B1 {
+                let bit = put_input_in_reg(ctx, inputs[0], NarrowValueMode::ZeroExtend64);
+                alu_rrr(ctx, AluOPRRR::Sub, zero_reg(), bit)
+            } else {
+                put_input_in_reg(ctx, inputs[0], NarrowValueMode::SignExtend64)
+            };
+            let rd = get_output_reg(ctx, outputs[0]);
+            ctx.emit(Inst::gen_move(rd.regs()[0], rn, I64));
+            if to_ty == I128 || to_ty == B128 {
+                ctx.emit(Inst::gen_move(rd.regs()[1], rn, I64));
+            }
+        }
+
+        Opcode::IsNull | Opcode::IsInvalid => {
+            // Null references are represented by the constant value 0; invalid
+            // references are represented by the constant value -1.
+            let rd = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let rn = put_input_in_reg(ctx, inputs[0], NarrowValueMode::None);
+            let rn = match op {
+                Opcode::IsNull => rn,
+                Opcode::IsInvalid => alu_rr_imm12(ctx, AluOPRRI::Addi, rn, 1),
+                _ => unreachable!(),
+            };
+            let bit = alu_rr_imm12(ctx, AluOPRRI::Sltiu, rn, 1);
+            materialize_bool_result(ctx, rd, ty.unwrap(), bit);
+        }
+
+        Opcode::Icmp => {
+            let condcode = ctx.data(insn).cond_code().unwrap();
+            let rd = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let in_ty = ctx.input_ty(insn, 0);
+            let bit = lower_icmp(ctx, condcode, inputs[0], inputs[1], in_ty)?;
+            materialize_bool_result(ctx, rd, ty.unwrap(), bit);
+        }
+
+        Opcode::Fcmp => {
+            let condcode = ctx.data(insn).fp_cond_code().unwrap();
+            let rd = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let in_ty = ctx.input_ty(insn, 0);
+            let bit = lower_fcmp(ctx, condcode, inputs[0], inputs[1], in_ty);
+            materialize_bool_result(ctx, rd, ty.unwrap(), bit);
+        }
+
+        Opcode::Trueif => {
+            let condcode = ctx.data(insn).cond_code().unwrap();
+            let rd = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let cmp = lower_flags_condition(ctx, inputs[0], condcode)?;
+            let bit = lower_int_compare_to_bit(ctx, cmp);
+            materialize_bool_result(ctx, rd, ty.unwrap(), bit);
+        }
+
+        Opcode::Trueff => {
+            let condcode = ctx.data(insn).fp_cond_code().unwrap();
+            let rd = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let cmp = lower_fflags_condition(ctx, inputs[0], condcode);
+            let bit = lower_int_compare_to_bit(ctx, cmp);
+            materialize_bool_result(ctx, rd, ty.unwrap(), bit);
+        }
+
+        Opcode::Ifcmp | Opcode::Ffcmp => {
+            // An Ifcmp/Ffcmp must always be seen as a use of a brif/brff or trueif/trueff
+            // instruction. This will always be the case as long as the IR uses an Ifcmp/Ffcmp from
+            // the same block, or a dominating block. In other words, it cannot pass through a BB
+            // param (phi). The flags pass of the verifier will ensure this.
+            panic!("Should never reach ifcmp as isel root!");
+        }
+
+        Opcode::IaddIfcout => {
+            // The carry is recomputed by the flags' users, see
+            // `lower_flags_condition`; only the sum is produced here.
+            let rd = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let rs1 = put_input_in_reg(ctx, inputs[0], NarrowValueMode::None);
+            let rs2 = put_input_in_reg(ctx, inputs[1], NarrowValueMode::None);
+            ctx.emit(Inst::AluRRR {
+                alu_op: AluOPRRR::Add,
+                rd,
+                rs1,
+                rs2,
+            });
+        }
+
+        Opcode::FallthroughReturn | Opcode::Return => {
+            for (i, input) in inputs.iter().enumerate() {
+                // N.B.: the upper bits of narrow return values are undefined;
+                // the ABI code extends them if the signature asks for it.
+                let src_regs = put_input_in_regs(ctx, *input);
+                let retval_regs = ctx.retval(i);
+
+                assert_eq!(src_regs.len(), retval_regs.len());
+                let ty = ctx.input_ty(insn, i);
+                let (_, tys) = Inst::rc_for_type(ty)?;
+
+                src_regs
+                    .regs()
+                    .iter()
+                    .zip(retval_regs.regs().iter())
+                    .zip(tys.iter())
+                    .for_each(|((&src, &dst), &ty)| {
+                        ctx.emit(Inst::gen_move(dst, src, ty));
+                    });
+            }
+            // N.B.: the Ret itself is generated by the ABI.
+        }
+
+        Opcode::Debugtrap => {
+            ctx.emit(Inst::EBreak);
+        }
+
+        Opcode::Trap | Opcode::ResumableTrap => {
+            let trap_code = ctx.data(insn).trap_code().unwrap();
+            ctx.emit_safepoint(Inst::Udf { trap_code });
+        }
+
+        Opcode::Trapif => {
+            let trap_code = ctx.data(insn).trap_code().unwrap();
+            let condcode = ctx.data(insn).cond_code().unwrap();
+            let kind = lower_flags_condition(ctx, inputs[0], condcode)?;
+            ctx.emit_safepoint(Inst::TrapIf { kind, trap_code });
+        }
+
+        Opcode::Trapff => {
+            let trap_code = ctx.data(insn).trap_code().unwrap();
+            let condcode = ctx.data(insn).fp_cond_code().unwrap();
+            let kind = lower_fflags_condition(ctx, inputs[0], condcode);
+            ctx.emit_safepoint(Inst::TrapIf { kind, trap_code });
+        }
+
+        Opcode::Trapz | Opcode::Trapnz | Opcode::ResumableTrapnz => {
+            panic!("trapz / trapnz / resumable_trapnz should have been removed by legalization!");
+        }
+
+        Opcode::FuncAddr => {
+            let rd = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let (extname, _) = ctx.call_target(insn).unwrap();
+            let extname = extname.clone();
+            ctx.emit(Inst::LoadExtName {
+                rd,
+                name: Box::new(extname),
+                offset: 0,
+            });
+        }
+
+        Opcode::GlobalValue => {
+            panic!("global_value should have been removed by legalization!");
+        }
+
+        Opcode::SymbolValue => {
+            let rd = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let (extname, _, offset) = ctx.symbol_value(insn).unwrap();
+            let extname = extname.clone();
+            ctx.emit(Inst::LoadExtName {
+                rd,
+                name: Box::new(extname),
+                offset,
+            });
+        }
+
+        Opcode::ReturnCall | Opcode::ReturnCallIndirect => {
+            return Err(CodegenError::Unsupported(format!(
+                "{}: tail calls are not supported on this target",
+                op
+            )));
+        }
+
+        Opcode::Call | Opcode::CallIndirect => {
+            let caller_conv = ctx.abi().call_conv();
+            let (mut abi, inputs) = match op {
+                Opcode::Call => {
+                    let (extname, dist) = ctx.call_target(insn).unwrap();
+                    let extname = extname.clone();
+                    let sig = ctx.call_sig(insn).unwrap();
+                    assert!(inputs.len() == sig.params.len());
+                    assert!(outputs.len() == sig.returns.len());
+                    (
+                        Riscv64ABICaller::from_func(sig, &extname, dist, caller_conv, flags)?,
+                        &inputs[..],
+                    )
+                }
+                Opcode::CallIndirect => {
+                    let ptr = put_input_in_reg(ctx, inputs[0], NarrowValueMode::ZeroExtend64);
+                    let sig = ctx.call_sig(insn).unwrap();
+                    assert!(inputs.len() - 1 == sig.params.len());
+                    assert!(outputs.len() == sig.returns.len());
+                    (
+                        Riscv64ABICaller::from_ptr(sig, ptr, op, caller_conv, flags)?,
+                        &inputs[1..],
+                    )
+                }
+                _ => unreachable!(),
+            };
+
+            abi.emit_stack_pre_adjust(ctx);
+            assert!(inputs.len() == abi.num_args());
+            for i in abi.get_copy_to_arg_order() {
+                let input = inputs[i];
+                let arg_regs = put_input_in_regs(ctx, input);
+                abi.emit_copy_regs_to_arg(ctx, i, arg_regs);
+            }
+            abi.emit_call(ctx);
+            for (i, output) in outputs.iter().enumerate() {
+                let retval_regs = get_output_reg(ctx, *output);
+                abi.emit_copy_retval_to_regs(ctx, i, retval_regs);
+            }
+            abi.emit_stack_post_adjust(ctx);
+        }
+
+        Opcode::GetPinnedReg | Opcode::SetPinnedReg => {
+            return Err(CodegenError::Unsupported(format!(
+                "{}: the pinned register is not supported on riscv64",
+                op
+            )));
+        }
+
+        Opcode::Jump
+        | Opcode::Brz
+        | Opcode::Brnz
+        | Opcode::BrIcmp
+        | Opcode::Brif
+        | Opcode::Brff
+        | Opcode::BrTable => {
+            panic!("Branch opcode reached non-branch lowering logic!");
+        }
+
+        Opcode::Isplit => {
+            assert_eq!(
+                ctx.input_ty(insn, 0),
+                I128,
+                "Isplit only implemented for i128's"
+            );
+            assert_eq!(ty.unwrap(), I64);
+
+            let src_regs = put_input_in_regs(ctx, inputs[0]);
+            let dst_lo = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let dst_hi = get_output_reg(ctx, outputs[1]).only_reg().unwrap();
+
+            ctx.emit(Inst::gen_move(dst_lo, src_regs.regs()[0], I64));
+            ctx.emit(Inst::gen_move(dst_hi, src_regs.regs()[1], I64));
+        }
+
+        Opcode::Iconcat => {
+            assert_eq!(
+                ctx.output_ty(insn, 0),
+                I128,
+                "Iconcat only implemented for i128's"
+            );
+
+            let src_lo = put_input_in_reg(ctx, inputs[0], NarrowValueMode::None);
+            let src_hi = put_input_in_reg(ctx, inputs[1], NarrowValueMode::None);
+            let dst = get_output_reg(ctx, outputs[0]);
+
+            ctx.emit(Inst::gen_move(dst.regs()[0], src_lo, I64));
+            ctx.emit(Inst::gen_move(dst.regs()[1], src_hi, I64));
+        }
+
+        Opcode::Imax | Opcode::Umax | Opcode::Umin | Opcode::Imin => {
+            let ty = ty.unwrap();
+            if ty == I128 {
+                return unsupported(ctx);
+            }
+            let rd = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let narrow_mode = match op {
+                Opcode::Imax | Opcode::Imin => NarrowValueMode::SignExtend64,
+                _ => NarrowValueMode::ZeroExtend64,
+            };
+            let rs1 = put_input_in_reg(ctx, inputs[0], narrow_mode);
+            let rs2 = put_input_in_reg(ctx, inputs[1], narrow_mode);
+            if isa_flags.has_zbb() {
+                let alu_op = match op {
+                    Opcode::Imax => AluOPRRR::Max,
+                    Opcode::Umax => AluOPRRR::Maxu,
+                    Opcode::Imin => AluOPRRR::Min,
+                    Opcode::Umin => AluOPRRR::Minu,
+                    _ => unreachable!(),
+                };
+                ctx.emit(Inst::AluRRR {
+                    alu_op,
+                    rd,
+                    rs1,
+                    rs2,
+                });
+            } else {
+                let kind = match op {
+                    Opcode::Imax => IntCC::SignedGreaterThan,
+                    Opcode::Umax => IntCC::UnsignedGreaterThan,
+                    Opcode::Imin => IntCC::SignedLessThan,
+                    Opcode::Umin => IntCC::UnsignedLessThan,
+                    _ => unreachable!(),
+                };
+                let cond = lower_int_compare_to_bit(ctx, IntegerCompare { kind, rs1, rs2 });
+                ctx.emit(Inst::Select {
+                    rd,
+                    cond,
+                    x: rs1,
+                    y: rs2,
+                    ty: I64,
+                });
+            }
+        }
+
+        Opcode::Iabs => {
+            let ty = ty.unwrap();
+            if ty == I128 {
+                return unsupported(ctx);
+            }
+            // abs(x) = (x ^ s) - s, where s = x >> 63 (arithmetic).
+            let rd = get_output_reg(ctx, outputs[0]).only_reg().unwrap();
+            let rn = put_input_in_reg(ctx, inputs[0], NarrowValueMode::SignExtend64);
+            let sign = alu_rr_imm12(ctx, AluOPRRI::Srai, rn, 63);
+            let flipped = alu_rrr(ctx, AluOPRRR::Xor, rn, sign);
+            ctx.emit(Inst::AluRRR {
+                alu_op: AluOPRRR::Sub,
+                rd,
+                rs1: flipped,
+                rs2: sign,
+            });
+        }
+
+        Opcode::Fadd
+        | Opcode::Fsub
+        | Opcode::Fmul
+        | Opcode::Fdiv
+        | Opcode::Sqrt
+        | Opcode::Fneg
+        | Opcode::Fabs
+        | Opcode::Fcopysign
+        | Opcode::Fma
+        | Opcode::Fpromote
+        | Opcode::Fdemote
+        | Opcode::Fmin
+        | Opcode::Fmax
+        | Opcode::FminPseudo
+        | Opcode::FmaxPseudo
+        | Opcode::Ceil
+        | Opcode::Floor
+        | Opcode::Trunc
+        | Opcode::Nearest
+        | Opcode::FcvtFromSint
+        | Opcode::FcvtFromUint
+        | Opcode::FcvtToSint
+        | Opcode::FcvtToUint
+        | Opcode::FcvtToSintSat
+        | Opcode::FcvtToUintSat
+        | Opcode::Bitcast => {
+            // These are all lowered in ISLE for the types riscv64 supports.
+            return unsupported(ctx);
+        }
+
+        Opcode::TlsValue => {
+            return Err(CodegenError::Unsupported(format!(
+                "riscv64: thread-local storage is not supported"
+            )));
+        }
+
+        Opcode::IaddImm
+        | Opcode::ImulImm
+        | Opcode::UdivImm
+        | Opcode::SdivImm
+        | Opcode::UremImm
+        | Opcode::SremImm
+        | Opcode::IrsubImm
+        | Opcode::IaddCin
+        | Opcode::IaddIfcin
+        | Opcode::IaddCout
+        | Opcode::IaddCarry
+        | Opcode::IaddIfcarry
+        | Opcode::IsubBin
+        | Opcode::IsubIfbin
+        | Opcode::IsubBout
+        | Opcode::IsubIfbout
+        | Opcode::IsubBorrow
+        | Opcode::IsubIfborrow
+        | Opcode::BandImm
+        | Opcode::BorImm
+        | Opcode::BxorImm
+        | Opcode::RotlImm
+        | Opcode::RotrImm
+        | Opcode::IshlImm
+        | Opcode::UshrImm
+        | Opcode::SshrImm
+        | Opcode::IcmpImm
+        | Opcode::IfcmpImm => {
+            panic!("ALU+imm and ALU+carry ops should not appear here!");
+        }
+
+        Opcode::Vconst
+        | Opcode::RawBitcast
+        | Opcode::Shuffle
+        | Opcode::Swizzle
+        | Opcode::Insertlane
+        | Opcode::Extractlane
+        | Opcode::ScalarToVector
+        | Opcode::Splat
+        | Opcode::VanyTrue
+        | Opcode::VallTrue
+        | Opcode::VhighBits
+        | Opcode::Vselect
+        | Opcode::Snarrow
+        | Opcode::Unarrow
+        | Opcode::Uunarrow
+        | Opcode::SwidenLow
+        | Opcode::SwidenHigh
+        | Opcode::UwidenLow
+        | Opcode::UwidenHigh
+        | Opcode::WideningPairwiseDotProductS
+        | Opcode::SqmulRoundSat
+        | Opcode::FvpromoteLow
+        | Opcode::Fvdemote
+        | Opcode::FcvtLowFromSint
+        | Opcode::IaddPairwise
+        | Opcode::AvgRound
+        | Opcode::UaddSat
+        | Opcode::SaddSat
+        | Opcode::UsubSat
+        | Opcode::SsubSat
+        | Opcode::Sload8x8
+        | Opcode::Uload8x8
+        | Opcode::Sload16x4
+        | Opcode::Uload16x4
+        | Opcode::Sload32x2
+        | Opcode::Uload32x2
+        | Opcode::Sload8x8Complex
+        | Opcode::Uload8x8Complex
+        | Opcode::Sload16x4Complex
+        | Opcode::Uload16x4Complex
+        | Opcode::Sload32x2Complex
+        | Opcode::Uload32x2Complex => {
+            // SIMD instructions are rejected before reaching this point.
+            return unsupported(ctx);
+        }
+
+        Opcode::ConstAddr | Opcode::Vconcat | Opcode::Vsplit | Opcode::IfcmpSp => {
+            return Err(CodegenError::Unsupported(format!(
+                "Unimplemented lowering: {}",
+                op
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Offset an address by `n` bytes.
+fn amode_add(mem: &AMode, n: i64) -> AMode {
+    match mem {
+        &AMode::RegOffset(reg, off, ty) => AMode::RegOffset(reg, off + n, ty),
+        &AMode::SPOffset(off, ty) => AMode::SPOffset(off + n, ty),
+        &AMode::FPOffset(off, ty) => AMode::FPOffset(off + n, ty),
+        &AMode::NominalSPOffset(off, ty) => AMode::NominalSPOffset(off + n, ty),
+    }
+}
+
+/// Convert a condition into a register that is nonzero exactly when the
+/// condition holds.
+fn condition_to_reg<C: LowerCtx<I = Inst>>(ctx: &mut C, cmp: IntegerCompare) -> Reg {
+    if cmp.kind == IntCC::NotEqual && cmp.rs2 == zero_reg() {
+        cmp.rs1
+    } else {
+        lower_int_compare_to_bit(ctx, cmp)
+    }
+}
+
+/// Lower a select of `x` or `y`, on whether `cond` is nonzero.
+fn lower_select<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    cond: Reg,
+    x: InsnInput,
+    y: InsnInput,
+    output: InsnOutput,
+    ty: Type,
+) {
+    let rd = get_output_reg(ctx, output);
+    let x = put_input_in_regs(ctx, x);
+    let y = put_input_in_regs(ctx, y);
+    let ty = if rd.len() > 1 { I64 } else { ty };
+    for i in 0..rd.len() {
+        ctx.emit(Inst::Select {
+            rd: rd.regs()[i],
+            cond,
+            x: x.regs()[i],
+            y: y.regs()[i],
+            ty,
+        });
+    }
+}
+
+/// Compute the address of the aligned word containing a narrow atomic access
+/// at `addr`, the bit offset of the accessed value within it, and the mask
+/// selecting the value.
+fn lower_narrow_atomic_address<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    addr: Reg,
+    ty: Type,
+) -> (Reg, Reg, Reg) {
+    let word_addr = alu_rr_imm12(ctx, AluOPRRI::Andi, addr, -4);
+    let byte_offset = alu_rr_imm12(ctx, AluOPRRI::Andi, addr, 3);
+    let shift = alu_rr_imm12(ctx, AluOPRRI::Slli, byte_offset, 3);
+    let ones = ctx.alloc_tmp(I64).only_reg().unwrap();
+    lower_constant_u64(ctx, ones, (1 << ty.bits()) - 1);
+    let mask = alu_rrr(ctx, AluOPRRR::Sll, ones.to_reg(), shift);
+    (word_addr, shift, mask)
+}
+
+/// Load a 64-bit constant into a new register.
+fn constant_reg<C: LowerCtx<I = Inst>>(ctx: &mut C, value: u64) -> Reg {
+    let rd = ctx.alloc_tmp(I64).only_reg().unwrap();
+    lower_constant_u64(ctx, rd, value);
+    rd.to_reg()
+}
+
+/// Count the set bits of a 64-bit register, with the classic SWAR sequence.
+fn lower_popcnt64<C: LowerCtx<I = Inst>>(ctx: &mut C, x: Reg) -> Reg {
+    // x = x - ((x >> 1) & 0x5555...)
+    let m1 = constant_reg(ctx, 0x5555_5555_5555_5555);
+    let t = alu_rr_imm12(ctx, AluOPRRI::Srli, x, 1);
+    let t = alu_rrr(ctx, AluOPRRR::And, t, m1);
+    let x = alu_rrr(ctx, AluOPRRR::Sub, x, t);
+    // x = (x & 0x3333...) + ((x >> 2) & 0x3333...)
+    let m2 = constant_reg(ctx, 0x3333_3333_3333_3333);
+    let lo = alu_rrr(ctx, AluOPRRR::And, x, m2);
+    let hi = alu_rr_imm12(ctx, AluOPRRI::Srli, x, 2);
+    let hi = alu_rrr(ctx, AluOPRRR::And, hi, m2);
+    let x = alu_rrr(ctx, AluOPRRR::Add, lo, hi);
+    // x = (x + (x >> 4)) & 0x0f0f...
+    let m4 = constant_reg(ctx, 0x0f0f_0f0f_0f0f_0f0f);
+    let t = alu_rr_imm12(ctx, AluOPRRI::Srli, x, 4);
+    let x = alu_rrr(ctx, AluOPRRR::Add, x, t);
+    let x = alu_rrr(ctx, AluOPRRR::And, x, m4);
+    // Sum the byte counts into the top byte.
+    let h01 = constant_reg(ctx, 0x0101_0101_0101_0101);
+    let x = alu_rrr(ctx, AluOPRRR::Mul, x, h01);
+    alu_rr_imm12(ctx, AluOPRRI::Srli, x, 56)
+}
+
+/// Count the leading zeros of a 64-bit register: smear the highest set bit
+/// to the right and count the bits that remain clear.
+fn lower_clz64<C: LowerCtx<I = Inst>>(ctx: &mut C, x: Reg) -> Reg {
+    let mut x = x;
+    for shift in [1, 2, 4, 8, 16, 32] {
+        let t = alu_rr_imm12(ctx, AluOPRRI::Srli, x, shift);
+        x = alu_rrr(ctx, AluOPRRR::Or, x, t);
+    }
+    let not = alu_rr_imm12(ctx, AluOPRRI::Xori, x, -1);
+    lower_popcnt64(ctx, not)
+}
+
+/// Reverse the bits of a 64-bit register by swapping ever larger groups of
+/// bits; with Zbb the bytes are reversed with a single `rev8`.
+fn lower_bitrev64<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    x: Reg,
+    isa_flags: &riscv64_settings::Flags,
+) -> Reg {
+    // x = ((x >> shift) & mask) | ((x & mask) << shift)
+    let swap = |ctx: &mut C, x: Reg, shift: i64, mask: u64| {
+        let m = constant_reg(ctx, mask);
+        let hi = alu_rr_imm12(ctx, AluOPRRI::Srli, x, shift);
+        let hi = alu_rrr(ctx, AluOPRRR::And, hi, m);
+        let lo = alu_rrr(ctx, AluOPRRR::And, x, m);
+        let lo = alu_rr_imm12(ctx, AluOPRRI::Slli, lo, shift);
+        alu_rrr(ctx, AluOPRRR::Or, hi, lo)
+    };
+    let x = swap(ctx, x, 1, 0x5555_5555_5555_5555);
+    let x = swap(ctx, x, 2, 0x3333_3333_3333_3333);
+    let x = swap(ctx, x, 4, 0x0f0f_0f0f_0f0f_0f0f);
+    if isa_flags.has_zbb() {
+        let rd = ctx.alloc_tmp(I64).only_reg().unwrap();
+        ctx.emit(Inst::AluRR {
+            alu_op: AluOPRR::Rev8,
+            rd,
+            rs: x,
+        });
+        rd.to_reg()
+    } else {
+        let x = swap(ctx, x, 8, 0x00ff_00ff_00ff_00ff);
+        let x = swap(ctx, x, 16, 0x0000_ffff_0000_ffff);
+        let hi = alu_rr_imm12(ctx, AluOPRRI::Srli, x, 32);
+        let lo = alu_rr_imm12(ctx, AluOPRRI::Slli, x, 32);
+        alu_rrr(ctx, AluOPRRR::Or, hi, lo)
+    }
+}
+
+//============================================================================
+// Lowering: main entry point for lowering a branch group
+
+pub(crate) fn lower_branch<C: LowerCtx<I = Inst>>(
+    ctx: &mut C,
+    branches: &[IRInst],
+    targets: &[MachLabel],
+) -> CodegenResult<()> {
+    // A block should end with at most two branches. The first may be a
+    // conditional branch; a conditional branch can be followed only by an
+    // unconditional branch or fallthrough. Otherwise, if only one branch,
+    // it may be an unconditional branch, a fallthrough, a return, or a
+    // trap. These conditions are verified by `is_ebb_basic()` during the
+    // verifier pass.
+    assert!(branches.len() <= 2);
+
+    if branches.len() == 2 {
+        // Must be a conditional branch followed by an unconditional branch.
+        let op0 = ctx.data(branches[0]).opcode();
+        let op1 = ctx.data(branches[1]).opcode();
+
+        assert!(op1 == Opcode::Jump);
+        let taken = BranchTarget::Label(targets[0]);
+        // not_taken target is the target of the second branch, even if it is a Fallthrough
+        // instruction: because we reorder blocks while we lower, the fallthrough in the new
+        // order is not (necessarily) the same as the fallthrough in CLIF. So we use the
+        // explicitly-provided target.
+        let not_taken = BranchTarget::Label(targets[1]);
+
+        let kind = match op0 {
+            Opcode::Brz | Opcode::Brnz => {
+                let flag_input = InsnInput {
+                    insn: branches[0],
+                    input: 0,
+                };
+                let kind = lower_condition(ctx, flag_input)?;
+                if op0 == Opcode::Brz {
+                    kind.inverse()
+                } else {
+                    kind
+                }
+            }
+            Opcode::BrIcmp => {
+                let condcode = ctx.data(branches[0]).cond_code().unwrap();
+                let lhs = InsnInput {
+                    insn: branches[0],
+                    input: 0,
+                };
+                let rhs = InsnInput {
+                    insn: branches[0],
+                    input: 1,
+                };
+                let ty = ctx.input_ty(branches[0], 0);
+                match condcode {
+                    IntCC::Overflow | IntCC::NotOverflow => {}
+                    _ if ty != I128 => {
+                        let (rs1, rs2) = put_cmp_inputs_in_regs(ctx, condcode, lhs, rhs);
+                        ctx.emit(Inst::CondBr {
+                            taken,
+                            not_taken,
+                            kind: IntegerCompare {
+                                kind: condcode,
+                                rs1,
+                                rs2,
+                            },
+                        });
+                        return Ok(());
+                    }
+                    _ => {}
+                }
+                let bit = lower_icmp(ctx, condcode, lhs, rhs, ty)?;
+                IntegerCompare {
+                    kind: IntCC::NotEqual,
+                    rs1: bit,
+                    rs2: zero_reg(),
+                }
+            }
+            Opcode::Brif => {
+                let condcode = ctx.data(branches[0]).cond_code().unwrap();
+                let flag_input = InsnInput {
+                    insn: branches[0],
+                    input: 0,
+                };
+                lower_flags_condition(ctx, flag_input, condcode)?
+            }
+            Opcode::Brff => {
+                let condcode = ctx.data(branches[0]).fp_cond_code().unwrap();
+                let flag_input = InsnInput {
+                    insn: branches[0],
+                    input: 0,
+                };
+                lower_fflags_condition(ctx, flag_input, condcode)
+            }
+            _ => unimplemented!(),
+        };
+        ctx.emit(Inst::CondBr {
+            taken,
+            not_taken,
+            kind,
+        });
+    } else {
+        // Must be an unconditional branch or an indirect branch.
+        let op = ctx.data(branches[0]).opcode();
+        match op {
+            Opcode::Jump => {
+                assert!(branches.len() == 1);
+                ctx.emit(Inst::Jal {
+                    dest: BranchTarget::Label(targets[0]),
+                });
+            }
+
+            Opcode::BrTable => {
+                // Expand `br_table index, default, JT` to a single
+                // `JTSequence`, which bounds-checks the index and jumps
+                // through an inline table of `jal` instructions. This must be
+                // *one* instruction in the vcode because we cannot allow
+                // regalloc to insert any spills/fills in the middle of the
+                // sequence; otherwise, the `auipc`'s PC-relative offset to the
+                // jumptable would be incorrect.
+                let jt_size = targets.len() - 1;
+                assert!(jt_size <= std::u32::MAX as usize);
+
+                // The sequence is at most ten instructions long, followed by
+                // the table.
+                ctx.emit(Inst::EmitIsland {
+                    needed_space: 4 * (10 + jt_size) as CodeOffset,
+                });
+
+                let index = put_input_in_reg(
+                    ctx,
+                    InsnInput {
+                        insn: branches[0],
+                        input: 0,
+                    },
+                    NarrowValueMode::ZeroExtend64,
+                );
+
+                let jt_targets: Vec<BranchTarget> = targets
+                    .iter()
+                    .skip(1)
+                    .map(|bix| BranchTarget::Label(*bix))
+                    .collect();
+                let default_target = BranchTarget::Label(targets[0]);
+                let targets_for_term: Vec<MachLabel> = targets.to_vec();
+                ctx.emit(Inst::JTSequence {
+                    index,
+                    info: Box::new(JTSequenceInfo {
+                        targets: jt_targets,
+                        default_target,
+                        targets_for_term,
+                    }),
+                });
+            }
+
+            _ => panic!("Unknown branch type!"),
+        }
+    }
+
+    Ok(())
+}
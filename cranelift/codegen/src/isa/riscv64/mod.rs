@@ -0,0 +1,325 @@
+//! RISC-V 64-bit Instruction Set Architecture.
+
+use crate::ir::condcodes::IntCC;
+use crate::ir::Function;
+use crate::isa::riscv64::settings as riscv64_settings;
+#[cfg(feature = "unwind")]
+use crate::isa::unwind::systemv::RegisterMappingError;
+use crate::isa::Builder as IsaBuilder;
+use crate::machinst::{
+    compile, MachBackend, MachCompileResult, MachTextSectionBuilder, TargetIsaAdapter,
+    TextSectionBuilder, VCode,
+};
+use crate::result::{CodegenError, CodegenResult};
+use crate::settings as shared_settings;
+use alloc::{boxed::Box, vec::Vec};
+use regalloc::{PrettyPrint, RealRegUniverse, Reg};
+use target_lexicon::{Architecture, Triple};
+
+// New backend:
+mod abi;
+pub(crate) mod inst;
+mod lower;
+mod lower_inst;
+mod settings;
+
+use inst::create_reg_universe;
+
+use self::inst::EmitInfo;
+
+/// A RISC-V 64 backend.
+pub struct Riscv64Backend {
+    triple: Triple,
+    flags: shared_settings::Flags,
+    isa_flags: riscv64_settings::Flags,
+    reg_universe: RealRegUniverse,
+}
+
+impl Riscv64Backend {
+    /// Create a new RISC-V 64 backend with the given (shared) flags.
+    pub fn new_with_flags(
+        triple: Triple,
+        flags: shared_settings::Flags,
+        isa_flags: riscv64_settings::Flags,
+    ) -> Riscv64Backend {
+        let reg_universe = create_reg_universe(&flags);
+        Riscv64Backend {
+            triple,
+            flags,
+            isa_flags,
+            reg_universe,
+        }
+    }
+
+    /// This performs lowering to VCode, register-allocates the code, computes block layout and
+    /// finalizes branches. The result is ready for binary emission.
+    fn compile_vcode(
+        &self,
+        func: &Function,
+        flags: shared_settings::Flags,
+    ) -> CodegenResult<VCode<inst::Inst>> {
+        let emit_info = EmitInfo::new(flags.clone(), self.isa_flags.clone());
+        let abi = Box::new(abi::Riscv64ABICallee::new(func, flags)?);
+        compile::compile::<Riscv64Backend>(func, self, abi, emit_info)
+    }
+}
+
+impl MachBackend for Riscv64Backend {
+    fn compile_function(
+        &self,
+        func: &Function,
+        want_disasm: bool,
+    ) -> CodegenResult<MachCompileResult> {
+        let flags = self.flags();
+        let vcode = self.compile_vcode(func, flags.clone())?;
+
+        let (buffer, bb_starts, bb_edges) = vcode.emit();
+        let frame_size = vcode.frame_size();
+        let stackslot_offsets = vcode.stackslot_offsets().clone();
+
+        let disasm = if want_disasm {
+            Some(vcode.show_rru(Some(&create_reg_universe(flags))))
+        } else {
+            None
+        };
+
+        let buffer = buffer.finish();
+
+        Ok(MachCompileResult {
+            buffer,
+            frame_size,
+            disasm,
+            value_labels_ranges: Default::default(),
+            stackslot_offsets,
+            bb_starts,
+            bb_edges,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "riscv64"
+    }
+
+    fn triple(&self) -> &Triple {
+        &self.triple
+    }
+
+    fn flags(&self) -> &shared_settings::Flags {
+        &self.flags
+    }
+
+    fn isa_flags(&self) -> Vec<shared_settings::Value> {
+        self.isa_flags.iter().collect()
+    }
+
+    fn reg_universe(&self) -> &RealRegUniverse {
+        &self.reg_universe
+    }
+
+    fn unsigned_add_overflow_condition(&self) -> IntCC {
+        // There are no flags on riscv64; `iadd_ifcout` is lowered to an add, and
+        // the carry out is recomputed at each use as `sum < lhs`.
+        IntCC::UnsignedLessThan
+    }
+
+    #[cfg(feature = "unwind")]
+    fn emit_unwind_info(
+        &self,
+        result: &MachCompileResult,
+        kind: crate::machinst::UnwindInfoKind,
+    ) -> CodegenResult<Option<crate::isa::unwind::UnwindInfo>> {
+        use crate::isa::unwind::UnwindInfo;
+        use crate::machinst::UnwindInfoKind;
+        Ok(match kind {
+            UnwindInfoKind::SystemV => {
+                let mapper = self::inst::unwind::systemv::RegisterMapper;
+                Some(UnwindInfo::SystemV(
+                    crate::isa::unwind::systemv::create_unwind_info_from_insts(
+                        &result.buffer.unwind_info[..],
+                        result.buffer.data.len(),
+                        &mapper,
+                    )?,
+                ))
+            }
+            _ => None,
+        })
+    }
+
+    #[cfg(feature = "unwind")]
+    fn create_systemv_cie(&self) -> Option<gimli::write::CommonInformationEntry> {
+        Some(inst::unwind::systemv::create_cie())
+    }
+
+    #[cfg(feature = "unwind")]
+    fn map_reg_to_dwarf(&self, reg: Reg) -> Result<u16, RegisterMappingError> {
+        inst::unwind::systemv::map_reg(reg).map(|reg| reg.0)
+    }
+
+    fn text_section_builder(&self, num_funcs: u32) -> Box<dyn TextSectionBuilder> {
+        Box::new(MachTextSectionBuilder::<inst::Inst>::new(num_funcs))
+    }
+}
+
+/// Create a new `isa::Builder`.
+pub fn isa_builder(triple: Triple) -> IsaBuilder {
+    assert!(matches!(triple.architecture, Architecture::Riscv64(_)));
+    IsaBuilder {
+        triple,
+        setup: riscv64_settings::builder(),
+        constructor: |triple, shared_flags, builder| {
+            if shared_flags.enable_probestack()
+                && shared_flags.probestack_strategy() == shared_settings::ProbestackStrategy::Inline
+            {
+                return Err(CodegenError::Unsupported(
+                    "riscv64: inline stack probes are not supported".into(),
+                ));
+            }
+            let isa_flags = riscv64_settings::Flags::new(&shared_flags, builder);
+            let backend = Riscv64Backend::new_with_flags(triple, shared_flags, isa_flags);
+            Ok(Box::new(TargetIsaAdapter::new(backend)))
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cursor::{Cursor, FuncCursor};
+    use crate::ir::types::*;
+    use crate::ir::{AbiParam, ExternalName, Function, InstBuilder, Signature};
+    use crate::isa::CallConv;
+    use crate::settings;
+    use crate::settings::Configurable;
+    use core::str::FromStr;
+    use target_lexicon::Triple;
+
+    #[test]
+    fn test_compile_function() {
+        let name = ExternalName::testcase("test0");
+        let mut sig = Signature::new(CallConv::SystemV);
+        sig.params.push(AbiParam::new(I32));
+        sig.returns.push(AbiParam::new(I32));
+        let mut func = Function::with_name_signature(name, sig);
+
+        let bb0 = func.dfg.make_block();
+        let arg0 = func.dfg.append_block_param(bb0, I32);
+
+        let mut pos = FuncCursor::new(&mut func);
+        pos.insert_block(bb0);
+        let v0 = pos.ins().iconst(I32, 0x1234);
+        let v1 = pos.ins().iadd(arg0, v0);
+        pos.ins().return_(&[v1]);
+
+        let mut shared_flags_builder = settings::builder();
+        shared_flags_builder.set("opt_level", "none").unwrap();
+        let shared_flags = settings::Flags::new(shared_flags_builder);
+        let isa_flags = riscv64_settings::Flags::new(&shared_flags, riscv64_settings::builder());
+        let backend = Riscv64Backend::new_with_flags(
+            Triple::from_str("riscv64").unwrap(),
+            shared_flags,
+            isa_flags,
+        );
+        let buffer = backend.compile_function(&mut func, false).unwrap().buffer;
+        let code = &buffer.data[..];
+
+        // lui a4, 1
+        // addiw a4, a4, 564
+        // add a0, a0, a4
+        // ret
+        let golden = vec![
+            0x37, 0x17, 0x00, 0x00, 0x1b, 0x07, 0x47, 0x23, 0x33, 0x05, 0xe5, 0x00, 0x67, 0x80,
+            0x00, 0x00,
+        ];
+
+        assert_eq!(code, &golden[..]);
+    }
+
+    #[test]
+    fn test_branch_lowering() {
+        let name = ExternalName::testcase("test0");
+        let mut sig = Signature::new(CallConv::SystemV);
+        sig.params.push(AbiParam::new(I32));
+        sig.returns.push(AbiParam::new(I32));
+        let mut func = Function::with_name_signature(name, sig);
+
+        let bb0 = func.dfg.make_block();
+        let arg0 = func.dfg.append_block_param(bb0, I32);
+        let bb1 = func.dfg.make_block();
+        let bb2 = func.dfg.make_block();
+        let bb3 = func.dfg.make_block();
+
+        let mut pos = FuncCursor::new(&mut func);
+        pos.insert_block(bb0);
+        let v0 = pos.ins().iconst(I32, 0x1234);
+        let v1 = pos.ins().iadd(arg0, v0);
+        pos.ins().brnz(v1, bb1, &[]);
+        pos.ins().jump(bb2, &[]);
+        pos.insert_block(bb1);
+        pos.ins().brnz(v1, bb2, &[]);
+        pos.ins().jump(bb3, &[]);
+        pos.insert_block(bb2);
+        let v2 = pos.ins().iadd(v1, v0);
+        pos.ins().brnz(v2, bb2, &[]);
+        pos.ins().jump(bb1, &[]);
+        pos.insert_block(bb3);
+        let v3 = pos.ins().isub(v1, v0);
+        pos.ins().return_(&[v3]);
+
+        let mut shared_flags_builder = settings::builder();
+        shared_flags_builder.set("opt_level", "none").unwrap();
+        let shared_flags = settings::Flags::new(shared_flags_builder);
+        let isa_flags = riscv64_settings::Flags::new(&shared_flags, riscv64_settings::builder());
+        let backend = Riscv64Backend::new_with_flags(
+            Triple::from_str("riscv64").unwrap(),
+            shared_flags,
+            isa_flags,
+        );
+        let result = backend
+            .compile_function(&mut func, /* want_disasm = */ false)
+            .unwrap();
+        let code = &result.buffer.data[..];
+
+        // lui t3, 1
+        // addiw t3, t3, 564
+        // add a4, a0, t3
+        // sext.w a1, a4
+        // bnez a1, 0x28
+        // lui a5, 1
+        // addiw a5, a5, 564
+        // add a0, a4, a5
+        // sext.w a6, a0
+        // bnez a6, 0x14
+        // sext.w t1, a4
+        // bnez t1, 0x14
+        // lui t3, 1
+        // addiw t3, t3, 564
+        // sub a0, a4, t3
+        // ret
+        let golden = vec![
+            55, 30, 0, 0, 27, 14, 78, 35, 51, 7, 197, 1, 155, 5, 7, 0, 99, 156, 5, 0, 183, 23, 0,
+            0, 155, 135, 71, 35, 51, 5, 247, 0, 27, 8, 5, 0, 227, 24, 8, 254, 27, 3, 7, 0, 227, 20,
+            3, 254, 55, 30, 0, 0, 27, 14, 78, 35, 51, 5, 199, 65, 103, 128, 0, 0,
+        ];
+
+        assert_eq!(code, &golden[..]);
+    }
+
+    #[test]
+    fn test_inline_probestack_rejected() {
+        let mut shared_flags_builder = settings::builder();
+        shared_flags_builder
+            .set("probestack_strategy", "inline")
+            .unwrap();
+        let shared_flags = settings::Flags::new(shared_flags_builder.clone());
+        let isa = isa_builder(Triple::from_str("riscv64").unwrap()).finish(shared_flags);
+        assert!(matches!(isa, Err(CodegenError::Unsupported(_))));
+
+        // The strategy doesn't matter when stack probes are disabled.
+        shared_flags_builder
+            .set("enable_probestack", "false")
+            .unwrap();
+        let shared_flags = settings::Flags::new(shared_flags_builder);
+        let isa = isa_builder(Triple::from_str("riscv64").unwrap()).finish(shared_flags);
+        assert!(isa.is_ok());
+    }
+}
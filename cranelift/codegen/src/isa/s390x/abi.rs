@@ -182,6 +182,13 @@ impl ABIMachineSpec for S390xMachineDeps {
         args_or_rets: ArgsOrRets,
         add_ret_area_ptr: bool,
     ) -> CodegenResult<(Vec<ABIArg>, i64, Option<usize>)> {
+        if call_conv == isa::CallConv::Tail {
+            return Err(CodegenError::Unsupported(format!(
+                "calling convention {} is not supported on s390x",
+                call_conv
+            )));
+        }
+
         let mut next_gpr = 0;
         let mut next_fpr = 0;
         let mut next_stack: u64 = 0;
@@ -340,7 +347,7 @@ impl ABIMachineSpec for S390xMachineDeps {
         }
     }
 
-    fn gen_ret() -> Inst {
+    fn gen_ret(_stack_bytes_to_pop: u32) -> Inst {
         Inst::Ret { link: gpr(14) }
     }
 
@@ -456,6 +463,12 @@ impl ABIMachineSpec for S390xMachineDeps {
         smallvec![]
     }
 
+    fn gen_inline_probestack(_: u32, _: u32) -> SmallInstVec<Self::I> {
+        // Inline stack probes aren't implemented here yet; the ISA builder
+        // rejects the inline strategy when stack probes are enabled.
+        unreachable!("inline stack probes are not supported on s390x")
+    }
+
     // Returns stack bytes used as well as instructions. Does not adjust
     // nominal SP offset; abi_impl generic code will do that.
     fn gen_clobber_save(
@@ -622,6 +635,7 @@ impl ABIMachineSpec for S390xMachineDeps {
         tmp: Writable<Reg>,
         _callee_conv: isa::CallConv,
         _caller_conv: isa::CallConv,
+        _callee_pop_size: u32,
     ) -> SmallVec<[(InstIsSafepoint, Inst); 2]> {
         let mut insts = SmallVec::new();
         match &dest {
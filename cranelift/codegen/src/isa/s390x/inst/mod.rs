@@ -10,7 +10,7 @@ use crate::machinst::*;
 use crate::{settings, CodegenError, CodegenResult};
 
 use regalloc::{PrettyPrint, RegUsageCollector, RegUsageMapper};
-use regalloc::{RealRegUniverse, Reg, RegClass, SpillSlot, VirtualReg, Writable};
+use regalloc::{RealRegUniverse, Reg, RegClass, Writable};
 
 use alloc::boxed::Box;
 use alloc::vec::Vec;
@@ -2470,10 +2470,6 @@ impl MachInst for Inst {
         }
     }
 
-    fn maybe_direct_reload(&self, _reg: VirtualReg, _slot: SpillSlot) -> Option<Inst> {
-        None
-    }
-
     fn rc_for_type(ty: Type) -> CodegenResult<(&'static [RegClass], &'static [Type])> {
         match ty {
             types::I8 => Ok((&[RegClass::I64], &[types::I8])),
@@ -2522,10 +2518,6 @@ impl MachInst for Inst {
         44
     }
 
-    fn ref_type_regclass(_: &settings::Flags) -> RegClass {
-        RegClass::I64
-    }
-
     fn gen_value_label_marker(label: ValueLabel, reg: Reg) -> Self {
         Inst::ValueLabelMarker { label, reg }
     }
@@ -100,7 +100,8 @@ mod tests {
     fn test_simple_func() {
         let isa = lookup(triple!("s390x"))
             .expect("expect s390x ISA")
-            .finish(Flags::new(builder()));
+            .finish(Flags::new(builder()))
+            .expect("expect ISA");
 
         let mut context = Context::for_function(create_function(
             CallConv::SystemV,
@@ -142,7 +143,8 @@ mod tests {
     fn test_multi_return_func() {
         let isa = lookup(triple!("s390x"))
             .expect("expect s390x ISA")
-            .finish(Flags::new(builder()));
+            .finish(Flags::new(builder()))
+            .expect("expect ISA");
 
         let mut context = Context::for_function(create_multi_return_function(
             CallConv::SystemV,
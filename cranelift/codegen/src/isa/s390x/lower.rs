@@ -10,7 +10,7 @@ use crate::isa::s390x::S390xBackend;
 use crate::machinst::lower::*;
 use crate::machinst::*;
 use crate::settings::Flags;
-use crate::CodegenResult;
+use crate::{CodegenError, CodegenResult};
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::convert::TryFrom;
@@ -2632,6 +2632,13 @@ fn lower_insn_to_regs<C: LowerCtx<I = Inst>>(
             ctx.emit(Inst::Debugtrap);
         }
 
+        Opcode::ReturnCall | Opcode::ReturnCallIndirect => {
+            return Err(CodegenError::Unsupported(format!(
+                "{}: tail calls are not supported on this target",
+                op
+            )));
+        }
+
         Opcode::Call | Opcode::CallIndirect => {
             let caller_conv = ctx.abi().call_conv();
             let (mut abi, inputs) = match op {
@@ -10,7 +10,7 @@ use crate::machinst::{
     compile, MachBackend, MachCompileResult, MachTextSectionBuilder, TargetIsaAdapter,
     TextSectionBuilder, VCode,
 };
-use crate::result::CodegenResult;
+use crate::result::{CodegenError, CodegenResult};
 use crate::settings as shared_settings;
 
 use alloc::{boxed::Box, vec::Vec};
@@ -171,9 +171,16 @@ pub fn isa_builder(triple: Triple) -> IsaBuilder {
         triple,
         setup: s390x_settings::builder(),
         constructor: |triple, shared_flags, builder| {
+            if shared_flags.enable_probestack()
+                && shared_flags.probestack_strategy() == shared_settings::ProbestackStrategy::Inline
+            {
+                return Err(CodegenError::Unsupported(
+                    "s390x: inline stack probes are not supported".into(),
+                ));
+            }
             let isa_flags = s390x_settings::Flags::new(&shared_flags, builder);
             let backend = S390xBackend::new_with_flags(triple, shared_flags, isa_flags);
-            Box::new(TargetIsaAdapter::new(backend))
+            Ok(Box::new(TargetIsaAdapter::new(backend)))
         },
     }
 }
@@ -275,25 +282,25 @@ mod test {
 
         // FIXME: the branching logic should be optimized more
 
-        // ahi %r2, 4660
-        // chi %r2, 0
+        // ahik %r4, %r2, 4660
+        // chi %r4, 0
         // jglh label1 ; jg label2
         // jg label6
         // jg label3
-        // ahik %r3, %r2, 4660
-        // chi %r3, 0
+        // ahik %r2, %r4, 4660
+        // chi %r2, 0
         // jglh label4 ; jg label5
         // jg label3
         // jg label6
-        // chi %r2, 0
+        // chi %r4, 0
         // jglh label7 ; jg label8
         // jg label3
-        // ahi %r2, -4660
+        // ahik %r2, %r4, -4660
         // br %r14
         let golden = vec![
-            167, 42, 18, 52, 167, 46, 0, 0, 192, 100, 0, 0, 0, 11, 236, 50, 18, 52, 0, 216, 167,
-            62, 0, 0, 192, 100, 255, 255, 255, 251, 167, 46, 0, 0, 192, 100, 255, 255, 255, 246,
-            167, 42, 237, 204, 7, 254,
+            236, 66, 18, 52, 0, 216, 167, 78, 0, 0, 192, 100, 0, 0, 0, 11, 236, 36, 18, 52, 0, 216,
+            167, 46, 0, 0, 192, 100, 255, 255, 255, 251, 167, 78, 0, 0, 192, 100, 255, 255, 255,
+            246, 236, 36, 237, 204, 0, 216, 7, 254,
         ];
 
         assert_eq!(code, &golden[..]);
@@ -173,6 +173,22 @@ pub enum UnwindInst {
         /// The saved register.
         reg: RealReg,
     },
+    /// This function pops `size` bytes of stack arguments when it returns, and
+    /// its return address is at the current SP. For such functions, the SP at
+    /// callsite is taken to be the caller's SP after the return, above the
+    /// popped arguments: a tail call may have replaced the arguments the caller
+    /// pushed with a different amount, and only the top of the argument area
+    /// stays in place. The offsets to the caller's SP given by later
+    /// pseudo-ops do not include `size`. This comes first in the prologue if
+    /// the function receives stack arguments, and again if the return address
+    /// is moved down to grow the argument area.
+    CalleePopsArgs {
+        /// The offset from the current SP to the SP at caller's callsite.
+        offset_upward_to_caller_sp: u32,
+        /// The number of bytes of stack arguments this function pops,
+        /// including any room made for the arguments of its tail calls.
+        size: u32,
+    },
     /// Defines if the aarch64-specific pointer authentication available for ARM v8.3+ devices
     /// is enabled for certain pointers or not.
     Aarch64SetPointerAuth {
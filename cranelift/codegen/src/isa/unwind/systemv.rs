@@ -148,6 +148,11 @@ pub(crate) trait RegisterMapper<Reg> {
     fn lr_offset(&self) -> Option<u32> {
         None
     }
+    /// Gets the return address register, if the call instruction pushes the
+    /// return address to the stack.
+    fn ra(&self) -> Option<Register> {
+        None
+    }
 }
 
 /// Represents unwind information for a single System V ABI function.
@@ -169,11 +174,32 @@ pub(crate) fn create_unwind_info_from_insts<MR: RegisterMapper<regalloc::Reg>>(
 
     let mut cfa_offset = 0;
     let mut clobber_offset_to_cfa = 0;
+    // The size of the stack arguments this function pops, if it does; offsets
+    // to the caller's SP in later pseudo-ops leave it out.
+    let mut popped_args_size = 0;
     for &(instruction_offset, ref inst) in insts {
         match inst {
+            &UnwindInst::CalleePopsArgs {
+                offset_upward_to_caller_sp,
+                size,
+            } => {
+                let ra = mr.ra().expect(
+                    "callee-popped arguments on a target that does not push the return address",
+                );
+                instructions.push((
+                    instruction_offset,
+                    CallFrameInstruction::CfaOffset(offset_upward_to_caller_sp as i32),
+                ));
+                instructions.push((
+                    instruction_offset,
+                    CallFrameInstruction::Offset(ra, -(offset_upward_to_caller_sp as i32)),
+                ));
+                popped_args_size = size;
+            }
             &UnwindInst::PushFrameRegs {
                 offset_upward_to_caller_sp,
             } => {
+                let offset_upward_to_caller_sp = offset_upward_to_caller_sp + popped_args_size;
                 // Define CFA in terms of current SP (SP changed and we haven't
                 // set FP yet).
                 instructions.push((
@@ -206,6 +232,7 @@ pub(crate) fn create_unwind_info_from_insts<MR: RegisterMapper<regalloc::Reg>>(
                 offset_upward_to_caller_sp,
                 offset_downward_to_clobbers,
             } => {
+                let offset_upward_to_caller_sp = offset_upward_to_caller_sp + popped_args_size;
                 // Define CFA in terms of FP. Note that we assume it was already
                 // defined correctly in terms of the current SP, and FP has just
                 // been set to the current SP, so we do not need to change the
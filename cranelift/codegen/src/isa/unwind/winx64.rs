@@ -268,6 +268,7 @@ pub(crate) fn create_unwind_info_from_insts<MR: RegisterMapper<regalloc::Reg>>(
     let mut unwind_codes = vec![];
     let mut frame_register_offset = 0;
     let mut max_unwind_offset = 0;
+    let mut popped_args_size = None;
     for &(instruction_offset, ref inst) in insts {
         let instruction_offset = ensure_unwind_offset(instruction_offset)?;
         match inst {
@@ -309,6 +310,19 @@ pub(crate) fn create_unwind_info_from_insts<MR: RegisterMapper<regalloc::Reg>>(
                     });
                 }
             },
+            &UnwindInst::CalleePopsArgs { size, .. } => {
+                // Windows unwind codes always expect the return address
+                // right above the frame's pushed registers, and callers
+                // keep a frame pointer, so popped arguments need no
+                // description unless the return address was moved.
+                if popped_args_size.map_or(false, |s| s != size) {
+                    return Err(CodegenError::Unsupported(
+                        "growing the incoming argument area is not supported with Windows unwind info"
+                            .into(),
+                    ));
+                }
+                popped_args_size = Some(size);
+            }
             &UnwindInst::Aarch64SetPointerAuth { .. } => {
                 unreachable!("no aarch64 on x64");
             }
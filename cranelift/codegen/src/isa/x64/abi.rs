@@ -246,7 +246,9 @@ impl ABIMachineSpec for X64ABIMachineSpec {
                     // Note that in all cases 16-byte stack alignment happens
                     // separately after all args.
                     let size = (reg_ty.bits() / 8) as u64;
-                    let size = if args_or_rets == ArgsOrRets::Rets && call_conv.extends_wasmtime() {
+                    let size = if args_or_rets == ArgsOrRets::Rets
+                        && (call_conv.extends_wasmtime() || call_conv == CallConv::Tail)
+                    {
                         size
                     } else {
                         std::cmp::max(size, 8)
@@ -357,8 +359,8 @@ impl ABIMachineSpec for X64ABIMachineSpec {
         }
     }
 
-    fn gen_ret() -> Self::I {
-        Inst::ret()
+    fn gen_ret(stack_bytes_to_pop: u32) -> Self::I {
+        Inst::ret(stack_bytes_to_pop)
     }
 
     fn gen_epilogue_placeholder() -> Self::I {
@@ -491,10 +493,41 @@ impl ABIMachineSpec for X64ABIMachineSpec {
             uses: vec![regs::rax()],
             defs: vec![],
             opcode: Opcode::Call,
+            callee_pop_size: 0,
         });
         insts
     }
 
+    fn gen_inline_probestack(frame_size: u32, guard_size: u32) -> SmallInstVec<Self::I> {
+        // Unroll at most this many probes before falling back to a loop.
+        const PROBE_MAX_UNROLL: u32 = 4;
+
+        let probe_count = frame_size / guard_size;
+        let mut insts = SmallVec::new();
+        if probe_count <= PROBE_MAX_UNROLL {
+            // `mov %esp, -guard_size * i(%rsp)` for each page, from the top
+            // down. The stored value is irrelevant; we only need to touch the
+            // page.
+            for i in 1..=probe_count {
+                let offset = -((guard_size * i) as i64);
+                insts.push(Self::gen_store_stack(
+                    StackAMode::SPOffset(offset, I32),
+                    regs::rsp(),
+                    I32,
+                ));
+            }
+        } else {
+            // %r11 is caller-saved and not used for arguments in any
+            // supported calling convention, so it's free in the prologue.
+            insts.push(Inst::StackProbeLoop {
+                tmp: Writable::from_reg(regs::r11()),
+                frame_size,
+                guard_size,
+            });
+        }
+        insts
+    }
+
     fn gen_clobber_save(
         _call_conv: isa::CallConv,
         setup_frame: bool,
@@ -641,13 +674,14 @@ impl ABIMachineSpec for X64ABIMachineSpec {
         tmp: Writable<Reg>,
         _callee_conv: isa::CallConv,
         _caller_conv: isa::CallConv,
+        callee_pop_size: u32,
     ) -> SmallVec<[(InstIsSafepoint, Self::I); 2]> {
         let mut insts = SmallVec::new();
         match dest {
             &CallDest::ExtName(ref name, RelocDistance::Near) => {
                 insts.push((
                     InstIsSafepoint::Yes,
-                    Inst::call_known(name.clone(), uses, defs, opcode),
+                    Inst::call_known(name.clone(), uses, defs, opcode, callee_pop_size),
                 ));
             }
             &CallDest::ExtName(ref name, RelocDistance::Far) => {
@@ -661,19 +695,105 @@ impl ABIMachineSpec for X64ABIMachineSpec {
                 ));
                 insts.push((
                     InstIsSafepoint::Yes,
-                    Inst::call_unknown(RegMem::reg(tmp.to_reg()), uses, defs, opcode),
+                    Inst::call_unknown(
+                        RegMem::reg(tmp.to_reg()),
+                        uses,
+                        defs,
+                        opcode,
+                        callee_pop_size,
+                    ),
                 ));
             }
             &CallDest::Reg(reg) => {
                 insts.push((
                     InstIsSafepoint::Yes,
-                    Inst::call_unknown(RegMem::reg(reg), uses, defs, opcode),
+                    Inst::call_unknown(RegMem::reg(reg), uses, defs, opcode, callee_pop_size),
                 ));
             }
         }
         insts
     }
 
+    fn gen_return_call(
+        dest: &CallDest,
+        uses: Vec<Reg>,
+        stack_bytes_to_pop: u32,
+    ) -> CodegenResult<SmallInstVec<Self::I>> {
+        // The frame is torn down, restoring callee-saved registers, between
+        // these instructions and the jump, so the target address goes in
+        // r11, which is neither callee-saved nor used for arguments.
+        let target = Writable::from_reg(regs::r11());
+        let mut insts = SmallVec::new();
+        match dest {
+            &CallDest::ExtName(ref name, RelocDistance::Near) => {
+                insts.push(Inst::ReturnCallKnown {
+                    dest: name.clone(),
+                    uses,
+                    stack_bytes_to_pop,
+                });
+            }
+            &CallDest::ExtName(ref name, RelocDistance::Far) => {
+                insts.push(Inst::LoadExtName {
+                    dst: target,
+                    name: Box::new(name.clone()),
+                    offset: 0,
+                });
+                insts.push(Inst::ReturnCallUnknown {
+                    dest: target.to_reg(),
+                    uses,
+                    stack_bytes_to_pop,
+                });
+            }
+            &CallDest::Reg(reg) => {
+                insts.push(Inst::gen_move(target, reg, I64));
+                insts.push(Inst::ReturnCallUnknown {
+                    dest: target.to_reg(),
+                    uses,
+                    stack_bytes_to_pop,
+                });
+            }
+        }
+        Ok(insts)
+    }
+
+    fn gen_callee_pops_args_entry(
+        flags: &settings::Flags,
+        incoming_args_size: u32,
+        growth: u32,
+    ) -> SmallInstVec<Self::I> {
+        let mut insts = SmallVec::new();
+        if flags.unwind_info() && incoming_args_size > 0 {
+            insts.push(Inst::Unwind {
+                inst: UnwindInst::CalleePopsArgs {
+                    offset_upward_to_caller_sp: 8 + incoming_args_size,
+                    size: incoming_args_size,
+                },
+            });
+        }
+        if growth > 0 {
+            // The return address is still on top of the stack, and we have
+            // not saved anything yet, so %r10 is free to use.
+            let tmp = Writable::from_reg(regs::r10());
+            insts.push(Inst::mov64_m_r(Amode::imm_reg(0, regs::rsp()), tmp));
+            insts.extend(Self::gen_sp_reg_adjust(-(growth as i32)));
+            insts.push(Inst::mov_r_m(
+                OperandSize::Size64,
+                tmp.to_reg(),
+                Amode::imm_reg(0, regs::rsp()),
+            ));
+            if flags.unwind_info() {
+                let size = incoming_args_size + growth;
+                insts.push(Inst::Unwind {
+                    inst: UnwindInst::CalleePopsArgs {
+                        offset_upward_to_caller_sp: 8 + size,
+                        size,
+                    },
+                });
+            }
+        }
+        insts
+    }
+
     fn gen_memcpy(
         call_conv: isa::CallConv,
         dst: Reg,
@@ -715,6 +835,7 @@ impl ABIMachineSpec for X64ABIMachineSpec {
             /* uses = */ vec![arg0, arg1, arg2],
             /* defs = */ Self::get_regs_clobbered_by_call(call_conv),
             Opcode::Call,
+            /* callee_pop_size = */ 0,
         ));
         insts
     }
@@ -809,7 +930,11 @@ impl ABIMachineSpec for X64ABIMachineSpec {
             CallConv::BaldrdashWindows => {
                 todo!("baldrdash windows");
             }
-            CallConv::Fast | CallConv::Cold | CallConv::SystemV | CallConv::WasmtimeSystemV => regs
+            CallConv::Fast
+            | CallConv::Cold
+            | CallConv::SystemV
+            | CallConv::WasmtimeSystemV
+            | CallConv::Tail => regs
                 .iter()
                 .cloned()
                 .filter(|r| is_callee_save_systemv(r.to_reg()))
@@ -932,7 +1057,8 @@ fn get_intreg_for_retval(
         CallConv::BaldrdashSystemV
         | CallConv::Baldrdash2020
         | CallConv::WasmtimeSystemV
-        | CallConv::WasmtimeFastcall => {
+        | CallConv::WasmtimeFastcall
+        | CallConv::Tail => {
             if intreg_idx == 0 && retval_idx == 0 {
                 Some(regs::rax())
             } else {
@@ -963,7 +1089,8 @@ fn get_fltreg_for_retval(
         CallConv::BaldrdashSystemV
         | CallConv::Baldrdash2020
         | CallConv::WasmtimeFastcall
-        | CallConv::WasmtimeSystemV => {
+        | CallConv::WasmtimeSystemV
+        | CallConv::Tail => {
             if fltreg_idx == 0 && retval_idx == 0 {
                 Some(regs::xmm0())
             } else {
@@ -1,4 +1,4 @@
-use crate::binemit::{Addend, Reloc};
+use crate::binemit::{Addend, Reloc, StackMap};
 use crate::ir::immediates::{Ieee32, Ieee64};
 use crate::ir::LibCall;
 use crate::ir::TrapCode;
@@ -34,6 +34,73 @@ fn emit_signed_cvt(
     inst.emit(sink, info, state);
 }
 
+/// Emits the stack adjustment that follows a call to a function that pops
+/// `callee_pop_size` bytes of its stack arguments, so that the caller finds SP
+/// where it left it. This has to be part of the call instruction: the register
+/// allocator could otherwise put spills or reloads in between.
+fn emit_callee_pop_fixup(
+    sink: &mut MachBuffer<Inst>,
+    info: &EmitInfo,
+    state: &mut EmitState,
+    callee_pop_size: u32,
+) {
+    if callee_pop_size > 0 {
+        let inst = Inst::alu_rmi_r(
+            OperandSize::Size64,
+            AluRmiROpcode::Sub,
+            RegMemImm::imm(callee_pop_size),
+            Writable::from_reg(regs::rsp()),
+        );
+        inst.emit(sink, info, state);
+    }
+}
+
+/// Drops the words holding the stack arguments popped by the callee from the
+/// start of a stack map taken at a call. Unwinders see the SP of a caller of
+/// such a function as the SP after the return, so that is where the map has to
+/// start; the arguments are never marked as holding references anyway.
+fn stack_map_above_popped_args(stack_map: StackMap, callee_pop_size: u32) -> StackMap {
+    if callee_pop_size == 0 {
+        return stack_map;
+    }
+    debug_assert_eq!(callee_pop_size % 8, 0);
+    let popped_words = (callee_pop_size / 8) as usize;
+    let bits: Vec<bool> = (popped_words..stack_map.mapped_words() as usize)
+        .map(|i| stack_map.get_bit(i))
+        .collect();
+    StackMap::from_slice(&bits)
+}
+
+/// Emits the sequence that moves the return address up by
+/// `stack_bytes_to_pop` bytes before a tail call, so that it ends up right
+/// below the callee's stack arguments. This runs after the frame is torn down,
+/// when %r10 is neither callee-saved nor holding an argument.
+fn emit_return_address_move(
+    sink: &mut MachBuffer<Inst>,
+    info: &EmitInfo,
+    state: &mut EmitState,
+    stack_bytes_to_pop: u32,
+) {
+    if stack_bytes_to_pop > 0 {
+        let tmp = Writable::from_reg(regs::r10());
+        let inst = Inst::mov64_m_r(Amode::imm_reg(0, regs::rsp()), tmp);
+        inst.emit(sink, info, state);
+        let inst = Inst::alu_rmi_r(
+            OperandSize::Size64,
+            AluRmiROpcode::Add,
+            RegMemImm::imm(stack_bytes_to_pop),
+            Writable::from_reg(regs::rsp()),
+        );
+        inst.emit(sink, info, state);
+        let inst = Inst::mov_r_m(
+            OperandSize::Size64,
+            tmp.to_reg(),
+            Amode::imm_reg(0, regs::rsp()),
+        );
+        inst.emit(sink, info, state);
+    }
+}
+
 /// Emits a one way conditional jump if CC is set (true).
 fn one_way_jmp(sink: &mut MachBuffer<Inst>, cc: CC, label: MachLabel) {
     let cond_start = sink.cur_offset();
@@ -1138,11 +1205,17 @@ pub(crate) fn emit(
             sink.put1(0x58 + (enc_dst & 7));
         }
 
-        Inst::CallKnown { dest, opcode, .. } => {
+        Inst::CallKnown {
+            dest,
+            opcode,
+            callee_pop_size,
+            ..
+        } => {
             if info.flags.enable_probestack() {
                 sink.add_trap(state.cur_srcloc(), TrapCode::StackOverflow);
             }
             if let Some(s) = state.take_stack_map() {
+                let s = stack_map_above_popped_args(s, *callee_pop_size);
                 sink.add_stack_map(StackMapExtent::UpcomingBytes(5), s);
             }
             sink.put1(0xE8);
@@ -1154,9 +1227,15 @@ pub(crate) fn emit(
                 let loc = state.cur_srcloc();
                 sink.add_call_site(loc, *opcode);
             }
+            emit_callee_pop_fixup(sink, info, state, *callee_pop_size);
         }
 
-        Inst::CallUnknown { dest, opcode, .. } => {
+        Inst::CallUnknown {
+            dest,
+            opcode,
+            callee_pop_size,
+            ..
+        } => {
             if info.flags.enable_probestack() {
                 sink.add_trap(state.cur_srcloc(), TrapCode::StackOverflow);
             }
@@ -1191,15 +1270,58 @@ pub(crate) fn emit(
                 }
             }
             if let Some(s) = state.take_stack_map() {
+                let s = stack_map_above_popped_args(s, *callee_pop_size);
                 sink.add_stack_map(StackMapExtent::StartedAtOffset(start_offset), s);
             }
             if opcode.is_call() {
                 let loc = state.cur_srcloc();
                 sink.add_call_site(loc, *opcode);
             }
+            emit_callee_pop_fixup(sink, info, state, *callee_pop_size);
         }
 
-        Inst::Ret {} => sink.put1(0xC3),
+        Inst::ReturnCallKnown {
+            dest,
+            stack_bytes_to_pop,
+            ..
+        } => {
+            emit_return_address_move(sink, info, state, *stack_bytes_to_pop);
+            sink.put1(0xE9);
+            // The addend adjusts for the difference between the end of the instruction and the
+            // beginning of the immediate field.
+            emit_reloc(sink, state, Reloc::X86CallPCRel4, &dest, -4);
+            sink.put4(0);
+        }
+
+        Inst::ReturnCallUnknown {
+            dest,
+            stack_bytes_to_pop,
+            ..
+        } => {
+            emit_return_address_move(sink, info, state, *stack_bytes_to_pop);
+            let reg_enc = int_reg_enc(*dest);
+            emit_std_enc_enc(
+                sink,
+                LegacyPrefixes::None,
+                0xFF,
+                1,
+                4, /*subopcode*/
+                reg_enc,
+                RexFlags::clear_w(),
+            );
+        }
+
+        Inst::Ret { stack_bytes_to_pop } => {
+            if *stack_bytes_to_pop == 0 {
+                sink.put1(0xC3);
+            } else {
+                let stack_bytes_to_pop: u16 = (*stack_bytes_to_pop)
+                    .try_into()
+                    .expect("too many stack bytes to pop on return");
+                sink.put1(0xC2);
+                sink.put2(stack_bytes_to_pop);
+            }
+        }
 
         Inst::JmpKnown { dst } => {
             let br_start = sink.cur_offset();
@@ -2605,6 +2727,62 @@ pub(crate) fn emit(
             one_way_jmp(sink, CC::NZ, again_label);
         }
 
+        Inst::StackProbeLoop {
+            tmp,
+            frame_size,
+            guard_size,
+        } => {
+            debug_assert!(*guard_size > 0 && *guard_size <= *frame_size);
+            debug_assert!(*frame_size <= i32::MAX as u32);
+
+            // Emit this:
+            //
+            //    movq   $-guard_size, %tmp
+            //   again:
+            //    movl   %tmp, (%rsp,%tmp,1)    // probe; the stored value is irrelevant
+            //    subq   $guard_size, %tmp
+            //    cmpq   $-frame_size, %tmp
+            //    jge    again
+            //
+            // This touches every page of the frame in order, from the highest address down,
+            // without moving %rsp.
+            let guard_size = *guard_size as i64;
+            let frame_size = *frame_size as i64;
+            let again_label = sink.get_label();
+
+            // movq $-guard_size, %tmp
+            let i1 = Inst::imm(OperandSize::Size64, (-guard_size) as u64, *tmp);
+            i1.emit(sink, info, state);
+
+            // again:
+            sink.bind_label(again_label);
+
+            // movl %tmp, (%rsp,%tmp,1)
+            let amode = Amode::imm_reg_reg_shift(0, regs::rsp(), tmp.to_reg(), 0);
+            let i2 = Inst::mov_r_m(OperandSize::Size32, tmp.to_reg(), amode);
+            i2.emit(sink, info, state);
+
+            // subq $guard_size, %tmp
+            let i3 = Inst::alu_rmi_r(
+                OperandSize::Size64,
+                AluRmiROpcode::Sub,
+                RegMemImm::imm(guard_size as u32),
+                *tmp,
+            );
+            i3.emit(sink, info, state);
+
+            // cmpq $-frame_size, %tmp
+            let i4 = Inst::cmp_rmi_r(
+                OperandSize::Size64,
+                RegMemImm::imm((-frame_size) as u32),
+                tmp.to_reg(),
+            );
+            i4.emit(sink, info, state);
+
+            // jge again
+            one_way_jmp(sink, CC::NL, again_label);
+        }
+
         Inst::Fence { kind } => {
             sink.put1(0x0F);
             sink.put1(0xAE);
@@ -3202,15 +3202,30 @@ fn test_x64_emit() {
             Vec::new(),
             Vec::new(),
             Opcode::Call,
+            0,
         ),
         "E800000000",
         "call    User { namespace: 0, index: 0 }",
     ));
+    insns.push((
+        Inst::call_known(
+            ExternalName::User {
+                namespace: 0,
+                index: 0,
+            },
+            Vec::new(),
+            Vec::new(),
+            Opcode::Call,
+            32,
+        ),
+        "E8000000004883EC20",
+        "call    User { namespace: 0, index: 0 }; subq    $32, %rsp",
+    ));
 
     // ========================================================
     // CallUnknown
     fn call_unknown(rm: RegMem) -> Inst {
-        Inst::call_unknown(rm, Vec::new(), Vec::new(), Opcode::CallIndirect)
+        Inst::call_unknown(rm, Vec::new(), Vec::new(), Opcode::CallIndirect, 0)
     }
 
     insns.push((call_unknown(RegMem::reg(rbp)), "FFD5", "call    *%rbp"));
@@ -3225,6 +3240,65 @@ fn test_x64_emit() {
         "41FF949241010000",
         "call    *321(%r10,%rdx,4)",
     ));
+    insns.push((
+        Inst::call_unknown(
+            RegMem::reg(r11),
+            Vec::new(),
+            Vec::new(),
+            Opcode::CallIndirect,
+            256,
+        ),
+        "41FFD34881EC00010000",
+        "call    *%r11; subq    $256, %rsp",
+    ));
+
+    // ========================================================
+    // ReturnCallKnown
+    insns.push((
+        Inst::ReturnCallKnown {
+            dest: ExternalName::User {
+                namespace: 0,
+                index: 0,
+            },
+            uses: Vec::new(),
+            stack_bytes_to_pop: 0,
+        },
+        "E900000000",
+        "return_call User { namespace: 0, index: 0 }",
+    ));
+    insns.push((
+        Inst::ReturnCallKnown {
+            dest: ExternalName::User {
+                namespace: 0,
+                index: 0,
+            },
+            uses: Vec::new(),
+            stack_bytes_to_pop: 16,
+        },
+        "4C8B14244883C4104C891424E900000000",
+        "return_call $16, User { namespace: 0, index: 0 }",
+    ));
+
+    // ========================================================
+    // ReturnCallUnknown
+    insns.push((
+        Inst::ReturnCallUnknown {
+            dest: r11,
+            uses: Vec::new(),
+            stack_bytes_to_pop: 0,
+        },
+        "41FFE3",
+        "return_call *%r11",
+    ));
+    insns.push((
+        Inst::ReturnCallUnknown {
+            dest: r11,
+            uses: Vec::new(),
+            stack_bytes_to_pop: 32,
+        },
+        "4C8B14244883C4204C89142441FFE3",
+        "return_call $32, *%r11",
+    ));
 
     // ========================================================
     // LoadExtName
@@ -3268,7 +3342,8 @@ fn test_x64_emit() {
 
     // ========================================================
     // Ret
-    insns.push((Inst::ret(), "C3", "ret"));
+    insns.push((Inst::ret(0), "C3", "ret"));
+    insns.push((Inst::ret(48), "C23000", "ret     $48"));
 
     // ========================================================
     // JmpKnown skipped for now
@@ -4407,6 +4482,17 @@ fn test_x64_emit() {
         "sfence",
     ));
 
+    // StackProbeLoop
+    insns.push((
+        Inst::StackProbeLoop {
+            tmp: w_r11,
+            frame_size: 100000,
+            guard_size: 4096,
+        },
+        "49C7C300F0FFFF46895C1C004981EB001000004981FB6079FEFF0F8DE7FFFFFF",
+        "stack_probe_loop %r11, frame_size=100000, guard_size=4096",
+    ));
+
     // ========================================================
     // Misc instructions.
 
@@ -11,8 +11,7 @@ use crate::{settings, settings::Flags, CodegenError, CodegenResult};
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 use regalloc::{
-    PrettyPrint, PrettyPrintSized, RealRegUniverse, Reg, RegClass, RegUsageCollector, SpillSlot,
-    VirtualReg, Writable,
+    PrettyPrint, PrettyPrintSized, RealRegUniverse, Reg, RegClass, RegUsageCollector, Writable,
 };
 use smallvec::{smallvec, SmallVec};
 use std::fmt;
@@ -367,24 +366,46 @@ pub enum Inst {
 
     // =====================================
     // Control flow instructions.
-    /// Direct call: call simm32.
+    /// Direct call: call simm32. If the callee pops `callee_pop_size` bytes
+    /// of stack arguments, they are pushed back right after it returns.
     CallKnown {
         dest: ExternalName,
         uses: Vec<Reg>,
         defs: Vec<Writable<Reg>>,
         opcode: Opcode,
+        callee_pop_size: u32,
     },
 
-    /// Indirect call: callq (reg mem).
+    /// Indirect call: callq (reg mem). If the callee pops `callee_pop_size`
+    /// bytes of stack arguments, they are pushed back right after it returns.
     CallUnknown {
         dest: RegMem,
         uses: Vec<Reg>,
         defs: Vec<Writable<Reg>>,
         opcode: Opcode,
+        callee_pop_size: u32,
     },
 
-    /// Return.
-    Ret,
+    /// Direct tail call: jmp simm32. Emitted after the frame has been torn
+    /// down, so the callee returns directly to our caller. The return address
+    /// is first moved up by `stack_bytes_to_pop` bytes, using %r10.
+    ReturnCallKnown {
+        dest: ExternalName,
+        uses: Vec<Reg>,
+        stack_bytes_to_pop: u32,
+    },
+
+    /// Indirect tail call: jmp *reg. Emitted after the frame has been torn
+    /// down, so the callee returns directly to our caller. The return address
+    /// is first moved up by `stack_bytes_to_pop` bytes, using %r10.
+    ReturnCallUnknown {
+        dest: Reg,
+        uses: Vec<Reg>,
+        stack_bytes_to_pop: u32,
+    },
+
+    /// Return, popping `stack_bytes_to_pop` bytes of stack arguments.
+    Ret { stack_bytes_to_pop: u32 },
 
     /// A placeholder instruction, generating no code, meaning that a function epilogue must be
     /// inserted there.
@@ -500,6 +521,19 @@ pub enum Inst {
     /// A memory fence (mfence, lfence or sfence).
     Fence { kind: FenceKind },
 
+    /// A synthetic instruction that probes the stack below %rsp, one store every `guard_size`
+    /// bytes from `%rsp - guard_size` down to `%rsp - frame_size`, without moving %rsp. This is
+    /// used in the prologue when inline stack probes are enabled and the frame is too large to
+    /// unroll the probes.
+    ///
+    /// `tmp` is a scratch register holding the current (negative) probe offset; its value
+    /// afterwards has no meaning. %rflags is written.
+    StackProbeLoop {
+        tmp: Writable<Reg>,
+        frame_size: u32,
+        guard_size: u32,
+    },
+
     // =====================================
     // Meta-instructions generating no code.
     /// Marker, no-op in generated code: SP "virtual offset" is adjusted. This
@@ -552,8 +586,11 @@ impl Inst {
             // don't have to be checked.
             Inst::AluRmiR { .. }
             | Inst::AtomicRmwSeq { .. }
+            | Inst::StackProbeLoop { .. }
             | Inst::CallKnown { .. }
             | Inst::CallUnknown { .. }
+            | Inst::ReturnCallKnown { .. }
+            | Inst::ReturnCallUnknown { .. }
             | Inst::CheckedDivOrRemSeq { .. }
             | Inst::Cmove { .. }
             | Inst::CmpRmiR { .. }
@@ -584,7 +621,7 @@ impl Inst {
             | Inst::Nop { .. }
             | Inst::Pop64 { .. }
             | Inst::Push64 { .. }
-            | Inst::Ret
+            | Inst::Ret { .. }
             | Inst::Setcc { .. }
             | Inst::ShiftR { .. }
             | Inst::SignExtendData { .. }
@@ -1129,12 +1166,14 @@ impl Inst {
         uses: Vec<Reg>,
         defs: Vec<Writable<Reg>>,
         opcode: Opcode,
+        callee_pop_size: u32,
     ) -> Inst {
         Inst::CallKnown {
             dest,
             uses,
             defs,
             opcode,
+            callee_pop_size,
         }
     }
 
@@ -1143,6 +1182,7 @@ impl Inst {
         uses: Vec<Reg>,
         defs: Vec<Writable<Reg>>,
         opcode: Opcode,
+        callee_pop_size: u32,
     ) -> Inst {
         dest.assert_regclass_is(RegClass::I64);
         Inst::CallUnknown {
@@ -1150,11 +1190,12 @@ impl Inst {
             uses,
             defs,
             opcode,
+            callee_pop_size,
         }
     }
 
-    pub(crate) fn ret() -> Inst {
-        Inst::Ret
+    pub(crate) fn ret(stack_bytes_to_pop: u32) -> Inst {
+        Inst::Ret { stack_bytes_to_pop }
     }
 
     pub(crate) fn epilogue_placeholder() -> Inst {
@@ -1615,6 +1656,22 @@ impl PrettyPrint for Inst {
             }
         }
 
+        fn show_callee_pop(size: u32) -> String {
+            if size == 0 {
+                String::new()
+            } else {
+                format!("; {} ${}, %rsp", ljustify("subq".to_string()), size)
+            }
+        }
+
+        fn show_stack_bytes_to_pop(size: u32) -> String {
+            if size == 0 {
+                String::new()
+            } else {
+                format!("${}, ", size)
+            }
+        }
+
         match self {
             Inst::Nop { len } => format!("{} len={}", ljustify("nop".to_string()), len),
 
@@ -2046,15 +2103,57 @@ impl PrettyPrint for Inst {
                 format!("{} {}", ljustify("popq".to_string()), dst.show_rru(mb_rru))
             }
 
-            Inst::CallKnown { dest, .. } => format!("{} {:?}", ljustify("call".to_string()), dest),
+            Inst::CallKnown {
+                dest,
+                callee_pop_size,
+                ..
+            } => format!(
+                "{} {:?}{}",
+                ljustify("call".to_string()),
+                dest,
+                show_callee_pop(*callee_pop_size)
+            ),
 
-            Inst::CallUnknown { dest, .. } => format!(
-                "{} *{}",
+            Inst::CallUnknown {
+                dest,
+                callee_pop_size,
+                ..
+            } => format!(
+                "{} *{}{}",
                 ljustify("call".to_string()),
+                dest.show_rru(mb_rru),
+                show_callee_pop(*callee_pop_size)
+            ),
+
+            Inst::ReturnCallKnown {
+                dest,
+                stack_bytes_to_pop,
+                ..
+            } => format!(
+                "{} {}{:?}",
+                ljustify("return_call".to_string()),
+                show_stack_bytes_to_pop(*stack_bytes_to_pop),
+                dest
+            ),
+
+            Inst::ReturnCallUnknown {
+                dest,
+                stack_bytes_to_pop,
+                ..
+            } => format!(
+                "{} {}*{}",
+                ljustify("return_call".to_string()),
+                show_stack_bytes_to_pop(*stack_bytes_to_pop),
                 dest.show_rru(mb_rru)
             ),
 
-            Inst::Ret => "ret".to_string(),
+            Inst::Ret { stack_bytes_to_pop } => {
+                if *stack_bytes_to_pop == 0 {
+                    "ret".to_string()
+                } else {
+                    format!("{} ${}", ljustify("ret".to_string()), stack_bytes_to_pop)
+                }
+            }
 
             Inst::EpiloguePlaceholder => "epilogue placeholder".to_string(),
 
@@ -2124,6 +2223,17 @@ impl PrettyPrint for Inst {
                     ty.bits(), op)
             }
 
+            Inst::StackProbeLoop {
+                tmp,
+                frame_size,
+                guard_size,
+            } => format!(
+                "stack_probe_loop {}, frame_size={}, guard_size={}",
+                show_ireg_sized(tmp.to_reg(), mb_rru, 8),
+                frame_size,
+                guard_size
+            ),
+
             Inst::Fence { kind } => match kind {
                 FenceKind::MFence => "mfence".to_string(),
                 FenceKind::LFence => "lfence".to_string(),
@@ -2457,6 +2567,15 @@ fn x64_get_regs(inst: &Inst, collector: &mut RegUsageCollector) {
             dest.get_regs_as_uses(collector);
         }
 
+        Inst::ReturnCallKnown { ref uses, .. } => {
+            collector.add_uses(uses);
+        }
+
+        Inst::ReturnCallUnknown { ref uses, dest, .. } => {
+            collector.add_uses(uses);
+            collector.add_use(*dest);
+        }
+
         Inst::JmpTableSeq {
             ref idx,
             ref tmp1,
@@ -2491,6 +2610,10 @@ fn x64_get_regs(inst: &Inst, collector: &mut RegUsageCollector) {
             collector.add_mod(Writable::from_reg(regs::rax()));
         }
 
+        Inst::StackProbeLoop { tmp, .. } => {
+            collector.add_def(*tmp);
+        }
+
         Inst::AtomicRmwSeq { .. } => {
             collector.add_use(regs::r9());
             collector.add_use(regs::r10());
@@ -2498,7 +2621,7 @@ fn x64_get_regs(inst: &Inst, collector: &mut RegUsageCollector) {
             collector.add_def(Writable::from_reg(regs::rax()));
         }
 
-        Inst::Ret
+        Inst::Ret { .. }
         | Inst::EpiloguePlaceholder
         | Inst::JmpKnown { .. }
         | Inst::JmpIf { .. }
@@ -2913,6 +3036,23 @@ pub(crate) fn x64_map_regs<RM: RegMapper>(inst: &mut Inst, mapper: &RM) {
             dest.map_uses(mapper);
         }
 
+        Inst::ReturnCallKnown { ref mut uses, .. } => {
+            for r in uses.iter_mut() {
+                mapper.map_use(r);
+            }
+        }
+
+        Inst::ReturnCallUnknown {
+            ref mut uses,
+            ref mut dest,
+            ..
+        } => {
+            for r in uses.iter_mut() {
+                mapper.map_use(r);
+            }
+            mapper.map_use(dest);
+        }
+
         Inst::JmpTableSeq {
             ref mut idx,
             ref mut tmp1,
@@ -2924,6 +3064,10 @@ pub(crate) fn x64_map_regs<RM: RegMapper>(inst: &mut Inst, mapper: &RM) {
             mapper.map_def(tmp2);
         }
 
+        Inst::StackProbeLoop { ref mut tmp, .. } => {
+            mapper.map_def(tmp);
+        }
+
         Inst::JmpUnknown { ref mut target } => target.map_uses(mapper),
 
         Inst::LoadExtName { ref mut dst, .. } => mapper.map_def(dst),
@@ -2939,7 +3083,7 @@ pub(crate) fn x64_map_regs<RM: RegMapper>(inst: &mut Inst, mapper: &RM) {
 
         Inst::ValueLabelMarker { ref mut reg, .. } => mapper.map_use(reg),
 
-        Inst::Ret
+        Inst::Ret { .. }
         | Inst::EpiloguePlaceholder
         | Inst::JmpKnown { .. }
         | Inst::JmpCond { .. }
@@ -3019,7 +3163,10 @@ impl MachInst for Inst {
     fn is_term<'a>(&'a self) -> MachTerminator<'a> {
         match self {
             // Interesting cases.
-            &Self::Ret | &Self::EpiloguePlaceholder => MachTerminator::Ret,
+            &Self::Ret { .. } | &Self::EpiloguePlaceholder => MachTerminator::Ret,
+            &Self::ReturnCallKnown { .. } | &Self::ReturnCallUnknown { .. } => {
+                MachTerminator::RetCall
+            }
             &Self::JmpKnown { dst } => MachTerminator::Uncond(dst),
             &Self::JmpCond {
                 taken, not_taken, ..
@@ -3080,10 +3227,6 @@ impl MachInst for Inst {
         Inst::nop(std::cmp::min(preferred_size, 15) as u8)
     }
 
-    fn maybe_direct_reload(&self, _reg: VirtualReg, _slot: SpillSlot) -> Option<Inst> {
-        None
-    }
-
     fn rc_for_type(ty: Type) -> CodegenResult<(&'static [RegClass], &'static [Type])> {
         match ty {
             types::I8 => Ok((&[RegClass::I64], &[types::I8])),
@@ -3234,10 +3377,6 @@ impl MachInst for Inst {
         15
     }
 
-    fn ref_type_regclass(_: &settings::Flags) -> RegClass {
-        RegClass::I64
-    }
-
     fn gen_value_label_marker(label: ValueLabel, reg: Reg) -> Self {
         Inst::ValueLabelMarker { label, reg }
     }
@@ -92,14 +92,17 @@ impl crate::isa::unwind::systemv::RegisterMapper<Reg> for RegisterMapper {
     fn fp(&self) -> Option<u16> {
         Some(X86_64::RBP.0)
     }
+    fn ra(&self) -> Option<u16> {
+        Some(X86_64::RA.0)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::cursor::{Cursor, FuncCursor};
     use crate::ir::{
-        types, AbiParam, ExternalName, Function, InstBuilder, Signature, StackSlotData,
-        StackSlotKind,
+        types, AbiParam, ExtFuncData, ExternalName, Function, InstBuilder, Signature,
+        StackSlotData, StackSlotKind,
     };
     use crate::isa::{lookup, CallConv};
     use crate::settings::{builder, Flags};
@@ -112,7 +115,8 @@ mod tests {
     fn test_simple_func() {
         let isa = lookup(triple!("x86_64"))
             .expect("expect x86 ISA")
-            .finish(Flags::new(builder()));
+            .finish(Flags::new(builder()))
+            .expect("expect ISA");
 
         let mut context = Context::for_function(create_function(
             CallConv::SystemV,
@@ -154,7 +158,8 @@ mod tests {
     fn test_multi_return_func() {
         let isa = lookup(triple!("x86_64"))
             .expect("expect x86 ISA")
-            .finish(Flags::new(builder()));
+            .finish(Flags::new(builder()))
+            .expect("expect ISA");
 
         let mut context = Context::for_function(create_multi_return_function(CallConv::SystemV));
 
@@ -196,4 +201,56 @@ mod tests {
 
         func
     }
+
+    #[test]
+    fn test_tail_call_growth_func() {
+        let isa = lookup(triple!("x86_64"))
+            .expect("expect x86 ISA")
+            .finish(Flags::new(builder()))
+            .expect("expect ISA");
+
+        let mut context = Context::for_function(create_tail_call_growth_function());
+
+        context.compile(&*isa).expect("expected compilation");
+
+        let fde = match context
+            .create_unwind_info(isa.as_ref())
+            .expect("can create unwind info")
+        {
+            Some(crate::isa::unwind::UnwindInfo::SystemV(info)) => {
+                info.to_fde(Address::Constant(1234))
+            }
+            _ => panic!("expected unwind information"),
+        };
+
+        assert_eq!(format!("{:?}", fde), "FrameDescriptionEntry { address: Constant(1234), length: 59, lsda: None, instructions: [(12, CfaOffset(24)), (12, Offset(Register(16), -24)), (13, CfaOffset(32)), (13, Offset(Register(6), -32)), (16, CfaRegister(Register(6)))] }");
+    }
+
+    /// Creates a `tail` function which receives no stack arguments and tail
+    /// calls a function which receives some.
+    fn create_tail_call_growth_function() -> Function {
+        let mut sig = Signature::new(CallConv::Tail);
+        sig.params.push(AbiParam::new(types::I64));
+        let mut func = Function::with_name_signature(ExternalName::user(0, 0), sig);
+
+        let mut callee_sig = Signature::new(CallConv::Tail);
+        callee_sig
+            .params
+            .extend([AbiParam::new(types::I64); 8].iter());
+        let signature = func.import_signature(callee_sig);
+        let callee = func.import_function(ExtFuncData {
+            name: ExternalName::user(0, 1),
+            signature,
+            colocated: false,
+        });
+
+        let block0 = func.dfg.make_block();
+        let v0 = func.dfg.append_block_param(block0, types::I64);
+
+        let mut pos = FuncCursor::new(&mut func);
+        pos.insert_block(block0);
+        pos.ins().return_call(callee, &[v0; 8]);
+
+        func
+    }
 }
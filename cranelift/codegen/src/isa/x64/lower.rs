@@ -3188,6 +3188,39 @@ fn lower_insn_to_regs<C: LowerCtx<I = Inst>>(
             abi.emit_stack_post_adjust(ctx);
         }
 
+        Opcode::ReturnCall | Opcode::ReturnCallIndirect => {
+            let caller_conv = ctx.abi().call_conv();
+            let (mut abi, inputs) = match op {
+                Opcode::ReturnCall => {
+                    let (extname, dist) = ctx.call_target(insn).unwrap();
+                    let sig = ctx.call_sig(insn).unwrap();
+                    assert_eq!(inputs.len(), sig.params.len());
+                    (
+                        X64ABICaller::from_func(sig, &extname, dist, caller_conv, flags)?,
+                        &inputs[..],
+                    )
+                }
+
+                Opcode::ReturnCallIndirect => {
+                    let ptr = put_input_in_reg(ctx, inputs[0]);
+                    let sig = ctx.call_sig(insn).unwrap();
+                    assert_eq!(inputs.len() - 1, sig.params.len());
+                    (
+                        X64ABICaller::from_ptr(sig, ptr, op, caller_conv, flags)?,
+                        &inputs[1..],
+                    )
+                }
+
+                _ => unreachable!(),
+            };
+
+            let args = inputs
+                .iter()
+                .map(|&input| put_input_in_regs(ctx, input))
+                .collect::<Vec<_>>();
+            abi.emit_return_call(ctx, &args)?;
+        }
+
         Opcode::Debugtrap => {
             ctx.emit(Inst::Hlt);
         }
@@ -2072,176 +2072,1629 @@ pub fn constructor_not<C: Context>(ctx: &mut C, arg0: Type, arg1: Reg) -> Option
 // Generated as internal constructor for term lower.
 pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueRegs> {
     let pattern0_0 = arg0;
-    let pattern1_0 = C::inst_data(ctx, pattern0_0);
-    match &pattern1_0 {
-        &InstructionData::UnaryIeee32 {
-            opcode: ref pattern2_0,
-            imm: pattern2_1,
-        } => {
-            if let &Opcode::F32const = &pattern2_0 {
-                let pattern4_0 = C::u64_from_ieee32(ctx, pattern2_1);
-                // Rule at src/isa/x64/lower.isle line 46.
-                let expr0_0: Type = F32;
-                let expr1_0 = constructor_imm(ctx, expr0_0, pattern4_0)?;
-                let expr2_0 = C::value_reg(ctx, expr1_0);
-                return Some(expr2_0);
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if pattern2_0 == I128 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Imul = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    // Rule at src/isa/x64/lower.isle line 719.
+                    let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                    let expr1_0: usize = 0;
+                    let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                    let expr3_0: usize = 1;
+                    let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                    let expr5_0 = C::put_in_regs(ctx, pattern7_1);
+                    let expr6_0: usize = 0;
+                    let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
+                    let expr8_0: usize = 1;
+                    let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
+                    let expr10_0: Type = I64;
+                    let expr11_0 = RegMemImm::Reg { reg: expr9_0 };
+                    let expr12_0 = constructor_mul(ctx, expr10_0, expr2_0, &expr11_0)?;
+                    let expr13_0: Type = I64;
+                    let expr14_0 = RegMemImm::Reg { reg: expr7_0 };
+                    let expr15_0 = constructor_mul(ctx, expr13_0, expr4_0, &expr14_0)?;
+                    let expr16_0: Type = I64;
+                    let expr17_0 = RegMemImm::Reg { reg: expr15_0 };
+                    let expr18_0 = constructor_add(ctx, expr16_0, expr12_0, &expr17_0)?;
+                    let expr19_0: Type = I64;
+                    let expr20_0 = RegMem::Reg { reg: expr7_0 };
+                    let expr21_0 = constructor_mulhi_u(ctx, expr19_0, expr2_0, &expr20_0)?;
+                    let expr22_0: usize = 0;
+                    let expr23_0 = C::value_regs_get(ctx, expr21_0, expr22_0);
+                    let expr24_0: usize = 1;
+                    let expr25_0 = C::value_regs_get(ctx, expr21_0, expr24_0);
+                    let expr26_0: Type = I64;
+                    let expr27_0 = RegMemImm::Reg { reg: expr25_0 };
+                    let expr28_0 = constructor_add(ctx, expr26_0, expr18_0, &expr27_0)?;
+                    let expr29_0 = C::value_regs(ctx, expr23_0, expr28_0);
+                    return Some(expr29_0);
+                }
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if let Some(()) = C::avx512vl_enabled(ctx, pattern2_0) {
+            if let Some(()) = C::avx512dq_enabled(ctx, pattern2_0) {
+                if let Some((pattern5_0, pattern5_1)) = C::multi_lane(ctx, pattern2_0) {
+                    if pattern5_0 == 64 {
+                        if pattern5_1 == 2 {
+                            let pattern8_0 = C::inst_data(ctx, pattern0_0);
+                            if let &InstructionData::Binary {
+                                opcode: ref pattern9_0,
+                                args: ref pattern9_1,
+                            } = &pattern8_0
+                            {
+                                if let &Opcode::Imul = &pattern9_0 {
+                                    let (pattern11_0, pattern11_1) =
+                                        C::unpack_value_array_2(ctx, &pattern9_1);
+                                    // Rule at src/isa/x64/lower.isle line 754.
+                                    let expr0_0 = C::put_in_reg_mem(ctx, pattern11_0);
+                                    let expr1_0 = C::put_in_reg(ctx, pattern11_1);
+                                    let expr2_0 = constructor_vpmullq(ctx, &expr0_0, expr1_0)?;
+                                    let expr3_0 = C::value_reg(ctx, expr2_0);
+                                    return Some(expr3_0);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if pattern2_0 == B128 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Band => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/x64/lower.isle line 384.
+                        let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                        let expr1_0: usize = 0;
+                        let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                        let expr3_0: usize = 1;
+                        let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                        let expr5_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr6_0: Type = I64;
+                        let expr7_0 = RegMemImm::Reg { reg: expr5_0 };
+                        let expr8_0 = constructor_m_and(ctx, expr6_0, expr2_0, &expr7_0)?;
+                        let expr9_0 = C::value_regs(ctx, expr8_0, expr4_0);
+                        return Some(expr9_0);
+                    }
+                    &Opcode::Bor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/x64/lower.isle line 458.
+                        let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                        let expr1_0: usize = 0;
+                        let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                        let expr3_0: usize = 1;
+                        let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                        let expr5_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr6_0: Type = I64;
+                        let expr7_0 = RegMemImm::Reg { reg: expr5_0 };
+                        let expr8_0 = constructor_or(ctx, expr6_0, expr2_0, &expr7_0)?;
+                        let expr9_0 = C::value_regs(ctx, expr8_0, expr4_0);
+                        return Some(expr9_0);
+                    }
+                    &Opcode::Bxor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/x64/lower.isle line 524.
+                        let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                        let expr1_0: usize = 0;
+                        let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                        let expr3_0: usize = 1;
+                        let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                        let expr5_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr6_0: Type = I64;
+                        let expr7_0 = RegMemImm::Reg { reg: expr5_0 };
+                        let expr8_0 = constructor_xor(ctx, expr6_0, expr2_0, &expr7_0)?;
+                        let expr9_0 = C::value_regs(ctx, expr8_0, expr4_0);
+                        return Some(expr9_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if pattern2_0 == I128 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Iadd => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/x64/lower.isle line 117.
+                        let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                        let expr1_0: usize = 0;
+                        let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                        let expr3_0: usize = 1;
+                        let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                        let expr5_0 = C::put_in_regs(ctx, pattern7_1);
+                        let expr6_0: usize = 0;
+                        let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
+                        let expr8_0: usize = 1;
+                        let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
+                        let expr10_0: Type = I64;
+                        let expr11_0 = RegMemImm::Reg { reg: expr7_0 };
+                        let expr12_0 =
+                            constructor_add_with_flags(ctx, expr10_0, expr2_0, &expr11_0)?;
+                        let expr13_0: Type = I64;
+                        let expr14_0 = RegMemImm::Reg { reg: expr9_0 };
+                        let expr15_0 = constructor_adc(ctx, expr13_0, expr4_0, &expr14_0)?;
+                        let expr16_0 = constructor_with_flags(ctx, &expr12_0, &expr15_0)?;
+                        return Some(expr16_0);
+                    }
+                    &Opcode::Band => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/x64/lower.isle line 374.
+                        let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                        let expr1_0: usize = 0;
+                        let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                        let expr3_0: usize = 1;
+                        let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                        let expr5_0 = C::put_in_regs(ctx, pattern7_1);
+                        let expr6_0: usize = 0;
+                        let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
+                        let expr8_0: usize = 1;
+                        let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
+                        let expr10_0: Type = I64;
+                        let expr11_0 = RegMemImm::Reg { reg: expr7_0 };
+                        let expr12_0 = constructor_m_and(ctx, expr10_0, expr2_0, &expr11_0)?;
+                        let expr13_0: Type = I64;
+                        let expr14_0 = RegMemImm::Reg { reg: expr9_0 };
+                        let expr15_0 = constructor_m_and(ctx, expr13_0, expr4_0, &expr14_0)?;
+                        let expr16_0 = C::value_regs(ctx, expr12_0, expr15_0);
+                        return Some(expr16_0);
+                    }
+                    &Opcode::Bor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/x64/lower.isle line 455.
+                        let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                        let expr1_0 = C::put_in_regs(ctx, pattern7_1);
+                        let expr2_0 = constructor_or_i128(ctx, expr0_0, expr1_0)?;
+                        return Some(expr2_0);
+                    }
+                    &Opcode::Bxor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/x64/lower.isle line 514.
+                        let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                        let expr1_0: usize = 0;
+                        let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                        let expr3_0: usize = 1;
+                        let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                        let expr5_0 = C::put_in_regs(ctx, pattern7_1);
+                        let expr6_0: usize = 0;
+                        let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
+                        let expr8_0: usize = 1;
+                        let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
+                        let expr10_0: Type = I64;
+                        let expr11_0 = RegMemImm::Reg { reg: expr7_0 };
+                        let expr12_0 = constructor_xor(ctx, expr10_0, expr2_0, &expr11_0)?;
+                        let expr13_0: Type = I64;
+                        let expr14_0 = RegMemImm::Reg { reg: expr9_0 };
+                        let expr15_0 = constructor_xor(ctx, expr13_0, expr4_0, &expr14_0)?;
+                        let expr16_0 = C::value_regs(ctx, expr12_0, expr15_0);
+                        return Some(expr16_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some((pattern3_0, pattern3_1)) = C::multi_lane(ctx, pattern2_0) {
+            if pattern3_0 == 16 {
+                if pattern3_1 == 8 {
+                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
+                    if let &InstructionData::Binary {
+                        opcode: ref pattern7_0,
+                        args: ref pattern7_1,
+                    } = &pattern6_0
+                    {
+                        if let &Opcode::Imul = &pattern7_0 {
+                            let (pattern9_0, pattern9_1) =
+                                C::unpack_value_array_2(ctx, &pattern7_1);
+                            if let Some(pattern10_0) = C::def_inst(ctx, pattern9_0) {
+                                let pattern11_0 = C::inst_data(ctx, pattern10_0);
+                                if let &InstructionData::Unary {
+                                    opcode: ref pattern12_0,
+                                    arg: pattern12_1,
+                                } = &pattern11_0
+                                {
+                                    match &pattern12_0 {
+                                        &Opcode::SwidenLow => {
+                                            let pattern14_0 = C::value_type(ctx, pattern12_1);
+                                            if let Some((pattern15_0, pattern15_1)) =
+                                                C::multi_lane(ctx, pattern14_0)
+                                            {
+                                                if pattern15_0 == 8 {
+                                                    if pattern15_1 == 16 {
+                                                        if let Some(pattern18_0) =
+                                                            C::def_inst(ctx, pattern9_1)
+                                                        {
+                                                            let pattern19_0 =
+                                                                C::inst_data(ctx, pattern18_0);
+                                                            if let &InstructionData::Unary {
+                                                                opcode: ref pattern20_0,
+                                                                arg: pattern20_1,
+                                                            } = &pattern19_0
+                                                            {
+                                                                if let &Opcode::SwidenLow =
+                                                                    &pattern20_0
+                                                                {
+                                                                    let pattern22_0 = C::value_type(
+                                                                        ctx,
+                                                                        pattern20_1,
+                                                                    );
+                                                                    if let Some((
+                                                                        pattern23_0,
+                                                                        pattern23_1,
+                                                                    )) = C::multi_lane(
+                                                                        ctx,
+                                                                        pattern22_0,
+                                                                    ) {
+                                                                        if pattern23_0 == 8 {
+                                                                            if pattern23_1 == 16 {
+                                                                                // Rule at src/isa/x64/lower.isle line 842.
+                                                                                let expr0_0 = C::put_in_reg_mem(ctx, pattern12_1);
+                                                                                let expr1_0 = constructor_pmovsxbw(ctx, &expr0_0)?;
+                                                                                let expr2_0 = C::put_in_reg_mem(ctx, pattern20_1);
+                                                                                let expr3_0 = constructor_pmovsxbw(ctx, &expr2_0)?;
+                                                                                let expr4_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr3_0,
+                                                                                    };
+                                                                                let expr5_0 = constructor_pmullw(ctx, expr1_0, &expr4_0)?;
+                                                                                let expr6_0 =
+                                                                                    C::value_reg(
+                                                                                        ctx,
+                                                                                        expr5_0,
+                                                                                    );
+                                                                                return Some(
+                                                                                    expr6_0,
+                                                                                );
+                                                                            }
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        &Opcode::SwidenHigh => {
+                                            let pattern14_0 = C::value_type(ctx, pattern12_1);
+                                            if let Some((pattern15_0, pattern15_1)) =
+                                                C::multi_lane(ctx, pattern14_0)
+                                            {
+                                                if pattern15_0 == 8 {
+                                                    if pattern15_1 == 16 {
+                                                        if let Some(pattern18_0) =
+                                                            C::def_inst(ctx, pattern9_1)
+                                                        {
+                                                            let pattern19_0 =
+                                                                C::inst_data(ctx, pattern18_0);
+                                                            if let &InstructionData::Unary {
+                                                                opcode: ref pattern20_0,
+                                                                arg: pattern20_1,
+                                                            } = &pattern19_0
+                                                            {
+                                                                if let &Opcode::SwidenHigh =
+                                                                    &pattern20_0
+                                                                {
+                                                                    let pattern22_0 = C::value_type(
+                                                                        ctx,
+                                                                        pattern20_1,
+                                                                    );
+                                                                    if let Some((
+                                                                        pattern23_0,
+                                                                        pattern23_1,
+                                                                    )) = C::multi_lane(
+                                                                        ctx,
+                                                                        pattern22_0,
+                                                                    ) {
+                                                                        if pattern23_0 == 8 {
+                                                                            if pattern23_1 == 16 {
+                                                                                // Rule at src/isa/x64/lower.isle line 802.
+                                                                                let expr0_0 =
+                                                                                    C::put_in_reg(
+                                                                                        ctx,
+                                                                                        pattern12_1,
+                                                                                    );
+                                                                                let expr1_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr0_0,
+                                                                                    };
+                                                                                let expr2_0: u8 = 8;
+                                                                                let expr3_0 = OperandSize::Size32;
+                                                                                let expr4_0 = constructor_palignr(ctx, expr0_0, &expr1_0, expr2_0, &expr3_0)?;
+                                                                                let expr5_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr4_0,
+                                                                                    };
+                                                                                let expr6_0 = constructor_pmovsxbw(ctx, &expr5_0)?;
+                                                                                let expr7_0 =
+                                                                                    C::put_in_reg(
+                                                                                        ctx,
+                                                                                        pattern20_1,
+                                                                                    );
+                                                                                let expr8_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr7_0,
+                                                                                    };
+                                                                                let expr9_0: u8 = 8;
+                                                                                let expr10_0 = OperandSize::Size32;
+                                                                                let expr11_0 = constructor_palignr(ctx, expr7_0, &expr8_0, expr9_0, &expr10_0)?;
+                                                                                let expr12_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr11_0,
+                                                                                    };
+                                                                                let expr13_0 = constructor_pmovsxbw(ctx, &expr12_0)?;
+                                                                                let expr14_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr13_0,
+                                                                                    };
+                                                                                let expr15_0 = constructor_pmullw(ctx, expr6_0, &expr14_0)?;
+                                                                                let expr16_0 =
+                                                                                    C::value_reg(
+                                                                                        ctx,
+                                                                                        expr15_0,
+                                                                                    );
+                                                                                return Some(
+                                                                                    expr16_0,
+                                                                                );
+                                                                            }
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        &Opcode::UwidenLow => {
+                                            let pattern14_0 = C::value_type(ctx, pattern12_1);
+                                            if let Some((pattern15_0, pattern15_1)) =
+                                                C::multi_lane(ctx, pattern14_0)
+                                            {
+                                                if pattern15_0 == 8 {
+                                                    if pattern15_1 == 16 {
+                                                        if let Some(pattern18_0) =
+                                                            C::def_inst(ctx, pattern9_1)
+                                                        {
+                                                            let pattern19_0 =
+                                                                C::inst_data(ctx, pattern18_0);
+                                                            if let &InstructionData::Unary {
+                                                                opcode: ref pattern20_0,
+                                                                arg: pattern20_1,
+                                                            } = &pattern19_0
+                                                            {
+                                                                if let &Opcode::UwidenLow =
+                                                                    &pattern20_0
+                                                                {
+                                                                    let pattern22_0 = C::value_type(
+                                                                        ctx,
+                                                                        pattern20_1,
+                                                                    );
+                                                                    if let Some((
+                                                                        pattern23_0,
+                                                                        pattern23_1,
+                                                                    )) = C::multi_lane(
+                                                                        ctx,
+                                                                        pattern22_0,
+                                                                    ) {
+                                                                        if pattern23_0 == 8 {
+                                                                            if pattern23_1 == 16 {
+                                                                                // Rule at src/isa/x64/lower.isle line 918.
+                                                                                let expr0_0 = C::put_in_reg_mem(ctx, pattern12_1);
+                                                                                let expr1_0 = constructor_pmovzxbw(ctx, &expr0_0)?;
+                                                                                let expr2_0 = C::put_in_reg_mem(ctx, pattern20_1);
+                                                                                let expr3_0 = constructor_pmovzxbw(ctx, &expr2_0)?;
+                                                                                let expr4_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr3_0,
+                                                                                    };
+                                                                                let expr5_0 = constructor_pmullw(ctx, expr1_0, &expr4_0)?;
+                                                                                let expr6_0 =
+                                                                                    C::value_reg(
+                                                                                        ctx,
+                                                                                        expr5_0,
+                                                                                    );
+                                                                                return Some(
+                                                                                    expr6_0,
+                                                                                );
+                                                                            }
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        &Opcode::UwidenHigh => {
+                                            let pattern14_0 = C::value_type(ctx, pattern12_1);
+                                            if let Some((pattern15_0, pattern15_1)) =
+                                                C::multi_lane(ctx, pattern14_0)
+                                            {
+                                                if pattern15_0 == 8 {
+                                                    if pattern15_1 == 16 {
+                                                        if let Some(pattern18_0) =
+                                                            C::def_inst(ctx, pattern9_1)
+                                                        {
+                                                            let pattern19_0 =
+                                                                C::inst_data(ctx, pattern18_0);
+                                                            if let &InstructionData::Unary {
+                                                                opcode: ref pattern20_0,
+                                                                arg: pattern20_1,
+                                                            } = &pattern19_0
+                                                            {
+                                                                if let &Opcode::UwidenHigh =
+                                                                    &pattern20_0
+                                                                {
+                                                                    let pattern22_0 = C::value_type(
+                                                                        ctx,
+                                                                        pattern20_1,
+                                                                    );
+                                                                    if let Some((
+                                                                        pattern23_0,
+                                                                        pattern23_1,
+                                                                    )) = C::multi_lane(
+                                                                        ctx,
+                                                                        pattern22_0,
+                                                                    ) {
+                                                                        if pattern23_0 == 8 {
+                                                                            if pattern23_1 == 16 {
+                                                                                // Rule at src/isa/x64/lower.isle line 878.
+                                                                                let expr0_0 =
+                                                                                    C::put_in_reg(
+                                                                                        ctx,
+                                                                                        pattern12_1,
+                                                                                    );
+                                                                                let expr1_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr0_0,
+                                                                                    };
+                                                                                let expr2_0: u8 = 8;
+                                                                                let expr3_0 = OperandSize::Size32;
+                                                                                let expr4_0 = constructor_palignr(ctx, expr0_0, &expr1_0, expr2_0, &expr3_0)?;
+                                                                                let expr5_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr4_0,
+                                                                                    };
+                                                                                let expr6_0 = constructor_pmovzxbw(ctx, &expr5_0)?;
+                                                                                let expr7_0 =
+                                                                                    C::put_in_reg(
+                                                                                        ctx,
+                                                                                        pattern20_1,
+                                                                                    );
+                                                                                let expr8_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr7_0,
+                                                                                    };
+                                                                                let expr9_0: u8 = 8;
+                                                                                let expr10_0 = OperandSize::Size32;
+                                                                                let expr11_0 = constructor_palignr(ctx, expr7_0, &expr8_0, expr9_0, &expr10_0)?;
+                                                                                let expr12_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr11_0,
+                                                                                    };
+                                                                                let expr13_0 = constructor_pmovzxbw(ctx, &expr12_0)?;
+                                                                                let expr14_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr13_0,
+                                                                                    };
+                                                                                let expr15_0 = constructor_pmullw(ctx, expr6_0, &expr14_0)?;
+                                                                                let expr16_0 =
+                                                                                    C::value_reg(
+                                                                                        ctx,
+                                                                                        expr15_0,
+                                                                                    );
+                                                                                return Some(
+                                                                                    expr16_0,
+                                                                                );
+                                                                            }
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        _ => {}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            if pattern3_0 == 32 {
+                if pattern3_1 == 4 {
+                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
+                    if let &InstructionData::Binary {
+                        opcode: ref pattern7_0,
+                        args: ref pattern7_1,
+                    } = &pattern6_0
+                    {
+                        if let &Opcode::Imul = &pattern7_0 {
+                            let (pattern9_0, pattern9_1) =
+                                C::unpack_value_array_2(ctx, &pattern7_1);
+                            if let Some(pattern10_0) = C::def_inst(ctx, pattern9_0) {
+                                let pattern11_0 = C::inst_data(ctx, pattern10_0);
+                                if let &InstructionData::Unary {
+                                    opcode: ref pattern12_0,
+                                    arg: pattern12_1,
+                                } = &pattern11_0
+                                {
+                                    match &pattern12_0 {
+                                        &Opcode::SwidenLow => {
+                                            let pattern14_0 = C::value_type(ctx, pattern12_1);
+                                            if let Some((pattern15_0, pattern15_1)) =
+                                                C::multi_lane(ctx, pattern14_0)
+                                            {
+                                                if pattern15_0 == 16 {
+                                                    if pattern15_1 == 8 {
+                                                        if let Some(pattern18_0) =
+                                                            C::def_inst(ctx, pattern9_1)
+                                                        {
+                                                            let pattern19_0 =
+                                                                C::inst_data(ctx, pattern18_0);
+                                                            if let &InstructionData::Unary {
+                                                                opcode: ref pattern20_0,
+                                                                arg: pattern20_1,
+                                                            } = &pattern19_0
+                                                            {
+                                                                if let &Opcode::SwidenLow =
+                                                                    &pattern20_0
+                                                                {
+                                                                    let pattern22_0 = C::value_type(
+                                                                        ctx,
+                                                                        pattern20_1,
+                                                                    );
+                                                                    if let Some((
+                                                                        pattern23_0,
+                                                                        pattern23_1,
+                                                                    )) = C::multi_lane(
+                                                                        ctx,
+                                                                        pattern22_0,
+                                                                    ) {
+                                                                        if pattern23_0 == 16 {
+                                                                            if pattern23_1 == 8 {
+                                                                                // Rule at src/isa/x64/lower.isle line 852.
+                                                                                let expr0_0 =
+                                                                                    C::put_in_reg(
+                                                                                        ctx,
+                                                                                        pattern12_1,
+                                                                                    );
+                                                                                let expr1_0 =
+                                                                                    C::put_in_reg(
+                                                                                        ctx,
+                                                                                        pattern20_1,
+                                                                                    );
+                                                                                let expr2_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr1_0,
+                                                                                    };
+                                                                                let expr3_0 = constructor_pmullw(ctx, expr0_0, &expr2_0)?;
+                                                                                let expr4_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr1_0,
+                                                                                    };
+                                                                                let expr5_0 = constructor_pmulhw(ctx, expr0_0, &expr4_0)?;
+                                                                                let expr6_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr5_0,
+                                                                                    };
+                                                                                let expr7_0 = constructor_punpcklwd(ctx, expr3_0, &expr6_0)?;
+                                                                                let expr8_0 =
+                                                                                    C::value_reg(
+                                                                                        ctx,
+                                                                                        expr7_0,
+                                                                                    );
+                                                                                return Some(
+                                                                                    expr8_0,
+                                                                                );
+                                                                            }
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        &Opcode::SwidenHigh => {
+                                            let pattern14_0 = C::value_type(ctx, pattern12_1);
+                                            if let Some((pattern15_0, pattern15_1)) =
+                                                C::multi_lane(ctx, pattern14_0)
+                                            {
+                                                if pattern15_0 == 16 {
+                                                    if pattern15_1 == 8 {
+                                                        if let Some(pattern18_0) =
+                                                            C::def_inst(ctx, pattern9_1)
+                                                        {
+                                                            let pattern19_0 =
+                                                                C::inst_data(ctx, pattern18_0);
+                                                            if let &InstructionData::Unary {
+                                                                opcode: ref pattern20_0,
+                                                                arg: pattern20_1,
+                                                            } = &pattern19_0
+                                                            {
+                                                                if let &Opcode::SwidenHigh =
+                                                                    &pattern20_0
+                                                                {
+                                                                    let pattern22_0 = C::value_type(
+                                                                        ctx,
+                                                                        pattern20_1,
+                                                                    );
+                                                                    if let Some((
+                                                                        pattern23_0,
+                                                                        pattern23_1,
+                                                                    )) = C::multi_lane(
+                                                                        ctx,
+                                                                        pattern22_0,
+                                                                    ) {
+                                                                        if pattern23_0 == 16 {
+                                                                            if pattern23_1 == 8 {
+                                                                                // Rule at src/isa/x64/lower.isle line 816.
+                                                                                let expr0_0 =
+                                                                                    C::put_in_reg(
+                                                                                        ctx,
+                                                                                        pattern12_1,
+                                                                                    );
+                                                                                let expr1_0 =
+                                                                                    C::put_in_reg(
+                                                                                        ctx,
+                                                                                        pattern20_1,
+                                                                                    );
+                                                                                let expr2_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr1_0,
+                                                                                    };
+                                                                                let expr3_0 = constructor_pmullw(ctx, expr0_0, &expr2_0)?;
+                                                                                let expr4_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr1_0,
+                                                                                    };
+                                                                                let expr5_0 = constructor_pmulhw(ctx, expr0_0, &expr4_0)?;
+                                                                                let expr6_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr5_0,
+                                                                                    };
+                                                                                let expr7_0 = constructor_punpckhwd(ctx, expr3_0, &expr6_0)?;
+                                                                                let expr8_0 =
+                                                                                    C::value_reg(
+                                                                                        ctx,
+                                                                                        expr7_0,
+                                                                                    );
+                                                                                return Some(
+                                                                                    expr8_0,
+                                                                                );
+                                                                            }
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        &Opcode::UwidenLow => {
+                                            let pattern14_0 = C::value_type(ctx, pattern12_1);
+                                            if let Some((pattern15_0, pattern15_1)) =
+                                                C::multi_lane(ctx, pattern14_0)
+                                            {
+                                                if pattern15_0 == 16 {
+                                                    if pattern15_1 == 8 {
+                                                        if let Some(pattern18_0) =
+                                                            C::def_inst(ctx, pattern9_1)
+                                                        {
+                                                            let pattern19_0 =
+                                                                C::inst_data(ctx, pattern18_0);
+                                                            if let &InstructionData::Unary {
+                                                                opcode: ref pattern20_0,
+                                                                arg: pattern20_1,
+                                                            } = &pattern19_0
+                                                            {
+                                                                if let &Opcode::UwidenLow =
+                                                                    &pattern20_0
+                                                                {
+                                                                    let pattern22_0 = C::value_type(
+                                                                        ctx,
+                                                                        pattern20_1,
+                                                                    );
+                                                                    if let Some((
+                                                                        pattern23_0,
+                                                                        pattern23_1,
+                                                                    )) = C::multi_lane(
+                                                                        ctx,
+                                                                        pattern22_0,
+                                                                    ) {
+                                                                        if pattern23_0 == 16 {
+                                                                            if pattern23_1 == 8 {
+                                                                                // Rule at src/isa/x64/lower.isle line 928.
+                                                                                let expr0_0 =
+                                                                                    C::put_in_reg(
+                                                                                        ctx,
+                                                                                        pattern12_1,
+                                                                                    );
+                                                                                let expr1_0 =
+                                                                                    C::put_in_reg(
+                                                                                        ctx,
+                                                                                        pattern20_1,
+                                                                                    );
+                                                                                let expr2_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr1_0,
+                                                                                    };
+                                                                                let expr3_0 = constructor_pmullw(ctx, expr0_0, &expr2_0)?;
+                                                                                let expr4_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr1_0,
+                                                                                    };
+                                                                                let expr5_0 = constructor_pmulhuw(ctx, expr0_0, &expr4_0)?;
+                                                                                let expr6_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr5_0,
+                                                                                    };
+                                                                                let expr7_0 = constructor_punpcklwd(ctx, expr3_0, &expr6_0)?;
+                                                                                let expr8_0 =
+                                                                                    C::value_reg(
+                                                                                        ctx,
+                                                                                        expr7_0,
+                                                                                    );
+                                                                                return Some(
+                                                                                    expr8_0,
+                                                                                );
+                                                                            }
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        &Opcode::UwidenHigh => {
+                                            let pattern14_0 = C::value_type(ctx, pattern12_1);
+                                            if let Some((pattern15_0, pattern15_1)) =
+                                                C::multi_lane(ctx, pattern14_0)
+                                            {
+                                                if pattern15_0 == 16 {
+                                                    if pattern15_1 == 8 {
+                                                        if let Some(pattern18_0) =
+                                                            C::def_inst(ctx, pattern9_1)
+                                                        {
+                                                            let pattern19_0 =
+                                                                C::inst_data(ctx, pattern18_0);
+                                                            if let &InstructionData::Unary {
+                                                                opcode: ref pattern20_0,
+                                                                arg: pattern20_1,
+                                                            } = &pattern19_0
+                                                            {
+                                                                if let &Opcode::UwidenHigh =
+                                                                    &pattern20_0
+                                                                {
+                                                                    let pattern22_0 = C::value_type(
+                                                                        ctx,
+                                                                        pattern20_1,
+                                                                    );
+                                                                    if let Some((
+                                                                        pattern23_0,
+                                                                        pattern23_1,
+                                                                    )) = C::multi_lane(
+                                                                        ctx,
+                                                                        pattern22_0,
+                                                                    ) {
+                                                                        if pattern23_0 == 16 {
+                                                                            if pattern23_1 == 8 {
+                                                                                // Rule at src/isa/x64/lower.isle line 892.
+                                                                                let expr0_0 =
+                                                                                    C::put_in_reg(
+                                                                                        ctx,
+                                                                                        pattern12_1,
+                                                                                    );
+                                                                                let expr1_0 =
+                                                                                    C::put_in_reg(
+                                                                                        ctx,
+                                                                                        pattern20_1,
+                                                                                    );
+                                                                                let expr2_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr1_0,
+                                                                                    };
+                                                                                let expr3_0 = constructor_pmullw(ctx, expr0_0, &expr2_0)?;
+                                                                                let expr4_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr1_0,
+                                                                                    };
+                                                                                let expr5_0 = constructor_pmulhuw(ctx, expr0_0, &expr4_0)?;
+                                                                                let expr6_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr5_0,
+                                                                                    };
+                                                                                let expr7_0 = constructor_punpckhwd(ctx, expr3_0, &expr6_0)?;
+                                                                                let expr8_0 =
+                                                                                    C::value_reg(
+                                                                                        ctx,
+                                                                                        expr7_0,
+                                                                                    );
+                                                                                return Some(
+                                                                                    expr8_0,
+                                                                                );
+                                                                            }
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        _ => {}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            if pattern3_0 == 64 {
+                if pattern3_1 == 2 {
+                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
+                    if let &InstructionData::Binary {
+                        opcode: ref pattern7_0,
+                        args: ref pattern7_1,
+                    } = &pattern6_0
+                    {
+                        if let &Opcode::Imul = &pattern7_0 {
+                            let (pattern9_0, pattern9_1) =
+                                C::unpack_value_array_2(ctx, &pattern7_1);
+                            if let Some(pattern10_0) = C::def_inst(ctx, pattern9_0) {
+                                let pattern11_0 = C::inst_data(ctx, pattern10_0);
+                                if let &InstructionData::Unary {
+                                    opcode: ref pattern12_0,
+                                    arg: pattern12_1,
+                                } = &pattern11_0
+                                {
+                                    match &pattern12_0 {
+                                        &Opcode::SwidenLow => {
+                                            let pattern14_0 = C::value_type(ctx, pattern12_1);
+                                            if let Some((pattern15_0, pattern15_1)) =
+                                                C::multi_lane(ctx, pattern14_0)
+                                            {
+                                                if pattern15_0 == 32 {
+                                                    if pattern15_1 == 4 {
+                                                        if let Some(pattern18_0) =
+                                                            C::def_inst(ctx, pattern9_1)
+                                                        {
+                                                            let pattern19_0 =
+                                                                C::inst_data(ctx, pattern18_0);
+                                                            if let &InstructionData::Unary {
+                                                                opcode: ref pattern20_0,
+                                                                arg: pattern20_1,
+                                                            } = &pattern19_0
+                                                            {
+                                                                if let &Opcode::SwidenLow =
+                                                                    &pattern20_0
+                                                                {
+                                                                    let pattern22_0 = C::value_type(
+                                                                        ctx,
+                                                                        pattern20_1,
+                                                                    );
+                                                                    if let Some((
+                                                                        pattern23_0,
+                                                                        pattern23_1,
+                                                                    )) = C::multi_lane(
+                                                                        ctx,
+                                                                        pattern22_0,
+                                                                    ) {
+                                                                        if pattern23_0 == 32 {
+                                                                            if pattern23_1 == 4 {
+                                                                                // Rule at src/isa/x64/lower.isle line 864.
+                                                                                let expr0_0 = C::put_in_reg_mem(ctx, pattern12_1);
+                                                                                let expr1_0: u8 =
+                                                                                    80;
+                                                                                let expr2_0 = OperandSize::Size32;
+                                                                                let expr3_0 = constructor_pshufd(ctx, &expr0_0, expr1_0, &expr2_0)?;
+                                                                                let expr4_0 = C::put_in_reg_mem(ctx, pattern20_1);
+                                                                                let expr5_0: u8 =
+                                                                                    80;
+                                                                                let expr6_0 = OperandSize::Size32;
+                                                                                let expr7_0 = constructor_pshufd(ctx, &expr4_0, expr5_0, &expr6_0)?;
+                                                                                let expr8_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr7_0,
+                                                                                    };
+                                                                                let expr9_0 = constructor_pmuldq(ctx, expr3_0, &expr8_0)?;
+                                                                                let expr10_0 =
+                                                                                    C::value_reg(
+                                                                                        ctx,
+                                                                                        expr9_0,
+                                                                                    );
+                                                                                return Some(
+                                                                                    expr10_0,
+                                                                                );
+                                                                            }
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        &Opcode::SwidenHigh => {
+                                            let pattern14_0 = C::value_type(ctx, pattern12_1);
+                                            if let Some((pattern15_0, pattern15_1)) =
+                                                C::multi_lane(ctx, pattern14_0)
+                                            {
+                                                if pattern15_0 == 32 {
+                                                    if pattern15_1 == 4 {
+                                                        if let Some(pattern18_0) =
+                                                            C::def_inst(ctx, pattern9_1)
+                                                        {
+                                                            let pattern19_0 =
+                                                                C::inst_data(ctx, pattern18_0);
+                                                            if let &InstructionData::Unary {
+                                                                opcode: ref pattern20_0,
+                                                                arg: pattern20_1,
+                                                            } = &pattern19_0
+                                                            {
+                                                                if let &Opcode::SwidenHigh =
+                                                                    &pattern20_0
+                                                                {
+                                                                    let pattern22_0 = C::value_type(
+                                                                        ctx,
+                                                                        pattern20_1,
+                                                                    );
+                                                                    if let Some((
+                                                                        pattern23_0,
+                                                                        pattern23_1,
+                                                                    )) = C::multi_lane(
+                                                                        ctx,
+                                                                        pattern22_0,
+                                                                    ) {
+                                                                        if pattern23_0 == 32 {
+                                                                            if pattern23_1 == 4 {
+                                                                                // Rule at src/isa/x64/lower.isle line 828.
+                                                                                let expr0_0 = C::put_in_reg_mem(ctx, pattern12_1);
+                                                                                let expr1_0: u8 =
+                                                                                    250;
+                                                                                let expr2_0 = OperandSize::Size32;
+                                                                                let expr3_0 = constructor_pshufd(ctx, &expr0_0, expr1_0, &expr2_0)?;
+                                                                                let expr4_0 = C::put_in_reg_mem(ctx, pattern20_1);
+                                                                                let expr5_0: u8 =
+                                                                                    250;
+                                                                                let expr6_0 = OperandSize::Size32;
+                                                                                let expr7_0 = constructor_pshufd(ctx, &expr4_0, expr5_0, &expr6_0)?;
+                                                                                let expr8_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr7_0,
+                                                                                    };
+                                                                                let expr9_0 = constructor_pmuldq(ctx, expr3_0, &expr8_0)?;
+                                                                                let expr10_0 =
+                                                                                    C::value_reg(
+                                                                                        ctx,
+                                                                                        expr9_0,
+                                                                                    );
+                                                                                return Some(
+                                                                                    expr10_0,
+                                                                                );
+                                                                            }
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        &Opcode::UwidenLow => {
+                                            let pattern14_0 = C::value_type(ctx, pattern12_1);
+                                            if let Some((pattern15_0, pattern15_1)) =
+                                                C::multi_lane(ctx, pattern14_0)
+                                            {
+                                                if pattern15_0 == 32 {
+                                                    if pattern15_1 == 4 {
+                                                        if let Some(pattern18_0) =
+                                                            C::def_inst(ctx, pattern9_1)
+                                                        {
+                                                            let pattern19_0 =
+                                                                C::inst_data(ctx, pattern18_0);
+                                                            if let &InstructionData::Unary {
+                                                                opcode: ref pattern20_0,
+                                                                arg: pattern20_1,
+                                                            } = &pattern19_0
+                                                            {
+                                                                if let &Opcode::UwidenLow =
+                                                                    &pattern20_0
+                                                                {
+                                                                    let pattern22_0 = C::value_type(
+                                                                        ctx,
+                                                                        pattern20_1,
+                                                                    );
+                                                                    if let Some((
+                                                                        pattern23_0,
+                                                                        pattern23_1,
+                                                                    )) = C::multi_lane(
+                                                                        ctx,
+                                                                        pattern22_0,
+                                                                    ) {
+                                                                        if pattern23_0 == 32 {
+                                                                            if pattern23_1 == 4 {
+                                                                                // Rule at src/isa/x64/lower.isle line 940.
+                                                                                let expr0_0 = C::put_in_reg_mem(ctx, pattern12_1);
+                                                                                let expr1_0: u8 =
+                                                                                    80;
+                                                                                let expr2_0 = OperandSize::Size32;
+                                                                                let expr3_0 = constructor_pshufd(ctx, &expr0_0, expr1_0, &expr2_0)?;
+                                                                                let expr4_0 = C::put_in_reg_mem(ctx, pattern20_1);
+                                                                                let expr5_0: u8 =
+                                                                                    80;
+                                                                                let expr6_0 = OperandSize::Size32;
+                                                                                let expr7_0 = constructor_pshufd(ctx, &expr4_0, expr5_0, &expr6_0)?;
+                                                                                let expr8_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr7_0,
+                                                                                    };
+                                                                                let expr9_0 = constructor_pmuludq(ctx, expr3_0, &expr8_0)?;
+                                                                                let expr10_0 =
+                                                                                    C::value_reg(
+                                                                                        ctx,
+                                                                                        expr9_0,
+                                                                                    );
+                                                                                return Some(
+                                                                                    expr10_0,
+                                                                                );
+                                                                            }
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        &Opcode::UwidenHigh => {
+                                            let pattern14_0 = C::value_type(ctx, pattern12_1);
+                                            if let Some((pattern15_0, pattern15_1)) =
+                                                C::multi_lane(ctx, pattern14_0)
+                                            {
+                                                if pattern15_0 == 32 {
+                                                    if pattern15_1 == 4 {
+                                                        if let Some(pattern18_0) =
+                                                            C::def_inst(ctx, pattern9_1)
+                                                        {
+                                                            let pattern19_0 =
+                                                                C::inst_data(ctx, pattern18_0);
+                                                            if let &InstructionData::Unary {
+                                                                opcode: ref pattern20_0,
+                                                                arg: pattern20_1,
+                                                            } = &pattern19_0
+                                                            {
+                                                                if let &Opcode::UwidenHigh =
+                                                                    &pattern20_0
+                                                                {
+                                                                    let pattern22_0 = C::value_type(
+                                                                        ctx,
+                                                                        pattern20_1,
+                                                                    );
+                                                                    if let Some((
+                                                                        pattern23_0,
+                                                                        pattern23_1,
+                                                                    )) = C::multi_lane(
+                                                                        ctx,
+                                                                        pattern22_0,
+                                                                    ) {
+                                                                        if pattern23_0 == 32 {
+                                                                            if pattern23_1 == 4 {
+                                                                                // Rule at src/isa/x64/lower.isle line 904.
+                                                                                let expr0_0 = C::put_in_reg_mem(ctx, pattern12_1);
+                                                                                let expr1_0: u8 =
+                                                                                    250;
+                                                                                let expr2_0 = OperandSize::Size32;
+                                                                                let expr3_0 = constructor_pshufd(ctx, &expr0_0, expr1_0, &expr2_0)?;
+                                                                                let expr4_0 = C::put_in_reg_mem(ctx, pattern20_1);
+                                                                                let expr5_0: u8 =
+                                                                                    250;
+                                                                                let expr6_0 = OperandSize::Size32;
+                                                                                let expr7_0 = constructor_pshufd(ctx, &expr4_0, expr5_0, &expr6_0)?;
+                                                                                let expr8_0 =
+                                                                                    RegMem::Reg {
+                                                                                        reg:
+                                                                                            expr7_0,
+                                                                                    };
+                                                                                let expr9_0 = constructor_pmuludq(ctx, expr3_0, &expr8_0)?;
+                                                                                let expr10_0 =
+                                                                                    C::value_reg(
+                                                                                        ctx,
+                                                                                        expr9_0,
+                                                                                    );
+                                                                                return Some(
+                                                                                    expr10_0,
+                                                                                );
+                                                                            }
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        _ => {}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
             }
         }
-        &InstructionData::UnaryIeee64 {
-            opcode: ref pattern2_0,
-            imm: pattern2_1,
-        } => {
-            if let &Opcode::F64const = &pattern2_0 {
-                let pattern4_0 = C::u64_from_ieee64(ctx, pattern2_1);
-                // Rule at src/isa/x64/lower.isle line 51.
-                let expr0_0: Type = F64;
-                let expr1_0 = constructor_imm(ctx, expr0_0, pattern4_0)?;
-                let expr2_0 = C::value_reg(ctx, expr1_0);
-                return Some(expr2_0);
+    }
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if let Some((pattern3_0, pattern3_1)) = C::multi_lane(ctx, pattern2_0) {
+            if pattern3_0 == 8 {
+                if pattern3_1 == 16 {
+                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
+                    if let &InstructionData::Binary {
+                        opcode: ref pattern7_0,
+                        args: ref pattern7_1,
+                    } = &pattern6_0
+                    {
+                        if let &Opcode::Iadd = &pattern7_0 {
+                            let (pattern9_0, pattern9_1) =
+                                C::unpack_value_array_2(ctx, &pattern7_1);
+                            // Rule at src/isa/x64/lower.isle line 96.
+                            let expr0_0 = C::put_in_reg(ctx, pattern9_0);
+                            let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
+                            let expr2_0 = constructor_paddb(ctx, expr0_0, &expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                    }
+                }
+            }
+            if pattern3_0 == 16 {
+                if pattern3_1 == 8 {
+                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
+                    if let &InstructionData::Binary {
+                        opcode: ref pattern7_0,
+                        args: ref pattern7_1,
+                    } = &pattern6_0
+                    {
+                        match &pattern7_0 {
+                            &Opcode::Iadd => {
+                                let (pattern9_0, pattern9_1) =
+                                    C::unpack_value_array_2(ctx, &pattern7_1);
+                                // Rule at src/isa/x64/lower.isle line 101.
+                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
+                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
+                                let expr2_0 = constructor_paddw(ctx, expr0_0, &expr1_0)?;
+                                let expr3_0 = C::value_reg(ctx, expr2_0);
+                                return Some(expr3_0);
+                            }
+                            &Opcode::Imul => {
+                                let (pattern9_0, pattern9_1) =
+                                    C::unpack_value_array_2(ctx, &pattern7_1);
+                                // Rule at src/isa/x64/lower.isle line 746.
+                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
+                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
+                                let expr2_0 = constructor_pmullw(ctx, expr0_0, &expr1_0)?;
+                                let expr3_0 = C::value_reg(ctx, expr2_0);
+                                return Some(expr3_0);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            if pattern3_0 == 32 {
+                if pattern3_1 == 4 {
+                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
+                    if let &InstructionData::Binary {
+                        opcode: ref pattern7_0,
+                        args: ref pattern7_1,
+                    } = &pattern6_0
+                    {
+                        match &pattern7_0 {
+                            &Opcode::Iadd => {
+                                let (pattern9_0, pattern9_1) =
+                                    C::unpack_value_array_2(ctx, &pattern7_1);
+                                // Rule at src/isa/x64/lower.isle line 106.
+                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
+                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
+                                let expr2_0 = constructor_paddd(ctx, expr0_0, &expr1_0)?;
+                                let expr3_0 = C::value_reg(ctx, expr2_0);
+                                return Some(expr3_0);
+                            }
+                            &Opcode::Imul => {
+                                let (pattern9_0, pattern9_1) =
+                                    C::unpack_value_array_2(ctx, &pattern7_1);
+                                // Rule at src/isa/x64/lower.isle line 749.
+                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
+                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
+                                let expr2_0 = constructor_pmulld(ctx, expr0_0, &expr1_0)?;
+                                let expr3_0 = C::value_reg(ctx, expr2_0);
+                                return Some(expr3_0);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            if pattern3_0 == 64 {
+                if pattern3_1 == 2 {
+                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
+                    if let &InstructionData::Binary {
+                        opcode: ref pattern7_0,
+                        args: ref pattern7_1,
+                    } = &pattern6_0
+                    {
+                        match &pattern7_0 {
+                            &Opcode::Iadd => {
+                                let (pattern9_0, pattern9_1) =
+                                    C::unpack_value_array_2(ctx, &pattern7_1);
+                                // Rule at src/isa/x64/lower.isle line 111.
+                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
+                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
+                                let expr2_0 = constructor_paddq(ctx, expr0_0, &expr1_0)?;
+                                let expr3_0 = C::value_reg(ctx, expr2_0);
+                                return Some(expr3_0);
+                            }
+                            &Opcode::Imul => {
+                                let (pattern9_0, pattern9_1) =
+                                    C::unpack_value_array_2(ctx, &pattern7_1);
+                                // Rule at src/isa/x64/lower.isle line 780.
+                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
+                                let expr1_0 = C::put_in_reg(ctx, pattern9_1);
+                                let expr2_0: u32 = 32;
+                                let expr3_0 = RegMemImm::Imm { simm32: expr2_0 };
+                                let expr4_0 = constructor_psrlq(ctx, expr0_0, &expr3_0)?;
+                                let expr5_0 = RegMem::Reg { reg: expr1_0 };
+                                let expr6_0 = constructor_pmuludq(ctx, expr4_0, &expr5_0)?;
+                                let expr7_0: u32 = 32;
+                                let expr8_0 = RegMemImm::Imm { simm32: expr7_0 };
+                                let expr9_0 = constructor_psrlq(ctx, expr1_0, &expr8_0)?;
+                                let expr10_0 = RegMem::Reg { reg: expr9_0 };
+                                let expr11_0 = constructor_pmuludq(ctx, expr0_0, &expr10_0)?;
+                                let expr12_0 = RegMem::Reg { reg: expr11_0 };
+                                let expr13_0 = constructor_paddq(ctx, expr6_0, &expr12_0)?;
+                                let expr14_0: u32 = 32;
+                                let expr15_0 = RegMemImm::Imm { simm32: expr14_0 };
+                                let expr16_0 = constructor_psllq(ctx, expr13_0, &expr15_0)?;
+                                let expr17_0 = RegMem::Reg { reg: expr1_0 };
+                                let expr18_0 = constructor_pmuludq(ctx, expr0_0, &expr17_0)?;
+                                let expr19_0 = RegMem::Reg { reg: expr16_0 };
+                                let expr20_0 = constructor_paddq(ctx, expr18_0, &expr19_0)?;
+                                let expr21_0 = C::value_reg(ctx, expr20_0);
+                                return Some(expr21_0);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Band => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/x64/lower.isle line 368.
+                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr1_0 = C::put_in_reg_mem(ctx, pattern7_1);
+                        let expr2_0 = constructor_sse_and(ctx, pattern2_0, expr0_0, &expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    &Opcode::Bor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/x64/lower.isle line 440.
+                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr1_0 = C::put_in_reg_mem(ctx, pattern7_1);
+                        let expr2_0 = constructor_sse_or(ctx, pattern2_0, expr0_0, &expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    &Opcode::Bxor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/x64/lower.isle line 509.
+                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr1_0 = C::put_in_reg_mem(ctx, pattern7_1);
+                        let expr2_0 = constructor_sse_xor(ctx, pattern2_0, expr0_0, &expr1_0)?;
+                        let expr3_0 = C::value_reg(ctx, expr2_0);
+                        return Some(expr3_0);
+                    }
+                    _ => {}
+                }
             }
         }
-        &InstructionData::TernaryImm8 {
-            opcode: ref pattern2_0,
-            args: ref pattern2_1,
-            imm: pattern2_2,
-        } => {
-            if let &Opcode::Insertlane = &pattern2_0 {
-                let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
-                let pattern5_0 = C::value_type(ctx, pattern4_0);
-                let pattern6_0 = C::u8_from_uimm8(ctx, pattern2_2);
-                // Rule at src/isa/x64/lower.isle line 1055.
-                let expr0_0 = C::put_in_reg(ctx, pattern4_0);
-                let expr1_0 = C::put_in_reg_mem(ctx, pattern4_1);
-                let expr2_0 =
-                    constructor_vec_insert_lane(ctx, pattern5_0, expr0_0, &expr1_0, pattern6_0)?;
-                let expr3_0 = C::value_reg(ctx, expr2_0);
-                return Some(expr3_0);
+    }
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if pattern2_0 == I128 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Isub => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/x64/lower.isle line 286.
+                        let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                        let expr1_0: usize = 0;
+                        let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                        let expr3_0: usize = 1;
+                        let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                        let expr5_0 = C::put_in_regs(ctx, pattern7_1);
+                        let expr6_0: usize = 0;
+                        let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
+                        let expr8_0: usize = 1;
+                        let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
+                        let expr10_0: Type = I64;
+                        let expr11_0 = RegMemImm::Reg { reg: expr7_0 };
+                        let expr12_0 =
+                            constructor_sub_with_flags(ctx, expr10_0, expr2_0, &expr11_0)?;
+                        let expr13_0: Type = I64;
+                        let expr14_0 = RegMemImm::Reg { reg: expr9_0 };
+                        let expr15_0 = constructor_sbb(ctx, expr13_0, expr4_0, &expr14_0)?;
+                        let expr16_0 = constructor_with_flags(ctx, &expr12_0, &expr15_0)?;
+                        return Some(expr16_0);
+                    }
+                    &Opcode::Rotl => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        // Rule at src/isa/x64/lower.isle line 652.
+                        let expr0_0 = C::put_in_regs(ctx, pattern7_0);
+                        let expr1_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                        let expr2_0 = constructor_shl_i128(ctx, expr0_0, expr1_0)?;
+                        let expr3_0: Type = I64;
+                        let expr4_0: Type = I64;
+                        let expr5_0: u64 = 128;
+                        let expr6_0 = constructor_imm(ctx, expr4_0, expr5_0)?;
+                        let expr7_0 = RegMemImm::Reg { reg: expr1_0 };
+                        let expr8_0 = constructor_sub(ctx, expr3_0, expr6_0, &expr7_0)?;
+                        let expr9_0 = constructor_shr_i128(ctx, expr0_0, expr8_0)?;
+                        let expr10_0 = constructor_or_i128(ctx, expr2_0, expr9_0)?;
+                        return Some(expr10_0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Iadd => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_0) {
+                            // Rule at src/isa/x64/lower.isle line 76.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr1_0 = constructor_add(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                            return Some(expr2_0);
+                        }
+                    }
+                    &Opcode::Imul => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_0) {
+                            // Rule at src/isa/x64/lower.isle line 686.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr1_0 = constructor_mul(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                            return Some(expr2_0);
+                        }
+                    }
+                    &Opcode::IaddIfcout => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_0) {
+                            // Rule at src/isa/x64/lower.isle line 183.
+                            let expr0_0: Type = I64;
+                            let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
+                            let expr2_0 = C::writable_reg_to_reg(ctx, expr1_0);
+                            let expr3_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr4_0 = constructor_add(ctx, pattern3_0, expr3_0, &pattern8_0)?;
+                            let expr5_0 = C::value_regs(ctx, expr4_0, expr2_0);
+                            return Some(expr5_0);
+                        }
+                    }
+                    &Opcode::Band => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_0) {
+                            // Rule at src/isa/x64/lower.isle line 355.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr1_0 = constructor_m_and(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                            return Some(expr2_0);
+                        }
+                    }
+                    &Opcode::Bor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_0) {
+                            // Rule at src/isa/x64/lower.isle line 427.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr1_0 = constructor_or(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                            return Some(expr2_0);
+                        }
+                    }
+                    &Opcode::Bxor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_0) {
+                            // Rule at src/isa/x64/lower.isle line 501.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                            let expr1_0 = constructor_xor(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                            return Some(expr2_0);
+                        }
+                    }
+                    _ => {}
+                }
             }
         }
-        _ => {}
     }
+    let pattern0_0 = arg0;
     if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
         let pattern2_0 = C::value_type(ctx, pattern1_0);
-        if pattern2_0 == B128 {
-            let pattern4_0 = C::inst_data(ctx, pattern0_0);
-            match &pattern4_0 {
-                &InstructionData::UnaryBool {
-                    opcode: ref pattern5_0,
-                    imm: pattern5_1,
-                } => {
-                    if let &Opcode::Bconst = &pattern5_0 {
-                        if pattern5_1 == true {
-                            // Rule at src/isa/x64/lower.isle line 39.
-                            let expr0_0: Type = B64;
-                            let expr1_0: u64 = 1;
-                            let expr2_0 = constructor_imm(ctx, expr0_0, expr1_0)?;
-                            let expr3_0: Type = B64;
-                            let expr4_0: u64 = 0;
-                            let expr5_0 = constructor_imm(ctx, expr3_0, expr4_0)?;
-                            let expr6_0 = C::value_regs(ctx, expr2_0, expr5_0);
-                            return Some(expr6_0);
-                        }
-                        if pattern5_1 == false {
-                            // Rule at src/isa/x64/lower.isle line 34.
-                            let expr0_0: Type = B64;
-                            let expr1_0: u64 = 0;
-                            let expr2_0 = constructor_imm(ctx, expr0_0, expr1_0)?;
-                            let expr3_0: Type = B64;
-                            let expr4_0: u64 = 0;
-                            let expr5_0 = constructor_imm(ctx, expr3_0, expr4_0)?;
-                            let expr6_0 = C::value_regs(ctx, expr2_0, expr5_0);
-                            return Some(expr6_0);
+        if let Some((pattern3_0, pattern3_1)) = C::multi_lane(ctx, pattern2_0) {
+            if pattern3_0 == 8 {
+                if pattern3_1 == 16 {
+                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
+                    if let &InstructionData::Binary {
+                        opcode: ref pattern7_0,
+                        args: ref pattern7_1,
+                    } = &pattern6_0
+                    {
+                        if let &Opcode::Isub = &pattern7_0 {
+                            let (pattern9_0, pattern9_1) =
+                                C::unpack_value_array_2(ctx, &pattern7_1);
+                            // Rule at src/isa/x64/lower.isle line 265.
+                            let expr0_0 = C::put_in_reg(ctx, pattern9_0);
+                            let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
+                            let expr2_0 = constructor_psubb(ctx, expr0_0, &expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
                         }
                     }
                 }
-                &InstructionData::Binary {
-                    opcode: ref pattern5_0,
-                    args: ref pattern5_1,
-                } => {
-                    match &pattern5_0 {
-                        &Opcode::Band => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/x64/lower.isle line 384.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0: usize = 0;
-                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
-                            let expr3_0: usize = 1;
-                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
-                            let expr5_0 = constructor_lo_reg(ctx, pattern7_1)?;
-                            let expr6_0: Type = I64;
-                            let expr7_0 = RegMemImm::Reg { reg: expr5_0 };
-                            let expr8_0 = constructor_m_and(ctx, expr6_0, expr2_0, &expr7_0)?;
-                            let expr9_0 = C::value_regs(ctx, expr8_0, expr4_0);
-                            return Some(expr9_0);
-                        }
-                        &Opcode::Bor => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/x64/lower.isle line 458.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0: usize = 0;
-                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
-                            let expr3_0: usize = 1;
-                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
-                            let expr5_0 = constructor_lo_reg(ctx, pattern7_1)?;
-                            let expr6_0: Type = I64;
-                            let expr7_0 = RegMemImm::Reg { reg: expr5_0 };
-                            let expr8_0 = constructor_or(ctx, expr6_0, expr2_0, &expr7_0)?;
-                            let expr9_0 = C::value_regs(ctx, expr8_0, expr4_0);
-                            return Some(expr9_0);
+            }
+            if pattern3_0 == 16 {
+                if pattern3_1 == 8 {
+                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
+                    if let &InstructionData::Binary {
+                        opcode: ref pattern7_0,
+                        args: ref pattern7_1,
+                    } = &pattern6_0
+                    {
+                        if let &Opcode::Isub = &pattern7_0 {
+                            let (pattern9_0, pattern9_1) =
+                                C::unpack_value_array_2(ctx, &pattern7_1);
+                            // Rule at src/isa/x64/lower.isle line 270.
+                            let expr0_0 = C::put_in_reg(ctx, pattern9_0);
+                            let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
+                            let expr2_0 = constructor_psubw(ctx, expr0_0, &expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
                         }
-                        &Opcode::Bxor => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/x64/lower.isle line 524.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0: usize = 0;
-                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
-                            let expr3_0: usize = 1;
-                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
-                            let expr5_0 = constructor_lo_reg(ctx, pattern7_1)?;
-                            let expr6_0: Type = I64;
-                            let expr7_0 = RegMemImm::Reg { reg: expr5_0 };
-                            let expr8_0 = constructor_xor(ctx, expr6_0, expr2_0, &expr7_0)?;
-                            let expr9_0 = C::value_regs(ctx, expr8_0, expr4_0);
-                            return Some(expr9_0);
+                    }
+                }
+            }
+            if pattern3_0 == 32 {
+                if pattern3_1 == 4 {
+                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
+                    if let &InstructionData::Binary {
+                        opcode: ref pattern7_0,
+                        args: ref pattern7_1,
+                    } = &pattern6_0
+                    {
+                        if let &Opcode::Isub = &pattern7_0 {
+                            let (pattern9_0, pattern9_1) =
+                                C::unpack_value_array_2(ctx, &pattern7_1);
+                            // Rule at src/isa/x64/lower.isle line 275.
+                            let expr0_0 = C::put_in_reg(ctx, pattern9_0);
+                            let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
+                            let expr2_0 = constructor_psubd(ctx, expr0_0, &expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
                         }
-                        _ => {}
                     }
                 }
-                &InstructionData::Unary {
-                    opcode: ref pattern5_0,
-                    arg: pattern5_1,
-                } => {
-                    if let &Opcode::Bnot = &pattern5_0 {
-                        // Rule at src/isa/x64/lower.isle line 1031.
-                        let expr0_0 = constructor_i128_not(ctx, pattern5_1)?;
-                        return Some(expr0_0);
+            }
+            if pattern3_0 == 64 {
+                if pattern3_1 == 2 {
+                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
+                    if let &InstructionData::Binary {
+                        opcode: ref pattern7_0,
+                        args: ref pattern7_1,
+                    } = &pattern6_0
+                    {
+                        if let &Opcode::Isub = &pattern7_0 {
+                            let (pattern9_0, pattern9_1) =
+                                C::unpack_value_array_2(ctx, &pattern7_1);
+                            // Rule at src/isa/x64/lower.isle line 280.
+                            let expr0_0 = C::put_in_reg(ctx, pattern9_0);
+                            let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
+                            let expr2_0 = constructor_psubq(ctx, expr0_0, &expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
                     }
                 }
-                _ => {}
             }
         }
-        if pattern2_0 == I128 {
+        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
             let pattern4_0 = C::inst_data(ctx, pattern0_0);
             match &pattern4_0 {
-                &InstructionData::UnaryImm {
-                    opcode: ref pattern5_0,
-                    imm: pattern5_1,
-                } => {
-                    if let &Opcode::Iconst = &pattern5_0 {
-                        let pattern7_0 = C::u64_from_imm64(ctx, pattern5_1);
-                        // Rule at src/isa/x64/lower.isle line 15.
-                        let expr0_0: Type = I64;
-                        let expr1_0 = constructor_imm(ctx, expr0_0, pattern7_0)?;
-                        let expr2_0: Type = I64;
-                        let expr3_0: u64 = 0;
-                        let expr4_0 = constructor_imm(ctx, expr2_0, expr3_0)?;
-                        let expr5_0 = C::value_regs(ctx, expr1_0, expr4_0);
-                        return Some(expr5_0);
-                    }
-                }
                 &InstructionData::Binary {
                     opcode: ref pattern5_0,
                     args: ref pattern5_1,
@@ -2250,159 +3703,145 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                         &Opcode::Iadd => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/x64/lower.isle line 117.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0: usize = 0;
-                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
-                            let expr3_0: usize = 1;
-                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
-                            let expr5_0 = C::put_in_regs(ctx, pattern7_1);
-                            let expr6_0: usize = 0;
-                            let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
-                            let expr8_0: usize = 1;
-                            let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
-                            let expr10_0: Type = I64;
-                            let expr11_0 = RegMemImm::Reg { reg: expr7_0 };
-                            let expr12_0 =
-                                constructor_add_with_flags(ctx, expr10_0, expr2_0, &expr11_0)?;
-                            let expr13_0: Type = I64;
-                            let expr14_0 = RegMemImm::Reg { reg: expr9_0 };
-                            let expr15_0 = constructor_adc(ctx, expr13_0, expr4_0, &expr14_0)?;
-                            let expr16_0 = constructor_with_flags(ctx, &expr12_0, &expr15_0)?;
-                            return Some(expr16_0);
+                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_0) {
+                                // Rule at src/isa/x64/lower.isle line 88.
+                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
+                                let expr2_0 = constructor_add(ctx, pattern3_0, expr0_0, &expr1_0)?;
+                                let expr3_0 = C::value_reg(ctx, expr2_0);
+                                return Some(expr3_0);
+                            }
                         }
-                        &Opcode::Isub => {
+                        &Opcode::Imul => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/x64/lower.isle line 286.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0: usize = 0;
-                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
-                            let expr3_0: usize = 1;
-                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
-                            let expr5_0 = C::put_in_regs(ctx, pattern7_1);
-                            let expr6_0: usize = 0;
-                            let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
-                            let expr8_0: usize = 1;
-                            let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
-                            let expr10_0: Type = I64;
-                            let expr11_0 = RegMemImm::Reg { reg: expr7_0 };
-                            let expr12_0 =
-                                constructor_sub_with_flags(ctx, expr10_0, expr2_0, &expr11_0)?;
-                            let expr13_0: Type = I64;
-                            let expr14_0 = RegMemImm::Reg { reg: expr9_0 };
-                            let expr15_0 = constructor_sbb(ctx, expr13_0, expr4_0, &expr14_0)?;
-                            let expr16_0 = constructor_with_flags(ctx, &expr12_0, &expr15_0)?;
-                            return Some(expr16_0);
+                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_0) {
+                                // Rule at src/isa/x64/lower.isle line 698.
+                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
+                                let expr2_0 = constructor_mul(ctx, pattern3_0, expr0_0, &expr1_0)?;
+                                let expr3_0 = C::value_reg(ctx, expr2_0);
+                                return Some(expr3_0);
+                            }
                         }
-                        &Opcode::Imul => {
+                        &Opcode::IaddIfcout => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/x64/lower.isle line 719.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0: usize = 0;
-                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
-                            let expr3_0: usize = 1;
-                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
-                            let expr5_0 = C::put_in_regs(ctx, pattern7_1);
-                            let expr6_0: usize = 0;
-                            let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
-                            let expr8_0: usize = 1;
-                            let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
-                            let expr10_0: Type = I64;
-                            let expr11_0 = RegMemImm::Reg { reg: expr9_0 };
-                            let expr12_0 = constructor_mul(ctx, expr10_0, expr2_0, &expr11_0)?;
-                            let expr13_0: Type = I64;
-                            let expr14_0 = RegMemImm::Reg { reg: expr7_0 };
-                            let expr15_0 = constructor_mul(ctx, expr13_0, expr4_0, &expr14_0)?;
-                            let expr16_0: Type = I64;
-                            let expr17_0 = RegMemImm::Reg { reg: expr15_0 };
-                            let expr18_0 = constructor_add(ctx, expr16_0, expr12_0, &expr17_0)?;
-                            let expr19_0: Type = I64;
-                            let expr20_0 = RegMem::Reg { reg: expr7_0 };
-                            let expr21_0 = constructor_mulhi_u(ctx, expr19_0, expr2_0, &expr20_0)?;
-                            let expr22_0: usize = 0;
-                            let expr23_0 = C::value_regs_get(ctx, expr21_0, expr22_0);
-                            let expr24_0: usize = 1;
-                            let expr25_0 = C::value_regs_get(ctx, expr21_0, expr24_0);
-                            let expr26_0: Type = I64;
-                            let expr27_0 = RegMemImm::Reg { reg: expr25_0 };
-                            let expr28_0 = constructor_add(ctx, expr26_0, expr18_0, &expr27_0)?;
-                            let expr29_0 = C::value_regs(ctx, expr23_0, expr28_0);
-                            return Some(expr29_0);
+                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_0) {
+                                // Rule at src/isa/x64/lower.isle line 199.
+                                let expr0_0: Type = I64;
+                                let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
+                                let expr2_0 = C::writable_reg_to_reg(ctx, expr1_0);
+                                let expr3_0 = C::put_in_reg(ctx, pattern7_1);
+                                let expr4_0 = C::sink_load(ctx, &pattern8_0);
+                                let expr5_0 = constructor_add(ctx, pattern3_0, expr3_0, &expr4_0)?;
+                                let expr6_0 = C::value_regs(ctx, expr5_0, expr2_0);
+                                return Some(expr6_0);
+                            }
                         }
                         &Opcode::Band => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/x64/lower.isle line 374.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0: usize = 0;
-                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
-                            let expr3_0: usize = 1;
-                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
-                            let expr5_0 = C::put_in_regs(ctx, pattern7_1);
-                            let expr6_0: usize = 0;
-                            let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
-                            let expr8_0: usize = 1;
-                            let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
-                            let expr10_0: Type = I64;
-                            let expr11_0 = RegMemImm::Reg { reg: expr7_0 };
-                            let expr12_0 = constructor_m_and(ctx, expr10_0, expr2_0, &expr11_0)?;
-                            let expr13_0: Type = I64;
-                            let expr14_0 = RegMemImm::Reg { reg: expr9_0 };
-                            let expr15_0 = constructor_m_and(ctx, expr13_0, expr4_0, &expr14_0)?;
-                            let expr16_0 = C::value_regs(ctx, expr12_0, expr15_0);
-                            return Some(expr16_0);
+                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_0) {
+                                // Rule at src/isa/x64/lower.isle line 341.
+                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
+                                let expr2_0 =
+                                    constructor_m_and(ctx, pattern3_0, expr0_0, &expr1_0)?;
+                                let expr3_0 = C::value_reg(ctx, expr2_0);
+                                return Some(expr3_0);
+                            }
                         }
                         &Opcode::Bor => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/x64/lower.isle line 455.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0 = C::put_in_regs(ctx, pattern7_1);
-                            let expr2_0 = constructor_or_i128(ctx, expr0_0, expr1_0)?;
-                            return Some(expr2_0);
+                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_0) {
+                                // Rule at src/isa/x64/lower.isle line 413.
+                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
+                                let expr2_0 = constructor_or(ctx, pattern3_0, expr0_0, &expr1_0)?;
+                                let expr3_0 = C::value_reg(ctx, expr2_0);
+                                return Some(expr3_0);
+                            }
                         }
                         &Opcode::Bxor => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/x64/lower.isle line 514.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0: usize = 0;
-                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
-                            let expr3_0: usize = 1;
-                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
-                            let expr5_0 = C::put_in_regs(ctx, pattern7_1);
-                            let expr6_0: usize = 0;
-                            let expr7_0 = C::value_regs_get(ctx, expr5_0, expr6_0);
-                            let expr8_0: usize = 1;
-                            let expr9_0 = C::value_regs_get(ctx, expr5_0, expr8_0);
-                            let expr10_0: Type = I64;
-                            let expr11_0 = RegMemImm::Reg { reg: expr7_0 };
-                            let expr12_0 = constructor_xor(ctx, expr10_0, expr2_0, &expr11_0)?;
-                            let expr13_0: Type = I64;
-                            let expr14_0 = RegMemImm::Reg { reg: expr9_0 };
-                            let expr15_0 = constructor_xor(ctx, expr13_0, expr4_0, &expr14_0)?;
-                            let expr16_0 = C::value_regs(ctx, expr12_0, expr15_0);
-                            return Some(expr16_0);
+                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_0) {
+                                // Rule at src/isa/x64/lower.isle line 487.
+                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
+                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
+                                let expr2_0 = constructor_xor(ctx, pattern3_0, expr0_0, &expr1_0)?;
+                                let expr3_0 = C::value_reg(ctx, expr2_0);
+                                return Some(expr3_0);
+                            }
                         }
-                        &Opcode::Rotl => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/x64/lower.isle line 652.
-                            let expr0_0 = C::put_in_regs(ctx, pattern7_0);
-                            let expr1_0 = constructor_lo_reg(ctx, pattern7_1)?;
-                            let expr2_0 = constructor_shl_i128(ctx, expr0_0, expr1_0)?;
-                            let expr3_0: Type = I64;
-                            let expr4_0: Type = I64;
-                            let expr5_0: u64 = 128;
-                            let expr6_0 = constructor_imm(ctx, expr4_0, expr5_0)?;
-                            let expr7_0 = RegMemImm::Reg { reg: expr1_0 };
-                            let expr8_0 = constructor_sub(ctx, expr3_0, expr6_0, &expr7_0)?;
-                            let expr9_0 = constructor_shr_i128(ctx, expr0_0, expr8_0)?;
-                            let expr10_0 = constructor_or_i128(ctx, expr2_0, expr9_0)?;
-                            return Some(expr10_0);
+                        _ => {}
+                    }
+                }
+                &InstructionData::BinaryImm64 {
+                    opcode: ref pattern5_0,
+                    arg: pattern5_1,
+                    imm: pattern5_2,
+                } => {
+                    if let &Opcode::IaddImm = &pattern5_0 {
+                        if let Some(pattern7_0) = C::simm32_from_imm64(ctx, pattern5_2) {
+                            // Rule at src/isa/x64/lower.isle line 214.
+                            let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                            let expr1_0 = constructor_add(ctx, pattern3_0, expr0_0, &pattern7_0)?;
+                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                            return Some(expr2_0);
                         }
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some(pattern3_0) = C::ty_32_or_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Rotl = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    if let Some(pattern8_0) = C::imm8_from_value(ctx, pattern7_1) {
+                        // Rule at src/isa/x64/lower.isle line 647.
+                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr1_0 = constructor_m_rotl(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                        let expr2_0 = C::value_reg(ctx, expr1_0);
+                        return Some(expr2_0);
+                    }
+                }
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if pattern2_0 == B128 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Unary {
+                opcode: ref pattern5_0,
+                arg: pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Bnot = &pattern5_0 {
+                    // Rule at src/isa/x64/lower.isle line 1031.
+                    let expr0_0 = constructor_i128_not(ctx, pattern5_1)?;
+                    return Some(expr0_0);
+                }
+            }
+        }
+        if pattern2_0 == I128 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            match &pattern4_0 {
+                &InstructionData::Binary {
+                    opcode: ref pattern5_0,
+                    args: ref pattern5_1,
+                } => {
+                    match &pattern5_0 {
                         &Opcode::Ishl => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
@@ -2434,6 +3873,194 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                         return Some(expr0_0);
                     }
                 }
+                &InstructionData::BinaryImm64 {
+                    opcode: ref pattern5_0,
+                    arg: pattern5_1,
+                    imm: pattern5_2,
+                } => {
+                    if let &Opcode::IaddImm = &pattern5_0 {
+                        if let Some(pattern7_0) = C::simm32_from_imm64(ctx, pattern5_2) {
+                            // Rule at src/isa/x64/lower.isle line 224.
+                            let expr0_0 = C::put_in_regs(ctx, pattern5_1);
+                            let expr1_0: usize = 0;
+                            let expr2_0 = C::value_regs_get(ctx, expr0_0, expr1_0);
+                            let expr3_0: usize = 1;
+                            let expr4_0 = C::value_regs_get(ctx, expr0_0, expr3_0);
+                            let expr5_0: Type = I64;
+                            let expr6_0 =
+                                constructor_add_with_flags(ctx, expr5_0, expr2_0, &pattern7_0)?;
+                            let expr7_0: Type = I64;
+                            let expr8_0: u32 = 0;
+                            let expr9_0 = RegMemImm::Imm { simm32: expr8_0 };
+                            let expr10_0 = constructor_adc(ctx, expr7_0, expr4_0, &expr9_0)?;
+                            let expr11_0 = constructor_with_flags(ctx, &expr6_0, &expr10_0)?;
+                            return Some(expr11_0);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Iadd => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 72.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = constructor_add(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                            return Some(expr2_0);
+                        }
+                    }
+                    &Opcode::Isub => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 252.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = constructor_sub(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                            return Some(expr2_0);
+                        }
+                    }
+                    &Opcode::Imul => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 682.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = constructor_mul(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                            return Some(expr2_0);
+                        }
+                    }
+                    &Opcode::IaddIfcout => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 177.
+                            let expr0_0: Type = I64;
+                            let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
+                            let expr2_0 = C::writable_reg_to_reg(ctx, expr1_0);
+                            let expr3_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr4_0 = constructor_add(ctx, pattern3_0, expr3_0, &pattern8_0)?;
+                            let expr5_0 = C::value_regs(ctx, expr4_0, expr2_0);
+                            return Some(expr5_0);
+                        }
+                    }
+                    &Opcode::Band => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 349.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = constructor_m_and(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                            return Some(expr2_0);
+                        }
+                    }
+                    &Opcode::Bor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 421.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = constructor_or(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                            return Some(expr2_0);
+                        }
+                    }
+                    &Opcode::Bxor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 495.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = constructor_xor(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                            return Some(expr2_0);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::ty_32_or_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Rotl = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    // Rule at src/isa/x64/lower.isle line 641.
+                    let expr0_0 = constructor_lo_reg(ctx, pattern7_1)?;
+                    let expr1_0 = C::put_in_reg(ctx, pattern7_0);
+                    let expr2_0 = Imm8Reg::Reg { reg: expr0_0 };
+                    let expr3_0 = constructor_m_rotl(ctx, pattern3_0, expr1_0, &expr2_0)?;
+                    let expr4_0 = C::value_reg(ctx, expr3_0);
+                    return Some(expr4_0);
+                }
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
+        if pattern2_0 == B128 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::UnaryBool {
+                opcode: ref pattern5_0,
+                imm: pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Bconst = &pattern5_0 {
+                    if pattern5_1 == true {
+                        // Rule at src/isa/x64/lower.isle line 39.
+                        let expr0_0: Type = B64;
+                        let expr1_0: u64 = 1;
+                        let expr2_0 = constructor_imm(ctx, expr0_0, expr1_0)?;
+                        let expr3_0: Type = B64;
+                        let expr4_0: u64 = 0;
+                        let expr5_0 = constructor_imm(ctx, expr3_0, expr4_0)?;
+                        let expr6_0 = C::value_regs(ctx, expr2_0, expr5_0);
+                        return Some(expr6_0);
+                    }
+                    if pattern5_1 == false {
+                        // Rule at src/isa/x64/lower.isle line 34.
+                        let expr0_0: Type = B64;
+                        let expr1_0: u64 = 0;
+                        let expr2_0 = constructor_imm(ctx, expr0_0, expr1_0)?;
+                        let expr3_0: Type = B64;
+                        let expr4_0: u64 = 0;
+                        let expr5_0 = constructor_imm(ctx, expr3_0, expr4_0)?;
+                        let expr6_0 = C::value_regs(ctx, expr2_0, expr5_0);
+                        return Some(expr6_0);
+                    }
+                }
+            }
+        }
+        if pattern2_0 == I128 {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            match &pattern4_0 {
+                &InstructionData::UnaryImm {
+                    opcode: ref pattern5_0,
+                    imm: pattern5_1,
+                } => {
+                    if let &Opcode::Iconst = &pattern5_0 {
+                        let pattern7_0 = C::u64_from_imm64(ctx, pattern5_1);
+                        // Rule at src/isa/x64/lower.isle line 15.
+                        let expr0_0: Type = I64;
+                        let expr1_0 = constructor_imm(ctx, expr0_0, pattern7_0)?;
+                        let expr2_0: Type = I64;
+                        let expr3_0: u64 = 0;
+                        let expr4_0 = constructor_imm(ctx, expr2_0, expr3_0)?;
+                        let expr5_0 = C::value_regs(ctx, expr1_0, expr4_0);
+                        return Some(expr5_0);
+                    }
+                }
                 &InstructionData::BinaryImm64 {
                     opcode: ref pattern5_0,
                     arg: pattern5_1,
@@ -2463,6 +4090,231 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                 _ => {}
             }
         }
+        if let Some(()) = C::avx512vl_enabled(ctx, pattern2_0) {
+            if let Some(()) = C::avx512f_enabled(ctx, pattern2_0) {
+                if pattern2_0 == I64X2 {
+                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
+                    if let &InstructionData::Unary {
+                        opcode: ref pattern7_0,
+                        arg: pattern7_1,
+                    } = &pattern6_0
+                    {
+                        if let &Opcode::Iabs = &pattern7_0 {
+                            // Rule at src/isa/x64/lower.isle line 984.
+                            let expr0_0 = C::put_in_reg_mem(ctx, pattern7_1);
+                            let expr1_0 = constructor_vpabsq(ctx, &expr0_0)?;
+                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                            return Some(expr2_0);
+                        }
+                    }
+                }
+            }
+        }
+        if let Some((pattern3_0, pattern3_1)) = C::multi_lane(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Unary {
+                opcode: ref pattern5_0,
+                arg: pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Bnot = &pattern5_0 {
+                    // Rule at src/isa/x64/lower.isle line 1036.
+                    let expr0_0 = C::put_in_reg(ctx, pattern5_1);
+                    let expr1_0 = constructor_vector_all_ones(ctx, pattern2_0)?;
+                    let expr2_0 = RegMem::Reg { reg: expr1_0 };
+                    let expr3_0 = constructor_sse_xor(ctx, pattern2_0, expr0_0, &expr2_0)?;
+                    let expr4_0 = C::value_reg(ctx, expr3_0);
+                    return Some(expr4_0);
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                match &pattern5_0 {
+                    &Opcode::Iadd => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 82.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = C::sink_load(ctx, &pattern8_0);
+                            let expr2_0 = constructor_add(ctx, pattern3_0, expr0_0, &expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                    }
+                    &Opcode::Isub => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 257.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = C::sink_load(ctx, &pattern8_0);
+                            let expr2_0 = constructor_sub(ctx, pattern3_0, expr0_0, &expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                    }
+                    &Opcode::Imul => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 692.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = C::sink_load(ctx, &pattern8_0);
+                            let expr2_0 = constructor_mul(ctx, pattern3_0, expr0_0, &expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                    }
+                    &Opcode::IaddIfcout => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 191.
+                            let expr0_0: Type = I64;
+                            let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
+                            let expr2_0 = C::writable_reg_to_reg(ctx, expr1_0);
+                            let expr3_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr4_0 = C::sink_load(ctx, &pattern8_0);
+                            let expr5_0 = constructor_add(ctx, pattern3_0, expr3_0, &expr4_0)?;
+                            let expr6_0 = C::value_regs(ctx, expr5_0, expr2_0);
+                            return Some(expr6_0);
+                        }
+                    }
+                    &Opcode::Band => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 335.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = C::sink_load(ctx, &pattern8_0);
+                            let expr2_0 = constructor_m_and(ctx, pattern3_0, expr0_0, &expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                    }
+                    &Opcode::Bor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 407.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = C::sink_load(ctx, &pattern8_0);
+                            let expr2_0 = constructor_or(ctx, pattern3_0, expr0_0, &expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                    }
+                    &Opcode::Bxor => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 481.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = C::sink_load(ctx, &pattern8_0);
+                            let expr2_0 = constructor_xor(ctx, pattern3_0, expr0_0, &expr1_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                    }
+                    &Opcode::Ishl => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::imm8_from_value(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 545.
+                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                            let expr1_0 = constructor_shl(ctx, pattern3_0, expr0_0, &pattern8_0)?;
+                            let expr2_0 = C::value_reg(ctx, expr1_0);
+                            return Some(expr2_0);
+                        }
+                    }
+                    &Opcode::Ushr => {
+                        let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                        if let Some(pattern8_0) = C::imm8_from_value(ctx, pattern7_1) {
+                            // Rule at src/isa/x64/lower.isle line 591.
+                            let expr0_0 = ExtendKind::Zero;
+                            let expr1_0 =
+                                constructor_extend_to_reg(ctx, pattern7_0, pattern3_0, &expr0_0)?;
+                            let expr2_0 = constructor_shr(ctx, pattern3_0, expr1_0, &pattern8_0)?;
+                            let expr3_0 = C::value_reg(ctx, expr2_0);
+                            return Some(expr3_0);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if let Some(pattern3_0) = C::ty_8_or_16(ctx, pattern2_0) {
+            let pattern4_0 = C::inst_data(ctx, pattern0_0);
+            if let &InstructionData::Binary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Rotl = &pattern5_0 {
+                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
+                    if let Some(pattern8_0) = C::imm8_from_value(ctx, pattern7_1) {
+                        // Rule at src/isa/x64/lower.isle line 635.
+                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                        let expr1_0 = C::ty_bits_mask(ctx, pattern3_0);
+                        let expr2_0 = C::mask_imm8_const(ctx, &pattern8_0, expr1_0);
+                        let expr3_0 = constructor_m_rotl(ctx, pattern3_0, expr0_0, &expr2_0)?;
+                        let expr4_0 = C::value_reg(ctx, expr3_0);
+                        return Some(expr4_0);
+                    }
+                }
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    let pattern1_0 = C::inst_data(ctx, pattern0_0);
+    match &pattern1_0 {
+        &InstructionData::UnaryIeee32 {
+            opcode: ref pattern2_0,
+            imm: pattern2_1,
+        } => {
+            if let &Opcode::F32const = &pattern2_0 {
+                let pattern4_0 = C::u64_from_ieee32(ctx, pattern2_1);
+                // Rule at src/isa/x64/lower.isle line 46.
+                let expr0_0: Type = F32;
+                let expr1_0 = constructor_imm(ctx, expr0_0, pattern4_0)?;
+                let expr2_0 = C::value_reg(ctx, expr1_0);
+                return Some(expr2_0);
+            }
+        }
+        &InstructionData::UnaryIeee64 {
+            opcode: ref pattern2_0,
+            imm: pattern2_1,
+        } => {
+            if let &Opcode::F64const = &pattern2_0 {
+                let pattern4_0 = C::u64_from_ieee64(ctx, pattern2_1);
+                // Rule at src/isa/x64/lower.isle line 51.
+                let expr0_0: Type = F64;
+                let expr1_0 = constructor_imm(ctx, expr0_0, pattern4_0)?;
+                let expr2_0 = C::value_reg(ctx, expr1_0);
+                return Some(expr2_0);
+            }
+        }
+        &InstructionData::TernaryImm8 {
+            opcode: ref pattern2_0,
+            args: ref pattern2_1,
+            imm: pattern2_2,
+        } => {
+            if let &Opcode::Insertlane = &pattern2_0 {
+                let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                let pattern5_0 = C::value_type(ctx, pattern4_0);
+                let pattern6_0 = C::u8_from_uimm8(ctx, pattern2_2);
+                // Rule at src/isa/x64/lower.isle line 1055.
+                let expr0_0 = C::put_in_reg(ctx, pattern4_0);
+                let expr1_0 = C::put_in_reg_mem(ctx, pattern4_1);
+                let expr2_0 =
+                    constructor_vec_insert_lane(ctx, pattern5_0, expr0_0, &expr1_0, pattern6_0)?;
+                let expr3_0 = C::value_reg(ctx, expr2_0);
+                return Some(expr3_0);
+            }
+        }
+        _ => {}
+    }
+    if let Some(pattern1_0) = C::first_result(ctx, pattern0_0) {
+        let pattern2_0 = C::value_type(ctx, pattern1_0);
         if pattern2_0 == I8X16 {
             let pattern4_0 = C::inst_data(ctx, pattern0_0);
             match &pattern4_0 {
@@ -2746,824 +4598,73 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
             } => {
                 if let &Opcode::BandNot = &pattern4_0 {
                     let (pattern6_0, pattern6_1) = C::unpack_value_array_2(ctx, &pattern4_1);
-                    // Rule at src/isa/x64/lower.isle line 967.
-                    let expr0_0 = C::put_in_reg(ctx, pattern6_1);
-                    let expr1_0 = C::put_in_reg_mem(ctx, pattern6_0);
-                    let expr2_0 = constructor_sse_and_not(ctx, pattern2_0, expr0_0, &expr1_0)?;
-                    let expr3_0 = C::value_reg(ctx, expr2_0);
-                    return Some(expr3_0);
-                }
-            }
-            _ => {}
-        }
-        if let Some(()) = C::avx512vl_enabled(ctx, pattern2_0) {
-            if let Some(()) = C::avx512dq_enabled(ctx, pattern2_0) {
-                if let Some((pattern5_0, pattern5_1)) = C::multi_lane(ctx, pattern2_0) {
-                    if pattern5_0 == 64 {
-                        if pattern5_1 == 2 {
-                            let pattern8_0 = C::inst_data(ctx, pattern0_0);
-                            if let &InstructionData::Binary {
-                                opcode: ref pattern9_0,
-                                args: ref pattern9_1,
-                            } = &pattern8_0
-                            {
-                                if let &Opcode::Imul = &pattern9_0 {
-                                    let (pattern11_0, pattern11_1) =
-                                        C::unpack_value_array_2(ctx, &pattern9_1);
-                                    // Rule at src/isa/x64/lower.isle line 754.
-                                    let expr0_0 = C::put_in_reg_mem(ctx, pattern11_0);
-                                    let expr1_0 = C::put_in_reg(ctx, pattern11_1);
-                                    let expr2_0 = constructor_vpmullq(ctx, &expr0_0, expr1_0)?;
-                                    let expr3_0 = C::value_reg(ctx, expr2_0);
-                                    return Some(expr3_0);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            if let Some(()) = C::avx512f_enabled(ctx, pattern2_0) {
-                if pattern2_0 == I64X2 {
-                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
-                    if let &InstructionData::Unary {
-                        opcode: ref pattern7_0,
-                        arg: pattern7_1,
-                    } = &pattern6_0
-                    {
-                        if let &Opcode::Iabs = &pattern7_0 {
-                            // Rule at src/isa/x64/lower.isle line 984.
-                            let expr0_0 = C::put_in_reg_mem(ctx, pattern7_1);
-                            let expr1_0 = constructor_vpabsq(ctx, &expr0_0)?;
-                            let expr2_0 = C::value_reg(ctx, expr1_0);
-                            return Some(expr2_0);
-                        }
-                    }
-                }
-            }
-        }
-        if let Some((pattern3_0, pattern3_1)) = C::multi_lane(ctx, pattern2_0) {
-            if pattern3_0 == 8 {
-                if pattern3_1 == 16 {
-                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
-                    if let &InstructionData::Binary {
-                        opcode: ref pattern7_0,
-                        args: ref pattern7_1,
-                    } = &pattern6_0
-                    {
-                        match &pattern7_0 {
-                            &Opcode::AvgRound => {
-                                let (pattern9_0, pattern9_1) =
-                                    C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 662.
-                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
-                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_pavgb(ctx, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            &Opcode::UaddSat => {
-                                let (pattern9_0, pattern9_1) =
-                                    C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 144.
-                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
-                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_paddusb(ctx, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            &Opcode::SaddSat => {
-                                let (pattern9_0, pattern9_1) =
-                                    C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 132.
-                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
-                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_paddsb(ctx, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            &Opcode::UsubSat => {
-                                let (pattern9_0, pattern9_1) =
-                                    C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 313.
-                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
-                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_psubusb(ctx, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            &Opcode::SsubSat => {
-                                let (pattern9_0, pattern9_1) =
-                                    C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 301.
-                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
-                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_psubsb(ctx, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            &Opcode::Iadd => {
-                                let (pattern9_0, pattern9_1) =
-                                    C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 96.
-                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
-                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_paddb(ctx, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            &Opcode::Isub => {
-                                let (pattern9_0, pattern9_1) =
-                                    C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 265.
-                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
-                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_psubb(ctx, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-            }
-            if pattern3_0 == 16 {
-                if pattern3_1 == 8 {
-                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
-                    if let &InstructionData::Binary {
-                        opcode: ref pattern7_0,
-                        args: ref pattern7_1,
-                    } = &pattern6_0
-                    {
-                        match &pattern7_0 {
-                            &Opcode::AvgRound => {
-                                let (pattern9_0, pattern9_1) =
-                                    C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 666.
-                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
-                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_pavgw(ctx, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            &Opcode::UaddSat => {
-                                let (pattern9_0, pattern9_1) =
-                                    C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 149.
-                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
-                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_paddusw(ctx, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            &Opcode::SaddSat => {
-                                let (pattern9_0, pattern9_1) =
-                                    C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 137.
-                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
-                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_paddsw(ctx, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            &Opcode::UsubSat => {
-                                let (pattern9_0, pattern9_1) =
-                                    C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 318.
-                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
-                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_psubusw(ctx, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            &Opcode::SsubSat => {
-                                let (pattern9_0, pattern9_1) =
-                                    C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 306.
-                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
-                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_psubsw(ctx, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            &Opcode::Iadd => {
-                                let (pattern9_0, pattern9_1) =
-                                    C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 101.
-                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
-                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_paddw(ctx, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            &Opcode::Isub => {
+                    // Rule at src/isa/x64/lower.isle line 967.
+                    let expr0_0 = C::put_in_reg(ctx, pattern6_1);
+                    let expr1_0 = C::put_in_reg_mem(ctx, pattern6_0);
+                    let expr2_0 = constructor_sse_and_not(ctx, pattern2_0, expr0_0, &expr1_0)?;
+                    let expr3_0 = C::value_reg(ctx, expr2_0);
+                    return Some(expr3_0);
+                }
+            }
+            _ => {}
+        }
+        if let Some((pattern3_0, pattern3_1)) = C::multi_lane(ctx, pattern2_0) {
+            if pattern3_0 == 8 {
+                if pattern3_1 == 16 {
+                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
+                    if let &InstructionData::Binary {
+                        opcode: ref pattern7_0,
+                        args: ref pattern7_1,
+                    } = &pattern6_0
+                    {
+                        match &pattern7_0 {
+                            &Opcode::AvgRound => {
                                 let (pattern9_0, pattern9_1) =
                                     C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 270.
+                                // Rule at src/isa/x64/lower.isle line 662.
                                 let expr0_0 = C::put_in_reg(ctx, pattern9_0);
                                 let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_psubw(ctx, expr0_0, &expr1_0)?;
+                                let expr2_0 = constructor_pavgb(ctx, expr0_0, &expr1_0)?;
                                 let expr3_0 = C::value_reg(ctx, expr2_0);
                                 return Some(expr3_0);
                             }
-                            &Opcode::Imul => {
+                            &Opcode::UaddSat => {
                                 let (pattern9_0, pattern9_1) =
                                     C::unpack_value_array_2(ctx, &pattern7_1);
-                                if let Some(pattern10_0) = C::def_inst(ctx, pattern9_0) {
-                                    let pattern11_0 = C::inst_data(ctx, pattern10_0);
-                                    if let &InstructionData::Unary {
-                                        opcode: ref pattern12_0,
-                                        arg: pattern12_1,
-                                    } = &pattern11_0
-                                    {
-                                        match &pattern12_0 {
-                                            &Opcode::SwidenLow => {
-                                                let pattern14_0 = C::value_type(ctx, pattern12_1);
-                                                if let Some((pattern15_0, pattern15_1)) =
-                                                    C::multi_lane(ctx, pattern14_0)
-                                                {
-                                                    if pattern15_0 == 8 {
-                                                        if pattern15_1 == 16 {
-                                                            if let Some(pattern18_0) =
-                                                                C::def_inst(ctx, pattern9_1)
-                                                            {
-                                                                let pattern19_0 =
-                                                                    C::inst_data(ctx, pattern18_0);
-                                                                if let &InstructionData::Unary {
-                                                                    opcode: ref pattern20_0,
-                                                                    arg: pattern20_1,
-                                                                } = &pattern19_0
-                                                                {
-                                                                    if let &Opcode::SwidenLow =
-                                                                        &pattern20_0
-                                                                    {
-                                                                        let pattern22_0 =
-                                                                            C::value_type(
-                                                                                ctx,
-                                                                                pattern20_1,
-                                                                            );
-                                                                        if let Some((
-                                                                            pattern23_0,
-                                                                            pattern23_1,
-                                                                        )) = C::multi_lane(
-                                                                            ctx,
-                                                                            pattern22_0,
-                                                                        ) {
-                                                                            if pattern23_0 == 8 {
-                                                                                if pattern23_1 == 16
-                                                                                {
-                                                                                    // Rule at src/isa/x64/lower.isle line 842.
-                                                                                    let expr0_0 = C::put_in_reg_mem(ctx, pattern12_1);
-                                                                                    let expr1_0 = constructor_pmovsxbw(ctx, &expr0_0)?;
-                                                                                    let expr2_0 = C::put_in_reg_mem(ctx, pattern20_1);
-                                                                                    let expr3_0 = constructor_pmovsxbw(ctx, &expr2_0)?;
-                                                                                    let expr4_0 = RegMem::Reg {
-                                                                                        reg: expr3_0,
-                                                                                    };
-                                                                                    let expr5_0 = constructor_pmullw(ctx, expr1_0, &expr4_0)?;
-                                                                                    let expr6_0 = C::value_reg(ctx, expr5_0);
-                                                                                    return Some(
-                                                                                        expr6_0,
-                                                                                    );
-                                                                                }
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            &Opcode::SwidenHigh => {
-                                                let pattern14_0 = C::value_type(ctx, pattern12_1);
-                                                if let Some((pattern15_0, pattern15_1)) =
-                                                    C::multi_lane(ctx, pattern14_0)
-                                                {
-                                                    if pattern15_0 == 8 {
-                                                        if pattern15_1 == 16 {
-                                                            if let Some(pattern18_0) =
-                                                                C::def_inst(ctx, pattern9_1)
-                                                            {
-                                                                let pattern19_0 =
-                                                                    C::inst_data(ctx, pattern18_0);
-                                                                if let &InstructionData::Unary {
-                                                                    opcode: ref pattern20_0,
-                                                                    arg: pattern20_1,
-                                                                } = &pattern19_0
-                                                                {
-                                                                    if let &Opcode::SwidenHigh =
-                                                                        &pattern20_0
-                                                                    {
-                                                                        let pattern22_0 =
-                                                                            C::value_type(
-                                                                                ctx,
-                                                                                pattern20_1,
-                                                                            );
-                                                                        if let Some((
-                                                                            pattern23_0,
-                                                                            pattern23_1,
-                                                                        )) = C::multi_lane(
-                                                                            ctx,
-                                                                            pattern22_0,
-                                                                        ) {
-                                                                            if pattern23_0 == 8 {
-                                                                                if pattern23_1 == 16
-                                                                                {
-                                                                                    // Rule at src/isa/x64/lower.isle line 802.
-                                                                                    let expr0_0 = C::put_in_reg(ctx, pattern12_1);
-                                                                                    let expr1_0 = RegMem::Reg {
-                                                                                        reg: expr0_0,
-                                                                                    };
-                                                                                    let expr2_0: u8 = 8;
-                                                                                    let expr3_0 = OperandSize::Size32;
-                                                                                    let expr4_0 = constructor_palignr(ctx, expr0_0, &expr1_0, expr2_0, &expr3_0)?;
-                                                                                    let expr5_0 = RegMem::Reg {
-                                                                                        reg: expr4_0,
-                                                                                    };
-                                                                                    let expr6_0 = constructor_pmovsxbw(ctx, &expr5_0)?;
-                                                                                    let expr7_0 = C::put_in_reg(ctx, pattern20_1);
-                                                                                    let expr8_0 = RegMem::Reg {
-                                                                                        reg: expr7_0,
-                                                                                    };
-                                                                                    let expr9_0: u8 = 8;
-                                                                                    let expr10_0 = OperandSize::Size32;
-                                                                                    let expr11_0 = constructor_palignr(ctx, expr7_0, &expr8_0, expr9_0, &expr10_0)?;
-                                                                                    let expr12_0 = RegMem::Reg {
-                                                                                        reg: expr11_0,
-                                                                                    };
-                                                                                    let expr13_0 = constructor_pmovsxbw(ctx, &expr12_0)?;
-                                                                                    let expr14_0 = RegMem::Reg {
-                                                                                        reg: expr13_0,
-                                                                                    };
-                                                                                    let expr15_0 = constructor_pmullw(ctx, expr6_0, &expr14_0)?;
-                                                                                    let expr16_0 = C::value_reg(ctx, expr15_0);
-                                                                                    return Some(
-                                                                                        expr16_0,
-                                                                                    );
-                                                                                }
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            &Opcode::UwidenLow => {
-                                                let pattern14_0 = C::value_type(ctx, pattern12_1);
-                                                if let Some((pattern15_0, pattern15_1)) =
-                                                    C::multi_lane(ctx, pattern14_0)
-                                                {
-                                                    if pattern15_0 == 8 {
-                                                        if pattern15_1 == 16 {
-                                                            if let Some(pattern18_0) =
-                                                                C::def_inst(ctx, pattern9_1)
-                                                            {
-                                                                let pattern19_0 =
-                                                                    C::inst_data(ctx, pattern18_0);
-                                                                if let &InstructionData::Unary {
-                                                                    opcode: ref pattern20_0,
-                                                                    arg: pattern20_1,
-                                                                } = &pattern19_0
-                                                                {
-                                                                    if let &Opcode::UwidenLow =
-                                                                        &pattern20_0
-                                                                    {
-                                                                        let pattern22_0 =
-                                                                            C::value_type(
-                                                                                ctx,
-                                                                                pattern20_1,
-                                                                            );
-                                                                        if let Some((
-                                                                            pattern23_0,
-                                                                            pattern23_1,
-                                                                        )) = C::multi_lane(
-                                                                            ctx,
-                                                                            pattern22_0,
-                                                                        ) {
-                                                                            if pattern23_0 == 8 {
-                                                                                if pattern23_1 == 16
-                                                                                {
-                                                                                    // Rule at src/isa/x64/lower.isle line 918.
-                                                                                    let expr0_0 = C::put_in_reg_mem(ctx, pattern12_1);
-                                                                                    let expr1_0 = constructor_pmovzxbw(ctx, &expr0_0)?;
-                                                                                    let expr2_0 = C::put_in_reg_mem(ctx, pattern20_1);
-                                                                                    let expr3_0 = constructor_pmovzxbw(ctx, &expr2_0)?;
-                                                                                    let expr4_0 = RegMem::Reg {
-                                                                                        reg: expr3_0,
-                                                                                    };
-                                                                                    let expr5_0 = constructor_pmullw(ctx, expr1_0, &expr4_0)?;
-                                                                                    let expr6_0 = C::value_reg(ctx, expr5_0);
-                                                                                    return Some(
-                                                                                        expr6_0,
-                                                                                    );
-                                                                                }
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            &Opcode::UwidenHigh => {
-                                                let pattern14_0 = C::value_type(ctx, pattern12_1);
-                                                if let Some((pattern15_0, pattern15_1)) =
-                                                    C::multi_lane(ctx, pattern14_0)
-                                                {
-                                                    if pattern15_0 == 8 {
-                                                        if pattern15_1 == 16 {
-                                                            if let Some(pattern18_0) =
-                                                                C::def_inst(ctx, pattern9_1)
-                                                            {
-                                                                let pattern19_0 =
-                                                                    C::inst_data(ctx, pattern18_0);
-                                                                if let &InstructionData::Unary {
-                                                                    opcode: ref pattern20_0,
-                                                                    arg: pattern20_1,
-                                                                } = &pattern19_0
-                                                                {
-                                                                    if let &Opcode::UwidenHigh =
-                                                                        &pattern20_0
-                                                                    {
-                                                                        let pattern22_0 =
-                                                                            C::value_type(
-                                                                                ctx,
-                                                                                pattern20_1,
-                                                                            );
-                                                                        if let Some((
-                                                                            pattern23_0,
-                                                                            pattern23_1,
-                                                                        )) = C::multi_lane(
-                                                                            ctx,
-                                                                            pattern22_0,
-                                                                        ) {
-                                                                            if pattern23_0 == 8 {
-                                                                                if pattern23_1 == 16
-                                                                                {
-                                                                                    // Rule at src/isa/x64/lower.isle line 878.
-                                                                                    let expr0_0 = C::put_in_reg(ctx, pattern12_1);
-                                                                                    let expr1_0 = RegMem::Reg {
-                                                                                        reg: expr0_0,
-                                                                                    };
-                                                                                    let expr2_0: u8 = 8;
-                                                                                    let expr3_0 = OperandSize::Size32;
-                                                                                    let expr4_0 = constructor_palignr(ctx, expr0_0, &expr1_0, expr2_0, &expr3_0)?;
-                                                                                    let expr5_0 = RegMem::Reg {
-                                                                                        reg: expr4_0,
-                                                                                    };
-                                                                                    let expr6_0 = constructor_pmovzxbw(ctx, &expr5_0)?;
-                                                                                    let expr7_0 = C::put_in_reg(ctx, pattern20_1);
-                                                                                    let expr8_0 = RegMem::Reg {
-                                                                                        reg: expr7_0,
-                                                                                    };
-                                                                                    let expr9_0: u8 = 8;
-                                                                                    let expr10_0 = OperandSize::Size32;
-                                                                                    let expr11_0 = constructor_palignr(ctx, expr7_0, &expr8_0, expr9_0, &expr10_0)?;
-                                                                                    let expr12_0 = RegMem::Reg {
-                                                                                        reg: expr11_0,
-                                                                                    };
-                                                                                    let expr13_0 = constructor_pmovzxbw(ctx, &expr12_0)?;
-                                                                                    let expr14_0 = RegMem::Reg {
-                                                                                        reg: expr13_0,
-                                                                                    };
-                                                                                    let expr15_0 = constructor_pmullw(ctx, expr6_0, &expr14_0)?;
-                                                                                    let expr16_0 = C::value_reg(ctx, expr15_0);
-                                                                                    return Some(
-                                                                                        expr16_0,
-                                                                                    );
-                                                                                }
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            _ => {}
-                                        }
-                                    }
-                                }
-                                // Rule at src/isa/x64/lower.isle line 746.
+                                // Rule at src/isa/x64/lower.isle line 144.
                                 let expr0_0 = C::put_in_reg(ctx, pattern9_0);
                                 let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_pmullw(ctx, expr0_0, &expr1_0)?;
+                                let expr2_0 = constructor_paddusb(ctx, expr0_0, &expr1_0)?;
                                 let expr3_0 = C::value_reg(ctx, expr2_0);
                                 return Some(expr3_0);
                             }
-                            _ => {}
-                        }
-                    }
-                }
-            }
-            if pattern3_0 == 32 {
-                if pattern3_1 == 4 {
-                    let pattern6_0 = C::inst_data(ctx, pattern0_0);
-                    if let &InstructionData::Binary {
-                        opcode: ref pattern7_0,
-                        args: ref pattern7_1,
-                    } = &pattern6_0
-                    {
-                        match &pattern7_0 {
-                            &Opcode::Iadd => {
+                            &Opcode::SaddSat => {
                                 let (pattern9_0, pattern9_1) =
                                     C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 106.
+                                // Rule at src/isa/x64/lower.isle line 132.
                                 let expr0_0 = C::put_in_reg(ctx, pattern9_0);
                                 let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_paddd(ctx, expr0_0, &expr1_0)?;
+                                let expr2_0 = constructor_paddsb(ctx, expr0_0, &expr1_0)?;
                                 let expr3_0 = C::value_reg(ctx, expr2_0);
                                 return Some(expr3_0);
                             }
-                            &Opcode::Isub => {
+                            &Opcode::UsubSat => {
                                 let (pattern9_0, pattern9_1) =
                                     C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 275.
+                                // Rule at src/isa/x64/lower.isle line 313.
                                 let expr0_0 = C::put_in_reg(ctx, pattern9_0);
                                 let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_psubd(ctx, expr0_0, &expr1_0)?;
+                                let expr2_0 = constructor_psubusb(ctx, expr0_0, &expr1_0)?;
                                 let expr3_0 = C::value_reg(ctx, expr2_0);
                                 return Some(expr3_0);
                             }
-                            &Opcode::Imul => {
+                            &Opcode::SsubSat => {
                                 let (pattern9_0, pattern9_1) =
                                     C::unpack_value_array_2(ctx, &pattern7_1);
-                                if let Some(pattern10_0) = C::def_inst(ctx, pattern9_0) {
-                                    let pattern11_0 = C::inst_data(ctx, pattern10_0);
-                                    if let &InstructionData::Unary {
-                                        opcode: ref pattern12_0,
-                                        arg: pattern12_1,
-                                    } = &pattern11_0
-                                    {
-                                        match &pattern12_0 {
-                                            &Opcode::SwidenLow => {
-                                                let pattern14_0 = C::value_type(ctx, pattern12_1);
-                                                if let Some((pattern15_0, pattern15_1)) =
-                                                    C::multi_lane(ctx, pattern14_0)
-                                                {
-                                                    if pattern15_0 == 16 {
-                                                        if pattern15_1 == 8 {
-                                                            if let Some(pattern18_0) =
-                                                                C::def_inst(ctx, pattern9_1)
-                                                            {
-                                                                let pattern19_0 =
-                                                                    C::inst_data(ctx, pattern18_0);
-                                                                if let &InstructionData::Unary {
-                                                                    opcode: ref pattern20_0,
-                                                                    arg: pattern20_1,
-                                                                } = &pattern19_0
-                                                                {
-                                                                    if let &Opcode::SwidenLow =
-                                                                        &pattern20_0
-                                                                    {
-                                                                        let pattern22_0 =
-                                                                            C::value_type(
-                                                                                ctx,
-                                                                                pattern20_1,
-                                                                            );
-                                                                        if let Some((
-                                                                            pattern23_0,
-                                                                            pattern23_1,
-                                                                        )) = C::multi_lane(
-                                                                            ctx,
-                                                                            pattern22_0,
-                                                                        ) {
-                                                                            if pattern23_0 == 16 {
-                                                                                if pattern23_1 == 8
-                                                                                {
-                                                                                    // Rule at src/isa/x64/lower.isle line 852.
-                                                                                    let expr0_0 = C::put_in_reg(ctx, pattern12_1);
-                                                                                    let expr1_0 = C::put_in_reg(ctx, pattern20_1);
-                                                                                    let expr2_0 = RegMem::Reg {
-                                                                                        reg: expr1_0,
-                                                                                    };
-                                                                                    let expr3_0 = constructor_pmullw(ctx, expr0_0, &expr2_0)?;
-                                                                                    let expr4_0 = RegMem::Reg {
-                                                                                        reg: expr1_0,
-                                                                                    };
-                                                                                    let expr5_0 = constructor_pmulhw(ctx, expr0_0, &expr4_0)?;
-                                                                                    let expr6_0 = RegMem::Reg {
-                                                                                        reg: expr5_0,
-                                                                                    };
-                                                                                    let expr7_0 = constructor_punpcklwd(ctx, expr3_0, &expr6_0)?;
-                                                                                    let expr8_0 = C::value_reg(ctx, expr7_0);
-                                                                                    return Some(
-                                                                                        expr8_0,
-                                                                                    );
-                                                                                }
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            &Opcode::SwidenHigh => {
-                                                let pattern14_0 = C::value_type(ctx, pattern12_1);
-                                                if let Some((pattern15_0, pattern15_1)) =
-                                                    C::multi_lane(ctx, pattern14_0)
-                                                {
-                                                    if pattern15_0 == 16 {
-                                                        if pattern15_1 == 8 {
-                                                            if let Some(pattern18_0) =
-                                                                C::def_inst(ctx, pattern9_1)
-                                                            {
-                                                                let pattern19_0 =
-                                                                    C::inst_data(ctx, pattern18_0);
-                                                                if let &InstructionData::Unary {
-                                                                    opcode: ref pattern20_0,
-                                                                    arg: pattern20_1,
-                                                                } = &pattern19_0
-                                                                {
-                                                                    if let &Opcode::SwidenHigh =
-                                                                        &pattern20_0
-                                                                    {
-                                                                        let pattern22_0 =
-                                                                            C::value_type(
-                                                                                ctx,
-                                                                                pattern20_1,
-                                                                            );
-                                                                        if let Some((
-                                                                            pattern23_0,
-                                                                            pattern23_1,
-                                                                        )) = C::multi_lane(
-                                                                            ctx,
-                                                                            pattern22_0,
-                                                                        ) {
-                                                                            if pattern23_0 == 16 {
-                                                                                if pattern23_1 == 8
-                                                                                {
-                                                                                    // Rule at src/isa/x64/lower.isle line 816.
-                                                                                    let expr0_0 = C::put_in_reg(ctx, pattern12_1);
-                                                                                    let expr1_0 = C::put_in_reg(ctx, pattern20_1);
-                                                                                    let expr2_0 = RegMem::Reg {
-                                                                                        reg: expr1_0,
-                                                                                    };
-                                                                                    let expr3_0 = constructor_pmullw(ctx, expr0_0, &expr2_0)?;
-                                                                                    let expr4_0 = RegMem::Reg {
-                                                                                        reg: expr1_0,
-                                                                                    };
-                                                                                    let expr5_0 = constructor_pmulhw(ctx, expr0_0, &expr4_0)?;
-                                                                                    let expr6_0 = RegMem::Reg {
-                                                                                        reg: expr5_0,
-                                                                                    };
-                                                                                    let expr7_0 = constructor_punpckhwd(ctx, expr3_0, &expr6_0)?;
-                                                                                    let expr8_0 = C::value_reg(ctx, expr7_0);
-                                                                                    return Some(
-                                                                                        expr8_0,
-                                                                                    );
-                                                                                }
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            &Opcode::UwidenLow => {
-                                                let pattern14_0 = C::value_type(ctx, pattern12_1);
-                                                if let Some((pattern15_0, pattern15_1)) =
-                                                    C::multi_lane(ctx, pattern14_0)
-                                                {
-                                                    if pattern15_0 == 16 {
-                                                        if pattern15_1 == 8 {
-                                                            if let Some(pattern18_0) =
-                                                                C::def_inst(ctx, pattern9_1)
-                                                            {
-                                                                let pattern19_0 =
-                                                                    C::inst_data(ctx, pattern18_0);
-                                                                if let &InstructionData::Unary {
-                                                                    opcode: ref pattern20_0,
-                                                                    arg: pattern20_1,
-                                                                } = &pattern19_0
-                                                                {
-                                                                    if let &Opcode::UwidenLow =
-                                                                        &pattern20_0
-                                                                    {
-                                                                        let pattern22_0 =
-                                                                            C::value_type(
-                                                                                ctx,
-                                                                                pattern20_1,
-                                                                            );
-                                                                        if let Some((
-                                                                            pattern23_0,
-                                                                            pattern23_1,
-                                                                        )) = C::multi_lane(
-                                                                            ctx,
-                                                                            pattern22_0,
-                                                                        ) {
-                                                                            if pattern23_0 == 16 {
-                                                                                if pattern23_1 == 8
-                                                                                {
-                                                                                    // Rule at src/isa/x64/lower.isle line 928.
-                                                                                    let expr0_0 = C::put_in_reg(ctx, pattern12_1);
-                                                                                    let expr1_0 = C::put_in_reg(ctx, pattern20_1);
-                                                                                    let expr2_0 = RegMem::Reg {
-                                                                                        reg: expr1_0,
-                                                                                    };
-                                                                                    let expr3_0 = constructor_pmullw(ctx, expr0_0, &expr2_0)?;
-                                                                                    let expr4_0 = RegMem::Reg {
-                                                                                        reg: expr1_0,
-                                                                                    };
-                                                                                    let expr5_0 = constructor_pmulhuw(ctx, expr0_0, &expr4_0)?;
-                                                                                    let expr6_0 = RegMem::Reg {
-                                                                                        reg: expr5_0,
-                                                                                    };
-                                                                                    let expr7_0 = constructor_punpcklwd(ctx, expr3_0, &expr6_0)?;
-                                                                                    let expr8_0 = C::value_reg(ctx, expr7_0);
-                                                                                    return Some(
-                                                                                        expr8_0,
-                                                                                    );
-                                                                                }
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            &Opcode::UwidenHigh => {
-                                                let pattern14_0 = C::value_type(ctx, pattern12_1);
-                                                if let Some((pattern15_0, pattern15_1)) =
-                                                    C::multi_lane(ctx, pattern14_0)
-                                                {
-                                                    if pattern15_0 == 16 {
-                                                        if pattern15_1 == 8 {
-                                                            if let Some(pattern18_0) =
-                                                                C::def_inst(ctx, pattern9_1)
-                                                            {
-                                                                let pattern19_0 =
-                                                                    C::inst_data(ctx, pattern18_0);
-                                                                if let &InstructionData::Unary {
-                                                                    opcode: ref pattern20_0,
-                                                                    arg: pattern20_1,
-                                                                } = &pattern19_0
-                                                                {
-                                                                    if let &Opcode::UwidenHigh =
-                                                                        &pattern20_0
-                                                                    {
-                                                                        let pattern22_0 =
-                                                                            C::value_type(
-                                                                                ctx,
-                                                                                pattern20_1,
-                                                                            );
-                                                                        if let Some((
-                                                                            pattern23_0,
-                                                                            pattern23_1,
-                                                                        )) = C::multi_lane(
-                                                                            ctx,
-                                                                            pattern22_0,
-                                                                        ) {
-                                                                            if pattern23_0 == 16 {
-                                                                                if pattern23_1 == 8
-                                                                                {
-                                                                                    // Rule at src/isa/x64/lower.isle line 892.
-                                                                                    let expr0_0 = C::put_in_reg(ctx, pattern12_1);
-                                                                                    let expr1_0 = C::put_in_reg(ctx, pattern20_1);
-                                                                                    let expr2_0 = RegMem::Reg {
-                                                                                        reg: expr1_0,
-                                                                                    };
-                                                                                    let expr3_0 = constructor_pmullw(ctx, expr0_0, &expr2_0)?;
-                                                                                    let expr4_0 = RegMem::Reg {
-                                                                                        reg: expr1_0,
-                                                                                    };
-                                                                                    let expr5_0 = constructor_pmulhuw(ctx, expr0_0, &expr4_0)?;
-                                                                                    let expr6_0 = RegMem::Reg {
-                                                                                        reg: expr5_0,
-                                                                                    };
-                                                                                    let expr7_0 = constructor_punpckhwd(ctx, expr3_0, &expr6_0)?;
-                                                                                    let expr8_0 = C::value_reg(ctx, expr7_0);
-                                                                                    return Some(
-                                                                                        expr8_0,
-                                                                                    );
-                                                                                }
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            _ => {}
-                                        }
-                                    }
-                                }
-                                // Rule at src/isa/x64/lower.isle line 749.
+                                // Rule at src/isa/x64/lower.isle line 301.
                                 let expr0_0 = C::put_in_reg(ctx, pattern9_0);
                                 let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_pmulld(ctx, expr0_0, &expr1_0)?;
+                                let expr2_0 = constructor_psubsb(ctx, expr0_0, &expr1_0)?;
                                 let expr3_0 = C::value_reg(ctx, expr2_0);
                                 return Some(expr3_0);
                             }
@@ -3572,8 +4673,8 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                     }
                 }
             }
-            if pattern3_0 == 64 {
-                if pattern3_1 == 2 {
+            if pattern3_0 == 16 {
+                if pattern3_1 == 8 {
                     let pattern6_0 = C::inst_data(ctx, pattern0_0);
                     if let &InstructionData::Binary {
                         opcode: ref pattern7_0,
@@ -3581,313 +4682,55 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                     } = &pattern6_0
                     {
                         match &pattern7_0 {
-                            &Opcode::Iadd => {
+                            &Opcode::AvgRound => {
                                 let (pattern9_0, pattern9_1) =
                                     C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 111.
+                                // Rule at src/isa/x64/lower.isle line 666.
                                 let expr0_0 = C::put_in_reg(ctx, pattern9_0);
                                 let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_paddq(ctx, expr0_0, &expr1_0)?;
+                                let expr2_0 = constructor_pavgw(ctx, expr0_0, &expr1_0)?;
                                 let expr3_0 = C::value_reg(ctx, expr2_0);
                                 return Some(expr3_0);
                             }
-                            &Opcode::Isub => {
+                            &Opcode::UaddSat => {
                                 let (pattern9_0, pattern9_1) =
                                     C::unpack_value_array_2(ctx, &pattern7_1);
-                                // Rule at src/isa/x64/lower.isle line 280.
+                                // Rule at src/isa/x64/lower.isle line 149.
                                 let expr0_0 = C::put_in_reg(ctx, pattern9_0);
                                 let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
-                                let expr2_0 = constructor_psubq(ctx, expr0_0, &expr1_0)?;
+                                let expr2_0 = constructor_paddusw(ctx, expr0_0, &expr1_0)?;
                                 let expr3_0 = C::value_reg(ctx, expr2_0);
                                 return Some(expr3_0);
                             }
-                            &Opcode::Imul => {
+                            &Opcode::SaddSat => {
                                 let (pattern9_0, pattern9_1) =
                                     C::unpack_value_array_2(ctx, &pattern7_1);
-                                if let Some(pattern10_0) = C::def_inst(ctx, pattern9_0) {
-                                    let pattern11_0 = C::inst_data(ctx, pattern10_0);
-                                    if let &InstructionData::Unary {
-                                        opcode: ref pattern12_0,
-                                        arg: pattern12_1,
-                                    } = &pattern11_0
-                                    {
-                                        match &pattern12_0 {
-                                            &Opcode::SwidenLow => {
-                                                let pattern14_0 = C::value_type(ctx, pattern12_1);
-                                                if let Some((pattern15_0, pattern15_1)) =
-                                                    C::multi_lane(ctx, pattern14_0)
-                                                {
-                                                    if pattern15_0 == 32 {
-                                                        if pattern15_1 == 4 {
-                                                            if let Some(pattern18_0) =
-                                                                C::def_inst(ctx, pattern9_1)
-                                                            {
-                                                                let pattern19_0 =
-                                                                    C::inst_data(ctx, pattern18_0);
-                                                                if let &InstructionData::Unary {
-                                                                    opcode: ref pattern20_0,
-                                                                    arg: pattern20_1,
-                                                                } = &pattern19_0
-                                                                {
-                                                                    if let &Opcode::SwidenLow =
-                                                                        &pattern20_0
-                                                                    {
-                                                                        let pattern22_0 =
-                                                                            C::value_type(
-                                                                                ctx,
-                                                                                pattern20_1,
-                                                                            );
-                                                                        if let Some((
-                                                                            pattern23_0,
-                                                                            pattern23_1,
-                                                                        )) = C::multi_lane(
-                                                                            ctx,
-                                                                            pattern22_0,
-                                                                        ) {
-                                                                            if pattern23_0 == 32 {
-                                                                                if pattern23_1 == 4
-                                                                                {
-                                                                                    // Rule at src/isa/x64/lower.isle line 864.
-                                                                                    let expr0_0 = C::put_in_reg_mem(ctx, pattern12_1);
-                                                                                    let expr1_0: u8 = 80;
-                                                                                    let expr2_0 = OperandSize::Size32;
-                                                                                    let expr3_0 = constructor_pshufd(ctx, &expr0_0, expr1_0, &expr2_0)?;
-                                                                                    let expr4_0 = C::put_in_reg_mem(ctx, pattern20_1);
-                                                                                    let expr5_0: u8 = 80;
-                                                                                    let expr6_0 = OperandSize::Size32;
-                                                                                    let expr7_0 = constructor_pshufd(ctx, &expr4_0, expr5_0, &expr6_0)?;
-                                                                                    let expr8_0 = RegMem::Reg {
-                                                                                        reg: expr7_0,
-                                                                                    };
-                                                                                    let expr9_0 = constructor_pmuldq(ctx, expr3_0, &expr8_0)?;
-                                                                                    let expr10_0 = C::value_reg(ctx, expr9_0);
-                                                                                    return Some(
-                                                                                        expr10_0,
-                                                                                    );
-                                                                                }
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            &Opcode::SwidenHigh => {
-                                                let pattern14_0 = C::value_type(ctx, pattern12_1);
-                                                if let Some((pattern15_0, pattern15_1)) =
-                                                    C::multi_lane(ctx, pattern14_0)
-                                                {
-                                                    if pattern15_0 == 32 {
-                                                        if pattern15_1 == 4 {
-                                                            if let Some(pattern18_0) =
-                                                                C::def_inst(ctx, pattern9_1)
-                                                            {
-                                                                let pattern19_0 =
-                                                                    C::inst_data(ctx, pattern18_0);
-                                                                if let &InstructionData::Unary {
-                                                                    opcode: ref pattern20_0,
-                                                                    arg: pattern20_1,
-                                                                } = &pattern19_0
-                                                                {
-                                                                    if let &Opcode::SwidenHigh =
-                                                                        &pattern20_0
-                                                                    {
-                                                                        let pattern22_0 =
-                                                                            C::value_type(
-                                                                                ctx,
-                                                                                pattern20_1,
-                                                                            );
-                                                                        if let Some((
-                                                                            pattern23_0,
-                                                                            pattern23_1,
-                                                                        )) = C::multi_lane(
-                                                                            ctx,
-                                                                            pattern22_0,
-                                                                        ) {
-                                                                            if pattern23_0 == 32 {
-                                                                                if pattern23_1 == 4
-                                                                                {
-                                                                                    // Rule at src/isa/x64/lower.isle line 828.
-                                                                                    let expr0_0 = C::put_in_reg_mem(ctx, pattern12_1);
-                                                                                    let expr1_0: u8 = 250;
-                                                                                    let expr2_0 = OperandSize::Size32;
-                                                                                    let expr3_0 = constructor_pshufd(ctx, &expr0_0, expr1_0, &expr2_0)?;
-                                                                                    let expr4_0 = C::put_in_reg_mem(ctx, pattern20_1);
-                                                                                    let expr5_0: u8 = 250;
-                                                                                    let expr6_0 = OperandSize::Size32;
-                                                                                    let expr7_0 = constructor_pshufd(ctx, &expr4_0, expr5_0, &expr6_0)?;
-                                                                                    let expr8_0 = RegMem::Reg {
-                                                                                        reg: expr7_0,
-                                                                                    };
-                                                                                    let expr9_0 = constructor_pmuldq(ctx, expr3_0, &expr8_0)?;
-                                                                                    let expr10_0 = C::value_reg(ctx, expr9_0);
-                                                                                    return Some(
-                                                                                        expr10_0,
-                                                                                    );
-                                                                                }
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            &Opcode::UwidenLow => {
-                                                let pattern14_0 = C::value_type(ctx, pattern12_1);
-                                                if let Some((pattern15_0, pattern15_1)) =
-                                                    C::multi_lane(ctx, pattern14_0)
-                                                {
-                                                    if pattern15_0 == 32 {
-                                                        if pattern15_1 == 4 {
-                                                            if let Some(pattern18_0) =
-                                                                C::def_inst(ctx, pattern9_1)
-                                                            {
-                                                                let pattern19_0 =
-                                                                    C::inst_data(ctx, pattern18_0);
-                                                                if let &InstructionData::Unary {
-                                                                    opcode: ref pattern20_0,
-                                                                    arg: pattern20_1,
-                                                                } = &pattern19_0
-                                                                {
-                                                                    if let &Opcode::UwidenLow =
-                                                                        &pattern20_0
-                                                                    {
-                                                                        let pattern22_0 =
-                                                                            C::value_type(
-                                                                                ctx,
-                                                                                pattern20_1,
-                                                                            );
-                                                                        if let Some((
-                                                                            pattern23_0,
-                                                                            pattern23_1,
-                                                                        )) = C::multi_lane(
-                                                                            ctx,
-                                                                            pattern22_0,
-                                                                        ) {
-                                                                            if pattern23_0 == 32 {
-                                                                                if pattern23_1 == 4
-                                                                                {
-                                                                                    // Rule at src/isa/x64/lower.isle line 940.
-                                                                                    let expr0_0 = C::put_in_reg_mem(ctx, pattern12_1);
-                                                                                    let expr1_0: u8 = 80;
-                                                                                    let expr2_0 = OperandSize::Size32;
-                                                                                    let expr3_0 = constructor_pshufd(ctx, &expr0_0, expr1_0, &expr2_0)?;
-                                                                                    let expr4_0 = C::put_in_reg_mem(ctx, pattern20_1);
-                                                                                    let expr5_0: u8 = 80;
-                                                                                    let expr6_0 = OperandSize::Size32;
-                                                                                    let expr7_0 = constructor_pshufd(ctx, &expr4_0, expr5_0, &expr6_0)?;
-                                                                                    let expr8_0 = RegMem::Reg {
-                                                                                        reg: expr7_0,
-                                                                                    };
-                                                                                    let expr9_0 = constructor_pmuludq(ctx, expr3_0, &expr8_0)?;
-                                                                                    let expr10_0 = C::value_reg(ctx, expr9_0);
-                                                                                    return Some(
-                                                                                        expr10_0,
-                                                                                    );
-                                                                                }
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            &Opcode::UwidenHigh => {
-                                                let pattern14_0 = C::value_type(ctx, pattern12_1);
-                                                if let Some((pattern15_0, pattern15_1)) =
-                                                    C::multi_lane(ctx, pattern14_0)
-                                                {
-                                                    if pattern15_0 == 32 {
-                                                        if pattern15_1 == 4 {
-                                                            if let Some(pattern18_0) =
-                                                                C::def_inst(ctx, pattern9_1)
-                                                            {
-                                                                let pattern19_0 =
-                                                                    C::inst_data(ctx, pattern18_0);
-                                                                if let &InstructionData::Unary {
-                                                                    opcode: ref pattern20_0,
-                                                                    arg: pattern20_1,
-                                                                } = &pattern19_0
-                                                                {
-                                                                    if let &Opcode::UwidenHigh =
-                                                                        &pattern20_0
-                                                                    {
-                                                                        let pattern22_0 =
-                                                                            C::value_type(
-                                                                                ctx,
-                                                                                pattern20_1,
-                                                                            );
-                                                                        if let Some((
-                                                                            pattern23_0,
-                                                                            pattern23_1,
-                                                                        )) = C::multi_lane(
-                                                                            ctx,
-                                                                            pattern22_0,
-                                                                        ) {
-                                                                            if pattern23_0 == 32 {
-                                                                                if pattern23_1 == 4
-                                                                                {
-                                                                                    // Rule at src/isa/x64/lower.isle line 904.
-                                                                                    let expr0_0 = C::put_in_reg_mem(ctx, pattern12_1);
-                                                                                    let expr1_0: u8 = 250;
-                                                                                    let expr2_0 = OperandSize::Size32;
-                                                                                    let expr3_0 = constructor_pshufd(ctx, &expr0_0, expr1_0, &expr2_0)?;
-                                                                                    let expr4_0 = C::put_in_reg_mem(ctx, pattern20_1);
-                                                                                    let expr5_0: u8 = 250;
-                                                                                    let expr6_0 = OperandSize::Size32;
-                                                                                    let expr7_0 = constructor_pshufd(ctx, &expr4_0, expr5_0, &expr6_0)?;
-                                                                                    let expr8_0 = RegMem::Reg {
-                                                                                        reg: expr7_0,
-                                                                                    };
-                                                                                    let expr9_0 = constructor_pmuludq(ctx, expr3_0, &expr8_0)?;
-                                                                                    let expr10_0 = C::value_reg(ctx, expr9_0);
-                                                                                    return Some(
-                                                                                        expr10_0,
-                                                                                    );
-                                                                                }
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            _ => {}
-                                        }
-                                    }
-                                }
-                                // Rule at src/isa/x64/lower.isle line 780.
+                                // Rule at src/isa/x64/lower.isle line 137.
                                 let expr0_0 = C::put_in_reg(ctx, pattern9_0);
-                                let expr1_0 = C::put_in_reg(ctx, pattern9_1);
-                                let expr2_0: u32 = 32;
-                                let expr3_0 = RegMemImm::Imm { simm32: expr2_0 };
-                                let expr4_0 = constructor_psrlq(ctx, expr0_0, &expr3_0)?;
-                                let expr5_0 = RegMem::Reg { reg: expr1_0 };
-                                let expr6_0 = constructor_pmuludq(ctx, expr4_0, &expr5_0)?;
-                                let expr7_0: u32 = 32;
-                                let expr8_0 = RegMemImm::Imm { simm32: expr7_0 };
-                                let expr9_0 = constructor_psrlq(ctx, expr1_0, &expr8_0)?;
-                                let expr10_0 = RegMem::Reg { reg: expr9_0 };
-                                let expr11_0 = constructor_pmuludq(ctx, expr0_0, &expr10_0)?;
-                                let expr12_0 = RegMem::Reg { reg: expr11_0 };
-                                let expr13_0 = constructor_paddq(ctx, expr6_0, &expr12_0)?;
-                                let expr14_0: u32 = 32;
-                                let expr15_0 = RegMemImm::Imm { simm32: expr14_0 };
-                                let expr16_0 = constructor_psllq(ctx, expr13_0, &expr15_0)?;
-                                let expr17_0 = RegMem::Reg { reg: expr1_0 };
-                                let expr18_0 = constructor_pmuludq(ctx, expr0_0, &expr17_0)?;
-                                let expr19_0 = RegMem::Reg { reg: expr16_0 };
-                                let expr20_0 = constructor_paddq(ctx, expr18_0, &expr19_0)?;
-                                let expr21_0 = C::value_reg(ctx, expr20_0);
-                                return Some(expr21_0);
+                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
+                                let expr2_0 = constructor_paddsw(ctx, expr0_0, &expr1_0)?;
+                                let expr3_0 = C::value_reg(ctx, expr2_0);
+                                return Some(expr3_0);
+                            }
+                            &Opcode::UsubSat => {
+                                let (pattern9_0, pattern9_1) =
+                                    C::unpack_value_array_2(ctx, &pattern7_1);
+                                // Rule at src/isa/x64/lower.isle line 318.
+                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
+                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
+                                let expr2_0 = constructor_psubusw(ctx, expr0_0, &expr1_0)?;
+                                let expr3_0 = C::value_reg(ctx, expr2_0);
+                                return Some(expr3_0);
+                            }
+                            &Opcode::SsubSat => {
+                                let (pattern9_0, pattern9_1) =
+                                    C::unpack_value_array_2(ctx, &pattern7_1);
+                                // Rule at src/isa/x64/lower.isle line 306.
+                                let expr0_0 = C::put_in_reg(ctx, pattern9_0);
+                                let expr1_0 = C::put_in_reg_mem(ctx, pattern9_1);
+                                let expr2_0 = constructor_psubsw(ctx, expr0_0, &expr1_0)?;
+                                let expr3_0 = C::value_reg(ctx, expr2_0);
+                                return Some(expr3_0);
                             }
                             _ => {}
                         }
@@ -3895,80 +4738,26 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                 }
             }
             let pattern4_0 = C::inst_data(ctx, pattern0_0);
-            match &pattern4_0 {
-                &InstructionData::Binary {
-                    opcode: ref pattern5_0,
-                    args: ref pattern5_1,
-                } => {
-                    match &pattern5_0 {
-                        &Opcode::Band => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/x64/lower.isle line 368.
-                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                            let expr1_0 = C::put_in_reg_mem(ctx, pattern7_1);
-                            let expr2_0 = constructor_sse_and(ctx, pattern2_0, expr0_0, &expr1_0)?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
-                        &Opcode::Bor => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/x64/lower.isle line 440.
-                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                            let expr1_0 = C::put_in_reg_mem(ctx, pattern7_1);
-                            let expr2_0 = constructor_sse_or(ctx, pattern2_0, expr0_0, &expr1_0)?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
-                        &Opcode::Bxor => {
-                            let (pattern7_0, pattern7_1) =
-                                C::unpack_value_array_2(ctx, &pattern5_1);
-                            // Rule at src/isa/x64/lower.isle line 509.
-                            let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                            let expr1_0 = C::put_in_reg_mem(ctx, pattern7_1);
-                            let expr2_0 = constructor_sse_xor(ctx, pattern2_0, expr0_0, &expr1_0)?;
-                            let expr3_0 = C::value_reg(ctx, expr2_0);
-                            return Some(expr3_0);
-                        }
-                        _ => {}
-                    }
-                }
-                &InstructionData::Ternary {
-                    opcode: ref pattern5_0,
-                    args: ref pattern5_1,
-                } => {
-                    if let &Opcode::Bitselect = &pattern5_0 {
-                        let (pattern7_0, pattern7_1, pattern7_2) =
-                            C::unpack_value_array_3(ctx, &pattern5_1);
-                        // Rule at src/isa/x64/lower.isle line 1041.
-                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                        let expr1_0 = C::put_in_reg(ctx, pattern7_1);
-                        let expr2_0 = RegMem::Reg { reg: expr0_0 };
-                        let expr3_0 = constructor_sse_and(ctx, pattern2_0, expr1_0, &expr2_0)?;
-                        let expr4_0 = C::put_in_reg_mem(ctx, pattern7_2);
-                        let expr5_0 = constructor_sse_and_not(ctx, pattern2_0, expr0_0, &expr4_0)?;
-                        let expr6_0 = RegMem::Reg { reg: expr3_0 };
-                        let expr7_0 = constructor_sse_or(ctx, pattern2_0, expr5_0, &expr6_0)?;
-                        let expr8_0 = C::value_reg(ctx, expr7_0);
-                        return Some(expr8_0);
-                    }
-                }
-                &InstructionData::Unary {
-                    opcode: ref pattern5_0,
-                    arg: pattern5_1,
-                } => {
-                    if let &Opcode::Bnot = &pattern5_0 {
-                        // Rule at src/isa/x64/lower.isle line 1036.
-                        let expr0_0 = C::put_in_reg(ctx, pattern5_1);
-                        let expr1_0 = constructor_vector_all_ones(ctx, pattern2_0)?;
-                        let expr2_0 = RegMem::Reg { reg: expr1_0 };
-                        let expr3_0 = constructor_sse_xor(ctx, pattern2_0, expr0_0, &expr2_0)?;
-                        let expr4_0 = C::value_reg(ctx, expr3_0);
-                        return Some(expr4_0);
-                    }
+            if let &InstructionData::Ternary {
+                opcode: ref pattern5_0,
+                args: ref pattern5_1,
+            } = &pattern4_0
+            {
+                if let &Opcode::Bitselect = &pattern5_0 {
+                    let (pattern7_0, pattern7_1, pattern7_2) =
+                        C::unpack_value_array_3(ctx, &pattern5_1);
+                    // Rule at src/isa/x64/lower.isle line 1041.
+                    let expr0_0 = C::put_in_reg(ctx, pattern7_0);
+                    let expr1_0 = C::put_in_reg(ctx, pattern7_1);
+                    let expr2_0 = RegMem::Reg { reg: expr0_0 };
+                    let expr3_0 = constructor_sse_and(ctx, pattern2_0, expr1_0, &expr2_0)?;
+                    let expr4_0 = C::put_in_reg_mem(ctx, pattern7_2);
+                    let expr5_0 = constructor_sse_and_not(ctx, pattern2_0, expr0_0, &expr4_0)?;
+                    let expr6_0 = RegMem::Reg { reg: expr3_0 };
+                    let expr7_0 = constructor_sse_or(ctx, pattern2_0, expr5_0, &expr6_0)?;
+                    let expr8_0 = C::value_reg(ctx, expr7_0);
+                    return Some(expr8_0);
                 }
-                _ => {}
             }
         }
         if let Some(pattern3_0) = C::fits_in_64(ctx, pattern2_0) {
@@ -4015,38 +4804,6 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                         &Opcode::Iadd => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_0) {
-                                // Rule at src/isa/x64/lower.isle line 76.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
-                                let expr1_0 =
-                                    constructor_add(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
-                            }
-                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_0) {
-                                // Rule at src/isa/x64/lower.isle line 88.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
-                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
-                                let expr2_0 = constructor_add(ctx, pattern3_0, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 72.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr1_0 =
-                                    constructor_add(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
-                            }
-                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 82.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
-                                let expr2_0 = constructor_add(ctx, pattern3_0, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
                             // Rule at src/isa/x64/lower.isle line 64.
                             let expr0_0 = C::put_in_reg(ctx, pattern7_0);
                             let expr1_0 = C::put_in_reg(ctx, pattern7_1);
@@ -4058,22 +4815,6 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                         &Opcode::Isub => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 252.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr1_0 =
-                                    constructor_sub(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
-                            }
-                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 257.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
-                                let expr2_0 = constructor_sub(ctx, pattern3_0, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
                             // Rule at src/isa/x64/lower.isle line 245.
                             let expr0_0 = C::put_in_reg(ctx, pattern7_0);
                             let expr1_0 = C::put_in_reg(ctx, pattern7_1);
@@ -4085,38 +4826,6 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                         &Opcode::Imul => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_0) {
-                                // Rule at src/isa/x64/lower.isle line 686.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
-                                let expr1_0 =
-                                    constructor_mul(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
-                            }
-                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_0) {
-                                // Rule at src/isa/x64/lower.isle line 698.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
-                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
-                                let expr2_0 = constructor_mul(ctx, pattern3_0, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 682.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr1_0 =
-                                    constructor_mul(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
-                            }
-                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 692.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
-                                let expr2_0 = constructor_mul(ctx, pattern3_0, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
                             // Rule at src/isa/x64/lower.isle line 675.
                             let expr0_0 = C::put_in_reg(ctx, pattern7_0);
                             let expr1_0 = C::put_in_reg(ctx, pattern7_1);
@@ -4128,50 +4837,6 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                         &Opcode::IaddIfcout => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_0) {
-                                // Rule at src/isa/x64/lower.isle line 183.
-                                let expr0_0: Type = I64;
-                                let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
-                                let expr2_0 = C::writable_reg_to_reg(ctx, expr1_0);
-                                let expr3_0 = C::put_in_reg(ctx, pattern7_1);
-                                let expr4_0 =
-                                    constructor_add(ctx, pattern3_0, expr3_0, &pattern8_0)?;
-                                let expr5_0 = C::value_regs(ctx, expr4_0, expr2_0);
-                                return Some(expr5_0);
-                            }
-                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_0) {
-                                // Rule at src/isa/x64/lower.isle line 199.
-                                let expr0_0: Type = I64;
-                                let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
-                                let expr2_0 = C::writable_reg_to_reg(ctx, expr1_0);
-                                let expr3_0 = C::put_in_reg(ctx, pattern7_1);
-                                let expr4_0 = C::sink_load(ctx, &pattern8_0);
-                                let expr5_0 = constructor_add(ctx, pattern3_0, expr3_0, &expr4_0)?;
-                                let expr6_0 = C::value_regs(ctx, expr5_0, expr2_0);
-                                return Some(expr6_0);
-                            }
-                            if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 177.
-                                let expr0_0: Type = I64;
-                                let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
-                                let expr2_0 = C::writable_reg_to_reg(ctx, expr1_0);
-                                let expr3_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr4_0 =
-                                    constructor_add(ctx, pattern3_0, expr3_0, &pattern8_0)?;
-                                let expr5_0 = C::value_regs(ctx, expr4_0, expr2_0);
-                                return Some(expr5_0);
-                            }
-                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 191.
-                                let expr0_0: Type = I64;
-                                let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
-                                let expr2_0 = C::writable_reg_to_reg(ctx, expr1_0);
-                                let expr3_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr4_0 = C::sink_load(ctx, &pattern8_0);
-                                let expr5_0 = constructor_add(ctx, pattern3_0, expr3_0, &expr4_0)?;
-                                let expr6_0 = C::value_regs(ctx, expr5_0, expr2_0);
-                                return Some(expr6_0);
-                            }
                             // Rule at src/isa/x64/lower.isle line 167.
                             let expr0_0: Type = I64;
                             let expr1_0 = C::temp_writable_reg(ctx, expr0_0);
@@ -4186,40 +4851,6 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                         &Opcode::Band => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_0) {
-                                // Rule at src/isa/x64/lower.isle line 355.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
-                                let expr1_0 =
-                                    constructor_m_and(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
-                            }
-                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_0) {
-                                // Rule at src/isa/x64/lower.isle line 341.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
-                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
-                                let expr2_0 =
-                                    constructor_m_and(ctx, pattern3_0, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 349.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr1_0 =
-                                    constructor_m_and(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
-                            }
-                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 335.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
-                                let expr2_0 =
-                                    constructor_m_and(ctx, pattern3_0, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
                             // Rule at src/isa/x64/lower.isle line 328.
                             let expr0_0 = C::put_in_reg(ctx, pattern7_0);
                             let expr1_0 = C::put_in_reg(ctx, pattern7_1);
@@ -4231,38 +4862,6 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                         &Opcode::Bor => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_0) {
-                                // Rule at src/isa/x64/lower.isle line 427.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
-                                let expr1_0 =
-                                    constructor_or(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
-                            }
-                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_0) {
-                                // Rule at src/isa/x64/lower.isle line 413.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
-                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
-                                let expr2_0 = constructor_or(ctx, pattern3_0, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 421.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr1_0 =
-                                    constructor_or(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
-                            }
-                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 407.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
-                                let expr2_0 = constructor_or(ctx, pattern3_0, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
                             // Rule at src/isa/x64/lower.isle line 400.
                             let expr0_0 = C::put_in_reg(ctx, pattern7_0);
                             let expr1_0 = C::put_in_reg(ctx, pattern7_1);
@@ -4274,38 +4873,6 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                         &Opcode::Bxor => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_0) {
-                                // Rule at src/isa/x64/lower.isle line 501.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
-                                let expr1_0 =
-                                    constructor_xor(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
-                            }
-                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_0) {
-                                // Rule at src/isa/x64/lower.isle line 487.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_1);
-                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
-                                let expr2_0 = constructor_xor(ctx, pattern3_0, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
-                            if let Some(pattern8_0) = C::simm32_from_value(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 495.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr1_0 =
-                                    constructor_xor(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
-                            }
-                            if let Some(pattern8_0) = C::sinkable_load(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 481.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr1_0 = C::sink_load(ctx, &pattern8_0);
-                                let expr2_0 = constructor_xor(ctx, pattern3_0, expr0_0, &expr1_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
                             // Rule at src/isa/x64/lower.isle line 474.
                             let expr0_0 = C::put_in_reg(ctx, pattern7_0);
                             let expr1_0 = C::put_in_reg(ctx, pattern7_1);
@@ -4317,14 +4884,6 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                         &Opcode::Ishl => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            if let Some(pattern8_0) = C::imm8_from_value(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 545.
-                                let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                                let expr1_0 =
-                                    constructor_shl(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                                let expr2_0 = C::value_reg(ctx, expr1_0);
-                                return Some(expr2_0);
-                            }
                             // Rule at src/isa/x64/lower.isle line 539.
                             let expr0_0 = constructor_lo_reg(ctx, pattern7_1)?;
                             let expr1_0 = C::put_in_reg(ctx, pattern7_0);
@@ -4336,17 +4895,6 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                         &Opcode::Ushr => {
                             let (pattern7_0, pattern7_1) =
                                 C::unpack_value_array_2(ctx, &pattern5_1);
-                            if let Some(pattern8_0) = C::imm8_from_value(ctx, pattern7_1) {
-                                // Rule at src/isa/x64/lower.isle line 591.
-                                let expr0_0 = ExtendKind::Zero;
-                                let expr1_0 = constructor_extend_to_reg(
-                                    ctx, pattern7_0, pattern3_0, &expr0_0,
-                                )?;
-                                let expr2_0 =
-                                    constructor_shr(ctx, pattern3_0, expr1_0, &pattern8_0)?;
-                                let expr3_0 = C::value_reg(ctx, expr2_0);
-                                return Some(expr3_0);
-                            }
                             // Rule at src/isa/x64/lower.isle line 584.
                             let expr0_0 = ExtendKind::Zero;
                             let expr1_0 =
@@ -4391,32 +4939,6 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
                 _ => {}
             }
         }
-        if let Some(pattern3_0) = C::ty_32_or_64(ctx, pattern2_0) {
-            let pattern4_0 = C::inst_data(ctx, pattern0_0);
-            if let &InstructionData::Binary {
-                opcode: ref pattern5_0,
-                args: ref pattern5_1,
-            } = &pattern4_0
-            {
-                if let &Opcode::Rotl = &pattern5_0 {
-                    let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                    if let Some(pattern8_0) = C::imm8_from_value(ctx, pattern7_1) {
-                        // Rule at src/isa/x64/lower.isle line 647.
-                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                        let expr1_0 = constructor_m_rotl(ctx, pattern3_0, expr0_0, &pattern8_0)?;
-                        let expr2_0 = C::value_reg(ctx, expr1_0);
-                        return Some(expr2_0);
-                    }
-                    // Rule at src/isa/x64/lower.isle line 641.
-                    let expr0_0 = constructor_lo_reg(ctx, pattern7_1)?;
-                    let expr1_0 = C::put_in_reg(ctx, pattern7_0);
-                    let expr2_0 = Imm8Reg::Reg { reg: expr0_0 };
-                    let expr3_0 = constructor_m_rotl(ctx, pattern3_0, expr1_0, &expr2_0)?;
-                    let expr4_0 = C::value_reg(ctx, expr3_0);
-                    return Some(expr4_0);
-                }
-            }
-        }
         if let Some(pattern3_0) = C::ty_8_or_16(ctx, pattern2_0) {
             let pattern4_0 = C::inst_data(ctx, pattern0_0);
             if let &InstructionData::Binary {
@@ -4426,15 +4948,6 @@ pub fn constructor_lower<C: Context>(ctx: &mut C, arg0: Inst) -> Option<ValueReg
             {
                 if let &Opcode::Rotl = &pattern5_0 {
                     let (pattern7_0, pattern7_1) = C::unpack_value_array_2(ctx, &pattern5_1);
-                    if let Some(pattern8_0) = C::imm8_from_value(ctx, pattern7_1) {
-                        // Rule at src/isa/x64/lower.isle line 635.
-                        let expr0_0 = C::put_in_reg(ctx, pattern7_0);
-                        let expr1_0 = C::ty_bits_mask(ctx, pattern3_0);
-                        let expr2_0 = C::mask_imm8_const(ctx, &pattern8_0, expr1_0);
-                        let expr3_0 = constructor_m_rotl(ctx, pattern3_0, expr0_0, &expr2_0)?;
-                        let expr4_0 = C::value_reg(ctx, expr3_0);
-                        return Some(expr4_0);
-                    }
                     // Rule at src/isa/x64/lower.isle line 631.
                     let expr0_0: Type = I32;
                     let expr1_0 = ExtendKind::Zero;
@@ -168,8 +168,8 @@ fn isa_constructor(
     triple: Triple,
     shared_flags: Flags,
     builder: shared_settings::Builder,
-) -> Box<dyn TargetIsa> {
+) -> CodegenResult<Box<dyn TargetIsa>> {
     let isa_flags = x64_settings::Flags::new(&shared_flags, builder);
     let backend = X64Backend::new_with_flags(triple, shared_flags, isa_flags);
-    Box::new(TargetIsaAdapter::new(backend))
+    Ok(Box::new(TargetIsaAdapter::new(backend)))
 }
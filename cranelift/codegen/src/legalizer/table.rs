@@ -7,11 +7,13 @@ use crate::cursor::{Cursor, FuncCursor};
 use crate::ir::condcodes::IntCC;
 use crate::ir::immediates::Offset32;
 use crate::ir::{self, InstBuilder};
+use crate::isa::TargetIsa;
 
 /// Expand a `table_addr` instruction according to the definition of the table.
 pub fn expand_table_addr(
     inst: ir::Inst,
     func: &mut ir::Function,
+    isa: &dyn TargetIsa,
     table: ir::Table,
     index: ir::Value,
     element_offset: Offset32,
@@ -31,6 +33,12 @@ pub fn expand_table_addr(
         .icmp(IntCC::UnsignedGreaterThanOrEqual, index, bound);
     pos.ins().trapnz(oob, ir::TrapCode::TableOutOfBounds);
 
+    let spectre_oob_comparison = if isa.flags().enable_table_access_spectre_mitigation() {
+        Some((IntCC::UnsignedGreaterThanOrEqual, index, bound))
+    } else {
+        None
+    };
+
     compute_addr(
         inst,
         table,
@@ -39,6 +47,7 @@ pub fn expand_table_addr(
         index_ty,
         element_offset,
         pos.func,
+        spectre_oob_comparison,
     );
 }
 
@@ -51,6 +60,11 @@ fn compute_addr(
     index_ty: ir::Type,
     element_offset: Offset32,
     func: &mut ir::Function,
+    // If we are performing Spectre mitigation with conditional selects, the
+    // values to compare and the condition code that indicates an out-of-bounds
+    // condition; on this condition, the conditional move will choose a
+    // speculatively safe address (a zero / null pointer) instead.
+    spectre_oob_comparison: Option<(IntCC, ir::Value, ir::Value)>,
 ) {
     let mut pos = FuncCursor::new(func).at_inst(inst);
     pos.use_srcloc(inst);
@@ -77,7 +91,19 @@ fn compute_addr(
         offset = pos.ins().imul_imm(index, element_size as i64);
     }
 
-    if element_offset == Offset32::new(0) {
+    if let Some((cc, a, b)) = spectre_oob_comparison {
+        let mut final_addr = pos.ins().iadd(base, offset);
+        if element_offset != Offset32::new(0) {
+            let imm: i64 = element_offset.into();
+            final_addr = pos.ins().iadd_imm(final_addr, imm);
+        }
+        let zero = pos.ins().iconst(addr_ty, 0);
+        let flags = pos.ins().ifcmp(a, b);
+        pos.func
+            .dfg
+            .replace(inst)
+            .selectif_spectre_guard(addr_ty, cc, flags, zero, final_addr);
+    } else if element_offset == Offset32::new(0) {
         pos.func.dfg.replace(inst).iadd(base, offset);
     } else {
         let imm: i64 = element_offset.into();
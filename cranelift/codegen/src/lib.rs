@@ -96,6 +96,7 @@ mod constant_hash;
 mod context;
 mod dce;
 mod divconst_magic_numbers;
+mod egraph;
 mod fx;
 mod inst_predicates;
 mod iterators;
@@ -103,6 +104,7 @@ mod legalizer;
 mod licm;
 mod log;
 mod nan_canonicalization;
+mod opts;
 mod remove_constant_phis;
 mod result;
 mod scoped_hash_map;
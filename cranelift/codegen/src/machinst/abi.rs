@@ -5,6 +5,7 @@ use crate::ir::{Signature, StackSlot};
 use crate::isa::CallConv;
 use crate::machinst::*;
 use crate::settings;
+use crate::CodegenResult;
 use regalloc::{Reg, Set, SpillSlot, Writable};
 use smallvec::SmallVec;
 
@@ -42,12 +43,14 @@ pub trait ABICallee {
     /// Get the calling convention implemented by this ABI object.
     fn call_conv(&self) -> CallConv;
 
-    /// Get the liveins of the function.
-    fn liveins(&self) -> Set<RealReg>;
-
     /// Get the liveouts of the function.
     fn liveouts(&self) -> Set<RealReg>;
 
+    /// Get the registers that are not preserved across calls in this
+    /// function's calling convention. The register allocator prefers these,
+    /// since using them does not require saving them in the prologue.
+    fn caller_saved_regs(&self) -> Vec<Writable<Reg>>;
+
     /// Number of arguments.
     fn num_args(&self) -> usize;
 
@@ -87,6 +90,10 @@ pub trait ABICallee {
         from_reg: ValueRegs<Writable<Reg>>,
     ) -> SmallInstVec<Self::I>;
 
+    /// Get the register holding the pointer to the return-value area, if the
+    /// function has one. Tail calls pass this pointer on to their callee.
+    fn ret_area_ptr(&self) -> Option<Reg>;
+
     /// Generate a return instruction.
     fn gen_ret(&self) -> Self::I;
 
@@ -149,6 +156,11 @@ pub trait ABICallee {
     /// likely closely related.
     fn gen_epilogue(&self) -> SmallInstVec<Self::I>;
 
+    /// Generate the epilogue for a tail call, post-regalloc. This is the same
+    /// as `gen_epilogue()` except that it does not generate the return
+    /// instruction; the tail call's jump follows it instead.
+    fn gen_tail_call_epilogue(&self) -> SmallInstVec<Self::I>;
+
     /// Returns the full frame size for the given function, after prologue
     /// emission has run. This comprises the spill slots and stack-storage slots
     /// (but not storage for clobbered callee-save registers, arguments pushed
@@ -157,9 +169,14 @@ pub trait ABICallee {
     /// as in Baldrdash (SpiderMonkey integration).
     fn frame_size(&self) -> u32;
 
-    /// Returns the size of arguments expected on the stack.
+    /// Returns the size of the incoming stack-argument area. This includes
+    /// room for the stack arguments of any tail calls made by the function.
     fn stack_args_size(&self) -> u32;
 
+    /// Returns the nominal-SP offset of the area that struct arguments of
+    /// tail calls are staged in.
+    fn tail_call_staging_offset(&self) -> u32;
+
     /// Get the spill-slot size.
     fn get_spillslot_size(&self, rc: RegClass) -> u32;
 
@@ -242,4 +259,21 @@ pub trait ABICaller {
     /// This function should only be called once, as it is allowed to re-use
     /// parts of the ABICaller object in emitting instructions.
     fn emit_call<C: LowerCtx<I = Self::I>>(&mut self, ctx: &mut C);
+
+    /// Emit a tail call, given the source registers of all arguments in
+    /// signature order.
+    ///
+    /// This copies the arguments into place and emits a terminator that jumps
+    /// to the callee once the current frame has been torn down, so that the
+    /// callee returns directly to the current function's caller. Stack
+    /// arguments are written into the current function's own incoming
+    /// argument area, so tail calls that need more stack argument space than
+    /// the current function received are rejected.
+    ///
+    /// Like `emit_call()`, this function should only be called once.
+    fn emit_return_call<C: LowerCtx<I = Self::I>>(
+        &mut self,
+        ctx: &mut C,
+        args: &[ValueRegs<Reg>],
+    ) -> CodegenResult<()>;
 }
@@ -122,6 +122,27 @@
 //!   - Return v2 in memory at `[P]`.
 //!   - Return v1 in memory at `[P+8]`.
 //!   - Return v0 in memory at `[P+16]`.
+//!
+//! # Tail Calls
+//!
+//! Functions using the `tail` calling convention pop their own stack
+//! arguments when they return, so that a tail call may pass more stack
+//! arguments than its caller received: whoever returns to the original caller
+//! pops however many bytes that caller pushed. Each such function reserves an
+//! incoming argument area as large as the largest of its own stack arguments
+//! and those of every tail call it makes. If that is larger than what its
+//! caller pushed, the prologue moves the return address down to make room
+//! before setting up the frame. A tail call writes its stack arguments to the
+//! top of this area, tears down the frame, and moves the return address up
+//! against them before jumping to the callee.
+//!
+//! Callers of `tail` functions leave SP where it was before the call by
+//! pushing the popped bytes back immediately after the call returns.
+//!
+//! The unwind info of a `tail` function takes its caller's SP to be the SP
+//! after the return, at the top of the argument area, since that is the only
+//! part of the area that stays in place across tail calls. Stack maps at calls
+//! to such functions are relative to that SP too.
 
 use super::abi::*;
 use crate::binemit::StackMap;
@@ -129,8 +150,8 @@ use crate::ir::types::*;
 use crate::ir::{ArgumentExtension, ArgumentPurpose, StackSlot};
 use crate::machinst::*;
 use crate::settings;
-use crate::CodegenResult;
 use crate::{ir, isa};
+use crate::{CodegenError, CodegenResult};
 use alloc::vec::Vec;
 use regalloc::{RealReg, Reg, RegClass, Set, SpillSlot, Writable};
 use smallvec::{smallvec, SmallVec};
@@ -365,8 +386,10 @@ pub trait ABIMachineSpec {
         to_bits: u8,
     ) -> Self::I;
 
-    /// Generate a return instruction.
-    fn gen_ret() -> Self::I;
+    /// Generate a return instruction, popping `stack_bytes_to_pop` bytes of
+    /// stack arguments above the return address. This is always zero on
+    /// targets that do not support the `tail` calling convention.
+    fn gen_ret(stack_bytes_to_pop: u32) -> Self::I;
 
     /// Generate an "epilogue placeholder" instruction, recognized by lowering
     /// when using the Baldrdash ABI.
@@ -435,6 +458,11 @@ pub trait ABIMachineSpec {
     /// Generate a probestack call.
     fn gen_probestack(_frame_size: u32) -> SmallInstVec<Self::I>;
 
+    /// Generate an inline stack probe sequence, touching every `guard_size`
+    /// page of a frame of `frame_size` bytes below the current SP, in order
+    /// from the highest address down.
+    fn gen_inline_probestack(_frame_size: u32, _guard_size: u32) -> SmallInstVec<Self::I>;
+
     /// Get all clobbered registers that are callee-saved according to the ABI; the result
     /// contains the registers in a sorted order.
     fn get_clobbered_callee_saves(
@@ -490,8 +518,44 @@ pub trait ABIMachineSpec {
         tmp: Writable<Reg>,
         callee_conv: isa::CallConv,
         callee_conv: isa::CallConv,
+        callee_pop_size: u32,
     ) -> SmallVec<[(InstIsSafepoint, Self::I); 2]>;
 
+    /// Generate a tail call instruction/sequence. This is emitted after the
+    /// arguments have been copied into place, and must produce an instruction
+    /// that `is_term()` reports as `MachTerminator::RetCall`; the frame is torn
+    /// down right before it post-regalloc. Since that restores callee-saved
+    /// registers, the called address must be moved into a register that is
+    /// neither callee-saved nor used for arguments.
+    ///
+    /// After the frame is torn down, the return address must be moved up by
+    /// `stack_bytes_to_pop` bytes, so that it sits right below the stack
+    /// arguments written for the callee.
+    fn gen_return_call(
+        _dest: &CallDest,
+        _uses: Vec<Reg>,
+        _stack_bytes_to_pop: u32,
+    ) -> CodegenResult<SmallInstVec<Self::I>> {
+        Err(CodegenError::Unsupported(
+            "tail calls are not supported on this target".into(),
+        ))
+    }
+
+    /// Generate the start of the prologue of a function that pops
+    /// `incoming_args_size` bytes of stack arguments when it returns, before
+    /// the frame is set up. This moves the return address `growth` bytes down
+    /// the stack if the incoming argument area needs to grow, and describes
+    /// the popped arguments in the unwind info. Only `tail` functions pop
+    /// their own arguments, so targets that do not support that calling
+    /// convention can leave this out.
+    fn gen_callee_pops_args_entry(
+        _flags: &settings::Flags,
+        _incoming_args_size: u32,
+        _growth: u32,
+    ) -> SmallInstVec<Self::I> {
+        unreachable!("only `tail` functions pop their own stack arguments")
+    }
+
     /// Generate a memcpy invocation. Used to set up struct args. May clobber
     /// caller-save registers; we only memcpy before we start to set up args for
     /// a call.
@@ -596,8 +660,16 @@ pub struct ABICalleeImpl<M: ABIMachineSpec> {
     sig: ABISig,
     /// Offsets to each stackslot.
     stackslots: PrimaryMap<StackSlot, u32>,
-    /// Total stack size of all stackslots.
+    /// Total stack size of all stackslots, including the tail call staging
+    /// area.
     stackslots_size: u32,
+    /// Offset of the area that struct arguments of tail calls are copied
+    /// through, after all stackslots.
+    tail_call_staging_offset: u32,
+    /// Size of the incoming stack-argument area, which may be larger than
+    /// this function's own stack arguments to make room for those of the
+    /// tail calls it makes.
+    tail_args_size: u32,
     /// Stack size to be reserved for outgoing arguments.
     outgoing_args_size: u32,
     /// Clobbered registers, from regalloc.
@@ -633,8 +705,9 @@ pub struct ABICalleeImpl<M: ABIMachineSpec> {
     /// manually register-allocated and carefully only use caller-saved
     /// registers and keep nothing live after this sequence of instructions.
     stack_limit: Option<(Reg, SmallInstVec<M::I>)>,
-    /// Are we to invoke the probestack function in the prologue? If so,
-    /// what is the minimum size at which we must invoke it?
+    /// Are we to probe the stack in the prologue? If so, what is the minimum
+    /// frame size at which we must do so? This is also the guard size used
+    /// by inline probes.
     probestack_min_frame: Option<u32>,
     /// Whether it is necessary to generate the usual frame-setup sequence.
     setup_frame: bool,
@@ -686,7 +759,8 @@ impl<M: ABIMachineSpec> ABICalleeImpl<M> {
                 || call_conv.extends_windows_fastcall()
                 || call_conv == isa::CallConv::AppleAarch64
                 || call_conv == isa::CallConv::WasmtimeSystemV
-                || call_conv == isa::CallConv::WasmtimeAppleAarch64,
+                || call_conv == isa::CallConv::WasmtimeAppleAarch64
+                || call_conv == isa::CallConv::Tail,
             "Unsupported calling convention: {:?}",
             call_conv
         );
@@ -703,6 +777,41 @@ impl<M: ABIMachineSpec> ABICalleeImpl<M> {
             stackslots.push(off);
         }
 
+        // Size the incoming argument area for the stack arguments of all tail
+        // calls made here, and the staging area for copying struct arguments
+        // to them, which have to be read before any of them is written.
+        let mut tail_args_size = sig.stack_arg_space as u32;
+        let mut tail_call_staging_size = 0;
+        if call_conv == isa::CallConv::Tail {
+            for block in f.layout.blocks() {
+                for inst in f.layout.block_insts(block) {
+                    match f.dfg[inst].opcode() {
+                        ir::Opcode::ReturnCall | ir::Opcode::ReturnCallIndirect => {}
+                        _ => continue,
+                    }
+                    let callee_sig = &f.dfg.signatures[f.dfg.call_signature(inst).unwrap()];
+                    let callee_sig = ABISig::from_func_sig::<M>(
+                        &ensure_struct_return_ptr_is_returned(callee_sig),
+                        &flags,
+                    )?;
+                    let size = callee_sig.stack_arg_space as u32;
+                    tail_args_size = tail_args_size.max(size);
+                    if callee_sig.args.iter().any(|arg| arg.is_struct_arg()) {
+                        tail_call_staging_size = tail_call_staging_size.max(size);
+                    }
+                }
+            }
+            // The return instruction pops the whole area.
+            if tail_args_size > u32::from(u16::MAX) {
+                return Err(CodegenError::Unsupported(format!(
+                    "{} bytes of stack arguments for tail calls",
+                    tail_args_size
+                )));
+            }
+        }
+        let tail_call_staging_offset = stack_offset;
+        stack_offset += tail_call_staging_size;
+
         // Figure out what instructions, if any, will be needed to check the
         // stack limit. This can either be specified as a special-purpose
         // argument or as a global value which often calculates the stack limit
@@ -712,7 +821,7 @@ impl<M: ABIMachineSpec> ABICalleeImpl<M> {
                 .map(|reg| (reg, smallvec![]))
                 .or_else(|| f.stack_limit.map(|gv| gen_stack_limit::<M>(f, &sig, gv)));
 
-        // Determine whether a probestack call is required for large enough
+        // Determine whether stack probes are required for large enough
         // frames (and the minimum frame size if so).
         let probestack_min_frame = if flags.enable_probestack() {
             assert!(
@@ -729,6 +838,8 @@ impl<M: ABIMachineSpec> ABICalleeImpl<M> {
             sig,
             stackslots,
             stackslots_size: stack_offset,
+            tail_call_staging_offset,
+            tail_args_size,
             outgoing_args_size: 0,
             clobbered: Set::empty(),
             spillslots: None,
@@ -745,6 +856,26 @@ impl<M: ABIMachineSpec> ABICalleeImpl<M> {
         })
     }
 
+    /// Number of bytes the prologue moves the return address down by, to
+    /// make the incoming argument area large enough for tail calls.
+    fn incoming_args_growth(&self) -> u32 {
+        self.tail_args_size - self.sig.stack_arg_space as u32
+    }
+
+    /// Number of bytes of stack arguments popped when returning.
+    fn ret_pop_size(&self) -> u32 {
+        if self.call_conv == isa::CallConv::Tail {
+            self.tail_args_size
+        } else {
+            0
+        }
+    }
+
+    /// Offset from FP to this function's own incoming stack arguments.
+    fn fp_to_own_arg_offset(&self) -> i64 {
+        M::fp_to_arg_offset(self.call_conv, &self.flags) + i64::from(self.incoming_args_growth())
+    }
+
     /// Inserts instructions necessary for checking the stack limit into the
     /// prologue.
     ///
@@ -951,20 +1082,6 @@ impl<M: ABIMachineSpec> ABICallee for ABICalleeImpl<M> {
         self.sig.call_conv
     }
 
-    fn liveins(&self) -> Set<RealReg> {
-        let mut set: Set<RealReg> = Set::empty();
-        for arg in &self.sig.args {
-            if let &ABIArg::Slots { ref slots, .. } = arg {
-                for slot in slots {
-                    if let ABIArgSlot::Reg { reg, .. } = slot {
-                        set.insert(*reg);
-                    }
-                }
-            }
-        }
-        set
-    }
-
     fn liveouts(&self) -> Set<RealReg> {
         let mut set: Set<RealReg> = Set::empty();
         for ret in &self.sig.rets {
@@ -979,6 +1096,10 @@ impl<M: ABIMachineSpec> ABICallee for ABICalleeImpl<M> {
         set
     }
 
+    fn caller_saved_regs(&self) -> Vec<Writable<Reg>> {
+        M::get_regs_clobbered_by_call(self.sig.call_conv)
+    }
+
     fn num_args(&self) -> usize {
         self.sig.args.len()
     }
@@ -1013,10 +1134,7 @@ impl<M: ABIMachineSpec> ABICallee for ABICalleeImpl<M> {
                         }
                         &ABIArgSlot::Stack { offset, ty, .. } => {
                             insts.push(M::gen_load_stack(
-                                StackAMode::FPOffset(
-                                    M::fp_to_arg_offset(self.call_conv, &self.flags) + offset,
-                                    ty,
-                                ),
+                                StackAMode::FPOffset(self.fp_to_own_arg_offset() + offset, ty),
                                 *into_reg,
                                 ty,
                             ));
@@ -1027,10 +1145,7 @@ impl<M: ABIMachineSpec> ABICallee for ABICalleeImpl<M> {
             &ABIArg::StructArg { offset, .. } => {
                 let into_reg = into_regs.only_reg().unwrap();
                 insts.push(M::gen_get_stack_addr(
-                    StackAMode::FPOffset(
-                        M::fp_to_arg_offset(self.call_conv, &self.flags) + offset,
-                        I8,
-                    ),
+                    StackAMode::FPOffset(self.fp_to_own_arg_offset() + offset, I8),
                     into_reg,
                     I8,
                 ));
@@ -1155,8 +1270,12 @@ impl<M: ABIMachineSpec> ABICallee for ABICalleeImpl<M> {
         }
     }
 
+    fn ret_area_ptr(&self) -> Option<Reg> {
+        self.ret_area_ptr.map(|r| r.to_reg())
+    }
+
     fn gen_ret(&self) -> Self::I {
-        M::gen_ret()
+        M::gen_ret(self.ret_pop_size())
     }
 
     fn gen_epilogue_placeholder(&self) -> Self::I {
@@ -1268,6 +1387,14 @@ impl<M: ABIMachineSpec> ABICallee for ABICalleeImpl<M> {
 
             insts.extend(M::gen_debug_frame_info(&self.flags).into_iter());
 
+            if self.ret_pop_size() > 0 {
+                insts.extend(M::gen_callee_pops_args_entry(
+                    &self.flags,
+                    self.sig.stack_arg_space as u32,
+                    self.incoming_args_growth(),
+                ));
+            }
+
             if self.setup_frame {
                 // set up frame
                 insts.extend(M::gen_prologue_frame_setup(&self.flags).into_iter());
@@ -1282,7 +1409,14 @@ impl<M: ABIMachineSpec> ABICallee for ABICalleeImpl<M> {
                 }
                 if let Some(min_frame) = &self.probestack_min_frame {
                     if total_stacksize >= *min_frame {
-                        insts.extend(M::gen_probestack(total_stacksize));
+                        match self.flags.probestack_strategy() {
+                            settings::ProbestackStrategy::Outline => {
+                                insts.extend(M::gen_probestack(total_stacksize));
+                            }
+                            settings::ProbestackStrategy::Inline => {
+                                insts.extend(M::gen_inline_probestack(total_stacksize, *min_frame));
+                            }
+                        }
                     }
                 }
             }
@@ -1315,6 +1449,17 @@ impl<M: ABIMachineSpec> ABICallee for ABICalleeImpl<M> {
     }
 
     fn gen_epilogue(&self) -> SmallInstVec<M::I> {
+        let mut insts = self.gen_tail_call_epilogue();
+
+        if !self.call_conv.extends_baldrdash() {
+            insts.push(M::gen_ret(self.ret_pop_size()));
+        }
+
+        log::trace!("Epilogue: {:?}", insts);
+        insts
+    }
+
+    fn gen_tail_call_epilogue(&self) -> SmallInstVec<M::I> {
         let mut insts = smallvec![];
 
         // Restore clobbered registers.
@@ -1332,15 +1477,10 @@ impl<M: ABIMachineSpec> ABICallee for ABICalleeImpl<M> {
         // the CFG, so early returns in the middle of function bodies would cause an incorrect
         // offset for the rest of the body.
 
-        if !self.call_conv.extends_baldrdash() {
-            if self.setup_frame {
-                insts.extend(M::gen_epilogue_frame_restore(&self.flags));
-            }
-
-            insts.push(M::gen_ret());
+        if !self.call_conv.extends_baldrdash() && self.setup_frame {
+            insts.extend(M::gen_epilogue_frame_restore(&self.flags));
         }
 
-        log::trace!("Epilogue: {:?}", insts);
         insts
     }
 
@@ -1350,7 +1490,11 @@ impl<M: ABIMachineSpec> ABICallee for ABICalleeImpl<M> {
     }
 
     fn stack_args_size(&self) -> u32 {
-        self.sig.stack_arg_space as u32
+        self.tail_args_size
+    }
+
+    fn tail_call_staging_offset(&self) -> u32 {
+        self.tail_call_staging_offset
     }
 
     fn get_spillslot_size(&self, rc: RegClass) -> u32 {
@@ -1492,58 +1636,13 @@ impl<M: ABIMachineSpec> ABICallerImpl<M> {
             _mach: PhantomData,
         })
     }
-}
-
-fn adjust_stack_and_nominal_sp<M: ABIMachineSpec, C: LowerCtx<I = M::I>>(
-    ctx: &mut C,
-    off: i32,
-    is_sub: bool,
-) {
-    if off == 0 {
-        return;
-    }
-    let amt = if is_sub { -off } else { off };
-    for inst in M::gen_sp_reg_adjust(amt) {
-        ctx.emit(inst);
-    }
-    ctx.emit(M::gen_nominal_sp_adj(-amt));
-}
-
-impl<M: ABIMachineSpec> ABICaller for ABICallerImpl<M> {
-    type I = M::I;
-
-    fn signature(&self) -> &ir::Signature {
-        &self.ir_sig
-    }
-
-    fn num_args(&self) -> usize {
-        if self.sig.stack_ret_arg.is_some() {
-            self.sig.args.len() - 1
-        } else {
-            self.sig.args.len()
-        }
-    }
-
-    fn accumulate_outgoing_args_size<C: LowerCtx<I = Self::I>>(&self, ctx: &mut C) {
-        let off = self.sig.stack_arg_space + self.sig.stack_ret_space;
-        ctx.abi().accumulate_outgoing_args_size(off as u32);
-    }
 
-    fn emit_stack_pre_adjust<C: LowerCtx<I = Self::I>>(&self, ctx: &mut C) {
-        let off = self.sig.stack_arg_space + self.sig.stack_ret_space;
-        adjust_stack_and_nominal_sp::<M, C>(ctx, off as i32, /* is_sub = */ true)
-    }
-
-    fn emit_stack_post_adjust<C: LowerCtx<I = Self::I>>(&self, ctx: &mut C) {
-        let off = self.sig.stack_arg_space + self.sig.stack_ret_space;
-        adjust_stack_and_nominal_sp::<M, C>(ctx, off as i32, /* is_sub = */ false)
-    }
-
-    fn emit_copy_regs_to_arg<C: LowerCtx<I = Self::I>>(
+    fn emit_copy_regs_to_arg_impl<C: LowerCtx<I = M::I>>(
         &self,
         ctx: &mut C,
         idx: usize,
         from_regs: ValueRegs<Reg>,
+        is_tail_call: bool,
     ) {
         let word_rc = M::word_reg_class();
         let word_bits = M::word_bits() as usize;
@@ -1606,11 +1705,19 @@ impl<M: ABIMachineSpec> ABICaller for ABICallerImpl<M> {
                                 // Store the extended version.
                                 ty = M::word_type();
                             }
-                            ctx.emit(M::gen_store_stack(
-                                StackAMode::SPOffset(offset, ty),
-                                *from_reg,
-                                ty,
-                            ));
+                            // Tail calls store stack arguments into the top of
+                            // our own incoming argument area rather than the
+                            // outgoing one, as our frame is gone by the time
+                            // the callee runs.
+                            let amode = if is_tail_call {
+                                let base = M::fp_to_arg_offset(self.caller_conv, &self.flags)
+                                    + i64::from(ctx.abi().stack_args_size())
+                                    - self.sig.stack_arg_space;
+                                StackAMode::FPOffset(base + offset, ty)
+                            } else {
+                                StackAMode::SPOffset(offset, ty)
+                            };
+                            ctx.emit(M::gen_store_stack(amode, *from_reg, ty));
                         }
                     }
                 }
@@ -1637,6 +1744,61 @@ impl<M: ABIMachineSpec> ABICaller for ABICallerImpl<M> {
             }
         }
     }
+}
+
+fn adjust_stack_and_nominal_sp<M: ABIMachineSpec, C: LowerCtx<I = M::I>>(
+    ctx: &mut C,
+    off: i32,
+    is_sub: bool,
+) {
+    if off == 0 {
+        return;
+    }
+    let amt = if is_sub { -off } else { off };
+    for inst in M::gen_sp_reg_adjust(amt) {
+        ctx.emit(inst);
+    }
+    ctx.emit(M::gen_nominal_sp_adj(-amt));
+}
+
+impl<M: ABIMachineSpec> ABICaller for ABICallerImpl<M> {
+    type I = M::I;
+
+    fn signature(&self) -> &ir::Signature {
+        &self.ir_sig
+    }
+
+    fn num_args(&self) -> usize {
+        if self.sig.stack_ret_arg.is_some() {
+            self.sig.args.len() - 1
+        } else {
+            self.sig.args.len()
+        }
+    }
+
+    fn accumulate_outgoing_args_size<C: LowerCtx<I = Self::I>>(&self, ctx: &mut C) {
+        let off = self.sig.stack_arg_space + self.sig.stack_ret_space;
+        ctx.abi().accumulate_outgoing_args_size(off as u32);
+    }
+
+    fn emit_stack_pre_adjust<C: LowerCtx<I = Self::I>>(&self, ctx: &mut C) {
+        let off = self.sig.stack_arg_space + self.sig.stack_ret_space;
+        adjust_stack_and_nominal_sp::<M, C>(ctx, off as i32, /* is_sub = */ true)
+    }
+
+    fn emit_stack_post_adjust<C: LowerCtx<I = Self::I>>(&self, ctx: &mut C) {
+        let off = self.sig.stack_arg_space + self.sig.stack_ret_space;
+        adjust_stack_and_nominal_sp::<M, C>(ctx, off as i32, /* is_sub = */ false)
+    }
+
+    fn emit_copy_regs_to_arg<C: LowerCtx<I = Self::I>>(
+        &self,
+        ctx: &mut C,
+        idx: usize,
+        from_regs: ValueRegs<Reg>,
+    ) {
+        self.emit_copy_regs_to_arg_impl(ctx, idx, from_regs, /* is_tail_call = */ false)
+    }
 
     fn get_copy_to_arg_order(&self) -> SmallVec<[usize; 8]> {
         let mut ret = SmallVec::new();
@@ -1706,6 +1868,13 @@ impl<M: ABIMachineSpec> ABICaller for ABICallerImpl<M> {
             self.emit_copy_regs_to_arg(ctx, i, ValueRegs::one(rd.to_reg()));
         }
         let tmp = ctx.alloc_tmp(word_type).only_reg().unwrap();
+        // `tail` callees pop their stack arguments, which must be pushed back
+        // for the usual stack adjustment after the call.
+        let callee_pop_size = if self.sig.call_conv == isa::CallConv::Tail {
+            self.sig.stack_arg_space as u32
+        } else {
+            0
+        };
         for (is_safepoint, inst) in M::gen_call(
             &self.dest,
             uses,
@@ -1714,6 +1883,7 @@ impl<M: ABIMachineSpec> ABICaller for ABICallerImpl<M> {
             tmp,
             self.sig.call_conv,
             self.caller_conv,
+            callee_pop_size,
         )
         .into_iter()
         {
@@ -1723,4 +1893,98 @@ impl<M: ABIMachineSpec> ABICaller for ABICallerImpl<M> {
             }
         }
     }
+
+    fn emit_return_call<C: LowerCtx<I = Self::I>>(
+        &mut self,
+        ctx: &mut C,
+        args: &[ValueRegs<Reg>],
+    ) -> CodegenResult<()> {
+        assert_eq!(args.len(), self.num_args());
+
+        // The callee's stack arguments go at the top of our incoming argument
+        // area, which is sized for all of our tail calls; the rest of it is
+        // popped before the jump.
+        let stack_bytes_to_pop = ctx.abi().stack_args_size() - self.sig.stack_arg_space as u32;
+        let arg_base =
+            M::fp_to_arg_offset(self.caller_conv, &self.flags) + i64::from(stack_bytes_to_pop);
+
+        // Struct arguments may be copied from our own incoming arguments,
+        // which they are about to overwrite, so all of them are copied into
+        // the staging area before any is written to its final place. The
+        // memcpys clobber caller-saved registers, so this comes before any
+        // other argument is set up.
+        let staging_base = i64::from(ctx.abi().tail_call_staging_offset());
+        let memcpy_call_conv = isa::CallConv::for_libcall(&self.flags, self.sig.call_conv);
+        let struct_args: SmallVec<[(usize, i64, u64); 4]> = self
+            .sig
+            .args
+            .iter()
+            .enumerate()
+            .filter_map(|(i, arg)| match arg {
+                &ABIArg::StructArg { offset, size, .. } => Some((i, offset, size)),
+                _ => None,
+            })
+            .collect();
+        for &(i, offset, size) in &struct_args {
+            let dst_ptr = ctx.alloc_tmp(M::word_type()).only_reg().unwrap();
+            ctx.emit(M::gen_get_stack_addr(
+                StackAMode::NominalSPOffset(staging_base + offset, I8),
+                dst_ptr,
+                I8,
+            ));
+            let src_ptr = args[i].only_reg().unwrap();
+            for inst in M::gen_memcpy(memcpy_call_conv, dst_ptr.to_reg(), src_ptr, size as usize) {
+                ctx.emit(inst);
+            }
+        }
+        for &(_, offset, size) in &struct_args {
+            let src_ptr = ctx.alloc_tmp(M::word_type()).only_reg().unwrap();
+            ctx.emit(M::gen_get_stack_addr(
+                StackAMode::NominalSPOffset(staging_base + offset, I8),
+                src_ptr,
+                I8,
+            ));
+            let dst_ptr = ctx.alloc_tmp(M::word_type()).only_reg().unwrap();
+            ctx.emit(M::gen_get_stack_addr(
+                StackAMode::FPOffset(arg_base + offset, I8),
+                dst_ptr,
+                I8,
+            ));
+            for inst in M::gen_memcpy(
+                memcpy_call_conv,
+                dst_ptr.to_reg(),
+                src_ptr.to_reg(),
+                size as usize,
+            ) {
+                ctx.emit(inst);
+            }
+        }
+
+        for i in self.get_copy_to_arg_order() {
+            if !self.sig.args[i].is_struct_arg() {
+                self.emit_copy_regs_to_arg_impl(ctx, i, args[i], /* is_tail_call = */ true);
+            }
+        }
+
+        // The callee has the same return types as we do, so it can write its
+        // return values straight into our caller's return-value area.
+        if let Some(i) = self.sig.stack_ret_arg {
+            let ret_area_ptr = ctx
+                .abi()
+                .ret_area_ptr()
+                .expect("tail caller must have a return-value area too");
+            self.emit_copy_regs_to_arg_impl(
+                ctx,
+                i,
+                ValueRegs::one(ret_area_ptr),
+                /* is_tail_call = */ true,
+            );
+        }
+
+        let uses = mem::replace(&mut self.uses, Default::default());
+        for inst in M::gen_return_call(&self.dest, uses, stack_bytes_to_pop)? {
+            ctx.emit(inst);
+        }
+        Ok(())
+    }
 }
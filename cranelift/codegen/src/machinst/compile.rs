@@ -3,10 +3,9 @@
 use crate::ir::Function;
 use crate::log::DeferredDisplay;
 use crate::machinst::*;
-use crate::settings;
 use crate::timing;
 
-use regalloc::{allocate_registers_with_opts, Algorithm, Options, PrettyPrint};
+use regalloc::PrettyPrint;
 
 /// Compile the given function down to VCode with allocated registers, ready
 /// for binary emission.
@@ -24,7 +23,7 @@ where
     // Build the lowering context.
     let lower = Lower::new(f, abi, emit_info, block_order)?;
     // Lower the IR.
-    let (mut vcode, stack_map_request_info) = {
+    let mut vcode = {
         let _tt = timing::vcode_lower();
         lower.lower(b)?
     };
@@ -37,70 +36,47 @@ where
     );
 
     // Perform register allocation.
-    let (run_checker, algorithm) = match vcode.flags().regalloc() {
-        settings::Regalloc::Backtracking => (false, Algorithm::Backtracking(Default::default())),
-        settings::Regalloc::BacktrackingChecked => {
-            (true, Algorithm::Backtracking(Default::default()))
-        }
-        settings::Regalloc::ExperimentalLinearScan => {
-            (false, Algorithm::LinearScan(Default::default()))
-        }
-        settings::Regalloc::ExperimentalLinearScanChecked => {
-            (true, Algorithm::LinearScan(Default::default()))
-        }
-    };
-
-    #[cfg(feature = "regalloc-snapshot")]
-    {
-        use std::fs;
-        use std::path::Path;
-        if let Some(path) = std::env::var("SERIALIZE_REGALLOC").ok() {
-            let snapshot = regalloc::IRSnapshot::from_function(&vcode, b.reg_universe());
-            let serialized = bincode::serialize(&snapshot).expect("couldn't serialize snapshot");
-
-            let file_path = Path::new(&path).join(Path::new(&format!("ir{}.bin", f.name)));
-            fs::write(file_path, &serialized).expect("couldn't write IR snapshot file");
-        }
-    }
-
-    // If either there are no reference-typed values, or else there are
-    // but there are no safepoints at which we need to know about them,
-    // then we don't need stack maps.
-    let sri = if stack_map_request_info.reftyped_vregs.len() > 0
-        && stack_map_request_info.safepoint_insns.len() > 0
-    {
-        Some(&stack_map_request_info)
-    } else {
-        None
-    };
+    let env = RegEnv::new(b.reg_universe(), &vcode.abi().caller_saved_regs());
+    vcode.compute_operands(&env);
 
     let result = {
         let _tt = timing::regalloc();
-        allocate_registers_with_opts(
-            &mut vcode,
-            b.reg_universe(),
-            sri,
-            Options {
-                run_checker,
-                algorithm,
-            },
-        )
-        .map_err(|err| {
-            log::error!(
-                "Register allocation error for vcode\n{}\nError: {:?}",
-                vcode.show_rru(Some(b.reg_universe())),
+        let opts = regalloc2::RegallocOptions { verbose_log: false };
+        regalloc2::run(&vcode, &env.machine_env, &opts)
+            .map_err(|err| {
+                log::error!(
+                    "Register allocation error for vcode\n{}\nError: {:?}",
+                    vcode.show_rru(Some(b.reg_universe())),
+                    err
+                );
                 err
-            );
-            err
-        })
-        .expect("register allocation")
+            })
+            .expect("register allocation")
     };
 
+    // Run the regalloc checker, if requested.
+    if vcode.flags().regalloc_checker() {
+        let _tt = timing::regalloc_checker();
+        let mut checker = regalloc2::checker::Checker::new(&vcode, &env.machine_env);
+        checker.prepare(&result);
+        checker
+            .run()
+            .map_err(|err| {
+                log::error!(
+                    "Register allocation checker errors:\n{:?}\nfor vcode:\n{}",
+                    err,
+                    vcode.show_rru(Some(b.reg_universe()))
+                );
+                err
+            })
+            .expect("register allocation checker");
+    }
+
     // Reorder vcode into final order and copy out final instruction sequence
     // all at once. This also inserts prologues/epilogues.
     {
         let _tt = timing::vcode_post_ra();
-        vcode.replace_insns_from_regalloc(result);
+        vcode.replace_insns_from_regalloc(result, &env);
     }
 
     log::trace!(
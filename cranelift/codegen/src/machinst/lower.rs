@@ -25,7 +25,7 @@ use crate::CodegenResult;
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::convert::TryInto;
-use regalloc::{Reg, StackmapRequestInfo, Writable};
+use regalloc::{Reg, Writable};
 use smallvec::{smallvec, SmallVec};
 use std::fmt::Debug;
 
@@ -290,6 +290,17 @@ pub struct Lower<'func, I: VCodeInst> {
     /// Ranges in `block_insts` constituting BBs.
     block_ranges: Vec<(usize, usize)>,
 
+    /// Ranges in `block_succs` giving the successors of each BB, in the same
+    /// (reverse block) order as `block_ranges`.
+    block_succ_ranges: Vec<(usize, usize)>,
+
+    /// Successors of BBs, each with the range of its arguments in
+    /// `block_succ_args`.
+    block_succs: Vec<(BlockIndex, usize, usize)>,
+
+    /// Arguments passed by branches to their successors' block parameters.
+    block_succ_args: Vec<Reg>,
+
     /// Instructions collected for the BB in progress, in reverse order, with
     /// source-locs attached.
     bb_insts: Vec<InstTuple<I>>,
@@ -460,6 +471,9 @@ impl<'func, I: VCodeInst> Lower<'func, I> {
             cur_inst: None,
             block_insts: vec![],
             block_ranges: vec![],
+            block_succ_ranges: vec![],
+            block_succs: vec![],
+            block_succ_args: vec![],
             bb_insts: vec![],
             ir_insts: vec![],
             pinned_reg: None,
@@ -535,138 +549,39 @@ impl<'func, I: VCodeInst> Lower<'func, I> {
         self.emit(inst);
     }
 
-    fn lower_edge(&mut self, pred: Block, inst: Inst, succ: Block) -> CodegenResult<()> {
-        log::trace!("lower_edge: pred {} succ {}", pred, succ);
-
-        let num_args = self.f.dfg.block_params(succ).len();
-        debug_assert!(num_args == self.f.dfg.inst_variable_args(inst).len());
-
-        // Most blocks have no params, so skip all the hoop-jumping below and make an early exit.
-        if num_args == 0 {
-            return Ok(());
-        }
-
-        self.cur_inst = Some(inst);
-
-        // Make up two vectors of info:
-        //
-        // * one for dsts which are to be assigned constants.  We'll deal with those second, so
-        //   as to minimise live ranges.
-        //
-        // * one for dsts whose sources are non-constants.
-
-        let mut const_bundles: SmallVec<[_; 16]> = SmallVec::new();
-        let mut var_bundles: SmallVec<[_; 16]> = SmallVec::new();
-
-        let mut i = 0;
-        for (dst_val, src_val) in self
-            .f
-            .dfg
-            .block_params(succ)
-            .iter()
-            .zip(self.f.dfg.inst_variable_args(inst).iter())
-        {
-            let src_val = self.f.dfg.resolve_aliases(*src_val);
-            let ty = self.f.dfg.value_type(src_val);
-
-            debug_assert!(ty == self.f.dfg.value_type(*dst_val));
-            let dst_regs = self.value_regs[*dst_val];
-
-            let input = self.get_value_as_source_or_const(src_val);
-            log::trace!("jump arg {} is {}", i, src_val);
-            i += 1;
-
-            if let Some(c) = input.constant {
-                log::trace!(" -> constant {}", c);
-                const_bundles.push((ty, writable_value_regs(dst_regs), c));
-            } else {
-                let src_regs = self.put_value_in_regs(src_val);
-                log::trace!(" -> reg {:?}", src_regs);
-                // Skip self-assignments.  Not only are they pointless, they falsely trigger the
-                // overlap-check below and hence can cause a lot of unnecessary copying through
-                // temporaries.
-                if dst_regs != src_regs {
-                    var_bundles.push((ty, writable_value_regs(dst_regs), src_regs));
-                }
-            }
-        }
-
-        // Deal first with the moves whose sources are variables.
-
-        // FIXME: use regalloc.rs' SparseSetU here.  This would avoid all heap allocation
-        // for cases of up to circa 16 args.  Currently not possible because regalloc.rs
-        // does not export it.
-        let mut src_reg_set = FxHashSet::<Reg>::default();
-        for (_, _, src_regs) in &var_bundles {
-            for &reg in src_regs.regs() {
-                src_reg_set.insert(reg);
-            }
-        }
-        let mut overlaps = false;
-        'outer: for (_, dst_regs, _) in &var_bundles {
-            for &reg in dst_regs.regs() {
-                if src_reg_set.contains(&reg.to_reg()) {
-                    overlaps = true;
-                    break 'outer;
-                }
+    /// Lower the arguments that lowered block `bindex` passes to the block
+    /// parameters of its successors, and record its successors.
+    fn lower_branch_args(&mut self, bindex: BlockIndex) -> CodegenResult<()> {
+        let f = self.f;
+        let start = self.block_succs.len();
+        let num_succs = self.vcode.block_order().succ_indices(bindex).len();
+        for i in 0..num_succs {
+            let (inst, succ) = self.vcode.block_order().succ_indices(bindex)[i];
+            // A `br_table` may branch to the same edge block more than once.
+            if self.block_succs[start..].iter().any(|&(s, _, _)| s == succ) {
+                continue;
             }
-        }
 
-        // If, as is mostly the case, the source and destination register sets are non
-        // overlapping, then we can copy directly, so as to save the register allocator work.
-        if !overlaps {
-            for (ty, dst_regs, src_regs) in &var_bundles {
-                let (_, reg_tys) = I::rc_for_type(*ty)?;
-                for ((dst, src), reg_ty) in dst_regs
-                    .regs()
-                    .iter()
-                    .zip(src_regs.regs().iter())
-                    .zip(reg_tys.iter())
-                {
-                    self.emit(I::gen_move(*dst, *src, *reg_ty));
-                }
-            }
-        } else {
-            // There's some overlap, so play safe and copy via temps.
-            let mut tmp_regs = SmallVec::<[ValueRegs<Writable<Reg>>; 16]>::new();
-            for (ty, _, _) in &var_bundles {
-                tmp_regs.push(self.alloc_tmp(*ty));
-            }
-            for ((ty, _, src_reg), tmp_reg) in var_bundles.iter().zip(tmp_regs.iter()) {
-                let (_, reg_tys) = I::rc_for_type(*ty)?;
-                for ((tmp, src), reg_ty) in tmp_reg
-                    .regs()
-                    .iter()
-                    .zip(src_reg.regs().iter())
-                    .zip(reg_tys.iter())
-                {
-                    self.emit(I::gen_move(*tmp, *src, *reg_ty));
-                }
-            }
-            for ((ty, dst_reg, _), tmp_reg) in var_bundles.iter().zip(tmp_regs.iter()) {
-                let (_, reg_tys) = I::rc_for_type(*ty)?;
-                for ((dst, tmp), reg_ty) in dst_reg
-                    .regs()
-                    .iter()
-                    .zip(tmp_reg.regs().iter())
-                    .zip(reg_tys.iter())
-                {
-                    self.emit(I::gen_move(*dst, tmp.to_reg(), *reg_ty));
+            // Only blocks containing an original CLIF block have parameters;
+            // edge blocks just jump to their successor.
+            let args_start = self.block_succ_args.len();
+            let succ_lb = self.vcode.block_order().lowered_order()[succ as usize];
+            if succ_lb.orig_block().is_some() {
+                self.cur_inst = Some(inst);
+                for &arg in f.dfg.inst_variable_args(inst) {
+                    let ty = f.dfg.value_type(arg);
+                    let (_, reg_tys) = I::rc_for_type(ty)?;
+                    let regs = self.put_value_in_regs(arg);
+                    for (&reg, &reg_ty) in regs.regs().iter().zip(reg_tys.iter()) {
+                        let reg = self.ensure_in_vreg(reg, reg_ty);
+                        self.block_succ_args.push(reg);
+                    }
                 }
             }
+            self.block_succs
+                .push((succ, args_start, self.block_succ_args.len()));
         }
-
-        // Now, finally, deal with the moves whose sources are constants.
-        for (ty, dst_reg, const_val) in &const_bundles {
-            for inst in I::gen_constant(*dst_reg, *const_val as u128, *ty, |ty| {
-                self.alloc_tmp(ty).only_reg().unwrap()
-            })
-            .into_iter()
-            {
-                self.emit(inst);
-            }
-        }
-
+        self.block_succ_ranges.push((start, self.block_succs.len()));
         Ok(())
     }
 
@@ -866,7 +781,30 @@ impl<'func, I: VCodeInst> Lower<'func, I> {
     }
 
     fn copy_bbs_to_vcode(&mut self) {
-        for &(start, end) in self.block_ranges.iter().rev() {
+        let f = self.f;
+        let entry = f.layout.entry_block();
+        for (bindex, (&(start, end), &(succ_start, succ_end))) in self
+            .block_ranges
+            .iter()
+            .rev()
+            .zip(self.block_succ_ranges.iter().rev())
+            .enumerate()
+        {
+            // The values of the original block's parameters arrive as block
+            // parameters, except in the entry block, where they are set up by
+            // the ABI code.
+            let lb = self.vcode.block_order().lowered_order()[bindex];
+            if let Some(bb) = lb.orig_block().filter(|&bb| Some(bb) != entry) {
+                for &param in f.dfg.block_params(bb) {
+                    for &reg in self.value_regs[param].regs() {
+                        self.vcode.add_block_param(reg.to_virtual_reg());
+                    }
+                }
+            }
+            for &(succ, args_start, args_end) in &self.block_succs[succ_start..succ_end] {
+                self.vcode
+                    .add_succ(succ, &self.block_succ_args[args_start..args_end]);
+            }
             for &InstTuple {
                 loc,
                 is_safepoint,
@@ -926,10 +864,7 @@ impl<'func, I: VCodeInst> Lower<'func, I> {
     }
 
     /// Lower the function.
-    pub fn lower<B: LowerBackend<MInst = I>>(
-        mut self,
-        backend: &B,
-    ) -> CodegenResult<(VCode<I>, StackmapRequestInfo)> {
+    pub fn lower<B: LowerBackend<MInst = I>>(mut self, backend: &B) -> CodegenResult<VCode<I>> {
         log::trace!("about to lower function: {:?}", self.f);
 
         // Initialize the ABI object, giving it a temp if requested.
@@ -982,21 +917,16 @@ impl<'func, I: VCodeInst> Lower<'func, I> {
                 self.finish_ir_inst(SourceLoc::default());
             }
 
-            // Out-edge phi moves.
-            if let Some((pred, inst, succ)) = lb.out_edge() {
-                self.lower_edge(pred, inst, succ)?;
-                self.finish_ir_inst(SourceLoc::default());
-            }
+            // Branch arguments, computed before the branches themselves so
+            // that they cannot disturb any flags the branches depend on.
+            self.lower_branch_args(bindex)?;
+            self.finish_ir_inst(SourceLoc::default());
+
             // Original block body.
             if let Some(bb) = lb.orig_block() {
                 self.lower_clif_block(backend, bb)?;
                 self.emit_value_label_markers_for_block_args(bb);
             }
-            // In-edge phi moves.
-            if let Some((pred, inst, succ)) = lb.in_edge() {
-                self.lower_edge(pred, inst, succ)?;
-                self.finish_ir_inst(SourceLoc::default());
-            }
 
             if bindex == 0 {
                 // Set up the function with arg vreg inits.
@@ -1010,10 +940,10 @@ impl<'func, I: VCodeInst> Lower<'func, I> {
         self.copy_bbs_to_vcode();
 
         // Now that we've emitted all instructions into the VCodeBuilder, let's build the VCode.
-        let (vcode, stack_map_info) = self.vcode.build();
+        let vcode = self.vcode.build();
         log::trace!("built vcode: {:?}", vcode);
 
-        Ok((vcode, stack_map_info))
+        Ok(vcode)
     }
 }
 
@@ -30,13 +30,16 @@
 //!         |                        - in-memory constants held by insns,
 //!         |                          with unknown offsets.
 //!         |                        - critical edges (actually all edges)
-//!         |                          are split.)
+//!         |                          are split.
+//!         |                        - values flow between blocks through
+//!         |                          block parameters and branch
+//!         |                          arguments.)
 //!         | [regalloc]
 //!         |
 //!     VCode<arch_backend::Inst>   (machine instructions:
 //!         |                        - all real registers.
-//!         |                        - new instruction sequence returned
-//!         |                          out-of-band in RegAllocResult.
+//!         |                        - new instruction sequence built from
+//!         |                          the regalloc2 output.
 //!         |                        - instruction sequence has spills,
 //!         |                          reloads, and moves inserted.
 //!         |                        - other invariants same as above.)
@@ -71,9 +74,7 @@ use alloc::vec::Vec;
 use core::fmt::Debug;
 use cranelift_entity::PrimaryMap;
 use regalloc::RegUsageCollector;
-use regalloc::{
-    RealReg, RealRegUniverse, Reg, RegClass, RegUsageMapper, SpillSlot, VirtualReg, Writable,
-};
+use regalloc::{RealReg, RealRegUniverse, Reg, RegClass, RegUsageMapper, VirtualReg, Writable};
 use smallvec::{smallvec, SmallVec};
 use std::string::String;
 use target_lexicon::Triple;
@@ -151,11 +152,6 @@ pub trait MachInst: Clone + Debug {
         alloc_tmp: F,
     ) -> SmallVec<[Self; 4]>;
 
-    /// Possibly operate on a value directly in a spill-slot rather than a
-    /// register. Useful if the machine has register-memory instruction forms
-    /// (e.g., add directly from or directly to memory), like x86.
-    fn maybe_direct_reload(&self, reg: VirtualReg, slot: SpillSlot) -> Option<Self>;
-
     /// Determine register class(es) to store the given Cranelift type, and the
     /// Cranelift type actually stored in the underlying register(s).  May return
     /// an error if the type isn't supported by this backend.
@@ -193,10 +189,6 @@ pub trait MachInst: Clone + Debug {
     /// What is the worst-case instruction size emitted by this instruction type?
     fn worst_case_size() -> CodeOffset;
 
-    /// What is the register class used for reference types (GC-observable pointers)? Can
-    /// be dependent on compilation flags.
-    fn ref_type_regclass(_flags: &Flags) -> RegClass;
-
     /// Does this instruction define a ValueLabel? Returns the `Reg` whose value
     /// becomes the new value of the `ValueLabel` after this instruction.
     fn defines_value_label(&self) -> Option<(ValueLabel, Reg)> {
@@ -268,6 +260,9 @@ pub enum MachTerminator<'a> {
     None,
     /// A return instruction.
     Ret,
+    /// A tail call, which tears down the current frame and transfers control
+    /// to another function that returns directly to our caller.
+    RetCall,
     /// An unconditional branch to another block.
     Uncond(MachLabel),
     /// A conditional branch to one of two other blocks.
@@ -1,5 +1,7 @@
 use crate::ir::Type;
-use regalloc::{Reg, RegUsageMapper, Writable};
+use alloc::vec::Vec;
+use regalloc::{RealReg, RealRegUniverse, Reg, RegClass, RegUsageMapper, VirtualReg, Writable};
+use regalloc2::{Allocation, MachineEnv, Operand, OperandKind, PReg, VReg};
 use smallvec::SmallVec;
 use std::cell::Cell;
 
@@ -106,3 +108,181 @@ impl RegMapper for RegRenamer {
         self.get_rename(reg, false)
     }
 }
+
+/// The number of `regalloc2` vreg indices reserved for real registers.
+///
+/// Instructions may name real registers directly, e.g. for ABI arguments and
+/// return values or for instructions with fixed register operands. Each such
+/// register is given to `regalloc2` as a vreg pinned to the register's `PReg`,
+/// with the same index as that `PReg`. Virtual registers are numbered after
+/// these.
+pub const PINNED_VREGS: usize = PReg::NUM_INDEX;
+
+/// Get the `regalloc2` register class for a `regalloc` register class.
+pub fn regclass_to_rc2(rc: RegClass) -> regalloc2::RegClass {
+    match rc {
+        RegClass::I32 | RegClass::I64 => regalloc2::RegClass::Int,
+        RegClass::F32 | RegClass::F64 | RegClass::V128 => regalloc2::RegClass::Float,
+        RegClass::INVALID => panic!("invalid register class"),
+    }
+}
+
+/// Get the `regalloc2` vreg for a virtual register.
+pub fn virtual_reg_to_vreg(vreg: VirtualReg) -> VReg {
+    VReg::new(
+        PINNED_VREGS + vreg.get_index(),
+        regclass_to_rc2(vreg.get_class()),
+    )
+}
+
+/// The register environment given to `regalloc2`, derived from a backend's
+/// `RealRegUniverse`.
+#[derive(Debug)]
+pub struct RegEnv {
+    /// The allocatable registers of each class, in order of preference, and
+    /// the scratch register of each class.
+    pub machine_env: MachineEnv,
+    /// The `PReg` of each register in the universe, or `None` if the register
+    /// is not allocatable.
+    pinned: Vec<Option<PReg>>,
+    /// The real register for each `PReg` index.
+    real_regs: Vec<Option<RealReg>>,
+    /// The `regalloc` class of the registers in each `regalloc2` class.
+    classes: [Option<RegClass>; 2],
+}
+
+impl RegEnv {
+    /// Create the environment for `universe`. Registers in `caller_saved` are
+    /// preferred, since using them does not require saving them in the
+    /// prologue.
+    ///
+    /// The `suggested_scratch` register of each class (or its last allocatable
+    /// register, if there is no suggestion) is reserved for the allocator to
+    /// resolve cyclic moves, and is not otherwise allocated.
+    pub fn new(universe: &RealRegUniverse, caller_saved: &[Writable<Reg>]) -> Self {
+        let mut pinned = vec![None; universe.regs.len()];
+        let mut real_regs = vec![None; 2 * (PReg::MAX + 1)];
+        let mut preferred_regs_by_class = [vec![], vec![]];
+        let mut non_preferred_regs_by_class = [vec![], vec![]];
+        let mut scratch_by_class = [PReg::invalid(), PReg::invalid()];
+        let mut classes = [None, None];
+
+        for (rc_index, info) in universe.allocable_by_class.iter().enumerate() {
+            let info = match info {
+                Some(info) => info,
+                None => continue,
+            };
+            let rc = RegClass::rc_from_u32(rc_index as u32);
+            let class = regclass_to_rc2(rc);
+            assert!(
+                classes[class as usize].is_none(),
+                "at most one register class per regalloc2 class"
+            );
+            classes[class as usize] = Some(rc);
+            let scratch = info.suggested_scratch.unwrap_or(info.last);
+            for (hw_enc, index) in (info.first..=info.last).enumerate() {
+                let preg = PReg::new(hw_enc, class);
+                real_regs[preg.index()] = Some(universe.regs[index].0);
+                if index == scratch {
+                    scratch_by_class[class as usize] = preg;
+                    continue;
+                }
+                pinned[index] = Some(preg);
+                let is_caller_saved = caller_saved.iter().any(|r| {
+                    let r = r.to_reg();
+                    r.is_real() && r.get_index() == index
+                });
+                if is_caller_saved {
+                    preferred_regs_by_class[class as usize].push(preg);
+                } else {
+                    non_preferred_regs_by_class[class as usize].push(preg);
+                }
+            }
+        }
+
+        Self {
+            machine_env: MachineEnv {
+                preferred_regs_by_class,
+                non_preferred_regs_by_class,
+                scratch_by_class,
+                fixed_stack_slots: vec![],
+            },
+            pinned,
+            real_regs,
+            classes,
+        }
+    }
+
+    /// Get the `regalloc2` vreg for `reg`, or `None` if `reg` is a real
+    /// register that is not managed by the allocator, such as the stack
+    /// pointer or a scratch register.
+    pub fn vreg(&self, reg: Reg) -> Option<VReg> {
+        match reg.as_virtual_reg() {
+            Some(vreg) => Some(virtual_reg_to_vreg(vreg)),
+            None => self.pinned[reg.get_index()].map(|preg| VReg::new(preg.index(), preg.class())),
+        }
+    }
+
+    /// Get the real register for `preg`.
+    pub fn real_reg(&self, preg: PReg) -> RealReg {
+        self.real_regs[preg.index()].expect("PReg outside of the register universe")
+    }
+
+    /// Get the `regalloc` register class of the registers in `class`, if
+    /// there are any.
+    pub fn regclass(&self, class: regalloc2::RegClass) -> Option<RegClass> {
+        self.classes[class as usize]
+    }
+}
+
+/// Maps the virtual registers of one instruction to the real registers that
+/// `regalloc2` assigned to its operands.
+#[derive(Debug)]
+pub struct AllocationMapper<'a> {
+    env: &'a RegEnv,
+    operands: &'a [Operand],
+    allocs: &'a [Allocation],
+}
+
+impl<'a> AllocationMapper<'a> {
+    /// Create a mapper for an instruction with the given operands and their
+    /// allocations.
+    pub fn new(env: &'a RegEnv, operands: &'a [Operand], allocs: &'a [Allocation]) -> Self {
+        Self {
+            env,
+            operands,
+            allocs,
+        }
+    }
+
+    fn lookup(&self, vreg: VirtualReg, kind: OperandKind) -> Option<RealReg> {
+        let vreg = virtual_reg_to_vreg(vreg);
+        let (_, alloc) = self
+            .operands
+            .iter()
+            .zip(self.allocs)
+            .find(|(op, _)| op.vreg() == vreg && op.kind() == kind)?;
+        let preg = alloc
+            .as_reg()
+            .expect("register operand allocated to a stack slot");
+        Some(self.env.real_reg(preg))
+    }
+}
+
+impl<'a> RegUsageMapper for AllocationMapper<'a> {
+    // A register that an instruction lists as modified as well as used or
+    // defined only has a `Mod` operand; see `VCode::compute_operands()`.
+    fn get_use(&self, vreg: VirtualReg) -> Option<RealReg> {
+        self.lookup(vreg, OperandKind::Use)
+            .or_else(|| self.lookup(vreg, OperandKind::Mod))
+    }
+
+    fn get_def(&self, vreg: VirtualReg) -> Option<RealReg> {
+        self.lookup(vreg, OperandKind::Def)
+            .or_else(|| self.lookup(vreg, OperandKind::Mod))
+    }
+
+    fn get_mod(&self, vreg: VirtualReg) -> Option<RealReg> {
+        self.lookup(vreg, OperandKind::Mod)
+    }
+}
@@ -21,15 +21,12 @@ use crate::ir::{self, types, Constant, ConstantData, SourceLoc};
 use crate::machinst::*;
 use crate::settings;
 use crate::timing;
-use regalloc::Function as RegallocFunction;
 use regalloc::Set as RegallocSet;
-use regalloc::{
-    BlockIx, InstIx, PrettyPrint, Range, RegAllocResult, RegClass, RegUsageCollector,
-    RegUsageMapper, SpillSlot, StackmapRequestInfo,
-};
+use regalloc::{PrettyPrint, RegClass, RegUsageCollector, SpillSlot};
+use regalloc2::{Allocation, Edit, InstOrEdit, Operand, OperandKind, OperandPos, VReg};
 
 use alloc::boxed::Box;
-use alloc::{borrow::Cow, vec::Vec};
+use alloc::vec::Vec;
 use cranelift_entity::{entity_impl, Keys, PrimaryMap};
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -51,10 +48,7 @@ impl<I: MachInst + MachInstEmit> VCodeInst for I {}
 /// This is essentially a standard CFG of basic blocks, where each basic block
 /// consists of lowered instructions produced by the machine-specific backend.
 pub struct VCode<I: VCodeInst> {
-    /// Function liveins.
-    liveins: RegallocSet<RealReg>,
-
-    /// Function liveouts.
+    /// Function liveouts. These are used by every return instruction.
     liveouts: RegallocSet<RealReg>,
 
     /// VReg IR-level types.
@@ -66,6 +60,21 @@ pub struct VCode<I: VCodeInst> {
     /// Lowered machine instructions in order corresponding to the original IR.
     insts: Vec<I>,
 
+    /// Register operands of each instruction, as seen by the register
+    /// allocator: a range in `operands` per instruction. Filled in by
+    /// `compute_operands()`.
+    operand_ranges: Vec<(u32, u32)>,
+
+    /// Operand lists, concatenated into one Vec.
+    operands: Vec<Operand>,
+
+    /// Whether each instruction is a move that the register allocator
+    /// carries out itself. These moves are removed from the final code.
+    is_move: Vec<bool>,
+
+    /// Spill slots needed for a value of each register-allocator class.
+    spillslot_sizes: [u32; 2],
+
     /// Source locations for each instruction. (`SourceLoc` is a `u32`, so it is
     /// reasonable to keep one of these per instruction.)
     srclocs: Vec<SourceLoc>,
@@ -82,7 +91,26 @@ pub struct VCode<I: VCodeInst> {
     /// Block successor lists, concatenated into one Vec. The `block_succ_range`
     /// list of tuples above gives (start, end) ranges within this list that
     /// correspond to each basic block's successors.
-    block_succs: Vec<BlockIx>,
+    block_succs: Vec<regalloc2::Block>,
+
+    /// Block predecessors: index range in the predecessor-list below.
+    block_pred_range: Vec<(usize, usize)>,
+
+    /// Block predecessor lists, concatenated into one Vec.
+    block_preds: Vec<regalloc2::Block>,
+
+    /// Block parameters: index range in the parameter-list below.
+    block_params_range: Vec<(usize, usize)>,
+
+    /// Block parameter lists, concatenated into one Vec.
+    block_params: Vec<VReg>,
+
+    /// Arguments passed to the block parameters of each successor: an index
+    /// range in the argument-list below for each entry in `block_succs`.
+    branch_block_arg_range: Vec<(usize, usize)>,
+
+    /// Branch argument lists, concatenated into one Vec.
+    branch_block_args: Vec<VReg>,
 
     /// Block-order information.
     block_order: BlockLoweringOrder,
@@ -94,9 +122,8 @@ pub struct VCode<I: VCodeInst> {
     /// immutable across function compilations within the same module.
     emit_info: I::Info,
 
-    /// Safepoint instruction indices. Filled in post-regalloc. (Prior to
-    /// regalloc, the safepoint instructions are listed in the separate
-    /// `StackmapRequestInfo` held separate from the `VCode`.)
+    /// Safepoint instruction indices, in ascending order. Prior to regalloc
+    /// these index the lowered instructions; afterwards, the final ones.
     safepoint_insns: Vec<InsnIndex>,
 
     /// For each safepoint entry in `safepoint_insns`, a list of `SpillSlot`s.
@@ -104,6 +131,9 @@ pub struct VCode<I: VCodeInst> {
     /// post-regalloc.
     safepoint_slots: Vec<Vec<SpillSlot>>,
 
+    /// Reference-typed vregs, which must be in spill slots at safepoints.
+    reftyped_vregs: Vec<VReg>,
+
     /// Do we generate debug info?
     generate_debug_info: bool,
 
@@ -137,15 +167,15 @@ pub struct VCodeBuilder<I: VCodeInst> {
     /// In-progress VCode.
     vcode: VCode<I>,
 
-    /// In-progress stack map-request info.
-    stack_map_info: StackmapRequestInfo,
-
     /// Index of the last block-start in the vcode.
     block_start: InsnIndex,
 
     /// Start of succs for the current block in the concatenated succs list.
     succ_start: usize,
 
+    /// Start of params for the current block in the concatenated params list.
+    params_start: usize,
+
     /// Current source location.
     cur_srcloc: SourceLoc,
 }
@@ -158,7 +188,6 @@ impl<I: VCodeInst> VCodeBuilder<I> {
         block_order: BlockLoweringOrder,
         constants: VCodeConstants,
     ) -> VCodeBuilder<I> {
-        let reftype_class = I::ref_type_regclass(abi.flags());
         let vcode = VCode::new(
             abi,
             emit_info,
@@ -166,17 +195,12 @@ impl<I: VCodeInst> VCodeBuilder<I> {
             constants,
             /* generate_debug_info = */ true,
         );
-        let stack_map_info = StackmapRequestInfo {
-            reftype_class,
-            reftyped_vregs: vec![],
-            safepoint_insns: vec![],
-        };
 
         VCodeBuilder {
             vcode,
-            stack_map_info,
             block_start: 0,
             succ_start: 0,
+            params_start: 0,
             cur_srcloc: SourceLoc::default(),
         }
     }
@@ -200,7 +224,7 @@ impl<I: VCodeInst> VCodeBuilder<I> {
         }
         self.vcode.vreg_types[vreg.get_index()] = ty;
         if is_reftype(ty) {
-            self.stack_map_info.reftyped_vregs.push(vreg);
+            self.vcode.reftyped_vregs.push(virtual_reg_to_vreg(vreg));
             self.vcode.have_ref_values = true;
         }
     }
@@ -224,36 +248,48 @@ impl<I: VCodeInst> VCodeBuilder<I> {
             .block_succ_range
             .push((self.succ_start, succ_end));
         self.succ_start = succ_end;
+        // End the block parameter list.
+        let params_end = self.vcode.block_params.len();
+        self.vcode
+            .block_params_range
+            .push((self.params_start, params_end));
+        self.params_start = params_end;
+    }
+
+    /// Add a parameter to the current block. Each predecessor passes a value
+    /// for it along its branch to this block.
+    pub fn add_block_param(&mut self, param: VirtualReg) {
+        self.vcode.block_params.push(virtual_reg_to_vreg(param));
+    }
+
+    /// Add a successor to the current block, along with the values passed to
+    /// the successor's block parameters.
+    pub fn add_succ(&mut self, succ: BlockIndex, args: &[Reg]) {
+        self.vcode
+            .block_succs
+            .push(regalloc2::Block::new(succ as usize));
+        let start = self.vcode.branch_block_args.len();
+        for arg in args {
+            let arg = arg
+                .as_virtual_reg()
+                .expect("branch argument in a real register");
+            self.vcode.branch_block_args.push(virtual_reg_to_vreg(arg));
+        }
+        let end = self.vcode.branch_block_args.len();
+        self.vcode.branch_block_arg_range.push((start, end));
     }
 
     /// Push an instruction for the current BB and current IR inst within the BB.
     pub fn push(&mut self, insn: I, is_safepoint: bool) {
-        match insn.is_term() {
-            MachTerminator::None | MachTerminator::Ret => {}
-            MachTerminator::Uncond(target) => {
-                self.vcode.block_succs.push(BlockIx::new(target.get()));
-            }
-            MachTerminator::Cond(true_branch, false_branch) => {
-                self.vcode.block_succs.push(BlockIx::new(true_branch.get()));
-                self.vcode
-                    .block_succs
-                    .push(BlockIx::new(false_branch.get()));
-            }
-            MachTerminator::Indirect(targets) => {
-                for target in targets {
-                    self.vcode.block_succs.push(BlockIx::new(target.get()));
-                }
-            }
-        }
         if insn.defines_value_label().is_some() {
             self.vcode.has_value_labels = true;
         }
         self.vcode.insts.push(insn);
         self.vcode.srclocs.push(self.cur_srcloc);
         if is_safepoint {
-            self.stack_map_info
+            self.vcode
                 .safepoint_insns
-                .push(InstIx::new((self.vcode.insts.len() - 1) as u32));
+                .push((self.vcode.insts.len() - 1) as InsnIndex);
         }
     }
 
@@ -267,13 +303,15 @@ impl<I: VCodeInst> VCodeBuilder<I> {
         &mut self.vcode.constants
     }
 
-    /// Build the final VCode, returning the vcode itself as well as auxiliary
-    /// information, such as the stack map request information.
-    pub fn build(self) -> (VCode<I>, StackmapRequestInfo) {
-        // TODO: come up with an abstraction for "vcode and auxiliary data". The
-        // auxiliary data needs to be separate from the vcode so that it can be
-        // referenced as the vcode is mutated (e.g. by the register allocator).
-        (self.vcode, self.stack_map_info)
+    /// Build the final VCode.
+    pub fn build(mut self) -> VCode<I> {
+        // Safepoints only matter if there are reference-typed values whose
+        // locations must be recorded in stack maps.
+        if self.vcode.reftyped_vregs.is_empty() {
+            self.vcode.safepoint_insns.clear();
+        }
+        self.vcode.compute_preds();
+        self.vcode
     }
 }
 
@@ -285,6 +323,18 @@ fn is_redundant_move<I: VCodeInst>(insn: &I) -> bool {
     }
 }
 
+/// The type to use for moving a whole register of class `rc`.
+fn move_type(rc: RegClass) -> Type {
+    match rc {
+        RegClass::I32 => types::I32,
+        RegClass::I64 => types::I64,
+        RegClass::F32 => types::F32,
+        RegClass::F64 => types::F64,
+        RegClass::V128 => types::I8X16,
+        RegClass::INVALID => panic!("invalid register class"),
+    }
+}
+
 /// Is this type a reference type?
 fn is_reftype(ty: Type) -> bool {
     ty == types::R64 || ty == types::R32
@@ -300,21 +350,31 @@ impl<I: VCodeInst> VCode<I> {
         generate_debug_info: bool,
     ) -> VCode<I> {
         VCode {
-            liveins: abi.liveins(),
             liveouts: abi.liveouts(),
             vreg_types: vec![],
             have_ref_values: false,
             insts: vec![],
+            operand_ranges: vec![],
+            operands: vec![],
+            is_move: vec![],
+            spillslot_sizes: [0; 2],
             srclocs: vec![],
             entry: 0,
             block_ranges: vec![],
             block_succ_range: vec![],
             block_succs: vec![],
+            block_pred_range: vec![],
+            block_preds: vec![],
+            block_params_range: vec![],
+            block_params: vec![],
+            branch_block_arg_range: vec![],
+            branch_block_args: vec![],
             block_order,
             abi,
             emit_info,
             safepoint_insns: vec![],
             safepoint_slots: vec![],
+            reftyped_vregs: vec![],
             generate_debug_info,
             insts_layout: RefCell::new((vec![], vec![], 0)),
             constants,
@@ -327,9 +387,9 @@ impl<I: VCodeInst> VCode<I> {
         self.abi.flags()
     }
 
-    /// Get the IR-level type of a VReg.
-    pub fn vreg_type(&self, vreg: VirtualReg) -> Type {
-        self.vreg_types[vreg.get_index()]
+    /// Access the ABI object.
+    pub fn abi(&self) -> &dyn ABICallee<I = I> {
+        &*self.abi
     }
 
     /// Get the number of blocks. Block indices will be in the range `0 ..
@@ -344,39 +404,161 @@ impl<I: VCodeInst> VCode<I> {
     }
 
     /// Get the successors for a block.
-    pub fn succs(&self, block: BlockIndex) -> &[BlockIx] {
+    pub fn succs(&self, block: BlockIndex) -> &[regalloc2::Block] {
         let (start, end) = self.block_succ_range[block as usize];
         &self.block_succs[start..end]
     }
 
+    /// Compute the predecessor lists from the successor lists.
+    fn compute_preds(&mut self) {
+        let mut counts = vec![0; self.num_blocks()];
+        for succ in &self.block_succs {
+            counts[succ.index()] += 1;
+        }
+        let mut start = 0;
+        for count in counts {
+            self.block_pred_range.push((start, start));
+            start += count;
+        }
+        self.block_preds = vec![regalloc2::Block::invalid(); start];
+        for block in 0..self.num_blocks() {
+            let (succ_start, succ_end) = self.block_succ_range[block];
+            for i in succ_start..succ_end {
+                let succ = self.block_succs[i].index();
+                let end = &mut self.block_pred_range[succ].1;
+                self.block_preds[*end] = regalloc2::Block::new(block);
+                *end += 1;
+            }
+        }
+    }
+
+    /// Compute the register operands of each instruction, in the form the
+    /// register allocator expects, given the register environment `env`.
+    pub fn compute_operands(&mut self, env: &RegEnv) {
+        for class in [regalloc2::RegClass::Int, regalloc2::RegClass::Float] {
+            if let Some(rc) = env.regclass(class) {
+                self.spillslot_sizes[class as usize] = self.abi.get_spillslot_size(rc);
+            }
+        }
+
+        let mut vecs = RegUsageCollector::get_empty_reg_vecs_test_framework_only(false);
+        self.operand_ranges.clear();
+        self.operands.clear();
+        self.is_move.clear();
+
+        for insn in &self.insts {
+            let start = self.operands.len() as u32;
+            let mut is_move = false;
+
+            // Moves between two allocatable registers are carried out by the
+            // register allocator, which can often coalesce them away.
+            if let Some((dst, src)) = insn.is_move() {
+                if let (Some(dst), Some(src)) = (env.vreg(dst.to_reg()), env.vreg(src)) {
+                    if dst != src && dst.class() == src.class() {
+                        self.operands.push(Operand::reg_use(src));
+                        self.operands.push(Operand::reg_def(dst));
+                        is_move = true;
+                    }
+                }
+            }
+
+            if !is_move {
+                vecs.uses.clear();
+                vecs.defs.clear();
+                vecs.mods.clear();
+                insn.get_regs(&mut RegUsageCollector::new(&mut vecs));
+                for regs in [&mut vecs.uses, &mut vecs.defs, &mut vecs.mods] {
+                    regs.sort_unstable();
+                    regs.dedup();
+                }
+
+                // A register that is modified is neither a plain use nor a
+                // plain def.
+                let mods = &vecs.mods;
+                let operands = &mut self.operands;
+                let mut add = |regs: &[Reg], kind, pos| {
+                    for &reg in regs {
+                        if kind != OperandKind::Mod && mods.binary_search(&reg).is_ok() {
+                            continue;
+                        }
+                        if let Some(vreg) = env.vreg(reg) {
+                            operands.push(Operand::new(
+                                vreg,
+                                regalloc2::OperandConstraint::Reg,
+                                kind,
+                                pos,
+                            ));
+                        }
+                    }
+                };
+                add(&vecs.uses, OperandKind::Use, OperandPos::Early);
+                add(&vecs.defs, OperandKind::Def, OperandPos::Late);
+                add(&vecs.mods, OperandKind::Mod, OperandPos::Early);
+
+                // Return values must stay in their registers until the
+                // return itself.
+                if insn.is_term() == MachTerminator::Ret {
+                    for reg in self.liveouts.iter() {
+                        if let Some(vreg) = env.vreg(reg.to_reg()) {
+                            self.operands.push(Operand::reg_use(vreg));
+                        }
+                    }
+                }
+            }
+
+            self.operand_ranges
+                .push((start, self.operands.len() as u32));
+            self.is_move.push(is_move);
+        }
+    }
+
+    /// Get the register operands of an instruction.
+    fn insn_operands(&self, insn: usize) -> &[Operand] {
+        let (start, end) = self.operand_ranges[insn];
+        &self.operands[start as usize..end as usize]
+    }
+
     /// Take the results of register allocation, with a sequence of
     /// instructions including spliced fill/reload/move instructions, and replace
     /// the VCode with them.
-    pub fn replace_insns_from_regalloc(&mut self, result: RegAllocResult<Self>) {
+    pub fn replace_insns_from_regalloc(&mut self, result: regalloc2::Output, env: &RegEnv) {
         // Record the spillslot count and clobbered registers for the ABI/stack
         // setup code.
-        self.abi.set_num_spillslots(result.num_spill_slots as usize);
-        self.abi
-            .set_clobbered(result.clobbered_registers.map(|r| Writable::from_reg(*r)));
+        self.abi.set_num_spillslots(result.num_spillslots);
+        let mut clobbered = RegallocSet::empty();
+        for (i, insn) in self.insts.iter().enumerate() {
+            if self.is_move[i] || !insn.is_included_in_clobbers() {
+                continue;
+            }
+            let allocs = result.inst_allocs(regalloc2::Inst::new(i));
+            for (op, alloc) in self.insn_operands(i).iter().zip(allocs) {
+                if op.kind() != OperandKind::Use {
+                    if let Some(preg) = alloc.as_reg() {
+                        clobbered.insert(Writable::from_reg(env.real_reg(preg)));
+                    }
+                }
+            }
+        }
+        for (_, edit) in &result.edits {
+            let Edit::Move { to, .. } = edit;
+            if let Some(preg) = to.as_reg() {
+                clobbered.insert(Writable::from_reg(env.real_reg(preg)));
+            }
+        }
+        self.abi.set_clobbered(clobbered);
 
         let mut final_insns = vec![];
         let mut final_block_ranges = vec![(0, 0); self.num_blocks()];
         let mut final_srclocs = vec![];
         let mut final_safepoint_insns = vec![];
+        let mut final_safepoint_slots = vec![];
         let mut safept_idx = 0;
+        let mut safept_slot_idx = 0;
 
-        assert!(result.target_map.elems().len() == self.num_blocks());
         for block in 0..self.num_blocks() {
-            let start = result.target_map.elems()[block].get() as usize;
-            let end = if block == self.num_blocks() - 1 {
-                result.insns.len()
-            } else {
-                result.target_map.elems()[block + 1].get() as usize
-            };
-            let block = block as BlockIndex;
             let final_start = final_insns.len() as InsnIndex;
 
-            if block == self.entry {
+            if block as BlockIndex == self.entry {
                 // Start with the prologue.
                 let prologue = self.abi.gen_prologue();
                 let len = prologue.len();
@@ -384,51 +566,86 @@ impl<I: VCodeInst> VCode<I> {
                 final_srclocs.extend(iter::repeat(SourceLoc::default()).take(len));
             }
 
-            for i in start..end {
-                let insn = &result.insns[i];
+            for inst_or_edit in result.block_insts_and_edits(&*self, regalloc2::Block::new(block)) {
+                let iix = match inst_or_edit {
+                    InstOrEdit::Edit(Edit::Move { from, to }) => {
+                        if let Some(insn) = self.gen_edit_move(*from, *to, env) {
+                            final_insns.push(insn);
+                            final_srclocs.push(SourceLoc::default());
+                        }
+                        continue;
+                    }
+                    InstOrEdit::Inst(iix) => iix.index(),
+                };
+
+                // Moves were turned into edits by the register allocator.
+                if self.is_move[iix] {
+                    continue;
+                }
+
+                let mut insn = self.insts[iix].clone();
+                let mapper = AllocationMapper::new(
+                    env,
+                    self.insn_operands(iix),
+                    result.inst_allocs(regalloc2::Inst::new(iix)),
+                );
+                insn.map_regs(&mapper);
 
-                // Elide redundant moves at this point (we only know what is
+                // Elide moves that became redundant (we only know what is
                 // redundant once registers are allocated).
-                if is_redundant_move(insn) {
+                if is_redundant_move(&insn) {
                     continue;
                 }
 
-                // Is there a srcloc associated with this insn? Look it up based on original
-                // instruction index (if new insn corresponds to some original insn, i.e., is not
-                // an inserted load/spill/move).
-                let orig_iix = result.orig_insn_map[InstIx::new(i as u32)];
-                let srcloc = if orig_iix.is_invalid() {
-                    SourceLoc::default()
-                } else {
-                    self.srclocs[orig_iix.get() as usize]
-                };
+                let srcloc = self.srclocs[iix];
 
                 // Whenever encountering a return instruction, replace it
-                // with the epilogue.
+                // with the epilogue. Tail calls keep their jump to the callee
+                // but need the frame torn down before it.
                 let is_ret = insn.is_term() == MachTerminator::Ret;
+                let is_ret_call = insn.is_term() == MachTerminator::RetCall;
                 if is_ret {
                     let epilogue = self.abi.gen_epilogue();
                     let len = epilogue.len();
                     final_insns.extend(epilogue.into_iter());
                     final_srclocs.extend(iter::repeat(srcloc).take(len));
+                } else if is_ret_call {
+                    let epilogue = self.abi.gen_tail_call_epilogue();
+                    let len = epilogue.len();
+                    final_insns.extend(epilogue.into_iter());
+                    final_srclocs.extend(iter::repeat(srcloc).take(len));
+                    final_insns.push(insn);
+                    final_srclocs.push(srcloc);
                 } else {
-                    final_insns.push(insn.clone());
+                    final_insns.push(insn);
                     final_srclocs.push(srcloc);
                 }
 
                 // Was this instruction a safepoint instruction? Add its final
-                // index to the safepoint insn-index list if so.
-                if safept_idx < result.new_safepoint_insns.len()
-                    && (result.new_safepoint_insns[safept_idx].get() as usize) == i
+                // index to the safepoint insn-index list if so, along with the
+                // spill slots holding reference-typed values across it.
+                if safept_idx < self.safepoint_insns.len()
+                    && self.safepoint_insns[safept_idx] as usize == iix
                 {
-                    let idx = final_insns.len() - 1;
-                    final_safepoint_insns.push(idx as InsnIndex);
+                    let mut slots = vec![];
+                    while safept_slot_idx < result.safepoint_slots.len()
+                        && result.safepoint_slots[safept_slot_idx].0.inst().index() <= iix
+                    {
+                        let (point, alloc) = result.safepoint_slots[safept_slot_idx];
+                        if point.inst().index() == iix {
+                            let slot = alloc.as_stack().expect("reference value not spilled");
+                            slots.push(SpillSlot::new(slot.index() as u32));
+                        }
+                        safept_slot_idx += 1;
+                    }
+                    final_safepoint_insns.push((final_insns.len() - 1) as InsnIndex);
+                    final_safepoint_slots.push(slots);
                     safept_idx += 1;
                 }
             }
 
             let final_end = final_insns.len() as InsnIndex;
-            final_block_ranges[block as usize] = (final_start, final_end);
+            final_block_ranges[block] = (final_start, final_end);
         }
 
         debug_assert!(final_insns.len() == final_srclocs.len());
@@ -441,7 +658,39 @@ impl<I: VCodeInst> VCode<I> {
         // Save safepoint slot-lists. These will be passed to the `EmitState`
         // for the machine backend during emission so that it can do
         // target-specific translations of slot numbers to stack offsets.
-        self.safepoint_slots = result.stackmaps;
+        self.safepoint_slots = final_safepoint_slots;
+    }
+
+    /// Generate the instruction for a move inserted by the register allocator,
+    /// if any is needed.
+    fn gen_edit_move(&self, from: Allocation, to: Allocation, env: &RegEnv) -> Option<I> {
+        match (from.as_reg(), to.as_reg()) {
+            (Some(from), Some(to)) => {
+                if from == to {
+                    return None;
+                }
+                let ty = move_type(env.regclass(from.class()).unwrap());
+                Some(I::gen_move(
+                    Writable::from_reg(env.real_reg(to).to_reg()),
+                    env.real_reg(from).to_reg(),
+                    ty,
+                ))
+            }
+            (Some(from), None) => {
+                let slot = to.as_stack().expect("move to an invalid allocation");
+                let slot = SpillSlot::new(slot.index() as u32);
+                Some(self.abi.gen_spill(slot, env.real_reg(from)))
+            }
+            (None, Some(to)) => {
+                let slot = from.as_stack().expect("move from an invalid allocation");
+                let slot = SpillSlot::new(slot.index() as u32);
+                Some(
+                    self.abi
+                        .gen_reload(Writable::from_reg(env.real_reg(to)), slot),
+                )
+            }
+            (None, None) => panic!("stack-to-stack move from the register allocator"),
+        }
     }
 
     /// Emit the instructions to a `MachBuffer`, containing fixed-up code and external
@@ -592,9 +841,9 @@ impl<I: VCodeInst> VCode<I> {
 
                 final_bb_starts.push(from);
                 // Resolve each `succ` label and add edges.
-                let succs = self.block_succs(BlockIx::new(block as u32));
-                for succ in succs.iter() {
-                    let to = buffer.resolve_label_offset(MachLabel::from_block(succ.get()));
+                for succ in self.succs(block as BlockIndex) {
+                    let to = buffer
+                        .resolve_label_offset(MachLabel::from_block(succ.index() as BlockIndex));
                     bb_edges.push((from, to));
                 }
             }
@@ -624,106 +873,115 @@ impl<I: VCodeInst> VCode<I> {
     }
 }
 
-impl<I: VCodeInst> RegallocFunction for VCode<I> {
-    type Inst = I;
-
-    fn insns(&self) -> &[I] {
-        &self.insts[..]
-    }
-
-    fn insns_mut(&mut self) -> &mut [I] {
-        &mut self.insts[..]
+impl<I: VCodeInst> regalloc2::Function for VCode<I> {
+    fn num_insts(&self) -> usize {
+        self.insts.len()
     }
 
-    fn get_insn(&self, insn: InstIx) -> &I {
-        &self.insts[insn.get() as usize]
+    fn num_blocks(&self) -> usize {
+        self.block_ranges.len()
     }
 
-    fn get_insn_mut(&mut self, insn: InstIx) -> &mut I {
-        &mut self.insts[insn.get() as usize]
+    fn entry_block(&self) -> regalloc2::Block {
+        regalloc2::Block::new(self.entry as usize)
     }
 
-    fn blocks(&self) -> Range<BlockIx> {
-        Range::new(BlockIx::new(0), self.block_ranges.len())
+    fn block_insns(&self, block: regalloc2::Block) -> regalloc2::InstRange {
+        let (start, end) = self.block_ranges[block.index()];
+        regalloc2::InstRange::forward(
+            regalloc2::Inst::new(start as usize),
+            regalloc2::Inst::new(end as usize),
+        )
     }
 
-    fn entry_block(&self) -> BlockIx {
-        BlockIx::new(self.entry)
+    fn block_succs(&self, block: regalloc2::Block) -> &[regalloc2::Block] {
+        let (start, end) = self.block_succ_range[block.index()];
+        &self.block_succs[start..end]
     }
 
-    fn block_insns(&self, block: BlockIx) -> Range<InstIx> {
-        let (start, end) = self.block_ranges[block.get() as usize];
-        Range::new(InstIx::new(start), (end - start) as usize)
+    fn block_preds(&self, block: regalloc2::Block) -> &[regalloc2::Block] {
+        let (start, end) = self.block_pred_range[block.index()];
+        &self.block_preds[start..end]
     }
 
-    fn block_succs(&self, block: BlockIx) -> Cow<[BlockIx]> {
-        let (start, end) = self.block_succ_range[block.get() as usize];
-        Cow::Borrowed(&self.block_succs[start..end])
+    fn block_params(&self, block: regalloc2::Block) -> &[VReg] {
+        let (start, end) = self.block_params_range[block.index()];
+        &self.block_params[start..end]
     }
 
-    fn is_ret(&self, insn: InstIx) -> bool {
-        match self.insts[insn.get() as usize].is_term() {
-            MachTerminator::Ret => true,
+    fn is_ret(&self, insn: regalloc2::Inst) -> bool {
+        match self.insts[insn.index()].is_term() {
+            MachTerminator::Ret | MachTerminator::RetCall => true,
             _ => false,
         }
     }
 
-    fn is_included_in_clobbers(&self, insn: &I) -> bool {
-        insn.is_included_in_clobbers()
-    }
-
-    fn get_regs(insn: &I, collector: &mut RegUsageCollector) {
-        insn.get_regs(collector)
-    }
-
-    fn map_regs<RUM: RegUsageMapper>(insn: &mut I, mapper: &RUM) {
-        insn.map_regs(mapper);
+    fn is_branch(&self, insn: regalloc2::Inst) -> bool {
+        match self.insts[insn.index()].is_term() {
+            MachTerminator::Uncond(..)
+            | MachTerminator::Cond(..)
+            | MachTerminator::Indirect(..) => true,
+            _ => false,
+        }
     }
 
-    fn is_move(&self, insn: &I) -> Option<(Writable<Reg>, Reg)> {
-        insn.is_move()
+    fn branch_blockparams(
+        &self,
+        block: regalloc2::Block,
+        _insn: regalloc2::Inst,
+        succ_idx: usize,
+    ) -> &[VReg] {
+        let (succ_start, _) = self.block_succ_range[block.index()];
+        let (start, end) = self.branch_block_arg_range[succ_start + succ_idx];
+        &self.branch_block_args[start..end]
     }
 
-    fn get_num_vregs(&self) -> usize {
-        self.vreg_types.len()
+    fn requires_refs_on_stack(&self, insn: regalloc2::Inst) -> bool {
+        self.safepoint_insns
+            .binary_search(&(insn.index() as InsnIndex))
+            .is_ok()
     }
 
-    fn get_spillslot_size(&self, regclass: RegClass, _: VirtualReg) -> u32 {
-        self.abi.get_spillslot_size(regclass)
+    fn is_move(&self, insn: regalloc2::Inst) -> Option<(Operand, Operand)> {
+        if self.is_move[insn.index()] {
+            let operands = self.insn_operands(insn.index());
+            Some((operands[0], operands[1]))
+        } else {
+            None
+        }
     }
 
-    fn gen_spill(&self, to_slot: SpillSlot, from_reg: RealReg, _: Option<VirtualReg>) -> I {
-        self.abi.gen_spill(to_slot, from_reg)
+    fn inst_operands(&self, insn: regalloc2::Inst) -> &[Operand] {
+        self.insn_operands(insn.index())
     }
 
-    fn gen_reload(
-        &self,
-        to_reg: Writable<RealReg>,
-        from_slot: SpillSlot,
-        _: Option<VirtualReg>,
-    ) -> I {
-        self.abi.gen_reload(to_reg, from_slot)
+    fn inst_clobbers(&self, _insn: regalloc2::Inst) -> &[regalloc2::PReg] {
+        &[]
     }
 
-    fn gen_move(&self, to_reg: Writable<RealReg>, from_reg: RealReg, vreg: VirtualReg) -> I {
-        let ty = self.vreg_type(vreg);
-        I::gen_move(to_reg.map(|r| r.to_reg()), from_reg.to_reg(), ty)
+    fn num_vregs(&self) -> usize {
+        PINNED_VREGS + self.vreg_types.len()
     }
 
-    fn gen_zero_len_nop(&self) -> I {
-        I::gen_nop(0)
+    fn reftype_vregs(&self) -> &[VReg] {
+        &self.reftyped_vregs[..]
     }
 
-    fn maybe_direct_reload(&self, insn: &I, reg: VirtualReg, slot: SpillSlot) -> Option<I> {
-        insn.maybe_direct_reload(reg, slot)
+    fn is_pinned_vreg(&self, vreg: VReg) -> Option<regalloc2::PReg> {
+        // See `RegEnv::vreg()` for the numbering of pinned vregs.
+        if vreg.vreg() < PINNED_VREGS {
+            Some(regalloc2::PReg::from_index(vreg.vreg()))
+        } else {
+            None
+        }
     }
 
-    fn func_liveins(&self) -> RegallocSet<RealReg> {
-        self.liveins.clone()
+    fn spillslot_size(&self, regclass: regalloc2::RegClass) -> usize {
+        self.spillslot_sizes[regclass as usize] as usize
     }
 
-    fn func_liveouts(&self) -> RegallocSet<RealReg> {
-        self.liveouts.clone()
+    fn allow_multiple_vreg_defs(&self) -> bool {
+        true
     }
 }
 
@@ -735,7 +993,7 @@ impl<I: VCodeInst> fmt::Debug for VCode<I> {
         for block in 0..self.num_blocks() {
             writeln!(f, "Block {}:", block,)?;
             for succ in self.succs(block as BlockIndex) {
-                writeln!(f, "  (successor: Block {})", succ.get())?;
+                writeln!(f, "  (successor: Block {})", succ.index())?;
             }
             let (start, end) = self.block_ranges[block];
             writeln!(f, "  (instruction range: {} .. {})", start, end)?;
@@ -768,7 +1026,7 @@ impl<I: VCodeInst> PrettyPrint for VCode<I> {
                 write!(&mut s, "  (original IR block: {})\n", bb).unwrap();
             }
             for succ in self.succs(block) {
-                write!(&mut s, "  (successor: Block {})\n", succ.get()).unwrap();
+                write!(&mut s, "  (successor: Block {})\n", succ.index()).unwrap();
             }
             let (start, end) = self.block_ranges[block as usize];
             write!(&mut s, "  (instruction range: {} .. {})\n", start, end).unwrap();
@@ -0,0 +1,375 @@
+//! ISLE integration glue code for the mid-end optimizer's rewrite rules.
+
+// Pull in the ISLE generated code.
+#[allow(unused)]
+mod generated_code;
+
+use crate::divconst_magic_numbers::{magic_s32, magic_s64, magic_u32, magic_u64};
+use crate::divconst_magic_numbers::{MS32, MS64, MU32, MU64};
+use crate::egraph::EGraph;
+use crate::ir::condcodes::{FloatCC, IntCC};
+use crate::ir::{
+    immediates::*, types::*, Constant, FuncRef, GlobalValue, Heap, Immediate, InstructionData,
+    Opcode, StackSlot, Table, Type, Value,
+};
+use crate::machinst::isle::{ValueArray2, ValueArray3};
+use generated_code::{
+    constructor_band, constructor_iadd, constructor_iconst_s, constructor_iconst_u,
+    constructor_imul, constructor_ineg, constructor_isub, constructor_smulhi, constructor_sshr,
+    constructor_umulhi, constructor_ushr,
+};
+
+/// Rewrite the freshly inserted pure node `value` into a cheaper equivalent value, if any
+/// rewrite rule applies to it.
+pub(crate) fn simplify(egraph: &mut EGraph, value: Value) -> Option<Value> {
+    generated_code::constructor_simplify(&mut IsleContext { egraph }, value)
+}
+
+struct IsleContext<'a, 'b> {
+    egraph: &'a mut EGraph<'b>,
+}
+
+/// Get the bits of `x` as a constant of type `ty`, truncated to the type's width.
+fn mask(ty: Type, x: u64) -> u64 {
+    x & (u64::MAX >> (64 - ty.bits()))
+}
+
+/// Sign-extend the constant `x` of type `ty`.
+fn sign_extend(ty: Type, x: u64) -> i64 {
+    let shift = 64 - ty.bits();
+    ((x << shift) as i64) >> shift
+}
+
+impl IsleContext<'_, '_> {
+    /// Build the quotient of the unsigned division of `x` by the constant `d`, which is neither
+    /// zero nor a power of two.
+    fn udiv_quotient(&mut self, ty: Type, x: Value, d: u64) -> Value {
+        debug_assert!(d >= 3 && !d.is_power_of_two());
+        let (mul_by, do_add, shift_by) = if ty == I32 {
+            let MU32 {
+                mul_by,
+                do_add,
+                shift_by,
+            } = magic_u32(d as u32);
+            (u64::from(mul_by), do_add, shift_by as u64)
+        } else {
+            let MU64 {
+                mul_by,
+                do_add,
+                shift_by,
+            } = magic_u64(d);
+            (mul_by, do_add, shift_by as u64)
+        };
+
+        let magic = constructor_iconst_u(self, ty, mul_by).unwrap();
+        let q1 = constructor_umulhi(self, ty, x, magic).unwrap();
+        if do_add {
+            let t1 = constructor_isub(self, ty, x, q1).unwrap();
+            let one = constructor_iconst_u(self, ty, 1).unwrap();
+            let t2 = constructor_ushr(self, ty, t1, one).unwrap();
+            let t3 = constructor_iadd(self, ty, t2, q1).unwrap();
+            let shift = constructor_iconst_u(self, ty, shift_by - 1).unwrap();
+            constructor_ushr(self, ty, t3, shift).unwrap()
+        } else if shift_by > 0 {
+            let shift = constructor_iconst_u(self, ty, shift_by).unwrap();
+            constructor_ushr(self, ty, q1, shift).unwrap()
+        } else {
+            q1
+        }
+    }
+
+    /// Build the quotient or the remainder of the signed division of `x` by the constant `d`,
+    /// which is none of -1, 0 and 1.
+    fn sdiv_or_srem(&mut self, ty: Type, x: Value, d: i64, is_rem: bool) -> Value {
+        debug_assert!(!(-1..=1).contains(&d));
+        let bits = u64::from(ty.bits());
+
+        // Is `d` a power of two, or the negation of one?
+        let power_of_two = if d == i64::MIN >> (64 - bits) {
+            Some((true, bits - 1))
+        } else if (d.unsigned_abs()).is_power_of_two() {
+            Some((d < 0, u64::from(d.unsigned_abs().trailing_zeros())))
+        } else {
+            None
+        };
+
+        if let Some((is_negative, k)) = power_of_two {
+            // Round towards zero by adding `2^k - 1` to negative dividends before shifting.
+            let t1 = if k == 1 {
+                x
+            } else {
+                let shift = constructor_iconst_u(self, ty, k - 1).unwrap();
+                constructor_sshr(self, ty, x, shift).unwrap()
+            };
+            let shift = constructor_iconst_u(self, ty, bits - k).unwrap();
+            let t2 = constructor_ushr(self, ty, t1, shift).unwrap();
+            let t3 = constructor_iadd(self, ty, x, t2).unwrap();
+            if is_rem {
+                // The sign of `d` doesn't matter for the remainder.
+                let mask = constructor_iconst_s(self, ty, -1 << k).unwrap();
+                let t4 = constructor_band(self, ty, t3, mask).unwrap();
+                constructor_isub(self, ty, x, t4).unwrap()
+            } else {
+                let shift = constructor_iconst_u(self, ty, k).unwrap();
+                let t4 = constructor_sshr(self, ty, t3, shift).unwrap();
+                if is_negative {
+                    constructor_ineg(self, ty, t4).unwrap()
+                } else {
+                    t4
+                }
+            }
+        } else {
+            let (mul_by, shift_by) = if ty == I32 {
+                let MS32 { mul_by, shift_by } = magic_s32(d as i32);
+                (i64::from(mul_by), shift_by as u64)
+            } else {
+                let MS64 { mul_by, shift_by } = magic_s64(d);
+                (mul_by, shift_by as u64)
+            };
+
+            let magic = constructor_iconst_s(self, ty, mul_by).unwrap();
+            let q1 = constructor_smulhi(self, ty, x, magic).unwrap();
+            let q2 = if d > 0 && mul_by < 0 {
+                constructor_iadd(self, ty, q1, x).unwrap()
+            } else if d < 0 && mul_by > 0 {
+                constructor_isub(self, ty, q1, x).unwrap()
+            } else {
+                q1
+            };
+            let q3 = if shift_by == 0 {
+                q2
+            } else {
+                let shift = constructor_iconst_u(self, ty, shift_by).unwrap();
+                constructor_sshr(self, ty, q2, shift).unwrap()
+            };
+            // Add one to negative quotients, to round towards zero.
+            let shift = constructor_iconst_u(self, ty, bits - 1).unwrap();
+            let t1 = constructor_ushr(self, ty, q3, shift).unwrap();
+            let quotient = constructor_iadd(self, ty, q3, t1).unwrap();
+            if is_rem {
+                self.remainder(ty, x, quotient, d as u64)
+            } else {
+                quotient
+            }
+        }
+    }
+
+    /// Build the remainder `x - quotient * d` of a division by the constant `d`.
+    fn remainder(&mut self, ty: Type, x: Value, quotient: Value, d: u64) -> Value {
+        let d = constructor_iconst_u(self, ty, d).unwrap();
+        let t = constructor_imul(self, ty, quotient, d).unwrap();
+        constructor_isub(self, ty, x, t).unwrap()
+    }
+}
+
+impl generated_code::Context for IsleContext<'_, '_> {
+    #[inline]
+    fn unpack_value_array_2(&mut self, arr: &ValueArray2) -> (Value, Value) {
+        let [a, b] = *arr;
+        (a, b)
+    }
+
+    #[inline]
+    fn pack_value_array_2(&mut self, a: Value, b: Value) -> ValueArray2 {
+        [a, b]
+    }
+
+    #[inline]
+    fn unpack_value_array_3(&mut self, arr: &ValueArray3) -> (Value, Value, Value) {
+        let [a, b, c] = *arr;
+        (a, b, c)
+    }
+
+    #[inline]
+    fn pack_value_array_3(&mut self, a: Value, b: Value, c: Value) -> ValueArray3 {
+        [a, b, c]
+    }
+
+    #[inline]
+    fn inst_data(&mut self, value: Value) -> Option<(Type, InstructionData)> {
+        self.egraph.node_data(value)
+    }
+
+    #[inline]
+    fn make_inst(&mut self, ty: Type, data: &InstructionData) -> Value {
+        self.egraph.make_node(ty, data.clone())
+    }
+
+    #[inline]
+    fn has_type(&mut self, value: Value) -> (Type, Value) {
+        (self.egraph.func.dfg.value_type(value), value)
+    }
+
+    #[inline]
+    fn ty_32_or_64(&mut self, ty: Type) -> Option<Type> {
+        if ty == I32 || ty == I64 {
+            Some(ty)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn int_fits_in_64(&mut self, ty: Type) -> Option<Type> {
+        if ty.is_int() && ty.bits() <= 64 {
+            Some(ty)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn ty_bits(&mut self, ty: Type) -> u8 {
+        ty.bits() as u8
+    }
+
+    #[inline]
+    fn iconst_u(&mut self, value: Value) -> Option<(Type, u64)> {
+        self.egraph.iconst_value(value)
+    }
+
+    #[inline]
+    fn iconst_s(&mut self, value: Value) -> Option<(Type, i64)> {
+        let (ty, bits) = self.egraph.iconst_value(value)?;
+        Some((ty, sign_extend(ty, bits)))
+    }
+
+    #[inline]
+    fn imm64_masked(&mut self, ty: Type, x: u64) -> Imm64 {
+        Imm64::new(mask(ty, x) as i64)
+    }
+
+    #[inline]
+    fn u64_add(&mut self, x: u64, y: u64) -> u64 {
+        x.wrapping_add(y)
+    }
+
+    #[inline]
+    fn u64_sub(&mut self, x: u64, y: u64) -> u64 {
+        x.wrapping_sub(y)
+    }
+
+    #[inline]
+    fn u64_mul(&mut self, x: u64, y: u64) -> u64 {
+        x.wrapping_mul(y)
+    }
+
+    #[inline]
+    fn u64_and(&mut self, x: u64, y: u64) -> u64 {
+        x & y
+    }
+
+    #[inline]
+    fn u64_or(&mut self, x: u64, y: u64) -> u64 {
+        x | y
+    }
+
+    #[inline]
+    fn u64_xor(&mut self, x: u64, y: u64) -> u64 {
+        x ^ y
+    }
+
+    #[inline]
+    fn u64_not(&mut self, x: u64) -> u64 {
+        !x
+    }
+
+    #[inline]
+    fn u64_neg(&mut self, x: u64) -> u64 {
+        x.wrapping_neg()
+    }
+
+    #[inline]
+    fn u64_udiv(&mut self, x: u64, y: u64) -> u64 {
+        x / y
+    }
+
+    #[inline]
+    fn u64_urem(&mut self, x: u64, y: u64) -> u64 {
+        x % y
+    }
+
+    #[inline]
+    fn u64_shl(&mut self, ty: Type, x: u64, y: u64) -> u64 {
+        x << (y % u64::from(ty.bits()))
+    }
+
+    #[inline]
+    fn u64_ushr(&mut self, ty: Type, x: u64, y: u64) -> u64 {
+        mask(ty, x) >> (y % u64::from(ty.bits()))
+    }
+
+    #[inline]
+    fn u64_sshr(&mut self, ty: Type, x: u64, y: u64) -> u64 {
+        (sign_extend(ty, x) >> (y % u64::from(ty.bits()))) as u64
+    }
+
+    #[inline]
+    fn i64_as_u64(&mut self, x: i64) -> u64 {
+        x as u64
+    }
+
+    #[inline]
+    fn u64_sextend(&mut self, ty: Type, x: u64) -> u64 {
+        sign_extend(ty, x) as u64
+    }
+
+    fn intcc_eval(&mut self, ty: Type, cc: &IntCC, x: u64, y: u64) -> bool {
+        let (ux, uy) = (mask(ty, x), mask(ty, y));
+        let (sx, sy) = (sign_extend(ty, x), sign_extend(ty, y));
+        match cc {
+            IntCC::Equal => ux == uy,
+            IntCC::NotEqual => ux != uy,
+            IntCC::SignedLessThan => sx < sy,
+            IntCC::SignedGreaterThanOrEqual => sx >= sy,
+            IntCC::SignedGreaterThan => sx > sy,
+            IntCC::SignedLessThanOrEqual => sx <= sy,
+            IntCC::UnsignedLessThan => ux < uy,
+            IntCC::UnsignedGreaterThanOrEqual => ux >= uy,
+            IntCC::UnsignedGreaterThan => ux > uy,
+            IntCC::UnsignedLessThanOrEqual => ux <= uy,
+            IntCC::Overflow | IntCC::NotOverflow => {
+                // Does the signed subtraction `x - y` overflow?
+                let diff = i128::from(sx) - i128::from(sy);
+                let bits = ty.bits() - 1;
+                let overflow = diff < -(1i128 << bits) || diff >= (1i128 << bits);
+                overflow == (*cc == IntCC::Overflow)
+            }
+        }
+    }
+
+    #[inline]
+    fn u64_nonzero(&mut self, x: u64) -> Option<u64> {
+        if x != 0 {
+            Some(x)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn u64_power_of_two(&mut self, x: u64) -> Option<u64> {
+        if x.is_power_of_two() {
+            Some(u64::from(x.trailing_zeros()))
+        } else {
+            None
+        }
+    }
+
+    fn udiv_by_const(&mut self, ty: Type, x: Value, d: u64) -> Value {
+        self.udiv_quotient(ty, x, d)
+    }
+
+    fn urem_by_const(&mut self, ty: Type, x: Value, d: u64) -> Value {
+        let quotient = self.udiv_quotient(ty, x, d);
+        self.remainder(ty, x, quotient, d)
+    }
+
+    fn sdiv_by_const(&mut self, ty: Type, x: Value, d: i64) -> Value {
+        self.sdiv_or_srem(ty, x, d, false)
+    }
+
+    fn srem_by_const(&mut self, ty: Type, x: Value, d: i64) -> Value {
+        self.sdiv_or_srem(ty, x, d, true)
+    }
+}
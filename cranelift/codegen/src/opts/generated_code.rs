@@ -0,0 +1,3613 @@
+// GENERATED BY ISLE. DO NOT EDIT!
+//
+// Generated automatically from the instruction-selection DSL code in:
+// - src/clif_opt.isle
+// - src/prelude_opt.isle
+// - src/opts/algebraic.isle
+// - src/opts/cprop.isle
+
+#![allow(dead_code, unreachable_code, unreachable_patterns)]
+#![allow(unused_imports, unused_variables, non_snake_case)]
+#![allow(irrefutable_let_patterns)]
+
+use super::*; // Pulls in all external types.
+
+/// Context during lowering: an implementation of this trait
+/// must be provided with all external constructors and extractors.
+/// A mutable borrow is passed along through all lowering logic.
+pub trait Context {
+    fn unpack_value_array_2(&mut self, arg0: &ValueArray2) -> (Value, Value);
+    fn pack_value_array_2(&mut self, arg0: Value, arg1: Value) -> ValueArray2;
+    fn unpack_value_array_3(&mut self, arg0: &ValueArray3) -> (Value, Value, Value);
+    fn pack_value_array_3(&mut self, arg0: Value, arg1: Value, arg2: Value) -> ValueArray3;
+    fn inst_data(&mut self, arg0: Value) -> Option<(Type, InstructionData)>;
+    fn make_inst(&mut self, arg0: Type, arg1: &InstructionData) -> Value;
+    fn has_type(&mut self, arg0: Value) -> (Type, Value);
+    fn ty_32_or_64(&mut self, arg0: Type) -> Option<Type>;
+    fn int_fits_in_64(&mut self, arg0: Type) -> Option<Type>;
+    fn ty_bits(&mut self, arg0: Type) -> u8;
+    fn iconst_u(&mut self, arg0: Value) -> Option<(Type, u64)>;
+    fn iconst_s(&mut self, arg0: Value) -> Option<(Type, i64)>;
+    fn imm64_masked(&mut self, arg0: Type, arg1: u64) -> Imm64;
+    fn u64_add(&mut self, arg0: u64, arg1: u64) -> u64;
+    fn u64_sub(&mut self, arg0: u64, arg1: u64) -> u64;
+    fn u64_mul(&mut self, arg0: u64, arg1: u64) -> u64;
+    fn u64_and(&mut self, arg0: u64, arg1: u64) -> u64;
+    fn u64_or(&mut self, arg0: u64, arg1: u64) -> u64;
+    fn u64_xor(&mut self, arg0: u64, arg1: u64) -> u64;
+    fn u64_not(&mut self, arg0: u64) -> u64;
+    fn u64_neg(&mut self, arg0: u64) -> u64;
+    fn u64_udiv(&mut self, arg0: u64, arg1: u64) -> u64;
+    fn u64_urem(&mut self, arg0: u64, arg1: u64) -> u64;
+    fn u64_shl(&mut self, arg0: Type, arg1: u64, arg2: u64) -> u64;
+    fn u64_ushr(&mut self, arg0: Type, arg1: u64, arg2: u64) -> u64;
+    fn u64_sshr(&mut self, arg0: Type, arg1: u64, arg2: u64) -> u64;
+    fn i64_as_u64(&mut self, arg0: i64) -> u64;
+    fn u64_sextend(&mut self, arg0: Type, arg1: u64) -> u64;
+    fn intcc_eval(&mut self, arg0: Type, arg1: &IntCC, arg2: u64, arg3: u64) -> bool;
+    fn u64_nonzero(&mut self, arg0: u64) -> Option<u64>;
+    fn u64_power_of_two(&mut self, arg0: u64) -> Option<u64>;
+    fn udiv_by_const(&mut self, arg0: Type, arg1: Value, arg2: u64) -> Value;
+    fn urem_by_const(&mut self, arg0: Type, arg1: Value, arg2: u64) -> Value;
+    fn sdiv_by_const(&mut self, arg0: Type, arg1: Value, arg2: i64) -> Value;
+    fn srem_by_const(&mut self, arg0: Type, arg1: Value, arg2: i64) -> Value;
+}
+
+// Generated as internal constructor for term func_addr.
+pub fn constructor_func_addr<C: Context>(ctx: &mut C, arg0: Type, arg1: FuncRef) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 401.
+    let expr0_0 = Opcode::FuncAddr;
+    let expr1_0 = InstructionData::FuncAddr {
+        opcode: expr0_0,
+        func_ref: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term splat.
+pub fn constructor_splat<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 409.
+    let expr0_0 = Opcode::Splat;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term swizzle.
+pub fn constructor_swizzle<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 417.
+    let expr0_0 = Opcode::Swizzle;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term insertlane.
+pub fn constructor_insertlane<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+    arg3: Uimm8,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/clif_opt.isle line 425.
+    let expr0_0 = Opcode::Insertlane;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::TernaryImm8 {
+        opcode: expr0_0,
+        args: expr1_0,
+        imm: pattern3_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term extractlane.
+pub fn constructor_extractlane<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Uimm8,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 433.
+    let expr0_0 = Opcode::Extractlane;
+    let expr1_0 = InstructionData::BinaryImm8 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term imin.
+pub fn constructor_imin<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 441.
+    let expr0_0 = Opcode::Imin;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term umin.
+pub fn constructor_umin<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 449.
+    let expr0_0 = Opcode::Umin;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term imax.
+pub fn constructor_imax<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 457.
+    let expr0_0 = Opcode::Imax;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term umax.
+pub fn constructor_umax<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 465.
+    let expr0_0 = Opcode::Umax;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term avg_round.
+pub fn constructor_avg_round<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 473.
+    let expr0_0 = Opcode::AvgRound;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term uadd_sat.
+pub fn constructor_uadd_sat<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 481.
+    let expr0_0 = Opcode::UaddSat;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term sadd_sat.
+pub fn constructor_sadd_sat<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 489.
+    let expr0_0 = Opcode::SaddSat;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term usub_sat.
+pub fn constructor_usub_sat<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 497.
+    let expr0_0 = Opcode::UsubSat;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term ssub_sat.
+pub fn constructor_ssub_sat<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 505.
+    let expr0_0 = Opcode::SsubSat;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term stack_addr.
+pub fn constructor_stack_addr<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: StackSlot,
+    arg2: Offset32,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 513.
+    let expr0_0 = Opcode::StackAddr;
+    let expr1_0 = InstructionData::StackLoad {
+        opcode: expr0_0,
+        stack_slot: pattern1_0,
+        offset: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term global_value.
+pub fn constructor_global_value<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: GlobalValue,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 521.
+    let expr0_0 = Opcode::GlobalValue;
+    let expr1_0 = InstructionData::UnaryGlobalValue {
+        opcode: expr0_0,
+        global_value: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term symbol_value.
+pub fn constructor_symbol_value<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: GlobalValue,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 529.
+    let expr0_0 = Opcode::SymbolValue;
+    let expr1_0 = InstructionData::UnaryGlobalValue {
+        opcode: expr0_0,
+        global_value: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term tls_value.
+pub fn constructor_tls_value<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: GlobalValue,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 537.
+    let expr0_0 = Opcode::TlsValue;
+    let expr1_0 = InstructionData::UnaryGlobalValue {
+        opcode: expr0_0,
+        global_value: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term heap_addr.
+pub fn constructor_heap_addr<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Heap,
+    arg2: Value,
+    arg3: Uimm32,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/clif_opt.isle line 545.
+    let expr0_0 = Opcode::HeapAddr;
+    let expr1_0 = InstructionData::HeapAddr {
+        opcode: expr0_0,
+        arg: pattern2_0,
+        heap: pattern1_0,
+        imm: pattern3_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term table_addr.
+pub fn constructor_table_addr<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Table,
+    arg2: Value,
+    arg3: Offset32,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/clif_opt.isle line 553.
+    let expr0_0 = Opcode::TableAddr;
+    let expr1_0 = InstructionData::TableAddr {
+        opcode: expr0_0,
+        arg: pattern2_0,
+        table: pattern1_0,
+        offset: pattern3_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term iconst.
+pub fn constructor_iconst<C: Context>(ctx: &mut C, arg0: Type, arg1: Imm64) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 561.
+    let expr0_0 = Opcode::Iconst;
+    let expr1_0 = InstructionData::UnaryImm {
+        opcode: expr0_0,
+        imm: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term f32const.
+pub fn constructor_f32const<C: Context>(ctx: &mut C, arg0: Type, arg1: Ieee32) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 569.
+    let expr0_0 = Opcode::F32const;
+    let expr1_0 = InstructionData::UnaryIeee32 {
+        opcode: expr0_0,
+        imm: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term f64const.
+pub fn constructor_f64const<C: Context>(ctx: &mut C, arg0: Type, arg1: Ieee64) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 577.
+    let expr0_0 = Opcode::F64const;
+    let expr1_0 = InstructionData::UnaryIeee64 {
+        opcode: expr0_0,
+        imm: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term bconst.
+pub fn constructor_bconst<C: Context>(ctx: &mut C, arg0: Type, arg1: bool) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 585.
+    let expr0_0 = Opcode::Bconst;
+    let expr1_0 = InstructionData::UnaryBool {
+        opcode: expr0_0,
+        imm: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term vconst.
+pub fn constructor_vconst<C: Context>(ctx: &mut C, arg0: Type, arg1: Constant) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 593.
+    let expr0_0 = Opcode::Vconst;
+    let expr1_0 = InstructionData::UnaryConst {
+        opcode: expr0_0,
+        constant_handle: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term const_addr.
+pub fn constructor_const_addr<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Constant,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 601.
+    let expr0_0 = Opcode::ConstAddr;
+    let expr1_0 = InstructionData::UnaryConst {
+        opcode: expr0_0,
+        constant_handle: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term shuffle.
+pub fn constructor_shuffle<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+    arg3: Immediate,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/clif_opt.isle line 609.
+    let expr0_0 = Opcode::Shuffle;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Shuffle {
+        opcode: expr0_0,
+        args: expr1_0,
+        imm: pattern3_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term null.
+pub fn constructor_null<C: Context>(ctx: &mut C, arg0: Type) -> Option<Value> {
+    let pattern0_0 = arg0;
+    // Rule at src/clif_opt.isle line 617.
+    let expr0_0 = Opcode::Null;
+    let expr1_0 = InstructionData::NullAry { opcode: expr0_0 };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term select.
+pub fn constructor_select<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+    arg3: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/clif_opt.isle line 625.
+    let expr0_0 = Opcode::Select;
+    let expr1_0 = C::pack_value_array_3(ctx, pattern1_0, pattern2_0, pattern3_0);
+    let expr2_0 = InstructionData::Ternary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term selectif.
+pub fn constructor_selectif<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: &IntCC,
+    arg2: Value,
+    arg3: Value,
+    arg4: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    let pattern4_0 = arg4;
+    // Rule at src/clif_opt.isle line 633.
+    let expr0_0 = Opcode::Selectif;
+    let expr1_0 = C::pack_value_array_3(ctx, pattern2_0, pattern3_0, pattern4_0);
+    let expr2_0 = InstructionData::IntSelect {
+        opcode: expr0_0,
+        args: expr1_0,
+        cond: pattern1_0.clone(),
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term bitselect.
+pub fn constructor_bitselect<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+    arg3: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/clif_opt.isle line 641.
+    let expr0_0 = Opcode::Bitselect;
+    let expr1_0 = C::pack_value_array_3(ctx, pattern1_0, pattern2_0, pattern3_0);
+    let expr2_0 = InstructionData::Ternary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term copy.
+pub fn constructor_copy<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 649.
+    let expr0_0 = Opcode::Copy;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term vconcat.
+pub fn constructor_vconcat<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 657.
+    let expr0_0 = Opcode::Vconcat;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term vselect.
+pub fn constructor_vselect<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+    arg3: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/clif_opt.isle line 665.
+    let expr0_0 = Opcode::Vselect;
+    let expr1_0 = C::pack_value_array_3(ctx, pattern1_0, pattern2_0, pattern3_0);
+    let expr2_0 = InstructionData::Ternary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term vany_true.
+pub fn constructor_vany_true<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 673.
+    let expr0_0 = Opcode::VanyTrue;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term vall_true.
+pub fn constructor_vall_true<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 681.
+    let expr0_0 = Opcode::VallTrue;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term vhigh_bits.
+pub fn constructor_vhigh_bits<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 689.
+    let expr0_0 = Opcode::VhighBits;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term icmp.
+pub fn constructor_icmp<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: &IntCC,
+    arg2: Value,
+    arg3: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/clif_opt.isle line 697.
+    let expr0_0 = Opcode::Icmp;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern2_0, pattern3_0);
+    let expr2_0 = InstructionData::IntCompare {
+        opcode: expr0_0,
+        args: expr1_0,
+        cond: pattern1_0.clone(),
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term icmp_imm.
+pub fn constructor_icmp_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: &IntCC,
+    arg2: Value,
+    arg3: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/clif_opt.isle line 705.
+    let expr0_0 = Opcode::IcmpImm;
+    let expr1_0 = InstructionData::IntCompareImm {
+        opcode: expr0_0,
+        arg: pattern2_0,
+        cond: pattern1_0.clone(),
+        imm: pattern3_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term iadd.
+pub fn constructor_iadd<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 713.
+    let expr0_0 = Opcode::Iadd;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term isub.
+pub fn constructor_isub<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 721.
+    let expr0_0 = Opcode::Isub;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term ineg.
+pub fn constructor_ineg<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 729.
+    let expr0_0 = Opcode::Ineg;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term iabs.
+pub fn constructor_iabs<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 737.
+    let expr0_0 = Opcode::Iabs;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term imul.
+pub fn constructor_imul<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 745.
+    let expr0_0 = Opcode::Imul;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term umulhi.
+pub fn constructor_umulhi<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 753.
+    let expr0_0 = Opcode::Umulhi;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term smulhi.
+pub fn constructor_smulhi<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 761.
+    let expr0_0 = Opcode::Smulhi;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term sqmul_round_sat.
+pub fn constructor_sqmul_round_sat<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 769.
+    let expr0_0 = Opcode::SqmulRoundSat;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term iadd_imm.
+pub fn constructor_iadd_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 801.
+    let expr0_0 = Opcode::IaddImm;
+    let expr1_0 = InstructionData::BinaryImm64 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term imul_imm.
+pub fn constructor_imul_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 809.
+    let expr0_0 = Opcode::ImulImm;
+    let expr1_0 = InstructionData::BinaryImm64 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term udiv_imm.
+pub fn constructor_udiv_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 817.
+    let expr0_0 = Opcode::UdivImm;
+    let expr1_0 = InstructionData::BinaryImm64 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term sdiv_imm.
+pub fn constructor_sdiv_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 825.
+    let expr0_0 = Opcode::SdivImm;
+    let expr1_0 = InstructionData::BinaryImm64 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term urem_imm.
+pub fn constructor_urem_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 833.
+    let expr0_0 = Opcode::UremImm;
+    let expr1_0 = InstructionData::BinaryImm64 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term srem_imm.
+pub fn constructor_srem_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 841.
+    let expr0_0 = Opcode::SremImm;
+    let expr1_0 = InstructionData::BinaryImm64 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term irsub_imm.
+pub fn constructor_irsub_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 849.
+    let expr0_0 = Opcode::IrsubImm;
+    let expr1_0 = InstructionData::BinaryImm64 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term iadd_cin.
+pub fn constructor_iadd_cin<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+    arg3: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/clif_opt.isle line 857.
+    let expr0_0 = Opcode::IaddCin;
+    let expr1_0 = C::pack_value_array_3(ctx, pattern1_0, pattern2_0, pattern3_0);
+    let expr2_0 = InstructionData::Ternary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term iadd_ifcin.
+pub fn constructor_iadd_ifcin<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+    arg3: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/clif_opt.isle line 865.
+    let expr0_0 = Opcode::IaddIfcin;
+    let expr1_0 = C::pack_value_array_3(ctx, pattern1_0, pattern2_0, pattern3_0);
+    let expr2_0 = InstructionData::Ternary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term isub_bin.
+pub fn constructor_isub_bin<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+    arg3: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/clif_opt.isle line 873.
+    let expr0_0 = Opcode::IsubBin;
+    let expr1_0 = C::pack_value_array_3(ctx, pattern1_0, pattern2_0, pattern3_0);
+    let expr2_0 = InstructionData::Ternary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term isub_ifbin.
+pub fn constructor_isub_ifbin<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+    arg3: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/clif_opt.isle line 881.
+    let expr0_0 = Opcode::IsubIfbin;
+    let expr1_0 = C::pack_value_array_3(ctx, pattern1_0, pattern2_0, pattern3_0);
+    let expr2_0 = InstructionData::Ternary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term band.
+pub fn constructor_band<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 889.
+    let expr0_0 = Opcode::Band;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term bor.
+pub fn constructor_bor<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 897.
+    let expr0_0 = Opcode::Bor;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term bxor.
+pub fn constructor_bxor<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 905.
+    let expr0_0 = Opcode::Bxor;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term bnot.
+pub fn constructor_bnot<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 913.
+    let expr0_0 = Opcode::Bnot;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term band_not.
+pub fn constructor_band_not<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 921.
+    let expr0_0 = Opcode::BandNot;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term bor_not.
+pub fn constructor_bor_not<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 929.
+    let expr0_0 = Opcode::BorNot;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term bxor_not.
+pub fn constructor_bxor_not<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 937.
+    let expr0_0 = Opcode::BxorNot;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term band_imm.
+pub fn constructor_band_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 945.
+    let expr0_0 = Opcode::BandImm;
+    let expr1_0 = InstructionData::BinaryImm64 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term bor_imm.
+pub fn constructor_bor_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 953.
+    let expr0_0 = Opcode::BorImm;
+    let expr1_0 = InstructionData::BinaryImm64 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term bxor_imm.
+pub fn constructor_bxor_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 961.
+    let expr0_0 = Opcode::BxorImm;
+    let expr1_0 = InstructionData::BinaryImm64 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term rotl.
+pub fn constructor_rotl<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 969.
+    let expr0_0 = Opcode::Rotl;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term rotr.
+pub fn constructor_rotr<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 977.
+    let expr0_0 = Opcode::Rotr;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term rotl_imm.
+pub fn constructor_rotl_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 985.
+    let expr0_0 = Opcode::RotlImm;
+    let expr1_0 = InstructionData::BinaryImm64 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term rotr_imm.
+pub fn constructor_rotr_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 993.
+    let expr0_0 = Opcode::RotrImm;
+    let expr1_0 = InstructionData::BinaryImm64 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term ishl.
+pub fn constructor_ishl<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1001.
+    let expr0_0 = Opcode::Ishl;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term ushr.
+pub fn constructor_ushr<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1009.
+    let expr0_0 = Opcode::Ushr;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term sshr.
+pub fn constructor_sshr<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1017.
+    let expr0_0 = Opcode::Sshr;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term ishl_imm.
+pub fn constructor_ishl_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1025.
+    let expr0_0 = Opcode::IshlImm;
+    let expr1_0 = InstructionData::BinaryImm64 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term ushr_imm.
+pub fn constructor_ushr_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1033.
+    let expr0_0 = Opcode::UshrImm;
+    let expr1_0 = InstructionData::BinaryImm64 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term sshr_imm.
+pub fn constructor_sshr_imm<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Imm64,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1041.
+    let expr0_0 = Opcode::SshrImm;
+    let expr1_0 = InstructionData::BinaryImm64 {
+        opcode: expr0_0,
+        arg: pattern1_0,
+        imm: pattern2_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term bitrev.
+pub fn constructor_bitrev<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1049.
+    let expr0_0 = Opcode::Bitrev;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term clz.
+pub fn constructor_clz<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1057.
+    let expr0_0 = Opcode::Clz;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term cls.
+pub fn constructor_cls<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1065.
+    let expr0_0 = Opcode::Cls;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term ctz.
+pub fn constructor_ctz<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1073.
+    let expr0_0 = Opcode::Ctz;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term popcnt.
+pub fn constructor_popcnt<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1081.
+    let expr0_0 = Opcode::Popcnt;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term fcmp.
+pub fn constructor_fcmp<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: &FloatCC,
+    arg2: Value,
+    arg3: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/clif_opt.isle line 1089.
+    let expr0_0 = Opcode::Fcmp;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern2_0, pattern3_0);
+    let expr2_0 = InstructionData::FloatCompare {
+        opcode: expr0_0,
+        args: expr1_0,
+        cond: pattern1_0.clone(),
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term fadd.
+pub fn constructor_fadd<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1097.
+    let expr0_0 = Opcode::Fadd;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term fsub.
+pub fn constructor_fsub<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1105.
+    let expr0_0 = Opcode::Fsub;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term fmul.
+pub fn constructor_fmul<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1113.
+    let expr0_0 = Opcode::Fmul;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term fdiv.
+pub fn constructor_fdiv<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1121.
+    let expr0_0 = Opcode::Fdiv;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term sqrt.
+pub fn constructor_sqrt<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1129.
+    let expr0_0 = Opcode::Sqrt;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term fma.
+pub fn constructor_fma<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+    arg3: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    let pattern3_0 = arg3;
+    // Rule at src/clif_opt.isle line 1137.
+    let expr0_0 = Opcode::Fma;
+    let expr1_0 = C::pack_value_array_3(ctx, pattern1_0, pattern2_0, pattern3_0);
+    let expr2_0 = InstructionData::Ternary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term fneg.
+pub fn constructor_fneg<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1145.
+    let expr0_0 = Opcode::Fneg;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term fabs.
+pub fn constructor_fabs<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1153.
+    let expr0_0 = Opcode::Fabs;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term fcopysign.
+pub fn constructor_fcopysign<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1161.
+    let expr0_0 = Opcode::Fcopysign;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term fmin.
+pub fn constructor_fmin<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1169.
+    let expr0_0 = Opcode::Fmin;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term fmin_pseudo.
+pub fn constructor_fmin_pseudo<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1177.
+    let expr0_0 = Opcode::FminPseudo;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term fmax.
+pub fn constructor_fmax<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1185.
+    let expr0_0 = Opcode::Fmax;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term fmax_pseudo.
+pub fn constructor_fmax_pseudo<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1193.
+    let expr0_0 = Opcode::FmaxPseudo;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term ceil.
+pub fn constructor_ceil<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1201.
+    let expr0_0 = Opcode::Ceil;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term floor.
+pub fn constructor_floor<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1209.
+    let expr0_0 = Opcode::Floor;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term trunc.
+pub fn constructor_trunc<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1217.
+    let expr0_0 = Opcode::Trunc;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term nearest.
+pub fn constructor_nearest<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1225.
+    let expr0_0 = Opcode::Nearest;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term is_null.
+pub fn constructor_is_null<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1233.
+    let expr0_0 = Opcode::IsNull;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term is_invalid.
+pub fn constructor_is_invalid<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1241.
+    let expr0_0 = Opcode::IsInvalid;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term trueif.
+pub fn constructor_trueif<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: &IntCC,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1249.
+    let expr0_0 = Opcode::Trueif;
+    let expr1_0 = InstructionData::IntCond {
+        opcode: expr0_0,
+        arg: pattern2_0,
+        cond: pattern1_0.clone(),
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term trueff.
+pub fn constructor_trueff<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: &FloatCC,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1257.
+    let expr0_0 = Opcode::Trueff;
+    let expr1_0 = InstructionData::FloatCond {
+        opcode: expr0_0,
+        arg: pattern2_0,
+        cond: pattern1_0.clone(),
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term bitcast.
+pub fn constructor_bitcast<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1265.
+    let expr0_0 = Opcode::Bitcast;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term raw_bitcast.
+pub fn constructor_raw_bitcast<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1273.
+    let expr0_0 = Opcode::RawBitcast;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term scalar_to_vector.
+pub fn constructor_scalar_to_vector<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1281.
+    let expr0_0 = Opcode::ScalarToVector;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term breduce.
+pub fn constructor_breduce<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1289.
+    let expr0_0 = Opcode::Breduce;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term bextend.
+pub fn constructor_bextend<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1297.
+    let expr0_0 = Opcode::Bextend;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term bint.
+pub fn constructor_bint<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1305.
+    let expr0_0 = Opcode::Bint;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term bmask.
+pub fn constructor_bmask<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1313.
+    let expr0_0 = Opcode::Bmask;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term ireduce.
+pub fn constructor_ireduce<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1321.
+    let expr0_0 = Opcode::Ireduce;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term snarrow.
+pub fn constructor_snarrow<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1329.
+    let expr0_0 = Opcode::Snarrow;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term unarrow.
+pub fn constructor_unarrow<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1337.
+    let expr0_0 = Opcode::Unarrow;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term uunarrow.
+pub fn constructor_uunarrow<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1345.
+    let expr0_0 = Opcode::Uunarrow;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term swiden_low.
+pub fn constructor_swiden_low<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1353.
+    let expr0_0 = Opcode::SwidenLow;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term swiden_high.
+pub fn constructor_swiden_high<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1361.
+    let expr0_0 = Opcode::SwidenHigh;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term uwiden_low.
+pub fn constructor_uwiden_low<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1369.
+    let expr0_0 = Opcode::UwidenLow;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term uwiden_high.
+pub fn constructor_uwiden_high<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1377.
+    let expr0_0 = Opcode::UwidenHigh;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term iadd_pairwise.
+pub fn constructor_iadd_pairwise<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1385.
+    let expr0_0 = Opcode::IaddPairwise;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term widening_pairwise_dot_product_s.
+pub fn constructor_widening_pairwise_dot_product_s<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1393.
+    let expr0_0 = Opcode::WideningPairwiseDotProductS;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term uextend.
+pub fn constructor_uextend<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1401.
+    let expr0_0 = Opcode::Uextend;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term sextend.
+pub fn constructor_sextend<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1409.
+    let expr0_0 = Opcode::Sextend;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term fpromote.
+pub fn constructor_fpromote<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1417.
+    let expr0_0 = Opcode::Fpromote;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term fdemote.
+pub fn constructor_fdemote<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1425.
+    let expr0_0 = Opcode::Fdemote;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term fvdemote.
+pub fn constructor_fvdemote<C: Context>(ctx: &mut C, arg0: Type, arg1: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1433.
+    let expr0_0 = Opcode::Fvdemote;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term fvpromote_low.
+pub fn constructor_fvpromote_low<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1441.
+    let expr0_0 = Opcode::FvpromoteLow;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term fcvt_to_uint_sat.
+pub fn constructor_fcvt_to_uint_sat<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1455.
+    let expr0_0 = Opcode::FcvtToUintSat;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term fcvt_to_sint_sat.
+pub fn constructor_fcvt_to_sint_sat<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1469.
+    let expr0_0 = Opcode::FcvtToSintSat;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term fcvt_from_uint.
+pub fn constructor_fcvt_from_uint<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1477.
+    let expr0_0 = Opcode::FcvtFromUint;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term fcvt_from_sint.
+pub fn constructor_fcvt_from_sint<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1485.
+    let expr0_0 = Opcode::FcvtFromSint;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term fcvt_low_from_sint.
+pub fn constructor_fcvt_low_from_sint<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/clif_opt.isle line 1493.
+    let expr0_0 = Opcode::FcvtLowFromSint;
+    let expr1_0 = InstructionData::Unary {
+        opcode: expr0_0,
+        arg: pattern1_0,
+    };
+    let expr2_0 = C::make_inst(ctx, pattern0_0, &expr1_0);
+    return Some(expr2_0);
+}
+
+// Generated as internal constructor for term iconcat.
+pub fn constructor_iconcat<C: Context>(
+    ctx: &mut C,
+    arg0: Type,
+    arg1: Value,
+    arg2: Value,
+) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    let pattern2_0 = arg2;
+    // Rule at src/clif_opt.isle line 1501.
+    let expr0_0 = Opcode::Iconcat;
+    let expr1_0 = C::pack_value_array_2(ctx, pattern1_0, pattern2_0);
+    let expr2_0 = InstructionData::Binary {
+        opcode: expr0_0,
+        args: expr1_0,
+    };
+    let expr3_0 = C::make_inst(ctx, pattern0_0, &expr2_0);
+    return Some(expr3_0);
+}
+
+// Generated as internal constructor for term simplify.
+pub fn constructor_simplify<C: Context>(ctx: &mut C, arg0: Value) -> Option<Value> {
+    let pattern0_0 = arg0;
+    if let Some((pattern1_0, pattern1_1)) = C::inst_data(ctx, pattern0_0) {
+        if let &InstructionData::Ternary {
+            opcode: ref pattern2_0,
+            args: ref pattern2_1,
+        } = &pattern1_1
+        {
+            if let &Opcode::Select = &pattern2_0 {
+                let (pattern4_0, pattern4_1, pattern4_2) =
+                    C::unpack_value_array_3(ctx, &pattern2_1);
+                if let Some((pattern5_0, pattern5_1)) = C::inst_data(ctx, pattern4_0) {
+                    if let &InstructionData::UnaryBool {
+                        opcode: ref pattern6_0,
+                        imm: pattern6_1,
+                    } = &pattern5_1
+                    {
+                        if let &Opcode::Bconst = &pattern6_0 {
+                            if pattern6_1 == true {
+                                // Rule at src/opts/cprop.isle line 52.
+                                return Some(pattern4_1);
+                            }
+                            if pattern6_1 == false {
+                                // Rule at src/opts/cprop.isle line 53.
+                                return Some(pattern4_2);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some((pattern1_0, pattern1_1)) = C::inst_data(ctx, pattern0_0) {
+        if let &InstructionData::Ternary {
+            opcode: ref pattern2_0,
+            args: ref pattern2_1,
+        } = &pattern1_1
+        {
+            if let &Opcode::Select = &pattern2_0 {
+                let (pattern4_0, pattern4_1, pattern4_2) =
+                    C::unpack_value_array_3(ctx, &pattern2_1);
+                if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_0) {
+                    if let Some(pattern6_0) = C::u64_nonzero(ctx, pattern5_1) {
+                        // Rule at src/opts/cprop.isle line 54.
+                        return Some(pattern4_1);
+                    }
+                }
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some((pattern1_0, pattern1_1)) = C::inst_data(ctx, pattern0_0) {
+        match &pattern1_1 {
+            &InstructionData::Binary {
+                opcode: ref pattern2_0,
+                args: ref pattern2_1,
+            } => {
+                match &pattern2_0 {
+                    &Opcode::Iadd => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_0) {
+                            if let Some((pattern6_0, pattern6_1)) = C::iconst_u(ctx, pattern4_1) {
+                                // Rule at src/opts/cprop.isle line 8.
+                                let expr0_0 = C::u64_add(ctx, pattern5_1, pattern6_1);
+                                let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                return Some(expr1_0);
+                            }
+                        }
+                    }
+                    &Opcode::Isub => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_0) {
+                            if let Some((pattern6_0, pattern6_1)) = C::iconst_u(ctx, pattern4_1) {
+                                // Rule at src/opts/cprop.isle line 10.
+                                let expr0_0 = C::u64_sub(ctx, pattern5_1, pattern6_1);
+                                let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                return Some(expr1_0);
+                            }
+                        }
+                    }
+                    &Opcode::Imul => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_0) {
+                            if let Some((pattern6_0, pattern6_1)) = C::iconst_u(ctx, pattern4_1) {
+                                // Rule at src/opts/cprop.isle line 12.
+                                let expr0_0 = C::u64_mul(ctx, pattern5_1, pattern6_1);
+                                let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                return Some(expr1_0);
+                            }
+                        }
+                    }
+                    &Opcode::Udiv => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_0) {
+                            if let Some((pattern6_0, pattern6_1)) = C::iconst_u(ctx, pattern4_1) {
+                                // Rule at src/opts/cprop.isle line 35.
+                                let expr0_0 = C::u64_udiv(ctx, pattern5_1, pattern6_1);
+                                let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                return Some(expr1_0);
+                            }
+                        }
+                    }
+                    &Opcode::Urem => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_0) {
+                            if let Some((pattern6_0, pattern6_1)) = C::iconst_u(ctx, pattern4_1) {
+                                // Rule at src/opts/cprop.isle line 37.
+                                let expr0_0 = C::u64_urem(ctx, pattern5_1, pattern6_1);
+                                let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                return Some(expr1_0);
+                            }
+                        }
+                    }
+                    &Opcode::Band => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_0) {
+                            if let Some((pattern6_0, pattern6_1)) = C::iconst_u(ctx, pattern4_1) {
+                                // Rule at src/opts/cprop.isle line 17.
+                                let expr0_0 = C::u64_and(ctx, pattern5_1, pattern6_1);
+                                let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                return Some(expr1_0);
+                            }
+                        }
+                    }
+                    &Opcode::Bor => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_0) {
+                            if let Some((pattern6_0, pattern6_1)) = C::iconst_u(ctx, pattern4_1) {
+                                // Rule at src/opts/cprop.isle line 19.
+                                let expr0_0 = C::u64_or(ctx, pattern5_1, pattern6_1);
+                                let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                return Some(expr1_0);
+                            }
+                        }
+                    }
+                    &Opcode::Bxor => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_0) {
+                            if let Some((pattern6_0, pattern6_1)) = C::iconst_u(ctx, pattern4_1) {
+                                // Rule at src/opts/cprop.isle line 21.
+                                let expr0_0 = C::u64_xor(ctx, pattern5_1, pattern6_1);
+                                let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                return Some(expr1_0);
+                            }
+                        }
+                    }
+                    &Opcode::Ishl => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_0) {
+                            if let Some((pattern6_0, pattern6_1)) = C::iconst_u(ctx, pattern4_1) {
+                                // Rule at src/opts/cprop.isle line 26.
+                                let expr0_0 = C::u64_shl(ctx, pattern1_0, pattern5_1, pattern6_1);
+                                let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                return Some(expr1_0);
+                            }
+                        }
+                    }
+                    &Opcode::Ushr => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_0) {
+                            if let Some((pattern6_0, pattern6_1)) = C::iconst_u(ctx, pattern4_1) {
+                                // Rule at src/opts/cprop.isle line 28.
+                                let expr0_0 = C::u64_ushr(ctx, pattern1_0, pattern5_1, pattern6_1);
+                                let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                return Some(expr1_0);
+                            }
+                        }
+                    }
+                    &Opcode::Sshr => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_0) {
+                            if let Some((pattern6_0, pattern6_1)) = C::iconst_u(ctx, pattern4_1) {
+                                // Rule at src/opts/cprop.isle line 30.
+                                let expr0_0 = C::u64_sshr(ctx, pattern1_0, pattern5_1, pattern6_1);
+                                let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                return Some(expr1_0);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            &InstructionData::IntCompare {
+                opcode: ref pattern2_0,
+                args: ref pattern2_1,
+                cond: ref pattern2_2,
+            } => {
+                if let &Opcode::Icmp = &pattern2_0 {
+                    let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_0) {
+                        if let Some((pattern6_0, pattern6_1)) = C::iconst_u(ctx, pattern4_1) {
+                            // Rule at src/opts/cprop.isle line 47.
+                            let expr0_0 =
+                                C::intcc_eval(ctx, pattern5_0, &pattern2_2, pattern5_1, pattern6_1);
+                            let expr1_0 = constructor_bconst(ctx, pattern1_0, expr0_0)?;
+                            return Some(expr1_0);
+                        }
+                    }
+                }
+            }
+            &InstructionData::Ternary {
+                opcode: ref pattern2_0,
+                args: ref pattern2_1,
+            } => {
+                if let &Opcode::Select = &pattern2_0 {
+                    let (pattern4_0, pattern4_1, pattern4_2) =
+                        C::unpack_value_array_3(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_0) {
+                        if pattern5_1 == 0 {
+                            // Rule at src/opts/cprop.isle line 55.
+                            return Some(pattern4_2);
+                        }
+                    }
+                }
+            }
+            &InstructionData::Unary {
+                opcode: ref pattern2_0,
+                arg: pattern2_1,
+            } => {
+                match &pattern2_0 {
+                    &Opcode::Ineg => {
+                        if let Some((pattern4_0, pattern4_1)) = C::iconst_u(ctx, pattern2_1) {
+                            // Rule at src/opts/cprop.isle line 14.
+                            let expr0_0 = C::u64_neg(ctx, pattern4_1);
+                            let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                            return Some(expr1_0);
+                        }
+                    }
+                    &Opcode::Bnot => {
+                        if let Some((pattern4_0, pattern4_1)) = C::iconst_u(ctx, pattern2_1) {
+                            // Rule at src/opts/cprop.isle line 23.
+                            let expr0_0 = C::u64_not(ctx, pattern4_1);
+                            let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                            return Some(expr1_0);
+                        }
+                    }
+                    &Opcode::Ireduce => {
+                        if let Some((pattern4_0, pattern4_1)) = C::iconst_u(ctx, pattern2_1) {
+                            // Rule at src/opts/cprop.isle line 44.
+                            let expr0_0 = constructor_iconst_u(ctx, pattern1_0, pattern4_1)?;
+                            return Some(expr0_0);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        if let Some(pattern2_0) = C::int_fits_in_64(ctx, pattern1_0) {
+            if let &InstructionData::Unary {
+                opcode: ref pattern3_0,
+                arg: pattern3_1,
+            } = &pattern1_1
+            {
+                match &pattern3_0 {
+                    &Opcode::Uextend => {
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern3_1) {
+                            // Rule at src/opts/cprop.isle line 40.
+                            let expr0_0 = constructor_iconst_u(ctx, pattern2_0, pattern5_1)?;
+                            return Some(expr0_0);
+                        }
+                    }
+                    &Opcode::Sextend => {
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern3_1) {
+                            // Rule at src/opts/cprop.isle line 42.
+                            let expr0_0 = C::u64_sextend(ctx, pattern5_0, pattern5_1);
+                            let expr1_0 = constructor_iconst_u(ctx, pattern2_0, expr0_0)?;
+                            return Some(expr1_0);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some((pattern1_0, pattern1_1)) = C::inst_data(ctx, pattern0_0) {
+        if pattern1_0 == B1 {
+            if let &InstructionData::IntCompare {
+                opcode: ref pattern3_0,
+                args: ref pattern3_1,
+                cond: ref pattern3_2,
+            } = &pattern1_1
+            {
+                if let &Opcode::Icmp = &pattern3_0 {
+                    let (pattern5_0, pattern5_1) = C::unpack_value_array_2(ctx, &pattern3_1);
+                    if pattern5_1 == pattern5_0 {
+                        match &pattern3_2 {
+                            &IntCC::Equal => {
+                                // Rule at src/opts/algebraic.isle line 50.
+                                let expr0_0: Type = B1;
+                                let expr1_0: bool = true;
+                                let expr2_0 = constructor_bconst(ctx, expr0_0, expr1_0)?;
+                                return Some(expr2_0);
+                            }
+                            &IntCC::NotEqual => {
+                                // Rule at src/opts/algebraic.isle line 60.
+                                let expr0_0: Type = B1;
+                                let expr1_0: bool = false;
+                                let expr2_0 = constructor_bconst(ctx, expr0_0, expr1_0)?;
+                                return Some(expr2_0);
+                            }
+                            &IntCC::SignedGreaterThan => {
+                                // Rule at src/opts/algebraic.isle line 62.
+                                let expr0_0: Type = B1;
+                                let expr1_0: bool = false;
+                                let expr2_0 = constructor_bconst(ctx, expr0_0, expr1_0)?;
+                                return Some(expr2_0);
+                            }
+                            &IntCC::SignedGreaterThanOrEqual => {
+                                // Rule at src/opts/algebraic.isle line 52.
+                                let expr0_0: Type = B1;
+                                let expr1_0: bool = true;
+                                let expr2_0 = constructor_bconst(ctx, expr0_0, expr1_0)?;
+                                return Some(expr2_0);
+                            }
+                            &IntCC::SignedLessThan => {
+                                // Rule at src/opts/algebraic.isle line 64.
+                                let expr0_0: Type = B1;
+                                let expr1_0: bool = false;
+                                let expr2_0 = constructor_bconst(ctx, expr0_0, expr1_0)?;
+                                return Some(expr2_0);
+                            }
+                            &IntCC::SignedLessThanOrEqual => {
+                                // Rule at src/opts/algebraic.isle line 54.
+                                let expr0_0: Type = B1;
+                                let expr1_0: bool = true;
+                                let expr2_0 = constructor_bconst(ctx, expr0_0, expr1_0)?;
+                                return Some(expr2_0);
+                            }
+                            &IntCC::UnsignedGreaterThan => {
+                                // Rule at src/opts/algebraic.isle line 66.
+                                let expr0_0: Type = B1;
+                                let expr1_0: bool = false;
+                                let expr2_0 = constructor_bconst(ctx, expr0_0, expr1_0)?;
+                                return Some(expr2_0);
+                            }
+                            &IntCC::UnsignedGreaterThanOrEqual => {
+                                // Rule at src/opts/algebraic.isle line 56.
+                                let expr0_0: Type = B1;
+                                let expr1_0: bool = true;
+                                let expr2_0 = constructor_bconst(ctx, expr0_0, expr1_0)?;
+                                return Some(expr2_0);
+                            }
+                            &IntCC::UnsignedLessThan => {
+                                // Rule at src/opts/algebraic.isle line 68.
+                                let expr0_0: Type = B1;
+                                let expr1_0: bool = false;
+                                let expr2_0 = constructor_bconst(ctx, expr0_0, expr1_0)?;
+                                return Some(expr2_0);
+                            }
+                            &IntCC::UnsignedLessThanOrEqual => {
+                                // Rule at src/opts/algebraic.isle line 58.
+                                let expr0_0: Type = B1;
+                                let expr1_0: bool = true;
+                                let expr2_0 = constructor_bconst(ctx, expr0_0, expr1_0)?;
+                                return Some(expr2_0);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+        if let &InstructionData::Binary {
+            opcode: ref pattern2_0,
+            args: ref pattern2_1,
+        } = &pattern1_1
+        {
+            match &pattern2_0 {
+                &Opcode::Imul => {
+                    let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                        if pattern5_1 == 0 {
+                            // Rule at src/opts/algebraic.isle line 14.
+                            let expr0_0: u64 = 0;
+                            let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                            return Some(expr1_0);
+                        }
+                    }
+                }
+                &Opcode::Urem => {
+                    let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                        if pattern5_1 == 1 {
+                            // Rule at src/opts/algebraic.isle line 96.
+                            let expr0_0: u64 = 0;
+                            let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                            return Some(expr1_0);
+                        }
+                    }
+                }
+                &Opcode::Srem => {
+                    let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                        if pattern5_1 == 1 {
+                            // Rule at src/opts/algebraic.isle line 98.
+                            let expr0_0: u64 = 0;
+                            let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                            return Some(expr1_0);
+                        }
+                    }
+                }
+                &Opcode::Band => {
+                    let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                        if pattern5_1 == 0 {
+                            // Rule at src/opts/algebraic.isle line 17.
+                            let expr0_0: u64 = 0;
+                            let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                            return Some(expr1_0);
+                        }
+                    }
+                }
+                &Opcode::Bor => {
+                    let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::iconst_s(ctx, pattern4_1) {
+                        if pattern5_1 == -1 {
+                            // Rule at src/opts/algebraic.isle line 21.
+                            return Some(pattern4_1);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some(pattern2_0) = C::int_fits_in_64(ctx, pattern1_0) {
+            if let &InstructionData::Binary {
+                opcode: ref pattern3_0,
+                args: ref pattern3_1,
+            } = &pattern1_1
+            {
+                match &pattern3_0 {
+                    &Opcode::Isub => {
+                        let (pattern5_0, pattern5_1) = C::unpack_value_array_2(ctx, &pattern3_1);
+                        if pattern5_1 == pattern5_0 {
+                            // Rule at src/opts/algebraic.isle line 39.
+                            let expr0_0: u64 = 0;
+                            let expr1_0 = constructor_iconst_u(ctx, pattern2_0, expr0_0)?;
+                            return Some(expr1_0);
+                        }
+                    }
+                    &Opcode::Bxor => {
+                        let (pattern5_0, pattern5_1) = C::unpack_value_array_2(ctx, &pattern3_1);
+                        if pattern5_1 == pattern5_0 {
+                            // Rule at src/opts/algebraic.isle line 41.
+                            let expr0_0: u64 = 0;
+                            let expr1_0 = constructor_iconst_u(ctx, pattern2_0, expr0_0)?;
+                            return Some(expr1_0);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some((pattern1_0, pattern1_1)) = C::inst_data(ctx, pattern0_0) {
+        match &pattern1_1 {
+            &InstructionData::Binary {
+                opcode: ref pattern2_0,
+                args: ref pattern2_1,
+            } => {
+                match &pattern2_0 {
+                    &Opcode::Iadd => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                            if pattern5_1 == 0 {
+                                // Rule at src/opts/algebraic.isle line 11.
+                                return Some(pattern4_0);
+                            }
+                        }
+                    }
+                    &Opcode::Isub => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                            if pattern5_1 == 0 {
+                                // Rule at src/opts/algebraic.isle line 12.
+                                return Some(pattern4_0);
+                            }
+                        }
+                    }
+                    &Opcode::Imul => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                            if pattern5_1 == 1 {
+                                // Rule at src/opts/algebraic.isle line 13.
+                                return Some(pattern4_0);
+                            }
+                        }
+                    }
+                    &Opcode::Udiv => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                            if pattern5_1 == 1 {
+                                // Rule at src/opts/algebraic.isle line 94.
+                                return Some(pattern4_0);
+                            }
+                        }
+                    }
+                    &Opcode::Sdiv => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                            if pattern5_1 == 1 {
+                                // Rule at src/opts/algebraic.isle line 95.
+                                return Some(pattern4_0);
+                            }
+                        }
+                    }
+                    &Opcode::Band => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_s(ctx, pattern4_1) {
+                            if pattern5_1 == -1 {
+                                // Rule at src/opts/algebraic.isle line 19.
+                                return Some(pattern4_0);
+                            }
+                        }
+                    }
+                    &Opcode::Bor => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                            if pattern5_1 == 0 {
+                                // Rule at src/opts/algebraic.isle line 20.
+                                return Some(pattern4_0);
+                            }
+                        }
+                    }
+                    &Opcode::Bxor => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                            if pattern5_1 == 0 {
+                                // Rule at src/opts/algebraic.isle line 22.
+                                return Some(pattern4_0);
+                            }
+                        }
+                    }
+                    &Opcode::Rotl => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                            if pattern5_1 == 0 {
+                                // Rule at src/opts/algebraic.isle line 27.
+                                return Some(pattern4_0);
+                            }
+                        }
+                    }
+                    &Opcode::Rotr => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                            if pattern5_1 == 0 {
+                                // Rule at src/opts/algebraic.isle line 28.
+                                return Some(pattern4_0);
+                            }
+                        }
+                    }
+                    &Opcode::Ishl => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                            if pattern5_1 == 0 {
+                                // Rule at src/opts/algebraic.isle line 24.
+                                return Some(pattern4_0);
+                            }
+                        }
+                    }
+                    &Opcode::Ushr => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                            if pattern5_1 == 0 {
+                                // Rule at src/opts/algebraic.isle line 25.
+                                return Some(pattern4_0);
+                            }
+                        }
+                    }
+                    &Opcode::Sshr => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                            if pattern5_1 == 0 {
+                                // Rule at src/opts/algebraic.isle line 26.
+                                return Some(pattern4_0);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            &InstructionData::Unary {
+                opcode: ref pattern2_0,
+                arg: pattern2_1,
+            } => {
+                match &pattern2_0 {
+                    &Opcode::Ineg => {
+                        if let Some((pattern4_0, pattern4_1)) = C::inst_data(ctx, pattern2_1) {
+                            if let &InstructionData::Unary {
+                                opcode: ref pattern5_0,
+                                arg: pattern5_1,
+                            } = &pattern4_1
+                            {
+                                if let &Opcode::Ineg = &pattern5_0 {
+                                    // Rule at src/opts/algebraic.isle line 31.
+                                    return Some(pattern5_1);
+                                }
+                            }
+                        }
+                    }
+                    &Opcode::Bnot => {
+                        if let Some((pattern4_0, pattern4_1)) = C::inst_data(ctx, pattern2_1) {
+                            if let &InstructionData::Unary {
+                                opcode: ref pattern5_0,
+                                arg: pattern5_1,
+                            } = &pattern4_1
+                            {
+                                if let &Opcode::Bnot = &pattern5_0 {
+                                    // Rule at src/opts/algebraic.isle line 30.
+                                    return Some(pattern5_1);
+                                }
+                            }
+                        }
+                    }
+                    &Opcode::Ireduce => {
+                        if let Some((pattern4_0, pattern4_1)) = C::inst_data(ctx, pattern2_1) {
+                            if let &InstructionData::Unary {
+                                opcode: ref pattern5_0,
+                                arg: pattern5_1,
+                            } = &pattern4_1
+                            {
+                                match &pattern5_0 {
+                                    &Opcode::Uextend => {
+                                        let (pattern7_0, pattern7_1) = C::has_type(ctx, pattern5_1);
+                                        if pattern7_0 == pattern1_0 {
+                                            // Rule at src/opts/algebraic.isle line 84.
+                                            return Some(pattern7_1);
+                                        }
+                                    }
+                                    &Opcode::Sextend => {
+                                        let (pattern7_0, pattern7_1) = C::has_type(ctx, pattern5_1);
+                                        if pattern7_0 == pattern1_0 {
+                                            // Rule at src/opts/algebraic.isle line 85.
+                                            return Some(pattern7_1);
+                                        }
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                    }
+                    &Opcode::Uextend => {
+                        if let Some((pattern4_0, pattern4_1)) = C::inst_data(ctx, pattern2_1) {
+                            if let &InstructionData::Unary {
+                                opcode: ref pattern5_0,
+                                arg: pattern5_1,
+                            } = &pattern4_1
+                            {
+                                if let &Opcode::Uextend = &pattern5_0 {
+                                    // Rule at src/opts/algebraic.isle line 73.
+                                    let expr0_0 = constructor_uextend(ctx, pattern1_0, pattern5_1)?;
+                                    return Some(expr0_0);
+                                }
+                            }
+                        }
+                    }
+                    &Opcode::Sextend => {
+                        if let Some((pattern4_0, pattern4_1)) = C::inst_data(ctx, pattern2_1) {
+                            if let &InstructionData::Unary {
+                                opcode: ref pattern5_0,
+                                arg: pattern5_1,
+                            } = &pattern4_1
+                            {
+                                match &pattern5_0 {
+                                    &Opcode::Uextend => {
+                                        // Rule at src/opts/algebraic.isle line 80.
+                                        let expr0_0 =
+                                            constructor_uextend(ctx, pattern1_0, pattern5_1)?;
+                                        return Some(expr0_0);
+                                    }
+                                    &Opcode::Sextend => {
+                                        // Rule at src/opts/algebraic.isle line 75.
+                                        let expr0_0 =
+                                            constructor_sextend(ctx, pattern1_0, pattern5_1)?;
+                                        return Some(expr0_0);
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some((pattern1_0, pattern1_1)) = C::inst_data(ctx, pattern0_0) {
+        match &pattern1_1 {
+            &InstructionData::Binary {
+                opcode: ref pattern2_0,
+                args: ref pattern2_1,
+            } => {
+                match &pattern2_0 {
+                    &Opcode::Isub => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_0) {
+                            if pattern5_1 == 0 {
+                                // Rule at src/opts/algebraic.isle line 34.
+                                let expr0_0 = constructor_ineg(ctx, pattern1_0, pattern4_1)?;
+                                return Some(expr0_0);
+                            }
+                        }
+                    }
+                    &Opcode::Band => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if pattern4_1 == pattern4_0 {
+                            // Rule at src/opts/algebraic.isle line 43.
+                            return Some(pattern4_0);
+                        }
+                    }
+                    &Opcode::Bor => {
+                        let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                        if pattern4_1 == pattern4_0 {
+                            // Rule at src/opts/algebraic.isle line 44.
+                            return Some(pattern4_0);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            &InstructionData::Ternary {
+                opcode: ref pattern2_0,
+                args: ref pattern2_1,
+            } => {
+                if let &Opcode::Select = &pattern2_0 {
+                    let (pattern4_0, pattern4_1, pattern4_2) =
+                        C::unpack_value_array_3(ctx, &pattern2_1);
+                    if pattern4_2 == pattern4_1 {
+                        // Rule at src/opts/algebraic.isle line 46.
+                        return Some(pattern4_1);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some((pattern1_0, pattern1_1)) = C::inst_data(ctx, pattern0_0) {
+        if let &InstructionData::Binary {
+            opcode: ref pattern2_0,
+            args: ref pattern2_1,
+        } = &pattern1_1
+        {
+            match &pattern2_0 {
+                &Opcode::Iadd => {
+                    let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::inst_data(ctx, pattern4_0) {
+                        if let &InstructionData::Binary {
+                            opcode: ref pattern6_0,
+                            args: ref pattern6_1,
+                        } = &pattern5_1
+                        {
+                            match &pattern6_0 {
+                                &Opcode::Iadd => {
+                                    let (pattern8_0, pattern8_1) =
+                                        C::unpack_value_array_2(ctx, &pattern6_1);
+                                    if let Some((pattern9_0, pattern9_1)) =
+                                        C::iconst_u(ctx, pattern8_1)
+                                    {
+                                        if let Some((pattern10_0, pattern10_1)) =
+                                            C::iconst_u(ctx, pattern4_1)
+                                        {
+                                            // Rule at src/opts/cprop.isle line 64.
+                                            let expr0_0 = C::u64_add(ctx, pattern9_1, pattern10_1);
+                                            let expr1_0 =
+                                                constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                            let expr2_0 = constructor_iadd(
+                                                ctx, pattern1_0, pattern8_0, expr1_0,
+                                            )?;
+                                            return Some(expr2_0);
+                                        }
+                                    }
+                                }
+                                &Opcode::Isub => {
+                                    let (pattern8_0, pattern8_1) =
+                                        C::unpack_value_array_2(ctx, &pattern6_1);
+                                    if let Some((pattern9_0, pattern9_1)) =
+                                        C::iconst_u(ctx, pattern8_1)
+                                    {
+                                        if let Some((pattern10_0, pattern10_1)) =
+                                            C::iconst_u(ctx, pattern4_1)
+                                        {
+                                            // Rule at src/opts/cprop.isle line 76.
+                                            let expr0_0 = C::u64_sub(ctx, pattern10_1, pattern9_1);
+                                            let expr1_0 =
+                                                constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                            let expr2_0 = constructor_iadd(
+                                                ctx, pattern1_0, pattern8_0, expr1_0,
+                                            )?;
+                                            return Some(expr2_0);
+                                        }
+                                    }
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                &Opcode::Imul => {
+                    let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::inst_data(ctx, pattern4_0) {
+                        if let &InstructionData::Binary {
+                            opcode: ref pattern6_0,
+                            args: ref pattern6_1,
+                        } = &pattern5_1
+                        {
+                            if let &Opcode::Imul = &pattern6_0 {
+                                let (pattern8_0, pattern8_1) =
+                                    C::unpack_value_array_2(ctx, &pattern6_1);
+                                if let Some((pattern9_0, pattern9_1)) = C::iconst_u(ctx, pattern8_1)
+                                {
+                                    if let Some((pattern10_0, pattern10_1)) =
+                                        C::iconst_u(ctx, pattern4_1)
+                                    {
+                                        // Rule at src/opts/cprop.isle line 66.
+                                        let expr0_0 = C::u64_mul(ctx, pattern9_1, pattern10_1);
+                                        let expr1_0 =
+                                            constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                        let expr2_0 =
+                                            constructor_imul(ctx, pattern1_0, pattern8_0, expr1_0)?;
+                                        return Some(expr2_0);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                &Opcode::Band => {
+                    let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::inst_data(ctx, pattern4_0) {
+                        if let &InstructionData::Binary {
+                            opcode: ref pattern6_0,
+                            args: ref pattern6_1,
+                        } = &pattern5_1
+                        {
+                            if let &Opcode::Band = &pattern6_0 {
+                                let (pattern8_0, pattern8_1) =
+                                    C::unpack_value_array_2(ctx, &pattern6_1);
+                                if let Some((pattern9_0, pattern9_1)) = C::iconst_u(ctx, pattern8_1)
+                                {
+                                    if let Some((pattern10_0, pattern10_1)) =
+                                        C::iconst_u(ctx, pattern4_1)
+                                    {
+                                        // Rule at src/opts/cprop.isle line 68.
+                                        let expr0_0 = C::u64_and(ctx, pattern9_1, pattern10_1);
+                                        let expr1_0 =
+                                            constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                        let expr2_0 =
+                                            constructor_band(ctx, pattern1_0, pattern8_0, expr1_0)?;
+                                        return Some(expr2_0);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                &Opcode::Bor => {
+                    let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::inst_data(ctx, pattern4_0) {
+                        if let &InstructionData::Binary {
+                            opcode: ref pattern6_0,
+                            args: ref pattern6_1,
+                        } = &pattern5_1
+                        {
+                            if let &Opcode::Bor = &pattern6_0 {
+                                let (pattern8_0, pattern8_1) =
+                                    C::unpack_value_array_2(ctx, &pattern6_1);
+                                if let Some((pattern9_0, pattern9_1)) = C::iconst_u(ctx, pattern8_1)
+                                {
+                                    if let Some((pattern10_0, pattern10_1)) =
+                                        C::iconst_u(ctx, pattern4_1)
+                                    {
+                                        // Rule at src/opts/cprop.isle line 70.
+                                        let expr0_0 = C::u64_or(ctx, pattern9_1, pattern10_1);
+                                        let expr1_0 =
+                                            constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                        let expr2_0 =
+                                            constructor_bor(ctx, pattern1_0, pattern8_0, expr1_0)?;
+                                        return Some(expr2_0);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                &Opcode::Bxor => {
+                    let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::inst_data(ctx, pattern4_0) {
+                        if let &InstructionData::Binary {
+                            opcode: ref pattern6_0,
+                            args: ref pattern6_1,
+                        } = &pattern5_1
+                        {
+                            if let &Opcode::Bxor = &pattern6_0 {
+                                let (pattern8_0, pattern8_1) =
+                                    C::unpack_value_array_2(ctx, &pattern6_1);
+                                if let Some((pattern9_0, pattern9_1)) = C::iconst_u(ctx, pattern8_1)
+                                {
+                                    if let Some((pattern10_0, pattern10_1)) =
+                                        C::iconst_u(ctx, pattern4_1)
+                                    {
+                                        // Rule at src/opts/cprop.isle line 72.
+                                        let expr0_0 = C::u64_xor(ctx, pattern9_1, pattern10_1);
+                                        let expr1_0 =
+                                            constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                                        let expr2_0 =
+                                            constructor_bxor(ctx, pattern1_0, pattern8_0, expr1_0)?;
+                                        return Some(expr2_0);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some((pattern1_0, pattern1_1)) = C::inst_data(ctx, pattern0_0) {
+        if let &InstructionData::Binary {
+            opcode: ref pattern2_0,
+            args: ref pattern2_1,
+        } = &pattern1_1
+        {
+            match &pattern2_0 {
+                &Opcode::Isub => {
+                    let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                        // Rule at src/opts/cprop.isle line 80.
+                        let expr0_0 = C::u64_neg(ctx, pattern5_1);
+                        let expr1_0 = constructor_iconst_u(ctx, pattern1_0, expr0_0)?;
+                        let expr2_0 = constructor_iadd(ctx, pattern1_0, pattern4_0, expr1_0)?;
+                        return Some(expr2_0);
+                    }
+                }
+                &Opcode::Imul => {
+                    let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                        if let Some(pattern6_0) = C::u64_power_of_two(ctx, pattern5_1) {
+                            // Rule at src/opts/algebraic.isle line 90.
+                            let expr0_0 = constructor_iconst_u(ctx, pattern1_0, pattern6_0)?;
+                            let expr1_0 = constructor_ishl(ctx, pattern1_0, pattern4_0, expr0_0)?;
+                            return Some(expr1_0);
+                        }
+                    }
+                }
+                &Opcode::Udiv => {
+                    let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                        if let Some(pattern6_0) = C::u64_power_of_two(ctx, pattern5_1) {
+                            // Rule at src/opts/algebraic.isle line 103.
+                            let expr0_0 = constructor_iconst_u(ctx, pattern1_0, pattern6_0)?;
+                            let expr1_0 = constructor_ushr(ctx, pattern1_0, pattern4_0, expr0_0)?;
+                            return Some(expr1_0);
+                        }
+                    }
+                }
+                &Opcode::Urem => {
+                    let (pattern4_0, pattern4_1) = C::unpack_value_array_2(ctx, &pattern2_1);
+                    if let Some((pattern5_0, pattern5_1)) = C::iconst_u(ctx, pattern4_1) {
+                        if let Some(pattern6_0) = C::u64_power_of_two(ctx, pattern5_1) {
+                            // Rule at src/opts/algebraic.isle line 105.
+                            let expr0_0: Type = I64;
+                            let expr1_0: u64 = 1;
+                            let expr2_0 = C::u64_shl(ctx, expr0_0, expr1_0, pattern6_0);
+                            let expr3_0: u64 = 1;
+                            let expr4_0 = C::u64_sub(ctx, expr2_0, expr3_0);
+                            let expr5_0 = constructor_iconst_u(ctx, pattern1_0, expr4_0)?;
+                            let expr6_0 = constructor_band(ctx, pattern1_0, pattern4_0, expr5_0)?;
+                            return Some(expr6_0);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    let pattern0_0 = arg0;
+    if let Some((pattern1_0, pattern1_1)) = C::inst_data(ctx, pattern0_0) {
+        if let Some(pattern2_0) = C::ty_32_or_64(ctx, pattern1_0) {
+            if let &InstructionData::Binary {
+                opcode: ref pattern3_0,
+                args: ref pattern3_1,
+            } = &pattern1_1
+            {
+                match &pattern3_0 {
+                    &Opcode::Udiv => {
+                        let (pattern5_0, pattern5_1) = C::unpack_value_array_2(ctx, &pattern3_1);
+                        if let Some((pattern6_0, pattern6_1)) = C::iconst_u(ctx, pattern5_1) {
+                            // Rule at src/opts/algebraic.isle line 110.
+                            let expr0_0 = C::udiv_by_const(ctx, pattern2_0, pattern5_0, pattern6_1);
+                            return Some(expr0_0);
+                        }
+                    }
+                    &Opcode::Sdiv => {
+                        let (pattern5_0, pattern5_1) = C::unpack_value_array_2(ctx, &pattern3_1);
+                        if let Some((pattern6_0, pattern6_1)) = C::iconst_s(ctx, pattern5_1) {
+                            // Rule at src/opts/algebraic.isle line 114.
+                            let expr0_0 = C::sdiv_by_const(ctx, pattern2_0, pattern5_0, pattern6_1);
+                            return Some(expr0_0);
+                        }
+                    }
+                    &Opcode::Urem => {
+                        let (pattern5_0, pattern5_1) = C::unpack_value_array_2(ctx, &pattern3_1);
+                        if let Some((pattern6_0, pattern6_1)) = C::iconst_u(ctx, pattern5_1) {
+                            // Rule at src/opts/algebraic.isle line 112.
+                            let expr0_0 = C::urem_by_const(ctx, pattern2_0, pattern5_0, pattern6_1);
+                            return Some(expr0_0);
+                        }
+                    }
+                    &Opcode::Srem => {
+                        let (pattern5_0, pattern5_1) = C::unpack_value_array_2(ctx, &pattern3_1);
+                        if let Some((pattern6_0, pattern6_1)) = C::iconst_s(ctx, pattern5_1) {
+                            // Rule at src/opts/algebraic.isle line 116.
+                            let expr0_0 = C::srem_by_const(ctx, pattern2_0, pattern5_0, pattern6_1);
+                            return Some(expr0_0);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    return None;
+}
+
+// Generated as internal constructor for term iconst_u.
+pub fn constructor_iconst_u<C: Context>(ctx: &mut C, arg0: Type, arg1: u64) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/prelude_opt.isle line 79.
+    let expr0_0 = C::imm64_masked(ctx, pattern0_0, pattern1_0);
+    let expr1_0 = constructor_iconst(ctx, pattern0_0, expr0_0)?;
+    return Some(expr1_0);
+}
+
+// Generated as internal constructor for term iconst_s.
+pub fn constructor_iconst_s<C: Context>(ctx: &mut C, arg0: Type, arg1: i64) -> Option<Value> {
+    let pattern0_0 = arg0;
+    let pattern1_0 = arg1;
+    // Rule at src/prelude_opt.isle line 85.
+    let expr0_0 = C::i64_as_u64(ctx, pattern1_0);
+    let expr1_0 = C::imm64_masked(ctx, pattern0_0, expr0_0);
+    let expr2_0 = constructor_iconst(ctx, pattern0_0, expr1_0)?;
+    return Some(expr2_0);
+}
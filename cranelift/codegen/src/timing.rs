@@ -56,6 +56,7 @@ define_passes! {
     dce: "Dead code elimination",
     gvn: "Global value numbering",
     licm: "Loop invariant code motion",
+    egraph: "E-graph based optimization",
     unreachable_code: "Remove unreachable blocks",
     remove_constant_phis: "Remove constant phi-nodes",
 
@@ -65,6 +66,7 @@ define_passes! {
     vcode_emit_finish: "VCode emission finalization",
 
     regalloc: "Register allocation",
+    regalloc_checker: "Register allocation symbolic verification",
     binemit: "Binary machine code emission",
     layout_renumber: "Layout full renumbering",
 
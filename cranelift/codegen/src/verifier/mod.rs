@@ -580,7 +580,7 @@ impl<'a> Verifier<'a> {
         let num_fixed_results = inst_data.opcode().constraints().num_fixed_results();
         // var_results is 0 if we aren't a call instruction
         let var_results = dfg
-            .call_signature(inst)
+            .call_results_signature(inst)
             .map_or(0, |sig| dfg.signatures[sig].returns.len());
         let total_results = num_fixed_results + var_results;
 
@@ -1214,6 +1214,7 @@ impl<'a> Verifier<'a> {
         let _ = self.typecheck_fixed_args(inst, ctrl_type, errors);
         let _ = self.typecheck_variable_args(inst, errors);
         let _ = self.typecheck_return(inst, errors);
+        let _ = self.typecheck_tail_call(inst, errors);
         let _ = self.typecheck_special(inst, ctrl_type, errors);
 
         Ok(())
@@ -1440,6 +1441,52 @@ impl<'a> Verifier<'a> {
         Ok(())
     }
 
+    fn typecheck_tail_call(
+        &self,
+        inst: Inst,
+        errors: &mut VerifierErrors,
+    ) -> VerifierStepResult<()> {
+        match self.func.dfg[inst].opcode() {
+            Opcode::ReturnCall | Opcode::ReturnCallIndirect => {}
+            _ => return Ok(()),
+        }
+        let sig_ref = self.func.dfg.call_signature(inst).unwrap();
+        let callee_sig = &self.func.dfg.signatures[sig_ref];
+        let caller_sig = &self.func.signature;
+
+        if !caller_sig.call_conv.supports_tail_calls() {
+            return errors.nonfatal((
+                inst,
+                self.context(inst),
+                format!(
+                    "tail calls are not supported in functions with the {} calling convention",
+                    caller_sig.call_conv
+                ),
+            ));
+        }
+        if callee_sig.call_conv != caller_sig.call_conv {
+            return errors.nonfatal((
+                inst,
+                self.context(inst),
+                format!(
+                    "callee calling convention {} must match caller calling convention {}",
+                    callee_sig.call_conv, caller_sig.call_conv
+                ),
+            ));
+        }
+
+        let callee_returns = callee_sig.returns.iter().map(|r| r.value_type);
+        let caller_returns = caller_sig.returns.iter().map(|r| r.value_type);
+        if !callee_returns.eq(caller_returns) {
+            return errors.nonfatal((
+                inst,
+                self.context(inst),
+                "return types of tail call must match function signature",
+            ));
+        }
+        Ok(())
+    }
+
     // Check special-purpose type constraints that can't be expressed in the normal opcode
     // constraints.
     fn typecheck_special(
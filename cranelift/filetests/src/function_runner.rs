@@ -50,7 +50,9 @@ impl SingleFunctionCompiler {
     pub fn with_host_isa(flags: settings::Flags) -> Self {
         let builder =
             builder_with_options(true).expect("Unable to build a TargetIsa for the current host");
-        let isa = builder.finish(flags);
+        let isa = builder
+            .finish(flags)
+            .expect("Unable to build a TargetIsa for the current host");
         Self::new(isa)
     }
 
@@ -3,14 +3,20 @@
 //! The `interpret` test command interprets each function on the host machine
 //! using [RunCommand](cranelift_reader::RunCommand)s.
 
+use crate::runtest_environment::RuntestEnvironment;
 use crate::subtest::{Context, SubTest};
+use cranelift_codegen::data_value::DataValue;
+use cranelift_codegen::ir::ArgumentPurpose;
 use cranelift_codegen::{self, ir};
+use cranelift_interpreter::address::AddressSize;
 use cranelift_interpreter::environment::FunctionStore;
-use cranelift_interpreter::interpreter::{Interpreter, InterpreterState};
+use cranelift_interpreter::interpreter::{HeapInit, Interpreter, InterpreterState};
+use cranelift_interpreter::state::{MemoryError, State};
 use cranelift_interpreter::step::ControlFlow;
 use cranelift_reader::{parse_run_command, TestCommand};
 use log::trace;
 use std::borrow::Cow;
+use std::convert::TryFrom;
 
 struct TestInterpret;
 
@@ -36,6 +42,7 @@ impl SubTest for TestInterpret {
     }
 
     fn run(&self, func: Cow<ir::Function>, context: &Context) -> anyhow::Result<()> {
+        let test_env = RuntestEnvironment::parse(&context.details.comments[..])?;
         for comment in context.details.comments.iter() {
             if let Some(command) = parse_run_command(comment.text, &func.signature)? {
                 trace!("Parsed run command: {}", command);
@@ -44,11 +51,35 @@ impl SubTest for TestInterpret {
                 env.add(func.name.to_string(), &func);
 
                 command
-                    .run(|func_name, args| {
-                        // Because we have stored function names with a leading %, we need to re-add it.
-                        let func_name = &format!("%{}", func_name);
-                        let state = InterpreterState::default().with_function_store(env);
-                        match Interpreter::new(state).call_by_name(func_name, args) {
+                    .run(|_, args| {
+                        // Call the function by its stored name rather than the one in the run
+                        // command, since long testcase names are truncated when parsed.
+                        let func_name = &func.name.to_string();
+                        let mut state = InterpreterState::default().with_function_store(env);
+
+                        let first_arg_is_vmctx = func
+                            .signature
+                            .params
+                            .first()
+                            .map(|p| p.purpose == ArgumentPurpose::VMContext)
+                            .unwrap_or(false);
+
+                        if !first_arg_is_vmctx && test_env.is_active() {
+                            return Err(concat!(
+                                "This test requests a heap, but the first argument is not `i64 vmctx`.\n",
+                                "See docs/testing.md for more info on using heap annotations."
+                            ).to_string());
+                        }
+
+                        let mut args_with_vmctx = Vec::with_capacity(args.len() + 1);
+                        if test_env.is_active() {
+                            let vmctx = register_heaps(&mut state, &test_env)
+                                .map_err(|e| format!("unable to allocate heaps: {}", e))?;
+                            args_with_vmctx.push(vmctx);
+                        }
+                        args_with_vmctx.extend_from_slice(args);
+
+                        match Interpreter::new(state).call_by_name(func_name, &args_with_vmctx) {
                             Ok(ControlFlow::Return(results)) => Ok(results.to_vec()),
                             Ok(_) => {
                                 panic!("Unexpected returned control flow--this is likely a bug.")
@@ -62,3 +93,30 @@ impl SubTest for TestInterpret {
         Ok(())
     }
 }
+
+/// Allocates the heaps requested by the test environment in the interpreter and builds the
+/// `vmctx` struct pointing to them, laid out as described in
+/// [RuntestContext](crate::runtest_environment::RuntestContext). Returns the address of the
+/// `vmctx` struct.
+fn register_heaps(
+    state: &mut InterpreterState,
+    test_env: &RuntestEnvironment,
+) -> Result<DataValue, MemoryError> {
+    let mut vmctx_struct = vec![];
+    for heap in test_env.heaps.iter() {
+        let size: u64 = heap.size.into();
+        let heap_id = state.register_heap(HeapInit::Zeroed(size as usize));
+
+        // Each heap is described by its start and end addresses.
+        for offset in [0, size] {
+            let addr = state.heap_address(AddressSize::_64, heap_id, offset)?;
+            let mut bytes = [0; 8];
+            DataValue::try_from(addr)?.write_to_slice(&mut bytes);
+            vmctx_struct.extend_from_slice(&bytes);
+        }
+    }
+
+    let vmctx_id = state.register_heap(HeapInit::FromBacking(vmctx_struct));
+    let vmctx = state.heap_address(AddressSize::_64, vmctx_id, 0)?;
+    DataValue::try_from(vmctx)
+}
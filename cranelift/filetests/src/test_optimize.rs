@@ -0,0 +1,54 @@
+//! Test command for testing the e-graph based mid-end optimizer.
+//!
+//! The `optimize` test command runs each function through the e-graph pass, which performs GVN,
+//! LICM, constant folding and algebraic simplifications.
+//!
+//! The resulting function is sent to `filecheck`.
+
+use crate::subtest::{run_filecheck, Context, SubTest};
+use cranelift_codegen;
+use cranelift_codegen::ir::Function;
+use cranelift_reader::TestCommand;
+use std::borrow::Cow;
+
+struct TestOptimize;
+
+pub fn subtest(parsed: &TestCommand) -> anyhow::Result<Box<dyn SubTest>> {
+    assert_eq!(parsed.command, "optimize");
+    if !parsed.options.is_empty() {
+        anyhow::bail!("No options allowed on {}", parsed);
+    }
+    Ok(Box::new(TestOptimize))
+}
+
+impl SubTest for TestOptimize {
+    fn name(&self) -> &'static str {
+        "optimize"
+    }
+
+    fn needs_isa(&self) -> bool {
+        true
+    }
+
+    fn is_mutating(&self) -> bool {
+        true
+    }
+
+    fn run(&self, func: Cow<Function>, context: &Context) -> anyhow::Result<()> {
+        let isa = context.isa.expect("optimize needs an ISA");
+        let mut comp_ctx = cranelift_codegen::Context::for_function(func.into_owned());
+
+        comp_ctx.flowgraph();
+        comp_ctx.compute_domtree();
+        comp_ctx
+            .eliminate_unreachable_code(isa)
+            .map_err(|e| crate::pretty_anyhow_error(&comp_ctx.func, Into::into(e)))?;
+        comp_ctx.compute_loop_analysis();
+        comp_ctx
+            .egraph_pass(isa)
+            .map_err(|e| crate::pretty_anyhow_error(&comp_ctx.func, Into::into(e)))?;
+
+        let text = comp_ctx.func.display().to_string();
+        run_filecheck(&text, context)
+    }
+}
@@ -1346,7 +1346,7 @@ block0:
 
         let target = isa::lookup(triple)
             .ok()
-            .map(|b| b.finish(shared_flags))
+            .map(|b| b.finish(shared_flags).unwrap())
             .expect("This test requires x86_64 support.");
 
         let mut sig = Signature::new(target.default_call_conv());
@@ -1558,7 +1558,7 @@ block0:
 
         let target = isa::lookup(triple)
             .ok()
-            .map(|b| b.finish(shared_flags))
+            .map(|b| b.finish(shared_flags).unwrap())
             .expect("This test requires x86_64 support.");
 
         let mut sig = Signature::new(target.default_call_conv());
@@ -21,16 +21,21 @@
 //! The only exception to this is the "stack" region, where, because we only have a single "stack"
 //! we have 0 "entry" bits, and thus is all offset.
 //!
+//! "function" addresses are produced by `func_addr` and symbolic global values, the "entry" field
+//! holding the function's index in the [FunctionStore](crate::environment::FunctionStore). The
+//! "offset" field of a function address indexes into that function's constant pool, which is how
+//! `const_addr` addresses are represented.
+//!
 //! | address size | address kind | region value (2 bits) | entry bits (#) | offset bits (#) |
 //! |--------------|--------------|-----------------------|----------------|-----------------|
 //! | 32           | Stack        | 0b00                  | 0              | 30              |
 //! | 32           | Heap         | 0b01                  | 2              | 28              |
 //! | 32           | Table        | 0b10                  | 5              | 25              |
-//! | 32           | GlobalValue  | 0b11                  | 6              | 24              |
+//! | 32           | Function     | 0b11                  | 6              | 24              |
 //! | 64           | Stack        | 0b00                  | 0              | 62              |
 //! | 64           | Heap         | 0b01                  | 6              | 56              |
 //! | 64           | Table        | 0b10                  | 10             | 52              |
-//! | 64           | GlobalValue  | 0b11                  | 12             | 50              |
+//! | 64           | Function     | 0b11                  | 12             | 50              |
 
 use crate::state::MemoryError;
 use cranelift_codegen::data_value::DataValue;
@@ -70,7 +75,7 @@ pub enum AddressRegion {
     Stack,
     Heap,
     Table,
-    GlobalValue,
+    Function,
 }
 
 impl AddressRegion {
@@ -80,7 +85,7 @@ impl AddressRegion {
             0 => AddressRegion::Stack,
             1 => AddressRegion::Heap,
             2 => AddressRegion::Table,
-            3 => AddressRegion::GlobalValue,
+            3 => AddressRegion::Function,
             _ => unreachable!(),
         }
     }
@@ -90,7 +95,7 @@ impl AddressRegion {
             AddressRegion::Stack => 0,
             AddressRegion::Heap => 1,
             AddressRegion::Table => 2,
-            AddressRegion::GlobalValue => 3,
+            AddressRegion::Function => 3,
         }
     }
 }
@@ -145,11 +150,11 @@ impl Address {
 
             (AddressSize::_32, AddressRegion::Heap) => 2,
             (AddressSize::_32, AddressRegion::Table) => 5,
-            (AddressSize::_32, AddressRegion::GlobalValue) => 6,
+            (AddressSize::_32, AddressRegion::Function) => 6,
 
             (AddressSize::_64, AddressRegion::Heap) => 6,
             (AddressSize::_64, AddressRegion::Table) => 10,
-            (AddressSize::_64, AddressRegion::GlobalValue) => 12,
+            (AddressSize::_64, AddressRegion::Function) => 12,
         }
     }
 
@@ -235,7 +240,7 @@ mod tests {
             AddressRegion::Stack,
             AddressRegion::Heap,
             AddressRegion::Table,
-            AddressRegion::GlobalValue,
+            AddressRegion::Function,
         ];
 
         for region in all_regions {
@@ -257,9 +262,9 @@ mod tests {
             (AddressSize::_32, AddressRegion::Table, 0, 0),
             (AddressSize::_32, AddressRegion::Table, 1, 1),
             (AddressSize::_32, AddressRegion::Table, 31, 0x1FF_FFFF),
-            (AddressSize::_32, AddressRegion::GlobalValue, 0, 0),
-            (AddressSize::_32, AddressRegion::GlobalValue, 1, 1),
-            (AddressSize::_32, AddressRegion::GlobalValue, 63, 0xFF_FFFF),
+            (AddressSize::_32, AddressRegion::Function, 0, 0),
+            (AddressSize::_32, AddressRegion::Function, 1, 1),
+            (AddressSize::_32, AddressRegion::Function, 63, 0xFF_FFFF),
             (AddressSize::_64, AddressRegion::Stack, 0, 0),
             (AddressSize::_64, AddressRegion::Stack, 0, 1),
             (
@@ -275,9 +280,9 @@ mod tests {
             (AddressSize::_64, AddressRegion::Table, 0, 0),
             (AddressSize::_64, AddressRegion::Table, 1, 1),
             (AddressSize::_64, AddressRegion::Table, 31, 0x1FF_FFFF),
-            (AddressSize::_64, AddressRegion::GlobalValue, 0, 0),
-            (AddressSize::_64, AddressRegion::GlobalValue, 1, 1),
-            (AddressSize::_64, AddressRegion::GlobalValue, 63, 0xFF_FFFF),
+            (AddressSize::_64, AddressRegion::Function, 0, 0),
+            (AddressSize::_64, AddressRegion::Function, 1, 1),
+            (AddressSize::_64, AddressRegion::Function, 63, 0xFF_FFFF),
         ];
 
         for (size, region, entry, offset) in test_addresses {
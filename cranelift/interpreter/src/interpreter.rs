@@ -8,12 +8,17 @@ use crate::frame::Frame;
 use crate::instruction::DfgInstructionContext;
 use crate::state::{MemoryError, State};
 use crate::step::{step, ControlFlow, StepError};
-use crate::value::ValueError;
+use crate::value::{Value, ValueError};
 use cranelift_codegen::data_value::DataValue;
 use cranelift_codegen::ir::condcodes::{FloatCC, IntCC};
-use cranelift_codegen::ir::{Block, FuncRef, Function, StackSlot, Type, Value as ValueRef};
+use cranelift_codegen::ir::{
+    ArgumentPurpose, Block, Constant, ExternalName, FuncRef, Function, GlobalValue,
+    GlobalValueData, StackSlot, Type, Value as ValueRef,
+};
+use cranelift_entity::{entity_impl, PrimaryMap};
 use log::trace;
 use std::collections::HashSet;
+use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::iter;
 use thiserror::Error;
@@ -90,8 +95,8 @@ impl<'a> Interpreter<'a> {
     /// instructions, which may continue in other blocks, until the function returns.
     fn block(&mut self, block: Block) -> Result<ControlFlow<'a, DataValue>, InterpreterError> {
         trace!("Block: {}", block);
-        let function = self.state.current_frame_mut().function;
-        let layout = &function.layout;
+        let mut function = self.state.current_frame_mut().function;
+        let mut layout = &function.layout;
         let mut maybe_inst = layout.first_inst(block);
         while let Some(inst) = maybe_inst {
             if self.consume_fuel() == FuelResult::Stop {
@@ -122,6 +127,25 @@ impl<'a> Interpreter<'a> {
                         .set_all(function.dfg.inst_results(inst), returned_arguments);
                     maybe_inst = layout.next_inst(inst)
                 }
+                ControlFlow::ReturnCall(called_function, arguments) => {
+                    // Replace the current frame with the callee's rather than nesting it, so
+                    // that tail recursion runs in constant space.
+                    trace!("Tail call: {}({:?})", called_function.name, arguments);
+                    self.state.pop_frame();
+                    let first_block = called_function
+                        .layout
+                        .blocks()
+                        .next()
+                        .expect("to have a first block");
+                    let parameters = called_function.dfg.block_params(first_block);
+                    self.state.push_frame(called_function);
+                    self.state
+                        .current_frame_mut()
+                        .set_all(parameters, arguments.to_vec());
+                    function = called_function;
+                    layout = &function.layout;
+                    maybe_inst = layout.first_inst(first_block)
+                }
                 ControlFlow::Return(returned_values) => {
                     self.state.pop_frame();
                     return Ok(ControlFlow::Return(returned_values));
@@ -172,6 +196,20 @@ pub enum InterpreterError {
     FuelExhausted,
 }
 
+/// An opaque reference to a heap registered with [InterpreterState::register_heap].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HeapId(u32);
+entity_impl!(HeapId, "heap");
+
+/// The initial contents of a heap registered with [InterpreterState::register_heap].
+#[derive(Debug, Clone)]
+pub enum HeapInit {
+    /// A zero-initialized heap of the given size, in bytes.
+    Zeroed(usize),
+    /// A heap initialized with the given bytes.
+    FromBacking(Vec<u8>),
+}
+
 /// Maintains the [Interpreter]'s state, implementing the [State] trait.
 pub struct InterpreterState<'a> {
     pub functions: FunctionStore<'a>,
@@ -179,9 +217,10 @@ pub struct InterpreterState<'a> {
     /// Number of bytes from the bottom of the stack where the current frame's stack space is
     pub frame_offset: usize,
     pub stack: Vec<u8>,
-    pub heap: Vec<u8>,
+    pub heaps: PrimaryMap<HeapId, Vec<u8>>,
     pub iflags: HashSet<IntCC>,
     pub fflags: HashSet<FloatCC>,
+    pub pinned_reg: DataValue,
 }
 
 impl Default for InterpreterState<'_> {
@@ -191,9 +230,10 @@ impl Default for InterpreterState<'_> {
             frame_stack: vec![],
             frame_offset: 0,
             stack: Vec::with_capacity(1024),
-            heap: vec![0; 1024],
+            heaps: PrimaryMap::new(),
             iflags: HashSet::new(),
             fflags: HashSet::new(),
+            pinned_reg: DataValue::U64(0),
         }
     }
 }
@@ -203,6 +243,32 @@ impl<'a> InterpreterState<'a> {
         Self { functions, ..self }
     }
 
+    /// Registers a new heap with the interpreter; its address can then be obtained with
+    /// [State::heap_address] and passed to the interpreted functions, e.g. through their `vmctx`.
+    pub fn register_heap(&mut self, init: HeapInit) -> HeapId {
+        let backing = match init {
+            HeapInit::Zeroed(size) => vec![0; size],
+            HeapInit::FromBacking(backing) => backing,
+        };
+        self.heaps.push(backing)
+    }
+
+    /// Retrieve the constant pool of the function at `entry` in the [FunctionStore], with each
+    /// constant laid out one after the other in the order of their handles.
+    fn constant_pool(&self, entry: u64) -> Option<Vec<u8>> {
+        let function = self
+            .functions
+            .get_by_index(FuncIndex::from_u32(u32::try_from(entry).ok()?))?;
+        Some(
+            function
+                .dfg
+                .constants
+                .iter()
+                .flat_map(|(_, data)| data.iter().copied())
+                .collect(),
+        )
+    }
+
     fn current_frame_mut(&mut self) -> &mut Frame<'a> {
         let num_frames = self.frame_stack.len();
         match num_frames {
@@ -310,47 +376,180 @@ impl<'a> State<'a, DataValue> for InterpreterState<'a> {
         Address::from_parts(size, AddressRegion::Stack, 0, final_offset)
     }
 
-    fn heap_address(&self, _size: AddressSize, _offset: u64) -> Result<Address, MemoryError> {
-        unimplemented!()
+    fn heap_address(
+        &self,
+        size: AddressSize,
+        heap_id: HeapId,
+        offset: u64,
+    ) -> Result<Address, MemoryError> {
+        let heap = self.heaps.get(heap_id).ok_or(MemoryError::InvalidEntry {
+            entry: heap_id.as_u32() as u64,
+            max: self.heaps.len() as u64,
+        })?;
+
+        // Allow addressing one past the end of the heap so that its bound can be computed.
+        if offset > heap.len() as u64 {
+            return Err(MemoryError::InvalidOffset {
+                offset,
+                max: heap.len() as u64,
+            });
+        }
+
+        Address::from_parts(size, AddressRegion::Heap, heap_id.as_u32() as u64, offset)
     }
 
-    fn checked_load(&self, addr: Address, ty: Type) -> Result<DataValue, MemoryError> {
-        let load_size = ty.bytes() as usize;
+    fn function_address(
+        &self,
+        size: AddressSize,
+        name: &ExternalName,
+    ) -> Result<Address, MemoryError> {
+        let index = self
+            .functions
+            .index_of(&name.to_string())
+            .ok_or_else(|| MemoryError::UnknownFunction(name.clone()))?;
+        Address::from_parts(size, AddressRegion::Function, index.as_u32() as u64, 0)
+    }
 
-        let src = match addr.region {
-            AddressRegion::Stack => {
-                let addr_start = addr.offset as usize;
-                let addr_end = addr_start + load_size;
-                if addr_end > self.stack.len() {
-                    return Err(MemoryError::OutOfBoundsLoad { addr, load_size });
-                }
+    fn get_function_from_address(&self, address: Address) -> Option<&'a Function> {
+        // Only the start of a function can be called; the rest of the address range holds its
+        // constant pool.
+        if address.region != AddressRegion::Function || address.offset != 0 {
+            return None;
+        }
+        self.functions
+            .get_by_index(FuncIndex::from_u32(u32::try_from(address.entry).ok()?))
+    }
+
+    fn constant_address(
+        &self,
+        size: AddressSize,
+        constant: Constant,
+    ) -> Result<Address, MemoryError> {
+        let function = self.get_current_function();
+        let offset = function
+            .dfg
+            .constants
+            .iter()
+            .take_while(|(handle, _)| **handle != constant)
+            .map(|(_, data)| data.len() as u64)
+            .sum();
+        let function_address = self.function_address(size, &function.name)?;
+        Address::from_parts(
+            size,
+            AddressRegion::Function,
+            function_address.entry,
+            offset,
+        )
+    }
+
+    fn resolve_global_value(&self, gv: GlobalValue) -> Result<DataValue, MemoryError> {
+        let function = self.get_current_function();
+        match &function.global_values[gv] {
+            GlobalValueData::VMContext => function
+                .special_param(ArgumentPurpose::VMContext)
+                .and_then(|vmctx| self.get_value(vmctx))
+                .ok_or(MemoryError::InvalidGlobalValue(gv)),
+            GlobalValueData::Load {
+                base,
+                offset,
+                global_type,
+                ..
+            } => {
+                let base = self.resolve_global_value(*base)?;
+                let addr = offset_address(base, i64::from(*offset))?;
+                self.checked_load(Address::try_from(addr)?, *global_type)
+            }
+            GlobalValueData::IAddImm { base, offset, .. } => {
+                let base = self.resolve_global_value(*base)?;
+                offset_address(base, i64::from(*offset))
+            }
+            GlobalValueData::Symbol { name, offset, .. } => {
+                // The interpreter has no notion of a target pointer width, so symbols always
+                // resolve to 64-bit addresses.
+                let addr = self.function_address(AddressSize::_64, name)?;
+                let addr = DataValue::try_from(addr)?;
+                offset_address(addr, i64::from(*offset))
+            }
+        }
+    }
 
-                &self.stack[addr_start..addr_end]
+    fn checked_load(&self, addr: Address, ty: Type) -> Result<DataValue, MemoryError> {
+        let load_size = ty.bytes() as usize;
+        let addr_start = addr.offset as usize;
+        let addr_end = addr_start + load_size;
+
+        let constants;
+        let memory: &[u8] = match addr.region {
+            AddressRegion::Stack => &self.stack,
+            AddressRegion::Heap => self.heaps.get(HeapId::from_u32(addr.entry as u32)).ok_or(
+                MemoryError::OutOfBoundsLoad {
+                    addr: addr.clone(),
+                    load_size,
+                },
+            )?,
+            AddressRegion::Function => {
+                constants = self
+                    .constant_pool(addr.entry)
+                    .ok_or(MemoryError::OutOfBoundsLoad {
+                        addr: addr.clone(),
+                        load_size,
+                    })?;
+                &constants
             }
-            _ => unimplemented!(),
+            // Tables aren't backed by any memory in the interpreter.
+            AddressRegion::Table => return Err(MemoryError::OutOfBoundsLoad { addr, load_size }),
         };
 
-        Ok(DataValue::read_from_slice(src, ty))
+        if addr_end > memory.len() {
+            return Err(MemoryError::OutOfBoundsLoad { addr, load_size });
+        }
+
+        Ok(DataValue::read_from_slice(
+            &memory[addr_start..addr_end],
+            ty,
+        ))
     }
 
     fn checked_store(&mut self, addr: Address, v: DataValue) -> Result<(), MemoryError> {
         let store_size = v.ty().bytes() as usize;
-
-        let dst = match addr.region {
-            AddressRegion::Stack => {
-                let addr_start = addr.offset as usize;
-                let addr_end = addr_start + store_size;
-                if addr_end > self.stack.len() {
-                    return Err(MemoryError::OutOfBoundsStore { addr, store_size });
-                }
-
-                &mut self.stack[addr_start..addr_end]
+        let addr_start = addr.offset as usize;
+        let addr_end = addr_start + store_size;
+
+        let memory: &mut [u8] = match addr.region {
+            AddressRegion::Stack => &mut self.stack,
+            AddressRegion::Heap => match self.heaps.get_mut(HeapId::from_u32(addr.entry as u32)) {
+                Some(heap) => heap,
+                None => return Err(MemoryError::OutOfBoundsStore { addr, store_size }),
+            },
+            // Functions and their constant pools are read-only, and tables
+            // aren't backed by any memory in the interpreter.
+            AddressRegion::Function | AddressRegion::Table => {
+                return Err(MemoryError::OutOfBoundsStore { addr, store_size })
             }
-            _ => unimplemented!(),
         };
 
-        Ok(v.write_to_slice(dst))
+        if addr_end > memory.len() {
+            return Err(MemoryError::OutOfBoundsStore { addr, store_size });
+        }
+
+        Ok(v.write_to_slice(&mut memory[addr_start..addr_end]))
+    }
+
+    fn get_pinned_reg(&self) -> DataValue {
+        self.pinned_reg.clone()
     }
+
+    fn set_pinned_reg(&mut self, v: DataValue) {
+        self.pinned_reg = v;
+    }
+}
+
+/// Offset an address `base` by `offset` bytes, wrapping around at the width of the address.
+fn offset_address(base: DataValue, offset: i64) -> Result<DataValue, MemoryError> {
+    let ty = base.ty();
+    let invalid = || MemoryError::InvalidAddress(base.clone());
+    let base_int = base.clone().into_int().map_err(|_| invalid())?;
+    DataValue::from_integer(base_int + offset as i128, ty).map_err(|_| invalid())
 }
 
 #[cfg(test)]
@@ -463,6 +662,35 @@ mod tests {
         assert_eq!(result, vec![DataValue::I32(0)])
     }
 
+    // Tail calls replace the current frame, so deep tail recursion must neither grow the
+    // interpreter's frame stack nor the host stack.
+    #[test]
+    fn tail_call_recursion() {
+        let code = "
+        function %count(i32, i32) -> i32 tail {
+            fn0 = %count(i32, i32) -> i32 tail
+        block0(v0: i32, v1: i32):
+            brz v0, block1
+            v2 = iadd_imm v0, -1
+            v3 = iadd_imm v1, 1
+            return_call fn0(v2, v3)
+        block1:
+            return v1
+        }";
+
+        let mut env = FunctionStore::default();
+        let funcs = parse_functions(code).unwrap().to_vec();
+        funcs.iter().for_each(|f| env.add(f.name.to_string(), f));
+
+        let state = InterpreterState::default().with_function_store(env);
+        let result = Interpreter::new(state)
+            .call_by_name("%count", &[DataValue::I32(100_000), DataValue::I32(0)])
+            .unwrap()
+            .unwrap_return();
+
+        assert_eq!(result, vec![DataValue::I32(100_000)])
+    }
+
     #[test]
     fn state_flags() {
         let mut state = InterpreterState::default();
@@ -720,4 +948,76 @@ mod tests {
 
         assert_eq!(trap, CraneliftTrap::User(TrapCode::HeapOutOfBounds));
     }
+
+    #[test]
+    fn out_of_bounds_heap_load_traps() {
+        let code = "
+        function %heap_load(i64) -> i64 {
+        block0(v0: i64):
+            v1 = load.i64 v0+4
+            return v1
+        }";
+
+        let func = parse_functions(code).unwrap().into_iter().next().unwrap();
+        let mut env = FunctionStore::default();
+        env.add(func.name.to_string(), &func);
+        let mut state = InterpreterState::default().with_function_store(env);
+        let heap = state.register_heap(HeapInit::Zeroed(8));
+        let addr = state.heap_address(AddressSize::_64, heap, 0).unwrap();
+        let addr = DataValue::try_from(addr).unwrap();
+        let trap = Interpreter::new(state)
+            .call_by_name("%heap_load", &[addr])
+            .unwrap()
+            .unwrap_trap();
+
+        assert_eq!(trap, CraneliftTrap::User(TrapCode::HeapOutOfBounds));
+    }
+
+    #[test]
+    fn table_load_traps() {
+        let code = "
+        function %table_load(i64) -> i64 {
+        block0(v0: i64):
+            v1 = load.i64 v0
+            return v1
+        }";
+
+        let func = parse_functions(code).unwrap().into_iter().next().unwrap();
+        let mut env = FunctionStore::default();
+        env.add(func.name.to_string(), &func);
+        let state = InterpreterState::default().with_function_store(env);
+        let addr = Address::from_parts(AddressSize::_64, AddressRegion::Table, 0, 0).unwrap();
+        let addr = DataValue::try_from(addr).unwrap();
+        let trap = Interpreter::new(state)
+            .call_by_name("%table_load", &[addr])
+            .unwrap()
+            .unwrap_trap();
+
+        assert_eq!(trap, CraneliftTrap::User(TrapCode::HeapOutOfBounds));
+    }
+
+    #[test]
+    fn call_indirect_to_non_function_traps() {
+        let code = "
+        function %call_stack() {
+            ss0 = explicit_slot 8
+            sig0 = ()
+
+        block0:
+            v0 = stack_addr.i64 ss0
+            call_indirect sig0, v0()
+            return
+        }";
+
+        let func = parse_functions(code).unwrap().into_iter().next().unwrap();
+        let mut env = FunctionStore::default();
+        env.add(func.name.to_string(), &func);
+        let state = InterpreterState::default().with_function_store(env);
+        let trap = Interpreter::new(state)
+            .call_by_name("%call_stack", &[])
+            .unwrap()
+            .unwrap_trap();
+
+        assert_eq!(trap, CraneliftTrap::User(TrapCode::HeapOutOfBounds));
+    }
 }
@@ -1,9 +1,12 @@
 //! Cranelift instructions modify the state of the machine; the [State] trait describes these
 //! ways this can happen.
 use crate::address::{Address, AddressSize};
+use crate::interpreter::HeapId;
 use cranelift_codegen::data_value::DataValue;
 use cranelift_codegen::ir::condcodes::{FloatCC, IntCC};
-use cranelift_codegen::ir::{FuncRef, Function, StackSlot, Type, Value};
+use cranelift_codegen::ir::{
+    Constant, ExternalName, FuncRef, Function, GlobalValue, StackSlot, Type, Value,
+};
 use cranelift_entity::PrimaryMap;
 use smallvec::SmallVec;
 use thiserror::Error;
@@ -66,14 +69,41 @@ pub trait State<'a, V> {
         slot: StackSlot,
         offset: u64,
     ) -> Result<Address, MemoryError>;
-    /// Computes a heap address
-    fn heap_address(&self, size: AddressSize, offset: u64) -> Result<Address, MemoryError>;
+    /// Computes the address of a heap, including an offset.
+    fn heap_address(
+        &self,
+        size: AddressSize,
+        heap_id: HeapId,
+        offset: u64,
+    ) -> Result<Address, MemoryError>;
+    /// Computes the address of the function with the given `name`.
+    fn function_address(
+        &self,
+        size: AddressSize,
+        name: &ExternalName,
+    ) -> Result<Address, MemoryError>;
+    /// Retrieve the [Function] that the given `address` points to, if any.
+    fn get_function_from_address(&self, address: Address) -> Option<&'a Function>;
+    /// Computes the address of a constant in the constant pool of the currently executing
+    /// [Function].
+    fn constant_address(
+        &self,
+        size: AddressSize,
+        constant: Constant,
+    ) -> Result<Address, MemoryError>;
+    /// Compute the value of a [GlobalValue] in the currently executing [Function].
+    fn resolve_global_value(&self, gv: GlobalValue) -> Result<V, MemoryError>;
     /// Retrieve a value `V` from memory at the given `address`, checking if it belongs either to the
     /// stack or to one of the heaps; the number of bytes loaded corresponds to the specified [Type].
     fn checked_load(&self, address: Address, ty: Type) -> Result<V, MemoryError>;
     /// Store a value `V` into memory at the given `address`, checking if it belongs either to the
     /// stack or to one of the heaps; the number of bytes stored corresponds to the specified [Type].
     fn checked_store(&mut self, address: Address, v: V) -> Result<(), MemoryError>;
+
+    /// Retrieve the value of the pinned register.
+    fn get_pinned_reg(&self) -> V;
+    /// Set the value of the pinned register.
+    fn set_pinned_reg(&mut self, v: V);
 }
 
 #[derive(Error, Debug)]
@@ -90,6 +120,10 @@ pub enum MemoryError {
     OutOfBoundsLoad { addr: Address, load_size: usize },
     #[error("Store of {store_size} bytes is larger than available size at address {addr:?}")]
     OutOfBoundsStore { addr: Address, store_size: usize },
+    #[error("Unable to find the function {0} in the function store")]
+    UnknownFunction(ExternalName),
+    #[error("Unable to resolve the global value {0}")]
+    InvalidGlobalValue(GlobalValue),
 }
 
 /// This dummy state allows interpretation over an immutable mapping of values in a single frame.
@@ -102,7 +136,7 @@ impl<'a, V> ImmutableRegisterState<'a, V> {
 
 impl<'a, V> State<'a, V> for ImmutableRegisterState<'a, V>
 where
-    V: Clone,
+    V: crate::value::Value,
 {
     fn get_function(&self, _func_ref: FuncRef) -> Option<&'a Function> {
         None
@@ -145,21 +179,74 @@ where
     fn stack_address(
         &self,
         _size: AddressSize,
-        _slot: StackSlot,
+        slot: StackSlot,
         _offset: u64,
     ) -> Result<Address, MemoryError> {
-        unimplemented!()
+        // There's no stack, and so no stack slots, in this state.
+        Err(MemoryError::InvalidEntry {
+            entry: slot.as_u32() as u64,
+            max: 0,
+        })
     }
 
-    fn heap_address(&self, _size: AddressSize, _offset: u64) -> Result<Address, MemoryError> {
-        unimplemented!()
+    fn heap_address(
+        &self,
+        _size: AddressSize,
+        heap_id: HeapId,
+        _offset: u64,
+    ) -> Result<Address, MemoryError> {
+        Err(MemoryError::InvalidEntry {
+            entry: heap_id.as_u32() as u64,
+            max: 0,
+        })
+    }
+
+    fn function_address(
+        &self,
+        _size: AddressSize,
+        name: &ExternalName,
+    ) -> Result<Address, MemoryError> {
+        Err(MemoryError::UnknownFunction(name.clone()))
+    }
+
+    fn get_function_from_address(&self, _address: Address) -> Option<&'a Function> {
+        None
+    }
+
+    fn constant_address(
+        &self,
+        _size: AddressSize,
+        constant: Constant,
+    ) -> Result<Address, MemoryError> {
+        Err(MemoryError::InvalidEntry {
+            entry: constant.as_u32() as u64,
+            max: 0,
+        })
+    }
+
+    fn resolve_global_value(&self, gv: GlobalValue) -> Result<V, MemoryError> {
+        Err(MemoryError::InvalidGlobalValue(gv))
+    }
+
+    fn checked_load(&self, addr: Address, ty: Type) -> Result<V, MemoryError> {
+        Err(MemoryError::OutOfBoundsLoad {
+            addr,
+            load_size: ty.bytes() as usize,
+        })
+    }
+
+    fn checked_store(&mut self, addr: Address, v: V) -> Result<(), MemoryError> {
+        Err(MemoryError::OutOfBoundsStore {
+            addr,
+            store_size: v.ty().bytes() as usize,
+        })
     }
 
-    fn checked_load(&self, _addr: Address, _ty: Type) -> Result<V, MemoryError> {
+    fn get_pinned_reg(&self) -> V {
         unimplemented!()
     }
 
-    fn checked_store(&mut self, _addr: Address, _v: V) -> Result<(), MemoryError> {
+    fn set_pinned_reg(&mut self, _v: V) {
         unimplemented!()
     }
 }
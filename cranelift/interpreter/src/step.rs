@@ -5,9 +5,10 @@ use crate::instruction::InstructionContext;
 use crate::state::{MemoryError, State};
 use crate::value::{Value, ValueConversionKind, ValueError, ValueResult};
 use cranelift_codegen::data_value::DataValue;
-use cranelift_codegen::ir::condcodes::{FloatCC, IntCC};
+use cranelift_codegen::ir::condcodes::{CondCode, FloatCC, IntCC};
 use cranelift_codegen::ir::{
-    types, Block, FuncRef, Function, InstructionData, Opcode, TrapCode, Value as ValueRef,
+    types, Block, FuncRef, Function, HeapStyle, InstructionData, Opcode, TrapCode,
+    Value as ValueRef,
 };
 use log::trace;
 use smallvec::{smallvec, SmallVec};
@@ -121,6 +122,8 @@ where
         MemoryError::InvalidEntry { .. } => TrapCode::HeapOutOfBounds,
         MemoryError::OutOfBoundsStore { .. } => TrapCode::HeapOutOfBounds,
         MemoryError::OutOfBoundsLoad { .. } => TrapCode::HeapOutOfBounds,
+        MemoryError::UnknownFunction(_) => TrapCode::HeapOutOfBounds,
+        MemoryError::InvalidGlobalValue(_) => TrapCode::HeapOutOfBounds,
     };
 
     // Assigns or traps depending on the value of the result
@@ -135,14 +138,12 @@ where
         Err(e) => ControlFlow::Trap(CraneliftTrap::User(memerror_to_trap(e))),
     };
 
-    let calculate_addr = |imm: V, args: SmallVec<[V; 1]>| -> ValueResult<u64> {
-        let imm = imm.convert(ValueConversionKind::ZeroExtend(ctrl_ty))?;
-        let args = args
-            .into_iter()
-            .map(|v| v.convert(ValueConversionKind::ZeroExtend(ctrl_ty)))
-            .collect::<ValueResult<SmallVec<[V; 1]>>>()?;
-
-        Ok(sum(imm, args)? as u64)
+    // Sums the address arguments of a memory access with its offset immediate. Note that the
+    // address type may differ from the controlling type, e.g. in `load.i8` from an `i64` address.
+    let calculate_addr = |imm: V, args: SmallVec<[V; 1]>| -> ValueResult<DataValue> {
+        let addr_ty = args[0].ty();
+        let imm = Value::int(imm.into_int()?, addr_ty)?;
+        address_value(sum(imm, args)?, addr_ty)
     };
 
     // Interpret a binary instruction with the given `op`, assigning the resulting value to the
@@ -282,8 +283,47 @@ where
                 unreachable!()
             }
         }
-        Opcode::CallIndirect => unimplemented!("CallIndirect"),
-        Opcode::FuncAddr => unimplemented!("FuncAddr"),
+        Opcode::CallIndirect | Opcode::ReturnCallIndirect => {
+            let callee = arg(0)?;
+            let addr = address_value(callee.clone().into_int()?, callee.ty())?;
+            match Address::try_from(addr)
+                .ok()
+                .and_then(|addr| state.get_function_from_address(addr))
+            {
+                Some(function) if inst.opcode() == Opcode::CallIndirect => {
+                    ControlFlow::Call(function, args_range(1..)?)
+                }
+                Some(function) => ControlFlow::ReturnCall(function, args_range(1..)?),
+                // Natively, calling an address that is not a function would crash.
+                None => ControlFlow::Trap(CraneliftTrap::User(TrapCode::HeapOutOfBounds)),
+            }
+        }
+        Opcode::ReturnCall => {
+            if let InstructionData::Call { func_ref, .. } = inst {
+                let function = state
+                    .get_function(func_ref)
+                    .ok_or(StepError::UnknownFunction(func_ref))?;
+                ControlFlow::ReturnCall(function, args()?)
+            } else {
+                unreachable!()
+            }
+        }
+        Opcode::FuncAddr => {
+            if let InstructionData::FuncAddr { func_ref, .. } = inst {
+                let function = state
+                    .get_function(func_ref)
+                    .ok_or(StepError::UnknownFunction(func_ref))?;
+                assign_or_memtrap({
+                    AddressSize::try_from(ctrl_ty).and_then(|addr_size| {
+                        let addr = state.function_address(addr_size, &function.name)?;
+                        let dv = DataValue::try_from(addr)?;
+                        Ok(dv.into())
+                    })
+                })
+            } else {
+                unreachable!()
+            }
+        }
         Opcode::Load
         | Opcode::LoadComplex
         | Opcode::Uload8
@@ -310,7 +350,23 @@ where
         | Opcode::Uload32x2Complex
         | Opcode::Sload32x2
         | Opcode::Sload32x2Complex => {
-            let ctrl_ty = inst_context.controlling_type().unwrap();
+            // The extending vector loads are controlled by their address type, so their result
+            // type is implied by the opcode instead.
+            let ctrl_ty = match inst.opcode() {
+                Opcode::Uload8x8
+                | Opcode::Uload8x8Complex
+                | Opcode::Sload8x8
+                | Opcode::Sload8x8Complex => types::I16X8,
+                Opcode::Uload16x4
+                | Opcode::Uload16x4Complex
+                | Opcode::Sload16x4
+                | Opcode::Sload16x4Complex => types::I32X4,
+                Opcode::Uload32x2
+                | Opcode::Uload32x2Complex
+                | Opcode::Sload32x2
+                | Opcode::Sload32x2Complex => types::I64X2,
+                _ => inst_context.controlling_type().unwrap(),
+            };
             let (load_ty, kind) = match inst.opcode() {
                 Opcode::Load | Opcode::LoadComplex => (ctrl_ty, None),
                 Opcode::Uload8 | Opcode::Uload8Complex => {
@@ -333,16 +389,22 @@ where
                 }
                 Opcode::Uload8x8
                 | Opcode::Uload8x8Complex
-                | Opcode::Sload8x8
-                | Opcode::Sload8x8Complex
                 | Opcode::Uload16x4
                 | Opcode::Uload16x4Complex
+                | Opcode::Uload32x2
+                | Opcode::Uload32x2Complex => (
+                    types::I64,
+                    Some(ValueConversionKind::ZeroExtend(ctrl_ty.lane_type())),
+                ),
+                Opcode::Sload8x8
+                | Opcode::Sload8x8Complex
                 | Opcode::Sload16x4
                 | Opcode::Sload16x4Complex
-                | Opcode::Uload32x2
-                | Opcode::Uload32x2Complex
                 | Opcode::Sload32x2
-                | Opcode::Sload32x2Complex => unimplemented!(),
+                | Opcode::Sload32x2Complex => (
+                    types::I64,
+                    Some(ValueConversionKind::SignExtend(ctrl_ty.lane_type())),
+                ),
                 _ => unreachable!(),
             };
 
@@ -352,6 +414,18 @@ where
             );
 
             match (loaded, kind) {
+                (ControlFlow::Assign(ret), Some(c)) if ctrl_ty.is_vector() => {
+                    // Split the loaded 64 bits into half-width lanes and extend each of them.
+                    let half_ty = ctrl_ty.lane_type().half_width().unwrap();
+                    let bits = ret[0].clone().into_int()?;
+                    let lanes = (0..ctrl_ty.lane_count() as u32)
+                        .map(|i| {
+                            let lane = bits >> (i * half_ty.bits() as u32);
+                            V::int(lane, half_ty)?.convert(c.clone())
+                        })
+                        .collect::<ValueResult<SimdVec<V>>>()?;
+                    assign(vectorizelanes(&lanes, ctrl_ty)?)
+                }
                 (ControlFlow::Assign(ret), Some(c)) => ControlFlow::Assign(
                     ret.into_iter()
                         .map(|loaded| loaded.convert(c.clone()))
@@ -424,19 +498,83 @@ where
                 })
             })
         }
-        Opcode::GlobalValue => unimplemented!("GlobalValue"),
-        Opcode::SymbolValue => unimplemented!("SymbolValue"),
+        Opcode::GlobalValue | Opcode::SymbolValue => {
+            if let InstructionData::UnaryGlobalValue { global_value, .. } = inst {
+                assign(state.resolve_global_value(global_value)?)
+            } else {
+                unreachable!()
+            }
+        }
         Opcode::TlsValue => unimplemented!("TlsValue"),
-        Opcode::HeapAddr => unimplemented!("HeapAddr"),
-        Opcode::GetPinnedReg => unimplemented!("GetPinnedReg"),
-        Opcode::SetPinnedReg => unimplemented!("SetPinnedReg"),
-        Opcode::TableAddr => unimplemented!("TableAddr"),
+        Opcode::HeapAddr => {
+            if let InstructionData::HeapAddr { heap, imm, .. } = inst {
+                let heap_data = &state.get_current_function().heaps[heap];
+                let index = arg(0)?.convert(ValueConversionKind::ZeroExtend(ctrl_ty))?;
+                let access_end = unsigned(index.clone())? + u128::from(u32::from(imm));
+                let bound = match heap_data.style {
+                    HeapStyle::Static { bound } => u128::from(u64::from(bound)),
+                    HeapStyle::Dynamic { bound_gv } => {
+                        unsigned(state.resolve_global_value(bound_gv)?)?
+                    }
+                };
+
+                if access_end > bound {
+                    ControlFlow::Trap(CraneliftTrap::User(TrapCode::HeapOutOfBounds))
+                } else {
+                    let base = state.resolve_global_value(heap_data.base)?;
+                    assign(Value::add(base, index)?)
+                }
+            } else {
+                unreachable!()
+            }
+        }
+        Opcode::GetPinnedReg => assign(state.get_pinned_reg()),
+        Opcode::SetPinnedReg => {
+            let arg0 = arg(0)?;
+            state.set_pinned_reg(arg0);
+            ControlFlow::Continue
+        }
+        Opcode::TableAddr => {
+            if let InstructionData::TableAddr { table, offset, .. } = inst {
+                let table_data = &state.get_current_function().tables[table];
+                let index = arg(0)?.convert(ValueConversionKind::ZeroExtend(ctrl_ty))?;
+                let bound = state.resolve_global_value(table_data.bound_gv)?;
+
+                if unsigned(index.clone())? >= unsigned(bound)? {
+                    ControlFlow::Trap(CraneliftTrap::User(TrapCode::TableOutOfBounds))
+                } else {
+                    let base = state.resolve_global_value(table_data.base_gv)?;
+                    let element_size =
+                        Value::int(u64::from(table_data.element_size) as i128, ctrl_ty)?;
+                    let offset = Value::int(i64::from(offset) as i128, ctrl_ty)?;
+                    let element_offset = Value::add(Value::mul(index, element_size)?, offset)?;
+                    assign(Value::add(base, element_offset)?)
+                }
+            } else {
+                unreachable!()
+            }
+        }
         Opcode::Iconst => assign(Value::int(imm().into_int()?, ctrl_ty)?),
         Opcode::F32const => assign(imm()),
         Opcode::F64const => assign(imm()),
         Opcode::Bconst => assign(imm()),
         Opcode::Vconst => assign(imm()),
-        Opcode::ConstAddr => unimplemented!("ConstAddr"),
+        Opcode::ConstAddr => {
+            if let InstructionData::UnaryConst {
+                constant_handle, ..
+            } = inst
+            {
+                assign_or_memtrap({
+                    AddressSize::try_from(ctrl_ty).and_then(|addr_size| {
+                        let addr = state.constant_address(addr_size, constant_handle)?;
+                        let dv = DataValue::try_from(addr)?;
+                        Ok(dv.into())
+                    })
+                })
+            } else {
+                unreachable!()
+            }
+        }
         Opcode::Null => unimplemented!("Null"),
         Opcode::Nop => ControlFlow::Continue,
         Opcode::Select => choose(arg(0)?.into_bool()?, arg(1)?, arg(2)?),
@@ -621,13 +759,24 @@ where
             Value::add(Value::add(arg(0)?, arg(1)?)?, Value::int(1, ctrl_ty)?)?,
             Value::add(arg(0)?, arg(1)?)?,
         ),
-        Opcode::IaddIfcin => unimplemented!("IaddIfcin"),
+        Opcode::IaddIfcin => {
+            let carry_in = state.has_iflag(CARRY_FLAG);
+            let (sum, _) = add_with_carry(arg(0)?, arg(1)?, carry_in, ctrl_ty)?;
+            assign(sum)
+        }
         Opcode::IaddCout => {
             let sum = Value::add(arg(0)?, arg(1)?)?;
             let carry = Value::lt(&sum, &arg(0)?)? && Value::lt(&sum, &arg(1)?)?;
             assign_multiple(&[sum, Value::bool(carry, types::B1)?])
         }
-        Opcode::IaddIfcout => unimplemented!("IaddIfcout"),
+        Opcode::IaddIfcout | Opcode::IaddIfcarry => {
+            let carry_in = inst.opcode() == Opcode::IaddIfcarry && state.has_iflag(CARRY_FLAG);
+            let (sum, carry_out) = add_with_carry(arg(0)?, arg(1)?, carry_in, ctrl_ty)?;
+            set_carry_flag(state, carry_out);
+            // The `iflags` result is modeled by the interpreter's flags, so assign the carry as a
+            // stand-in for it.
+            assign_multiple(&[sum, Value::bool(carry_out, types::B1)?])
+        }
         Opcode::IaddCarry => {
             let mut sum = Value::add(arg(0)?, arg(1)?)?;
             if Value::into_bool(arg(2)?)? {
@@ -636,19 +785,28 @@ where
             let carry = Value::lt(&sum, &arg(0)?)? && Value::lt(&sum, &arg(1)?)?;
             assign_multiple(&[sum, Value::bool(carry, types::B1)?])
         }
-        Opcode::IaddIfcarry => unimplemented!("IaddIfcarry"),
         Opcode::IsubBin => choose(
             Value::into_bool(arg(2)?)?,
             Value::sub(arg(0)?, Value::add(arg(1)?, Value::int(1, ctrl_ty)?)?)?,
             Value::sub(arg(0)?, arg(1)?)?,
         ),
-        Opcode::IsubIfbin => unimplemented!("IsubIfbin"),
+        Opcode::IsubIfbin => {
+            let borrow_in = state.has_iflag(CARRY_FLAG);
+            let (difference, _) = sub_with_borrow(arg(0)?, arg(1)?, borrow_in, ctrl_ty)?;
+            assign(difference)
+        }
         Opcode::IsubBout => {
             let sum = Value::sub(arg(0)?, arg(1)?)?;
             let borrow = Value::lt(&arg(0)?, &arg(1)?)?;
             assign_multiple(&[sum, Value::bool(borrow, types::B1)?])
         }
-        Opcode::IsubIfbout => unimplemented!("IsubIfbout"),
+        Opcode::IsubIfbout | Opcode::IsubIfborrow => {
+            let borrow_in = inst.opcode() == Opcode::IsubIfborrow && state.has_iflag(CARRY_FLAG);
+            let (difference, borrow_out) = sub_with_borrow(arg(0)?, arg(1)?, borrow_in, ctrl_ty)?;
+            set_carry_flag(state, borrow_out);
+            // As above, the borrow stands in for the `iflags` result.
+            assign_multiple(&[difference, Value::bool(borrow_out, types::B1)?])
+        }
         Opcode::IsubBorrow => {
             let rhs = if Value::into_bool(arg(2)?)? {
                 Value::add(arg(1)?, Value::int(1, ctrl_ty)?)?
@@ -659,7 +817,6 @@ where
             let sum = Value::sub(arg(0)?, rhs)?;
             assign_multiple(&[sum, Value::bool(borrow, types::B1)?])
         }
-        Opcode::IsubIfborrow => unimplemented!("IsubIfborrow"),
         Opcode::Band => binary(Value::and, arg(0)?, arg(1)?)?,
         Opcode::Bor => binary(Value::or, arg(0)?, arg(1)?)?,
         Opcode::Bxor => binary(Value::xor, arg(0)?, arg(1)?)?,
@@ -1049,6 +1206,9 @@ pub enum ControlFlow<'a, V> {
     ContinueAt(Block, SmallVec<[V; 1]>),
     /// Indicates a call the given [Function] with the supplied arguments.
     Call(&'a Function, SmallVec<[V; 1]>),
+    /// Indicates a tail call to the given [Function] with the supplied arguments, replacing the
+    /// current frame; the callee's return values are returned from the current function.
+    ReturnCall(&'a Function, SmallVec<[V; 1]>),
     /// Return from the current function with the given parameters, e.g.: `return [v1, v2]`.
     Return(SmallVec<[V; 1]>),
     /// Stop with a program-generated trap; note that these are distinct from errors that may occur
@@ -1088,6 +1248,59 @@ pub enum CraneliftTrap {
     Resumable,
 }
 
+/// The integer condition code that the interpreter uses to represent the carry (or borrow) flag
+/// produced and consumed by the `*_if*` carry instructions. This follows the x86 convention, where
+/// an unsigned overflow is observed as [IntCC::UnsignedLessThan] (e.g. by `trapif ult`).
+const CARRY_FLAG: IntCC = IntCC::UnsignedLessThan;
+
+/// Replace the interpreter's integer flags with the result of a carry-producing instruction.
+fn set_carry_flag<'a, V>(state: &mut dyn State<'a, V>, carry: bool) {
+    state.clear_flags();
+    if carry {
+        state.set_iflag(CARRY_FLAG);
+    } else {
+        state.set_iflag(CARRY_FLAG.inverse());
+    }
+}
+
+/// Interpret an integer value as unsigned, e.g. to perform bounds checks.
+fn unsigned<V: Value>(v: V) -> ValueResult<u128> {
+    Ok(v.convert(ValueConversionKind::ToUnsigned)?.into_int()? as u128)
+}
+
+/// Convert an integer into an address value of type `addr_ty`, wrapping it around at the width
+/// of that type.
+fn address_value(addr: i128, addr_ty: types::Type) -> ValueResult<DataValue> {
+    DataValue::from_integer(addr, addr_ty).map_err(|_| ValueError::InvalidValue(addr_ty))
+}
+
+/// Compute `x + y + carry_in`, returning the wrapped sum and whether the unsigned addition
+/// carried out of `ty`.
+fn add_with_carry<V: Value>(x: V, y: V, carry_in: bool, ty: types::Type) -> ValueResult<(V, bool)> {
+    let (sum, c1) = unsigned(x)?.overflowing_add(unsigned(y)?);
+    let (sum, c2) = sum.overflowing_add(carry_in as u128);
+    let carry = if ty.bits() < 128 {
+        sum >> ty.bits() != 0
+    } else {
+        c1 || c2
+    };
+    Ok((Value::int(sum as i128, ty)?, carry))
+}
+
+/// Compute `x - (y + borrow_in)`, returning the wrapped difference and whether the unsigned
+/// subtraction borrowed.
+fn sub_with_borrow<V: Value>(
+    x: V,
+    y: V,
+    borrow_in: bool,
+    ty: types::Type,
+) -> ValueResult<(V, bool)> {
+    let (x, y) = (unsigned(x)?, unsigned(y)?);
+    let borrow = x < y || (borrow_in && x == y);
+    let difference = x.wrapping_sub(y).wrapping_sub(borrow_in as u128);
+    Ok((Value::int(difference as i128, ty)?, borrow))
+}
+
 /// Compare two values using the given integer condition `code`.
 fn icmp<V>(ctrl_ty: types::Type, code: IntCC, left: &V, right: &V) -> ValueResult<V>
 where
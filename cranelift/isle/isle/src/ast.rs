@@ -67,6 +67,9 @@ pub struct Decl {
     pub term: Ident,
     pub arg_tys: Vec<Ident>,
     pub ret_ty: Ident,
+    /// Whether this term's rules may match the same inputs at the same
+    /// priority, which is otherwise an error.
+    pub overlapping: bool,
     pub pos: Pos,
 }
 
@@ -1,12 +1,22 @@
 //! Compilation process, from AST to Sema to Sequences of Insts.
 
-use crate::error::Result;
-use crate::{ast, codegen, sema, trie};
+use crate::error::{Error, Result};
+use crate::{ast, codegen, overlap, sema, trie};
 
 /// Compile the given AST definitions into Rust source code.
+///
+/// Any warnings are discarded; see `compile_with_warnings`.
 pub fn compile(defs: &ast::Defs) -> Result<String> {
+    let (code, _warnings) = compile_with_warnings(defs)?;
+    Ok(code)
+}
+
+/// Compile the given AST definitions into Rust source code, also returning
+/// warnings about terms declared as overlapping whose rules never overlap.
+pub fn compile_with_warnings(defs: &ast::Defs) -> Result<(String, Vec<Error>)> {
     let mut typeenv = sema::TypeEnv::from_ast(defs)?;
     let termenv = sema::TermEnv::from_ast(&mut typeenv, defs)?;
+    let warnings = overlap::check(&mut typeenv, &termenv)?;
     let tries = trie::build_tries(&typeenv, &termenv);
-    Ok(codegen::codegen(&typeenv, &termenv, &tries))
+    Ok((codegen::codegen(&typeenv, &termenv, &tries), warnings))
 }
@@ -51,6 +51,23 @@ pub enum Error {
         span: SourceSpan,
     },
 
+    /// The input ISLE source has rules that are ambiguous or unreachable, or
+    /// declares a term as overlapping when its rules don't overlap.
+    #[error("overlapping rules: {msg}")]
+    #[diagnostic()]
+    OverlapError {
+        /// The error message.
+        msg: String,
+
+        /// The input ISLE source.
+        #[source_code]
+        src: Source,
+
+        /// The location of the offending rule.
+        #[label("{msg}")]
+        span: SourceSpan,
+    },
+
     /// Multiple errors.
     #[error("Found {} errors:\n\n{}",
             self.unwrap_errors().len(),
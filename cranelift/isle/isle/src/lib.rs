@@ -24,6 +24,7 @@ pub mod compile;
 pub mod error;
 pub mod ir;
 pub mod lexer;
+pub mod overlap;
 pub mod parser;
 pub mod sema;
 pub mod trie;
@@ -0,0 +1,375 @@
+//! Overlap detection for rules.
+//!
+//! When more than one rule of a term can match the same input, the generated
+//! code picks the rule with the highest priority. Among rules with the same
+//! priority the choice is an artifact of how the decision trie is built, so it
+//! is an error for two rules to have equal priorities if they may match the
+//! same input, unless their term is declared with `(decl overlapping ...)`.
+//! Rules that can never match, because a rule with a higher priority matches
+//! everything they would or because another rule with the same priority has an
+//! equivalent pattern, are always errors.
+//!
+//! The analysis works on the patterns produced by semantic analysis, after
+//! extractor macros have been expanded. It is conservative: two patterns are
+//! only considered disjoint when no value can match both regardless of what
+//! external extractors do, and a pattern only shadows another when it matches
+//! every value the other does. External extractors are assumed to be pure, so
+//! two uses of the same extractor on the same value produce the same results.
+
+use crate::error::{Error, Result};
+use crate::sema::{
+    ExtractorKind, Pattern, Rule, Term, TermArgPattern, TermEnv, TermId, TermKind, TypeEnv,
+};
+use std::collections::BTreeMap;
+
+/// Check the rules of every term for overlap.
+///
+/// Unreachable rules and, outside of terms declared as `overlapping`, rules
+/// that may match the same input as another rule with the same priority are
+/// returned as errors. The returned warnings describe terms declared as
+/// `overlapping` whose rules don't overlap.
+pub fn check(tyenv: &mut TypeEnv, termenv: &TermEnv) -> Result<Vec<Error>> {
+    let mut rules_by_term: BTreeMap<TermId, Vec<&Rule>> = BTreeMap::new();
+    for rule in &termenv.rules {
+        if let Some(term) = rule.lhs.root_term() {
+            rules_by_term.entry(term).or_default().push(rule);
+        }
+    }
+
+    let mut warnings = vec![];
+    for term in &termenv.terms {
+        let rules = rules_by_term
+            .get(&term.id)
+            .map_or(&[][..], |rules| &rules[..]);
+        check_term_rules(tyenv, termenv, term, rules, &mut warnings);
+    }
+
+    tyenv.return_errors()?;
+    Ok(warnings)
+}
+
+fn check_term_rules(
+    tyenv: &mut TypeEnv,
+    termenv: &TermEnv,
+    term: &Term,
+    rules: &[&Rule],
+    warnings: &mut Vec<Error>,
+) {
+    let analysis = Analysis { termenv };
+    let prio = |rule: &Rule| rule.prio.unwrap_or(0);
+    let mut any_overlap = false;
+
+    for (i, rule) in rules.iter().enumerate() {
+        // A rule that is shadowed is reported once, against the first rule
+        // that shadows it, and isn't additionally reported as overlapping.
+        let shadowing_rule = rules.iter().enumerate().find(|&(j, other)| {
+            j != i
+                && analysis.subsumes(&other.lhs, &rule.lhs)
+                && (prio(other) > prio(rule)
+                    // The trie keeps only the first of two rules with
+                    // equivalent patterns and equal priorities.
+                    || (j < i
+                        && prio(other) == prio(rule)
+                        && analysis.subsumes(&rule.lhs, &other.lhs)))
+        });
+        if let Some((_, other)) = shadowing_rule {
+            let reason = if prio(other) > prio(rule) {
+                "has a higher priority and matches all of its inputs"
+            } else {
+                "has the same priority and matches exactly the same inputs"
+            };
+            let msg = format!(
+                "Rule can never match: the rule at {} {}",
+                other.pos.pretty_print_line(&tyenv.filenames),
+                reason
+            );
+            let err = tyenv.overlap_error(rule.pos, msg);
+            tyenv.errors.push(err);
+            continue;
+        }
+
+        // Report ambiguous rules against the later rule of each pair.
+        let overlapping = rules[..i]
+            .iter()
+            .filter(|other| prio(other) == prio(rule) && !analysis.disjoint(&other.lhs, &rule.lhs))
+            .map(|other| other.pos.pretty_print_line(&tyenv.filenames))
+            .collect::<Vec<_>>();
+        if overlapping.is_empty() {
+            continue;
+        }
+        any_overlap = true;
+        if !term.overlapping {
+            let msg = format!(
+                "Rule may match the same inputs as other rules with the same priority, at {}; \
+                 use distinct priorities to choose between them",
+                overlapping.join(", ")
+            );
+            let err = tyenv.overlap_error(rule.pos, msg);
+            tyenv.errors.push(err);
+        }
+    }
+
+    if term.overlapping && !any_overlap {
+        let msg = format!(
+            "Term '{}' at {} is declared as overlapping, but none of its rules overlap",
+            tyenv.syms[term.name.index()],
+            term.decl_pos.pretty_print_line(&tyenv.filenames)
+        );
+        warnings.push(tyenv.overlap_error(term.decl_pos, msg));
+    }
+}
+
+struct Analysis<'a> {
+    termenv: &'a TermEnv,
+}
+
+impl<'a> Analysis<'a> {
+    /// Is it impossible for any value to match both `a` and `b`?
+    fn disjoint(&self, a: &Pattern, b: &Pattern) -> bool {
+        match (a, b) {
+            (&Pattern::BindPattern(_, _, ref a), b) | (b, &Pattern::BindPattern(_, _, ref a)) => {
+                self.disjoint(a, b)
+            }
+            (&Pattern::And(_, ref subpats), other) | (other, &Pattern::And(_, ref subpats)) => {
+                subpats.iter().any(|subpat| self.disjoint(subpat, other))
+            }
+            (&Pattern::ConstInt(_, a), &Pattern::ConstInt(_, b)) => a != b,
+            (&Pattern::ConstPrim(_, a), &Pattern::ConstPrim(_, b)) => a != b,
+            (&Pattern::Term(_, a_term, ref a_args), &Pattern::Term(_, b_term, ref b_args)) => {
+                if a_term != b_term {
+                    return self.is_enum_variant(a_term) && self.is_enum_variant(b_term);
+                }
+                match self.comparable_args(a_args, b_args) {
+                    Some(pairs) => pairs.into_iter().any(|(a, b)| self.disjoint(a, b)),
+                    None => false,
+                }
+            }
+            // Wildcards and variables, which may be equal to anything, as
+            // well as constants matched against extractors.
+            _ => false,
+        }
+    }
+
+    /// Does `general` match every value that `specific` matches?
+    fn subsumes(&self, general: &Pattern, specific: &Pattern) -> bool {
+        if self.irrefutable(general) {
+            return true;
+        }
+        match (general, specific) {
+            (&Pattern::BindPattern(_, _, ref general), specific) => {
+                self.subsumes(general, specific)
+            }
+            (general, &Pattern::BindPattern(_, _, ref specific)) => {
+                self.subsumes(general, specific)
+            }
+            (&Pattern::And(_, ref subpats), specific) => {
+                subpats.iter().all(|subpat| self.subsumes(subpat, specific))
+            }
+            (general, &Pattern::And(_, ref subpats)) => {
+                subpats.iter().any(|subpat| self.subsumes(general, subpat))
+            }
+            (&Pattern::ConstInt(_, a), &Pattern::ConstInt(_, b)) => a == b,
+            (&Pattern::ConstPrim(_, a), &Pattern::ConstPrim(_, b)) => a == b,
+            (&Pattern::Term(_, g_term, ref g_args), &Pattern::Term(_, s_term, ref s_args))
+                if g_term == s_term =>
+            {
+                match self.comparable_args(g_args, s_args) {
+                    Some(pairs) => pairs.into_iter().all(|(g, s)| self.subsumes(g, s)),
+                    None => false,
+                }
+            }
+            // A variable in `general` must be equal to a value bound
+            // elsewhere, which we don't track.
+            _ => false,
+        }
+    }
+
+    /// Does `pat` match every value of its type?
+    fn irrefutable(&self, pat: &Pattern) -> bool {
+        match pat {
+            &Pattern::Wildcard(_) => true,
+            &Pattern::BindPattern(_, _, ref subpat) => self.irrefutable(subpat),
+            &Pattern::And(_, ref subpats) => subpats.iter().all(|subpat| self.irrefutable(subpat)),
+            &Pattern::Term(_, term, ref args) => {
+                self.is_infallible_extractor(term)
+                    && args.iter().all(|arg| match arg {
+                        TermArgPattern::Pattern(subpat) => self.irrefutable(subpat),
+                        TermArgPattern::Expr(_) => true,
+                    })
+            }
+            &Pattern::Var(..) | &Pattern::ConstInt(..) | &Pattern::ConstPrim(..) => false,
+        }
+    }
+
+    /// Pair up the argument patterns of two uses of the same term, if the
+    /// term is guaranteed to produce the same results for both. This requires
+    /// that the expressions passed to the term are equal and don't depend on
+    /// the variables of either rule.
+    fn comparable_args<'p>(
+        &self,
+        a_args: &'p [TermArgPattern],
+        b_args: &'p [TermArgPattern],
+    ) -> Option<Vec<(&'p Pattern, &'p Pattern)>> {
+        if a_args.len() != b_args.len() {
+            return None;
+        }
+        let mut pairs = vec![];
+        for (a, b) in a_args.iter().zip(b_args.iter()) {
+            match (a, b) {
+                (TermArgPattern::Pattern(a), TermArgPattern::Pattern(b)) => pairs.push((a, b)),
+                (TermArgPattern::Expr(a), TermArgPattern::Expr(b)) if a == b && !a.uses_vars() => {}
+                _ => return None,
+            }
+        }
+        Some(pairs)
+    }
+
+    fn is_enum_variant(&self, term: TermId) -> bool {
+        matches!(
+            self.termenv.terms[term.index()].kind,
+            TermKind::EnumVariant { .. }
+        )
+    }
+
+    fn is_infallible_extractor(&self, term: TermId) -> bool {
+        matches!(
+            self.termenv.terms[term.index()].kind,
+            TermKind::Decl {
+                extractor_kind: Some(ExtractorKind::ExternalExtractor {
+                    infallible: true,
+                    ..
+                }),
+                ..
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::lexer::Lexer;
+    use crate::parser::parse;
+
+    const PRELUDE: &str = r"
+        (type u32 (primitive u32))
+        (type A (enum (X (x u32)) (Y (y u32))))
+        (type B (enum (P) (Q)))
+
+        (decl Ext (u32) u32)
+        (extern extractor Ext ext)
+        (decl InfallibleExt (u32) u32)
+        (extern extractor infallible InfallibleExt infallible_ext)
+
+        (decl Lower (A) B)
+    ";
+
+    /// Check the given rules, returning the warnings or the errors.
+    fn check_rules(rules: &str) -> std::result::Result<Vec<String>, Vec<String>> {
+        let text = format!("{}{}", PRELUDE, rules);
+        let ast = parse(Lexer::from_str(&text, "file.isle").unwrap()).expect("should parse");
+        let mut tyenv = TypeEnv::from_ast(&ast).expect("should not have type errors");
+        let termenv = TermEnv::from_ast(&mut tyenv, &ast).expect("should not have term errors");
+        match check(&mut tyenv, &termenv) {
+            Ok(warnings) => Ok(warnings.iter().map(ToString::to_string).collect()),
+            Err(Error::Errors(errors)) => Err(errors.iter().map(ToString::to_string).collect()),
+            Err(e) => Err(vec![e.to_string()]),
+        }
+    }
+
+    #[test]
+    fn disjoint_rules() {
+        let warnings = check_rules(
+            r"
+            (rule (Lower (A.X 0)) (B.P))
+            (rule (Lower (A.X 1)) (B.Q))
+            (rule (Lower (A.Y _)) (B.Q))
+            ",
+        )
+        .unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+    }
+
+    #[test]
+    fn overlapping_rules_with_distinct_priorities() {
+        let warnings = check_rules(
+            r"
+            (rule 1 (Lower (A.X (Ext 0))) (B.P))
+            (rule (Lower (A.X _)) (B.Q))
+            ",
+        )
+        .unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+    }
+
+    #[test]
+    fn overlapping_rules_with_equal_priorities() {
+        let errors = check_rules(
+            r"
+            (rule (Lower (A.X (Ext 0))) (B.P))
+            (rule (Lower (A.X 1)) (B.Q))
+            (rule (Lower (A.Y y)) (B.Q))
+            (rule (Lower (A.X (InfallibleExt 2))) (B.Q))
+            ",
+        )
+        .unwrap_err();
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors[0].contains("priority, at file.isle line 13;"));
+        assert!(errors[1].contains("priority, at file.isle line 13, file.isle line 14;"));
+    }
+
+    #[test]
+    fn overlapping_term() {
+        let warnings = check_rules(
+            r"
+            (decl overlapping LowerAny (A) B)
+            (rule (LowerAny (A.X (Ext 0))) (B.P))
+            (rule (LowerAny (A.X 1)) (B.Q))
+            (rule (Lower _) (B.P))
+            ",
+        )
+        .unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+
+        let warnings = check_rules(
+            r"
+            (decl overlapping LowerAny (A) B)
+            (rule (LowerAny (A.X _)) (B.P))
+            (rule (LowerAny (A.Y _)) (B.Q))
+            (rule (Lower _) (B.P))
+            ",
+        )
+        .unwrap();
+        assert_eq!(warnings.len(), 1, "{:?}", warnings);
+        assert!(warnings[0].contains("'LowerAny' at file.isle line 13 is declared as overlapping"));
+    }
+
+    #[test]
+    fn shadowed_rules() {
+        let errors = check_rules(
+            r"
+            (rule 1 (Lower (A.X (InfallibleExt _))) (B.P))
+            (rule (Lower (A.X 0)) (B.Q))
+            (rule 2 (Lower (and (A.Y _) a)) (B.P))
+            (rule 1 (Lower (A.Y 3)) (B.Q))
+            ",
+        )
+        .unwrap_err();
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors[0].contains("rule at file.isle line 13 has a higher priority"));
+        assert!(errors[1].contains("rule at file.isle line 15 has a higher priority"));
+    }
+
+    #[test]
+    fn duplicate_rules() {
+        let errors = check_rules(
+            r"
+            (rule (Lower (A.X x)) (B.P))
+            (rule (Lower (A.X _)) (B.Q))
+            ",
+        )
+        .unwrap_err();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains("rule at file.isle line 13 has the same priority"));
+    }
+}
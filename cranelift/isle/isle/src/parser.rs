@@ -266,6 +266,11 @@ impl<'a> Parser<'a> {
 
     fn parse_decl(&mut self) -> Result<Decl> {
         let pos = self.pos();
+        let mut overlapping = false;
+        if self.is_sym_str("overlapping") {
+            self.symbol()?;
+            overlapping = true;
+        }
         let term = self.parse_ident()?;
 
         self.lparen()?;
@@ -281,6 +286,7 @@ impl<'a> Parser<'a> {
             term,
             arg_tys,
             ret_ty,
+            overlapping,
             pos,
         })
     }
@@ -208,6 +208,9 @@ pub struct Term {
     pub ret_ty: TypeId,
     /// The kind of this term.
     pub kind: TermKind,
+    /// Whether this term's rules may match the same inputs at the same
+    /// priority, as declared with `(decl overlapping ...)`.
+    pub overlapping: bool,
 }
 
 /// The kind of a term.
@@ -521,6 +524,24 @@ impl Expr {
             &Self::Let { ty: t, .. } => t,
         }
     }
+
+    /// Does this expression refer to any variable bound in the left-hand side?
+    pub fn uses_vars(&self) -> bool {
+        match self {
+            &Self::Term(_, _, ref args) => args.iter().any(|arg| arg.uses_vars()),
+            &Self::Var(..) => true,
+            &Self::ConstInt(..) | &Self::ConstPrim(..) => false,
+            &Self::Let {
+                ref bindings,
+                ref body,
+                ..
+            } => {
+                // Conservatively includes the variables bound by the `let`
+                // itself.
+                bindings.iter().any(|(_, _, expr)| expr.uses_vars()) || body.uses_vars()
+            }
+        }
+    }
 }
 
 /// Given an `Option<T>`, unwrap the inner `T` value, or `continue` if it is
@@ -623,7 +644,7 @@ impl TypeEnv {
         Ok(tyenv)
     }
 
-    fn return_errors(&mut self) -> Result<()> {
+    pub(crate) fn return_errors(&mut self) -> Result<()> {
         match self.errors.len() {
             0 => Ok(()),
             1 => Err(self.errors.pop().unwrap()),
@@ -721,6 +742,19 @@ impl TypeEnv {
         self.errors.push(err);
     }
 
+    pub(crate) fn overlap_error(&self, pos: Pos, msg: String) -> Error {
+        let e = Error::OverlapError {
+            msg,
+            src: Source::new(
+                self.filenames[pos.file].clone(),
+                self.file_texts[pos.file].clone(),
+            ),
+            span: miette::SourceSpan::from((pos.offset, 1)),
+        };
+        log::trace!("{}", e);
+        e
+    }
+
     fn intern_mut(&mut self, ident: &ast::Ident) -> Sym {
         if let Some(s) = self.sym_map.get(&ident.0).copied() {
             s
@@ -832,6 +866,7 @@ impl TermEnv {
                             constructor_kind: None,
                             extractor_kind: None,
                         },
+                        overlapping: decl.overlapping,
                     });
                 }
                 _ => {}
@@ -870,6 +905,7 @@ impl TermEnv {
                             kind: TermKind::EnumVariant {
                                 variant: variant.id,
                             },
+                            overlapping: false,
                         });
                         self.term_map.insert(variant.fullname, tid);
                     }
@@ -129,13 +129,12 @@ pub fn build_tries(typeenv: &TypeEnv, termenv: &TermEnv) -> BTreeMap<TermId, Tri
 ///
 /// - For each out-edge with priority [P_lo, P_hi] s.g. P \in [P_lo,
 ///   P_hi], and token T:
-///   - Trim the subnode at P, yielding children C_lo and C_hi.
-///   - Both children must be non-empty (have at least one leaf)
-///     because the original node must have had a leaf at P_lo
-///     and a leaf at P_hi.
-///   - Replace the one edge with two edges, one for each child, with
-///     the original match op, and with ranges calculated according to
-///     the trimmed children.
+///   - Trim the subnode to [P_lo, P - 1], [P, P] and [P + 1, P_hi],
+///     yielding children C_lo, C_mid and C_hi. Any of these may be
+///     empty (have no leaf), in which case it is dropped.
+///   - Replace the one edge with an edge for each remaining child,
+///     with the original match op, and with ranges calculated
+///     according to the trimmed children.
 ///
 /// To trim a node into range [P_lo, P_hi]:
 ///
@@ -151,7 +150,9 @@ pub fn build_tries(typeenv: &TypeEnv, termenv: &TermEnv) -> BTreeMap<TermId, Tri
 /// As we descend a path to insert a leaf node, we (i) priority-split
 /// if any edges' priority ranges overlap the insertion priority
 /// range, and (ii) expand priority ranges on edges to include the new
-/// leaf node's priority.
+/// leaf node's priority, unless that would make the edge's range
+/// overlap another edge's, in which case we add a new edge for just
+/// the new leaf node's priority instead.
 ///
 /// As long as we do this, we ensure the two key priority-trie
 /// invariants:
@@ -225,32 +226,29 @@ impl PrioRange {
         }
     }
 
-    fn split_at(&self, prio: Prio) -> (PrioRange, PrioRange) {
+    /// Split this range into the parts below, at, and above `prio`,
+    /// omitting the parts which are empty.
+    fn split_at(&self, prio: Prio) -> Vec<PrioRange> {
         assert!(self.contains(prio));
         assert!(!self.is_unit());
-        if prio == self.min {
-            (
-                PrioRange {
-                    min: self.min,
-                    max: self.min,
-                },
-                PrioRange {
-                    min: self.min + 1,
-                    max: self.max,
-                },
-            )
-        } else {
-            (
-                PrioRange {
-                    min: self.min,
-                    max: prio - 1,
-                },
-                PrioRange {
-                    min: prio,
-                    max: self.max,
-                },
-            )
+        let mut ranges = vec![];
+        if prio > self.min {
+            ranges.push(PrioRange {
+                min: self.min,
+                max: prio - 1,
+            });
+        }
+        ranges.push(PrioRange {
+            min: prio,
+            max: prio,
+        });
+        if prio < self.max {
+            ranges.push(PrioRange {
+                min: prio + 1,
+                max: self.max,
+            });
         }
+        ranges
     }
 }
 
@@ -319,12 +317,13 @@ impl TrieNode {
             _ => panic!("insert on leaf node!"),
         };
 
-        // Do we need to split?
+        // Priority-split every edge whose range contains `prio`, other
+        // than edges whose range is exactly `[prio, prio]`, so that the
+        // rules below it with higher and lower priorities than `prio` end
+        // up on separate edges.
         let needs_split = edges
             .iter()
             .any(|edge| edge.range.contains(prio) && !edge.range.is_unit());
-
-        // If so, pass over all edges/subnodes and split each.
         if needs_split {
             let mut new_edges = vec![];
             for edge in std::mem::take(edges) {
@@ -333,77 +332,55 @@ impl TrieNode {
                     continue;
                 }
 
-                let (lo_range, hi_range) = edge.range.split_at(prio);
-                let lo = edge.node.trim(lo_range);
-                let hi = edge.node.trim(hi_range);
-                if let Some((node, range)) = lo {
-                    new_edges.push(TrieEdge {
-                        range,
-                        symbol: edge.symbol.clone(),
-                        node,
-                    });
-                }
-                if let Some((node, range)) = hi {
-                    new_edges.push(TrieEdge {
-                        range,
-                        symbol: edge.symbol,
-                        node,
-                    });
+                for range in edge.range.split_at(prio) {
+                    if let Some((node, range)) = edge.node.trim(range) {
+                        new_edges.push(TrieEdge {
+                            range,
+                            symbol: edge.symbol.clone(),
+                            node,
+                        });
+                    }
                 }
             }
             *edges = new_edges;
         }
 
-        // Now find or insert the appropriate edge.
-        let mut edge: Option<usize> = None;
-        let mut last_edge_with_op: Option<usize> = None;
-        let mut last_edge_with_op_prio: Option<Prio> = None;
-        for i in 0..(edges.len() + 1) {
-            if i == edges.len() || prio > edges[i].range.max {
-                // We've passed all edges with overlapping priority
-                // ranges. Maybe the last edge we saw with the op
-                // we're inserting can have its range expanded,
-                // however.
-                if last_edge_with_op.is_some() {
-                    // Move it to the end of the run of equal-unit-range ops.
-                    edges.swap(last_edge_with_op.unwrap(), i - 1);
-                    edge = Some(i - 1);
-                    edges[i - 1].range.max = prio;
-                    break;
-                }
-                edges.insert(
-                    i,
-                    TrieEdge {
-                        range: PrioRange {
-                            min: prio,
-                            max: prio,
-                        },
-                        symbol: op.clone(),
-                        node: TrieNode::Empty,
-                    },
-                );
-                edge = Some(i);
-                break;
-            }
-            if i == edges.len() {
-                break;
-            }
-            if edges[i].symbol == op {
-                last_edge_with_op = Some(i);
-                last_edge_with_op_prio = Some(edges[i].range.max);
-            }
-            if last_edge_with_op_prio.is_some()
-                && last_edge_with_op_prio.unwrap() < edges[i].range.max
-            {
-                last_edge_with_op = None;
-                last_edge_with_op_prio = None;
+        // Now find or insert the appropriate edge. An edge for `op` at
+        // exactly this priority can be reused as-is, and another edge for
+        // `op` can have its range expanded to include `prio` as long as
+        // that doesn't make it overlap any other edge. Otherwise we add a
+        // new edge just for this priority.
+        let unit = PrioRange {
+            min: prio,
+            max: prio,
+        };
+        let edge = edges
+            .iter()
+            .position(|edge| edge.symbol == op && edge.range.is_unit() && edge.range.contains(prio))
+            .or_else(|| {
+                (0..edges.len()).find(|&i| {
+                    let range = edges[i].range.union(unit);
+                    edges[i].symbol == op
+                        && edges
+                            .iter()
+                            .enumerate()
+                            .all(|(j, other)| j == i || !other.range.overlaps(range))
+                })
+            });
+        let edge = match edge {
+            Some(i) => {
+                edges[i].range = edges[i].range.union(unit);
+                i
             }
-            if edges[i].range.contains(prio) && edges[i].symbol == op {
-                edge = Some(i);
-                break;
+            None => {
+                edges.push(TrieEdge {
+                    range: unit,
+                    symbol: op.clone(),
+                    node: TrieNode::Empty,
+                });
+                edges.len() - 1
             }
-        }
-        let edge = edge.expect("Must have found an edge at least at last iter");
+        };
         let edge = &mut edges[edge];
 
         if is_last {
@@ -585,3 +562,74 @@ impl<'a> TermFunctionsBuilder<'a> {
         functions_by_term
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::lexer::Lexer;
+    use crate::parser::parse;
+
+    /// Check the two priority-trie invariants for `node`, returning the
+    /// priorities of its leaves.
+    fn check_invariants(node: &TrieNode) -> Vec<Prio> {
+        match node {
+            TrieNode::Empty => vec![],
+            TrieNode::Leaf { prio, .. } => vec![*prio],
+            TrieNode::Decision { edges } => {
+                let mut prios = vec![];
+                for (i, edge) in edges.iter().enumerate() {
+                    for other in &edges[i + 1..] {
+                        assert!(
+                            !edge.range.overlaps(other.range)
+                                || (edge.range.is_unit() && other.range.is_unit()),
+                            "edges with overlapping ranges {:?} and {:?}",
+                            edge.range,
+                            other.range
+                        );
+                    }
+                    let leaves = check_invariants(&edge.node);
+                    for prio in &leaves {
+                        assert!(
+                            edge.range.contains(*prio),
+                            "leaf with priority {} below edge with range {:?}",
+                            prio,
+                            edge.range
+                        );
+                    }
+                    prios.extend(leaves);
+                }
+                prios
+            }
+        }
+    }
+
+    #[test]
+    fn priority_ranges() {
+        let text = r"
+            (type u32 (primitive u32))
+            (type A (enum (X (x u32)) (Y (y u32))))
+
+            (decl Ext (u32) u32)
+            (extern extractor Ext ext)
+
+            (decl Lower (A) u32)
+            (rule 6 (Lower (A.X (Ext 6))) 6)
+            (rule 2 (Lower (A.X (Ext 2))) 2)
+            (rule 5 (Lower (A.X 5)) 5)
+            (rule 3 (Lower (A.X x)) 3)
+            (rule 4 (Lower (A.Y 4)) 4)
+            (rule 1 (Lower (A.Y (Ext 1))) 1)
+            (rule 0 (Lower (A.Y y)) 0)
+        ";
+        let ast = parse(Lexer::from_str(text, "file.isle").unwrap()).expect("should parse");
+        let mut tyenv = TypeEnv::from_ast(&ast).expect("should not have type errors");
+        let termenv = TermEnv::from_ast(&mut tyenv, &ast).expect("should not have term errors");
+        let tries = build_tries(&tyenv, &termenv);
+        assert!(!tries.is_empty());
+        for trie in tries.values() {
+            let mut prios = check_invariants(trie);
+            prios.sort();
+            assert_eq!(prios, (0..=6).collect::<Vec<_>>());
+        }
+    }
+}
@@ -36,7 +36,10 @@ fn main() -> Result<()> {
 
     let lexer = lexer::Lexer::from_files(opts.inputs)?;
     let defs = parser::parse(lexer)?;
-    let code = compile::compile(&defs)?;
+    let (code, warnings) = compile::compile_with_warnings(&defs)?;
+    for warning in warnings {
+        eprintln!("Warning: {:?}", miette::Report::new(warning));
+    }
 
     let stdout = io::stdout();
     let (mut output, output_name): (Box<dyn Write>, _) = match &opts.output {
@@ -1,38 +1,50 @@
 //! Defines `JITModule`.
 
+use crate::debug::{create_debug_image, DebugImage};
 use crate::{compiled_blob::CompiledBlob, memory::Memory};
 use cranelift_codegen::isa::TargetIsa;
 use cranelift_codegen::settings::Configurable;
 use cranelift_codegen::{self, ir, settings};
 use cranelift_codegen::{
-    binemit::{Addend, CodeInfo, CodeOffset, Reloc, RelocSink, StackMapSink, TrapSink},
-    CodegenError,
+    binemit::{
+        Addend, CodeInfo, CodeOffset, NullStackMapSink, NullTrapSink, Reloc, RelocSink,
+        StackMapSink, TrapSink,
+    },
+    CodegenError, Context,
 };
 use cranelift_entity::SecondaryMap;
+use cranelift_module::dwarf::CompiledFunctionDebugInfo;
 use cranelift_module::{
-    DataContext, DataDescription, DataId, FuncId, Init, Linkage, Module, ModuleCompiledFunction,
-    ModuleDeclarations, ModuleError, ModuleResult, RelocRecord,
+    DataContext, DataDescription, DataId, DebugContext, FuncId, Init, Linkage, Module,
+    ModuleCompiledFunction, ModuleDeclarations, ModuleError, ModuleResult, RelocRecord,
 };
 use log::info;
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::ffi::CString;
 use std::io::Write;
+use std::panic::{self, AssertUnwindSafe};
 use std::ptr;
 use std::ptr::NonNull;
 use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::{Arc, Mutex};
 use target_lexicon::PointerWidth;
 
 const EXECUTABLE_DATA_ALIGNMENT: u64 = 0x10;
 const WRITABLE_DATA_ALIGNMENT: u64 = 0x8;
 const READONLY_DATA_ALIGNMENT: u64 = 0x1;
 
+/// The size of the stub standing in for a function that hasn't been lazily compiled yet.
+const LAZY_STUB_SIZE: usize = 192;
+
 /// A builder for `JITModule`.
 pub struct JITBuilder {
     isa: Box<dyn TargetIsa>,
     symbols: HashMap<String, *const u8>,
     libcall_names: Box<dyn Fn(ir::LibCall) -> String + Send + Sync>,
     hotswap_enabled: bool,
+    lazy_compile_enabled: bool,
+    debug_context: Option<DebugContext>,
 }
 
 impl JITBuilder {
@@ -52,7 +64,9 @@ impl JITBuilder {
         let isa_builder = cranelift_native::builder().unwrap_or_else(|msg| {
             panic!("host machine is not supported: {}", msg);
         });
-        let isa = isa_builder.finish(settings::Flags::new(flag_builder));
+        let isa = isa_builder
+            .finish(settings::Flags::new(flag_builder))
+            .unwrap_or_else(|err| panic!("host machine is not supported: {}", err));
         Self::with_isa(isa, libcall_names)
     }
 
@@ -76,6 +90,8 @@ impl JITBuilder {
             symbols,
             libcall_names,
             hotswap_enabled: false,
+            lazy_compile_enabled: false,
+            debug_context: None,
         }
     }
 
@@ -123,6 +139,37 @@ impl JITBuilder {
         self.hotswap_enabled = enabled;
         self
     }
+
+    /// Enable or disable lazy compilation.
+    ///
+    /// With lazy compilation, `define_function` only records the IR of the function and emits a
+    /// small stub in its place. The first call to the stub compiles the function and patches its
+    /// GOT entry, so that later calls go straight to the compiled code. Functions which are never
+    /// called are never compiled.
+    ///
+    /// Lazy compilation requires hotswap support (see [`JITBuilder::hotswap`]), as all calls
+    /// between functions have to go through the GOT, and is currently only supported on x86_64.
+    /// No traps or stack maps are reported for lazily compiled functions, and a compilation error
+    /// aborts the process, as it can't be reported to the caller of the stub.
+    pub fn lazy_compile(&mut self, enabled: bool) -> &mut Self {
+        self.lazy_compile_enabled = enabled;
+        self
+    }
+
+    /// Emit DWARF debug info for the functions registered in the given `DebugContext`.
+    ///
+    /// The `DebugContext` can be accessed through [`Module::debug_context`] to register debug
+    /// info while the module is being built. Each call to
+    /// [`JITModule::finalize_definitions`] creates an in-memory ELF image with the DWARF of the
+    /// functions defined since the previous call. With the `gdb-jit` feature enabled, these
+    /// images are registered with debuggers like GDB and LLDB through GDB's JIT compilation
+    /// interface. Otherwise they can be accessed through [`JITModule::debug_images`].
+    ///
+    /// No debug info is emitted for lazily compiled functions.
+    pub fn debug_context(&mut self, debug_context: DebugContext) -> &mut Self {
+        self.debug_context = Some(debug_context);
+        self
+    }
 }
 
 /// A pending update to the GOT.
@@ -134,13 +181,150 @@ struct GotUpdate {
     ptr: *const u8,
 }
 
+/// The state needed to compile functions on their first call, when lazy compilation is enabled.
+///
+/// The stubs of lazily compiled functions refer to this directly, so it lives in its own
+/// allocation, which is leaked along with the code unless [`JITModule::free_memory`] is called.
+struct LazyState {
+    isa: Arc<dyn TargetIsa>,
+    inner: Mutex<LazyStateInner>,
+}
+
+struct LazyStateInner {
+    /// Memory for the code of lazily compiled functions.
+    code: Memory,
+    libcall_got_entries: HashMap<ir::LibCall, NonNull<AtomicPtr<u8>>>,
+    libcall_plt_entries: HashMap<ir::LibCall, NonNull<[u8; 16]>>,
+    functions: SecondaryMap<FuncId, Option<LazyFunction>>,
+}
+
+/// A function defined while lazy compilation is enabled.
+#[derive(Clone)]
+struct LazyFunction {
+    name: String,
+    /// The IR of the function, until it has been compiled.
+    func: Option<ir::Function>,
+    got_entry: NonNull<AtomicPtr<u8>>,
+    /// The GOT entries, and PLT entries for functions, of all the functions and data objects
+    /// referred to by `func`. These are resolved when the function is defined, as the module
+    /// itself isn't reachable from the stub.
+    names: Vec<(
+        ir::ExternalName,
+        NonNull<AtomicPtr<u8>>,
+        Option<NonNull<[u8; 16]>>,
+    )>,
+    compiled: Option<*const u8>,
+}
+
+impl LazyState {
+    /// Compile the given function, if it hasn't been compiled yet, and return its address.
+    fn compile(&self, id: FuncId) -> *const u8 {
+        let mut inner = self.inner.lock().unwrap();
+        let LazyStateInner {
+            code,
+            libcall_got_entries,
+            libcall_plt_entries,
+            functions,
+        } = &mut *inner;
+        let lazy = functions[id]
+            .as_mut()
+            .expect("function must be defined before it can be compiled");
+        if let Some(ptr) = lazy.compiled {
+            return ptr;
+        }
+
+        let mut ctx = Context::for_function(lazy.func.take().unwrap());
+        let CodeInfo {
+            total_size: code_size,
+            ..
+        } = ctx
+            .compile(&*self.isa)
+            .unwrap_or_else(|err| panic!("failed to compile function {}: {:?}", lazy.name, err));
+
+        let size = code_size as usize;
+        let ptr = code
+            .allocate(size, EXECUTABLE_DATA_ALIGNMENT)
+            .expect("TODO: handle OOM etc.");
+
+        let mut reloc_sink = JITRelocSink::default();
+        unsafe {
+            ctx.emit_to_memory(
+                ptr,
+                &mut reloc_sink,
+                &mut NullTrapSink {},
+                &mut NullStackMapSink {},
+            )
+        };
+
+        let lookup = |name: &ir::ExternalName| {
+            lazy.names
+                .iter()
+                .find(|(n, _, _)| n == name)
+                .unwrap_or_else(|| panic!("can't resolve symbol {}", name))
+        };
+        CompiledBlob {
+            ptr,
+            size,
+            relocs: reloc_sink.relocs,
+        }
+        .perform_relocations(
+            |name| match *name {
+                ir::ExternalName::LibCall(ref libcall) => libcall_plt_entries
+                    .get(libcall)
+                    .unwrap_or_else(|| panic!("can't resolve libcall {}", libcall))
+                    .as_ptr()
+                    .cast::<u8>(),
+                _ => unreachable!("non GOT or PLT relocation in function {} to {}", id, name),
+            },
+            |name| match *name {
+                ir::ExternalName::LibCall(ref libcall) => libcall_got_entries
+                    .get(libcall)
+                    .unwrap_or_else(|| panic!("can't resolve libcall {}", libcall))
+                    .as_ptr()
+                    .cast::<u8>(),
+                _ => lookup(name).1.as_ptr().cast::<u8>(),
+            },
+            |name| match *name {
+                ir::ExternalName::LibCall(ref libcall) => libcall_plt_entries
+                    .get(libcall)
+                    .unwrap_or_else(|| panic!("can't resolve libcall {}", libcall))
+                    .as_ptr()
+                    .cast::<u8>(),
+                _ => lookup(name)
+                    .2
+                    .expect("PLT relocations can only have functions as target")
+                    .as_ptr()
+                    .cast::<u8>(),
+            },
+        );
+
+        code.set_readable_and_executable();
+        record_function_for_perf(ptr, size, &lazy.name);
+
+        unsafe { lazy.got_entry.as_ref() }.store(ptr, Ordering::SeqCst);
+        lazy.compiled = Some(ptr);
+        ptr
+    }
+}
+
+/// Called by the stub of a lazily compiled function on its first call.
+extern "C" fn compile_lazy_function(state: *const LazyState, id: u32) -> *const u8 {
+    let state = unsafe { &*state };
+    // Unwinding into JIT code isn't possible, so abort on errors.
+    match panic::catch_unwind(AssertUnwindSafe(|| state.compile(FuncId::from_u32(id)))) {
+        Ok(ptr) => ptr,
+        Err(_) => std::process::abort(),
+    }
+}
+
 /// A `JITModule` implements `Module` and emits code and data into memory where it can be
 /// directly called and accessed.
 ///
 /// See the `JITBuilder` for a convenient way to construct `JITModule` instances.
 pub struct JITModule {
-    isa: Box<dyn TargetIsa>,
+    isa: Arc<dyn TargetIsa>,
     hotswap_enabled: bool,
+    lazy: Option<NonNull<LazyState>>,
     symbols: HashMap<String, *const u8>,
     libcall_names: Box<dyn Fn(ir::LibCall) -> String>,
     memory: MemoryHandle,
@@ -157,6 +341,10 @@ pub struct JITModule {
 
     /// Updates to the GOT awaiting relocations to be made and region protections to be set
     pending_got_updates: Vec<GotUpdate>,
+
+    debug_context: Option<DebugContext>,
+    debug_functions_to_finalize: Vec<(FuncId, CompiledFunctionDebugInfo)>,
+    debug_images: Vec<DebugImage>,
 }
 
 /// A handle to allow freeing memory allocated by the `Module`.
@@ -179,6 +367,20 @@ impl JITModule {
         self.memory.code.free_memory();
         self.memory.readonly.free_memory();
         self.memory.writable.free_memory();
+        if let Some(lazy) = self.lazy {
+            let lazy = Box::from_raw(lazy.as_ptr());
+            lazy.inner.lock().unwrap().code.free_memory();
+        }
+        for image in self.debug_images.drain(..) {
+            image.free();
+        }
+    }
+
+    /// Returns the ELF images containing the DWARF debug info of the finalized functions.
+    ///
+    /// See [`JITBuilder::debug_context`].
+    pub fn debug_images(&self) -> impl Iterator<Item = &[u8]> {
+        self.debug_images.iter().map(|image| image.bytes())
     }
 
     fn lookup_symbol(&self, name: &str) -> Option<*const u8> {
@@ -221,7 +423,7 @@ impl JITModule {
         let got_entry = self.new_got_entry(val);
         self.function_got_entries[id] = Some(got_entry);
         let plt_entry = self.new_plt_entry(got_entry);
-        self.record_function_for_perf(
+        record_function_for_perf(
             plt_entry.as_ptr().cast(),
             std::mem::size_of::<[u8; 16]>(),
             &format!("{}@plt", self.declarations.get_function_decl(id).name),
@@ -249,6 +451,69 @@ impl JITModule {
         std::ptr::write(plt_ptr, plt_val);
     }
 
+    unsafe fn write_lazy_stub_bytes(stub_ptr: *mut u8, state: *const LazyState, id: FuncId) {
+        assert!(
+            cfg!(target_arch = "x86_64"),
+            "Lazy compilation is currently only supported on x86_64"
+        );
+        // Save the argument registers of both System V and Windows x64, call
+        // `compile_lazy_function(state, id)` and tail call its result:
+        //
+        //   push rbp; mov rbp, rsp
+        //   push rdi; push rsi; push rdx; push rcx; push r8; push r9; push rax
+        //   sub rsp, 168
+        //   movdqu [rsp + 32 + 16 * i], xmm<i>    for i in 0..8
+        //   movabs rdi, state; mov rcx, rdi
+        //   mov esi, id; mov edx, esi
+        //   movabs rax, compile_lazy_function; call rax
+        //   mov r11, rax
+        //   movdqu xmm<i>, [rsp + 32 + 16 * i]    for i in 0..8
+        //   add rsp, 168
+        //   pop rax; pop r9; pop r8; pop rcx; pop rdx; pop rsi; pop rdi
+        //   pop rbp
+        //   jmp r11
+        //
+        // The stack is 16-byte aligned at the call, and the 32 bytes below
+        // the saved xmm registers are the shadow space required by Windows x64.
+        let mut stub = Vec::with_capacity(LAZY_STUB_SIZE);
+        stub.extend_from_slice(&[0x55, 0x48, 0x89, 0xe5]);
+        stub.extend_from_slice(&[0x57, 0x56, 0x52, 0x51, 0x41, 0x50, 0x41, 0x51, 0x50]);
+        stub.extend_from_slice(&[0x48, 0x81, 0xec, 0xa8, 0x00, 0x00, 0x00]);
+        let movdqu_disp32 = |stub: &mut Vec<u8>, opcode: u8, xmm: u8| {
+            let disp = 32 + 16 * u32::from(xmm);
+            if disp < 0x80 {
+                stub.extend_from_slice(&[0xf3, 0x0f, opcode, 0x44 | (xmm << 3), 0x24, disp as u8]);
+            } else {
+                stub.extend_from_slice(&[0xf3, 0x0f, opcode, 0x84 | (xmm << 3), 0x24]);
+                stub.extend_from_slice(&disp.to_le_bytes());
+            }
+        };
+        for xmm in 0..8 {
+            movdqu_disp32(&mut stub, 0x7f, xmm);
+        }
+        stub.extend_from_slice(&[0x48, 0xbf]);
+        stub.extend_from_slice(&(state as u64).to_le_bytes());
+        stub.extend_from_slice(&[0x48, 0x89, 0xf9]);
+        stub.push(0xbe);
+        stub.extend_from_slice(&id.as_u32().to_le_bytes());
+        stub.extend_from_slice(&[0x89, 0xf2]);
+        stub.extend_from_slice(&[0x48, 0xb8]);
+        stub.extend_from_slice(&(compile_lazy_function as *const u8 as u64).to_le_bytes());
+        stub.extend_from_slice(&[0xff, 0xd0]);
+        stub.extend_from_slice(&[0x49, 0x89, 0xc3]);
+        for xmm in 0..8 {
+            movdqu_disp32(&mut stub, 0x6f, xmm);
+        }
+        stub.extend_from_slice(&[0x48, 0x81, 0xc4, 0xa8, 0x00, 0x00, 0x00]);
+        stub.extend_from_slice(&[0x58, 0x41, 0x59, 0x41, 0x58, 0x59, 0x5a, 0x5e, 0x5f]);
+        stub.push(0x5d);
+        stub.extend_from_slice(&[0x41, 0xff, 0xe3]);
+        // Pad with int3.
+        assert!(stub.len() <= LAZY_STUB_SIZE);
+        stub.resize(LAZY_STUB_SIZE, 0xcc);
+        std::ptr::copy_nonoverlapping(stub.as_ptr(), stub_ptr, LAZY_STUB_SIZE);
+    }
+
     fn get_address(&self, name: &ir::ExternalName) -> *const u8 {
         match *name {
             ir::ExternalName::User { .. } => {
@@ -346,15 +611,27 @@ impl JITModule {
     ///
     /// The pointer remains valid until either [`JITModule::free_memory`] is called or in the future
     /// some way of deallocating this individual function is used.
+    ///
+    /// With lazy compilation enabled this is the address of the PLT entry of the function, which
+    /// jumps to the lazy compilation stub until the function has been compiled.
     pub fn get_finalized_function(&self, func_id: FuncId) -> *const u8 {
         let info = &self.compiled_functions[func_id];
         assert!(
             !self.functions_to_finalize.iter().any(|x| *x == func_id),
             "function not yet finalized"
         );
-        info.as_ref()
+        let ptr = info
+            .as_ref()
             .expect("function must be compiled before it can be finalized")
-            .ptr
+            .ptr;
+        if self.lazy.is_some() {
+            self.function_plt_entries[func_id]
+                .unwrap()
+                .as_ptr()
+                .cast::<u8>()
+        } else {
+            ptr
+        }
     }
 
     /// Returns the address and size of a finalized data object.
@@ -374,29 +651,16 @@ impl JITModule {
         (compiled.ptr, compiled.size)
     }
 
-    fn record_function_for_perf(&self, ptr: *mut u8, size: usize, name: &str) {
-        // The Linux perf tool supports JIT code via a /tmp/perf-$PID.map file,
-        // which contains memory regions and their associated names.  If we
-        // are profiling with perf and saving binaries to PERF_BUILDID_DIR
-        // for post-profile analysis, write information about each function
-        // we define.
-        if cfg!(target_os = "linux") && ::std::env::var_os("PERF_BUILDID_DIR").is_some() {
-            let mut map_file = ::std::fs::OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(format!("/tmp/perf-{}.map", ::std::process::id()))
-                .unwrap();
-
-            let _ = writeln!(map_file, "{:x} {:x} {}", ptr as usize, size, name);
-        }
-    }
-
     /// Finalize all functions and data objects that are defined but not yet finalized.
     /// All symbols referenced in their bodies that are declared as needing a definition
     /// must be defined by this point.
     ///
     /// Use `get_finalized_function` and `get_finalized_data` to obtain the final
     /// artifacts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if debug info is enabled and can't be emitted.
     pub fn finalize_definitions(&mut self) {
         for func in std::mem::take(&mut self.functions_to_finalize) {
             let decl = self.declarations.get_function_decl(func);
@@ -436,9 +700,36 @@ impl JITModule {
             unsafe { libc::syscall(libc::SYS_membarrier, cmd) };
         }
 
+        #[cfg(all(target_arch = "riscv64", target_os = "linux"))]
+        {
+            const SYS_RISCV_FLUSH_ICACHE: libc::c_long = 259;
+
+            // Ensure that no hart has fetched a stale instruction stream. A
+            // null range with no flags asks the kernel to flush everything.
+            unsafe { libc::syscall(SYS_RISCV_FLUSH_ICACHE, 0usize, 0usize, 0usize) };
+        }
+
         for update in self.pending_got_updates.drain(..) {
             unsafe { update.entry.as_ref() }.store(update.ptr as *mut _, Ordering::SeqCst);
         }
+
+        if let Some(debug_context) = &self.debug_context {
+            let functions = std::mem::take(&mut self.debug_functions_to_finalize);
+            if !functions.is_empty() {
+                let functions = functions
+                    .iter()
+                    .map(|(func, info)| (*func, info))
+                    .collect::<Vec<_>>();
+                let compiled_functions = &self.compiled_functions;
+                let image = create_debug_image(&*self.isa, debug_context, &functions, &|func| {
+                    compiled_functions[func].as_ref().unwrap().ptr as u64
+                })
+                .unwrap_or_else(|err| panic!("failed to emit debug info: {}", err));
+                if let Some(image) = image {
+                    self.debug_images.push(DebugImage::new(image));
+                }
+            }
+        }
     }
 
     /// Create a new `JITModule`.
@@ -449,10 +740,17 @@ impl JITModule {
                 "Hotswapping requires PIC code"
             );
         }
+        if builder.lazy_compile_enabled {
+            assert!(
+                builder.hotswap_enabled,
+                "Lazy compilation requires hotswap support"
+            );
+        }
 
         let mut module = Self {
-            isa: builder.isa,
+            isa: Arc::from(builder.isa),
             hotswap_enabled: builder.hotswap_enabled,
+            lazy: None,
             symbols: builder.symbols,
             libcall_names: builder.libcall_names,
             memory: MemoryHandle {
@@ -471,6 +769,9 @@ impl JITModule {
             functions_to_finalize: Vec::new(),
             data_objects_to_finalize: Vec::new(),
             pending_got_updates: Vec::new(),
+            debug_context: builder.debug_context,
+            debug_functions_to_finalize: Vec::new(),
+            debug_images: Vec::new(),
         };
 
         // Pre-create a GOT and PLT entry for each libcall.
@@ -497,6 +798,19 @@ impl JITModule {
             module.libcall_plt_entries.insert(libcall, plt_entry);
         }
 
+        if builder.lazy_compile_enabled {
+            let lazy = Box::new(LazyState {
+                isa: module.isa.clone(),
+                inner: Mutex::new(LazyStateInner {
+                    code: Memory::new(),
+                    libcall_got_entries: module.libcall_got_entries.clone(),
+                    libcall_plt_entries: module.libcall_plt_entries.clone(),
+                    functions: SecondaryMap::new(),
+                }),
+            });
+            module.lazy = Some(NonNull::new(Box::into_raw(lazy)).unwrap());
+        }
+
         #[cfg(all(target_arch = "aarch64", target_os = "linux"))]
         {
             let cmd: libc::c_int = 64; // MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED_SYNC_CORE
@@ -533,6 +847,83 @@ impl JITModule {
 
         Ok(())
     }
+
+    /// Record the function for lazy compilation and emit a stub that compiles it on its first
+    /// call.
+    ///
+    /// The function is verified and legalized here, so that invalid functions are reported as
+    /// errors instead of aborting the process when the stub compiles them.
+    fn define_lazy_function(
+        &mut self,
+        id: FuncId,
+        lazy: NonNull<LazyState>,
+        ctx: &mut cranelift_codegen::Context,
+    ) -> ModuleResult<ModuleCompiledFunction> {
+        ctx.verify_if(self.isa())?;
+        ctx.compute_cfg();
+        ctx.legalize(self.isa())?;
+
+        let func = std::mem::replace(&mut ctx.func, ir::Function::new());
+
+        let mut names = vec![];
+        for ext_func in func.dfg.ext_funcs.values() {
+            if let ir::ExternalName::User { .. } = ext_func.name {
+                let func_id = FuncId::from_name(&ext_func.name);
+                names.push((
+                    ext_func.name.clone(),
+                    self.function_got_entries[func_id].unwrap(),
+                    Some(self.function_plt_entries[func_id].unwrap()),
+                ));
+            }
+        }
+        for global_value in func.global_values.values() {
+            if let ir::GlobalValueData::Symbol {
+                name: name @ ir::ExternalName::User { .. },
+                ..
+            } = global_value
+            {
+                let plt_entry = if ModuleDeclarations::is_function(name) {
+                    Some(self.function_plt_entries[FuncId::from_name(name)].unwrap())
+                } else {
+                    None
+                };
+                names.push((name.clone(), self.get_got_address(name), plt_entry));
+            }
+        }
+
+        let name = self.declarations.get_function_decl(id).name.clone();
+        let got_entry = self.function_got_entries[id].unwrap();
+        let state = unsafe { lazy.as_ref() };
+        state.inner.lock().unwrap().functions[id] = Some(LazyFunction {
+            name: name.clone(),
+            func: Some(func),
+            got_entry,
+            names,
+            compiled: None,
+        });
+
+        let ptr = self
+            .memory
+            .code
+            .allocate(LAZY_STUB_SIZE, EXECUTABLE_DATA_ALIGNMENT)
+            .expect("TODO: handle OOM etc.");
+        unsafe { Self::write_lazy_stub_bytes(ptr, lazy.as_ptr(), id) };
+        record_function_for_perf(ptr, LAZY_STUB_SIZE, &format!("{} (lazy stub)", name));
+
+        self.compiled_functions[id] = Some(CompiledBlob {
+            ptr,
+            size: LAZY_STUB_SIZE,
+            relocs: vec![],
+        });
+        self.pending_got_updates.push(GotUpdate {
+            entry: got_entry,
+            ptr,
+        });
+
+        Ok(ModuleCompiledFunction {
+            size: LAZY_STUB_SIZE as u32,
+        })
+    }
 }
 
 impl Module for JITModule {
@@ -544,6 +935,10 @@ impl Module for JITModule {
         &self.declarations
     }
 
+    fn debug_context(&mut self) -> Option<&mut DebugContext> {
+        self.debug_context.as_mut()
+    }
+
     fn declare_function(
         &mut self,
         name: &str,
@@ -661,6 +1056,10 @@ impl Module for JITModule {
             return Err(ModuleError::DuplicateDefinition(decl.name.to_owned()));
         }
 
+        if let Some(lazy) = self.lazy {
+            return self.define_lazy_function(id, lazy, ctx);
+        }
+
         let CodeInfo {
             total_size: code_size,
             ..
@@ -676,7 +1075,12 @@ impl Module for JITModule {
         let mut reloc_sink = JITRelocSink::default();
         unsafe { ctx.emit_to_memory(ptr, &mut reloc_sink, trap_sink, stack_map_sink) };
 
-        self.record_function_for_perf(ptr, size, &decl.name);
+        if self.debug_context.is_some() {
+            let debug_info = CompiledFunctionDebugInfo::new(ctx, self.isa())?;
+            self.debug_functions_to_finalize.push((id, debug_info));
+        }
+
+        record_function_for_perf(ptr, size, &decl.name);
         self.compiled_functions[id] = Some(CompiledBlob {
             ptr,
             size,
@@ -749,7 +1153,7 @@ impl Module for JITModule {
             ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, size);
         }
 
-        self.record_function_for_perf(ptr, size, &decl.name);
+        record_function_for_perf(ptr, size, &decl.name);
         self.compiled_functions[id] = Some(CompiledBlob {
             ptr,
             size,
@@ -850,6 +1254,23 @@ impl Module for JITModule {
     }
 }
 
+fn record_function_for_perf(ptr: *mut u8, size: usize, name: &str) {
+    // The Linux perf tool supports JIT code via a /tmp/perf-$PID.map file,
+    // which contains memory regions and their associated names.  If we
+    // are profiling with perf and saving binaries to PERF_BUILDID_DIR
+    // for post-profile analysis, write information about each function
+    // we define.
+    if cfg!(target_os = "linux") && ::std::env::var_os("PERF_BUILDID_DIR").is_some() {
+        let mut map_file = ::std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(format!("/tmp/perf-{}.map", ::std::process::id()))
+            .unwrap();
+
+        let _ = writeln!(map_file, "{:x} {:x} {}", ptr as usize, size, name);
+    }
+}
+
 #[cfg(not(windows))]
 fn lookup_with_dlsym(name: &str) -> Option<*const u8> {
     let c_str = CString::new(name).unwrap();
@@ -102,6 +102,23 @@ impl CompiledBlob {
                         iptr.write_unaligned(ins);
                     }
                 }
+                Reloc::RiscvCall => {
+                    let base = get_address(name);
+                    let what = unsafe { base.offset(isize::try_from(addend).unwrap()) };
+                    // The `auipc` and `jalr` pair covers a 32-bit offset.
+                    // The low 12 bits are sign-extended by `jalr`, so the
+                    // high part is rounded to compensate.
+                    let pcrel = i32::try_from((what as isize) - (at as isize)).unwrap() as u32;
+                    let hi20 = pcrel.wrapping_add(0x800) & 0xfffff000;
+                    let lo12 = pcrel.wrapping_sub(hi20) & 0xfff;
+                    let iptr = at as *mut u32;
+                    unsafe {
+                        let auipc = iptr.read_unaligned();
+                        iptr.write_unaligned(auipc | hi20);
+                        let jalr = iptr.add(1).read_unaligned();
+                        iptr.add(1).write_unaligned(jalr | (lo12 << 20));
+                    }
+                }
                 _ => unimplemented!(),
             }
         }
@@ -0,0 +1,199 @@
+//! DWARF debug info for JIT compiled functions.
+//!
+//! The DWARF of the functions finalized together is wrapped in an in-memory ELF image using
+//! absolute addresses. With the `gdb-jit` feature enabled, these images are registered with
+//! debuggers through GDB's JIT compilation interface.
+
+use cranelift_codegen::isa::TargetIsa;
+use cranelift_module::dwarf::{emit_dwarf, CompiledFunctionDebugInfo, DwarfAddresses};
+use cranelift_module::{DebugContext, FuncId, ModuleError, ModuleResult};
+use object::write::Object;
+use object::SectionKind;
+use std::ptr::{self, NonNull};
+
+/// An entry in the linked list of images of GDB's JIT compilation interface.
+#[repr(C)]
+struct JitCodeEntry {
+    next_entry: *mut JitCodeEntry,
+    prev_entry: *mut JitCodeEntry,
+    symfile_addr: *const u8,
+    symfile_size: u64,
+}
+
+/// An ELF image containing the DWARF of some functions.
+///
+/// Like the code it describes, the image is leaked unless it is freed explicitly.
+pub(crate) struct DebugImage {
+    entry: NonNull<JitCodeEntry>,
+}
+
+impl DebugImage {
+    /// Create a new image and register it with debuggers.
+    pub(crate) fn new(bytes: Vec<u8>) -> Self {
+        let bytes = Box::leak(bytes.into_boxed_slice());
+        let entry = Box::new(JitCodeEntry {
+            next_entry: ptr::null_mut(),
+            prev_entry: ptr::null_mut(),
+            symfile_addr: bytes.as_ptr(),
+            symfile_size: bytes.len() as u64,
+        });
+        let entry = NonNull::new(Box::into_raw(entry)).unwrap();
+        #[cfg(feature = "gdb-jit")]
+        unsafe {
+            gdb_jit::register(entry.as_ptr())
+        };
+        Self { entry }
+    }
+
+    /// The contents of the image.
+    pub(crate) fn bytes(&self) -> &[u8] {
+        unsafe {
+            let entry = self.entry.as_ref();
+            std::slice::from_raw_parts(entry.symfile_addr, entry.symfile_size as usize)
+        }
+    }
+
+    /// Unregister the image from debuggers and free it.
+    pub(crate) unsafe fn free(self) {
+        #[cfg(feature = "gdb-jit")]
+        gdb_jit::unregister(self.entry.as_ptr());
+        let entry = Box::from_raw(self.entry.as_ptr());
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+            entry.symfile_addr as *mut u8,
+            entry.symfile_size as usize,
+        )));
+    }
+}
+
+/// Create an ELF image containing the DWARF of the given functions, whose code has been loaded
+/// at the addresses returned by `address_of`.
+///
+/// Returns `None` if debug info isn't supported for the target.
+pub(crate) fn create_debug_image(
+    isa: &dyn TargetIsa,
+    debug_context: &DebugContext,
+    functions: &[(FuncId, &CompiledFunctionDebugInfo)],
+    address_of: &dyn Fn(FuncId) -> u64,
+) -> ModuleResult<Option<Vec<u8>>> {
+    let architecture = match isa.triple().architecture {
+        target_lexicon::Architecture::X86_64 => object::Architecture::X86_64,
+        target_lexicon::Architecture::Aarch64(_) => object::Architecture::Aarch64,
+        target_lexicon::Architecture::Riscv64(_) => object::Architecture::Riscv64,
+        target_lexicon::Architecture::S390x => object::Architecture::S390x,
+        _ => return Ok(None),
+    };
+    let endian = match isa.triple().endianness().unwrap() {
+        target_lexicon::Endianness::Little => object::Endianness::Little,
+        target_lexicon::Endianness::Big => object::Endianness::Big,
+    };
+
+    let sections = emit_dwarf(
+        isa,
+        debug_context,
+        functions,
+        DwarfAddresses::Absolute(address_of),
+    )?;
+
+    // All addresses are absolute and every section starts at offset 0, so the contents of the
+    // sections are final and no relocations are needed.
+    let mut obj = Object::new(object::BinaryFormat::Elf, architecture, endian);
+    for section in sections {
+        let id = obj.add_section(
+            vec![],
+            section.id.name().as_bytes().to_vec(),
+            SectionKind::Debug,
+        );
+        obj.set_section_data(id, section.body, 1);
+    }
+    let bytes = obj
+        .write()
+        .map_err(|err| ModuleError::Backend(anyhow::anyhow!("{}", err)))?;
+    Ok(Some(bytes))
+}
+
+/// The debugger side of GDB's JIT compilation interface. Debuggers set a breakpoint in
+/// `__jit_debug_register_code` and read the list of images from `__jit_debug_descriptor`.
+#[cfg(feature = "gdb-jit")]
+mod gdb_jit {
+    use super::JitCodeEntry;
+    use std::ptr;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    const JIT_NOACTION: u32 = 0;
+    const JIT_REGISTER_FN: u32 = 1;
+    const JIT_UNREGISTER_FN: u32 = 2;
+
+    #[repr(C)]
+    struct JitDescriptor {
+        version: u32,
+        action_flag: u32,
+        relevant_entry: *mut JitCodeEntry,
+        first_entry: *mut JitCodeEntry,
+    }
+
+    #[no_mangle]
+    #[allow(non_upper_case_globals)]
+    static mut __jit_debug_descriptor: JitDescriptor = JitDescriptor {
+        version: 1,
+        action_flag: JIT_NOACTION,
+        relevant_entry: ptr::null_mut(),
+        first_entry: ptr::null_mut(),
+    };
+
+    #[no_mangle]
+    #[inline(never)]
+    extern "C" fn __jit_debug_register_code() {
+        // Make sure calls to this function aren't optimized away, as debuggers set a breakpoint
+        // in it.
+        unsafe { ptr::read_volatile(ptr::addr_of!(__jit_debug_descriptor.action_flag)) };
+    }
+
+    /// Protects `__jit_debug_descriptor` against concurrent modification within the process.
+    static LOCK: AtomicBool = AtomicBool::new(false);
+
+    fn with_lock(f: impl FnOnce()) {
+        while LOCK
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            std::hint::spin_loop();
+        }
+        f();
+        LOCK.store(false, Ordering::Release);
+    }
+
+    pub(super) unsafe fn register(entry: *mut JitCodeEntry) {
+        with_lock(|| {
+            let desc = &mut *ptr::addr_of_mut!(__jit_debug_descriptor);
+            (*entry).next_entry = desc.first_entry;
+            if !desc.first_entry.is_null() {
+                (*desc.first_entry).prev_entry = entry;
+            }
+            desc.first_entry = entry;
+            desc.relevant_entry = entry;
+            desc.action_flag = JIT_REGISTER_FN;
+            __jit_debug_register_code();
+            desc.action_flag = JIT_NOACTION;
+            desc.relevant_entry = ptr::null_mut();
+        });
+    }
+
+    pub(super) unsafe fn unregister(entry: *mut JitCodeEntry) {
+        with_lock(|| {
+            let desc = &mut *ptr::addr_of_mut!(__jit_debug_descriptor);
+            if !(*entry).prev_entry.is_null() {
+                (*(*entry).prev_entry).next_entry = (*entry).next_entry;
+            } else {
+                desc.first_entry = (*entry).next_entry;
+            }
+            if !(*entry).next_entry.is_null() {
+                (*(*entry).next_entry).prev_entry = (*entry).prev_entry;
+            }
+            desc.relevant_entry = entry;
+            desc.action_flag = JIT_UNREGISTER_FN;
+            __jit_debug_register_code();
+            desc.action_flag = JIT_NOACTION;
+            desc.relevant_entry = ptr::null_mut();
+        });
+    }
+}
@@ -25,6 +25,7 @@
 
 mod backend;
 mod compiled_blob;
+mod debug;
 mod memory;
 
 pub use crate::backend::{JITBuilder, JITModule};
@@ -17,7 +17,9 @@ fn error_on_incompatible_sig_in_declare_function() {
     let isa_builder = cranelift_native::builder().unwrap_or_else(|msg| {
         panic!("host machine is not supported: {}", msg);
     });
-    let isa = isa_builder.finish(settings::Flags::new(flag_builder));
+    let isa = isa_builder
+        .finish(settings::Flags::new(flag_builder))
+        .unwrap();
     let mut module = JITModule::new(JITBuilder::with_isa(isa, default_libcall_names()));
 
     let mut sig = Signature {
@@ -75,7 +77,9 @@ fn panic_on_define_after_finalize() {
     let isa_builder = cranelift_native::builder().unwrap_or_else(|msg| {
         panic!("host machine is not supported: {}", msg);
     });
-    let isa = isa_builder.finish(settings::Flags::new(flag_builder));
+    let isa = isa_builder
+        .finish(settings::Flags::new(flag_builder))
+        .unwrap();
     let mut module = JITModule::new(JITBuilder::with_isa(isa, default_libcall_names()));
 
     define_simple_function(&mut module);
@@ -164,7 +168,9 @@ fn libcall_function() {
     let isa_builder = cranelift_native::builder().unwrap_or_else(|msg| {
         panic!("host machine is not supported: {}", msg);
     });
-    let isa = isa_builder.finish(settings::Flags::new(flag_builder));
+    let isa = isa_builder
+        .finish(settings::Flags::new(flag_builder))
+        .unwrap();
     let mut module = JITModule::new(JITBuilder::with_isa(isa, default_libcall_names()));
 
     let sig = Signature {
@@ -213,3 +219,356 @@ fn libcall_function() {
 
     module.finalize_definitions();
 }
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+fn lazy_compile_function() {
+    let mut flag_builder = settings::builder();
+    flag_builder.set("use_colocated_libcalls", "false").unwrap();
+    flag_builder.set("is_pic", "true").unwrap();
+    let isa_builder = cranelift_native::builder().unwrap_or_else(|msg| {
+        panic!("host machine is not supported: {}", msg);
+    });
+    let isa = isa_builder
+        .finish(settings::Flags::new(flag_builder))
+        .unwrap();
+    let mut builder = JITBuilder::with_isa(isa, default_libcall_names());
+    builder.hotswap(true).lazy_compile(true);
+    let mut module = JITModule::new(builder);
+
+    let mut sig = module.make_signature();
+    sig.params.push(AbiParam::new(types::I64));
+    sig.params.push(AbiParam::new(types::F64));
+    sig.params.push(AbiParam::new(types::I64));
+    sig.returns.push(AbiParam::new(types::I64));
+
+    let callee_id = module
+        .declare_function("callee", Linkage::Local, &sig)
+        .unwrap();
+    let caller_id = module
+        .declare_function("caller", Linkage::Local, &sig)
+        .unwrap();
+
+    let mut trap_sink = NullTrapSink {};
+    let mut stack_map_sink = NullStackMapSink {};
+    let mut ctx = Context::new();
+    let mut func_ctx = FunctionBuilderContext::new();
+
+    // callee(a, b, c) = a * 2 + b as i64 + c
+    ctx.func =
+        Function::with_name_signature(ExternalName::user(0, callee_id.as_u32()), sig.clone());
+    {
+        let mut bcx: FunctionBuilder = FunctionBuilder::new(&mut ctx.func, &mut func_ctx);
+        let block = bcx.create_block();
+        bcx.append_block_params_for_function_params(block);
+        bcx.switch_to_block(block);
+        let params = bcx.block_params(block).to_vec();
+        let a = bcx.ins().imul_imm(params[0], 2);
+        let b = bcx.ins().fcvt_to_sint(types::I64, params[1]);
+        let sum = bcx.ins().iadd(a, b);
+        let sum = bcx.ins().iadd(sum, params[2]);
+        bcx.ins().return_(&[sum]);
+        bcx.seal_all_blocks();
+        bcx.finalize();
+    }
+    module
+        .define_function(callee_id, &mut ctx, &mut trap_sink, &mut stack_map_sink)
+        .unwrap();
+    module.clear_context(&mut ctx);
+
+    // caller(a, b, c) = callee(a, b, c) + 1
+    ctx.func = Function::with_name_signature(ExternalName::user(0, caller_id.as_u32()), sig);
+    {
+        let mut bcx: FunctionBuilder = FunctionBuilder::new(&mut ctx.func, &mut func_ctx);
+        let block = bcx.create_block();
+        bcx.append_block_params_for_function_params(block);
+        bcx.switch_to_block(block);
+        let params = bcx.block_params(block).to_vec();
+        let callee = module.declare_func_in_func(callee_id, &mut bcx.func);
+        let call = bcx.ins().call(callee, &params);
+        let result = bcx.inst_results(call)[0];
+        let result = bcx.ins().iadd_imm(result, 1);
+        bcx.ins().return_(&[result]);
+        bcx.seal_all_blocks();
+        bcx.finalize();
+    }
+    module
+        .define_function(caller_id, &mut ctx, &mut trap_sink, &mut stack_map_sink)
+        .unwrap();
+
+    module.finalize_definitions();
+
+    let caller = module.get_finalized_function(caller_id);
+    let caller = unsafe { std::mem::transmute::<_, extern "C" fn(i64, f64, i64) -> i64>(caller) };
+    let callee = module.get_finalized_function(callee_id);
+    let callee = unsafe { std::mem::transmute::<_, extern "C" fn(i64, f64, i64) -> i64>(callee) };
+
+    assert_eq!(caller(1, 2.0, 3), 8);
+    assert_eq!(caller(10, 20.0, 30), 71);
+    assert_eq!(callee(1, 2.0, 3), 7);
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+fn lazy_compile_invalid_function() {
+    let mut flag_builder = settings::builder();
+    flag_builder.set("use_colocated_libcalls", "false").unwrap();
+    flag_builder.set("is_pic", "true").unwrap();
+    let isa_builder = cranelift_native::builder().unwrap_or_else(|msg| {
+        panic!("host machine is not supported: {}", msg);
+    });
+    let isa = isa_builder
+        .finish(settings::Flags::new(flag_builder))
+        .unwrap();
+    let mut builder = JITBuilder::with_isa(isa, default_libcall_names());
+    builder.hotswap(true).lazy_compile(true);
+    let mut module = JITModule::new(builder);
+
+    let mut sig = module.make_signature();
+    sig.returns.push(AbiParam::new(types::I64));
+    let func_id = module
+        .declare_function("invalid", Linkage::Local, &sig)
+        .unwrap();
+
+    let mut trap_sink = NullTrapSink {};
+    let mut stack_map_sink = NullStackMapSink {};
+    let mut ctx = Context::new();
+    let mut func_ctx = FunctionBuilderContext::new();
+
+    // Returns an `i32` from a function declared to return an `i64`.
+    ctx.func = Function::with_name_signature(ExternalName::user(0, func_id.as_u32()), sig);
+    {
+        let mut bcx: FunctionBuilder = FunctionBuilder::new(&mut ctx.func, &mut func_ctx);
+        let block = bcx.create_block();
+        bcx.switch_to_block(block);
+        let zero = bcx.ins().iconst(types::I32, 0);
+        bcx.ins().return_(&[zero]);
+        bcx.seal_all_blocks();
+        bcx.finalize();
+    }
+    match module.define_function(func_id, &mut ctx, &mut trap_sink, &mut stack_map_sink) {
+        Err(ModuleError::Compilation(cranelift_codegen::CodegenError::Verifier(_))) => {}
+        Err(e) => panic!("expected a verifier error, got {}", e),
+        Ok(_) => panic!("expected a verifier error"),
+    }
+}
+
+#[test]
+fn debug_info() {
+    use object::{Object, ObjectSection};
+
+    let mut flag_builder = settings::builder();
+    flag_builder.set("use_colocated_libcalls", "false").unwrap();
+    // FIXME set back to true once the x64 backend supports it.
+    flag_builder.set("is_pic", "false").unwrap();
+    let isa_builder = cranelift_native::builder().unwrap_or_else(|msg| {
+        panic!("host machine is not supported: {}", msg);
+    });
+    let isa = isa_builder
+        .finish(settings::Flags::new(flag_builder))
+        .unwrap();
+    let mut builder = JITBuilder::with_isa(isa, default_libcall_names());
+    builder.debug_context(DebugContext::new("test", "foo.src", "/tmp"));
+    let mut module = JITModule::new(builder);
+
+    let mut sig = module.make_signature();
+    sig.params.push(AbiParam::new(types::I64));
+    sig.returns.push(AbiParam::new(types::I64));
+    let func_id = module
+        .declare_function("double", Linkage::Local, &sig)
+        .unwrap();
+
+    let debug = module.debug_context().unwrap();
+    let file = debug.add_file(DebugFile {
+        name: "foo.src".to_owned(),
+        directory: None,
+    });
+    let int = debug.add_type(DebugType::Base {
+        name: "int".to_owned(),
+        size: 8,
+        encoding: BaseTypeEncoding::Signed,
+    });
+    let location = DebugLocation {
+        file,
+        line: 2,
+        column: 5,
+    };
+    let srcloc = debug.source_loc(location);
+    debug.define_function(
+        func_id,
+        FunctionDebugInfo {
+            name: "double".to_owned(),
+            location: None,
+            variables: vec![DebugVariable {
+                name: "x".to_owned(),
+                ty: int,
+                is_parameter: true,
+                location: None,
+                label: ValueLabel::new(0),
+            }],
+        },
+    );
+
+    let mut ctx = Context::new();
+    ctx.func = Function::with_name_signature(ExternalName::user(0, func_id.as_u32()), sig);
+    ctx.func.dfg.collect_debug_info();
+    let mut func_ctx = FunctionBuilderContext::new();
+    {
+        let mut bcx: FunctionBuilder = FunctionBuilder::new(&mut ctx.func, &mut func_ctx);
+        let block = bcx.create_block();
+        bcx.append_block_params_for_function_params(block);
+        bcx.switch_to_block(block);
+        let x = bcx.block_params(block)[0];
+        bcx.set_val_label(x, ValueLabel::new(0));
+        bcx.set_srcloc(srcloc);
+        let result = bcx.ins().iadd(x, x);
+        bcx.ins().return_(&[result]);
+        bcx.seal_all_blocks();
+        bcx.finalize();
+    }
+
+    let mut trap_sink = NullTrapSink {};
+    let mut stack_map_sink = NullStackMapSink {};
+    module
+        .define_function(func_id, &mut ctx, &mut trap_sink, &mut stack_map_sink)
+        .unwrap();
+    assert_eq!(module.debug_images().count(), 0);
+    let value_labels_ranges = &ctx
+        .mach_compile_result
+        .as_ref()
+        .unwrap()
+        .value_labels_ranges;
+
+    module.finalize_definitions();
+
+    let images = module.debug_images().collect::<Vec<_>>();
+    assert_eq!(images.len(), 1);
+    assert_eq!(&images[0][..4], b"\x7fELF");
+
+    let double = module.get_finalized_function(func_id);
+
+    // The image describes the function at its final address.
+    let file = object::File::parse(images[0]).unwrap();
+    let endian = if file.is_little_endian() {
+        gimli::RunTimeEndian::Little
+    } else {
+        gimli::RunTimeEndian::Big
+    };
+    let dwarf = gimli::Dwarf::load(|id| -> Result<_, gimli::Error> {
+        let data = match file.section_by_name(id.name()) {
+            Some(section) => section.data().unwrap(),
+            None => &[],
+        };
+        Ok(gimli::EndianSlice::new(data, endian))
+    })
+    .unwrap();
+    let (address, size) = function_range(&dwarf, "double");
+    assert_eq!(address, double as u64);
+
+    // The prologue has no source location, and the function has no declaration to attribute it
+    // to, so it gets line 0. The `iadd` and the `return` are on line 2.
+    let rows = line_rows(&dwarf);
+    let lines = rows.iter().map(|&(_, line)| line).collect::<Vec<_>>();
+    assert_eq!(lines, [0, 2]);
+    assert_eq!(rows[0].0, address);
+    assert!(rows[1].0 > address && rows[1].0 < address + size);
+
+    // The location list of `x` follows the value label ranges of the compiled function, and it
+    // starts out in a register.
+    let locations = variable_locations(&dwarf, "x");
+    let ranges = locations
+        .iter()
+        .map(|(range, _)| (range.begin - address, range.end - address))
+        .collect::<Vec<_>>();
+    let expected = value_labels_ranges[&ValueLabel::new(0)]
+        .iter()
+        .map(|range| (u64::from(range.start), u64::from(range.end).min(size)))
+        .collect::<Vec<_>>();
+    assert_eq!(ranges, expected);
+    let op = gimli::DwOp(locations[0].1[0]);
+    assert!(
+        op >= gimli::DW_OP_reg0 && op <= gimli::DW_OP_reg31,
+        "{}",
+        op
+    );
+
+    let double = unsafe { std::mem::transmute::<_, extern "C" fn(i64) -> i64>(double) };
+    assert_eq!(double(21), 42);
+}
+
+type Reader<'a> = gimli::EndianSlice<'a, gimli::RunTimeEndian>;
+
+/// Returns the entry of the compilation unit with the given tag and name.
+fn find_entry<'a>(
+    dwarf: &gimli::Dwarf<Reader<'a>>,
+    unit: &gimli::Unit<Reader<'a>>,
+    tag: gimli::DwTag,
+    name: &str,
+) -> gimli::UnitOffset {
+    let mut entries = unit.entries();
+    while let Some((_, entry)) = entries.next_dfs().unwrap() {
+        if entry.tag() != tag {
+            continue;
+        }
+        let attr = entry.attr_value(gimli::DW_AT_name).unwrap().unwrap();
+        if dwarf.attr_string(unit, attr).unwrap().slice() == name.as_bytes() {
+            return entry.offset();
+        }
+    }
+    panic!("no entry for {}", name);
+}
+
+fn only_unit<'a>(dwarf: &gimli::Dwarf<Reader<'a>>) -> gimli::Unit<Reader<'a>> {
+    let mut units = dwarf.units();
+    let header = units.next().unwrap().unwrap();
+    assert!(units.next().unwrap().is_none());
+    dwarf.unit(header).unwrap()
+}
+
+/// Returns the start address and the size of the function with the given name.
+fn function_range(dwarf: &gimli::Dwarf<Reader>, name: &str) -> (u64, u64) {
+    let unit = only_unit(dwarf);
+    let offset = find_entry(dwarf, &unit, gimli::DW_TAG_subprogram, name);
+    let entry = unit.entry(offset).unwrap();
+    let low_pc = match entry.attr_value(gimli::DW_AT_low_pc).unwrap() {
+        Some(gimli::AttributeValue::Addr(addr)) => addr,
+        value => panic!("unexpected low_pc {:?}", value),
+    };
+    let size = entry
+        .attr_value(gimli::DW_AT_high_pc)
+        .unwrap()
+        .and_then(|value| value.udata_value())
+        .unwrap();
+    (low_pc, size)
+}
+
+/// Returns the address and line of each row of the line program, without the end of the sequence.
+fn line_rows(dwarf: &gimli::Dwarf<Reader>) -> Vec<(u64, u64)> {
+    let unit = only_unit(dwarf);
+    let mut rows = unit.line_program.unwrap().rows();
+    let mut result = vec![];
+    while let Some((_, row)) = rows.next_row().unwrap() {
+        if !row.end_sequence() {
+            result.push((row.address(), row.line().map_or(0, |line| line.get())));
+        }
+    }
+    result
+}
+
+/// Returns the ranges of the location list of the variable with the given name, with the bytes of
+/// the location expression for each.
+fn variable_locations(dwarf: &gimli::Dwarf<Reader>, name: &str) -> Vec<(gimli::Range, Vec<u8>)> {
+    let unit = only_unit(dwarf);
+    let offset = find_entry(dwarf, &unit, gimli::DW_TAG_formal_parameter, name);
+    let entry = unit.entry(offset).unwrap();
+    let offset = match entry.attr_value(gimli::DW_AT_location).unwrap() {
+        Some(gimli::AttributeValue::LocationListsRef(offset)) => offset,
+        value => panic!("unexpected location {:?}", value),
+    };
+    let mut locations = dwarf.locations(&unit, offset).unwrap();
+    let mut result = vec![];
+    while let Some(location) = locations.next().unwrap() {
+        result.push((location.range, location.data.0.slice().to_vec()));
+    }
+    result
+}
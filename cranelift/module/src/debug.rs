@@ -0,0 +1,231 @@
+//! Debug info registered by frontends, to be emitted as DWARF by the backends.
+//!
+//! Frontends create a [`DebugContext`], pass it to the backend when building it and then use
+//! [`Module::debug_context`](crate::Module::debug_context) to register the source files, types,
+//! functions and variables of the module. Source locations are interned by the `DebugContext`,
+//! which hands out the `ir::SourceLoc`s to attach to instructions, and variables refer to the
+//! `ir::ValueLabel`s attached to values.
+
+use super::HashMap;
+use crate::module::FuncId;
+use cranelift_codegen::entity::{entity_impl, PrimaryMap, SecondaryMap};
+use cranelift_codegen::ir;
+use std::string::String;
+use std::vec::Vec;
+
+/// A source file identifier for use in the debug info API.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileId(u32);
+entity_impl!(FileId, "file");
+
+/// A type identifier for use in the debug info API.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DebugTypeId(u32);
+entity_impl!(DebugTypeId, "type");
+
+/// A source file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DebugFile {
+    /// The name of the file, either absolute or relative to `directory`.
+    pub name: String,
+    /// The directory containing the file, or `None` for the compilation directory.
+    pub directory: Option<String>,
+}
+
+/// How the value of a base type is encoded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BaseTypeEncoding {
+    /// A boolean.
+    Boolean,
+    /// A signed integer.
+    Signed,
+    /// An unsigned integer.
+    Unsigned,
+    /// A signed character.
+    SignedChar,
+    /// An unsigned character.
+    UnsignedChar,
+    /// A binary floating point number.
+    Float,
+    /// A machine address.
+    Address,
+}
+
+/// The type of a variable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DebugType {
+    /// A type which is not made up of other types.
+    Base {
+        /// The name of the type.
+        name: String,
+        /// The size of the type in bytes.
+        size: u8,
+        /// How values of the type are encoded.
+        encoding: BaseTypeEncoding,
+    },
+    /// A pointer.
+    Pointer {
+        /// The name of the type, if it has one.
+        name: Option<String>,
+        /// The size of the pointer in bytes.
+        size: u8,
+        /// The type pointed to, or `None` for an untyped pointer.
+        pointee: Option<DebugTypeId>,
+    },
+}
+
+/// A position in a source file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DebugLocation {
+    /// The source file.
+    pub file: FileId,
+    /// The line number, starting at 1, or 0 if unknown.
+    pub line: u32,
+    /// The column number, starting at 1, or 0 if unknown.
+    pub column: u32,
+}
+
+/// A variable or parameter of a function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DebugVariable {
+    /// The name of the variable.
+    pub name: String,
+    /// The type of the variable.
+    pub ty: DebugTypeId,
+    /// Whether this is a parameter of the function rather than a local variable.
+    pub is_parameter: bool,
+    /// The declaration of the variable, if known.
+    pub location: Option<DebugLocation>,
+    /// The label of the values holding the variable, as set by `FunctionBuilder::set_val_label`.
+    ///
+    /// The variable has no location (is "optimized out") wherever no value with this label is
+    /// live.
+    pub label: ir::ValueLabel,
+}
+
+/// The debug info of a function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionDebugInfo {
+    /// The name of the function in the source language.
+    pub name: String,
+    /// The declaration of the function, if known.
+    pub location: Option<DebugLocation>,
+    /// The variables and parameters of the function.
+    pub variables: Vec<DebugVariable>,
+}
+
+/// The debug info of a module, as registered by a frontend.
+///
+/// Only functions which have debug info registered here are described by the DWARF emitted by
+/// the backends, though instructions of all of them may have source locations.
+pub struct DebugContext {
+    producer: String,
+    name: String,
+    comp_dir: String,
+    files: PrimaryMap<FileId, DebugFile>,
+    types: PrimaryMap<DebugTypeId, DebugType>,
+    locations: Vec<DebugLocation>,
+    location_ids: HashMap<DebugLocation, ir::SourceLoc>,
+    functions: SecondaryMap<FuncId, Option<FunctionDebugInfo>>,
+}
+
+impl DebugContext {
+    /// Create a new `DebugContext`.
+    ///
+    /// `producer` names the compiler, `name` the primary source file of the module, and
+    /// `comp_dir` the directory relative paths are resolved against.
+    pub fn new<P, N, D>(producer: P, name: N, comp_dir: D) -> Self
+    where
+        P: Into<String>,
+        N: Into<String>,
+        D: Into<String>,
+    {
+        Self {
+            producer: producer.into(),
+            name: name.into(),
+            comp_dir: comp_dir.into(),
+            files: PrimaryMap::new(),
+            types: PrimaryMap::new(),
+            locations: Vec::new(),
+            location_ids: HashMap::new(),
+            functions: SecondaryMap::new(),
+        }
+    }
+
+    /// The name of the compiler producing the module.
+    pub fn producer(&self) -> &str {
+        &self.producer
+    }
+
+    /// The name of the primary source file of the module.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The directory relative paths are resolved against.
+    pub fn comp_dir(&self) -> &str {
+        &self.comp_dir
+    }
+
+    /// Register a source file.
+    pub fn add_file(&mut self, file: DebugFile) -> FileId {
+        self.files.push(file)
+    }
+
+    /// Get a registered source file.
+    pub fn file(&self, file: FileId) -> &DebugFile {
+        &self.files[file]
+    }
+
+    /// Iterate over all registered source files.
+    pub fn files(&self) -> impl Iterator<Item = (FileId, &DebugFile)> {
+        self.files.iter()
+    }
+
+    /// Register a type.
+    pub fn add_type(&mut self, ty: DebugType) -> DebugTypeId {
+        self.types.push(ty)
+    }
+
+    /// Get a registered type.
+    pub fn debug_type(&self, ty: DebugTypeId) -> &DebugType {
+        &self.types[ty]
+    }
+
+    /// Iterate over all registered types.
+    pub fn types(&self) -> impl Iterator<Item = (DebugTypeId, &DebugType)> {
+        self.types.iter()
+    }
+
+    /// Get the `ir::SourceLoc` to attach to instructions at the given source location.
+    pub fn source_loc(&mut self, location: DebugLocation) -> ir::SourceLoc {
+        let locations = &mut self.locations;
+        *self.location_ids.entry(location).or_insert_with(|| {
+            let srcloc = ir::SourceLoc::new(locations.len() as u32);
+            assert!(!srcloc.is_default(), "too many source locations");
+            locations.push(location);
+            srcloc
+        })
+    }
+
+    /// Get the source location of an `ir::SourceLoc` handed out by
+    /// [`source_loc`](Self::source_loc), or `None` for any other `ir::SourceLoc`.
+    pub fn location(&self, srcloc: ir::SourceLoc) -> Option<DebugLocation> {
+        if srcloc.is_default() {
+            return None;
+        }
+        self.locations.get(srcloc.bits() as usize).copied()
+    }
+
+    /// Register the debug info of a function.
+    ///
+    /// This may be done either before or after the function is defined.
+    pub fn define_function(&mut self, func: FuncId, info: FunctionDebugInfo) {
+        self.functions[func] = Some(info);
+    }
+
+    /// Get the debug info of a function, if any has been registered.
+    pub fn function(&self, func: FuncId) -> Option<&FunctionDebugInfo> {
+        self.functions[func].as_ref()
+    }
+}
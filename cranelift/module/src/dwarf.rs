@@ -0,0 +1,519 @@
+//! Emission of DWARF debug info for the functions of a module.
+//!
+//! The backends collect a [`CompiledFunctionDebugInfo`] for each function when it is defined and
+//! use [`emit_dwarf`] to combine them with the debug info registered in the [`DebugContext`].
+
+use crate::debug::{BaseTypeEncoding, DebugContext, DebugType, FileId};
+use crate::module::{FuncId, ModuleError, ModuleResult};
+use cranelift_codegen::entity::SecondaryMap;
+use cranelift_codegen::gimli::write::{
+    Address, AttributeValue, DwarfUnit, EndianVec, Expression, FrameTable, LineProgram, LineString,
+    Location, LocationList, Range, RangeList, Sections, UnitEntryId, Writer,
+};
+use cranelift_codegen::gimli::{self, constants, Encoding, Format, LineEncoding, RunTimeEndian};
+use cranelift_codegen::ir::{self, LabelValueLoc};
+use cranelift_codegen::isa::unwind::UnwindInfo;
+use cranelift_codegen::isa::TargetIsa;
+use cranelift_codegen::{Context, MachSrcLoc, ValueLabelsRanges};
+use std::vec::Vec;
+
+/// The debug info Cranelift computed while compiling a function.
+pub struct CompiledFunctionDebugInfo {
+    size: u32,
+    srclocs: Vec<MachSrcLoc>,
+    value_labels_ranges: ValueLabelsRanges,
+    unwind_info: Option<UnwindInfo>,
+}
+
+impl CompiledFunctionDebugInfo {
+    /// Collect the debug info of the function which was just compiled in `ctx`.
+    pub fn new(ctx: &Context, isa: &dyn TargetIsa) -> ModuleResult<Self> {
+        let result = ctx
+            .mach_compile_result
+            .as_ref()
+            .expect("function must be compiled before collecting its debug info");
+        Ok(Self {
+            size: result.buffer.total_size(),
+            srclocs: result.buffer.get_srclocs_sorted().to_vec(),
+            value_labels_ranges: result.value_labels_ranges.clone(),
+            unwind_info: ctx.create_unwind_info(isa)?,
+        })
+    }
+}
+
+/// How DWARF refers to the code of functions.
+pub enum DwarfAddresses<'a> {
+    /// Through relocations against the symbols of the functions.
+    Relocatable,
+    /// By the absolute address the code of each function has been loaded at.
+    Absolute(&'a dyn Fn(FuncId) -> u64),
+}
+
+/// A DWARF section.
+pub struct DwarfSection {
+    /// The section.
+    pub id: gimli::SectionId,
+    /// The contents of the section.
+    pub body: Vec<u8>,
+    /// The relocations to apply to the contents of the section.
+    pub relocs: Vec<DwarfReloc>,
+}
+
+/// A relocation in a DWARF section.
+#[derive(Clone, Debug)]
+pub struct DwarfReloc {
+    /// The offset of the relocation in the section.
+    pub offset: u32,
+    /// The size of the relocated value in bytes.
+    pub size: u8,
+    /// The target of the relocation.
+    pub target: DwarfRelocTarget,
+    /// The addend of the relocation, which is also written to the section contents.
+    pub addend: i64,
+}
+
+/// The target of a relocation in a DWARF section.
+#[derive(Clone, Copy, Debug)]
+pub enum DwarfRelocTarget {
+    /// The start of the code of a function.
+    Function(FuncId),
+    /// The start of another DWARF section.
+    Section(gimli::SectionId),
+}
+
+/// Emit the DWARF sections describing the given compiled functions.
+///
+/// Only the functions which have debug info registered in `debug` are described. Empty sections
+/// are omitted from the result.
+pub fn emit_dwarf(
+    isa: &dyn TargetIsa,
+    debug: &DebugContext,
+    functions: &[(FuncId, &CompiledFunctionDebugInfo)],
+    addresses: DwarfAddresses,
+) -> ModuleResult<Vec<DwarfSection>> {
+    let address = |func: FuncId, offset: u32| match addresses {
+        DwarfAddresses::Relocatable => Address::Symbol {
+            symbol: func.as_u32() as usize,
+            addend: i64::from(offset),
+        },
+        DwarfAddresses::Absolute(address_of) => {
+            Address::Constant(address_of(func) + u64::from(offset))
+        }
+    };
+    let functions = functions
+        .iter()
+        .filter(|(func, _)| debug.function(*func).is_some())
+        .collect::<Vec<_>>();
+
+    let encoding = Encoding {
+        format: Format::Dwarf32,
+        version: 4,
+        address_size: isa.triple().pointer_width().unwrap().bytes(),
+    };
+    let line_program = LineProgram::new(
+        encoding,
+        LineEncoding::default(),
+        LineString::String(debug.comp_dir().as_bytes().to_vec()),
+        LineString::String(debug.name().as_bytes().to_vec()),
+        None,
+    );
+    let mut dwarf = DwarfUnit::new(encoding);
+    dwarf.unit.line_program = line_program;
+
+    let mut files = SecondaryMap::<FileId, Option<gimli::write::FileId>>::new();
+    for (file, info) in debug.files() {
+        let program = &mut dwarf.unit.line_program;
+        let directory = match &info.directory {
+            Some(directory) => {
+                program.add_directory(LineString::String(directory.as_bytes().to_vec()))
+            }
+            None => program.default_directory(),
+        };
+        files[file] = Some(program.add_file(
+            LineString::String(info.name.as_bytes().to_vec()),
+            directory,
+            None,
+        ));
+    }
+
+    let root = dwarf.unit.root();
+    let entry = dwarf.unit.get_mut(root);
+    entry.set(
+        constants::DW_AT_producer,
+        AttributeValue::String(debug.producer().as_bytes().to_vec()),
+    );
+    entry.set(
+        constants::DW_AT_name,
+        AttributeValue::String(debug.name().as_bytes().to_vec()),
+    );
+    entry.set(
+        constants::DW_AT_comp_dir,
+        AttributeValue::String(debug.comp_dir().as_bytes().to_vec()),
+    );
+    entry.set(
+        constants::DW_AT_low_pc,
+        AttributeValue::Address(Address::Constant(0)),
+    );
+    let ranges = dwarf.unit.ranges.add(RangeList(
+        functions
+            .iter()
+            .map(|(func, info)| Range::StartLength {
+                begin: address(*func, 0),
+                length: u64::from(info.size),
+            })
+            .collect(),
+    ));
+    dwarf.unit.get_mut(root).set(
+        constants::DW_AT_ranges,
+        AttributeValue::RangeListRef(ranges),
+    );
+
+    let types = add_types(&mut dwarf, debug, root);
+
+    for &&(func, info) in &functions {
+        let func_debug = debug.function(func).unwrap();
+
+        // Line table rows for every change of source location. Code without a known source
+        // location gets line 0, except for the prologue, which gets the declaration of the
+        // function.
+        let program = &mut dwarf.unit.line_program;
+        program.begin_sequence(Some(address(func, 0)));
+        let mut prev = None;
+        let mut prev_end = 0;
+        let mut in_prologue = true;
+        for srcloc in &info.srclocs {
+            if srcloc.start > prev_end && prev.is_some() {
+                add_row(program, &files, prev_end, None);
+                prev = None;
+            }
+            let mut location = debug.location(srcloc.loc);
+            if location.is_some() {
+                in_prologue = false;
+            } else if in_prologue {
+                location = func_debug.location;
+            }
+            if prev != Some(location) {
+                add_row(program, &files, srcloc.start, location);
+                prev = Some(location);
+            }
+            prev_end = srcloc.end;
+        }
+        program.end_sequence(u64::from(info.size));
+
+        let subprogram = dwarf.unit.add(root, constants::DW_TAG_subprogram);
+        let entry = dwarf.unit.get_mut(subprogram);
+        entry.set(
+            constants::DW_AT_name,
+            AttributeValue::String(func_debug.name.as_bytes().to_vec()),
+        );
+        if let Some(location) = func_debug.location {
+            entry.set(
+                constants::DW_AT_decl_file,
+                AttributeValue::FileIndex(files[location.file]),
+            );
+            entry.set(
+                constants::DW_AT_decl_line,
+                AttributeValue::Udata(u64::from(location.line)),
+            );
+        }
+        entry.set(
+            constants::DW_AT_low_pc,
+            AttributeValue::Address(address(func, 0)),
+        );
+        entry.set(
+            constants::DW_AT_high_pc,
+            AttributeValue::Udata(u64::from(info.size)),
+        );
+        let mut frame_base = Expression::new();
+        frame_base.op(constants::DW_OP_call_frame_cfa);
+        entry.set(
+            constants::DW_AT_frame_base,
+            AttributeValue::Exprloc(frame_base),
+        );
+
+        for variable in &func_debug.variables {
+            let tag = if variable.is_parameter {
+                constants::DW_TAG_formal_parameter
+            } else {
+                constants::DW_TAG_variable
+            };
+            let locations = info
+                .value_labels_ranges
+                .get(&variable.label)
+                .map(|ranges| {
+                    ranges
+                        .iter()
+                        .filter(|range| range.start < range.end.min(info.size))
+                        .filter_map(|range| {
+                            Some(Location::StartLength {
+                                begin: address(func, range.start),
+                                length: u64::from(range.end.min(info.size) - range.start),
+                                data: translate_loc(isa, range.loc)?,
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            let locations = if locations.is_empty() {
+                None
+            } else {
+                Some(dwarf.unit.locations.add(LocationList(locations)))
+            };
+
+            let var = dwarf.unit.add(subprogram, tag);
+            let entry = dwarf.unit.get_mut(var);
+            entry.set(
+                constants::DW_AT_name,
+                AttributeValue::String(variable.name.as_bytes().to_vec()),
+            );
+            entry.set(
+                constants::DW_AT_type,
+                AttributeValue::UnitRef(types[variable.ty]),
+            );
+            if let Some(location) = variable.location {
+                entry.set(
+                    constants::DW_AT_decl_file,
+                    AttributeValue::FileIndex(files[location.file]),
+                );
+                entry.set(
+                    constants::DW_AT_decl_line,
+                    AttributeValue::Udata(u64::from(location.line)),
+                );
+            }
+            if let Some(locations) = locations {
+                entry.set(
+                    constants::DW_AT_location,
+                    AttributeValue::LocationListRef(locations),
+                );
+            }
+        }
+    }
+
+    let endian = match isa.endianness() {
+        ir::Endianness::Little => RunTimeEndian::Little,
+        ir::Endianness::Big => RunTimeEndian::Big,
+    };
+    let mut sections = Sections::new(WriterRelocate {
+        relocs: Vec::new(),
+        writer: EndianVec::new(endian),
+    });
+    dwarf.write(&mut sections).map_err(dwarf_error)?;
+
+    if let Some(cie) = isa.create_systemv_cie() {
+        let mut frames = FrameTable::default();
+        let cie = frames.add_cie(cie);
+        for &&(func, info) in &functions {
+            if let Some(UnwindInfo::SystemV(unwind_info)) = &info.unwind_info {
+                frames.add_fde(cie, unwind_info.to_fde(address(func, 0)));
+            }
+        }
+        frames
+            .write_debug_frame(&mut sections.debug_frame)
+            .map_err(dwarf_error)?;
+    }
+
+    let mut result = Vec::new();
+    sections
+        .for_each_mut(|id, section| -> gimli::write::Result<()> {
+            if section.writer.len() != 0 {
+                result.push(DwarfSection {
+                    id,
+                    body: section.writer.take(),
+                    relocs: std::mem::take(&mut section.relocs),
+                });
+            }
+            Ok(())
+        })
+        .map_err(dwarf_error)?;
+    Ok(result)
+}
+
+fn dwarf_error(err: gimli::write::Error) -> ModuleError {
+    ModuleError::Backend(anyhow::anyhow!("failed to emit DWARF: {}", err))
+}
+
+fn add_row(
+    program: &mut LineProgram,
+    files: &SecondaryMap<FileId, Option<gimli::write::FileId>>,
+    offset: u32,
+    location: Option<crate::debug::DebugLocation>,
+) {
+    let row = program.row();
+    row.address_offset = u64::from(offset);
+    match location {
+        Some(location) => {
+            row.file = files[location.file].unwrap();
+            row.line = u64::from(location.line);
+            row.column = u64::from(location.column);
+            row.is_statement = true;
+        }
+        None => {
+            row.line = 0;
+            row.column = 0;
+            row.is_statement = false;
+        }
+    }
+    program.generate_row();
+}
+
+/// Add a DIE for every type registered in `debug`.
+fn add_types(
+    dwarf: &mut DwarfUnit,
+    debug: &DebugContext,
+    root: UnitEntryId,
+) -> SecondaryMap<crate::debug::DebugTypeId, UnitEntryId> {
+    let mut types = SecondaryMap::with_default(root);
+    for (id, ty) in debug.types() {
+        types[id] = match ty {
+            DebugType::Base { .. } => dwarf.unit.add(root, constants::DW_TAG_base_type),
+            DebugType::Pointer { .. } => dwarf.unit.add(root, constants::DW_TAG_pointer_type),
+        };
+    }
+    for (id, ty) in debug.types() {
+        let entry = dwarf.unit.get_mut(types[id]);
+        match ty {
+            DebugType::Base {
+                name,
+                size,
+                encoding,
+            } => {
+                entry.set(
+                    constants::DW_AT_name,
+                    AttributeValue::String(name.as_bytes().to_vec()),
+                );
+                entry.set(constants::DW_AT_byte_size, AttributeValue::Data1(*size));
+                let encoding = match encoding {
+                    BaseTypeEncoding::Boolean => constants::DW_ATE_boolean,
+                    BaseTypeEncoding::Signed => constants::DW_ATE_signed,
+                    BaseTypeEncoding::Unsigned => constants::DW_ATE_unsigned,
+                    BaseTypeEncoding::SignedChar => constants::DW_ATE_signed_char,
+                    BaseTypeEncoding::UnsignedChar => constants::DW_ATE_unsigned_char,
+                    BaseTypeEncoding::Float => constants::DW_ATE_float,
+                    BaseTypeEncoding::Address => constants::DW_ATE_address,
+                };
+                entry.set(
+                    constants::DW_AT_encoding,
+                    AttributeValue::Encoding(encoding),
+                );
+            }
+            DebugType::Pointer {
+                name,
+                size,
+                pointee,
+            } => {
+                if let Some(name) = name {
+                    entry.set(
+                        constants::DW_AT_name,
+                        AttributeValue::String(name.as_bytes().to_vec()),
+                    );
+                }
+                entry.set(constants::DW_AT_byte_size, AttributeValue::Data1(*size));
+                if let Some(pointee) = pointee {
+                    entry.set(
+                        constants::DW_AT_type,
+                        AttributeValue::UnitRef(types[*pointee]),
+                    );
+                }
+            }
+        }
+    }
+    types
+}
+
+/// Translate the location of a value into a DWARF location description, or `None` if it can't
+/// be described.
+fn translate_loc(isa: &dyn TargetIsa, loc: LabelValueLoc) -> Option<Expression> {
+    let mut expr = Expression::new();
+    match loc {
+        LabelValueLoc::Reg(reg) => {
+            let reg = isa.map_regalloc_reg_to_dwarf(reg).ok()?;
+            expr.op_reg(gimli::Register(reg));
+        }
+        LabelValueLoc::SPOffset(offset) => {
+            let sp = match isa.triple().architecture {
+                target_lexicon::Architecture::X86_64 => gimli::X86_64::RSP,
+                target_lexicon::Architecture::Aarch64(_) => gimli::AArch64::SP,
+                target_lexicon::Architecture::Riscv64(_) => gimli::RiscV::SP,
+                // %r15 is the stack pointer on s390x.
+                target_lexicon::Architecture::S390x => gimli::Register(15),
+                _ => return None,
+            };
+            expr.op_breg(sp, offset);
+        }
+    }
+    Some(expr)
+}
+
+/// A `Writer` which records the relocations needed for the addresses and section offsets it
+/// writes.
+#[derive(Clone)]
+struct WriterRelocate {
+    relocs: Vec<DwarfReloc>,
+    writer: EndianVec<RunTimeEndian>,
+}
+
+impl Writer for WriterRelocate {
+    type Endian = RunTimeEndian;
+
+    fn endian(&self) -> Self::Endian {
+        self.writer.endian()
+    }
+
+    fn len(&self) -> usize {
+        self.writer.len()
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> gimli::write::Result<()> {
+        self.writer.write(bytes)
+    }
+
+    fn write_at(&mut self, offset: usize, bytes: &[u8]) -> gimli::write::Result<()> {
+        self.writer.write_at(offset, bytes)
+    }
+
+    fn write_address(&mut self, address: Address, size: u8) -> gimli::write::Result<()> {
+        match address {
+            Address::Constant(val) => self.write_udata(val, size),
+            Address::Symbol { symbol, addend } => {
+                self.relocs.push(DwarfReloc {
+                    offset: self.len() as u32,
+                    size,
+                    target: DwarfRelocTarget::Function(FuncId::from_u32(symbol as u32)),
+                    addend,
+                });
+                self.write_udata(addend as u64, size)
+            }
+        }
+    }
+
+    fn write_offset(
+        &mut self,
+        val: usize,
+        section: gimli::SectionId,
+        size: u8,
+    ) -> gimli::write::Result<()> {
+        self.relocs.push(DwarfReloc {
+            offset: self.len() as u32,
+            size,
+            target: DwarfRelocTarget::Section(section),
+            addend: val as i64,
+        });
+        self.write_udata(val as u64, size)
+    }
+
+    fn write_offset_at(
+        &mut self,
+        offset: usize,
+        val: usize,
+        section: gimli::SectionId,
+        size: u8,
+    ) -> gimli::write::Result<()> {
+        self.relocs.push(DwarfReloc {
+            offset: offset as u32,
+            size,
+            target: DwarfRelocTarget::Section(section),
+            addend: val as i64,
+        });
+        self.write_udata_at(offset, val as u64, size)
+    }
+}
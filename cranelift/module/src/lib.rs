@@ -37,10 +37,17 @@ use std::string::String;
 use cranelift_codegen::ir;
 
 mod data_context;
+mod debug;
+#[cfg(feature = "debug-info")]
+pub mod dwarf;
 mod module;
 mod traps;
 
 pub use crate::data_context::{DataContext, DataDescription, Init};
+pub use crate::debug::{
+    BaseTypeEncoding, DebugContext, DebugFile, DebugLocation, DebugType, DebugTypeId,
+    DebugVariable, FileId, FunctionDebugInfo,
+};
 pub use crate::module::{
     DataId, FuncId, FuncOrDataId, Linkage, Module, ModuleCompiledFunction, ModuleDeclarations,
     ModuleError, ModuleResult, RelocRecord,
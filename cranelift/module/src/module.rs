@@ -7,6 +7,7 @@
 
 use super::HashMap;
 use crate::data_context::DataContext;
+use crate::debug::DebugContext;
 use cranelift_codegen::binemit;
 use cranelift_codegen::entity::{entity_impl, PrimaryMap};
 use cranelift_codegen::{ir, isa, CodegenError, Context};
@@ -437,6 +438,12 @@ pub trait Module {
     /// Get all declarations in this module.
     fn declarations(&self) -> &ModuleDeclarations;
 
+    /// Get the debug info of this module, if the backend emits debug info and has been given a
+    /// `DebugContext` to register it in.
+    fn debug_context(&mut self) -> Option<&mut DebugContext> {
+        None
+    }
+
     /// Get the module identifier for a given name, if that name
     /// has been declared.
     fn get_name(&self, name: &str) -> Option<FuncOrDataId> {
@@ -585,6 +592,10 @@ impl<M: Module> Module for &mut M {
         (**self).declarations()
     }
 
+    fn debug_context(&mut self) -> Option<&mut DebugContext> {
+        (**self).debug_context()
+    }
+
     fn get_name(&self, name: &str) -> Option<FuncOrDataId> {
         (**self).get_name(name)
     }
@@ -139,6 +139,31 @@ pub fn builder_with_options(infer_native_flags: bool) -> Result<isa::Builder, &'
         }
     }
 
+    // On riscv64 the kernel reports the single-letter extensions in
+    // AT_HWCAP, one bit per letter. The bit-manipulation extensions are not
+    // reported there, so they are left disabled.
+    #[cfg(all(target_arch = "riscv64", target_os = "linux"))]
+    {
+        use cranelift_codegen::settings::Configurable;
+
+        if !infer_native_flags {
+            return Ok(isa_builder);
+        }
+
+        let v = unsafe { libc::getauxval(libc::AT_HWCAP) };
+        for (ext, setting) in [
+            ('m', "has_m"),
+            ('a', "has_a"),
+            ('f', "has_f"),
+            ('d', "has_d"),
+        ] {
+            let bit = 1 << (ext as u32 - 'a' as u32);
+            if (v & bit) == 0 {
+                isa_builder.set(setting, "false").unwrap();
+            }
+        }
+    }
+
     // squelch warnings about unused mut/variables on some platforms.
     drop(&mut isa_builder);
     drop(infer_native_flags);
@@ -156,7 +181,9 @@ mod tests {
     fn test() {
         if let Ok(isa_builder) = builder() {
             let flag_builder = settings::builder();
-            let isa = isa_builder.finish(settings::Flags::new(flag_builder));
+            let isa = isa_builder
+                .finish(settings::Flags::new(flag_builder))
+                .unwrap();
 
             if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
                 assert_eq!(isa.default_call_conv(), CallConv::AppleAarch64);
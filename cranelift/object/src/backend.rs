@@ -8,9 +8,12 @@ use cranelift_codegen::{
     binemit::{Addend, CodeOffset, Reloc, RelocSink, StackMapSink, TrapSink},
     CodegenError,
 };
+use cranelift_module::dwarf::{
+    emit_dwarf, CompiledFunctionDebugInfo, DwarfAddresses, DwarfRelocTarget,
+};
 use cranelift_module::{
-    DataContext, DataDescription, DataId, FuncId, Init, Linkage, Module, ModuleCompiledFunction,
-    ModuleDeclarations, ModuleError, ModuleResult, RelocRecord,
+    DataContext, DataDescription, DataId, DebugContext, FuncId, Init, Linkage, Module,
+    ModuleCompiledFunction, ModuleDeclarations, ModuleError, ModuleResult, RelocRecord,
 };
 use log::info;
 use object::write::{
@@ -34,6 +37,7 @@ pub struct ObjectBuilder {
     libcall_names: Box<dyn Fn(ir::LibCall) -> String + Send + Sync>,
     function_alignment: u64,
     per_function_section: bool,
+    debug_context: Option<DebugContext>,
 }
 
 impl ObjectBuilder {
@@ -73,6 +77,7 @@ impl ObjectBuilder {
             target_lexicon::Architecture::X86_64 => object::Architecture::X86_64,
             target_lexicon::Architecture::Arm(_) => object::Architecture::Arm,
             target_lexicon::Architecture::Aarch64(_) => object::Architecture::Aarch64,
+            target_lexicon::Architecture::Riscv64(_) => object::Architecture::Riscv64,
             architecture => {
                 return Err(ModuleError::Backend(anyhow!(
                     "target architecture {:?} is unsupported",
@@ -93,6 +98,7 @@ impl ObjectBuilder {
             libcall_names,
             function_alignment: 1,
             per_function_section: false,
+            debug_context: None,
         })
     }
 
@@ -107,6 +113,15 @@ impl ObjectBuilder {
         self.per_function_section = per_function_section;
         self
     }
+
+    /// Emit DWARF debug info for the functions registered in the given `DebugContext`.
+    ///
+    /// The `DebugContext` can be accessed through [`Module::debug_context`] to register debug
+    /// info while the module is being built. DWARF is only supported for ELF and COFF objects.
+    pub fn debug_context(&mut self, debug_context: DebugContext) -> &mut Self {
+        self.debug_context = Some(debug_context);
+        self
+    }
 }
 
 /// An `ObjectModule` implements `Module` and emits ".o" files using the `object` library.
@@ -125,6 +140,8 @@ pub struct ObjectModule {
     per_function_section: bool,
     anon_func_number: u64,
     anon_data_number: u64,
+    debug_context: Option<DebugContext>,
+    debug_functions: Vec<(FuncId, CompiledFunctionDebugInfo)>,
 }
 
 impl ObjectModule {
@@ -145,6 +162,8 @@ impl ObjectModule {
             per_function_section: builder.per_function_section,
             anon_func_number: 0,
             anon_data_number: 0,
+            debug_context: builder.debug_context,
+            debug_functions: Vec::new(),
         }
     }
 }
@@ -170,6 +189,10 @@ impl Module for ObjectModule {
         &self.declarations
     }
 
+    fn debug_context(&mut self) -> Option<&mut DebugContext> {
+        self.debug_context.as_mut()
+    }
+
     fn declare_function(
         &mut self,
         name: &str,
@@ -322,7 +345,17 @@ impl Module for ObjectModule {
             stack_map_sink,
         )?;
 
-        self.define_function_bytes(func_id, &code, &reloc_sink.relocs)
+        let debug_info = if self.debug_context.is_some() {
+            Some(CompiledFunctionDebugInfo::new(ctx, self.isa())?)
+        } else {
+            None
+        };
+
+        let compiled = self.define_function_bytes(func_id, &code, &reloc_sink.relocs)?;
+        if let Some(debug_info) = debug_info {
+            self.debug_functions.push((func_id, debug_info));
+        }
+        Ok(compiled)
     }
 
     fn define_function_bytes(
@@ -477,7 +510,16 @@ impl Module for ObjectModule {
 
 impl ObjectModule {
     /// Finalize all relocations and output an object.
+    ///
+    /// # Panics
+    ///
+    /// Panics if debug info is enabled and can't be emitted.
     pub fn finish(mut self) -> ObjectProduct {
+        if self.debug_context.is_some() {
+            self.emit_debug_info()
+                .unwrap_or_else(|err| panic!("failed to emit debug info: {}", err));
+        }
+
         let symbol_relocs = mem::take(&mut self.relocs);
         for symbol in symbol_relocs {
             for &ObjectRelocRecord {
@@ -522,6 +564,79 @@ impl ObjectModule {
         }
     }
 
+    /// Add DWARF sections describing the functions registered in the `DebugContext`.
+    fn emit_debug_info(&mut self) -> ModuleResult<()> {
+        let debug_context = self.debug_context.as_ref().unwrap();
+        let functions = self
+            .debug_functions
+            .iter()
+            .map(|(func, info)| (*func, info))
+            .collect::<Vec<_>>();
+        let dwarf = emit_dwarf(
+            &*self.isa,
+            debug_context,
+            &functions,
+            DwarfAddresses::Relocatable,
+        )?;
+
+        let format = self.object.format();
+        let section_offset_kind = match format {
+            object::BinaryFormat::Elf => RelocationKind::Absolute,
+            object::BinaryFormat::Coff => RelocationKind::SectionOffset,
+            _ => {
+                return Err(ModuleError::Backend(anyhow!(
+                    "DWARF debug info is not supported for {:?} objects",
+                    format
+                )))
+            }
+        };
+
+        let sections = dwarf
+            .iter()
+            .map(|section| {
+                let id = self.object.add_section(
+                    vec![],
+                    section.id.name().as_bytes().to_vec(),
+                    SectionKind::Debug,
+                );
+                self.object.set_section_data(id, section.body.clone(), 1);
+                (section.id, id)
+            })
+            .collect::<HashMap<_, _>>();
+
+        for section in &dwarf {
+            for reloc in &section.relocs {
+                let (kind, symbol) = match reloc.target {
+                    DwarfRelocTarget::Function(func) => {
+                        (RelocationKind::Absolute, self.functions[func].unwrap().0)
+                    }
+                    DwarfRelocTarget::Section(target) => {
+                        let target = match sections.get(&target) {
+                            Some(&target) => target,
+                            // Offsets into sections without any contents are always 0.
+                            None => continue,
+                        };
+                        (section_offset_kind, self.object.section_symbol(target))
+                    }
+                };
+                self.object
+                    .add_relocation(
+                        sections[&section.id],
+                        Relocation {
+                            offset: u64::from(reloc.offset),
+                            size: reloc.size * 8,
+                            kind,
+                            encoding: RelocationEncoding::Generic,
+                            symbol,
+                            addend: reloc.addend,
+                        },
+                    )
+                    .map_err(|err| ModuleError::Backend(anyhow!("{}", err)))?;
+            }
+        }
+        Ok(())
+    }
+
     /// This should only be called during finish because it creates
     /// symbols for missing libcalls.
     fn get_symbol(&mut self, name: &ir::ExternalName) -> SymbolId {
@@ -580,6 +695,18 @@ impl ObjectModule {
                 RelocationEncoding::AArch64Call,
                 26,
             ),
+            Reloc::RiscvCall => {
+                assert_eq!(
+                    self.object.format(),
+                    object::BinaryFormat::Elf,
+                    "RiscvCall is not supported for this file format"
+                );
+                (
+                    RelocationKind::Elf(object::elf::R_RISCV_CALL_PLT),
+                    RelocationEncoding::Generic,
+                    0,
+                )
+            }
             Reloc::ElfX86_64TlsGd => {
                 assert_eq!(
                     self.object.format(),
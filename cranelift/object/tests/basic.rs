@@ -14,7 +14,9 @@ use cranelift_object::*;
 fn error_on_incompatible_sig_in_declare_function() {
     let flag_builder = settings::builder();
     let isa_builder = cranelift_codegen::isa::lookup_by_name("x86_64-unknown-linux-gnu").unwrap();
-    let isa = isa_builder.finish(settings::Flags::new(flag_builder));
+    let isa = isa_builder
+        .finish(settings::Flags::new(flag_builder))
+        .unwrap();
     let mut module =
         ObjectModule::new(ObjectBuilder::new(isa, "foo", default_libcall_names()).unwrap());
     let mut sig = Signature {
@@ -67,7 +69,9 @@ fn define_simple_function(module: &mut ObjectModule) -> FuncId {
 fn panic_on_define_after_finalize() {
     let flag_builder = settings::builder();
     let isa_builder = cranelift_codegen::isa::lookup_by_name("x86_64-unknown-linux-gnu").unwrap();
-    let isa = isa_builder.finish(settings::Flags::new(flag_builder));
+    let isa = isa_builder
+        .finish(settings::Flags::new(flag_builder))
+        .unwrap();
     let mut module =
         ObjectModule::new(ObjectBuilder::new(isa, "foo", default_libcall_names()).unwrap());
 
@@ -152,7 +156,9 @@ fn switch_error() {
 fn libcall_function() {
     let flag_builder = settings::builder();
     let isa_builder = cranelift_codegen::isa::lookup_by_name("x86_64-unknown-linux-gnu").unwrap();
-    let isa = isa_builder.finish(settings::Flags::new(flag_builder));
+    let isa = isa_builder
+        .finish(settings::Flags::new(flag_builder))
+        .unwrap();
     let mut module =
         ObjectModule::new(ObjectBuilder::new(isa, "foo", default_libcall_names()).unwrap());
 
@@ -210,7 +216,9 @@ fn libcall_function() {
 fn reject_nul_byte_symbol_for_func() {
     let flag_builder = settings::builder();
     let isa_builder = cranelift_codegen::isa::lookup_by_name("x86_64-unknown-linux-gnu").unwrap();
-    let isa = isa_builder.finish(settings::Flags::new(flag_builder));
+    let isa = isa_builder
+        .finish(settings::Flags::new(flag_builder))
+        .unwrap();
     let mut module =
         ObjectModule::new(ObjectBuilder::new(isa, "foo", default_libcall_names()).unwrap());
 
@@ -232,7 +240,9 @@ fn reject_nul_byte_symbol_for_func() {
 fn reject_nul_byte_symbol_for_data() {
     let flag_builder = settings::builder();
     let isa_builder = cranelift_codegen::isa::lookup_by_name("x86_64-unknown-linux-gnu").unwrap();
-    let isa = isa_builder.finish(settings::Flags::new(flag_builder));
+    let isa = isa_builder
+        .finish(settings::Flags::new(flag_builder))
+        .unwrap();
     let mut module =
         ObjectModule::new(ObjectBuilder::new(isa, "foo", default_libcall_names()).unwrap());
 
@@ -245,3 +255,237 @@ fn reject_nul_byte_symbol_for_data() {
         )
         .unwrap();
 }
+
+#[test]
+fn debug_info() {
+    use object::{Object, ObjectSection};
+
+    let flag_builder = settings::builder();
+    let isa_builder = cranelift_codegen::isa::lookup_by_name("x86_64-unknown-linux-gnu").unwrap();
+    let isa = isa_builder
+        .finish(settings::Flags::new(flag_builder))
+        .unwrap();
+    let mut builder = ObjectBuilder::new(isa, "foo", default_libcall_names()).unwrap();
+    builder.debug_context(DebugContext::new("test", "foo.src", "/tmp"));
+    let mut module = ObjectModule::new(builder);
+
+    let sig = Signature {
+        params: vec![AbiParam::new(types::I64), AbiParam::new(types::I64)],
+        returns: vec![AbiParam::new(types::I64)],
+        call_conv: CallConv::SystemV,
+    };
+    let func_id = module
+        .declare_function("add", Linkage::Export, &sig)
+        .unwrap();
+
+    let debug = module.debug_context().unwrap();
+    let file = debug.add_file(DebugFile {
+        name: "foo.src".to_owned(),
+        directory: None,
+    });
+    let int = debug.add_type(DebugType::Base {
+        name: "int".to_owned(),
+        size: 8,
+        encoding: BaseTypeEncoding::Signed,
+    });
+    let line = |line| DebugLocation {
+        file,
+        line,
+        column: 1,
+    };
+    let line_2 = debug.source_loc(line(2));
+    let line_3 = debug.source_loc(line(3));
+    debug.define_function(
+        func_id,
+        FunctionDebugInfo {
+            name: "add".to_owned(),
+            location: Some(line(1)),
+            variables: vec![
+                DebugVariable {
+                    name: "a".to_owned(),
+                    ty: int,
+                    is_parameter: true,
+                    location: Some(line(1)),
+                    label: ValueLabel::new(0),
+                },
+                DebugVariable {
+                    name: "b".to_owned(),
+                    ty: int,
+                    is_parameter: true,
+                    location: Some(line(1)),
+                    label: ValueLabel::new(1),
+                },
+            ],
+        },
+    );
+
+    let mut ctx = Context::new();
+    ctx.func = Function::with_name_signature(ExternalName::user(0, func_id.as_u32()), sig);
+    ctx.func.dfg.collect_debug_info();
+    let mut func_ctx = FunctionBuilderContext::new();
+    {
+        let mut bcx: FunctionBuilder = FunctionBuilder::new(&mut ctx.func, &mut func_ctx);
+        let block = bcx.create_block();
+        bcx.append_block_params_for_function_params(block);
+        bcx.switch_to_block(block);
+        let a = bcx.block_params(block)[0];
+        let b = bcx.block_params(block)[1];
+        bcx.set_val_label(a, ValueLabel::new(0));
+        bcx.set_val_label(b, ValueLabel::new(1));
+        bcx.set_srcloc(line_2);
+        let sum = bcx.ins().iadd(a, b);
+        bcx.set_srcloc(line_3);
+        bcx.ins().return_(&[sum]);
+        bcx.seal_all_blocks();
+        bcx.finalize();
+    }
+
+    let mut trap_sink = NullTrapSink {};
+    let mut stack_map_sink = NullStackMapSink {};
+    module
+        .define_function(func_id, &mut ctx, &mut trap_sink, &mut stack_map_sink)
+        .unwrap();
+
+    let value_labels_ranges = &ctx
+        .mach_compile_result
+        .as_ref()
+        .unwrap()
+        .value_labels_ranges;
+
+    let bytes = module.finish().emit().unwrap();
+    let file = object::File::parse(&*bytes).unwrap();
+    for name in &[
+        ".debug_info",
+        ".debug_abbrev",
+        ".debug_line",
+        ".debug_frame",
+    ] {
+        assert!(file.section_by_name(name).is_some(), "missing {}", name);
+    }
+    // The code addresses are relocated against the symbol of the function.
+    assert!(file
+        .section_by_name(".debug_info")
+        .unwrap()
+        .relocations()
+        .next()
+        .is_some());
+
+    // The relocation addends are also written to the sections, so without applying the
+    // relocations the addresses are relative to the start of the function.
+    let dwarf = gimli::Dwarf::load(|id| -> Result<_, gimli::Error> {
+        let data = match file.section_by_name(id.name()) {
+            Some(section) => section.data().unwrap(),
+            None => &[],
+        };
+        Ok(gimli::EndianSlice::new(data, gimli::LittleEndian))
+    })
+    .unwrap();
+    let (func, size) = function_range(&dwarf, "add");
+    assert_eq!(func, 0);
+
+    // The prologue and the copy of `a` into the result register have no source location, so they
+    // are attributed to the declaration of the function, followed by the `iadd` and the `return`.
+    assert_eq!(line_rows(&dwarf), [(0, 1), (7, 2), (10, 3)]);
+
+    // The location lists follow the value label ranges of the compiled function, and both
+    // parameters start out in their argument registers.
+    let reg = |reg: gimli::Register| vec![gimli::DW_OP_reg0.0 + reg.0 as u8];
+    for (name, label, entry_reg) in &[
+        ("a", ValueLabel::new(0), gimli::X86_64::RDI),
+        ("b", ValueLabel::new(1), gimli::X86_64::RSI),
+    ] {
+        let locations = variable_locations(&dwarf, name);
+        let ranges = locations
+            .iter()
+            .map(|(range, _)| (range.begin, range.end))
+            .collect::<Vec<_>>();
+        let expected = value_labels_ranges[label]
+            .iter()
+            .map(|range| (u64::from(range.start), u64::from(range.end).min(size)))
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, expected, "locations of {}", name);
+        assert_eq!(
+            locations[0].1,
+            reg(*entry_reg),
+            "entry location of {}",
+            name
+        );
+    }
+}
+
+type Reader<'a> = gimli::EndianSlice<'a, gimli::LittleEndian>;
+
+/// Returns the entry of the compilation unit with the given tag and name.
+fn find_entry<'a>(
+    dwarf: &gimli::Dwarf<Reader<'a>>,
+    unit: &gimli::Unit<Reader<'a>>,
+    tag: gimli::DwTag,
+    name: &str,
+) -> gimli::UnitOffset {
+    let mut entries = unit.entries();
+    while let Some((_, entry)) = entries.next_dfs().unwrap() {
+        if entry.tag() != tag {
+            continue;
+        }
+        let attr = entry.attr_value(gimli::DW_AT_name).unwrap().unwrap();
+        if dwarf.attr_string(unit, attr).unwrap().slice() == name.as_bytes() {
+            return entry.offset();
+        }
+    }
+    panic!("no entry for {}", name);
+}
+
+fn only_unit<'a>(dwarf: &gimli::Dwarf<Reader<'a>>) -> gimli::Unit<Reader<'a>> {
+    let mut units = dwarf.units();
+    let header = units.next().unwrap().unwrap();
+    assert!(units.next().unwrap().is_none());
+    dwarf.unit(header).unwrap()
+}
+
+/// Returns the start address and the size of the function with the given name.
+fn function_range(dwarf: &gimli::Dwarf<Reader>, name: &str) -> (u64, u64) {
+    let unit = only_unit(dwarf);
+    let offset = find_entry(dwarf, &unit, gimli::DW_TAG_subprogram, name);
+    let entry = unit.entry(offset).unwrap();
+    let low_pc = match entry.attr_value(gimli::DW_AT_low_pc).unwrap() {
+        Some(gimli::AttributeValue::Addr(addr)) => addr,
+        value => panic!("unexpected low_pc {:?}", value),
+    };
+    let size = entry
+        .attr_value(gimli::DW_AT_high_pc)
+        .unwrap()
+        .and_then(|value| value.udata_value())
+        .unwrap();
+    (low_pc, size)
+}
+
+/// Returns the address and line of each row of the line program, without the end of the sequence.
+fn line_rows(dwarf: &gimli::Dwarf<Reader>) -> Vec<(u64, u64)> {
+    let unit = only_unit(dwarf);
+    let mut rows = unit.line_program.unwrap().rows();
+    let mut result = vec![];
+    while let Some((_, row)) = rows.next_row().unwrap() {
+        if !row.end_sequence() {
+            result.push((row.address(), row.line().map_or(0, |line| line.get())));
+        }
+    }
+    result
+}
+
+/// Returns the ranges of the location list of the variable with the given name, with the bytes of
+/// the location expression for each.
+fn variable_locations(dwarf: &gimli::Dwarf<Reader>, name: &str) -> Vec<(gimli::Range, Vec<u8>)> {
+    let unit = only_unit(dwarf);
+    let offset = find_entry(dwarf, &unit, gimli::DW_TAG_formal_parameter, name);
+    let entry = unit.entry(offset).unwrap();
+    let offset = match entry.attr_value(gimli::DW_AT_location).unwrap() {
+        Some(gimli::AttributeValue::LocationListsRef(offset)) => offset,
+        value => panic!("unexpected location {:?}", value),
+    };
+    let mut locations = dwarf.locations(&unit, offset).unwrap();
+    let mut result = vec![];
+    while let Some(location) = locations.next().unwrap() {
+        result.push((location.range, location.data.0.slice().to_vec()));
+    }
+    result
+}
@@ -1048,7 +1048,10 @@ impl<'a> Parser<'a> {
             specified_target = true;
 
             // Construct a trait object with the aggregate settings.
-            targets.push(isa_builder.finish(settings::Flags::new(flag_builder.clone())));
+            match isa_builder.finish(settings::Flags::new(flag_builder.clone())) {
+                Ok(isa) => targets.push(isa),
+                Err(e) => return err!(loc, "invalid settings for target '{}': {}", targ, e),
+            }
         }
 
         if !specified_target {
@@ -1117,7 +1120,17 @@ impl<'a> Parser<'a> {
                     isaspec::parse_options(words, &mut isa_builder, self.loc)?;
 
                     // Construct a trait object with the aggregate settings.
-                    targets.push(isa_builder.finish(settings::Flags::new(flag_builder.clone())));
+                    match isa_builder.finish(settings::Flags::new(flag_builder.clone())) {
+                        Ok(isa) => targets.push(isa),
+                        Err(e) => {
+                            return err!(
+                                loc,
+                                "invalid settings for target '{}': {}",
+                                target_name,
+                                e
+                            )
+                        }
+                    }
                 }
                 _ => break,
             }
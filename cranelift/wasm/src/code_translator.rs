@@ -78,7 +78,7 @@ use crate::translation_utils::{
     block_with_params, blocktype_params_results, f32_translation, f64_translation,
 };
 use crate::wasm_unsupported;
-use crate::{FuncIndex, GlobalIndex, MemoryIndex, TableIndex, TypeIndex, WasmResult};
+use crate::{FuncIndex, GlobalIndex, MemoryIndex, TableIndex, TagIndex, TypeIndex, WasmResult};
 use core::convert::TryInto;
 use core::{i32, u32};
 use cranelift_codegen::ir::condcodes::{FloatCC, IntCC};
@@ -93,7 +93,7 @@ use smallvec::SmallVec;
 use std::cmp;
 use std::convert::TryFrom;
 use std::vec::Vec;
-use wasmparser::{FuncValidator, MemoryImmediate, Operator, WasmModuleResources};
+use wasmparser::{FuncValidator, MemoryImmediate, Operator, WasmFuncType, WasmModuleResources};
 
 // Clippy warns about "align: _" but its important to document that the flags field is ignored
 #[cfg_attr(
@@ -372,7 +372,7 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
                 _ => unreachable!(),
             }
         }
-        Operator::End => {
+        Operator::End | Operator::Delegate { .. } => {
             let frame = state.control_stack.pop().unwrap();
             let next_block = frame.following_code();
 
@@ -388,6 +388,8 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
                 // below.
             }
 
+            translate_try_exit(op, &frame, builder, state, environ)?;
+
             builder.switch_to_block(next_block);
             builder.seal_block(next_block);
 
@@ -550,17 +552,56 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
             state.popn(return_count);
             state.reachable = false;
         }
-        /********************************** Exception handing **********************************/
-        Operator::Try { .. }
-        | Operator::Catch { .. }
-        | Operator::Throw { .. }
-        | Operator::Rethrow { .. }
-        | Operator::Delegate { .. }
-        | Operator::CatchAll => {
-            return Err(wasm_unsupported!(
-                "proposed exception handling operator {:?}",
-                op
-            ));
+        /********************************** Exception handing **********************************
+         * A `try` is translated like a `block`, except that the calls inside of it send
+         * exceptions to its handler. Exceptions are raised by the environment, which unwinds the
+         * stack to the innermost call that has a handler and resumes it with the exception
+         * pending, so each call inside a `try` is followed by a check for a pending exception.
+         * The handler tests the exception against each `catch` clause in turn and exceptions
+         * which no clause handles continue on to the next enclosing `try`, or are unwound out of
+         * this function. The `end` and `delegate` which finish a `try` are translated along with
+         * `end`.
+         ***********************************************************************************/
+        Operator::Try { ty } => {
+            let (params, results) = blocktype_params_results(validator, *ty)?;
+            let next = block_with_params(builder, results.clone(), environ)?;
+            state.push_try(next, params.len(), results.len());
+        }
+        Operator::Catch { index } => {
+            translate_catch_clause(Some(TagIndex::from_u32(*index)), builder, state, environ)?;
+        }
+        Operator::CatchAll => {
+            translate_catch_clause(None, builder, state, environ)?;
+        }
+        Operator::Throw { index } => {
+            let num_args = validator
+                .resources()
+                .tag_at(*index)
+                .expect("should be valid")
+                .len_inputs();
+            environ.translate_throw(
+                builder.cursor(),
+                TagIndex::from_u32(*index),
+                state.peekn(num_args),
+            )?;
+            state.popn(num_args);
+            translate_call_exception_check(builder, state, environ)?;
+            builder.ins().trap(ir::TrapCode::UnreachableCodeReached);
+            state.reachable = false;
+        }
+        Operator::Rethrow { relative_depth } => {
+            let i = state.control_stack.len() - 1 - (*relative_depth as usize);
+            let exception = match state.control_stack[i] {
+                ControlStackFrame::Try {
+                    caught: Some(exception),
+                    ..
+                } => exception,
+                _ => unreachable!("`rethrow` must target a reachable `catch` clause"),
+            };
+            environ.translate_rethrow(builder.cursor(), exception)?;
+            translate_call_exception_check(builder, state, environ)?;
+            builder.ins().trap(ir::TrapCode::UnreachableCodeReached);
+            state.reachable = false;
         }
         /************************************ Calls ****************************************
          * The call instructions pop off their arguments from the stack and append their
@@ -595,6 +636,7 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
             );
             state.popn(num_args);
             state.pushn(inst_results);
+            translate_call_exception_check(builder, state, environ)?;
         }
         Operator::CallIndirect { index, table_index } => {
             // `index` is the index of the function's signature and `table_index` is the index of
@@ -628,6 +670,7 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
             );
             state.popn(num_args);
             state.pushn(inst_results);
+            translate_call_exception_check(builder, state, environ)?;
         }
         /******************************* Memory management ***********************************
          * Memory management is handled by environment. It is usually translated into calls to
@@ -2010,8 +2053,56 @@ pub fn translate_operator<FE: FuncEnvironment + ?Sized>(
             let b_high = builder.ins().uwiden_high(b);
             state.push1(builder.ins().imul(a_high, b_high));
         }
-        Operator::ReturnCall { .. } | Operator::ReturnCallIndirect { .. } => {
-            return Err(wasm_unsupported!("proposed tail-call operator {:?}", op));
+        /******************************* Tail calls ****************************************
+         * The tail call instructions pop off their arguments and transfer control to the
+         * callee, which returns directly to our caller. Like `return`, they make the rest of
+         * the block unreachable.
+         ************************************************************************************/
+        Operator::ReturnCall { function_index } => {
+            let (fref, num_args) = state.get_direct_func(builder.func, *function_index, environ)?;
+
+            // Bitcast any vector arguments to their default type, I8X16, before calling.
+            let callee_signature =
+                &builder.func.dfg.signatures[builder.func.dfg.ext_funcs[fref].signature];
+            let args = state.peekn_mut(num_args);
+            let types = wasm_param_types(&callee_signature.params, |i| {
+                environ.is_wasm_parameter(&callee_signature, i)
+            });
+            bitcast_arguments(args, &types, builder);
+
+            environ.translate_return_call(
+                builder.cursor(),
+                FuncIndex::from_u32(*function_index),
+                fref,
+                args,
+            )?;
+            state.popn(num_args);
+            state.reachable = false;
+        }
+        Operator::ReturnCallIndirect { index, table_index } => {
+            let (sigref, num_args) = state.get_indirect_sig(builder.func, *index, environ)?;
+            let table = state.get_or_create_table(builder.func, *table_index, environ)?;
+            let callee = state.pop1();
+
+            // Bitcast any vector arguments to their default type, I8X16, before calling.
+            let callee_signature = &builder.func.dfg.signatures[sigref];
+            let args = state.peekn_mut(num_args);
+            let types = wasm_param_types(&callee_signature.params, |i| {
+                environ.is_wasm_parameter(&callee_signature, i)
+            });
+            bitcast_arguments(args, &types, builder);
+
+            environ.translate_return_call_indirect(
+                builder.cursor(),
+                TableIndex::from_u32(*table_index),
+                table,
+                TypeIndex::from_u32(*index),
+                sigref,
+                callee,
+                state.peekn(num_args),
+            )?;
+            state.popn(num_args);
+            state.reachable = false;
         }
     };
     Ok(())
@@ -2047,6 +2138,17 @@ fn translate_unreachable_operator<FE: FuncEnvironment + ?Sized>(
         Operator::Loop { ty: _ } | Operator::Block { ty: _ } => {
             state.push_block(ir::Block::reserved_value(), 0, 0);
         }
+        Operator::Try { ty: _ } => {
+            // Nothing in an unreachable `try` can throw, so none of its
+            // clauses are reachable either.
+            state.push_try(ir::Block::reserved_value(), 0, 0);
+        }
+        Operator::Catch { index } => {
+            translate_catch_clause(Some(TagIndex::from_u32(index)), builder, state, environ)?;
+        }
+        Operator::CatchAll => {
+            translate_catch_clause(None, builder, state, environ)?;
+        }
         Operator::Else => {
             let i = state.control_stack.len() - 1;
             match state.control_stack[i] {
@@ -2095,13 +2197,13 @@ fn translate_unreachable_operator<FE: FuncEnvironment + ?Sized>(
                 _ => unreachable!(),
             }
         }
-        Operator::End => {
-            let stack = &mut state.stack;
-            let control_stack = &mut state.control_stack;
-            let frame = control_stack.pop().unwrap();
+        Operator::End | Operator::Delegate { .. } => {
+            let frame = state.control_stack.pop().unwrap();
 
             // Pop unused parameters from stack.
-            frame.truncate_value_stack_to_original_size(stack);
+            frame.truncate_value_stack_to_original_size(&mut state.stack);
+
+            translate_try_exit(op, &frame, builder, state, environ)?;
 
             let reachable_anyway = match frame {
                 // If it is a loop we also have to seal the body loop block
@@ -2138,7 +2240,9 @@ fn translate_unreachable_operator<FE: FuncEnvironment + ?Sized>(
 
                 // And add the return values of the block but only if the next block is reachable
                 // (which corresponds to testing if the stack depth is 1)
-                stack.extend_from_slice(builder.block_params(frame.following_code()));
+                state
+                    .stack
+                    .extend_from_slice(builder.block_params(frame.following_code()));
                 state.reachable = true;
             }
         }
@@ -2671,6 +2775,152 @@ fn translate_br_if_args(
     (br_destination, inputs)
 }
 
+/// Returns the landing pad of the innermost `try` whose body encloses the innermost `frames`
+/// control frames, which is where exceptions raised within them are handled, if there is one.
+fn exception_handler(
+    frames: usize,
+    builder: &mut FunctionBuilder,
+    state: &mut FuncTranslationState,
+) -> Option<ir::Block> {
+    for frame in state.control_stack[..frames].iter_mut().rev() {
+        if let ControlStackFrame::Try {
+            in_body: true,
+            handler: ref mut landing_pad,
+            ..
+        } = *frame
+        {
+            return Some(*landing_pad.get_or_insert_with(|| builder.create_block()));
+        }
+    }
+    None
+}
+
+/// Checks whether the call just translated completed by raising an exception, dispatching it
+/// to its handler if so.
+///
+/// Exceptions raised by calls outside of any `try` never return to this function, so there is
+/// nothing to check for those.
+fn translate_call_exception_check<FE: FuncEnvironment + ?Sized>(
+    builder: &mut FunctionBuilder,
+    state: &mut FuncTranslationState,
+    environ: &mut FE,
+) -> WasmResult<()> {
+    let handler = match exception_handler(state.control_stack.len(), builder, state) {
+        Some(handler) => handler,
+        None => return Ok(()),
+    };
+    let pending = environ.translate_exception_pending(builder)?;
+    canonicalise_then_brnz(builder, pending, handler, &[]);
+    let next_block = builder.create_block();
+    canonicalise_then_jump(builder, next_block, &[]);
+    builder.seal_block(next_block); // The only predecessor is the current block.
+    builder.switch_to_block(next_block);
+    Ok(())
+}
+
+/// Ends the body of the innermost `try`, or its current clause, and starts translating its
+/// `catch` clause for `tag`, or its `catch_all` clause if `tag` is `None`.
+fn translate_catch_clause<FE: FuncEnvironment + ?Sized>(
+    tag: Option<TagIndex>,
+    builder: &mut FunctionBuilder,
+    state: &mut FuncTranslationState,
+    environ: &mut FE,
+) -> WasmResult<()> {
+    let i = state.control_stack.len() - 1;
+    if state.reachable {
+        let frame = &mut state.control_stack[i];
+        frame.set_branched_to_exit();
+        let return_count = frame.num_return_values();
+        let destination = frame.following_code();
+        canonicalise_then_jump(builder, destination, state.peekn(return_count));
+    }
+    state.control_stack[i].truncate_value_stack_to_original_size(&mut state.stack);
+
+    let handler = match state.control_stack[i] {
+        ControlStackFrame::Try {
+            ref mut handler,
+            ref mut in_body,
+            ref mut caught,
+            ..
+        } => {
+            *in_body = false;
+            *caught = None;
+            handler.take()
+        }
+        _ => unreachable!(),
+    };
+
+    // If nothing can throw to this clause then it is unreachable.
+    let handler = match handler {
+        Some(handler) => handler,
+        None => {
+            state.reachable = false;
+            return Ok(());
+        }
+    };
+    builder.switch_to_block(handler);
+    builder.seal_block(handler); // All throwing code before this clause has been translated.
+
+    // Exceptions with a different tag move on to the next clause.
+    if let Some(tag) = tag {
+        let matches = environ.translate_exception_matches(builder.cursor(), tag)?;
+        let next_clause = builder.create_block();
+        canonicalise_then_brz(builder, matches, next_clause, &[]);
+        let clause_block = builder.create_block();
+        canonicalise_then_jump(builder, clause_block, &[]);
+        builder.seal_block(clause_block); // The only predecessor is the current block.
+        builder.switch_to_block(clause_block);
+        if let ControlStackFrame::Try { handler, .. } = &mut state.control_stack[i] {
+            *handler = Some(next_clause);
+        }
+    }
+
+    let (exception, values) = environ.translate_catch(builder.cursor(), tag)?;
+    if let ControlStackFrame::Try { caught, .. } = &mut state.control_stack[i] {
+        *caught = Some(exception);
+    }
+    state.pushn(&values);
+    state.reachable = true;
+    Ok(())
+}
+
+/// Translates the exceptional exit of a `try` frame which has just been popped by an `end` or a
+/// `delegate`: exceptions that none of its clauses handled are sent on to the enclosing handler,
+/// or for a `delegate` to the handler of the block that it names.
+fn translate_try_exit<FE: FuncEnvironment + ?Sized>(
+    op: &Operator,
+    frame: &ControlStackFrame,
+    builder: &mut FunctionBuilder,
+    state: &mut FuncTranslationState,
+    environ: &mut FE,
+) -> WasmResult<()> {
+    let handler = match *frame {
+        ControlStackFrame::Try {
+            handler: Some(handler),
+            ..
+        } => handler,
+        _ => return Ok(()),
+    };
+    let frames = match *op {
+        Operator::Delegate { relative_depth } => {
+            state.control_stack.len() - relative_depth as usize
+        }
+        _ => state.control_stack.len(),
+    };
+    builder.switch_to_block(handler);
+    builder.seal_block(handler); // All throwing code inside the `try` has been translated.
+    match exception_handler(frames, builder, state) {
+        Some(handler) => {
+            canonicalise_then_jump(builder, handler, &[]);
+        }
+        None => {
+            environ.translate_resume_unwind(builder.cursor())?;
+            builder.ins().trap(ir::TrapCode::UnreachableCodeReached);
+        }
+    }
+    Ok(())
+}
+
 /// Determine the returned value type of a WebAssembly operator
 fn type_of(operator: &Operator) -> Type {
     match operator {
@@ -245,6 +245,131 @@ pub trait FuncEnvironment: TargetEnvironment {
         Ok(pos.ins().call(callee, call_args))
     }
 
+    /// Translate a `return_call` WebAssembly instruction at `pos`.
+    ///
+    /// Insert instructions at `pos` for a direct tail call to the function `callee_index`. The
+    /// inserted code must end in a terminator, since the callee returns directly to our caller.
+    ///
+    /// The function reference `callee` was previously created by `make_direct_func()`.
+    fn translate_return_call(
+        &mut self,
+        mut pos: FuncCursor,
+        _callee_index: FuncIndex,
+        callee: ir::FuncRef,
+        call_args: &[ir::Value],
+    ) -> WasmResult<()> {
+        pos.ins().return_call(callee, call_args);
+        Ok(())
+    }
+
+    /// Translate a `return_call_indirect` WebAssembly instruction at `pos`.
+    ///
+    /// Insert instructions at `pos` for an indirect tail call to the function `callee` in the
+    /// table `table_index` with WebAssembly signature `sig_index`, following the same rules as
+    /// `translate_call_indirect()`. The inserted code must end in a terminator.
+    #[cfg_attr(feature = "cargo-clippy", allow(clippy::too_many_arguments))]
+    fn translate_return_call_indirect(
+        &mut self,
+        _pos: FuncCursor,
+        _table_index: TableIndex,
+        _table: ir::Table,
+        _sig_index: TypeIndex,
+        _sig_ref: ir::SigRef,
+        _callee: ir::Value,
+        _call_args: &[ir::Value],
+    ) -> WasmResult<()> {
+        Err(WasmError::Unsupported(
+            "`return_call_indirect` is not supported by this environment".to_string(),
+        ))
+    }
+
+    /// Translate a check, performed after a call inside the body of a `try`
+    /// returns, of whether the call raised an exception.
+    ///
+    /// Exceptions are raised by unwinding the stack, and the environment must
+    /// stop unwinding at the calls for which this is called and resume them
+    /// with the exception pending, since the translator dispatches it to the
+    /// `try` block's handler from here. Calls outside of any `try` block are
+    /// never resumed like this, so exceptions they raise unwind this function.
+    ///
+    /// Returns a value which is nonzero if an exception is pending.
+    fn translate_exception_pending(
+        &mut self,
+        _builder: &mut FunctionBuilder,
+    ) -> WasmResult<ir::Value> {
+        Err(WasmError::Unsupported(
+            "exception handling is not supported by this environment".to_string(),
+        ))
+    }
+
+    /// Translate a `throw` WebAssembly instruction, raising an exception with
+    /// tag `tag_index` which carries the values `args`.
+    ///
+    /// The inserted code is a call which unwinds the stack to the exception's
+    /// handler, as described for `translate_exception_pending`, so it only
+    /// returns if the handler is a `try` block in this function.
+    fn translate_throw(
+        &mut self,
+        _pos: FuncCursor,
+        _tag_index: TagIndex,
+        _args: &[ir::Value],
+    ) -> WasmResult<()> {
+        Err(WasmError::Unsupported(
+            "exception handling is not supported by this environment".to_string(),
+        ))
+    }
+
+    /// Translate a `rethrow` WebAssembly instruction, raising `exception`
+    /// again, which was previously returned from `translate_catch`.
+    ///
+    /// Like `translate_throw` this only returns if the exception's handler is
+    /// in this function.
+    fn translate_rethrow(&mut self, _pos: FuncCursor, _exception: ir::Value) -> WasmResult<()> {
+        Err(WasmError::Unsupported(
+            "exception handling is not supported by this environment".to_string(),
+        ))
+    }
+
+    /// Translate continuing to unwind the pending exception, which no handler
+    /// in this function caught, out of this function.
+    ///
+    /// The inserted code must not return.
+    fn translate_resume_unwind(&mut self, _pos: FuncCursor) -> WasmResult<()> {
+        Err(WasmError::Unsupported(
+            "exception handling is not supported by this environment".to_string(),
+        ))
+    }
+
+    /// Translate a test of whether the pending exception has tag `tag_index`.
+    ///
+    /// Returns a nonzero value if it does.
+    fn translate_exception_matches(
+        &mut self,
+        _pos: FuncCursor,
+        _tag_index: TagIndex,
+    ) -> WasmResult<ir::Value> {
+        Err(WasmError::Unsupported(
+            "exception handling is not supported by this environment".to_string(),
+        ))
+    }
+
+    /// Translate catching the pending exception, clearing it.
+    ///
+    /// For a `catch` clause `tag_index` is the tag that the exception is
+    /// known to have and the values it carries are returned along with the
+    /// exception itself. For a `catch_all` clause `tag_index` is `None` and no
+    /// values are returned. The returned exception is a reference value which
+    /// may later be passed to `translate_rethrow`.
+    fn translate_catch(
+        &mut self,
+        _pos: FuncCursor,
+        _tag_index: Option<TagIndex>,
+    ) -> WasmResult<(ir::Value, Vec<ir::Value>)> {
+        Err(WasmError::Unsupported(
+            "exception handling is not supported by this environment".to_string(),
+        ))
+    }
+
     /// Translate a `memory.grow` WebAssembly instruction.
     ///
     /// The `index` provided identifies the linear memory to grow, and `heap` is the heap reference
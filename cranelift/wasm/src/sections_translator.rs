@@ -44,7 +44,9 @@ fn entity_type(
             EntityType::Instance(environ.type_to_instance_type(TypeIndex::from_u32(sig))?)
         }
         ImportSectionEntryType::Memory(ty) => EntityType::Memory(memory(ty)),
-        ImportSectionEntryType::Tag(t) => EntityType::Tag(tag(t)),
+        ImportSectionEntryType::Tag(t) => {
+            EntityType::Tag(environ.type_to_signature(TypeIndex::from_u32(t.type_index))?)
+        }
         ImportSectionEntryType::Global(ty) => EntityType::Global(global(ty, GlobalInit::Import)?),
         ImportSectionEntryType::Table(ty) => EntityType::Table(table(ty)?),
     })
@@ -522,7 +524,7 @@ pub fn parse_instance_section<'data>(
                     ExternalKind::Instance => {
                         EntityIndex::Instance(InstanceIndex::from_u32(arg.index))
                     }
-                    ExternalKind::Tag => unimplemented!(),
+                    ExternalKind::Tag => EntityIndex::Tag(TagIndex::from_u32(arg.index)),
 
                     // this won't pass validation
                     ExternalKind::Type => unreachable!(),
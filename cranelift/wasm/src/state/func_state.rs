@@ -39,8 +39,8 @@ pub enum ElseData {
     },
 }
 
-/// A control stack frame can be an `if`, a `block` or a `loop`, each one having the following
-/// fields:
+/// A control stack frame can be an `if`, a `block`, a `loop` or a `try`, each one having the
+/// following fields:
 ///
 /// - `destination`: reference to the `Block` that will hold the code after the control block;
 /// - `num_return_values`: number of values returned by the control block;
@@ -48,7 +48,8 @@ pub enum ElseData {
 ///
 /// Moreover, the `if` frame has the `branch_inst` field that points to the `brz` instruction
 /// separating the `true` and `false` branch. The `loop` frame has a `header` field that references
-/// the `Block` that contains the beginning of the body of the loop.
+/// the `Block` that contains the beginning of the body of the loop. The `try` frame keeps track
+/// of where exceptions thrown inside of it are handled.
 #[derive(Debug)]
 pub enum ControlStackFrame {
     If {
@@ -85,6 +86,24 @@ pub enum ControlStackFrame {
         num_return_values: usize,
         original_stack_size: usize,
     },
+    Try {
+        destination: Block,
+        num_param_values: usize,
+        num_return_values: usize,
+        original_stack_size: usize,
+        exit_is_branched_to: bool,
+        /// Where exceptions reaching this `try` are dispatched to.
+        ///
+        /// While translating the body this is the landing pad that the first
+        /// `catch` clause starts with, and while translating the clauses it is
+        /// the start of the next clause to test. This is `None` until an
+        /// exception can actually get here.
+        handler: Option<Block>,
+        /// Are we still translating the body, rather than one of the clauses?
+        in_body: bool,
+        /// The exception caught by the current clause, used by `rethrow`.
+        caught: Option<Value>,
+    },
 }
 
 /// Helper methods for the control stack objects.
@@ -99,6 +118,9 @@ impl ControlStackFrame {
             }
             | Self::Loop {
                 num_return_values, ..
+            }
+            | Self::Try {
+                num_return_values, ..
             } => num_return_values,
         }
     }
@@ -112,6 +134,9 @@ impl ControlStackFrame {
             }
             | Self::Loop {
                 num_param_values, ..
+            }
+            | Self::Try {
+                num_param_values, ..
             } => num_param_values,
         }
     }
@@ -119,12 +144,15 @@ impl ControlStackFrame {
         match *self {
             Self::If { destination, .. }
             | Self::Block { destination, .. }
-            | Self::Loop { destination, .. } => destination,
+            | Self::Loop { destination, .. }
+            | Self::Try { destination, .. } => destination,
         }
     }
     pub fn br_destination(&self) -> Block {
         match *self {
-            Self::If { destination, .. } | Self::Block { destination, .. } => destination,
+            Self::If { destination, .. }
+            | Self::Block { destination, .. }
+            | Self::Try { destination, .. } => destination,
             Self::Loop { header, .. } => header,
         }
     }
@@ -143,12 +171,16 @@ impl ControlStackFrame {
             | Self::Loop {
                 original_stack_size,
                 ..
+            }
+            | Self::Try {
+                original_stack_size,
+                ..
             } => original_stack_size,
         }
     }
     pub fn is_loop(&self) -> bool {
         match *self {
-            Self::If { .. } | Self::Block { .. } => false,
+            Self::If { .. } | Self::Block { .. } | Self::Try { .. } => false,
             Self::Loop { .. } => true,
         }
     }
@@ -162,6 +194,10 @@ impl ControlStackFrame {
             | Self::Block {
                 exit_is_branched_to,
                 ..
+            }
+            | Self::Try {
+                exit_is_branched_to,
+                ..
             } => exit_is_branched_to,
             Self::Loop { .. } => false,
         }
@@ -176,6 +212,10 @@ impl ControlStackFrame {
             | Self::Block {
                 ref mut exit_is_branched_to,
                 ..
+            }
+            | Self::Try {
+                ref mut exit_is_branched_to,
+                ..
             } => *exit_is_branched_to = true,
             Self::Loop { .. } => {}
         }
@@ -251,6 +291,12 @@ impl FuncTranslationState {
     pub fn reachable(&self) -> bool {
         self.reachable
     }
+
+    /// The values currently on the wasm operand stack, bottom first.
+    #[inline]
+    pub fn stack(&self) -> &[Value] {
+        &self.stack
+    }
 }
 
 impl FuncTranslationState {
@@ -438,6 +484,26 @@ impl FuncTranslationState {
             blocktype,
         });
     }
+
+    /// Push a try on the control stack.
+    pub(crate) fn push_try(
+        &mut self,
+        following_code: Block,
+        num_param_types: usize,
+        num_result_types: usize,
+    ) {
+        debug_assert!(num_param_types <= self.stack.len());
+        self.control_stack.push(ControlStackFrame::Try {
+            destination: following_code,
+            original_stack_size: self.stack.len() - num_param_types,
+            num_param_values: num_param_types,
+            num_return_values: num_result_types,
+            exit_is_branched_to: false,
+            handler: None,
+            in_body: true,
+            caught: None,
+        });
+    }
 }
 
 /// Methods for handling entity references.
@@ -46,6 +46,68 @@ WASI_DECLARE_OWN(config)
  */
 WASI_API_EXTERN own wasi_config_t* wasi_config_new();
 
+/**
+ * \typedef wasi_output_buffer_t
+ * \brief Convenience alias for #wasi_output_buffer_t
+ *
+ * \struct wasi_output_buffer_t
+ * \brief A growable in-memory buffer which can capture a WASI program's
+ * stdout or stderr.
+ *
+ * Buffers are attached to a configuration with
+ * #wasi_config_set_stdout_buffer or #wasi_config_set_stderr_buffer, and the
+ * output written to them can be read back with #wasi_output_buffer_contents
+ * at any time, for example after a call into the WASI program has returned.
+ * The buffer shares its contents with the configuration it's attached to, so
+ * it's valid to delete the buffer before or after the configuration.
+ *
+ * \fn void wasi_output_buffer_delete(wasi_output_buffer_t *);
+ * \brief Deletes an output buffer.
+ */
+WASI_DECLARE_OWN(output_buffer)
+
+/**
+ * \brief Creates a new empty output buffer.
+ *
+ * The caller is expected to deallocate the returned buffer.
+ */
+WASI_API_EXTERN own wasi_output_buffer_t* wasi_output_buffer_new();
+
+/**
+ * \brief Copies the output captured so far by `buffer` into `contents`.
+ *
+ * The `contents` vector is initialized by this function and owned by the
+ * caller afterwards.
+ */
+WASI_API_EXTERN void wasi_output_buffer_contents(const wasi_output_buffer_t* buffer, own wasm_byte_vec_t* contents);
+
+/**
+ * \brief Discards all output captured so far by `buffer`.
+ */
+WASI_API_EXTERN void wasi_output_buffer_clear(const wasi_output_buffer_t* buffer);
+
+/**
+ * \brief Callback used to read data for a WASI program's stdin.
+ *
+ * The callback is given the `env` pointer it was registered with and a buffer
+ * of `len` bytes to fill in. It returns the number of bytes read, which must
+ * be at most `len`, where 0 indicates end-of-file, or a negative value to
+ * indicate an error. Returning more than `len` is reported to the WASI program
+ * as an error.
+ */
+typedef ptrdiff_t (*wasi_read_callback_t)(void *env, uint8_t *buf, size_t len);
+
+/**
+ * \brief Callback used to receive data written by a WASI program to stdout or
+ * stderr.
+ *
+ * The callback is given the `env` pointer it was registered with and the `len`
+ * bytes which were written. It returns how many bytes were consumed, which
+ * must be at most `len`, or a negative value to indicate an error. Returning
+ * more than `len` is reported to the WASI program as an error.
+ */
+typedef ptrdiff_t (*wasi_write_callback_t)(void *env, const uint8_t *buf, size_t len);
+
 /**
  * \brief Sets the argv list for this configuration object.
  *
@@ -100,6 +162,23 @@ WASI_API_EXTERN bool wasi_config_set_stdin_file(wasi_config_t* config, const cha
  */
 WASI_API_EXTERN void wasi_config_inherit_stdin(wasi_config_t* config);
 
+/**
+ * \brief Configures standard input to be read from the specified bytes.
+ *
+ * The WASI program will read the contents of `binary` from stdin followed by
+ * end-of-file. This function takes ownership of the `binary` vector.
+ */
+WASI_API_EXTERN void wasi_config_set_stdin_bytes(wasi_config_t* config, own wasm_byte_vec_t* binary);
+
+/**
+ * \brief Configures standard input to be read by invoking `callback`.
+ *
+ * The `env` pointer is passed to each invocation of `callback`, and the
+ * optional `finalizer` is invoked with `env` once the callback is no longer
+ * needed.
+ */
+WASI_API_EXTERN void wasi_config_set_stdin_callback(wasi_config_t* config, wasi_read_callback_t callback, void *env, void (*finalizer)(void*));
+
 /**
  * \brief Configures standard output to be written to the specified file.
  *
@@ -117,6 +196,23 @@ WASI_API_EXTERN bool wasi_config_set_stdout_file(wasi_config_t* config, const ch
  */
 WASI_API_EXTERN void wasi_config_inherit_stdout(wasi_config_t* config);
 
+/**
+ * \brief Configures standard output to be captured into `buffer`.
+ *
+ * The `buffer` is not consumed by this function and can be used to read the
+ * output back with #wasi_output_buffer_contents.
+ */
+WASI_API_EXTERN void wasi_config_set_stdout_buffer(wasi_config_t* config, const wasi_output_buffer_t* buffer);
+
+/**
+ * \brief Configures standard output to be written by invoking `callback`.
+ *
+ * The `env` pointer is passed to each invocation of `callback`, and the
+ * optional `finalizer` is invoked with `env` once the callback is no longer
+ * needed.
+ */
+WASI_API_EXTERN void wasi_config_set_stdout_callback(wasi_config_t* config, wasi_write_callback_t callback, void *env, void (*finalizer)(void*));
+
 /**
  * \brief Configures standard output to be written to the specified file.
  *
@@ -134,6 +230,23 @@ WASI_API_EXTERN bool wasi_config_set_stderr_file(wasi_config_t* config, const ch
  */
 WASI_API_EXTERN void wasi_config_inherit_stderr(wasi_config_t* config);
 
+/**
+ * \brief Configures standard error to be captured into `buffer`.
+ *
+ * The `buffer` is not consumed by this function and can be used to read the
+ * output back with #wasi_output_buffer_contents.
+ */
+WASI_API_EXTERN void wasi_config_set_stderr_buffer(wasi_config_t* config, const wasi_output_buffer_t* buffer);
+
+/**
+ * \brief Configures standard error to be written by invoking `callback`.
+ *
+ * The `env` pointer is passed to each invocation of `callback`, and the
+ * optional `finalizer` is invoked with `env` once the callback is no longer
+ * needed.
+ */
+WASI_API_EXTERN void wasi_config_set_stderr_callback(wasi_config_t* config, wasi_write_callback_t callback, void *env, void (*finalizer)(void*));
+
 /**
  * \brief Configures a "preopened directory" to be available to WASI APIs.
  *
@@ -165,7 +165,9 @@
 #define WASMTIME_API_H
 
 #include <wasi.h>
+#include <wasmtime/async.h>
 #include <wasmtime/config.h>
+#include <wasmtime/engine.h>
 #include <wasmtime/error.h>
 #include <wasmtime/extern.h>
 #include <wasmtime/func.h>
@@ -174,6 +176,7 @@
 #include <wasmtime/linker.h>
 #include <wasmtime/memory.h>
 #include <wasmtime/module.h>
+#include <wasmtime/sharedmemory.h>
 #include <wasmtime/store.h>
 #include <wasmtime/table.h>
 #include <wasmtime/trap.h>
@@ -0,0 +1,277 @@
+/**
+ * \file wasmtime/async.h
+ *
+ * \brief Wasmtime async functionality
+ *
+ * Async functionality in Wasmtime is well documented in the Rust API, see
+ * [`Config::async_support`](https://docs.wasmtime.dev/api/wasmtime/struct.Config.html#method.async_support).
+ * The C API exposes the same functionality, but since C has no native notion
+ * of futures they're represented here as opaque objects which are polled
+ * manually by the embedder.
+ *
+ * Invoking WebAssembly asynchronously executes it on a separate native stack,
+ * a "fiber". Whenever an async host function isn't ready yet, or wasm has been
+ * configured to periodically yield (for example with
+ * #wasmtime_context_out_of_fuel_async_yield), the fiber is suspended and
+ * #wasmtime_call_future_poll returns `false`. Polling the future again resumes
+ * execution where it left off.
+ *
+ * Note that all async functionality requires #wasmtime_config_async_support_set
+ * to be enabled, and stores created with such a config may only invoke
+ * WebAssembly through the async functions in this header.
+ */
+
+#ifndef WASMTIME_ASYNC_H
+#define WASMTIME_ASYNC_H
+
+#include <wasm.h>
+#include <wasmtime/config.h>
+#include <wasmtime/error.h>
+#include <wasmtime/func.h>
+#include <wasmtime/linker.h>
+#include <wasmtime/store.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * \brief Whether or not to enable support for asynchronous functions in
+ * Wasmtime.
+ *
+ * This setting is `false` by default. When enabled, host functions can be
+ * defined with #wasmtime_func_new_async and wasm must be invoked with
+ * #wasmtime_func_call_async and #wasmtime_linker_instantiate_async.
+ *
+ * For more information see the Rust documentation at
+ * https://docs.wasmtime.dev/api/wasmtime/struct.Config.html#method.async_support.
+ */
+WASMTIME_CONFIG_PROP(void, async_support, bool)
+
+/**
+ * \brief Configures the size of the stacks used for asynchronous execution.
+ *
+ * This setting is 2MB by default. It must be at least as large as the value
+ * configured with #wasmtime_config_max_wasm_stack_set, and this function
+ * returns `false` if that's not the case.
+ *
+ * For more information see the Rust documentation at
+ * https://docs.wasmtime.dev/api/wasmtime/struct.Config.html#method.async_stack_size.
+ */
+WASMTIME_CONFIG_PROP(bool, async_stack_size, size_t)
+
+/**
+ * \brief Configures a store to yield execution when it runs out of fuel.
+ *
+ * When WebAssembly running in this store exhausts its fuel then, instead of
+ * trapping, `fuel_to_inject` units of fuel are added and the currently
+ * executing future returns from #wasmtime_call_future_poll as not-ready. This
+ * happens up to `injection_count` times, after which running out of fuel traps
+ * as usual.
+ *
+ * This is useful for cooperatively time-slicing multiple pieces of
+ * WebAssembly. Requires both #wasmtime_config_consume_fuel_set and
+ * #wasmtime_config_async_support_set to be enabled.
+ *
+ * For more information see the Rust documentation at
+ * https://docs.wasmtime.dev/api/wasmtime/struct.Store.html#method.out_of_fuel_async_yield.
+ */
+WASM_API_EXTERN void wasmtime_context_out_of_fuel_async_yield(
+    wasmtime_context_t *context,
+    uint64_t injection_count,
+    uint64_t fuel_to_inject);
+
+/**
+ * \brief Configures epoch-deadline expiration to yield to the async caller and
+ * then update the deadline.
+ *
+ * When the epoch deadline is reached the currently executing future returns
+ * from #wasmtime_call_future_poll as not-ready, and once resumed the deadline
+ * is set to `delta` ticks beyond the current epoch. Requires both
+ * #wasmtime_config_epoch_interruption_set and
+ * #wasmtime_config_async_support_set to be enabled.
+ *
+ * For more information see the Rust documentation at
+ * https://docs.wasmtime.dev/api/wasmtime/struct.Store.html#method.epoch_deadline_async_yield_and_update.
+ */
+WASM_API_EXTERN void wasmtime_context_epoch_deadline_async_yield_and_update(
+    wasmtime_context_t *context,
+    uint64_t delta);
+
+/**
+ * \brief The callback used to poll a #wasmtime_async_continuation_t.
+ *
+ * Returns `true` once the asynchronous work of the host function is done, at
+ * which point the results of the host function must have been written. While
+ * this returns `false` the WebAssembly that called the host function remains
+ * suspended.
+ */
+typedef bool (*wasmtime_func_async_continuation_callback_t)(void *env);
+
+/**
+ * \brief The asynchronous half of a host function defined with
+ * #wasmtime_func_async_callback_t.
+ */
+typedef struct wasmtime_async_continuation_t {
+  /// Callback invoked each time the containing future is polled.
+  wasmtime_func_async_continuation_callback_t callback;
+  /// User-provided argument passed to `callback`.
+  void *env;
+  /// Optional finalizer for `env`, invoked once the continuation is no longer
+  /// needed.
+  void (*finalizer)(void *);
+} wasmtime_async_continuation_t;
+
+/**
+ * \brief Callback signature for #wasmtime_func_new_async.
+ *
+ * This is the same as #wasmtime_func_callback_t except that the host function
+ * is able to suspend the calling WebAssembly. The arguments are:
+ *
+ * \param env user-provided argument passed to #wasmtime_func_new_async
+ * \param caller a temporary object that can only be used during this function
+ * call, not from within the continuation.
+ * \param args the arguments provided to this function invocation
+ * \param nargs how many arguments are provided
+ * \param results where to write the results of this function
+ * \param nresults how many results must be produced
+ * \param trap_ret where to store a trap to raise in WebAssembly
+ * \param continuation_ret the continuation that is polled until the host
+ * function has completed
+ *
+ * The `args`, `results` and `trap_ret` pointers remain valid until the
+ * continuation has completed, so results and traps may be written either from
+ * this callback or from the continuation. The continuation is initialized to
+ * one that's immediately complete, so host functions which don't need to
+ * suspend can leave it as-is.
+ *
+ * If a trap is written to `trap_ret` then ownership of it is transferred to
+ * the engine and it's raised in WebAssembly once the continuation completes.
+ */
+typedef void (*wasmtime_func_async_callback_t)(
+    void *env,
+    wasmtime_caller_t *caller,
+    const wasmtime_val_t *args,
+    size_t nargs,
+    wasmtime_val_t *results,
+    size_t nresults,
+    wasm_trap_t **trap_ret,
+    wasmtime_async_continuation_t *continuation_ret);
+
+/**
+ * \brief Creates a new host-defined function which may suspend WebAssembly.
+ *
+ * This is the same as #wasmtime_func_new except that the callback is an
+ * asynchronous host function. The `store` must have been created with a config
+ * that has #wasmtime_config_async_support_set enabled.
+ */
+WASM_API_EXTERN void wasmtime_func_new_async(
+    wasmtime_context_t *store,
+    const wasm_functype_t* type,
+    wasmtime_func_async_callback_t callback,
+    void *env,
+    void (*finalizer)(void*),
+    wasmtime_func_t *ret
+);
+
+/**
+ * \brief Defines a new async function in this linker.
+ *
+ * This is the same as #wasmtime_linker_define_func except that the callback is
+ * an asynchronous host function, see #wasmtime_func_async_callback_t. The
+ * linker must have been created with an engine whose config has
+ * #wasmtime_config_async_support_set enabled.
+ */
+WASM_API_EXTERN wasmtime_error_t *wasmtime_linker_define_async_func(
+    wasmtime_linker_t *linker,
+    const char *module,
+    size_t module_len,
+    const char *name,
+    size_t name_len,
+    const wasm_functype_t *ty,
+    wasmtime_func_async_callback_t cb,
+    void *data,
+    void (*finalizer)(void*)
+);
+
+/**
+ * \brief An opaque type representing an asynchronous call or instantiation
+ * which is in progress.
+ *
+ * Futures are created with #wasmtime_func_call_async or
+ * #wasmtime_linker_instantiate_async, driven to completion with
+ * #wasmtime_call_future_poll, and must be deleted with
+ * #wasmtime_call_future_delete.
+ */
+typedef struct wasmtime_call_future wasmtime_call_future_t;
+
+/**
+ * \brief Executes WebAssembly in the future until it either completes or
+ * suspends.
+ *
+ * Returns `true` once the underlying operation has completed, at which point
+ * its outputs have been written, and `false` if it's suspended and needs to be
+ * polled again. A future must not be polled again once it has completed.
+ */
+WASM_API_EXTERN bool wasmtime_call_future_poll(wasmtime_call_future_t *future);
+
+/**
+ * \brief Deletes the future provided.
+ *
+ * This can be called on a future which has not yet completed, in which case
+ * the in-progress WebAssembly is cancelled.
+ */
+WASM_API_EXTERN void wasmtime_call_future_delete(wasmtime_call_future_t *future);
+
+/**
+ * \brief Invokes a WebAssembly function asynchronously.
+ *
+ * This is the same as #wasmtime_func_call except that the returned future
+ * must be polled with #wasmtime_call_future_poll to make progress. The `store`
+ * must have been created with a config that has
+ * #wasmtime_config_async_support_set enabled.
+ *
+ * The arguments are copied before this function returns, but `store`, `func`,
+ * `results`, `trap_ret` and `error_ret` must all remain valid until the
+ * returned future has been deleted. Once the future completes then exactly one
+ * of three outcomes happened, mirroring #wasmtime_func_call: `results` was
+ * written, a trap was written to `trap_ret`, or an error was written to
+ * `error_ret`. The `store` must not be used for anything else while the future
+ * is alive.
+ */
+WASM_API_EXTERN wasmtime_call_future_t* wasmtime_func_call_async(
+    wasmtime_context_t *store,
+    const wasmtime_func_t *func,
+    const wasmtime_val_t *args,
+    size_t nargs,
+    wasmtime_val_t *results,
+    size_t nresults,
+    wasm_trap_t **trap_ret,
+    wasmtime_error_t **error_ret);
+
+/**
+ * \brief Instantiates a module asynchronously with the items defined in this
+ * linker.
+ *
+ * This is the same as #wasmtime_linker_instantiate except that the returned
+ * future must be polled with #wasmtime_call_future_poll to make progress, for
+ * example if the start function calls async host functions.
+ *
+ * All pointers passed must remain valid until the returned future has been
+ * deleted. Once the future completes then either the instance was written to
+ * `instance`, a trap was written to `trap_ret`, or an error was written to
+ * `error_ret`.
+ */
+WASM_API_EXTERN wasmtime_call_future_t *wasmtime_linker_instantiate_async(
+    const wasmtime_linker_t *linker,
+    wasmtime_context_t *store,
+    const wasmtime_module_t *module,
+    wasmtime_instance_t *instance,
+    wasm_trap_t **trap_ret,
+    wasmtime_error_t **error_ret);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif // WASMTIME_ASYNC_H
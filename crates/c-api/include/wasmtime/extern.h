@@ -8,6 +8,7 @@
 #define WASMTIME_EXTERN_H
 
 #include <wasmtime/module.h>
+#include <wasmtime/sharedmemory.h>
 #include <wasmtime/store.h>
 
 #ifdef __cplusplus
@@ -84,6 +85,20 @@ typedef struct wasmtime_global {
   size_t index;
 } wasmtime_global_t;
 
+/// \brief Representation of an exception tag in Wasmtime.
+///
+/// Tags are represented with a 64-bit identifying integer in Wasmtime.
+/// They do not have any destructor associated with them. Tags cannot
+/// interoperate between #wasmtime_store_t instances and if the wrong tag
+/// is passed to the wrong store then it may trigger an assertion to abort the
+/// process.
+typedef struct wasmtime_tag {
+  /// Internal identifier of what store this belongs to, never zero.
+  uint64_t store_id;
+  /// Internal index within the store.
+  size_t index;
+} wasmtime_tag_t;
+
 /// \brief Discriminant of #wasmtime_extern_t
 typedef uint8_t wasmtime_extern_kind_t;
 
@@ -105,6 +120,12 @@ typedef uint8_t wasmtime_extern_kind_t;
 /// \brief Value of #wasmtime_extern_kind_t meaning that #wasmtime_extern_t is
 /// a module
 #define WASMTIME_EXTERN_MODULE 5
+/// \brief Value of #wasmtime_extern_kind_t meaning that #wasmtime_extern_t is
+/// a shared memory
+#define WASMTIME_EXTERN_SHAREDMEMORY 6
+/// \brief Value of #wasmtime_extern_kind_t meaning that #wasmtime_extern_t is
+/// an exception tag
+#define WASMTIME_EXTERN_TAG 7
 
 /**
  * \typedef wasmtime_extern_union_t
@@ -132,6 +153,13 @@ typedef union wasmtime_extern_union {
     /// Note that this may be an owned pointer depending on the ownership of the
     /// #wasmtime_extern_t container value.
     wasmtime_module_t *module;
+    /// Field used if #wasmtime_extern_t::kind is #WASMTIME_EXTERN_SHAREDMEMORY
+    ///
+    /// Note that this may be an owned pointer depending on the ownership of the
+    /// #wasmtime_extern_t container value.
+    wasmtime_sharedmemory_t *sharedmemory;
+    /// Field used if #wasmtime_extern_t::kind is #WASMTIME_EXTERN_TAG
+    wasmtime_tag_t tag;
 } wasmtime_extern_union_t;
 
 /**
@@ -142,7 +170,7 @@ typedef union wasmtime_extern_union {
  * \brief Container for different kinds of extern items.
  *
  * Note that this structure may contain an owned value, namely
- * #wasmtime_module_t, depending on the context in which this is used. APIs
+ * #wasmtime_module_t or #wasmtime_sharedmemory_t, depending on the context in which this is used. APIs
  * which consume a #wasmtime_extern_t do not take ownership, but APIs that
  * return #wasmtime_extern_t require that #wasmtime_extern_delete is called to
  * deallocate the value.
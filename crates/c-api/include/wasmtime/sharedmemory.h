@@ -0,0 +1,103 @@
+/**
+ * \file wasmtime/sharedmemory.h
+ *
+ * Wasmtime API for interacting with wasm shared memories.
+ */
+
+#ifndef WASMTIME_SHAREDMEMORY_H
+#define WASMTIME_SHAREDMEMORY_H
+
+#include <wasm.h>
+#include <wasmtime/error.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * \typedef wasmtime_sharedmemory_t
+ * \brief Convenience alias for #wasmtime_sharedmemory
+ *
+ * \struct wasmtime_sharedmemory
+ * \brief A WebAssembly linear memory which may be shared between threads.
+ *
+ * Unlike #wasmtime_memory_t a shared memory is not owned by any particular
+ * store and is instead tied to a #wasm_engine_t. It can be imported into any
+ * number of instances living in stores created from the same engine.
+ */
+typedef struct wasmtime_sharedmemory wasmtime_sharedmemory_t;
+
+/**
+ * \brief Creates a new shared WebAssembly linear memory.
+ *
+ * \param engine the engine that the memory is associated with
+ * \param ty the type of the memory to create, which must be shared
+ * \param ret where to store the returned memory
+ *
+ * If an error happens when creating the memory it's returned and owned by the
+ * caller. If an error happens then `ret` is not filled in.
+ */
+WASM_API_EXTERN wasmtime_error_t *wasmtime_sharedmemory_new(
+    const wasm_engine_t *engine,
+    const wasm_memorytype_t *ty,
+    wasmtime_sharedmemory_t **ret
+);
+
+/**
+ * \brief Deletes a shared memory handle.
+ *
+ * The underlying memory is only deallocated once all handles to it, including
+ * those held by instances which imported it, have been dropped.
+ */
+WASM_API_EXTERN void wasmtime_sharedmemory_delete(wasmtime_sharedmemory_t *memory);
+
+/**
+ * \brief Creates a new handle to the same underlying shared memory.
+ */
+WASM_API_EXTERN wasmtime_sharedmemory_t *wasmtime_sharedmemory_clone(const wasmtime_sharedmemory_t *memory);
+
+/**
+ * \brief Returns the type of the shared memory specified
+ */
+WASM_API_EXTERN wasm_memorytype_t *wasmtime_sharedmemory_type(const wasmtime_sharedmemory_t *memory);
+
+/**
+ * \brief Returns the base pointer in memory where the shared memory starts.
+ *
+ * Note that other threads may concurrently read and write this memory, so
+ * accesses through this pointer must be synchronized appropriately.
+ */
+WASM_API_EXTERN uint8_t *wasmtime_sharedmemory_data(const wasmtime_sharedmemory_t *memory);
+
+/**
+ * \brief Returns the byte length of this shared memory.
+ */
+WASM_API_EXTERN size_t wasmtime_sharedmemory_data_size(const wasmtime_sharedmemory_t *memory);
+
+/**
+ * \brief Returns the length, in WebAssembly pages, of this shared memory
+ */
+WASM_API_EXTERN uint64_t wasmtime_sharedmemory_size(const wasmtime_sharedmemory_t *memory);
+
+/**
+ * \brief Attempts to grow the specified shared memory by `delta` pages.
+ *
+ * \param memory the memory to grow
+ * \param delta the number of pages to grow by
+ * \param prev_size where to store the previous size of memory
+ *
+ * If memory cannot be grown then `prev_size` is left unchanged and an error is
+ * returned. Otherwise `prev_size` is set to the previous size of the memory, in
+ * WebAssembly pages, and `NULL` is returned.
+ */
+WASM_API_EXTERN wasmtime_error_t *wasmtime_sharedmemory_grow(
+    const wasmtime_sharedmemory_t *memory,
+    uint64_t delta,
+    uint64_t *prev_size
+);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif // WASMTIME_SHAREDMEMORY_H
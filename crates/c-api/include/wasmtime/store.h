@@ -153,6 +153,17 @@ WASM_API_EXTERN bool wasmtime_context_fuel_consumed(const wasmtime_context_t *co
  */
 WASM_API_EXTERN wasmtime_error_t *wasmtime_context_consume_fuel(wasmtime_context_t *context, uint64_t fuel, uint64_t *remaining);
 
+/**
+ * \brief Configures the relative deadline at which point WebAssembly code will
+ * trap.
+ *
+ * This function configures the store-local epoch deadline after which point
+ * WebAssembly code will trap.
+ *
+ * See also #wasmtime_config_epoch_interruption_set.
+ */
+WASM_API_EXTERN void wasmtime_context_set_epoch_deadline(wasmtime_context_t *context, uint64_t ticks_beyond_current);
+
 /**
  * \brief Configres WASI state within the specified store.
  *
@@ -0,0 +1,257 @@
+//! Support for Wasmtime's asynchronous execution in the C API.
+//!
+//! Rust futures can't be represented directly in C, so instead this module
+//! exposes an opaque `wasmtime_call_future_t` which the embedder polls to
+//! completion. Similarly async host functions are modeled as a synchronous
+//! callback which hands back a "continuation" that is repeatedly polled until
+//! the host function has finished.
+
+use crate::{
+    wasm_config_t, wasm_functype_t, wasm_trap_t, wasmtime_caller_t, wasmtime_error_t,
+    wasmtime_val_t, wasmtime_val_union, CStoreContextMut, StoreData,
+};
+use std::ffi::c_void;
+use std::future::Future;
+use std::mem::MaybeUninit;
+use std::pin::Pin;
+use std::ptr;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use wasmtime::{Caller, Func, Trap, Val};
+
+#[no_mangle]
+pub extern "C" fn wasmtime_config_async_support_set(c: &mut wasm_config_t, enable: bool) {
+    c.config.async_support(enable);
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_config_async_stack_size_set(c: &mut wasm_config_t, size: usize) -> bool {
+    c.config.async_stack_size(size).is_ok()
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_context_out_of_fuel_async_yield(
+    mut store: CStoreContextMut<'_>,
+    injection_count: u64,
+    fuel_to_inject: u64,
+) {
+    store.out_of_fuel_async_yield(injection_count, fuel_to_inject);
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_context_epoch_deadline_async_yield_and_update(
+    mut store: CStoreContextMut<'_>,
+    delta: u64,
+) {
+    store.epoch_deadline_async_yield_and_update(delta);
+}
+
+pub type wasmtime_func_async_continuation_callback_t = extern "C" fn(*mut c_void) -> bool;
+
+/// A C-defined computation which is polled until it returns `true`, used to
+/// implement the asynchronous half of an async host function.
+#[repr(C)]
+pub struct wasmtime_async_continuation_t {
+    pub callback: wasmtime_func_async_continuation_callback_t,
+    pub env: *mut c_void,
+    pub finalizer: Option<extern "C" fn(*mut c_void)>,
+}
+
+unsafe impl Send for wasmtime_async_continuation_t {}
+
+impl Drop for wasmtime_async_continuation_t {
+    fn drop(&mut self) {
+        if let Some(f) = self.finalizer {
+            f(self.env);
+        }
+    }
+}
+
+impl Future for wasmtime_async_continuation_t {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if (self.callback)(self.env) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+extern "C" fn continuation_already_complete(_env: *mut c_void) -> bool {
+    true
+}
+
+pub type wasmtime_func_async_callback_t = extern "C" fn(
+    *mut c_void,
+    *mut wasmtime_caller_t,
+    *const wasmtime_val_t,
+    usize,
+    *mut wasmtime_val_t,
+    usize,
+    &mut Option<Box<wasm_trap_t>>,
+    &mut wasmtime_async_continuation_t,
+);
+
+#[no_mangle]
+pub unsafe extern "C" fn wasmtime_func_new_async(
+    store: CStoreContextMut<'_>,
+    ty: &wasm_functype_t,
+    callback: wasmtime_func_async_callback_t,
+    data: *mut c_void,
+    finalizer: Option<extern "C" fn(*mut std::ffi::c_void)>,
+    func: &mut Func,
+) {
+    let ty = ty.ty().ty.clone();
+    let cb = c_async_callback_to_rust_fn(callback, data, finalizer);
+    *func = Func::new_async(store, ty, cb);
+}
+
+pub(crate) unsafe fn c_async_callback_to_rust_fn(
+    callback: wasmtime_func_async_callback_t,
+    data: *mut c_void,
+    finalizer: Option<extern "C" fn(*mut std::ffi::c_void)>,
+) -> impl for<'a> Fn(
+    Caller<'a, StoreData>,
+    &'a [Val],
+    &'a mut [Val],
+) -> Box<dyn Future<Output = Result<(), Trap>> + Send + 'a>
+       + Send
+       + Sync
+       + 'static {
+    let foreign = crate::ForeignData { data, finalizer };
+    move |caller, params, results| {
+        // Unlike synchronous host functions the storage for `params/results`
+        // must live until the continuation has finished, so it's owned by the
+        // returned future rather than being borrowed from the store.
+        let mut vals = Vec::with_capacity(params.len() + results.len());
+        vals.extend(params.iter().cloned().map(|p| wasmtime_val_t::from_val(p)));
+        vals.extend((0..results.len()).map(|_| wasmtime_val_t {
+            kind: crate::WASMTIME_I32,
+            of: wasmtime_val_union { i32: 0 },
+        }));
+        let (params, out_results) = vals.split_at_mut(params.len());
+
+        // Invoke the synchronous half of the C function, which hands us back
+        // a continuation to poll.
+        let mut caller = wasmtime_caller_t { caller };
+        // The trap may be written from the continuation, after this closure
+        // has returned, so it's stored in a slot whose address doesn't change
+        // when it's moved into the returned future.
+        let mut trap: Box<Option<Box<wasm_trap_t>>> = Box::new(None);
+        let mut continuation = wasmtime_async_continuation_t {
+            callback: continuation_already_complete,
+            env: ptr::null_mut(),
+            finalizer: None,
+        };
+        callback(
+            foreign.data,
+            &mut caller,
+            params.as_ptr(),
+            params.len(),
+            out_results.as_mut_ptr(),
+            out_results.len(),
+            &mut *trap,
+            &mut continuation,
+        );
+        let nparams = params.len();
+
+        Box::new(async move {
+            continuation.await;
+            if let Some(trap) = *trap {
+                return Err(trap.trap);
+            }
+            for (i, result) in vals[nparams..].iter().enumerate() {
+                results[i] = unsafe { result.to_val() };
+            }
+            Ok(())
+        })
+    }
+}
+
+/// An in-progress asynchronous call into WebAssembly which is driven to
+/// completion with `wasmtime_call_future_poll`.
+pub struct wasmtime_call_future_t<'a> {
+    pub(crate) underlying: Pin<Box<dyn Future<Output = ()> + 'a>>,
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_call_future_delete(_future: Box<wasmtime_call_future_t>) {}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_call_future_poll(future: &mut wasmtime_call_future_t) -> bool {
+    // The C API has no notion of wakers, embedders are instead expected to
+    // poll the future again whenever it's appropriate for them to do so.
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    match future.underlying.as_mut().poll(&mut cx) {
+        Poll::Ready(()) => true,
+        Poll::Pending => false,
+    }
+}
+
+fn noop_waker() -> Waker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+}
+
+/// Stores the error of an asynchronous operation into either `trap_ret` or
+/// `err_ret`, depending on whether it was a trap.
+pub(crate) fn handle_call_error(
+    err: anyhow::Error,
+    trap_ret: &mut *mut wasm_trap_t,
+    err_ret: &mut *mut wasmtime_error_t,
+) {
+    match err.downcast::<Trap>() {
+        Ok(trap) => *trap_ret = Box::into_raw(Box::new(wasm_trap_t::new(trap))),
+        Err(err) => *err_ret = Box::into_raw(Box::new(wasmtime_error_t::from(err))),
+    }
+}
+
+async fn do_func_call_async(
+    mut store: CStoreContextMut<'_>,
+    func: &Func,
+    params: Vec<Val>,
+    results: &mut [MaybeUninit<wasmtime_val_t>],
+    trap_ret: &mut *mut wasm_trap_t,
+    err_ret: &mut *mut wasmtime_error_t,
+) {
+    let mut wt_results = vec![Val::null(); results.len()];
+    match func.call_async(&mut store, &params, &mut wt_results).await {
+        Ok(()) => {
+            for (slot, val) in results.iter_mut().zip(wt_results) {
+                crate::initialize(slot, wasmtime_val_t::from_val(val));
+            }
+        }
+        Err(err) => handle_call_error(err, trap_ret, err_ret),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn wasmtime_func_call_async<'a>(
+    store: CStoreContextMut<'a>,
+    func: &'a Func,
+    args: *const wasmtime_val_t,
+    nargs: usize,
+    results: *mut MaybeUninit<wasmtime_val_t>,
+    nresults: usize,
+    trap_ret: &'a mut *mut wasm_trap_t,
+    err_ret: &'a mut *mut wasmtime_error_t,
+) -> Box<wasmtime_call_future_t<'a>> {
+    // Arguments are converted eagerly since the caller is not required to
+    // keep them alive once this function returns, but results are only
+    // written once the future completes.
+    let params = crate::slice_from_raw_parts(args, nargs)
+        .iter()
+        .map(|i| i.to_val())
+        .collect();
+    let results = crate::slice_from_raw_parts_mut(results, nresults);
+    let fut = do_func_call_async(store, func, params, results, trap_ret, err_ret);
+    Box::new(wasmtime_call_future_t {
+        underlying: Box::pin(fut),
+    })
+}
@@ -196,7 +196,7 @@ pub extern "C" fn wasm_func_as_extern_const(f: &wasm_func_t) -> &wasm_extern_t {
 
 #[repr(C)]
 pub struct wasmtime_caller_t<'a> {
-    caller: Caller<'a, crate::StoreData>,
+    pub(crate) caller: Caller<'a, crate::StoreData>,
 }
 
 pub type wasmtime_func_callback_t = extern "C" fn(
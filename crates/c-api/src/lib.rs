@@ -22,6 +22,7 @@ mod linker;
 mod memory;
 mod module;
 mod r#ref;
+mod sharedmemory;
 mod store;
 mod table;
 mod trap;
@@ -40,6 +41,7 @@ pub use crate::memory::*;
 pub use crate::module::*;
 pub use crate::r#extern::*;
 pub use crate::r#ref::*;
+pub use crate::sharedmemory::*;
 pub use crate::store::*;
 pub use crate::table::*;
 pub use crate::trap::*;
@@ -52,6 +54,11 @@ mod wasi;
 #[cfg(feature = "wasi")]
 pub use crate::wasi::*;
 
+#[cfg(feature = "async")]
+mod r#async;
+#[cfg(feature = "async")]
+pub use crate::r#async::*;
+
 #[cfg(feature = "wat")]
 mod wat2wasm;
 #[cfg(feature = "wat")]
@@ -74,6 +74,29 @@ pub unsafe extern "C" fn wasmtime_linker_define_func(
     handle_result(linker.linker.func_new(module, name, ty, cb), |_linker| ())
 }
 
+#[cfg(feature = "async")]
+#[no_mangle]
+pub unsafe extern "C" fn wasmtime_linker_define_async_func(
+    linker: &mut wasmtime_linker_t,
+    module: *const u8,
+    module_len: usize,
+    name: *const u8,
+    name_len: usize,
+    ty: &wasm_functype_t,
+    callback: crate::wasmtime_func_async_callback_t,
+    data: *mut c_void,
+    finalizer: Option<extern "C" fn(*mut std::ffi::c_void)>,
+) -> Option<Box<wasmtime_error_t>> {
+    let ty = ty.ty().ty.clone();
+    let module = to_str!(module, module_len);
+    let name = to_str!(name, name_len);
+    let cb = crate::r#async::c_async_callback_to_rust_fn(callback, data, finalizer);
+    handle_result(
+        linker.linker.func_new_async(module, name, ty, cb),
+        |_linker| (),
+    )
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn wasmtime_linker_define_func_unchecked(
     linker: &mut wasmtime_linker_t,
@@ -132,6 +155,37 @@ pub extern "C" fn wasmtime_linker_instantiate(
     super::instance::handle_instantiate(result, instance_ptr, trap_ptr)
 }
 
+#[cfg(feature = "async")]
+async fn do_linker_instantiate_async(
+    linker: &wasmtime_linker_t,
+    store: CStoreContextMut<'_>,
+    module: &wasmtime_module_t,
+    instance_ptr: &mut Instance,
+    trap_ret: &mut *mut wasm_trap_t,
+    err_ret: &mut *mut wasmtime_error_t,
+) {
+    match linker.linker.instantiate_async(store, &module.module).await {
+        Ok(instance) => *instance_ptr = instance,
+        Err(err) => crate::r#async::handle_call_error(err, trap_ret, err_ret),
+    }
+}
+
+#[cfg(feature = "async")]
+#[no_mangle]
+pub extern "C" fn wasmtime_linker_instantiate_async<'a>(
+    linker: &'a wasmtime_linker_t,
+    store: CStoreContextMut<'a>,
+    module: &'a wasmtime_module_t,
+    instance_ptr: &'a mut Instance,
+    trap_ret: &'a mut *mut wasm_trap_t,
+    err_ret: &'a mut *mut wasmtime_error_t,
+) -> Box<crate::wasmtime_call_future_t<'a>> {
+    let fut = do_linker_instantiate_async(linker, store, module, instance_ptr, trap_ret, err_ret);
+    Box::new(crate::wasmtime_call_future_t {
+        underlying: Box::pin(fut),
+    })
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn wasmtime_linker_module(
     linker: &mut wasmtime_linker_t,
@@ -0,0 +1,60 @@
+use crate::{handle_result, wasm_engine_t, wasm_memorytype_t, wasmtime_error_t};
+use wasmtime::SharedMemory;
+
+#[derive(Clone)]
+pub struct wasmtime_sharedmemory_t {
+    pub(crate) memory: SharedMemory,
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_sharedmemory_new(
+    engine: &wasm_engine_t,
+    ty: &wasm_memorytype_t,
+    ret: &mut *mut wasmtime_sharedmemory_t,
+) -> Option<Box<wasmtime_error_t>> {
+    handle_result(
+        SharedMemory::new(&engine.engine, ty.ty().ty.clone()),
+        |memory| *ret = Box::into_raw(Box::new(wasmtime_sharedmemory_t { memory })),
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_sharedmemory_delete(_memory: Box<wasmtime_sharedmemory_t>) {}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_sharedmemory_clone(
+    memory: &wasmtime_sharedmemory_t,
+) -> Box<wasmtime_sharedmemory_t> {
+    Box::new(memory.clone())
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_sharedmemory_type(
+    memory: &wasmtime_sharedmemory_t,
+) -> Box<wasm_memorytype_t> {
+    Box::new(wasm_memorytype_t::new(memory.memory.ty()))
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_sharedmemory_data(memory: &wasmtime_sharedmemory_t) -> *mut u8 {
+    memory.memory.data().as_ptr() as *mut u8
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_sharedmemory_data_size(memory: &wasmtime_sharedmemory_t) -> usize {
+    memory.memory.data_size()
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_sharedmemory_size(memory: &wasmtime_sharedmemory_t) -> u64 {
+    memory.memory.size()
+}
+
+#[no_mangle]
+pub extern "C" fn wasmtime_sharedmemory_grow(
+    memory: &wasmtime_sharedmemory_t,
+    delta: u64,
+    prev_size: &mut u64,
+) -> Option<Box<wasmtime_error_t>> {
+    handle_result(memory.memory.grow(delta), |prev| *prev_size = prev)
+}
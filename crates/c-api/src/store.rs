@@ -163,6 +163,14 @@ pub extern "C" fn wasmtime_context_consume_fuel(
     })
 }
 
+#[no_mangle]
+pub extern "C" fn wasmtime_context_set_epoch_deadline(
+    mut store: CStoreContextMut<'_>,
+    ticks_beyond_current: u64,
+) {
+    store.set_epoch_deadline(ticks_beyond_current);
+}
+
 #[repr(C)]
 pub struct wasmtime_interrupt_handle_t {
     handle: InterruptHandle,
@@ -1,15 +1,19 @@
 //! The WASI embedding API definitions for Wasmtime.
 
+use crate::{wasm_byte_vec_t, ForeignData};
 use anyhow::Result;
 use cap_std::ambient_authority;
-use std::ffi::CStr;
+use std::ffi::{c_void, CStr};
 use std::fs::File;
+use std::io::{self, Cursor, Read, Write};
 use std::os::raw::{c_char, c_int};
 use std::path::{Path, PathBuf};
 use std::slice;
+use std::sync::{Arc, RwLock};
+use wasi_common::pipe::{ReadPipe, WritePipe};
 use wasmtime_wasi::{
     sync::{Dir, WasiCtxBuilder},
-    WasiCtx,
+    WasiCtx, WasiFile,
 };
 
 unsafe fn cstr_to_path<'a>(path: *const c_char) -> Option<&'a Path> {
@@ -24,20 +28,134 @@ unsafe fn create_file(path: *const c_char) -> Option<File> {
     File::create(cstr_to_path(path)?).ok()
 }
 
+fn file_to_wasi(file: File) -> Box<dyn WasiFile> {
+    let file = cap_std::fs::File::from_std(file);
+    Box::new(wasi_cap_std_sync::file::File::from_cap_std(file))
+}
+
+/// Where a WASI program's stdin is read from.
+enum WasiConfigReadPipe {
+    None,
+    Inherit,
+    File(File),
+    Bytes(Vec<u8>),
+    Callback(CallbackReader),
+}
+
+impl Default for WasiConfigReadPipe {
+    fn default() -> Self {
+        WasiConfigReadPipe::None
+    }
+}
+
+/// Where a WASI program's stdout or stderr is written to.
+enum WasiConfigWritePipe {
+    None,
+    Inherit,
+    File(File),
+    Buffer(Arc<RwLock<Vec<u8>>>),
+    Callback(CallbackWriter),
+}
+
+impl Default for WasiConfigWritePipe {
+    fn default() -> Self {
+        WasiConfigWritePipe::None
+    }
+}
+
+impl WasiConfigWritePipe {
+    fn into_wasi_file(self) -> Option<Box<dyn WasiFile>> {
+        match self {
+            WasiConfigWritePipe::None | WasiConfigWritePipe::Inherit => None,
+            WasiConfigWritePipe::File(file) => Some(file_to_wasi(file)),
+            WasiConfigWritePipe::Buffer(buf) => Some(Box::new(WritePipe::from_shared(buf))),
+            WasiConfigWritePipe::Callback(cb) => Some(Box::new(WritePipe::new(cb))),
+        }
+    }
+}
+
+pub type wasi_read_callback_t = extern "C" fn(*mut c_void, *mut u8, usize) -> isize;
+pub type wasi_write_callback_t = extern "C" fn(*mut c_void, *const u8, usize) -> isize;
+
+/// A `Read` implementation which defers to a C callback.
+struct CallbackReader {
+    callback: wasi_read_callback_t,
+    foreign: ForeignData,
+}
+
+// The callback is only ever invoked with exclusive access through the
+// `RwLock` inside of `ReadPipe`.
+unsafe impl Sync for CallbackReader {}
+
+impl Read for CallbackReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = (self.callback)(self.foreign.data, buf.as_mut_ptr(), buf.len());
+        if n < 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "stdin callback failed",
+            ));
+        }
+        if n as usize > buf.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stdin callback read more bytes than the buffer holds",
+            ));
+        }
+        Ok(n as usize)
+    }
+}
+
+/// A `Write` implementation which defers to a C callback.
+struct CallbackWriter {
+    callback: wasi_write_callback_t,
+    foreign: ForeignData,
+}
+
+// Same as `CallbackReader` above, `WritePipe` only provides exclusive access.
+unsafe impl Sync for CallbackWriter {}
+
+impl Write for CallbackWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = (self.callback)(self.foreign.data, buf.as_ptr(), buf.len());
+        if n < 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "output callback failed",
+            ));
+        }
+        if n as usize > buf.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "output callback consumed more bytes than were written",
+            ));
+        }
+        Ok(n as usize)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A growable in-memory buffer which captures the output of a WASI program.
+#[repr(C)]
+#[derive(Default)]
+pub struct wasi_output_buffer_t {
+    buffer: Arc<RwLock<Vec<u8>>>,
+}
+
 #[repr(C)]
 #[derive(Default)]
 pub struct wasi_config_t {
     args: Vec<Vec<u8>>,
     env: Vec<(Vec<u8>, Vec<u8>)>,
-    stdin: Option<File>,
-    stdout: Option<File>,
-    stderr: Option<File>,
+    stdin: WasiConfigReadPipe,
+    stdout: WasiConfigWritePipe,
+    stderr: WasiConfigWritePipe,
     preopens: Vec<(Dir, PathBuf)>,
     inherit_args: bool,
     inherit_env: bool,
-    inherit_stdin: bool,
-    inherit_stdout: bool,
-    inherit_stderr: bool,
 }
 
 impl wasi_config_t {
@@ -67,26 +185,26 @@ impl wasi_config_t {
                 .collect::<Result<Vec<(String, String)>>>()?;
             builder = builder.envs(&env)?;
         }
-        if self.inherit_stdin {
-            builder = builder.inherit_stdin();
-        } else if let Some(file) = self.stdin {
-            let file = cap_std::fs::File::from_std(file);
-            let file = wasi_cap_std_sync::file::File::from_cap_std(file);
-            builder = builder.stdin(Box::new(file));
+        match self.stdin {
+            WasiConfigReadPipe::None => {}
+            WasiConfigReadPipe::Inherit => builder = builder.inherit_stdin(),
+            WasiConfigReadPipe::File(file) => builder = builder.stdin(file_to_wasi(file)),
+            WasiConfigReadPipe::Bytes(bytes) => {
+                builder = builder.stdin(Box::new(ReadPipe::new(Cursor::new(bytes))))
+            }
+            WasiConfigReadPipe::Callback(cb) => {
+                builder = builder.stdin(Box::new(ReadPipe::new(cb)))
+            }
         }
-        if self.inherit_stdout {
+        if let WasiConfigWritePipe::Inherit = self.stdout {
             builder = builder.inherit_stdout();
-        } else if let Some(file) = self.stdout {
-            let file = cap_std::fs::File::from_std(file);
-            let file = wasi_cap_std_sync::file::File::from_cap_std(file);
-            builder = builder.stdout(Box::new(file));
+        } else if let Some(file) = self.stdout.into_wasi_file() {
+            builder = builder.stdout(file);
         }
-        if self.inherit_stderr {
+        if let WasiConfigWritePipe::Inherit = self.stderr {
             builder = builder.inherit_stderr();
-        } else if let Some(file) = self.stderr {
-            let file = cap_std::fs::File::from_std(file);
-            let file = wasi_cap_std_sync::file::File::from_cap_std(file);
-            builder = builder.stderr(Box::new(file));
+        } else if let Some(file) = self.stderr.into_wasi_file() {
+            builder = builder.stderr(file);
         }
         for (dir, path) in self.preopens {
             builder = builder.preopened_dir(dir, path)?;
@@ -160,16 +278,35 @@ pub unsafe extern "C" fn wasi_config_set_stdin_file(
         None => return false,
     };
 
-    config.stdin = Some(file);
-    config.inherit_stdin = false;
+    config.stdin = WasiConfigReadPipe::File(file);
 
     true
 }
 
 #[no_mangle]
 pub extern "C" fn wasi_config_inherit_stdin(config: &mut wasi_config_t) {
-    config.stdin = None;
-    config.inherit_stdin = true;
+    config.stdin = WasiConfigReadPipe::Inherit;
+}
+
+#[no_mangle]
+pub extern "C" fn wasi_config_set_stdin_bytes(
+    config: &mut wasi_config_t,
+    binary: &mut wasm_byte_vec_t,
+) {
+    config.stdin = WasiConfigReadPipe::Bytes(binary.take());
+}
+
+#[no_mangle]
+pub extern "C" fn wasi_config_set_stdin_callback(
+    config: &mut wasi_config_t,
+    callback: wasi_read_callback_t,
+    data: *mut c_void,
+    finalizer: Option<extern "C" fn(*mut c_void)>,
+) {
+    config.stdin = WasiConfigReadPipe::Callback(CallbackReader {
+        callback,
+        foreign: ForeignData { data, finalizer },
+    });
 }
 
 #[no_mangle]
@@ -182,16 +319,35 @@ pub unsafe extern "C" fn wasi_config_set_stdout_file(
         None => return false,
     };
 
-    config.stdout = Some(file);
-    config.inherit_stdout = false;
+    config.stdout = WasiConfigWritePipe::File(file);
 
     true
 }
 
 #[no_mangle]
 pub extern "C" fn wasi_config_inherit_stdout(config: &mut wasi_config_t) {
-    config.stdout = None;
-    config.inherit_stdout = true;
+    config.stdout = WasiConfigWritePipe::Inherit;
+}
+
+#[no_mangle]
+pub extern "C" fn wasi_config_set_stdout_buffer(
+    config: &mut wasi_config_t,
+    buffer: &wasi_output_buffer_t,
+) {
+    config.stdout = WasiConfigWritePipe::Buffer(buffer.buffer.clone());
+}
+
+#[no_mangle]
+pub extern "C" fn wasi_config_set_stdout_callback(
+    config: &mut wasi_config_t,
+    callback: wasi_write_callback_t,
+    data: *mut c_void,
+    finalizer: Option<extern "C" fn(*mut c_void)>,
+) {
+    config.stdout = WasiConfigWritePipe::Callback(CallbackWriter {
+        callback,
+        foreign: ForeignData { data, finalizer },
+    });
 }
 
 #[no_mangle]
@@ -204,16 +360,35 @@ pub unsafe extern "C" fn wasi_config_set_stderr_file(
         None => return false,
     };
 
-    (*config).stderr = Some(file);
-    (*config).inherit_stderr = false;
+    config.stderr = WasiConfigWritePipe::File(file);
 
     true
 }
 
 #[no_mangle]
 pub extern "C" fn wasi_config_inherit_stderr(config: &mut wasi_config_t) {
-    config.stderr = None;
-    config.inherit_stderr = true;
+    config.stderr = WasiConfigWritePipe::Inherit;
+}
+
+#[no_mangle]
+pub extern "C" fn wasi_config_set_stderr_buffer(
+    config: &mut wasi_config_t,
+    buffer: &wasi_output_buffer_t,
+) {
+    config.stderr = WasiConfigWritePipe::Buffer(buffer.buffer.clone());
+}
+
+#[no_mangle]
+pub extern "C" fn wasi_config_set_stderr_callback(
+    config: &mut wasi_config_t,
+    callback: wasi_write_callback_t,
+    data: *mut c_void,
+    finalizer: Option<extern "C" fn(*mut c_void)>,
+) {
+    config.stderr = WasiConfigWritePipe::Callback(CallbackWriter {
+        callback,
+        foreign: ForeignData { data, finalizer },
+    });
 }
 
 #[no_mangle]
@@ -239,3 +414,24 @@ pub unsafe extern "C" fn wasi_config_preopen_dir(
 
     true
 }
+
+#[no_mangle]
+pub extern "C" fn wasi_output_buffer_new() -> Box<wasi_output_buffer_t> {
+    Box::new(wasi_output_buffer_t::default())
+}
+
+#[no_mangle]
+pub extern "C" fn wasi_output_buffer_delete(_buffer: Box<wasi_output_buffer_t>) {}
+
+#[no_mangle]
+pub extern "C" fn wasi_output_buffer_contents(
+    buffer: &wasi_output_buffer_t,
+    contents: &mut wasm_byte_vec_t,
+) {
+    contents.set_buffer(buffer.buffer.read().unwrap().clone());
+}
+
+#[no_mangle]
+pub extern "C" fn wasi_output_buffer_clear(buffer: &wasi_output_buffer_t) {
+    buffer.buffer.write().unwrap().clear();
+}
@@ -3,10 +3,11 @@
 //! This module contains the implementation of how Cranelift is configured, as
 //! well as providing a function to return the default configuration to build.
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use cranelift_codegen::isa;
 use cranelift_codegen::settings::{self, Configurable, SetError};
 use std::fmt;
+use target_lexicon::{Architecture, CallingConvention, Triple};
 use wasmtime_environ::{CompilerBuilder, Setting, SettingKind};
 
 #[derive(Clone)]
@@ -14,6 +15,8 @@ struct Builder {
     flags: settings::Builder,
     isa_flags: isa::Builder,
     linkopts: LinkOptions,
+    tail_calls: bool,
+    exceptions: bool,
 }
 
 #[derive(Clone, Default)]
@@ -47,6 +50,8 @@ pub fn builder() -> Box<dyn CompilerBuilder> {
         flags,
         isa_flags: cranelift_native::builder().expect("host machine is not a supported target"),
         linkopts: LinkOptions::default(),
+        tail_calls: false,
+        exceptions: false,
     })
 }
 
@@ -74,6 +79,21 @@ impl CompilerBuilder for Builder {
             self.linkopts.force_jump_veneers = value.parse()?;
             return Ok(());
         }
+        if name == "wasmtime_tail_calls" {
+            let enable = value.parse()?;
+            if enable && !supports_tail_calls(self.triple()) {
+                bail!(
+                    "the tail call proposal is not supported on target `{}`",
+                    self.triple()
+                );
+            }
+            self.tail_calls = enable;
+            return Ok(());
+        }
+        if name == "wasmtime_exceptions" {
+            self.exceptions = value.parse()?;
+            return Ok(());
+        }
 
         // ... then forward this to Cranelift
         if let Err(err) = self.flags.set(name, value) {
@@ -101,12 +121,17 @@ impl CompilerBuilder for Builder {
         Ok(())
     }
 
-    fn build(&self) -> Box<dyn wasmtime_environ::Compiler> {
+    fn build(&self) -> Result<Box<dyn wasmtime_environ::Compiler>> {
         let isa = self
             .isa_flags
             .clone()
-            .finish(settings::Flags::new(self.flags.clone()));
-        Box::new(crate::compiler::Compiler::new(isa, self.linkopts.clone()))
+            .finish(settings::Flags::new(self.flags.clone()))?;
+        Ok(Box::new(crate::compiler::Compiler::new(
+            isa,
+            self.linkopts.clone(),
+            self.tail_calls,
+            self.exceptions,
+        )))
     }
 
     fn settings(&self) -> Vec<Setting> {
@@ -127,6 +152,13 @@ impl CompilerBuilder for Builder {
     }
 }
 
+/// Tail calls are currently only implemented by Cranelift's x86-64 backend,
+/// and only for System V targets.
+fn supports_tail_calls(triple: &Triple) -> bool {
+    triple.architecture == Architecture::X86_64
+        && triple.default_calling_convention() == Ok(CallingConvention::SystemV)
+}
+
 impl fmt::Debug for Builder {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Builder")
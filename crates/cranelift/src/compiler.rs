@@ -38,14 +38,23 @@ pub(crate) struct Compiler {
     translators: Mutex<Vec<FuncTranslator>>,
     isa: Box<dyn TargetIsa>,
     linkopts: LinkOptions,
+    tail_calls: bool,
+    exceptions: bool,
 }
 
 impl Compiler {
-    pub(crate) fn new(isa: Box<dyn TargetIsa>, linkopts: LinkOptions) -> Compiler {
+    pub(crate) fn new(
+        isa: Box<dyn TargetIsa>,
+        linkopts: LinkOptions,
+        tail_calls: bool,
+        exceptions: bool,
+    ) -> Compiler {
         Compiler {
             translators: Default::default(),
             isa,
             linkopts,
+            tail_calls,
+            exceptions,
         }
     }
 
@@ -116,12 +125,21 @@ impl wasmtime_environ::Compiler for Compiler {
         let func_index = module.func_index(func_index);
         let mut context = Context::new();
         context.func.name = get_func_name(func_index);
-        context.func.signature = func_signature(isa, translation, types, func_index);
+        context.func.signature =
+            func_signature(isa, translation, types, func_index, self.tail_calls);
         if tunables.generate_native_debuginfo {
             context.func.collect_debug_info();
         }
 
-        let mut func_env = FuncEnvironment::new(isa, translation, types, tunables);
+        let mut func_env = FuncEnvironment::new(
+            isa,
+            translation,
+            types,
+            tunables,
+            func_index,
+            self.tail_calls,
+            self.exceptions,
+        );
 
         // We use these as constant offsets below in
         // `stack_limit_from_arguments`, so assert their values here. This
@@ -203,6 +221,15 @@ impl wasmtime_environ::Compiler for Compiler {
         log::debug!("{:?} translated in {:?}", func_index, timing.total());
         log::trace!("{:?} timing info\n{}", func_index, timing);
 
+        // Calls are emitted in order, so the return addresses of those which
+        // have exception handlers are sorted already.
+        let exception_handlers = reloc_sink
+            .call_sites
+            .iter()
+            .filter(|(_, srcloc)| func_env.is_exception_handler(*srcloc))
+            .map(|(ret_addr, _)| *ret_addr)
+            .collect();
+
         let length = u32::try_from(code_buf.len()).unwrap();
         Ok(Box::new(CompiledFunction {
             body: code_buf,
@@ -214,6 +241,7 @@ impl wasmtime_environ::Compiler for Compiler {
             info: FunctionInfo {
                 start_srcloc: address_transform.start_srcloc,
                 stack_maps: stack_map_sink.finish(),
+                exception_handlers,
                 start: 0,
                 length,
             },
@@ -378,7 +406,7 @@ impl Compiler {
 
         // The wasm signature we're calling in this trampoline has the actual
         // ABI of the function signature described by `ty`
-        let wasm_signature = indirect_signature(isa, ty);
+        let wasm_signature = indirect_signature(isa, ty, self.tail_calls);
 
         // The host signature has the `VMTrampoline` signature where the ABI is
         // fixed.
@@ -460,7 +488,7 @@ impl Compiler {
     ) -> Result<CompiledFunction, CompileError> {
         let isa = &*self.isa;
         let pointer_type = isa.pointer_type();
-        let wasm_signature = indirect_signature(isa, ty);
+        let wasm_signature = indirect_signature(isa, ty, self.tail_calls);
         // The host signature has an added parameter for the `values_vec` input
         // and output.
         let mut host_signature = blank_sig(isa, wasmtime_call_conv(isa));
@@ -631,6 +659,10 @@ fn collect_address_maps(
 struct RelocSink {
     /// Relocations recorded for the function.
     func_relocs: Vec<Relocation>,
+
+    /// Return addresses of the calls in the function, and the source location
+    /// of each call.
+    call_sites: Vec<(binemit::CodeOffset, ir::SourceLoc)>,
 }
 
 impl binemit::RelocSink for RelocSink {
@@ -657,6 +689,15 @@ impl binemit::RelocSink for RelocSink {
             addend,
         });
     }
+
+    fn add_call_site(
+        &mut self,
+        _opcode: ir::Opcode,
+        ret_addr: binemit::CodeOffset,
+        srcloc: ir::SourceLoc,
+    ) {
+        self.call_sites.push((ret_addr, srcloc));
+    }
 }
 
 impl RelocSink {
@@ -664,6 +705,7 @@ impl RelocSink {
     fn new() -> Self {
         Self {
             func_relocs: Vec::new(),
+            call_sites: Vec::new(),
         }
     }
 }
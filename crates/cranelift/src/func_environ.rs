@@ -10,7 +10,7 @@ use cranelift_frontend::FunctionBuilder;
 use cranelift_frontend::Variable;
 use cranelift_wasm::{
     self, FuncIndex, FuncTranslationState, GlobalIndex, GlobalVariable, MemoryIndex, TableIndex,
-    TargetEnvironment, TypeIndex, WasmError, WasmResult, WasmType,
+    TagIndex, TargetEnvironment, TypeIndex, WasmError, WasmResult, WasmType,
 };
 use std::convert::TryFrom;
 use std::mem;
@@ -20,6 +20,10 @@ use wasmtime_environ::{
     TypeTables, VMOffsets, INTERRUPTED, WASM_PAGE_SIZE,
 };
 
+/// The size of each slot in the buffers of values exchanged with the runtime,
+/// which matches the size of a `ValRaw`.
+const VALUE_SIZE: usize = mem::size_of::<u128>();
+
 /// Compute an `ir::ExternalName` for a given wasm function index.
 pub fn get_func_name(func_index: FuncIndex) -> ir::ExternalName {
     ir::ExternalName::user(0, func_index.as_u32())
@@ -131,6 +135,16 @@ pub struct FuncEnvironment<'module_environment> {
     /// in `*const VMInterrupts`
     fuel_var: cranelift_frontend::Variable,
 
+    /// A function-local variable which caches the value of the store's epoch
+    /// deadline, loaded from `*const VMInterrupts`. It's reloaded whenever the
+    /// cached value indicates the deadline may have been reached, so it never
+    /// causes a spurious interruption.
+    epoch_deadline_var: cranelift_frontend::Variable,
+
+    /// A function-local variable which caches the pointer to the engine's
+    /// epoch counter, loaded once from the vmctx.
+    epoch_ptr_var: cranelift_frontend::Variable,
+
     /// A function-local variable which caches the value of `*const
     /// VMInterrupts` for this function's vmctx argument. This pointer is stored
     /// in the vmctx itself, but never changes for the lifetime of the function,
@@ -138,6 +152,31 @@ pub struct FuncEnvironment<'module_environment> {
     vminterrupts_ptr: cranelift_frontend::Variable,
 
     fuel_consumed: i64,
+
+    /// Whether the tail call proposal is enabled, in which case all wasm
+    /// functions use the `tail` calling convention.
+    tail_calls: bool,
+
+    /// Whether the exception handling proposal is enabled, in which case calls
+    /// inside `try` blocks are followed by a check for a pending exception.
+    exceptions: bool,
+
+    /// Source locations of the wasm instructions whose calls are inside `try`
+    /// blocks, which are where unwinding stops for exceptions they raise.
+    exception_handler_srclocs: Vec<ir::SourceLoc>,
+
+    /// The index of the function being translated.
+    func_index: FuncIndex,
+
+    /// The number of wasm locals of the function, including its parameters.
+    num_locals: usize,
+
+    /// The stack slot holding this frame's `VMCoreDumpFrame` and the values
+    /// which follow it, if core dumps are enabled.
+    coredump_slot: Option<ir::StackSlot>,
+
+    /// The deepest operand stack recorded in `coredump_slot` so far.
+    coredump_max_stack: usize,
 }
 
 impl<'module_environment> FuncEnvironment<'module_environment> {
@@ -146,6 +185,9 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
         translation: &'module_environment ModuleTranslation<'module_environment>,
         types: &'module_environment TypeTables,
         tunables: &'module_environment Tunables,
+        func_index: FuncIndex,
+        tail_calls: bool,
+        exceptions: bool,
     ) -> Self {
         let builtin_function_signatures = BuiltinFunctionSignatures::new(
             isa.pointer_type(),
@@ -166,11 +208,20 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
             offsets: VMOffsets::new(isa.pointer_bytes(), &translation.module),
             tunables,
             fuel_var: Variable::new(0),
+            epoch_deadline_var: Variable::new(0),
+            epoch_ptr_var: Variable::new(0),
             vminterrupts_ptr: Variable::new(0),
 
             // Start with at least one fuel being consumed because even empty
             // functions should consume at least some fuel.
             fuel_consumed: 1,
+            tail_calls,
+            exceptions,
+            exception_handler_srclocs: Vec::new(),
+            func_index,
+            num_locals: 0,
+            coredump_slot: None,
+            coredump_max_stack: 0,
         }
     }
 
@@ -178,6 +229,169 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
         self.isa.pointer_type()
     }
 
+    /// Returns the types of the values carried by exceptions with the given
+    /// tag.
+    fn tag_params(&self, index: TagIndex) -> &'module_environment [WasmType] {
+        self.types.wasm_signatures[self.module.tags[index]].params()
+    }
+
+    /// Returns whether calls made by the wasm instruction at `srcloc` are
+    /// inside a `try` block, which means that unwinding stops there for
+    /// exceptions they raise.
+    pub fn is_exception_handler(&self, srcloc: ir::SourceLoc) -> bool {
+        self.exception_handler_srclocs.contains(&srcloc)
+    }
+
+    /// Builds the argument list for a direct call to a locally-defined
+    /// function, where the callee's vmctx is the same as the caller's.
+    fn local_call_args(&self, pos: &FuncCursor<'_>, call_args: &[ir::Value]) -> Vec<ir::Value> {
+        let mut real_call_args = Vec::with_capacity(call_args.len() + 2);
+        let caller_vmctx = pos.func.special_param(ArgumentPurpose::VMContext).unwrap();
+
+        // First append the callee vmctx address, which is the same as the caller vmctx in
+        // this case.
+        real_call_args.push(caller_vmctx);
+
+        // Then append the caller vmctx address.
+        real_call_args.push(caller_vmctx);
+
+        // Then append the regular call arguments.
+        real_call_args.extend_from_slice(call_args);
+        real_call_args
+    }
+
+    /// Loads the address and vmctx of the imported function `callee_index`,
+    /// returning the address to call along with the full argument list.
+    fn imported_call_target(
+        &mut self,
+        pos: &mut FuncCursor<'_>,
+        callee_index: FuncIndex,
+        call_args: &[ir::Value],
+    ) -> (ir::Value, Vec<ir::Value>) {
+        let mut real_call_args = Vec::with_capacity(call_args.len() + 2);
+        let caller_vmctx = pos.func.special_param(ArgumentPurpose::VMContext).unwrap();
+        let pointer_type = self.pointer_type();
+        let vmctx = self.vmctx(&mut pos.func);
+        let base = pos.ins().global_value(pointer_type, vmctx);
+
+        let mem_flags = ir::MemFlags::trusted();
+
+        // Load the callee address.
+        let body_offset =
+            i32::try_from(self.offsets.vmctx_vmfunction_import_body(callee_index)).unwrap();
+        let func_addr = pos.ins().load(pointer_type, mem_flags, base, body_offset);
+
+        // First append the callee vmctx address.
+        let vmctx_offset =
+            i32::try_from(self.offsets.vmctx_vmfunction_import_vmctx(callee_index)).unwrap();
+        let vmctx = pos.ins().load(pointer_type, mem_flags, base, vmctx_offset);
+        real_call_args.push(vmctx);
+        real_call_args.push(caller_vmctx);
+
+        // Then append the regular call arguments.
+        real_call_args.extend_from_slice(call_args);
+
+        (func_addr, real_call_args)
+    }
+
+    /// Looks up `callee` in the table `table_index`, trapping if the entry is
+    /// null or, if necessary, doesn't have the signature `ty_index`. Returns
+    /// the address to call along with the full argument list.
+    fn indirect_call_target(
+        &mut self,
+        pos: &mut FuncCursor<'_>,
+        table_index: TableIndex,
+        table: ir::Table,
+        ty_index: TypeIndex,
+        callee: ir::Value,
+        call_args: &[ir::Value],
+    ) -> (ir::Value, Vec<ir::Value>) {
+        let pointer_type = self.pointer_type();
+
+        let table_entry_addr = pos.ins().table_addr(pointer_type, table, callee, 0);
+
+        // Dereference the table entry to get the pointer to the
+        // `VMCallerCheckedAnyfunc`.
+        let anyfunc_ptr =
+            pos.ins()
+                .load(pointer_type, ir::MemFlags::trusted(), table_entry_addr, 0);
+
+        // Check for whether the table element is null, and trap if so.
+        pos.ins()
+            .trapz(anyfunc_ptr, ir::TrapCode::IndirectCallToNull);
+
+        // Dereference anyfunc pointer to get the function address.
+        let mem_flags = ir::MemFlags::trusted();
+        let mut func_addr = pos.ins().load(
+            pointer_type,
+            mem_flags,
+            anyfunc_ptr,
+            i32::from(self.offsets.vmcaller_checked_anyfunc_func_ptr()),
+        );
+
+        // If necessary, check the signature.
+        match self.module.table_plans[table_index].style {
+            TableStyle::CallerChecksSignature => {
+                let sig_id_size = self.offsets.size_of_vmshared_signature_index();
+                let sig_id_type = Type::int(u16::from(sig_id_size) * 8).unwrap();
+                let vmctx = self.vmctx(pos.func);
+                let base = pos.ins().global_value(pointer_type, vmctx);
+                let offset =
+                    i32::try_from(self.offsets.vmctx_vmshared_signature_id(ty_index)).unwrap();
+
+                // Load the caller ID.
+                let mut mem_flags = ir::MemFlags::trusted();
+                mem_flags.set_readonly();
+                let caller_sig_id = pos.ins().load(sig_id_type, mem_flags, base, offset);
+
+                // Load the callee ID.
+                let mem_flags = ir::MemFlags::trusted();
+                let callee_sig_id = pos.ins().load(
+                    sig_id_type,
+                    mem_flags,
+                    anyfunc_ptr,
+                    i32::from(self.offsets.vmcaller_checked_anyfunc_type_index()),
+                );
+
+                // Check that they match.
+                let cmp = pos.ins().icmp(IntCC::Equal, callee_sig_id, caller_sig_id);
+                pos.ins().trapz(cmp, ir::TrapCode::BadSignature);
+
+                // If the check is mispredicted, make the speculative call go
+                // to a null address instead of a function of the wrong type.
+                if self.isa.flags().enable_table_access_spectre_mitigation() {
+                    let zero = pos.ins().iconst(pointer_type, 0);
+                    let flags = pos.ins().ifcmp(callee_sig_id, caller_sig_id);
+                    func_addr = pos.ins().selectif_spectre_guard(
+                        pointer_type,
+                        IntCC::NotEqual,
+                        flags,
+                        zero,
+                        func_addr,
+                    );
+                }
+            }
+        }
+
+        let mut real_call_args = Vec::with_capacity(call_args.len() + 2);
+        let caller_vmctx = pos.func.special_param(ArgumentPurpose::VMContext).unwrap();
+
+        // First append the callee vmctx address.
+        let vmctx = pos.ins().load(
+            pointer_type,
+            mem_flags,
+            anyfunc_ptr,
+            i32::from(self.offsets.vmcaller_checked_anyfunc_vmctx()),
+        );
+        real_call_args.push(vmctx);
+        real_call_args.push(caller_vmctx);
+
+        // Then append the regular call arguments.
+        real_call_args.extend_from_slice(call_args);
+
+        (func_addr, real_call_args)
+    }
+
     fn vmctx(&mut self, func: &mut Function) -> ir::GlobalValue {
         self.vmctx.unwrap_or_else(|| {
             let vmctx = func.create_global_value(ir::GlobalValueData::VMContext);
@@ -382,7 +596,11 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
             | Operator::Unreachable
             | Operator::Return
             | Operator::Else
-            | Operator::End => 0,
+            | Operator::End
+            | Operator::Try { .. }
+            | Operator::Catch { .. }
+            | Operator::CatchAll
+            | Operator::Delegate { .. } => 0,
 
             // everything else, just call it one operation.
             _ => 1,
@@ -403,7 +621,9 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
             | Operator::CallIndirect { .. }
             | Operator::Call { .. }
             | Operator::ReturnCall { .. }
-            | Operator::ReturnCallIndirect { .. } => {
+            | Operator::ReturnCallIndirect { .. }
+            | Operator::Throw { .. }
+            | Operator::Rethrow { .. } => {
                 self.fuel_increment_var(builder);
                 self.fuel_save_from_var(builder);
             }
@@ -427,8 +647,12 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
             // Exiting a scope means that we need to update the fuel
             // consumption because there are multiple ways to exit a scope and
             // this is the only time we have to account for instructions
-            // executed so far.
+            // executed so far. The same goes for the clauses and the
+            // `delegate` of a `try` block.
             | Operator::End
+            | Operator::Catch { .. }
+            | Operator::CatchAll
+            | Operator::Delegate { .. }
 
             // This is similar to `end`, except that it's only the terminator
             // for an `if` block. The same reasoning applies though in that we
@@ -457,6 +681,232 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
         }
     }
 
+    fn coredump_function_entry(&mut self, builder: &mut FunctionBuilder<'_>) {
+        // Each frame keeps a `VMCoreDumpFrame` in a stack slot, followed by
+        // the values of its locals and then of its operand stack, and links
+        // it into the list in `VMInterrupts` so the runtime can read it if
+        // the frame traps. The slot is sized in `coredump_function_exit` once
+        // the deepest operand stack recorded is known.
+        let slot =
+            builder.create_stack_slot(ir::StackSlotData::new(ir::StackSlotKind::ExplicitSlot, 0));
+        self.coredump_slot = Some(slot);
+        self.coredump_max_stack = 0;
+
+        let pointer_type = self.pointer_type();
+        let interrupts = builder.use_var(self.vminterrupts_ptr);
+        let prev = builder.ins().load(
+            pointer_type,
+            ir::MemFlags::trusted(),
+            interrupts,
+            i32::from(self.offsets.vminterrupts_coredump_frames()),
+        );
+        builder
+            .ins()
+            .stack_store(prev, slot, i32::from(self.offsets.vmcoredump_frame_prev()));
+        let vmctx = self.vmctx(builder.func);
+        let vmctx = builder.ins().global_value(pointer_type, vmctx);
+        builder.ins().stack_store(
+            vmctx,
+            slot,
+            i32::from(self.offsets.vmcoredump_frame_vmctx()),
+        );
+        for (offset, value) in [
+            (
+                self.offsets.vmcoredump_frame_func_index(),
+                self.func_index.as_u32(),
+            ),
+            (
+                self.offsets.vmcoredump_frame_num_locals(),
+                u32::try_from(self.num_locals).unwrap(),
+            ),
+            (self.offsets.vmcoredump_frame_num_stack(), 0),
+        ] {
+            let value = builder.ins().iconst(I32, i64::from(value));
+            builder.ins().stack_store(value, slot, i32::from(offset));
+        }
+
+        // The types of locals never change, so they're only stored once.
+        for i in 0..self.num_locals {
+            let value = builder.use_var(Variable::new(i));
+            self.coredump_store_value(builder, i, value);
+            self.coredump_store_type(builder, i, value);
+        }
+
+        self.coredump_link(builder);
+    }
+
+    fn coredump_function_exit(&mut self, builder: &mut FunctionBuilder<'_>, reachable: bool) {
+        if reachable {
+            self.coredump_unlink(builder);
+        }
+        let slot = self.coredump_slot.unwrap();
+        let entries = self.num_locals + self.coredump_max_stack;
+        let size = usize::from(self.offsets.size_of_vmcoredump_frame())
+            + entries * usize::from(self.offsets.size_of_vmcoredump_value());
+        builder.func.stack_slots[slot].size = u32::try_from(size).unwrap();
+    }
+
+    fn coredump_before_op(
+        &mut self,
+        op: &Operator<'_>,
+        builder: &mut FunctionBuilder<'_>,
+        state: &FuncTranslationState,
+    ) {
+        match op {
+            // Leaving the function, so the frame is no longer on the stack.
+            Operator::Return
+            | Operator::ReturnCall { .. }
+            | Operator::ReturnCallIndirect { .. } => self.coredump_unlink(builder),
+
+            // These can neither trap nor call other functions, so the operand
+            // stack doesn't need to be recorded for them.
+            Operator::Nop
+            | Operator::Drop
+            | Operator::Block { .. }
+            | Operator::If { .. }
+            | Operator::Else
+            | Operator::End
+            | Operator::Br { .. }
+            | Operator::BrIf { .. }
+            | Operator::BrTable { .. }
+            | Operator::LocalGet { .. }
+            | Operator::LocalSet { .. }
+            | Operator::LocalTee { .. }
+            | Operator::I32Const { .. }
+            | Operator::I64Const { .. }
+            | Operator::F32Const { .. }
+            | Operator::F64Const { .. }
+            | Operator::Select
+            | Operator::TypedSelect { .. } => {}
+
+            // Everything else records the operand stack first, which is how
+            // the stack of a frame is known wherever it traps. The arguments
+            // of calls belong to the callee's frame once it's entered, so
+            // they're left out.
+            _ => {
+                let args = match op {
+                    Operator::Call { function_index } => {
+                        let index = self.module.functions[FuncIndex::from_u32(*function_index)];
+                        self.types.wasm_signatures[index].params().len()
+                    }
+                    Operator::CallIndirect { index, .. } => {
+                        let index =
+                            self.module.types[TypeIndex::from_u32(*index)].unwrap_function();
+                        self.types.wasm_signatures[index].params().len() + 1
+                    }
+                    _ => 0,
+                };
+                let stack = state.stack();
+                let stack = &stack[..stack.len() - args];
+                for (i, value) in stack.iter().enumerate() {
+                    self.coredump_store_value(builder, self.num_locals + i, *value);
+                    self.coredump_store_type(builder, self.num_locals + i, *value);
+                }
+                let height = builder.ins().iconst(I32, stack.len() as i64);
+                builder.ins().stack_store(
+                    height,
+                    self.coredump_slot.unwrap(),
+                    i32::from(self.offsets.vmcoredump_frame_num_stack()),
+                );
+                self.coredump_max_stack = self.coredump_max_stack.max(stack.len());
+            }
+        }
+    }
+
+    fn coredump_after_op(&mut self, op: &Operator<'_>, builder: &mut FunctionBuilder<'_>) {
+        match op {
+            Operator::LocalSet { local_index } | Operator::LocalTee { local_index } => {
+                let value = builder.use_var(Variable::with_u32(*local_index));
+                self.coredump_store_value(builder, *local_index as usize, value);
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the type of `value` as recorded for core dumps, which is its
+    /// type's wasm binary encoding or zero for types which aren't recorded.
+    fn coredump_value_type(&self, builder: &FunctionBuilder<'_>, value: ir::Value) -> u8 {
+        // Note that `funcref` values are pointers which can't be told apart
+        // from `i64` values.
+        match builder.func.dfg.value_type(value) {
+            I32 => 0x7f,
+            I64 => 0x7e,
+            F32 => 0x7d,
+            F64 => 0x7c,
+            _ => 0,
+        }
+    }
+
+    /// Returns the offset in `coredump_slot` of the `index`th value following
+    /// the `VMCoreDumpFrame`.
+    fn coredump_value_offset(&self, index: usize) -> i32 {
+        let offset = usize::from(self.offsets.size_of_vmcoredump_frame())
+            + index * usize::from(self.offsets.size_of_vmcoredump_value());
+        i32::try_from(offset).unwrap()
+    }
+
+    fn coredump_store_value(
+        &mut self,
+        builder: &mut FunctionBuilder<'_>,
+        index: usize,
+        value: ir::Value,
+    ) {
+        if self.coredump_value_type(builder, value) != 0 {
+            let offset = self.coredump_value_offset(index);
+            builder
+                .ins()
+                .stack_store(value, self.coredump_slot.unwrap(), offset);
+        }
+    }
+
+    fn coredump_store_type(
+        &mut self,
+        builder: &mut FunctionBuilder<'_>,
+        index: usize,
+        value: ir::Value,
+    ) {
+        let ty = self.coredump_value_type(builder, value);
+        let ty = builder.ins().iconst(I8, i64::from(ty));
+        let offset =
+            self.coredump_value_offset(index) + i32::from(self.offsets.vmcoredump_value_type());
+        builder
+            .ins()
+            .stack_store(ty, self.coredump_slot.unwrap(), offset);
+    }
+
+    /// Makes this frame's record the youngest in `VMInterrupts`.
+    fn coredump_link(&mut self, builder: &mut FunctionBuilder<'_>) {
+        let pointer_type = self.pointer_type();
+        let record = builder
+            .ins()
+            .stack_addr(pointer_type, self.coredump_slot.unwrap(), 0);
+        let interrupts = builder.use_var(self.vminterrupts_ptr);
+        builder.ins().store(
+            ir::MemFlags::trusted(),
+            record,
+            interrupts,
+            i32::from(self.offsets.vminterrupts_coredump_frames()),
+        );
+    }
+
+    /// Restores the record which was the youngest when this frame was
+    /// entered.
+    fn coredump_unlink(&mut self, builder: &mut FunctionBuilder<'_>) {
+        let pointer_type = self.pointer_type();
+        let prev = builder.ins().stack_load(
+            pointer_type,
+            self.coredump_slot.unwrap(),
+            i32::from(self.offsets.vmcoredump_frame_prev()),
+        );
+        let interrupts = builder.use_var(self.vminterrupts_ptr);
+        builder.ins().store(
+            ir::MemFlags::trusted(),
+            prev,
+            interrupts,
+            i32::from(self.offsets.vminterrupts_coredump_frames()),
+        );
+    }
+
     fn fuel_after_op(&mut self, op: &Operator<'_>, builder: &mut FunctionBuilder<'_>) {
         // After a function call we need to reload our fuel value since the
         // function may have changed it.
@@ -558,6 +1008,114 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
         builder.switch_to_block(continuation_block);
     }
 
+    fn epoch_function_entry(&mut self, builder: &mut FunctionBuilder<'_>) {
+        // On function entry we cache both the epoch deadline and the pointer
+        // to the engine's epoch counter in function-local variables so loop
+        // headers only need a single load and compare in the common case.
+        builder.declare_var(self.epoch_deadline_var, ir::types::I64);
+        self.epoch_load_deadline_into_var(builder);
+        builder.declare_var(self.epoch_ptr_var, self.pointer_type());
+        let epoch_ptr = self.epoch_ptr(builder);
+        builder.def_var(self.epoch_ptr_var, epoch_ptr);
+
+        // Checks at loop headers alone aren't enough to bound the time
+        // between checks: a tree of calls without any loops can still run for
+        // exponentially long. Checking on every function entry as well bounds
+        // it by the straight-line length of any function body.
+        self.epoch_check(builder);
+    }
+
+    /// Loads the pointer to the engine's epoch counter out of the vmctx.
+    fn epoch_ptr(&mut self, builder: &mut FunctionBuilder<'_>) -> ir::Value {
+        let vmctx = self.vmctx(builder.func);
+        let pointer_type = self.pointer_type();
+        let base = builder.ins().global_value(pointer_type, vmctx);
+        let offset = i32::try_from(self.offsets.vmctx_epoch_ptr()).unwrap();
+        builder
+            .ins()
+            .load(pointer_type, ir::MemFlags::trusted(), base, offset)
+    }
+
+    /// Loads the current value of the engine's epoch counter.
+    fn epoch_load_current(&mut self, builder: &mut FunctionBuilder<'_>) -> ir::Value {
+        let addr = builder.use_var(self.epoch_ptr_var);
+        builder.ins().load(
+            ir::types::I64,
+            ir::MemFlags::trusted(),
+            addr,
+            ir::immediates::Offset32::new(0),
+        )
+    }
+
+    /// Loads the store's epoch deadline from `VMInterrupts` into
+    /// `self.epoch_deadline_var`.
+    fn epoch_load_deadline_into_var(&mut self, builder: &mut FunctionBuilder<'_>) {
+        let interrupts = builder.use_var(self.vminterrupts_ptr);
+        let deadline = builder.ins().load(
+            ir::types::I64,
+            ir::MemFlags::trusted(),
+            interrupts,
+            ir::immediates::Offset32::new(i32::from(self.offsets.vminterrupts_epoch_deadline())),
+        );
+        builder.def_var(self.epoch_deadline_var, deadline);
+    }
+
+    /// Checks whether the current epoch has reached the deadline, and if so
+    /// calls the new-epoch intrinsic.
+    fn epoch_check(&mut self, builder: &mut FunctionBuilder<'_>) {
+        let new_epoch_block = builder.create_block();
+        let new_epoch_doublecheck_block = builder.create_block();
+        let continuation_block = builder.create_block();
+
+        // Compare the current epoch against our cached deadline. The cached
+        // value may be out of date if a function we called updated the
+        // deadline, but that's fine since it's reloaded below before we
+        // actually call out to the runtime.
+        let epoch_deadline = builder.use_var(self.epoch_deadline_var);
+        let cur_epoch_value = self.epoch_load_current(builder);
+        let cmp = builder.ins().ifcmp(cur_epoch_value, epoch_deadline);
+        builder
+            .ins()
+            .brif(IntCC::UnsignedGreaterThanOrEqual, cmp, new_epoch_block, &[]);
+        builder.ins().jump(continuation_block, &[]);
+        builder.seal_block(new_epoch_block);
+
+        // The epoch has reached our cached deadline, but the real deadline
+        // may have moved in the meantime, so do a precise check against the
+        // latest value.
+        builder.switch_to_block(new_epoch_block);
+        self.epoch_load_deadline_into_var(builder);
+        let fresh_epoch_deadline = builder.use_var(self.epoch_deadline_var);
+        let fresh_cmp = builder.ins().ifcmp(cur_epoch_value, fresh_epoch_deadline);
+        builder.ins().brif(
+            IntCC::UnsignedGreaterThanOrEqual,
+            fresh_cmp,
+            new_epoch_doublecheck_block,
+            &[],
+        );
+        builder.ins().jump(continuation_block, &[]);
+        builder.seal_block(new_epoch_doublecheck_block);
+
+        // The deadline really has been reached, so call the intrinsic which
+        // either traps or yields. It returns the new deadline so there's no
+        // need to reload it afterwards.
+        builder.switch_to_block(new_epoch_doublecheck_block);
+        let new_epoch_sig = self.builtin_function_signatures.new_epoch(builder.func);
+        let (vmctx, new_epoch) = self.translate_load_builtin_function_address(
+            &mut builder.cursor(),
+            BuiltinFunctionIndex::new_epoch(),
+        );
+        let call = builder
+            .ins()
+            .call_indirect(new_epoch_sig, new_epoch, &[vmctx]);
+        let new_deadline = *builder.func.dfg.inst_results(call).first().unwrap();
+        builder.def_var(self.epoch_deadline_var, new_deadline);
+        builder.ins().jump(continuation_block, &[]);
+        builder.seal_block(continuation_block);
+
+        builder.switch_to_block(continuation_block);
+    }
+
     fn memory_index_type(&self, index: MemoryIndex) -> ir::Type {
         if self.module.memory_plans[index].memory.memory64 {
             I64
@@ -631,8 +1189,11 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
     }
 
     fn after_locals(&mut self, num_locals: usize) {
+        self.num_locals = num_locals;
         self.vminterrupts_ptr = Variable::new(num_locals);
         self.fuel_var = Variable::new(num_locals + 1);
+        self.epoch_deadline_var = Variable::new(num_locals + 2);
+        self.epoch_ptr_var = Variable::new(num_locals + 3);
     }
 
     fn make_table(&mut self, func: &mut ir::Function, index: TableIndex) -> WasmResult<ir::Table> {
@@ -1282,7 +1843,11 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         index: TypeIndex,
     ) -> WasmResult<ir::SigRef> {
         let index = self.module.types[index].unwrap_function();
-        let sig = crate::indirect_signature(self.isa, &self.types.wasm_signatures[index]);
+        let sig = crate::indirect_signature(
+            self.isa,
+            &self.types.wasm_signatures[index],
+            self.tail_calls,
+        );
         Ok(func.import_signature(sig))
     }
 
@@ -1291,7 +1856,13 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         func: &mut ir::Function,
         index: FuncIndex,
     ) -> WasmResult<ir::FuncRef> {
-        let sig = crate::func_signature(self.isa, self.translation, self.types, index);
+        let sig = crate::func_signature(
+            self.isa,
+            self.translation,
+            self.types,
+            index,
+            self.tail_calls,
+        );
         let signature = func.import_signature(sig);
         let name = get_func_name(index);
         Ok(func.import_function(ir::ExtFuncData {
@@ -1326,75 +1897,8 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         callee: ir::Value,
         call_args: &[ir::Value],
     ) -> WasmResult<ir::Inst> {
-        let pointer_type = self.pointer_type();
-
-        let table_entry_addr = pos.ins().table_addr(pointer_type, table, callee, 0);
-
-        // Dereference the table entry to get the pointer to the
-        // `VMCallerCheckedAnyfunc`.
-        let anyfunc_ptr =
-            pos.ins()
-                .load(pointer_type, ir::MemFlags::trusted(), table_entry_addr, 0);
-
-        // Check for whether the table element is null, and trap if so.
-        pos.ins()
-            .trapz(anyfunc_ptr, ir::TrapCode::IndirectCallToNull);
-
-        // Dereference anyfunc pointer to get the function address.
-        let mem_flags = ir::MemFlags::trusted();
-        let func_addr = pos.ins().load(
-            pointer_type,
-            mem_flags,
-            anyfunc_ptr,
-            i32::from(self.offsets.vmcaller_checked_anyfunc_func_ptr()),
-        );
-
-        // If necessary, check the signature.
-        match self.module.table_plans[table_index].style {
-            TableStyle::CallerChecksSignature => {
-                let sig_id_size = self.offsets.size_of_vmshared_signature_index();
-                let sig_id_type = Type::int(u16::from(sig_id_size) * 8).unwrap();
-                let vmctx = self.vmctx(pos.func);
-                let base = pos.ins().global_value(pointer_type, vmctx);
-                let offset =
-                    i32::try_from(self.offsets.vmctx_vmshared_signature_id(ty_index)).unwrap();
-
-                // Load the caller ID.
-                let mut mem_flags = ir::MemFlags::trusted();
-                mem_flags.set_readonly();
-                let caller_sig_id = pos.ins().load(sig_id_type, mem_flags, base, offset);
-
-                // Load the callee ID.
-                let mem_flags = ir::MemFlags::trusted();
-                let callee_sig_id = pos.ins().load(
-                    sig_id_type,
-                    mem_flags,
-                    anyfunc_ptr,
-                    i32::from(self.offsets.vmcaller_checked_anyfunc_type_index()),
-                );
-
-                // Check that they match.
-                let cmp = pos.ins().icmp(IntCC::Equal, callee_sig_id, caller_sig_id);
-                pos.ins().trapz(cmp, ir::TrapCode::BadSignature);
-            }
-        }
-
-        let mut real_call_args = Vec::with_capacity(call_args.len() + 2);
-        let caller_vmctx = pos.func.special_param(ArgumentPurpose::VMContext).unwrap();
-
-        // First append the callee vmctx address.
-        let vmctx = pos.ins().load(
-            pointer_type,
-            mem_flags,
-            anyfunc_ptr,
-            i32::from(self.offsets.vmcaller_checked_anyfunc_vmctx()),
-        );
-        real_call_args.push(vmctx);
-        real_call_args.push(caller_vmctx);
-
-        // Then append the regular call arguments.
-        real_call_args.extend_from_slice(call_args);
-
+        let (func_addr, real_call_args) =
+            self.indirect_call_target(&mut pos, table_index, table, ty_index, callee, call_args);
         Ok(pos.ins().call_indirect(sig_ref, func_addr, &real_call_args))
     }
 
@@ -1405,49 +1909,56 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         callee: ir::FuncRef,
         call_args: &[ir::Value],
     ) -> WasmResult<ir::Inst> {
-        let mut real_call_args = Vec::with_capacity(call_args.len() + 2);
-        let caller_vmctx = pos.func.special_param(ArgumentPurpose::VMContext).unwrap();
-
         // Handle direct calls to locally-defined functions.
         if !self.module.is_imported_function(callee_index) {
-            // First append the callee vmctx address, which is the same as the caller vmctx in
-            // this case.
-            real_call_args.push(caller_vmctx);
-
-            // Then append the caller vmctx address.
-            real_call_args.push(caller_vmctx);
-
-            // Then append the regular call arguments.
-            real_call_args.extend_from_slice(call_args);
-
+            let real_call_args = self.local_call_args(&pos, call_args);
             return Ok(pos.ins().call(callee, &real_call_args));
         }
 
         // Handle direct calls to imported functions. We use an indirect call
         // so that we don't have to patch the code at runtime.
-        let pointer_type = self.pointer_type();
         let sig_ref = pos.func.dfg.ext_funcs[callee].signature;
-        let vmctx = self.vmctx(&mut pos.func);
-        let base = pos.ins().global_value(pointer_type, vmctx);
-
-        let mem_flags = ir::MemFlags::trusted();
-
-        // Load the callee address.
-        let body_offset =
-            i32::try_from(self.offsets.vmctx_vmfunction_import_body(callee_index)).unwrap();
-        let func_addr = pos.ins().load(pointer_type, mem_flags, base, body_offset);
+        let (func_addr, real_call_args) =
+            self.imported_call_target(&mut pos, callee_index, call_args);
+        Ok(pos.ins().call_indirect(sig_ref, func_addr, &real_call_args))
+    }
 
-        // First append the callee vmctx address.
-        let vmctx_offset =
-            i32::try_from(self.offsets.vmctx_vmfunction_import_vmctx(callee_index)).unwrap();
-        let vmctx = pos.ins().load(pointer_type, mem_flags, base, vmctx_offset);
-        real_call_args.push(vmctx);
-        real_call_args.push(caller_vmctx);
+    fn translate_return_call_indirect(
+        &mut self,
+        mut pos: FuncCursor<'_>,
+        table_index: TableIndex,
+        table: ir::Table,
+        ty_index: TypeIndex,
+        sig_ref: ir::SigRef,
+        callee: ir::Value,
+        call_args: &[ir::Value],
+    ) -> WasmResult<()> {
+        let (func_addr, real_call_args) =
+            self.indirect_call_target(&mut pos, table_index, table, ty_index, callee, call_args);
+        pos.ins()
+            .return_call_indirect(sig_ref, func_addr, &real_call_args);
+        Ok(())
+    }
 
-        // Then append the regular call arguments.
-        real_call_args.extend_from_slice(call_args);
+    fn translate_return_call(
+        &mut self,
+        mut pos: FuncCursor<'_>,
+        callee_index: FuncIndex,
+        callee: ir::FuncRef,
+        call_args: &[ir::Value],
+    ) -> WasmResult<()> {
+        if !self.module.is_imported_function(callee_index) {
+            let real_call_args = self.local_call_args(&pos, call_args);
+            pos.ins().return_call(callee, &real_call_args);
+            return Ok(());
+        }
 
-        Ok(pos.ins().call_indirect(sig_ref, func_addr, &real_call_args))
+        let sig_ref = pos.func.dfg.ext_funcs[callee].signature;
+        let (func_addr, real_call_args) =
+            self.imported_call_target(&mut pos, callee_index, call_args);
+        pos.ins()
+            .return_call_indirect(sig_ref, func_addr, &real_call_args);
+        Ok(())
     }
 
     fn translate_memory_grow(
@@ -1482,6 +1993,23 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         index: MemoryIndex,
         _heap: ir::Heap,
     ) -> WasmResult<ir::Value> {
+        // The length of a shared memory can be changed at any time by other
+        // threads, and only the runtime's `SharedMemory` knows it for sure, so
+        // ask the runtime rather than reading a `VMMemoryDefinition`.
+        if self.module.memory_plans[index].memory.shared {
+            let func_sig = self.builtin_function_signatures.memory_size(&mut pos.func);
+            let memory_index = pos.ins().iconst(I32, index.index() as i64);
+            let (vmctx, func_addr) = self.translate_load_builtin_function_address(
+                &mut pos,
+                BuiltinFunctionIndex::memory_size(),
+            );
+            let call_inst = pos
+                .ins()
+                .call_indirect(func_sig, func_addr, &[vmctx, memory_index]);
+            let result = *pos.func.dfg.inst_results(call_inst).first().unwrap();
+            return Ok(self.cast_pointer_to_memory_index(pos, result, index));
+        }
+
         let pointer_type = self.pointer_type();
         let vmctx = self.vmctx(&mut pos.func);
         let base = pos.ins().global_value(pointer_type, vmctx);
@@ -1755,6 +2283,178 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         Ok(*pos.func.dfg.inst_results(call_inst).first().unwrap())
     }
 
+    fn translate_exception_pending(
+        &mut self,
+        builder: &mut FunctionBuilder,
+    ) -> WasmResult<ir::Value> {
+        // Calls are only known by their source location once they're compiled,
+        // so record that of the call we're checking. All other calls made by
+        // the same wasm instruction are to builtins which don't throw.
+        let block = builder.current_block().unwrap();
+        let call = builder.func.layout.last_inst(block).unwrap();
+        debug_assert!(builder.func.dfg[call].opcode().is_call());
+        let srcloc = builder.func.srclocs[call];
+        if self.exception_handler_srclocs.last() != Some(&srcloc) {
+            self.exception_handler_srclocs.push(srcloc);
+        }
+
+        // The callee may have consumed fuel before throwing, and the handler
+        // we may be about to branch to continues counting from our cached
+        // value, so refresh it first.
+        if self.tunables.consume_fuel {
+            self.fuel_load_into_var(builder);
+        }
+        // Likewise the frames the exception was unwound past never unlinked
+        // their core dump records, and the handler continues in this frame.
+        if self.tunables.coredump_on_trap {
+            self.coredump_link(builder);
+        }
+        let interrupts = builder.use_var(self.vminterrupts_ptr);
+        Ok(builder.ins().load(
+            self.pointer_type(),
+            ir::MemFlags::trusted(),
+            interrupts,
+            i32::from(self.offsets.vminterrupts_pending_exception()),
+        ))
+    }
+
+    fn translate_throw(
+        &mut self,
+        mut pos: FuncCursor,
+        tag_index: TagIndex,
+        args: &[ir::Value],
+    ) -> WasmResult<()> {
+        let params = self.tag_params(tag_index);
+        debug_assert_eq!(params.len(), args.len());
+
+        // The values carried by the exception are passed to the runtime in a
+        // buffer of `ValRaw`s, like the arguments of host functions. Reference
+        // values are then repeated at the end of the buffer so that the
+        // runtime, which doesn't know the tag's type, can take ownership of
+        // them.
+        let refs = args
+            .iter()
+            .zip(params)
+            .filter(|(_, ty)| matches!(ty, WasmType::ExternRef | WasmType::ExnRef))
+            .map(|(arg, _)| *arg)
+            .collect::<Vec<_>>();
+        let pointer_type = self.pointer_type();
+        let values = if args.is_empty() {
+            pos.ins().iconst(pointer_type, 0)
+        } else {
+            let ss = pos.func.create_stack_slot(ir::StackSlotData::new(
+                ir::StackSlotKind::ExplicitSlot,
+                (VALUE_SIZE * (args.len() + refs.len())) as u32,
+            ));
+            let values = pos.ins().stack_addr(pointer_type, ss, 0);
+            for (i, arg) in args.iter().chain(&refs).enumerate() {
+                pos.ins().store(
+                    ir::MemFlags::trusted(),
+                    *arg,
+                    values,
+                    (i * VALUE_SIZE) as i32,
+                );
+            }
+            values
+        };
+
+        let builtin_sig = self.builtin_function_signatures.throw(&mut pos.func);
+        let (vmctx, builtin_addr) =
+            self.translate_load_builtin_function_address(&mut pos, BuiltinFunctionIndex::throw());
+        let tag_index_arg = pos.ins().iconst(I32, tag_index.as_u32() as i64);
+        let len_arg = pos.ins().iconst(I32, args.len() as i64);
+        let refs_len_arg = pos.ins().iconst(I32, refs.len() as i64);
+        pos.ins().call_indirect(
+            builtin_sig,
+            builtin_addr,
+            &[vmctx, tag_index_arg, values, len_arg, refs_len_arg],
+        );
+        Ok(())
+    }
+
+    fn translate_rethrow(&mut self, mut pos: FuncCursor, exception: ir::Value) -> WasmResult<()> {
+        let builtin_sig = self.builtin_function_signatures.rethrow(&mut pos.func);
+        let (vmctx, builtin_addr) =
+            self.translate_load_builtin_function_address(&mut pos, BuiltinFunctionIndex::rethrow());
+        pos.ins()
+            .call_indirect(builtin_sig, builtin_addr, &[vmctx, exception]);
+        Ok(())
+    }
+
+    fn translate_resume_unwind(&mut self, mut pos: FuncCursor) -> WasmResult<()> {
+        let builtin_sig = self
+            .builtin_function_signatures
+            .resume_unwind(&mut pos.func);
+        let (vmctx, builtin_addr) = self.translate_load_builtin_function_address(
+            &mut pos,
+            BuiltinFunctionIndex::resume_unwind(),
+        );
+        pos.ins().call_indirect(builtin_sig, builtin_addr, &[vmctx]);
+        Ok(())
+    }
+
+    fn translate_exception_matches(
+        &mut self,
+        mut pos: FuncCursor,
+        tag_index: TagIndex,
+    ) -> WasmResult<ir::Value> {
+        let builtin_sig = self
+            .builtin_function_signatures
+            .exception_matches(&mut pos.func);
+        let (vmctx, builtin_addr) = self.translate_load_builtin_function_address(
+            &mut pos,
+            BuiltinFunctionIndex::exception_matches(),
+        );
+        let tag_index_arg = pos.ins().iconst(I32, tag_index.as_u32() as i64);
+        let call_inst = pos
+            .ins()
+            .call_indirect(builtin_sig, builtin_addr, &[vmctx, tag_index_arg]);
+        Ok(pos.func.dfg.first_result(call_inst))
+    }
+
+    fn translate_catch(
+        &mut self,
+        mut pos: FuncCursor,
+        tag_index: Option<TagIndex>,
+    ) -> WasmResult<(ir::Value, Vec<ir::Value>)> {
+        let params = match tag_index {
+            Some(tag_index) => self.tag_params(tag_index).to_vec(),
+            None => Vec::new(),
+        };
+
+        let pointer_type = self.pointer_type();
+        let (values, ss) = if params.is_empty() {
+            (pos.ins().iconst(pointer_type, 0), None)
+        } else {
+            let ss = pos.func.create_stack_slot(ir::StackSlotData::new(
+                ir::StackSlotKind::ExplicitSlot,
+                (VALUE_SIZE * params.len()) as u32,
+            ));
+            (pos.ins().stack_addr(pointer_type, ss, 0), Some(ss))
+        };
+
+        let builtin_sig = self
+            .builtin_function_signatures
+            .catch_exception(&mut pos.func);
+        let (vmctx, builtin_addr) = self.translate_load_builtin_function_address(
+            &mut pos,
+            BuiltinFunctionIndex::catch_exception(),
+        );
+        let call_inst = pos
+            .ins()
+            .call_indirect(builtin_sig, builtin_addr, &[vmctx, values]);
+        let exception = pos.func.dfg.first_result(call_inst);
+
+        let mut results = Vec::with_capacity(params.len());
+        if let Some(ss) = ss {
+            for (i, ty) in params.iter().enumerate() {
+                let ty = crate::value_type(self.isa, *ty);
+                results.push(pos.ins().stack_load(ty, ss, (i * VALUE_SIZE) as i32));
+            }
+        }
+        Ok((exception, results))
+    }
+
     fn translate_loop_header(&mut self, builder: &mut FunctionBuilder) -> WasmResult<()> {
         // If enabled check the interrupt flag to prevent long or infinite
         // loops.
@@ -1787,6 +2487,12 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
             self.fuel_check(builder);
         }
 
+        // If we are performing epoch-based interruption, check to see
+        // if the epoch counter has changed.
+        if self.tunables.epoch_interruption {
+            self.epoch_check(builder);
+        }
+
         Ok(())
     }
 
@@ -1796,6 +2502,9 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         builder: &mut FunctionBuilder,
         state: &FuncTranslationState,
     ) -> WasmResult<()> {
+        if self.tunables.coredump_on_trap && state.reachable() {
+            self.coredump_before_op(op, builder, state);
+        }
         if self.tunables.consume_fuel {
             self.fuel_before_op(op, builder, state.reachable());
         }
@@ -1811,6 +2520,9 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         if self.tunables.consume_fuel && state.reachable() {
             self.fuel_after_op(op, builder);
         }
+        if self.tunables.coredump_on_trap && state.reachable() {
+            self.coredump_after_op(op, builder);
+        }
         Ok(())
     }
 
@@ -1821,13 +2533,26 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
     ) -> WasmResult<()> {
         // If the `vminterrupts_ptr` variable will get used then we initialize
         // it here.
-        if self.tunables.consume_fuel || self.tunables.interruptable {
+        if self.tunables.consume_fuel
+            || self.tunables.interruptable
+            || self.tunables.epoch_interruption
+            || self.tunables.coredump_on_trap
+            || self.exceptions
+        {
             self.declare_vminterrupts_ptr(builder);
         }
+        // Record the frame for core dumps before anything which may trap.
+        if self.tunables.coredump_on_trap {
+            self.coredump_function_entry(builder);
+        }
         // Additionally we initialize `fuel_var` if it will get used.
         if self.tunables.consume_fuel {
             self.fuel_function_entry(builder);
         }
+        // Initialize the epoch variables and check the deadline on entry.
+        if self.tunables.epoch_interruption {
+            self.epoch_function_entry(builder);
+        }
         Ok(())
     }
 
@@ -1839,6 +2564,9 @@ impl<'module_environment> cranelift_wasm::FuncEnvironment for FuncEnvironment<'m
         if self.tunables.consume_fuel && state.reachable() {
             self.fuel_function_exit(builder);
         }
+        if self.tunables.coredump_on_trap {
+            self.coredump_function_exit(builder, state.reachable());
+        }
         Ok(())
     }
 
@@ -208,6 +208,23 @@ fn wasmtime_call_conv(isa: &dyn TargetIsa) -> CallConv {
     }
 }
 
+/// Returns the calling convention used for wasm functions which may be called
+/// from outside of their module.
+///
+/// This is the default calling convention for `isa` unless the tail call
+/// proposal is enabled, in which case all wasm functions use the `tail`
+/// calling convention so that they can perform tail calls to each other. It
+/// matches the default convention except that functions pop their own stack
+/// arguments, so host code only calls wasm functions which take all of their
+/// arguments in registers directly, and uses trampolines for the rest.
+fn wasm_call_conv(isa: &dyn TargetIsa, tail_calls: bool) -> CallConv {
+    if tail_calls {
+        CallConv::Tail
+    } else {
+        wasmtime_call_conv(isa)
+    }
+}
+
 /// Appends the types of the `wasm` function signature into the `sig` signature
 /// provided.
 ///
@@ -239,8 +256,8 @@ fn value_type(isa: &dyn TargetIsa, ty: WasmType) -> ir::types::Type {
 /// indirectly call a wasm function it must be possibly exported somehow (e.g.
 /// this assumes the function target to call doesn't use the "fast" calling
 /// convention).
-fn indirect_signature(isa: &dyn TargetIsa, wasm: &WasmFuncType) -> ir::Signature {
-    let mut sig = blank_sig(isa, wasmtime_call_conv(isa));
+fn indirect_signature(isa: &dyn TargetIsa, wasm: &WasmFuncType, tail_calls: bool) -> ir::Signature {
+    let mut sig = blank_sig(isa, wasm_call_conv(isa, tail_calls));
     push_types(isa, &mut sig, wasm);
     return sig;
 }
@@ -255,8 +272,13 @@ fn func_signature(
     translation: &ModuleTranslation,
     types: &TypeTables,
     index: FuncIndex,
+    tail_calls: bool,
 ) -> ir::Signature {
     let call_conv = match translation.module.defined_func_index(index) {
+        // Tail calls require the caller and callee to agree on the calling
+        // convention, so every function uses the same one.
+        _ if tail_calls => CallConv::Tail,
+
         // If this is a defined function in the module and it's never possibly
         // exported, then we can optimize this function to use the fastest
         // calling convention since it's purely an internal implementation
@@ -5,6 +5,9 @@ macro_rules! foreach_builtin_function {
         $mac! {
             /// Returns an index for wasm's `memory.grow` builtin function.
             memory32_grow(vmctx, i64, i32) -> (pointer);
+            /// Returns an index for wasm's `memory.size` builtin function, used
+            /// for shared memories whose size may be changed by other threads.
+            memory_size(vmctx, i32) -> (pointer);
             /// Returns an index for wasm's `table.copy` when both tables are locally
             /// defined.
             table_copy(vmctx, i32, i32, i32, i32, i32) -> ();
@@ -45,6 +48,21 @@ macro_rules! foreach_builtin_function {
             memory_atomic_wait64(vmctx, i32, pointer, i64, i64) -> (i32);
             /// Invoked when fuel has run out while executing a function.
             out_of_gas(vmctx) -> ();
+            /// Invoked when we reach a new epoch.
+            new_epoch(vmctx) -> (i64);
+            /// Returns an index for wasm's `throw` instruction.
+            throw(vmctx, i32, pointer, i32, i32) -> ();
+            /// Returns an index for wasm's `rethrow` instruction.
+            rethrow(vmctx, reference) -> ();
+            /// Returns an index to test whether the pending exception was thrown
+            /// with a given tag.
+            exception_matches(vmctx, i32) -> (i32);
+            /// Returns an index to take the pending exception and copy the values
+            /// it carries out, for wasm's `catch` and `catch_all` clauses.
+            catch_exception(vmctx, pointer) -> (reference);
+            /// Returns an index to continue unwinding the pending exception
+            /// out of a function which doesn't handle it.
+            resume_unwind(vmctx) -> ();
         }
     };
 }
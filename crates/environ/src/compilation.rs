@@ -23,6 +23,11 @@ pub struct FunctionInfo {
     pub start_srcloc: FilePos,
     pub stack_maps: Vec<StackMapInformation>,
 
+    /// Sorted offsets, relative to the start of the function, of the return
+    /// addresses of calls inside wasm `try` blocks. Exceptions raised by these
+    /// calls are handled by this function, so unwinding stops there.
+    pub exception_handlers: Vec<u32>,
+
     /// Offset in the text section of where this function starts.
     pub start: u64,
     /// The size of the compiled function, in bytes.
@@ -104,7 +109,10 @@ pub trait CompilerBuilder: Send + Sync + fmt::Debug {
     fn settings(&self) -> Vec<Setting>;
 
     /// Builds a new [`Compiler`] object from this configuration.
-    fn build(&self) -> Box<dyn Compiler>;
+    ///
+    /// Returns an error if the configured settings aren't supported by the
+    /// target.
+    fn build(&self) -> Result<Box<dyn Compiler>>;
 }
 
 /// Description of compiler settings returned by [`CompilerBuilder::settings`].
@@ -207,6 +215,7 @@ pub trait Compiler: Send + Sync {
                 Arm(_) => Architecture::Arm,
                 Aarch64(_) => Architecture::Aarch64,
                 S390x => Architecture::S390x,
+                Riscv64(_) => Architecture::Riscv64,
                 architecture => {
                     anyhow::bail!("target architecture {:?} is unsupported", architecture,);
                 }
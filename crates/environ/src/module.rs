@@ -56,6 +56,20 @@ impl MemoryStyle {
             );
         }
 
+        // Shared memories can be accessed concurrently from other threads, so
+        // they're never allowed to move once allocated. If one didn't fit the
+        // static bound above then reserve exactly its declared maximum instead
+        // of falling back to a dynamic memory. Validation guarantees that
+        // shared memories always have a maximum.
+        if memory.shared {
+            if let Some(maximum) = memory.maximum {
+                return (
+                    Self::Static { bound: maximum },
+                    tunables.static_memory_offset_guard_size,
+                );
+            }
+        }
+
         // Otherwise, make it dynamic.
         (
             Self::Dynamic {
@@ -159,6 +173,37 @@ pub enum MemoryInitialization {
         /// This is used to fail module instantiation after the pages are initialized.
         out_of_bounds: bool,
     },
+
+    /// Memory initialization is static.
+    ///
+    /// To be static, the same requirements as for paged initialization must
+    /// be met, and additionally all data segments must be in bounds of their
+    /// memory's minimum size.
+    ///
+    /// Each defined memory is initialized from at most one contiguous image
+    /// whose offset within the linear memory, and whose location within the
+    /// data of the compiled module, are both aligned to a host page size.
+    /// This makes it possible to map the image copy-on-write into a linear
+    /// memory, directly from a file such as a serialized module, rather than
+    /// copying data segments into fresh memory on every instantiation.
+    ///
+    /// Without such a mapping, static initialization is performed with a
+    /// single copy per memory.
+    Static {
+        /// The map of defined memory index to its initialization image, if
+        /// any. Memories without an image start out as all zeros.
+        map: PrimaryMap<DefinedMemoryIndex, Option<StaticMemoryInitializer>>,
+    },
+}
+
+/// The initial contents of a linear memory with static memory initialization.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StaticMemoryInitializer {
+    /// The byte offset within the linear memory at which the image starts.
+    pub offset: u64,
+    /// The range of the image within the data of the compiled module, similar
+    /// to the `data` field of `MemoryInitializer`.
+    pub data: Range<u32>,
 }
 
 impl ModuleTranslation<'_> {
@@ -171,7 +216,7 @@ impl ModuleTranslation<'_> {
     pub fn try_paged_init(&mut self) {
         let initializers = match &self.module.memory_initialization {
             MemoryInitialization::Segmented(list) => list,
-            MemoryInitialization::Paged { .. } => return,
+            MemoryInitialization::Paged { .. } | MemoryInitialization::Static { .. } => return,
         };
         let page_size = u64::from(WASM_PAGE_SIZE);
         let num_defined_memories =
@@ -280,6 +325,144 @@ impl ModuleTranslation<'_> {
         }
         self.module.memory_initialization = MemoryInitialization::Paged { map, out_of_bounds };
     }
+
+    /// Attempts to convert segmented memory initialization into static
+    /// initialization for the module that this translation represents.
+    ///
+    /// The `page_size` given is the alignment used for the images of each
+    /// memory, both within the linear memory and within the data of the
+    /// module. Images which are larger than `max_image_size_always_allowed`
+    /// are only created if at least half of their contents come from data
+    /// segments, to avoid bloating the compiled module with large and sparse
+    /// images.
+    ///
+    /// If this module's memory initialization is not compatible with static
+    /// initialization then this won't change anything. Otherwise if it is
+    /// compatible then the `memory_initialization` field will be updated.
+    pub fn try_static_init(&mut self, page_size: u64, max_image_size_always_allowed: u64) {
+        let initializers = match &self.module.memory_initialization {
+            MemoryInitialization::Segmented(list) => list,
+            MemoryInitialization::Paged { .. } | MemoryInitialization::Static { .. } => return,
+        };
+        assert!(page_size.is_power_of_two());
+        let num_defined_memories =
+            self.module.memory_plans.len() - self.module.num_imported_memories;
+
+        // First determine the page-aligned extent of each memory's image,
+        // along with how many bytes of data it contains. Anything that would
+        // need to be processed at instantiation time, such as imported
+        // memories, global bases, or out-of-bounds segments whose trap must
+        // be observed, means that static initialization isn't possible.
+        struct Extent {
+            start: u64,
+            end: u64,
+            data_size: u64,
+        }
+        let mut extents = PrimaryMap::with_capacity(num_defined_memories);
+        for _ in 0..num_defined_memories {
+            extents.push(None::<Extent>);
+        }
+
+        assert_eq!(initializers.len(), self.data.len());
+        for initializer in initializers.iter() {
+            let memory_index = match (
+                self.module.defined_memory_index(initializer.memory_index),
+                initializer.base.is_some(),
+            ) {
+                (Some(index), false) => index,
+                _ => return,
+            };
+
+            let len = u64::from(initializer.data.end - initializer.data.start);
+            let memory = &self.module.memory_plans[initializer.memory_index].memory;
+            let initial_memory_end = match memory.minimum.checked_mul(u64::from(WASM_PAGE_SIZE)) {
+                Some(end) => end,
+                None => return,
+            };
+            let end = match initializer.offset.checked_add(len) {
+                Some(end) if end <= initial_memory_end => end,
+                _ => return,
+            };
+            if len == 0 {
+                continue;
+            }
+
+            let start = initializer.offset & !(page_size - 1);
+            let end = match end.checked_add(page_size - 1) {
+                Some(end) => end & !(page_size - 1),
+                None => return,
+            };
+            let extent = extents[memory_index].get_or_insert(Extent {
+                start,
+                end,
+                data_size: 0,
+            });
+            extent.start = extent.start.min(start);
+            extent.end = extent.end.max(end);
+            extent.data_size += len;
+        }
+
+        // Next check that the images are reasonably dense and that they all
+        // fit in the data of the compiled module, which is indexed with
+        // `u32`.
+        let mut total_image_size = 0u64;
+        for extent in extents.values().flatten() {
+            let image_size = extent.end - extent.start;
+            if image_size > max_image_size_always_allowed && extent.data_size < image_size / 2 {
+                return;
+            }
+            total_image_size += image_size;
+        }
+        if total_image_size > u64::from(u32::MAX) {
+            return;
+        }
+
+        // If we've gotten this far then we're switching to static
+        // initialization. Build each image by applying the data segments in
+        // order, just as instantiation would, and then replace the original
+        // data segments in `self.data` with the images. Each image is a
+        // multiple of `page_size` in length so all images remain aligned
+        // within the data as long as the first one is.
+        let mut images = PrimaryMap::with_capacity(num_defined_memories);
+        for extent in extents.values() {
+            images.push(
+                extent
+                    .as_ref()
+                    .map(|e| (e.start, vec![0; (e.end - e.start) as usize])),
+            );
+        }
+        for (initializer, data) in initializers.iter().zip(&self.data) {
+            if data.is_empty() {
+                continue;
+            }
+            let index = self
+                .module
+                .defined_memory_index(initializer.memory_index)
+                .unwrap();
+            let (start, image) = images[index].as_mut().unwrap();
+            let offset = (initializer.offset - *start) as usize;
+            image[offset..][..data.len()].copy_from_slice(data);
+        }
+
+        self.data.clear();
+        let mut map = PrimaryMap::with_capacity(images.len());
+        let mut offset = 0;
+        for (memory, image) in images {
+            let init = image.map(|(start, image)| {
+                let end = offset + (image.len() as u32);
+                let init = StaticMemoryInitializer {
+                    offset: start,
+                    data: offset..end,
+                };
+                offset = end;
+                self.data.push(image.into());
+                init
+            });
+            let index = map.push(init);
+            assert_eq!(index, memory);
+        }
+        self.module.memory_initialization = MemoryInitialization::Static { map };
+    }
 }
 
 impl Default for MemoryInitialization {
@@ -405,6 +588,9 @@ pub struct Module {
     /// Number of imported or aliased globals in the module.
     pub num_imported_globals: usize,
 
+    /// Number of imported or aliased tags in the module.
+    pub num_imported_tags: usize,
+
     /// Types of functions, imported and local.
     pub functions: PrimaryMap<FuncIndex, SignatureIndex>,
 
@@ -417,6 +603,9 @@ pub struct Module {
     /// WebAssembly global variables.
     pub globals: PrimaryMap<GlobalIndex, Global>,
 
+    /// Types of exception tags, imported and local.
+    pub tags: PrimaryMap<TagIndex, SignatureIndex>,
+
     /// The type of each wasm instance this module defines.
     pub instances: PrimaryMap<InstanceIndex, InstanceTypeIndex>,
 
@@ -599,6 +788,29 @@ impl Module {
         index.index() < self.num_imported_globals
     }
 
+    /// Convert a `DefinedTagIndex` into a `TagIndex`.
+    #[inline]
+    pub fn tag_index(&self, defined_tag: DefinedTagIndex) -> TagIndex {
+        TagIndex::new(self.num_imported_tags + defined_tag.index())
+    }
+
+    /// Convert a `TagIndex` into a `DefinedTagIndex`. Returns None if the
+    /// index is an imported tag.
+    #[inline]
+    pub fn defined_tag_index(&self, tag: TagIndex) -> Option<DefinedTagIndex> {
+        if tag.index() < self.num_imported_tags {
+            None
+        } else {
+            Some(DefinedTagIndex::new(tag.index() - self.num_imported_tags))
+        }
+    }
+
+    /// Test whether the given tag index is for an imported tag.
+    #[inline]
+    pub fn is_imported_tag(&self, index: TagIndex) -> bool {
+        index.index() < self.num_imported_tags
+    }
+
     /// Returns an iterator of all the imports in this module, along with their
     /// module name, field name, and type that's being imported.
     pub fn imports(&self) -> impl Iterator<Item = (&str, Option<&str>, EntityType)> {
@@ -619,6 +831,7 @@ impl Module {
             EntityIndex::Function(i) => EntityType::Function(self.functions[i]),
             EntityIndex::Instance(i) => EntityType::Instance(self.instances[i]),
             EntityIndex::Module(i) => EntityType::Module(self.modules[i]),
+            EntityIndex::Tag(i) => EntityType::Tag(self.tags[i]),
         }
     }
 }
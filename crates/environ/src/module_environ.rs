@@ -5,8 +5,8 @@ use crate::module::{
 use crate::{
     DataIndex, DefinedFuncIndex, ElemIndex, EntityIndex, EntityType, FuncIndex, Global,
     GlobalIndex, GlobalInit, InstanceIndex, InstanceTypeIndex, MemoryIndex, ModuleIndex,
-    ModuleTypeIndex, PrimaryMap, SignatureIndex, TableIndex, Tunables, TypeIndex, WasmError,
-    WasmFuncType, WasmResult,
+    ModuleTypeIndex, PrimaryMap, SignatureIndex, TableIndex, TagIndex, Tunables, TypeIndex,
+    WasmError, WasmFuncType, WasmResult,
 };
 use cranelift_entity::packed_option::ReservedValue;
 use std::borrow::Cow;
@@ -358,9 +358,6 @@ impl<'data> ModuleEnvironment<'data> {
                             EntityType::Instance(signature)
                         }
                         ImportSectionEntryType::Memory(ty) => {
-                            if ty.shared {
-                                return Err(WasmError::Unsupported("shared memories".to_owned()));
-                            }
                             self.result.module.num_imported_memories += 1;
                             EntityType::Memory(ty.into())
                         }
@@ -372,9 +369,12 @@ impl<'data> ModuleEnvironment<'data> {
                             self.result.module.num_imported_tables += 1;
                             EntityType::Table(ty.try_into()?)
                         }
-
-                        // doesn't get past validation
-                        ImportSectionEntryType::Tag(_) => unreachable!(),
+                        ImportSectionEntryType::Tag(ty) => {
+                            let index = TypeIndex::from_u32(ty.type_index);
+                            let sig_index = self.result.module.types[index].unwrap_function();
+                            self.result.module.num_imported_tags += 1;
+                            EntityType::Tag(sig_index)
+                        }
                     };
                     self.declare_import(import.module, import.field, ty);
                 }
@@ -414,9 +414,6 @@ impl<'data> ModuleEnvironment<'data> {
 
                 for entry in memories {
                     let memory = entry?;
-                    if memory.shared {
-                        return Err(WasmError::Unsupported("shared memories".to_owned()));
-                    }
                     let plan = MemoryPlan::for_memory(memory.into(), &self.tunables);
                     self.result.module.memory_plans.push(plan);
                 }
@@ -425,9 +422,14 @@ impl<'data> ModuleEnvironment<'data> {
             Payload::TagSection(tags) => {
                 validator.tag_section(&tags)?;
 
-                // This feature isn't enabled at this time, so we should
-                // never get here.
-                unreachable!();
+                let cnt = usize::try_from(tags.get_count()).unwrap();
+                self.result.module.tags.reserve_exact(cnt);
+
+                for entry in tags {
+                    let ty = TypeIndex::from_u32(entry?.type_index);
+                    let sig_index = self.result.module.types[ty].unwrap_function();
+                    self.result.module.tags.push(sig_index);
+                }
             }
 
             Payload::GlobalSection(globals) => {
@@ -489,9 +491,10 @@ impl<'data> ModuleEnvironment<'data> {
                         ExternalKind::Instance => {
                             EntityIndex::Instance(InstanceIndex::from_u32(index))
                         }
+                        ExternalKind::Tag => EntityIndex::Tag(TagIndex::from_u32(index)),
 
                         // this never gets past validation
-                        ExternalKind::Tag | ExternalKind::Type => unreachable!(),
+                        ExternalKind::Type => unreachable!(),
                     };
                     self.result
                         .module
@@ -750,9 +753,12 @@ impl<'data> ModuleEnvironment<'data> {
                                 ExternalKind::Instance => {
                                     EntityIndex::Instance(InstanceIndex::from_u32(arg.index))
                                 }
+                                ExternalKind::Tag => {
+                                    EntityIndex::Tag(TagIndex::from_u32(arg.index))
+                                }
 
                                 // this won't pass validation
-                                ExternalKind::Tag | ExternalKind::Type => unreachable!(),
+                                ExternalKind::Type => unreachable!(),
                             };
                             Ok((arg.name.to_string(), index))
                         })
@@ -875,7 +881,10 @@ impl<'data> ModuleEnvironment<'data> {
                                 EntityType::Module(sig) => {
                                     self.result.module.modules.push(*sig);
                                 }
-                                EntityType::Tag(_) => unimplemented!(),
+                                EntityType::Tag(sig) => {
+                                    self.result.module.tags.push(*sig);
+                                    self.result.module.num_imported_tags += 1;
+                                }
                             }
                             self.result
                                 .module
@@ -1106,7 +1115,9 @@ and for re-adding support for interface types you can see this issue:
                 EntityType::Instance(self.type_to_instance_type(TypeIndex::from_u32(sig))?)
             }
             ImportSectionEntryType::Memory(ty) => EntityType::Memory(ty.into()),
-            ImportSectionEntryType::Tag(t) => EntityType::Tag(t.into()),
+            ImportSectionEntryType::Tag(t) => {
+                EntityType::Tag(self.type_to_signature(TypeIndex::from_u32(t.type_index))?)
+            }
             ImportSectionEntryType::Global(ty) => {
                 EntityType::Global(Global::new(ty, GlobalInit::Import)?)
             }
@@ -1132,7 +1143,7 @@ and for re-adding support for interface types you can see this issue:
                 EntityIndex::Instance(self.result.module.instances.push(ty))
             }
             EntityType::Module(ty) => EntityIndex::Module(self.result.module.modules.push(ty)),
-            EntityType::Tag(_) => unimplemented!(),
+            EntityType::Tag(ty) => EntityIndex::Tag(self.result.module.tags.push(ty)),
         }
     }
 
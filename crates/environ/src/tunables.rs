@@ -36,6 +36,11 @@ pub struct Tunables {
     /// will be consumed every time a wasm instruction is executed.
     pub consume_fuel: bool,
 
+    /// Whether or not we use epoch-based interruption, meaning that generated
+    /// code checks the engine's epoch counter against the store's deadline at
+    /// function entry and loop headers.
+    pub epoch_interruption: bool,
+
     /// Whether or not to treat the static memory bound as the maximum for unbounded heaps.
     pub static_memory_bound_is_maximum: bool,
 
@@ -46,6 +51,10 @@ pub struct Tunables {
     /// Indicates whether an address map from compiled native code back to wasm
     /// offsets in the original file is generated.
     pub generate_address_map: bool,
+
+    /// Whether or not generated code records the locals and operand stack of
+    /// its frames so that they can be included in core dumps.
+    pub coredump_on_trap: bool,
 }
 
 impl Default for Tunables {
@@ -88,9 +97,11 @@ impl Default for Tunables {
             parse_wasm_debuginfo: true,
             interruptable: false,
             consume_fuel: false,
+            epoch_interruption: false,
             static_memory_bound_is_maximum: false,
             guard_before_linear_memory: true,
             generate_address_map: true,
+            coredump_on_trap: false,
         }
     }
 }
@@ -5,6 +5,7 @@
 //
 // struct VMContext {
 //      interrupts: *const VMInterrupts,
+//      epoch_ptr: *mut AtomicU64,
 //      externref_activations_table: *mut VMExternRefActivationsTable,
 //      store: *mut dyn Store,
 //      signature_ids: [VMSharedSignatureIndex; module.num_signature_ids],
@@ -12,16 +13,18 @@
 //      imported_tables: [VMTableImport; module.num_imported_tables],
 //      imported_memories: [VMMemoryImport; module.num_imported_memories],
 //      imported_globals: [VMGlobalImport; module.num_imported_globals],
+//      imported_tags: [VMTagImport; module.num_imported_tags],
 //      tables: [VMTableDefinition; module.num_defined_tables],
 //      memories: [VMMemoryDefinition; module.num_defined_memories],
 //      globals: [VMGlobalDefinition; module.num_defined_globals],
+//      tags: [VMTagDefinition; module.num_defined_tags],
 //      anyfuncs: [VMCallerCheckedAnyfunc; module.num_imported_functions + module.num_defined_functions],
 //      builtins: VMBuiltinFunctionsArray,
 // }
 
 use crate::{
-    BuiltinFunctionIndex, DefinedGlobalIndex, DefinedMemoryIndex, DefinedTableIndex, FuncIndex,
-    GlobalIndex, MemoryIndex, Module, TableIndex, TypeIndex,
+    BuiltinFunctionIndex, DefinedGlobalIndex, DefinedMemoryIndex, DefinedTableIndex,
+    DefinedTagIndex, FuncIndex, GlobalIndex, MemoryIndex, Module, TableIndex, TagIndex, TypeIndex,
 };
 use more_asserts::assert_lt;
 use std::convert::TryFrom;
@@ -62,6 +65,8 @@ pub struct VMOffsets<P> {
     pub num_imported_memories: u32,
     /// The number of imported globals in the module.
     pub num_imported_globals: u32,
+    /// The number of imported tags in the module.
+    pub num_imported_tags: u32,
     /// The number of defined functions in the module.
     pub num_defined_functions: u32,
     /// The number of defined tables in the module.
@@ -70,9 +75,12 @@ pub struct VMOffsets<P> {
     pub num_defined_memories: u32,
     /// The number of defined globals in the module.
     pub num_defined_globals: u32,
+    /// The number of defined tags in the module.
+    pub num_defined_tags: u32,
 
     // precalculated offsets of various member fields
     interrupts: u32,
+    epoch_ptr: u32,
     externref_activations_table: u32,
     store: u32,
     signature_ids: u32,
@@ -80,9 +88,11 @@ pub struct VMOffsets<P> {
     imported_tables: u32,
     imported_memories: u32,
     imported_globals: u32,
+    imported_tags: u32,
     defined_tables: u32,
     defined_memories: u32,
     defined_globals: u32,
+    defined_tags: u32,
     defined_anyfuncs: u32,
     builtin_functions: u32,
     size: u32,
@@ -126,6 +136,8 @@ pub struct VMOffsetsFields<P> {
     pub num_imported_memories: u32,
     /// The number of imported globals in the module.
     pub num_imported_globals: u32,
+    /// The number of imported tags in the module.
+    pub num_imported_tags: u32,
     /// The number of defined functions in the module.
     pub num_defined_functions: u32,
     /// The number of defined tables in the module.
@@ -134,6 +146,8 @@ pub struct VMOffsetsFields<P> {
     pub num_defined_memories: u32,
     /// The number of defined globals in the module.
     pub num_defined_globals: u32,
+    /// The number of defined tags in the module.
+    pub num_defined_tags: u32,
 }
 
 impl<P: PtrSize> VMOffsets<P> {
@@ -146,10 +160,12 @@ impl<P: PtrSize> VMOffsets<P> {
             num_imported_tables: cast_to_u32(module.num_imported_tables),
             num_imported_memories: cast_to_u32(module.num_imported_memories),
             num_imported_globals: cast_to_u32(module.num_imported_globals),
+            num_imported_tags: cast_to_u32(module.num_imported_tags),
             num_defined_functions: cast_to_u32(module.functions.len()),
             num_defined_tables: cast_to_u32(module.table_plans.len()),
             num_defined_memories: cast_to_u32(module.memory_plans.len()),
             num_defined_globals: cast_to_u32(module.globals.len()),
+            num_defined_tags: cast_to_u32(module.tags.len()),
         })
     }
 
@@ -169,11 +185,14 @@ impl<P: PtrSize> From<VMOffsetsFields<P>> for VMOffsets<P> {
             num_imported_tables: fields.num_imported_tables,
             num_imported_memories: fields.num_imported_memories,
             num_imported_globals: fields.num_imported_globals,
+            num_imported_tags: fields.num_imported_tags,
             num_defined_functions: fields.num_defined_functions,
             num_defined_tables: fields.num_defined_tables,
             num_defined_memories: fields.num_defined_memories,
             num_defined_globals: fields.num_defined_globals,
+            num_defined_tags: fields.num_defined_tags,
             interrupts: 0,
+            epoch_ptr: 0,
             externref_activations_table: 0,
             store: 0,
             signature_ids: 0,
@@ -181,19 +200,25 @@ impl<P: PtrSize> From<VMOffsetsFields<P>> for VMOffsets<P> {
             imported_tables: 0,
             imported_memories: 0,
             imported_globals: 0,
+            imported_tags: 0,
             defined_tables: 0,
             defined_memories: 0,
             defined_globals: 0,
+            defined_tags: 0,
             defined_anyfuncs: 0,
             builtin_functions: 0,
             size: 0,
         };
 
         ret.interrupts = 0;
-        ret.externref_activations_table = ret
+        ret.epoch_ptr = ret
             .interrupts
             .checked_add(u32::from(ret.ptr.size()))
             .unwrap();
+        ret.externref_activations_table = ret
+            .epoch_ptr
+            .checked_add(u32::from(ret.ptr.size()))
+            .unwrap();
         ret.store = ret
             .externref_activations_table
             .checked_add(u32::from(ret.ptr.size()))
@@ -235,7 +260,7 @@ impl<P: PtrSize> From<VMOffsetsFields<P>> for VMOffsets<P> {
                     .unwrap(),
             )
             .unwrap();
-        ret.defined_tables = ret
+        ret.imported_tags = ret
             .imported_globals
             .checked_add(
                 ret.num_imported_globals
@@ -243,6 +268,14 @@ impl<P: PtrSize> From<VMOffsetsFields<P>> for VMOffsets<P> {
                     .unwrap(),
             )
             .unwrap();
+        ret.defined_tables = ret
+            .imported_tags
+            .checked_add(
+                ret.num_imported_tags
+                    .checked_mul(u32::from(ret.size_of_vmtag_import()))
+                    .unwrap(),
+            )
+            .unwrap();
         ret.defined_memories = ret
             .defined_tables
             .checked_add(
@@ -261,7 +294,7 @@ impl<P: PtrSize> From<VMOffsetsFields<P>> for VMOffsets<P> {
                 .unwrap(),
             16,
         );
-        ret.defined_anyfuncs = ret
+        ret.defined_tags = ret
             .defined_globals
             .checked_add(
                 ret.num_defined_globals
@@ -269,6 +302,16 @@ impl<P: PtrSize> From<VMOffsetsFields<P>> for VMOffsets<P> {
                     .unwrap(),
             )
             .unwrap();
+        ret.defined_anyfuncs = align(
+            ret.defined_tags
+                .checked_add(
+                    ret.num_defined_tags
+                        .checked_mul(u32::from(ret.size_of_vmtag_definition()))
+                        .unwrap(),
+                )
+                .unwrap(),
+            u32::from(ret.pointer_size()),
+        );
         ret.builtin_functions = ret
             .defined_anyfuncs
             .checked_add(
@@ -447,6 +490,38 @@ impl<P: PtrSize> VMOffsets<P> {
     }
 }
 
+/// Offsets for `VMTagImport`.
+impl<P: PtrSize> VMOffsets<P> {
+    /// The offset of the `from` field.
+    #[allow(clippy::erasing_op)]
+    #[inline]
+    pub fn vmtag_import_from(&self) -> u8 {
+        0 * self.pointer_size()
+    }
+
+    /// Return the size of `VMTagImport`.
+    #[allow(clippy::identity_op)]
+    #[inline]
+    pub fn size_of_vmtag_import(&self) -> u8 {
+        1 * self.pointer_size()
+    }
+}
+
+/// Offsets for `VMTagDefinition`.
+impl<P: PtrSize> VMOffsets<P> {
+    /// The offset of the `type_index` field.
+    #[inline]
+    pub fn vmtag_definition_type_index(&self) -> u8 {
+        0
+    }
+
+    /// Return the size of `VMTagDefinition`.
+    #[inline]
+    pub fn size_of_vmtag_definition(&self) -> u8 {
+        self.size_of_vmshared_signature_index()
+    }
+}
+
 /// Offsets for `VMSharedSignatureIndex`.
 impl<P: PtrSize> VMOffsets<P> {
     /// Return the size of `VMSharedSignatureIndex`.
@@ -469,6 +544,83 @@ impl<P: PtrSize> VMOffsets<P> {
     pub fn vminterrupts_fuel_consumed(&self) -> u8 {
         self.pointer_size()
     }
+
+    /// Return the offset of the `epoch_deadline` field of `VMInterrupts`
+    #[inline]
+    pub fn vminterrupts_epoch_deadline(&self) -> u8 {
+        // Placed after the `i64`-sized `fuel_consumed` field.
+        self.vminterrupts_fuel_consumed() + 8
+    }
+
+    /// Return the offset of the `pending_exception` field of `VMInterrupts`
+    #[inline]
+    pub fn vminterrupts_pending_exception(&self) -> u8 {
+        // Placed after the `u64`-sized `epoch_deadline` field.
+        self.vminterrupts_epoch_deadline() + 8
+    }
+
+    /// Return the offset of the `coredump_frames` field of `VMInterrupts`
+    #[inline]
+    pub fn vminterrupts_coredump_frames(&self) -> u8 {
+        self.vminterrupts_pending_exception() + self.pointer_size()
+    }
+}
+
+/// Offsets for `VMCoreDumpFrame`.
+impl<P: PtrSize> VMOffsets<P> {
+    /// Return the offset of the `prev` field of `VMCoreDumpFrame`.
+    #[allow(clippy::erasing_op)]
+    #[inline]
+    pub fn vmcoredump_frame_prev(&self) -> u8 {
+        0 * self.pointer_size()
+    }
+
+    /// Return the offset of the `vmctx` field of `VMCoreDumpFrame`.
+    #[allow(clippy::identity_op)]
+    #[inline]
+    pub fn vmcoredump_frame_vmctx(&self) -> u8 {
+        1 * self.pointer_size()
+    }
+
+    /// Return the offset of the `func_index` field of `VMCoreDumpFrame`.
+    #[inline]
+    pub fn vmcoredump_frame_func_index(&self) -> u8 {
+        2 * self.pointer_size()
+    }
+
+    /// Return the offset of the `num_locals` field of `VMCoreDumpFrame`.
+    #[inline]
+    pub fn vmcoredump_frame_num_locals(&self) -> u8 {
+        self.vmcoredump_frame_func_index() + 4
+    }
+
+    /// Return the offset of the `num_stack` field of `VMCoreDumpFrame`.
+    #[inline]
+    pub fn vmcoredump_frame_num_stack(&self) -> u8 {
+        self.vmcoredump_frame_num_locals() + 4
+    }
+
+    /// Return the size of `VMCoreDumpFrame`, which is followed in memory by
+    /// its locals and then its operand stack.
+    #[inline]
+    pub fn size_of_vmcoredump_frame(&self) -> u8 {
+        // Padded so that the values which follow are 8-byte aligned.
+        2 * self.pointer_size() + 16
+    }
+
+    /// Return the size of each local or operand stack entry following a
+    /// `VMCoreDumpFrame`. The value is stored at the start of the entry and
+    /// the byte at `vmcoredump_value_type` describes its type.
+    #[inline]
+    pub fn size_of_vmcoredump_value(&self) -> u8 {
+        16
+    }
+
+    /// Return the offset of the type of a `VMCoreDumpFrame` entry.
+    #[inline]
+    pub fn vmcoredump_value_type(&self) -> u8 {
+        8
+    }
 }
 
 /// Offsets for `VMCallerCheckedAnyfunc`.
@@ -508,6 +660,12 @@ impl<P: PtrSize> VMOffsets<P> {
         self.interrupts
     }
 
+    /// Return the offset to the `*const AtomicU64` epoch-counter pointer.
+    #[inline]
+    pub fn vmctx_epoch_ptr(&self) -> u32 {
+        self.epoch_ptr
+    }
+
     /// The offset of the `*mut VMExternRefActivationsTable` member.
     #[inline]
     pub fn vmctx_externref_activations_table(&self) -> u32 {
@@ -552,6 +710,12 @@ impl<P: PtrSize> VMOffsets<P> {
         self.imported_globals
     }
 
+    /// The offset of the imported `tags` array.
+    #[inline]
+    pub fn vmctx_imported_tags_begin(&self) -> u32 {
+        self.imported_tags
+    }
+
     /// The offset of the `tables` array.
     #[inline]
     pub fn vmctx_tables_begin(&self) -> u32 {
@@ -570,6 +734,12 @@ impl<P: PtrSize> VMOffsets<P> {
         self.defined_globals
     }
 
+    /// The offset of the `tags` array.
+    #[inline]
+    pub fn vmctx_tags_begin(&self) -> u32 {
+        self.defined_tags
+    }
+
     /// The offset of the `anyfuncs` array.
     #[inline]
     pub fn vmctx_anyfuncs_begin(&self) -> u32 {
@@ -628,6 +798,13 @@ impl<P: PtrSize> VMOffsets<P> {
             + index.as_u32() * u32::from(self.size_of_vmglobal_import())
     }
 
+    /// Return the offset to `VMTagImport` index `index`.
+    #[inline]
+    pub fn vmctx_vmtag_import(&self, index: TagIndex) -> u32 {
+        assert_lt!(index.as_u32(), self.num_imported_tags);
+        self.vmctx_imported_tags_begin() + index.as_u32() * u32::from(self.size_of_vmtag_import())
+    }
+
     /// Return the offset to `VMTableDefinition` index `index`.
     #[inline]
     pub fn vmctx_vmtable_definition(&self, index: DefinedTableIndex) -> u32 {
@@ -649,6 +826,13 @@ impl<P: PtrSize> VMOffsets<P> {
         self.vmctx_globals_begin() + index.as_u32() * u32::from(self.size_of_vmglobal_definition())
     }
 
+    /// Return the offset to the `VMTagDefinition` index `index`.
+    #[inline]
+    pub fn vmctx_vmtag_definition(&self, index: DefinedTagIndex) -> u32 {
+        assert_lt!(index.as_u32(), self.num_defined_tags);
+        self.vmctx_tags_begin() + index.as_u32() * u32::from(self.size_of_vmtag_definition())
+    }
+
     /// Return the offset to the `VMCallerCheckedAnyfunc` for the given function
     /// index (either imported or defined).
     #[inline]
@@ -721,6 +905,12 @@ impl<P: PtrSize> VMOffsets<P> {
         self.vmctx_vmglobal_import(index) + u32::from(self.vmglobal_import_from())
     }
 
+    /// Return the offset to the `from` field in `VMTagImport` index `index`.
+    #[inline]
+    pub fn vmctx_vmtag_import_from(&self, index: TagIndex) -> u32 {
+        self.vmctx_vmtag_import(index) + u32::from(self.vmtag_import_from())
+    }
+
     /// Return the offset to builtin function in `VMBuiltinFunctionsArray` index `index`.
     #[inline]
     pub fn vmctx_builtin_function(&self, index: BuiltinFunctionIndex) -> u32 {
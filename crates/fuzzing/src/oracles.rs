@@ -21,9 +21,9 @@ use std::time::{Duration, Instant};
 use wasmtime::*;
 use wasmtime_wast::WastContext;
 
-#[cfg(not(any(windows, target_arch = "s390x")))]
+#[cfg(not(any(windows, target_arch = "s390x", target_arch = "riscv64")))]
 pub use self::v8::*;
-#[cfg(not(any(windows, target_arch = "s390x")))]
+#[cfg(not(any(windows, target_arch = "s390x", target_arch = "riscv64")))]
 mod v8;
 
 static CNT: AtomicUsize = AtomicUsize::new(0);
@@ -40,9 +40,13 @@ pub fn dummy_extern<T>(store: &mut Store<T>, ty: ExternType) -> Result<Extern> {
         ExternType::Func(func_ty) => Extern::Func(dummy_func(store, func_ty)),
         ExternType::Global(global_ty) => Extern::Global(dummy_global(store, global_ty)),
         ExternType::Table(table_ty) => Extern::Table(dummy_table(store, table_ty)?),
+        ExternType::Memory(mem_ty) if mem_ty.is_shared() => {
+            Extern::SharedMemory(SharedMemory::new(store.engine(), mem_ty)?)
+        }
         ExternType::Memory(mem_ty) => Extern::Memory(dummy_memory(store, mem_ty)?),
         ExternType::Instance(instance_ty) => Extern::Instance(dummy_instance(store, instance_ty)?),
         ExternType::Module(module_ty) => Extern::Module(dummy_module(store.engine(), module_ty)),
+        ExternType::Tag(tag_ty) => Extern::Tag(Tag::new(store, tag_ty)?),
     })
 }
 
@@ -187,6 +191,11 @@ impl WatGenerator {
                 self.func_sig(ty);
                 write!(self.dst, ")").unwrap();
             }
+            ExternType::Tag(ty) => {
+                write!(self.dst, "(tag ").unwrap();
+                self.tag_sig(ty);
+                write!(self.dst, ")").unwrap();
+            }
             ExternType::Instance(ty) => {
                 writeln!(self.dst, "(instance").unwrap();
                 for ty in ty.exports() {
@@ -232,6 +241,7 @@ impl WatGenerator {
             ExternType::Instance(_) => write!(self.dst, "instance").unwrap(),
             ExternType::Table(_) => write!(self.dst, "table").unwrap(),
             ExternType::Module(_) => write!(self.dst, "module").unwrap(),
+            ExternType::Tag(_) => write!(self.dst, "tag").unwrap(),
         }
         writeln!(self.dst, " ${}))", wat_name).unwrap();
     }
@@ -287,6 +297,11 @@ impl WatGenerator {
                 }
                 writeln!(self.dst, ")").unwrap();
             }
+            ExternType::Tag(ty) => {
+                write!(self.dst, "(tag ${} ", name).unwrap();
+                self.tag_sig(ty);
+                writeln!(self.dst, ")").unwrap();
+            }
             ExternType::Module(ty) => {
                 writeln!(self.dst, "(module ${}", name).unwrap();
                 for ty in ty.imports() {
@@ -320,6 +335,14 @@ impl WatGenerator {
         write!(self.dst, ")").unwrap();
     }
 
+    fn tag_sig(&mut self, ty: &TagType) {
+        write!(self.dst, "(param ").unwrap();
+        for ty in ty.params() {
+            write!(self.dst, "{} ", wat_ty(&ty)).unwrap();
+        }
+        write!(self.dst, ")").unwrap();
+    }
+
     fn value(&mut self, ty: &ValType) {
         match ty {
             ValType::I32 => write!(self.dst, "i32.const 0").unwrap(),
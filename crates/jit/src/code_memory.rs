@@ -177,6 +177,18 @@ impl CodeMemory {
                 .unwrap();
             }
 
+            #[cfg(all(target_arch = "riscv64", target_os = "linux"))]
+            {
+                // Ensure that no hart has fetched a stale instruction stream.
+                // A `fence.i` only affects the current hart, so ask the kernel
+                // to synchronize all of them.
+                const SYS_RISCV_FLUSH_ICACHE: libc::c_long = 259;
+                let start = ret.text.as_ptr();
+                let end = start.add(ret.text.len());
+                let rc = libc::syscall(SYS_RISCV_FLUSH_ICACHE, start, end, 0);
+                assert_eq!(rc, 0, "unable to flush the instruction cache");
+            }
+
             // With all our memory set up use the platform-specific
             // `UnwindRegistration` implementation to inform the general
             // runtime that there's unwinding information available for all
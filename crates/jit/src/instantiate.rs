@@ -14,12 +14,15 @@ use std::ops::Range;
 use std::sync::Arc;
 use thiserror::Error;
 use wasmtime_environ::{
-    CompileError, DefinedFuncIndex, FunctionInfo, InstanceSignature, InstanceTypeIndex, Module,
-    ModuleSignature, ModuleTranslation, ModuleTypeIndex, PrimaryMap, SignatureIndex,
-    StackMapInformation, Trampoline, Tunables, WasmFuncType, ELF_WASMTIME_ADDRMAP,
+    CompileError, DefinedFuncIndex, FunctionInfo, InstanceSignature, InstanceTypeIndex,
+    MemoryInitialization, Module, ModuleSignature, ModuleTranslation, ModuleTypeIndex, PrimaryMap,
+    SignatureIndex, StackMapInformation, Trampoline, Tunables, WasmFuncType, ELF_WASMTIME_ADDRMAP,
     ELF_WASMTIME_TRAPS,
 };
-use wasmtime_runtime::{GdbJitImageRegistration, InstantiationError, VMFunctionBody, VMTrampoline};
+use wasmtime_runtime::{
+    CompiledModuleId, GdbJitImageRegistration, InstantiationError, ModuleMemoryImages,
+    VMFunctionBody, VMTrampoline,
+};
 
 /// This is the name of the section in the final ELF image which contains
 /// concatenated data segments from the original wasm module.
@@ -146,9 +149,18 @@ pub fn finish_compile(
         ELF_WASM_DATA.as_bytes().to_vec(),
         SectionKind::ReadOnlyData,
     );
+    // With static memory initialization the data consists of page-aligned
+    // images of each memory. Aligning the start of the section to a page as
+    // well means the images are page-aligned within the final artifact, so
+    // they can be mapped directly into linear memories.
+    let data_align = match &module.memory_initialization {
+        MemoryInitialization::Static { .. } => region::page::size() as u64,
+        _ => 1,
+    };
     let mut total_data_len = 0;
-    for data in data.iter() {
-        obj.append_section_data(data_id, data, 1);
+    for (i, data) in data.iter().enumerate() {
+        let align = if i == 0 { data_align } else { 1 };
+        obj.append_section_data(data_id, data, align);
         total_data_len += data.len();
     }
     for data in passive_data.iter() {
@@ -248,6 +260,8 @@ pub struct CompiledModule {
     code: Range<usize>,
     code_memory: CodeMemory,
     dbg_jit_registration: Option<GdbJitImageRegistration>,
+    memory_images: Option<ModuleMemoryImages>,
+    unique_id: CompiledModuleId,
 }
 
 impl CompiledModule {
@@ -267,10 +281,14 @@ impl CompiledModule {
     ///
     /// The `profiler` argument here is used to inform JIT profiling runtimes
     /// about new code that is loaded.
+    ///
+    /// Copy-on-write images of the module's linear memories are only created
+    /// if `memory_init_cow` is set.
     pub fn from_artifacts(
         mmap: MmapVec,
         info: Option<CompiledModuleInfo>,
         profiler: &dyn ProfilingAgent,
+        memory_init_cow: bool,
     ) -> Result<Arc<Self>> {
         // Transfer ownership of `obj` to a `CodeMemory` object which will
         // manage permissions, such as the executable bit. Once it's located
@@ -312,9 +330,21 @@ impl CompiledModule {
             dbg_jit_registration: None,
             code_memory,
             meta: info.meta,
+            memory_images: None,
+            unique_id: CompiledModuleId::alloc(),
         };
         ret.register_debug_and_profiling(profiler)?;
 
+        // Create the copy-on-write images of linear memories up front, if
+        // enabled and the module uses static memory initialization, since this
+        // may involve copying data into memfds which shouldn't happen
+        // per-instantiation.
+        if memory_init_cow {
+            ret.memory_images =
+                ModuleMemoryImages::new(&ret.module, ret.wasm_data(), ret.wasm_data_file())
+                    .context("failed to create memory images")?;
+        }
+
         Ok(Arc::new(ret))
     }
 
@@ -348,6 +378,28 @@ impl CompiledModule {
         &self.mmap()[self.wasm_data.clone()]
     }
 
+    /// Returns the file this compiled module was mapped from, if any, along
+    /// with the offset of `wasm_data` within that file.
+    ///
+    /// This is used to map memory images directly from the file.
+    pub fn wasm_data_file(&self) -> Option<(&std::fs::File, u64)> {
+        let (file, offset) = self.mmap().original_file()?;
+        Some((file, offset + self.wasm_data.start as u64))
+    }
+
+    /// Returns the copy-on-write images of this module's linear memories, if
+    /// the module uses static memory initialization and images are supported
+    /// on this platform.
+    pub fn memory_images(&self) -> Option<&ModuleMemoryImages> {
+        self.memory_images.as_ref()
+    }
+
+    /// Returns the ID which uniquely identifies this module within the
+    /// process.
+    pub fn unique_id(&self) -> CompiledModuleId {
+        self.unique_id
+    }
+
     /// Returns the encoded address map section used to pass to
     /// `wasmtime_environ::lookup_file_pos`.
     pub fn address_map_data(&self) -> &[u8] {
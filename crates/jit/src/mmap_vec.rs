@@ -1,5 +1,6 @@
 use anyhow::{Context, Error, Result};
 use object::write::{Object, WritableBuffer};
+use std::fs::File;
 use std::ops::{Deref, DerefMut, Range, RangeTo};
 use std::path::Path;
 use std::sync::Arc;
@@ -93,6 +94,13 @@ impl MmapVec {
         self.mmap.is_readonly()
     }
 
+    /// Returns the file that the original mmap was created from, along with
+    /// the offset within that file of the start of this `MmapVec`.
+    pub fn original_file(&self) -> Option<(&File, u64)> {
+        let file = self.mmap.original_file()?;
+        Some((file, self.range.start as u64))
+    }
+
     /// "Drains" leading bytes up to the end specified in `range` from this
     /// `MmapVec`, returning a separately owned `MmapVec` which retains access
     /// to the bytes.
@@ -228,6 +228,7 @@ impl State {
             Architecture::Arm(_) => elf::EM_ARM as u32,
             Architecture::Aarch64(_) => elf::EM_AARCH64 as u32,
             Architecture::S390x => elf::EM_S390 as u32,
+            Architecture::Riscv64(_) => elf::EM_RISCV as u32,
             _ => unimplemented!("unrecognized architecture"),
         }
     }
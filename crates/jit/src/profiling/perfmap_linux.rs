@@ -0,0 +1,110 @@
+//! Support for perf map files, which `perf` reads to symbolize jitted code.
+//!
+//! Each line of `/tmp/perf-<pid>.map` describes one function as
+//! `START SIZE NAME`, with the start address and size in hexadecimal. Unlike
+//! jitdump there's no need to post-process the recorded profile: `perf` picks
+//! the map file up directly when reporting on the process.
+//!
+//! Usage Example:
+//!     Record
+//!         perf record -g target/debug/wasmtime --profile=perfmap test.wasm
+//!     Report
+//!         perf report
+//!     Or, live
+//!         perf top -p <pid>
+
+use crate::{CompiledModule, ProfilingAgent};
+use anyhow::Result;
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::process;
+use std::sync::Mutex;
+use wasmtime_environ::EntityRef;
+
+/// Interface for driving the creation of perf map files
+#[derive(Debug)]
+pub struct PerfMapAgent {
+    file: Mutex<File>,
+}
+
+impl PerfMapAgent {
+    /// Intialize a PerfMapAgent, opening this process's perf map file
+    pub fn new() -> Result<Self> {
+        let filename = format!("/tmp/perf-{}.map", process::id());
+        // Several engines in one process each get an agent, so append to any
+        // map written so far rather than truncating it.
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&filename)?;
+        Ok(PerfMapAgent {
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Writes out a batch of entries with a single write, so that entries
+    /// from different agents don't interleave within a line.
+    fn write_entries(&self, entries: &str) {
+        if entries.is_empty() {
+            return;
+        }
+        let mut file = self.file.lock().unwrap();
+        if let Err(err) = file.write_all(entries.as_bytes()) {
+            println!("perfmap: failed writing entries: {:?}", err);
+        }
+    }
+}
+
+/// Appends the perf map line for the code at `addr` to `entries`.
+fn push_entry(entries: &mut String, name: &str, addr: *const u8, len: usize) {
+    // A line ends the entry, so the name can't contain one.
+    let name = name.replace('\n', " ");
+    writeln!(entries, "{:x} {:x} {}", addr as usize, len, name).unwrap();
+}
+
+impl ProfilingAgent for PerfMapAgent {
+    fn module_load(&self, module: &CompiledModule, _dbg_image: Option<&[u8]>) {
+        let mut entries = String::new();
+        for (idx, func) in module.finished_functions() {
+            let (addr, len) = unsafe { ((*func).as_ptr() as *const u8, (*func).len()) };
+            let name = super::debug_name(module.module(), idx);
+            push_entry(&mut entries, &name, addr, len);
+        }
+        for (idx, func, len) in module.trampolines() {
+            let addr = func as usize as *const u8;
+            let name = format!("wasm::trampoline[{}]", idx.index());
+            push_entry(&mut entries, &name, addr, len);
+        }
+        self.write_entries(&entries);
+    }
+
+    fn trampoline_load(&self, image: &object::File<'_>) {
+        use object::{Object, ObjectSection, ObjectSymbol, SectionKind, SymbolKind};
+
+        let text_base = match image.sections().find(|s| s.kind() == SectionKind::Text) {
+            Some(section) => match section.data() {
+                Ok(data) => data.as_ptr() as usize,
+                Err(_) => return,
+            },
+            None => return,
+        };
+
+        let mut entries = String::new();
+        for sym in image.symbols() {
+            if !sym.is_definition() || sym.kind() != SymbolKind::Text {
+                continue;
+            }
+            let address = sym.address();
+            let size = sym.size();
+            if address == 0 || size == 0 {
+                continue;
+            }
+            if let Ok(name) = sym.name() {
+                let addr = (text_base + address as usize) as *const u8;
+                push_entry(&mut entries, name, addr, size as usize);
+            }
+        }
+        self.write_entries(&entries);
+    }
+}
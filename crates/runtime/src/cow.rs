@@ -0,0 +1,488 @@
+//! Copy-on-write initialization support: creation of backing images for
+//! modules, and logic to support mapping these backing images into memory.
+
+use crate::InstantiationError;
+use anyhow::{Context, Result};
+use std::convert::TryFrom;
+use std::fs::File;
+use std::os::unix::io::{FromRawFd, IntoRawFd};
+use std::sync::Arc;
+use wasmtime_environ::{DefinedMemoryIndex, MemoryInitialization, Module, PrimaryMap};
+
+/// Backing images for memories in a module.
+///
+/// This is meant to be built once, when a module is first loaded/constructed,
+/// and then used many times for instantiation.
+pub struct ModuleMemoryImages {
+    memories: PrimaryMap<DefinedMemoryIndex, Option<Arc<MemoryImage>>>,
+}
+
+impl ModuleMemoryImages {
+    /// Get the MemoryImage for a given memory.
+    pub fn get_memory_image(&self, defined_index: DefinedMemoryIndex) -> Option<&Arc<MemoryImage>> {
+        self.memories[defined_index].as_ref()
+    }
+}
+
+/// One backing image for one memory.
+#[derive(Debug)]
+pub struct MemoryImage {
+    /// The file containing the image's contents, either a sealed memfd or
+    /// the file that a compiled module was loaded from.
+    fd: File,
+    /// Length of the image, in bytes, which is a multiple of the host page
+    /// size.
+    len: usize,
+    /// Offset of the image within `fd`, which is a multiple of the host page
+    /// size.
+    fd_offset: u64,
+    /// Offset of the image within the linear memory, which is a multiple of
+    /// the host page size.
+    linear_memory_offset: usize,
+}
+
+impl MemoryImage {
+    fn new(
+        page_size: u32,
+        linear_memory_offset: u64,
+        data: &[u8],
+        file: Option<(&File, u64)>,
+    ) -> Result<Option<MemoryImage>> {
+        let page_size = u64::from(page_size);
+        let len = u64::try_from(data.len()).unwrap();
+
+        // The image was aligned at compile time to the page size of the
+        // compiling host. If that's smaller than our own page size then the
+        // image can't be mapped and memory will be initialized by copying
+        // instead.
+        if len % page_size != 0 || linear_memory_offset % page_size != 0 {
+            return Ok(None);
+        }
+        let len = usize::try_from(len).unwrap();
+        let linear_memory_offset = match usize::try_from(linear_memory_offset) {
+            Ok(offset) => offset,
+            Err(_) => return Ok(None),
+        };
+
+        // If the compiled module was mapped from a file, such as a `.cwasm`,
+        // and the image is suitably aligned within it, then map the image
+        // directly from that file. The mapping is private, so the file is
+        // never modified.
+        if let Some((file, fd_offset)) = file {
+            if fd_offset % page_size == 0 {
+                return Ok(Some(MemoryImage {
+                    fd: file
+                        .try_clone()
+                        .context("failed to duplicate file handle")?,
+                    len,
+                    fd_offset,
+                    linear_memory_offset,
+                }));
+            }
+        }
+
+        // Otherwise copy the image into a memfd which is then sealed so its
+        // contents can't change while it's mapped into linear memories.
+        let memfd = rustix::fs::memfd_create(
+            "wasm-memory-image",
+            rustix::fs::MemfdFlags::CLOEXEC | rustix::fs::MemfdFlags::ALLOW_SEALING,
+        )
+        .context("failed to create memfd")?;
+        let memfd = unsafe { File::from_raw_fd(memfd.into_raw_fd()) };
+        std::io::Write::write_all(&mut &memfd, data)
+            .context("failed to write memory image to memfd")?;
+        rustix::fs::fcntl_add_seals(
+            &memfd,
+            rustix::fs::SealFlags::GROW
+                | rustix::fs::SealFlags::SHRINK
+                | rustix::fs::SealFlags::WRITE
+                | rustix::fs::SealFlags::SEAL,
+        )
+        .context("failed to seal memfd")?;
+
+        Ok(Some(MemoryImage {
+            fd: memfd,
+            len,
+            fd_offset: 0,
+            linear_memory_offset,
+        }))
+    }
+
+    /// Maps this image copy-on-write into the linear memory starting at
+    /// `base`, replacing anything previously mapped at that location.
+    pub(crate) unsafe fn map_at(&self, base: usize) -> Result<()> {
+        let addr = (base + self.linear_memory_offset) as *mut _;
+        let ptr = rustix::io::mmap(
+            addr,
+            self.len,
+            rustix::io::ProtFlags::READ | rustix::io::ProtFlags::WRITE,
+            rustix::io::MapFlags::PRIVATE | rustix::io::MapFlags::FIXED,
+            &self.fd,
+            self.fd_offset,
+        )
+        .context("failed to map memory image")?;
+        assert_eq!(ptr, addr);
+        Ok(())
+    }
+
+    /// Replaces the mapping of this image in the linear memory starting at
+    /// `base` with fresh zeroed pages.
+    unsafe fn remap_as_zeros_at(&self, base: usize) -> Result<()> {
+        let addr = (base + self.linear_memory_offset) as *mut _;
+        let ptr = rustix::io::mmap_anonymous(
+            addr,
+            self.len,
+            rustix::io::ProtFlags::READ | rustix::io::ProtFlags::WRITE,
+            rustix::io::MapFlags::PRIVATE | rustix::io::MapFlags::FIXED,
+        )
+        .context("failed to unmap memory image")?;
+        assert_eq!(ptr, addr);
+        Ok(())
+    }
+
+    fn end(&self) -> usize {
+        self.linear_memory_offset + self.len
+    }
+}
+
+impl ModuleMemoryImages {
+    /// Create a new `ModuleMemoryImages` for the given module.
+    ///
+    /// The `wasm_data` is the data section of the compiled module that the
+    /// module's memory initializers index into. If the compiled module was
+    /// mapped from a file then `file` is that file along with the offset of
+    /// `wasm_data` within it, which allows images to be mapped directly from
+    /// the file rather than copied into memfds.
+    ///
+    /// Returns `None` if the module does not use static memory
+    /// initialization, in which case its memories are initialized by
+    /// processing data segments at instantiation time.
+    pub fn new(
+        module: &Module,
+        wasm_data: &[u8],
+        file: Option<(&File, u64)>,
+    ) -> Result<Option<ModuleMemoryImages>> {
+        let map = match &module.memory_initialization {
+            MemoryInitialization::Static { map } => map,
+            _ => return Ok(None),
+        };
+        let page_size = region::page::size() as u32;
+        let mut memories = PrimaryMap::with_capacity(map.len());
+        for (defined_index, init) in map {
+            // Shared memories may be in use by other instances so they're
+            // always initialized by copying.
+            let plan = &module.memory_plans[module.memory_index(defined_index)];
+            let image = match init {
+                Some(init) if !plan.memory.shared => {
+                    let data = &wasm_data[init.data.start as usize..init.data.end as usize];
+                    let file = file.map(|(f, offset)| (f, offset + u64::from(init.data.start)));
+                    MemoryImage::new(page_size, init.offset, data, file)?.map(Arc::new)
+                }
+                _ => None,
+            };
+            let idx = memories.push(image);
+            assert_eq!(idx, defined_index);
+        }
+        Ok(Some(ModuleMemoryImages { memories }))
+    }
+}
+
+/// A single slot handled by the copy-on-write memory initialization
+/// mechanism.
+///
+/// The mmap scheme is:
+///
+/// base ==> (points here)
+/// - (image.offset bytes)   anonymous zero memory, pre-image
+/// - (image.len bytes)      CoW mapping of memory image
+/// - (up to static_size)    anonymous zero memory, post-image
+///
+/// The ordering of mmaps to set this up is:
+///
+/// - once, when pooling allocator is created:
+///   - one large mmap to create 8GiB * instances * memories slots
+///
+/// - per instantiation of new image in a slot:
+///   - mmap of anonymous zero memory, from 0 to max heap size
+///     (static_size)
+///   - mmap of CoW'd image, from `image.linear_memory_offset` to
+///     `image.linear_memory_offset + image.len`. This overwrites part of
+///     the anonymous zero memory, potentially splitting it into a pre-
+///     and post-region.
+///   - mprotect(PROT_NONE) on the part of the heap beyond the initial
+///     heap size; we re-mprotect it with R+W bits when the heap is
+///     grown.
+///
+/// When the slot is reused for another instantiation of the same image
+/// only the accessible size of the heap needs to be adjusted, since
+/// `clear_and_remain_ready` has already reset its contents with `madvise`.
+#[derive(Debug)]
+pub struct MemoryImageSlot {
+    /// The base of the actual heap memory. Bytes at this address are what is
+    /// seen by the Wasm guest code.
+    base: usize,
+    /// The maximum static memory size, plus post-guard.
+    static_size: usize,
+    /// The image that is mapped into this slot, if any.
+    image: Option<Arc<MemoryImage>>,
+    /// The size of the heap that is readable and writable. Pages beyond this
+    /// are mapped as `PROT_NONE`.
+    accessible: usize,
+    /// Whether this slot has been instantiated and possibly written to since
+    /// it was last reset.
+    dirty: bool,
+    /// Whether this slot should reset its entire range to inaccessible
+    /// anonymous memory when dropped.
+    clear_on_drop: bool,
+    /// Whether the contents of this slot's range are unknown and must be reset
+    /// before the slot is first instantiated.
+    needs_reset: bool,
+}
+
+impl MemoryImageSlot {
+    /// Create a new slot for the static memory reservation starting at
+    /// `base_addr`, which is `static_size` bytes long.
+    ///
+    /// The reservation is reset to inaccessible anonymous memory when the slot
+    /// is first instantiated, since a previous slot for the same range may have
+    /// failed to reset it when it was dropped.
+    pub fn create(base_addr: *mut u8, static_size: usize) -> Self {
+        MemoryImageSlot {
+            base: base_addr as usize,
+            static_size,
+            image: None,
+            accessible: 0,
+            dirty: false,
+            clear_on_drop: true,
+            needs_reset: true,
+        }
+    }
+
+    /// Inform the slot that it should *not* reset its memory on drop, because
+    /// whoever owns the reservation is about to unmap or reset it anyway.
+    pub fn no_clear_on_drop(&mut self) {
+        self.clear_on_drop = false;
+    }
+
+    /// Prepares this slot for a new instance whose memory is `initial_size`
+    /// bytes and whose contents are given by `maybe_image`.
+    pub fn instantiate(
+        &mut self,
+        initial_size: usize,
+        maybe_image: Option<&Arc<MemoryImage>>,
+    ) -> Result<(), InstantiationError> {
+        assert!(!self.dirty);
+        assert!(initial_size <= self.static_size);
+
+        if self.needs_reset {
+            self.reset_with_anon_memory()
+                .map_err(InstantiationError::Resource)?;
+            self.needs_reset = false;
+        }
+
+        // If the image mapped into this slot isn't the one requested, then
+        // first reset the previous image's region back to zeros, and then
+        // map in the new image.
+        let same_image = match (&self.image, maybe_image) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        if !same_image {
+            if let Some(image) = self.image.take() {
+                unsafe {
+                    image
+                        .remap_as_zeros_at(self.base)
+                        .map_err(InstantiationError::Resource)?;
+                }
+                // The remapped region is readable and writable, so include it
+                // in the accessible region to be fixed up below.
+                self.accessible = self.accessible.max(image.end());
+            }
+            if let Some(image) = maybe_image {
+                assert!(image.end() <= initial_size);
+                unsafe {
+                    image
+                        .map_at(self.base)
+                        .map_err(InstantiationError::Resource)?;
+                }
+                self.accessible = self.accessible.max(image.end());
+                self.image = Some(image.clone());
+            }
+        }
+
+        // Finally make exactly the initial heap accessible.
+        if self.accessible < initial_size {
+            self.set_protection(self.accessible..initial_size, true)
+                .map_err(InstantiationError::Resource)?;
+        } else if self.accessible > initial_size {
+            self.set_protection(initial_size..self.accessible, false)
+                .map_err(InstantiationError::Resource)?;
+        }
+        self.accessible = initial_size;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Makes the heap accessible up to `size_bytes`, for when memory grows.
+    pub fn set_heap_limit(&mut self, size_bytes: usize) -> Result<()> {
+        assert!(size_bytes <= self.static_size);
+        if size_bytes > self.accessible {
+            self.set_protection(self.accessible..size_bytes, true)?;
+            self.accessible = size_bytes;
+        }
+        Ok(())
+    }
+
+    /// Resets the contents of this slot so it's ready for another
+    /// instantiation of the same image.
+    ///
+    /// Pages mapped from the image revert to their original contents and
+    /// all other pages revert to zeros. Nothing is unmapped, so the next
+    /// instantiation of the same image is only an `mprotect` away.
+    pub fn clear_and_remain_ready(&mut self) -> Result<()> {
+        assert!(self.dirty);
+        unsafe {
+            rustix::io::madvise(
+                self.base as *mut _,
+                self.accessible,
+                rustix::io::Advice::LinuxDontNeed,
+            )
+            .context("madvise failed to reset memory image slot")?;
+        }
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn set_protection(&self, range: std::ops::Range<usize>, readwrite: bool) -> Result<()> {
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.static_size);
+        if range.is_empty() {
+            return Ok(());
+        }
+        let protection = if readwrite {
+            region::Protection::READ_WRITE
+        } else {
+            region::Protection::NONE
+        };
+        unsafe {
+            region::protect(
+                (self.base + range.start) as *const u8,
+                range.end - range.start,
+                protection,
+            )
+        }
+        .context("failed to change protection of memory image slot")
+    }
+
+    /// Returns whether this slot has a memory image mapped into it.
+    pub fn has_image(&self) -> bool {
+        self.image.is_some()
+    }
+
+    fn reset_with_anon_memory(&mut self) -> Result<()> {
+        unsafe {
+            let ptr = rustix::io::mmap_anonymous(
+                self.base as *mut _,
+                self.static_size,
+                rustix::io::ProtFlags::empty(),
+                rustix::io::MapFlags::PRIVATE | rustix::io::MapFlags::FIXED,
+            )
+            .context("failed to reset memory image slot")?;
+            assert_eq!(ptr as usize, self.base);
+        }
+        self.image = None;
+        self.accessible = 0;
+        Ok(())
+    }
+}
+
+impl Drop for MemoryImageSlot {
+    fn drop(&mut self) {
+        // The slot may be dropped without being returned to its pool, for
+        // example if instantiation failed. In that case the contents of the
+        // slot are unknown so replace everything with inaccessible anonymous
+        // memory, releasing any pages and image mappings. Errors can't be
+        // returned from here, but a new slot for the same range resets it
+        // again before it's used, so a failure only delays the cleanup.
+        if self.clear_on_drop {
+            if let Err(e) = self.reset_with_anon_memory() {
+                log::warn!("failed to reset memory image slot on drop: {:?}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mmap::Mmap;
+
+    fn test_image(linear_memory_offset: usize, data: &[u8]) -> Arc<MemoryImage> {
+        let page_size = region::page::size();
+        let mut image = data.to_vec();
+        image.resize((data.len() + page_size - 1) / page_size * page_size, 0);
+        let image = MemoryImage::new(page_size as u32, linear_memory_offset as u64, &image, None);
+        Arc::new(image.unwrap().unwrap())
+    }
+
+    #[test]
+    fn instantiate_no_image() {
+        // 4 MiB mmap'd area, not accessible
+        let mut mmap = Mmap::accessible_reserved(0, 4 << 20).unwrap();
+        let mut slot = MemoryImageSlot::create(mmap.as_mut_ptr(), 4 << 20);
+        slot.no_clear_on_drop();
+
+        // Instantiate with 64 KiB initial size.
+        slot.instantiate(64 << 10, None).unwrap();
+        let slice = mmap.as_mut_slice();
+        assert_eq!(0, slice[0]);
+        assert_eq!(0, slice[65535]);
+        slice[1024] = 42;
+        assert_eq!(42, slice[1024]);
+
+        // Grow the heap.
+        slot.set_heap_limit(128 << 10).unwrap();
+        assert_eq!(0, slice[131071]);
+
+        // Reset, and instantiate again: the written byte is gone.
+        slot.clear_and_remain_ready().unwrap();
+        slot.instantiate(64 << 10, None).unwrap();
+        assert_eq!(0, slice[1024]);
+    }
+
+    #[test]
+    fn instantiate_image() {
+        let page_size = region::page::size();
+        let mut mmap = Mmap::accessible_reserved(0, 4 << 20).unwrap();
+        let mut slot = MemoryImageSlot::create(mmap.as_mut_ptr(), 4 << 20);
+        slot.no_clear_on_drop();
+
+        let image = test_image(page_size, &[1, 2, 3, 4]);
+        slot.instantiate(64 << 10, Some(&image)).unwrap();
+        assert!(slot.has_image());
+        let slice = mmap.as_mut_slice();
+        assert_eq!(&[1, 2, 3, 4], &slice[page_size..][..4]);
+        slice[page_size] = 5;
+        slice[0] = 6;
+
+        // Reusing the slot for the same image restores the original contents.
+        slot.clear_and_remain_ready().unwrap();
+        slot.instantiate(64 << 10, Some(&image)).unwrap();
+        assert_eq!(&[1, 2, 3, 4], &slice[page_size..][..4]);
+        assert_eq!(0, slice[0]);
+
+        // Switching to a different image replaces the previous one.
+        let image2 = test_image(2 * page_size, &[10, 11]);
+        slot.clear_and_remain_ready().unwrap();
+        slot.instantiate(64 << 10, Some(&image2)).unwrap();
+        assert_eq!(&[0, 0, 0, 0], &slice[page_size..][..4]);
+        assert_eq!(&[10, 11], &slice[2 * page_size..][..2]);
+
+        // And no image at all leaves only zeros.
+        slot.clear_and_remain_ready().unwrap();
+        slot.instantiate(64 << 10, None).unwrap();
+        assert!(!slot.has_image());
+        assert_eq!(&[0, 0], &slice[2 * page_size..][..2]);
+    }
+}
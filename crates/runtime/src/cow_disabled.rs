@@ -0,0 +1,76 @@
+//! Shims for copy-on-write memory initialization when it is not supported on
+//! the current platform, or is disabled at compile time.
+
+#![allow(dead_code)]
+
+use crate::InstantiationError;
+use anyhow::Result;
+use std::fs::File;
+use std::sync::Arc;
+use wasmtime_environ::{DefinedMemoryIndex, Module};
+
+/// A shim for the memory image container when support is not included.
+pub enum ModuleMemoryImages {}
+
+/// A shim for an individual memory image.
+#[derive(Debug)]
+pub enum MemoryImage {}
+
+impl MemoryImage {
+    pub(crate) unsafe fn map_at(&self, _: usize) -> Result<()> {
+        match *self {}
+    }
+}
+
+impl ModuleMemoryImages {
+    /// Construct a new set of memory images. This variant is used
+    /// when copy-on-write is not supported; it always returns `Ok(None)`.
+    pub fn new(
+        _: &Module,
+        _: &[u8],
+        _: Option<(&File, u64)>,
+    ) -> Result<Option<ModuleMemoryImages>> {
+        Ok(None)
+    }
+
+    /// Get the memory image for a particular memory.
+    pub fn get_memory_image(&self, _: DefinedMemoryIndex) -> Option<&Arc<MemoryImage>> {
+        match *self {}
+    }
+}
+
+/// A placeholder for `MemoryImageSlot` when copy-on-write memory
+/// initialization is not included. Slots are never created so this type is
+/// uninhabited.
+#[derive(Debug)]
+pub enum MemoryImageSlot {}
+
+impl MemoryImageSlot {
+    pub(crate) fn create(_: *mut u8, _: usize) -> Self {
+        panic!("create() on invalid MemoryImageSlot");
+    }
+
+    pub(crate) fn no_clear_on_drop(&mut self) {
+        match *self {}
+    }
+
+    pub(crate) fn instantiate(
+        &mut self,
+        _: usize,
+        _: Option<&Arc<MemoryImage>>,
+    ) -> Result<(), InstantiationError> {
+        match *self {}
+    }
+
+    pub(crate) fn set_heap_limit(&mut self, _: usize) -> Result<()> {
+        match *self {}
+    }
+
+    pub(crate) fn clear_and_remain_ready(&mut self) -> Result<()> {
+        match *self {}
+    }
+
+    pub(crate) fn has_image(&self) -> bool {
+        match *self {}
+    }
+}
@@ -0,0 +1,207 @@
+//! Runtime representation of WebAssembly exceptions, and unwinding of wasm
+//! frames to the handler of an exception.
+//!
+//! Exceptions are raised by unwinding the stack with the system unwinder,
+//! which finds wasm frames through the unwind tables registered for compiled
+//! code. Cranelift has no notion of exceptional control flow though, so rather
+//! than jumping to a landing pad the unwinder resumes the innermost call which
+//! is inside a wasm `try` block, as if it returned. That call is followed by a
+//! check of `VMInterrupts::pending_exception` which branches to the handler.
+//! Frames whose calls aren't inside a `try` are unwound without running any of
+//! their code, and so are Rust frames in between, such as those of libcalls
+//! and host functions, just like for traps.
+
+use crate::externref::{ModuleInfoLookup, VMExternRef};
+use crate::vmcontext::{VMTagDefinition, ValRaw};
+use backtrace::Backtrace;
+
+/// A thrown WebAssembly exception.
+///
+/// Exceptions are reference counted by wrapping them in a `VMExternRef`, which
+/// is what compiled code holds on to between a `catch` and a `rethrow`, and
+/// what `VMInterrupts::pending_exception` owns while the exception unwinds.
+pub struct VMException {
+    tag: *mut VMTagDefinition,
+    values: Box<[ValRaw]>,
+    refs: Box<[VMExternRef]>,
+    backtrace: Backtrace,
+}
+
+// The tag pointer is only used for identity comparisons, and the values are
+// plain bits, so it's fine to send exceptions across threads.
+unsafe impl Send for VMException {}
+unsafe impl Sync for VMException {}
+
+impl VMException {
+    /// Creates a new exception with the given tag and values, capturing the
+    /// current backtrace.
+    ///
+    /// The `externref` and `exnref` values among `values` are raw pointers
+    /// which the exception doesn't own, so `refs` must hold a reference to
+    /// each of them to keep them alive for as long as the exception.
+    pub fn new(
+        tag: *mut VMTagDefinition,
+        values: Box<[ValRaw]>,
+        refs: Box<[VMExternRef]>,
+    ) -> VMException {
+        VMException {
+            tag,
+            values,
+            refs,
+            backtrace: Backtrace::new_unresolved(),
+        }
+    }
+
+    /// Returns the tag this exception was thrown with.
+    pub fn tag(&self) -> *mut VMTagDefinition {
+        self.tag
+    }
+
+    /// Returns the values this exception carries.
+    pub fn values(&self) -> &[ValRaw] {
+        &self.values
+    }
+
+    /// Returns the references which keep the reference values this exception
+    /// carries alive.
+    pub fn refs(&self) -> &[VMExternRef] {
+        &self.refs
+    }
+
+    /// Returns the backtrace captured when this exception was first thrown.
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.backtrace
+    }
+}
+
+/// A wasm frame which handles an exception, along with the register state to
+/// resume it with.
+pub(crate) struct Handler {
+    regs: imp::Regs,
+}
+
+impl Handler {
+    /// Walks the stack from the caller outwards, stopping at frames whose stack
+    /// pointer is above `limit`, and returns the innermost wasm frame which
+    /// handles exceptions raised by its current call.
+    pub(crate) fn find(module_info_lookup: &dyn ModuleInfoLookup, limit: usize) -> Option<Handler> {
+        imp::find(module_info_lookup, limit).map(|regs| Handler { regs })
+    }
+
+    /// Resumes execution in the handler's frame, after the call which raised
+    /// the exception.
+    ///
+    /// # Safety
+    ///
+    /// Frames between here and the handler are discarded, so none of them may
+    /// have destructors to run, and the handler's frame must still be live.
+    pub(crate) unsafe fn resume(self) -> ! {
+        imp::resume(&self.regs)
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(all(target_arch = "x86_64", unix))] {
+        mod imp {
+            use crate::externref::ModuleInfoLookup;
+            use std::os::raw::{c_int, c_void};
+
+            /// The callee-saved `rbx`, `rbp`, `r12`, `r13`, `r14` and `r15`,
+            /// followed by the stack pointer and the address to resume at.
+            pub type Regs = [usize; 8];
+
+            /// The DWARF numbers of the callee-saved registers in `Regs`.
+            const CALLEE_SAVED: [c_int; 6] = [3, 6, 12, 13, 14, 15];
+
+            #[repr(C)]
+            struct UnwindContext {
+                _private: [u8; 0],
+            }
+
+            const URC_NO_REASON: c_int = 0;
+            const URC_NORMAL_STOP: c_int = 4;
+
+            extern "C" {
+                fn _Unwind_Backtrace(
+                    trace: extern "C" fn(*mut UnwindContext, *mut c_void) -> c_int,
+                    arg: *mut c_void,
+                ) -> c_int;
+                fn _Unwind_GetIP(ctx: *mut UnwindContext) -> usize;
+                fn _Unwind_GetCFA(ctx: *mut UnwindContext) -> usize;
+                fn _Unwind_GetGR(ctx: *mut UnwindContext, index: c_int) -> usize;
+            }
+
+            #[link(name = "wasmtime-helpers")]
+            extern "C" {
+                fn wasmtime_resume_frame(regs: *const usize) -> !;
+            }
+
+            struct Search<'a> {
+                module_info_lookup: &'a dyn ModuleInfoLookup,
+                limit: usize,
+                regs: Option<Regs>,
+            }
+
+            pub fn find(module_info_lookup: &dyn ModuleInfoLookup, limit: usize) -> Option<Regs> {
+                let mut search = Search {
+                    module_info_lookup,
+                    limit,
+                    regs: None,
+                };
+                unsafe {
+                    _Unwind_Backtrace(visit_frame, &mut search as *mut Search as *mut c_void);
+                }
+                search.regs
+            }
+
+            extern "C" fn visit_frame(ctx: *mut UnwindContext, arg: *mut c_void) -> c_int {
+                unsafe {
+                    let search = &mut *(arg as *mut Search);
+                    // The CFA of the frame's callee is the frame's stack
+                    // pointer once the call returns, even for callees which pop
+                    // their stack arguments.
+                    let sp = _Unwind_GetCFA(ctx);
+                    if sp > search.limit {
+                        return URC_NORMAL_STOP;
+                    }
+                    let pc = _Unwind_GetIP(ctx);
+                    let handles = search
+                        .module_info_lookup
+                        .lookup(pc)
+                        .map_or(false, |info| info.has_exception_handler(pc));
+                    if !handles {
+                        return URC_NO_REASON;
+                    }
+                    let mut regs = [0; 8];
+                    for (reg, index) in regs.iter_mut().zip(CALLEE_SAVED.iter()) {
+                        *reg = _Unwind_GetGR(ctx, *index);
+                    }
+                    regs[6] = sp;
+                    regs[7] = pc;
+                    search.regs = Some(regs);
+                    URC_NORMAL_STOP
+                }
+            }
+
+            pub unsafe fn resume(regs: &Regs) -> ! {
+                wasmtime_resume_frame(regs.as_ptr())
+            }
+        }
+    } else {
+        mod imp {
+            use crate::externref::ModuleInfoLookup;
+
+            pub type Regs = ();
+
+            pub fn find(_module_info_lookup: &dyn ModuleInfoLookup, _limit: usize) -> Option<Regs> {
+                // `Engine::new` rejects the exception handling proposal on
+                // other platforms.
+                unreachable!("wasm exceptions are not supported on this platform")
+            }
+
+            pub unsafe fn resume(_regs: &Regs) -> ! {
+                unreachable!()
+            }
+        }
+    }
+}
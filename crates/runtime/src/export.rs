@@ -1,5 +1,6 @@
 use crate::vmcontext::{
     VMCallerCheckedAnyfunc, VMContext, VMGlobalDefinition, VMMemoryDefinition, VMTableDefinition,
+    VMTagDefinition,
 };
 use std::ptr::NonNull;
 use wasmtime_environ::{Global, MemoryPlan, TablePlan};
@@ -17,6 +18,9 @@ pub enum Export {
 
     /// A global export value.
     Global(ExportGlobal),
+
+    /// An exception tag export value.
+    Tag(ExportTag),
 }
 
 /// A function export value.
@@ -104,3 +108,21 @@ impl From<ExportGlobal> for Export {
         Export::Global(func)
     }
 }
+
+/// An exception tag export value.
+#[derive(Debug, Clone, Copy)]
+pub struct ExportTag {
+    /// The address of the tag definition, which also serves as the tag's
+    /// identity. Its type is recorded in the definition itself.
+    pub definition: *mut VMTagDefinition,
+}
+
+// See docs on send/sync for `ExportFunction` above.
+unsafe impl Send for ExportTag {}
+unsafe impl Sync for ExportTag {}
+
+impl From<ExportTag> for Export {
+    fn from(func: ExportTag) -> Export {
+        Export::Tag(func)
+    }
+}
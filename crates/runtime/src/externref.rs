@@ -806,6 +806,10 @@ pub trait ModuleInfoLookup {
 pub trait ModuleInfo {
     /// Lookup the stack map at a program counter value.
     fn lookup_stack_map(&self, pc: usize) -> Option<&StackMap>;
+
+    /// Returns whether the wasm function which a call returns to at `pc`
+    /// handles the exceptions that the call raises.
+    fn has_exception_handler(&self, pc: usize) -> bool;
 }
 
 #[derive(Debug, Default)]
@@ -1026,10 +1030,12 @@ mod tests {
             num_imported_tables: 0,
             num_imported_memories: 0,
             num_imported_globals: 0,
+            num_imported_tags: 0,
             num_defined_functions: 0,
             num_defined_tables: 0,
             num_defined_memories: 0,
             num_defined_globals: 0,
+            num_defined_tags: 0,
         });
         assert_eq!(
             offsets.vm_extern_data_ref_count(),
@@ -1053,10 +1059,12 @@ mod tests {
             num_imported_tables: 0,
             num_imported_memories: 0,
             num_imported_globals: 0,
+            num_imported_tags: 0,
             num_defined_functions: 0,
             num_defined_tables: 0,
             num_defined_memories: 0,
             num_defined_globals: 0,
+            num_defined_tags: 0,
         });
         assert_eq!(
             offsets.vm_extern_ref_activation_table_next() as usize,
@@ -1080,10 +1088,12 @@ mod tests {
             num_imported_tables: 0,
             num_imported_memories: 0,
             num_imported_globals: 0,
+            num_imported_tags: 0,
             num_defined_functions: 0,
             num_defined_tables: 0,
             num_defined_memories: 0,
             num_defined_globals: 0,
+            num_defined_tags: 0,
         });
         assert_eq!(
             offsets.vm_extern_ref_activation_table_end() as usize,
@@ -102,3 +102,30 @@ struct JITDescriptor __jit_debug_descriptor = {1, 0, NULL, NULL};
 struct JITDescriptor* wasmtime_jit_debug_descriptor() {
   return &__jit_debug_descriptor;
 }
+
+#if defined(__x86_64__) && !defined(CFG_TARGET_OS_windows)
+// Resumes execution at `regs[7]` with the stack pointer set to `regs[6]` and
+// the callee-saved registers rbx, rbp, r12, r13, r14 and r15 set to
+// `regs[0..6]`, which is how wasm exceptions are delivered to the frame that
+// handles them once the unwinder has recovered that frame's registers.
+//
+// The address to resume at is loaded before switching stacks since `regs`
+// lives below the new stack pointer.
+__attribute__((noreturn))
+void wasmtime_resume_frame(const uintptr_t *regs) {
+  __asm__ volatile(
+      "movq 56(%0), %%rcx\n\t"
+      "movq 0(%0), %%rbx\n\t"
+      "movq 8(%0), %%rbp\n\t"
+      "movq 16(%0), %%r12\n\t"
+      "movq 24(%0), %%r13\n\t"
+      "movq 32(%0), %%r14\n\t"
+      "movq 40(%0), %%r15\n\t"
+      "movq 48(%0), %%rsp\n\t"
+      "jmpq *%%rcx\n\t"
+      :
+      : "D"(regs)
+      : "memory");
+  __builtin_unreachable();
+}
+#endif
@@ -1,4 +1,6 @@
-use crate::vmcontext::{VMFunctionImport, VMGlobalImport, VMMemoryImport, VMTableImport};
+use crate::vmcontext::{
+    VMFunctionImport, VMGlobalImport, VMMemoryImport, VMTableImport, VMTagImport,
+};
 
 /// Resolved import pointers.
 ///
@@ -24,4 +26,7 @@ pub struct Imports<'a> {
 
     /// Resolved addresses for imported globals.
     pub globals: &'a [VMGlobalImport],
+
+    /// Resolved addresses for imported exception tags.
+    pub tags: &'a [VMTagImport],
 }
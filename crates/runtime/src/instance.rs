@@ -4,14 +4,15 @@
 
 use crate::export::Export;
 use crate::externref::VMExternRefActivationsTable;
-use crate::memory::{Memory, RuntimeMemoryCreator};
+use crate::memory::{Memory, RuntimeMemoryCreator, SharedMemory};
 use crate::table::{Table, TableElement, TableElementType};
 use crate::traphandlers::Trap;
 use crate::vmcontext::{
     VMCallerCheckedAnyfunc, VMContext, VMFunctionImport, VMGlobalDefinition, VMGlobalImport,
     VMInterrupts, VMMemoryDefinition, VMMemoryImport, VMTableDefinition, VMTableImport,
+    VMTagDefinition, VMTagImport,
 };
-use crate::{ExportFunction, ExportGlobal, ExportMemory, ExportTable, Store};
+use crate::{ExportFunction, ExportGlobal, ExportMemory, ExportTable, ExportTag, Store};
 use anyhow::Error;
 use memoffset::offset_of;
 use more_asserts::assert_lt;
@@ -21,12 +22,14 @@ use std::convert::TryFrom;
 use std::hash::Hash;
 use std::ops::Range;
 use std::ptr::NonNull;
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::{mem, ptr, slice};
 use wasmtime_environ::{
     packed_option::ReservedValue, DataIndex, DefinedGlobalIndex, DefinedMemoryIndex,
-    DefinedTableIndex, ElemIndex, EntityIndex, EntityRef, EntitySet, FuncIndex, GlobalIndex,
-    HostPtr, MemoryIndex, Module, PrimaryMap, TableIndex, TrapCode, VMOffsets, WasmType,
+    DefinedTableIndex, DefinedTagIndex, ElemIndex, EntityIndex, EntityRef, EntitySet, FuncIndex,
+    GlobalIndex, HostPtr, MemoryIndex, Module, PrimaryMap, TableIndex, TagIndex, TrapCode,
+    VMOffsets, WasmType,
 };
 
 mod allocator;
@@ -128,6 +131,11 @@ impl Instance {
         unsafe { &*self.vmctx_plus_offset(self.offsets.vmctx_vmglobal_import(index)) }
     }
 
+    /// Return the indexed `VMTagImport`.
+    fn imported_tag(&self, index: TagIndex) -> &VMTagImport {
+        unsafe { &*self.vmctx_plus_offset(self.offsets.vmctx_vmtag_import(index)) }
+    }
+
     /// Return the indexed `VMTableDefinition`.
     #[allow(dead_code)]
     fn table(&self, index: DefinedTableIndex) -> VMTableDefinition {
@@ -151,13 +159,44 @@ impl Instance {
         if let Some(defined_index) = self.module.defined_memory_index(index) {
             self.memory(defined_index)
         } else {
+            // Go through the instance that defines the memory rather than
+            // reading `import.from` directly, since for shared memories only
+            // the defining `Memory` knows the current length.
             let import = self.imported_memory(index);
-            *unsafe { import.from.as_ref().unwrap() }
+            unsafe {
+                let foreign_instance = (*import.vmctx).instance();
+                let foreign_memory_index = foreign_instance.memory_index(&*import.from);
+                foreign_instance.memory(foreign_memory_index)
+            }
+        }
+    }
+
+    /// Get a locally defined or imported memory as a `SharedMemory`, returning
+    /// `None` if the memory isn't shared.
+    pub(crate) fn get_shared_memory(&self, index: MemoryIndex) -> Option<SharedMemory> {
+        let (instance, index) = match self.module.defined_memory_index(index) {
+            Some(defined_index) => (self, defined_index),
+            None => unsafe {
+                let import = self.imported_memory(index);
+                let foreign_instance = (*import.vmctx).instance();
+                let foreign_memory_index = foreign_instance.memory_index(&*import.from);
+                (foreign_instance, foreign_memory_index)
+            },
+        };
+        match &instance.memories[index] {
+            Memory::Shared(memory) => Some(memory.clone()),
+            _ => None,
         }
     }
 
     /// Return the indexed `VMMemoryDefinition`.
     fn memory(&self, index: DefinedMemoryIndex) -> VMMemoryDefinition {
+        // Shared memories may have been grown by another instance since the
+        // definition in this `VMContext` was last updated, so consult the
+        // memory itself.
+        if let Memory::Shared(memory) = &self.memories[index] {
+            return memory.vmmemory();
+        }
         unsafe { *self.memory_ptr(index) }
     }
 
@@ -198,11 +237,34 @@ impl Instance {
         }
     }
 
+    /// Return the indexed `VMTagDefinition`.
+    fn tag_ptr(&self, index: DefinedTagIndex) -> *mut VMTagDefinition {
+        unsafe { self.vmctx_plus_offset(self.offsets.vmctx_vmtag_definition(index)) }
+    }
+
+    /// Get a raw pointer to the tag at the given index regardless whether it
+    /// is defined locally or imported from another module.
+    ///
+    /// The returned pointer is the identity of the tag when matching thrown
+    /// exceptions against `catch` clauses.
+    pub(crate) fn defined_or_imported_tag_ptr(&self, index: TagIndex) -> *mut VMTagDefinition {
+        if let Some(index) = self.module.defined_tag_index(index) {
+            self.tag_ptr(index)
+        } else {
+            self.imported_tag(index).from
+        }
+    }
+
     /// Return a pointer to the interrupts structure
     pub fn interrupts(&self) -> *mut *const VMInterrupts {
         unsafe { self.vmctx_plus_offset(self.offsets.vmctx_interrupts()) }
     }
 
+    /// Return a pointer to the global epoch counter used by this instance.
+    pub fn epoch_ptr(&self) -> *mut *const AtomicU64 {
+        unsafe { self.vmctx_plus_offset(self.offsets.vmctx_epoch_ptr()) }
+    }
+
     /// Return a pointer to the `VMExternRefActivationsTable`.
     pub fn externref_activations_table(&self) -> *mut *mut VMExternRefActivationsTable {
         unsafe { self.vmctx_plus_offset(self.offsets.vmctx_externref_activations_table()) }
@@ -268,7 +330,14 @@ impl Instance {
             EntityIndex::Memory(index) => {
                 let (definition, vmctx) =
                     if let Some(def_index) = self.module.defined_memory_index(*index) {
-                        (self.memory_ptr(def_index), self.vmctx_ptr())
+                        // Everything importing a shared memory points at the
+                        // definition owned by the memory itself so that they
+                        // all see its current length.
+                        let definition = match &self.memories[def_index] {
+                            Memory::Shared(memory) => memory.vmmemory_ptr(),
+                            _ => self.memory_ptr(def_index),
+                        };
+                        (definition, self.vmctx_ptr())
                     } else {
                         let import = self.imported_memory(*index);
                         (import.from, import.vmctx)
@@ -290,6 +359,10 @@ impl Instance {
                 global: self.module.globals[*index],
             }
             .into(),
+            EntityIndex::Tag(index) => ExportTag {
+                definition: self.defined_or_imported_tag_ptr(*index),
+            }
+            .into(),
 
             EntityIndex::Instance(_) | EntityIndex::Module(_) => {
                 panic!("can't use this api for modules/instances")
@@ -333,12 +406,17 @@ impl Instance {
 
     /// Return the memory index for the given `VMMemoryDefinition`.
     unsafe fn memory_index(&self, memory: &VMMemoryDefinition) -> DefinedMemoryIndex {
+        let memory = memory as *const VMMemoryDefinition;
+        for (index, defined) in self.memories.iter() {
+            if let Memory::Shared(shared) = defined {
+                if shared.vmmemory_ptr() as *const _ == memory {
+                    return index;
+                }
+            }
+        }
         let index = DefinedMemoryIndex::new(
-            usize::try_from(
-                (memory as *const VMMemoryDefinition)
-                    .offset_from(self.memory_ptr(DefinedMemoryIndex::new(0))),
-            )
-            .unwrap(),
+            usize::try_from(memory.offset_from(self.memory_ptr(DefinedMemoryIndex::new(0))))
+                .unwrap(),
         );
         assert_lt!(index.index(), self.memories.len());
         index
@@ -1,3 +1,4 @@
+use crate::cow::ModuleMemoryImages;
 use crate::imports::Imports;
 use crate::instance::{Instance, InstanceHandle, RuntimeMemoryCreator};
 use crate::memory::{DefaultMemoryCreator, Memory};
@@ -5,9 +6,9 @@ use crate::table::Table;
 use crate::traphandlers::Trap;
 use crate::vmcontext::{
     VMBuiltinFunctionsArray, VMCallerCheckedAnyfunc, VMContext, VMGlobalDefinition,
-    VMSharedSignatureIndex,
+    VMSharedSignatureIndex, VMTagDefinition,
 };
-use crate::Store;
+use crate::{CompiledModuleId, Store};
 use anyhow::Result;
 use std::alloc;
 use std::any::Any;
@@ -77,6 +78,17 @@ pub struct InstanceAllocationRequest<'a> {
     /// responsibility of the callee when allocating to ensure that this data
     /// outlives the instance.
     pub wasm_data: *const [u8],
+
+    /// The memory images of the module being instantiated, if any, which are
+    /// used to initialize its linear memories copy-on-write instead of
+    /// copying in data segments.
+    pub memory_images: Option<&'a ModuleMemoryImages>,
+
+    /// The unique ID of the compiled module being instantiated, if any.
+    ///
+    /// This is `None` for instances created by the host, such as those
+    /// backing host-defined functions, memories and tables.
+    pub unique_id: Option<CompiledModuleId>,
 }
 
 /// A pointer to a Store. This Option<*mut dyn Store> is wrapped in a struct
@@ -211,6 +223,24 @@ pub unsafe trait InstanceAllocator: Send + Sync {
     /// The provided stack is required to have been allocated with `allocate_fiber_stack`.
     #[cfg(feature = "async")]
     unsafe fn deallocate_fiber_stack(&self, stack: &wasmtime_fiber::FiberStack);
+
+    /// Returns statistics about how often instances were allocated into slots
+    /// last used by the same module, for allocators which keep track of this.
+    fn affinity_stats(&self) -> Option<AffinityStats> {
+        None
+    }
+}
+
+/// Counters of how often an instance allocator was able to reuse a slot which
+/// was last used by the same module.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AffinityStats {
+    /// The number of allocations which reused a slot last used by the same
+    /// module.
+    pub hits: u64,
+    /// The number of allocations which couldn't reuse a slot last used by the
+    /// same module.
+    pub misses: u64,
 }
 
 pub enum SharedSignatures<'a> {
@@ -407,6 +437,9 @@ fn check_init_bounds(instance: &mut Instance, module: &Module) -> Result<(), Ins
         MemoryInitialization::Segmented(initializers) => {
             check_memory_init_bounds(instance, initializers)?;
         }
+        // Static initialization is only used when all data segments are
+        // known to be in bounds.
+        MemoryInitialization::Static { .. } => {}
     }
 
     Ok(())
@@ -455,6 +488,26 @@ fn initialize_instance(
         MemoryInitialization::Segmented(initializers) => {
             initialize_memories(instance, initializers)?;
         }
+        MemoryInitialization::Static { map } => {
+            for (index, init) in map {
+                // Memories whose image was mapped into them when they were
+                // created are already initialized.
+                if !instance.memories[index].needs_init() {
+                    continue;
+                }
+                let init = match init {
+                    Some(init) => init,
+                    None => continue,
+                };
+
+                let memory = instance.memory(index);
+                let slice =
+                    unsafe { slice::from_raw_parts_mut(memory.base, memory.current_length) };
+                let start = usize::try_from(init.offset).unwrap();
+                let end = start + (init.data.end - init.data.start) as usize;
+                slice[start..end].copy_from_slice(instance.wasm_data(init.data.clone()));
+            }
+        }
     }
 
     Ok(())
@@ -463,6 +516,7 @@ fn initialize_instance(
 unsafe fn initialize_vmcontext(instance: &mut Instance, req: InstanceAllocationRequest) {
     if let Some(store) = req.store.as_raw() {
         *instance.interrupts() = (*store).vminterrupts();
+        *instance.epoch_ptr() = (*store).epoch_ptr();
         *instance.externref_activations_table() = (*store).externref_activations_table().0;
         instance.set_store(store);
     }
@@ -510,6 +564,12 @@ unsafe fn initialize_vmcontext(instance: &mut Instance, req: InstanceAllocationR
         instance.vmctx_plus_offset(instance.offsets.vmctx_imported_globals_begin()),
         req.imports.globals.len(),
     );
+    debug_assert_eq!(req.imports.tags.len(), module.num_imported_tags);
+    ptr::copy(
+        req.imports.tags.as_ptr(),
+        instance.vmctx_plus_offset(instance.offsets.vmctx_imported_tags_begin()),
+        req.imports.tags.len(),
+    );
 
     // Initialize the functions
     let mut base = instance.anyfunc_base();
@@ -555,6 +615,18 @@ unsafe fn initialize_vmcontext(instance: &mut Instance, req: InstanceAllocationR
         ptr = ptr.add(1);
     }
 
+    // Initialize the defined tags
+    let mut ptr = instance.vmctx_plus_offset(instance.offsets.vmctx_tags_begin());
+    for sig in module.tags.values().skip(module.num_imported_tags) {
+        ptr::write(
+            ptr,
+            VMTagDefinition {
+                type_index: req.shared_signatures.lookup(*sig),
+            },
+        );
+        ptr = ptr.add(1);
+    }
+
     // Initialize the defined globals
     initialize_vmcontext_globals(instance);
 }
@@ -647,6 +719,7 @@ impl OnDemandInstanceAllocator {
         &self,
         module: &Module,
         store: &mut StorePtr,
+        memory_images: Option<&ModuleMemoryImages>,
     ) -> Result<PrimaryMap<DefinedMemoryIndex, Memory>, InstantiationError> {
         let creator = self
             .mem_creator
@@ -655,13 +728,24 @@ impl OnDemandInstanceAllocator {
         let num_imports = module.num_imported_memories;
         let mut memories: PrimaryMap<DefinedMemoryIndex, _> =
             PrimaryMap::with_capacity(module.memory_plans.len() - num_imports);
-        for plan in &module.memory_plans.values().as_slice()[num_imports..] {
+        for (memory_idx, plan) in module.memory_plans.iter().skip(num_imports) {
+            let defined_memory_idx = module
+                .defined_memory_index(memory_idx)
+                .expect("Skipped imports, should never be None");
+            let image =
+                memory_images.and_then(|images| images.get_memory_image(defined_memory_idx));
+
             memories.push(
-                Memory::new_dynamic(plan, creator, unsafe {
-                    store
-                        .get()
-                        .expect("if module has memory plans, store is not empty")
-                })
+                Memory::new_dynamic(
+                    plan,
+                    creator,
+                    unsafe {
+                        store
+                            .get()
+                            .expect("if module has memory plans, store is not empty")
+                    },
+                    image,
+                )
                 .map_err(InstantiationError::Resource)?,
             );
         }
@@ -684,7 +768,7 @@ unsafe impl InstanceAllocator for OnDemandInstanceAllocator {
         &self,
         mut req: InstanceAllocationRequest,
     ) -> Result<InstanceHandle, InstantiationError> {
-        let memories = self.create_memories(&req.module, &mut req.store)?;
+        let memories = self.create_memories(&req.module, &mut req.store, req.memory_images)?;
         let tables = Self::create_tables(&req.module, &mut req.store)?;
 
         let host_state = std::mem::replace(&mut req.host_state, Box::new(()));
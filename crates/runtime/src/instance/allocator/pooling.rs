@@ -8,19 +8,21 @@
 //! when modules can be constrained based on configurable limits.
 
 use super::{
-    initialize_instance, initialize_vmcontext, InstanceAllocationRequest, InstanceAllocator,
-    InstanceHandle, InstantiationError,
+    initialize_instance, initialize_vmcontext, AffinityStats, InstanceAllocationRequest,
+    InstanceAllocator, InstanceHandle, InstantiationError,
 };
-use crate::{instance::Instance, Memory, Mmap, Table, VMContext};
+use crate::cow::{MemoryImageSlot, ModuleMemoryImages};
+use crate::{instance::Instance, CompiledModuleId, Memory, Mmap, Table, VMContext};
 use anyhow::{anyhow, bail, Context, Result};
 use rand::Rng;
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::marker;
 use std::mem;
 use std::sync::{Arc, Mutex};
 use wasmtime_environ::{
-    EntitySet, HostPtr, MemoryStyle, Module, PrimaryMap, Tunables, VMOffsets, VMOffsetsFields,
-    WASM_PAGE_SIZE,
+    DefinedMemoryIndex, EntityRef, EntitySet, HostPtr, MemoryStyle, Module, PrimaryMap, Tunables,
+    VMOffsets, VMOffsetsFields, WASM_PAGE_SIZE,
 };
 
 cfg_if::cfg_if! {
@@ -48,6 +50,18 @@ use imp::{commit_stack_pages, decommit_stack_pages};
 #[cfg(feature = "async")]
 use super::FiberStackError;
 
+/// Whether linear memories in the pool are managed with `MemoryImageSlot`s,
+/// which map memory images copy-on-write and reset memories with `madvise`
+/// when their slot is reused.
+///
+/// This isn't compatible with the `uffd` feature, whose fault handler manages
+/// the contents of the pool's memories itself.
+const USE_IMAGE_SLOTS: bool = cfg!(all(
+    feature = "memory-init-cow",
+    target_os = "linux",
+    not(feature = "uffd")
+));
+
 fn round_up_to_pow2(n: usize, to: usize) -> usize {
     debug_assert!(to > 0);
     debug_assert!(to.is_power_of_two());
@@ -169,6 +183,11 @@ impl ModuleLimits {
             );
         }
 
+        // Space for tags isn't reserved in the pooled `VMContext` slots.
+        if !module.tags.is_empty() {
+            bail!("exception tags are not supported by the pooling allocator");
+        }
+
         for (i, plan) in module.table_plans.values().as_slice()[module.num_imported_tables..]
             .iter()
             .enumerate()
@@ -202,6 +221,15 @@ impl ModuleLimits {
                     i,
                 );
             }
+
+            // Memories in the pool are owned by a single instance slot and are
+            // reset when that slot is reused, so they can't be shared.
+            if plan.memory.shared {
+                bail!(
+                    "memory index {} is a shared memory, which is not supported by the pooling allocator",
+                    i,
+                );
+            }
         }
 
         Ok(())
@@ -248,6 +276,14 @@ pub enum PoolingAllocationStrategy {
     NextAvailable,
     /// Allocate from a random available instance.
     Random,
+    /// Try to allocate an instance slot that was last used by the same module,
+    /// falling back to a slot that hasn't been used yet and then to the next
+    /// available instance.
+    ///
+    /// Reusing a slot for the same module means its linear memories can be
+    /// reused with minimal work, for example keeping copy-on-write memory
+    /// images mapped between instantiations.
+    ReuseAffinity,
 }
 
 impl PoolingAllocationStrategy {
@@ -255,7 +291,7 @@ impl PoolingAllocationStrategy {
         debug_assert!(free_count > 0);
 
         match self {
-            Self::NextAvailable => free_count - 1,
+            Self::NextAvailable | Self::ReuseAffinity => free_count - 1,
             Self::Random => rand::thread_rng().gen_range(0..free_count),
         }
     }
@@ -267,6 +303,114 @@ impl Default for PoolingAllocationStrategy {
     }
 }
 
+/// The free list of instance slots in an `InstancePool`.
+///
+/// Alongside the list of all free slots this tracks the module that last
+/// used each slot, and the free slots which were last used by each module,
+/// so that the `ReuseAffinity` strategy can find a matching slot in constant
+/// time.
+#[derive(Debug)]
+struct FreeList {
+    /// The indices of all free slots.
+    slots: Vec<usize>,
+    /// The position of each free slot within `slots`.
+    slot_positions: Vec<usize>,
+    /// The free slots last used by each module, where `None` is used for
+    /// slots which haven't been used by a module yet.
+    by_module: HashMap<Option<CompiledModuleId>, Vec<usize>>,
+    /// The position of each free slot within its list in `by_module`.
+    module_positions: Vec<usize>,
+    /// The module that last used each slot.
+    last_module: Vec<Option<CompiledModuleId>>,
+    stats: AffinityStats,
+}
+
+impl FreeList {
+    fn new(max_instances: usize) -> Self {
+        let slots: Vec<usize> = (0..max_instances).collect();
+        let mut by_module = HashMap::new();
+        by_module.insert(None, slots.clone());
+        Self {
+            slot_positions: slots.clone(),
+            module_positions: slots.clone(),
+            slots,
+            by_module,
+            last_module: vec![None; max_instances],
+            stats: AffinityStats::default(),
+        }
+    }
+
+    #[cfg(test)]
+    fn slots(&self) -> &[usize] {
+        &self.slots
+    }
+
+    fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Takes a free slot for an instance of the module `id`.
+    fn alloc(
+        &mut self,
+        strategy: PoolingAllocationStrategy,
+        id: Option<CompiledModuleId>,
+    ) -> usize {
+        debug_assert!(!self.is_empty());
+
+        let index = match (strategy, id) {
+            (PoolingAllocationStrategy::ReuseAffinity, Some(_)) => {
+                match self.by_module.get(&id).and_then(|slots| slots.last()) {
+                    Some(index) => {
+                        self.stats.hits += 1;
+                        *index
+                    }
+                    None => {
+                        self.stats.misses += 1;
+                        match self.by_module.get(&None).and_then(|slots| slots.last()) {
+                            Some(index) => *index,
+                            None => self.slots[strategy.next(self.slots.len())],
+                        }
+                    }
+                }
+            }
+            _ => self.slots[strategy.next(self.slots.len())],
+        };
+
+        self.remove(index);
+        self.last_module[index] = id;
+        index
+    }
+
+    /// Returns the slot `index` to the free list.
+    fn free(&mut self, index: usize) {
+        self.slot_positions[index] = self.slots.len();
+        self.slots.push(index);
+
+        let slots = self.by_module.entry(self.last_module[index]).or_default();
+        self.module_positions[index] = slots.len();
+        slots.push(index);
+    }
+
+    fn remove(&mut self, index: usize) {
+        let pos = self.slot_positions[index];
+        self.slots.swap_remove(pos);
+        if let Some(moved) = self.slots.get(pos) {
+            self.slot_positions[*moved] = pos;
+        }
+
+        let id = self.last_module[index];
+        let slots = self.by_module.get_mut(&id).unwrap();
+        let pos = self.module_positions[index];
+        slots.swap_remove(pos);
+        if let Some(moved) = slots.get(pos) {
+            self.module_positions[*moved] = pos;
+        }
+        if slots.is_empty() {
+            self.by_module.remove(&id);
+        }
+    }
+}
+
 /// Represents a pool of maximal `Instance` structures.
 ///
 /// Each index in the pool provides enough space for a maximal `Instance`
@@ -281,7 +425,7 @@ struct InstancePool {
     mapping: Mmap,
     instance_size: usize,
     max_instances: usize,
-    free_list: Mutex<Vec<usize>>,
+    free_list: Mutex<FreeList>,
     memories: MemoryPool,
     tables: TablePool,
     empty_module: Arc<Module>,
@@ -303,10 +447,12 @@ impl InstancePool {
             num_imported_tables: module_limits.imported_tables,
             num_imported_memories: module_limits.imported_memories,
             num_imported_globals: module_limits.imported_globals,
+            num_imported_tags: 0,
             num_defined_functions: module_limits.functions,
             num_defined_tables: module_limits.tables,
             num_defined_memories: module_limits.memories,
             num_defined_globals: module_limits.globals,
+            num_defined_tags: 0,
         });
 
         let instance_size = round_up_to_pow2(
@@ -329,7 +475,7 @@ impl InstancePool {
             mapping,
             instance_size,
             max_instances,
-            free_list: Mutex::new((0..max_instances).collect()),
+            free_list: Mutex::new(FreeList::new(max_instances)),
             memories: MemoryPool::new(module_limits, instance_limits, tunables)?,
             tables: TablePool::new(module_limits, instance_limits)?,
             empty_module: Arc::new(Module::default()),
@@ -390,11 +536,7 @@ impl InstancePool {
             instance.set_store(store);
         }
 
-        Self::set_instance_memories(
-            instance,
-            self.memories.get(index),
-            self.memories.max_wasm_pages,
-        )?;
+        Self::set_instance_memories(index, instance, &self.memories, req.memory_images)?;
 
         Self::set_instance_tables(
             instance,
@@ -419,8 +561,7 @@ impl InstancePool {
             if free_list.is_empty() {
                 return Err(InstantiationError::Limit(self.max_instances as u32));
             }
-            let free_index = strategy.next(free_list.len());
-            free_list.swap_remove(free_index)
+            free_list.alloc(strategy, req.unique_id)
         };
 
         unsafe {
@@ -448,7 +589,9 @@ impl InstancePool {
         let instance = unsafe { &mut *handle.instance };
 
         // Decommit any linear memories that were used
-        for (memory, base) in instance.memories.values_mut().zip(self.memories.get(index)) {
+        for ((def_mem_idx, memory), base) in
+            instance.memories.iter_mut().zip(self.memories.get(index))
+        {
             let mut memory = mem::take(memory);
             debug_assert!(memory.is_static());
 
@@ -460,8 +603,22 @@ impl InstancePool {
             drop(&mut memory); // require mutable on all platforms, not just uffd
 
             let size = memory.byte_size();
-            drop(memory);
-            decommit_memory_pages(base, size).expect("failed to decommit linear memory pages");
+            match memory.unwrap_static_image() {
+                Some(mut slot) => {
+                    // Reset the slot so it's ready for the next instance. If
+                    // that fails then the slot is dropped instead, which
+                    // resets its memory to a fresh inaccessible reservation
+                    // and a new slot is created the next time it's used.
+                    if slot.clear_and_remain_ready().is_ok() {
+                        self.memories
+                            .return_memory_image_slot(index, def_mem_idx, slot);
+                    }
+                }
+                None => {
+                    decommit_memory_pages(base, size)
+                        .expect("failed to decommit linear memory pages");
+                }
+            }
         }
 
         instance.memories.clear();
@@ -498,29 +655,51 @@ impl InstancePool {
         instance.offsets = VMOffsets::new(HostPtr, &self.empty_module);
         instance.wasm_data = &[];
 
-        self.free_list.lock().unwrap().push(index);
+        self.free_list.lock().unwrap().free(index);
     }
 
     fn set_instance_memories(
+        instance_index: usize,
         instance: &mut Instance,
-        mut memories: impl Iterator<Item = *mut u8>,
-        max_pages: u64,
+        memories: &MemoryPool,
+        memory_images: Option<&ModuleMemoryImages>,
     ) -> Result<(), InstantiationError> {
         let module = instance.module.as_ref();
 
         debug_assert!(instance.memories.is_empty());
 
-        for plan in
-            (&module.memory_plans.values().as_slice()[module.num_imported_memories..]).iter()
+        for (memory_index, plan) in module
+            .memory_plans
+            .iter()
+            .skip(module.num_imported_memories)
         {
+            let defined_index = module
+                .defined_memory_index(memory_index)
+                .expect("should be a defined memory since we skipped imported ones");
+
             let memory = unsafe {
                 std::slice::from_raw_parts_mut(
-                    memories.next().unwrap(),
-                    (max_pages as usize) * (WASM_PAGE_SIZE as usize),
+                    memories.get_base(instance_index, defined_index),
+                    (memories.max_wasm_pages as usize) * (WASM_PAGE_SIZE as usize),
                 )
             };
+
+            let slot = if USE_IMAGE_SLOTS {
+                let mut slot = memories.take_memory_image_slot(instance_index, defined_index);
+                let image = memory_images.and_then(|images| images.get_memory_image(defined_index));
+                let initial_size =
+                    usize::try_from(plan.memory.minimum).unwrap() * (WASM_PAGE_SIZE as usize);
+
+                // If this fails then the slot is dropped, which resets its
+                // memory to a fresh inaccessible reservation.
+                slot.instantiate(initial_size, image)?;
+                Some(slot)
+            } else {
+                None
+            };
+
             instance.memories.push(
-                Memory::new_static(plan, memory, commit_memory_pages, unsafe {
+                Memory::new_static(plan, memory, commit_memory_pages, slot, unsafe {
                     &mut *instance.store()
                 })
                 .map_err(InstantiationError::Resource)?,
@@ -589,6 +768,10 @@ impl Drop for InstancePool {
 #[derive(Debug)]
 struct MemoryPool {
     mapping: Mmap,
+    // The copy-on-write slot of each memory in the pool, if `USE_IMAGE_SLOTS`.
+    // Ownership of a slot is transferred to its `Memory` while in use, so a
+    // `None` entry is either in use or hasn't been created yet.
+    image_slots: Vec<Mutex<Option<MemoryImageSlot>>>,
     // The size, in bytes, of each linear memory's reservation plus the guard
     // region allocated for it.
     memory_size: usize,
@@ -673,8 +856,16 @@ impl MemoryPool {
         let mapping = Mmap::accessible_reserved(0, allocation_size)
             .context("failed to create memory pool mapping")?;
 
+        let num_image_slots = if USE_IMAGE_SLOTS {
+            max_instances * max_memories
+        } else {
+            0
+        };
+        let image_slots = (0..num_image_slots).map(|_| Mutex::new(None)).collect();
+
         let pool = Self {
             mapping,
+            image_slots,
             memory_size,
             initial_memory_offset,
             max_memories,
@@ -701,6 +892,52 @@ impl MemoryPool {
         let size = self.memory_size;
         (0..self.max_memories).map(move |i| unsafe { base.add(i * size) })
     }
+
+    fn get_base(&self, instance_index: usize, memory_index: DefinedMemoryIndex) -> *mut u8 {
+        debug_assert!(memory_index.index() < self.max_memories);
+        self.get(instance_index).nth(memory_index.index()).unwrap()
+    }
+
+    /// Takes ownership of the copy-on-write slot for the given memory,
+    /// creating it if it doesn't exist yet. The slot should be returned with
+    /// `return_memory_image_slot` once the memory is deallocated.
+    fn take_memory_image_slot(
+        &self,
+        instance_index: usize,
+        memory_index: DefinedMemoryIndex,
+    ) -> MemoryImageSlot {
+        let idx = instance_index * self.max_memories + memory_index.index();
+        let slot = self.image_slots[idx].lock().unwrap().take();
+        slot.unwrap_or_else(|| {
+            MemoryImageSlot::create(
+                self.get_base(instance_index, memory_index),
+                self.memory_size,
+            )
+        })
+    }
+
+    /// Returns a copy-on-write slot taken with `take_memory_image_slot`.
+    fn return_memory_image_slot(
+        &self,
+        instance_index: usize,
+        memory_index: DefinedMemoryIndex,
+        slot: MemoryImageSlot,
+    ) {
+        let idx = instance_index * self.max_memories + memory_index.index();
+        *self.image_slots[idx].lock().unwrap() = Some(slot);
+    }
+}
+
+impl Drop for MemoryPool {
+    fn drop(&mut self) {
+        // The whole pool is about to be unmapped, so there's no need for
+        // slots to reset their memory when they're dropped.
+        for slot in mem::take(&mut self.image_slots) {
+            if let Some(mut slot) = slot.into_inner().unwrap() {
+                slot.no_clear_on_drop();
+            }
+        }
+    }
 }
 
 /// Represents a pool of WebAssembly tables.
@@ -1019,6 +1256,10 @@ unsafe impl InstanceAllocator for PoolingInstanceAllocator {
         self.instances.deallocate(handle);
     }
 
+    fn affinity_stats(&self) -> Option<AffinityStats> {
+        Some(self.instances.free_list.lock().unwrap().stats)
+    }
+
     #[cfg(all(feature = "async", unix))]
     fn allocate_fiber_stack(&self) -> Result<wasmtime_fiber::FiberStack, FiberStackError> {
         self.stacks.allocate(self.strategy)
@@ -1356,6 +1597,53 @@ mod test {
         assert_eq!(strat.next(1), 0);
     }
 
+    #[test]
+    fn test_reuse_affinity_allocation_strategy() {
+        let strat = PoolingAllocationStrategy::ReuseAffinity;
+        let a = Some(CompiledModuleId::alloc());
+        let b = Some(CompiledModuleId::alloc());
+        let mut free_list = FreeList::new(3);
+
+        // Unused slots are handed out first, and count as misses.
+        assert_eq!(free_list.alloc(strat, a), 2);
+        assert_eq!(free_list.alloc(strat, b), 1);
+        assert_eq!(free_list.stats, AffinityStats { hits: 0, misses: 2 });
+
+        // Freed slots go back to the module which last used them, even when
+        // they aren't the next available slot.
+        free_list.free(2);
+        free_list.free(1);
+        assert_eq!(free_list.alloc(strat, a), 2);
+        assert_eq!(free_list.alloc(strat, b), 1);
+        assert_eq!(free_list.stats, AffinityStats { hits: 2, misses: 2 });
+
+        // Once no unused slots remain a miss takes any free slot.
+        free_list.free(1);
+        let c = Some(CompiledModuleId::alloc());
+        assert_eq!(free_list.alloc(strat, c), 0);
+        assert_eq!(free_list.alloc(strat, c), 1);
+        assert_eq!(free_list.stats, AffinityStats { hits: 2, misses: 4 });
+        assert!(free_list.is_empty());
+
+        // Host instances don't count towards the statistics.
+        free_list.free(0);
+        assert_eq!(free_list.alloc(strat, None), 0);
+        assert_eq!(free_list.stats, AffinityStats { hits: 2, misses: 4 });
+
+        // Other strategies keep their behavior.
+        free_list.free(0);
+        free_list.free(2);
+        free_list.free(1);
+        assert_eq!(free_list.slots(), &[0, 2, 1]);
+        assert_eq!(
+            free_list.alloc(PoolingAllocationStrategy::NextAvailable, a),
+            1
+        );
+        assert_eq!(free_list.slots(), &[0, 2]);
+        assert_eq!(free_list.alloc(strat, a), 2);
+        assert_eq!(free_list.slots(), &[0]);
+    }
+
     #[cfg(target_pointer_width = "64")]
     #[test]
     fn test_instance_pool() -> Result<()> {
@@ -1388,7 +1676,7 @@ mod test {
         assert_eq!(instances.instance_size, region::page::size());
         assert_eq!(instances.max_instances, 3);
 
-        assert_eq!(&*instances.free_list.lock().unwrap(), &[0, 1, 2]);
+        assert_eq!(instances.free_list.lock().unwrap().slots(), &[0, 1, 2]);
 
         let mut handles = Vec::new();
         let module = Arc::new(Module::default());
@@ -1408,18 +1696,21 @@ mod test {
                                 tables: &[],
                                 memories: &[],
                                 globals: &[],
+                                tags: &[],
                             },
                             shared_signatures: VMSharedSignatureIndex::default().into(),
                             host_state: Box::new(()),
                             store: StorePtr::empty(),
                             wasm_data: &[],
+                            memory_images: None,
+                            unique_id: None,
                         },
                     )
                     .expect("allocation should succeed"),
             );
         }
 
-        assert_eq!(&*instances.free_list.lock().unwrap(), &[]);
+        assert_eq!(instances.free_list.lock().unwrap().slots(), &[]);
 
         match instances.allocate(
             PoolingAllocationStrategy::NextAvailable,
@@ -1432,11 +1723,14 @@ mod test {
                     tables: &[],
                     memories: &[],
                     globals: &[],
+                    tags: &[],
                 },
                 shared_signatures: VMSharedSignatureIndex::default().into(),
                 host_state: Box::new(()),
                 store: StorePtr::empty(),
                 wasm_data: &[],
+                memory_images: None,
+                unique_id: None,
             },
         ) {
             Err(InstantiationError::Limit(3)) => {}
@@ -1447,7 +1741,7 @@ mod test {
             instances.deallocate(&handle);
         }
 
-        assert_eq!(&*instances.free_list.lock().unwrap(), &[2, 1, 0]);
+        assert_eq!(instances.free_list.lock().unwrap().slots(), &[2, 1, 0]);
 
         Ok(())
     }
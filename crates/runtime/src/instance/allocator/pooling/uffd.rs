@@ -517,6 +517,9 @@ mod test {
                 fn vminterrupts(&self) -> *mut crate::VMInterrupts {
                     std::ptr::null_mut()
                 }
+                fn epoch_ptr(&self) -> *const std::sync::atomic::AtomicU64 {
+                    std::ptr::null()
+                }
                 fn externref_activations_table(
                     &mut self,
                 ) -> (
@@ -546,6 +549,9 @@ mod test {
                 fn out_of_gas(&mut self) -> Result<(), anyhow::Error> {
                     Ok(())
                 }
+                fn new_epoch(&mut self) -> Result<u64, anyhow::Error> {
+                    Ok(0)
+                }
             }
             struct MockModuleInfo;
             impl crate::ModuleInfoLookup for MockModuleInfo {
@@ -577,11 +583,14 @@ mod test {
                                     tables: &[],
                                     memories: &[],
                                     globals: &[],
+                                    tags: &[],
                                 },
                                 shared_signatures: VMSharedSignatureIndex::default().into(),
                                 host_state: Box::new(()),
                                 store: StorePtr::new(&mut mock_store),
                                 wasm_data: &[],
+                                memory_images: None,
+                                unique_id: None,
                             },
                         )
                         .expect("instance should allocate"),
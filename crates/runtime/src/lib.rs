@@ -21,7 +21,9 @@
 )]
 
 use anyhow::Error;
+use std::sync::atomic::AtomicU64;
 
+mod exception;
 mod export;
 mod externref;
 mod imports;
@@ -29,6 +31,8 @@ mod instance;
 mod jit_int;
 mod memory;
 mod mmap;
+mod module_id;
+mod parking_spot;
 mod table;
 mod traphandlers;
 mod vmcontext;
@@ -36,29 +40,44 @@ mod vmcontext;
 pub mod debug_builtins;
 pub mod libcalls;
 
+cfg_if::cfg_if! {
+    if #[cfg(all(feature = "memory-init-cow", target_os = "linux"))] {
+        mod cow;
+    } else {
+        mod cow_disabled;
+        use cow_disabled as cow;
+    }
+}
+
+pub use crate::cow::{MemoryImage, ModuleMemoryImages};
+pub use crate::exception::VMException;
 pub use crate::export::*;
 pub use crate::externref::*;
 pub use crate::imports::Imports;
 pub use crate::instance::{
-    InstanceAllocationRequest, InstanceAllocator, InstanceHandle, InstantiationError, LinkError,
-    OnDemandInstanceAllocator, StorePtr,
+    AffinityStats, InstanceAllocationRequest, InstanceAllocator, InstanceHandle,
+    InstantiationError, LinkError, OnDemandInstanceAllocator, StorePtr,
 };
 #[cfg(feature = "pooling-allocator")]
 pub use crate::instance::{
     InstanceLimits, ModuleLimits, PoolingAllocationStrategy, PoolingInstanceAllocator,
 };
 pub use crate::jit_int::GdbJitImageRegistration;
-pub use crate::memory::{Memory, RuntimeLinearMemory, RuntimeMemoryCreator};
+pub use crate::memory::{
+    Memory, RuntimeLinearMemory, RuntimeMemoryCreator, SharedMemory, WaitResult,
+};
 pub use crate::mmap::Mmap;
+pub use crate::module_id::CompiledModuleId;
 pub use crate::table::{Table, TableElement};
 pub use crate::traphandlers::{
-    catch_traps, init_traps, raise_lib_trap, raise_user_trap, resume_panic, tls_eager_initialize,
-    SignalHandler, TlsRestore, Trap,
+    catch_traps, init_traps, raise_exception, raise_lib_trap, raise_user_trap, resume_panic,
+    tls_eager_initialize, SignalHandler, TlsRestore, Trap,
 };
 pub use crate::vmcontext::{
-    VMCallerCheckedAnyfunc, VMContext, VMFunctionBody, VMFunctionImport, VMGlobalDefinition,
-    VMGlobalImport, VMInterrupts, VMInvokeArgument, VMMemoryDefinition, VMMemoryImport,
-    VMSharedSignatureIndex, VMTableDefinition, VMTableImport, VMTrampoline, ValRaw,
+    CoreDumpFrameState, CoreDumpValue, VMCallerCheckedAnyfunc, VMContext, VMCoreDumpFrame,
+    VMFunctionBody, VMFunctionImport, VMGlobalDefinition, VMGlobalImport, VMInterrupts,
+    VMInvokeArgument, VMMemoryDefinition, VMMemoryImport, VMSharedSignatureIndex,
+    VMTableDefinition, VMTableImport, VMTagDefinition, VMTagImport, VMTrampoline, ValRaw,
 };
 
 /// Version number of this crate.
@@ -84,6 +103,11 @@ pub unsafe trait Store {
     /// in the `VMContext`.
     fn vminterrupts(&self) -> *mut VMInterrupts;
 
+    /// Returns a pointer to the global epoch counter.
+    ///
+    /// Used to configure the `VMContext` on initialization.
+    fn epoch_ptr(&self) -> *const AtomicU64;
+
     /// Returns the externref management structures necessary for this store.
     ///
     /// The first element returned is the table in which externrefs are stored
@@ -119,4 +143,9 @@ pub unsafe trait Store {
     /// is returned that's raised as a trap. Otherwise wasm execution will
     /// continue as normal.
     fn out_of_gas(&mut self) -> Result<(), Error>;
+    /// Callback invoked whenever an instance observes that the epoch counter
+    /// has reached its deadline. If an error is returned that's raised as a
+    /// trap. Otherwise wasm execution will continue with the returned value
+    /// as the new deadline.
+    fn new_epoch(&mut self) -> Result<u64, Error>;
 }
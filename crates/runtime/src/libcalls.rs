@@ -56,15 +56,20 @@
 //!   }
 //!   ```
 
+use crate::exception::VMException;
 use crate::externref::VMExternRef;
 use crate::instance::Instance;
+use crate::memory::SharedMemory;
 use crate::table::{Table, TableElementType};
 use crate::traphandlers::{raise_lib_trap, resume_panic, Trap};
-use crate::vmcontext::{VMCallerCheckedAnyfunc, VMContext};
+use crate::vmcontext::{VMCallerCheckedAnyfunc, VMContext, ValRaw};
 use backtrace::Backtrace;
 use std::mem;
 use std::ptr::{self, NonNull};
-use wasmtime_environ::{DataIndex, ElemIndex, GlobalIndex, MemoryIndex, TableIndex, TrapCode};
+use std::time::{Duration, Instant};
+use wasmtime_environ::{
+    DataIndex, ElemIndex, GlobalIndex, MemoryIndex, TableIndex, TagIndex, TrapCode,
+};
 
 const TOINT_32: f32 = 1.0 / f32::EPSILON;
 const TOINT_64: f64 = 1.0 / f64::EPSILON;
@@ -204,6 +209,13 @@ pub unsafe extern "C" fn wasmtime_memory32_grow(
     }
 }
 
+/// Implementation of `memory.size` for shared memories.
+pub unsafe extern "C" fn wasmtime_memory_size(vmctx: *mut VMContext, memory_index: u32) -> usize {
+    let instance = (*vmctx).instance();
+    let memory_index = MemoryIndex::from_u32(memory_index);
+    instance.get_memory(memory_index).current_length / (wasmtime_environ::WASM_PAGE_SIZE as usize)
+}
+
 /// Implementation of `table.grow`.
 pub unsafe extern "C" fn wasmtime_table_grow(
     vmctx: *mut VMContext,
@@ -454,19 +466,18 @@ pub unsafe extern "C" fn wasmtime_memory_atomic_notify(
     vmctx: *mut VMContext,
     memory_index: u32,
     addr: usize,
-    _count: u32,
+    count: u32,
 ) -> u32 {
     let result = {
         let memory = MemoryIndex::from_u32(memory_index);
         let instance = (*vmctx).instance();
-        // this should never overflow since addr + 4 either hits a guard page
-        // or it's been validated to be in-bounds already. Double-check for now
-        // just to be sure.
-        let addr_to_check = addr.checked_add(4).unwrap();
-        validate_atomic_addr(instance, memory, addr_to_check).and_then(|()| {
-            Err(Trap::User(anyhow::anyhow!(
-                "unimplemented: wasm atomics (fn wasmtime_memory_atomic_notify) unsupported",
-            )))
+        validate_atomic_addr(instance, memory, addr, 4).map(|addr| {
+            match instance.get_shared_memory(memory) {
+                Some(shared) => shared.atomic_notify(addr, count),
+                // Nothing can ever wait on an unshared memory, so there's
+                // nobody to wake up.
+                None => 0,
+            }
         })
     };
     match result {
@@ -480,19 +491,15 @@ pub unsafe extern "C" fn wasmtime_memory_atomic_wait32(
     vmctx: *mut VMContext,
     memory_index: u32,
     addr: usize,
-    _expected: u32,
-    _timeout: u64,
+    expected: u32,
+    timeout: u64,
 ) -> u32 {
     let result = {
         let memory = MemoryIndex::from_u32(memory_index);
         let instance = (*vmctx).instance();
-        // see wasmtime_memory_atomic_notify for why this shouldn't overflow
-        // but we still double-check
-        let addr_to_check = addr.checked_add(4).unwrap();
-        validate_atomic_addr(instance, memory, addr_to_check).and_then(|()| {
-            Err(Trap::User(anyhow::anyhow!(
-                "unimplemented: wasm atomics (fn wasmtime_memory_atomic_wait32) unsupported",
-            )))
+        validate_atomic_addr(instance, memory, addr, 4).and_then(|addr| {
+            let shared = atomic_wait_memory(instance, memory)?;
+            Ok(shared.atomic_wait32(addr, expected, atomic_wait_deadline(timeout)) as u32)
         })
     };
     match result {
@@ -506,19 +513,15 @@ pub unsafe extern "C" fn wasmtime_memory_atomic_wait64(
     vmctx: *mut VMContext,
     memory_index: u32,
     addr: usize,
-    _expected: u64,
-    _timeout: u64,
+    expected: u64,
+    timeout: u64,
 ) -> u32 {
     let result = {
         let memory = MemoryIndex::from_u32(memory_index);
         let instance = (*vmctx).instance();
-        // see wasmtime_memory_atomic_notify for why this shouldn't overflow
-        // but we still double-check
-        let addr_to_check = addr.checked_add(8).unwrap();
-        validate_atomic_addr(instance, memory, addr_to_check).and_then(|()| {
-            Err(Trap::User(anyhow::anyhow!(
-                "unimplemented: wasm atomics (fn wasmtime_memory_atomic_wait64) unsupported",
-            )))
+        validate_atomic_addr(instance, memory, addr, 8).and_then(|addr| {
+            let shared = atomic_wait_memory(instance, memory)?;
+            Ok(shared.atomic_wait64(addr, expected, atomic_wait_deadline(timeout)) as u32)
         })
     };
     match result {
@@ -527,6 +530,30 @@ pub unsafe extern "C" fn wasmtime_memory_atomic_wait64(
     }
 }
 
+/// Returns the shared memory to wait on, trapping if the memory isn't shared
+/// as the threads proposal requires.
+unsafe fn atomic_wait_memory(
+    instance: &Instance,
+    memory: MemoryIndex,
+) -> Result<SharedMemory, Trap> {
+    instance.get_shared_memory(memory).ok_or_else(|| {
+        Trap::User(anyhow::anyhow!(
+            "atomic wait on non-shared memory is not allowed"
+        ))
+    })
+}
+
+/// Converts the relative `timeout` of an atomic wait, in nanoseconds, into a
+/// deadline. Negative timeouts wait forever.
+fn atomic_wait_deadline(timeout: u64) -> Option<Instant> {
+    if (timeout as i64) < 0 {
+        return None;
+    }
+    // A deadline too far in the future to represent is as good as no deadline
+    // at all.
+    Instant::now().checked_add(Duration::from_nanos(timeout))
+}
+
 /// For atomic operations we still check the actual address despite this also
 /// being checked via the `heap_addr` instruction in cranelift. The reason for
 /// that is because the `heap_addr` instruction can defer to a later segfault to
@@ -536,18 +563,24 @@ pub unsafe extern "C" fn wasmtime_memory_atomic_wait64(
 /// In the situations where bounds checks were elided in JIT code (because oob
 /// would then be later guaranteed to segfault) this manual check is here
 /// so we don't segfault from Rust.
+///
+/// Note that `addr` is the native address computed by the JIT code. On
+/// success the corresponding byte offset into the linear memory is returned.
 unsafe fn validate_atomic_addr(
     instance: &Instance,
     memory: MemoryIndex,
     addr: usize,
-) -> Result<(), Trap> {
-    if addr > instance.get_memory(memory).current_length {
-        return Err(Trap::Wasm {
+    access_size: usize,
+) -> Result<u64, Trap> {
+    let definition = instance.get_memory(memory);
+    let offset = addr.wrapping_sub(definition.base as usize);
+    match offset.checked_add(access_size) {
+        Some(end) if end <= definition.current_length => Ok(offset as u64),
+        _ => Err(Trap::Wasm {
             trap_code: TrapCode::HeapOutOfBounds,
             backtrace: Backtrace::new_unresolved(),
-        });
+        }),
     }
-    Ok(())
 }
 
 /// Hook for when an instance runs out of fuel.
@@ -557,3 +590,116 @@ pub unsafe extern "C" fn wasmtime_out_of_gas(vmctx: *mut VMContext) {
         Err(err) => crate::traphandlers::raise_user_trap(err),
     }
 }
+
+/// Hook for when an instance observes that the epoch has changed.
+pub unsafe extern "C" fn wasmtime_new_epoch(vmctx: *mut VMContext) -> u64 {
+    match (*(*vmctx).instance().store()).new_epoch() {
+        Ok(new_deadline) => new_deadline,
+        Err(err) => crate::traphandlers::raise_user_trap(err),
+    }
+}
+
+/// Implementation of wasm's `throw` instruction: raises a new exception with
+/// the given tag and the `len` values at `values`.
+///
+/// The `nrefs` reference values among them follow at the end of `values`, so
+/// that the exception can keep them alive.
+pub unsafe extern "C" fn wasmtime_throw(
+    vmctx: *mut VMContext,
+    tag_index: u32,
+    values: *const ValRaw,
+    len: u32,
+    nrefs: u32,
+) {
+    let instance = (*vmctx).instance();
+    let tag = instance.defined_or_imported_tag_ptr(TagIndex::from_u32(tag_index));
+    let (values, refs) = if len == 0 {
+        (Default::default(), Default::default())
+    } else {
+        let all = std::slice::from_raw_parts(values, (len + nrefs) as usize);
+        let (values, refs) = all.split_at(len as usize);
+        let refs = refs
+            .iter()
+            .filter(|r| r.externref != 0)
+            .map(|r| VMExternRef::clone_from_raw(r.externref as *mut u8))
+            .collect();
+        (values.into(), refs)
+    };
+    let exception = VMExternRef::new(VMException::new(tag, values, refs));
+    (*(*instance.store()).vminterrupts()).set_pending_exception(exception);
+    raise_pending_exception(instance)
+}
+
+/// Implementation of wasm's `rethrow` instruction: raises a previously caught
+/// exception again.
+pub unsafe extern "C" fn wasmtime_rethrow(vmctx: *mut VMContext, exception: *mut u8) {
+    debug_assert!(!exception.is_null());
+    let exception = VMExternRef::clone_from_raw(exception);
+    let instance = (*vmctx).instance();
+    (*(*instance.store()).vminterrupts()).set_pending_exception(exception);
+    raise_pending_exception(instance)
+}
+
+/// Continues unwinding the pending exception out of a `try` block none of whose
+/// clauses matched it.
+pub unsafe extern "C" fn wasmtime_resume_unwind(vmctx: *mut VMContext) {
+    raise_pending_exception((*vmctx).instance())
+}
+
+/// Unwinds to the handler of the pending exception. Callers must make sure that
+/// nothing with a destructor is live, as it would be leaked.
+unsafe fn raise_pending_exception(instance: &Instance) -> ! {
+    let (_, module_info_lookup) = (*instance.store()).externref_activations_table();
+    crate::traphandlers::raise_exception(module_info_lookup)
+}
+
+/// Tests whether the pending exception was thrown with the given tag.
+pub unsafe extern "C" fn wasmtime_exception_matches(vmctx: *mut VMContext, tag_index: u32) -> u32 {
+    let instance = (*vmctx).instance();
+    let tag = instance.defined_or_imported_tag_ptr(TagIndex::from_u32(tag_index));
+    let interrupts = &*(*instance.store()).vminterrupts();
+    let exception = interrupts
+        .take_pending_exception()
+        .expect("an exception should be pending");
+    let matches = exception
+        .downcast_ref::<VMException>()
+        .map_or(false, |e| e.tag() == tag);
+    interrupts.set_pending_exception(exception);
+    matches as u32
+}
+
+/// Takes the pending exception for a `catch` or `catch_all` clause, copying the
+/// values it carries to `values` unless that is null.
+///
+/// The returned reference is kept alive by the `VMExternRefActivationsTable`
+/// so that it can later be rethrown.
+pub unsafe extern "C" fn wasmtime_catch_exception(
+    vmctx: *mut VMContext,
+    values: *mut ValRaw,
+) -> *mut u8 {
+    let instance = (*vmctx).instance();
+    let exception = (*(*instance.store()).vminterrupts())
+        .take_pending_exception()
+        .expect("an exception should be pending");
+    if !values.is_null() {
+        let payload = exception
+            .downcast_ref::<VMException>()
+            .expect("pending exceptions should be `VMException`s")
+            .values();
+        ptr::copy_nonoverlapping(payload.as_ptr(), values, payload.len());
+    }
+    // The reference values among the copied ones are only borrowed from the
+    // exception, so they have to be kept alive by the activations table too.
+    // Only the first insertion may GC, as the exception itself keeps the
+    // others alive until then.
+    let refs = exception
+        .downcast_ref::<VMException>()
+        .map_or(Vec::new(), |e| e.refs().to_vec());
+    let raw = exception.as_raw();
+    let (activations_table, module_info_lookup) = (*instance.store()).externref_activations_table();
+    activations_table.insert_with_gc(exception, module_info_lookup);
+    for r in refs {
+        activations_table.insert_without_gc(r);
+    }
+    raw
+}
@@ -2,13 +2,19 @@
 //!
 //! `RuntimeLinearMemory` is to WebAssembly linear memories what `Table` is to WebAssembly tables.
 
+use crate::cow::{MemoryImage, MemoryImageSlot};
 use crate::mmap::Mmap;
+use crate::parking_spot::{ParkResult, ParkingSpot};
 use crate::vmcontext::VMMemoryDefinition;
 use crate::Store;
 use anyhow::Error;
 use anyhow::{bail, format_err, Result};
 use more_asserts::{assert_ge, assert_le};
+use std::cell::UnsafeCell;
 use std::convert::TryFrom;
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
 use wasmtime_environ::{MemoryPlan, MemoryStyle, WASM32_MAX_PAGES, WASM64_MAX_PAGES};
 
 const WASM_PAGE_SIZE: usize = wasmtime_environ::WASM_PAGE_SIZE as usize;
@@ -17,11 +23,16 @@ const WASM_PAGE_SIZE_U64: u64 = wasmtime_environ::WASM_PAGE_SIZE as u64;
 /// A memory allocator
 pub trait RuntimeMemoryCreator: Send + Sync {
     /// Create new RuntimeLinearMemory
+    ///
+    /// If `memory_image` is provided then the creator may use it to
+    /// initialize the contents of the new memory, in which case the returned
+    /// memory's `needs_init` must return `false`.
     fn new_memory(
         &self,
         plan: &MemoryPlan,
         minimum: usize,
         maximum: Option<usize>,
+        memory_image: Option<&Arc<MemoryImage>>,
     ) -> Result<Box<dyn RuntimeLinearMemory>>;
 }
 
@@ -35,8 +46,14 @@ impl RuntimeMemoryCreator for DefaultMemoryCreator {
         plan: &MemoryPlan,
         minimum: usize,
         maximum: Option<usize>,
+        memory_image: Option<&Arc<MemoryImage>>,
     ) -> Result<Box<dyn RuntimeLinearMemory>> {
-        Ok(Box::new(MmapMemory::new(plan, minimum, maximum)?))
+        Ok(Box::new(MmapMemory::new(
+            plan,
+            minimum,
+            maximum,
+            memory_image,
+        )?))
     }
 }
 
@@ -58,6 +75,24 @@ pub trait RuntimeLinearMemory: Send + Sync {
     /// Return a `VMMemoryDefinition` for exposing the memory to compiled wasm
     /// code.
     fn vmmemory(&self) -> VMMemoryDefinition;
+
+    /// Returns the [`SharedMemory`] this linear memory is a handle to, if any.
+    ///
+    /// A `RuntimeMemoryCreator` can return a clone of an existing
+    /// [`SharedMemory`] from `new_memory` to have a `shared` memory of a new
+    /// instance use that allocation rather than a fresh one.
+    fn as_shared_memory(&self) -> Option<&SharedMemory> {
+        None
+    }
+
+    /// Returns whether the contents of this memory still need to be
+    /// initialized from the module's data segments.
+    ///
+    /// This is `false` for memories whose initial contents were mapped from a
+    /// memory image when they were created.
+    fn needs_init(&self) -> bool {
+        true
+    }
 }
 
 /// A linear memory instance.
@@ -86,11 +121,23 @@ pub struct MmapMemory {
     // optimize loads and stores with constant offsets.
     pre_guard_size: usize,
     offset_guard_size: usize,
+
+    // Whether the initial contents of this memory were mapped copy-on-write
+    // from a memory image.
+    initialized_from_image: bool,
 }
 
 impl MmapMemory {
     /// Create a new linear memory instance with specified minimum and maximum number of wasm pages.
-    pub fn new(plan: &MemoryPlan, minimum: usize, mut maximum: Option<usize>) -> Result<Self> {
+    ///
+    /// If `memory_image` is given then it's mapped copy-on-write into the
+    /// new memory to provide its initial contents.
+    pub fn new(
+        plan: &MemoryPlan,
+        minimum: usize,
+        mut maximum: Option<usize>,
+        memory_image: Option<&Arc<MemoryImage>>,
+    ) -> Result<Self> {
         // It's a programmer error for these two configuration values to exceed
         // the host available address space, so panic if such a configuration is
         // found (mostly an issue for hypothetical 32-bit hosts).
@@ -126,6 +173,14 @@ impl MmapMemory {
             mmap.make_accessible(pre_guard_bytes, minimum)?;
         }
 
+        // The image is always within the memory's minimum size, so it can be
+        // mapped over the accessible region created above.
+        if let Some(image) = memory_image {
+            unsafe {
+                image.map_at(mmap.as_mut_ptr() as usize + pre_guard_bytes)?;
+            }
+        }
+
         Ok(Self {
             mmap,
             accessible: minimum,
@@ -133,6 +188,7 @@ impl MmapMemory {
             pre_guard_size: pre_guard_bytes,
             offset_guard_size: offset_guard_bytes,
             extra_to_reserve_on_growth,
+            initialized_from_image: memory_image.is_some(),
         })
     }
 }
@@ -191,6 +247,10 @@ impl RuntimeLinearMemory for MmapMemory {
             current_length: self.accessible,
         }
     }
+
+    fn needs_init(&self) -> bool {
+        !self.initialized_from_image
+    }
 }
 
 /// Representation of a runtime wasm linear memory.
@@ -214,11 +274,21 @@ pub enum Memory {
         /// These pages need their protection level reset before the memory can grow.
         #[cfg(all(feature = "uffd", target_os = "linux"))]
         guard_page_faults: Vec<(usize, usize, fn(*mut u8, usize) -> Result<()>)>,
+
+        /// The copy-on-write slot managing `base`, if memory images are in
+        /// use. When present this is used instead of `make_accessible`, and
+        /// the slot is handed back to the allocator when the memory is
+        /// deallocated so it can be reused.
+        memory_image: Option<MemoryImageSlot>,
     },
 
     /// A "dynamic" memory whose data is managed at runtime and lifetime is tied
     /// to this instance.
     Dynamic(Box<dyn RuntimeLinearMemory>),
+
+    /// A memory with a `shared` type whose allocation may also be in use by
+    /// other instances, possibly in other stores and on other threads.
+    Shared(SharedMemory),
 }
 
 impl Memory {
@@ -227,26 +297,40 @@ impl Memory {
         plan: &MemoryPlan,
         creator: &dyn RuntimeMemoryCreator,
         store: &mut dyn Store,
+        memory_image: Option<&Arc<MemoryImage>>,
     ) -> Result<Self> {
-        let (minimum, maximum) = Self::limit_new(plan, store)?;
-        Ok(Memory::Dynamic(creator.new_memory(plan, minimum, maximum)?))
+        let (minimum, maximum) = Self::limit_new(plan, Some(store))?;
+        let memory = creator.new_memory(plan, minimum, maximum, memory_image)?;
+        if plan.memory.shared {
+            let shared = memory.as_shared_memory().cloned();
+            return Ok(Memory::Shared(match shared {
+                Some(shared) => shared,
+                None => SharedMemory::wrap(plan, memory)?,
+            }));
+        }
+        Ok(Memory::Dynamic(memory))
     }
 
     /// Create a new static (immovable) memory instance for the specified plan.
+    ///
+    /// If `memory_image` is given then it must already have been
+    /// instantiated for this memory, and it's used to make memory accessible
+    /// instead of `make_accessible`.
     pub fn new_static(
         plan: &MemoryPlan,
         base: &'static mut [u8],
         make_accessible: fn(*mut u8, usize) -> Result<()>,
+        memory_image: Option<MemoryImageSlot>,
         store: &mut dyn Store,
     ) -> Result<Self> {
-        let (minimum, maximum) = Self::limit_new(plan, store)?;
+        let (minimum, maximum) = Self::limit_new(plan, Some(store))?;
 
         let base = match maximum {
             Some(max) if max < base.len() => &mut base[..max],
             _ => base,
         };
 
-        if minimum > 0 {
+        if minimum > 0 && memory_image.is_none() {
             make_accessible(base.as_mut_ptr(), minimum)?;
         }
 
@@ -256,6 +340,7 @@ impl Memory {
             make_accessible,
             #[cfg(all(feature = "uffd", target_os = "linux"))]
             guard_page_faults: Vec::new(),
+            memory_image,
         })
     }
 
@@ -263,7 +348,10 @@ impl Memory {
     ///
     /// Returns the minimum size and optional maximum size of the memory, in
     /// bytes.
-    fn limit_new(plan: &MemoryPlan, store: &mut dyn Store) -> Result<(usize, Option<usize>)> {
+    fn limit_new(
+        plan: &MemoryPlan,
+        store: Option<&mut dyn Store>,
+    ) -> Result<(usize, Option<usize>)> {
         // Sanity-check what should already be true from wasm module validation.
         let absolute_max = if plan.memory.memory64 {
             WASM64_MAX_PAGES
@@ -324,11 +412,16 @@ impl Memory {
         // calculation overflowed. This means that the `minimum` we're informing
         // the limiter is lossy and may not be 100% accurate, but for now the
         // expected uses of limiter means that's ok.
-        if !store.memory_growing(0, minimum.unwrap_or(absolute_max), maximum)? {
-            bail!(
-                "memory minimum size of {} pages exceeds memory limits",
-                plan.memory.minimum
-            );
+        //
+        // Shared memories created directly by the embedder aren't associated
+        // with any store, so there's no limiter to consult for those.
+        if let Some(store) = store {
+            if !store.memory_growing(0, minimum.unwrap_or(absolute_max), maximum)? {
+                bail!(
+                    "memory minimum size of {} pages exceeds memory limits",
+                    plan.memory.minimum
+                );
+            }
         }
 
         // At this point we need to actually handle overflows, so bail out with
@@ -347,6 +440,7 @@ impl Memory {
         match self {
             Memory::Static { size, .. } => *size,
             Memory::Dynamic(mem) => mem.byte_size(),
+            Memory::Shared(mem) => mem.byte_size(),
         }
     }
 
@@ -360,6 +454,7 @@ impl Memory {
         match self {
             Memory::Static { base, .. } => Some(base.len()),
             Memory::Dynamic(mem) => mem.maximum_byte_size(),
+            Memory::Shared(mem) => mem.maximum_byte_size(),
         }
     }
 
@@ -373,6 +468,30 @@ impl Memory {
         }
     }
 
+    /// Returns whether the contents of this memory still need to be
+    /// initialized from the module's data segments, or whether they were
+    /// already provided by a memory image.
+    pub(crate) fn needs_init(&self) -> bool {
+        match self {
+            Memory::Static { memory_image, .. } => match memory_image {
+                Some(slot) => !slot.has_image(),
+                None => true,
+            },
+            Memory::Dynamic(mem) => mem.needs_init(),
+            Memory::Shared(_) => true,
+        }
+    }
+
+    /// Consumes this memory, returning the copy-on-write slot of a static
+    /// memory, if any, so it can be reused for later instances.
+    #[cfg(feature = "pooling-allocator")]
+    pub(crate) fn unwrap_static_image(self) -> Option<MemoryImageSlot> {
+        match self {
+            Memory::Static { memory_image, .. } => memory_image,
+            Memory::Dynamic(_) | Memory::Shared(_) => None,
+        }
+    }
+
     /// Grow memory by the specified amount of wasm pages.
     ///
     /// Returns `None` if memory can't be grown by the specified amount
@@ -395,42 +514,28 @@ impl Memory {
         delta_pages: u64,
         store: &mut dyn Store,
     ) -> Result<Option<usize>, Error> {
+        // Growth of shared memories needs to be atomic with respect to other
+        // threads, so it's entirely handled while holding the shared lock.
+        if let Memory::Shared(mem) = self {
+            return mem.grow(delta_pages, Some(store));
+        }
+
         let old_byte_size = self.byte_size();
         // Wasm spec: when growing by 0 pages, always return the current size.
         if delta_pages == 0 {
             return Ok(Some(old_byte_size));
         }
 
-        // largest wasm-page-aligned region of memory it is possible to
-        // represent in a usize. This will be impossible for the system to
-        // actually allocate.
-        let absolute_max = 0usize.wrapping_sub(WASM_PAGE_SIZE);
-        // calculate byte size of the new allocation. Let it overflow up to
-        // usize::MAX, then clamp it down to absolute_max.
-        let new_byte_size = usize::try_from(delta_pages)
-            .unwrap_or(usize::MAX)
-            .saturating_mul(WASM_PAGE_SIZE)
-            .saturating_add(old_byte_size);
-        let new_byte_size = if new_byte_size > absolute_max {
-            absolute_max
-        } else {
-            new_byte_size
+        let new_byte_size = match limit_grow(
+            old_byte_size,
+            self.maximum_byte_size(),
+            delta_pages,
+            Some(&mut *store),
+        )? {
+            Some(size) => size,
+            None => return Ok(None),
         };
 
-        let maximum = self.maximum_byte_size();
-        // Store limiter gets first chance to reject memory_growing.
-        if !store.memory_growing(old_byte_size, new_byte_size, maximum)? {
-            return Ok(None);
-        }
-
-        // Never exceed maximum, even if limiter permitted it.
-        if let Some(max) = maximum {
-            if new_byte_size > max {
-                store.memory_grow_failed(&format_err!("Memory maximum size exceeded"));
-                return Ok(None);
-            }
-        }
-
         #[cfg(all(feature = "uffd", target_os = "linux"))]
         {
             if self.is_static() {
@@ -447,6 +552,7 @@ impl Memory {
                 base,
                 size,
                 make_accessible,
+                memory_image,
                 ..
             } => {
                 // Never exceed static memory size
@@ -456,10 +562,14 @@ impl Memory {
                 }
 
                 // Operating system can fail to make memory accessible
-                if let Err(e) = make_accessible(
-                    base.as_mut_ptr().add(old_byte_size),
-                    new_byte_size - old_byte_size,
-                ) {
+                let result = match memory_image {
+                    Some(slot) => slot.set_heap_limit(new_byte_size),
+                    None => make_accessible(
+                        base.as_mut_ptr().add(old_byte_size),
+                        new_byte_size - old_byte_size,
+                    ),
+                };
+                if let Err(e) = result {
                     store.memory_grow_failed(&e);
                     return Ok(None);
                 }
@@ -471,6 +581,7 @@ impl Memory {
                     return Ok(None);
                 }
             }
+            Memory::Shared(_) => unreachable!(),
         }
         Ok(Some(old_byte_size))
     }
@@ -483,6 +594,7 @@ impl Memory {
                 current_length: *size,
             },
             Memory::Dynamic(mem) => mem.vmmemory(),
+            Memory::Shared(mem) => mem.vmmemory(),
         }
     }
 
@@ -504,7 +616,7 @@ impl Memory {
             } => {
                 guard_page_faults.push((page_addr as usize, size, reset));
             }
-            Memory::Dynamic(_) => {
+            Memory::Dynamic(_) | Memory::Shared(_) => {
                 unreachable!("dynamic memories should not have guard page faults")
             }
         }
@@ -525,7 +637,7 @@ impl Memory {
                     reset(addr as *mut u8, len)?;
                 }
             }
-            Memory::Dynamic(_) => {
+            Memory::Dynamic(_) | Memory::Shared(_) => {
                 unreachable!("dynamic memories should not have guard page faults")
             }
         }
@@ -534,6 +646,307 @@ impl Memory {
     }
 }
 
+/// Calls the `store`'s limiter, if any, to determine whether a memory of
+/// `old_byte_size` bytes may grow by `delta_pages` wasm pages.
+///
+/// Returns the new size of the memory, in bytes, or `None` if the growth has
+/// been rejected.
+fn limit_grow(
+    old_byte_size: usize,
+    maximum: Option<usize>,
+    delta_pages: u64,
+    mut store: Option<&mut dyn Store>,
+) -> Result<Option<usize>, Error> {
+    // largest wasm-page-aligned region of memory it is possible to
+    // represent in a usize. This will be impossible for the system to
+    // actually allocate.
+    let absolute_max = 0usize.wrapping_sub(WASM_PAGE_SIZE);
+    // calculate byte size of the new allocation. Let it overflow up to
+    // usize::MAX, then clamp it down to absolute_max.
+    let new_byte_size = usize::try_from(delta_pages)
+        .unwrap_or(usize::MAX)
+        .saturating_mul(WASM_PAGE_SIZE)
+        .saturating_add(old_byte_size);
+    let new_byte_size = if new_byte_size > absolute_max {
+        absolute_max
+    } else {
+        new_byte_size
+    };
+
+    // Store limiter gets first chance to reject memory_growing.
+    if let Some(store) = &mut store {
+        if !store.memory_growing(old_byte_size, new_byte_size, maximum)? {
+            return Ok(None);
+        }
+    }
+
+    // Never exceed maximum, even if limiter permitted it.
+    if let Some(max) = maximum {
+        if new_byte_size > max {
+            if let Some(store) = &mut store {
+                store.memory_grow_failed(&format_err!("Memory maximum size exceeded"));
+            }
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(new_byte_size))
+}
+
+/// A linear memory with a `shared` type.
+///
+/// Shared memories may be used by many instances at once, possibly in
+/// different stores and on different threads, so this is a cheaply-cloneable
+/// handle to the underlying allocation. Shared memories always have a static
+/// style, meaning their base pointer never moves, and growing them is
+/// serialized through a lock so concurrent `memory.grow`s all observe a
+/// consistent previous size.
+///
+/// A shared memory owns the single `VMMemoryDefinition` describing it, which
+/// every instance importing or exporting the memory points at, so that all of
+/// them observe growth by any other thread.
+#[derive(Clone)]
+pub struct SharedMemory(Arc<SharedMemoryInner>);
+
+struct SharedMemoryInner {
+    memory: RwLock<Box<dyn RuntimeLinearMemory>>,
+    spot: ParkingSpot,
+    ty: wasmtime_environ::Memory,
+    /// The definition pointed at by all users of this memory. Its `base`
+    /// never changes and its `current_length` is only ever accessed
+    /// atomically, see `SharedMemory::current_length`.
+    definition: UnsafeCell<VMMemoryDefinition>,
+}
+
+// The raw pointers in `definition` are what make this type not `Send` and
+// `Sync` automatically; they point at `memory`, which is both, and the
+// mutable parts of `definition` are only accessed atomically.
+unsafe impl Send for SharedMemoryInner {}
+unsafe impl Sync for SharedMemoryInner {}
+
+/// The result of a `memory.atomic.wait32` or `memory.atomic.wait64`, encoded
+/// as the value the instruction returns.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum WaitResult {
+    /// The waiting thread was woken up by a `memory.atomic.notify`.
+    Ok = 0,
+    /// The value in memory didn't match the expected value.
+    Mismatch = 1,
+    /// The timeout expired before the thread was woken up.
+    TimedOut = 2,
+}
+
+impl SharedMemory {
+    /// Allocates a new shared memory for the specified plan.
+    ///
+    /// Memories created this way aren't associated with any store, so no
+    /// resource limiter is consulted for their initial allocation.
+    pub fn new(plan: MemoryPlan) -> Result<Self> {
+        let (minimum, maximum) = Memory::limit_new(&plan, None)?;
+        let mmap_memory = MmapMemory::new(&plan, minimum, maximum, None)?;
+        Self::wrap(&plan, Box::new(mmap_memory))
+    }
+
+    /// Wraps an existing linear memory allocation for `plan` in a
+    /// `SharedMemory`.
+    ///
+    /// Returns an error if `plan` isn't for a `shared` memory or if the
+    /// memory would be able to move when it's grown.
+    ///
+    /// The base pointer of `memory` must never change: compiled code on other
+    /// threads may be accessing the memory while it's grown. Memories created
+    /// by the default allocator honor the static style of `plan`, but custom
+    /// `RuntimeMemoryCreator`s must as well, which is checked on every growth;
+    /// a memory which moves anyway causes a panic, since continuing would
+    /// leave other threads accessing freed memory.
+    pub fn wrap(plan: &MemoryPlan, memory: Box<dyn RuntimeLinearMemory>) -> Result<Self> {
+        if !plan.memory.shared {
+            bail!("shared memory must have a `shared` memory type");
+        }
+        if plan.memory.maximum.is_none() {
+            bail!("shared memory must have a maximum size");
+        }
+        if !matches!(plan.style, MemoryStyle::Static { .. }) {
+            bail!("shared memory can only be built from a static memory allocation");
+        }
+        let definition = UnsafeCell::new(memory.vmmemory());
+        Ok(SharedMemory(Arc::new(SharedMemoryInner {
+            memory: RwLock::new(memory),
+            spot: ParkingSpot::default(),
+            ty: plan.memory,
+            definition,
+        })))
+    }
+
+    /// Returns the wasm type of this memory.
+    pub fn ty(&self) -> wasmtime_environ::Memory {
+        self.0.ty
+    }
+
+    /// Returns the number of accessible bytes in this memory.
+    pub fn byte_size(&self) -> usize {
+        self.0.memory.read().unwrap().byte_size()
+    }
+
+    /// Returns the maximum number of bytes this memory can grow to.
+    pub fn maximum_byte_size(&self) -> Option<usize> {
+        self.0.memory.read().unwrap().maximum_byte_size()
+    }
+
+    /// Returns a `VMMemoryDefinition` describing the current state of this
+    /// memory.
+    ///
+    /// Note that the `current_length` of the returned definition may be out of
+    /// date as soon as it's returned if another thread grows this memory.
+    pub fn vmmemory(&self) -> VMMemoryDefinition {
+        VMMemoryDefinition {
+            base: unsafe { (*self.0.definition.get()).base },
+            current_length: self.current_length().load(Ordering::SeqCst),
+        }
+    }
+
+    /// Returns a pointer to the `VMMemoryDefinition` shared by every instance
+    /// using this memory.
+    ///
+    /// The pointer is valid for as long as this memory is alive. Its
+    /// `current_length` field may be concurrently updated by other threads, so
+    /// it must only be accessed atomically.
+    pub fn vmmemory_ptr(&self) -> *mut VMMemoryDefinition {
+        self.0.definition.get()
+    }
+
+    fn current_length(&self) -> &AtomicUsize {
+        // Safety: `AtomicUsize` has the same layout as `usize`, and the field
+        // is only ever accessed through this atomic.
+        unsafe {
+            &*(std::ptr::addr_of!((*self.0.definition.get()).current_length) as *const AtomicUsize)
+        }
+    }
+
+    /// Grows the underlying memory to `size` bytes and publishes the new size
+    /// to every user of this memory. Must be called with the write lock held.
+    fn grow_locked(&self, memory: &mut Box<dyn RuntimeLinearMemory>, size: usize) -> Result<()> {
+        memory.grow_to(size)?;
+        let vmmemory = memory.vmmemory();
+        assert_eq!(
+            vmmemory.base,
+            unsafe { (*self.0.definition.get()).base },
+            "the base pointer of a shared memory moved while growing it"
+        );
+        self.current_length()
+            .store(vmmemory.current_length, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Grows this memory by `delta_pages` wasm pages.
+    ///
+    /// If a `store` is provided its resource limiter is consulted before
+    /// growing. Returns the previous size of the memory, in bytes, on success
+    /// and `None` if the memory couldn't be grown.
+    pub fn grow(
+        &self,
+        delta_pages: u64,
+        mut store: Option<&mut dyn Store>,
+    ) -> Result<Option<usize>, Error> {
+        let mut memory = self.0.memory.write().unwrap();
+        let old_byte_size = memory.byte_size();
+        // Wasm spec: when growing by 0 pages, always return the current size.
+        if delta_pages == 0 {
+            return Ok(Some(old_byte_size));
+        }
+
+        let new_byte_size = match limit_grow(
+            old_byte_size,
+            memory.maximum_byte_size(),
+            delta_pages,
+            store.as_deref_mut(),
+        )? {
+            Some(size) => size,
+            None => return Ok(None),
+        };
+
+        if let Err(e) = self.grow_locked(&mut memory, new_byte_size) {
+            if let Some(store) = store {
+                store.memory_grow_failed(&e);
+            }
+            return Ok(None);
+        }
+        Ok(Some(old_byte_size))
+    }
+
+    /// Implementation of `memory.atomic.notify` for this shared memory.
+    ///
+    /// Wakes up at most `count` threads waiting on the byte offset `addr`,
+    /// which must already have been bounds-checked, and returns how many were
+    /// woken up.
+    pub fn atomic_notify(&self, addr: u64, count: u32) -> u32 {
+        self.0.spot.unpark(addr, count)
+    }
+
+    /// Implementation of `memory.atomic.wait32` for this shared memory.
+    ///
+    /// The byte offset `addr` must already have been bounds-checked and be
+    /// 4-byte aligned. A `timeout` of `None` waits forever.
+    pub fn atomic_wait32(&self, addr: u64, expected: u32, timeout: Option<Instant>) -> WaitResult {
+        debug_assert!(addr % 4 == 0);
+        let definition = self.vmmemory();
+        // Safety: the address was bounds-checked by the caller, and memory can
+        // only grow, never move, so it stays valid for the duration of the
+        // wait.
+        let atomic = unsafe { &*(definition.base.add(addr as usize) as *const AtomicU32) };
+        self.wait(addr, || atomic.load(Ordering::SeqCst) == expected, timeout)
+    }
+
+    /// Implementation of `memory.atomic.wait64` for this shared memory.
+    ///
+    /// The byte offset `addr` must already have been bounds-checked and be
+    /// 8-byte aligned. A `timeout` of `None` waits forever.
+    pub fn atomic_wait64(&self, addr: u64, expected: u64, timeout: Option<Instant>) -> WaitResult {
+        debug_assert!(addr % 8 == 0);
+        let definition = self.vmmemory();
+        // Safety: see `atomic_wait32`.
+        let atomic = unsafe { &*(definition.base.add(addr as usize) as *const AtomicU64) };
+        self.wait(addr, || atomic.load(Ordering::SeqCst) == expected, timeout)
+    }
+
+    fn wait(
+        &self,
+        addr: u64,
+        validate: impl FnOnce() -> bool,
+        timeout: Option<Instant>,
+    ) -> WaitResult {
+        match self.0.spot.park(addr, validate, timeout) {
+            ParkResult::Unparked => WaitResult::Ok,
+            ParkResult::Invalid => WaitResult::Mismatch,
+            ParkResult::TimedOut => WaitResult::TimedOut,
+        }
+    }
+}
+
+impl RuntimeLinearMemory for SharedMemory {
+    fn byte_size(&self) -> usize {
+        SharedMemory::byte_size(self)
+    }
+
+    fn maximum_byte_size(&self) -> Option<usize> {
+        SharedMemory::maximum_byte_size(self)
+    }
+
+    fn grow_to(&mut self, size: usize) -> Result<()> {
+        let mut memory = self.0.memory.write().unwrap();
+        self.grow_locked(&mut memory, size)
+    }
+
+    fn vmmemory(&self) -> VMMemoryDefinition {
+        SharedMemory::vmmemory(self)
+    }
+
+    fn as_shared_memory(&self) -> Option<&SharedMemory> {
+        Some(self)
+    }
+}
+
 // The default memory representation is an empty memory that cannot grow.
 impl Default for Memory {
     fn default() -> Self {
@@ -543,6 +956,7 @@ impl Default for Memory {
             make_accessible: |_, _| unreachable!(),
             #[cfg(all(feature = "uffd", target_os = "linux"))]
             guard_page_faults: Vec::new(),
+            memory_image: None,
         }
     }
 }
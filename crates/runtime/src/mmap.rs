@@ -363,6 +363,12 @@ impl Mmap {
         self.file.is_some()
     }
 
+    /// Returns the file that this mapping was created from with
+    /// `Mmap::from_file`, if any. The file is mapped starting at offset 0.
+    pub fn original_file(&self) -> Option<&File> {
+        self.file.as_ref()
+    }
+
     /// Makes the specified `range` within this `Mmap` to be read/write.
     pub unsafe fn make_writable(&self, range: Range<usize>) -> Result<()> {
         assert!(range.start <= self.len());
@@ -0,0 +1,22 @@
+//! Unique IDs for modules in the runtime.
+
+use std::num::NonZeroU64;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A unique identifier (within the process) for a compiled module.
+///
+/// Instance allocators can use this to tell whether two instantiations are
+/// of the same module, for example to reuse resources which were already
+/// prepared for that module.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CompiledModuleId(NonZeroU64);
+
+impl CompiledModuleId {
+    /// Allocates a new ID which is different from all previously allocated
+    /// IDs.
+    pub fn alloc() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        CompiledModuleId(NonZeroU64::new(id).expect("module id overflowed"))
+    }
+}
@@ -0,0 +1,180 @@
+//! Implementation of the wait queue backing `memory.atomic.wait32`,
+//! `memory.atomic.wait64` and `memory.atomic.notify`.
+//!
+//! Each `ParkingSpot` keeps a FIFO queue of parked threads per address. A
+//! thread is only ever woken up by an `unpark` for its address or by its
+//! timeout expiring; spurious wakeups of the underlying condition variable are
+//! never observed by callers.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Instant;
+
+/// The result of a call to [`ParkingSpot::park`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParkResult {
+    /// The thread was woken up by a call to [`ParkingSpot::unpark`].
+    Unparked,
+    /// The validation callback returned `false` so the thread wasn't parked.
+    Invalid,
+    /// The timeout expired before the thread was woken up.
+    TimedOut,
+}
+
+/// A collection of wait queues keyed by address.
+#[derive(Default)]
+pub struct ParkingSpot {
+    inner: Mutex<HashMap<u64, VecDeque<Arc<Waiter>>>>,
+}
+
+#[derive(Default)]
+struct Waiter {
+    cvar: Condvar,
+    notified: AtomicBool,
+}
+
+impl ParkingSpot {
+    /// Parks the current thread on `key` until it's unparked or `timeout`, if
+    /// any, is reached.
+    ///
+    /// The `validate` callback is invoked while the internal lock is held,
+    /// before the thread is enqueued. If it returns `false` the thread isn't
+    /// parked and `ParkResult::Invalid` is returned. Since `unpark` takes the
+    /// same lock, no wakeup can be lost between `validate` and the thread
+    /// going to sleep.
+    pub fn park(
+        &self,
+        key: u64,
+        validate: impl FnOnce() -> bool,
+        timeout: Option<Instant>,
+    ) -> ParkResult {
+        let mut inner = self.inner.lock().unwrap();
+        if !validate() {
+            return ParkResult::Invalid;
+        }
+
+        let waiter = Arc::new(Waiter::default());
+        inner.entry(key).or_default().push_back(waiter.clone());
+
+        loop {
+            // `notified` is only ever written while the lock is held, so a
+            // relaxed load suffices here.
+            if waiter.notified.load(Ordering::Relaxed) {
+                return ParkResult::Unparked;
+            }
+
+            inner = match timeout {
+                Some(timeout) => {
+                    let now = Instant::now();
+                    if now >= timeout {
+                        // Dequeue ourselves since nobody else will.
+                        let queue = inner.get_mut(&key).unwrap();
+                        queue.retain(|w| !Arc::ptr_eq(w, &waiter));
+                        if queue.is_empty() {
+                            inner.remove(&key);
+                        }
+                        return ParkResult::TimedOut;
+                    }
+                    waiter.cvar.wait_timeout(inner, timeout - now).unwrap().0
+                }
+                None => waiter.cvar.wait(inner).unwrap(),
+            };
+        }
+    }
+
+    /// Wakes up at most `count` threads parked on `key`, in the order they
+    /// were parked.
+    ///
+    /// Returns the number of threads that were woken up.
+    pub fn unpark(&self, key: u64, count: u32) -> u32 {
+        if count == 0 {
+            return 0;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        let queue = match inner.get_mut(&key) {
+            Some(queue) => queue,
+            None => return 0,
+        };
+
+        let mut unparked = 0;
+        while unparked < count {
+            let waiter = match queue.pop_front() {
+                Some(waiter) => waiter,
+                None => break,
+            };
+            waiter.notified.store(true, Ordering::Relaxed);
+            waiter.cvar.notify_one();
+            unparked += 1;
+        }
+        if queue.is_empty() {
+            inner.remove(&key);
+        }
+        unparked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParkResult, ParkingSpot};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn invalid_does_not_park() {
+        let spot = ParkingSpot::default();
+        assert_eq!(spot.park(0, || false, None), ParkResult::Invalid);
+        assert_eq!(spot.unpark(0, 1), 0);
+    }
+
+    #[test]
+    fn times_out() {
+        let spot = ParkingSpot::default();
+        let timeout = Instant::now() + Duration::from_millis(10);
+        assert_eq!(spot.park(0, || true, Some(timeout)), ParkResult::TimedOut);
+        assert!(Instant::now() >= timeout);
+        // The timed out thread must have been removed from the queue.
+        assert_eq!(spot.unpark(0, 1), 0);
+    }
+
+    #[test]
+    fn unpark_count() {
+        let spot = Arc::new(ParkingSpot::default());
+        let parked = Arc::new(AtomicU32::new(0));
+
+        let threads = (0..4)
+            .map(|_| {
+                let spot = spot.clone();
+                let parked = parked.clone();
+                thread::spawn(move || {
+                    spot.park(
+                        7,
+                        || {
+                            parked.fetch_add(1, Ordering::SeqCst);
+                            true
+                        },
+                        None,
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        while parked.load(Ordering::SeqCst) != 4 {
+            thread::yield_now();
+        }
+
+        // Nothing is parked on another address.
+        assert_eq!(spot.unpark(8, u32::MAX), 0);
+
+        let mut unparked = spot.unpark(7, 1);
+        unparked += spot.unpark(7, 2);
+        unparked += spot.unpark(7, u32::MAX);
+        assert_eq!(unparked, 4);
+
+        for t in threads {
+            assert_eq!(t.join().unwrap(), ParkResult::Unparked);
+        }
+    }
+}
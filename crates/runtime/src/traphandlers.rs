@@ -1,7 +1,8 @@
 //! WebAssembly trap handling, which is built on top of the lower-level
 //! signalhandling mechanisms.
 
-use crate::{VMContext, VMInterrupts};
+use crate::exception::Handler;
+use crate::{ModuleInfoLookup, VMContext, VMExternRef, VMInterrupts};
 use anyhow::Error;
 use backtrace::Backtrace;
 use std::any::Any;
@@ -98,6 +99,26 @@ pub unsafe fn raise_lib_trap(trap: Trap) -> ! {
     tls::with(|info| info.unwrap().unwind_with(UnwindReason::LibTrap(trap)))
 }
 
+/// Raises the pending WebAssembly exception immediately.
+///
+/// The stack is unwound to the innermost wasm frame which handles the
+/// exception, as described in the `exception` module. If there is no such frame
+/// then the exception is returned from `catch_traps` below as a
+/// `Trap::Exception`.
+///
+/// # Safety
+///
+/// Only safe to call when wasm code is on the stack, aka `catch_traps` must
+/// have been previously called, and an exception must be pending in the
+/// `VMInterrupts` passed to it. Additionally no Rust destructors can be on the
+/// stack. They will be skipped and not executed.
+pub unsafe fn raise_exception(module_info_lookup: &dyn ModuleInfoLookup) -> ! {
+    tls::with(|info| {
+        info.unwrap()
+            .unwind_to_exception_handler(module_info_lookup)
+    })
+}
+
 /// Carries a Rust panic across wasm code and resumes the panic on the other
 /// side.
 ///
@@ -141,6 +162,10 @@ pub enum Trap {
         /// Native stack backtrace at the time the OOM occurred
         backtrace: Backtrace,
     },
+
+    /// A WebAssembly exception which no wasm frame handled, raised through
+    /// `raise_exception`.
+    Exception(VMExternRef),
 }
 
 impl Trap {
@@ -177,7 +202,7 @@ pub unsafe fn catch_traps<'a, F>(
 where
     F: FnMut(*mut VMContext),
 {
-    return CallThreadState::new(signal_handler).with(vminterrupts, |cx| {
+    return CallThreadState::new(vminterrupts, signal_handler).with(|cx| {
         wasmtime_setjmp(
             cx.jmp_buf.as_ptr(),
             call_closure::<F>,
@@ -200,6 +225,7 @@ pub struct CallThreadState {
     unwind: UnsafeCell<MaybeUninit<UnwindReason>>,
     jmp_buf: Cell<*const u8>,
     handling_trap: Cell<bool>,
+    interrupts: *mut VMInterrupts,
     signal_handler: Option<*const SignalHandler<'static>>,
     prev: Cell<tls::Ptr>,
 }
@@ -209,35 +235,37 @@ enum UnwindReason {
     UserTrap(Error),
     LibTrap(Trap),
     JitTrap { backtrace: Backtrace, pc: usize },
+    Exception,
 }
 
 impl CallThreadState {
     #[inline]
-    fn new(signal_handler: Option<*const SignalHandler<'static>>) -> CallThreadState {
+    fn new(
+        interrupts: *mut VMInterrupts,
+        signal_handler: Option<*const SignalHandler<'static>>,
+    ) -> CallThreadState {
         CallThreadState {
             unwind: UnsafeCell::new(MaybeUninit::uninit()),
             jmp_buf: Cell::new(ptr::null()),
             handling_trap: Cell::new(false),
+            interrupts,
             signal_handler,
             prev: Cell::new(ptr::null()),
         }
     }
 
-    fn with(
-        self,
-        interrupts: *mut VMInterrupts,
-        closure: impl FnOnce(&CallThreadState) -> i32,
-    ) -> Result<(), Box<Trap>> {
+    fn with(self, closure: impl FnOnce(&CallThreadState) -> i32) -> Result<(), Box<Trap>> {
         let ret = tls::set(&self, || closure(&self))?;
         if ret != 0 {
             Ok(())
         } else {
-            Err(unsafe { self.read_trap(interrupts) })
+            Err(unsafe { self.read_trap() })
         }
     }
 
     #[cold]
-    unsafe fn read_trap(&self, interrupts: *mut VMInterrupts) -> Box<Trap> {
+    unsafe fn read_trap(&self) -> Box<Trap> {
+        let interrupts = self.interrupts;
         Box::new(match (*self.unwind.get()).as_ptr().read() {
             UnwindReason::UserTrap(data) => Trap::User(data),
             UnwindReason::LibTrap(trap) => trap,
@@ -250,17 +278,37 @@ impl CallThreadState {
                     maybe_interrupted,
                 }
             }
+            UnwindReason::Exception => Trap::Exception(
+                (*interrupts)
+                    .take_pending_exception()
+                    .expect("an exception should be pending"),
+            ),
             UnwindReason::Panic(panic) => std::panic::resume_unwind(panic),
         })
     }
 
     fn unwind_with(&self, reason: UnwindReason) -> ! {
         unsafe {
+            // The state that wasm frames keep for core dumps is lost once we
+            // unwind past them, so it's recorded here.
+            if !matches!(reason, UnwindReason::Panic(_)) {
+                (*self.interrupts).capture_coredump_frames();
+            }
             (*self.unwind.get()).as_mut_ptr().write(reason);
             wasmtime_longjmp(self.jmp_buf.get());
         }
     }
 
+    fn unwind_to_exception_handler(&self, module_info_lookup: &dyn ModuleInfoLookup) -> ! {
+        // This state lives in a frame of `catch_traps`, so every frame of the
+        // wasm it called has a lower stack pointer than its address.
+        let limit = self as *const CallThreadState as usize;
+        if let Some(handler) = Handler::find(module_info_lookup, limit) {
+            unsafe { handler.resume() }
+        }
+        self.unwind_with(UnwindReason::Exception)
+    }
+
     /// Trap handler using our thread-local state.
     ///
     /// * `pc` - the program counter the trap happened at
@@ -319,6 +367,7 @@ impl CallThreadState {
     fn capture_backtrace(&self, pc: *const u8) {
         let backtrace = Backtrace::new_unresolved();
         unsafe {
+            (*self.interrupts).capture_coredump_frames();
             (*self.unwind.get())
                 .as_mut_ptr()
                 .write(UnwindReason::JitTrap {
@@ -191,6 +191,10 @@ unsafe fn get_pc(cx: *mut libc::c_void, _signum: libc::c_int) -> *const u8 {
             };
             let cx = &*(cx as *const libc::ucontext_t);
             (cx.uc_mcontext.psw.addr - trap_offset) as *const u8
+        } else if #[cfg(all(target_os = "linux", target_arch = "riscv64"))] {
+            // The slot for `x0`, which is hardwired to zero, holds the pc.
+            let cx = &*(cx as *const libc::ucontext_t);
+            cx.uc_mcontext.__gregs[0] as *const u8
         } else if #[cfg(all(target_os = "macos", target_arch = "x86_64"))] {
             let cx = &*(cx as *const libc::ucontext_t);
             (*cx.uc_mcontext).__ss.__rip as *const u8
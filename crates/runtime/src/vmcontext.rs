@@ -6,7 +6,8 @@ use crate::instance::Instance;
 use std::any::Any;
 use std::cell::UnsafeCell;
 use std::marker;
-use std::ptr::NonNull;
+use std::mem;
+use std::ptr::{self, NonNull};
 use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
 use std::u32;
 use wasmtime_environ::BuiltinFunctionIndex;
@@ -197,6 +198,45 @@ mod test_vmglobal_import {
     }
 }
 
+/// The fields compiled code needs to access to utilize a WebAssembly exception
+/// tag imported from another instance.
+///
+/// A tag's identity is the address of its `VMTagDefinition`, so importing a tag
+/// only needs a pointer to its definition.
+#[derive(Debug, Copy, Clone)]
+#[repr(C)]
+pub struct VMTagImport {
+    /// A pointer to the imported tag description.
+    pub from: *mut VMTagDefinition,
+}
+
+// Declare that this type is send/sync, it's the responsibility of users of
+// `VMTagImport` to uphold this guarantee.
+unsafe impl Send for VMTagImport {}
+unsafe impl Sync for VMTagImport {}
+
+#[cfg(test)]
+mod test_vmtag_import {
+    use super::VMTagImport;
+    use memoffset::offset_of;
+    use std::mem::size_of;
+    use wasmtime_environ::{Module, VMOffsets};
+
+    #[test]
+    fn check_vmtag_import_offsets() {
+        let module = Module::new();
+        let offsets = VMOffsets::new(size_of::<*mut u8>() as u8, &module);
+        assert_eq!(
+            size_of::<VMTagImport>(),
+            usize::from(offsets.size_of_vmtag_import())
+        );
+        assert_eq!(
+            offset_of!(VMTagImport, from),
+            usize::from(offsets.vmtag_import_from())
+        );
+    }
+}
+
 /// The fields compiled code needs to access to utilize a WebAssembly linear
 /// memory defined within the instance, namely the start address and the
 /// size in bytes.
@@ -482,6 +522,36 @@ impl VMGlobalDefinition {
     }
 }
 
+/// The definition of a WebAssembly exception tag within an instance.
+#[derive(Debug, Copy, Clone)]
+#[repr(C)]
+pub struct VMTagDefinition {
+    /// The type of the values carried by exceptions with this tag.
+    pub type_index: VMSharedSignatureIndex,
+}
+
+#[cfg(test)]
+mod test_vmtag_definition {
+    use super::VMTagDefinition;
+    use memoffset::offset_of;
+    use std::mem::size_of;
+    use wasmtime_environ::{Module, VMOffsets};
+
+    #[test]
+    fn check_vmtag_definition_offsets() {
+        let module = Module::new();
+        let offsets = VMOffsets::new(size_of::<*mut u8>() as u8, &module);
+        assert_eq!(
+            size_of::<VMTagDefinition>(),
+            usize::from(offsets.size_of_vmtag_definition())
+        );
+        assert_eq!(
+            offset_of!(VMTagDefinition, type_index),
+            usize::from(offsets.vmtag_definition_type_index())
+        );
+    }
+}
+
 /// An index into the shared signature registry, usable for checking signatures
 /// at indirect calls.
 #[repr(C)]
@@ -601,6 +671,7 @@ impl VMBuiltinFunctionsArray {
 
         ptrs[BuiltinFunctionIndex::memory32_grow().index() as usize] =
             wasmtime_memory32_grow as usize;
+        ptrs[BuiltinFunctionIndex::memory_size().index() as usize] = wasmtime_memory_size as usize;
         ptrs[BuiltinFunctionIndex::table_copy().index() as usize] = wasmtime_table_copy as usize;
         ptrs[BuiltinFunctionIndex::table_grow_funcref().index() as usize] =
             wasmtime_table_grow as usize;
@@ -631,6 +702,15 @@ impl VMBuiltinFunctionsArray {
         ptrs[BuiltinFunctionIndex::memory_atomic_wait64().index() as usize] =
             wasmtime_memory_atomic_wait64 as usize;
         ptrs[BuiltinFunctionIndex::out_of_gas().index() as usize] = wasmtime_out_of_gas as usize;
+        ptrs[BuiltinFunctionIndex::new_epoch().index() as usize] = wasmtime_new_epoch as usize;
+        ptrs[BuiltinFunctionIndex::throw().index() as usize] = wasmtime_throw as usize;
+        ptrs[BuiltinFunctionIndex::rethrow().index() as usize] = wasmtime_rethrow as usize;
+        ptrs[BuiltinFunctionIndex::exception_matches().index() as usize] =
+            wasmtime_exception_matches as usize;
+        ptrs[BuiltinFunctionIndex::catch_exception().index() as usize] =
+            wasmtime_catch_exception as usize;
+        ptrs[BuiltinFunctionIndex::resume_unwind().index() as usize] =
+            wasmtime_resume_unwind as usize;
 
         if cfg!(debug_assertions) {
             for i in 0..ptrs.len() {
@@ -694,15 +774,41 @@ pub struct VMInterrupts {
     /// turning positive a wasm trap will be generated. This field is only
     /// modified if wasm is configured to consume fuel.
     pub fuel_consumed: UnsafeCell<i64>,
+
+    /// Deadline epoch for interruption: if epoch-based interruption
+    /// is enabled and the global (per engine) epoch counter is
+    /// observed to reach or exceed this value, the guest code will
+    /// yield if running asynchronously.
+    pub epoch_deadline: UnsafeCell<u64>,
+
+    /// The WebAssembly exception currently being thrown, if any.
+    ///
+    /// This is an owned `VMExternRef` wrapping a `VMException`, or null when
+    /// no exception is pending. Compiled code checks it after calls inside of
+    /// a `try` block, which is where the unwinder resumes a handling frame.
+    pub pending_exception: UnsafeCell<*mut u8>,
+
+    /// The youngest wasm frame that records its locals and operand stack for
+    /// core dumps, or null.
+    ///
+    /// Only code compiled with core dumps enabled maintains this list: each
+    /// frame links a `VMCoreDumpFrame` in on entry and unlinks it when it
+    /// returns.
+    pub coredump_frames: UnsafeCell<*mut VMCoreDumpFrame>,
+
+    /// The frames of `coredump_frames` as they were when wasm last trapped,
+    /// since they're gone by the time the trap is handed to the embedder.
+    coredump_snapshot: UnsafeCell<Vec<CoreDumpFrameState>>,
 }
 
-// The `VMInterrupts` type is a pod-type with no destructor, and we only access
-// `stack_limit` from other threads, so add in these trait impls which are
-// otherwise not available due to the `fuel_consumed` variable in
-// `VMInterrupts`.
+// The `VMInterrupts` type is a pod-type apart from the pending exception and
+// core dump state, and we only access `stack_limit` from other threads, so add
+// in these trait impls which are otherwise not available due to the
+// `fuel_consumed`, `epoch_deadline`, `pending_exception` and core dump
+// variables in `VMInterrupts`.
 //
-// Note that users of `fuel_consumed` understand that the unsafety encompasses
-// ensuring that it's only mutated/accessed from one thread dynamically.
+// Note that users of those fields understand that the unsafety encompasses
+// ensuring that they're only mutated/accessed from one thread dynamically.
 unsafe impl Send for VMInterrupts {}
 unsafe impl Sync for VMInterrupts {}
 
@@ -712,6 +818,68 @@ impl VMInterrupts {
         self.stack_limit
             .store(wasmtime_environ::INTERRUPTED, SeqCst);
     }
+
+    /// Makes `exception` the pending exception, releasing any previously
+    /// pending one.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called from the thread currently running wasm in the store
+    /// that owns these interrupts.
+    pub unsafe fn set_pending_exception(&self, exception: VMExternRef) {
+        drop(self.take_pending_exception());
+        *self.pending_exception.get() = exception.into_raw();
+    }
+
+    /// Takes the pending exception, if any, leaving no exception pending.
+    ///
+    /// # Safety
+    ///
+    /// Same as `set_pending_exception`.
+    pub unsafe fn take_pending_exception(&self) -> Option<VMExternRef> {
+        let raw = ptr::replace(self.pending_exception.get(), ptr::null_mut());
+        if raw.is_null() {
+            None
+        } else {
+            Some(VMExternRef::from_raw(raw))
+        }
+    }
+
+    /// Records the state of every frame in `coredump_frames`, replacing any
+    /// previously captured frames.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called from the thread currently running wasm in the store
+    /// that owns these interrupts, while all of the frames in
+    /// `coredump_frames` are still live.
+    pub unsafe fn capture_coredump_frames(&self) {
+        let snapshot = &mut *self.coredump_snapshot.get();
+        snapshot.clear();
+        let mut frame = *self.coredump_frames.get();
+        while !frame.is_null() {
+            snapshot.push(CoreDumpFrameState::read(frame));
+            frame = (*frame).prev;
+        }
+    }
+
+    /// Takes the frames recorded by the last `capture_coredump_frames`,
+    /// youngest first.
+    ///
+    /// # Safety
+    ///
+    /// Same as `capture_coredump_frames`.
+    pub unsafe fn take_coredump_frames(&self) -> Vec<CoreDumpFrameState> {
+        mem::take(&mut *self.coredump_snapshot.get())
+    }
+}
+
+impl Drop for VMInterrupts {
+    fn drop(&mut self) {
+        unsafe {
+            drop(self.take_pending_exception());
+        }
+    }
 }
 
 impl Default for VMInterrupts {
@@ -719,6 +887,10 @@ impl Default for VMInterrupts {
         VMInterrupts {
             stack_limit: AtomicUsize::new(usize::max_value()),
             fuel_consumed: UnsafeCell::new(0),
+            epoch_deadline: UnsafeCell::new(0),
+            pending_exception: UnsafeCell::new(ptr::null_mut()),
+            coredump_frames: UnsafeCell::new(ptr::null_mut()),
+            coredump_snapshot: UnsafeCell::new(Vec::new()),
         }
     }
 }
@@ -738,6 +910,144 @@ mod test_vminterrupts {
             offset_of!(VMInterrupts, stack_limit),
             usize::from(offsets.vminterrupts_stack_limit())
         );
+        assert_eq!(
+            offset_of!(VMInterrupts, fuel_consumed),
+            usize::from(offsets.vminterrupts_fuel_consumed())
+        );
+        assert_eq!(
+            offset_of!(VMInterrupts, epoch_deadline),
+            usize::from(offsets.vminterrupts_epoch_deadline())
+        );
+        assert_eq!(
+            offset_of!(VMInterrupts, pending_exception),
+            usize::from(offsets.vminterrupts_pending_exception())
+        );
+        assert_eq!(
+            offset_of!(VMInterrupts, coredump_frames),
+            usize::from(offsets.vminterrupts_coredump_frames())
+        );
+    }
+}
+
+/// The record a wasm frame keeps of its state when compiled with core dumps
+/// enabled.
+///
+/// This is followed in memory by `num_locals` entries for the frame's locals
+/// and then `num_stack` entries for its operand stack, each of which is
+/// `size_of_vmcoredump_value` bytes. An entry holds the value in its first
+/// bytes and the wasm binary encoding of its type in the byte at
+/// `vmcoredump_value_type`, or zero if the value wasn't recorded.
+#[derive(Debug)]
+#[repr(C)]
+pub struct VMCoreDumpFrame {
+    /// The next older frame which records its state.
+    pub prev: *mut VMCoreDumpFrame,
+
+    /// The `VMContext` of the instance this frame is executing in.
+    pub vmctx: *mut VMContext,
+
+    /// The index of the function this frame is executing.
+    pub func_index: u32,
+
+    /// The number of locals of the function.
+    pub num_locals: u32,
+
+    /// The height of the operand stack at the last instruction which may
+    /// have trapped.
+    pub num_stack: u32,
+
+    _padding: u32,
+}
+
+#[cfg(test)]
+mod test_vmcoredump_frame {
+    use super::VMCoreDumpFrame;
+    use memoffset::offset_of;
+    use std::mem::size_of;
+    use wasmtime_environ::{Module, VMOffsets};
+
+    #[test]
+    fn check_vmcoredump_frame_offsets() {
+        let module = Module::new();
+        let offsets = VMOffsets::new(size_of::<*mut u8>() as u8, &module);
+        assert_eq!(
+            size_of::<VMCoreDumpFrame>(),
+            usize::from(offsets.size_of_vmcoredump_frame())
+        );
+        assert_eq!(
+            offset_of!(VMCoreDumpFrame, prev),
+            usize::from(offsets.vmcoredump_frame_prev())
+        );
+        assert_eq!(
+            offset_of!(VMCoreDumpFrame, vmctx),
+            usize::from(offsets.vmcoredump_frame_vmctx())
+        );
+        assert_eq!(
+            offset_of!(VMCoreDumpFrame, func_index),
+            usize::from(offsets.vmcoredump_frame_func_index())
+        );
+        assert_eq!(
+            offset_of!(VMCoreDumpFrame, num_locals),
+            usize::from(offsets.vmcoredump_frame_num_locals())
+        );
+        assert_eq!(
+            offset_of!(VMCoreDumpFrame, num_stack),
+            usize::from(offsets.vmcoredump_frame_num_stack())
+        );
+    }
+}
+
+/// A local or operand stack value recorded by a `VMCoreDumpFrame`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CoreDumpValue {
+    /// An `i32` value.
+    I32(i32),
+    /// An `i64` value, which is also how `funcref` values are recorded.
+    I64(i64),
+    /// The bits of an `f32` value.
+    F32(u32),
+    /// The bits of an `f64` value.
+    F64(u64),
+    /// A value of a type that isn't recorded, such as `v128` or `externref`.
+    Missing,
+}
+
+/// The state of a wasm frame captured from its `VMCoreDumpFrame`.
+#[derive(Debug)]
+pub struct CoreDumpFrameState {
+    /// The `VMContext` of the instance the frame was executing in.
+    pub vmctx: *mut VMContext,
+    /// The index of the function the frame was executing.
+    pub func_index: u32,
+    /// The values of the frame's locals.
+    pub locals: Vec<CoreDumpValue>,
+    /// The frame's operand stack, bottom first.
+    pub stack: Vec<CoreDumpValue>,
+}
+
+impl CoreDumpFrameState {
+    unsafe fn read(frame: *const VMCoreDumpFrame) -> CoreDumpFrameState {
+        // Entries are only guaranteed to be aligned to the pointer size.
+        let entry = |i: u32| -> CoreDumpValue {
+            let entry = (frame as *const u8)
+                .add(mem::size_of::<VMCoreDumpFrame>())
+                .add(16 * i as usize);
+            match *entry.add(8) {
+                0x7f => CoreDumpValue::I32(ptr::read_unaligned(entry as *const i32)),
+                0x7e => CoreDumpValue::I64(ptr::read_unaligned(entry as *const i64)),
+                0x7d => CoreDumpValue::F32(ptr::read_unaligned(entry as *const u32)),
+                0x7c => CoreDumpValue::F64(ptr::read_unaligned(entry as *const u64)),
+                _ => CoreDumpValue::Missing,
+            }
+        };
+        let num_locals = (*frame).num_locals;
+        let num_stack = (*frame).num_stack;
+        CoreDumpFrameState {
+            vmctx: (*frame).vmctx,
+            func_index: (*frame).func_index,
+            locals: (0..num_locals).map(entry).collect(),
+            stack: (num_locals..num_locals + num_stack).map(entry).collect(),
+        }
     }
 }
 
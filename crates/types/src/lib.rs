@@ -175,6 +175,11 @@ entity_impl!(DefinedMemoryIndex);
 pub struct DefinedGlobalIndex(u32);
 entity_impl!(DefinedGlobalIndex);
 
+/// Index type of a defined tag inside the WebAssembly module.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub struct DefinedTagIndex(u32);
+entity_impl!(DefinedTagIndex);
+
 /// Index type of a table (imported or defined) inside the WebAssembly module.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Serialize, Deserialize)]
 pub struct TableIndex(u32);
@@ -250,6 +255,8 @@ pub enum EntityIndex {
     Module(ModuleIndex),
     /// Instance index.
     Instance(InstanceIndex),
+    /// Tag index.
+    Tag(TagIndex),
 }
 
 /// A type of an item in a wasm module where an item is typically something that
@@ -261,8 +268,9 @@ pub enum EntityType {
     Global(Global),
     /// A linear memory with the specified limits
     Memory(Memory),
-    /// An event definition.
-    Tag(Tag),
+    /// An exception tag where the index points to the type section and
+    /// records the types of the values carried by the exception.
+    Tag(SignatureIndex),
     /// A table with the specified element type and limits
     Table(Table),
     /// A function type where the index points to the type section and records a
@@ -7,6 +7,20 @@ fn main() {
     println!("cargo:wasi={}", wasi.display());
     // and available to our own crate as WASI_ROOT:
     println!("cargo:rustc-env=WASI_ROOT={}", wasi.display());
-    // and this build.rs script doesn't depend on any files.
+
+    // `sock_accept` was added to snapshot 1 after it was first published, so
+    // an old checkout of the submodule fails to compile with an error about
+    // the trait instead of the witx. Point at the real cause.
+    let snapshot1 = wasi.join("phases/snapshot/witx/wasi_snapshot_preview1.witx");
+    if let Ok(witx) = std::fs::read_to_string(&snapshot1) {
+        if !witx.contains("(export \"sock_accept\")") {
+            panic!(
+                "{} doesn't declare `sock_accept`; run `git submodule update` to \
+                 check out the WASI revision this crate is built against",
+                snapshot1.display()
+            );
+        }
+    }
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={}", snapshot1.display());
 }
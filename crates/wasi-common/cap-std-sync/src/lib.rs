@@ -14,8 +14,8 @@
 //! `cap_std::fs::Dir`, and provides convenience methods for inheriting the
 //! parent process's stdio, args, and env.
 //!
-//! For the convenience of consumers, `cap_std::fs::Dir` is re-exported from
-//! this crate. This saves consumers tracking an additional dep on the exact
+//! For the convenience of consumers, `cap_std::fs::Dir` and
+//! `cap_std::net::TcpListener` are re-exported from this crate. This saves consumers tracking an additional dep on the exact
 //! version of cap_std used by this crate, if they want to avoid it.
 //!
 //! The only place we expect to run into long-term compatibility issues
@@ -36,17 +36,20 @@
 pub mod clocks;
 pub mod dir;
 pub mod file;
+pub mod net;
 pub mod sched;
 pub mod stdio;
 
 pub use cap_std::ambient_authority;
 pub use cap_std::fs::Dir;
+pub use cap_std::net::TcpListener;
 pub use clocks::clocks_ctx;
+pub use net::Socket;
 pub use sched::sched_ctx;
 
 use cap_rand::RngCore;
 use std::path::Path;
-use wasi_common::{table::Table, Error, WasiCtx, WasiFile};
+use wasi_common::{file::FileCaps, table::Table, Error, WasiCtx, WasiFile};
 
 pub struct WasiCtxBuilder(WasiCtx);
 
@@ -120,6 +123,16 @@ impl WasiCtxBuilder {
         self.0.push_preopened_dir(dir, guest_path)?;
         Ok(self)
     }
+    pub fn preopened_socket(mut self, fd: u32, socket: impl Into<Socket>) -> Result<Self, Error> {
+        let socket: Socket = socket.into();
+        let file: Box<dyn WasiFile> = socket.into();
+        let caps = FileCaps::FDSTAT_SET_FLAGS
+            | FileCaps::FILESTAT_GET
+            | FileCaps::READ
+            | FileCaps::POLL_READWRITE;
+        self.0.insert_file(fd, file, caps);
+        Ok(self)
+    }
     pub fn build(self) -> WasiCtx {
         self.0
     }
@@ -0,0 +1,460 @@
+#[cfg(unix)]
+use io_lifetimes::{AsFd, AsFilelike, BorrowedFd};
+#[cfg(windows)]
+use io_lifetimes::{AsSocket, BorrowedSocket};
+use std::any::Any;
+use std::convert::TryInto;
+use std::io::{self, Read, Write};
+use std::net::Shutdown;
+use system_interface::io::{Peek, ReadReady};
+use wasi_common::{
+    file::{Advice, FdFlags, FileType, Filestat, RiFlags, RoFlags, SdFlags, SiFlags, WasiFile},
+    Error, ErrorExt,
+};
+
+/// A socket which can be preopened into a `WasiCtx`, see
+/// `WasiCtxBuilder::preopened_socket`.
+pub enum Socket {
+    TcpListener(cap_std::net::TcpListener),
+    TcpStream(cap_std::net::TcpStream),
+    #[cfg(unix)]
+    UnixListener(cap_std::os::unix::net::UnixListener),
+    #[cfg(unix)]
+    UnixStream(cap_std::os::unix::net::UnixStream),
+}
+
+impl From<cap_std::net::TcpListener> for Socket {
+    fn from(listener: cap_std::net::TcpListener) -> Self {
+        Self::TcpListener(listener)
+    }
+}
+
+impl From<cap_std::net::TcpStream> for Socket {
+    fn from(stream: cap_std::net::TcpStream) -> Self {
+        Self::TcpStream(stream)
+    }
+}
+
+#[cfg(unix)]
+impl From<cap_std::os::unix::net::UnixListener> for Socket {
+    fn from(listener: cap_std::os::unix::net::UnixListener) -> Self {
+        Self::UnixListener(listener)
+    }
+}
+
+#[cfg(unix)]
+impl From<cap_std::os::unix::net::UnixStream> for Socket {
+    fn from(stream: cap_std::os::unix::net::UnixStream) -> Self {
+        Self::UnixStream(stream)
+    }
+}
+
+impl From<Socket> for Box<dyn WasiFile> {
+    fn from(socket: Socket) -> Self {
+        match socket {
+            Socket::TcpListener(listener) => Box::new(TcpListener::from_cap_std(listener)),
+            Socket::TcpStream(stream) => Box::new(TcpStream::from_cap_std(stream)),
+            #[cfg(unix)]
+            Socket::UnixListener(listener) => Box::new(UnixListener::from_cap_std(listener)),
+            #[cfg(unix)]
+            Socket::UnixStream(stream) => Box::new(UnixStream::from_cap_std(stream)),
+        }
+    }
+}
+
+macro_rules! socket_handle_impl {
+    ($ty:ty) => {
+        #[cfg(windows)]
+        impl AsSocket for $ty {
+            fn as_socket(&self) -> BorrowedSocket<'_> {
+                self.0.as_socket()
+            }
+        }
+        #[cfg(unix)]
+        impl AsFd for $ty {
+            fn as_fd(&self) -> BorrowedFd<'_> {
+                self.0.as_fd()
+            }
+        }
+    };
+}
+
+macro_rules! wasi_listen_write_impl {
+    ($ty:ty, $stream:ty) => {
+        impl $ty {
+            /// Accepts a new connection, setting `fdflags` on the returned
+            /// stream.
+            pub fn accept(&self, fdflags: FdFlags) -> Result<$stream, Error> {
+                let (stream, _) = self.0.accept()?;
+                set_fdflags(fdflags, |nonblocking| stream.set_nonblocking(nonblocking))?;
+                Ok(<$stream>::from_cap_std(stream))
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl WasiFile for $ty {
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+            async fn sock_accept(&self, fdflags: FdFlags) -> Result<Box<dyn WasiFile>, Error> {
+                Ok(Box::new(self.accept(fdflags)?))
+            }
+            async fn datasync(&self) -> Result<(), Error> {
+                Err(Error::badf())
+            }
+            async fn sync(&self) -> Result<(), Error> {
+                Err(Error::badf())
+            }
+            async fn get_filetype(&self) -> Result<FileType, Error> {
+                Ok(FileType::SocketStream)
+            }
+            async fn get_fdflags(&self) -> Result<FdFlags, Error> {
+                get_fdflags(&self.0)
+            }
+            async fn set_fdflags(&mut self, fdflags: FdFlags) -> Result<(), Error> {
+                set_fdflags(fdflags, |nonblocking| self.0.set_nonblocking(nonblocking))
+            }
+            async fn get_filestat(&self) -> Result<Filestat, Error> {
+                Ok(socket_filestat())
+            }
+            async fn set_filestat_size(&self, _size: u64) -> Result<(), Error> {
+                Err(Error::badf())
+            }
+            async fn advise(&self, _offset: u64, _len: u64, _advice: Advice) -> Result<(), Error> {
+                Err(Error::badf())
+            }
+            async fn allocate(&self, _offset: u64, _len: u64) -> Result<(), Error> {
+                Err(Error::badf())
+            }
+            async fn set_times(
+                &self,
+                _atime: Option<wasi_common::SystemTimeSpec>,
+                _mtime: Option<wasi_common::SystemTimeSpec>,
+            ) -> Result<(), Error> {
+                Err(Error::badf())
+            }
+            async fn read_vectored<'a>(
+                &self,
+                _bufs: &mut [io::IoSliceMut<'a>],
+            ) -> Result<u64, Error> {
+                Err(Error::badf())
+            }
+            async fn read_vectored_at<'a>(
+                &self,
+                _bufs: &mut [io::IoSliceMut<'a>],
+                _offset: u64,
+            ) -> Result<u64, Error> {
+                Err(Error::badf())
+            }
+            async fn write_vectored<'a>(&self, _bufs: &[io::IoSlice<'a>]) -> Result<u64, Error> {
+                Err(Error::badf())
+            }
+            async fn write_vectored_at<'a>(
+                &self,
+                _bufs: &[io::IoSlice<'a>],
+                _offset: u64,
+            ) -> Result<u64, Error> {
+                Err(Error::badf())
+            }
+            async fn seek(&self, _pos: std::io::SeekFrom) -> Result<u64, Error> {
+                Err(Error::seek_pipe())
+            }
+            async fn peek(&self, _buf: &mut [u8]) -> Result<u64, Error> {
+                Err(Error::badf())
+            }
+            async fn num_ready_bytes(&self) -> Result<u64, Error> {
+                // A listener is only polled for pending connections, so report
+                // a single byte to make it look readable when one arrives.
+                Ok(1)
+            }
+            async fn readable(&self) -> Result<(), Error> {
+                Err(Error::badf())
+            }
+            async fn writable(&self) -> Result<(), Error> {
+                Err(Error::badf())
+            }
+        }
+
+        socket_handle_impl!($ty);
+    };
+}
+
+macro_rules! wasi_stream_write_impl {
+    ($ty:ty) => {
+        #[async_trait::async_trait]
+        impl WasiFile for $ty {
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+            async fn datasync(&self) -> Result<(), Error> {
+                Err(Error::badf())
+            }
+            async fn sync(&self) -> Result<(), Error> {
+                Err(Error::badf())
+            }
+            async fn get_filetype(&self) -> Result<FileType, Error> {
+                Ok(FileType::SocketStream)
+            }
+            async fn get_fdflags(&self) -> Result<FdFlags, Error> {
+                get_fdflags(&self.0)
+            }
+            async fn set_fdflags(&mut self, fdflags: FdFlags) -> Result<(), Error> {
+                set_fdflags(fdflags, |nonblocking| self.0.set_nonblocking(nonblocking))
+            }
+            async fn get_filestat(&self) -> Result<Filestat, Error> {
+                Ok(socket_filestat())
+            }
+            async fn set_filestat_size(&self, _size: u64) -> Result<(), Error> {
+                Err(Error::badf())
+            }
+            async fn advise(&self, _offset: u64, _len: u64, _advice: Advice) -> Result<(), Error> {
+                Err(Error::badf())
+            }
+            async fn allocate(&self, _offset: u64, _len: u64) -> Result<(), Error> {
+                Err(Error::badf())
+            }
+            async fn set_times(
+                &self,
+                _atime: Option<wasi_common::SystemTimeSpec>,
+                _mtime: Option<wasi_common::SystemTimeSpec>,
+            ) -> Result<(), Error> {
+                Err(Error::badf())
+            }
+            async fn read_vectored<'a>(
+                &self,
+                bufs: &mut [io::IoSliceMut<'a>],
+            ) -> Result<u64, Error> {
+                let n = Read::read_vectored(&mut &self.0, bufs)?;
+                Ok(n.try_into()?)
+            }
+            async fn read_vectored_at<'a>(
+                &self,
+                _bufs: &mut [io::IoSliceMut<'a>],
+                _offset: u64,
+            ) -> Result<u64, Error> {
+                Err(Error::seek_pipe())
+            }
+            async fn write_vectored<'a>(&self, bufs: &[io::IoSlice<'a>]) -> Result<u64, Error> {
+                let n = Write::write_vectored(&mut &self.0, bufs)?;
+                Ok(n.try_into()?)
+            }
+            async fn write_vectored_at<'a>(
+                &self,
+                _bufs: &[io::IoSlice<'a>],
+                _offset: u64,
+            ) -> Result<u64, Error> {
+                Err(Error::seek_pipe())
+            }
+            async fn seek(&self, _pos: std::io::SeekFrom) -> Result<u64, Error> {
+                Err(Error::seek_pipe())
+            }
+            async fn peek(&self, buf: &mut [u8]) -> Result<u64, Error> {
+                let n = Peek::peek(&self.0, buf)?;
+                Ok(n.try_into()?)
+            }
+            async fn num_ready_bytes(&self) -> Result<u64, Error> {
+                Ok(self.0.num_ready_bytes()?)
+            }
+            #[cfg(unix)]
+            async fn readable(&self) -> Result<(), Error> {
+                wait_ready(&self.0, rustix::io::PollFlags::IN)
+            }
+            #[cfg(windows)]
+            async fn readable(&self) -> Result<(), Error> {
+                // Windows uses a handle based scheduler which checks readiness
+                // itself.
+                Err(Error::badf())
+            }
+            #[cfg(unix)]
+            async fn writable(&self) -> Result<(), Error> {
+                wait_ready(&self.0, rustix::io::PollFlags::OUT)
+            }
+            #[cfg(windows)]
+            async fn writable(&self) -> Result<(), Error> {
+                // Windows uses a handle based scheduler which checks readiness
+                // itself.
+                Err(Error::badf())
+            }
+            async fn sock_recv<'a>(
+                &self,
+                ri_data: &mut [io::IoSliceMut<'a>],
+                ri_flags: RiFlags,
+            ) -> Result<(u64, RoFlags), Error> {
+                let n = if ri_flags.contains(RiFlags::RECV_PEEK) {
+                    recv_peek(&self.0, ri_data)?
+                } else if ri_flags.contains(RiFlags::RECV_WAITALL) {
+                    recv_waitall(&self.0, ri_data)?
+                } else {
+                    Read::read_vectored(&mut &self.0, ri_data)?
+                };
+                Ok((n.try_into()?, RoFlags::empty()))
+            }
+            async fn sock_send<'a>(
+                &self,
+                si_data: &[io::IoSlice<'a>],
+                _si_flags: SiFlags,
+            ) -> Result<u64, Error> {
+                let n = Write::write_vectored(&mut &self.0, si_data)?;
+                Ok(n.try_into()?)
+            }
+            async fn sock_shutdown(&self, how: SdFlags) -> Result<(), Error> {
+                let how = if how == SdFlags::RD | SdFlags::WR {
+                    Shutdown::Both
+                } else if how == SdFlags::RD {
+                    Shutdown::Read
+                } else if how == SdFlags::WR {
+                    Shutdown::Write
+                } else {
+                    return Err(
+                        Error::invalid_argument().context("shutdown must be given RD, WR or both")
+                    );
+                };
+                self.0.shutdown(how)?;
+                Ok(())
+            }
+        }
+
+        socket_handle_impl!($ty);
+    };
+}
+
+pub struct TcpListener(cap_std::net::TcpListener);
+
+impl TcpListener {
+    pub fn from_cap_std(listener: cap_std::net::TcpListener) -> Self {
+        TcpListener(listener)
+    }
+}
+wasi_listen_write_impl!(TcpListener, TcpStream);
+
+pub struct TcpStream(cap_std::net::TcpStream);
+
+impl TcpStream {
+    pub fn from_cap_std(stream: cap_std::net::TcpStream) -> Self {
+        TcpStream(stream)
+    }
+}
+wasi_stream_write_impl!(TcpStream);
+
+#[cfg(unix)]
+pub struct UnixListener(cap_std::os::unix::net::UnixListener);
+
+#[cfg(unix)]
+impl UnixListener {
+    pub fn from_cap_std(listener: cap_std::os::unix::net::UnixListener) -> Self {
+        UnixListener(listener)
+    }
+}
+#[cfg(unix)]
+wasi_listen_write_impl!(UnixListener, UnixStream);
+
+#[cfg(unix)]
+pub struct UnixStream(cap_std::os::unix::net::UnixStream);
+
+#[cfg(unix)]
+impl UnixStream {
+    pub fn from_cap_std(stream: cap_std::os::unix::net::UnixStream) -> Self {
+        UnixStream(stream)
+    }
+}
+#[cfg(unix)]
+wasi_stream_write_impl!(UnixStream);
+
+/// Peeks at as much data as fits in all of `bufs`, filling them in order.
+fn recv_peek(socket: &impl Peek, bufs: &mut [io::IoSliceMut]) -> io::Result<usize> {
+    let mut data = vec![0; bufs.iter().map(|buf| buf.len()).sum()];
+    let n = socket.peek(&mut data)?;
+    let mut rest = &data[..n];
+    for buf in bufs.iter_mut() {
+        if rest.is_empty() {
+            break;
+        }
+        let len = buf.len().min(rest.len());
+        buf[..len].copy_from_slice(&rest[..len]);
+        rest = &rest[len..];
+    }
+    Ok(n)
+}
+
+/// Reads until all of `bufs` are full or the peer closes the connection.
+///
+/// Data which has already been read is never discarded: if reading fails
+/// after some data was read, for example because the socket would block, the
+/// number of bytes read so far is returned instead of the error.
+fn recv_waitall(mut socket: impl Read, bufs: &mut [io::IoSliceMut]) -> io::Result<usize> {
+    let mut n = 0;
+    for buf in bufs.iter_mut() {
+        let mut filled = 0;
+        while filled < buf.len() {
+            match socket.read(&mut buf[filled..]) {
+                Ok(0) => return Ok(n),
+                Ok(len) => {
+                    filled += len;
+                    n += len;
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(_) if n > 0 => return Ok(n),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    Ok(n)
+}
+
+/// Blocks until `socket` is ready for the operations in `flags`.
+#[cfg(unix)]
+fn wait_ready<Socketlike: AsFd>(
+    socket: &Socketlike,
+    flags: rustix::io::PollFlags,
+) -> Result<(), Error> {
+    let mut pollfds = [rustix::io::PollFd::from_borrowed_fd(socket.as_fd(), flags)];
+    loop {
+        match rustix::io::poll(&mut pollfds, -1) {
+            Ok(_) => return Ok(()),
+            Err(rustix::io::Error::INTR) => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+fn socket_filestat() -> Filestat {
+    Filestat {
+        device_id: 0,
+        inode: 0,
+        filetype: FileType::SocketStream,
+        nlink: 0,
+        size: 0,
+        atim: None,
+        mtim: None,
+        ctim: None,
+    }
+}
+
+#[cfg(unix)]
+fn get_fdflags<Socketlike: AsFd>(socket: &Socketlike) -> Result<FdFlags, Error> {
+    use system_interface::fs::GetSetFdFlags;
+    let fdflags = socket.as_filelike_view::<std::fs::File>().get_fd_flags()?;
+    Ok(crate::file::from_sysif_fdflags(fdflags))
+}
+
+#[cfg(windows)]
+fn get_fdflags<Socketlike: AsSocket>(_socket: &Socketlike) -> Result<FdFlags, Error> {
+    // Windows has no way to query whether a socket is in non-blocking mode.
+    Ok(FdFlags::empty())
+}
+
+/// Sockets only support the `NONBLOCK` flag, which is applied with the given
+/// `set_nonblocking` function.
+fn set_fdflags(
+    fdflags: FdFlags,
+    set_nonblocking: impl FnOnce(bool) -> io::Result<()>,
+) -> Result<(), Error> {
+    if fdflags == FdFlags::NONBLOCK {
+        set_nonblocking(true)?;
+    } else if fdflags.is_empty() {
+        set_nonblocking(false)?;
+    } else {
+        return Err(Error::invalid_argument().context("sockets only support the NONBLOCK flag"));
+    }
+    Ok(())
+}
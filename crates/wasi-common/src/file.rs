@@ -37,6 +37,29 @@ pub trait WasiFile: Send + Sync {
 
     async fn readable(&self) -> Result<(), Error>;
     async fn writable(&self) -> Result<(), Error>;
+
+    // The socket operations are only supported by files backed by sockets, so
+    // they default to failing with `badf`.
+    async fn sock_accept(&self, _fdflags: FdFlags) -> Result<Box<dyn WasiFile>, Error> {
+        Err(Error::badf())
+    }
+    async fn sock_recv<'a>(
+        &self,
+        _ri_data: &mut [std::io::IoSliceMut<'a>],
+        _ri_flags: RiFlags,
+    ) -> Result<(u64, RoFlags), Error> {
+        Err(Error::badf())
+    }
+    async fn sock_send<'a>(
+        &self,
+        _si_data: &[std::io::IoSlice<'a>],
+        _si_flags: SiFlags,
+    ) -> Result<u64, Error> {
+        Err(Error::badf())
+    }
+    async fn sock_shutdown(&self, _how: SdFlags) -> Result<(), Error> {
+        Err(Error::badf())
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -62,6 +85,31 @@ bitflags! {
     }
 }
 
+bitflags! {
+    pub struct RiFlags: u32 {
+        const RECV_PEEK    = 0b1;
+        const RECV_WAITALL = 0b10;
+    }
+}
+
+bitflags! {
+    pub struct RoFlags: u32 {
+        const RECV_DATA_TRUNCATED = 0b1;
+    }
+}
+
+bitflags! {
+    // No send flags are defined yet.
+    pub struct SiFlags: u32 {}
+}
+
+bitflags! {
+    pub struct SdFlags: u32 {
+        const RD = 0b1;
+        const WR = 0b10;
+    }
+}
+
 bitflags! {
     pub struct OFlags: u32 {
         const CREATE    = 0b1;
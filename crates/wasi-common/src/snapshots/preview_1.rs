@@ -2,7 +2,7 @@ use crate::{
     dir::{DirCaps, DirEntry, DirEntryExt, DirFdStat, ReaddirCursor, ReaddirEntity, TableDirExt},
     file::{
         Advice, FdFlags, FdStat, FileCaps, FileEntry, FileEntryExt, FileType, Filestat, OFlags,
-        TableFileExt, WasiFile,
+        RiFlags, RoFlags, SdFlags, SiFlags, TableFileExt, WasiFile,
     },
     sched::{
         subscription::{RwEventFlags, SubscriptionResult},
@@ -1148,26 +1148,89 @@ impl wasi_snapshot_preview1::WasiSnapshotPreview1 for WasiCtx {
         Ok(())
     }
 
+    async fn sock_accept(
+        &mut self,
+        fd: types::Fd,
+        flags: types::Fdflags,
+    ) -> Result<types::Fd, Error> {
+        let table = self.table();
+        let f = table.get_file(u32::from(fd))?.get_cap(FileCaps::READ)?;
+
+        let file = f.sock_accept(FdFlags::from(flags)).await?;
+        let file_caps = FileCaps::READ
+            | FileCaps::WRITE
+            | FileCaps::FDSTAT_SET_FLAGS
+            | FileCaps::POLL_READWRITE
+            | FileCaps::FILESTAT_GET;
+
+        let fd = table.push(Box::new(FileEntry::new(file_caps, file)))?;
+        Ok(types::Fd::from(fd))
+    }
+
     async fn sock_recv<'a>(
         &mut self,
-        _fd: types::Fd,
-        _ri_data: &types::IovecArray<'a>,
-        _ri_flags: types::Riflags,
+        fd: types::Fd,
+        ri_data: &types::IovecArray<'a>,
+        ri_flags: types::Riflags,
     ) -> Result<(types::Size, types::Roflags), Error> {
-        Err(Error::trap("sock_recv unsupported"))
+        let table = self.table();
+        let f = table.get_file(u32::from(fd))?.get_cap(FileCaps::READ)?;
+
+        let mut guest_slices: Vec<wiggle::GuestSliceMut<u8>> = ri_data
+            .iter()
+            .map(|iov_ptr| {
+                let iov_ptr = iov_ptr?;
+                let iov: types::Iovec = iov_ptr.read()?;
+                Ok(iov.buf.as_array(iov.buf_len).as_slice_mut()?)
+            })
+            .collect::<Result<_, Error>>()?;
+
+        let mut ioslices: Vec<IoSliceMut> = guest_slices
+            .iter_mut()
+            .map(|s| IoSliceMut::new(&mut *s))
+            .collect();
+
+        let (bytes_read, roflags) = f.sock_recv(&mut ioslices, RiFlags::from(ri_flags)).await?;
+        Ok((types::Size::try_from(bytes_read)?, roflags.into()))
     }
 
     async fn sock_send<'a>(
         &mut self,
-        _fd: types::Fd,
-        _si_data: &types::CiovecArray<'a>,
-        _si_flags: types::Siflags,
+        fd: types::Fd,
+        si_data: &types::CiovecArray<'a>,
+        si_flags: types::Siflags,
     ) -> Result<types::Size, Error> {
-        Err(Error::trap("sock_send unsupported"))
+        let table = self.table();
+        let f = table.get_file(u32::from(fd))?.get_cap(FileCaps::WRITE)?;
+
+        let si_flags = SiFlags::from_bits(u32::from(si_flags))
+            .ok_or_else(|| Error::not_supported().context("unknown send flags"))?;
+
+        let guest_slices: Vec<wiggle::GuestSlice<u8>> = si_data
+            .iter()
+            .map(|iov_ptr| {
+                let iov_ptr = iov_ptr?;
+                let iov: types::Ciovec = iov_ptr.read()?;
+                Ok(iov.buf.as_array(iov.buf_len).as_slice()?)
+            })
+            .collect::<Result<_, Error>>()?;
+
+        let ioslices: Vec<IoSlice> = guest_slices
+            .iter()
+            .map(|s| IoSlice::new(s.deref()))
+            .collect();
+        let bytes_written = f.sock_send(&ioslices, si_flags).await?;
+
+        Ok(types::Size::try_from(bytes_written)?)
     }
 
-    async fn sock_shutdown(&mut self, _fd: types::Fd, _how: types::Sdflags) -> Result<(), Error> {
-        Err(Error::trap("sock_shutdown unsupported"))
+    async fn sock_shutdown(&mut self, fd: types::Fd, how: types::Sdflags) -> Result<(), Error> {
+        let table = self.table();
+        let f = table
+            .get_file(u32::from(fd))?
+            .get_cap(FileCaps::FDSTAT_SET_FLAGS)?;
+
+        f.sock_shutdown(SdFlags::from(how)).await
     }
 }
 
@@ -1463,6 +1526,10 @@ convert_flags_bidirectional!(
     SYNC
 );
 
+convert_flags!(types::Riflags, RiFlags, RECV_PEEK, RECV_WAITALL);
+convert_flags!(RoFlags, types::Roflags, RECV_DATA_TRUNCATED);
+convert_flags!(types::Sdflags, SdFlags, RD, WR);
+
 impl From<&types::Oflags> for OFlags {
     fn from(oflags: &types::Oflags) -> OFlags {
         let mut out = OFlags::empty();
@@ -6,7 +6,7 @@ use io_lifetimes::{AsHandle, BorrowedHandle};
 use std::any::Any;
 use std::io;
 use wasi_common::{
-    file::{Advice, FdFlags, FileType, Filestat, WasiFile},
+    file::{Advice, FdFlags, FileType, Filestat, RiFlags, RoFlags, SdFlags, SiFlags, WasiFile},
     Error,
 };
 
@@ -39,8 +39,13 @@ pub fn stderr() -> Stderr {
     Stderr(wasi_cap_std_sync::stdio::stderr())
 }
 
+// The optional block contains any extra `WasiFile` methods that can't just be
+// forwarded to the wrapped `wasi-cap-std-sync` type, such as `sock_accept`.
 macro_rules! wasi_file_impl {
     ($ty:ty) => {
+        wasi_file_impl!($ty, {});
+    };
+    ($ty:ty, { $($extra:tt)* }) => {
         #[wiggle::async_trait]
         impl WasiFile for $ty {
             fn as_any(&self) -> &dyn Any {
@@ -112,6 +117,25 @@ macro_rules! wasi_file_impl {
             async fn num_ready_bytes(&self) -> Result<u64, Error> {
                 block_on_dummy_executor(|| self.0.num_ready_bytes())
             }
+            async fn sock_recv<'a>(
+                &self,
+                ri_data: &mut [io::IoSliceMut<'a>],
+                ri_flags: RiFlags,
+            ) -> Result<(u64, RoFlags), Error> {
+                block_on_dummy_executor(move || self.0.sock_recv(ri_data, ri_flags))
+            }
+            async fn sock_send<'a>(
+                &self,
+                si_data: &[io::IoSlice<'a>],
+                si_flags: SiFlags,
+            ) -> Result<u64, Error> {
+                block_on_dummy_executor(move || self.0.sock_send(si_data, si_flags))
+            }
+            async fn sock_shutdown(&self, how: SdFlags) -> Result<(), Error> {
+                block_on_dummy_executor(move || self.0.sock_shutdown(how))
+            }
+
+            $($extra)*
 
             #[cfg(not(windows))]
             async fn readable(&self) -> Result<(), Error> {
@@ -173,6 +197,17 @@ macro_rules! wasi_file_impl {
                 Err(Error::badf())
             }
         }
+    };
+}
+pub(crate) use wasi_file_impl;
+
+wasi_file_impl!(File);
+wasi_file_impl!(Stdin);
+wasi_file_impl!(Stdout);
+wasi_file_impl!(Stderr);
+
+macro_rules! as_handle_impl {
+    ($ty:ty) => {
         #[cfg(windows)]
         impl AsHandle for $ty {
             fn as_handle(&self) -> BorrowedHandle<'_> {
@@ -182,7 +217,7 @@ macro_rules! wasi_file_impl {
     };
 }
 
-wasi_file_impl!(File);
-wasi_file_impl!(Stdin);
-wasi_file_impl!(Stdout);
-wasi_file_impl!(Stderr);
+as_handle_impl!(File);
+as_handle_impl!(Stdin);
+as_handle_impl!(Stdout);
+as_handle_impl!(Stderr);
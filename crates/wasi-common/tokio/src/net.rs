@@ -0,0 +1,116 @@
+use crate::block_on_dummy_executor;
+use crate::file::wasi_file_impl;
+#[cfg(not(windows))]
+use io_lifetimes::AsFd;
+use std::any::Any;
+use std::io;
+use wasi_common::{
+    file::{Advice, FdFlags, FileType, Filestat, RiFlags, RoFlags, SdFlags, SiFlags, WasiFile},
+    Error,
+};
+
+/// A socket which can be preopened into a `WasiCtx`, see
+/// `WasiCtxBuilder::preopened_socket`.
+pub enum Socket {
+    TcpListener(cap_std::net::TcpListener),
+    TcpStream(cap_std::net::TcpStream),
+    #[cfg(unix)]
+    UnixListener(cap_std::os::unix::net::UnixListener),
+    #[cfg(unix)]
+    UnixStream(cap_std::os::unix::net::UnixStream),
+}
+
+impl From<cap_std::net::TcpListener> for Socket {
+    fn from(listener: cap_std::net::TcpListener) -> Self {
+        Self::TcpListener(listener)
+    }
+}
+
+impl From<cap_std::net::TcpStream> for Socket {
+    fn from(stream: cap_std::net::TcpStream) -> Self {
+        Self::TcpStream(stream)
+    }
+}
+
+#[cfg(unix)]
+impl From<cap_std::os::unix::net::UnixListener> for Socket {
+    fn from(listener: cap_std::os::unix::net::UnixListener) -> Self {
+        Self::UnixListener(listener)
+    }
+}
+
+#[cfg(unix)]
+impl From<cap_std::os::unix::net::UnixStream> for Socket {
+    fn from(stream: cap_std::os::unix::net::UnixStream) -> Self {
+        Self::UnixStream(stream)
+    }
+}
+
+impl From<Socket> for Box<dyn WasiFile> {
+    fn from(socket: Socket) -> Self {
+        match socket {
+            Socket::TcpListener(listener) => Box::new(TcpListener::from_cap_std(listener)),
+            Socket::TcpStream(stream) => Box::new(TcpStream::from_cap_std(stream)),
+            #[cfg(unix)]
+            Socket::UnixListener(listener) => Box::new(UnixListener::from_cap_std(listener)),
+            #[cfg(unix)]
+            Socket::UnixStream(stream) => Box::new(UnixStream::from_cap_std(stream)),
+        }
+    }
+}
+
+pub struct TcpListener(wasi_cap_std_sync::net::TcpListener);
+
+impl TcpListener {
+    pub fn from_cap_std(listener: cap_std::net::TcpListener) -> Self {
+        TcpListener(wasi_cap_std_sync::net::TcpListener::from_cap_std(listener))
+    }
+}
+
+pub struct TcpStream(wasi_cap_std_sync::net::TcpStream);
+
+impl TcpStream {
+    pub fn from_cap_std(stream: cap_std::net::TcpStream) -> Self {
+        TcpStream(wasi_cap_std_sync::net::TcpStream::from_cap_std(stream))
+    }
+}
+
+#[cfg(unix)]
+pub struct UnixListener(wasi_cap_std_sync::net::UnixListener);
+
+#[cfg(unix)]
+impl UnixListener {
+    pub fn from_cap_std(listener: cap_std::os::unix::net::UnixListener) -> Self {
+        UnixListener(wasi_cap_std_sync::net::UnixListener::from_cap_std(listener))
+    }
+}
+
+#[cfg(unix)]
+pub struct UnixStream(wasi_cap_std_sync::net::UnixStream);
+
+#[cfg(unix)]
+impl UnixStream {
+    pub fn from_cap_std(stream: cap_std::os::unix::net::UnixStream) -> Self {
+        UnixStream(wasi_cap_std_sync::net::UnixStream::from_cap_std(stream))
+    }
+}
+
+// Accepted connections are wrapped in this crate's stream types so that they
+// can be polled with tokio as well.
+macro_rules! wasi_listen_impl {
+    ($ty:ty, $stream:ident) => {
+        wasi_file_impl!($ty, {
+            async fn sock_accept(&self, fdflags: FdFlags) -> Result<Box<dyn WasiFile>, Error> {
+                let stream = tokio::task::block_in_place(|| self.0.accept(fdflags))?;
+                Ok(Box::new($stream(stream)))
+            }
+        });
+    };
+}
+
+wasi_listen_impl!(TcpListener, TcpStream);
+wasi_file_impl!(TcpStream);
+#[cfg(unix)]
+wasi_listen_impl!(UnixListener, UnixStream);
+#[cfg(unix)]
+wasi_file_impl!(UnixStream);
@@ -11,7 +11,7 @@ use wasmparser::WasmFeatures;
 #[cfg(feature = "cache")]
 use wasmtime_cache::CacheConfig;
 use wasmtime_environ::{CompilerBuilder, Tunables};
-use wasmtime_jit::{JitDumpAgent, NullProfilerAgent, ProfilingAgent, VTuneAgent};
+use wasmtime_jit::{JitDumpAgent, NullProfilerAgent, PerfMapAgent, ProfilingAgent, VTuneAgent};
 use wasmtime_runtime::{InstanceAllocator, OnDemandInstanceAllocator, RuntimeMemoryCreator};
 
 #[cfg(feature = "pooling-allocator")]
@@ -104,6 +104,7 @@ pub struct Config {
     pub(crate) module_version: ModuleVersionStrategy,
     pub(crate) parallel_compilation: bool,
     pub(crate) paged_memory_initialization: bool,
+    pub(crate) memory_init_cow: bool,
 }
 
 impl Config {
@@ -129,6 +130,7 @@ impl Config {
             parallel_compilation: true,
             // Default to paged memory initialization when using uffd on linux
             paged_memory_initialization: cfg!(all(target_os = "linux", feature = "uffd")),
+            memory_init_cow: true,
         };
         #[cfg(compiler)]
         {
@@ -282,6 +284,33 @@ impl Config {
         self
     }
 
+    /// Configures whether a core dump of the store is captured whenever
+    /// WebAssembly traps.
+    ///
+    /// When enabled, a trap raised while executing wasm records the contents
+    /// of every memory, global and table in the store along with the wasm
+    /// frames on the stack at the time of the trap. The result is available
+    /// through [`Trap::coredump`](crate::Trap::coredump) and can be written out
+    /// in the [wasm coredump format] with
+    /// [`WasmCoreDump::serialize`](crate::WasmCoreDump::serialize) for
+    /// post-mortem debugging.
+    ///
+    /// To record the locals and operand stack of each frame, compiled code
+    /// stores them in the frame as it executes, which slows down all wasm
+    /// code. Capturing a core dump also copies all linear memory in the
+    /// store, so this can make traps significantly more expensive.
+    ///
+    /// Core dumps locate frames within their functions with the address map,
+    /// so this requires [`Config::generate_address_map`] to be enabled.
+    ///
+    /// By default this option is `false`.
+    ///
+    /// [wasm coredump format]: https://github.com/WebAssembly/tool-conventions/blob/main/Coredump.md
+    pub fn coredump_on_trap(&mut self, enable: bool) -> &mut Self {
+        self.tunables.coredump_on_trap = enable;
+        self
+    }
+
     /// Configures whether functions and loops will be interruptable via the
     /// [`Store::interrupt_handle`](crate::Store::interrupt_handle) method.
     ///
@@ -316,6 +345,86 @@ impl Config {
         self
     }
 
+    /// Enables epoch-based interruption.
+    ///
+    /// When executing code in async mode, we sometimes want to
+    /// implement a form of cooperative timeslicing: long-running Wasm
+    /// guest code should periodically yield to the executor
+    /// loop. This yielding could be implemented by using "fuel" (see
+    /// [`consume_fuel`](Config::consume_fuel)). However, fuel
+    /// instrumentation is somewhat expensive: it modifies the
+    /// compiled form of the Wasm code so that it maintains a precise
+    /// instruction count, frequently checking this count against the
+    /// remaining fuel. If one does not need this precise count or
+    /// deterministic interruptions, and only needs a periodic
+    /// interrupt of some form, then it would be better to have a more
+    /// lightweight mechanism.
+    ///
+    /// Epoch-based interruption is that mechanism. There is a global
+    /// "epoch", which is a counter that divides time into arbitrary
+    /// periods (or epochs). This counter lives on the
+    /// [`Engine`](crate::Engine) and can be incremented by calling
+    /// [`Engine::increment_epoch`](crate::Engine::increment_epoch).
+    /// Epoch-based instrumentation works by setting a "deadline
+    /// epoch". The compiled code knows the deadline, and at certain
+    /// points, checks the current epoch against that deadline. It
+    /// will yield if the deadline has been reached.
+    ///
+    /// The idea is that checking an infrequently-changing counter is
+    /// cheaper than counting and frequently storing a precise metric
+    /// (instructions executed) locally. The interruptions are not
+    /// deterministic, but if the embedder increments the epoch in a
+    /// periodic way (say, every regular timer tick by a thread or
+    /// signal handler), then we can ensure that all async code will
+    /// yield to the executor within a bounded time.
+    ///
+    /// The [`Store`](crate::Store) tracks the deadline, and controls
+    /// what happens when the deadline is reached during
+    /// execution. Two behaviors are possible:
+    ///
+    /// - Trap if code is executing when the epoch deadline is
+    ///   met. See
+    ///   [`Store::epoch_deadline_trap`](crate::Store::epoch_deadline_trap).
+    ///
+    /// - Yield to the executor loop, then resume when the future is
+    ///   next polled. See
+    ///   [`Store::epoch_deadline_async_yield_and_update`](crate::Store::epoch_deadline_async_yield_and_update).
+    ///
+    /// The first is the default; set the second for the timeslicing
+    /// behavior described above.
+    ///
+    /// This feature is available with or without async
+    /// support. However, without async support, only the trapping
+    /// behavior is available. In this mode, epoch-based interruption
+    /// can serve as a simple external-interruption mechanism.
+    ///
+    /// An initial deadline can be set before executing code by
+    /// calling
+    /// [`Store::set_epoch_deadline`](crate::Store::set_epoch_deadline).
+    ///
+    /// ## When to use fuel vs. epochs
+    ///
+    /// In general, epoch-based interruption results in faster
+    /// execution. This difference is sometimes significant: in some
+    /// measurements, up to 2-3x. This is because epoch-based
+    /// interruption does less work: it only watches for a global
+    /// rarely-changing counter to increment, rather than keeping a
+    /// local frequently-changing counter and comparing it to a
+    /// deadline.
+    ///
+    /// Fuel, in contrast, should be used when *deterministic*
+    /// yielding or trapping is needed. For example, if it is required
+    /// that the same function call with the same starting state will
+    /// always either complete or trap with an out-of-fuel error,
+    /// deterministically, then fuel with a fixed bound should be
+    /// used.
+    ///
+    /// By default this option is `false`.
+    pub fn epoch_interruption(&mut self, enable: bool) -> &mut Self {
+        self.tunables.epoch_interruption = enable;
+        self
+    }
+
     /// Configures the maximum amount of stack space available for
     /// executing WebAssembly code.
     ///
@@ -381,7 +490,9 @@ impl Config {
     ///
     /// This feature gates items such as shared memories and atomic
     /// instructions. Note that enabling the threads feature will
-    /// also enable the bulk memory feature.
+    /// also enable the bulk memory feature. Shared memories can be created
+    /// from the host with [`SharedMemory::new`](crate::SharedMemory::new) and
+    /// imported into instances across any number of stores and threads.
     ///
     /// This is `false` by default.
     ///
@@ -526,6 +637,59 @@ impl Config {
         self
     }
 
+    /// Configures whether the WebAssembly tail call [proposal] will be enabled
+    /// for compilation.
+    ///
+    /// This feature gates the `return_call` and `return_call_indirect`
+    /// instructions, which are guaranteed to not grow the stack. When enabled
+    /// all wasm functions are compiled with Cranelift's `tail` calling
+    /// convention, which is currently only implemented for x86_64 System V
+    /// targets; creating an [`Engine`](crate::Engine) for any other target
+    /// will fail. In that convention functions pop their own stack
+    /// arguments, so host functions and calls from the host with enough
+    /// parameters to need stack arguments go through an extra trampoline.
+    ///
+    /// This is `false` by default.
+    ///
+    /// [proposal]: https://github.com/webassembly/tail-call
+    pub fn wasm_tail_call(&mut self, enable: bool) -> &mut Self {
+        self.features.tail_call = enable;
+        self
+    }
+
+    /// Configures whether the WebAssembly exception handling [proposal] will
+    /// be enabled for compilation.
+    ///
+    /// This feature gates tags and the `try`, `catch`, `catch_all`,
+    /// `delegate`, `throw` and `rethrow` instructions. Exceptions which wasm
+    /// doesn't catch are returned from [`Func::call`](crate::Func::call) as a
+    /// [`Trap`](crate::Trap) whose [`Trap::exception`](crate::Trap::exception)
+    /// describes the exception, and host functions may throw exceptions into
+    /// wasm by returning a `Trap` created from an
+    /// [`Exception`](crate::Exception).
+    ///
+    /// Exceptions are raised by unwinding the native stack with the unwind
+    /// tables registered for compiled code, so calls made outside of `try`
+    /// blocks cost nothing extra. This is only supported on x86_64 Unix
+    /// platforms for now, and [`Engine::new`](crate::Engine::new) fails
+    /// elsewhere if this is enabled. Caught exceptions are tracked with the
+    /// same stack maps as `externref` values, so enabling this also enables
+    /// the [reference types proposal](Config::wasm_reference_types). Modules
+    /// defining tags aren't supported by the pooling instance allocator.
+    ///
+    /// This is `false` by default.
+    ///
+    /// [proposal]: https://github.com/webassembly/exception-handling
+    pub fn wasm_exceptions(&mut self, enable: bool) -> &mut Self {
+        self.features.exceptions = enable;
+
+        if enable {
+            self.wasm_reference_types(true);
+        }
+
+        self
+    }
+
     /// Configures which compilation strategy will be used for wasm modules.
     ///
     /// This method can be used to configure which compiler is used for wasm
@@ -554,6 +718,7 @@ impl Config {
         self.profiler = match profile {
             ProfilingStrategy::JitDump => Arc::new(JitDumpAgent::new()?) as Arc<dyn ProfilingAgent>,
             ProfilingStrategy::VTune => Arc::new(VTuneAgent::new()?) as Arc<dyn ProfilingAgent>,
+            ProfilingStrategy::PerfMap => Arc::new(PerfMapAgent::new()?) as Arc<dyn ProfilingAgent>,
             ProfilingStrategy::None => Arc::new(NullProfilerAgent),
         };
         Ok(self)
@@ -752,6 +917,33 @@ impl Config {
         self
     }
 
+    /// Configures whether copy-on-write memory-mapped data is used to
+    /// initialize a linear memory.
+    ///
+    /// This setting is `true` by default. When enabled, compilation organizes
+    /// the data segments of each linear memory into a single page-aligned
+    /// image that's stored in the compiled module. On Linux, with the
+    /// `memory-init-cow` feature of this crate, instantiation then maps that
+    /// image copy-on-write into the linear memory instead of copying data
+    /// segments into it, either directly from the file of a module loaded
+    /// with [`Module::deserialize_file`](crate::Module::deserialize_file) or
+    /// otherwise from a memfd. When the pooling allocator reuses a linear
+    /// memory for another instance of the same module, the memory is reset
+    /// to the image with `madvise` rather than being re-initialized.
+    ///
+    /// Modules that import a memory, have data segments that use a global
+    /// base, or have out-of-bounds data segments continue to be initialized
+    /// by copying each data segment individually, as do modules whose data
+    /// would produce very large and sparse images. Shared memories are always
+    /// initialized by copying.
+    ///
+    /// If [`Config::paged_memory_initialization`] is also enabled then paged
+    /// initialization takes precedence.
+    pub fn memory_init_cow(&mut self, enable: bool) -> &mut Self {
+        self.memory_init_cow = enable;
+        self
+    }
+
     /// Configures the maximum size, in bytes, where a linear memory is
     /// considered static, above which it'll be considered dynamic.
     ///
@@ -1128,6 +1320,7 @@ impl Clone for Config {
             module_version: self.module_version.clone(),
             parallel_compilation: self.parallel_compilation,
             paged_memory_initialization: self.paged_memory_initialization,
+            memory_init_cow: self.memory_init_cow,
         }
     }
 }
@@ -1160,7 +1353,8 @@ impl fmt::Debug for Config {
                 "guard_before_linear_memory",
                 &self.tunables.guard_before_linear_memory,
             )
-            .field("parallel_compilation", &self.parallel_compilation);
+            .field("parallel_compilation", &self.parallel_compilation)
+            .field("coredump_on_trap", &self.tunables.coredump_on_trap);
         #[cfg(compiler)]
         {
             f.field("compiler", &self.compiler);
@@ -1217,6 +1411,10 @@ pub enum ProfilingStrategy {
 
     /// Collect profiling info using the "ittapi", used with `VTune` on Linux.
     VTune,
+
+    /// Write function symbols to a "perf map" file (`/tmp/perf-<pid>.map`),
+    /// which `perf` on Linux reads to name JIT code without post-processing.
+    PerfMap,
 }
 
 /// Select how wasm backtrace detailed information is handled.
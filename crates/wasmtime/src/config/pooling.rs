@@ -249,6 +249,18 @@ pub enum PoolingAllocationStrategy {
     NextAvailable,
     /// Allocate from a random available instance.
     Random,
+    /// Try to allocate an instance slot that was last used by the same module,
+    /// falling back to a slot that hasn't been used yet and then to the next
+    /// available instance.
+    ///
+    /// Reusing a slot for the same module lets its resources be reused with
+    /// minimal work, for example keeping copy-on-write memory images mapped
+    /// between instantiations. This is a good fit for workloads which
+    /// instantiate a small set of modules many times.
+    ///
+    /// How often slots could be reused is reported by
+    /// [`Engine::pooling_affinity_stats`](crate::Engine::pooling_affinity_stats).
+    ReuseAffinity,
 }
 
 impl Default for PoolingAllocationStrategy {
@@ -256,6 +268,7 @@ impl Default for PoolingAllocationStrategy {
         match wasmtime_runtime::PoolingAllocationStrategy::default() {
             wasmtime_runtime::PoolingAllocationStrategy::NextAvailable => Self::NextAvailable,
             wasmtime_runtime::PoolingAllocationStrategy::Random => Self::Random,
+            wasmtime_runtime::PoolingAllocationStrategy::ReuseAffinity => Self::ReuseAffinity,
         }
     }
 }
@@ -268,6 +281,23 @@ impl Into<wasmtime_runtime::PoolingAllocationStrategy> for PoolingAllocationStra
         match self {
             Self::NextAvailable => wasmtime_runtime::PoolingAllocationStrategy::NextAvailable,
             Self::Random => wasmtime_runtime::PoolingAllocationStrategy::Random,
+            Self::ReuseAffinity => wasmtime_runtime::PoolingAllocationStrategy::ReuseAffinity,
         }
     }
 }
+
+/// Counters of how often the pooling instance allocator was able to allocate
+/// an instance into a slot which was last used by the same module.
+///
+/// These are only updated when using
+/// [`PoolingAllocationStrategy::ReuseAffinity`], and instances created by the
+/// host (for example to back host-defined functions) are not counted.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PoolingAffinityStats {
+    /// The number of instantiations which reused a slot last used by the same
+    /// module.
+    pub hits: u64,
+    /// The number of instantiations which couldn't reuse a slot last used by
+    /// the same module.
+    pub misses: u64,
+}
@@ -0,0 +1,612 @@
+//! Capturing the state of a store when WebAssembly traps.
+//!
+//! When [`Config::coredump_on_trap`](crate::Config::coredump_on_trap) is
+//! enabled a [`WasmCoreDump`] is attached to traps raised by wasm. Core dumps
+//! can be serialized in the [wasm coredump format], which is itself a wasm
+//! module whose memories, globals and tables are initialized to the state of
+//! the store at the time of the trap, along with custom sections describing
+//! the instances in the store and the wasm stack.
+//!
+//! [wasm coredump format]: https://github.com/WebAssembly/tool-conventions/blob/main/Coredump.md
+
+use crate::{
+    FrameInfo, Global, GlobalType, Memory, MemoryType, Mutability, StoreContextMut, Table,
+    TableType, Val, ValType,
+};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use wasmtime_environ::EntityIndex;
+use wasmtime_runtime::{CoreDumpFrameState, CoreDumpValue, Export, InstanceHandle};
+
+/// A snapshot of a store taken when WebAssembly trapped.
+///
+/// Core dumps are only captured when
+/// [`Config::coredump_on_trap`](crate::Config::coredump_on_trap) is enabled,
+/// and are retrieved with [`Trap::coredump`](crate::Trap::coredump).
+pub struct WasmCoreDump {
+    modules: Vec<Option<String>>,
+    instances: Vec<CoreDumpInstance>,
+    memories: Vec<CoreDumpMemory>,
+    globals: Vec<CoreDumpGlobal>,
+    tables: Vec<CoreDumpTable>,
+    frames: Vec<CoreDumpFrame>,
+}
+
+struct CoreDumpInstance {
+    module: u32,
+    memories: Vec<u32>,
+    globals: Vec<u32>,
+}
+
+/// The contents of a linear memory at the time of a trap.
+pub struct CoreDumpMemory {
+    ty: MemoryType,
+    data: Vec<u8>,
+}
+
+/// The value of a global at the time of a trap.
+#[derive(Debug)]
+pub struct CoreDumpGlobal {
+    ty: GlobalType,
+    value: Val,
+}
+
+/// The contents of a table at the time of a trap.
+#[derive(Debug)]
+pub struct CoreDumpTable {
+    ty: TableType,
+    elements: Vec<Val>,
+}
+
+/// A WebAssembly frame that was on the stack at the time of a trap.
+#[derive(Debug)]
+pub struct CoreDumpFrame {
+    instance: usize,
+    func_index: u32,
+    func_offset: Option<usize>,
+    locals: Vec<Option<Val>>,
+    stack: Vec<Option<Val>>,
+}
+
+impl WasmCoreDump {
+    /// Snapshots all state of the store in `store`.
+    ///
+    /// The `frames` are the wasm frames of the trap, youngest first, and
+    /// `frame_modules` is the module each of those frames belongs to. Frames
+    /// of modules not instantiated in this store are omitted. The
+    /// `frame_states` are the locals and operand stacks recorded by wasm
+    /// frames when they trapped, also youngest first.
+    pub(crate) fn new<T>(
+        store: &mut StoreContextMut<'_, T>,
+        frames: &[FrameInfo],
+        frame_modules: &[Arc<wasmtime_environ::Module>],
+        frame_states: Vec<CoreDumpFrameState>,
+    ) -> WasmCoreDump {
+        let handles: Vec<InstanceHandle> = store.0.all_instances().cloned().collect();
+        let mut modules: Vec<Arc<wasmtime_environ::Module>> = Vec::new();
+        let mut dump = WasmCoreDump {
+            modules: Vec::new(),
+            instances: Vec::new(),
+            memories: Vec::new(),
+            globals: Vec::new(),
+            tables: Vec::new(),
+            frames: Vec::new(),
+        };
+
+        // Items imported by one instance are defined by another, so they're
+        // identified by the address of their definition to avoid capturing
+        // them multiple times.
+        let mut memory_indices = HashMap::new();
+        let mut global_indices = HashMap::new();
+        let mut table_indices = HashMap::new();
+
+        for handle in handles.iter() {
+            let module = handle.module();
+            let module_index = match modules.iter().position(|m| Arc::ptr_eq(m, module)) {
+                Some(i) => i,
+                None => {
+                    modules.push(module.clone());
+                    dump.modules.push(module.name.clone());
+                    modules.len() - 1
+                }
+            };
+            let mut instance = CoreDumpInstance {
+                module: module_index as u32,
+                memories: Vec::new(),
+                globals: Vec::new(),
+            };
+
+            for (index, _) in module.memory_plans.iter() {
+                let export = match handle.lookup_by_declaration(&EntityIndex::Memory(index)) {
+                    Export::Memory(m) => m,
+                    _ => unreachable!(),
+                };
+                let next = dump.memories.len() as u32;
+                let index = *memory_indices
+                    .entry(export.definition as usize)
+                    .or_insert(next);
+                if index == next {
+                    let memory = unsafe { Memory::from_wasmtime_memory(export, store.0) };
+                    dump.memories.push(CoreDumpMemory {
+                        ty: memory.ty(&*store),
+                        data: memory.data(&*store).to_vec(),
+                    });
+                }
+                instance.memories.push(index);
+            }
+
+            for (index, _) in module.globals.iter() {
+                let export = match handle.lookup_by_declaration(&EntityIndex::Global(index)) {
+                    Export::Global(g) => g,
+                    _ => unreachable!(),
+                };
+                let next = dump.globals.len() as u32;
+                let index = *global_indices
+                    .entry(export.definition as usize)
+                    .or_insert(next);
+                if index == next {
+                    let global = unsafe { Global::from_wasmtime_global(export, store.0) };
+                    dump.globals.push(CoreDumpGlobal {
+                        ty: global.ty(&*store),
+                        value: global.get(&mut *store),
+                    });
+                }
+                instance.globals.push(index);
+            }
+
+            for (index, _) in module.table_plans.iter() {
+                let export = match handle.lookup_by_declaration(&EntityIndex::Table(index)) {
+                    Export::Table(t) => t,
+                    _ => unreachable!(),
+                };
+                let next = dump.tables.len();
+                if *table_indices
+                    .entry(export.definition as usize)
+                    .or_insert(next)
+                    == next
+                {
+                    let table = unsafe { Table::from_wasmtime_table(export, store.0) };
+                    let elements = (0..table.size(&*store))
+                        .map(|i| table.get(&mut *store, i).unwrap())
+                        .collect();
+                    dump.tables.push(CoreDumpTable {
+                        ty: table.ty(&*store),
+                        elements,
+                    });
+                }
+            }
+
+            dump.instances.push(instance);
+        }
+
+        // Every wasm frame records its state once it has been entered, so
+        // the recorded states line up with the frames of the backtrace from
+        // the oldest one. The only frame which may be missing its state is
+        // the youngest, if it trapped before getting to record it, such as
+        // on stack overflow.
+        let mut states = frame_states.into_iter().rev().peekable();
+        for (frame, module) in frames.iter().zip(frame_modules).rev() {
+            let state = states.next_if(|state| {
+                state.func_index == frame.func_index()
+                    && handles
+                        .iter()
+                        .any(|h| h.vmctx_ptr() == state.vmctx && Arc::ptr_eq(h.module(), module))
+            });
+            // Without a recorded state frames are only known by their module,
+            // so they're attributed to the first instance of that module in
+            // the store.
+            let instance = match &state {
+                Some(state) => handles.iter().position(|h| h.vmctx_ptr() == state.vmctx),
+                None => handles
+                    .iter()
+                    .position(|handle| Arc::ptr_eq(handle.module(), module)),
+            };
+            if let Some(instance) = instance {
+                let (locals, stack) = match state {
+                    Some(state) => (
+                        state.locals.iter().map(coredump_val).collect(),
+                        state.stack.iter().map(coredump_val).collect(),
+                    ),
+                    None => (Vec::new(), Vec::new()),
+                };
+                dump.frames.push(CoreDumpFrame {
+                    instance,
+                    func_index: frame.func_index(),
+                    func_offset: frame.func_offset(),
+                    locals,
+                    stack,
+                });
+            }
+        }
+        dump.frames.reverse();
+
+        dump
+    }
+
+    /// Returns the wasm frames that were on the stack when the trap happened,
+    /// youngest first.
+    pub fn frames(&self) -> &[CoreDumpFrame] {
+        &self.frames
+    }
+
+    /// Returns the contents of every linear memory in the store.
+    pub fn memories(&self) -> &[CoreDumpMemory] {
+        &self.memories
+    }
+
+    /// Returns the value of every global in the store.
+    pub fn globals(&self) -> &[CoreDumpGlobal] {
+        &self.globals
+    }
+
+    /// Returns the contents of every table in the store.
+    pub fn tables(&self) -> &[CoreDumpTable] {
+        &self.tables
+    }
+
+    /// Serializes this core dump in the [wasm coredump format], naming the
+    /// program that trapped `name`.
+    ///
+    /// The resulting wasm module contains one memory, global and table for
+    /// each of those captured in the store, initialized to their contents at
+    /// the time of the trap. References can't be represented in a module so
+    /// globals and table elements of reference types are `null` in the output.
+    ///
+    /// Locals and operand stack values which weren't recorded, see
+    /// [`CoreDumpFrame::locals`], are written as missing. Frames whose offset
+    /// within their function isn't known, see [`CoreDumpFrame::func_offset`],
+    /// can't be represented and are left out of the stack.
+    ///
+    /// [wasm coredump format]: https://github.com/WebAssembly/tool-conventions/blob/main/Coredump.md
+    pub fn serialize(&self, name: &str) -> Vec<u8> {
+        let mut module = b"\0asm\x01\0\0\0".to_vec();
+
+        let mut core = Vec::new();
+        core.push(0x00);
+        write_name(&mut core, name);
+        write_custom_section(&mut module, "core", &core);
+
+        let mut coremodules = Vec::new();
+        write_u32(&mut coremodules, self.modules.len() as u32);
+        for name in self.modules.iter() {
+            coremodules.push(0x00);
+            write_name(&mut coremodules, name.as_deref().unwrap_or("<unknown>"));
+        }
+        write_custom_section(&mut module, "coremodules", &coremodules);
+
+        let mut coreinstances = Vec::new();
+        write_u32(&mut coreinstances, self.instances.len() as u32);
+        for instance in self.instances.iter() {
+            coreinstances.push(0x00);
+            write_u32(&mut coreinstances, instance.module);
+            write_u32(&mut coreinstances, instance.memories.len() as u32);
+            for memory in instance.memories.iter() {
+                write_u32(&mut coreinstances, *memory);
+            }
+            write_u32(&mut coreinstances, instance.globals.len() as u32);
+            for global in instance.globals.iter() {
+                write_u32(&mut coreinstances, *global);
+            }
+        }
+        write_custom_section(&mut module, "coreinstances", &coreinstances);
+
+        let mut corestack = Vec::new();
+        corestack.push(0x00);
+        write_name(&mut corestack, "main");
+        let frames = self
+            .frames
+            .iter()
+            .filter_map(|frame| Some((frame, frame.func_offset?)))
+            .collect::<Vec<_>>();
+        write_u32(&mut corestack, frames.len() as u32);
+        for (frame, func_offset) in frames {
+            corestack.push(0x00);
+            write_u32(&mut corestack, frame.instance as u32);
+            write_u32(&mut corestack, frame.func_index);
+            write_u32(&mut corestack, func_offset as u32);
+            write_u32(&mut corestack, frame.locals.len() as u32);
+            for local in frame.locals.iter() {
+                write_coredump_val(&mut corestack, local);
+            }
+            write_u32(&mut corestack, frame.stack.len() as u32);
+            for val in frame.stack.iter() {
+                write_coredump_val(&mut corestack, val);
+            }
+        }
+        write_custom_section(&mut module, "corestack", &corestack);
+
+        if !self.tables.is_empty() {
+            let mut tables = Vec::new();
+            write_u32(&mut tables, self.tables.len() as u32);
+            for table in self.tables.iter() {
+                write_val_type(&mut tables, &table.ty.element());
+                let size = table.elements.len() as u64;
+                write_limits(&mut tables, 0, size, table.ty.maximum().map(u64::from));
+            }
+            write_section(&mut module, 4, &tables);
+        }
+
+        if !self.memories.is_empty() {
+            let mut memories = Vec::new();
+            write_u32(&mut memories, self.memories.len() as u32);
+            for memory in self.memories.iter() {
+                let mut flags = 0;
+                if memory.ty.is_shared() {
+                    flags |= 0x02;
+                }
+                if memory.ty.is_64() {
+                    flags |= 0x04;
+                }
+                let pages = memory.data.len() as u64 / u64::from(wasmtime_environ::WASM_PAGE_SIZE);
+                write_limits(&mut memories, flags, pages, memory.ty.maximum());
+            }
+            write_section(&mut module, 5, &memories);
+        }
+
+        if !self.globals.is_empty() {
+            let mut globals = Vec::new();
+            write_u32(&mut globals, self.globals.len() as u32);
+            for global in self.globals.iter() {
+                write_val_type(&mut globals, global.ty.content());
+                globals.push(match global.ty.mutability() {
+                    Mutability::Const => 0x00,
+                    Mutability::Var => 0x01,
+                });
+                write_const(&mut globals, &global.value);
+            }
+            write_section(&mut module, 6, &globals);
+        }
+
+        if !self.memories.is_empty() {
+            let mut data = Vec::new();
+            write_u32(&mut data, self.memories.len() as u32);
+            for (i, memory) in self.memories.iter().enumerate() {
+                data.push(0x02);
+                write_u32(&mut data, i as u32);
+                if memory.ty.is_64() {
+                    write_const(&mut data, &Val::I64(0));
+                } else {
+                    write_const(&mut data, &Val::I32(0));
+                }
+                write_u32(&mut data, memory.data.len() as u32);
+                data.extend_from_slice(&memory.data);
+            }
+            write_section(&mut module, 11, &data);
+        }
+
+        module
+    }
+}
+
+impl fmt::Debug for WasmCoreDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WasmCoreDump")
+            .field("memories", &self.memories)
+            .field("globals", &self.globals)
+            .field("tables", &self.tables)
+            .field("frames", &self.frames)
+            .finish()
+    }
+}
+
+impl CoreDumpMemory {
+    /// Returns the type of this memory.
+    pub fn ty(&self) -> &MemoryType {
+        &self.ty
+    }
+
+    /// Returns the contents of this memory.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl fmt::Debug for CoreDumpMemory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CoreDumpMemory")
+            .field("ty", &self.ty)
+            .field("len", &self.data.len())
+            .finish()
+    }
+}
+
+impl CoreDumpGlobal {
+    /// Returns the type of this global.
+    pub fn ty(&self) -> &GlobalType {
+        &self.ty
+    }
+
+    /// Returns the value of this global.
+    pub fn value(&self) -> &Val {
+        &self.value
+    }
+}
+
+impl CoreDumpTable {
+    /// Returns the type of this table.
+    pub fn ty(&self) -> &TableType {
+        &self.ty
+    }
+
+    /// Returns the elements of this table.
+    pub fn elements(&self) -> &[Val] {
+        &self.elements
+    }
+}
+
+impl CoreDumpFrame {
+    /// Returns the index of the instance this frame is executing in.
+    ///
+    /// Instances are numbered in the order they were created in the store.
+    pub fn instance(&self) -> usize {
+        self.instance
+    }
+
+    /// Returns the WebAssembly function index for this frame.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the offset from the start of this frame's function to the
+    /// instruction that was executing, if known.
+    ///
+    /// This is only `None` if the frame trapped outside of the code of any
+    /// instruction, such as a stack overflow on entry to the function.
+    pub fn func_offset(&self) -> Option<usize> {
+        self.func_offset
+    }
+
+    /// Returns the values of this frame's locals at the time of the trap.
+    ///
+    /// Values which weren't recorded are `None`: this is the case for locals
+    /// of type `v128` and `externref`, and `funcref` locals are recorded as
+    /// their raw `i64` address. The list is empty if the frame trapped before
+    /// recording its state, such as on stack overflow.
+    pub fn locals(&self) -> &[Option<Val>] {
+        &self.locals
+    }
+
+    /// Returns this frame's operand stack at the time of the trap, bottom
+    /// first.
+    ///
+    /// The same values as for [`CoreDumpFrame::locals`] aren't recorded. The
+    /// stack is recorded before each instruction which may trap or call
+    /// another function, and values pushed by the trapping instruction itself
+    /// aren't part of it.
+    pub fn stack(&self) -> &[Option<Val>] {
+        &self.stack
+    }
+}
+
+fn coredump_val(val: &CoreDumpValue) -> Option<Val> {
+    match *val {
+        CoreDumpValue::I32(i) => Some(Val::I32(i)),
+        CoreDumpValue::I64(i) => Some(Val::I64(i)),
+        CoreDumpValue::F32(bits) => Some(Val::F32(bits)),
+        CoreDumpValue::F64(bits) => Some(Val::F64(bits)),
+        CoreDumpValue::Missing => None,
+    }
+}
+
+fn write_u32(dst: &mut Vec<u8>, val: u32) {
+    write_u64(dst, u64::from(val));
+}
+
+fn write_u64(dst: &mut Vec<u8>, mut val: u64) {
+    loop {
+        let byte = (val & 0x7f) as u8;
+        val >>= 7;
+        if val == 0 {
+            dst.push(byte);
+            return;
+        }
+        dst.push(byte | 0x80);
+    }
+}
+
+fn write_i64(dst: &mut Vec<u8>, mut val: i64) {
+    loop {
+        let byte = (val & 0x7f) as u8;
+        val >>= 7;
+        let done = (val == 0 && byte & 0x40 == 0) || (val == -1 && byte & 0x40 != 0);
+        if done {
+            dst.push(byte);
+            return;
+        }
+        dst.push(byte | 0x80);
+    }
+}
+
+fn write_name(dst: &mut Vec<u8>, name: &str) {
+    write_u32(dst, name.len() as u32);
+    dst.extend_from_slice(name.as_bytes());
+}
+
+fn write_section(dst: &mut Vec<u8>, id: u8, contents: &[u8]) {
+    dst.push(id);
+    write_u32(dst, contents.len() as u32);
+    dst.extend_from_slice(contents);
+}
+
+fn write_custom_section(dst: &mut Vec<u8>, name: &str, contents: &[u8]) {
+    let mut section = Vec::new();
+    write_name(&mut section, name);
+    section.extend_from_slice(contents);
+    write_section(dst, 0, &section);
+}
+
+fn write_limits(dst: &mut Vec<u8>, mut flags: u8, minimum: u64, maximum: Option<u64>) {
+    if maximum.is_some() {
+        flags |= 0x01;
+    }
+    dst.push(flags);
+    write_u64(dst, minimum);
+    if let Some(maximum) = maximum {
+        write_u64(dst, maximum);
+    }
+}
+
+fn write_val_type(dst: &mut Vec<u8>, ty: &ValType) {
+    dst.push(match ty {
+        ValType::I32 => 0x7f,
+        ValType::I64 => 0x7e,
+        ValType::F32 => 0x7d,
+        ValType::F64 => 0x7c,
+        ValType::V128 => 0x7b,
+        ValType::FuncRef => 0x70,
+        ValType::ExternRef => 0x6f,
+    });
+}
+
+/// Writes a local or operand stack value of the `corestack` section.
+fn write_coredump_val(dst: &mut Vec<u8>, val: &Option<Val>) {
+    match val {
+        Some(Val::I32(i)) => {
+            dst.push(0x7f);
+            write_i64(dst, i64::from(*i));
+        }
+        Some(Val::I64(i)) => {
+            dst.push(0x7e);
+            write_i64(dst, *i);
+        }
+        Some(Val::F32(bits)) => {
+            dst.push(0x7d);
+            dst.extend_from_slice(&bits.to_le_bytes());
+        }
+        Some(Val::F64(bits)) => {
+            dst.push(0x7c);
+            dst.extend_from_slice(&bits.to_le_bytes());
+        }
+        _ => dst.push(0x01),
+    }
+}
+
+/// Writes a constant expression producing `val`.
+fn write_const(dst: &mut Vec<u8>, val: &Val) {
+    match val {
+        Val::I32(i) => {
+            dst.push(0x41);
+            write_i64(dst, i64::from(*i));
+        }
+        Val::I64(i) => {
+            dst.push(0x42);
+            write_i64(dst, *i);
+        }
+        Val::F32(bits) => {
+            dst.push(0x43);
+            dst.extend_from_slice(&bits.to_le_bytes());
+        }
+        Val::F64(bits) => {
+            dst.push(0x44);
+            dst.extend_from_slice(&bits.to_le_bytes());
+        }
+        Val::V128(bits) => {
+            dst.extend_from_slice(&[0xfd, 0x0c]);
+            dst.extend_from_slice(&bits.to_le_bytes());
+        }
+        Val::FuncRef(_) => dst.extend_from_slice(&[0xd0, 0x70]),
+        Val::ExternRef(_) => dst.extend_from_slice(&[0xd0, 0x6f]),
+    }
+    dst.push(0x0b);
+}
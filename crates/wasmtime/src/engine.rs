@@ -1,8 +1,9 @@
 use crate::signatures::SignatureRegistry;
 use crate::{Config, Trap};
-use anyhow::Result;
+use anyhow::{bail, Result};
 #[cfg(feature = "parallel-compilation")]
 use rayon::prelude::*;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 #[cfg(feature = "cache")]
 use wasmtime_cache::CacheConfig;
@@ -41,6 +42,7 @@ struct EngineInner {
     compiler: Box<dyn wasmtime_environ::Compiler>,
     allocator: Box<dyn InstanceAllocator>,
     signatures: SignatureRegistry,
+    epoch: AtomicU64,
 }
 
 impl Engine {
@@ -58,13 +60,37 @@ impl Engine {
         let allocator = config.build_allocator()?;
         allocator.adjust_tunables(&mut config.tunables);
 
+        // Tail calls change the calling convention that the compiler uses for
+        // all wasm functions, so it has to know about them up front.
+        #[cfg(compiler)]
+        if config.features.tail_call {
+            config.compiler.set("wasmtime_tail_calls", "true")?;
+        }
+        // Core dumps record where each frame is in its function, which is only
+        // known with the address map.
+        if config.tunables.coredump_on_trap && !config.tunables.generate_address_map {
+            bail!("core dumps on trap require the address map to be generated");
+        }
+        // Exceptions are unwound to their handlers with the system unwinder,
+        // which is only hooked up for x86_64 Unix platforms.
+        if config.features.exceptions && !cfg!(all(target_arch = "x86_64", unix)) {
+            bail!("the exception handling proposal is not supported on this platform");
+        }
+        // Exceptions also change how calls are compiled, since calls in
+        // `try` blocks are followed by a check for a pending exception.
+        #[cfg(compiler)]
+        if config.features.exceptions {
+            config.compiler.set("wasmtime_exceptions", "true")?;
+        }
+
         Ok(Engine {
             inner: Arc::new(EngineInner {
                 #[cfg(compiler)]
-                compiler: config.compiler.build(),
+                compiler: config.compiler.build()?,
                 config,
                 allocator,
                 signatures: registry,
+                epoch: AtomicU64::new(0),
             }),
         })
     }
@@ -105,6 +131,21 @@ impl Engine {
         self.inner.allocator.as_ref()
     }
 
+    /// Returns how often instances were allocated into a slot last used by the
+    /// same module, if this engine uses the pooling instance allocator.
+    ///
+    /// See [`PoolingAllocationStrategy::ReuseAffinity`](crate::PoolingAllocationStrategy::ReuseAffinity)
+    /// for more information.
+    #[cfg(feature = "pooling-allocator")]
+    #[cfg_attr(nightlydoc, doc(cfg(feature = "pooling-allocator")))]
+    pub fn pooling_affinity_stats(&self) -> Option<crate::PoolingAffinityStats> {
+        let stats = self.allocator().affinity_stats()?;
+        Some(crate::PoolingAffinityStats {
+            hits: stats.hits,
+            misses: stats.misses,
+        })
+    }
+
     #[cfg(feature = "cache")]
     pub(crate) fn cache_config(&self) -> &CacheConfig {
         &self.config().cache_config
@@ -119,6 +160,37 @@ impl Engine {
         &self.inner.signatures
     }
 
+    pub(crate) fn epoch_counter(&self) -> &AtomicU64 {
+        &self.inner.epoch
+    }
+
+    pub(crate) fn current_epoch(&self) -> u64 {
+        self.epoch_counter().load(Ordering::Relaxed)
+    }
+
+    /// Increments the epoch.
+    ///
+    /// When using epoch-based interruption, currently-executing Wasm
+    /// code within this engine will trap or yield "soon" when the
+    /// epoch deadline is reached or exceeded. (The configuration, and
+    /// the deadline, are set on the `Store`.) The intent of the
+    /// design is for this method to be called by the embedder at some
+    /// regular cadence, for example by a thread that wakes up at some
+    /// interval, or by a signal handler.
+    ///
+    /// See [`Config::epoch_interruption`](crate::Config::epoch_interruption)
+    /// for an introduction to epoch-based interruption and pointers
+    /// to the other relevant methods.
+    ///
+    /// ## Signal Safety
+    ///
+    /// This method is signal-safe: it does not make any syscalls, and
+    /// performs only an atomic increment to the epoch value in
+    /// memory.
+    pub fn increment_epoch(&self) {
+        self.inner.epoch.fetch_add(1, Ordering::Relaxed);
+    }
+
     /// Ahead-of-time (AOT) compiles a WebAssembly module.
     ///
     /// The `bytes` provided must be in one of two formats:
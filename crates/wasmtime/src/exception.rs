@@ -0,0 +1,209 @@
+use crate::store::{StoreOpaque, Stored};
+use crate::trampoline::generate_tag_export;
+use crate::{AsContext, AsContextMut, StoreContextMut, TagType, Val};
+use anyhow::{bail, Result};
+use std::fmt;
+use wasmtime_runtime::{VMException, VMExternRef, VMSharedSignatureIndex};
+
+/// A WebAssembly exception tag.
+///
+/// Tags are defined by wasm modules with the exception handling proposal, and
+/// every exception is thrown with a tag which determines the types of the
+/// values it carries. `catch` clauses in wasm select the exceptions they
+/// handle by tag.
+///
+/// A [`Tag`] "belongs" to the store that it was originally created within
+/// (either via [`Tag::new`] or via instantiating a [`Module`](crate::Module)).
+/// Operations on a [`Tag`] only work with the store it belongs to, and if
+/// another store is passed in by accident then methods will panic.
+#[derive(Copy, Clone, Debug)]
+#[repr(transparent)] // here for the C API
+pub struct Tag(Stored<wasmtime_runtime::ExportTag>);
+
+impl Tag {
+    /// Creates a new WebAssembly tag with the type `ty`.
+    ///
+    /// The returned tag is distinct from every other tag, including those with
+    /// the same type, and can be imported by wasm modules to throw or catch
+    /// exceptions shared with the host.
+    pub fn new(mut store: impl AsContextMut, ty: TagType) -> Result<Tag> {
+        let store = store.as_context_mut().0;
+        unsafe {
+            let export = generate_tag_export(store, &ty)?;
+            Ok(Tag::from_wasmtime_tag(export, store))
+        }
+    }
+
+    /// Returns the type of this tag.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this tag.
+    pub fn ty(&self, store: impl AsContext) -> TagType {
+        self._ty(store.as_context().0)
+    }
+
+    fn _ty(&self, store: &StoreOpaque) -> TagType {
+        let ty = store
+            .engine()
+            .signatures()
+            .lookup_type(self.sig_index(store))
+            .expect("signature should be registered");
+        TagType::from_wasm_func_type(ty)
+    }
+
+    /// Returns whether `self` and `other` are the same tag, for example to
+    /// find out which tag an [`Exception`] was thrown with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own both tags.
+    pub fn same(&self, store: impl AsContext, other: &Tag) -> bool {
+        let store = store.as_context().0;
+        store[self.0].definition == store[other.0].definition
+    }
+
+    pub(crate) unsafe fn from_wasmtime_tag(
+        wasmtime_export: wasmtime_runtime::ExportTag,
+        store: &mut StoreOpaque,
+    ) -> Tag {
+        Tag(store.store_data_mut().insert(wasmtime_export))
+    }
+
+    pub(crate) fn sig_index(&self, store: &StoreOpaque) -> VMSharedSignatureIndex {
+        unsafe { (*store[self.0].definition).type_index }
+    }
+
+    pub(crate) fn comes_from_same_store(&self, store: &StoreOpaque) -> bool {
+        store.store_data().contains(self.0)
+    }
+
+    pub(crate) fn vmimport(&self, store: &StoreOpaque) -> wasmtime_runtime::VMTagImport {
+        wasmtime_runtime::VMTagImport {
+            from: store[self.0].definition,
+        }
+    }
+}
+
+/// A WebAssembly exception, which is the error of a [`Trap`](crate::Trap)
+/// returned when wasm throws an exception that it doesn't catch.
+///
+/// Host functions can also throw exceptions into wasm by returning a `Trap`
+/// created from an [`Exception`], which wasm may then catch. This requires the
+/// exception handling proposal to be enabled with
+/// [`Config::wasm_exceptions`](crate::Config::wasm_exceptions), otherwise the
+/// exception is just an ordinary trap.
+///
+/// # Examples
+///
+/// ```
+/// # use wasmtime::*;
+/// # fn main() -> anyhow::Result<()> {
+/// let mut config = Config::new();
+/// config.wasm_exceptions(true);
+/// let engine = Engine::new(&config)?;
+/// let mut store = Store::new(&engine, ());
+/// let module = Module::new(
+///     &engine,
+///     r#"
+///         (module
+///             (tag (export "error") (param i32))
+///             (func (export "run") (param i32)
+///                 local.get 0
+///                 throw 0))
+///     "#,
+/// )?;
+/// let instance = Instance::new(&mut store, &module, &[])?;
+/// let error = instance.get_tag(&mut store, "error").unwrap();
+/// let run = instance.get_typed_func::<i32, (), _>(&mut store, "run")?;
+///
+/// let trap = run.call(&mut store, 42).unwrap_err();
+/// let exception = trap.exception().unwrap();
+/// assert!(exception.tag().same(&store, &error));
+/// assert_eq!(exception.values()[0].unwrap_i32(), 42);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Exception {
+    tag: Tag,
+    values: Vec<Val>,
+}
+
+impl Exception {
+    /// Creates a new exception with the given `tag`, carrying `values`.
+    ///
+    /// The values are checked against the type of `tag` when the exception is
+    /// thrown into wasm.
+    pub fn new(tag: Tag, values: Vec<Val>) -> Exception {
+        Exception { tag, values }
+    }
+
+    /// Returns the tag this exception was thrown with.
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Returns the values this exception carries.
+    pub fn values(&self) -> &[Val] {
+        &self.values
+    }
+
+    /// Converts an exception thrown by wasm into an `Exception`.
+    pub(crate) unsafe fn from_vm<T>(
+        exception: &VMException,
+        store: &mut StoreContextMut<'_, T>,
+    ) -> Exception {
+        let tag = Tag::from_wasmtime_tag(
+            wasmtime_runtime::ExportTag {
+                definition: exception.tag(),
+            },
+            store.0,
+        );
+        let ty = tag._ty(store.0);
+        let values = ty
+            .params()
+            .zip(exception.values())
+            .map(|(ty, raw)| Val::from_raw(&mut *store, *raw, ty))
+            .collect();
+        Exception { tag, values }
+    }
+
+    /// Converts this exception into one which can be thrown into wasm.
+    pub(crate) fn into_vm<T>(self, store: &mut StoreContextMut<'_, T>) -> Result<VMExternRef> {
+        if !self.tag.comes_from_same_store(store.0) {
+            bail!("cross-`Store` exceptions are not supported");
+        }
+        let ty = self.tag._ty(store.0);
+        if ty.params().len() != self.values.len()
+            || ty.params().zip(&self.values).any(|(ty, v)| v.ty() != ty)
+        {
+            bail!("exception values do not match the type of its tag");
+        }
+        let mut values = Vec::with_capacity(self.values.len());
+        let mut refs = Vec::new();
+        for value in &self.values {
+            if !value.comes_from_same_store(store.0) {
+                bail!("cross-`Store` values are not supported");
+            }
+            if let Val::ExternRef(Some(e)) = value {
+                refs.push(e.inner.clone());
+            }
+            values.push(unsafe { value.to_raw(&mut *store) });
+        }
+        let definition = store.0[self.tag.0].definition;
+        Ok(VMExternRef::new(VMException::new(
+            definition,
+            values.into_boxed_slice(),
+            refs.into_boxed_slice(),
+        )))
+    }
+}
+
+impl fmt::Display for Exception {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "uncaught wasm exception")
+    }
+}
+
+impl std::error::Error for Exception {}
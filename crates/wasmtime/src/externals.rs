@@ -2,7 +2,7 @@ use crate::store::{StoreData, StoreOpaque, Stored};
 use crate::trampoline::{generate_global_export, generate_table_export};
 use crate::{
     AsContext, AsContextMut, ExternRef, ExternType, Func, GlobalType, Instance, Memory, Module,
-    Mutability, TableType, Trap, Val, ValType,
+    Mutability, SharedMemory, TableType, Tag, Trap, Val, ValType,
 };
 use anyhow::{anyhow, bail, Result};
 use std::mem;
@@ -29,10 +29,15 @@ pub enum Extern {
     Table(Table),
     /// A WebAssembly linear memory.
     Memory(Memory),
+    /// A WebAssembly shared memory; these are handled separately from
+    /// [`Memory`].
+    SharedMemory(SharedMemory),
     /// A WebAssembly instance.
     Instance(Instance),
     /// A WebAssembly module.
     Module(Module),
+    /// A WebAssembly exception tag.
+    Tag(Tag),
 }
 
 impl Extern {
@@ -76,6 +81,17 @@ impl Extern {
         }
     }
 
+    /// Returns the underlying `SharedMemory`, if this external is a shared
+    /// memory.
+    ///
+    /// Returns `None` if this is not a shared memory.
+    pub fn into_shared_memory(self) -> Option<SharedMemory> {
+        match self {
+            Extern::SharedMemory(memory) => Some(memory),
+            _ => None,
+        }
+    }
+
     /// Returns the underlying `Instance`, if this external is a instance.
     ///
     /// Returns `None` if this is not a instance.
@@ -96,6 +112,16 @@ impl Extern {
         }
     }
 
+    /// Returns the underlying `Tag`, if this external is a tag.
+    ///
+    /// Returns `None` if this is not a tag.
+    pub fn into_tag(self) -> Option<Tag> {
+        match self {
+            Extern::Tag(tag) => Some(tag),
+            _ => None,
+        }
+    }
+
     /// Returns the type associated with this `Extern`.
     ///
     /// The `store` argument provided must own this `Extern` and is used to look
@@ -109,10 +135,12 @@ impl Extern {
         match self {
             Extern::Func(ft) => ExternType::Func(ft.ty(store)),
             Extern::Memory(ft) => ExternType::Memory(ft.ty(store)),
+            Extern::SharedMemory(ft) => ExternType::Memory(ft.ty()),
             Extern::Table(tt) => ExternType::Table(tt.ty(store)),
             Extern::Global(gt) => ExternType::Global(gt.ty(store)),
             Extern::Instance(i) => ExternType::Instance(i.ty(store)),
             Extern::Module(m) => ExternType::Module(m.ty()),
+            Extern::Tag(t) => ExternType::Tag(t.ty(store)),
         }
     }
 
@@ -125,7 +153,11 @@ impl Extern {
                 Extern::Func(Func::from_wasmtime_function(f, store))
             }
             wasmtime_runtime::Export::Memory(m) => {
-                Extern::Memory(Memory::from_wasmtime_memory(m, store))
+                if m.memory.memory.shared {
+                    Extern::SharedMemory(SharedMemory::from_wasmtime_memory(m, store))
+                } else {
+                    Extern::Memory(Memory::from_wasmtime_memory(m, store))
+                }
             }
             wasmtime_runtime::Export::Global(g) => {
                 Extern::Global(Global::from_wasmtime_global(g, store))
@@ -133,6 +165,7 @@ impl Extern {
             wasmtime_runtime::Export::Table(t) => {
                 Extern::Table(Table::from_wasmtime_table(t, store))
             }
+            wasmtime_runtime::Export::Tag(t) => Extern::Tag(Tag::from_wasmtime_tag(t, store)),
         }
     }
 
@@ -143,9 +176,10 @@ impl Extern {
             Extern::Memory(m) => m.comes_from_same_store(store),
             Extern::Table(t) => store.store_data().contains(t.0),
             Extern::Instance(i) => i.comes_from_same_store(store),
-            // Modules don't live in stores right now, so they're compatible
-            // with all stores.
-            Extern::Module(_) => true,
+            Extern::Tag(t) => t.comes_from_same_store(store),
+            // Shared memories and modules don't live in stores, so they're
+            // compatible with all stores.
+            Extern::SharedMemory(_) | Extern::Module(_) => true,
         }
     }
 
@@ -154,9 +188,11 @@ impl Extern {
             Extern::Func(_) => "function",
             Extern::Table(_) => "table",
             Extern::Memory(_) => "memory",
+            Extern::SharedMemory(_) => "shared memory",
             Extern::Global(_) => "global",
             Extern::Instance(_) => "instance",
             Extern::Module(_) => "module",
+            Extern::Tag(_) => "tag",
         }
     }
 }
@@ -179,12 +215,24 @@ impl From<Memory> for Extern {
     }
 }
 
+impl From<SharedMemory> for Extern {
+    fn from(r: SharedMemory) -> Self {
+        Extern::SharedMemory(r)
+    }
+}
+
 impl From<Table> for Extern {
     fn from(r: Table) -> Self {
         Extern::Table(r)
     }
 }
 
+impl From<Tag> for Extern {
+    fn from(r: Tag) -> Self {
+        Extern::Tag(r)
+    }
+}
+
 impl From<Instance> for Extern {
     fn from(r: Instance) -> Self {
         Extern::Instance(r)
@@ -774,6 +822,12 @@ impl<'instance> Export<'instance> {
         self.definition.into_memory()
     }
 
+    /// Consume this `Export` and return the contained `SharedMemory`, if it's
+    /// a shared memory, or `None` otherwise.
+    pub fn into_shared_memory(self) -> Option<SharedMemory> {
+        self.definition.into_shared_memory()
+    }
+
     /// Consume this `Export` and return the contained `Global`, if it's a global,
     /// or `None` otherwise.
     pub fn into_global(self) -> Option<Global> {
@@ -791,4 +845,10 @@ impl<'instance> Export<'instance> {
     pub fn into_module(self) -> Option<Module> {
         self.definition.into_module()
     }
+
+    /// Consume this `Export` and return the contained `Tag`, if it's a tag, or
+    /// `None` otherwise.
+    pub fn into_tag(self) -> Option<Tag> {
+        self.definition.into_tag()
+    }
 }
@@ -1,7 +1,7 @@
 use crate::store::{StoreData, StoreOpaque, Stored};
 use crate::{
-    AsContext, AsContextMut, CallHook, Engine, Extern, FuncType, Instance, InterruptHandle,
-    StoreContext, StoreContextMut, Trap, Val, ValRaw, ValType,
+    AsContext, AsContextMut, CallHook, Engine, Exception, Extern, FuncType, Instance,
+    InterruptHandle, StoreContext, StoreContextMut, Trap, Val, ValRaw, ValType,
 };
 use anyhow::{bail, Context as _, Result};
 use std::future::Future;
@@ -14,8 +14,8 @@ use std::sync::Arc;
 use wasmtime_environ::{EntityIndex, FuncIndex};
 use wasmtime_runtime::{
     raise_user_trap, ExportFunction, InstanceAllocator, InstanceHandle, OnDemandInstanceAllocator,
-    VMCallerCheckedAnyfunc, VMContext, VMFunctionBody, VMFunctionImport, VMSharedSignatureIndex,
-    VMTrampoline,
+    VMCallerCheckedAnyfunc, VMContext, VMException, VMFunctionBody, VMFunctionImport,
+    VMSharedSignatureIndex, VMTrampoline,
 };
 
 /// A WebAssembly function which can be called.
@@ -1172,16 +1172,105 @@ pub(crate) fn invoke_wasm_and_catch_traps<T>(
             exit_wasm(store, exit);
             return Err(trap);
         }
+        // Frames which trap don't unlink their core dump records, so restore
+        // the list as it was before calling in.
+        let coredump_frames = *(*store.0.vminterrupts()).coredump_frames.get();
         let result = wasmtime_runtime::catch_traps(
             store.0.vminterrupts(),
             store.0.signal_handler(),
             store.0.default_callee(),
             closure,
         );
+        *(*store.0.vminterrupts()).coredump_frames.get() = coredump_frames;
         exit_wasm(store, exit);
         store.0.call_hook(CallHook::ReturningFromWasm)?;
-        result.map_err(Trap::from_runtime_box)
+        let result = result.map_err(|trap| match *trap {
+            wasmtime_runtime::Trap::Exception(exception) => {
+                let exception = exception
+                    .downcast_ref::<VMException>()
+                    .expect("pending exceptions should be `VMException`s");
+                let backtrace = exception.backtrace().clone();
+                Trap::from_exception(Exception::from_vm(exception, store), backtrace)
+            }
+            trap => Trap::from_runtime(trap),
+        });
+        if store.0.engine().config().tunables.coredump_on_trap {
+            return result.map_err(|trap| {
+                let frames = (*store.0.vminterrupts()).take_coredump_frames();
+                trap.with_coredump(store, frames)
+            });
+        }
+        result
+    }
+}
+
+/// Makes `trap` the pending exception of `store` if it's an [`Exception`] and
+/// the exception handling proposal is enabled, so that it's thrown into the
+/// wasm which called the host rather than unwinding it as a trap. The caller
+/// then raises it with `raise_pending_exception` once it's left the store.
+///
+/// Returns the trap back if it should be raised as usual.
+fn throw_host_exception<T>(store: &mut StoreContextMut<'_, T>, trap: Trap) -> Result<(), Trap> {
+    if !store.engine().config().features.exceptions {
+        return Err(trap);
+    }
+    let exception = match trap.exception() {
+        Some(exception) => exception.clone(),
+        None => return Err(trap),
+    };
+    let exception = exception.into_vm(store)?;
+    unsafe {
+        (*store.0.vminterrupts()).set_pending_exception(exception);
+    }
+    Ok(())
+}
+
+/// Returns whether `throw_host_exception` made an exception pending in the
+/// store of `caller_vmctx`.
+pub(crate) unsafe fn exception_pending(caller_vmctx: *mut VMContext) -> bool {
+    let store = InstanceHandle::from_vmctx(caller_vmctx).store();
+    !(*(*(*store).vminterrupts()).pending_exception.get()).is_null()
+}
+
+/// Raises the exception pending in the store of `caller_vmctx` by unwinding to
+/// the wasm which handles it.
+///
+/// # Safety
+///
+/// As with traps, nothing on the stack may need a destructor.
+pub(crate) unsafe fn raise_pending_exception(caller_vmctx: *mut VMContext) -> ! {
+    let store = InstanceHandle::from_vmctx(caller_vmctx).store();
+    let (_, module_info_lookup) = (*store).externref_activations_table();
+    wasmtime_runtime::raise_exception(module_info_lookup)
+}
+
+/// Returns whether wasm functions of type `ty` receive some of their arguments
+/// on the stack when `engine` has the tail call proposal enabled.
+///
+/// Wasm functions then use Cranelift's `tail` calling convention, in which the
+/// callee pops its own stack arguments, so native code can neither call them
+/// nor be called by them directly. Calls in both directions go through
+/// trampolines compiled by Cranelift instead. This mirrors how the convention
+/// assigns arguments to registers on x86_64, the only target supporting it.
+pub(crate) fn tail_call_needs_stack_args(engine: &Engine, ty: &FuncType) -> bool {
+    if !engine.config().features.tail_call {
+        return false;
+    }
+    // The callee and caller `VMContext`s come first.
+    let mut int_args = 2;
+    let mut float_args = 0;
+    for param in ty.params() {
+        match param {
+            ValType::F32 | ValType::F64 | ValType::V128 => float_args += 1,
+            ValType::I32 | ValType::I64 | ValType::FuncRef | ValType::ExternRef => int_args += 1,
+        }
+    }
+    // Only the first result is returned in a register, and the address of the
+    // area for the others is passed as the last argument.
+    if ty.results().len() > 1 {
+        int_args += 1;
     }
+    int_args > 6 || float_args > 8
 }
 
 /// This function is called to register state within `Store` whenever
@@ -1649,9 +1738,9 @@ impl<T> Caller<'_, T> {
 
     /// Looks up an export from the caller's module by the `name` given.
     ///
-    /// Note that this function is only implemented for the `Extern::Memory`
-    /// and the `Extern::Func` types currently. No other exported structures
-    /// can be acquired through this method.
+    /// Note that this function is only implemented for the `Extern::Memory`,
+    /// `Extern::SharedMemory` and the `Extern::Func` types currently. No other
+    /// exported structures can be acquired through this method.
     ///
     /// Note that when accessing and calling exported functions, one should
     /// adhere to the guidelines of the interface types proposal.  This method
@@ -1689,6 +1778,7 @@ impl<T> Caller<'_, T> {
         {
             Extern::Func(f) => Some(Extern::Func(f)),
             Extern::Memory(f) => Some(Extern::Memory(f)),
+            Extern::SharedMemory(f) => Some(Extern::SharedMemory(f)),
             // Intentionally ignore other Extern items here since this API is
             // supposed to be a temporary stop-gap until interface types.
             _ => None,
@@ -1832,6 +1922,7 @@ macro_rules! impl_into_func {
                     enum CallResult<U> {
                         Ok(U),
                         Trap(anyhow::Error),
+                        Exception,
                         Panic(Box<dyn std::any::Any + Send>),
                     }
 
@@ -1884,7 +1975,12 @@ macro_rules! impl_into_func {
                                 } else {
                                     match ret.into_abi_for_ret(caller.store.0, retptr) {
                                         Ok(val) => CallResult::Ok(val),
-                                        Err(trap) => CallResult::Trap(trap.into()),
+                                        // See `HostFunc::new_unchecked` for
+                                        // how exceptions are thrown.
+                                        Err(trap) => match throw_host_exception(&mut caller.store, trap) {
+                                            Ok(()) => CallResult::Exception,
+                                            Err(trap) => CallResult::Trap(trap.into()),
+                                        },
                                     }
                                 }
 
@@ -1895,6 +1991,7 @@ macro_rules! impl_into_func {
                     match result {
                         CallResult::Ok(val) => val,
                         CallResult::Trap(trap) => raise_user_trap(trap),
+                        CallResult::Exception => raise_pending_exception(caller_vmctx),
                         CallResult::Panic(panic) => wasmtime_runtime::resume_panic(panic),
                     }
                 }
@@ -1958,6 +2055,10 @@ macro_rules! impl_into_func {
                     .expect("failed to create raw function")
                 };
 
+                if engine.config().features.tail_call && tail_call_needs_stack_args(&ty) {
+                    return tail_call_adapter(engine, &ty, instance, trampoline);
+                }
+
                 (instance, trampoline)
             }
         }
@@ -1966,6 +2067,75 @@ macro_rules! impl_into_func {
 
 for_each_function_signature!(impl_into_func);
 
+/// Puts a function compiled by Cranelift in front of a `Func::wrap` function
+/// of type `ty` that wasm would otherwise call directly, for when that call
+/// would pass it stack arguments native code can't pop (see
+/// `tail_call_needs_stack_args`). The adapter enters the wrapped function's
+/// `instance` through its `trampoline`, the same way `Func::call` does.
+#[cfg(compiler)]
+fn tail_call_adapter(
+    engine: &Engine,
+    ty: &FuncType,
+    instance: InstanceHandle,
+    trampoline: VMTrampoline,
+) -> (InstanceHandle, VMTrampoline) {
+    let idx = EntityIndex::Function(FuncIndex::from_u32(0));
+    let export = match instance.lookup_by_declaration(&idx) {
+        wasmtime_runtime::Export::Function(f) => f,
+        _ => unreachable!(),
+    };
+    let wrapped = WrappedHostFunc {
+        instance,
+        trampoline,
+        export,
+        engine: engine.clone(),
+    };
+    let func = move |caller_vmctx: *mut VMContext, values: *mut ValRaw| unsafe {
+        let anyfunc = wrapped.export.anyfunc.as_ref();
+        (wrapped.trampoline)(
+            anyfunc.vmctx,
+            caller_vmctx,
+            anyfunc.func_ptr.as_ptr(),
+            values,
+        );
+        Ok(())
+    };
+    crate::trampoline::create_function(ty, func, engine).expect("failed to create function")
+}
+
+#[cfg(not(compiler))]
+fn tail_call_adapter(
+    _engine: &Engine,
+    _ty: &FuncType,
+    _instance: InstanceHandle,
+    _trampoline: VMTrampoline,
+) -> (InstanceHandle, VMTrampoline) {
+    panic!(
+        "wrapping functions which take stack arguments with tail calls enabled requires a compiler"
+    )
+}
+
+/// A `Func::wrap` function behind a `tail_call_adapter`, which owns it.
+#[cfg(compiler)]
+struct WrappedHostFunc {
+    instance: InstanceHandle,
+    trampoline: VMTrampoline,
+    export: ExportFunction,
+    engine: Engine,
+}
+
+#[cfg(compiler)]
+impl Drop for WrappedHostFunc {
+    fn drop(&mut self) {
+        unsafe {
+            self.engine
+                .signatures()
+                .unregister(self.export.anyfunc.as_ref().type_index);
+            OnDemandInstanceAllocator::default().deallocate(&self.instance);
+        }
+    }
+}
+
 /// Representation of a host-defined function.
 ///
 /// This is used for `Func::new` but also for `Linker`-defined functions. For
@@ -2015,7 +2185,15 @@ impl HostFunc {
         func: impl Fn(Caller<'_, T>, *mut ValRaw) -> Result<(), Trap> + Send + Sync + 'static,
     ) -> Self {
         let func = move |caller_vmctx, values: *mut ValRaw| unsafe {
-            Caller::<T>::with(caller_vmctx, |caller| func(caller, values))
+            Caller::<T>::with(caller_vmctx, |mut caller| {
+                match func(caller.sub_caller(), values) {
+                    Ok(()) => Ok(()),
+                    // Exceptions thrown by the host are raised in the calling
+                    // wasm rather than trapping, by the trampoline once this
+                    // returns.
+                    Err(trap) => throw_host_exception(&mut caller.store, trap),
+                }
+            })
         };
         let (instance, trampoline) = crate::trampoline::create_function(&ty, func, engine)
             .expect("failed to create function");
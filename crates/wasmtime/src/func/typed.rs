@@ -1,6 +1,6 @@
-use super::{invoke_wasm_and_catch_traps, HostAbi};
+use super::{invoke_wasm_and_catch_traps, tail_call_needs_stack_args, HostAbi};
 use crate::store::{AutoAssertNoGc, StoreOpaque};
-use crate::{AsContextMut, ExternRef, Func, StoreContextMut, Trap, ValType};
+use crate::{AsContextMut, ExternRef, Func, StoreContextMut, Trap, ValRaw, ValType};
 use anyhow::{bail, Result};
 use std::marker;
 use std::mem::{self, MaybeUninit};
@@ -145,6 +145,11 @@ where
             }
         };
 
+        let data = &store.0.store_data()[self.func.0];
+        if store.0.engine().config().features.tail_call && tail_call_needs_stack_args(&data.ty) {
+            return self.call_through_trampoline(store, params);
+        }
+
         // Try to capture only a single variable (a tuple) in the closure below.
         // This means the size of the closure is one pointer and is much more
         // efficient to move in memory. This closure is actually invoked on the
@@ -174,6 +179,34 @@ where
         result?;
         Ok(Results::from_abi(store.0, ret.assume_init()))
     }
+
+    /// Calls this function through its trampoline, passing the parameters and
+    /// results in memory, for functions which can't be called directly from
+    /// native code (see `tail_call_needs_stack_args`).
+    unsafe fn call_through_trampoline<T>(
+        &self,
+        store: &mut StoreContextMut<'_, T>,
+        params: Params::Abi,
+    ) -> Result<Results, Trap> {
+        let data = &store.0.store_data()[self.func.0];
+        let trampoline = data.trampoline();
+        let anyfunc = data.export().anyfunc;
+        let len = data.ty.params().len().max(data.ty.results().len());
+        let mut values = vec![ValRaw { v128: 0 }; len];
+        Params::store_raw(params, values.as_mut_ptr());
+        invoke_wasm_and_catch_traps(store, |callee| {
+            trampoline(
+                (*anyfunc.as_ptr()).vmctx,
+                callee,
+                (*anyfunc.as_ptr()).func_ptr.as_ptr(),
+                values.as_mut_ptr(),
+            )
+        })?;
+        Ok(Results::from_abi(
+            store.0,
+            Results::load_raw(values.as_ptr()),
+        ))
+    }
 }
 
 /// A trait implemented for types which can be arguments and results for
@@ -379,6 +412,9 @@ pub unsafe trait WasmParams: Send {
         vmctx2: *mut VMContext,
         abi: Self::Abi,
     ) -> R::ResultAbi;
+
+    #[doc(hidden)]
+    unsafe fn store_raw(abi: Self::Abi, ptr: *mut ValRaw);
 }
 
 // Forward an impl from `T` to `(T,)` for convenience if there's only one
@@ -411,6 +447,10 @@ where
     ) -> R::ResultAbi {
         <(T,) as WasmParams>::invoke::<R>(func, vmctx1, vmctx2, abi)
     }
+
+    unsafe fn store_raw(abi: Self::Abi, ptr: *mut ValRaw) {
+        <(T,) as WasmParams>::store_raw(abi, ptr)
+    }
 }
 
 macro_rules! impl_wasm_params {
@@ -490,6 +530,15 @@ macro_rules! impl_wasm_params {
                     fnptr(vmctx1, vmctx2, $($t,)* retptr)
                 })
             }
+
+            unsafe fn store_raw(abi: Self::Abi, _ptr: *mut ValRaw) {
+                let ($($t,)*) = abi;
+                let mut _n = 0;
+                $(
+                    *_ptr.add(_n).cast::<$t::Abi>() = $t;
+                    _n += 1;
+                )*
+            }
         }
     };
 }
@@ -507,6 +556,8 @@ pub unsafe trait WasmResults: WasmParams {
     type ResultAbi: HostAbi;
     #[doc(hidden)]
     unsafe fn from_abi(store: &mut StoreOpaque, abi: Self::ResultAbi) -> Self;
+    #[doc(hidden)]
+    unsafe fn load_raw(ptr: *const ValRaw) -> Self::ResultAbi;
 }
 
 // Forwards from a bare type `T` to the 1-tuple type `(T,)`
@@ -519,6 +570,10 @@ where
     unsafe fn from_abi(store: &mut StoreOpaque, abi: Self::ResultAbi) -> Self {
         <(T,) as WasmResults>::from_abi(store, abi).0
     }
+
+    unsafe fn load_raw(ptr: *const ValRaw) -> Self::ResultAbi {
+        <(T,) as WasmResults>::load_raw(ptr)
+    }
 }
 
 macro_rules! impl_wasm_results {
@@ -534,6 +589,16 @@ macro_rules! impl_wasm_results {
                 let ($($t,)*) = abi;
                 ($($t::from_abi($t, store),)*)
             }
+
+            #[inline]
+            unsafe fn load_raw(_ptr: *const ValRaw) -> Self::ResultAbi {
+                let mut _n = 0;
+                $(
+                    let $t = *_ptr.add(_n).cast::<$t::Abi>();
+                    _n += 1;
+                )*
+                ($($t,)*)
+            }
         }
     };
 }
@@ -4,19 +4,19 @@ use crate::store::{InstanceId, StoreData, StoreOpaque, Stored};
 use crate::types::matching;
 use crate::{
     AsContext, AsContextMut, Engine, Export, Extern, ExternType, Func, Global, InstanceType,
-    Memory, Module, StoreContextMut, Table, Trap, TypedFunc,
+    Memory, Module, SharedMemory, StoreContextMut, Table, Tag, Trap, TypedFunc,
 };
 use anyhow::{anyhow, bail, Context, Error, Result};
 use std::mem;
 use std::sync::Arc;
 use wasmtime_environ::{
     EntityIndex, EntityType, FuncIndex, GlobalIndex, Initializer, InstanceIndex, MemoryIndex,
-    ModuleIndex, PrimaryMap, TableIndex,
+    ModuleIndex, PrimaryMap, TableIndex, TagIndex,
 };
 use wasmtime_jit::TypeTables;
 use wasmtime_runtime::{
     Imports, InstanceAllocationRequest, InstantiationError, StorePtr, VMContext, VMFunctionBody,
-    VMFunctionImport, VMGlobalImport, VMMemoryImport, VMTableImport,
+    VMFunctionImport, VMGlobalImport, VMMemoryImport, VMTableImport, VMTagImport,
 };
 
 /// An instantiated WebAssembly module.
@@ -411,6 +411,18 @@ impl Instance {
         self.get_export(store, name)?.into_memory()
     }
 
+    /// Looks up an exported [`SharedMemory`] value by name.
+    ///
+    /// Returns `None` if there was no export named `name`, or if there was but
+    /// it wasn't a shared memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this instance.
+    pub fn get_shared_memory(&self, store: impl AsContextMut, name: &str) -> Option<SharedMemory> {
+        self.get_export(store, name)?.into_shared_memory()
+    }
+
     /// Looks up an exported [`Global`] value by name.
     ///
     /// Returns `None` if there was no export named `name`, or if there was but
@@ -422,6 +434,18 @@ impl Instance {
     pub fn get_global(&self, store: impl AsContextMut, name: &str) -> Option<Global> {
         self.get_export(store, name)?.into_global()
     }
+
+    /// Looks up an exported [`Tag`] value by name.
+    ///
+    /// Returns `None` if there was no export named `name`, or if there was but
+    /// it wasn't a tag.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this instance.
+    pub fn get_tag(&self, store: impl AsContextMut, name: &str) -> Option<Tag> {
+        self.get_export(store, name)?.into_tag()
+    }
 }
 
 struct Instantiator<'a> {
@@ -435,6 +459,7 @@ struct ImportsBuilder<'a> {
     tables: PrimaryMap<TableIndex, VMTableImport>,
     memories: PrimaryMap<MemoryIndex, VMMemoryImport>,
     globals: PrimaryMap<GlobalIndex, VMGlobalImport>,
+    tags: PrimaryMap<TagIndex, VMTagImport>,
     instances: PrimaryMap<InstanceIndex, Instance>,
     modules: PrimaryMap<ModuleIndex, Module>,
     initializer: usize,
@@ -545,14 +570,14 @@ impl<'a> Instantiator<'a> {
                     ImportSource::Externs(list) => {
                         let (head, remaining) = list.split_first().unwrap();
                         *list = remaining;
-                        self.cur.push(head.clone(), store);
+                        self.cur.push(head.clone(), store)?;
                     }
                     ImportSource::Definitions(list) => {
                         let (head, remaining) = list.split_first().unwrap();
                         *list = remaining;
                         // This unsafety is encapsulated with
                         // `Instantiator::new`, documented above.
-                        self.cur.push(unsafe { head.to_extern(store) }, store);
+                        self.cur.push(unsafe { head.to_extern(store) }, store)?;
                     }
 
                     // Otherwise if arguments are coming from our outer
@@ -588,6 +613,9 @@ impl<'a> Instantiator<'a> {
                             EntityIndex::Instance(i) => {
                                 self.cur.instances.push(outer.instances[i].clone());
                             }
+                            EntityIndex::Tag(i) => {
+                                self.cur.tags.push(outer.tags[i]);
+                            }
                         }
                     }
                 }
@@ -599,7 +627,7 @@ impl<'a> Instantiator<'a> {
             Some(Initializer::AliasInstanceExport { instance, export }) => {
                 let instance = self.cur.instances[*instance];
                 let export = instance._get_export(store, export).unwrap();
-                self.cur.push(export, store);
+                self.cur.push(export, store)?;
             }
 
             // A recursive instantiation of an instance.
@@ -714,6 +742,8 @@ impl<'a> Instantiator<'a> {
                         host_state: Box::new(Instance(instance_to_be)),
                         store: StorePtr::new(store.traitobj()),
                         wasm_data: compiled_module.wasm_data(),
+                        memory_images: self.cur.module.memory_images(),
+                        unique_id: Some(compiled_module.unique_id()),
                     })?;
 
             // The instance still has lots of setup, for example
@@ -845,6 +875,7 @@ impl<'a> ImportsBuilder<'a> {
             tables: PrimaryMap::with_capacity(raw.num_imported_tables),
             memories: PrimaryMap::with_capacity(raw.num_imported_memories),
             globals: PrimaryMap::with_capacity(raw.num_imported_globals),
+            tags: PrimaryMap::with_capacity(raw.num_imported_tags),
             instances: PrimaryMap::with_capacity(raw.instances.len()),
             modules: PrimaryMap::with_capacity(raw.modules.len()),
             module: module.clone(),
@@ -852,7 +883,7 @@ impl<'a> ImportsBuilder<'a> {
         }
     }
 
-    fn push(&mut self, item: Extern, store: &mut StoreOpaque) {
+    fn push(&mut self, item: Extern, store: &mut StoreOpaque) -> Result<()> {
         match item {
             Extern::Func(i) => {
                 self.functions.push(i.vmimport(store));
@@ -866,13 +897,20 @@ impl<'a> ImportsBuilder<'a> {
             Extern::Memory(i) => {
                 self.memories.push(i.vmimport(store));
             }
+            Extern::SharedMemory(i) => {
+                self.memories.push(i.vmimport(store)?);
+            }
             Extern::Instance(i) => {
                 self.instances.push(i);
             }
             Extern::Module(m) => {
                 self.modules.push(m);
             }
+            Extern::Tag(i) => {
+                self.tags.push(i.vmimport(store));
+            }
         }
+        Ok(())
     }
 
     fn build(&self) -> Imports<'_> {
@@ -881,6 +919,7 @@ impl<'a> ImportsBuilder<'a> {
             globals: self.globals.values().as_slice(),
             memories: self.memories.values().as_slice(),
             functions: self.functions.values().as_slice(),
+            tags: self.tags.values().as_slice(),
         }
     }
 }
@@ -375,7 +375,9 @@
 mod func;
 
 mod config;
+mod coredump;
 mod engine;
+mod exception;
 mod externals;
 mod instance;
 mod limits;
@@ -391,7 +393,9 @@ mod types;
 mod values;
 
 pub use crate::config::*;
+pub use crate::coredump::*;
 pub use crate::engine::*;
+pub use crate::exception::*;
 pub use crate::externals::*;
 pub use crate::func::*;
 pub use crate::instance::{Instance, InstancePre};
@@ -1,7 +1,8 @@
 use crate::store::{StoreData, StoreOpaque, Stored};
 use crate::trampoline::generate_memory_export;
-use crate::{AsContext, AsContextMut, MemoryType, StoreContext, StoreContextMut};
+use crate::{AsContext, AsContextMut, Engine, MemoryType, StoreContext, StoreContextMut};
 use anyhow::{bail, Result};
+use std::cell::UnsafeCell;
 use std::convert::TryFrom;
 use std::slice;
 
@@ -163,35 +164,10 @@ impl std::error::Error for MemoryAccessError {}
 ///
 /// ## `Memory` Safety and Threads
 ///
-/// Currently the `wasmtime` crate does not implement the wasm threads proposal,
-/// but it is planned to do so. It may be interesting to readers to see how this
-/// affects memory safety and what was previously just discussed as well.
-///
-/// Once threads are added into the mix, all of the above rules still apply.
-/// There's an additional consideration that all reads and writes can happen
-/// concurrently, though. This effectively means that any borrow into wasm
-/// memory are virtually never safe to have.
-///
-/// Mutable pointers are fundamentally unsafe to have in a concurrent scenario
-/// in the face of arbitrary wasm code. Only if you dynamically know for sure
-/// that wasm won't access a region would it be safe to construct a mutable
-/// pointer. Additionally even shared pointers are largely unsafe because their
-/// underlying contents may change, so unless `UnsafeCell` in one form or
-/// another is used everywhere there's no safety.
-///
-/// One important point about concurrency is that while [`Memory::grow`] can
-/// happen concurrently it will never relocate the base pointer. Shared
-/// memories must always have a maximum size and they will be preallocated such
-/// that growth will never relocate the base pointer. The current size of the
-/// memory may still change over time though.
-///
-/// Overall the general rule of thumb for shared memories is that you must
-/// atomically read and write everything. Nothing can be borrowed and everything
-/// must be eagerly copied out. This means that [`Memory::data`] and
-/// [`Memory::data_mut`] won't work in the future (they'll probably return an
-/// error) for shared memories when they're implemented. When possible it's
-/// recommended to use [`Memory::read`] and [`Memory::write`] which will still
-/// be provided.
+/// A [`Memory`] is never a shared memory. Memories declared as `shared` by the
+/// wasm threads proposal are instead represented with a [`SharedMemory`],
+/// which isn't owned by any particular store and has its own rules for safe
+/// access; see its documentation for more information.
 #[derive(Copy, Clone, Debug)]
 #[repr(transparent)] // here for the C API
 pub struct Memory(Stored<wasmtime_runtime::ExportMemory>);
@@ -256,8 +232,11 @@ impl Memory {
     }
 
     fn _new(store: &mut StoreOpaque, ty: MemoryType) -> Result<Memory> {
+        if ty.is_shared() {
+            bail!("shared memories must be created through `SharedMemory`");
+        }
         unsafe {
-            let export = generate_memory_export(store, &ty)?;
+            let export = generate_memory_export(store, &ty, None)?;
             Ok(Memory::from_wasmtime_memory(export, store))
         }
     }
@@ -500,8 +479,13 @@ impl Memory {
         unsafe {
             match (*mem).grow(delta, store)? {
                 Some(size) => {
-                    let vm = (*mem).vmmemory();
-                    *store[self.0].definition = vm;
+                    // Shared memories publish their new length themselves and
+                    // their definition may be read concurrently by other
+                    // threads, so only update unshared definitions here.
+                    if !matches!(*mem, wasmtime_runtime::Memory::Shared(_)) {
+                        let vm = (*mem).vmmemory();
+                        *store[self.0].definition = vm;
+                    }
                     Ok(u64::try_from(size).unwrap() / u64::from(wasmtime_environ::WASM_PAGE_SIZE))
                 }
                 None => bail!("failed to grow memory by `{}`", delta),
@@ -566,6 +550,172 @@ impl Memory {
     }
 }
 
+/// A WebAssembly linear memory which may be shared between threads.
+///
+/// Shared memories are part of the [WebAssembly threads proposal][threads] and
+/// are created either with [`SharedMemory::new`] or by instantiating a module
+/// which defines a `shared` memory. Unlike a [`Memory`] a `SharedMemory` isn't
+/// owned by any [`Store`](crate::Store): it's a cheaply-cloneable handle which
+/// can be sent to other threads and imported into instances living in any
+/// number of stores, all of which then operate on the same bytes.
+///
+/// Shared memories always have a maximum size and are allocated up front such
+/// that growing them never relocates their base pointer. Their size may still
+/// change at any time though, as any thread with access to the memory may grow
+/// it.
+///
+/// # Shared Memory Safety
+///
+/// Wasm running on other threads may read or write any byte of a shared
+/// memory at any time, so it's never possible to safely hand out `&[u8]` or
+/// `&mut [u8]` views of its contents. Instead [`SharedMemory::data`] returns a
+/// slice of [`UnsafeCell<u8>`] and it's up to the embedder to access it with
+/// appropriate synchronization, for example with atomic operations.
+///
+/// [threads]: https://github.com/webassembly/threads
+///
+/// # Examples
+///
+/// ```
+/// # use wasmtime::*;
+/// # fn main() -> anyhow::Result<()> {
+/// let mut config = Config::new();
+/// config.wasm_threads(true);
+/// let engine = Engine::new(&config)?;
+///
+/// let memory = SharedMemory::new(&engine, MemoryType::shared(1, 2))?;
+///
+/// let module = Module::new(&engine, r#"(module (memory (import "" "") 1 2 shared))"#)?;
+/// let mut store1 = Store::new(&engine, ());
+/// let mut store2 = Store::new(&engine, ());
+/// Instance::new(&mut store1, &module, &[memory.clone().into()])?;
+/// Instance::new(&mut store2, &module, &[memory.clone().into()])?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct SharedMemory(wasmtime_runtime::SharedMemory, Engine);
+
+impl SharedMemory {
+    /// Creates a new shared WebAssembly memory given the configuration of
+    /// `ty`.
+    ///
+    /// All memory is initialized to zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `ty` isn't a shared memory type, if the threads
+    /// proposal isn't enabled for `engine`, or if the memory couldn't be
+    /// allocated.
+    pub fn new(engine: &Engine, ty: MemoryType) -> Result<SharedMemory> {
+        if !ty.is_shared() {
+            bail!("shared memory must have the `shared` flag enabled on its memory type");
+        }
+        if !engine.config().features.threads {
+            bail!("the threads proposal must be enabled to create shared memories");
+        }
+        let plan = wasmtime_environ::MemoryPlan::for_memory(
+            ty.wasmtime_memory().clone(),
+            &engine.config().tunables,
+        );
+        let memory = wasmtime_runtime::SharedMemory::new(plan)?;
+        Ok(SharedMemory(memory, engine.clone()))
+    }
+
+    /// Returns the underlying type of this memory.
+    pub fn ty(&self) -> MemoryType {
+        MemoryType::from_wasmtime_memory(&self.0.ty())
+    }
+
+    /// Returns the size, in WebAssembly pages, of this wasm memory.
+    pub fn size(&self) -> u64 {
+        (self.data_size() / wasmtime_environ::WASM_PAGE_SIZE as usize) as u64
+    }
+
+    /// Returns the byte length of this memory.
+    ///
+    /// The returned value will be a multiple of the wasm page size, 64k. Note
+    /// that other threads may grow this memory at any time, so the returned
+    /// value is only a lower bound of the memory's current size.
+    pub fn data_size(&self) -> usize {
+        self.0.byte_size()
+    }
+
+    /// Returns the contents of this memory.
+    ///
+    /// The returned slice covers the size of the memory at the time this
+    /// method was called. Since the memory never moves and never shrinks the
+    /// slice stays valid for as long as this `SharedMemory` is alive, but its
+    /// contents may be concurrently modified by other threads, hence the
+    /// [`UnsafeCell`] wrapper.
+    pub fn data(&self) -> &[UnsafeCell<u8>] {
+        unsafe {
+            let definition = self.0.vmmemory();
+            slice::from_raw_parts(
+                definition.base.cast::<UnsafeCell<u8>>(),
+                definition.current_length,
+            )
+        }
+    }
+
+    /// Grows this WebAssembly memory by `delta` pages.
+    ///
+    /// On success returns the number of pages this memory previously had
+    /// before the growth succeeded. The base pointer of a shared memory never
+    /// changes when it's grown.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if memory could not be grown, for example if it
+    /// exceeds the maximum limits of this memory. Note that no
+    /// [`ResourceLimiter`](crate::ResourceLimiter) is consulted here since a
+    /// shared memory isn't owned by any store.
+    pub fn grow(&self, delta: u64) -> Result<u64> {
+        match self.0.grow(delta, None)? {
+            Some(size) => {
+                Ok(u64::try_from(size).unwrap() / u64::from(wasmtime_environ::WASM_PAGE_SIZE))
+            }
+            None => bail!("failed to grow memory by `{}`", delta),
+        }
+    }
+
+    /// Creates a host instance in `store` which wraps this memory so it can be
+    /// imported by instances in that store.
+    pub(crate) fn vmimport(
+        &self,
+        store: &mut StoreOpaque,
+    ) -> Result<wasmtime_runtime::VMMemoryImport> {
+        if !Engine::same(&self.1, store.engine()) {
+            bail!("cross-`Engine` instantiation is not currently supported");
+        }
+        let export = generate_memory_export(store, &self.ty(), Some(&self.0))?;
+        Ok(wasmtime_runtime::VMMemoryImport {
+            from: export.definition,
+            vmctx: export.vmctx,
+        })
+    }
+
+    pub(crate) unsafe fn from_wasmtime_memory(
+        wasmtime_export: wasmtime_runtime::ExportMemory,
+        store: &mut StoreOpaque,
+    ) -> SharedMemory {
+        let mut handle = wasmtime_runtime::InstanceHandle::from_vmctx(wasmtime_export.vmctx);
+        let idx = handle.memory_index(&*wasmtime_export.definition);
+        match &*handle.get_defined_memory(idx) {
+            wasmtime_runtime::Memory::Shared(memory) => {
+                SharedMemory(memory.clone(), store.engine().clone())
+            }
+            _ => unreachable!("exported a non-shared memory as a `SharedMemory`"),
+        }
+    }
+}
+
+impl std::fmt::Debug for SharedMemory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedMemory").finish()
+    }
+}
+
 /// A linear memory. This trait provides an interface for raw memory buffers
 /// which are used by wasmtime, e.g. inside ['Memory']. Such buffers are in
 /// principle not thread safe. By implementing this trait together with
@@ -633,6 +783,11 @@ pub unsafe trait MemoryCreator: Send + Sync {
     /// the maximum size of `ty` is guaranteed to fit within
     /// `reserved_size_in_bytes`.
     ///
+    /// For `shared` memory types `reserved_size_in_bytes` is always `Some`,
+    /// and not moving the base pointer is required rather than expected:
+    /// other threads may be accessing the memory while it's grown, so a
+    /// shared memory whose base pointer moves causes a panic.
+    ///
     /// The `guard_size_in_bytes` parameter indicates how many bytes of space,
     /// after the memory allocation, is expected to be unmapped. JIT code will
     /// elide bounds checks based on the `guard_size_in_bytes` provided, so for
@@ -675,4 +830,21 @@ mod tests {
             other => panic!("unexpected style {:?}", other),
         }
     }
+
+    // Shared memories can never move, so they're always static even when the
+    // tunables would otherwise make them dynamic.
+    #[test]
+    fn shared_memory_is_static() {
+        let mut cfg = Config::new();
+        cfg.wasm_threads(true)
+            .static_memory_maximum_size(0)
+            .dynamic_memory_guard_size(0);
+        let engine = Engine::new(&cfg).unwrap();
+        let mem = SharedMemory::new(&engine, MemoryType::shared(1, 2)).unwrap();
+        assert_eq!(mem.size(), 1);
+        assert_eq!(mem.grow(1).unwrap(), 1);
+        assert_eq!(mem.size(), 2);
+        assert!(mem.grow(1).is_err());
+        assert_eq!(mem.data().len(), 2 * 65536);
+    }
 }
@@ -11,6 +11,7 @@ use std::sync::Arc;
 use wasmparser::{Parser, ValidPayload, Validator};
 use wasmtime_environ::{ModuleEnvironment, ModuleIndex, PrimaryMap};
 use wasmtime_jit::{CompiledModule, CompiledModuleInfo, MmapVec, TypeTables};
+use wasmtime_runtime::ModuleMemoryImages;
 
 mod registry;
 mod serialization;
@@ -336,7 +337,12 @@ impl Module {
         };
 
         let modules = engine.run_maybe_parallel(artifacts, |(a, b)| {
-            CompiledModule::from_artifacts(a, b, &*engine.config().profiler)
+            CompiledModule::from_artifacts(
+                a,
+                b,
+                &*engine.config().profiler,
+                engine.config().memory_init_cow,
+            )
         })?;
 
         Self::from_parts(engine, modules, main_module, Arc::new(types), &[])
@@ -409,6 +415,14 @@ impl Module {
                 translation.try_paged_init();
             }
 
+            // Otherwise, if configured, attempt to use static memory
+            // initialization so that memories can be initialized from
+            // copy-on-write images. Images larger than 16MiB are only created
+            // if they're at least half full of data.
+            if engine.config().memory_init_cow {
+                translation.try_static_init(region::page::size() as u64, 16 << 20);
+            }
+
             let (mmap, info) =
                 wasmtime_jit::finish_compile(translation, obj, funcs, trampolines, tunables)?;
             Ok((mmap, Some(info)))
@@ -694,6 +708,12 @@ impl Module {
         &self.inner.module
     }
 
+    /// Returns the copy-on-write images of this module's linear memories, if
+    /// any, to initialize memories with when instantiating this module.
+    pub(crate) fn memory_images(&self) -> Option<&ModuleMemoryImages> {
+        self.inner.module.memory_images()
+    }
+
     pub(crate) fn env_module(&self) -> &wasmtime_environ::Module {
         self.compiled_module().module()
     }
@@ -923,6 +943,45 @@ impl Module {
     pub fn engine(&self) -> &Engine {
         &self.inner.engine
     }
+
+    /// Returns the native machine code of this module's compiled functions and
+    /// trampolines.
+    ///
+    /// This is intended for tools which inspect the code generated for a
+    /// module, such as `wasmtime explore`. The offsets returned by
+    /// [`Module::address_map`] and [`Module::function_locations`] are relative
+    /// to the start of this slice.
+    pub fn text(&self) -> &[u8] {
+        self.compiled_module().code()
+    }
+
+    /// Returns the mapping from offsets in [`Module::text`] back to offsets in
+    /// the original wasm binary, if it was generated (see
+    /// [`Config::generate_address_map`](crate::Config::generate_address_map)).
+    ///
+    /// Each item is an offset within the text section, and the offset of the
+    /// wasm instruction whose code starts there, if any. Items are sorted by
+    /// their text section offset, and each one covers the code up to the next
+    /// one.
+    pub fn address_map<'a>(&'a self) -> Option<impl Iterator<Item = (usize, Option<u32>)> + 'a> {
+        Some(
+            wasmtime_environ::iterate_address_map(self.compiled_module().address_map_data())?
+                .map(|(offset, pos)| (offset as usize, pos.file_offset())),
+        )
+    }
+
+    /// Returns the location of each function defined in this module within
+    /// [`Module::text`], as its offset and length in bytes.
+    ///
+    /// Functions are returned in the order they're defined in, so the `n`th
+    /// item is the function whose index is `n` plus the number of imported
+    /// functions.
+    pub fn function_locations<'a>(&'a self) -> impl ExactSizeIterator<Item = (usize, usize)> + 'a {
+        self.compiled_module()
+            .functions()
+            .values()
+            .map(|f| (f.start as usize, f.length as usize))
+    }
 }
 
 fn _assert_send_sync() {
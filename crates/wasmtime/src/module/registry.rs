@@ -139,6 +139,16 @@ impl ModuleInfo for RegisteredModule {
 
         Some(&info.stack_maps[index].stack_map)
     }
+
+    fn has_exception_handler(&self, pc: usize) -> bool {
+        let text_offset = pc - self.start;
+        let (index, func_offset) = match self.module.func_by_text_offset(text_offset) {
+            Some(found) => found,
+            None => return false,
+        };
+        let info = self.module.func_info(index);
+        info.exception_handlers.binary_search(&func_offset).is_ok()
+    }
 }
 
 // Counterpart to `RegisteredModule`, but stored in the global registry.
@@ -218,6 +228,13 @@ impl GlobalModuleRegistry {
         })
     }
 
+    /// Fetches the module that the code at a program counter in a backtrace
+    /// belongs to.
+    pub(crate) fn lookup_module_info(&self, pc: usize) -> Option<&Arc<wasmtime_environ::Module>> {
+        let (module, _) = self.module(pc)?;
+        Some(module.module.module())
+    }
+
     /// Fetches trap information about a program counter in a backtrace.
     pub(crate) fn lookup_trap_code(&self, pc: usize) -> Option<TrapCode> {
         let (module, offset) = self.module(pc)?;
@@ -338,7 +355,7 @@ impl GlobalRegisteredModule {
 /// each frame is described by this structure.
 ///
 /// [`Trap`]: crate::Trap
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FrameInfo {
     module_name: Option<String>,
     func_index: u32,
@@ -437,7 +454,7 @@ impl FrameInfo {
 /// When DWARF debug information is present in a wasm file then this structure
 /// can be found on a [`FrameInfo`] and can be used to learn about filenames,
 /// line numbers, etc, which are the origin of a function in a stack trace.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FrameSymbol {
     name: Option<String>,
     file: Option<String>,
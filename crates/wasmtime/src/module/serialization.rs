@@ -274,7 +274,12 @@ impl<'a> SerializedModule<'a> {
     pub fn into_module(self, engine: &Engine) -> Result<Module> {
         let (main_module, modules, types, upvars) = self.into_parts(engine)?;
         let modules = engine.run_maybe_parallel(modules, |(i, m)| {
-            CompiledModule::from_artifacts(i, m, &*engine.config().profiler)
+            CompiledModule::from_artifacts(
+                i,
+                m,
+                &*engine.config().profiler,
+                engine.config().memory_init_cow,
+            )
         })?;
 
         Module::from_parts(engine, modules, main_module, Arc::new(types), &upvars)
@@ -596,8 +601,10 @@ impl<'a> SerializedModule<'a> {
             parse_wasm_debuginfo,
             interruptable,
             consume_fuel,
+            epoch_interruption,
             static_memory_bound_is_maximum,
             guard_before_linear_memory,
+            coredump_on_trap,
 
             // This doesn't affect compilation, it's just a runtime setting.
             dynamic_memory_growth_reserve: _,
@@ -636,6 +643,11 @@ impl<'a> SerializedModule<'a> {
         )?;
         Self::check_bool(interruptable, other.interruptable, "interruption support")?;
         Self::check_bool(consume_fuel, other.consume_fuel, "fuel support")?;
+        Self::check_bool(
+            epoch_interruption,
+            other.epoch_interruption,
+            "epoch interruption",
+        )?;
         Self::check_bool(
             static_memory_bound_is_maximum,
             other.static_memory_bound_is_maximum,
@@ -646,6 +658,11 @@ impl<'a> SerializedModule<'a> {
             other.guard_before_linear_memory,
             "guard before linear memory",
         )?;
+        Self::check_bool(
+            coredump_on_trap,
+            other.coredump_on_trap,
+            "core dump support",
+        )?;
 
         Ok(())
     }
@@ -88,6 +88,7 @@ use std::mem::{self, ManuallyDrop};
 use std::ops::{Deref, DerefMut};
 use std::pin::Pin;
 use std::ptr;
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 use wasmtime_runtime::{
@@ -272,6 +273,7 @@ pub struct StoreOpaque {
     #[cfg(feature = "async")]
     async_state: AsyncState,
     out_of_gas_behavior: OutOfGas,
+    epoch_deadline_behavior: EpochDeadline,
     store_data: StoreData,
     default_callee: InstanceHandle,
 
@@ -379,6 +381,16 @@ enum OutOfGas {
     },
 }
 
+/// What to do when the engine's epoch counter reaches a store's deadline.
+#[derive(Copy, Clone)]
+enum EpochDeadline {
+    /// Return early with a trap.
+    Trap,
+    /// Extend the deadline by the specified number of ticks after yielding to
+    /// the async executor loop.
+    YieldAndExtendDeadline { delta: u64 },
+}
+
 impl<T> Store<T> {
     /// Creates a new [`Store`] to be associated with the given [`Engine`] and
     /// `data` provided.
@@ -409,6 +421,8 @@ impl<T> Store<T> {
                     module: Arc::new(wasmtime_environ::Module::default()),
                     store: StorePtr::empty(),
                     wasm_data: &[],
+                    memory_images: None,
+                    unique_id: None,
                 })
                 .expect("failed to allocate default callee")
         };
@@ -435,6 +449,7 @@ impl<T> Store<T> {
                     current_poll_cx: UnsafeCell::new(ptr::null_mut()),
                 },
                 out_of_gas_behavior: OutOfGas::Trap,
+                epoch_deadline_behavior: EpochDeadline::Trap,
                 store_data: StoreData::new(),
                 default_callee,
                 hostcall_val_storage: Vec::new(),
@@ -809,6 +824,93 @@ impl<T> Store<T> {
         self.inner
             .out_of_fuel_async_yield(injection_count, fuel_to_inject)
     }
+
+    /// Sets the epoch deadline to a certain number of ticks in the future.
+    ///
+    /// When the Wasm guest code is compiled with epoch-interruption
+    /// instrumentation
+    /// ([`Config::epoch_interruption()`](crate::Config::epoch_interruption)),
+    /// and when the `Engine`'s epoch is incremented
+    /// ([`Engine::increment_epoch()`](crate::Engine::increment_epoch))
+    /// past a deadline, execution can be configured to either trap or
+    /// yield and then continue.
+    ///
+    /// This deadline is always set relative to the current epoch:
+    /// `ticks_beyond_current` ticks in the future. The deadline can
+    /// be set explicitly via this method, or refilled automatically
+    /// on a yield if configured via
+    /// [`epoch_deadline_async_yield_and_update()`](Store::epoch_deadline_async_yield_and_update). After
+    /// this method is invoked, the deadline is reached when
+    /// [`Engine::increment_epoch()`] has been invoked at least
+    /// `ticks_beyond_current` times.
+    ///
+    /// Note that a store's deadline starts out at zero, so with epoch
+    /// interruption enabled wasm code will be interrupted immediately unless
+    /// a deadline is set first.
+    ///
+    /// See documentation on
+    /// [`Config::epoch_interruption()`](crate::Config::epoch_interruption)
+    /// for an introduction to epoch-based interruption.
+    pub fn set_epoch_deadline(&mut self, ticks_beyond_current: u64) {
+        self.inner.set_epoch_deadline(ticks_beyond_current);
+    }
+
+    /// Configures epoch-deadline expiration to trap.
+    ///
+    /// When epoch-interruption-instrumented code is executed on this
+    /// store and the epoch deadline is reached before completion,
+    /// with the store configured in this way, execution will
+    /// terminate with a trap as soon as an epoch check in the
+    /// instrumented code is reached.
+    ///
+    /// This behavior is the default if the store is not otherwise
+    /// configured via
+    /// [`epoch_deadline_trap()`](Store::epoch_deadline_trap) or
+    /// [`epoch_deadline_async_yield_and_update()`](Store::epoch_deadline_async_yield_and_update).
+    ///
+    /// This setting is intended to allow for coarse-grained
+    /// interruption, but not a deterministic deadline of a fixed,
+    /// finite interval. For deterministic interruption, see the
+    /// "fuel" mechanism instead.
+    ///
+    /// See documentation on
+    /// [`Config::epoch_interruption()`](crate::Config::epoch_interruption)
+    /// for an introduction to epoch-based interruption.
+    pub fn epoch_deadline_trap(&mut self) {
+        self.inner.epoch_deadline_trap();
+    }
+
+    /// Configures epoch-deadline expiration to yield to the async
+    /// caller and the update the deadline.
+    ///
+    /// When epoch-interruption-instrumented code is executed on this
+    /// store and the epoch deadline is reached before completion,
+    /// with the store configured in this way, execution will yield
+    /// (the future will return `Pending` but re-awake itself for
+    /// later execution) and, upon resuming, the store will be
+    /// configured with an epoch deadline equal to the current epoch
+    /// plus `delta` ticks.
+    ///
+    /// This setting is intended to allow for cooperative timeslicing
+    /// of multiple CPU-bound Wasm guests in different stores, all
+    /// executing under the control of an async executor. To drive
+    /// this, stores should be configured to "yield and update"
+    /// automatically with this function, and some external driver (a
+    /// thread that wakes up periodically, or a timer
+    /// signal/interrupt) should call
+    /// [`Engine::increment_epoch()`](crate::Engine::increment_epoch).
+    ///
+    /// See documentation on
+    /// [`Config::epoch_interruption()`](crate::Config::epoch_interruption)
+    /// for an introduction to epoch-based interruption.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if it is not called on a store associated with
+    /// an [async config](crate::Config::async_support).
+    pub fn epoch_deadline_async_yield_and_update(&mut self, delta: u64) {
+        self.inner.epoch_deadline_async_yield_and_update(delta);
+    }
 }
 
 impl<'a, T> StoreContext<'a, T> {
@@ -913,6 +1015,29 @@ impl<'a, T> StoreContextMut<'a, T> {
         self.0
             .out_of_fuel_async_yield(injection_count, fuel_to_inject)
     }
+
+    /// Sets the epoch deadline to a certain number of ticks in the future.
+    ///
+    /// For more information see [`Store::set_epoch_deadline`].
+    pub fn set_epoch_deadline(&mut self, ticks_beyond_current: u64) {
+        self.0.set_epoch_deadline(ticks_beyond_current);
+    }
+
+    /// Configures epoch-deadline expiration to trap.
+    ///
+    /// For more information see [`Store::epoch_deadline_trap`].
+    pub fn epoch_deadline_trap(&mut self) {
+        self.0.epoch_deadline_trap();
+    }
+
+    /// Configures epoch-deadline expiration to yield to the async caller and
+    /// then update the deadline.
+    ///
+    /// For more information see
+    /// [`Store::epoch_deadline_async_yield_and_update`].
+    pub fn epoch_deadline_async_yield_and_update(&mut self, delta: u64) {
+        self.0.epoch_deadline_async_yield_and_update(delta);
+    }
 }
 
 impl<T> StoreInner<T> {
@@ -1004,6 +1129,11 @@ impl StoreOpaque {
         }
     }
 
+    #[inline]
+    pub(crate) fn modules(&self) -> &ModuleRegistry {
+        &self.modules
+    }
+
     #[inline]
     pub(crate) fn modules_mut(&mut self) -> &mut ModuleRegistry {
         &mut self.modules
@@ -1025,6 +1155,10 @@ impl StoreOpaque {
         &mut self.instances[id.0].handle
     }
 
+    pub fn all_instances(&self) -> impl ExactSizeIterator<Item = &InstanceHandle> {
+        self.instances.iter().map(|i| &i.handle)
+    }
+
     #[cfg_attr(not(target_os = "linux"), allow(dead_code))] // not used on all platforms
     pub fn set_signal_handler(&mut self, handler: Option<Box<SignalHandler<'static>>>) {
         self.signal_handler = handler;
@@ -1093,6 +1227,28 @@ impl StoreOpaque {
         };
     }
 
+    fn epoch_deadline_trap(&mut self) {
+        self.epoch_deadline_behavior = EpochDeadline::Trap;
+    }
+
+    fn epoch_deadline_async_yield_and_update(&mut self, delta: u64) {
+        assert!(
+            self.async_support(),
+            "cannot use `epoch_deadline_async_yield_and_update` without enabling async support in the config"
+        );
+        self.epoch_deadline_behavior = EpochDeadline::YieldAndExtendDeadline { delta };
+    }
+
+    fn set_epoch_deadline(&mut self, delta: u64) {
+        // Set a new deadline based on the "epoch deadline delta".
+        //
+        // Safety: this is safe because the epoch deadline in the
+        // `VMInterrupts` is accessed only here and by Wasm guest code
+        // running in this store, and we have a `&mut self` here.
+        let epoch_deadline = unsafe { &mut *self.interrupts.epoch_deadline.get() };
+        *epoch_deadline = self.engine().current_epoch().saturating_add(delta);
+    }
+
     /// Yields execution to the caller on out-of-gas
     ///
     /// This only works on async futures and stores, and assumes that we're
@@ -1100,6 +1256,18 @@ impl StoreOpaque {
     /// and when we come back we'll continue with `fuel_to_inject` more fuel.
     #[cfg(feature = "async")]
     fn out_of_gas_yield(&mut self, fuel_to_inject: u64) -> Result<(), Trap> {
+        self.async_yield_impl()?;
+        self.add_fuel(fuel_to_inject).unwrap();
+        Ok(())
+    }
+
+    /// Yields execution to the caller once, returning once the future is
+    /// polled again.
+    ///
+    /// This only works on async futures and stores, and assumes that we're
+    /// executing on a fiber.
+    #[cfg(feature = "async")]
+    fn async_yield_impl(&mut self) -> Result<(), Trap> {
         // Small future that yields once and then returns ()
         #[derive(Default)]
         struct Yield {
@@ -1124,19 +1292,12 @@ impl StoreOpaque {
         }
 
         let mut future = Yield::default();
-        let result = unsafe { self.async_cx().block_on(Pin::new_unchecked(&mut future)) };
-        match result {
-            // If this finished successfully then we were resumed normally via a
-            // `poll`, so inject some more fuel and keep going.
-            Ok(()) => {
-                self.add_fuel(fuel_to_inject).unwrap();
-                Ok(())
-            }
-            // If the future was dropped while we were yielded, then we need to
-            // clean up this fiber. Do so by raising a trap which will abort all
-            // wasm and get caught on the other side to clean things up.
-            Err(trap) => Err(trap),
-        }
+
+        // If this finished successfully then we were resumed normally via a
+        // `poll`. If the future was dropped while we were yielded, then we
+        // need to clean up this fiber. Do so by raising a trap which will
+        // abort all wasm and get caught on the other side to clean things up.
+        unsafe { self.async_cx().block_on(Pin::new_unchecked(&mut future)) }
     }
 
     fn add_fuel(&mut self, fuel: u64) -> Result<()> {
@@ -1535,6 +1696,10 @@ unsafe impl<T> wasmtime_runtime::Store for StoreInner<T> {
         <StoreOpaque>::vminterrupts(self)
     }
 
+    fn epoch_ptr(&self) -> *const AtomicU64 {
+        self.engine.epoch_counter() as *const _
+    }
+
     fn externref_activations_table(
         &mut self,
     ) -> (
@@ -1669,6 +1834,38 @@ unsafe impl<T> wasmtime_runtime::Store for StoreInner<T> {
 
         impl std::error::Error for OutOfGasError {}
     }
+
+    fn new_epoch(&mut self) -> Result<u64, anyhow::Error> {
+        return match &mut self.epoch_deadline_behavior {
+            EpochDeadline::Trap => Err(anyhow::Error::new(EpochDeadlineError)),
+            #[cfg(feature = "async")]
+            EpochDeadline::YieldAndExtendDeadline { delta } => {
+                let delta = *delta;
+                // Do the async yield. May return a trap if future was
+                // canceled while we're yielded.
+                self.async_yield_impl()?;
+                // Set a new deadline.
+                self.set_epoch_deadline(delta);
+
+                // Return the new epoch deadline so the Wasm code
+                // doesn't have to reload it.
+                Ok(unsafe { *self.interrupts.epoch_deadline.get() })
+            }
+            #[cfg(not(feature = "async"))]
+            EpochDeadline::YieldAndExtendDeadline { .. } => unreachable!(),
+        };
+
+        #[derive(Debug)]
+        struct EpochDeadlineError;
+
+        impl fmt::Display for EpochDeadlineError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("epoch deadline reached during execution")
+            }
+        }
+
+        impl std::error::Error for EpochDeadlineError {}
+    }
 }
 
 impl<T: Default> Default for Store<T> {
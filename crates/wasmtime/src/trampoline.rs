@@ -4,6 +4,7 @@ mod func;
 mod global;
 mod memory;
 mod table;
+mod tag;
 
 pub(crate) use memory::MemoryCreatorProxy;
 
@@ -11,15 +12,16 @@ pub use self::func::*;
 use self::global::create_global;
 use self::memory::create_memory;
 use self::table::create_table;
+use self::tag::create_tag;
 use crate::store::{InstanceId, StoreOpaque};
-use crate::{GlobalType, MemoryType, TableType, Val};
+use crate::{GlobalType, MemoryType, TableType, TagType, Val};
 use anyhow::Result;
 use std::any::Any;
 use std::sync::Arc;
-use wasmtime_environ::{EntityIndex, GlobalIndex, MemoryIndex, Module, TableIndex};
+use wasmtime_environ::{EntityIndex, GlobalIndex, MemoryIndex, Module, TableIndex, TagIndex};
 use wasmtime_runtime::{
-    Imports, InstanceAllocationRequest, InstanceAllocator, OnDemandInstanceAllocator, StorePtr,
-    VMFunctionImport, VMSharedSignatureIndex,
+    Imports, InstanceAllocationRequest, InstanceAllocator, OnDemandInstanceAllocator,
+    RuntimeMemoryCreator, SharedMemory, StorePtr, VMFunctionImport, VMSharedSignatureIndex,
 };
 
 fn create_handle(
@@ -28,18 +30,36 @@ fn create_handle(
     host_state: Box<dyn Any + Send + Sync>,
     func_imports: &[VMFunctionImport],
     shared_signature_id: Option<VMSharedSignatureIndex>,
+) -> Result<InstanceId> {
+    let mem_creator = store.engine().config().mem_creator.clone();
+    create_handle_with_mem_creator(
+        module,
+        store,
+        host_state,
+        func_imports,
+        shared_signature_id,
+        mem_creator,
+    )
+}
+
+fn create_handle_with_mem_creator(
+    module: Module,
+    store: &mut StoreOpaque,
+    host_state: Box<dyn Any + Send + Sync>,
+    func_imports: &[VMFunctionImport],
+    shared_signature_id: Option<VMSharedSignatureIndex>,
+    mem_creator: Option<Arc<dyn RuntimeMemoryCreator>>,
 ) -> Result<InstanceId> {
     let mut imports = Imports::default();
     imports.functions = func_imports;
     let functions = &Default::default();
 
     unsafe {
-        let config = store.engine().config();
         // Use the on-demand allocator when creating handles associated with host objects
         // The configured instance allocator should only be used when creating module instances
         // as we don't want host objects to count towards instance limits.
-        let handle = OnDemandInstanceAllocator::new(config.mem_creator.clone(), 0).allocate(
-            InstanceAllocationRequest {
+        let handle =
+            OnDemandInstanceAllocator::new(mem_creator, 0).allocate(InstanceAllocationRequest {
                 module: Arc::new(module),
                 functions,
                 image_base: 0,
@@ -48,8 +68,9 @@ fn create_handle(
                 host_state,
                 store: StorePtr::new(store.traitobj()),
                 wasm_data: &[],
-            },
-        )?;
+                memory_images: None,
+                unique_id: None,
+            })?;
 
         Ok(store.add_instance(handle, true))
     }
@@ -71,8 +92,9 @@ pub fn generate_global_export(
 pub fn generate_memory_export(
     store: &mut StoreOpaque,
     m: &MemoryType,
+    preallocation: Option<&SharedMemory>,
 ) -> Result<wasmtime_runtime::ExportMemory> {
-    let instance = create_memory(store, m)?;
+    let instance = create_memory(store, m, preallocation)?;
     let idx = EntityIndex::Memory(MemoryIndex::from_u32(0));
     match store.instance(instance).lookup_by_declaration(&idx) {
         wasmtime_runtime::Export::Memory(m) => Ok(m),
@@ -91,3 +113,15 @@ pub fn generate_table_export(
         _ => unreachable!(),
     }
 }
+
+pub fn generate_tag_export(
+    store: &mut StoreOpaque,
+    t: &TagType,
+) -> Result<wasmtime_runtime::ExportTag> {
+    let instance = create_tag(store, t)?;
+    let idx = EntityIndex::Tag(TagIndex::from_u32(0));
+    match store.instance(instance).lookup_by_declaration(&idx) {
+        wasmtime_runtime::Export::Tag(t) => Ok(t),
+        _ => unreachable!(),
+    }
+}
@@ -50,7 +50,13 @@ unsafe extern "C" fn stub_fn<F>(
     }));
 
     match result {
-        Ok(Ok(())) => {}
+        // Exceptions thrown by the host are left pending by the closure, see
+        // `HostFunc::new_unchecked`, and unwound to their handler from here.
+        Ok(Ok(())) => {
+            if crate::func::exception_pending(caller_vmctx) {
+                crate::func::raise_pending_exception(caller_vmctx)
+            }
+        }
 
         // If a trap was raised (an error returned from the imported function)
         // then we smuggle the trap through `Box<dyn Error>` through to the
@@ -134,6 +140,8 @@ pub unsafe fn create_raw_function(
             host_state,
             store: StorePtr::empty(),
             wasm_data: &[],
+            memory_images: None,
+            unique_id: None,
         })?,
     )
 }
@@ -1,14 +1,26 @@
 use crate::memory::{LinearMemory, MemoryCreator};
 use crate::store::{InstanceId, StoreOpaque};
-use crate::trampoline::create_handle;
+use crate::trampoline::create_handle_with_mem_creator;
 use crate::MemoryType;
 use anyhow::{anyhow, Result};
 use std::convert::TryFrom;
 use std::sync::Arc;
 use wasmtime_environ::{EntityIndex, MemoryPlan, MemoryStyle, Module, WASM_PAGE_SIZE};
-use wasmtime_runtime::{RuntimeLinearMemory, RuntimeMemoryCreator, VMMemoryDefinition};
+use wasmtime_runtime::{
+    MemoryImage, RuntimeLinearMemory, RuntimeMemoryCreator, SharedMemory, VMMemoryDefinition,
+};
 
-pub fn create_memory(store: &mut StoreOpaque, memory: &MemoryType) -> Result<InstanceId> {
+/// Creates a host instance in `store` defining a single memory of type
+/// `memory`.
+///
+/// If `preallocation` is provided then the instance's memory will be that
+/// shared memory rather than a fresh allocation, which is how a shared memory
+/// is imported into any number of stores.
+pub fn create_memory(
+    store: &mut StoreOpaque,
+    memory: &MemoryType,
+    preallocation: Option<&SharedMemory>,
+) -> Result<InstanceId> {
     let mut module = Module::new();
 
     let memory_plan = wasmtime_environ::MemoryPlan::for_memory(
@@ -20,7 +32,27 @@ pub fn create_memory(store: &mut StoreOpaque, memory: &MemoryType) -> Result<Ins
         .exports
         .insert(String::new(), EntityIndex::Memory(memory_id));
 
-    create_handle(module, store, Box::new(()), &[], None)
+    let mem_creator = match preallocation {
+        Some(shared) => Some(Arc::new(SharedMemoryCreator(shared.clone())) as _),
+        None => store.engine().config().mem_creator.clone(),
+    };
+    create_handle_with_mem_creator(module, store, Box::new(()), &[], None, mem_creator)
+}
+
+/// A memory creator which hands out an existing shared memory instead of
+/// allocating a new one.
+struct SharedMemoryCreator(SharedMemory);
+
+impl RuntimeMemoryCreator for SharedMemoryCreator {
+    fn new_memory(
+        &self,
+        _plan: &MemoryPlan,
+        _minimum: usize,
+        _maximum: Option<usize>,
+        _memory_image: Option<&Arc<MemoryImage>>,
+    ) -> Result<Box<dyn RuntimeLinearMemory>> {
+        Ok(Box::new(self.0.clone()))
+    }
 }
 
 struct LinearMemoryProxy {
@@ -57,7 +89,10 @@ impl RuntimeMemoryCreator for MemoryCreatorProxy {
         plan: &MemoryPlan,
         minimum: usize,
         maximum: Option<usize>,
+        _memory_image: Option<&Arc<MemoryImage>>,
     ) -> Result<Box<dyn RuntimeLinearMemory>> {
+        // Custom memory creators can't make use of memory images, so the
+        // memories they create are always initialized by copying data.
         let ty = MemoryType::from_wasmtime_memory(&plan.memory);
         let reserved_size_in_bytes = match plan.style {
             MemoryStyle::Static { bound } => {
@@ -0,0 +1,41 @@
+use crate::store::{InstanceId, StoreOpaque};
+use crate::trampoline::create_handle;
+use crate::{Engine, TagType};
+use anyhow::Result;
+use wasmtime_environ::{EntityIndex, Module, SignatureIndex};
+use wasmtime_runtime::VMSharedSignatureIndex;
+
+/// Keeps the signature of a host-defined tag registered for as long as the tag
+/// exists.
+struct TagState {
+    engine: Engine,
+    sig: VMSharedSignatureIndex,
+}
+
+impl Drop for TagState {
+    fn drop(&mut self) {
+        unsafe {
+            self.engine.signatures().unregister(self.sig);
+        }
+    }
+}
+
+pub fn create_tag(store: &mut StoreOpaque, tag: &TagType) -> Result<InstanceId> {
+    let mut module = Module::new();
+    let sig = store
+        .engine()
+        .signatures()
+        .register(tag.as_wasm_func_type());
+    let state = TagState {
+        engine: store.engine().clone(),
+        sig,
+    };
+
+    let sig_id = SignatureIndex::from_u32(u32::max_value() - 1);
+    let tag_id = module.tags.push(sig_id);
+    module
+        .exports
+        .insert(String::new(), EntityIndex::Tag(tag_id));
+
+    create_handle(module, store, Box::new(state), &[], Some(sig))
+}
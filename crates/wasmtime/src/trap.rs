@@ -1,9 +1,10 @@
-use crate::module::GlobalModuleRegistry;
-use crate::FrameInfo;
+use crate::module::{GlobalModuleRegistry, ModuleRegistry};
+use crate::{AsContext, Exception, FrameInfo, StoreContextMut, WasmCoreDump};
 use backtrace::Backtrace;
 use std::fmt;
 use std::sync::Arc;
 use wasmtime_environ::TrapCode as EnvTrapCode;
+use wasmtime_runtime::CoreDumpFrameState;
 
 /// A struct representing an aborted instruction execution, with a message
 /// indicating the cause.
@@ -26,6 +27,9 @@ enum TrapReason {
 
     /// A specific code for a trap triggered while executing WASM.
     InstructionTrap(TrapCode),
+
+    /// A WebAssembly exception which wasn't caught.
+    Exception(Exception),
 }
 
 impl fmt::Display for TrapReason {
@@ -35,6 +39,7 @@ impl fmt::Display for TrapReason {
             TrapReason::I32Exit(status) => write!(f, "Exited with i32 exit status {}", status),
             TrapReason::Error(e) => write!(f, "{}", e),
             TrapReason::InstructionTrap(code) => write!(f, "wasm trap: {}", code),
+            TrapReason::Exception(e) => write!(f, "{}", e),
         }
     }
 }
@@ -128,9 +133,9 @@ impl fmt::Display for TrapCode {
 
 struct TrapInner {
     reason: TrapReason,
-    wasm_trace: Vec<FrameInfo>,
+    wasm_trace: WasmBacktrace,
     native_trace: Backtrace,
-    hint_wasm_backtrace_details_env: bool,
+    coredump: Option<WasmCoreDump>,
 }
 
 fn _assert_trap_is_sync_and_send(t: &Trap) -> (&dyn Sync, &dyn Send) {
@@ -161,6 +166,13 @@ impl Trap {
         )
     }
 
+    /// Creates a new `Trap` for an exception thrown by wasm which wasn't
+    /// caught, with the backtrace captured when it was thrown.
+    #[cold] // see Trap::new
+    pub(crate) fn from_exception(exception: Exception, backtrace: Backtrace) -> Self {
+        Trap::new_with_trace(None, TrapReason::Exception(exception), backtrace)
+    }
+
     #[cold] // see Trap::new
     pub(crate) fn from_runtime_box(runtime_trap: Box<wasmtime_runtime::Trap>) -> Self {
         Self::from_runtime(*runtime_trap)
@@ -193,6 +205,11 @@ impl Trap {
                 let reason = TrapReason::Message("out of memory".to_string());
                 Trap::new_with_trace(None, reason, backtrace)
             }
+            // Converting these needs the store, so it's done when returning
+            // from wasm instead.
+            wasmtime_runtime::Trap::Exception(_) => {
+                unreachable!("exceptions are converted by `invoke_wasm_and_catch_traps`")
+            }
         }
     }
 
@@ -219,53 +236,54 @@ impl Trap {
     ///   occurred, and this will iterate over the frames to find frames that
     ///   lie in wasm jit code.
     fn new_with_trace(trap_pc: Option<usize>, reason: TrapReason, native_trace: Backtrace) -> Self {
-        let mut wasm_trace = Vec::new();
-        let mut hint_wasm_backtrace_details_env = false;
-
-        GlobalModuleRegistry::with(|registry| {
-            for frame in native_trace.frames() {
-                let pc = frame.ip() as usize;
-                if pc == 0 {
-                    continue;
-                }
-                // Note that we need to be careful about the pc we pass in
-                // here to lookup frame information. This program counter is
-                // used to translate back to an original source location in
-                // the origin wasm module. If this pc is the exact pc that
-                // the trap happened at, then we look up that pc precisely.
-                // Otherwise backtrace information typically points at the
-                // pc *after* the call instruction (because otherwise it's
-                // likely a call instruction on the stack). In that case we
-                // want to lookup information for the previous instruction
-                // (the call instruction) so we subtract one as the lookup.
-                let pc_to_lookup = if Some(pc) == trap_pc { pc } else { pc - 1 };
-                if let Some((info, has_unparsed_debuginfo, wasm_backtrace_details_env_used)) =
-                    registry.lookup_frame_info(pc_to_lookup)
-                {
-                    wasm_trace.push(info);
+        let wasm_trace = WasmBacktrace::from_native(trap_pc, &native_trace, None);
+        Trap::new_with_backtrace(reason, wasm_trace, native_trace)
+    }
 
-                    // If this frame has unparsed debug information and the
-                    // store's configuration indicates that we were
-                    // respecting the environment variable of whether to
-                    // do this then we will print out a helpful note in
-                    // `Display` to indicate that more detailed information
-                    // in a trap may be available.
-                    if has_unparsed_debuginfo && wasm_backtrace_details_env_used {
-                        hint_wasm_backtrace_details_env = true;
-                    }
-                }
-            }
-        });
+    fn new_with_backtrace(
+        reason: TrapReason,
+        wasm_trace: WasmBacktrace,
+        native_trace: Backtrace,
+    ) -> Self {
         Trap {
             inner: Arc::new(TrapInner {
                 reason,
                 wasm_trace,
                 native_trace,
-                hint_wasm_backtrace_details_env,
+                coredump: None,
             }),
         }
     }
 
+    /// Captures a core dump of `store` for this trap, if it doesn't already
+    /// have one, using the `frames` recorded by wasm when it trapped.
+    ///
+    /// Traps which have no wasm frames, or which have been shared with other
+    /// `Trap` values by cloning, are left as-is.
+    #[cold] // see Trap::new
+    pub(crate) fn with_coredump<T>(
+        mut self,
+        store: &mut StoreContextMut<'_, T>,
+        frames: Vec<CoreDumpFrameState>,
+    ) -> Self {
+        if self.inner.coredump.is_some()
+            || self.inner.wasm_trace.frames.is_empty()
+            || Arc::strong_count(&self.inner) != 1
+        {
+            return self;
+        }
+        let coredump = WasmCoreDump::new(
+            store,
+            &self.inner.wasm_trace.frames,
+            &self.inner.wasm_trace.modules,
+            frames,
+        );
+        if let Some(inner) = Arc::get_mut(&mut self.inner) {
+            inner.coredump = Some(coredump);
+        }
+        self
+    }
+
     /// If the trap was the result of an explicit program exit with a classic
     /// `i32` exit status value, return the value, otherwise return `None`.
     pub fn i32_exit_status(&self) -> Option<i32> {
@@ -288,7 +306,7 @@ impl Trap {
     /// Returns a list of function frames in WebAssembly code that led to this
     /// trap happening.
     pub fn trace(&self) -> &[FrameInfo] {
-        &self.inner.wasm_trace
+        self.inner.wasm_trace.frames()
     }
 
     /// Code of a trap that happened while executing a WASM instruction.
@@ -299,6 +317,25 @@ impl Trap {
             _ => None,
         }
     }
+
+    /// Returns the core dump captured when this trap happened.
+    ///
+    /// This is only available if
+    /// [`Config::coredump_on_trap`](crate::Config::coredump_on_trap) was
+    /// enabled and the trap happened while executing WebAssembly.
+    pub fn coredump(&self) -> Option<&WasmCoreDump> {
+        self.inner.coredump.as_ref()
+    }
+
+    /// If the trap is a WebAssembly exception which wasn't caught, or is to be
+    /// thrown into wasm by a host function, return the exception, otherwise
+    /// return `None`.
+    pub fn exception(&self) -> Option<&Exception> {
+        match &self.inner.reason {
+            TrapReason::Exception(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Debug for Trap {
@@ -314,12 +351,152 @@ impl fmt::Debug for Trap {
 impl fmt::Display for Trap {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.inner.reason)?;
-        let trace = self.trace();
-        if trace.is_empty() {
+        if self.trace().is_empty() {
             return Ok(());
         }
-        writeln!(f, "\nwasm backtrace:")?;
-        for (i, frame) in self.trace().iter().enumerate() {
+        write!(f, "\n{}", self.inner.wasm_trace)
+    }
+}
+
+/// A backtrace of the WebAssembly frames on the stack.
+///
+/// Backtraces are captured automatically when a [`Trap`] is created and are
+/// available through [`Trap::trace`], but they can also be captured on demand
+/// with [`WasmBacktrace::capture`], for example to find out which wasm called
+/// a host function.
+///
+/// Errors which a host function converts into a [`Trap`] get the backtrace of
+/// the wasm which called it automatically. A backtrace can also be attached to
+/// an [`anyhow::Error`] explicitly with [`anyhow::Context`], in which case it's
+/// used as the trap's [`Trap::trace`] and the trap's message is still that of
+/// the error it was attached to.
+#[derive(Clone)]
+pub struct WasmBacktrace {
+    frames: Vec<FrameInfo>,
+    /// The module each of `frames` belongs to.
+    modules: Vec<Arc<wasmtime_environ::Module>>,
+    hint_wasm_backtrace_details_env: bool,
+}
+
+impl WasmBacktrace {
+    /// Captures the WebAssembly frames of `store` that are currently on the
+    /// stack.
+    ///
+    /// This is typically called from within a host function with its
+    /// [`Caller`](crate::Caller), in which case the youngest frame is the wasm
+    /// that called the host function. Frames belonging to other stores are
+    /// omitted, and if no wasm is executing then the backtrace is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmtime::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let engine = Engine::default();
+    /// let module = Module::new(
+    ///     &engine,
+    ///     r#"
+    ///         (module
+    ///             (import "" "log" (func $log))
+    ///             (func (export "run") (call $log)))
+    ///     "#,
+    /// )?;
+    /// let mut store = Store::new(&engine, ());
+    /// let log = Func::wrap(&mut store, |caller: Caller<'_, ()>| {
+    ///     let backtrace = WasmBacktrace::capture(&caller);
+    ///     assert_eq!(backtrace.frames().len(), 1);
+    ///     assert_eq!(backtrace.frames()[0].func_index(), 1);
+    /// });
+    /// let instance = Instance::new(&mut store, &module, &[log.into()])?;
+    /// let run = instance.get_typed_func::<(), (), _>(&mut store, "run")?;
+    /// run.call(&mut store, ())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capture(store: impl AsContext) -> WasmBacktrace {
+        let store = store.as_context();
+        WasmBacktrace::from_native(None, &Backtrace::new_unresolved(), Some(store.0.modules()))
+    }
+
+    /// Returns the list of WebAssembly frames in this backtrace, youngest
+    /// first.
+    pub fn frames(&self) -> &[FrameInfo] {
+        &self.frames
+    }
+
+    /// Finds the wasm frames within `native_trace`.
+    ///
+    /// See `Trap::new_with_trace` for the meaning of `trap_pc`. If `store_modules`
+    /// is provided then only frames of modules registered with it are kept.
+    fn from_native(
+        trap_pc: Option<usize>,
+        native_trace: &Backtrace,
+        store_modules: Option<&ModuleRegistry>,
+    ) -> WasmBacktrace {
+        let mut frames = Vec::new();
+        let mut modules = Vec::new();
+        let mut hint_wasm_backtrace_details_env = false;
+
+        GlobalModuleRegistry::with(|registry| {
+            for frame in native_trace.frames() {
+                let pc = frame.ip() as usize;
+                if pc == 0 {
+                    continue;
+                }
+                // Note that we need to be careful about the pc we pass in
+                // here to lookup frame information. This program counter is
+                // used to translate back to an original source location in
+                // the origin wasm module. If this pc is the exact pc that
+                // the trap happened at, then we look up that pc precisely.
+                // Otherwise backtrace information typically points at the
+                // pc *after* the call instruction (because otherwise it's
+                // likely a call instruction on the stack). In that case we
+                // want to lookup information for the previous instruction
+                // (the call instruction) so we subtract one as the lookup.
+                let pc_to_lookup = if Some(pc) == trap_pc { pc } else { pc - 1 };
+                if let Some(store_modules) = store_modules {
+                    if store_modules.lookup_module(pc_to_lookup).is_none() {
+                        continue;
+                    }
+                }
+                if let Some((info, has_unparsed_debuginfo, wasm_backtrace_details_env_used)) =
+                    registry.lookup_frame_info(pc_to_lookup)
+                {
+                    frames.push(info);
+                    modules.push(registry.lookup_module_info(pc_to_lookup).unwrap().clone());
+
+                    // If this frame has unparsed debug information and the
+                    // store's configuration indicates that we were
+                    // respecting the environment variable of whether to
+                    // do this then we will print out a helpful note in
+                    // `Display` to indicate that more detailed information
+                    // in a trap may be available.
+                    if has_unparsed_debuginfo && wasm_backtrace_details_env_used {
+                        hint_wasm_backtrace_details_env = true;
+                    }
+                }
+            }
+        });
+        WasmBacktrace {
+            frames,
+            modules,
+            hint_wasm_backtrace_details_env,
+        }
+    }
+}
+
+impl fmt::Debug for WasmBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WasmBacktrace")
+            .field("frames", &self.frames)
+            .finish()
+    }
+}
+
+impl fmt::Display for WasmBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "wasm backtrace:")?;
+        for (i, frame) in self.frames.iter().enumerate() {
             let name = frame.module_name().unwrap_or("<unknown>");
             write!(f, "  {:>3}: ", i)?;
 
@@ -369,7 +546,7 @@ impl fmt::Display for Trap {
                 }
             }
         }
-        if self.inner.hint_wasm_backtrace_details_env {
+        if self.hint_wasm_backtrace_details_env {
             writeln!(f, "note: using the `WASMTIME_BACKTRACE_DETAILS=1` environment variable to may show more debugging information")?;
         }
         Ok(())
@@ -380,22 +557,91 @@ impl std::error::Error for Trap {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match &self.inner.reason {
             TrapReason::Error(e) => e.source(),
-            TrapReason::I32Exit(_) | TrapReason::Message(_) | TrapReason::InstructionTrap(_) => {
-                None
-            }
+            TrapReason::I32Exit(_)
+            | TrapReason::Message(_)
+            | TrapReason::InstructionTrap(_)
+            | TrapReason::Exception(_) => None,
         }
     }
 }
 
+impl From<Exception> for Trap {
+    fn from(e: Exception) -> Trap {
+        Trap::new_with_trace(None, TrapReason::Exception(e), Backtrace::new_unresolved())
+    }
+}
+
 impl From<anyhow::Error> for Trap {
     fn from(e: anyhow::Error) -> Trap {
         match e.downcast::<Trap>() {
             Ok(trap) => trap,
-            Err(e) => Box::<dyn std::error::Error + Send + Sync>::from(e).into(),
+            Err(e) => match e.downcast::<Exception>() {
+                Ok(exception) => exception.into(),
+                Err(e) => match e.downcast_ref::<WasmBacktrace>() {
+                    Some(wasm_trace) => {
+                        let wasm_trace = wasm_trace.clone();
+                        let reason = TrapReason::Error(Box::new(HostError::new(e, &wasm_trace)));
+                        Trap::new_with_backtrace(reason, wasm_trace, Backtrace::new_unresolved())
+                    }
+                    None => Box::<dyn std::error::Error + Send + Sync>::from(e).into(),
+                },
+            },
         }
     }
 }
 
+/// An error returned by a host function with a [`WasmBacktrace`] attached as
+/// context.
+///
+/// The backtrace becomes the trap's trace, so it's skipped when displaying the
+/// error; otherwise it would replace the error's message and be printed twice.
+struct HostError {
+    error: anyhow::Error,
+    /// The index of the backtrace's context within `error.chain()`.
+    backtrace: Option<usize>,
+}
+
+impl HostError {
+    fn new(error: anyhow::Error, wasm_trace: &WasmBacktrace) -> HostError {
+        // Contexts can't be downcast from the chain, so the backtrace's is
+        // found by what it displays instead.
+        let wasm_trace = wasm_trace.to_string();
+        let backtrace = error.chain().position(|e| e.to_string() == wasm_trace);
+        HostError { error, backtrace }
+    }
+
+    /// The errors of the chain, outermost first, without the backtrace.
+    fn chain(&self) -> impl Iterator<Item = &(dyn std::error::Error + 'static)> {
+        let backtrace = self.backtrace;
+        self.error
+            .chain()
+            .enumerate()
+            .filter(move |(i, _)| Some(*i) != backtrace)
+            .map(|(_, e)| e)
+    }
+}
+
+impl fmt::Debug for HostError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.error, f)
+    }
+}
+
+impl fmt::Display for HostError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.chain().next() {
+            Some(e) => write!(f, "{}", e),
+            None => write!(f, "{}", self.error.root_cause()),
+        }
+    }
+}
+
+impl std::error::Error for HostError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.chain().nth(1)
+    }
+}
+
 impl From<Box<dyn std::error::Error + Send + Sync>> for Trap {
     fn from(e: Box<dyn std::error::Error + Send + Sync>) -> Trap {
         // If the top-level error is already a trap, don't be redundant and just return it.
@@ -119,6 +119,8 @@ pub enum ExternType {
     Instance(InstanceType),
     /// This external type is the type of a WebAssembly module.
     Module(ModuleType),
+    /// This external type is the type of a WebAssembly exception tag.
+    Tag(TagType),
 }
 
 macro_rules! accessors {
@@ -153,6 +155,7 @@ impl ExternType {
         (Memory(MemoryType) memory unwrap_memory)
         (Module(ModuleType) module unwrap_module)
         (Instance(InstanceType) instance unwrap_instance)
+        (Tag(TagType) tag unwrap_tag)
     }
 
     pub(crate) fn from_wasmtime(types: &TypeTables, ty: &EntityType) -> ExternType {
@@ -171,7 +174,9 @@ impl ExternType {
                 let ty = &types.instance_signatures[*ty];
                 InstanceType::from_wasmtime(types, ty).into()
             }
-            EntityType::Tag(_) => unimplemented!("wasm tag support"),
+            EntityType::Tag(idx) => {
+                TagType::from_wasm_func_type(types.wasm_signatures[*idx].clone()).into()
+            }
         }
     }
 }
@@ -212,6 +217,12 @@ impl From<InstanceType> for ExternType {
     }
 }
 
+impl From<TagType> for ExternType {
+    fn from(ty: TagType) -> ExternType {
+        ExternType::Tag(ty)
+    }
+}
+
 /// A descriptor for a function in a WebAssembly module.
 ///
 /// WebAssembly functions can have 0 or more parameters and results.
@@ -258,6 +269,44 @@ impl FuncType {
     }
 }
 
+// Tag Types
+
+/// A descriptor for an exception tag in a WebAssembly module.
+///
+/// Tags have a list of parameters, which are the types of the values carried
+/// by exceptions thrown with the tag.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct TagType {
+    sig: WasmFuncType,
+}
+
+impl TagType {
+    /// Creates a new tag descriptor whose exceptions carry values of the
+    /// types in `params`.
+    pub fn new(params: impl IntoIterator<Item = ValType>) -> TagType {
+        TagType {
+            sig: WasmFuncType::new(
+                params.into_iter().map(|t| t.to_wasm_type()).collect(),
+                Box::new([]),
+            ),
+        }
+    }
+
+    /// Returns the list of parameter types for this tag.
+    #[inline]
+    pub fn params(&self) -> impl ExactSizeIterator<Item = ValType> + '_ {
+        self.sig.params().iter().map(ValType::from_wasm_type)
+    }
+
+    pub(crate) fn as_wasm_func_type(&self) -> &WasmFuncType {
+        &self.sig
+    }
+
+    pub(crate) fn from_wasm_func_type(sig: WasmFuncType) -> TagType {
+        Self { sig }
+    }
+}
+
 // Global Types
 
 /// A WebAssembly global descriptor.
@@ -403,6 +452,26 @@ impl MemoryType {
         }
     }
 
+    /// Creates a new descriptor for a 32-bit shared WebAssembly memory given
+    /// the specified limits of the memory.
+    ///
+    /// The `minimum` and `maximum` values here are specified in units of
+    /// WebAssembly pages, which are 64k. Shared memories must always have a
+    /// maximum size.
+    ///
+    /// Note that shared memories are part of the threads proposal for
+    /// WebAssembly which is not standardized yet.
+    pub fn shared(minimum: u32, maximum: u32) -> MemoryType {
+        MemoryType {
+            ty: Memory {
+                memory64: false,
+                shared: true,
+                minimum: minimum.into(),
+                maximum: Some(maximum.into()),
+            },
+        }
+    }
+
     /// Returns whether this is a 64-bit memory or not.
     ///
     /// Note that 64-bit memories are part of the memory64 proposal for
@@ -411,6 +480,14 @@ impl MemoryType {
         self.ty.memory64
     }
 
+    /// Returns whether this is a shared memory or not.
+    ///
+    /// Note that shared memories are part of the threads proposal for
+    /// WebAssembly which is not standardized yet.
+    pub fn is_shared(&self) -> bool {
+        self.ty.shared
+    }
+
     /// Returns minimum number of WebAssembly pages this memory must have.
     ///
     /// Note that the return value, while a `u64`, will always fit into a `u32`
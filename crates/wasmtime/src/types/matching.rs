@@ -67,6 +67,10 @@ impl MatchCx<'_> {
         )
     }
 
+    pub fn shared_memory(&self, expected: &Memory, actual: &crate::SharedMemory) -> Result<()> {
+        self.memory_ty(expected, actual.ty().wasmtime_memory(), Some(actual.size()))
+    }
+
     fn memory_ty(
         &self,
         expected: &Memory,
@@ -101,6 +105,17 @@ impl MatchCx<'_> {
         self.vmshared_signature_index(expected, actual.sig_index(self.store.store_data()))
     }
 
+    pub fn tag(&self, expected: SignatureIndex, actual: &crate::Tag) -> Result<()> {
+        let matches = match self.signatures.shared_signature(expected) {
+            Some(idx) => actual.sig_index(self.store) == idx,
+            None => false,
+        };
+        if !matches {
+            bail!("tag types incompatible");
+        }
+        Ok(())
+    }
+
     pub(crate) fn host_func(
         &self,
         expected: SignatureIndex,
@@ -352,7 +367,17 @@ impl MatchCx<'_> {
                 }
                 _ => bail!("expected module, but found {}", actual_desc),
             },
-            EntityType::Tag(_) => unimplemented!(),
+            EntityType::Tag(expected) => match *actual_ty {
+                EntityType::Tag(actual) => {
+                    if self.types.wasm_signatures[*expected] == actual_types.wasm_signatures[actual]
+                    {
+                        Ok(())
+                    } else {
+                        bail!("tag types incompatible")
+                    }
+                }
+                _ => bail!("expected tag, but found {}", actual_desc),
+            },
         }
     }
 
@@ -369,6 +394,7 @@ impl MatchCx<'_> {
             },
             EntityType::Memory(expected) => match actual {
                 Extern::Memory(actual) => self.memory(expected, actual),
+                Extern::SharedMemory(actual) => self.shared_memory(expected, actual),
                 _ => bail!("expected memory, but found {}", actual.desc()),
             },
             EntityType::Function(expected) => match actual {
@@ -383,7 +409,10 @@ impl MatchCx<'_> {
                 Extern::Module(actual) => self.module(*expected, actual),
                 _ => bail!("expected module, but found {}", actual.desc()),
             },
-            EntityType::Tag(_) => unimplemented!(),
+            EntityType::Tag(expected) => match actual {
+                Extern::Tag(actual) => self.tag(*expected, actual),
+                _ => bail!("expected tag, but found {}", actual.desc()),
+            },
         }
     }
 
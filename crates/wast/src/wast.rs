@@ -205,6 +205,17 @@ impl<T> WastContext<T> {
         bail!("expected '{}', got '{}'", expected, actual)
     }
 
+    fn assert_exception(&self, result: Outcome) -> Result<()> {
+        let trap = match result {
+            Outcome::Ok(values) => bail!("expected exception, got {:?}", values),
+            Outcome::Trap(t) => t,
+        };
+        if trap.exception().is_none() {
+            bail!("expected exception, got '{}'", trap);
+        }
+        Ok(())
+    }
+
     /// Run a wast script from a byte buffer.
     pub fn run_buffer(&mut self, filename: &str, wast: &[u8]) -> Result<()> {
         let wast = str::from_utf8(wast)?;
@@ -349,7 +360,10 @@ impl<T> WastContext<T> {
                     )
                 }
             }
-            AssertException { .. } => bail!("unimplemented assert_exception"),
+            AssertException { span: _, exec } => {
+                let result = self.perform_execute(exec)?;
+                self.assert_exception(result)?;
+            }
         }
 
         Ok(())
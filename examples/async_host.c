@@ -0,0 +1,199 @@
+/*
+Example of an async host function which suspends WebAssembly while it waits,
+and which can fail with a trap once it resumes.
+
+You can compile and run this example on Linux with:
+
+   cargo build --release -p wasmtime-c-api
+   cc examples/async_host.c \
+       -I crates/c-api/include \
+       -I crates/c-api/wasm-c-api/include \
+       target/release/libwasmtime.a \
+       -lpthread -ldl -lm \
+       -o async_host
+   ./async_host
+
+Note that on Windows and macOS the command will be similar, but you'll need
+to tweak the `-lpthread` and such annotations.
+*/
+
+#include <assert.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <wasm.h>
+#include <wasmtime.h>
+
+static void exit_with_error(const char *message, wasmtime_error_t *error, wasm_trap_t *trap);
+
+// The state of one call to `fetch`, which finishes after a few polls.
+typedef struct fetch_call {
+  int polls_left;
+  int32_t arg;
+  wasmtime_val_t *results;
+  wasm_trap_t **trap_ret;
+} fetch_call_t;
+
+static bool fetch_poll(void *env) {
+  fetch_call_t *call = env;
+  if (call->polls_left > 0) {
+    call->polls_left--;
+    return false;
+  }
+
+  // Both the results and the trap are written from the continuation, after
+  // the callback which started the call has returned.
+  if (call->arg == 0) {
+    const char *message = "fetch failed";
+    *call->trap_ret = wasmtime_trap_new(message, strlen(message));
+  } else {
+    call->results[0].kind = WASMTIME_I32;
+    call->results[0].of.i32 = call->arg * 2;
+  }
+  return true;
+}
+
+static void fetch(
+    void *env,
+    wasmtime_caller_t *caller,
+    const wasmtime_val_t *args,
+    size_t nargs,
+    wasmtime_val_t *results,
+    size_t nresults,
+    wasm_trap_t **trap_ret,
+    wasmtime_async_continuation_t *continuation_ret) {
+  fetch_call_t *call = malloc(sizeof(fetch_call_t));
+  assert(call != NULL);
+  call->polls_left = 3;
+  call->arg = args[0].of.i32;
+  call->results = results;
+  call->trap_ret = trap_ret;
+
+  continuation_ret->callback = fetch_poll;
+  continuation_ret->env = call;
+  continuation_ret->finalizer = free;
+}
+
+// Polls `future` until it completes, returning how many times it suspended.
+static int poll_to_completion(wasmtime_call_future_t *future) {
+  int suspensions = 0;
+  while (!wasmtime_call_future_poll(future))
+    suspensions++;
+  wasmtime_call_future_delete(future);
+  return suspensions;
+}
+
+int main() {
+  wasmtime_error_t *error = NULL;
+  wasm_trap_t *trap = NULL;
+
+  wasm_config_t *config = wasm_config_new();
+  assert(config != NULL);
+  wasmtime_config_async_support_set(config, true);
+
+  wasm_engine_t *engine = wasm_engine_new_with_config(config);
+  assert(engine != NULL);
+  wasmtime_store_t *store = wasmtime_store_new(engine, NULL, NULL);
+  assert(store != NULL);
+  wasmtime_context_t *context = wasmtime_store_context(store);
+
+  // Define `fetch` as an async host function
+  wasmtime_linker_t *linker = wasmtime_linker_new(engine);
+  wasm_functype_t *fetch_ty = wasm_functype_new_1_1(wasm_valtype_new_i32(), wasm_valtype_new_i32());
+  error = wasmtime_linker_define_async_func(linker, "host", 4, "fetch", 5, fetch_ty, fetch, NULL, NULL);
+  if (error != NULL)
+    exit_with_error("failed to define fetch", error, NULL);
+  wasm_functype_delete(fetch_ty);
+
+  // Load our input file to parse it next
+  FILE* file = fopen("examples/async_host.wat", "r");
+  if (!file) {
+    printf("> Error loading file!\n");
+    return 1;
+  }
+  fseek(file, 0L, SEEK_END);
+  size_t file_size = ftell(file);
+  fseek(file, 0L, SEEK_SET);
+  wasm_byte_vec_t wat;
+  wasm_byte_vec_new_uninitialized(&wat, file_size);
+  if (fread(wat.data, file_size, 1, file) != 1) {
+    printf("> Error loading module!\n");
+    return 1;
+  }
+  fclose(file);
+
+  // Parse the wat into the binary wasm format
+  wasm_byte_vec_t wasm;
+  error = wasmtime_wat2wasm(wat.data, wat.size, &wasm);
+  if (error != NULL)
+    exit_with_error("failed to parse wat", error, NULL);
+  wasm_byte_vec_delete(&wat);
+
+  // Compile and instantiate our module
+  wasmtime_module_t *module = NULL;
+  error = wasmtime_module_new(engine, (uint8_t*) wasm.data, wasm.size, &module);
+  if (module == NULL)
+    exit_with_error("failed to compile module", error, NULL);
+  wasm_byte_vec_delete(&wasm);
+
+  wasmtime_instance_t instance;
+  poll_to_completion(wasmtime_linker_instantiate_async(linker, context, module, &instance, &trap, &error));
+  if (error != NULL || trap != NULL)
+    exit_with_error("failed to instantiate", error, trap);
+
+  // Lookup our `run` export function
+  wasmtime_extern_t run;
+  bool ok = wasmtime_instance_export_get(context, &instance, "run", strlen("run"), &run);
+  assert(ok);
+  assert(run.kind == WASMTIME_EXTERN_FUNC);
+
+  // A successful call suspends while `fetch` is pending
+  wasmtime_val_t params[1];
+  params[0].kind = WASMTIME_I32;
+  params[0].of.i32 = 21;
+  wasmtime_val_t results[1];
+  int suspensions = poll_to_completion(
+      wasmtime_func_call_async(context, &run.of.func, params, 1, results, 1, &trap, &error));
+  if (error != NULL || trap != NULL)
+    exit_with_error("failed to call run", error, trap);
+  assert(suspensions == 3);
+  assert(results[0].kind == WASMTIME_I32);
+  assert(results[0].of.i32 == 43);
+  printf("run(21) = %d after %d suspensions\n", results[0].of.i32, suspensions);
+
+  // A failing call raises the trap written by the continuation
+  params[0].of.i32 = 0;
+  poll_to_completion(
+      wasmtime_func_call_async(context, &run.of.func, params, 1, results, 1, &trap, &error));
+  if (error != NULL)
+    exit_with_error("failed to call run", error, NULL);
+  assert(trap != NULL);
+  wasm_byte_vec_t message;
+  wasm_trap_message(trap, &message);
+  char message_str[1024];
+  snprintf(message_str, sizeof(message_str), "%.*s", (int) message.size, message.data);
+  wasm_byte_vec_delete(&message);
+  printf("run(0) trapped: %s\n", message_str);
+  assert(strstr(message_str, "fetch failed") != NULL);
+  wasm_trap_delete(trap);
+
+  // Clean up after ourselves at this point
+  wasmtime_module_delete(module);
+  wasmtime_linker_delete(linker);
+  wasmtime_store_delete(store);
+  wasm_engine_delete(engine);
+  return 0;
+}
+
+static void exit_with_error(const char *message, wasmtime_error_t *error, wasm_trap_t *trap) {
+  fprintf(stderr, "error: %s\n", message);
+  wasm_byte_vec_t error_message;
+  if (error != NULL) {
+    wasmtime_error_message(error, &error_message);
+  } else {
+    wasm_trap_message(trap, &error_message);
+  }
+  fprintf(stderr, "%.*s\n", (int) error_message.size, error_message.data);
+  wasm_byte_vec_delete(&error_message);
+  exit(1);
+}
@@ -0,0 +1,46 @@
+//! Example of an async host function which suspends WebAssembly while it
+//! waits, and which can fail with a trap once it resumes.
+
+// You can execute this example with `cargo run --example async_host`
+
+use anyhow::Result;
+use wasmtime::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let mut config = Config::new();
+    config.async_support(true);
+    let engine = Engine::new(&config)?;
+    let mut store = Store::new(&engine, ());
+
+    // `fetch` yields to the executor a few times before producing its result,
+    // standing in for some I/O. Fetching `0` fails with a trap.
+    let mut linker = Linker::new(&engine);
+    let ty = FuncType::new([ValType::I32], [ValType::I32]);
+    linker.func_new_async("host", "fetch", ty, |_caller, params, results| {
+        Box::new(async move {
+            for _ in 0..3 {
+                tokio::task::yield_now().await;
+            }
+            let arg = params[0].unwrap_i32();
+            if arg == 0 {
+                return Err(Trap::new("fetch failed"));
+            }
+            results[0] = Val::I32(arg * 2);
+            Ok(())
+        })
+    })?;
+
+    let module = Module::from_file(&engine, "examples/async_host.wat")?;
+    let instance = linker.instantiate_async(&mut store, &module).await?;
+    let run = instance.get_typed_func::<i32, i32, _>(&mut store, "run")?;
+
+    let result = run.call_async(&mut store, 21).await?;
+    println!("run(21) = {}", result);
+    assert_eq!(result, 43);
+
+    let trap = run.call_async(&mut store, 0).await.unwrap_err();
+    println!("run(0) trapped: {}", trap);
+    assert!(trap.to_string().contains("fetch failed"));
+    Ok(())
+}
@@ -0,0 +1,5 @@
+(module
+  (import "host" "fetch" (func $fetch (param i32) (result i32)))
+  (func (export "run") (param i32) (result i32)
+    (i32.add (call $fetch (local.get 0)) (i32.const 1)))
+)
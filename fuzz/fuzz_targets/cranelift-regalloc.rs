@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use cranelift_codegen::settings::{self, Configurable};
+use cranelift_filetests::function_runner::SingleFunctionCompiler;
+use cranelift_fuzzgen::TestCase;
+
+fuzz_target!(|testcase: TestCase| {
+    // Run the register allocator's symbolic checker over the allocation of
+    // every generated function; compilation panics if it finds an error.
+    let mut builder = settings::builder();
+    builder.set("regalloc_checker", "true").unwrap();
+    let flags = settings::Flags::new(builder);
+
+    let mut compiler = SingleFunctionCompiler::with_host_isa(flags);
+    compiler.compile(testcase.func).unwrap();
+});
@@ -0,0 +1,398 @@
+//! The module that implements the `wasmtime explore` command.
+
+use crate::CommonOptions;
+use anyhow::{anyhow, bail, Context, Result};
+use capstone::prelude::*;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use structopt::{clap::AppSettings, StructOpt};
+use target_lexicon::{Architecture, Triple};
+use wasmtime::{Engine, ExternType, Module};
+
+lazy_static::lazy_static! {
+    static ref AFTER_HELP: String = {
+        format!(
+            "The generated HTML file shows the WebAssembly text format of the module \
+            next to the native code that Cranelift produced for it. Clicking on a \
+            WebAssembly instruction or a native instruction highlights the code \
+            corresponding to it in the other pane.\n\
+            \n\
+            {}\
+            \n\
+            Usage examples:\n\
+            \n\
+            Exploring the code generated for a module:\n\
+            \n  \
+            wasmtime explore example.wasm
+            \n\
+            Specifying the output file:\n\
+            \n  \
+            wasmtime explore -o example.html example.wasm\n\
+            \n\
+            Exploring the code generated with some Cranelift settings:\n\
+            \n  \
+            wasmtime explore --cranelift-set opt_level=none example.wasm\n",
+            crate::FLAG_EXPLANATIONS.as_str()
+        )
+    };
+}
+
+/// Explores the native code generated for a WebAssembly module.
+#[derive(StructOpt)]
+#[structopt(
+    name = "explore",
+    version = env!("CARGO_PKG_VERSION"),
+    setting = AppSettings::ColoredHelp,
+    after_help = AFTER_HELP.as_str()
+)]
+pub struct ExploreCommand {
+    #[structopt(flatten)]
+    common: CommonOptions,
+
+    /// The path of the output HTML file; defaults to <MODULE>.explore.html
+    #[structopt(short = "o", long, value_name = "OUTPUT", parse(from_os_str))]
+    output: Option<PathBuf>,
+
+    /// The path of the WebAssembly module to explore
+    #[structopt(index = 1, value_name = "MODULE", parse(from_os_str))]
+    module: PathBuf,
+}
+
+impl ExploreCommand {
+    /// Executes the command.
+    pub fn execute(mut self) -> Result<()> {
+        self.common.init_logging();
+
+        let mut config = self.common.config(None)?;
+        config.generate_address_map(true);
+
+        let engine = Engine::new(&config)?;
+
+        if self.module.file_name().is_none() {
+            bail!(
+                "'{}' is not a valid input module path",
+                self.module.display()
+            );
+        }
+
+        let input = fs::read(&self.module).with_context(|| "failed to read input file")?;
+        // The offsets in the address map are relative to the binary format.
+        let wasm = wat::parse_bytes(&input)?;
+
+        let output = self.output.take().unwrap_or_else(|| {
+            let mut output: PathBuf = self.module.file_name().unwrap().into();
+            output.set_extension("explore.html");
+            output
+        });
+
+        let html = generate(&engine, &wasm, &self.module.display().to_string())?;
+        fs::write(&output, html)
+            .with_context(|| format!("failed to write '{}'", output.display()))?;
+
+        Ok(())
+    }
+}
+
+/// A line of the WebAssembly text format, or of the disassembly.
+struct Line {
+    /// The offset in the WebAssembly binary of the instruction on this line.
+    wasm_offset: Option<u32>,
+    /// The prefix shown in the gutter: the offset of the WebAssembly
+    /// instruction, or the address and bytes of the native instruction.
+    gutter: String,
+    text: String,
+}
+
+/// Compile `wasm` and render the explorer's HTML page for it.
+fn generate(engine: &Engine, wasm: &[u8], title: &str) -> Result<String> {
+    let module = Module::from_binary(engine, wasm)?;
+    let wat = wat_lines(wasm)?;
+    let asm = asm_lines(&module)?;
+
+    // Only highlight the WebAssembly instructions that native code maps back
+    // to.
+    let mapped: HashSet<u32> = asm.iter().filter_map(|line| line.wasm_offset).collect();
+
+    let mut html = String::new();
+    writeln!(html, "<!DOCTYPE html>")?;
+    writeln!(html, "<html>")?;
+    writeln!(html, "<head>")?;
+    writeln!(html, "<meta charset=\"utf-8\">")?;
+    writeln!(html, "<title>wasmtime explore: {}</title>", escape(title))?;
+    writeln!(html, "<style>{}</style>", STYLE)?;
+    writeln!(html, "</head>")?;
+    writeln!(html, "<body>")?;
+    for (id, lines) in [("wat", &wat), ("asm", &asm)] {
+        writeln!(html, "<div id=\"{}\" class=\"pane\">", id)?;
+        for line in lines.iter() {
+            write_line(&mut html, line, &mapped)?;
+        }
+        writeln!(html, "</div>")?;
+    }
+    writeln!(html, "<script>{}</script>", SCRIPT)?;
+    writeln!(html, "</body>")?;
+    writeln!(html, "</html>")?;
+    Ok(html)
+}
+
+fn write_line(html: &mut String, line: &Line, mapped: &HashSet<u32>) -> Result<()> {
+    match line.wasm_offset.filter(|offset| mapped.contains(offset)) {
+        Some(offset) => write!(
+            html,
+            "<div class=\"line mapped\" data-offset=\"{:x}\" style=\"--hue: {}\">",
+            offset,
+            hue(offset)
+        )?,
+        None => write!(html, "<div class=\"line\">")?,
+    }
+    writeln!(
+        html,
+        "<span class=\"gutter\">{}</span>{}</div>",
+        escape(&line.gutter),
+        escape(&line.text)
+    )?;
+    Ok(())
+}
+
+/// The padding that `wasmprinter` puts in place of the offset of lines which
+/// don't print an item with an offset.
+const OFFSET_PADDING: &str = "           ";
+
+/// Print the WebAssembly text format of `wasm`, annotated with the offset of
+/// each instruction.
+fn wat_lines(wasm: &[u8]) -> Result<Vec<Line>> {
+    let mut printer = wasmprinter::Printer::new();
+    printer.print_offsets(true);
+    let text = printer.print(wasm)?;
+
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        // Lines are prefixed with the offset of the item they print, as in
+        // `(;@1f    ;)`, or with as much padding when there's no such item.
+        let (wasm_offset, text) = match line
+            .strip_prefix("(;@")
+            .and_then(|rest| rest.split_once(";)"))
+        {
+            Some((offset, text)) => (u32::from_str_radix(offset.trim(), 16).ok(), text),
+            None => (None, line.strip_prefix(OFFSET_PADDING).unwrap_or(line)),
+        };
+        lines.push(Line {
+            wasm_offset,
+            gutter: match wasm_offset {
+                Some(offset) => format!("{:#8x}", offset),
+                None => " ".repeat(8),
+            },
+            text: text.to_string(),
+        });
+    }
+    Ok(lines)
+}
+
+/// Disassemble the functions defined by `module`, annotating each native
+/// instruction with the offset of the WebAssembly instruction it was compiled
+/// from.
+fn asm_lines(module: &Module) -> Result<Vec<Line>> {
+    let text = module.text();
+    let address_map: Vec<(usize, Option<u32>)> = module
+        .address_map()
+        .ok_or_else(|| anyhow!("the module was compiled without an address map"))?
+        .collect();
+    let num_imported_funcs = module
+        .imports()
+        .filter(|import| matches!(import.ty(), ExternType::Func(_)))
+        .count();
+    let cs = disassembler(&Triple::host())?;
+
+    let mut lines = Vec::new();
+    for (i, (start, len)) in module.function_locations().enumerate() {
+        if i > 0 {
+            lines.push(Line {
+                wasm_offset: None,
+                gutter: String::new(),
+                text: String::new(),
+            });
+        }
+        lines.push(Line {
+            wasm_offset: None,
+            gutter: String::new(),
+            text: format!(";; function {}", num_imported_funcs + i),
+        });
+
+        let insts = cs
+            .disasm_all(&text[start..][..len], start as u64)
+            .map_err(|e| anyhow!("failed to disassemble function: {}", e))?;
+        for inst in insts.iter() {
+            let address = inst.address() as usize;
+            // Each entry of the address map covers the code up to the next
+            // one, so find the last one starting at or before this address.
+            let index = match address_map.binary_search_by_key(&address, |(offset, _)| *offset) {
+                Ok(index) => Some(index),
+                Err(0) => None,
+                Err(index) => Some(index - 1),
+            };
+            let wasm_offset = index.and_then(|index| address_map[index].1);
+
+            let bytes = inst
+                .bytes()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let mut text = inst.mnemonic().unwrap_or("").to_string();
+            if let Some(op_str) = inst.op_str().filter(|s| !s.is_empty()) {
+                text.push(' ');
+                text.push_str(op_str);
+            }
+            lines.push(Line {
+                wasm_offset,
+                gutter: format!("{:#8x}  {:<24}", address, bytes),
+                text,
+            });
+        }
+    }
+    Ok(lines)
+}
+
+fn disassembler(triple: &Triple) -> Result<Capstone> {
+    let cs = match triple.architecture {
+        Architecture::X86_64 => Capstone::new()
+            .x86()
+            .mode(arch::x86::ArchMode::Mode64)
+            .build(),
+        Architecture::Aarch64 { .. } => Capstone::new()
+            .arm64()
+            .mode(arch::arm64::ArchMode::Arm)
+            .build()
+            .and_then(|mut cs| {
+                // Constants are inlined in the code, so print them as data
+                // rather than stopping at the first one that isn't a valid
+                // instruction.
+                cs.set_skipdata(true)?;
+                Ok(cs)
+            }),
+        Architecture::S390x => Capstone::new()
+            .sysz()
+            .mode(arch::sysz::ArchMode::Default)
+            .build(),
+        _ => bail!(
+            "disassembling {} code is not supported",
+            triple.architecture
+        ),
+    };
+    cs.map_err(|e| anyhow!("failed to create a disassembler: {}", e))
+}
+
+/// Pick a background color for the code of the WebAssembly instruction at
+/// `offset`, so that neighbouring instructions are easy to tell apart.
+fn hue(offset: u32) -> u32 {
+    offset.wrapping_mul(2654435761) % 360
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const STYLE: &str = r#"
+body {
+    display: flex;
+    height: 100vh;
+    margin: 0;
+    font-family: monospace;
+    font-size: 13px;
+}
+.pane {
+    flex: 1;
+    overflow: auto;
+    padding: 8px;
+    white-space: pre;
+}
+#wat {
+    border-right: 1px solid #888;
+}
+.gutter {
+    color: #888;
+    margin-right: 1em;
+}
+.mapped {
+    background: hsl(var(--hue), 80%, 92%);
+    cursor: pointer;
+}
+.selected {
+    background: hsl(var(--hue), 80%, 75%);
+    outline: 1px solid hsl(var(--hue), 80%, 40%);
+}
+"#;
+
+const SCRIPT: &str = r#"
+let selected = [];
+document.addEventListener("click", event => {
+    const line = event.target.closest(".mapped");
+    if (line === null) {
+        return;
+    }
+    for (const other of selected) {
+        other.classList.remove("selected");
+    }
+    const offset = line.dataset.offset;
+    selected = document.querySelectorAll(`.mapped[data-offset="${offset}"]`);
+    for (const other of selected) {
+        other.classList.add("selected");
+    }
+    const otherPane = line.parentElement.id === "wat" ? "asm" : "wat";
+    const target = document.querySelector(`#${otherPane} .mapped[data-offset="${offset}"]`);
+    if (target !== null) {
+        target.scrollIntoView({ block: "center" });
+    }
+});
+"#;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64", target_arch = "s390x"))]
+    #[test]
+    fn test_successful_explore() -> Result<()> {
+        let (mut input, input_path) = NamedTempFile::new()?.into_parts();
+        input.write_all(
+            "(module (func (export \"f\") (param i32) (result i32) local.get 0 i32.const 1 i32.add))"
+                .as_bytes(),
+        )?;
+        drop(input);
+
+        let output_path = NamedTempFile::new()?.into_temp_path();
+
+        let command = ExploreCommand::from_iter_safe(vec![
+            "explore",
+            "--disable-logging",
+            "-o",
+            output_path.to_str().unwrap(),
+            input_path.to_str().unwrap(),
+        ])?;
+
+        command.execute()?;
+
+        let html = std::fs::read_to_string(output_path)?;
+        assert!(html.contains("i32.add"));
+        assert!(html.contains(";; function 0"));
+        // The `i32.add` instruction is at offset 0x24 of the binary, and both
+        // its text and its native code should be linked to it.
+        assert!(html.matches("data-offset=\"24\"").count() >= 2);
+
+        Ok(())
+    }
+}
@@ -13,7 +13,7 @@ use std::{
 };
 use structopt::{clap::AppSettings, StructOpt};
 use wasmtime::{Engine, Func, Linker, Module, Store, Trap, Val, ValType};
-use wasmtime_wasi::sync::{ambient_authority, Dir, WasiCtxBuilder};
+use wasmtime_wasi::sync::{ambient_authority, Dir, TcpListener, WasiCtxBuilder};
 
 #[cfg(feature = "wasi-nn")]
 use wasmtime_wasi_nn::WasiNnCtx;
@@ -24,9 +24,8 @@ use wasmtime_wasi_crypto::WasiCryptoCtx;
 fn parse_module(s: &OsStr) -> Result<PathBuf, OsString> {
     // Do not accept wasmtime subcommand names as the module name
     match s.to_str() {
-        Some("help") | Some("config") | Some("run") | Some("wast") | Some("compile") => {
-            Err("module name cannot be the same as a subcommand".into())
-        }
+        Some("help") | Some("config") | Some("run") | Some("wast") | Some("compile")
+        | Some("explore") => Err("module name cannot be the same as a subcommand".into()),
         _ => Ok(s.into()),
     }
 }
@@ -103,6 +102,18 @@ pub struct RunCommand {
     #[structopt(long, value_name = "FUNCTION")]
     invoke: Option<String>,
 
+    /// Grant access to a TCP socket listening on the given address
+    ///
+    /// The listening sockets are passed to the program as file descriptors
+    /// starting at 3, before any preopened directories, and the number of
+    /// sockets is passed in the `LISTEN_FDS` environment variable.
+    #[structopt(
+        long = "tcplisten",
+        number_of_values = 1,
+        value_name = "SOCKET ADDRESS"
+    )]
+    tcplisten: Vec<String>,
+
     /// Grant access to a guest directory mapped as a host directory
     #[structopt(long = "mapdir", number_of_values = 1, value_name = "GUEST_DIR::HOST_DIR", parse(try_from_str = parse_map_dirs))]
     map_dirs: Vec<(String, String)>,
@@ -133,6 +144,10 @@ pub struct RunCommand {
     )]
     wasm_timeout: Option<Duration>,
 
+    /// Write a wasm core dump to the given path if the module traps
+    #[structopt(long = "coredump-on-trap", value_name = "PATH", parse(from_os_str))]
+    coredump_on_trap: Option<PathBuf>,
+
     // NOTE: this must come last for trailing varargs
     /// The arguments to pass to the module
     #[structopt(value_name = "ARGS")]
@@ -148,11 +163,15 @@ impl RunCommand {
         if self.wasm_timeout.is_some() {
             config.interruptable(true);
         }
+        if self.coredump_on_trap.is_some() {
+            config.coredump_on_trap(true);
+        }
         let engine = Engine::new(&config)?;
         let mut store = Store::new(&engine, Host::default());
 
         // Make wasi available by default.
         let preopen_dirs = self.compute_preopen_dirs()?;
+        let preopen_sockets = self.compute_preopen_sockets()?;
         let argv = self.compute_argv();
 
         let mut linker = Linker::new(&engine);
@@ -162,6 +181,7 @@ impl RunCommand {
             &mut store,
             &mut linker,
             preopen_dirs,
+            preopen_sockets,
             &argv,
             &self.vars,
             &self.common.wasi_modules.unwrap_or(WasiModules::default()),
@@ -202,6 +222,14 @@ impl RunCommand {
 
                     eprintln!("Error: {:?}", e);
 
+                    if let (Some(path), Some(coredump)) = (&self.coredump_on_trap, trap.coredump())
+                    {
+                        let name = self.module.display().to_string();
+                        if let Err(err) = std::fs::write(path, coredump.serialize(&name)) {
+                            eprintln!("Failed to write core dump to `{}`: {}", path.display(), err);
+                        }
+                    }
+
                     // If the program exited because of a trap, return an error code
                     // to the outside environment indicating a more severe problem
                     // than a simple failure.
@@ -243,6 +271,21 @@ impl RunCommand {
         Ok(preopen_dirs)
     }
 
+    fn compute_preopen_sockets(&self) -> Result<Vec<TcpListener>> {
+        let mut listeners = Vec::new();
+
+        for address in self.tcplisten.iter() {
+            let listener = std::net::TcpListener::bind(address)
+                .with_context(|| format!("failed to bind to address '{}'", address))?;
+            // Guests are expected to wait for connections with `poll_oneoff`
+            // rather than blocking in `sock_accept`.
+            listener.set_nonblocking(true)?;
+            listeners.push(TcpListener::from_std(listener));
+        }
+
+        Ok(listeners)
+    }
+
     fn compute_argv(&self) -> Vec<String> {
         let mut result = Vec::new();
 
@@ -412,6 +455,7 @@ fn populate_with_wasi(
     store: &mut Store<Host>,
     linker: &mut Linker<Host>,
     preopen_dirs: Vec<(String, Dir)>,
+    preopen_sockets: Vec<TcpListener>,
     argv: &[String],
     vars: &[(String, String)],
     wasi_modules: &WasiModules,
@@ -422,6 +466,15 @@ fn populate_with_wasi(
         let mut builder = WasiCtxBuilder::new();
         builder = builder.inherit_stdio().args(argv)?.envs(vars)?;
 
+        if !preopen_sockets.is_empty() {
+            builder = builder.env("LISTEN_FDS", &preopen_sockets.len().to_string())?;
+        }
+        // Sockets are inserted at fixed descriptors starting just after stdio,
+        // and preopened directories are pushed after them.
+        for (fd, listener) in (3..).zip(preopen_sockets) {
+            builder = builder.preopened_socket(fd, listener)?;
+        }
+
         for (name, dir) in preopen_dirs.into_iter() {
             builder = builder.preopened_dir(dir, name)?;
         }
@@ -42,6 +42,11 @@ const SUPPORTED_WASM_FEATURES: &[(&str, &str)] = &[
     ("simd", "enables support for proposed SIMD instructions"),
     ("threads", "enables support for WebAssembly threads"),
     ("memory64", "enables support for 64-bit memories"),
+    ("tail-call", "enables support for the tail-call proposal"),
+    (
+        "exceptions",
+        "enables support for the exception handling proposal",
+    ),
 ];
 
 const SUPPORTED_WASI_MODULES: &[(&str, &str)] = &[
@@ -101,7 +106,14 @@ use std::path::PathBuf;
 use structopt::StructOpt;
 use wasmtime::{Config, ProfilingStrategy};
 
-fn pick_profiling_strategy(jitdump: bool, vtune: bool) -> Result<ProfilingStrategy> {
+fn pick_profiling_strategy(
+    profile: Option<ProfilingStrategy>,
+    jitdump: bool,
+    vtune: bool,
+) -> Result<ProfilingStrategy> {
+    if let Some(profile) = profile {
+        return Ok(profile);
+    }
     Ok(match (jitdump, vtune) {
         (true, false) => ProfilingStrategy::JitDump,
         (false, true) => ProfilingStrategy::VTune,
@@ -171,13 +183,23 @@ struct CommonOptions {
     wasi_modules: Option<WasiModules>,
 
     /// Generate jitdump file (supported on --features=profiling build)
-    #[structopt(long, conflicts_with = "vtune")]
+    #[structopt(long, conflicts_with_all = &["vtune", "profile"])]
     jitdump: bool,
 
     /// Generate vtune (supported on --features=vtune build)
-    #[structopt(long, conflicts_with = "jitdump")]
+    #[structopt(long, conflicts_with_all = &["jitdump", "profile"])]
     vtune: bool,
 
+    /// Profiling strategy for generated code
+    /// Supported strategies: none, jitdump, vtune, or perfmap
+    #[structopt(
+        long,
+        value_name = "STRATEGY",
+        parse(try_from_str = parse_profile),
+        verbatim_doc_comment,
+    )]
+    profile: Option<ProfilingStrategy>,
+
     /// Run optimization passes on translated functions, on by default
     #[structopt(short = "O", long)]
     optimize: bool,
@@ -244,6 +266,11 @@ struct CommonOptions {
     /// the data segments specified in the original wasm module.
     #[structopt(long)]
     paged_memory_initialization: bool,
+
+    /// Disables initializing linear memories by mapping copy-on-write images
+    /// of their contents, and copies data segments into memory instead.
+    #[structopt(long)]
+    disable_memory_init_cow: bool,
 }
 
 impl CommonOptions {
@@ -272,7 +299,11 @@ impl CommonOptions {
             .cranelift_debug_verifier(self.enable_cranelift_debug_verifier)
             .debug_info(self.debug_info)
             .cranelift_opt_level(self.opt_level())
-            .profiler(pick_profiling_strategy(self.jitdump, self.vtune)?)?
+            .profiler(pick_profiling_strategy(
+                self.profile,
+                self.jitdump,
+                self.vtune,
+            )?)?
             .cranelift_nan_canonicalization(self.enable_cranelift_nan_canonicalization);
 
         self.enable_wasm_features(&mut config);
@@ -317,6 +348,7 @@ impl CommonOptions {
         config.consume_fuel(self.consume_fuel);
         config.generate_address_map(!self.disable_address_map);
         config.paged_memory_initialization(self.paged_memory_initialization);
+        config.memory_init_cow(!self.disable_memory_init_cow);
 
         Ok(config)
     }
@@ -331,6 +363,8 @@ impl CommonOptions {
             multi_memory,
             memory64,
             module_linking,
+            tail_call,
+            exceptions,
         } = self.wasm_features.unwrap_or_default();
 
         if let Some(enable) = simd {
@@ -357,6 +391,12 @@ impl CommonOptions {
         if let Some(enable) = module_linking {
             config.wasm_module_linking(enable);
         }
+        if let Some(enable) = tail_call {
+            config.wasm_tail_call(enable);
+        }
+        if let Some(enable) = exceptions {
+            config.wasm_exceptions(enable);
+        }
     }
 
     fn opt_level(&self) -> wasmtime::OptLevel {
@@ -380,6 +420,19 @@ fn parse_opt_level(opt_level: &str) -> Result<wasmtime::OptLevel> {
     }
 }
 
+fn parse_profile(profile: &str) -> Result<ProfilingStrategy> {
+    match profile {
+        "none" => Ok(ProfilingStrategy::None),
+        "jitdump" => Ok(ProfilingStrategy::JitDump),
+        "vtune" => Ok(ProfilingStrategy::VTune),
+        "perfmap" => Ok(ProfilingStrategy::PerfMap),
+        other => bail!(
+            "unknown profiling strategy `{}`, only none,jitdump,vtune,perfmap accepted",
+            other
+        ),
+    }
+}
+
 #[derive(Default, Clone, Copy)]
 struct WasmFeatures {
     reference_types: Option<bool>,
@@ -390,6 +443,8 @@ struct WasmFeatures {
     threads: Option<bool>,
     multi_memory: Option<bool>,
     memory64: Option<bool>,
+    tail_call: Option<bool>,
+    exceptions: Option<bool>,
 }
 
 fn parse_wasm_features(features: &str) -> Result<WasmFeatures> {
@@ -439,6 +494,8 @@ fn parse_wasm_features(features: &str) -> Result<WasmFeatures> {
         threads: all.or(values["threads"]),
         multi_memory: all.or(values["multi-memory"]),
         memory64: all.or(values["memory64"]),
+        tail_call: all.or(values["tail-call"]),
+        exceptions: all.or(values["exceptions"]),
     })
 }
 
@@ -542,6 +599,8 @@ mod test {
             threads,
             multi_memory,
             memory64,
+            tail_call,
+            exceptions,
         } = options.wasm_features.unwrap();
 
         assert_eq!(reference_types, Some(true));
@@ -552,6 +611,8 @@ mod test {
         assert_eq!(threads, Some(true));
         assert_eq!(multi_memory, Some(true));
         assert_eq!(memory64, Some(true));
+        assert_eq!(tail_call, Some(true));
+        assert_eq!(exceptions, Some(true));
 
         Ok(())
     }
@@ -569,6 +630,8 @@ mod test {
             threads,
             multi_memory,
             memory64,
+            tail_call,
+            exceptions,
         } = options.wasm_features.unwrap();
 
         assert_eq!(reference_types, Some(false));
@@ -579,6 +642,8 @@ mod test {
         assert_eq!(threads, Some(false));
         assert_eq!(multi_memory, Some(false));
         assert_eq!(memory64, Some(false));
+        assert_eq!(tail_call, Some(false));
+        assert_eq!(exceptions, Some(false));
 
         Ok(())
     }
@@ -599,6 +664,8 @@ mod test {
             threads,
             multi_memory,
             memory64,
+            tail_call,
+            exceptions,
         } = options.wasm_features.unwrap();
 
         assert_eq!(reference_types, Some(false));
@@ -609,6 +676,8 @@ mod test {
         assert_eq!(threads, None);
         assert_eq!(multi_memory, Some(true));
         assert_eq!(memory64, Some(true));
+        assert_eq!(tail_call, None);
+        assert_eq!(exceptions, None);
 
         Ok(())
     }
@@ -654,6 +723,8 @@ mod test {
     feature_test!(test_threads_feature, threads, "threads");
     feature_test!(test_multi_memory_feature, multi_memory, "multi-memory");
     feature_test!(test_memory64_feature, memory64, "memory64");
+    feature_test!(test_tail_call_feature, tail_call, "tail-call");
+    feature_test!(test_exceptions_feature, exceptions, "exceptions");
 
     #[test]
     fn test_default_modules() {
@@ -711,4 +782,15 @@ mod test {
             }
         );
     }
+
+    #[test]
+    fn test_profile() {
+        let options = CommonOptions::from_iter_safe(vec!["foo", "--profile=perfmap"]).unwrap();
+        assert!(matches!(options.profile, Some(ProfilingStrategy::PerfMap)));
+
+        assert!(CommonOptions::from_iter_safe(vec!["foo", "--profile=unknown"]).is_err());
+        assert!(
+            CommonOptions::from_iter_safe(vec!["foo", "--profile=perfmap", "--jitdump"]).is_err()
+        );
+    }
 }
@@ -397,3 +397,51 @@ fn run_cwasm() -> Result<()> {
     assert_eq!(stdout, "");
     Ok(())
 }
+
+// Accept a connection on a socket passed with `--tcplisten` and send a message
+// to a client on localhost.
+#[test]
+fn tcp_accept_wasi_snapshot1() -> Result<()> {
+    use std::io::Read;
+    use std::net::{TcpListener, TcpStream};
+    use std::time::{Duration, Instant};
+
+    let wasm = build_wasm("tests/all/cli_tests/tcp_accept_wasi_snapshot1.wat")?;
+
+    // Find a free port for wasmtime to listen on.
+    let addr = TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+
+    let args = vec![
+        "run".to_string(),
+        "--disable-cache".to_string(),
+        "--tcplisten".to_string(),
+        addr.to_string(),
+        wasm.path().to_str().unwrap().to_string(),
+    ];
+    let wasmtime = std::thread::spawn(move || {
+        let args = args.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        run_wasmtime_for_output(&args)
+    });
+
+    // Keep trying to connect until wasmtime has started listening.
+    let start = Instant::now();
+    let mut stream = loop {
+        match TcpStream::connect(addr) {
+            Ok(stream) => break stream,
+            Err(e) if start.elapsed() > Duration::from_secs(30) => return Err(e.into()),
+            Err(_) => std::thread::sleep(Duration::from_millis(10)),
+        }
+    };
+    let mut message = String::new();
+    stream.read_to_string(&mut message)?;
+    assert_eq!(message, "hello\n");
+
+    let output = wasmtime.join().unwrap()?;
+    if !output.status.success() {
+        bail!(
+            "wasmtime failed:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(())
+}
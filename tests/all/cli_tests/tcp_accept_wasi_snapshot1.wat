@@ -0,0 +1,42 @@
+(module
+  (import "wasi_snapshot_preview1" "sock_accept"
+    (func $sock_accept (param i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "sock_send"
+    (func $sock_send (param i32 i32 i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "sock_shutdown"
+    (func $sock_shutdown (param i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "sched_yield"
+    (func $sched_yield (result i32)))
+  (memory (export "memory") 1)
+
+  ;; An iovec pointing at the message to send.
+  (data (i32.const 0) "\10\00\00\00\06\00\00\00")
+  (data (i32.const 16) "hello\n")
+
+  (func (export "_start")
+    (local $errno i32)
+    (local $fd i32)
+
+    ;; The listener passed with `--tcplisten` is non-blocking, so retry while
+    ;; `sock_accept` returns EAGAIN.
+    (block $accepted
+      (loop $retry
+        (local.set $errno
+          (call $sock_accept (i32.const 3) (i32.const 0) (i32.const 32)))
+        (br_if $accepted (i32.eqz (local.get $errno)))
+        (if (i32.ne (local.get $errno) (i32.const 6))
+          (then unreachable))
+        (drop (call $sched_yield))
+        (br $retry)))
+    (local.set $fd (i32.load (i32.const 32)))
+
+    (if (call $sock_send
+          (local.get $fd) (i32.const 0) (i32.const 1) (i32.const 0) (i32.const 40))
+      (then unreachable))
+    (if (i32.ne (i32.load (i32.const 40)) (i32.const 6))
+      (then unreachable))
+
+    ;; Shut down the write side so the client sees the end of the stream.
+    (if (call $sock_shutdown (local.get $fd) (i32.const 2))
+      (then unreachable)))
+)
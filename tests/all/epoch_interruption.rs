@@ -0,0 +1,159 @@
+use anyhow::Result;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use wasmtime::*;
+
+fn epoch_engine(async_support: bool) -> Engine {
+    let mut config = Config::new();
+    config.epoch_interruption(true).async_support(async_support);
+    Engine::new(&config).unwrap()
+}
+
+/// A module whose `run` export calls the `bump` import, which increments the
+/// engine's epoch, `n` times in a loop.
+fn bump_module(engine: &Engine) -> Result<Module> {
+    Module::new(
+        engine,
+        r#"
+            (module
+                (import "" "bump" (func $bump))
+                (func (export "run") (param i32)
+                    (loop $l
+                        call $bump
+                        local.get 0
+                        i32.const 1
+                        i32.sub
+                        local.tee 0
+                        br_if $l))
+            )
+        "#,
+    )
+}
+
+#[test]
+fn epoch_deadline_traps_immediately_by_default() -> Result<()> {
+    let engine = epoch_engine(false);
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, r#"(module (func (export "run")))"#)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<(), (), _>(&mut store, "run")?;
+
+    let trap = run.call(&mut store, ()).unwrap_err();
+    assert!(
+        trap.to_string().contains("epoch deadline reached"),
+        "bad message: {}",
+        trap
+    );
+
+    store.set_epoch_deadline(1);
+    run.call(&mut store, ())?;
+    Ok(())
+}
+
+#[test]
+fn epoch_deadline_trap_in_loop() -> Result<()> {
+    let engine = epoch_engine(false);
+    let mut store = Store::new(&engine, ());
+    let module = bump_module(&engine)?;
+    let bump = Func::wrap(&mut store, |caller: Caller<'_, ()>| {
+        caller.engine().increment_epoch();
+    });
+    let instance = Instance::new(&mut store, &module, &[bump.into()])?;
+    let run = instance.get_typed_func::<i32, (), _>(&mut store, "run")?;
+
+    // Enough ticks to finish.
+    store.set_epoch_deadline(11);
+    run.call(&mut store, 10)?;
+
+    // Not enough ticks to finish.
+    store.set_epoch_deadline(5);
+    let trap = run.call(&mut store, 10).unwrap_err();
+    assert!(
+        trap.to_string().contains("epoch deadline reached"),
+        "bad message: {}",
+        trap
+    );
+    Ok(())
+}
+
+#[test]
+fn epoch_increment_from_other_thread() -> Result<()> {
+    let engine = epoch_engine(false);
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, r#"(module (func (export "run") (loop br 0)))"#)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<(), (), _>(&mut store, "run")?;
+    store.set_epoch_deadline(1);
+
+    let handle = {
+        let engine = engine.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            engine.increment_epoch();
+        })
+    };
+    let trap = run.call(&mut store, ()).unwrap_err();
+    assert!(trap.to_string().contains("epoch deadline reached"));
+    handle.join().unwrap();
+    Ok(())
+}
+
+#[test]
+fn epoch_yield_and_update() -> Result<()> {
+    let engine = epoch_engine(true);
+    let mut store = Store::new(&engine, ());
+    let module = bump_module(&engine)?;
+    let bump = Func::wrap(&mut store, |caller: Caller<'_, ()>| {
+        caller.engine().increment_epoch();
+    });
+    let instance = run(Instance::new_async(&mut store, &module, &[bump.into()])).0?;
+    let run_func = instance.get_typed_func::<i32, (), _>(&mut store, "run")?;
+
+    store.set_epoch_deadline(1);
+    store.epoch_deadline_async_yield_and_update(1);
+
+    // Every bump reaches the deadline at the next loop header, so we expect
+    // to yield once per iteration.
+    let (result, yields) = run(run_func.call_async(&mut store, 5));
+    result?;
+    assert_eq!(yields, 5);
+    Ok(())
+}
+
+/// Runs `future` to completion, returning its output along with the number of
+/// times it yielded.
+fn run<F: Future>(future: F) -> (F::Output, usize) {
+    let mut f = Pin::from(Box::new(future));
+    let waker = dummy_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut yields = 0;
+    loop {
+        match f.as_mut().poll(&mut cx) {
+            Poll::Ready(val) => break (val, yields),
+            Poll::Pending => yields += 1,
+        }
+    }
+}
+
+fn dummy_waker() -> Waker {
+    return unsafe { Waker::from_raw(clone(5 as *const _)) };
+
+    unsafe fn clone(ptr: *const ()) -> RawWaker {
+        assert_eq!(ptr as usize, 5);
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
+        RawWaker::new(ptr, &VTABLE)
+    }
+
+    unsafe fn wake(ptr: *const ()) {
+        assert_eq!(ptr as usize, 5);
+    }
+
+    unsafe fn wake_by_ref(ptr: *const ()) {
+        assert_eq!(ptr as usize, 5);
+    }
+
+    unsafe fn drop(ptr: *const ()) {
+        assert_eq!(ptr as usize, 5);
+    }
+}
@@ -0,0 +1,284 @@
+use anyhow::Result;
+use wasmtime::*;
+
+fn engine() -> Engine {
+    let mut config = Config::new();
+    config.wasm_exceptions(true);
+    Engine::new(&config).unwrap()
+}
+
+#[test]
+fn uncaught_exception_reaches_host() -> Result<()> {
+    let wat = r#"
+        (module
+            (tag (export "e") (param i32 f64))
+            (func $throw (param i32)
+                (throw 0 (local.get 0) (f64.const 1.5)))
+            (func (export "run") (param i32) (result i32)
+                (call $throw (local.get 0))
+                (i32.const 0)))
+    "#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let e = instance.get_tag(&mut store, "e").unwrap();
+    let run = instance.get_typed_func::<i32, i32, _>(&mut store, "run")?;
+
+    let trap = run.call(&mut store, 7).unwrap_err();
+    let exception = trap.exception().expect("trap should be an exception");
+    assert!(exception.tag().same(&store, &e));
+    assert_eq!(exception.values().len(), 2);
+    assert_eq!(exception.values()[0].unwrap_i32(), 7);
+    assert_eq!(exception.values()[1].unwrap_f64(), 1.5);
+    assert!(trap.trap_code().is_none());
+    assert!(trap.to_string().contains("uncaught wasm exception"));
+
+    // The store is still usable after an exception escapes.
+    assert!(run.call(&mut store, 8).unwrap_err().exception().is_some());
+    Ok(())
+}
+
+#[test]
+fn host_exception_caught_by_wasm() -> Result<()> {
+    let wat = r#"
+        (module
+            (import "" "e" (tag $e (param i32)))
+            (import "" "host" (func $host (param i32) (result i32)))
+            (func (export "run") (param i32) (result i32)
+                (try (result i32)
+                    (do (call $host (local.get 0)))
+                    (catch $e (i32.const 100) (i32.add)))))
+    "#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let e = Tag::new(&mut store, TagType::new([ValType::I32]))?;
+    let host = Func::wrap(&mut store, move |x: i32| -> Result<i32, Trap> {
+        if x < 0 {
+            Err(Exception::new(e, vec![Val::I32(-x)]).into())
+        } else {
+            Ok(x)
+        }
+    });
+    let instance = Instance::new(&mut store, &module, &[e.into(), host.into()])?;
+    let run = instance.get_typed_func::<i32, i32, _>(&mut store, "run")?;
+
+    assert_eq!(run.call(&mut store, 5)?, 5);
+    assert_eq!(run.call(&mut store, -5)?, 105);
+    Ok(())
+}
+
+#[test]
+fn host_exception_propagates_through_wasm() -> Result<()> {
+    let wat = r#"
+        (module
+            (import "" "host" (func $host))
+            (func (export "run")
+                (try
+                    (do (call $host))
+                    (catch_all (rethrow 0)))))
+    "#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let e = Tag::new(&mut store, TagType::new([ValType::I64]))?;
+    let ty = FuncType::new(None, None);
+    let host = Func::new(&mut store, ty, move |_, _, _| {
+        Err(Exception::new(e, vec![Val::I64(42)]).into())
+    });
+    let instance = Instance::new(&mut store, &module, &[host.into()])?;
+    let run = instance.get_typed_func::<(), (), _>(&mut store, "run")?;
+
+    let trap = run.call(&mut store, ()).unwrap_err();
+    let exception = trap.exception().expect("trap should be an exception");
+    assert!(exception.tag().same(&store, &e));
+    assert_eq!(exception.values()[0].unwrap_i64(), 42);
+    Ok(())
+}
+
+#[test]
+fn traps_are_not_caught() -> Result<()> {
+    let wat = r#"
+        (module
+            (func (export "run")
+                (try
+                    (do unreachable)
+                    (catch_all))))
+    "#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<(), (), _>(&mut store, "run")?;
+
+    let trap = run.call(&mut store, ()).unwrap_err();
+    assert!(trap.exception().is_none());
+    assert_eq!(trap.trap_code(), Some(TrapCode::UnreachableCodeReached));
+    Ok(())
+}
+
+#[test]
+fn tag_import_type_mismatch() -> Result<()> {
+    let engine = engine();
+    let module = Module::new(&engine, r#"(module (import "" "" (tag (param i32))))"#)?;
+    let mut store = Store::new(&engine, ());
+    let e = Tag::new(&mut store, TagType::new([ValType::I64]))?;
+    assert!(Instance::new(&mut store, &module, &[e.into()]).is_err());
+    Ok(())
+}
+
+#[test]
+fn mismatched_host_exception() -> Result<()> {
+    let wat = r#"
+        (module
+            (import "" "host" (func $host))
+            (func (export "run") (call $host)))
+    "#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let e = Tag::new(&mut store, TagType::new([ValType::I32]))?;
+    let host = Func::wrap(&mut store, move || -> Result<(), Trap> {
+        Err(Exception::new(e, vec![Val::F32(0)]).into())
+    });
+    let instance = Instance::new(&mut store, &module, &[host.into()])?;
+    let run = instance.get_typed_func::<(), (), _>(&mut store, "run")?;
+
+    let trap = run.call(&mut store, ()).unwrap_err();
+    assert!(trap.exception().is_none());
+    assert!(trap
+        .to_string()
+        .contains("exception values do not match the type of its tag"));
+    Ok(())
+}
+
+#[test]
+fn exceptions_require_the_feature() -> Result<()> {
+    let engine = Engine::default();
+    let wat = r#"(module (tag) (func (throw 0)))"#;
+    assert!(Module::new(&engine, wat).is_err());
+    Ok(())
+}
+
+#[test]
+fn exception_unwinds_frames_without_try() -> Result<()> {
+    let wat = r#"
+        (module
+            (tag $e (param i32))
+            (func $throw (param i32) (result i32)
+                (throw $e (local.get 0)))
+            (func $middle (param i32) (result i32)
+                (i32.add (call $throw (local.get 0)) (i32.const 1000)))
+            (func (export "run") (param i32) (result i32)
+                (try (result i32)
+                    (do (i32.add (call $middle (local.get 0)) (i32.const 2000)))
+                    (catch $e (i32.const 100) (i32.add)))))
+    "#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<i32, i32, _>(&mut store, "run")?;
+
+    assert_eq!(run.call(&mut store, 5)?, 105);
+    assert_eq!(run.call(&mut store, 6)?, 106);
+    Ok(())
+}
+
+#[test]
+fn externref_payload_reaches_host() -> Result<()> {
+    let wat = r#"
+        (module
+            (tag (export "e") (param externref i32))
+            (func (export "run") (param externref)
+                (throw 0 (local.get 0) (i32.const 3))))
+    "#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<Option<ExternRef>, (), _>(&mut store, "run")?;
+
+    let trap = run
+        .call(&mut store, Some(ExternRef::new("payload")))
+        .unwrap_err();
+    store.gc();
+    let exception = trap.exception().expect("trap should be an exception");
+    let payload = exception.values()[0].unwrap_externref().unwrap();
+    assert_eq!(payload.data().downcast_ref::<&str>(), Some(&"payload"));
+    assert_eq!(exception.values()[1].unwrap_i32(), 3);
+    Ok(())
+}
+
+#[test]
+fn externref_payload_caught_by_wasm() -> Result<()> {
+    let wat = r#"
+        (module
+            (import "" "e" (tag $e (param externref)))
+            (import "" "host" (func $host))
+            (import "" "gc" (func $gc))
+            (func (export "run") (result externref)
+                (try (result externref)
+                    (do (call $host) (ref.null extern))
+                    (catch $e (call $gc)))))
+    "#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let e = Tag::new(&mut store, TagType::new([ValType::ExternRef]))?;
+    let host = Func::wrap(&mut store, move || -> Result<(), Trap> {
+        let payload = ExternRef::new(42_u32);
+        Err(Exception::new(e, vec![Val::ExternRef(Some(payload))]).into())
+    });
+    let gc = Func::wrap(&mut store, |mut caller: Caller<'_, ()>| caller.gc());
+    let instance = Instance::new(&mut store, &module, &[e.into(), host.into(), gc.into()])?;
+    let run = instance.get_typed_func::<(), Option<ExternRef>, _>(&mut store, "run")?;
+
+    let payload = run.call(&mut store, ())?.unwrap();
+    assert_eq!(payload.data().downcast_ref::<u32>(), Some(&42));
+    Ok(())
+}
+
+#[test]
+fn wasm_exception_crosses_host_frame() -> Result<()> {
+    let wat = r#"
+        (module
+            (tag $e (param i32))
+            (import "" "host" (func $host (param i32) (result i32)))
+            (func (export "throw") (param i32) (result i32)
+                (throw $e (local.get 0)))
+            (func $double (param i32) (result i32)
+                (i32.mul (local.get 0) (i32.const 2)))
+            (func (export "run") (param i32) (result i32)
+                (try (result i32)
+                    (do (call $host (local.get 0)))
+                    (catch $e (call $double)))))
+    "#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let host = Func::wrap(
+        &mut store,
+        |mut caller: Caller<'_, ()>, x: i32| -> Result<i32, Trap> {
+            let throw = caller
+                .get_export("throw")
+                .and_then(|e| e.into_func())
+                .unwrap()
+                .typed::<i32, i32, _>(&caller)
+                .unwrap();
+            let result = throw.call(&mut caller, x + 1);
+            assert!(result.as_ref().unwrap_err().exception().is_some());
+            result
+        },
+    );
+    let instance = Instance::new(&mut store, &module, &[host.into()])?;
+    let run = instance.get_typed_func::<i32, i32, _>(&mut store, "run")?;
+
+    // The exception unwinds out of the nested wasm call into the host, is
+    // rethrown through the host frame, and the handler then calls back into
+    // wasm.
+    assert_eq!(run.call(&mut store, 5)?, 12);
+    assert_eq!(run.call(&mut store, 20)?, 42);
+    Ok(())
+}
@@ -3,6 +3,9 @@ mod call_hook;
 mod cli_tests;
 mod custom_signal_handler;
 mod debug;
+mod epoch_interruption;
+#[cfg(all(target_arch = "x86_64", unix))]
+mod exceptions;
 mod externals;
 mod fuel;
 mod func;
@@ -29,6 +32,8 @@ mod relocs;
 mod stack_overflow;
 mod store;
 mod table;
+mod tail_call;
+mod threads;
 mod traps;
 mod wast;
 
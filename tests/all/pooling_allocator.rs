@@ -511,3 +511,117 @@ fn preserve_data_segments() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn memory_reset_between_instantiations() -> Result<()> {
+    let mut config = Config::new();
+    config.allocation_strategy(InstanceAllocationStrategy::Pooling {
+        strategy: PoolingAllocationStrategy::NextAvailable,
+        module_limits: ModuleLimits {
+            memory_pages: 2,
+            table_elements: 0,
+            ..Default::default()
+        },
+        instance_limits: InstanceLimits { count: 1 },
+    });
+    let engine = Engine::new(&config)?;
+    let m1 = Module::new(
+        &engine,
+        r#"
+            (module
+                (memory (export "mem") 1 2)
+                (data (i32.const 0) "foo")
+                (func (export "grow") (result i32) (memory.grow (i32.const 1))))
+        "#,
+    )?;
+    let m2 = Module::new(
+        &engine,
+        r#"
+            (module
+                (memory (export "mem") 1 2)
+                (data (i32.const 4096) "bar"))
+        "#,
+    )?;
+
+    // Dirty the memory of each instance, including a grown page, and make
+    // sure the next instance sharing the slot starts from its own image.
+    for m in [&m1, &m1, &m2, &m1, &m2, &m2] {
+        let mut store = Store::new(&engine, ());
+        let i = Instance::new(&mut store, m, &[])?;
+        let mem = i.get_memory(&mut store, "mem").unwrap();
+        assert_eq!(mem.data_size(&store), 65536);
+        let data = mem.data(&store);
+        if std::ptr::eq(m, &m1) {
+            assert_eq!(&data[..3], b"foo");
+            assert!(data[3..].iter().all(|b| *b == 0));
+            let grow = i.get_typed_func::<(), i32, _>(&mut store, "grow")?;
+            assert_eq!(grow.call(&mut store, ())?, 1);
+        } else {
+            assert_eq!(&data[4096..4099], b"bar");
+            assert!(data[..4096].iter().all(|b| *b == 0));
+            assert!(data[4099..].iter().all(|b| *b == 0));
+        }
+        mem.data_mut(&mut store).fill(0xff);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn reuse_affinity() -> Result<()> {
+    let mut config = Config::new();
+    config.allocation_strategy(InstanceAllocationStrategy::Pooling {
+        strategy: PoolingAllocationStrategy::ReuseAffinity,
+        module_limits: ModuleLimits {
+            memory_pages: 1,
+            table_elements: 10,
+            ..Default::default()
+        },
+        instance_limits: InstanceLimits { count: 2 },
+    });
+    config.dynamic_memory_guard_size(0);
+    config.static_memory_guard_size(0);
+    config.static_memory_maximum_size(65536);
+
+    let engine = Engine::new(&config)?;
+    let a = Module::new(
+        &engine,
+        r#"(module (memory (export "m") 1) (data (i32.const 0) "a"))"#,
+    )?;
+    let b = Module::new(
+        &engine,
+        r#"(module (memory (export "m") 1) (data (i32.const 0) "b"))"#,
+    )?;
+
+    assert_eq!(
+        engine.pooling_affinity_stats(),
+        Some(PoolingAffinityStats { hits: 0, misses: 0 })
+    );
+
+    for _ in 0..3 {
+        for (module, expected) in [(&a, b'a'), (&b, b'b')] {
+            let mut store = Store::new(&engine, ());
+            let instance = Instance::new(&mut store, module, &[])?;
+            let memory = instance.get_memory(&mut store, "m").unwrap();
+            assert_eq!(memory.data(&store)[0], expected);
+
+            // Dirty the memory to ensure it's reset before the next
+            // instantiation.
+            memory.data_mut(&mut store)[1] = 1;
+        }
+    }
+
+    // The first instantiation of each module can't reuse a slot, but every
+    // following instantiation should be placed into the slot it used last.
+    assert_eq!(
+        engine.pooling_affinity_stats(),
+        Some(PoolingAffinityStats { hits: 4, misses: 2 })
+    );
+
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &a, &[])?;
+    let memory = instance.get_memory(&mut store, "m").unwrap();
+    assert_eq!(&memory.data(&store)[..2], b"a\0");
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+#![cfg(all(target_arch = "x86_64", not(windows)))]
+
+use anyhow::Result;
+use wasmtime::*;
+
+fn engine() -> Engine {
+    let mut config = Config::new();
+    config.wasm_tail_call(true);
+    Engine::new(&config).unwrap()
+}
+
+#[test]
+fn tail_call_to_host_function() -> Result<()> {
+    let wat = r#"
+        (module
+            (import "" "double" (func $double (param i32) (result i32)))
+            (func (export "run") (param i32) (result i32)
+                (return_call $double (local.get 0))))
+    "#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let double = Func::wrap(&mut store, |x: i32| x * 2);
+    let instance = Instance::new(&mut store, &module, &[double.into()])?;
+    let run = instance.get_typed_func::<i32, i32, _>(&mut store, "run")?;
+    assert_eq!(run.call(&mut store, 21)?, 42);
+    Ok(())
+}
+
+#[test]
+fn tail_call_traps_have_backtraces() -> Result<()> {
+    let wat = r#"
+        (module
+            (func $count (param i32)
+                (if (i32.eqz (local.get 0)) (then unreachable))
+                (return_call $count (i32.sub (local.get 0) (i32.const 1))))
+            (func (export "run")
+                (call $count (i32.const 100000))))
+    "#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<(), (), _>(&mut store, "run")?;
+    let trap = run.call(&mut store, ()).unwrap_err();
+    assert_eq!(trap.trap_code(), Some(TrapCode::UnreachableCodeReached));
+    // Tail calls replace their caller's frame, so only a single `$count`
+    // frame is left on the stack.
+    assert_eq!(trap.trace().len(), 2);
+    Ok(())
+}
+
+#[test]
+fn tail_call_needing_more_stack_arguments() -> Result<()> {
+    let wat = r#"
+        (module
+            (func $many (param i32 i32 i32 i32 i32 i32 i32 i32) (result i32)
+                (i32.add
+                    (i32.add
+                        (i32.add (local.get 0) (local.get 1))
+                        (i32.add (local.get 2) (local.get 3)))
+                    (i32.add
+                        (i32.add (local.get 4) (local.get 5))
+                        (i32.add (local.get 6) (i32.mul (local.get 7) (i32.const 10))))))
+            (func $few (param i32) (result i32)
+                (return_call $many
+                    (local.get 0) (local.get 0) (local.get 0) (local.get 0)
+                    (local.get 0) (local.get 0) (local.get 0) (local.get 0)))
+            (func (export "run") (param i32) (result i32)
+                (i32.add
+                    (call $few (local.get 0))
+                    (call $few (i32.const 1))))
+            (func (export "many") (param i32 i32 i32 i32 i32 i32 i32 i32) (result i32)
+                (return_call $few (local.get 7))))
+    "#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run = instance.get_typed_func::<i32, i32, _>(&mut store, "run")?;
+    assert_eq!(run.call(&mut store, 2)?, 34 + 17);
+    let many = instance
+        .get_typed_func::<(i32, i32, i32, i32, i32, i32, i32, i32), i32, _>(&mut store, "many")?;
+    assert_eq!(many.call(&mut store, (0, 0, 0, 0, 0, 0, 0, 3))?, 51);
+    let many = instance.get_func(&mut store, "many").unwrap();
+    let mut results = [Val::I32(0)];
+    let params = [0, 0, 0, 0, 0, 0, 0, 1]
+        .iter()
+        .map(|&x| Val::I32(x))
+        .collect::<Vec<_>>();
+    many.call(&mut store, &params, &mut results)?;
+    assert_eq!(results[0].unwrap_i32(), 17);
+    Ok(())
+}
+
+#[test]
+fn tail_call_to_host_function_with_stack_arguments() -> Result<()> {
+    let wat = r#"
+        (module
+            (import "" "sum" (func $sum (param i32 i32 i32 i32 i32 i32 i32 i32) (result i32)))
+            (func (export "run") (param i32) (result i32)
+                (return_call $sum
+                    (local.get 0) (i32.const 1) (i32.const 2) (i32.const 3)
+                    (i32.const 4) (i32.const 5) (i32.const 6) (i32.const 7)))
+            (func (export "call") (param i32) (result i32)
+                (i32.add
+                    (call $sum
+                        (local.get 0) (i32.const 1) (i32.const 2) (i32.const 3)
+                        (i32.const 4) (i32.const 5) (i32.const 6) (i32.const 7))
+                    (i32.const 100))))
+    "#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let sum = Func::wrap(
+        &mut store,
+        |a: i32, b: i32, c: i32, d: i32, e: i32, f: i32, g: i32, h: i32| {
+            a + b + c + d + e + f + g + h * 10
+        },
+    );
+    let instance = Instance::new(&mut store, &module, &[sum.into()])?;
+    let run = instance.get_typed_func::<i32, i32, _>(&mut store, "run")?;
+    assert_eq!(run.call(&mut store, 100)?, 191);
+    let call = instance.get_typed_func::<i32, i32, _>(&mut store, "call")?;
+    assert_eq!(call.call(&mut store, 100)?, 291);
+    let sum = sum.typed::<(i32, i32, i32, i32, i32, i32, i32, i32), i32, _>(&store)?;
+    assert_eq!(sum.call(&mut store, (1, 1, 1, 1, 1, 1, 1, 1))?, 17);
+    Ok(())
+}
+
+#[test]
+fn unsupported_target() -> Result<()> {
+    let mut config = Config::new();
+    config.wasm_tail_call(true);
+    config.target("x86_64-pc-windows-msvc")?;
+    assert!(Engine::new(&config).is_err());
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use anyhow::Result;
+use std::thread;
+use std::time::Duration;
+use wasmtime::*;
+
+fn engine() -> Engine {
+    let mut config = Config::new();
+    config.wasm_threads(true);
+    Engine::new(&config).unwrap()
+}
+
+#[test]
+fn test_instantiate_shared_memory() -> Result<()> {
+    let wat = r#"(module (memory 1 1 shared))"#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let _instance = Instance::new(&mut store, &module, &[])?;
+    Ok(())
+}
+
+#[test]
+fn test_import_shared_memory() -> Result<()> {
+    let wat = r#"(module (import "env" "memory" (memory 1 5 shared)))"#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let shared_memory = SharedMemory::new(&engine, MemoryType::shared(1, 5))?;
+    let _instance = Instance::new(&mut store, &module, &[shared_memory.into()])?;
+    Ok(())
+}
+
+#[test]
+fn test_export_shared_memory() -> Result<()> {
+    let wat = r#"(module (memory (export "memory") 1 5 shared))"#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let shared_memory = instance.get_shared_memory(&mut store, "memory").unwrap();
+
+    assert_eq!(shared_memory.size(), 1);
+    assert!(shared_memory.ty().is_shared());
+    assert_eq!(shared_memory.ty().maximum(), Some(5));
+    assert!(instance.get_memory(&mut store, "memory").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_unshared_memory_mismatch() -> Result<()> {
+    let engine = engine();
+    let mut store = Store::new(&engine, ());
+
+    // A shared memory cannot be created through `Memory` ...
+    assert!(Memory::new(&mut store, MemoryType::shared(1, 1)).is_err());
+    // ... and an unshared type cannot be used for a `SharedMemory`.
+    assert!(SharedMemory::new(&engine, MemoryType::new(1, Some(1))).is_err());
+
+    // Shared and unshared memories are not interchangeable as imports.
+    let module = Module::new(&engine, r#"(module (import "" "" (memory 1 1)))"#)?;
+    let shared_memory = SharedMemory::new(&engine, MemoryType::shared(1, 1))?;
+    assert!(Instance::new(&mut store, &module, &[shared_memory.into()]).is_err());
+
+    let module = Module::new(&engine, r#"(module (import "" "" (memory 1 1 shared)))"#)?;
+    let memory = Memory::new(&mut store, MemoryType::new(1, Some(1)))?;
+    assert!(Instance::new(&mut store, &module, &[memory.into()]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_shared_memory_requires_threads() {
+    let engine = Engine::default();
+    assert!(SharedMemory::new(&engine, MemoryType::shared(1, 1)).is_err());
+}
+
+#[test]
+fn test_grow_shared_memory_across_stores() -> Result<()> {
+    let wat = r#"(module
+        (import "env" "memory" (memory 1 5 shared))
+        (func (export "size") (result i32) memory.size)
+        (func (export "grow") (param i32) (result i32)
+            local.get 0
+            memory.grow)
+    )"#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let shared_memory = SharedMemory::new(&engine, MemoryType::shared(1, 5))?;
+
+    let mut store1 = Store::new(&engine, ());
+    let instance1 = Instance::new(&mut store1, &module, &[shared_memory.clone().into()])?;
+    let mut store2 = Store::new(&engine, ());
+    let instance2 = Instance::new(&mut store2, &module, &[shared_memory.clone().into()])?;
+
+    let grow1 = instance1.get_typed_func::<i32, i32, _>(&mut store1, "grow")?;
+    let size2 = instance2.get_typed_func::<(), i32, _>(&mut store2, "size")?;
+
+    assert_eq!(size2.call(&mut store2, ())?, 1);
+    assert_eq!(grow1.call(&mut store1, 2)?, 1);
+    assert_eq!(size2.call(&mut store2, ())?, 3);
+    assert_eq!(shared_memory.size(), 3);
+
+    // Growing past the maximum fails everywhere.
+    assert_eq!(grow1.call(&mut store1, 3)?, -1);
+    assert!(shared_memory.grow(3).is_err());
+    assert_eq!(shared_memory.grow(2)?, 3);
+    assert_eq!(size2.call(&mut store2, ())?, 5);
+    Ok(())
+}
+
+#[test]
+fn test_shared_memory_across_threads() -> Result<()> {
+    let wat = r#"(module
+        (import "env" "memory" (memory 1 1 shared))
+        (func (export "add") (param i32)
+            i32.const 0
+            local.get 0
+            i32.atomic.rmw.add
+            drop)
+    )"#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let shared_memory = SharedMemory::new(&engine, MemoryType::shared(1, 1))?;
+
+    let threads = (0..4)
+        .map(|_| {
+            let engine = engine.clone();
+            let module = module.clone();
+            let shared_memory = shared_memory.clone();
+            thread::spawn(move || -> Result<()> {
+                let mut store = Store::new(&engine, ());
+                let instance = Instance::new(&mut store, &module, &[shared_memory.into()])?;
+                let add = instance.get_typed_func::<i32, (), _>(&mut store, "add")?;
+                for _ in 0..100 {
+                    add.call(&mut store, 1)?;
+                }
+                Ok(())
+            })
+        })
+        .collect::<Vec<_>>();
+    for t in threads {
+        t.join().unwrap()?;
+    }
+
+    let data = shared_memory.data();
+    let value = unsafe {
+        u32::from_le_bytes([
+            *data[0].get(),
+            *data[1].get(),
+            *data[2].get(),
+            *data[3].get(),
+        ])
+    };
+    assert_eq!(value, 400);
+    Ok(())
+}
+
+#[test]
+fn test_wait_notify_across_threads() -> Result<()> {
+    let wat = r#"(module
+        (import "env" "memory" (memory 1 1 shared))
+        (func (export "wait") (result i32)
+            i32.const 0
+            i32.const 0
+            i64.const -1
+            memory.atomic.wait32)
+        (func (export "wake") (result i32)
+            i32.const 0
+            i32.const 1
+            i32.atomic.store
+            i32.const 0
+            i32.const -1
+            memory.atomic.notify)
+    )"#;
+    let engine = engine();
+    let module = Module::new(&engine, wat)?;
+    let shared_memory = SharedMemory::new(&engine, MemoryType::shared(1, 1))?;
+
+    let waiter = {
+        let engine = engine.clone();
+        let module = module.clone();
+        let shared_memory = shared_memory.clone();
+        thread::spawn(move || -> Result<i32> {
+            let mut store = Store::new(&engine, ());
+            let instance = Instance::new(&mut store, &module, &[shared_memory.into()])?;
+            let wait = instance.get_typed_func::<(), i32, _>(&mut store, "wait")?;
+            Ok(wait.call(&mut store, ())?)
+        })
+    };
+
+    let mut store = Store::new(&engine, ());
+    let instance = Instance::new(&mut store, &module, &[shared_memory.into()])?;
+    let wake = instance.get_typed_func::<(), i32, _>(&mut store, "wake")?;
+
+    // Give the waiter a chance to start waiting. If it hasn't by the time we
+    // notify it'll instead observe the stored value and return "not-equal",
+    // so either way it mustn't hang.
+    thread::sleep(Duration::from_millis(100));
+    let woken = wake.call(&mut store, ())?;
+    let result = waiter.join().unwrap()?;
+    match result {
+        0 => assert_eq!(woken, 1),
+        1 => assert_eq!(woken, 0),
+        other => panic!("unexpected wait result {}", other),
+    }
+    Ok(())
+}
@@ -671,3 +671,281 @@ fn traps_without_address_map() -> Result<()> {
     assert_eq!(trace[1].module_offset(), None);
     Ok(())
 }
+
+#[test]
+#[cfg_attr(all(target_os = "macos", target_arch = "aarch64"), ignore)] // TODO #2808 system libunwind is broken on aarch64
+fn coredump_on_trap() -> Result<()> {
+    let mut config = Config::new();
+    config.coredump_on_trap(true);
+    let engine = Engine::new(&config)?;
+    let mut store = Store::new(&engine, ());
+    let wat = r#"
+        (module $coredump_mod
+            (memory 1)
+            (global $g (mut i32) (i32.const 0))
+            (table 2 funcref)
+            (func (export "run") (local i64)
+                (i32.store8 (i32.const 10) (i32.const 42))
+                (global.set $g (i32.const 7))
+                (local.set 0 (i64.const 9))
+                (f64.const 2.5)
+                (call $hello (i32.const 3))
+                drop)
+            (func $hello (param i32) (local f32)
+                (local.set 1 (f32.const 1.5))
+                (i32.const 5)
+                (unreachable))
+        )
+    "#;
+
+    let module = Module::new(&engine, wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run_func = instance.get_typed_func::<(), (), _>(&mut store, "run")?;
+
+    let e = run_func
+        .call(&mut store, ())
+        .err()
+        .expect("error calling function");
+    let coredump = e.coredump().expect("core dump should be captured");
+
+    let frames = coredump.frames();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].instance(), 0);
+    assert_eq!(frames[0].func_index(), 1);
+    assert_eq!(frames[1].func_index(), 0);
+
+    let locals = frames[0].locals();
+    assert_eq!(locals.len(), 2);
+    assert_eq!(locals[0].as_ref().unwrap().unwrap_i32(), 3);
+    assert_eq!(locals[1].as_ref().unwrap().unwrap_f32(), 1.5);
+    let stack = frames[0].stack();
+    assert_eq!(stack.len(), 1);
+    assert_eq!(stack[0].as_ref().unwrap().unwrap_i32(), 5);
+    let locals = frames[1].locals();
+    assert_eq!(locals.len(), 1);
+    assert_eq!(locals[0].as_ref().unwrap().unwrap_i64(), 9);
+    let stack = frames[1].stack();
+    assert_eq!(stack.len(), 1);
+    assert_eq!(stack[0].as_ref().unwrap().unwrap_f64(), 2.5);
+
+    assert_eq!(coredump.memories().len(), 1);
+    assert_eq!(coredump.memories()[0].data().len(), 65536);
+    assert_eq!(coredump.memories()[0].data()[10], 42);
+    assert_eq!(coredump.globals().len(), 1);
+    assert_eq!(coredump.globals()[0].value().unwrap_i32(), 7);
+    assert_eq!(coredump.tables().len(), 1);
+    assert_eq!(coredump.tables()[0].elements().len(), 2);
+
+    let bytes = coredump.serialize("test");
+    Module::new(&engine, &bytes)?;
+    assert!(wasmparser::Parser::new(0)
+        .parse_all(&bytes)
+        .filter_map(|payload| match payload {
+            Ok(wasmparser::Payload::CustomSection { name, .. }) => Some(name.to_string()),
+            _ => None,
+        })
+        .eq(["core", "coremodules", "coreinstances", "corestack"]));
+
+    // Without the option enabled no core dump is captured.
+    let mut store = Store::<()>::default();
+    let module = Module::new(store.engine(), wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run_func = instance.get_typed_func::<(), (), _>(&mut store, "run")?;
+    let e = run_func
+        .call(&mut store, ())
+        .err()
+        .expect("error calling function");
+    assert!(e.coredump().is_none());
+
+    // Frames are located with the address map, so it can't be disabled.
+    config.generate_address_map(false);
+    assert!(Engine::new(&config).is_err());
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(all(target_os = "macos", target_arch = "aarch64"), ignore)] // TODO #2808 system libunwind is broken on aarch64
+fn coredump_after_tail_call() -> Result<()> {
+    let mut config = Config::new();
+    config.coredump_on_trap(true);
+    config.wasm_tail_call(true);
+    let engine = Engine::new(&config)?;
+    let mut store = Store::new(&engine, ());
+    let wat = r#"
+        (module
+            (func (export "run") (local i32)
+                (local.set 0 (i32.const 1))
+                (call $a (i32.const 2)))
+            (func $a (param i32)
+                (return_call $b (i32.add (local.get 0) (i32.const 1))))
+            (func $b (param i32)
+                (unreachable))
+        )
+    "#;
+
+    let module = Module::new(&engine, wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run_func = instance.get_typed_func::<(), (), _>(&mut store, "run")?;
+
+    let e = run_func
+        .call(&mut store, ())
+        .err()
+        .expect("error calling function");
+    let coredump = e.coredump().expect("core dump should be captured");
+
+    // The frame of `$a` was replaced by `$b`, so it's not in the core dump.
+    let frames = coredump.frames();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].func_index(), 2);
+    assert_eq!(frames[0].locals()[0].as_ref().unwrap().unwrap_i32(), 3);
+    assert_eq!(frames[1].func_index(), 0);
+    assert_eq!(frames[1].locals()[0].as_ref().unwrap().unwrap_i32(), 1);
+
+    // A later trap in the same store doesn't see records of earlier calls.
+    let e = run_func
+        .call(&mut store, ())
+        .err()
+        .expect("error calling function");
+    assert_eq!(e.coredump().unwrap().frames().len(), 2);
+    Ok(())
+}
+
+#[test]
+#[cfg(all(target_arch = "x86_64", unix))]
+fn coredump_in_exception_handler() -> Result<()> {
+    let mut config = Config::new();
+    config.coredump_on_trap(true);
+    config.wasm_exceptions(true);
+    let engine = Engine::new(&config)?;
+    let mut store = Store::new(&engine, ());
+    let wat = r#"
+        (module
+            (tag $e (param i32))
+            (func (export "run") (local i32)
+                (local.set 0 (i32.const 4))
+                (try
+                    (do (call $throw (i32.const 5)))
+                    (catch $e (call $trap))))
+            (func $throw (param i32)
+                (call $deeper (local.get 0)))
+            (func $deeper (param i32)
+                (throw $e (local.get 0)))
+            (func $trap (param i32)
+                (unreachable))
+        )
+    "#;
+
+    let module = Module::new(&engine, wat)?;
+    let instance = Instance::new(&mut store, &module, &[])?;
+    let run_func = instance.get_typed_func::<(), (), _>(&mut store, "run")?;
+
+    let e = run_func
+        .call(&mut store, ())
+        .err()
+        .expect("error calling function");
+    assert!(e.exception().is_none());
+    let coredump = e.coredump().expect("core dump should be captured");
+
+    // The frames the exception unwound past are not in the core dump.
+    let frames = coredump.frames();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].func_index(), 3);
+    assert_eq!(frames[0].locals()[0].as_ref().unwrap().unwrap_i32(), 5);
+    assert_eq!(frames[1].func_index(), 0);
+    assert_eq!(frames[1].locals()[0].as_ref().unwrap().unwrap_i32(), 4);
+
+    let bytes = coredump.serialize("test");
+    Module::new(&engine, &bytes)?;
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(all(target_os = "macos", target_arch = "aarch64"), ignore)] // TODO #2808 system libunwind is broken on aarch64
+fn capture_backtrace_from_host() -> Result<()> {
+    let mut store = Store::<()>::default();
+    let wat = r#"
+        (module $capture_mod
+            (import "" "log" (func $log))
+            (import "" "fail" (func $fail))
+            (func $a (export "a") (call $b))
+            (func $b (call $log) (call $fail))
+        )
+    "#;
+
+    let module = Module::new(store.engine(), wat)?;
+    let log = Func::wrap(&mut store, |caller: Caller<'_, ()>| {
+        let backtrace = WasmBacktrace::capture(&caller);
+        let frames = backtrace.frames();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].module_name(), Some("capture_mod"));
+        assert_eq!(frames[0].func_name(), Some("b"));
+        assert_eq!(frames[1].func_name(), Some("a"));
+        assert!(backtrace.to_string().starts_with("wasm backtrace:"));
+    });
+    let fail = Func::wrap(&mut store, |caller: Caller<'_, ()>| -> Result<(), Trap> {
+        let backtrace = WasmBacktrace::capture(&caller);
+        Err(anyhow::anyhow!("host failure").context(backtrace).into())
+    });
+
+    let instance = Instance::new(&mut store, &module, &[log.into(), fail.into()])?;
+    let a = instance.get_typed_func::<(), (), _>(&mut store, "a")?;
+    let e = a
+        .call(&mut store, ())
+        .err()
+        .expect("error calling function");
+
+    let trace = e.trace();
+    assert_eq!(trace.len(), 2);
+    assert_eq!(trace[0].func_name(), Some("b"));
+    assert_eq!(trace[1].func_name(), Some("a"));
+
+    // The attached backtrace is the trap's trace, not its message.
+    assert_eq!(e.display_reason().to_string(), "host failure");
+    let message = e.to_string();
+    assert!(
+        message.starts_with("host failure\nwasm backtrace:\n"),
+        "{}",
+        message
+    );
+    assert_eq!(message.matches("wasm backtrace:").count(), 1, "{}", message);
+
+    // Outside of wasm there are no frames to capture.
+    assert!(WasmBacktrace::capture(&store).frames().is_empty());
+    Ok(())
+}
+
+#[test]
+fn host_error_without_backtrace_has_trace() -> Result<()> {
+    let mut store = Store::<()>::default();
+    let wat = r#"
+        (module $capture_mod
+            (import "" "fail" (func $fail))
+            (func $a (export "a") (call $b))
+            (func $b (call $fail))
+        )
+    "#;
+
+    let module = Module::new(store.engine(), wat)?;
+    let fail = Func::wrap(&mut store, || -> Result<(), Trap> {
+        Err(anyhow::anyhow!("host failure")
+            .context("while failing")
+            .into())
+    });
+
+    let instance = Instance::new(&mut store, &module, &[fail.into()])?;
+    let a = instance.get_typed_func::<(), (), _>(&mut store, "a")?;
+    let e = a
+        .call(&mut store, ())
+        .err()
+        .expect("error calling function");
+
+    let trace = e.trace();
+    assert_eq!(trace.len(), 2);
+    assert_eq!(trace[0].func_name(), Some("b"));
+    assert_eq!(trace[1].func_name(), Some("a"));
+    assert!(e
+        .to_string()
+        .starts_with("while failing\nwasm backtrace:\n"));
+    Ok(())
+}
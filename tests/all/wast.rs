@@ -19,6 +19,13 @@ fn run_wast(wast: &str, strategy: Strategy, pooling: bool) -> anyhow::Result<()>
     let multi_memory = feature_found(wast, "multi-memory");
     let module_linking = feature_found(wast, "module-linking");
     let threads = feature_found(wast, "threads");
+    let tail_call = feature_found(wast, "tail-call");
+    let exceptions = feature_found(wast, "exception-handling");
+
+    // Exceptions are only supported on x86_64 Unix platforms so far.
+    if exceptions && !cfg!(all(target_arch = "x86_64", unix)) {
+        return Ok(());
+    }
 
     let mut cfg = Config::new();
     cfg.wasm_simd(simd)
@@ -26,6 +33,8 @@ fn run_wast(wast: &str, strategy: Strategy, pooling: bool) -> anyhow::Result<()>
         .wasm_module_linking(module_linking)
         .wasm_threads(threads)
         .wasm_memory64(memory64)
+        .wasm_tail_call(tail_call)
+        .wasm_exceptions(exceptions)
         .strategy(strategy)?
         .cranelift_debug_verifier(true);
 
@@ -71,6 +80,16 @@ fn run_wast(wast: &str, strategy: Strategy, pooling: bool) -> anyhow::Result<()>
             return Ok(());
         }
 
+        // The pooling allocator doesn't support shared memories.
+        if wast.ends_with("wait_notify.wast") {
+            return Ok(());
+        }
+
+        // The pooling allocator doesn't support tags either.
+        if exceptions {
+            return Ok(());
+        }
+
         // The limits here are crafted such that the wast tests should pass.
         // However, these limits may become insufficient in the future as the wast tests change.
         // If a wast test fails because of a limit being "exceeded" or if memory/table
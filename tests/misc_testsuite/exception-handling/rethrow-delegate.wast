@@ -0,0 +1,56 @@
+(module
+  (tag $e0)
+  (tag $e1 (param i32))
+
+  (func (export "rethrow") (param i32) (result i32)
+    (try (result i32)
+      (do
+        (try (result i32)
+          (do (throw $e1 (local.get 0)))
+          (catch $e1 (drop) (rethrow 0))))
+      (catch $e1 (i32.const 1) (i32.add))))
+
+  (func (export "rethrow-outer") (result i32)
+    (try (result i32)
+      (do
+        (try
+          (do (throw $e1 (i32.const 10)))
+          (catch $e1
+            (drop)
+            (try
+              (do (throw $e0))
+              (catch $e0 (rethrow 1)))))
+        (i32.const 0))
+      (catch $e1)))
+
+  (func (export "rethrow-all") (result i32)
+    (try (result i32)
+      (do
+        (try
+          (do (throw $e1 (i32.const 4)))
+          (catch_all (rethrow 0)))
+        (i32.const 0))
+      (catch $e1)))
+
+  (func (export "delegate") (param i32) (result i32)
+    (try $outer (result i32)
+      (do
+        (try (result i32)
+          (do
+            (try (result i32)
+              (do (throw $e1 (local.get 0)))
+              (delegate $outer)))
+          (catch $e1 (i32.const 100))))
+      (catch $e1)))
+
+  (func (export "delegate-caller") (param i32)
+    (try
+      (do (throw $e1 (local.get 0)))
+      (delegate 0)))
+)
+
+(assert_return (invoke "rethrow" (i32.const 3)) (i32.const 4))
+(assert_return (invoke "rethrow-outer") (i32.const 10))
+(assert_return (invoke "rethrow-all") (i32.const 4))
+(assert_return (invoke "delegate" (i32.const 6)) (i32.const 6))
+(assert_exception (invoke "delegate-caller" (i32.const 1)))
@@ -0,0 +1,85 @@
+(module
+  (tag $e0)
+  (tag $e1 (param i32))
+  (tag $e2 (param i64 i32))
+
+  (func $throw-if (param i32)
+    (if (local.get 0) (then (throw $e1 (local.get 0)))))
+
+  (func (export "catch-local") (param i32) (result i32)
+    (try (result i32)
+      (do (throw $e1 (local.get 0)))
+      (catch $e1)))
+
+  (func (export "catch-call") (param i32) (result i32)
+    (try (result i32)
+      (do (call $throw-if (local.get 0)) (i32.const 0))
+      (catch $e1)))
+
+  (func (export "catch-multi") (param i64 i32) (result i64)
+    (try (result i64 i32)
+      (do (throw $e2 (local.get 0) (local.get 1)))
+      (catch $e2))
+    (i64.add (i64.extend_i32_u)))
+
+  (func (export "catch-wrong-tag") (result i32)
+    (try (result i32)
+      (do (throw $e0))
+      (catch $e1)))
+
+  (func (export "catch-all") (param i32) (result i32)
+    (try (result i32)
+      (do (call $throw-if (local.get 0)) (i32.const 0))
+      (catch $e0 (i32.const 1))
+      (catch_all (i32.const 2))))
+
+  (func (export "uncaught") (param i32)
+    (call $throw-if (local.get 0)))
+
+  (func (export "nested") (param i32) (result i32)
+    (try (result i32)
+      (do
+        (try (result i32)
+          (do (call $throw-if (local.get 0)) (i32.const 0))
+          (catch $e0 (i32.const 1))))
+      (catch $e1 (i32.const 100) (i32.add))))
+)
+
+(assert_return (invoke "catch-local" (i32.const 7)) (i32.const 7))
+(assert_return (invoke "catch-call" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "catch-call" (i32.const 3)) (i32.const 3))
+(assert_return (invoke "catch-multi" (i64.const 2) (i32.const 1)) (i64.const 3))
+(assert_exception (invoke "catch-wrong-tag"))
+(assert_return (invoke "catch-all" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "catch-all" (i32.const 1)) (i32.const 2))
+(assert_return (invoke "uncaught" (i32.const 0)))
+(assert_exception (invoke "uncaught" (i32.const 1)))
+(assert_return (invoke "nested" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "nested" (i32.const 5)) (i32.const 105))
+
+;; Exceptions propagate through imported functions and across instances.
+(module $thrower
+  (tag $e (export "e") (param i32))
+  (func (export "throw") (param i32)
+    (throw $e (local.get 0))))
+(register "thrower" $thrower)
+
+(module
+  (import "thrower" "e" (tag $e (param i32)))
+  (tag $local (param i32))
+  (import "thrower" "throw" (func $throw (param i32)))
+
+  (func (export "catch-imported") (param i32) (result i32)
+    (try (result i32)
+      (do (call $throw (local.get 0)) (i32.const 0))
+      (catch $e)))
+
+  (func (export "catch-local-tag") (param i32) (result i32)
+    (try (result i32)
+      (do (call $throw (local.get 0)) (i32.const 0))
+      (catch $local)
+      (catch_all (i32.const -1))))
+)
+
+(assert_return (invoke "catch-imported" (i32.const 9)) (i32.const 9))
+(assert_return (invoke "catch-local-tag" (i32.const 9)) (i32.const -1))
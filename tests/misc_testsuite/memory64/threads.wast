@@ -67,8 +67,8 @@
     i64.const 0 i64.const 0 i64.const 0 i64.atomic.rmw32.cmpxchg_u drop
   )
 
-  ;; these are unimplemented intrinsics that trap at runtime so just make sure
-  ;; we can codegen instead of also testing execution.
+  ;; waiting on a non-shared memory traps at runtime so just make sure we can
+  ;; codegen instead of also testing execution.
   (func $just_validate_codegen
     i64.const 0 i32.const 0 memory.atomic.notify drop
     i64.const 0 i32.const 0 i64.const 0 memory.atomic.wait32 drop
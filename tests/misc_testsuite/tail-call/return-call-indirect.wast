@@ -0,0 +1,43 @@
+(module
+  (type $t (func (param i64) (result i32)))
+  (table funcref (elem $even $odd $wrong-type))
+
+  (func $even (param i64) (result i32)
+    (if (result i32) (i64.eqz (local.get 0))
+      (then (i32.const 1))
+      (else
+        (return_call_indirect (type $t)
+          (i64.sub (local.get 0) (i64.const 1))
+          (i32.const 1)))))
+  (func $odd (param i64) (result i32)
+    (if (result i32) (i64.eqz (local.get 0))
+      (then (i32.const 0))
+      (else
+        (return_call_indirect (type $t)
+          (i64.sub (local.get 0) (i64.const 1))
+          (i32.const 0)))))
+  (func $wrong-type)
+
+  (func (export "even") (param i64) (result i32)
+    (return_call_indirect (type $t) (local.get 0) (i32.const 0)))
+  (func (export "call") (param i32) (result i32)
+    (return_call_indirect (type $t) (i64.const 0) (local.get 0)))
+)
+
+(assert_return (invoke "even" (i64.const 1000000)) (i32.const 1))
+(assert_return (invoke "even" (i64.const 999999)) (i32.const 0))
+(assert_trap (invoke "call" (i32.const 2)) "indirect call type mismatch")
+(assert_trap (invoke "call" (i32.const 3)) "undefined element")
+
+;; Tail calls to imported functions.
+(module $m
+  (func (export "add1") (param i32) (result i32)
+    (i32.add (local.get 0) (i32.const 1))))
+(register "m" $m)
+
+(module
+  (import "m" "add1" (func $add1 (param i32) (result i32)))
+  (func (export "run") (param i32) (result i32)
+    (return_call $add1 (local.get 0))))
+
+(assert_return (invoke "run" (i32.const 41)) (i32.const 42))
@@ -0,0 +1,62 @@
+;; Deep mutual recursion through `return_call` must not exhaust the stack.
+(module
+  (func $even (export "even") (param i64) (result i32)
+    (if (result i32) (i64.eqz (local.get 0))
+      (then (i32.const 1))
+      (else (return_call $odd (i64.sub (local.get 0) (i64.const 1))))))
+  (func $odd (export "odd") (param i64) (result i32)
+    (if (result i32) (i64.eqz (local.get 0))
+      (then (i32.const 0))
+      (else (return_call $even (i64.sub (local.get 0) (i64.const 1))))))
+
+  ;; An accumulator-passing loop with float arguments.
+  (func $sum (export "sum") (param i64 f64) (result f64)
+    (if (result f64) (i64.eqz (local.get 0))
+      (then (local.get 1))
+      (else
+        (return_call $sum
+          (i64.sub (local.get 0) (i64.const 1))
+          (f64.add (local.get 1) (f64.const 1))))))
+
+  ;; Enough parameters that some are passed on the stack; the callee needs no
+  ;; more stack argument space than the caller received.
+  (func $many (export "many")
+    (param i64 i32 i32 i32 i32 i32 i32 i32 i32) (result i32)
+    (if (result i32) (i64.eqz (local.get 0))
+      (then
+        (i32.add
+          (i32.add
+            (i32.add (local.get 1) (local.get 2))
+            (i32.add (local.get 3) (local.get 4)))
+          (i32.add
+            (i32.add (local.get 5) (local.get 6))
+            (i32.add (local.get 7) (local.get 8)))))
+      (else
+        (return_call $many
+          (i64.sub (local.get 0) (i64.const 1))
+          (local.get 2) (local.get 3) (local.get 4) (local.get 5)
+          (local.get 6) (local.get 7) (local.get 8) (local.get 1)))))
+
+  ;; Multiple results are returned through the caller's return area.
+  (func $swap (export "swap") (param i64 i32 i32) (result i32 i32)
+    (if (result i32 i32) (i64.eqz (local.get 0))
+      (then (local.get 1) (local.get 2))
+      (else
+        (return_call $swap
+          (i64.sub (local.get 0) (i64.const 1))
+          (local.get 2)
+          (local.get 1)))))
+)
+
+(assert_return (invoke "even" (i64.const 0)) (i32.const 1))
+(assert_return (invoke "odd" (i64.const 0)) (i32.const 0))
+(assert_return (invoke "even" (i64.const 1000000)) (i32.const 1))
+(assert_return (invoke "odd" (i64.const 1000001)) (i32.const 1))
+(assert_return (invoke "sum" (i64.const 1000000) (f64.const 0)) (f64.const 1000000))
+(assert_return
+  (invoke "many" (i64.const 1000000)
+    (i32.const 1) (i32.const 2) (i32.const 3) (i32.const 4)
+    (i32.const 5) (i32.const 6) (i32.const 7) (i32.const 8))
+  (i32.const 36))
+(assert_return (invoke "swap" (i64.const 1000001) (i32.const 1) (i32.const 2))
+  (i32.const 2) (i32.const 1))
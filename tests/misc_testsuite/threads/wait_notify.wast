@@ -0,0 +1,81 @@
+;; Single-threaded behavior of `memory.atomic.wait*` and `memory.atomic.notify`.
+
+(module
+  (memory 1 1 shared)
+
+  (func (export "init_i32") (param i32 i32)
+    local.get 0
+    local.get 1
+    i32.atomic.store)
+
+  (func (export "init_i64") (param i32 i64)
+    local.get 0
+    local.get 1
+    i64.atomic.store)
+
+  (func (export "wait32") (param i32 i32 i64) (result i32)
+    local.get 0
+    local.get 1
+    local.get 2
+    memory.atomic.wait32)
+
+  (func (export "wait64") (param i32 i64 i64) (result i32)
+    local.get 0
+    local.get 1
+    local.get 2
+    memory.atomic.wait64)
+
+  (func (export "notify") (param i32 i32) (result i32)
+    local.get 0
+    local.get 1
+    memory.atomic.notify)
+)
+
+;; nobody is waiting, so nobody is woken up
+(assert_return (invoke "notify" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "notify" (i32.const 0) (i32.const 10)) (i32.const 0))
+
+;; a value mismatch returns "not-equal" immediately
+(invoke "init_i32" (i32.const 0) (i32.const 42))
+(assert_return (invoke "wait32" (i32.const 0) (i32.const 0) (i64.const -1)) (i32.const 1))
+(invoke "init_i64" (i32.const 8) (i64.const 42))
+(assert_return (invoke "wait64" (i32.const 8) (i64.const 0) (i64.const -1)) (i32.const 1))
+
+;; a matching value with a timeout returns "timed-out"
+(assert_return (invoke "wait32" (i32.const 0) (i32.const 42) (i64.const 0)) (i32.const 2))
+(assert_return (invoke "wait32" (i32.const 0) (i32.const 42) (i64.const 1000)) (i32.const 2))
+(assert_return (invoke "wait64" (i32.const 8) (i64.const 42) (i64.const 0)) (i32.const 2))
+(assert_return (invoke "wait64" (i32.const 8) (i64.const 42) (i64.const 1000)) (i32.const 2))
+
+;; out of bounds
+(assert_trap (invoke "notify" (i32.const 65536) (i32.const 0)) "out of bounds memory access")
+(assert_trap (invoke "wait32" (i32.const 65536) (i32.const 0) (i64.const 0)) "out of bounds memory access")
+(assert_trap (invoke "wait64" (i32.const 65536) (i64.const 0) (i64.const 0)) "out of bounds memory access")
+
+(module
+  (memory 1 1)
+
+  (func (export "wait32") (result i32)
+    i32.const 0
+    i32.const 0
+    i64.const 0
+    memory.atomic.wait32)
+
+  (func (export "wait64") (result i32)
+    i32.const 0
+    i64.const 0
+    i64.const 0
+    memory.atomic.wait64)
+
+  (func (export "notify") (result i32)
+    i32.const 0
+    i32.const 1
+    memory.atomic.notify)
+)
+
+;; waiting isn't allowed on unshared memories
+(assert_trap (invoke "wait32") "atomic wait on non-shared memory")
+(assert_trap (invoke "wait64") "atomic wait on non-shared memory")
+
+;; but notifying is, and never wakes anything up
+(assert_return (invoke "notify") (i32.const 0))